@@ -16,5 +16,6 @@ pub fn get_fake_context() -> Context {
         Arc::new(MockDbReaderWriter),
         mempool.ac_client,
         NodeConfig::default(),
+        mempool.back_pressure_status,
     )
 }
@@ -42,6 +42,14 @@ impl HealthCheckSuccess {
             message: "aptos-node:ok".to_string(),
         }
     }
+
+    /// The node is healthy, but consensus is back pressured and new transactions are
+    /// rejected until it catches up.
+    pub fn busy() -> Self {
+        Self {
+            message: "aptos-node:busy".to_string(),
+        }
+    }
 }
 
 #[OpenApi]
@@ -69,6 +77,9 @@ impl BasicApi {
     /// 200 if the following condition is true:
     ///
     /// `server_latest_ledger_info_timestamp >= server_current_time_timestamp - duration_secs`
+    ///
+    /// If consensus is back pressured, the returned message is `aptos-node:busy`
+    /// instead of `aptos-node:ok`, and submitted transactions may be rejected.
     #[oai(
         path = "/-/healthy",
         method = "get",
@@ -111,8 +122,13 @@ impl BasicApi {
                 ));
             }
         }
+        let health_check_success = if self.context.is_back_pressured() {
+            HealthCheckSuccess::busy()
+        } else {
+            HealthCheckSuccess::new()
+        };
         HealthCheckResponse::try_from_rust_value((
-            HealthCheckSuccess::new(),
+            health_check_success,
             &ledger_info,
             HealthCheckResponseStatus::Ok,
             &accept_type,
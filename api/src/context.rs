@@ -6,7 +6,7 @@ use crate::{
     accept_type::AcceptType,
     response::{
         bcs_api_disabled, block_not_found_by_height, block_not_found_by_version,
-        block_pruned_by_height, json_api_disabled, version_not_found, version_pruned,
+        block_pruned_by_height, json_api_disabled, state_pruned, version_not_found, version_pruned,
         ForbiddenError, InternalError, NotFoundError, ServiceUnavailableError, StdApiError,
     },
 };
@@ -19,7 +19,10 @@ use aptos_config::config::{NodeConfig, RoleType};
 use aptos_crypto::HashValue;
 use aptos_gas_schedule::{AptosGasParameters, FromOnChainGasSchedule};
 use aptos_logger::{error, warn};
-use aptos_mempool::{MempoolClientRequest, MempoolClientSender, SubmissionStatus};
+use aptos_mempool::{
+    BackPressureStatus, MempoolClientRequest, MempoolClientSender, MempoolTransactionStatus,
+    SubmissionStatus, TransactionStatusReceiver,
+};
 use aptos_state_view::TStateView;
 use aptos_storage_interface::{
     state_view::{DbStateView, DbStateViewAtVersion, LatestDbStateCheckpointView},
@@ -63,6 +66,7 @@ pub struct Context {
     pub db: Arc<dyn DbReader>,
     mp_sender: MempoolClientSender,
     pub node_config: NodeConfig,
+    back_pressure_status: BackPressureStatus,
     gas_schedule_cache: Arc<RwLock<GasScheduleCache>>,
     gas_estimation_cache: Arc<RwLock<GasEstimationCache>>,
     gas_limit_cache: Arc<RwLock<GasLimitCache>>,
@@ -80,12 +84,14 @@ impl Context {
         db: Arc<dyn DbReader>,
         mp_sender: MempoolClientSender,
         node_config: NodeConfig,
+        back_pressure_status: BackPressureStatus,
     ) -> Self {
         Self {
             chain_id,
             db,
             mp_sender,
             node_config,
+            back_pressure_status,
             gas_schedule_cache: Arc::new(RwLock::new(GasScheduleCache {
                 last_updated_epoch: None,
                 gas_schedule_params: None,
@@ -165,6 +171,15 @@ impl Context {
         self.node_config.api.content_length_limit()
     }
 
+    pub fn archival_node_url(&self) -> Option<String> {
+        self.node_config.api.archival_node_url.clone()
+    }
+
+    /// Whether consensus currently reports quorum store back pressure.
+    pub fn is_back_pressured(&self) -> bool {
+        self.back_pressure_status.is_busy()
+    }
+
     pub fn failpoints_enabled(&self) -> bool {
         self.node_config.api.failpoints_enabled
     }
@@ -173,6 +188,10 @@ impl Context {
         self.node_config.api.max_submit_transaction_batch_size
     }
 
+    pub fn max_view_state_resources(&self) -> usize {
+        self.node_config.api.max_view_state_resources
+    }
+
     pub async fn submit_transaction(&self, txn: SignedTransaction) -> Result<SubmissionStatus> {
         let (req_sender, callback) = oneshot::channel();
         self.mp_sender
@@ -261,6 +280,22 @@ impl Context {
         } else if requested_ledger_version < latest_ledger_info.oldest_ledger_version.0 {
             return Err(version_pruned(
                 requested_ledger_version,
+                self.archival_node_url(),
+                &latest_ledger_info,
+            ));
+        }
+
+        // The state may be pruned more aggressively than the ledger history
+        let oldest_state_version =
+            self.get_oldest_state_version(&latest_ledger_info)
+                .map_err(|err| {
+                    E::internal_with_code(err, AptosErrorCode::InternalError, &latest_ledger_info)
+                })?;
+        if requested_ledger_version < oldest_state_version {
+            return Err(state_pruned(
+                requested_ledger_version,
+                oldest_state_version,
+                self.archival_node_url(),
                 &latest_ledger_info,
             ));
         }
@@ -268,6 +303,19 @@ impl Context {
         Ok((latest_ledger_info, requested_ledger_version))
     }
 
+    /// The oldest version at which the state (i.e., resources, modules and table items)
+    /// can be queried
+    pub fn get_oldest_state_version(&self, ledger_info: &LedgerInfo) -> Result<Version> {
+        let first_state_kv_version = self
+            .db
+            .get_first_state_kv_version()
+            .context("Failed to read the oldest state version from DB")?;
+        Ok(std::cmp::max(
+            ledger_info.oldest_ledger_version.0,
+            first_state_kv_version,
+        ))
+    }
+
     pub fn get_latest_ledger_info_with_signatures(&self) -> Result<LedgerInfoWithSignatures> {
         self.db.get_latest_ledger_info()
     }
@@ -542,7 +590,11 @@ impl Context {
         with_transactions: bool,
     ) -> Result<BcsBlock, E> {
         if version < latest_ledger_info.oldest_ledger_version.0 {
-            return Err(version_pruned(version, latest_ledger_info));
+            return Err(version_pruned(
+                version,
+                self.archival_node_url(),
+                latest_ledger_info,
+            ));
         } else if version > latest_ledger_info.version() {
             return Err(version_not_found(version, latest_ledger_info));
         }
@@ -788,6 +840,28 @@ impl Context {
         callback.await.map_err(anyhow::Error::from)
     }
 
+    /// Returns the status of the transaction in mempool, if it's there, and a receiver of its next
+    /// status change. No receiver is returned if too many requests are waiting already.
+    pub async fn subscribe_transaction_status(
+        &self,
+        hash: HashValue,
+    ) -> Result<(
+        Option<MempoolTransactionStatus>,
+        Option<TransactionStatusReceiver>,
+    )> {
+        let (req_sender, callback) = oneshot::channel();
+
+        self.mp_sender
+            .clone()
+            .send(MempoolClientRequest::SubscribeTransactionStatus(
+                hash, req_sender,
+            ))
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        callback.await.map_err(anyhow::Error::from)
+    }
+
     pub fn get_transaction_by_version(
         &self,
         version: u64,
@@ -0,0 +1,360 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A WebSocket API streaming committed events to clients.
+//!
+//! A single publisher task is woken up by the commit notifications of state sync, reads
+//! the newly committed events from storage and broadcasts them to all the connections.
+//! Each connection first catches up from storage, from its requested start version, and
+//! then follows the broadcast. Connections falling behind the broadcast catch up from
+//! storage again, so no event is ever skipped.
+
+use crate::context::Context;
+use anyhow::{format_err, Context as AnyhowContext, Result};
+use aptos_api_types::{Address, AsConverter, LedgerInfo};
+use aptos_event_notifications::EventNotificationListener;
+use aptos_logger::{debug, warn};
+use aptos_types::contract_event::{ContractEvent, EventWithVersion};
+use aptos_vm::data_cache::AsMoveResolver;
+use futures::{SinkExt, StreamExt};
+use move_core_types::{account_address::AccountAddress, language_storage::TypeTag};
+use poem::{
+    handler,
+    http::StatusCode,
+    web::{
+        websocket::{Message, WebSocket, WebSocketStream},
+        Data, Query,
+    },
+    IntoResponse,
+};
+use serde::Deserialize;
+use std::{
+    str::FromStr,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+use tokio::{
+    runtime::Handle,
+    sync::broadcast::{self, error::RecvError},
+};
+
+/// Number of committed batches buffered for each connection before it lags behind
+const EVENT_STREAM_CHANNEL_SIZE: usize = 1024;
+
+/// The events committed in the versions `[first_version, last_version]`
+#[derive(Debug)]
+pub(crate) struct CommittedEvents {
+    pub first_version: u64,
+    pub last_version: u64,
+    pub events: Vec<EventWithVersion>,
+}
+
+/// The shared state of the event streaming API
+pub struct EventStream {
+    context: Arc<Context>,
+    sender: broadcast::Sender<Arc<CommittedEvents>>,
+    connections: Arc<AtomicUsize>,
+}
+
+impl EventStream {
+    pub fn new(context: Arc<Context>) -> Self {
+        let (sender, _) = broadcast::channel(EVENT_STREAM_CHANNEL_SIZE);
+        Self {
+            context,
+            sender,
+            connections: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Spawns the publisher, broadcasting the events committed after each notification
+    pub fn start_publisher(
+        &self,
+        runtime_handle: &Handle,
+        mut listener: EventNotificationListener,
+    ) {
+        let context = self.context.clone();
+        let sender = self.sender.clone();
+        runtime_handle.spawn(async move {
+            let mut next_version = match latest_ledger_version(context.clone()).await {
+                Ok(version) => version + 1,
+                Err(error) => {
+                    warn!("Failed to start the event stream publisher: {:?}", error);
+                    return;
+                },
+            };
+
+            // State sync only notifies the latest synced version, so the events
+            // themselves are read from storage, up to the latest ledger info
+            while let Some(notification) = listener.next().await {
+                let ledger_version = match latest_ledger_version(context.clone()).await {
+                    Ok(version) => std::cmp::min(version, notification.version),
+                    Err(error) => {
+                        warn!("Failed to read the latest ledger version: {:?}", error);
+                        continue;
+                    },
+                };
+                while next_version <= ledger_version {
+                    match read_committed_events(context.clone(), next_version, ledger_version).await
+                    {
+                        Ok(committed_events) => {
+                            next_version = committed_events.last_version + 1;
+                            // Sending only fails when there are no connections
+                            let _ = sender.send(Arc::new(committed_events));
+                        },
+                        Err(error) => {
+                            // The missing versions are retried on the next notification
+                            warn!("Failed to read the committed events: {:?}", error);
+                            break;
+                        },
+                    }
+                }
+            }
+        });
+    }
+
+    fn acquire_connection(&self) -> Option<ConnectionGuard> {
+        let max_connections = self.context.node_config.api.max_event_stream_connections;
+        self.connections
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |connections| {
+                (connections < max_connections).then_some(connections + 1)
+            })
+            .ok()
+            .map(|_| ConnectionGuard(self.connections.clone()))
+    }
+}
+
+/// Releases a connection slot of the event stream when dropped
+struct ConnectionGuard(Arc<AtomicUsize>);
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// The filter applied on the events sent to a connection
+#[derive(Clone, Debug, Default)]
+pub(crate) struct EventFilter {
+    address: Option<AccountAddress>,
+    event_type: Option<TypeTag>,
+}
+
+impl EventFilter {
+    pub fn new(address: Option<Address>, event_type: Option<&str>) -> Result<Self> {
+        let event_type = event_type
+            .map(TypeTag::from_str)
+            .transpose()
+            .context("Invalid event type")?;
+        Ok(Self {
+            address: address.map(Into::into),
+            event_type,
+        })
+    }
+
+    /// Module events have no event key, so they never match an address filter
+    pub fn matches(&self, event: &ContractEvent) -> bool {
+        let address_matches = self.address.map_or(true, |address| {
+            event
+                .event_key()
+                .map_or(false, |key| key.get_creator_address() == address)
+        });
+        let type_matches = self
+            .event_type
+            .as_ref()
+            .map_or(true, |event_type| event.type_tag() == event_type);
+        address_matches && type_matches
+    }
+}
+
+#[derive(Deserialize)]
+pub struct EventStreamParams {
+    /// Ledger version to start streaming from, defaults to the next committed version
+    start_version: Option<u64>,
+    /// Only stream the events emitted under this account's event handles
+    address: Option<Address>,
+    /// Only stream the events of this Move type
+    event_type: Option<String>,
+}
+
+#[handler]
+pub async fn stream_events_poem(
+    ws: WebSocket,
+    event_stream: Data<&Arc<EventStream>>,
+    Query(params): Query<EventStreamParams>,
+) -> poem::Result<impl IntoResponse> {
+    let filter = EventFilter::new(params.address, params.event_type.as_deref())
+        .map_err(|err| poem::Error::from_string(format!("{:#}", err), StatusCode::BAD_REQUEST))?;
+
+    let context = event_stream.context.clone();
+    let ledger_info = latest_ledger_info(context.clone()).await.map_err(|err| {
+        poem::Error::from_string(err.to_string(), StatusCode::SERVICE_UNAVAILABLE)
+    })?;
+    let start_version = params.start_version.unwrap_or(ledger_info.version() + 1);
+    if start_version < ledger_info.oldest_ledger_version.0 {
+        return Err(poem::Error::from_string(
+            format!(
+                "Version({}) has been pruned, oldest available version is {}",
+                start_version, ledger_info.oldest_ledger_version.0
+            ),
+            StatusCode::GONE,
+        ));
+    }
+
+    let connection = event_stream.acquire_connection().ok_or_else(|| {
+        poem::Error::from_string(
+            "Too many event stream connections",
+            StatusCode::SERVICE_UNAVAILABLE,
+        )
+    })?;
+
+    // Subscribe before catching up from storage, so that no commit is missed in between
+    let receiver = event_stream.sender.subscribe();
+    Ok(ws.on_upgrade(move |socket| async move {
+        let _connection = connection;
+        if let Err(error) =
+            stream_events_to_socket(context, receiver, filter, start_version, socket).await
+        {
+            debug!("Event stream connection closed: {:?}", error);
+        }
+    }))
+}
+
+async fn stream_events_to_socket(
+    context: Arc<Context>,
+    mut receiver: broadcast::Receiver<Arc<CommittedEvents>>,
+    filter: EventFilter,
+    mut next_version: u64,
+    socket: WebSocketStream,
+) -> Result<()> {
+    let (mut sink, mut stream) = socket.split();
+    loop {
+        // Catch up from storage until the latest committed version
+        loop {
+            let ledger_version = latest_ledger_version(context.clone()).await?;
+            if next_version > ledger_version {
+                break;
+            }
+            let committed_events =
+                read_committed_events(context.clone(), next_version, ledger_version).await?;
+            for message in
+                to_messages(context.clone(), filter.clone(), committed_events.events).await?
+            {
+                sink.send(Message::Text(message)).await?;
+            }
+            next_version = committed_events.last_version + 1;
+        }
+
+        // Follow the broadcast until a gap in the versions requires catching up again
+        loop {
+            tokio::select! {
+                message = stream.next() => match message {
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return Ok(()),
+                    Some(Ok(_)) => continue,
+                },
+                committed_events = receiver.recv() => match committed_events {
+                    Ok(committed_events) => {
+                        if committed_events.last_version < next_version {
+                            continue;
+                        }
+                        if committed_events.first_version > next_version {
+                            break;
+                        }
+                        let events = committed_events
+                            .events
+                            .iter()
+                            .filter(|event| event.transaction_version >= next_version)
+                            .cloned()
+                            .collect();
+                        for message in to_messages(context.clone(), filter.clone(), events).await? {
+                            sink.send(Message::Text(message)).await?;
+                        }
+                        next_version = committed_events.last_version + 1;
+                    },
+                    Err(RecvError::Lagged(_)) => break,
+                    Err(RecvError::Closed) => return Ok(()),
+                },
+            }
+        }
+    }
+}
+
+async fn latest_ledger_info(context: Arc<Context>) -> Result<LedgerInfo> {
+    tokio::task::spawn_blocking(move || context.get_latest_ledger_info_wrapped()).await?
+}
+
+async fn latest_ledger_version(context: Arc<Context>) -> Result<u64> {
+    tokio::task::spawn_blocking(move || {
+        context
+            .db
+            .get_latest_ledger_info()
+            .map(|ledger_info| ledger_info.ledger_info().version())
+            .context("Failed to read the latest ledger version")
+    })
+    .await?
+}
+
+/// Reads the events committed from `start_version`, at most one page of transactions
+/// and never past `ledger_version`
+pub(crate) async fn read_committed_events(
+    context: Arc<Context>,
+    start_version: u64,
+    ledger_version: u64,
+) -> Result<CommittedEvents> {
+    tokio::task::spawn_blocking(move || {
+        let limit = std::cmp::min(
+            context.max_transactions_page_size() as u64,
+            ledger_version.saturating_sub(start_version) + 1,
+        ) as u16;
+        let transactions = context
+            .get_transactions(start_version, limit, ledger_version)
+            .context("Failed to read the committed transactions")?;
+        let last_version = transactions
+            .last()
+            .map(|txn| txn.version)
+            .ok_or_else(|| format_err!("No transaction found at version {}", start_version))?;
+        let events = transactions
+            .into_iter()
+            .flat_map(|txn| {
+                let version = txn.version;
+                txn.events
+                    .into_iter()
+                    .map(move |event| EventWithVersion::new(version, event))
+            })
+            .collect();
+        Ok(CommittedEvents {
+            first_version: start_version,
+            last_version,
+            events,
+        })
+    })
+    .await?
+}
+
+/// Converts the events matching the filter into JSON encoded `VersionedEvent`s
+pub(crate) async fn to_messages(
+    context: Arc<Context>,
+    filter: EventFilter,
+    events: Vec<EventWithVersion>,
+) -> Result<Vec<String>> {
+    let events: Vec<_> = events
+        .into_iter()
+        .filter(|event| filter.matches(&event.event))
+        .collect();
+    if events.is_empty() {
+        return Ok(vec![]);
+    }
+    tokio::task::spawn_blocking(move || {
+        context
+            .latest_state_view()?
+            .as_move_resolver()
+            .as_converter(context.db.clone())
+            .try_into_versioned_events(&events)
+            .context("Failed to convert events from storage into response")?
+            .iter()
+            .map(|event| serde_json::to_string(event).map_err(Into::into))
+            .collect()
+    })
+    .await?
+}
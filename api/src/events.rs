@@ -17,7 +17,7 @@ use crate::{
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::{
     verify_field_identifier, Address, AptosErrorCode, AsConverter, IdentifierWrapper, LedgerInfo,
-    MoveStructTag, VerifyInputWithRecursion, VersionedEvent, U64,
+    MoveStructTag, PageCursor, VerifyInputWithRecursion, VersionedEvent, U64,
 };
 use aptos_types::event::EventKey;
 use aptos_vm::data_cache::AsMoveResolver;
@@ -65,15 +65,20 @@ impl EventsApi {
         ///
         /// If unspecified, defaults to default page size
         limit: Query<Option<u16>>,
+        /// Cursor to continue the list of events from, instead of `start`
+        ///
+        /// Use the cursor returned in the X-Aptos-Cursor header of the previous page
+        cursor: Query<Option<PageCursor>>,
     ) -> BasicResultWith404<Vec<VersionedEvent>> {
         fail_point_poem("endpoint_get_events_by_event_key")?;
         self.context
             .check_api_output_enabled("Get events by event key", &accept_type)?;
-        let page = Page::new(
+        let page = Page::new::<BasicErrorWith404>(
             start.0.map(|v| v.0),
+            cursor.0,
             limit.0,
             self.context.max_events_page_size(),
-        );
+        )?;
 
         // Ensure that account exists
         let api = self.clone();
@@ -121,6 +126,10 @@ impl EventsApi {
         ///
         /// If unspecified, defaults to default page size
         limit: Query<Option<u16>>,
+        /// Cursor to continue the list of events from, instead of `start`
+        ///
+        /// Use the cursor returned in the X-Aptos-Cursor header of the previous page
+        cursor: Query<Option<PageCursor>>,
     ) -> BasicResultWith404<Vec<VersionedEvent>> {
         event_handle
             .0
@@ -137,11 +146,12 @@ impl EventsApi {
         fail_point_poem("endpoint_get_events_by_event_handle")?;
         self.context
             .check_api_output_enabled("Get events by event handle", &accept_type)?;
-        let page = Page::new(
+        let page = Page::new::<BasicErrorWith404>(
             start.0.map(|v| v.0),
+            cursor.0,
             limit.0,
             self.context.max_events_page_size(),
-        );
+        )?;
 
         let api = self.clone();
         api_spawn_blocking(move || {
@@ -163,6 +173,7 @@ impl EventsApi {
         event_key: EventKey,
     ) -> BasicResultWith404<Vec<VersionedEvent>> {
         let ledger_version = latest_ledger_info.version();
+        page.verify_cursor(&latest_ledger_info)?;
         let events = self
             .context
             .get_events(
@@ -180,6 +191,29 @@ impl EventsApi {
                 )
             })?;
 
+        // The next page starts right after the sequence number of the last event of this one,
+        // or where this one started if it's empty
+        let next_sequence_number = match events.last() {
+            Some(event) => Some(
+                event
+                    .event
+                    .v1()
+                    .context("Event by key is not a v1 event")
+                    .map_err(|err| {
+                        BasicErrorWith404::internal_with_code(
+                            err,
+                            AptosErrorCode::InternalError,
+                            &latest_ledger_info,
+                        )
+                    })?
+                    .sequence_number()
+                    + 1,
+            ),
+            None => page.start_option(),
+        };
+        let next_cursor = next_sequence_number
+            .map(|sequence_number| PageCursor::new(ledger_version, sequence_number));
+
         match accept_type {
             AcceptType::Json => {
                 let events = self
@@ -203,5 +237,6 @@ impl EventsApi {
                 BasicResponse::try_from_bcs((events, &latest_ledger_info, BasicResponseStatus::Ok))
             },
         }
+        .map(|v| v.with_page_cursor(next_cursor))
     }
 }
@@ -0,0 +1,638 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! An optional GraphQL API, serving the accounts, resources, transactions and events.
+//!
+//! Unlike the REST API, clients select the fields they need, and can fetch related data (e.g.,
+//! an account with its resources and its latest transactions) in a single query. All the fields
+//! of a query are resolved at the ledger version of the node when the request is received, so
+//! they're always consistent with each other. Batches of queries are supported as well.
+
+use crate::{
+    context::Context,
+    page::{determine_limit, DEFAULT_PAGE_SIZE},
+    response::{AptosErrorResponse, BasicErrorWith404},
+};
+use anyhow::{format_err, Context as AnyhowContext};
+use aptos_api_types::{
+    Address, AsConverter, HashValue, HexEncodedBytes, LedgerInfo, MoveStructValue,
+    TransactionPayload, VersionedEvent, WriteSetChange,
+};
+use aptos_types::{account_config::AccountResource, event::EventKey};
+use aptos_vm::data_cache::AsMoveResolver;
+use async_graphql::{
+    BatchRequest, BatchResponse, Context as GraphQlContext, EmptyMutation, EmptySubscription,
+    InputValueError, InputValueResult, Json, Object, Result, Scalar, ScalarType, Schema,
+    SimpleObject, Value,
+};
+use move_core_types::{account_address::AccountAddress, language_storage::StructTag};
+use poem::{handler, http::StatusCode, web::Data};
+use std::{str::FromStr, sync::Arc};
+
+type GraphQlSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// The shared state of the GraphQL API
+pub struct GraphQl {
+    context: Arc<Context>,
+    schema: GraphQlSchema,
+}
+
+impl GraphQl {
+    pub fn new(context: Arc<Context>) -> Self {
+        let config = &context.node_config.api.graphql;
+        let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+            .data(context.clone())
+            .limit_depth(config.max_depth)
+            .limit_complexity(config.max_complexity)
+            .finish();
+        Self { context, schema }
+    }
+}
+
+#[handler]
+pub async fn graphql_poem(
+    graphql: Data<&Arc<GraphQl>>,
+    poem::web::Json(batch): poem::web::Json<BatchRequest>,
+) -> poem::Result<poem::web::Json<BatchResponse>> {
+    let max_batch_size = graphql.context.node_config.api.graphql.max_batch_size;
+    if let BatchRequest::Batch(requests) = &batch {
+        if requests.len() > max_batch_size {
+            return Err(poem::Error::from_string(
+                format!(
+                    "Batch of {} queries exceeds the limit of {}",
+                    requests.len(),
+                    max_batch_size
+                ),
+                StatusCode::BAD_REQUEST,
+            ));
+        }
+    }
+
+    // Every query of the batch is resolved at the same ledger version
+    let context = graphql.context.clone();
+    let ledger_info = tokio::task::spawn_blocking(move || context.get_latest_ledger_info_wrapped())
+        .await
+        .map_err(anyhow::Error::from)
+        .and_then(|ledger_info| ledger_info)
+        .map_err(|err| {
+            poem::Error::from_string(err.to_string(), StatusCode::SERVICE_UNAVAILABLE)
+        })?;
+    Ok(poem::web::Json(
+        graphql.schema.execute_batch(batch.data(ledger_info)).await,
+    ))
+}
+
+/// Runs the blocking storage reads of a field outside of the async runtime
+async fn spawn_blocking<F, T>(func: F) -> Result<T>
+where
+    F: FnOnce() -> anyhow::Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    Ok(tokio::task::spawn_blocking(func).await??)
+}
+
+fn request_data(ctx: &GraphQlContext<'_>) -> (Arc<Context>, LedgerInfo) {
+    (
+        ctx.data_unchecked::<Arc<Context>>().clone(),
+        ctx.data_unchecked::<LedgerInfo>().clone(),
+    )
+}
+
+/// GraphQL errors have no status code, so the errors of the REST API are returned with their
+/// message only
+fn api_error(mut err: BasicErrorWith404) -> anyhow::Error {
+    anyhow::Error::msg(std::mem::take(&mut err.inner_mut().message))
+}
+
+/// The complexity of a page of items, which is resolved once per item
+fn page_complexity(limit: Option<u16>, child_complexity: usize) -> usize {
+    limit.unwrap_or(DEFAULT_PAGE_SIZE) as usize * child_complexity
+}
+
+/// A u64 encoded as a string, like in the REST API, as JSON numbers can't represent all of them
+#[derive(Clone, Copy, Debug)]
+pub struct U64(pub u64);
+
+#[Scalar]
+impl ScalarType for U64 {
+    fn parse(value: Value) -> InputValueResult<Self> {
+        let parsed = match &value {
+            Value::String(string) => string.parse().ok(),
+            Value::Number(number) => number.as_u64(),
+            _ => None,
+        };
+        parsed
+            .map(U64)
+            .ok_or_else(|| InputValueError::expected_type(value))
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.0.to_string())
+    }
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// The ledger info at which the query is resolved
+    async fn ledger_info(&self, ctx: &GraphQlContext<'_>) -> LedgerInfoObject {
+        ctx.data_unchecked::<LedgerInfo>().into()
+    }
+
+    /// An account, at the ledger version of the query unless given
+    async fn account(
+        &self,
+        ctx: &GraphQlContext<'_>,
+        address: String,
+        ledger_version: Option<U64>,
+    ) -> Result<AccountObject> {
+        let (context, ledger_info) = request_data(ctx);
+        let address = Address::from_str(&address)?;
+        let version = ledger_version.map_or(ledger_info.version(), |version| version.0);
+        if version > ledger_info.version() {
+            return Err(version_too_new(version, &ledger_info).into());
+        }
+        let oldest_state_version =
+            spawn_blocking(move || context.get_oldest_state_version(&ledger_info)).await?;
+        if version < oldest_state_version {
+            return Err(format!(
+                "The state at version {} has been pruned, the oldest version is {}",
+                version, oldest_state_version
+            )
+            .into());
+        }
+        Ok(AccountObject {
+            address: address.into(),
+            version,
+        })
+    }
+
+    /// The committed transactions, starting at `start`, or the latest ones if it isn't given
+    #[graphql(complexity = "page_complexity(limit, child_complexity)")]
+    async fn transactions(
+        &self,
+        ctx: &GraphQlContext<'_>,
+        start: Option<U64>,
+        limit: Option<u16>,
+    ) -> Result<Vec<TransactionObject>> {
+        let (context, ledger_info) = request_data(ctx);
+        let limit = determine_limit::<BasicErrorWith404>(
+            limit,
+            DEFAULT_PAGE_SIZE,
+            context.max_transactions_page_size(),
+            &ledger_info,
+        )
+        .map_err(api_error)?;
+        let ledger_version = ledger_info.version();
+        let start = start.map_or(ledger_version.saturating_sub(limit as u64 - 1), |start| {
+            start.0
+        });
+        if start > ledger_version {
+            return Err(version_too_new(start, &ledger_info).into());
+        }
+        if start < ledger_info.oldest_version() {
+            return Err(version_pruned(start, &ledger_info).into());
+        }
+
+        spawn_blocking(move || {
+            let data = context
+                .get_transactions(start, limit, ledger_version)
+                .context("Failed to read raw transactions from storage")?;
+            let timestamp = context.db.get_block_timestamp(start)?;
+            Ok(context
+                .render_transactions_sequential::<BasicErrorWith404>(&ledger_info, data, timestamp)
+                .map_err(api_error)?
+                .into_iter()
+                .map(TransactionObject)
+                .collect())
+        })
+        .await
+    }
+
+    /// A committed transaction, by version
+    async fn transaction_by_version(
+        &self,
+        ctx: &GraphQlContext<'_>,
+        version: U64,
+    ) -> Result<Option<TransactionObject>> {
+        let (context, ledger_info) = request_data(ctx);
+        let version = version.0;
+        if version > ledger_info.version() {
+            return Ok(None);
+        }
+        if version < ledger_info.oldest_version() {
+            return Err(version_pruned(version, &ledger_info).into());
+        }
+
+        spawn_blocking(move || {
+            let data = context.get_transaction_by_version(version, ledger_info.version())?;
+            Ok(context
+                .render_transactions_non_sequential::<BasicErrorWith404>(&ledger_info, vec![data])
+                .map_err(api_error)?
+                .pop()
+                .map(TransactionObject))
+        })
+        .await
+    }
+
+    /// A committed transaction, by hash
+    async fn transaction_by_hash(
+        &self,
+        ctx: &GraphQlContext<'_>,
+        hash: String,
+    ) -> Result<Option<TransactionObject>> {
+        let (context, ledger_info) = request_data(ctx);
+        let hash = HashValue::from_str(&hash)?;
+
+        spawn_blocking(move || {
+            let data = context.get_transaction_by_hash(hash.into(), ledger_info.version())?;
+            Ok(context
+                .render_transactions_non_sequential::<BasicErrorWith404>(
+                    &ledger_info,
+                    data.into_iter().collect(),
+                )
+                .map_err(api_error)?
+                .pop()
+                .map(TransactionObject))
+        })
+        .await
+    }
+}
+
+fn version_too_new(version: u64, ledger_info: &LedgerInfo) -> anyhow::Error {
+    format_err!(
+        "Version {} is higher than the ledger version {}",
+        version,
+        ledger_info.version()
+    )
+}
+
+fn version_pruned(version: u64, ledger_info: &LedgerInfo) -> anyhow::Error {
+    format_err!(
+        "Version {} has been pruned, the oldest version is {}",
+        version,
+        ledger_info.oldest_version()
+    )
+}
+
+/// The ledger info of the node
+#[derive(SimpleObject)]
+#[graphql(name = "LedgerInfo")]
+pub struct LedgerInfoObject {
+    chain_id: u8,
+    epoch: U64,
+    ledger_version: U64,
+    oldest_ledger_version: U64,
+    block_height: U64,
+    oldest_block_height: U64,
+    ledger_timestamp: U64,
+}
+
+impl From<&LedgerInfo> for LedgerInfoObject {
+    fn from(ledger_info: &LedgerInfo) -> Self {
+        Self {
+            chain_id: ledger_info.chain_id,
+            epoch: U64(ledger_info.epoch.0),
+            ledger_version: U64(ledger_info.ledger_version.0),
+            oldest_ledger_version: U64(ledger_info.oldest_ledger_version.0),
+            block_height: U64(ledger_info.block_height.0),
+            oldest_block_height: U64(ledger_info.oldest_block_height.0),
+            ledger_timestamp: U64(ledger_info.ledger_timestamp.0),
+        }
+    }
+}
+
+/// An account, or any other address holding resources (e.g., an object)
+#[derive(Clone, Debug)]
+pub struct AccountObject {
+    address: AccountAddress,
+    /// Ledger version at which the state of the account is read
+    version: u64,
+}
+
+impl AccountObject {
+    async fn account_resource(&self, ctx: &GraphQlContext<'_>) -> Result<Option<AccountResource>> {
+        let context = ctx.data_unchecked::<Arc<Context>>().clone();
+        let (address, version) = (self.address, self.version);
+        spawn_blocking(move || context.get_resource::<AccountResource>(address, version)).await
+    }
+}
+
+#[Object(name = "Account")]
+impl AccountObject {
+    async fn address(&self) -> String {
+        Address::from(self.address).to_string()
+    }
+
+    /// The sequence number of the account, unset if the address isn't an account
+    async fn sequence_number(&self, ctx: &GraphQlContext<'_>) -> Result<Option<U64>> {
+        Ok(self
+            .account_resource(ctx)
+            .await?
+            .map(|account| U64(account.sequence_number())))
+    }
+
+    /// The authentication key of the account, unset if the address isn't an account
+    async fn authentication_key(&self, ctx: &GraphQlContext<'_>) -> Result<Option<String>> {
+        Ok(self.account_resource(ctx).await?.map(|account| {
+            HexEncodedBytes::from(account.authentication_key().to_vec()).to_string()
+        }))
+    }
+
+    /// A resource of the account, including the members of resource groups
+    async fn resource(
+        &self,
+        ctx: &GraphQlContext<'_>,
+        resource_type: String,
+    ) -> Result<Option<ResourceObject>> {
+        let context = ctx.data_unchecked::<Arc<Context>>().clone();
+        let resource_type = StructTag::from_str(&resource_type)?;
+        let (address, version) = (self.address, self.version);
+
+        spawn_blocking(move || {
+            let state_view = context.state_view_at_version(version)?;
+            let resolver = state_view.as_move_resolver();
+            resolver
+                .get_resource(&address, &resource_type)
+                .context(format!(
+                    "Failed to query DB to check for {} at {}",
+                    resource_type, address
+                ))?
+                .map(|bytes| {
+                    resolver
+                        .as_converter(context.db.clone())
+                        .try_into_resource(&resource_type, &bytes)
+                        .map(Into::into)
+                })
+                .transpose()
+        })
+        .await
+    }
+
+    /// The first resources of the account, with the members of resource groups flattened
+    #[graphql(complexity = "page_complexity(limit, child_complexity)")]
+    async fn resources(
+        &self,
+        ctx: &GraphQlContext<'_>,
+        limit: Option<u16>,
+    ) -> Result<Vec<ResourceObject>> {
+        let (context, ledger_info) = request_data(ctx);
+        let limit = determine_limit::<BasicErrorWith404>(
+            limit,
+            DEFAULT_PAGE_SIZE,
+            context.max_account_resources_page_size(),
+            &ledger_info,
+        )
+        .map_err(api_error)?;
+        let (address, version) = (self.address, self.version);
+
+        spawn_blocking(move || {
+            let (resources, _) =
+                context.get_resources_by_pagination(address, None, version, limit as u64)?;
+            let state_view = context.state_view_at_version(version)?;
+            Ok(state_view
+                .as_move_resolver()
+                .as_converter(context.db.clone())
+                .try_into_resources(
+                    resources
+                        .iter()
+                        .map(|(typ, bytes)| (typ.clone(), bytes.as_slice())),
+                )?
+                .into_iter()
+                .map(Into::into)
+                .collect())
+        })
+        .await
+    }
+
+    /// The transactions sent by the account, starting at the sequence number `start`, or the
+    /// latest ones if it isn't given
+    #[graphql(complexity = "page_complexity(limit, child_complexity)")]
+    async fn transactions(
+        &self,
+        ctx: &GraphQlContext<'_>,
+        start: Option<U64>,
+        limit: Option<u16>,
+    ) -> Result<Vec<TransactionObject>> {
+        let (context, ledger_info) = request_data(ctx);
+        let limit = determine_limit::<BasicErrorWith404>(
+            limit,
+            DEFAULT_PAGE_SIZE,
+            context.max_transactions_page_size(),
+            &ledger_info,
+        )
+        .map_err(api_error)?;
+        let start = match start {
+            Some(start) => start.0,
+            None => match self.account_resource(ctx).await? {
+                Some(account) => account.sequence_number().saturating_sub(limit as u64),
+                None => return Ok(vec![]),
+            },
+        };
+        let (address, version) = (self.address, self.version);
+
+        spawn_blocking(move || {
+            let data = context
+                .get_account_transactions::<BasicErrorWith404>(
+                    address,
+                    Some(start),
+                    limit,
+                    version,
+                    &ledger_info,
+                )
+                .map_err(api_error)?;
+            Ok(context
+                .render_transactions_non_sequential::<BasicErrorWith404>(&ledger_info, data)
+                .map_err(api_error)?
+                .into_iter()
+                .map(TransactionObject)
+                .collect())
+        })
+        .await
+    }
+
+    /// The events of the event handle with the creation number, starting at the sequence
+    /// number `start`, or the latest ones if it isn't given
+    #[graphql(complexity = "page_complexity(limit, child_complexity)")]
+    async fn events(
+        &self,
+        ctx: &GraphQlContext<'_>,
+        creation_number: U64,
+        start: Option<U64>,
+        limit: Option<u16>,
+    ) -> Result<Vec<EventObject>> {
+        let (context, ledger_info) = request_data(ctx);
+        let limit = determine_limit::<BasicErrorWith404>(
+            limit,
+            DEFAULT_PAGE_SIZE,
+            context.max_events_page_size(),
+            &ledger_info,
+        )
+        .map_err(api_error)?;
+        let event_key = EventKey::new(creation_number.0, self.address);
+        let version = self.version;
+
+        spawn_blocking(move || {
+            let events = context
+                .get_events(&event_key, start.map(|start| start.0), limit, version)
+                .context(format!("Failed to find events by key {}", event_key))?;
+            Ok(context
+                .latest_state_view()?
+                .as_move_resolver()
+                .as_converter(context.db.clone())
+                .try_into_versioned_events(&events)?
+                .into_iter()
+                .map(Into::into)
+                .collect())
+        })
+        .await
+    }
+}
+
+/// A Move resource
+#[derive(SimpleObject)]
+#[graphql(name = "Resource")]
+pub struct ResourceObject {
+    #[graphql(name = "type")]
+    typ: String,
+    data: Json<MoveStructValue>,
+}
+
+impl From<aptos_api_types::MoveResource> for ResourceObject {
+    fn from(resource: aptos_api_types::MoveResource) -> Self {
+        Self {
+            typ: resource.typ.to_string(),
+            data: Json(resource.data),
+        }
+    }
+}
+
+/// An event, with the version of the transaction emitting it
+#[derive(SimpleObject)]
+#[graphql(name = "Event")]
+pub struct EventObject {
+    version: U64,
+    account_address: String,
+    creation_number: U64,
+    sequence_number: U64,
+    #[graphql(name = "type")]
+    typ: String,
+    data: Json<serde_json::Value>,
+}
+
+impl From<VersionedEvent> for EventObject {
+    fn from(event: VersionedEvent) -> Self {
+        Self {
+            version: U64(event.version.0),
+            account_address: event.guid.account_address.to_string(),
+            creation_number: U64(event.guid.creation_number.0),
+            sequence_number: U64(event.sequence_number.0),
+            typ: event.typ.to_string(),
+            data: Json(event.data),
+        }
+    }
+}
+
+/// A committed transaction
+pub struct TransactionObject(aptos_api_types::Transaction);
+
+impl TransactionObject {
+    fn info(&self) -> Result<&aptos_api_types::TransactionInfo> {
+        Ok(self.0.transaction_info()?)
+    }
+}
+
+#[Object(name = "Transaction")]
+impl TransactionObject {
+    /// The type of the transaction, e.g. `user_transaction`
+    #[graphql(name = "type")]
+    async fn typ(&self) -> &'static str {
+        self.0.type_str()
+    }
+
+    async fn version(&self) -> Result<U64> {
+        Ok(U64(self.info()?.version.0))
+    }
+
+    async fn hash(&self) -> Result<String> {
+        Ok(self.info()?.hash.to_string())
+    }
+
+    async fn success(&self) -> bool {
+        self.0.success()
+    }
+
+    async fn vm_status(&self) -> String {
+        self.0.vm_status()
+    }
+
+    async fn gas_used(&self) -> Result<U64> {
+        Ok(U64(self.info()?.gas_used.0))
+    }
+
+    /// The timestamp of the block of the transaction, in microseconds
+    async fn timestamp(&self) -> U64 {
+        U64(self.0.timestamp())
+    }
+
+    /// The sender of a user transaction
+    async fn sender(&self) -> Option<String> {
+        match &self.0 {
+            aptos_api_types::Transaction::UserTransaction(txn) => {
+                Some(txn.request.sender.to_string())
+            },
+            _ => None,
+        }
+    }
+
+    /// The sequence number of a user transaction
+    async fn sequence_number(&self) -> Option<U64> {
+        match &self.0 {
+            aptos_api_types::Transaction::UserTransaction(txn) => {
+                Some(U64(txn.request.sequence_number.0))
+            },
+            _ => None,
+        }
+    }
+
+    /// The payload of a user transaction
+    async fn payload(&self) -> Option<Json<TransactionPayload>> {
+        match &self.0 {
+            aptos_api_types::Transaction::UserTransaction(txn) => {
+                Some(Json(txn.request.payload.clone()))
+            },
+            _ => None,
+        }
+    }
+
+    async fn events(&self) -> Result<Vec<EventObject>> {
+        use aptos_api_types::Transaction::*;
+
+        let version = self.info()?.version;
+        let events = match &self.0 {
+            UserTransaction(txn) => &txn.events,
+            GenesisTransaction(txn) => &txn.events,
+            BlockMetadataTransaction(txn) => &txn.events,
+            PendingTransaction(_) | StateCheckpointTransaction(_) => return Ok(vec![]),
+        };
+        Ok(events
+            .iter()
+            .map(|event| {
+                VersionedEvent {
+                    version,
+                    guid: event.guid,
+                    sequence_number: event.sequence_number,
+                    typ: event.typ.clone(),
+                    data: event.data.clone(),
+                }
+                .into()
+            })
+            .collect())
+    }
+
+    /// The final state of the resources, modules and table items changed by the transaction
+    async fn changes(&self) -> Result<Json<Vec<WriteSetChange>>> {
+        Ok(Json(self.info()?.changes.clone()))
+    }
+}
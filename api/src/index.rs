@@ -5,10 +5,10 @@
 use crate::{
     accept_type::AcceptType,
     context::{api_spawn_blocking, Context},
-    response::{BasicResponse, BasicResponseStatus, BasicResult},
+    response::{BasicError, BasicResponse, BasicResponseStatus, BasicResult, InternalError},
     ApiTags,
 };
-use aptos_api_types::{IndexResponse, IndexResponseBcs};
+use aptos_api_types::{AptosErrorCode, IndexResponse, IndexResponseBcs, OldestQueryableVersion};
 use poem_openapi::OpenApi;
 use std::sync::Arc;
 
@@ -35,6 +35,17 @@ impl IndexApi {
         let ledger_info = self.context.get_latest_ledger_info()?;
 
         let node_role = self.context.node_role();
+        let oldest_state_version = self
+            .context
+            .get_oldest_state_version(&ledger_info)
+            .map_err(|err| {
+                BasicError::internal_with_code(err, AptosErrorCode::InternalError, &ledger_info)
+            })?;
+        let oldest_queryable_version = OldestQueryableVersion {
+            ledger: ledger_info.oldest_ledger_version,
+            state: oldest_state_version.into(),
+        };
+        let archival_node_url = self.context.archival_node_url();
 
         api_spawn_blocking(move || match accept_type {
             AcceptType::Json => {
@@ -42,6 +53,8 @@ impl IndexApi {
                     ledger_info.clone(),
                     node_role,
                     Some(aptos_build_info::get_git_hash()),
+                    Some(oldest_queryable_version),
+                    archival_node_url,
                 );
                 BasicResponse::try_from_json((
                     index_response,
@@ -12,12 +12,16 @@ mod blocks;
 mod check_size;
 pub mod context;
 mod error_converter;
+mod event_stream;
 mod events;
 mod failpoint;
+#[cfg(feature = "graphql")]
+mod graphql;
 mod index;
 mod log;
 pub mod metrics;
 mod page;
+mod request_quota;
 mod response;
 mod runtime;
 mod set_failpoints;
@@ -62,3 +62,12 @@ pub static REQUEST_SOURCE_CLIENT: Lazy<IntCounterVec> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+pub static REQUEST_QUOTA_EXCEEDED: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_api_request_quota_exceeded",
+        "API requests rejected for exceeding the request quota, grouped by client type (ip or api_key) and request kind (read or write)",
+        &["client_type", "request_kind"]
+    )
+    .unwrap()
+});
@@ -3,25 +3,59 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::response::BadRequestError;
-use aptos_api_types::{AptosErrorCode, LedgerInfo};
+use aptos_api_types::{AptosErrorCode, LedgerInfo, PageCursor};
 use serde::Deserialize;
 
-const DEFAULT_PAGE_SIZE: u16 = 25;
+pub(crate) const DEFAULT_PAGE_SIZE: u16 = 25;
 
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct Page {
     start: Option<u64>,
+    cursor: Option<PageCursor>,
     limit: Option<u16>,
     max_page_size: u16,
 }
 
 impl Page {
-    pub fn new(start: Option<u64>, limit: Option<u16>, max_page_size: u16) -> Self {
-        Self {
-            start,
+    /// Creates a page starting at `start`, or at the item the `cursor` points at. Only one of
+    /// them may be given.
+    pub fn new<E: BadRequestError>(
+        start: Option<u64>,
+        cursor: Option<PageCursor>,
+        limit: Option<u16>,
+        max_page_size: u16,
+    ) -> Result<Self, E> {
+        if start.is_some() && cursor.is_some() {
+            return Err(E::bad_request_with_code_no_info(
+                "Only one of start and cursor may be given",
+                AptosErrorCode::InvalidInput,
+            ));
+        }
+        Ok(Self {
+            start: cursor.map(|cursor| cursor.offset).or(start),
+            cursor,
             limit,
             max_page_size,
+        })
+    }
+
+    /// Verifies the cursor, if any, was returned by a node that isn't ahead of this one. Otherwise,
+    /// e.g., behind a load balancer, the next page could miss the items committed in between.
+    pub fn verify_cursor<E: BadRequestError>(&self, ledger_info: &LedgerInfo) -> Result<(), E> {
+        if let Some(cursor) = self.cursor {
+            if cursor.ledger_version > ledger_info.version() {
+                return Err(E::bad_request_with_code(
+                    format!(
+                        "Given cursor is from ledger version {}, which is ahead of the current ledger version {}",
+                        cursor.ledger_version,
+                        ledger_info.version()
+                    ),
+                    AptosErrorCode::InvalidInput,
+                    ledger_info,
+                ));
+            }
         }
+        Ok(())
     }
 
     /// Compute the start of the page for transactions
@@ -0,0 +1,272 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::metrics::REQUEST_QUOTA_EXCEEDED;
+use aptos_api_types::{
+    AptosError, AptosErrorCode, X_APTOS_API_KEY, X_APTOS_QUOTA_LIMIT, X_APTOS_QUOTA_REMAINING,
+};
+use aptos_config::config::{RequestBudgetConfig, RequestQuotaConfig};
+use aptos_infallible::Mutex;
+use aptos_rate_limiter::rate_limit::{Bucket, SharedBucket, TokenBucketRateLimiter};
+use hyper::Method;
+use poem::{
+    http::{header, HeaderValue, StatusCode},
+    Endpoint, IntoResponse, Middleware, Request, Response, Result,
+};
+use poem_openapi::payload::Json;
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::runtime::Handle;
+
+/// Interval at which the full buckets of the IP addresses are garbage collected
+const GARBAGE_COLLECTION_INTERVAL: Duration = Duration::from_secs(60);
+
+/// The kind of a request, each kind having its own budget
+#[derive(Clone, Copy, Debug)]
+enum RequestKind {
+    Read,
+    Write,
+}
+
+impl RequestKind {
+    /// Transaction submissions are writes, everything else (including simulations and
+    /// view functions) is a read
+    fn of(req: &Request) -> Self {
+        let path = req.uri().path();
+        if req.method() == Method::POST
+            && (path.ends_with("/transactions") || path.ends_with("/transactions/batch"))
+        {
+            RequestKind::Write
+        } else {
+            RequestKind::Read
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            RequestKind::Read => "read",
+            RequestKind::Write => "write",
+        }
+    }
+}
+
+/// The buckets of a single API key
+struct ApiKeyBuckets {
+    read: SharedBucket,
+    write: SharedBucket,
+}
+
+impl ApiKeyBuckets {
+    fn new(api_key_index: usize, budget: &RequestBudgetConfig) -> Self {
+        // The API keys themselves are secrets, so they're never logged
+        let new_bucket = |kind: RequestKind, size, rate| {
+            Arc::new(Mutex::new(Bucket::new(
+                "api_request_quota".to_owned(),
+                kind.as_str().to_owned(),
+                format!("api_key_{}", api_key_index),
+                size,
+                size,
+                rate,
+                None,
+            )))
+        };
+        Self {
+            read: new_bucket(
+                RequestKind::Read,
+                budget.read_bucket_size,
+                budget.read_bucket_rate,
+            ),
+            write: new_bucket(
+                RequestKind::Write,
+                budget.write_bucket_size,
+                budget.write_bucket_rate,
+            ),
+        }
+    }
+}
+
+/// The token buckets of all the clients
+struct RequestQuotaBuckets {
+    ip_read: TokenBucketRateLimiter<IpAddr>,
+    ip_write: TokenBucketRateLimiter<IpAddr>,
+    api_keys: HashMap<String, ApiKeyBuckets>,
+}
+
+impl RequestQuotaBuckets {
+    fn new(config: &RequestQuotaConfig) -> Self {
+        let ip_budget = &config.ip_budget;
+        let new_ip_limiter = |kind: RequestKind, size, rate| {
+            TokenBucketRateLimiter::new(
+                "api_request_quota",
+                kind.as_str().to_owned(),
+                // New buckets start full, so that full buckets can be garbage collected
+                100,
+                size,
+                rate,
+                None,
+            )
+        };
+        Self {
+            ip_read: new_ip_limiter(
+                RequestKind::Read,
+                ip_budget.read_bucket_size,
+                ip_budget.read_bucket_rate,
+            ),
+            ip_write: new_ip_limiter(
+                RequestKind::Write,
+                ip_budget.write_bucket_size,
+                ip_budget.write_bucket_rate,
+            ),
+            api_keys: config
+                .api_key_budgets
+                .iter()
+                .enumerate()
+                .map(|(index, (api_key, budget))| {
+                    (api_key.clone(), ApiKeyBuckets::new(index, budget))
+                })
+                .collect(),
+        }
+    }
+
+    /// Requests with a configured API key use its budget, and all the other requests use
+    /// the budget of their IP address
+    fn bucket(&self, req: &Request, kind: RequestKind) -> Option<(&'static str, SharedBucket)> {
+        let api_key_buckets = req
+            .headers()
+            .get(X_APTOS_API_KEY)
+            .and_then(|api_key| api_key.to_str().ok())
+            .and_then(|api_key| self.api_keys.get(api_key));
+        if let Some(api_key_buckets) = api_key_buckets {
+            let bucket = match kind {
+                RequestKind::Read => &api_key_buckets.read,
+                RequestKind::Write => &api_key_buckets.write,
+            };
+            return Some(("api_key", bucket.clone()));
+        }
+
+        let ip = req.remote_addr().as_socket_addr()?.ip();
+        let ip_limiter = match kind {
+            RequestKind::Read => &self.ip_read,
+            RequestKind::Write => &self.ip_write,
+        };
+        Some(("ip", ip_limiter.bucket(ip)))
+    }
+
+    /// The buckets of the IP addresses are created on their first request, and never
+    /// released otherwise
+    fn garbage_collect(&self) {
+        self.ip_read.garbage_collect_full_buckets();
+        self.ip_write.garbage_collect_full_buckets();
+    }
+}
+
+/// This middleware enforces the request quota of each client, and returns the remaining
+/// quota of the client in the response headers. Requests exceeding the quota are rejected
+/// with a 429.
+pub struct RequestQuota {
+    buckets: Option<Arc<RequestQuotaBuckets>>,
+}
+
+impl RequestQuota {
+    /// The middleware lets all the requests through when the quotas are disabled
+    pub fn new(config: &RequestQuotaConfig, runtime_handle: &Handle) -> Self {
+        if !config.enabled {
+            return Self { buckets: None };
+        }
+
+        let buckets = Arc::new(RequestQuotaBuckets::new(config));
+        let gc_buckets = buckets.clone();
+        runtime_handle.spawn(async move {
+            let mut interval = tokio::time::interval(GARBAGE_COLLECTION_INTERVAL);
+            loop {
+                interval.tick().await;
+                gc_buckets.garbage_collect();
+            }
+        });
+        Self {
+            buckets: Some(buckets),
+        }
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for RequestQuota {
+    type Output = RequestQuotaEndpoint<E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        RequestQuotaEndpoint {
+            inner: ep,
+            buckets: self.buckets.clone(),
+        }
+    }
+}
+
+/// Endpoint for RequestQuota middleware.
+pub struct RequestQuotaEndpoint<E> {
+    inner: E,
+    buckets: Option<Arc<RequestQuotaBuckets>>,
+}
+
+#[async_trait::async_trait]
+impl<E: Endpoint> Endpoint for RequestQuotaEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        let kind = RequestKind::of(&req);
+        let (client_type, bucket) = match self
+            .buckets
+            .as_ref()
+            .and_then(|buckets| buckets.bucket(&req, kind))
+        {
+            Some(client_bucket) => client_bucket,
+            None => return self.inner.call(req).await.map(IntoResponse::into_response),
+        };
+
+        let (acquired, limit, remaining) = {
+            let mut bucket = bucket.lock();
+            let acquired = bucket.acquire_all_tokens(1);
+            (acquired, bucket.size(), bucket.tokens())
+        };
+
+        let mut response = match acquired {
+            Ok(()) => self.inner.call(req).await?.into_response(),
+            Err(time_of_tokens_needed) => {
+                REQUEST_QUOTA_EXCEEDED
+                    .with_label_values(&[client_type, kind.as_str()])
+                    .inc();
+                quota_exceeded_response(kind, time_of_tokens_needed)
+            },
+        };
+        let headers = response.headers_mut();
+        headers.insert(X_APTOS_QUOTA_LIMIT, HeaderValue::from(limit));
+        headers.insert(X_APTOS_QUOTA_REMAINING, HeaderValue::from(remaining));
+        Ok(response)
+    }
+}
+
+fn quota_exceeded_response(kind: RequestKind, time_of_tokens_needed: Option<Instant>) -> Response {
+    // A single token is always eventually available, as buckets can't be empty
+    let retry_after_secs = time_of_tokens_needed
+        .map_or(Duration::from_secs(1), |time| {
+            time.saturating_duration_since(Instant::now())
+        })
+        .as_secs_f64()
+        .ceil()
+        .max(1.0) as u64;
+    let error = AptosError {
+        retry_after_secs: Some(retry_after_secs),
+        ..AptosError::new_with_error_code(
+            format!("The {} request quota is exceeded", kind.as_str()),
+            AptosErrorCode::RequestQuotaExceeded,
+        )
+    };
+    let mut response = Json(error).into_response();
+    response.set_status(StatusCode::TOO_MANY_REQUESTS);
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, HeaderValue::from(retry_after_secs));
+    response
+}
@@ -143,6 +143,8 @@ macro_rules! generate_error_response {
                 #[oai(header = "X-Aptos-Block-Height")] Option<u64>,
                 /// Oldest non-pruned block height of the chain
                 #[oai(header = "X-Aptos-Oldest-Block-Height")] Option<u64>,
+                /// Seconds to wait before retrying the request, when the node is overloaded
+                #[oai(header = "Retry-After")] Option<u64>,
             ),
             )*
         }
@@ -170,6 +172,7 @@ macro_rules! generate_error_response {
                     Some(ledger_info.epoch.into()),
                     Some(ledger_info.block_height.into()),
                     Some(ledger_info.oldest_block_height.into()),
+                    None,
                 ))
             }
 
@@ -189,6 +192,7 @@ macro_rules! generate_error_response {
                     None,
                     None,
                     None,
+                    None,
                 ))
             }
 
@@ -209,6 +213,7 @@ macro_rules! generate_error_response {
                     Some(ledger_info.epoch.into()),
                     Some(ledger_info.block_height.into()),
                     Some(ledger_info.oldest_block_height.into()),
+                    None,
                 ))
             }
 
@@ -216,6 +221,7 @@ macro_rules! generate_error_response {
                 aptos_error: aptos_api_types::AptosError,
                 ledger_info: &aptos_api_types::LedgerInfo
             ) -> Self where Self: Sized {
+                let retry_after_secs = aptos_error.retry_after_secs;
                 let payload = poem_openapi::payload::Json(Box::new(aptos_error));
                 Self::from($enum_name::$name(
                     payload,
@@ -226,6 +232,7 @@ macro_rules! generate_error_response {
                     Some(ledger_info.epoch.into()),
                     Some(ledger_info.block_height.into()),
                     Some(ledger_info.oldest_block_height.into()),
+                    retry_after_secs,
                 ))
             }
         }
@@ -245,6 +252,7 @@ macro_rules! generate_error_response {
                         _epoch,
                         _block_height,
                         _oldest_block_height,
+                        _retry_after_secs,
                     ) => &mut *inner,
                     )*
                 }
@@ -471,6 +479,17 @@ macro_rules! generate_success_response {
                 }
                 self
             }
+
+            pub fn with_page_cursor(mut self, new_cursor: Option<aptos_api_types::PageCursor>) -> Self {
+                match self {
+                    $(
+                    [<$enum_name>]::$name(_, _, _, _, _, _, _, _, ref mut cursor) => {
+                        *cursor = new_cursor.map(|c| c.to_string());
+                    }
+                    )*
+                }
+                self
+            }
         }
         }
     };
@@ -604,10 +623,61 @@ pub fn transaction_not_found_by_hash<E: NotFoundError>(
     )
 }
 
-pub fn version_pruned<E: GoneError>(ledger_version: u64, ledger_info: &LedgerInfo) -> E {
-    E::gone_with_code(
+pub fn version_pruned<E: GoneError>(
+    ledger_version: u64,
+    archival_node_url: Option<String>,
+    ledger_info: &LedgerInfo,
+) -> E {
+    build_pruned(
         format!("Ledger version({}) has been pruned", ledger_version),
         AptosErrorCode::VersionPruned,
+        ledger_info.oldest_ledger_version.0,
+        archival_node_url,
+        ledger_info,
+    )
+}
+
+pub fn state_pruned<E: GoneError>(
+    ledger_version: u64,
+    oldest_state_version: u64,
+    archival_node_url: Option<String>,
+    ledger_info: &LedgerInfo,
+) -> E {
+    build_pruned(
+        format!(
+            "State at ledger version({}) has been pruned",
+            ledger_version
+        ),
+        AptosErrorCode::StatePruned,
+        oldest_state_version,
+        archival_node_url,
+        ledger_info,
+    )
+}
+
+fn build_pruned<E: GoneError>(
+    message: String,
+    error_code: AptosErrorCode,
+    oldest_queryable_version: u64,
+    archival_node_url: Option<String>,
+    ledger_info: &LedgerInfo,
+) -> E {
+    let message = match &archival_node_url {
+        Some(url) => format!(
+            "{}, the oldest queryable version is {}. It may be queried from the archival node {}",
+            message, oldest_queryable_version, url
+        ),
+        None => format!(
+            "{}, the oldest queryable version is {}",
+            message, oldest_queryable_version
+        ),
+    };
+    E::gone_from_aptos_error(
+        AptosError {
+            oldest_queryable_version: Some(oldest_queryable_version),
+            archival_node_url,
+            ..AptosError::new_with_error_code(message, error_code)
+        },
         ledger_info,
     )
 }
@@ -2,17 +2,20 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "graphql")]
+use crate::graphql::{self, GraphQl};
 use crate::{
     accounts::AccountsApi, basic::BasicApi, blocks::BlocksApi, check_size::PostSizeLimit,
-    context::Context, error_converter::convert_error, events::EventsApi, index::IndexApi,
-    log::middleware_log, set_failpoints, state::StateApi, transactions::TransactionsApi,
-    view_function::ViewFunctionApi,
+    context::Context, error_converter::convert_error, event_stream, event_stream::EventStream,
+    events::EventsApi, index::IndexApi, log::middleware_log, request_quota::RequestQuota,
+    set_failpoints, state::StateApi, transactions::TransactionsApi, view_function::ViewFunctionApi,
 };
 use anyhow::Context as AnyhowContext;
-use aptos_api_types::X_APTOS_CLIENT;
+use aptos_api_types::{X_APTOS_API_KEY, X_APTOS_CLIENT};
 use aptos_config::config::{ApiConfig, NodeConfig};
+use aptos_event_notifications::EventNotificationListener;
 use aptos_logger::info;
-use aptos_mempool::MempoolClientSender;
+use aptos_mempool::{BackPressureStatus, MempoolClientSender};
 use aptos_storage_interface::DbReader;
 use aptos_types::chain_id::ChainId;
 use poem::{
@@ -35,13 +38,21 @@ pub fn bootstrap(
     chain_id: ChainId,
     db: Arc<dyn DbReader>,
     mp_sender: MempoolClientSender,
+    back_pressure_status: BackPressureStatus,
+    event_listener: Option<EventNotificationListener>,
 ) -> anyhow::Result<Runtime> {
     let max_runtime_workers = get_max_runtime_workers(&config.api);
     let runtime = aptos_runtimes::spawn_named_runtime("api".into(), Some(max_runtime_workers));
 
-    let context = Context::new(chain_id, db, mp_sender, config.clone());
+    let context = Context::new(
+        chain_id,
+        db,
+        mp_sender,
+        config.clone(),
+        back_pressure_status,
+    );
 
-    attach_poem_to_runtime(runtime.handle(), context, config, false)
+    attach_poem_to_runtime(runtime.handle(), context, config, false, event_listener)
         .context("Failed to attach poem to runtime")?;
 
     Ok(runtime)
@@ -116,11 +127,36 @@ pub fn attach_poem_to_runtime(
     context: Context,
     config: &NodeConfig,
     random_port: bool,
+    event_listener: Option<EventNotificationListener>,
 ) -> anyhow::Result<SocketAddr> {
     let context = Arc::new(context);
 
+    // The event stream is only served when it is enabled and fed with committed events
+    let event_stream = event_listener
+        .filter(|_| config.api.event_stream_enabled)
+        .map(|event_listener| {
+            let event_stream = EventStream::new(context.clone());
+            event_stream.start_publisher(runtime_handle, event_listener);
+            Arc::new(event_stream)
+        });
+
+    // The GraphQL API is only compiled in with the graphql feature
+    #[cfg(feature = "graphql")]
+    let graphql = config
+        .api
+        .graphql
+        .enabled
+        .then(|| Arc::new(GraphQl::new(context.clone())));
+    #[cfg(not(feature = "graphql"))]
+    anyhow::ensure!(
+        !config.api.graphql.enabled,
+        "The GraphQL API is enabled, but the node was built without the graphql feature"
+    );
+
     let size_limit = context.content_length_limit();
 
+    let request_quota = RequestQuota::new(&config.api.request_quota, runtime_handle);
+
     let api_service = get_api_service(context.clone());
 
     let spec_json = api_service.spec_endpoint();
@@ -174,26 +210,40 @@ pub fn attach_poem_to_runtime(
             .allow_methods(vec![Method::GET, Method::POST])
             .allow_headers(vec![
                 header::HeaderName::from_static(X_APTOS_CLIENT),
+                header::HeaderName::from_static(X_APTOS_API_KEY),
                 header::CONTENT_TYPE,
                 header::ACCEPT,
             ]);
 
+        let mut v1_route = Route::new()
+            .nest("/", api_service)
+            .at("/spec.json", spec_json)
+            .at("/spec.yaml", spec_yaml)
+            // TODO: We add this manually outside of the OpenAPI spec for now.
+            // https://github.com/poem-web/poem/issues/364
+            .at(
+                "/set_failpoint",
+                poem::get(set_failpoints::set_failpoint_poem).data(context.clone()),
+            );
+        // WebSockets can't be described by the OpenAPI spec, so this is added manually too
+        if let Some(event_stream) = event_stream {
+            v1_route = v1_route.at(
+                "/stream/events",
+                poem::get(event_stream::stream_events_poem).data(event_stream),
+            );
+        }
+        #[cfg(feature = "graphql")]
+        if let Some(graphql) = graphql {
+            v1_route = v1_route.at("/graphql", poem::post(graphql::graphql_poem).data(graphql));
+        }
+
         // Build routes for the API
         let route = Route::new()
             .at("/", root_handler)
-            .nest(
-                "/v1",
-                Route::new()
-                    .nest("/", api_service)
-                    .at("/spec.json", spec_json)
-                    .at("/spec.yaml", spec_yaml)
-                    // TODO: We add this manually outside of the OpenAPI spec for now.
-                    // https://github.com/poem-web/poem/issues/364
-                    .at(
-                        "/set_failpoint",
-                        poem::get(set_failpoints::set_failpoint_poem).data(context.clone()),
-                    ),
-            )
+            .nest("/v1", v1_route)
+            // Keep this inside of CORS, so that preflight requests aren't counted and
+            // rejections still carry the CORS headers
+            .with(request_quota)
             .with(cors)
             .with(PostSizeLimit::new(size_limit))
             // NOTE: Make sure to keep this after all the `with` middleware.
@@ -304,6 +354,8 @@ mod tests {
             ChainId::test(),
             context.db.clone(),
             context.mempool.ac_client.clone(),
+            context.mempool.back_pressure_status.clone(),
+            None,
         );
         assert!(ret.is_ok());
 
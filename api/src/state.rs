@@ -7,26 +7,28 @@ use crate::{
     failpoint::fail_point_poem,
     response::{
         api_forbidden, build_not_found, module_not_found, resource_not_found, table_item_not_found,
-        BadRequestError, BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResultWith404,
-        InternalError,
+        version_pruned, BadRequestError, BasicErrorWith404, BasicResponse, BasicResponseStatus,
+        BasicResultWith404, InternalError,
     },
     ApiTags, Context,
 };
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::{
-    verify_module_identifier, Address, AptosErrorCode, AsConverter, IdentifierWrapper,
-    MoveModuleBytecode, MoveResource, MoveStructTag, MoveValue, RawStateValueRequest,
-    RawTableItemRequest, TableItemRequest, VerifyInput, VerifyInputWithRecursion, U64,
+    verify_module_identifier, Address, AptosErrorCode, AsConverter, HexEncodedBytes,
+    IdentifierWrapper, MoveModuleBytecode, MoveResource, MoveStructTag, MoveValue,
+    RawStateValueRequest, RawTableItemRequest, TableItemRequest, VerifyInput,
+    VerifyInputWithRecursion, ViewStateRequest, ViewStateResource, ViewStateResponse, U64,
 };
 use aptos_state_view::TStateView;
 use aptos_types::{
     access_path::AccessPath,
+    account_address::AccountAddress,
     state_store::{state_key::StateKey, table::TableHandle},
 };
 use aptos_vm::data_cache::AsMoveResolver;
 use move_core_types::{
     language_storage::{ModuleId, StructTag},
-    resolver::MoveResolver,
+    resolver::{ModuleResolver, MoveResolver},
 };
 use poem_openapi::{
     param::{Path, Query},
@@ -272,9 +274,196 @@ impl StateApi {
         let api = self.clone();
         api_spawn_blocking(move || api.raw_value(&accept_type, request.0, ledger_version.0)).await
     }
+
+    /// View state
+    ///
+    /// Retrieves multiple resources, possibly from different accounts, at a single ledger version.
+    /// If the ledger version is not specified in the request, the latest ledger version is used.
+    /// Resources that don't exist at the ledger version are returned with null data.
+    ///
+    /// If proofs are requested, the resources are read at the latest state checkpoint at or
+    /// before the requested ledger version, and each resource comes with a sparse merkle proof
+    /// against the state root hash at that version.
+    ///
+    /// The Aptos nodes prune account state history, via a configurable time window.
+    /// If the requested ledger version has been pruned, the server responds with a 410.
+    #[oai(
+        path = "/view_state",
+        method = "post",
+        operation_id = "view_state",
+        tag = "ApiTags::Accounts"
+    )]
+    async fn view_state(
+        &self,
+        accept_type: AcceptType,
+        /// Request listing the resources to read
+        request: Json<ViewStateRequest>,
+        /// Ledger version to get state of accounts
+        ///
+        /// If not provided, it will be the latest version
+        ledger_version: Query<Option<U64>>,
+    ) -> BasicResultWith404<ViewStateResponse> {
+        request
+            .0
+            .verify()
+            .context("'request' invalid")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        fail_point_poem("endpoint_view_state")?;
+
+        if AcceptType::Bcs == accept_type {
+            return Err(api_forbidden(
+                "View state",
+                "Only JSON is supported as an AcceptType.",
+            ));
+        }
+        self.context
+            .check_api_output_enabled("View state", &accept_type)?;
+
+        let api = self.clone();
+        api_spawn_blocking(move || api.view_state_inner(request.0, ledger_version.0)).await
+    }
 }
 
 impl StateApi {
+    /// Read the requested resources at a single ledger version, with proofs if requested
+    fn view_state_inner(
+        &self,
+        request: ViewStateRequest,
+        ledger_version: Option<U64>,
+    ) -> BasicResultWith404<ViewStateResponse> {
+        let (ledger_info, requested_ledger_version) = self
+            .context
+            .get_latest_ledger_info_and_verify_lookup_version(ledger_version.map(|v| v.0))?;
+
+        let max_view_state_resources = self.context.max_view_state_resources();
+        if request.resources.len() > max_view_state_resources {
+            return Err(BasicErrorWith404::bad_request_with_code(
+                format!(
+                    "Requested too many resources: {}, while limit is {}",
+                    request.resources.len(),
+                    max_view_state_resources,
+                ),
+                AptosErrorCode::InvalidInput,
+                &ledger_info,
+            ));
+        }
+        let resources = request
+            .resources
+            .into_iter()
+            .map(|resource| {
+                let struct_tag: StructTag = resource.resource_type.clone().try_into()?;
+                Ok((resource, struct_tag))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()
+            .context("Failed to parse given resource type")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code(
+                    err,
+                    AptosErrorCode::InvalidInput,
+                    &ledger_info,
+                )
+            })?;
+
+        // Proofs are only available for the state checkpoints persisted in the state merkle db
+        let with_proof = request.with_proof.unwrap_or(false);
+        let (version, state_root_hash) = if with_proof {
+            let (version, state_root_hash) = self
+                .context
+                .db
+                .get_state_snapshot_before(requested_ledger_version + 1)
+                .context("Failed to read state snapshot from DB")
+                .map_err(|err| {
+                    BasicErrorWith404::internal_with_code(
+                        err,
+                        AptosErrorCode::InternalError,
+                        &ledger_info,
+                    )
+                })?
+                .ok_or_else(|| {
+                    version_pruned(
+                        requested_ledger_version,
+                        self.context.archival_node_url(),
+                        &ledger_info,
+                    )
+                })?;
+            (version, Some(state_root_hash))
+        } else {
+            (requested_ledger_version, None)
+        };
+
+        let state_view = self.context.state_view_at_version(version).map_err(|err| {
+            BasicErrorWith404::internal_with_code(err, AptosErrorCode::InternalError, &ledger_info)
+        })?;
+        let resolver = state_view.as_move_resolver();
+        let converter = resolver.as_converter(self.context.db.clone());
+
+        let resources = resources
+            .into_iter()
+            .map(|(resource, struct_tag)| {
+                let address: AccountAddress = resource.address.into();
+                let data = resolver
+                    .get_resource(&address, &struct_tag)
+                    .context(format!(
+                        "Failed to query DB to check for {} at {}",
+                        struct_tag, resource.address
+                    ))?
+                    .map(|bytes| converter.try_into_resource(&struct_tag, &bytes))
+                    .transpose()
+                    .context("Failed to deserialize resource data retrieved from DB")?;
+
+                let proof = if with_proof {
+                    // The proof is of the state item holding the resource
+                    let state_key = match get_resource_group(&resolver, &struct_tag) {
+                        Some(group_tag) => StateKey::access_path(
+                            AccessPath::resource_group_access_path(address, group_tag),
+                        ),
+                        None => StateKey::access_path(AccessPath::resource_access_path(
+                            address,
+                            struct_tag.clone(),
+                        )?),
+                    };
+                    let (_, proof) = self
+                        .context
+                        .db
+                        .get_state_value_with_proof_by_version(&state_key, version)
+                        .context(format!(
+                            "Failed to query DB for the proof of {} at {}",
+                            struct_tag, resource.address
+                        ))?;
+                    Some(HexEncodedBytes::from(bcs::to_bytes(&proof)?))
+                } else {
+                    None
+                };
+
+                Ok(ViewStateResource {
+                    address: resource.address,
+                    resource_type: resource.resource_type,
+                    data,
+                    proof,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+
+        BasicResponse::try_from_json((
+            ViewStateResponse {
+                ledger_version: version.into(),
+                state_root_hash: state_root_hash.map(Into::into),
+                resources,
+            },
+            &ledger_info,
+            BasicResponseStatus::Ok,
+        ))
+    }
+
     /// Read a resource at the ledger version
     ///
     /// JSON: Convert to MoveResource
@@ -608,3 +797,14 @@ impl StateApi {
         }
     }
 }
+
+/// Returns the resource group the resource is a member of, if any
+fn get_resource_group(resolver: &dyn ModuleResolver, struct_tag: &StructTag) -> Option<StructTag> {
+    let metadata =
+        aptos_framework::get_metadata(&resolver.get_module_metadata(&struct_tag.module_id()))?;
+    metadata
+        .struct_attributes
+        .get(struct_tag.name.as_ident_str().as_str())?
+        .iter()
+        .find_map(|attr| attr.get_resource_group_member())
+}
@@ -0,0 +1,161 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::new_test_context;
+use aptos_api_test_context::{current_function_name, TestContext};
+use aptos_api_types::AsConverter;
+use aptos_types::account_address;
+use aptos_vm::data_cache::AsMoveResolver;
+use move_core_types::{
+    language_storage::StructTag,
+    value::{IdentifierMappingKind, LayoutTag, MoveStructLayout, MoveTypeLayout},
+};
+use move_vm_types::{
+    value_transformation::{
+        deserialize_and_replace_values_with_ids, serialize_and_replace_ids_with_values,
+        TransformationError, TransformationResult, ValueToIdentifierMapping,
+    },
+    values::{Struct, Value},
+};
+use serde_json::{json, to_value};
+use std::{cell::RefCell, path::PathBuf, str::FromStr};
+
+/// Offset of the identifiers, so that they never collide with the values they replace
+const IDENTIFIER_OFFSET: u64 = 1000;
+
+/// Replaces the values of the delayed fields with identifiers, like the VM does when
+/// executing transactions in parallel
+#[derive(Default)]
+struct TestMapping {
+    values: RefCell<Vec<Value>>,
+}
+
+impl ValueToIdentifierMapping for TestMapping {
+    fn value_to_identifier(
+        &self,
+        _kind: &IdentifierMappingKind,
+        _layout: &MoveTypeLayout,
+        value: Value,
+    ) -> TransformationResult<Value> {
+        let mut values = self.values.borrow_mut();
+        let identifier = IDENTIFIER_OFFSET + values.len() as u64;
+        values.push(value);
+        Ok(Value::u64(identifier))
+    }
+
+    fn identifier_to_value(
+        &self,
+        _layout: &MoveTypeLayout,
+        identifier: Value,
+    ) -> TransformationResult<Value> {
+        let identifier = identifier.value_as::<u64>()?;
+        let values = self.values.borrow();
+        values
+            .get((identifier - IDENTIFIER_OFFSET) as usize)
+            .ok_or_else(|| TransformationError::new("Unknown identifier"))?
+            .copy_value()
+            .map_err(Into::into)
+    }
+}
+
+// The token identifiers are a resource group member, with both of their fields being snapshots
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_token_identifiers_delayed_fields() {
+    let mut context = new_test_context(current_function_name!());
+
+    let mut user = context.create_account().await;
+    let user_addr = user.address();
+    let named_addresses = vec![("hero".to_string(), user_addr)];
+    let txn = futures::executor::block_on(async move {
+        let path = PathBuf::from(std::env!("CARGO_MANIFEST_DIR"))
+            .join("../aptos-move/move-examples/token_objects/hero");
+        TestContext::build_package(path, named_addresses)
+    });
+    context.publish_package(&mut user, txn).await;
+    context
+        .api_execute_entry_function(
+            &mut user,
+            &format!("0x{}::hero::mint_hero", user_addr.to_hex()),
+            json!([]),
+            json!(["The best hero ever!", "Male", "Wukong", "Monkey God", ""]),
+        )
+        .await;
+
+    let token_addr = account_address::create_token_address(user_addr, "Hero Quest!", "Wukong");
+    let identifiers = context
+        .gen_resource(&token_addr, "0x4::token::ConcurrentTokenIdentifiers")
+        .await
+        .unwrap();
+    assert_eq!(identifiers["data"]["name"]["value"], "Wukong");
+    assert_eq!(
+        identifiers["delayed_fields"],
+        json!([
+            {"path": "index", "kind": "snapshot", "type": "u64"},
+            {"path": "name", "kind": "snapshot", "type": "0x1::string::String"},
+        ])
+    );
+
+    // Resources without delayed fields aren't annotated
+    let token = context
+        .gen_resource(&token_addr, "0x4::token::Token")
+        .await
+        .unwrap();
+    assert!(token.get("delayed_fields").is_none());
+}
+
+// The values exchanged with identifiers by the VM are rendered with their logical values
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_exchanged_delayed_fields_round_trip() {
+    let context = new_test_context(current_function_name!());
+
+    let aggregator_layout = MoveTypeLayout::Struct(MoveStructLayout::new(vec![
+        MoveTypeLayout::Tagged(
+            LayoutTag::IdentifierMapping(IdentifierMappingKind::Aggregator),
+            Box::new(MoveTypeLayout::U64),
+        ),
+        MoveTypeLayout::U64,
+    ]));
+    let layout = MoveTypeLayout::Struct(MoveStructLayout::new(vec![
+        aggregator_layout.clone(),
+        aggregator_layout,
+    ]));
+    let aggregator =
+        |value: u64| Value::struct_(Struct::pack(vec![Value::u64(value), Value::u64(u64::MAX)]));
+    let supply = Value::struct_(Struct::pack(vec![aggregator(1), aggregator(2)]));
+    let bytes = supply.simple_serialize(&layout).unwrap();
+
+    // Exchange the values with identifiers, and back
+    let mapping = TestMapping::default();
+    let exchanged_supply =
+        deserialize_and_replace_values_with_ids(&bytes, &layout, &mapping).unwrap();
+    assert!(!exchanged_supply.equals(&supply).unwrap());
+    let exchanged_bytes =
+        serialize_and_replace_ids_with_values(&exchanged_supply, &layout, &mapping).unwrap();
+    assert_eq!(exchanged_bytes, bytes);
+
+    let state_view = context.latest_state_view();
+    let resolver = state_view.as_move_resolver();
+    let converter = resolver.as_converter(context.db.clone());
+    let typ = StructTag::from_str("0x4::collection::ConcurrentSupply").unwrap();
+    let resource = converter.try_into_resource(&typ, &exchanged_bytes).unwrap();
+    assert_eq!(
+        to_value(&resource).unwrap(),
+        json!({
+            "type": "0x4::collection::ConcurrentSupply",
+            "data": {
+                "current_supply": {"value": "1", "max_value": u64::MAX.to_string()},
+                "total_minted": {"value": "2", "max_value": u64::MAX.to_string()},
+            },
+            "delayed_fields": [
+                {"path": "current_supply", "kind": "aggregator", "type": "u64"},
+                {"path": "total_minted", "kind": "aggregator", "type": "u64"},
+            ]
+        })
+    );
+
+    // The converted bytes are exchanged with the same identifiers again
+    let round_trip_supply =
+        deserialize_and_replace_values_with_ids(&exchanged_bytes, &layout, &TestMapping::default())
+            .unwrap();
+    assert!(round_trip_supply.equals(&exchanged_supply).unwrap());
+}
@@ -0,0 +1,59 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::new_test_context;
+use crate::event_stream::{read_committed_events, to_messages, EventFilter};
+use aptos_api_test_context::current_function_name;
+use serde_json::Value;
+use std::sync::Arc;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_read_committed_events_with_filter() {
+    let mut context = new_test_context(current_function_name!());
+    let start_version = context.get_latest_ledger_info().version() + 1;
+    let account = context.create_account().await;
+    let ledger_version = context.get_latest_ledger_info().version();
+
+    let api_context = Arc::new(context.context.clone());
+    let committed_events =
+        read_committed_events(api_context.clone(), start_version, ledger_version)
+            .await
+            .unwrap();
+    assert_eq!(committed_events.first_version, start_version);
+    assert_eq!(committed_events.last_version, ledger_version);
+    assert!(committed_events.events.iter().all(|event| {
+        event.transaction_version >= start_version && event.transaction_version <= ledger_version
+    }));
+
+    // Only the registration of the new account's coin store matches
+    let filter = EventFilter::new(
+        Some(account.address().into()),
+        Some("0x1::account::CoinRegisterEvent"),
+    )
+    .unwrap();
+    let messages = to_messages(api_context.clone(), filter, committed_events.events.clone())
+        .await
+        .unwrap();
+    assert_eq!(messages.len(), 1);
+    let event: Value = serde_json::from_str(&messages[0]).unwrap();
+    assert_eq!(event["type"], "0x1::account::CoinRegisterEvent");
+    assert_eq!(
+        event["guid"]["account_address"],
+        account.address().to_hex_literal()
+    );
+
+    // Without a filter, all the events are sent
+    let messages = to_messages(
+        api_context,
+        EventFilter::default(),
+        committed_events.events.clone(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(messages.len(), committed_events.events.len());
+}
+
+#[test]
+fn test_event_filter_with_invalid_event_type() {
+    assert!(EventFilter::new(None, Some("0x1::account::")).is_err());
+}
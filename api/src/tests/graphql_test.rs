@@ -0,0 +1,99 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{new_test_context, new_test_context_with_config};
+use aptos_api_test_context::{current_function_name, TestContext};
+use aptos_config::config::NodeConfig;
+use bytes::Bytes;
+use hyper::Response;
+use serde_json::{json, Value};
+
+fn new_graphql_test_context(test_name: String) -> TestContext {
+    let mut node_config = NodeConfig::default();
+    node_config.api.graphql.enabled = true;
+    node_config.api.graphql.max_batch_size = 2;
+    new_test_context_with_config(test_name, node_config)
+}
+
+async fn post_graphql(context: &TestContext, body: Value) -> Response<Bytes> {
+    let req = warp::test::request()
+        .method("POST")
+        .path("/v1/graphql")
+        .json(&body);
+    context.reply(req).await
+}
+
+async fn post_graphql_ok(context: &TestContext, body: Value) -> Value {
+    let resp = post_graphql(context, body).await;
+    assert_eq!(resp.status(), 200);
+    serde_json::from_slice(resp.body()).unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_query_account_and_transactions() {
+    let context = new_graphql_test_context(current_function_name!());
+
+    let resp = post_graphql_ok(
+        &context,
+        json!({
+            "query": r#"{
+                ledgerInfo { ledgerVersion }
+                account(address: "0x1") {
+                    address
+                    sequenceNumber
+                    resource(resourceType: "0x1::account::Account") { type }
+                    missing: resource(resourceType: "0x1::multisig_account::MultisigAccount") { type }
+                }
+                transactions(limit: 2) { version type success }
+            }"#,
+        }),
+    )
+    .await;
+    assert!(resp.get("errors").is_none(), "{}", resp);
+    let data = &resp["data"];
+    assert_eq!(data["account"]["address"], "0x1");
+    assert_eq!(data["account"]["sequenceNumber"], "0");
+    assert_eq!(data["account"]["resource"]["type"], "0x1::account::Account");
+    // Only the selected fields are returned
+    assert_eq!(data["account"]["resource"].as_object().unwrap().len(), 1);
+    // 0x1 isn't a multisig account
+    assert_eq!(data["account"]["missing"], Value::Null);
+
+    // The transactions are the latest ones at the ledger version of the query
+    let transactions = data["transactions"].as_array().unwrap();
+    assert_eq!(transactions.len(), 2);
+    assert_eq!(
+        transactions[1]["version"],
+        data["ledgerInfo"]["ledgerVersion"]
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_batch_queries() {
+    let context = new_graphql_test_context(current_function_name!());
+    let query = json!({"query": "{ transactionByVersion(version: \"0\") { version type } }"});
+
+    let resp = post_graphql_ok(&context, json!([query.clone(), query.clone()])).await;
+    let responses = resp.as_array().unwrap();
+    assert_eq!(responses.len(), 2);
+    assert_eq!(
+        responses[0]["data"]["transactionByVersion"],
+        json!({"version": "0", "type": "genesis_transaction"})
+    );
+
+    // Batches larger than the configured limit are rejected
+    let resp = post_graphql(&context, json!([query.clone(), query.clone(), query])).await;
+    assert_eq!(resp.status(), 400);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_graphql_disabled_by_default() {
+    let context = new_test_context(current_function_name!());
+
+    let resp = post_graphql(
+        &context,
+        json!({"query": "{ ledgerInfo { ledgerVersion } }"}),
+    )
+    .await;
+    assert_eq!(resp.status(), 404);
+}
@@ -2,8 +2,9 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use super::new_test_context;
+use super::{new_test_context, new_test_context_with_config};
 use aptos_api_test_context::current_function_name;
+use aptos_config::config::NodeConfig;
 use serde_json::json;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
@@ -13,6 +14,22 @@ async fn test_get_index() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_index_with_archival_node_url() {
+    let mut node_config = NodeConfig::default();
+    node_config.api.archival_node_url = Some("https://archive.example.com/v1".to_owned());
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+    let resp = context.get("/").await;
+    assert_eq!(
+        resp["archival_node_url"],
+        json!("https://archive.example.com/v1")
+    );
+    assert_eq!(
+        resp["oldest_queryable_version"],
+        json!({"ledger": "0", "state": "0"})
+    );
+}
+
 // TODO: Un-ignore this pending https://github.com/poem-web/poem/issues/343.
 #[ignore]
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
@@ -43,6 +60,17 @@ async fn test_health_check() {
     assert_eq!(resp.status(), 200)
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_health_check_back_pressure() {
+    let context = new_test_context(current_function_name!());
+    let resp = context.get("/-/healthy").await;
+    assert_eq!(resp["message"], "aptos-node:ok");
+
+    context.mempool.back_pressure_status.update(true, false);
+    let resp = context.get("/-/healthy").await;
+    assert_eq!(resp["message"], "aptos-node:busy");
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_openapi_spec() {
     let context = new_test_context(current_function_name!());
@@ -5,12 +5,17 @@
 mod accounts_test;
 mod blocks_test;
 mod converter_test;
+mod delayed_fields_test;
+mod event_stream_test;
 mod events_test;
+#[cfg(feature = "graphql")]
+mod graphql_test;
 mod index_test;
 mod invalid_post_request_test;
 mod modules;
 mod multisig_transactions_test;
 mod objects;
+mod request_quota_test;
 mod resource_groups;
 mod secp256k1_ecdsa;
 mod state_test;
@@ -18,6 +23,7 @@ mod string_resource_test;
 mod transaction_vector_test;
 mod transactions_test;
 mod view_function;
+mod webauthn;
 
 use aptos_api_test_context::{new_test_context as super_new_test_context, TestContext};
 use aptos_config::config::NodeConfig;
@@ -0,0 +1,82 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::new_test_context_with_config;
+use aptos_api_test_context::{current_function_name, TestContext};
+use aptos_api_types::{X_APTOS_API_KEY, X_APTOS_QUOTA_LIMIT, X_APTOS_QUOTA_REMAINING};
+use aptos_config::config::{NodeConfig, RequestBudgetConfig};
+use bytes::Bytes;
+use hyper::Response;
+use serde_json::Value;
+
+const API_KEY: &str = "test-api-key";
+
+fn new_test_context_with_quota(test_name: String) -> TestContext {
+    let budget = RequestBudgetConfig {
+        read_bucket_rate: 1,
+        read_bucket_size: 2,
+        ..Default::default()
+    };
+    let mut node_config = NodeConfig::default();
+    node_config.api.request_quota.enabled = true;
+    node_config.api.request_quota.ip_budget = budget;
+    node_config
+        .api
+        .request_quota
+        .api_key_budgets
+        .insert(API_KEY.to_owned(), RequestBudgetConfig {
+            read_bucket_size: 1,
+            ..budget
+        });
+    new_test_context_with_config(test_name, node_config)
+}
+
+async fn get_index(context: &TestContext, api_key: Option<&str>) -> Response<Bytes> {
+    let mut req = warp::test::request().method("GET").path("/v1/");
+    if let Some(api_key) = api_key {
+        req = req.header(X_APTOS_API_KEY, api_key);
+    }
+    context.reply(req).await
+}
+
+fn assert_quota(resp: &Response<Bytes>, limit: &str, remaining: &str) {
+    assert_eq!(resp.headers()[X_APTOS_QUOTA_LIMIT], limit);
+    assert_eq!(resp.headers()[X_APTOS_QUOTA_REMAINING], remaining);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_ip_request_quota() {
+    let context = new_test_context_with_quota(current_function_name!());
+
+    let resp = get_index(&context, None).await;
+    assert_eq!(resp.status(), 200);
+    assert_quota(&resp, "2", "1");
+    let resp = get_index(&context, None).await;
+    assert_eq!(resp.status(), 200);
+    assert_quota(&resp, "2", "0");
+
+    // The read budget of the IP address is exhausted
+    let resp = get_index(&context, None).await;
+    assert_eq!(resp.status(), 429);
+    assert_quota(&resp, "2", "0");
+    assert!(resp.headers().contains_key("Retry-After"));
+    let error: Value = serde_json::from_slice(resp.body()).unwrap();
+    assert_eq!(error["error_code"], "request_quota_exceeded");
+    assert!(error["retry_after_secs"].as_u64().unwrap() >= 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_api_key_request_quota() {
+    let context = new_test_context_with_quota(current_function_name!());
+
+    let resp = get_index(&context, Some(API_KEY)).await;
+    assert_eq!(resp.status(), 200);
+    assert_quota(&resp, "1", "0");
+    let resp = get_index(&context, Some(API_KEY)).await;
+    assert_eq!(resp.status(), 429);
+
+    // Requests without a known API key still use the budget of their IP address
+    let resp = get_index(&context, Some("unknown-api-key")).await;
+    assert_eq!(resp.status(), 200);
+    assert_quota(&resp, "2", "1");
+}
@@ -3,13 +3,19 @@
 
 use super::new_test_context;
 use aptos_api_test_context::{current_function_name, TestContext};
+use aptos_api_types::HexEncodedBytes;
+use aptos_crypto::hash::CryptoHash;
 use aptos_sdk::{transaction_builder::aptos_stdlib::aptos_token_stdlib, types::LocalAccount};
 use aptos_storage_interface::DbReader;
-use move_core_types::account_address::AccountAddress;
+use aptos_types::{
+    access_path::AccessPath, account_config::AccountResource, proof::SparseMerkleProof,
+    state_store::state_key::StateKey,
+};
+use move_core_types::{account_address::AccountAddress, move_resource::MoveStructType};
 use move_package::BuildConfig;
 use serde::Serialize;
 use serde_json::{json, Value};
-use std::{convert::TryInto, path::PathBuf};
+use std::{convert::TryInto, path::PathBuf, str::FromStr};
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_account_resource() {
@@ -298,6 +304,78 @@ async fn test_get_table_item() {
     assert_table_item(ctx, &nested_table, "u8", "u8", 2, 3).await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_view_state() {
+    let context = new_test_context(current_function_name!());
+    let requested_resources = json!([
+        {"address": "0x1", "resource_type": "0x1::account::Account"},
+        {"address": "0xA550C18", "resource_type": "0x1::account::Account"},
+        {"address": "0xA550C19", "resource_type": "0x1::account::Account"},
+    ]);
+
+    // Read the resources without proofs, and verify they match the single resource reads
+    let resp = context
+        .post("/view_state", json!({ "resources": requested_resources }))
+        .await;
+    assert!(resp["state_root_hash"].is_null());
+    let resources = resp["resources"].as_array().unwrap();
+    assert_eq!(resources.len(), 3);
+    for (resource, address) in resources.iter().zip(["0x1", "0xA550C18"]) {
+        let expected = context
+            .get(&get_account_resource(address, "0x1::account::Account"))
+            .await;
+        assert_eq!(resource["data"], expected);
+        assert!(resource["proof"].is_null());
+    }
+    assert!(resources[2]["data"].is_null());
+
+    // Read the resources with proofs, and verify the proofs against the state root hash
+    let resp = context
+        .post(
+            "/view_state",
+            json!({ "resources": requested_resources, "with_proof": true }),
+        )
+        .await;
+    let version: u64 = resp["ledger_version"].as_str().unwrap().parse().unwrap();
+    let state_root_hash =
+        aptos_api_types::HashValue::from_str(resp["state_root_hash"].as_str().unwrap()).unwrap();
+    for (resource, address) in
+        resp["resources"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .zip(["0x1", "0xA550C18", "0xA550C19"])
+    {
+        let state_key = StateKey::access_path(
+            AccessPath::resource_access_path(
+                AccountAddress::from_hex_literal(address).unwrap(),
+                AccountResource::struct_tag(),
+            )
+            .unwrap(),
+        );
+        let state_value = context
+            .db
+            .get_state_value_by_version(&state_key, version)
+            .unwrap();
+        let proof = HexEncodedBytes::from_str(resource["proof"].as_str().unwrap()).unwrap();
+        let proof: SparseMerkleProof = bcs::from_bytes(proof.inner()).unwrap();
+        proof
+            .verify(
+                state_root_hash.into(),
+                state_key.hash(),
+                state_value.as_ref(),
+            )
+            .unwrap();
+    }
+
+    // Requesting too many resources is rejected
+    let resources = vec![json!({"address": "0x1", "resource_type": "0x1::account::Account"}); 101];
+    context
+        .expect_status_code(400)
+        .post("/view_state", json!({ "resources": resources }))
+        .await;
+}
+
 fn get_account_resource(address: &str, struct_tag: &str) -> String {
     format!("/accounts/{}/resource/{}", address, struct_tag)
 }
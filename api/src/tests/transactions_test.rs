@@ -5,6 +5,7 @@
 use super::new_test_context;
 use crate::tests::new_test_context_with_config;
 use aptos_api_test_context::{assert_json, current_function_name, pretty, TestContext};
+use aptos_api_types::{HexEncodedBytes, PageCursor};
 use aptos_config::config::{GasEstimationStaticOverride, NodeConfig};
 use aptos_crypto::{
     ed25519::Ed25519PrivateKey,
@@ -27,7 +28,7 @@ use move_core_types::{
 use poem_openapi::types::ParseFromJSON;
 use rand::{distributions::Alphanumeric, thread_rng, Rng};
 use serde_json::json;
-use std::{path::PathBuf, time::Duration};
+use std::{path::PathBuf, str::FromStr, time::Duration};
 use tokio::time::sleep;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
@@ -112,6 +113,168 @@ async fn test_get_transactions_param_limit_exceeds_limit() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_transactions_with_cursor() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn]).await;
+    let all_txns = context.get("/transactions?start=0&limit=4").await;
+    assert_eq!(all_txns.as_array().unwrap().len(), 4);
+
+    // Make a request, assert we get a cursor back in the header pointing at the next page
+    let req = warp::test::request()
+        .method("GET")
+        .path("/v1/transactions?start=0&limit=2");
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 200);
+    let cursor = resp
+        .headers()
+        .get("X-Aptos-Cursor")
+        .expect("Cursor header was missing");
+    let cursor = PageCursor::from_str(cursor.to_str().unwrap()).unwrap();
+    assert_eq!(cursor.offset, 2);
+
+    // Make a request using the cursor, assert we get the next page
+    let txns = context
+        .get(&format!("/transactions?limit=2&cursor={}", cursor))
+        .await;
+    assert_eq!(
+        txns.as_array().unwrap()[..],
+        all_txns.as_array().unwrap()[2..4]
+    );
+
+    // A cursor can't be given together with a start
+    context
+        .expect_status_code(400)
+        .get(&format!("/transactions?start=0&cursor={}", cursor))
+        .await;
+
+    // A cursor from a ledger version ahead of the node is rejected
+    let cursor = PageCursor::new(cursor.ledger_version + 100, 2);
+    context
+        .expect_status_code(400)
+        .get(&format!("/transactions?cursor={}", cursor))
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_simulate_transaction_with_state_changes() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.create_account().await;
+    let payload = json!({
+        "type": "entry_function_payload",
+        "function": "0x1::aptos_account::transfer",
+        "type_arguments": [],
+        "arguments": [context.root_account().await.address().to_hex_literal(), "1"]
+    });
+
+    // The state changes are only returned on request
+    let resp = context
+        .simulate_transaction(&account, payload.clone(), 200)
+        .await;
+    assert!(resp[0].get("state_changes").is_none());
+
+    let resp = context
+        .simulate_transaction_with_query(&account, payload, "?with_state_changes=true", 200)
+        .await;
+    assert!(resp[0]["success"].as_bool().unwrap());
+    let state_changes = resp[0]["state_changes"].as_array().unwrap();
+    assert!(!state_changes.is_empty());
+
+    // The sender's account is written with its sequence number incremented
+    let account_change = state_changes
+        .iter()
+        .find(|change| {
+            change["after"][0]["address"] == account.address().to_hex_literal()
+                && change["after"][0]["data"]["type"] == "0x1::account::Account"
+        })
+        .unwrap();
+    assert!(account_change["before_bcs"].is_string());
+    assert!(account_change["after_bcs"].is_string());
+    assert_eq!(
+        account_change["before"][0]["data"]["data"]["sequence_number"],
+        "0"
+    );
+    assert_eq!(
+        account_change["after"][0]["data"]["data"]["sequence_number"],
+        "1"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_simulate_transaction_with_publish_diagnostics() {
+    let mut context = new_test_context(current_function_name!());
+    let mut account = context.create_account().await;
+    let path = PathBuf::from(std::env!("CARGO_MANIFEST_DIR")).join("src/tests/move/pack_abi");
+    let publish_txn =
+        TestContext::build_package(path, vec![("abi".to_string(), account.address())]);
+
+    let entry_function = publish_txn.clone().into_entry_function();
+    let metadata: Vec<u8> = bcs::from_bytes(&entry_function.args()[0]).unwrap();
+    let code: Vec<Vec<u8>> = bcs::from_bytes(&entry_function.args()[1]).unwrap();
+    let payload = json!({
+        "type": "entry_function_payload",
+        "function": "0x1::code::publish_package_txn",
+        "type_arguments": [],
+        "arguments": [
+            HexEncodedBytes::from(metadata).to_string(),
+            code.into_iter()
+                .map(|module| HexEncodedBytes::from(module).to_string())
+                .collect::<Vec<_>>(),
+        ]
+    });
+
+    // The diagnostics are only returned on request
+    let resp = context
+        .simulate_transaction(&account, payload.clone(), 200)
+        .await;
+    assert!(resp[0].get("publish_diagnostics").is_none());
+
+    let query = "?with_publish_diagnostics=true";
+    let resp = context
+        .simulate_transaction_with_query(&account, payload.clone(), query, 200)
+        .await;
+    assert!(resp[0]["success"].as_bool().unwrap());
+    assert_eq!(
+        resp[0]["publish_diagnostics"],
+        json!({
+            "publishable": true,
+            "verification_errors": [],
+            "incompatibilities": [],
+        })
+    );
+
+    // Publishing the same package again is a compatible upgrade
+    context.publish_package(&mut account, publish_txn).await;
+    let resp = context
+        .simulate_transaction_with_query(&account, payload, query, 200)
+        .await;
+    assert!(resp[0]["publish_diagnostics"]["publishable"]
+        .as_bool()
+        .unwrap());
+    assert!(resp[0]["publish_diagnostics"]["incompatibilities"]
+        .as_array()
+        .unwrap()
+        .is_empty());
+
+    // Other transactions have no diagnostics
+    let resp = context
+        .simulate_transaction_with_query(
+            &account,
+            json!({
+                "type": "entry_function_payload",
+                "function": "0x1::aptos_account::transfer",
+                "type_arguments": [],
+                "arguments": [account.address().to_hex_literal(), "1"]
+            }),
+            query,
+            200,
+        )
+        .await;
+    assert!(resp[0].get("publish_diagnostics").is_none());
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_transactions_output_user_transaction_with_entry_function_payload() {
     let mut context = new_test_context(current_function_name!());
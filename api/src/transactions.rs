@@ -14,7 +14,7 @@ use crate::{
         api_disabled, api_forbidden, transaction_not_found_by_hash,
         transaction_not_found_by_version, version_pruned, BadRequestError, BasicError,
         BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResult, BasicResultWith404,
-        InsufficientStorageError, InternalError,
+        InsufficientStorageError, InternalError, ServiceUnavailableError,
     },
     ApiTags,
 };
@@ -22,14 +22,17 @@ use anyhow::Context as AnyhowContext;
 use aptos_api_types::{
     verify_function_identifier, verify_module_identifier, Address, AptosError, AptosErrorCode,
     AsConverter, EncodeSubmissionRequest, GasEstimation, GasEstimationBcs, HashValue,
-    HexEncodedBytes, LedgerInfo, MoveType, PendingTransaction, SubmitTransactionRequest,
-    Transaction, TransactionData, TransactionOnChainData, TransactionsBatchSingleSubmissionFailure,
-    TransactionsBatchSubmissionResult, UserTransaction, VerifyInput, VerifyInputWithRecursion,
-    MAX_RECURSIVE_TYPES_ALLOWED, U64,
+    HexEncodedBytes, LedgerInfo, ModuleDiagnostic, MoveType, PageCursor, PendingTransaction,
+    PublishDiagnostics, SubmitTransactionRequest, SubmittedTransactionStatus, Transaction,
+    TransactionData, TransactionOnChainData, TransactionStatusUpdate,
+    TransactionsBatchSingleSubmissionFailure, TransactionsBatchSubmissionResult, UserTransaction,
+    VerifyInput, VerifyInputWithRecursion, MAX_RECURSIVE_TYPES_ALLOWED, U64,
 };
 use aptos_crypto::{hash::CryptoHash, signing_message};
+use aptos_mempool::MempoolTransactionStatus;
+use aptos_state_view::TStateView;
 use aptos_types::{
-    account_config::CoinStoreResource,
+    account_config::{CoinStoreResource, CORE_CODE_ADDRESS},
     mempool_status::MempoolStatusCode,
     transaction::{
         EntryFunction, ExecutionStatus, MultisigTransactionPayload, RawTransaction,
@@ -37,13 +40,13 @@ use aptos_types::{
     },
     vm_status::StatusCode,
 };
-use aptos_vm::{data_cache::AsMoveResolver, AptosVM};
+use aptos_vm::{data_cache::AsMoveResolver, AptosVM, PublishCompatibilityReport};
 use poem_openapi::{
     param::{Path, Query},
     payload::Json,
     ApiRequest, OpenApi,
 };
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 generate_success_response!(SubmitTransactionResponse, (202, Accepted));
 
@@ -160,15 +163,20 @@ impl TransactionsApi {
         ///
         /// If not provided, defaults to default page size
         limit: Query<Option<u16>>,
+        /// Cursor to continue the list of transactions from, instead of `start`
+        ///
+        /// Use the cursor returned in the X-Aptos-Cursor header of the previous page
+        cursor: Query<Option<PageCursor>>,
     ) -> BasicResultWith404<Vec<Transaction>> {
         fail_point_poem("endpoint_get_transactions")?;
         self.context
             .check_api_output_enabled("Get transactions", &accept_type)?;
-        let page = Page::new(
+        let page = Page::new::<BasicErrorWith404>(
             start.0.map(|v| v.0),
+            cursor.0,
             limit.0,
             self.context.max_transactions_page_size(),
-        );
+        )?;
 
         let api = self.clone();
         api_spawn_blocking(move || api.list(&accept_type, page)).await
@@ -208,6 +216,38 @@ impl TransactionsApi {
             .await
     }
 
+    /// Wait for transaction status by hash
+    ///
+    /// Waits for the status of a submitted transaction to change, instead of polling
+    /// `/transactions/by_hash/{txn_hash}`. The request returns as soon as the status of the
+    /// transaction differs from `known_status`, or with the current status once the wait times
+    /// out. Without `known_status`, the current status is returned right away.
+    ///
+    /// To follow a transaction, pass the last returned status until a final status
+    /// (`expired`, `rejected` or `committed`) is returned. Statuses other than `committed`
+    /// only reflect the mempool of this node. A 503 is returned when too many requests are
+    /// waiting already.
+    #[oai(
+        path = "/transactions/wait_status_by_hash/:txn_hash",
+        method = "get",
+        operation_id = "wait_transaction_status_by_hash",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn wait_transaction_status_by_hash(
+        &self,
+        accept_type: AcceptType,
+        /// Hash of transaction to follow
+        txn_hash: Path<HashValue>,
+        /// Last status of the transaction known by the client
+        known_status: Query<Option<SubmittedTransactionStatus>>,
+    ) -> BasicResult<TransactionStatusUpdate> {
+        fail_point_poem("endpoint_wait_transaction_status_by_hash")?;
+        self.context
+            .check_api_output_enabled("Wait transaction status by hash", &accept_type)?;
+        self.wait_transaction_status_by_hash_inner(&accept_type, txn_hash.0, known_status.0)
+            .await
+    }
+
     /// Get transaction by version
     ///
     /// Retrieves a transaction by a given version. If the version has been
@@ -261,15 +301,20 @@ impl TransactionsApi {
         ///
         /// If not provided, defaults to default page size
         limit: Query<Option<u16>>,
+        /// Cursor to continue the list of transactions from, instead of `start`
+        ///
+        /// Use the cursor returned in the X-Aptos-Cursor header of the previous page
+        cursor: Query<Option<PageCursor>>,
     ) -> BasicResultWith404<Vec<Transaction>> {
         fail_point_poem("endpoint_get_accounts_transactions")?;
         self.context
             .check_api_output_enabled("Get account transactions", &accept_type)?;
-        let page = Page::new(
+        let page = Page::new::<BasicErrorWith404>(
             start.0.map(|v| v.0),
+            cursor.0,
             limit.0,
             self.context.max_transactions_page_size(),
-        );
+        )?;
         let api = self.clone();
         api_spawn_blocking(move || api.list_by_account(&accept_type, page, address.0)).await
     }
@@ -419,6 +464,13 @@ impl TransactionsApi {
         /// If set to true, the transaction will use a higher price than the original
         /// estimate.
         estimate_prioritized_gas_unit_price: Query<Option<bool>>,
+        /// If set to true, the values of the state keys written by the transaction,
+        /// before and after it, will also be returned. Only supported with JSON
+        with_state_changes: Query<Option<bool>>,
+        /// If set to true and the transaction publishes a package with
+        /// `0x1::code::publish_package_txn`, the problems which would make publishing
+        /// fail, e.g. incompatible upgrades, will also be returned. Only supported with JSON
+        with_publish_diagnostics: Query<Option<bool>>,
         data: SubmitTransactionPost,
     ) -> SimulateTransactionResult<Vec<UserTransaction>> {
         data.verify()
@@ -435,6 +487,20 @@ impl TransactionsApi {
         }
         self.context
             .check_api_output_enabled("Simulate transaction", &accept_type)?;
+        let with_state_changes = with_state_changes.0.unwrap_or_default();
+        if with_state_changes && accept_type == AcceptType::Bcs {
+            return Err(SubmitTransactionError::bad_request_with_code_no_info(
+                "State changes are not supported for BCS simulation output",
+                AptosErrorCode::BcsNotSupported,
+            ));
+        }
+        let with_publish_diagnostics = with_publish_diagnostics.0.unwrap_or_default();
+        if with_publish_diagnostics && accept_type == AcceptType::Bcs {
+            return Err(SubmitTransactionError::bad_request_with_code_no_info(
+                "Publish diagnostics are not supported for BCS simulation output",
+                AptosErrorCode::BcsNotSupported,
+            ));
+        }
 
         let api = self.clone();
         let context = self.context.clone();
@@ -509,7 +575,13 @@ impl TransactionsApi {
                 );
             }
 
-            api.simulate(&accept_type, ledger_info, signed_transaction)
+            api.simulate(
+                &accept_type,
+                ledger_info,
+                signed_transaction,
+                with_state_changes,
+                with_publish_diagnostics,
+            )
         })
         .await
     }
@@ -620,6 +692,7 @@ impl TransactionsApi {
     fn list(&self, accept_type: &AcceptType, page: Page) -> BasicResultWith404<Vec<Transaction>> {
         let latest_ledger_info = self.context.get_latest_ledger_info()?;
         let ledger_version = latest_ledger_info.version();
+        page.verify_cursor(&latest_ledger_info)?;
 
         let limit = page.limit(&latest_ledger_info)?;
         let start_version = page.compute_start(limit, ledger_version, &latest_ledger_info)?;
@@ -634,6 +707,8 @@ impl TransactionsApi {
                     &latest_ledger_info,
                 )
             })?;
+        // The next page starts right after the last transaction of this one
+        let next_cursor = PageCursor::new(ledger_version, start_version + data.len() as u64);
 
         match accept_type {
             AcceptType::Json => {
@@ -654,6 +729,7 @@ impl TransactionsApi {
                 BasicResponse::try_from_bcs((data, &latest_ledger_info, BasicResponseStatus::Ok))
             },
         }
+        .map(|v| v.with_page_cursor(Some(next_cursor)))
     }
 
     async fn get_transaction_by_hash_inner(
@@ -685,6 +761,88 @@ impl TransactionsApi {
             .await
     }
 
+    async fn wait_transaction_status_by_hash_inner(
+        &self,
+        accept_type: &AcceptType,
+        hash: HashValue,
+        known_status: Option<SubmittedTransactionStatus>,
+    ) -> BasicResult<TransactionStatusUpdate> {
+        let context = self.context.clone();
+        let ledger_info = api_spawn_blocking(move || context.get_latest_ledger_info()).await?;
+
+        // Wait before reading the current status, so that no change is missed in between
+        let (mempool_status, receiver) = self
+            .context
+            .subscribe_transaction_status(hash.into())
+            .await
+            .context("Failed to subscribe to the mempool transaction status")
+            .map_err(|err| {
+                BasicError::internal_with_code(err, AptosErrorCode::InternalError, &ledger_info)
+            })?;
+        let mut status = if self.is_committed(hash, &ledger_info).await? {
+            SubmittedTransactionStatus::Committed
+        } else {
+            mempool_status.map_or(SubmittedTransactionStatus::NotFound, to_submitted_status)
+        };
+
+        if known_status == Some(status) && !status.is_final() {
+            let timeout = Duration::from_millis(
+                self.context
+                    .node_config
+                    .api
+                    .wait_transaction_status_timeout_ms,
+            );
+            let receiver = receiver.ok_or_else(|| {
+                BasicError::service_unavailable_with_code(
+                    "Too many requests are waiting for a transaction status change",
+                    AptosErrorCode::RequestQuotaExceeded,
+                    &ledger_info,
+                )
+            })?;
+            match tokio::time::timeout(timeout, receiver).await {
+                Ok(Ok(new_status)) => status = to_submitted_status(new_status),
+                // The transaction may have been committed by another node, or replaced in
+                // mempool, without a status change of the waited transaction
+                _ => {
+                    if self.is_committed(hash, &ledger_info).await? {
+                        status = SubmittedTransactionStatus::Committed;
+                    }
+                },
+            }
+        }
+
+        BasicResponse::try_from_rust_value((
+            TransactionStatusUpdate { hash, status },
+            &ledger_info,
+            BasicResponseStatus::Ok,
+            accept_type,
+        ))
+    }
+
+    /// Returns true if the transaction is committed, looking up the latest ledger version since
+    /// the transaction may be committed after the request started
+    async fn is_committed(
+        &self,
+        hash: HashValue,
+        ledger_info: &LedgerInfo,
+    ) -> Result<bool, BasicError> {
+        let context = self.context.clone();
+        let committed = tokio::task::spawn_blocking(move || {
+            let version = context
+                .get_latest_ledger_info_with_signatures()?
+                .ledger_info()
+                .version();
+            context.get_transaction_by_hash(hash.into(), version)
+        })
+        .await
+        .context("Failed to join task to read transaction by hash")
+        .and_then(|result| result.context("Failed to read transaction by hash from DB"))
+        .map_err(|err| {
+            BasicError::internal_with_code(err, AptosErrorCode::InternalError, ledger_info)
+        })?;
+        Ok(committed.is_some())
+    }
+
     fn get_transaction_by_version_inner(
         &self,
         accept_type: &AcceptType,
@@ -709,7 +867,11 @@ impl TransactionsApi {
             GetByVersionResponse::VersionTooNew => {
                 Err(transaction_not_found_by_version(version.0, &ledger_info))
             },
-            GetByVersionResponse::VersionTooOld => Err(version_pruned(version.0, &ledger_info)),
+            GetByVersionResponse::VersionTooOld => Err(version_pruned(
+                version.0,
+                self.context.archival_node_url(),
+                &ledger_info,
+            )),
         }
     }
 
@@ -820,6 +982,7 @@ impl TransactionsApi {
         account.get_account_resource()?;
 
         let latest_ledger_info = account.latest_ledger_info;
+        page.verify_cursor(&latest_ledger_info)?;
         // TODO: Return more specific errors from within this function.
         let data = self.context.get_account_transactions(
             address.into(),
@@ -828,6 +991,29 @@ impl TransactionsApi {
             latest_ledger_info.version(),
             &latest_ledger_info,
         )?;
+
+        // The next page starts right after the sequence number of the last transaction of
+        // this one, or where this one started if it's empty
+        let next_sequence_number = match data.last() {
+            Some(txn) => Some(
+                txn.transaction
+                    .try_as_signed_user_txn()
+                    .context("Account transaction is not a user transaction")
+                    .map_err(|err| {
+                        BasicErrorWith404::internal_with_code(
+                            err,
+                            AptosErrorCode::InternalError,
+                            &latest_ledger_info,
+                        )
+                    })?
+                    .sequence_number()
+                    + 1,
+            ),
+            None => page.start_option(),
+        };
+        let next_cursor = next_sequence_number
+            .map(|sequence_number| PageCursor::new(latest_ledger_info.version(), sequence_number));
+
         match accept_type {
             AcceptType::Json => BasicResponse::try_from_json((
                 self.context
@@ -839,6 +1025,7 @@ impl TransactionsApi {
                 BasicResponse::try_from_bcs((data, &latest_ledger_info, BasicResponseStatus::Ok))
             },
         }
+        .map(|v| v.with_page_cursor(next_cursor))
     }
 
     /// Parses a single signed transaction
@@ -902,6 +1089,15 @@ impl TransactionsApi {
                         }
                     },
 
+                    TransactionPayload::Batched(entry_functions) => {
+                        for entry_function in entry_functions {
+                            TransactionsApi::validate_entry_function_payload_format(
+                                ledger_info,
+                                entry_function,
+                            )?;
+                        }
+                    },
+
                     // Deprecated. Will be removed in the future.
                     TransactionPayload::ModuleBundle(_) => {},
                 }
@@ -1020,10 +1216,13 @@ impl TransactionsApi {
         match mempool_status.code {
             MempoolStatusCode::Accepted => Ok(()),
             MempoolStatusCode::MempoolIsFull | MempoolStatusCode::TooManyTransactions => {
-                Err(AptosError::new_with_error_code(
-                    &mempool_status.message,
-                    AptosErrorCode::MempoolIsFull,
-                ))
+                Err(AptosError {
+                    retry_after_secs: mempool_status.retry_after_secs,
+                    ..AptosError::new_with_error_code(
+                        &mempool_status.message,
+                        AptosErrorCode::MempoolIsFull,
+                    )
+                })
             },
             MempoolStatusCode::VmError => {
                 if let Some(status) = vm_status_opt {
@@ -1052,6 +1251,14 @@ impl TransactionsApi {
                 mempool_status.message,
                 AptosErrorCode::InvalidTransactionUpdate,
             )),
+            MempoolStatusCode::InsufficientGasPriceBump => Err(AptosError::new_with_error_code(
+                mempool_status.message,
+                AptosErrorCode::InsufficientGasPriceBump,
+            )),
+            MempoolStatusCode::RejectedByFilter => Err(AptosError::new_with_error_code(
+                mempool_status.message,
+                AptosErrorCode::TransactionRejectedByFilter,
+            )),
             MempoolStatusCode::UnknownStatus => Err(AptosError::new_with_error_code(
                 format!("Transaction was rejected with status {}", mempool_status,),
                 AptosErrorCode::InternalError,
@@ -1178,6 +1385,8 @@ impl TransactionsApi {
         accept_type: &AcceptType,
         ledger_info: LedgerInfo,
         txn: SignedTransaction,
+        with_state_changes: bool,
+        with_publish_diagnostics: bool,
     ) -> SimulateTransactionResult<Vec<UserTransaction>> {
         // Transactions shouldn't have a valid signature or this could be used to attack
         if txn.signature_is_valid() {
@@ -1190,9 +1399,63 @@ impl TransactionsApi {
 
         // Simulate transaction
         let state_view = self.context.latest_state_view_poem(&ledger_info)?;
-        let (_, output) = AptosVM::simulate_signed_transaction(&txn, &state_view);
+        let (_, output) = match state_view.version {
+            // Outputs are cached per state checkpoint, which the state view is at.
+            Some(checkpoint_version) => AptosVM::simulate_signed_transaction_at_version(
+                &txn,
+                &state_view,
+                checkpoint_version,
+            ),
+            None => AptosVM::simulate_signed_transaction(&txn, &state_view),
+        };
         let version = ledger_info.version();
 
+        // Render the written keys with their values before and after the transaction
+        let mut state_changes = if with_state_changes {
+            let resolver = state_view.as_move_resolver();
+            let converter = resolver.as_converter(self.context.db.clone());
+            let state_changes = output
+                .write_set()
+                .iter()
+                .map(|(state_key, op)| {
+                    let before = state_view.get_state_value_bytes(state_key)?;
+                    converter.try_into_state_key_change(
+                        state_key.clone(),
+                        before.as_deref(),
+                        op.clone(),
+                    )
+                })
+                .collect::<anyhow::Result<Vec<_>>>()
+                .context("Failed to convert the simulated write set")
+                .map_err(|err| {
+                    SubmitTransactionError::internal_with_code(
+                        err,
+                        AptosErrorCode::InternalError,
+                        &ledger_info,
+                    )
+                })?;
+            Some(state_changes)
+        } else {
+            None
+        };
+
+        // Dry run the publishing of the package, if any, to explain why it would fail
+        let mut publish_diagnostics = match published_code(txn.payload()) {
+            Some(code) if with_publish_diagnostics => {
+                let report = AptosVM::check_publish_compatibility(&state_view, code)
+                    .context("Failed to check the published package")
+                    .map_err(|err| {
+                        SubmitTransactionError::bad_request_with_code(
+                            err,
+                            AptosErrorCode::InvalidInput,
+                            &ledger_info,
+                        )
+                    })?;
+                Some(render_publish_report(report))
+            },
+            _ => None,
+        };
+
         // Ensure that all known statuses return their values in the output (even if they aren't supposed to)
         let exe_status = match output.status().clone() {
             TransactionStatus::Keep(exec_status) => exec_status,
@@ -1232,7 +1495,11 @@ impl TransactionsApi {
                 let mut user_transactions = Vec::new();
                 for transaction in transactions.into_iter() {
                     match transaction {
-                        Transaction::UserTransaction(user_txn) => user_transactions.push(*user_txn),
+                        Transaction::UserTransaction(mut user_txn) => {
+                            user_txn.state_changes = state_changes.take();
+                            user_txn.publish_diagnostics = publish_diagnostics.take();
+                            user_transactions.push(*user_txn)
+                        },
                         _ => {
                             return Err(SubmitTransactionError::internal_with_code(
                                 "Simulation transaction resulted in a non-UserTransaction",
@@ -1339,3 +1606,63 @@ enum GetByVersionResponse {
     VersionTooOld,
     Found(TransactionData),
 }
+
+fn to_submitted_status(status: MempoolTransactionStatus) -> SubmittedTransactionStatus {
+    match status {
+        MempoolTransactionStatus::Accepted => SubmittedTransactionStatus::Accepted,
+        MempoolTransactionStatus::Parked => SubmittedTransactionStatus::Parked,
+        MempoolTransactionStatus::Broadcast => SubmittedTransactionStatus::Broadcast,
+        MempoolTransactionStatus::Expired => SubmittedTransactionStatus::Expired,
+        MempoolTransactionStatus::Rejected => SubmittedTransactionStatus::Rejected,
+        MempoolTransactionStatus::Committed => SubmittedTransactionStatus::Committed,
+    }
+}
+
+/// Returns the modules published by `0x1::code::publish_package_txn`, if that's what the
+/// payload calls
+fn published_code(payload: &TransactionPayload) -> Option<Vec<Vec<u8>>> {
+    match payload {
+        TransactionPayload::EntryFunction(entry_function)
+            if *entry_function.module().address() == CORE_CODE_ADDRESS
+                && entry_function.module().name().as_str() == "code"
+                && entry_function.function().as_str() == "publish_package_txn" =>
+        {
+            bcs::from_bytes(entry_function.args().get(1)?).ok()
+        },
+        _ => None,
+    }
+}
+
+fn render_publish_report(report: PublishCompatibilityReport) -> PublishDiagnostics {
+    let publishable = report.is_publishable();
+    let verification_errors = report
+        .verification_errors
+        .into_iter()
+        .map(|error| ModuleDiagnostic {
+            module: error.module_id.into(),
+            message: match error.message {
+                Some(message) => format!("{:?}: {}", error.status_code, message),
+                None => format!("{:?}", error.status_code),
+            },
+        })
+        .collect();
+    let incompatibilities = report
+        .upgrades
+        .into_iter()
+        .flat_map(|upgrade| {
+            let module_id = upgrade.module_id;
+            upgrade
+                .incompatibilities
+                .into_iter()
+                .map(move |incompatibility| ModuleDiagnostic {
+                    module: module_id.clone().into(),
+                    message: incompatibility.to_string(),
+                })
+        })
+        .collect();
+    PublishDiagnostics {
+        publishable,
+        verification_errors,
+        incompatibilities,
+    }
+}
@@ -11,7 +11,12 @@ use crate::{
     ApiTags, Context,
 };
 use aptos_api_types::{AptosErrorCode, AsConverter, MoveValue, ViewRequest, U64};
-use aptos_vm::{data_cache::AsMoveResolver, AptosVM};
+use aptos_types::vm_status::StatusCode;
+use aptos_vm::{
+    data_cache::AsMoveResolver,
+    view_function::{ViewFunctionError, ViewFunctionLimits},
+    AptosVM,
+};
 use move_core_types::language_storage::TypeTag;
 use poem_openapi::{param::Query, payload::Json, OpenApi};
 use std::sync::Arc;
@@ -80,16 +85,49 @@ impl ViewFunctionApi {
                     )
                 })?;
 
+            let view_function_config = &context.node_config.api.view_function;
             let return_vals = AptosVM::execute_view_function(
                 &state_view,
                 entry_func.module().clone(),
                 entry_func.function().to_owned(),
                 entry_func.ty_args().to_owned(),
                 entry_func.args().to_owned(),
-                context.node_config.api.max_gas_view_function,
+                ViewFunctionLimits {
+                    max_gas: view_function_config.max_gas,
+                    max_stack_depth: view_function_config.max_stack_depth,
+                    max_returned_bytes: view_function_config.max_returned_bytes,
+                },
             )
-            .map_err(|err| {
-                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            .map_err(|err| match err {
+                ViewFunctionError::OutOfGas { .. } => {
+                    BasicErrorWith404::bad_request_with_vm_status(
+                        err,
+                        AptosErrorCode::ViewFunctionLimitExceeded,
+                        StatusCode::OUT_OF_GAS,
+                        &ledger_info,
+                    )
+                },
+                ViewFunctionError::MaxStackDepthExceeded { .. } => {
+                    BasicErrorWith404::bad_request_with_vm_status(
+                        err,
+                        AptosErrorCode::ViewFunctionLimitExceeded,
+                        StatusCode::CALL_STACK_OVERFLOW,
+                        &ledger_info,
+                    )
+                },
+                ViewFunctionError::MaxReturnedBytesExceeded { .. } => {
+                    BasicErrorWith404::bad_request_with_code(
+                        err,
+                        AptosErrorCode::ViewFunctionLimitExceeded,
+                        &ledger_info,
+                    )
+                },
+                ViewFunctionError::Execution(err) => {
+                    BasicErrorWith404::bad_request_with_code_no_info(
+                        err,
+                        AptosErrorCode::InvalidInput,
+                    )
+                },
             })?;
             match accept_type {
                 AcceptType::Bcs => BasicResponse::try_from_bcs((
@@ -11,7 +11,8 @@ use aptos_cached_packages::aptos_stdlib;
 use aptos_config::{
     config::{
         NodeConfig, RocksdbConfigs, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
-        DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
+        DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_LEDGER_TIERING_CONFIG,
+        NO_OP_STORAGE_PRUNER_CONFIG,
     },
     keys::ConfigKey,
 };
@@ -39,6 +40,7 @@ use aptos_types::{
     block_metadata::BlockMetadata,
     chain_id::ChainId,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    on_chain_config::BlockExecutionLimit,
     transaction::{
         signature_verified_transaction::into_signature_verified_block, Transaction,
         TransactionPayload, TransactionStatus,
@@ -125,8 +127,10 @@ pub fn new_test_context(
                 StorageDirPaths::from_path(&tmp_dir),
                 false,                       /* readonly */
                 NO_OP_STORAGE_PRUNER_CONFIG, /* pruner */
+                &NO_OP_LEDGER_TIERING_CONFIG,
                 RocksdbConfigs::default(),
                 false, /* indexer */
+                false, /* event type index */
                 BUFFERED_STATE_TARGET_ITEMS,
                 DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
             )
@@ -144,12 +148,14 @@ pub fn new_test_context(
         db.clone(),
         mempool.ac_client.clone(),
         node_config.clone(),
+        mempool.back_pressure_status.clone(),
     );
 
     // Configure the testing depending on which API version we're testing.
     let runtime_handle = tokio::runtime::Handle::current();
-    let poem_address = attach_poem_to_runtime(&runtime_handle, context.clone(), &node_config, true)
-        .expect("Failed to attach poem to runtime");
+    let poem_address =
+        attach_poem_to_runtime(&runtime_handle, context.clone(), &node_config, true, None)
+            .expect("Failed to attach poem to runtime");
     let api_specific_config = ApiSpecificConfig::V1(poem_address);
 
     TestContext::new(
@@ -613,7 +619,7 @@ impl TestContext {
             .execute_block(
                 (metadata.id(), into_signature_verified_block(txns.clone())).into(),
                 parent_id,
-                None,
+                BlockExecutionLimit::no_limit(),
             )
             .unwrap();
         let mut compute_status = result.compute_status().clone();
@@ -839,6 +845,17 @@ impl TestContext {
         sender: &LocalAccount,
         payload: Value,
         status_code: u16,
+    ) -> Value {
+        self.simulate_transaction_with_query(sender, payload, "", status_code)
+            .await
+    }
+
+    pub async fn simulate_transaction_with_query(
+        &mut self,
+        sender: &LocalAccount,
+        payload: Value,
+        query: &str,
+        status_code: u16,
     ) -> Value {
         let mut request = json!({
             "sender": sender.address(),
@@ -872,7 +889,7 @@ impl TestContext {
         });
 
         self.expect_status_code(status_code)
-            .post("/transactions/simulate", request)
+            .post(&format!("/transactions/simulate{}", query), request)
             .await
     }
 
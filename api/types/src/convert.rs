@@ -4,10 +4,10 @@
 
 use crate::{
     transaction::{
-        DecodedTableData, DeleteModule, DeleteResource, DeleteTableItem, DeletedTableData,
-        ModuleBundlePayload, MultisigPayload, MultisigTransactionPayload,
-        StateCheckpointTransaction, UserTransactionRequestInner, WriteModule, WriteResource,
-        WriteTableItem,
+        BatchedPayload, DecodedTableData, DeleteModule, DeleteResource, DeleteTableItem,
+        DeletedTableData, ModuleBundlePayload, MultisigPayload, MultisigTransactionPayload,
+        StateCheckpointTransaction, StateKeyChange, UserTransactionRequestInner, WriteModule,
+        WriteResource, WriteTableItem,
     },
     view::ViewRequest,
     Bytecode, DirectWriteSet, EntryFunctionId, EntryFunctionPayload, Event, HexEncodedBytes,
@@ -145,6 +145,14 @@ impl<'a, R: MoveResolver + ?Sized> MoveConverter<'a, R> {
                 (info, payload, events).into()
             },
             BlockMetadata(txn) => (&txn, info, events).into(),
+            // The randomness seed is not exposed through the API, the extended block metadata is
+            // served like the plain block metadata.
+            BlockMetadataExt(txn) => (
+                &aptos_types::block_metadata::BlockMetadata::from(txn),
+                info,
+                events,
+            )
+                .into(),
             StateCheckpoint(_) => {
                 Transaction::StateCheckpointTransaction(StateCheckpointTransaction {
                     info,
@@ -190,31 +198,14 @@ impl<'a, R: MoveResolver + ?Sized> MoveConverter<'a, R> {
         let ret = match payload {
             Script(s) => TransactionPayload::ScriptPayload(s.try_into()?),
             EntryFunction(fun) => {
-                let (module, function, ty_args, args) = fun.into_inner();
-                let func_args = self
-                    .inner
-                    .view_function_arguments(&module, &function, &ty_args, &args);
-
-                let json_args = match func_args {
-                    Ok(values) => values
-                        .into_iter()
-                        .map(|v| MoveValue::try_from(v)?.json())
-                        .collect::<Result<_>>()?,
-                    Err(_e) => args
-                        .into_iter()
-                        .map(|arg| HexEncodedBytes::from(arg).json())
-                        .collect::<Result<_>>()?,
-                };
-
-                TransactionPayload::EntryFunctionPayload(EntryFunctionPayload {
-                    arguments: json_args,
-                    function: EntryFunctionId {
-                        module: module.into(),
-                        name: function.into(),
-                    },
-                    type_arguments: ty_args.into_iter().map(|arg| arg.into()).collect(),
-                })
+                TransactionPayload::EntryFunctionPayload(self.try_into_entry_function_payload(fun)?)
             },
+            Batched(entry_functions) => TransactionPayload::BatchedPayload(BatchedPayload {
+                entry_functions: entry_functions
+                    .into_iter()
+                    .map(|fun| self.try_into_entry_function_payload(fun))
+                    .collect::<Result<_>>()?,
+            }),
             Multisig(multisig) => {
                 let transaction_payload = if let Some(payload) = multisig.transaction_payload {
                     match payload {
@@ -271,6 +262,36 @@ impl<'a, R: MoveResolver + ?Sized> MoveConverter<'a, R> {
         Ok(ret)
     }
 
+    fn try_into_entry_function_payload(
+        &self,
+        fun: aptos_types::transaction::EntryFunction,
+    ) -> Result<EntryFunctionPayload> {
+        let (module, function, ty_args, args) = fun.into_inner();
+        let func_args = self
+            .inner
+            .view_function_arguments(&module, &function, &ty_args, &args);
+
+        let json_args = match func_args {
+            Ok(values) => values
+                .into_iter()
+                .map(|v| MoveValue::try_from(v)?.json())
+                .collect::<Result<_>>()?,
+            Err(_e) => args
+                .into_iter()
+                .map(|arg| HexEncodedBytes::from(arg).json())
+                .collect::<Result<_>>()?,
+        };
+
+        Ok(EntryFunctionPayload {
+            arguments: json_args,
+            function: EntryFunctionId {
+                module: module.into(),
+                name: function.into(),
+            },
+            type_arguments: ty_args.into_iter().map(|arg| arg.into()).collect(),
+        })
+    }
+
     pub fn try_into_write_set_payload(
         &self,
         payload: aptos_types::transaction::WriteSetPayload,
@@ -327,6 +348,31 @@ impl<'a, R: MoveResolver + ?Sized> MoveConverter<'a, R> {
         }
     }
 
+    /// Converts a write of a simulated transaction, given the value of the key before it
+    pub fn try_into_state_key_change(
+        &self,
+        state_key: StateKey,
+        before: Option<&[u8]>,
+        op: WriteOp,
+    ) -> Result<StateKeyChange> {
+        let state_key_hash = state_key.hash().to_hex_literal();
+        let before_changes = match before {
+            // The previous value is rendered as the change which would have written it
+            Some(bytes) => self.try_into_write_set_changes(
+                state_key.clone(),
+                WriteOp::Modification(bytes.to_vec().into()),
+            )?,
+            None => vec![],
+        };
+        Ok(StateKeyChange {
+            state_key_hash,
+            before_bcs: before.map(|bytes| bytes.to_vec().into()),
+            after_bcs: op.bytes().map(|bytes| bytes.to_vec().into()),
+            before: before_changes,
+            after: self.try_into_write_set_changes(state_key, op)?,
+        })
+    }
+
     pub fn try_access_path_into_write_set_changes(
         &self,
         state_key_hash: String,
@@ -582,40 +628,17 @@ impl<'a, R: MoveResolver + ?Sized> MoveConverter<'a, R> {
 
         let ret = match payload {
             TransactionPayload::EntryFunctionPayload(entry_func_payload) => {
-                let EntryFunctionPayload {
-                    function,
-                    type_arguments,
-                    arguments,
-                } = entry_func_payload;
-
-                let module = function.module.clone();
-                let code = self.inner.get_module(&module.clone().into())? as Rc<dyn Bytecode>;
-                let func = code
-                    .find_entry_function(function.name.0.as_ident_str())
-                    .ok_or_else(|| format_err!("could not find entry function by {}", function))?;
-                ensure!(
-                    func.generic_type_params.len() == type_arguments.len(),
-                    "expect {} type arguments for entry function {}, but got {}",
-                    func.generic_type_params.len(),
-                    function,
-                    type_arguments.len()
-                );
-                let args = self
-                    .try_into_vm_values(func, arguments)?
-                    .iter()
-                    .map(bcs::to_bytes)
-                    .collect::<Result<_, bcs::Error>>()?;
-
-                Target::EntryFunction(EntryFunction::new(
-                    module.into(),
-                    function.name.into(),
-                    type_arguments
-                        .into_iter()
-                        .map(|v| v.try_into())
-                        .collect::<Result<_>>()?,
-                    args,
-                ))
+                Target::EntryFunction(self.try_into_aptos_core_entry_function(entry_func_payload)?)
             },
+            TransactionPayload::BatchedPayload(batched) => Target::Batched(
+                batched
+                    .entry_functions
+                    .into_iter()
+                    .map(|entry_func_payload| {
+                        self.try_into_aptos_core_entry_function(entry_func_payload)
+                    })
+                    .collect::<Result<_>>()?,
+            ),
             TransactionPayload::ScriptPayload(script) => {
                 let ScriptPayload {
                     code,
@@ -710,6 +733,45 @@ impl<'a, R: MoveResolver + ?Sized> MoveConverter<'a, R> {
         Ok(ret)
     }
 
+    fn try_into_aptos_core_entry_function(
+        &self,
+        entry_func_payload: EntryFunctionPayload,
+    ) -> Result<EntryFunction> {
+        let EntryFunctionPayload {
+            function,
+            type_arguments,
+            arguments,
+        } = entry_func_payload;
+
+        let module = function.module.clone();
+        let code = self.inner.get_module(&module.clone().into())? as Rc<dyn Bytecode>;
+        let func = code
+            .find_entry_function(function.name.0.as_ident_str())
+            .ok_or_else(|| format_err!("could not find entry function by {}", function))?;
+        ensure!(
+            func.generic_type_params.len() == type_arguments.len(),
+            "expect {} type arguments for entry function {}, but got {}",
+            func.generic_type_params.len(),
+            function,
+            type_arguments.len()
+        );
+        let args = self
+            .try_into_vm_values(func, arguments)?
+            .iter()
+            .map(bcs::to_bytes)
+            .collect::<Result<_, bcs::Error>>()?;
+
+        Ok(EntryFunction::new(
+            module.into(),
+            function.name.into(),
+            type_arguments
+                .into_iter()
+                .map(|v| v.try_into())
+                .collect::<Result<_>>()?,
+            args,
+        ))
+    }
+
     pub fn try_into_vm_values(
         &self,
         func: MoveFunction,
@@ -16,6 +16,12 @@ pub struct AptosError {
     pub error_code: AptosErrorCode,
     /// A code providing VM error details when submitting transactions to the VM
     pub vm_error_code: Option<u64>,
+    /// The number of seconds after which the request may succeed, when the node is overloaded
+    pub retry_after_secs: Option<u64>,
+    /// The oldest ledger version at which the requested data can be queried, when it's pruned
+    pub oldest_queryable_version: Option<u64>,
+    /// An archival node keeping the data pruned by this node, if one is configured
+    pub archival_node_url: Option<String>,
 }
 
 impl std::fmt::Display for AptosError {
@@ -35,6 +41,9 @@ impl AptosError {
             message: format!("{:#}", error),
             error_code,
             vm_error_code: None,
+            retry_after_secs: None,
+            oldest_queryable_version: None,
+            archival_node_url: None,
         }
     }
 
@@ -47,6 +56,9 @@ impl AptosError {
             message: format!("{:#}", error),
             error_code,
             vm_error_code: Some(vm_error_code as u64),
+            retry_after_secs: None,
+            oldest_queryable_version: None,
+            archival_node_url: None,
         }
     }
 }
@@ -86,9 +98,13 @@ pub enum AptosErrorCode {
     VersionPruned = 200,
     /// Block is fully or partially pruned
     BlockPruned = 201,
+    /// State (i.e., resources, modules and table items) at the ledger version is pruned
+    StatePruned = 202,
 
     /// The API's inputs were invalid
     InvalidInput = 300,
+    /// The view function exceeded one of the execution limits configured on the node
+    ViewFunctionLimitExceeded = 301,
 
     /// The transaction was an invalid update to an already submitted transaction.
     InvalidTransactionUpdate = 401,
@@ -96,11 +112,18 @@ pub enum AptosErrorCode {
     SequenceNumberTooOld = 402,
     /// The submitted transaction failed VM checks.
     VmError = 403,
+    /// The transaction replaces an already submitted transaction without increasing its gas
+    /// unit price enough.
+    InsufficientGasPriceBump = 404,
+    /// The transaction was rejected by the admission rules of the node's mempool.
+    TransactionRejectedByFilter = 405,
 
     /// Health check failed.
     HealthCheckFailed = 500,
     /// The mempool is full, no new transactions can be submitted.
     MempoolIsFull = 501,
+    /// The client exceeded its request quota, it may retry after `retry_after_secs`.
+    RequestQuotaExceeded = 502,
 
     /// Internal server error
     InternalError = 600,
@@ -19,3 +19,9 @@ pub const X_APTOS_LEDGER_TIMESTAMP: &str = "X-Aptos-Ledger-TimestampUsec";
 pub const X_APTOS_CURSOR: &str = "X-Aptos-Cursor";
 /// Provided by the client to identify what client it is.
 pub const X_APTOS_CLIENT: &str = "x-aptos-client";
+/// Provided by the client to use the request quota of its API key.
+pub const X_APTOS_API_KEY: &str = "x-aptos-api-key";
+/// Maximum number of requests of the kind of the request that a client can burst
+pub const X_APTOS_QUOTA_LIMIT: &str = "x-aptos-quota-limit";
+/// Number of requests of the kind of the request that the client can still send now
+pub const X_APTOS_QUOTA_REMAINING: &str = "x-aptos-quota-remaining";
@@ -26,6 +26,12 @@ pub struct IndexResponse {
     /// Git hash of the build of the API endpoint.  Can be used to determine the exact
     /// software version used by the API endpoint.
     pub git_hash: Option<String>,
+    /// The oldest versions at which each kind of data can be queried on this node
+    #[serde(default)]
+    pub oldest_queryable_version: Option<OldestQueryableVersion>,
+    /// An archival node keeping the data pruned by this node, if one is configured
+    #[serde(default)]
+    pub archival_node_url: Option<String>,
 }
 
 impl IndexResponse {
@@ -33,6 +39,8 @@ impl IndexResponse {
         ledger_info: LedgerInfo,
         node_role: RoleType,
         git_hash: Option<String>,
+        oldest_queryable_version: Option<OldestQueryableVersion>,
+        archival_node_url: Option<String>,
     ) -> IndexResponse {
         Self {
             chain_id: ledger_info.chain_id,
@@ -44,10 +52,21 @@ impl IndexResponse {
             block_height: ledger_info.block_height,
             node_role,
             git_hash,
+            oldest_queryable_version,
+            archival_node_url,
         }
     }
 }
 
+/// The oldest ledger versions at which the data can be queried, older data being pruned
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, PoemObject, Serialize)]
+pub struct OldestQueryableVersion {
+    /// Oldest version of the transactions, events and blocks
+    pub ledger: U64,
+    /// Oldest version of the state (i.e., resources, modules and table items)
+    pub state: U64,
+}
+
 /// The struct holding all data returned to the client by the
 /// index endpoint (i.e., GET "/").  This is just for the BCS response and
 /// cannot change
@@ -31,34 +31,38 @@ pub use convert::{new_vm_utf8_string, AsConverter, ExplainVMStatus, MoveConverte
 pub use error::{AptosError, AptosErrorCode};
 pub use hash::HashValue;
 pub use headers::*;
-pub use index::{IndexResponse, IndexResponseBcs};
+pub use index::{IndexResponse, IndexResponseBcs, OldestQueryableVersion};
 pub use ledger_info::LedgerInfo;
 pub use move_types::{
-    verify_field_identifier, verify_function_identifier, verify_module_identifier, EntryFunctionId,
-    HexEncodedBytes, MoveAbility, MoveFunction, MoveFunctionGenericTypeParam,
-    MoveFunctionVisibility, MoveModule, MoveModuleBytecode, MoveModuleId, MoveResource,
-    MoveScriptBytecode, MoveStruct, MoveStructField, MoveStructTag, MoveType, MoveValue,
-    ResourceGroup, MAX_RECURSIVE_TYPES_ALLOWED, U128, U256, U64,
+    verify_field_identifier, verify_function_identifier, verify_module_identifier, DelayedField,
+    DelayedFieldKind, EntryFunctionId, HexEncodedBytes, MoveAbility, MoveFunction,
+    MoveFunctionGenericTypeParam, MoveFunctionVisibility, MoveModule, MoveModuleBytecode,
+    MoveModuleId, MoveResource, MoveScriptBytecode, MoveStruct, MoveStructField, MoveStructTag,
+    MoveType, MoveValue, ResourceGroup, MAX_RECURSIVE_TYPES_ALLOWED, U128, U256, U64,
 };
 use serde::{Deserialize, Deserializer};
-pub use state::RawStateValueRequest;
+pub use state::{
+    RawStateValueRequest, ViewStateRequest, ViewStateResource, ViewStateResourceRequest,
+    ViewStateResponse,
+};
 use std::str::FromStr;
 pub use table::{RawTableItemRequest, TableItemRequest};
 pub use transaction::{
-    AccountSignature, BlockMetadataTransaction, DeleteModule, DeleteResource, DeleteTableItem,
-    DirectWriteSet, Ed25519Signature, EncodeSubmissionRequest, EntryFunctionPayload, Event,
-    FeePayerSignature, GasEstimation, GasEstimationBcs, GenesisPayload, GenesisTransaction,
-    ModuleBundlePayload, MultiAgentSignature, MultiEd25519Signature, MultiKeySignature,
-    MultisigPayload, MultisigTransactionPayload, PendingTransaction, PublicKey, ScriptPayload,
-    ScriptWriteSet, Signature, SingleKeySignature, SubmitTransactionRequest, Transaction,
-    TransactionData, TransactionId, TransactionInfo, TransactionOnChainData, TransactionPayload,
-    TransactionSignature, TransactionSigningMessage, TransactionsBatchSingleSubmissionFailure,
-    TransactionsBatchSubmissionResult, UserCreateSigningMessageRequest, UserTransaction,
-    UserTransactionRequest, VersionedEvent, WriteModule, WriteResource, WriteSet, WriteSetChange,
-    WriteSetPayload, WriteTableItem,
+    AccountSignature, BatchedPayload, BlockMetadataTransaction, DeleteModule, DeleteResource,
+    DeleteTableItem, DirectWriteSet, Ed25519Signature, EncodeSubmissionRequest,
+    EntryFunctionPayload, Event, FeePayerSignature, GasEstimation, GasEstimationBcs,
+    GenesisPayload, GenesisTransaction, ModuleBundlePayload, ModuleDiagnostic, MultiAgentSignature,
+    MultiEd25519Signature, MultiKeySignature, MultisigPayload, MultisigTransactionPayload,
+    PendingTransaction, PublicKey, PublishDiagnostics, ScriptPayload, ScriptWriteSet, Signature,
+    SingleKeySignature, StateKeyChange, SubmitTransactionRequest, SubmittedTransactionStatus,
+    Transaction, TransactionData, TransactionId, TransactionInfo, TransactionOnChainData,
+    TransactionPayload, TransactionSignature, TransactionSigningMessage, TransactionStatusUpdate,
+    TransactionsBatchSingleSubmissionFailure, TransactionsBatchSubmissionResult,
+    UserCreateSigningMessageRequest, UserTransaction, UserTransactionRequest, VersionedEvent,
+    WriteModule, WriteResource, WriteSet, WriteSetChange, WriteSetPayload, WriteTableItem,
 };
 pub use view::ViewRequest;
-pub use wrappers::{EventGuid, IdentifierWrapper, StateKeyWrapper};
+pub use wrappers::{EventGuid, IdentifierWrapper, PageCursor, StateKeyWrapper};
 
 pub fn deserialize_from_string<'de, D, T>(deserializer: D) -> Result<T, D::Error>
 where
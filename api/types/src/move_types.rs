@@ -39,19 +39,108 @@ pub struct MoveResource {
     #[oai(rename = "type")]
     pub typ: MoveStructTag,
     pub data: MoveStructValue,
+    /// The aggregators and snapshots in `data`, which are rendered with their logical values.
+    /// Unset if the resource has none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[oai(skip_serializing_if_is_none)]
+    pub delayed_fields: Option<Vec<DelayedField>>,
 }
 
 impl TryFrom<AnnotatedMoveStruct> for MoveResource {
     type Error = anyhow::Error;
 
     fn try_from(s: AnnotatedMoveStruct) -> anyhow::Result<Self> {
+        let mut delayed_fields = vec![];
+        DelayedField::collect_from_struct(&s, &mut vec![], &mut delayed_fields);
         Ok(Self {
             typ: s.type_.clone().into(),
             data: s.try_into()?,
+            delayed_fields: (!delayed_fields.is_empty()).then_some(delayed_fields),
         })
     }
 }
 
+/// An aggregator or a snapshot of the aggregator V2 module. The VM may execute transactions
+/// updating them in parallel, by replacing their values with identifiers (i.e., delaying the
+/// fields) until the transactions are committed, so their values are always logical values.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct DelayedField {
+    /// Path of the aggregator or the snapshot in the Move value, as the field names and vector
+    /// indices leading to it, separated by dots
+    pub path: String,
+    pub kind: DelayedFieldKind,
+    /// Type of the value of the aggregator or the snapshot
+    #[serde(rename = "type")]
+    #[oai(rename = "type")]
+    pub typ: MoveType,
+}
+
+impl DelayedField {
+    fn collect_from_struct(
+        s: &AnnotatedMoveStruct,
+        path: &mut Vec<String>,
+        delayed_fields: &mut Vec<DelayedField>,
+    ) {
+        if let Some(kind) = DelayedFieldKind::from_struct_tag(&s.type_) {
+            if let Some(typ) = s.type_.type_params.first() {
+                delayed_fields.push(DelayedField {
+                    path: path.join("."),
+                    kind,
+                    typ: typ.into(),
+                });
+            }
+            return;
+        }
+        for (name, value) in &s.value {
+            path.push(name.to_string());
+            Self::collect_from_value(value, path, delayed_fields);
+            path.pop();
+        }
+    }
+
+    fn collect_from_value(
+        value: &AnnotatedMoveValue,
+        path: &mut Vec<String>,
+        delayed_fields: &mut Vec<DelayedField>,
+    ) {
+        match value {
+            AnnotatedMoveValue::Struct(s) => Self::collect_from_struct(s, path, delayed_fields),
+            AnnotatedMoveValue::Vector(_, values) => {
+                for (index, value) in values.iter().enumerate() {
+                    path.push(index.to_string());
+                    Self::collect_from_value(value, path, delayed_fields);
+                    path.pop();
+                }
+            },
+            _ => {},
+        }
+    }
+}
+
+/// The kinds of delayed fields
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Enum)]
+#[serde(rename_all = "snake_case")]
+#[oai(rename_all = "snake_case")]
+pub enum DelayedFieldKind {
+    /// A `0x1::aggregator_v2::Aggregator`
+    Aggregator,
+    /// A `0x1::aggregator_v2::AggregatorSnapshot`
+    Snapshot,
+}
+
+impl DelayedFieldKind {
+    pub fn from_struct_tag(st: &StructTag) -> Option<Self> {
+        if st.address != CORE_CODE_ADDRESS || st.module.as_str() != "aggregator_v2" {
+            return None;
+        }
+        match st.name.as_str() {
+            "Aggregator" => Some(DelayedFieldKind::Aggregator),
+            "AggregatorSnapshot" => Some(DelayedFieldKind::Snapshot),
+            _ => None,
+        }
+    }
+}
+
 macro_rules! define_integer_type {
     ($n:ident, $t:ty, $d:literal) => {
         #[doc = $d]
@@ -1318,6 +1407,61 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_serialize_move_resource_with_delayed_fields() {
+        use AnnotatedMoveValue::*;
+
+        let aggregator = |value: u64| {
+            Struct(AnnotatedMoveStruct {
+                abilities: AbilitySet::EMPTY,
+                type_: aggregator_v2_struct("Aggregator", TypeTag::U64),
+                value: vec![
+                    (identifier("value"), U64(value)),
+                    (identifier("max_value"), U64(u64::MAX)),
+                ],
+            })
+        };
+        let snapshot = Struct(AnnotatedMoveStruct {
+            abilities: AbilitySet::EMPTY,
+            type_: aggregator_v2_struct("AggregatorSnapshot", TypeTag::U128),
+            value: vec![(identifier("value"), U128(3))],
+        });
+        let res = MoveResource::try_from(annotated_move_struct("Values", vec![
+            (identifier("supply"), aggregator(1)),
+            (
+                identifier("nested"),
+                Struct(annotated_move_struct("Nested", vec![(
+                    identifier("aggregators"),
+                    Vector(
+                        TypeTag::Struct(Box::new(aggregator_v2_struct("Aggregator", TypeTag::U64))),
+                        vec![aggregator(2)],
+                    ),
+                )])),
+            ),
+            (identifier("snapshot"), snapshot),
+        ]))
+        .unwrap();
+        let value = to_value(&res).unwrap();
+        assert_json(
+            value,
+            json!({
+                "type": "0x1::type::Values",
+                "data": {
+                    "supply": {"value": "1", "max_value": u64::MAX.to_string()},
+                    "nested": {
+                        "aggregators": [{"value": "2", "max_value": u64::MAX.to_string()}]
+                    },
+                    "snapshot": {"value": "3"},
+                },
+                "delayed_fields": [
+                    {"path": "supply", "kind": "aggregator", "type": "u64"},
+                    {"path": "nested.aggregators.0", "kind": "aggregator", "type": "u64"},
+                    {"path": "snapshot", "kind": "snapshot", "type": "u128"},
+                ]
+            }),
+        );
+    }
+
     #[test]
     fn test_serialize_deserialize_u64() {
         test_serialize_deserialize(U64::from(u64::MAX), json!(u64::MAX.to_string()))
@@ -1495,6 +1639,15 @@ mod tests {
         }
     }
 
+    fn aggregator_v2_struct(name: &str, type_param: TypeTag) -> StructTag {
+        StructTag {
+            address: address("0x1"),
+            module: identifier("aggregator_v2"),
+            name: identifier(name),
+            type_params: vec![type_param],
+        }
+    }
+
     fn address(hex: &str) -> AccountAddress {
         AccountAddress::from_hex_literal(hex).unwrap()
     }
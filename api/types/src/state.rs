@@ -1,7 +1,10 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::HexEncodedBytes;
+use crate::{
+    Address, HashValue, HexEncodedBytes, MoveResource, MoveStructTag, VerifyInput,
+    VerifyInputWithRecursion, U64,
+};
 use poem_openapi::Object;
 use serde::{Deserialize, Serialize};
 
@@ -10,3 +13,56 @@ use serde::{Deserialize, Serialize};
 pub struct RawStateValueRequest {
     pub key: HexEncodedBytes,
 }
+
+/// A resource of an account to read with the ViewState API
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct ViewStateResourceRequest {
+    /// Address of the account holding the resource
+    pub address: Address,
+    /// Name of the struct of the resource e.g. `0x1::account::Account`
+    pub resource_type: MoveStructTag,
+}
+
+/// Request for the ViewState API, reading multiple resources at a single ledger version
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct ViewStateRequest {
+    /// Resources to read
+    pub resources: Vec<ViewStateResourceRequest>,
+    /// Whether to return a sparse merkle proof for each resource
+    ///
+    /// If set, the resources are read at the latest state checkpoint at or before the
+    /// requested ledger version, as proofs are only available for state checkpoints.
+    pub with_proof: Option<bool>,
+}
+
+impl VerifyInput for ViewStateRequest {
+    fn verify(&self) -> anyhow::Result<()> {
+        for resource in &self.resources {
+            resource.resource_type.verify(0)?;
+        }
+        Ok(())
+    }
+}
+
+/// A resource read with the ViewState API
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct ViewStateResource {
+    pub address: Address,
+    pub resource_type: MoveStructTag,
+    /// The resource, or null if it doesn't exist at the ledger version
+    pub data: Option<MoveResource>,
+    /// BCS encoded sparse merkle proof of the state item holding the resource (i.e., the
+    /// resource itself, or the resource group it's a member of) against the state root hash
+    pub proof: Option<HexEncodedBytes>,
+}
+
+/// Response of the ViewState API
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct ViewStateResponse {
+    /// Ledger version all the resources were read at
+    pub ledger_version: U64,
+    /// State root hash at the ledger version, only given along with proofs
+    pub state_root_hash: Option<HashValue>,
+    /// Resources in the same order as in the request
+    pub resources: Vec<ViewStateResource>,
+}
@@ -26,7 +26,7 @@ use aptos_types::{
     },
 };
 use once_cell::sync::Lazy;
-use poem_openapi::{Object, Union};
+use poem_openapi::{Enum, Object, Union};
 use serde::{Deserialize, Serialize};
 use std::{
     boxed::Box,
@@ -275,6 +275,8 @@ impl
             request: (txn, payload).into(),
             events,
             timestamp: timestamp.into(),
+            state_changes: None,
+            publish_diagnostics: None,
         }))
     }
 }
@@ -363,6 +365,47 @@ impl From<(SignedTransaction, TransactionPayload)> for PendingTransaction {
     }
 }
 
+/// The status of a submitted transaction, as seen by the node
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Enum)]
+#[oai(rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum SubmittedTransactionStatus {
+    /// The transaction is neither in the mempool nor committed, e.g., it was evicted
+    NotFound,
+    /// The transaction is in the mempool, ready to be included in a block
+    Accepted,
+    /// The transaction is in the mempool, waiting for the transactions with lower sequence
+    /// numbers of its sender
+    Parked,
+    /// The transaction was broadcast to another node
+    Broadcast,
+    /// The transaction expired before being committed
+    Expired,
+    /// The transaction was rejected by consensus
+    Rejected,
+    /// The transaction is committed
+    Committed,
+}
+
+impl SubmittedTransactionStatus {
+    /// Returns true if the status of the transaction won't change anymore
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            SubmittedTransactionStatus::Expired
+                | SubmittedTransactionStatus::Rejected
+                | SubmittedTransactionStatus::Committed
+        )
+    }
+}
+
+/// The status of a submitted transaction
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct TransactionStatusUpdate {
+    pub hash: HashValue,
+    pub status: SubmittedTransactionStatus,
+}
+
 /// A transaction submitted by a user to change the state of the blockchain
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
 pub struct UserTransaction {
@@ -375,6 +418,16 @@ pub struct UserTransaction {
     /// Events generated by the transaction
     pub events: Vec<Event>,
     pub timestamp: U64,
+    /// Values of the state keys written by the transaction, before and after it.
+    /// Only returned by simulations requesting them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[oai(skip_serializing_if_is_none)]
+    pub state_changes: Option<Vec<StateKeyChange>>,
+    /// Problems which would make publishing the package of the transaction fail.
+    /// Only returned by simulations of `0x1::code::publish_package_txn` requesting them
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[oai(skip_serializing_if_is_none)]
+    pub publish_diagnostics: Option<PublishDiagnostics>,
 }
 
 /// A state checkpoint transaction
@@ -610,6 +663,7 @@ pub enum TransactionPayload {
     // Deprecated. Will be removed in the future.
     ModuleBundlePayload(ModuleBundlePayload),
     MultisigPayload(MultisigPayload),
+    BatchedPayload(BatchedPayload),
 }
 
 impl VerifyInput for TransactionPayload {
@@ -618,6 +672,7 @@ impl VerifyInput for TransactionPayload {
             TransactionPayload::EntryFunctionPayload(inner) => inner.verify(),
             TransactionPayload::ScriptPayload(inner) => inner.verify(),
             TransactionPayload::MultisigPayload(inner) => inner.verify(),
+            TransactionPayload::BatchedPayload(inner) => inner.verify(),
             // Deprecated. Will be removed in the future.
             TransactionPayload::ModuleBundlePayload(inner) => inner.verify(),
         }
@@ -728,6 +783,21 @@ impl VerifyInput for MultisigPayload {
     }
 }
 
+/// Payload which runs a sequence of entry functions atomically, in a single session
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct BatchedPayload {
+    pub entry_functions: Vec<EntryFunctionPayload>,
+}
+
+impl VerifyInput for BatchedPayload {
+    fn verify(&self) -> anyhow::Result<()> {
+        for entry_function in self.entry_functions.iter() {
+            entry_function.verify()?;
+        }
+        Ok(())
+    }
+}
+
 /// A writeset payload, used only for genesis
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
 pub struct WriteSetPayload {
@@ -768,6 +838,40 @@ pub enum WriteSetChange {
     WriteTableItem(WriteTableItem),
 }
 
+/// A state key written by a simulated transaction, with its values before and after it
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct StateKeyChange {
+    /// State key hash
+    pub state_key_hash: String,
+    /// BCS encoded value before the transaction, unset if the key didn't exist
+    pub before_bcs: Option<HexEncodedBytes>,
+    /// BCS encoded value after the transaction, unset if the key was deleted
+    pub after_bcs: Option<HexEncodedBytes>,
+    /// Value before the transaction, as the changes writing it. Empty if the key didn't exist
+    pub before: Vec<WriteSetChange>,
+    /// Changes of the key made by the transaction
+    pub after: Vec<WriteSetChange>,
+}
+
+/// Problems found by dry-running the publishing of a package
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct PublishDiagnostics {
+    /// Whether all the modules verify and all the upgrades pass the compatibility check
+    pub publishable: bool,
+    /// Modules failing bytecode verification
+    pub verification_errors: Vec<ModuleDiagnostic>,
+    /// Incompatibilities of the upgrades of already published modules. Rules which are not
+    /// enforced by the chain, e.g. friend linking, are reported but don't affect `publishable`
+    pub incompatibilities: Vec<ModuleDiagnostic>,
+}
+
+/// A problem with a module of a package
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct ModuleDiagnostic {
+    pub module: MoveModuleId,
+    pub message: String,
+}
+
 /// Delete a module
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
 pub struct DeleteModule {
@@ -1130,6 +1234,8 @@ impl VerifyInput for Secp256k1EcdsaSignature {
 pub enum Signature {
     Ed25519(HexEncodedBytes),
     Secp256k1Ecdsa(HexEncodedBytes),
+    /// BCS-serialized WebAuthn assertion
+    WebAuthn(HexEncodedBytes),
 }
 
 impl TryFrom<Signature> for AnySignature {
@@ -1139,6 +1245,7 @@ impl TryFrom<Signature> for AnySignature {
         Ok(match signature {
             Signature::Ed25519(s) => AnySignature::ed25519(s.inner().try_into()?),
             Signature::Secp256k1Ecdsa(s) => AnySignature::secp256k1_ecdsa(s.inner().try_into()?),
+            Signature::WebAuthn(s) => AnySignature::webauthn(bcs::from_bytes(s.inner())?),
         })
     }
 }
@@ -1152,6 +1259,11 @@ impl From<AnySignature> for Signature {
             AnySignature::Secp256k1Ecdsa { signature } => {
                 Signature::Secp256k1Ecdsa(signature.to_bytes().to_vec().into())
             },
+            AnySignature::WebAuthn { signature } => Signature::WebAuthn(
+                bcs::to_bytes(&signature)
+                    .expect("Only unhandleable errors happen here.")
+                    .into(),
+            ),
         }
     }
 }
@@ -1162,6 +1274,7 @@ impl From<AnySignature> for Signature {
 pub enum PublicKey {
     Ed25519(HexEncodedBytes),
     Secp256k1Ecdsa(HexEncodedBytes),
+    Secp256r1Ecdsa(HexEncodedBytes),
 }
 
 impl TryFrom<PublicKey> for AnyPublicKey {
@@ -1171,6 +1284,7 @@ impl TryFrom<PublicKey> for AnyPublicKey {
         Ok(match public_key {
             PublicKey::Ed25519(p) => AnyPublicKey::ed25519(p.inner().try_into()?),
             PublicKey::Secp256k1Ecdsa(p) => AnyPublicKey::secp256k1_ecdsa(p.inner().try_into()?),
+            PublicKey::Secp256r1Ecdsa(p) => AnyPublicKey::secp256r1_ecdsa(p.inner().try_into()?),
         })
     }
 }
@@ -1184,6 +1298,9 @@ impl From<AnyPublicKey> for PublicKey {
             AnyPublicKey::Secp256k1Ecdsa { public_key } => {
                 PublicKey::Secp256k1Ecdsa(public_key.to_bytes().to_vec().into())
             },
+            AnyPublicKey::Secp256r1Ecdsa { public_key } => {
+                PublicKey::Secp256r1Ecdsa(public_key.to_bytes().to_vec().into())
+            },
         }
     }
 }
@@ -1210,6 +1327,10 @@ impl VerifyInput for SingleKeySignature {
                 }
                 .verify()
             },
+            (PublicKey::Secp256r1Ecdsa(_), Signature::WebAuthn(_)) => {
+                let _: AccountAuthenticator = self.clone().try_into()?;
+                Ok(())
+            },
             _ => bail!("Invalid public key, signature match."),
         }
     }
@@ -1228,22 +1349,28 @@ impl TryFrom<SingleKeySignature> for AccountAuthenticator {
     type Error = anyhow::Error;
 
     fn try_from(value: SingleKeySignature) -> Result<Self, Self::Error> {
-        let key = match value.public_key {
-            PublicKey::Ed25519(p) => {
-                let key = p
-                    .inner()
-                    .try_into()
-                    .context("Failed to parse given public_key bytes as Ed25519PublicKey")?;
-                AnyPublicKey::ed25519(key)
-            },
-            PublicKey::Secp256k1Ecdsa(p) => {
-                let key = p
-                    .inner()
-                    .try_into()
-                    .context("Failed to parse given public_key bytes as Secp256k1EcdsaPublicKey")?;
-                AnyPublicKey::secp256k1_ecdsa(key)
-            },
-        };
+        let key =
+            match value.public_key {
+                PublicKey::Ed25519(p) => {
+                    let key = p
+                        .inner()
+                        .try_into()
+                        .context("Failed to parse given public_key bytes as Ed25519PublicKey")?;
+                    AnyPublicKey::ed25519(key)
+                },
+                PublicKey::Secp256k1Ecdsa(p) => {
+                    let key = p.inner().try_into().context(
+                        "Failed to parse given public_key bytes as Secp256k1EcdsaPublicKey",
+                    )?;
+                    AnyPublicKey::secp256k1_ecdsa(key)
+                },
+                PublicKey::Secp256r1Ecdsa(p) => {
+                    let key = p.inner().try_into().context(
+                        "Failed to parse given public_key bytes as Secp256r1EcdsaPublicKey",
+                    )?;
+                    AnyPublicKey::secp256r1_ecdsa(key)
+                },
+            };
 
         let signature = match value.signature {
             Signature::Ed25519(s) => {
@@ -1260,6 +1387,11 @@ impl TryFrom<SingleKeySignature> for AccountAuthenticator {
                     .context("Failed to parse given public_key bytes as Secp256k1EcdsaSignature")?;
                 AnySignature::secp256k1_ecdsa(signature)
             },
+            Signature::WebAuthn(s) => {
+                let signature = bcs::from_bytes(s.inner())
+                    .context("Failed to parse given signature bytes as WebAuthn assertion")?;
+                AnySignature::webauthn(signature)
+            },
         };
 
         let auth = SingleKeyAuthenticator::new(key, signature);
@@ -1317,27 +1449,39 @@ impl TryFrom<MultiKeySignature> for AccountAuthenticator {
                     )?;
                     AnyPublicKey::secp256k1_ecdsa(key)
                 },
+                PublicKey::Secp256r1Ecdsa(p) => {
+                    let key = p.inner().try_into().context(
+                        "Failed to parse given public_key bytes as Secp256r1EcdsaPublicKey",
+                    )?;
+                    AnyPublicKey::secp256r1_ecdsa(key)
+                },
             };
             public_keys.push(key);
         }
 
         let mut signatures = vec![];
         for indexed_signature in value.signatures {
-            let signature =
-                match indexed_signature.signature {
-                    Signature::Ed25519(s) => {
-                        let signature = s.inner().try_into().context(
-                            "Failed to parse given public_key bytes as Ed25519Signature",
-                        )?;
-                        AnySignature::ed25519(signature)
-                    },
-                    Signature::Secp256k1Ecdsa(s) => {
-                        let signature = s.inner().try_into().context(
-                            "Failed to parse given signature as Secp256k1EcdsaSignature",
-                        )?;
-                        AnySignature::secp256k1_ecdsa(signature)
-                    },
-                };
+            let signature = match indexed_signature.signature {
+                Signature::Ed25519(s) => {
+                    let signature = s
+                        .inner()
+                        .try_into()
+                        .context("Failed to parse given public_key bytes as Ed25519Signature")?;
+                    AnySignature::ed25519(signature)
+                },
+                Signature::Secp256k1Ecdsa(s) => {
+                    let signature = s
+                        .inner()
+                        .try_into()
+                        .context("Failed to parse given signature as Secp256k1EcdsaSignature")?;
+                    AnySignature::secp256k1_ecdsa(signature)
+                },
+                Signature::WebAuthn(s) => {
+                    let signature = bcs::from_bytes(s.inner())
+                        .context("Failed to parse given signature bytes as WebAuthn assertion")?;
+                    AnySignature::webauthn(signature)
+                },
+            };
             signatures.push((indexed_signature.index, signature));
         }
 
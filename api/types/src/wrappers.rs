@@ -15,7 +15,7 @@ use anyhow::{bail, Context};
 use aptos_types::{event::EventKey, state_store::state_key::StateKey};
 use move_core_types::identifier::{IdentStr, Identifier};
 use poem_openapi::Object;
-use serde::{Deserialize, Serialize};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 use std::{convert::From, fmt, ops::Deref, str::FromStr};
 
 /// A wrapper of a Move identifier
@@ -151,3 +151,70 @@ impl From<StateKeyWrapper> for StateKey {
         value.0
     }
 }
+
+/// An opaque cursor for the pagination of list endpoints, pointing at the next item of a list.
+/// It's made of the ledger version the previous page was read at, and the offset of the next
+/// item in the list (e.g., a transaction version or a sequence number), serialized as hex
+/// encoded bytes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PageCursor {
+    pub ledger_version: u64,
+    pub offset: u64,
+}
+
+impl PageCursor {
+    pub fn new(ledger_version: u64, offset: u64) -> Self {
+        Self {
+            ledger_version,
+            offset,
+        }
+    }
+}
+
+impl fmt::Display for PageCursor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bytes = bcs::to_bytes(&(self.ledger_version, self.offset)).map_err(|_| fmt::Error)?;
+        write!(f, "{}", hex::encode(bytes))
+    }
+}
+
+impl FromStr for PageCursor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self, anyhow::Error> {
+        let (ledger_version, offset) =
+            bcs::from_bytes(&hex::decode(s).context("Failed to decode cursor as hex string")?)
+                .context("Failed to decode cursor from hex string")?;
+        Ok(Self::new(ledger_version, offset))
+    }
+}
+
+impl Serialize for PageCursor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_string().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for PageCursor {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let cursor = <String>::deserialize(deserializer)?;
+        cursor.parse().map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::PageCursor;
+
+    #[test]
+    fn test_page_cursor_round_trip() {
+        let cursor = PageCursor::new(1000, 25);
+        assert_eq!(cursor.to_string(), "e8030000000000001900000000000000");
+        assert_eq!(cursor.to_string().parse::<PageCursor>().unwrap(), cursor);
+        assert!("e803".parse::<PageCursor>().is_err());
+        assert!("not a cursor".parse::<PageCursor>().is_err());
+    }
+}
@@ -12,12 +12,15 @@ use aptos_state_view::TStateView;
 use aptos_types::{
     account_address::AccountAddress,
     chain_id::ChainId,
-    on_chain_config::{Features, OnChainConfig, TimedFeaturesBuilder},
+    contract_event::ContractEvent,
+    on_chain_config::{BlockExecutionLimit, Features, OnChainConfig, TimedFeaturesBuilder},
+    state_store::{state_key::StateKey, state_value::StateValue},
     transaction::{
         signature_verified_transaction::SignatureVerifiedTransaction, SignedTransaction,
         Transaction, TransactionInfo, TransactionOutput, TransactionPayload, Version,
     },
     vm_status::VMStatus,
+    write_set::WriteSet,
 };
 use aptos_validator_interface::{
     AptosValidatorInterface, DBDebuggerInterface, DebuggerStateView, RestDebuggerInterface,
@@ -28,9 +31,11 @@ use aptos_vm::{
     AptosVM, VMExecutor,
 };
 use aptos_vm_logging::log_schema::AdapterLogSchema;
-use aptos_vm_types::{change_set::VMChangeSet, output::VMOutput, storage::ChangeSetConfigs};
+use aptos_vm_types::{
+    access_trace::AccessTrace, change_set::VMChangeSet, output::VMOutput, storage::ChangeSetConfigs,
+};
 use move_binary_format::errors::VMResult;
-use std::{path::Path, sync::Arc};
+use std::{collections::HashMap, path::Path, sync::Arc};
 
 pub struct AptosDebugger {
     debugger: Arc<dyn AptosValidatorInterface + Send>,
@@ -55,12 +60,28 @@ impl AptosDebugger {
         &self,
         version: Version,
         txns: Vec<Transaction>,
+    ) -> Result<Vec<TransactionOutput>> {
+        self.execute_transactions_at_version_with_overrides(version, txns, HashMap::new())
+    }
+
+    /// Same as `execute_transactions_at_version`, but the given state values are used instead of
+    /// the ones on chain, which allows replaying with a different framework.
+    pub fn execute_transactions_at_version_with_overrides(
+        &self,
+        version: Version,
+        txns: Vec<Transaction>,
+        overrides: HashMap<StateKey, StateValue>,
     ) -> Result<Vec<TransactionOutput>> {
         let sig_verified_txns: Vec<SignatureVerifiedTransaction> =
             txns.into_iter().map(|x| x.into()).collect::<Vec<_>>();
-        let state_view = DebuggerStateView::new(self.debugger.clone(), version);
-        AptosVM::execute_block(&sig_verified_txns, &state_view, None)
-            .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))
+        let state_view =
+            DebuggerStateView::new(self.debugger.clone(), version).with_overrides(overrides);
+        AptosVM::execute_block(
+            &sig_verified_txns,
+            &state_view,
+            BlockExecutionLimit::no_limit(),
+        )
+        .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))
     }
 
     pub fn execute_transaction_at_version_with_gas_profiler(
@@ -100,6 +121,7 @@ impl AptosDebugger {
                     ),
                     TransactionPayload::ModuleBundle(..) => unreachable!("not supported"),
                     TransactionPayload::Multisig(..) => unimplemented!("not supported yet"),
+                    TransactionPayload::Batched(..) => unimplemented!("not supported yet"),
                 };
                 Ok(gas_profiler)
             },
@@ -108,6 +130,30 @@ impl AptosDebugger {
         Ok((status, output, gas_profiler.finish()))
     }
 
+    /// Executes the transaction at the given version and returns its output together with the
+    /// trace of every state item it read or wrote.
+    pub fn execute_transaction_at_version_with_access_trace(
+        &self,
+        version: Version,
+        txn: SignedTransaction,
+    ) -> Result<(VMStatus, TransactionOutput, AccessTrace)> {
+        let state_view = DebuggerStateView::new(self.debugger.clone(), version);
+        let log_context = AdapterLogSchema::new(state_view.id(), 0);
+        let txn = txn
+            .check_signature()
+            .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?;
+
+        let vm = AptosVM::new_from_state_view(&state_view);
+        let resolver = state_view.as_move_resolver();
+
+        let (status, output) =
+            vm.execute_user_transaction_with_access_trace(&resolver, &txn, &log_context);
+        let (output, access_trace) = output
+            .try_into_transaction_output_with_access_trace(&resolver)
+            .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?;
+        Ok((status, output, access_trace.unwrap_or_default()))
+    }
+
     pub async fn execute_past_transactions(
         &self,
         mut begin: Version,
@@ -210,6 +256,26 @@ impl AptosDebugger {
         Ok(result)
     }
 
+    pub async fn get_committed_transaction_at_version(
+        &self,
+        version: Version,
+    ) -> Result<(Transaction, TransactionInfo)> {
+        let (mut txns, mut txn_infos) =
+            self.debugger.get_committed_transactions(version, 1).await?;
+        match (txns.pop(), txn_infos.pop()) {
+            (Some(txn), Some(txn_info)) => Ok((txn, txn_info)),
+            _ => Err(format_err!("Transaction at version {} not found", version)),
+        }
+    }
+
+    pub async fn get_committed_write_set(&self, version: Version) -> Result<WriteSet> {
+        self.debugger.get_committed_write_set(version).await
+    }
+
+    pub async fn get_committed_events(&self, version: Version) -> Result<Vec<ContractEvent>> {
+        self.debugger.get_committed_events(version).await
+    }
+
     pub async fn get_latest_version(&self) -> Result<Version> {
         self.debugger.get_latest_version().await
     }
@@ -228,7 +294,24 @@ impl AptosDebugger {
     where
         F: FnOnce(&mut SessionExt) -> VMResult<()>,
     {
-        let state_view = DebuggerStateView::new(self.debugger.clone(), version);
+        self.run_session_at_version_with_overrides(version, HashMap::new(), SessionId::Void, f)
+    }
+
+    /// Like `run_session_at_version`, but reads the given state values instead of the ones in
+    /// storage, and runs the session under the given id, which determines e.g. the script hash
+    /// seen by the transaction context.
+    pub fn run_session_at_version_with_overrides<F>(
+        &self,
+        version: Version,
+        overrides: HashMap<StateKey, StateValue>,
+        session_id: SessionId,
+        f: F,
+    ) -> Result<VMChangeSet>
+    where
+        F: FnOnce(&mut SessionExt) -> VMResult<()>,
+    {
+        let state_view =
+            DebuggerStateView::new(self.debugger.clone(), version).with_overrides(overrides);
         let state_view_storage = state_view.as_move_resolver();
         let features = Features::fetch_config(&state_view_storage).unwrap_or_default();
         let move_vm = MoveVmExt::new(
@@ -241,7 +324,7 @@ impl AptosDebugger {
             &state_view_storage,
         )
         .unwrap();
-        let mut session = move_vm.new_session(&state_view_storage, SessionId::Void);
+        let mut session = move_vm.new_session(&state_view_storage, session_id);
         f(&mut session).map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?;
         let change_set = session
             .finish(
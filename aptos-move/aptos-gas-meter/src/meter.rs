@@ -7,7 +7,7 @@ use aptos_gas_schedule::gas_params::{instr::*, txn::*};
 use aptos_types::{
     contract_event::ContractEvent, state_store::state_key::StateKey, write_set::WriteOp,
 };
-use aptos_vm_types::change_set::GroupWrite;
+use aptos_vm_types::{change_set::GroupWrite, storage_refund::StorageRefundCalculator};
 use move_binary_format::{
     errors::{Location, PartialVMError, PartialVMResult, VMResult},
     file_format::CodeOffset,
@@ -513,17 +513,15 @@ where
     }
 
     fn storage_fee_for_state_slot(&self, op: &WriteOp) -> Fee {
-        self.vm_gas_params().txn.storage_fee_for_slot(op)
+        StorageRefundCalculator::new(&self.vm_gas_params().txn).slot_fee(op)
     }
 
     fn storage_fee_refund_for_state_slot(&self, op: &WriteOp) -> Fee {
-        self.vm_gas_params().txn.storage_fee_refund_for_slot(op)
+        StorageRefundCalculator::new(&self.vm_gas_params().txn).refund(op)
     }
 
     fn storage_fee_for_state_bytes(&self, key: &StateKey, maybe_value_size: Option<u64>) -> Fee {
-        self.vm_gas_params()
-            .txn
-            .storage_fee_for_bytes(key, maybe_value_size)
+        StorageRefundCalculator::new(&self.vm_gas_params().txn).bytes_fee(key, maybe_value_size)
     }
 
     fn storage_fee_per_event(&self, event: &ContractEvent) -> Fee {
@@ -9,6 +9,7 @@ use aptos_types::{
 use aptos_vm_types::{
     change_set::{GroupWrite, VMChangeSet},
     storage::StorageGasParameters,
+    storage_refund::StorageRefundCalculator,
 };
 use move_binary_format::errors::{Location, PartialVMResult, VMResult};
 use move_core_types::gas_algebra::{InternalGas, InternalGasUnit, NumBytes};
@@ -215,23 +216,7 @@ pub trait AptosGasMeter: MoveGasMeter {
     // The slot fee is refundable, we record it on the WriteOp itself and it'll end up in
     // the state DB.
     fn maybe_record_storage_deposit(write_op: &mut WriteOp, slot_fee: Fee) {
-        use WriteOp::*;
-
-        match write_op {
-            CreationWithMetadata {
-                ref mut metadata,
-                data: _,
-            } => {
-                if !slot_fee.is_zero() {
-                    metadata.set_deposit(slot_fee.into())
-                }
-            },
-            Creation(..)
-            | Modification(..)
-            | Deletion
-            | ModificationWithMetadata { .. }
-            | DeletionWithMetadata { .. } => {},
-        }
+        StorageRefundCalculator::record_deposit(write_op, slot_fee)
     }
 
     // Below are getters reexported from the gas algebra.
@@ -0,0 +1,77 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::log::TransactionGasLog;
+use aptos_infallible::Mutex;
+use once_cell::sync::Lazy;
+use std::{collections::BTreeMap, sync::Arc};
+
+/// The profile of the most recently executed block, if block gas profiling is enabled.
+static LATEST_BLOCK_GAS_PROFILE: Lazy<Mutex<Option<Arc<BlockGasProfile>>>> =
+    Lazy::new(|| Mutex::new(None));
+
+/// Gas costs of all transactions in a block, aggregated by stack so that the code burning the
+/// most gas can be identified across transactions.
+#[derive(Debug, Default)]
+pub struct BlockGasProfile {
+    num_transactions: usize,
+    execution_and_io: BTreeMap<String, u64>,
+    storage_fees: BTreeMap<String, u64>,
+}
+
+impl BlockGasProfile {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_transaction(&mut self, log: &TransactionGasLog) {
+        self.num_transactions += 1;
+        aggregate_folded_stack_lines(
+            &mut self.execution_and_io,
+            log.exec_io.to_folded_stack_lines(),
+        );
+        aggregate_folded_stack_lines(&mut self.storage_fees, log.storage.to_folded_stack_lines());
+    }
+
+    pub fn num_transactions(&self) -> usize {
+        self.num_transactions
+    }
+
+    /// Returns the execution and IO costs in folded stack format, one line per stack, in
+    /// internal gas units.
+    pub fn execution_and_io_folded_stacks(&self) -> String {
+        to_folded_stacks(&self.execution_and_io)
+    }
+
+    /// Returns the storage fees in folded stack format, one line per stack, in Octa.
+    pub fn storage_fee_folded_stacks(&self) -> String {
+        to_folded_stacks(&self.storage_fees)
+    }
+}
+
+/// Makes the profile of a block available through [latest_block_gas_profile].
+pub fn publish_block_gas_profile(profile: BlockGasProfile) {
+    *LATEST_BLOCK_GAS_PROFILE.lock() = Some(Arc::new(profile));
+}
+
+/// Returns the profile of the most recently executed block, if any.
+pub fn latest_block_gas_profile() -> Option<Arc<BlockGasProfile>> {
+    LATEST_BLOCK_GAS_PROFILE.lock().clone()
+}
+
+fn aggregate_folded_stack_lines(stacks: &mut BTreeMap<String, u64>, lines: Vec<String>) {
+    for line in lines {
+        // Lines are formatted as "<stack> <count>".
+        if let Some((stack, count)) = line.rsplit_once(' ') {
+            let count: u64 = count.parse().expect("should be able to parse count as u64");
+            *stacks.entry(stack.to_string()).or_insert(0) += count;
+        }
+    }
+}
+
+fn to_folded_stacks(stacks: &BTreeMap<String, u64>) -> String {
+    stacks
+        .iter()
+        .map(|(stack, count)| format!("{} {}\n", stack, count))
+        .collect()
+}
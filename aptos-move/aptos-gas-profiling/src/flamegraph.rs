@@ -33,7 +33,7 @@ impl LineBuffer {
 impl StorageFees {
     /// Convert the storage fee log into folded stack lines, which can
     /// then be used to generate a flamegraph.
-    fn to_folded_stack_lines(&self) -> Vec<String> {
+    pub(crate) fn to_folded_stack_lines(&self) -> Vec<String> {
         let mut lines = LineBuffer::new();
 
         lines.push("transaction", self.txn_storage);
@@ -96,7 +96,7 @@ impl StorageFees {
 impl ExecutionAndIOCosts {
     /// Convert the execution gas log into folded stack lines, which can
     /// then be used to generate a flamegraph.
-    fn to_folded_stack_lines(&self) -> Vec<String> {
+    pub(crate) fn to_folded_stack_lines(&self) -> Vec<String> {
         let mut lines = LineBuffer::new();
 
         lines.push("intrinsic", self.intrinsic_cost);
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod aggregate;
+mod block;
 mod erased;
 mod flamegraph;
 mod log;
@@ -10,5 +11,6 @@ mod profiler;
 mod render;
 mod report;
 
+pub use block::{latest_block_gas_profile, publish_block_gas_profile, BlockGasProfile};
 pub use log::{FrameName, TransactionGasLog};
 pub use profiler::GasProfiler;
@@ -110,6 +110,11 @@ crate::gas_schedule::macros::define_gas_parameters!(
         [secp256k1_base: InternalGas, "secp256k1.base", 3000],
         [secp256k1_ecdsa_recover: InternalGasPerArg, "secp256k1.ecdsa_recover", 32200000],
 
+        [secp256r1_base: InternalGas, { 13.. => "secp256r1.base" }, 3000],
+        [secp256r1_per_msg_hashing_base: InternalGasPerArg, { 13.. => "secp256r1.per_msg_hashing_base" }, 60000],
+        [secp256r1_per_msg_byte_hashing: InternalGasPerByte, { 13.. => "secp256r1.per_msg_byte_hashing" }, 1000],
+        [secp256r1_ecdsa_verify: InternalGasPerArg, { 13.. => "secp256r1.ecdsa_verify" }, 16000000],
+
         [ristretto255_basepoint_mul: InternalGasPerArg, "ristretto255.basepoint_mul", 2560000],
         [ristretto255_basepoint_double_mul: InternalGasPerArg, "ristretto255.basepoint_double_mul", 8800000],
 
@@ -185,6 +190,9 @@ crate::gas_schedule::macros::define_gas_parameters!(
         // Based on SHA3-256's cost
         [transaction_context_generate_unique_address_base: InternalGas, { 10.. => "transaction_context.generate_unique_address.base" }, 80000],
 
+        [randomness_fetch_and_inc_counter: InternalGas, { 13.. => "randomness.fetch_and_inc_counter" }, 1102],
+        [randomness_is_unbiasable: InternalGas, { 13.. => "randomness.is_unbiasable" }, 1102],
+
         [code_request_publish_base: InternalGas, "code.request_publish.base", 10000],
         [code_request_publish_per_byte: InternalGasPerByte, "code.request_publish.per_byte", 40],
 
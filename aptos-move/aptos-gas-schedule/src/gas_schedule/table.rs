@@ -4,7 +4,7 @@
 //! This module defines the gas parameters for the table extension.
 
 use crate::gas_schedule::NativeGasParameters;
-use aptos_gas_algebra::{InternalGas, InternalGasPerByte};
+use aptos_gas_algebra::{InternalGas, InternalGasPerArg, InternalGasPerByte};
 
 crate::gas_schedule::macros::define_gas_parameters!(
     TableGasParameters,
@@ -34,5 +34,9 @@ crate::gas_schedule::macros::define_gas_parameters!(
         [destroy_empty_box_base: InternalGas, "destroy_empty_box.base", 24000],
 
         [drop_unchecked_box_base: InternalGas, "drop_unchecked_box.base", 2000],
+
+        [keys_box_base: InternalGas, { 13.. => "keys_box.base" }, 24000],
+        [keys_box_per_key: InternalGasPerArg, { 13.. => "keys_box.per_key" }, 8000],
+        [keys_box_per_byte_serialized: InternalGasPerByte, { 13.. => "keys_box.per_byte_serialized" }, 1000],
     ]
 );
@@ -9,9 +9,7 @@ use aptos_gas_algebra::{
     AbstractValueSize, Fee, FeePerByte, FeePerGasUnit, FeePerSlot, Gas, GasExpression,
     GasScalingFactor, GasUnit, NumSlots,
 };
-use aptos_types::{
-    contract_event::ContractEvent, state_store::state_key::StateKey, write_set::WriteOp,
-};
+use aptos_types::contract_event::ContractEvent;
 use move_core_types::gas_algebra::{
     InternalGas, InternalGasPerArg, InternalGasPerByte, InternalGasUnit, NumBytes, ToUnitWithParams,
 };
@@ -194,45 +192,6 @@ impl TransactionGasParameters {
         }
     }
 
-    pub fn storage_fee_for_slot(&self, op: &WriteOp) -> Fee {
-        use WriteOp::*;
-
-        match op {
-            Creation(..) | CreationWithMetadata { .. } => {
-                self.storage_fee_per_state_slot_create * NumSlots::new(1)
-            },
-            Modification(..)
-            | ModificationWithMetadata { .. }
-            | Deletion
-            | DeletionWithMetadata { .. } => 0.into(),
-        }
-    }
-
-    pub fn storage_fee_refund_for_slot(&self, op: &WriteOp) -> Fee {
-        use WriteOp::*;
-
-        match op {
-            DeletionWithMetadata { metadata, .. } => Fee::new(metadata.deposit()),
-            Creation(..)
-            | CreationWithMetadata { .. }
-            | Modification(..)
-            | ModificationWithMetadata { .. }
-            | Deletion => 0.into(),
-        }
-    }
-
-    /// Maybe value size is None for deletion Ops.
-    pub fn storage_fee_for_bytes(&self, key: &StateKey, maybe_value_size: Option<u64>) -> Fee {
-        if let Some(value_size) = maybe_value_size {
-            let size = NumBytes::new(key.size() as u64) + NumBytes::new(value_size);
-            if let Some(excess) = size.checked_sub(self.free_write_bytes_quota) {
-                return excess * self.storage_fee_per_excess_state_byte;
-            }
-        }
-
-        0.into()
-    }
-
     /// New formula to charge storage fee for an event, measured in APT.
     pub fn storage_fee_per_event(&self, event: &ContractEvent) -> Fee {
         NumBytes::new(event.size() as u64) * self.storage_fee_per_event_byte
@@ -21,6 +21,12 @@ use move_vm_types::{
 /// Special gas meter implementation that tracks the VM's memory usage based on the operations
 /// executed.
 ///
+/// The quota is taken from the `txn.memory_quota` gas parameter, so it is configured on chain
+/// through the gas schedule. A user transaction is metered by a single meter, shared by its
+/// prologue, payload and epilogue sessions, so the quota applies to the transaction as a whole
+/// rather than to each session. View functions get a meter of their own. Exceeding the quota
+/// aborts with `MEMORY_LIMIT_EXCEEDED` regardless of how much gas is left.
+///
 /// Must be composed with a base gas meter.
 pub struct MemoryTrackedGasMeter<G> {
     base: G,
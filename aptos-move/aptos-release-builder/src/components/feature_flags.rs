@@ -92,6 +92,11 @@ pub enum FeatureFlag {
     OperatorBeneficiaryChange,
     VMBinaryFormatV7,
     ResourceGroupsChargeAsSizeSum,
+    LazyFriendLoading,
+    WebAuthnSignature,
+    Randomness,
+    BatchedEntryFunctions,
+    TypeLayoutCache,
 }
 
 fn generate_features_blob(writer: &CodeWriter, data: &[u64]) {
@@ -240,6 +245,11 @@ impl From<FeatureFlag> for AptosFeatureFlag {
             FeatureFlag::ResourceGroupsChargeAsSizeSum => {
                 AptosFeatureFlag::RESOURCE_GROUPS_CHARGE_AS_SIZE_SUM
             },
+            FeatureFlag::LazyFriendLoading => AptosFeatureFlag::LAZY_FRIEND_LOADING,
+            FeatureFlag::WebAuthnSignature => AptosFeatureFlag::WEBAUTHN_SIGNATURE,
+            FeatureFlag::Randomness => AptosFeatureFlag::RANDOMNESS,
+            FeatureFlag::BatchedEntryFunctions => AptosFeatureFlag::BATCHED_ENTRY_FUNCTIONS,
+            FeatureFlag::TypeLayoutCache => AptosFeatureFlag::TYPE_LAYOUT_CACHE,
         }
     }
 }
@@ -311,6 +321,11 @@ impl From<AptosFeatureFlag> for FeatureFlag {
             AptosFeatureFlag::RESOURCE_GROUPS_CHARGE_AS_SIZE_SUM => {
                 FeatureFlag::ResourceGroupsChargeAsSizeSum
             },
+            AptosFeatureFlag::LAZY_FRIEND_LOADING => FeatureFlag::LazyFriendLoading,
+            AptosFeatureFlag::WEBAUTHN_SIGNATURE => FeatureFlag::WebAuthnSignature,
+            AptosFeatureFlag::RANDOMNESS => FeatureFlag::Randomness,
+            AptosFeatureFlag::BATCHED_ENTRY_FUNCTIONS => FeatureFlag::BatchedEntryFunctions,
+            AptosFeatureFlag::TYPE_LAYOUT_CACHE => FeatureFlag::TypeLayoutCache,
         }
     }
 }
@@ -0,0 +1,115 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_types::contract_event::ContractEvent;
+use std::fmt;
+
+/// Difference between the committed and the re-executed event at a single index.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EventDiff {
+    /// The event was emitted on chain, but not by the replay.
+    Missing { expected: ContractEvent },
+    /// The event was emitted by the replay, but not on chain.
+    Unexpected { actual: ContractEvent },
+    /// Both emitted an event at this index, with different contents.
+    Mismatch {
+        expected: ContractEvent,
+        actual: ContractEvent,
+    },
+}
+
+/// Per index difference between the committed events of a transaction and the ones emitted by
+/// replaying it. Events are ordered, so indices emitting identical events are omitted.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EventsDiff(Vec<(usize, EventDiff)>);
+
+impl EventsDiff {
+    pub fn new(expected: &[ContractEvent], actual: &[ContractEvent]) -> Self {
+        let mut diff = Vec::new();
+        for index in 0..expected.len().max(actual.len()) {
+            match (expected.get(index), actual.get(index)) {
+                (Some(expected), Some(actual)) if expected == actual => {},
+                (Some(expected), Some(actual)) => diff.push((index, EventDiff::Mismatch {
+                    expected: expected.clone(),
+                    actual: actual.clone(),
+                })),
+                (Some(expected), None) => diff.push((index, EventDiff::Missing {
+                    expected: expected.clone(),
+                })),
+                (None, Some(actual)) => diff.push((index, EventDiff::Unexpected {
+                    actual: actual.clone(),
+                })),
+                (None, None) => unreachable!("Index is within one of the event lists"),
+            }
+        }
+        Self(diff)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(usize, EventDiff)> {
+        self.0.iter()
+    }
+}
+
+impl fmt::Display for EventsDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, diff) in &self.0 {
+            match diff {
+                EventDiff::Missing { expected } => {
+                    writeln!(f, "- event {}: {:?}", index, expected)?;
+                },
+                EventDiff::Unexpected { actual } => {
+                    writeln!(f, "+ event {}: {:?}", index, actual)?;
+                },
+                EventDiff::Mismatch { expected, actual } => {
+                    writeln!(f, "~ event {}: {:?} -> {:?}", index, expected, actual)?;
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::{account_address::AccountAddress, event::EventKey};
+    use move_core_types::language_storage::TypeTag;
+
+    fn event(data: u8) -> ContractEvent {
+        ContractEvent::new_v1(EventKey::new(0, AccountAddress::ONE), 0, TypeTag::U8, vec![
+            data,
+        ])
+    }
+
+    #[test]
+    fn test_events_diff() {
+        let expected = vec![event(0), event(1), event(2)];
+        let actual = vec![event(0), event(3)];
+
+        assert!(EventsDiff::new(&expected, &expected).is_empty());
+
+        let diff = EventsDiff::new(&expected, &actual);
+        assert_eq!(diff.iter().cloned().collect::<Vec<_>>(), vec![
+            (1, EventDiff::Mismatch {
+                expected: event(1),
+                actual: event(3),
+            }),
+            (2, EventDiff::Missing { expected: event(2) }),
+        ]);
+
+        let diff = EventsDiff::new(&actual, &expected);
+        assert_eq!(diff.len(), 2);
+        assert_eq!(
+            diff.iter().last().unwrap(),
+            &(2, EventDiff::Unexpected { actual: event(2) })
+        );
+    }
+}
@@ -0,0 +1,178 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Deterministic replay of historical transactions, used to verify that VM upgrades do not change
+//! the semantics of what is already committed.
+//!
+//! Transactions are re-executed with `AptosVM::execute_block` against the state right before
+//! their version, fetched either from a local AptosDB or a REST fullnode, so state is read and
+//! writes are converted exactly as during block execution. The produced status, gas usage, write
+//! set and events are then compared against the committed ones.
+
+mod event_diff;
+mod write_set_diff;
+
+pub use crate::{
+    event_diff::{EventDiff, EventsDiff},
+    write_set_diff::{WriteOpDiff, WriteSetDiff},
+};
+use anyhow::{format_err, Result};
+use aptos_rest_client::Client;
+use aptos_types::{
+    contract_event::ContractEvent,
+    on_chain_config::BlockExecutionLimit,
+    transaction::{
+        signature_verified_transaction::SignatureVerifiedTransaction, ExecutionStatus, Transaction,
+        TransactionInfo, TransactionOutput, TransactionStatus, Version,
+    },
+    write_set::WriteSet,
+};
+use aptos_validator_interface::{
+    AptosValidatorInterface, DBDebuggerInterface, DebuggerStateView, RestDebuggerInterface,
+};
+use aptos_vm::{AptosVM, VMExecutor};
+use std::{fmt, path::Path, sync::Arc};
+
+/// Result of replaying a single transaction.
+#[derive(Clone, Debug)]
+pub struct ReplayResult {
+    pub version: Version,
+    /// Status committed on chain.
+    pub expected_status: ExecutionStatus,
+    /// Status produced by the replay.
+    pub status: TransactionStatus,
+    /// Gas used on chain and by the replay.
+    pub expected_gas_used: u64,
+    pub gas_used: u64,
+    pub write_set_diff: WriteSetDiff,
+    pub events_diff: EventsDiff,
+}
+
+impl ReplayResult {
+    /// Compares the output of replaying the transaction at the given version against what was
+    /// committed.
+    pub fn new(
+        version: Version,
+        txn_info: &TransactionInfo,
+        output: &TransactionOutput,
+        expected_write_set: &WriteSet,
+        expected_events: &[ContractEvent],
+    ) -> Self {
+        Self {
+            version,
+            expected_status: txn_info.status().clone(),
+            status: output.status().clone(),
+            expected_gas_used: txn_info.gas_used(),
+            gas_used: output.gas_used(),
+            write_set_diff: WriteSetDiff::new(expected_write_set, output.write_set()),
+            events_diff: EventsDiff::new(expected_events, output.events()),
+        }
+    }
+
+    pub fn is_status_match(&self) -> bool {
+        self.status == TransactionStatus::Keep(self.expected_status.clone())
+    }
+
+    pub fn is_gas_match(&self) -> bool {
+        self.gas_used == self.expected_gas_used
+    }
+
+    /// Returns true if the replay produced the same status, gas usage, writes and events as
+    /// committed.
+    pub fn is_match(&self) -> bool {
+        self.is_status_match()
+            && self.is_gas_match()
+            && self.write_set_diff.is_empty()
+            && self.events_diff.is_empty()
+    }
+}
+
+/// Lists every difference between the replay and the committed output, one per line.
+impl fmt::Display for ReplayResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.is_status_match() {
+            writeln!(
+                f,
+                "~ status: {:?} -> {:?}",
+                self.expected_status, self.status
+            )?;
+        }
+        if !self.is_gas_match() {
+            writeln!(
+                f,
+                "~ gas used: {} -> {}",
+                self.expected_gas_used, self.gas_used
+            )?;
+        }
+        write!(f, "{}{}", self.write_set_diff, self.events_diff)
+    }
+}
+
+pub struct ReplayHarness {
+    debugger: Arc<dyn AptosValidatorInterface + Send>,
+}
+
+impl ReplayHarness {
+    pub fn new(debugger: Arc<dyn AptosValidatorInterface + Send>) -> Self {
+        Self { debugger }
+    }
+
+    pub fn rest_client(rest_client: Client) -> Result<Self> {
+        Ok(Self::new(Arc::new(RestDebuggerInterface::new(rest_client))))
+    }
+
+    pub fn db<P: AsRef<Path> + Clone>(db_root_path: P) -> Result<Self> {
+        Ok(Self::new(Arc::new(DBDebuggerInterface::open(
+            db_root_path,
+        )?)))
+    }
+
+    /// Re-executes the transaction committed at the given version on top of the state right
+    /// before it, and compares the output against what was committed.
+    pub async fn replay_transaction(&self, version: Version) -> Result<ReplayResult> {
+        let (mut txns, mut txn_infos) =
+            self.debugger.get_committed_transactions(version, 1).await?;
+        let (txn, txn_info) = match (txns.pop(), txn_infos.pop()) {
+            (Some(txn), Some(txn_info)) => (txn, txn_info),
+            _ => return Err(format_err!("Transaction at version {} not found", version)),
+        };
+        let output = self.execute_transaction_at_version(version, txn)?;
+        let expected_write_set = self.debugger.get_committed_write_set(version).await?;
+        let expected_events = self.debugger.get_committed_events(version).await?;
+
+        Ok(ReplayResult::new(
+            version,
+            &txn_info,
+            &output,
+            &expected_write_set,
+            &expected_events,
+        ))
+    }
+
+    /// Replays all transactions in `[begin, begin + limit)` one by one, each against the
+    /// committed state right before it.
+    pub async fn replay_transactions(
+        &self,
+        begin: Version,
+        limit: u64,
+    ) -> Result<Vec<ReplayResult>> {
+        let mut results = Vec::with_capacity(limit as usize);
+        for version in begin..begin + limit {
+            results.push(self.replay_transaction(version).await?);
+        }
+        Ok(results)
+    }
+
+    fn execute_transaction_at_version(
+        &self,
+        version: Version,
+        txn: Transaction,
+    ) -> Result<TransactionOutput> {
+        let state_view = DebuggerStateView::new(self.debugger.clone(), version);
+        let txns: Vec<SignatureVerifiedTransaction> = vec![txn.into()];
+        AptosVM::execute_block(&txns, &state_view, BlockExecutionLimit::no_limit())
+            .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?
+            .pop()
+            .ok_or_else(|| format_err!("No output for transaction at version {}", version))
+    }
+}
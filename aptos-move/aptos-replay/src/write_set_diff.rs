@@ -0,0 +1,131 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_types::{
+    state_store::state_key::StateKey,
+    write_set::{WriteOp, WriteSet},
+};
+use std::{collections::BTreeMap, fmt};
+
+/// Difference between the committed and the re-executed write op of a single state key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WriteOpDiff {
+    /// The key was written on chain, but not by the replay.
+    Missing { expected: WriteOp },
+    /// The key was written by the replay, but not on chain.
+    Unexpected { actual: WriteOp },
+    /// The key was written by both, with different ops.
+    Mismatch { expected: WriteOp, actual: WriteOp },
+}
+
+/// Per state key difference between the committed write set of a transaction and the one
+/// produced by replaying it. Keys written identically by both are omitted.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WriteSetDiff(BTreeMap<StateKey, WriteOpDiff>);
+
+impl WriteSetDiff {
+    pub fn new(expected: &WriteSet, actual: &WriteSet) -> Self {
+        let mut diff = BTreeMap::new();
+        for (key, expected_op) in expected.iter() {
+            match actual.get(key) {
+                Some(actual_op) if actual_op == expected_op => {},
+                Some(actual_op) => {
+                    diff.insert(key.clone(), WriteOpDiff::Mismatch {
+                        expected: expected_op.clone(),
+                        actual: actual_op.clone(),
+                    });
+                },
+                None => {
+                    diff.insert(key.clone(), WriteOpDiff::Missing {
+                        expected: expected_op.clone(),
+                    });
+                },
+            }
+        }
+        for (key, actual_op) in actual.iter() {
+            if expected.get(key).is_none() {
+                diff.insert(key.clone(), WriteOpDiff::Unexpected {
+                    actual: actual_op.clone(),
+                });
+            }
+        }
+        Self(diff)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&StateKey, &WriteOpDiff)> {
+        self.0.iter()
+    }
+}
+
+impl fmt::Display for WriteSetDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (key, diff) in &self.0 {
+            match diff {
+                WriteOpDiff::Missing { expected } => {
+                    writeln!(f, "- {:?}: {:?}", key, expected)?;
+                },
+                WriteOpDiff::Unexpected { actual } => {
+                    writeln!(f, "+ {:?}: {:?}", key, actual)?;
+                },
+                WriteOpDiff::Mismatch { expected, actual } => {
+                    writeln!(f, "~ {:?}: {:?} -> {:?}", key, expected, actual)?;
+                },
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::write_set::WriteSetMut;
+
+    fn write_set(ops: Vec<(u8, WriteOp)>) -> WriteSet {
+        WriteSetMut::new(
+            ops.into_iter()
+                .map(|(key, op)| (StateKey::raw(vec![key]), op))
+                .collect(),
+        )
+        .freeze()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_write_set_diff() {
+        let expected = write_set(vec![
+            (0, WriteOp::Modification(vec![0].into())),
+            (1, WriteOp::Modification(vec![1].into())),
+            (2, WriteOp::Deletion),
+        ]);
+        let actual = write_set(vec![
+            (0, WriteOp::Modification(vec![0].into())),
+            (1, WriteOp::Modification(vec![2].into())),
+            (3, WriteOp::Creation(vec![3].into())),
+        ]);
+
+        assert!(WriteSetDiff::new(&expected, &expected).is_empty());
+
+        let diff = WriteSetDiff::new(&expected, &actual);
+        assert_eq!(diff.iter().collect::<Vec<_>>(), vec![
+            (&StateKey::raw(vec![1]), &WriteOpDiff::Mismatch {
+                expected: WriteOp::Modification(vec![1].into()),
+                actual: WriteOp::Modification(vec![2].into()),
+            }),
+            (&StateKey::raw(vec![2]), &WriteOpDiff::Missing {
+                expected: WriteOp::Deletion,
+            }),
+            (&StateKey::raw(vec![3]), &WriteOpDiff::Unexpected {
+                actual: WriteOp::Creation(vec![3].into()),
+            }),
+        ]);
+    }
+}
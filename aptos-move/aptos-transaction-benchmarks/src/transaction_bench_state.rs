@@ -15,7 +15,7 @@ use aptos_language_e2e_tests::{
 use aptos_types::{
     block_executor::partitioner::PartitionedTransactions,
     block_metadata::BlockMetadata,
-    on_chain_config::{OnChainConfig, ValidatorSet},
+    on_chain_config::{BlockExecutionLimit, OnChainConfig, ValidatorSet},
     transaction::{
         analyzed_transaction::AnalyzedTransaction,
         signature_verified_transaction::{
@@ -216,7 +216,8 @@ where
             transactions,
             self.state_view.as_ref(),
             1,
-            maybe_block_gas_limit,
+            BlockExecutionLimit::with_block_gas_limit(maybe_block_gas_limit),
+            None,
             None,
         )
         .expect("VM should not fail to start");
@@ -241,7 +242,7 @@ where
                 self.state_view.clone(),
                 transactions,
                 concurrency_level_per_shard,
-                maybe_block_gas_limit,
+                BlockExecutionLimit::with_block_gas_limit(maybe_block_gas_limit),
             )
             .expect("VM should not fail to start");
         let exec_time = timer.elapsed().as_millis();
@@ -265,7 +266,8 @@ where
             transactions,
             self.state_view.as_ref(),
             concurrency_level_per_shard,
-            maybe_block_gas_limit,
+            BlockExecutionLimit::with_block_gas_limit(maybe_block_gas_limit),
+            None,
             None,
         )
         .expect("VM should not fail to start");
@@ -13,15 +13,20 @@ use aptos_types::{
     account_config::CORE_CODE_ADDRESS,
     account_state::AccountState,
     account_view::AccountView,
+    contract_event::ContractEvent,
     on_chain_config::ValidatorSet,
     state_store::{
         state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
     },
     transaction::{Transaction, TransactionInfo, Version},
+    write_set::WriteSet,
 };
 use lru::LruCache;
 use move_binary_format::file_format::CompiledModule;
-use std::sync::{Arc, Mutex};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
 
 // TODO(skedia) Clean up this interfact to remove account specific logic and move to state store
@@ -46,6 +51,12 @@ pub trait AptosValidatorInterface: Sync {
         limit: u64,
     ) -> Result<(Vec<Transaction>, Vec<TransactionInfo>)>;
 
+    /// Get the write set committed by the transaction at the given version.
+    async fn get_committed_write_set(&self, version: Version) -> Result<WriteSet>;
+
+    /// Get the events emitted by the transaction at the given version.
+    async fn get_committed_events(&self, version: Version) -> Result<Vec<ContractEvent>>;
+
     async fn get_latest_version(&self) -> Result<Version>;
 
     async fn get_version_by_account_sequence(
@@ -117,6 +128,8 @@ pub struct DebuggerStateView {
     query_sender:
         Mutex<UnboundedSender<(StateKey, Version, std::sync::mpsc::Sender<Option<Vec<u8>>>)>>,
     version: Version,
+    /// Values served instead of the ones stored on chain, e.g. to replay with another framework.
+    overrides: HashMap<StateKey, StateValue>,
 }
 
 async fn handler_thread<'a>(
@@ -167,9 +180,15 @@ impl DebuggerStateView {
         Self {
             query_sender: Mutex::new(query_sender),
             version,
+            overrides: HashMap::new(),
         }
     }
 
+    pub fn with_overrides(mut self, overrides: HashMap<StateKey, StateValue>) -> Self {
+        self.overrides = overrides;
+        self
+    }
+
     fn get_state_value_internal(
         &self,
         state_key: &StateKey,
@@ -189,6 +208,9 @@ impl TStateView for DebuggerStateView {
     type Key = StateKey;
 
     fn get_state_value(&self, state_key: &StateKey) -> Result<Option<StateValue>> {
+        if let Some(value) = self.overrides.get(state_key) {
+            return Ok(Some(value.clone()));
+        }
         self.get_state_value_internal(state_key, self.version)
     }
 
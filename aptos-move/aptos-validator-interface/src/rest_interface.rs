@@ -11,8 +11,10 @@ use aptos_rest_client::{
 use aptos_types::{
     account_address::AccountAddress,
     account_state::AccountState,
+    contract_event::ContractEvent,
     state_store::{state_key::StateKey, state_value::StateValue},
     transaction::{Transaction, TransactionInfo, Version},
+    write_set::WriteSet,
 };
 use std::collections::BTreeMap;
 
@@ -92,6 +94,26 @@ impl AptosValidatorInterface for RestDebuggerInterface {
         Ok((txns, txn_infos))
     }
 
+    async fn get_committed_write_set(&self, version: Version) -> Result<WriteSet> {
+        self.0
+            .get_transactions_bcs(Some(version), Some(1))
+            .await?
+            .into_inner()
+            .pop()
+            .map(|txn| txn.changes)
+            .ok_or_else(|| anyhow!("Transaction at version {} not found", version))
+    }
+
+    async fn get_committed_events(&self, version: Version) -> Result<Vec<ContractEvent>> {
+        self.0
+            .get_transactions_bcs(Some(version), Some(1))
+            .await?
+            .into_inner()
+            .pop()
+            .map(|txn| txn.events)
+            .ok_or_else(|| anyhow!("Transaction at version {} not found", version))
+    }
+
     async fn get_latest_version(&self) -> Result<Version> {
         Ok(self.0.get_ledger_information().await?.into_inner().version)
     }
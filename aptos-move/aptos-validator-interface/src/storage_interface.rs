@@ -3,18 +3,21 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::AptosValidatorInterface;
-use anyhow::{bail, ensure, Result};
+use anyhow::{anyhow, bail, ensure, Result};
 use aptos_config::config::{
     RocksdbConfigs, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
-    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
+    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_LEDGER_TIERING_CONFIG,
+    NO_OP_STORAGE_PRUNER_CONFIG,
 };
 use aptos_db::AptosDB;
 use aptos_storage_interface::{DbReader, MAX_REQUEST_LIMIT};
 use aptos_types::{
     account_address::AccountAddress,
     account_state::AccountState,
+    contract_event::ContractEvent,
     state_store::{state_key::StateKey, state_key_prefix::StateKeyPrefix, state_value::StateValue},
     transaction::{Transaction, TransactionInfo, Version},
+    write_set::WriteSet,
 };
 use std::{path::Path, sync::Arc};
 
@@ -26,8 +29,10 @@ impl DBDebuggerInterface {
             StorageDirPaths::from_path(db_root_path),
             true,
             NO_OP_STORAGE_PRUNER_CONFIG,
+            &NO_OP_LEDGER_TIERING_CONFIG,
             RocksdbConfigs::default(),
             false,
+            false,
             BUFFERED_STATE_TARGET_ITEMS,
             DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
         )?)))
@@ -79,6 +84,21 @@ impl AptosValidatorInterface for DBDebuggerInterface {
         Ok((txns, txn_infos))
     }
 
+    async fn get_committed_write_set(&self, version: Version) -> Result<WriteSet> {
+        self.0
+            .get_write_set_iterator(version, 1)?
+            .next()
+            .ok_or_else(|| anyhow!("Write set at version {} not found", version))?
+    }
+
+    async fn get_committed_events(&self, version: Version) -> Result<Vec<ContractEvent>> {
+        let ledger_version = self.get_latest_version().await?;
+        self.0
+            .get_transaction_by_version(version, ledger_version, true)?
+            .events
+            .ok_or_else(|| anyhow!("Events at version {} not found", version))
+    }
+
     async fn get_latest_version(&self) -> Result<Version> {
         self.0.get_latest_version()
     }
@@ -0,0 +1,115 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::change_set::VMChangeSet;
+use aptos_types::{
+    state_store::state_key::StateKey,
+    write_set::{TransactionWrite, WriteOp, WriteOpKind},
+};
+use serde::{Deserialize, Serialize};
+
+/// Kind of access to a single state item.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum AccessOp {
+    Read,
+    Creation,
+    Modification,
+    Deletion,
+    /// Aggregator V1 delta, which is a write that is only materialized after execution.
+    Delta,
+}
+
+impl From<&WriteOp> for AccessOp {
+    fn from(op: &WriteOp) -> Self {
+        match op.write_op_kind() {
+            WriteOpKind::Creation => AccessOp::Creation,
+            WriteOpKind::Modification => AccessOp::Modification,
+            WriteOpKind::Deletion => AccessOp::Deletion,
+        }
+    }
+}
+
+/// A single recorded access: which key, how, and how many bytes were read or written.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AccessTraceEntry {
+    pub key: StateKey,
+    pub op: AccessOp,
+    pub size: u64,
+}
+
+/// Ordered list of state accesses performed by a transaction. Reads are recorded in the order
+/// in which they were issued to storage, followed by the writes of the final change set.
+///
+/// Traces are opt-in and only collected when explicitly enabled on the session, since they are
+/// meant for offline tooling (debugger, access-list hint generation, audits) and not for
+/// consensus-critical execution.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AccessTrace {
+    entries: Vec<AccessTraceEntry>,
+}
+
+impl AccessTrace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record_read(&mut self, key: StateKey, size: u64) {
+        self.entries.push(AccessTraceEntry {
+            key,
+            op: AccessOp::Read,
+            size,
+        });
+    }
+
+    pub fn extend_reads(&mut self, reads: impl IntoIterator<Item = (StateKey, u64)>) {
+        for (key, size) in reads {
+            self.record_read(key, size);
+        }
+    }
+
+    /// Records all writes of the change set, including resource group writes (sized by their
+    /// encoded group size) and aggregator V1 deltas.
+    pub fn record_writes(&mut self, change_set: &VMChangeSet) {
+        for (key, op) in change_set.write_set_iter() {
+            self.entries.push(AccessTraceEntry {
+                key: key.clone(),
+                op: op.into(),
+                size: op.bytes().map_or(0, |bytes| bytes.len() as u64),
+            });
+        }
+        for (key, group_write) in change_set.resource_group_write_set() {
+            self.entries.push(AccessTraceEntry {
+                key: key.clone(),
+                op: group_write.metadata_op().into(),
+                size: group_write.encoded_group_size().unwrap_or(0),
+            });
+        }
+        for key in change_set.aggregator_v1_delta_set().keys() {
+            self.entries.push(AccessTraceEntry {
+                key: key.clone(),
+                op: AccessOp::Delta,
+                size: 0,
+            });
+        }
+    }
+
+    pub fn entries(&self) -> &[AccessTraceEntry] {
+        &self.entries
+    }
+
+    pub fn into_entries(self) -> Vec<AccessTraceEntry> {
+        self.entries
+    }
+
+    pub fn reads(&self) -> impl Iterator<Item = &AccessTraceEntry> {
+        self.entries.iter().filter(|e| e.op == AccessOp::Read)
+    }
+
+    pub fn writes(&self) -> impl Iterator<Item = &AccessTraceEntry> {
+        self.entries.iter().filter(|e| e.op != AccessOp::Read)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
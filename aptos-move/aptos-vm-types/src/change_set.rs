@@ -312,6 +312,23 @@ impl VMChangeSet {
             + self.aggregator_v1_write_set().len()
     }
 
+    /// Returns the approximate size (in bytes) of the writes in the change set. Resource
+    /// group writes are accounted for with their encoded group size after the update.
+    pub fn write_set_approx_size(&self) -> u64 {
+        let write_ops_size: u64 = self
+            .write_set_iter()
+            .map(|(key, op)| key.size() as u64 + op.bytes().map_or(0, |bytes| bytes.len() as u64))
+            .sum();
+        let group_writes_size: u64 = self
+            .resource_group_write_set()
+            .iter()
+            .map(|(key, group_write)| {
+                key.size() as u64 + group_write.encoded_group_size().unwrap_or(0)
+            })
+            .sum();
+        write_ops_size + group_writes_size
+    }
+
     pub fn write_set_iter_mut(&mut self) -> impl Iterator<Item = (&StateKey, &mut WriteOp)> {
         self.resource_write_set
             .iter_mut()
@@ -1,12 +1,14 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod access_trace;
 pub mod change_set;
 pub mod check_change_set;
 pub mod output;
 pub mod resolver;
 pub mod resource_group_adapter;
 pub mod storage;
+pub mod storage_refund;
 
 #[cfg(test)]
 mod tests;
@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::change_set::VMChangeSet;
+use crate::{access_trace::AccessTrace, change_set::VMChangeSet};
 use aptos_aggregator::resolver::AggregatorV1Resolver;
 use aptos_types::{
     contract_event::ContractEvent, //contract_event::ContractEvent,
@@ -21,6 +21,7 @@ pub struct VMOutput {
     change_set: VMChangeSet,
     fee_statement: FeeStatement,
     status: TransactionStatus,
+    access_trace: Option<AccessTrace>,
 }
 
 impl VMOutput {
@@ -33,6 +34,7 @@ impl VMOutput {
             change_set,
             fee_statement,
             status,
+            access_trace: None,
         }
     }
 
@@ -41,9 +43,24 @@ impl VMOutput {
             change_set: VMChangeSet::empty(),
             fee_statement: FeeStatement::zero(),
             status,
+            access_trace: None,
         }
     }
 
+    /// Attaches a trace of state accesses performed while producing this output.
+    pub fn with_access_trace(mut self, access_trace: AccessTrace) -> Self {
+        self.access_trace = Some(access_trace);
+        self
+    }
+
+    pub fn access_trace(&self) -> Option<&AccessTrace> {
+        self.access_trace.as_ref()
+    }
+
+    pub fn take_access_trace(&mut self) -> Option<AccessTrace> {
+        self.access_trace.take()
+    }
+
     pub fn unpack(self) -> (VMChangeSet, u64, TransactionStatus) {
         (self.change_set, self.fee_statement.gas_used(), self.status)
     }
@@ -77,7 +94,7 @@ impl VMOutput {
     /// has an empty delta set.
     /// TODO[agg_v2](fix) organize materialization paths better.
     pub fn try_materialize(
-        self,
+        mut self,
         resolver: &impl AggregatorV1Resolver,
     ) -> anyhow::Result<Self, VMStatus> {
         // First, check if output of transaction should be discarded or delta
@@ -90,16 +107,17 @@ impl VMOutput {
             return Ok(self);
         }
 
+        let access_trace = self.access_trace.take();
         let (change_set, fee_statement, status) = self.unpack_with_fee_statement();
         let materialized_change_set =
             change_set.try_materialize_aggregator_v1_delta_set(resolver)?;
         // TODO[agg_v2](fix) shouldn't be needed when reorganized
         //     .try_materialize_aggregator_v2_changes(state_view)?;
-        Ok(VMOutput::new(
-            materialized_change_set,
-            fee_statement,
-            status,
-        ))
+        let output = VMOutput::new(materialized_change_set, fee_statement, status);
+        Ok(match access_trace {
+            Some(access_trace) => output.with_access_trace(access_trace),
+            None => output,
+        })
     }
 
     /// Same as `try_materialize` but also constructs `TransactionOutput`.
@@ -111,6 +129,18 @@ impl VMOutput {
         Self::convert_to_transaction_output(materialized_output)
     }
 
+    /// Same as `try_into_transaction_output`, but also returns the access trace attached to this
+    /// output, which `TransactionOutput` cannot hold.
+    pub fn try_into_transaction_output_with_access_trace(
+        self,
+        resolver: &impl AggregatorV1Resolver,
+    ) -> anyhow::Result<(TransactionOutput, Option<AccessTrace>), VMStatus> {
+        let mut materialized_output = self.try_materialize(resolver)?;
+        let access_trace = materialized_output.take_access_trace();
+        let output = Self::convert_to_transaction_output(materialized_output)?;
+        Ok((output, access_trace))
+    }
+
     /// Same as `try_materialize` but also constructs `TransactionOutput`.
     pub fn into_transaction_output(self) -> anyhow::Result<TransactionOutput, VMStatus> {
         let (change_set, fee_statement, status) = self.unpack_with_fee_statement();
@@ -5,12 +5,14 @@ use aptos_aggregator::{
     resolver::{TAggregatorV1View, TDelayedFieldView},
     types::DelayedFieldID,
 };
+use aptos_crypto::HashValue;
 use aptos_state_view::{StateView, StateViewId};
 use aptos_types::{
     state_store::{
         state_key::StateKey,
         state_storage_usage::StateStorageUsage,
         state_value::{StateValue, StateValueMetadataKind},
+        table::TableHandle,
     },
     write_set::WriteOp,
 };
@@ -51,6 +53,15 @@ pub trait TResourceView {
             .map(|maybe_state_value| maybe_state_value.map(StateValue::into_metadata))
     }
 
+    /// Returns the size of the resource in bytes, or None if it does not exist. During
+    /// parallel execution, the size is that of the latest speculative value, and reading
+    /// only the size creates a weaker dependency than reading the whole value.
+    fn get_resource_state_value_size(&self, state_key: &Self::Key) -> anyhow::Result<Option<u64>> {
+        // For size, layouts are not important.
+        self.get_resource_state_value(state_key, None)
+            .map(|maybe_state_value| maybe_state_value.map(|state_value| state_value.size() as u64))
+    }
+
     fn resource_exists(&self, state_key: &Self::Key) -> anyhow::Result<bool> {
         // For existence, layouts are not important.
         self.get_resource_state_value(state_key, None)
@@ -175,6 +186,17 @@ pub trait StateStorageView {
     fn id(&self) -> StateViewId;
 
     fn get_usage(&self) -> anyhow::Result<StateStorageUsage>;
+
+    /// Enumerates the keys of a table, see `TStateView::get_table_item_keys`. Only views backed
+    /// directly by storage support this, and the VM only uses it in view functions.
+    fn get_table_item_keys(
+        &self,
+        _handle: &TableHandle,
+        _start_after: Option<HashValue>,
+        _limit: usize,
+    ) -> anyhow::Result<Option<(Vec<Vec<u8>>, Option<HashValue>)>> {
+        Ok(None)
+    }
 }
 
 /// A fine-grained view of the state during execution.
@@ -271,6 +293,15 @@ where
     fn get_usage(&self) -> anyhow::Result<StateStorageUsage> {
         self.get_usage()
     }
+
+    fn get_table_item_keys(
+        &self,
+        handle: &TableHandle,
+        start_after: Option<HashValue>,
+        limit: usize,
+    ) -> anyhow::Result<Option<(Vec<Vec<u8>>, Option<HashValue>)>> {
+        self.get_table_item_keys(handle, start_after, limit)
+    }
 }
 
 /// Allows to query storage metadata in the VM session. Needed for storage refunds.
@@ -0,0 +1,164 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Storage fees for state writes: the refundable deposit charged when a state slot is allocated
+//! (recorded in the metadata of the slot and refunded when the slot is freed), and the
+//! non-refundable fee for the bytes of the written value.
+
+use aptos_gas_algebra::{Fee, FeePerByte, FeePerSlot, NumSlots};
+use aptos_gas_schedule::TransactionGasParameters;
+use aptos_types::{
+    on_chain_config::CurrentTimeMicroseconds,
+    state_store::{
+        state_key::StateKey,
+        state_value::{StateValueMetadata, StateValueMetadataKind},
+    },
+    write_set::WriteOp,
+};
+use move_core_types::gas_algebra::NumBytes;
+
+/// Storage fees charged and refunded for a single state write.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct StorageFeeChange {
+    /// Refundable fee for allocating a new slot, kept as the deposit in the slot metadata.
+    pub slot_fee: Fee,
+    /// Non-refundable fee for the bytes of the value exceeding the free quota.
+    pub bytes_fee: Fee,
+    /// Deposit refunded for freeing a slot.
+    pub refund: Fee,
+}
+
+impl StorageFeeChange {
+    /// Returns the total amount charged for the write.
+    pub fn charge(&self) -> Fee {
+        self.slot_fee + self.bytes_fee
+    }
+}
+
+/// Computes storage fees and refunds of state writes from the gas parameters.
+#[derive(Clone, Debug)]
+pub struct StorageRefundCalculator {
+    fee_per_slot_create: FeePerSlot,
+    free_write_bytes_quota: NumBytes,
+    fee_per_excess_state_byte: FeePerByte,
+}
+
+impl StorageRefundCalculator {
+    pub fn new(txn_gas_params: &TransactionGasParameters) -> Self {
+        Self {
+            fee_per_slot_create: txn_gas_params.storage_fee_per_state_slot_create,
+            free_write_bytes_quota: txn_gas_params.free_write_bytes_quota,
+            fee_per_excess_state_byte: txn_gas_params.storage_fee_per_excess_state_byte,
+        }
+    }
+
+    /// Returns the metadata for a newly allocated slot. The deposit is not known until the
+    /// storage fee is charged, so it is set to 0 and later recorded with `record_deposit`.
+    pub fn new_slot_metadata(creation_time: &CurrentTimeMicroseconds) -> StateValueMetadata {
+        StateValueMetadata::new(0, creation_time)
+    }
+
+    /// Calculates the fees for a write to the slot at the given key. `before` is the metadata
+    /// of the slot prior to the write (None if the slot did not exist, Some(None) if it has no
+    /// metadata), and `after_value_size` is the size of the value after the write (None if the
+    /// slot is freed).
+    pub fn calculate(
+        &self,
+        key: &StateKey,
+        before: Option<&StateValueMetadataKind>,
+        after_value_size: Option<u64>,
+    ) -> StorageFeeChange {
+        let slot_fee = match (before, after_value_size) {
+            (None, Some(_)) => self.fee_per_slot_create * NumSlots::new(1),
+            _ => 0.into(),
+        };
+        let refund = match (before, after_value_size) {
+            (Some(Some(metadata)), None) => Fee::new(metadata.deposit()),
+            _ => 0.into(),
+        };
+        StorageFeeChange {
+            slot_fee,
+            bytes_fee: self.bytes_fee(key, after_value_size),
+            refund,
+        }
+    }
+
+    /// Calculates the fees for a write op, with the metadata prior to the write derived from
+    /// the op. The value size is passed separately, as for resource groups it is the size of
+    /// the whole group rather than of the bytes in the op.
+    pub fn calculate_for_write_op(
+        &self,
+        key: &StateKey,
+        op: &WriteOp,
+        value_size: Option<u64>,
+    ) -> StorageFeeChange {
+        self.calculate(key, Self::metadata_before(op).as_ref(), value_size)
+    }
+
+    /// Returns the refundable fee for allocating the slot written by the op.
+    pub fn slot_fee(&self, op: &WriteOp) -> Fee {
+        match Self::metadata_before(op) {
+            None => self.fee_per_slot_create * NumSlots::new(1),
+            Some(_) => 0.into(),
+        }
+    }
+
+    /// Returns the deposit refunded when the op frees a slot.
+    pub fn refund(&self, op: &WriteOp) -> Fee {
+        match op {
+            WriteOp::DeletionWithMetadata { metadata } => Fee::new(metadata.deposit()),
+            WriteOp::Creation(..)
+            | WriteOp::CreationWithMetadata { .. }
+            | WriteOp::Modification(..)
+            | WriteOp::ModificationWithMetadata { .. }
+            | WriteOp::Deletion => 0.into(),
+        }
+    }
+
+    /// Returns the fee for the bytes of the key and value beyond the free quota. The value
+    /// size is None for deletions, which are free.
+    pub fn bytes_fee(&self, key: &StateKey, maybe_value_size: Option<u64>) -> Fee {
+        if let Some(value_size) = maybe_value_size {
+            let size = NumBytes::new(key.size() as u64) + NumBytes::new(value_size);
+            if let Some(excess) = size.checked_sub(self.free_write_bytes_quota) {
+                return excess * self.fee_per_excess_state_byte;
+            }
+        }
+
+        0.into()
+    }
+
+    /// Records the slot fee as the deposit in the metadata of a newly allocated slot, so that
+    /// it ends up in the state DB and can be refunded when the slot is freed.
+    pub fn record_deposit(op: &mut WriteOp, slot_fee: Fee) {
+        use WriteOp::*;
+
+        match op {
+            CreationWithMetadata {
+                ref mut metadata,
+                data: _,
+            } => {
+                if !slot_fee.is_zero() {
+                    metadata.set_deposit(slot_fee.into())
+                }
+            },
+            Creation(..)
+            | Modification(..)
+            | Deletion
+            | ModificationWithMetadata { .. }
+            | DeletionWithMetadata { .. } => {},
+        }
+    }
+
+    fn metadata_before(op: &WriteOp) -> Option<StateValueMetadataKind> {
+        use WriteOp::*;
+
+        match op {
+            Creation(..) | CreationWithMetadata { .. } => None,
+            Modification(..) | Deletion => Some(None),
+            ModificationWithMetadata { metadata, .. } | DeletionWithMetadata { metadata } => {
+                Some(Some(metadata.clone()))
+            },
+        }
+    }
+}
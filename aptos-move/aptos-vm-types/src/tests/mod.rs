@@ -1,9 +1,13 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(test)]
+mod test_access_trace;
 #[cfg(test)]
 mod test_change_set;
 #[cfg(test)]
 mod test_output;
 #[cfg(test)]
+mod test_storage_refund;
+#[cfg(test)]
 pub(crate) mod utils;
@@ -0,0 +1,77 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    access_trace::{AccessOp, AccessTrace, AccessTraceEntry},
+    tests::utils::{
+        as_state_key, build_vm_output, mock_add, mock_create_with_layout, mock_delete, mock_modify,
+    },
+};
+use aptos_language_e2e_tests::data_store::FakeDataStore;
+use aptos_types::state_store::state_key::StateKey;
+use claims::{assert_none, assert_ok, assert_some_eq};
+
+fn key(k: impl ToString) -> StateKey {
+    as_state_key!(k)
+}
+
+fn entry(k: impl ToString, op: AccessOp, size: u64) -> AccessTraceEntry {
+    AccessTraceEntry {
+        key: key(k),
+        op,
+        size,
+    }
+}
+
+#[test]
+fn test_access_trace_records_reads_and_writes() {
+    let vm_output = build_vm_output(
+        vec![mock_create_with_layout("0", 0, None)],
+        vec![],
+        vec![mock_modify("1", 1)],
+        vec![mock_delete("2")],
+        vec![mock_add("3", 3)],
+        vec![],
+    );
+
+    let mut trace = AccessTrace::new();
+    trace.extend_reads(vec![(key("4"), 10), (key("1"), 16)]);
+    trace.record_writes(vm_output.change_set());
+
+    assert_eq!(trace.entries(), &[
+        entry("4", AccessOp::Read, 10),
+        entry("1", AccessOp::Read, 16),
+        entry("0", AccessOp::Creation, 16),
+        entry("1", AccessOp::Modification, 16),
+        entry("2", AccessOp::Deletion, 0),
+        entry("3", AccessOp::Delta, 0),
+    ]);
+    assert_eq!(trace.reads().count(), 2);
+    assert_eq!(trace.writes().count(), 4);
+}
+
+#[test]
+fn test_access_trace_survives_materialization() {
+    let state_view = FakeDataStore::default();
+    let vm_output = build_vm_output(
+        vec![mock_create_with_layout("0", 0, None)],
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+        vec![],
+    );
+    assert_none!(vm_output.access_trace());
+
+    let mut trace = AccessTrace::new();
+    trace.record_read(key("0"), 0);
+    let vm_output = vm_output.with_access_trace(trace.clone());
+
+    let materialized_vm_output = assert_ok!(vm_output.clone().try_materialize(&state_view));
+    assert_some_eq!(materialized_vm_output.access_trace(), &trace);
+
+    // The trace is handed out next to the transaction output instead of being dropped.
+    let (_, output_trace) =
+        assert_ok!(vm_output.try_into_transaction_output_with_access_trace(&state_view));
+    assert_some_eq!(output_trace, trace);
+}
@@ -0,0 +1,110 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::storage_refund::{StorageFeeChange, StorageRefundCalculator};
+use aptos_gas_algebra::{Fee, NumSlots};
+use aptos_gas_schedule::{InitialGasSchedule, TransactionGasParameters};
+use aptos_types::{
+    on_chain_config::CurrentTimeMicroseconds,
+    state_store::{state_key::StateKey, state_value::StateValueMetadata},
+    write_set::WriteOp,
+};
+use move_core_types::gas_algebra::NumBytes;
+
+fn metadata(deposit: u64) -> StateValueMetadata {
+    StateValueMetadata::new(deposit, &CurrentTimeMicroseconds { microseconds: 1 })
+}
+
+fn calculator() -> (StorageRefundCalculator, Fee) {
+    let params = TransactionGasParameters::initial();
+    let slot_fee = params.storage_fee_per_state_slot_create * NumSlots::new(1);
+    (StorageRefundCalculator::new(&params), slot_fee)
+}
+
+#[test]
+fn test_slot_fee_and_refund() {
+    let (calculator, slot_fee) = calculator();
+    let key = StateKey::raw(vec![0]);
+
+    // Allocating a slot charges the deposit.
+    let change = calculator.calculate(&key, None, Some(10));
+    assert_eq!(change, StorageFeeChange {
+        slot_fee,
+        bytes_fee: 0.into(),
+        refund: 0.into(),
+    });
+
+    // Modifications neither charge nor refund a deposit.
+    let change = calculator.calculate(&key, Some(&Some(metadata(100))), Some(10));
+    assert_eq!(change.slot_fee, 0.into());
+    assert_eq!(change.refund, 0.into());
+
+    // Freeing a slot refunds the recorded deposit, or nothing for slots without metadata.
+    let change = calculator.calculate(&key, Some(&Some(metadata(100))), None);
+    assert_eq!(change, StorageFeeChange {
+        slot_fee: 0.into(),
+        bytes_fee: 0.into(),
+        refund: 100.into(),
+    });
+    let change = calculator.calculate(&key, Some(&None), None);
+    assert_eq!(change.refund, 0.into());
+}
+
+#[test]
+fn test_bytes_fee() {
+    let params = TransactionGasParameters::initial();
+    let calculator = StorageRefundCalculator::new(&params);
+    let key = StateKey::raw(vec![0]);
+    let free_value_size = u64::from(params.free_write_bytes_quota) - key.size() as u64;
+
+    assert_eq!(calculator.bytes_fee(&key, None), 0.into());
+    assert_eq!(calculator.bytes_fee(&key, Some(free_value_size)), 0.into());
+    assert_eq!(
+        calculator.bytes_fee(&key, Some(free_value_size + 10)),
+        NumBytes::new(10) * params.storage_fee_per_excess_state_byte
+    );
+}
+
+#[test]
+fn test_write_op_fees_are_consistent() {
+    let (calculator, slot_fee) = calculator();
+    let key = StateKey::raw(vec![0]);
+
+    for op in [
+        WriteOp::Creation(vec![1].into()),
+        WriteOp::CreationWithMetadata {
+            data: vec![1].into(),
+            metadata: metadata(0),
+        },
+        WriteOp::Modification(vec![1].into()),
+        WriteOp::ModificationWithMetadata {
+            data: vec![1].into(),
+            metadata: metadata(100),
+        },
+        WriteOp::Deletion,
+        WriteOp::DeletionWithMetadata {
+            metadata: metadata(100),
+        },
+    ] {
+        let value_size = op.bytes().map(|data| data.len() as u64);
+        let change = calculator.calculate_for_write_op(&key, &op, value_size);
+        assert_eq!(change.slot_fee, calculator.slot_fee(&op));
+        assert_eq!(change.refund, calculator.refund(&op));
+        assert_eq!(change.bytes_fee, calculator.bytes_fee(&key, value_size));
+        assert_eq!(change.charge(), change.slot_fee + change.bytes_fee);
+    }
+
+    let mut op = WriteOp::CreationWithMetadata {
+        data: vec![1].into(),
+        metadata: StorageRefundCalculator::new_slot_metadata(&CurrentTimeMicroseconds {
+            microseconds: 1,
+        }),
+    };
+    StorageRefundCalculator::record_deposit(&mut op, slot_fee);
+    assert_eq!(
+        calculator.refund(&WriteOp::DeletionWithMetadata {
+            metadata: op.metadata().unwrap().clone(),
+        }),
+        slot_fee
+    );
+}
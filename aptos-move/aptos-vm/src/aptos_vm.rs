@@ -2,6 +2,8 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+#[cfg(feature = "execution-tracing")]
+use crate::execution_tracer::{ExecutionTracerFactory, TracingGasMeter};
 use crate::{
     aptos_vm_impl::{get_transaction_output, AptosVMImpl, AptosVMInternals},
     block_executor::{AptosTransactionOutput, BlockAptosVM},
@@ -9,20 +11,33 @@ use crate::{
     data_cache::{AsMoveResolver, StorageAdapter},
     errors::expect_only_successful_execution,
     move_vm_ext::{
-        get_max_binary_format_version, AptosMoveResolver, RespawnedSession, SessionExt, SessionId,
+        get_max_binary_format_version, verifier_config, AptosMoveResolver, RespawnedSession,
+        SessionExt, SessionId,
     },
     sharded_block_executor::{executor_client::ExecutorClient, ShardedBlockExecutor},
+    simulation_output_cache::SimulationOutputCache,
     system_module_names::*,
     transaction_metadata::TransactionMetadata,
-    verifier, VMExecutor, VMValidator,
+    verifier::{
+        self,
+        module_compatibility::{
+            check_bundle_compatibility, ModuleVerificationError, PublishCompatibilityReport,
+        },
+    },
+    view_function::{StackDepthLimitedGasMeter, ViewFunctionError, ViewFunctionLimits},
+    VMExecutor, VMValidator,
+};
+use anyhow::Result;
+use aptos_block_executor::{
+    concurrency_controller::ConcurrencyController, txn_commit_hook::NoOpTransactionCommitHook,
 };
-use anyhow::{anyhow, Result};
-use aptos_block_executor::txn_commit_hook::NoOpTransactionCommitHook;
 use aptos_crypto::HashValue;
-use aptos_framework::natives::code::PublishRequest;
+use aptos_framework::natives::{code::PublishRequest, randomness::RandomnessContext};
 use aptos_gas_algebra::Gas;
 use aptos_gas_meter::{AptosGasMeter, StandardGasAlgebra, StandardGasMeter};
+use aptos_gas_profiling::{publish_block_gas_profile, BlockGasProfile, GasProfiler};
 use aptos_gas_schedule::VMGasParameters;
+use aptos_infallible::Mutex;
 use aptos_logger::{enabled, prelude::*, Level};
 use aptos_memory_usage_tracker::MemoryTrackedGasMeter;
 use aptos_state_view::StateView;
@@ -31,17 +46,21 @@ use aptos_types::{
     account_config::new_block_event_key,
     block_executor::partitioner::PartitionedTransactions,
     block_metadata::BlockMetadata,
+    block_metadata_ext::BlockMetadataExt,
     fee_statement::FeeStatement,
-    on_chain_config::{new_epoch_event_key, FeatureFlag, TimedFeatureOverride},
+    on_chain_config::{
+        new_epoch_event_key, BlockExecutionLimit, FeatureFlag, GasScheduleV2, TimedFeatureOverride,
+    },
     transaction::{
         signature_verified_transaction::SignatureVerifiedTransaction,
         EntryFunction, ExecutionError, ExecutionStatus, ModuleBundle, Multisig,
         MultisigTransactionPayload, SignatureCheckedTransaction, SignedTransaction,
         Transaction::{
-            BlockMetadata as BlockMetadataTransaction, GenesisTransaction, StateCheckpoint,
+            BlockMetadata as BlockMetadataTransaction,
+            BlockMetadataExt as BlockMetadataExtTransaction, GenesisTransaction, StateCheckpoint,
             UserTransaction,
         },
-        TransactionOutput, TransactionPayload, TransactionStatus, VMValidatorResult,
+        TransactionOutput, TransactionPayload, TransactionStatus, VMValidatorResult, Version,
         WriteSetPayload,
     },
     vm_status::{AbortLocation, StatusCode, VMStatus},
@@ -49,6 +68,7 @@ use aptos_types::{
 use aptos_utils::{aptos_try, return_on_failure};
 use aptos_vm_logging::{log_schema::AdapterLogSchema, speculative_error, speculative_log};
 use aptos_vm_types::{
+    access_trace::AccessTrace,
     change_set::VMChangeSet,
     output::VMOutput,
     resolver::{ExecutorView, ResourceGroupView},
@@ -87,11 +107,19 @@ use std::{
 };
 
 static EXECUTION_CONCURRENCY_LEVEL: OnceCell<usize> = OnceCell::new();
+static CONCURRENCY_CONTROLLER: OnceCell<Arc<ConcurrencyController>> = OnceCell::new();
+static BLOCK_GAS_PROFILING: OnceCell<bool> = OnceCell::new();
+static SIMULATION_OUTPUT_CACHE: OnceCell<SimulationOutputCache> = OnceCell::new();
+/// Gas logs of the transactions of the block being executed, if block gas profiling is enabled.
+static CURRENT_BLOCK_GAS_PROFILE: Lazy<Mutex<BlockGasProfile>> =
+    Lazy::new(|| Mutex::new(BlockGasProfile::new()));
 static NUM_EXECUTION_SHARD: OnceCell<usize> = OnceCell::new();
 static NUM_PROOF_READING_THREADS: OnceCell<usize> = OnceCell::new();
 static PARANOID_TYPE_CHECKS: OnceCell<bool> = OnceCell::new();
 static PROCESSED_TRANSACTIONS_DETAILED_COUNTERS: OnceCell<bool> = OnceCell::new();
 static TIMED_FEATURE_OVERRIDE: OnceCell<TimedFeatureOverride> = OnceCell::new();
+#[cfg(feature = "execution-tracing")]
+static EXECUTION_TRACER: OnceCell<Arc<dyn ExecutionTracerFactory>> = OnceCell::new();
 
 pub static RAYON_EXEC_POOL: Lazy<Arc<rayon::ThreadPool>> = Lazy::new(|| {
     Arc::new(
@@ -133,6 +161,15 @@ impl AptosVM {
         Self(AptosVMImpl::new(resolver))
     }
 
+    /// Creates the VM with the given gas schedule, bypassing the on-chain gas schedule. Meant
+    /// for local simulation only: the outputs of such a VM must not be committed.
+    pub fn new_with_gas_override(
+        resolver: &impl AptosMoveResolver,
+        gas_schedule: GasScheduleV2,
+    ) -> Self {
+        Self(AptosVMImpl::new_with_gas_override(resolver, &gas_schedule))
+    }
+
     pub fn new_from_state_view(state_view: &impl StateView) -> Self {
         Self(AptosVMImpl::new(&state_view.as_move_resolver()))
     }
@@ -157,12 +194,59 @@ impl AptosVM {
     ///
     /// The concurrency level is fixed to 1 if gas profiling is enabled.
     pub fn get_concurrency_level() -> usize {
+        if Self::get_block_gas_profiling() {
+            return 1;
+        }
         match EXECUTION_CONCURRENCY_LEVEL.get() {
             Some(concurrency_level) => *concurrency_level,
             None => 1,
         }
     }
 
+    /// Enables profiling the gas usage of all user transactions in each executed block when
+    /// invoked the first time. The profile of the latest block is published through
+    /// `aptos_gas_profiling::latest_block_gas_profile`. Blocks are executed sequentially while
+    /// profiling, so that every transaction is profiled exactly once.
+    pub fn set_block_gas_profiling_once(enable: bool) {
+        // Only the first call succeeds, due to OnceCell semantics.
+        BLOCK_GAS_PROFILING.set(enable).ok();
+    }
+
+    /// Get whether block gas profiling is enabled, by default disabled.
+    pub fn get_block_gas_profiling() -> bool {
+        match BLOCK_GAS_PROFILING.get() {
+            Some(enable) => *enable,
+            None => false,
+        }
+    }
+
+    /// Enables adapting the concurrency level of each block to the conflicts observed in
+    /// previous blocks, between the given minimum and the configured concurrency level, when
+    /// invoked the first time. Must be called after the concurrency level is set.
+    pub fn set_adaptive_concurrency_once(min_concurrency_level: usize) {
+        let controller =
+            ConcurrencyController::new(min_concurrency_level, Self::get_concurrency_level());
+        // Only the first call succeeds, due to OnceCell semantics.
+        CONCURRENCY_CONTROLLER.set(Arc::new(controller)).ok();
+    }
+
+    /// Enables caching the outputs of simulated transactions when invoked the first time, so
+    /// that simulating the same transaction against an unchanged state does not re-execute it.
+    pub fn set_simulation_output_cache_once(capacity: usize) {
+        // Only the first call succeeds, due to OnceCell semantics.
+        SIMULATION_OUTPUT_CACHE
+            .set(SimulationOutputCache::new(capacity))
+            .ok();
+    }
+
+    /// Invalidates cached outputs computed against states older than the given version, which
+    /// is expected to be the version of the latest committed state checkpoint.
+    pub fn advance_simulation_output_cache(version: Version) {
+        if let Some(cache) = SIMULATION_OUTPUT_CACHE.get() {
+            cache.advance_checkpoint(version);
+        }
+    }
+
     pub fn set_num_shards_once(mut num_shards: usize) {
         num_shards = max(num_shards, 1);
         // Only the first call succeeds, due to OnceCell semantics.
@@ -230,6 +314,13 @@ impl AptosVM {
         }
     }
 
+    /// Installs the factory of tracers for user transactions when invoked the first time.
+    #[cfg(feature = "execution-tracing")]
+    pub fn set_execution_tracer_once(factory: Arc<dyn ExecutionTracerFactory>) {
+        // Only the first call succeeds, due to OnceCell semantics.
+        EXECUTION_TRACER.set(factory).ok();
+    }
+
     pub fn internals(&self) -> AptosVMInternals {
         AptosVMInternals::new(&self.0)
     }
@@ -492,6 +583,16 @@ impl AptosVM {
                     )?;
                 },
                 TransactionPayload::EntryFunction(script_fn) => {
+                    // Only a direct call to a private or friend entry function can consume
+                    // randomness: nothing else can observe the outcome and abort on it.
+                    if session
+                        .is_friend_or_private_function(script_fn.module(), script_fn.function())?
+                    {
+                        session
+                            .get_native_extensions()
+                            .get_mut::<RandomnessContext>()
+                            .mark_unbiasable();
+                    }
                     self.validate_and_execute_entry_function(
                         &mut session,
                         gas_meter,
@@ -499,6 +600,18 @@ impl AptosVM {
                         script_fn,
                     )?;
                 },
+                TransactionPayload::Batched(entry_fns) => {
+                    // All calls share the session, so later calls observe the writes of
+                    // earlier ones, and a failure in any of them drops the writes of all.
+                    for entry_fn in entry_fns {
+                        self.validate_and_execute_entry_function(
+                            &mut session,
+                            gas_meter,
+                            txn_data.senders(),
+                            entry_fn,
+                        )?;
+                    }
+                },
 
                 // Not reachable as this function should only be invoked for entry or script
                 // transaction payload.
@@ -1109,6 +1222,14 @@ impl AptosVM {
         allow_too_new: bool,
         log_context: &AdapterLogSchema,
     ) -> Result<(), VMStatus> {
+        if transaction.authenticator_ref().has_webauthn_signature()
+            && !self
+                .0
+                .get_features()
+                .is_enabled(FeatureFlag::WEBAUTHN_SIGNATURE)
+        {
+            return Err(VMStatus::error(StatusCode::FEATURE_UNDER_GATING, None));
+        }
         self.check_transaction_format(transaction)?;
 
         let prologue_status = self.run_prologue(session, resolver, transaction, log_context);
@@ -1183,17 +1304,17 @@ impl AptosVM {
         let mut new_published_modules_loaded = false;
         let result = match txn.payload() {
             payload @ TransactionPayload::Script(_)
-            | payload @ TransactionPayload::EntryFunction(_) => self
-                .execute_script_or_entry_function(
-                    resolver,
-                    session,
-                    gas_meter,
-                    &txn_data,
-                    payload,
-                    log_context,
-                    &mut new_published_modules_loaded,
-                    &storage_gas_params.change_set_configs,
-                ),
+            | payload @ TransactionPayload::EntryFunction(_)
+            | payload @ TransactionPayload::Batched(_) => self.execute_script_or_entry_function(
+                resolver,
+                session,
+                gas_meter,
+                &txn_data,
+                payload,
+                log_context,
+                &mut new_published_modules_loaded,
+                &storage_gas_params.change_set_configs,
+            ),
             TransactionPayload::Multisig(payload) => self.execute_multisig_transaction(
                 resolver,
                 session,
@@ -1268,6 +1389,34 @@ impl AptosVM {
         // TODO: would we end up having a diverging behavior by creating the gas meter at an earlier time?
         let mut gas_meter = unwrap_or_discard!(self.make_standard_gas_meter(balance, log_context));
 
+        #[cfg(feature = "execution-tracing")]
+        if let Some(factory) = EXECUTION_TRACER.get() {
+            let mut gas_meter = TracingGasMeter::new(gas_meter, factory.new_tracer(txn));
+            let (vm_status, vm_output) =
+                self.execute_user_transaction_impl(resolver, txn, log_context, &mut gas_meter);
+            gas_meter.finish(&vm_output);
+            return (vm_status, vm_output);
+        }
+
+        if Self::get_block_gas_profiling() {
+            let mut gas_meter = match txn.payload() {
+                TransactionPayload::EntryFunction(entry_func) => GasProfiler::new_function(
+                    gas_meter,
+                    entry_func.module().clone(),
+                    entry_func.function().to_owned(),
+                    entry_func.ty_args().to_vec(),
+                ),
+                // Other payloads are attributed to a script frame.
+                _ => GasProfiler::new_script(gas_meter),
+            };
+            let (vm_status, vm_output) =
+                self.execute_user_transaction_impl(resolver, txn, log_context, &mut gas_meter);
+            CURRENT_BLOCK_GAS_PROFILE
+                .lock()
+                .record_transaction(&gas_meter.finish());
+            return (vm_status, vm_output);
+        }
+
         self.execute_user_transaction_impl(resolver, txn, log_context, &mut gas_meter)
     }
 
@@ -1296,6 +1445,24 @@ impl AptosVM {
         Ok((status, output, gas_meter))
     }
 
+    /// Same as `execute_user_transaction`, but also records every state item read or written by
+    /// the transaction. The trace is attached to the returned `VMOutput`, and only covers this
+    /// invocation: transactions executed by other calls are never traced.
+    pub fn execute_user_transaction_with_access_trace(
+        &self,
+        resolver: &impl AptosMoveResolver,
+        txn: &SignatureCheckedTransaction,
+        log_context: &AdapterLogSchema,
+    ) -> (VMStatus, VMOutput) {
+        resolver.enable_access_trace();
+        let (vm_status, vm_output) = self.execute_user_transaction(resolver, txn, log_context);
+
+        let mut access_trace = AccessTrace::new();
+        access_trace.extend_reads(resolver.take_recorded_reads().unwrap_or_default());
+        access_trace.record_writes(vm_output.change_set());
+        (vm_status, vm_output.with_access_trace(access_trace))
+    }
+
     fn execute_write_set(
         &self,
         resolver: &impl AptosMoveResolver,
@@ -1481,12 +1648,114 @@ impl AptosVM {
         Ok((VMStatus::Executed, output))
     }
 
+    fn process_block_prologue_ext(
+        &self,
+        resolver: &impl AptosMoveResolver,
+        block_metadata_ext: BlockMetadataExt,
+        log_context: &AdapterLogSchema,
+    ) -> Result<(VMStatus, VMOutput), VMStatus> {
+        fail_point!("move_adapter::process_block_prologue_ext", |_| {
+            Err(VMStatus::error(
+                StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR,
+                None,
+            ))
+        });
+
+        // Blocks without randomness are processed exactly like the plain block metadata.
+        if let BlockMetadataExt::V0(block_metadata) = block_metadata_ext {
+            return self.process_block_prologue(resolver, block_metadata, log_context);
+        }
+
+        let txn_data = TransactionMetadata {
+            sender: account_config::reserved_vm_address(),
+            max_gas_amount: 0.into(),
+            ..Default::default()
+        };
+        let mut gas_meter = UnmeteredGasMeter;
+        let mut session = self
+            .0
+            .new_session(resolver, SessionId::block_meta_ext(&block_metadata_ext));
+
+        let args =
+            serialize_values(&block_metadata_ext.get_prologue_ext_move_args(txn_data.sender));
+        session
+            .execute_function_bypass_visibility(
+                &BLOCK_MODULE,
+                BLOCK_PROLOGUE_EXT,
+                vec![],
+                args,
+                &mut gas_meter,
+            )
+            .map(|_return_vals| ())
+            .or_else(|e| {
+                expect_only_successful_execution(e, BLOCK_PROLOGUE_EXT.as_str(), log_context)
+            })?;
+        SYSTEM_TRANSACTIONS_EXECUTED.inc();
+
+        let output = get_transaction_output(
+            &mut (),
+            session,
+            FeeStatement::zero(),
+            ExecutionStatus::Success,
+            &self
+                .0
+                .get_storage_gas_parameters(log_context)?
+                .change_set_configs,
+        )?;
+        Ok((VMStatus::Executed, output))
+    }
+
     /// Executes a SignedTransaction without performing signature verification.
     pub fn simulate_signed_transaction(
         txn: &SignedTransaction,
         executor_view: &impl ExecutorView,
     ) -> (VMStatus, TransactionOutput) {
         let vm = AptosVM::new(&StorageAdapter::from_borrowed(executor_view));
+        Self::simulate_signed_transaction_with_vm(vm, txn, executor_view)
+    }
+
+    /// Like `simulate_signed_transaction`, but reuses the output of a previous simulation of the
+    /// same transaction against the state at the same version, if the execution output cache is
+    /// enabled. The executor view must be the state at the given version.
+    pub fn simulate_signed_transaction_at_version(
+        txn: &SignedTransaction,
+        executor_view: &impl ExecutorView,
+        version: Version,
+    ) -> (VMStatus, TransactionOutput) {
+        let cache = match SIMULATION_OUTPUT_CACHE.get() {
+            Some(cache) => cache,
+            None => return Self::simulate_signed_transaction(txn, executor_view),
+        };
+
+        let txn_hash = txn.clone().committed_hash();
+        if let Some(output) = cache.get(&txn_hash, version) {
+            return output;
+        }
+        let output = Self::simulate_signed_transaction(txn, executor_view);
+        cache.insert(txn_hash, version, output.clone());
+        output
+    }
+
+    /// Executes a SignedTransaction without performing signature verification, using the given
+    /// gas schedule instead of the on-chain one. Allows to estimate the effects of gas schedule
+    /// changes before they are proposed.
+    pub fn simulate_signed_transaction_with_gas_override(
+        txn: &SignedTransaction,
+        executor_view: &impl ExecutorView,
+        gas_schedule: GasScheduleV2,
+    ) -> (VMStatus, TransactionOutput) {
+        let vm = AptosVM::new_with_gas_override(
+            &StorageAdapter::from_borrowed(executor_view),
+            gas_schedule,
+        );
+        Self::simulate_signed_transaction_with_vm(vm, txn, executor_view)
+    }
+
+    fn simulate_signed_transaction_with_vm(
+        vm: AptosVM,
+        txn: &SignedTransaction,
+        executor_view: &impl ExecutorView,
+    ) -> (VMStatus, TransactionOutput) {
         let simulation_vm = AptosSimulationVM(vm);
         let log_context = AdapterLogSchema::new(executor_view.id(), 0);
 
@@ -1507,19 +1776,21 @@ impl AptosVM {
         func_name: Identifier,
         type_args: Vec<TypeTag>,
         arguments: Vec<Vec<u8>>,
-        gas_budget: u64,
-    ) -> Result<Vec<Vec<u8>>> {
+        limits: ViewFunctionLimits,
+    ) -> Result<Vec<Vec<u8>>, ViewFunctionError> {
         let vm = AptosVM::new_from_state_view(state_view);
         let log_context = AdapterLogSchema::new(state_view.id(), 0);
-        let mut gas_meter =
+        let mut gas_meter = StackDepthLimitedGasMeter::new(
             MemoryTrackedGasMeter::new(StandardGasMeter::new(StandardGasAlgebra::new(
                 vm.0.get_gas_feature_version(),
                 vm.0.get_gas_parameters(&log_context)?.vm.clone(),
                 vm.0.get_storage_gas_parameters(&log_context)?.clone(),
-                gas_budget,
-            )));
+                limits.max_gas,
+            ))),
+            limits.max_stack_depth,
+        );
 
-        let resolver = vm.as_move_resolver(&state_view);
+        let resolver = vm.as_move_resolver(&state_view).with_table_key_iteration();
         let mut session = vm.0.new_session(&resolver, SessionId::Void);
 
         let func_inst = session.load_function(&module_id, &func_name, &type_args)?;
@@ -1534,7 +1805,7 @@ impl AptosVM {
                 .is_enabled(FeatureFlag::STRUCT_CONSTRUCTORS),
         )?;
 
-        Ok(session
+        let return_values = session
             .execute_function_bypass_visibility(
                 &module_id,
                 func_name.as_ident_str(),
@@ -1542,11 +1813,61 @@ impl AptosVM {
                 arguments,
                 &mut gas_meter,
             )
-            .map_err(|err| anyhow!("Failed to execute function: {:?}", err))?
+            .map_err(|err| ViewFunctionError::from_execution_error(err, &limits))?
             .return_values
             .into_iter()
             .map(|(bytes, _ty)| bytes)
-            .collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+
+        let returned_bytes = return_values
+            .iter()
+            .map(|bytes| bytes.len() as u64)
+            .sum::<u64>();
+        if returned_bytes > limits.max_returned_bytes {
+            return Err(ViewFunctionError::MaxReturnedBytesExceeded {
+                returned_bytes,
+                max_returned_bytes: limits.max_returned_bytes,
+            });
+        }
+        Ok(return_values)
+    }
+
+    /// Dry-runs publishing of a package: runs the bytecode verifier on every module and checks
+    /// upgrades of existing modules for compatibility, without executing any transaction.
+    /// Unlike publishing, all problems are collected into a structured report instead of
+    /// failing on the first one. Fails only if the package cannot be deserialized.
+    pub fn check_publish_compatibility(
+        state_view: &impl StateView,
+        package_bytes: Vec<Vec<u8>>,
+    ) -> VMResult<PublishCompatibilityReport> {
+        let vm = AptosVM::new_from_state_view(state_view);
+        let modules = vm.deserialize_module_bundle(&ModuleBundle::new(package_bytes))?;
+
+        let config = verifier_config(vm.0.get_features(), vm.0.get_timed_features());
+        let verification_errors = modules
+            .iter()
+            .filter_map(|module| {
+                move_bytecode_verifier::verify_module_with_config(&config, module)
+                    .err()
+                    .map(|err| ModuleVerificationError {
+                        module_id: module.self_id(),
+                        status_code: err.major_status(),
+                        message: err.message().cloned(),
+                    })
+            })
+            .collect();
+
+        let resolver = vm.as_move_resolver(state_view);
+        let upgrades = check_bundle_compatibility(&resolver, &modules)?;
+
+        Ok(PublishCompatibilityReport {
+            verification_errors,
+            upgrades,
+            check_friend_linking: !vm
+                .0
+                .get_features()
+                .is_enabled(FeatureFlag::TREAT_FRIEND_AS_PRIVATE),
+        })
     }
 
     fn run_prologue_with_payload(
@@ -1569,6 +1890,17 @@ impl AptosVM {
                 self.0.check_gas(resolver, txn_data, log_context)?;
                 self.0.run_script_prologue(session, txn_data, log_context)
             },
+            TransactionPayload::Batched(_) => {
+                if !self
+                    .0
+                    .get_features()
+                    .is_enabled(FeatureFlag::BATCHED_ENTRY_FUNCTIONS)
+                {
+                    return Err(VMStatus::error(StatusCode::FEATURE_UNDER_GATING, None));
+                }
+                self.0.check_gas(resolver, txn_data, log_context)?;
+                self.0.run_script_prologue(session, txn_data, log_context)
+            },
             TransactionPayload::Multisig(multisig_payload) => {
                 self.0.check_gas(resolver, txn_data, log_context)?;
                 // Still run script prologue for multisig transaction to ensure the same tx
@@ -1749,6 +2081,15 @@ impl AptosVM {
                 let output = VMOutput::empty_with_status(status);
                 (VMStatus::Executed, output, Some("state_checkpoint".into()))
             },
+            BlockMetadataExtTransaction(block_metadata_ext) => {
+                fail_point!("aptos_vm::execution::block_metadata_ext");
+                let (vm_status, output) = self.process_block_prologue_ext(
+                    resolver,
+                    block_metadata_ext.clone(),
+                    log_context,
+                )?;
+                (vm_status, output, Some("block_prologue_ext".to_string()))
+            },
         })
     }
 }
@@ -1763,7 +2104,7 @@ impl VMExecutor for AptosVM {
     fn execute_block(
         transactions: &[SignatureVerifiedTransaction],
         state_view: &(impl StateView + Sync),
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
         fail_point!("move_adapter::execute_block", |_| {
             Err(VMStatus::error(
@@ -1778,6 +2119,21 @@ impl VMExecutor for AptosVM {
             transactions.len()
         );
 
+        let block_gas_profiling = Self::get_block_gas_profiling();
+        if block_gas_profiling {
+            *CURRENT_BLOCK_GAS_PROFILE.lock() = BlockGasProfile::new();
+        }
+
+        // Profiling requires sequential execution, which is not adapted.
+        let concurrency_controller = CONCURRENCY_CONTROLLER
+            .get()
+            .filter(|_| !block_gas_profiling)
+            .cloned();
+        let concurrency_level = match &concurrency_controller {
+            Some(concurrency_controller) => concurrency_controller.concurrency_level(),
+            None => Self::get_concurrency_level(),
+        };
+
         let count = transactions.len();
         let ret = BlockAptosVM::execute_block::<
             _,
@@ -1786,13 +2142,17 @@ impl VMExecutor for AptosVM {
             Arc::clone(&RAYON_EXEC_POOL),
             transactions,
             state_view,
-            Self::get_concurrency_level(),
-            maybe_block_gas_limit,
+            concurrency_level,
+            block_execution_limit,
             None,
+            concurrency_controller,
         );
         if ret.is_ok() {
             // Record the histogram count for transactions per block.
             BLOCK_TRANSACTION_COUNT.observe(count as f64);
+            if block_gas_profiling {
+                publish_block_gas_profile(std::mem::take(&mut *CURRENT_BLOCK_GAS_PROFILE.lock()));
+            }
         }
         ret
     }
@@ -1801,7 +2161,7 @@ impl VMExecutor for AptosVM {
         sharded_block_executor: &ShardedBlockExecutor<S, C>,
         transactions: PartitionedTransactions,
         state_view: Arc<S>,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
         let log_context = AdapterLogSchema::new(state_view.id(), 0);
         info!(
@@ -1815,7 +2175,7 @@ impl VMExecutor for AptosVM {
             state_view,
             transactions,
             AptosVM::get_concurrency_level(),
-            maybe_block_gas_limit,
+            block_execution_limit,
         );
         if ret.is_ok() {
             // Record the histogram count for transactions per block.
@@ -1844,25 +2204,55 @@ impl VMValidator for AptosVM {
         state_view: &impl StateView,
     ) -> VMValidatorResult {
         let _timer = TXN_VALIDATION_SECONDS.start_timer();
-        let log_context = AdapterLogSchema::new(state_view.id(), 0);
+        if let Err(result) = self.check_authenticator_gating(&transaction) {
+            return result;
+        }
 
+        let txn = match self.check_signature(transaction) {
+            Ok(t) => t,
+            _ => {
+                return VMValidatorResult::error(StatusCode::INVALID_SIGNATURE);
+            },
+        };
+        self.validate_checked_transaction_impl(txn, state_view)
+    }
+
+    fn validate_checked_transaction(
+        &self,
+        transaction: SignatureCheckedTransaction,
+        state_view: &impl StateView,
+    ) -> VMValidatorResult {
+        let _timer = TXN_VALIDATION_SECONDS.start_timer();
+        if let Err(result) = self.check_authenticator_gating(&transaction) {
+            return result;
+        }
+        self.validate_checked_transaction_impl(transaction, state_view)
+    }
+}
+
+impl AptosVM {
+    fn check_authenticator_gating(
+        &self,
+        transaction: &SignedTransaction,
+    ) -> Result<(), VMValidatorResult> {
         if !self
             .0
             .get_features()
             .is_enabled(FeatureFlag::SINGLE_SENDER_AUTHENTICATOR)
         {
             if let aptos_types::transaction::authenticator::TransactionAuthenticator::SingleSender{ .. } = transaction.authenticator_ref() {
-                return VMValidatorResult::error(StatusCode::FEATURE_UNDER_GATING);
+                return Err(VMValidatorResult::error(StatusCode::FEATURE_UNDER_GATING));
             }
         }
+        Ok(())
+    }
 
-        let txn = match self.check_signature(transaction) {
-            Ok(t) => t,
-            _ => {
-                return VMValidatorResult::error(StatusCode::INVALID_SIGNATURE);
-            },
-        };
-
+    fn validate_checked_transaction_impl(
+        &self,
+        txn: SignatureCheckedTransaction,
+        state_view: &impl StateView,
+    ) -> VMValidatorResult {
+        let log_context = AdapterLogSchema::new(state_view.id(), 0);
         let resolver = self.as_move_resolver(&state_view);
         let mut session = self.0.new_session(&resolver, SessionId::prologue(&txn));
         let validation_result = self.validate_signature_checked_transaction(
@@ -1957,18 +2347,17 @@ impl AptosSimulationVM {
         let mut new_published_modules_loaded = false;
         let result = match txn.payload() {
             payload @ TransactionPayload::Script(_)
-            | payload @ TransactionPayload::EntryFunction(_) => {
-                self.0.execute_script_or_entry_function(
-                    resolver,
-                    session,
-                    &mut gas_meter,
-                    &txn_data,
-                    payload,
-                    log_context,
-                    &mut new_published_modules_loaded,
-                    &storage_gas_params.change_set_configs,
-                )
-            },
+            | payload @ TransactionPayload::EntryFunction(_)
+            | payload @ TransactionPayload::Batched(_) => self.0.execute_script_or_entry_function(
+                resolver,
+                session,
+                &mut gas_meter,
+                &txn_data,
+                payload,
+                log_context,
+                &mut new_published_modules_loaded,
+                &storage_gas_params.change_set_configs,
+            ),
             TransactionPayload::Multisig(multisig) => {
                 if let Some(payload) = multisig.transaction_payload.clone() {
                     match payload {
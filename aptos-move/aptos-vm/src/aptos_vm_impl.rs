@@ -67,14 +67,7 @@ pub fn gas_config(
     config_storage: &impl ConfigStorage,
 ) -> (Result<AptosGasParameters, String>, u64) {
     match GasScheduleV2::fetch_config(config_storage) {
-        Some(gas_schedule) => {
-            let feature_version = gas_schedule.feature_version;
-            let map = gas_schedule.to_btree_map();
-            (
-                AptosGasParameters::from_on_chain_gas_schedule(&map, feature_version),
-                feature_version,
-            )
-        },
+        Some(gas_schedule) => gas_config_from_schedule(&gas_schedule),
         None => match GasSchedule::fetch_config(config_storage) {
             Some(gas_schedule) => {
                 let map = gas_schedule.to_btree_map();
@@ -85,12 +78,37 @@ pub fn gas_config(
     }
 }
 
+fn gas_config_from_schedule(
+    gas_schedule: &GasScheduleV2,
+) -> (Result<AptosGasParameters, String>, u64) {
+    let feature_version = gas_schedule.feature_version;
+    let map = gas_schedule.to_btree_map();
+    (
+        AptosGasParameters::from_on_chain_gas_schedule(&map, feature_version),
+        feature_version,
+    )
+}
+
 impl AptosVMImpl {
     pub fn new(resolver: &impl AptosMoveResolver) -> Self {
         let _timer = TIMER.timer_with(&["impl_new"]);
-        // Get the gas parameters
-        let (mut gas_params, gas_feature_version) = gas_config(resolver);
+        Self::new_with_gas_config(resolver, gas_config(resolver))
+    }
+
+    /// Creates the VM with the given gas schedule instead of the one stored on-chain, e.g. to
+    /// simulate transactions under a proposed gas schedule change.
+    pub fn new_with_gas_override(
+        resolver: &impl AptosMoveResolver,
+        gas_schedule: &GasScheduleV2,
+    ) -> Self {
+        let _timer = TIMER.timer_with(&["impl_new"]);
+        Self::new_with_gas_config(resolver, gas_config_from_schedule(gas_schedule))
+    }
 
+    fn new_with_gas_config(
+        resolver: &impl AptosMoveResolver,
+        (mut gas_params, gas_feature_version): (Result<AptosGasParameters, String>, u64),
+    ) -> Self {
         let storage_gas_params = match &mut gas_params {
             Ok(gas_params) => {
                 let storage_gas_params =
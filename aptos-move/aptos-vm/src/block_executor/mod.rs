@@ -12,7 +12,7 @@ use aptos_aggregator::{
     delayed_change::DelayedChange, delta_change_set::DeltaOp, types::DelayedFieldID,
 };
 use aptos_block_executor::{
-    errors::Error, executor::BlockExecutor,
+    concurrency_controller::ConcurrencyController, errors::Error, executor::BlockExecutor,
     task::TransactionOutput as BlockExecutorTransactionOutput,
     txn_commit_hook::TransactionCommitHook,
 };
@@ -22,6 +22,7 @@ use aptos_types::{
     contract_event::ContractEvent,
     executable::ExecutableTestType,
     fee_statement::FeeStatement,
+    on_chain_config::BlockExecutionLimit,
     state_store::state_key::StateKey,
     transaction::{
         signature_verified_transaction::SignatureVerifiedTransaction, BlockExecutableTransaction,
@@ -53,7 +54,8 @@ impl AptosTransactionOutput {
         }
     }
 
-    pub(crate) fn committed_output(&self) -> &TransactionOutput {
+    /// The materialized output of a committed transaction, e.g. for transaction commit hooks.
+    pub fn committed_output(&self) -> &TransactionOutput {
         self.committed_output.get().unwrap()
     }
 
@@ -268,6 +270,16 @@ impl BlockExecutorTransactionOutput for AptosTransactionOutput {
             .expect("Output to be set to get fee statement")
             .fee_statement()
     }
+
+    /// Should never be called after vm_output is consumed.
+    fn output_approx_size(&self) -> u64 {
+        self.vm_output
+            .lock()
+            .as_ref()
+            .expect("Output to be set to get output size")
+            .change_set()
+            .write_set_approx_size()
+    }
 }
 
 pub struct BlockAptosVM();
@@ -281,8 +293,9 @@ impl BlockAptosVM {
         signature_verified_block: &[SignatureVerifiedTransaction],
         state_view: &S,
         concurrency_level: usize,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
         transaction_commit_listener: Option<L>,
+        concurrency_controller: Option<Arc<ConcurrencyController>>,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
         let _timer = BLOCK_EXECUTOR_EXECUTE_BLOCK_SECONDS.start_timer();
         let num_txns = signature_verified_block.len();
@@ -293,7 +306,7 @@ impl BlockAptosVM {
         }
 
         BLOCK_EXECUTOR_CONCURRENCY.set(concurrency_level as i64);
-        let executor = BlockExecutor::<
+        let mut executor = BlockExecutor::<
             SignatureVerifiedTransaction,
             AptosExecutorTask<S>,
             S,
@@ -302,9 +315,12 @@ impl BlockAptosVM {
         >::new(
             concurrency_level,
             executor_thread_pool,
-            maybe_block_gas_limit,
+            block_execution_limit,
             transaction_commit_listener,
         );
+        if let Some(concurrency_controller) = concurrency_controller {
+            executor = executor.with_concurrency_controller(concurrency_controller);
+        }
 
         let ret = executor.execute_block(state_view, signature_verified_block, state_view);
         match ret {
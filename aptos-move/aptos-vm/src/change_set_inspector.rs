@@ -0,0 +1,301 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Structured, human-readable view of a `VMChangeSet`, used by replay verification and debugging
+//! tools instead of printing raw BCS bytes.
+
+use aptos_types::{
+    state_store::{state_key::StateKey, state_value::StateValueMetadata},
+    write_set::WriteOp,
+};
+use aptos_vm_types::change_set::{GroupWrite, VMChangeSet};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// What a write does to its state slot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WriteKind {
+    Created,
+    Modified,
+    Deleted,
+}
+
+impl From<&WriteOp> for WriteKind {
+    fn from(op: &WriteOp) -> Self {
+        use WriteOp::*;
+
+        match op {
+            Creation(_) | CreationWithMetadata { .. } => WriteKind::Created,
+            Modification(_) | ModificationWithMetadata { .. } => WriteKind::Modified,
+            Deletion | DeletionWithMetadata { .. } => WriteKind::Deleted,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct MetadataView {
+    pub deposit: u64,
+    pub creation_time_usecs: u64,
+}
+
+impl From<&StateValueMetadata> for MetadataView {
+    fn from(metadata: &StateValueMetadata) -> Self {
+        match metadata {
+            StateValueMetadata::V0 {
+                deposit,
+                creation_time_usecs,
+            } => Self {
+                deposit: *deposit,
+                creation_time_usecs: *creation_time_usecs,
+            },
+        }
+    }
+}
+
+/// A write to a single state slot.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct WriteView {
+    pub state_key: String,
+    pub kind: WriteKind,
+    /// Size of the written value in bytes, None for deletions.
+    pub size: Option<u64>,
+    pub metadata: Option<MetadataView>,
+}
+
+impl WriteView {
+    fn new(state_key: &StateKey, op: &WriteOp) -> Self {
+        Self {
+            state_key: format!("{:?}", state_key.inner()),
+            kind: op.into(),
+            size: op.bytes().map(|bytes| bytes.len() as u64),
+            metadata: op.metadata().map(MetadataView::from),
+        }
+    }
+}
+
+/// A write to a single member of a resource group.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct GroupMemberView {
+    pub tag: String,
+    pub kind: WriteKind,
+    pub size: Option<u64>,
+    pub has_layout: bool,
+}
+
+/// A write to a resource group, with the changes of its members.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct GroupWriteView {
+    pub state_key: String,
+    pub kind: WriteKind,
+    /// Size of the group after the write, None if the group is deleted.
+    pub group_size: Option<u64>,
+    pub metadata: Option<MetadataView>,
+    pub members: Vec<GroupMemberView>,
+}
+
+impl GroupWriteView {
+    fn new(state_key: &StateKey, group_write: &GroupWrite) -> Self {
+        let metadata_op = group_write.metadata_op();
+        let members = group_write
+            .inner_ops()
+            .iter()
+            .map(|(tag, (op, maybe_layout))| GroupMemberView {
+                tag: tag.to_string(),
+                kind: op.into(),
+                size: op.bytes().map(|bytes| bytes.len() as u64),
+                has_layout: maybe_layout.is_some(),
+            })
+            .collect();
+        Self {
+            state_key: format!("{:?}", state_key.inner()),
+            kind: metadata_op.into(),
+            group_size: group_write.encoded_group_size(),
+            metadata: metadata_op.metadata().map(MetadataView::from),
+            members,
+        }
+    }
+}
+
+/// A not yet materialized aggregator V1 delta.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct DeltaView {
+    pub state_key: String,
+    pub delta: String,
+}
+
+/// Structured view of all the changes in a `VMChangeSet`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ChangeSetInspection {
+    pub resources: Vec<WriteView>,
+    pub resource_groups: Vec<GroupWriteView>,
+    pub modules: Vec<WriteView>,
+    pub aggregator_v1_writes: Vec<WriteView>,
+    pub aggregator_v1_deltas: Vec<DeltaView>,
+    pub num_delayed_field_changes: usize,
+    pub num_reads_needing_exchange: usize,
+    pub num_events: usize,
+}
+
+pub struct ChangeSetInspector;
+
+impl ChangeSetInspector {
+    /// Returns a structured view of the change set, in the (sorted) order of state keys.
+    pub fn inspect(change_set: &VMChangeSet) -> ChangeSetInspection {
+        ChangeSetInspection {
+            resources: change_set
+                .resource_write_set()
+                .iter()
+                .map(|(state_key, (op, _))| WriteView::new(state_key, op))
+                .collect(),
+            resource_groups: change_set
+                .resource_group_write_set()
+                .iter()
+                .map(|(state_key, group_write)| GroupWriteView::new(state_key, group_write))
+                .collect(),
+            modules: write_views(change_set.module_write_set()),
+            aggregator_v1_writes: write_views(change_set.aggregator_v1_write_set()),
+            aggregator_v1_deltas: change_set
+                .aggregator_v1_delta_set()
+                .iter()
+                .map(|(state_key, delta)| DeltaView {
+                    state_key: format!("{:?}", state_key.inner()),
+                    delta: format!("{:?}", delta),
+                })
+                .collect(),
+            num_delayed_field_changes: change_set.delayed_field_change_set().len(),
+            num_reads_needing_exchange: change_set.reads_needing_delayed_field_exchange().len(),
+            num_events: change_set.events().len(),
+        }
+    }
+
+    /// Renders the structured view of the change set as pretty-printed JSON.
+    pub fn to_json(change_set: &VMChangeSet) -> anyhow::Result<String> {
+        Ok(serde_json::to_string_pretty(&Self::inspect(change_set))?)
+    }
+}
+
+fn write_views(write_set: &BTreeMap<StateKey, WriteOp>) -> Vec<WriteView> {
+    write_set
+        .iter()
+        .map(|(state_key, op)| WriteView::new(state_key, op))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_aggregator::delta_change_set::delta_add;
+    use aptos_gas_schedule::LATEST_GAS_FEATURE_VERSION;
+    use aptos_types::on_chain_config::CurrentTimeMicroseconds;
+    use aptos_vm_types::storage::ChangeSetConfigs;
+    use move_core_types::{
+        account_address::AccountAddress, identifier::Identifier, language_storage::StructTag,
+    };
+
+    fn tag(name: &str) -> StructTag {
+        StructTag {
+            address: AccountAddress::ONE,
+            module: Identifier::new("m").unwrap(),
+            name: Identifier::new(name).unwrap(),
+            type_params: vec![],
+        }
+    }
+
+    #[test]
+    fn test_inspect_change_set() {
+        let metadata = StateValueMetadata::new(10, &CurrentTimeMicroseconds { microseconds: 20 });
+        let resource_write_set = BTreeMap::from([
+            (
+                StateKey::raw(vec![0]),
+                (WriteOp::Creation(vec![1, 2].into()), None),
+            ),
+            (
+                StateKey::raw(vec![1]),
+                (
+                    WriteOp::DeletionWithMetadata {
+                        metadata: metadata.clone(),
+                    },
+                    None,
+                ),
+            ),
+        ]);
+        let resource_group_write_set = BTreeMap::from([(
+            StateKey::raw(vec![2]),
+            GroupWrite::new(
+                WriteOp::ModificationWithMetadata {
+                    data: vec![].into(),
+                    metadata: metadata.clone(),
+                },
+                100,
+                BTreeMap::from([
+                    (tag("A"), (WriteOp::Modification(vec![3].into()), None)),
+                    (tag("B"), (WriteOp::Deletion, None)),
+                ]),
+            ),
+        )]);
+        let aggregator_v1_delta_set = BTreeMap::from([(StateKey::raw(vec![3]), delta_add(5, 10))]);
+
+        let change_set = VMChangeSet::new(
+            resource_write_set,
+            resource_group_write_set,
+            BTreeMap::new(),
+            BTreeMap::new(),
+            aggregator_v1_delta_set,
+            BTreeMap::new(),
+            BTreeMap::new(),
+            vec![],
+            &ChangeSetConfigs::unlimited_at_gas_feature_version(LATEST_GAS_FEATURE_VERSION),
+        )
+        .unwrap();
+
+        let inspection = ChangeSetInspector::inspect(&change_set);
+        assert_eq!(inspection.resources, vec![
+            WriteView {
+                state_key: "Raw(00)".to_string(),
+                kind: WriteKind::Created,
+                size: Some(2),
+                metadata: None,
+            },
+            WriteView {
+                state_key: "Raw(01)".to_string(),
+                kind: WriteKind::Deleted,
+                size: None,
+                metadata: Some(MetadataView {
+                    deposit: 10,
+                    creation_time_usecs: 20,
+                }),
+            },
+        ]);
+        assert_eq!(inspection.resource_groups, vec![GroupWriteView {
+            state_key: "Raw(02)".to_string(),
+            kind: WriteKind::Modified,
+            group_size: Some(100),
+            metadata: Some(MetadataView {
+                deposit: 10,
+                creation_time_usecs: 20,
+            }),
+            members: vec![
+                GroupMemberView {
+                    tag: tag("A").to_string(),
+                    kind: WriteKind::Modified,
+                    size: Some(1),
+                    has_layout: false,
+                },
+                GroupMemberView {
+                    tag: tag("B").to_string(),
+                    kind: WriteKind::Deleted,
+                    size: None,
+                    has_layout: false,
+                },
+            ],
+        }]);
+        assert_eq!(inspection.aggregator_v1_deltas.len(), 1);
+        assert!(inspection.modules.is_empty());
+
+        let json: serde_json::Value =
+            serde_json::from_str(&ChangeSetInspector::to_json(&change_set).unwrap()).unwrap();
+        assert_eq!(json["resources"][0]["kind"], "created");
+        assert_eq!(json["resource_groups"][0]["members"][1]["kind"], "deleted");
+    }
+}
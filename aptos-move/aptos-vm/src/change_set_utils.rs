@@ -0,0 +1,218 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Conversion of Move change sets into Aptos write ops outside of a VM session, e.g. for tools
+//! replaying change sets against a custom resolver.
+
+use crate::{
+    data_cache::get_resource_group_from_metadata,
+    move_vm_ext::{
+        write_op_converter::{ConversionContext, WriteOpConverter},
+        AptosMoveResolver, ResourceGroupResolver, SessionExt,
+    },
+};
+use aptos_framework::natives::aggregator_natives::AggregatorChangeSet;
+use aptos_table_natives::TableChangeSet;
+use aptos_types::{access_path::AccessPath, state_store::state_key::StateKey};
+use aptos_vm_types::{change_set::VMChangeSet, storage::ChangeSetConfigs};
+use move_core_types::{
+    effects::{AccountChanges, ChangeSet as MoveChangeSet, Changes},
+    language_storage::StructTag,
+    resolver::ModuleResolver,
+    vm_status::{StatusCode, VMStatus},
+};
+use std::collections::BTreeMap;
+
+/// Converts a Move change set into a `VMChangeSet` containing the resulting write ops, in the
+/// same way a VM session does when it finishes.
+///
+/// Resources that are members of a resource group (according to the metadata of their module,
+/// as returned by the resolver) are combined into group writes. Depending on the resolver, these
+/// are either whole group writes (stored in the resource write set) or granular group writes.
+/// Metadata of the existing state values is read through the resolver, and new state values get
+/// metadata if `is_storage_slot_metadata_enabled` is set.
+///
+/// Ops that are inconsistent with the state (e.g. modifying a non-existent value) result in an
+/// `UNKNOWN_INVARIANT_VIOLATION_ERROR`, as the conversion is not speculative.
+///
+/// The resulting change set has no events, table or aggregator changes.
+pub fn convert_change_set(
+    resolver: &dyn AptosMoveResolver,
+    change_set: MoveChangeSet,
+    is_storage_slot_metadata_enabled: bool,
+    configs: &ChangeSetConfigs,
+) -> Result<VMChangeSet, VMStatus> {
+    let get_resource_group = |struct_tag: &StructTag| {
+        get_resource_group_from_metadata(
+            struct_tag,
+            &resolver.get_module_metadata(&struct_tag.module_id()),
+        )
+    };
+
+    let mut changes = Changes::new();
+    for (addr, account_changes) in change_set.into_inner() {
+        let (modules, resources) = account_changes.into_inner();
+        let mut resources_with_layout = BTreeMap::new();
+        for (struct_tag, op) in resources {
+            // A session reads group members before writing them, which loads the group into
+            // the resolver. Do the same, so existing groups are not treated as new ones.
+            if let Some(resource_group) = get_resource_group(&struct_tag) {
+                let group_key = StateKey::access_path(AccessPath::resource_group_access_path(
+                    addr,
+                    resource_group,
+                ));
+                resolver
+                    .resource_exists_in_group(&group_key, &struct_tag)
+                    .map_err(|e| VMStatus::error(StatusCode::STORAGE_ERROR, Some(e.to_string())))?;
+            }
+            resources_with_layout.insert(struct_tag, op.map(|data| (data, None)));
+        }
+        changes
+            .add_account_changeset(
+                addr,
+                AccountChanges::from_modules_resources(modules, resources_with_layout),
+            )
+            .map_err(|e| {
+                VMStatus::error(
+                    StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR,
+                    Some(e.to_string()),
+                )
+            })?;
+    }
+
+    let (changes, resource_group_change_set) =
+        SessionExt::split_and_merge_resource_groups(get_resource_group, resolver, changes, &mut ())
+            .map_err(|e| e.into_vm_status())?;
+
+    // Outside of a block, the state read through the resolver is final, so inconsistent ops are
+    // invariant violations rather than speculative errors.
+    let woc = WriteOpConverter::new(
+        resolver,
+        ConversionContext::Sequential,
+        is_storage_slot_metadata_enabled,
+    );
+    SessionExt::convert_change_set(
+        &woc,
+        changes,
+        resource_group_change_set,
+        vec![],
+        TableChangeSet::default(),
+        AggregatorChangeSet {
+            aggregator_v1_changes: BTreeMap::new(),
+            delayed_field_changes: BTreeMap::new(),
+            reads_needing_exchange: BTreeMap::new(),
+        },
+        &mut (),
+        configs,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_cache::AsMoveResolver;
+    use aptos_gas_schedule::LATEST_GAS_FEATURE_VERSION;
+    use aptos_state_view::in_memory_state_view::InMemoryStateView;
+    use aptos_types::{state_store::state_value::StateValue, write_set::WriteOp};
+    use move_core_types::{
+        account_address::AccountAddress, effects::Op as MoveStorageOp, identifier::Identifier,
+    };
+    use std::collections::HashMap;
+
+    fn struct_tag(name: &str) -> StructTag {
+        StructTag {
+            address: AccountAddress::ONE,
+            module: Identifier::new("m").unwrap(),
+            name: Identifier::new(name).unwrap(),
+            type_params: vec![],
+        }
+    }
+
+    fn state_key(addr: AccountAddress, name: &str) -> StateKey {
+        StateKey::access_path(AccessPath::resource_access_path(addr, struct_tag(name)).unwrap())
+    }
+
+    #[test]
+    fn test_convert_resources() {
+        let addr = AccountAddress::random();
+        let state_view = InMemoryStateView::new(HashMap::from([
+            (
+                state_key(addr, "Modified"),
+                StateValue::new_legacy(vec![1].into()),
+            ),
+            (
+                state_key(addr, "Deleted"),
+                StateValue::new_legacy(vec![2].into()),
+            ),
+        ]));
+        let resolver = state_view.as_move_resolver();
+
+        let mut account_changes = AccountChanges::new();
+        account_changes
+            .add_resource_op(struct_tag("Created"), MoveStorageOp::New(vec![3].into()))
+            .unwrap();
+        account_changes
+            .add_resource_op(
+                struct_tag("Modified"),
+                MoveStorageOp::Modify(vec![4].into()),
+            )
+            .unwrap();
+        account_changes
+            .add_resource_op(struct_tag("Deleted"), MoveStorageOp::Delete)
+            .unwrap();
+        let mut change_set = MoveChangeSet::new();
+        change_set
+            .add_account_changeset(addr, account_changes)
+            .unwrap();
+
+        let configs =
+            ChangeSetConfigs::unlimited_at_gas_feature_version(LATEST_GAS_FEATURE_VERSION);
+        let vm_change_set = convert_change_set(&resolver, change_set, false, &configs).unwrap();
+
+        let ops: BTreeMap<_, _> = vm_change_set
+            .resource_write_set()
+            .iter()
+            .map(|(key, (op, _))| (key.clone(), op.clone()))
+            .collect();
+        assert_eq!(
+            ops,
+            BTreeMap::from([
+                (
+                    state_key(addr, "Created"),
+                    WriteOp::Creation(vec![3].into())
+                ),
+                (
+                    state_key(addr, "Modified"),
+                    WriteOp::Modification(vec![4].into())
+                ),
+                (state_key(addr, "Deleted"), WriteOp::Deletion),
+            ])
+        );
+        assert!(vm_change_set.module_write_set().is_empty());
+        assert!(vm_change_set.resource_group_write_set().is_empty());
+    }
+
+    #[test]
+    fn test_inconsistent_op_is_invariant_violation() {
+        let addr = AccountAddress::random();
+        let state_view = InMemoryStateView::new(HashMap::new());
+        let resolver = state_view.as_move_resolver();
+
+        let mut account_changes = AccountChanges::new();
+        account_changes
+            .add_resource_op(struct_tag("Missing"), MoveStorageOp::Modify(vec![1].into()))
+            .unwrap();
+        let mut change_set = MoveChangeSet::new();
+        change_set
+            .add_account_changeset(addr, account_changes)
+            .unwrap();
+
+        let configs =
+            ChangeSetConfigs::unlimited_at_gas_feature_version(LATEST_GAS_FEATURE_VERSION);
+        let status = convert_change_set(&resolver, change_set, false, &configs).unwrap_err();
+        assert_eq!(
+            status.status_code(),
+            StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR
+        );
+    }
+}
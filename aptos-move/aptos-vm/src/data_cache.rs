@@ -6,8 +6,8 @@
 use crate::{
     aptos_vm_impl::gas_config,
     move_vm_ext::{
-        get_max_binary_format_version, get_max_identifier_size, AptosMoveResolver, AsExecutorView,
-        AsResourceGroupView, ResourceGroupResolver,
+        get_max_binary_format_version, get_max_identifier_size, AccessTraceResolver,
+        AptosMoveResolver, AsExecutorView, AsResourceGroupView, ResourceGroupResolver,
     },
 };
 #[allow(unused_imports)]
@@ -17,6 +17,7 @@ use aptos_aggregator::{
     resolver::{TAggregatorV1View, TDelayedFieldView},
     types::{DelayedFieldID, DelayedFieldValue, DelayedFieldsSpeculativeError, PanicOr},
 };
+use aptos_crypto::HashValue;
 use aptos_state_view::{StateView, StateViewId};
 use aptos_table_natives::{TableHandle, TableResolver};
 use aptos_types::{
@@ -85,6 +86,10 @@ pub struct StorageAdapter<'e, E> {
     deserializer_config: DeserializerConfig,
     resource_group_view: ResourceGroupAdapter<'e>,
     accessed_groups: RefCell<HashSet<StateKey>>,
+    // Reads recorded for the access trace, if enabled.
+    recorded_reads: RefCell<Option<Vec<(StateKey, u64)>>>,
+    // Whether table keys can be enumerated, see `with_table_key_iteration`.
+    table_key_iteration: bool,
 }
 
 impl<'e, E: ExecutorView> StorageAdapter<'e, E> {
@@ -134,6 +139,22 @@ impl<'e, E: ExecutorView> StorageAdapter<'e, E> {
             ),
             resource_group_view,
             accessed_groups: RefCell::new(HashSet::new()),
+            recorded_reads: RefCell::new(None),
+            table_key_iteration: false,
+        }
+    }
+
+    /// Allows enumerating table keys. Only view functions enable this: storage can't enumerate
+    /// the keys of a table consistently with the speculative state of a block, so transactions
+    /// must not depend on it, including when they are simulated against storage.
+    pub(crate) fn with_table_key_iteration(mut self) -> Self {
+        self.table_key_iteration = true;
+        self
+    }
+
+    fn record_read(&self, key: &StateKey, size: usize) {
+        if let Some(reads) = self.recorded_reads.borrow_mut().as_mut() {
+            reads.push((key.clone(), size as u64));
         }
     }
 
@@ -174,6 +195,7 @@ impl<'e, E: ExecutorView> StorageAdapter<'e, E> {
             };
 
             let buf_size = resource_size(&buf);
+            self.record_read(&key, buf_size);
             Ok((buf, buf_size + group_size as usize))
         } else {
             let access_path = AccessPath::resource_access_path(*address, struct_tag.clone())
@@ -181,13 +203,15 @@ impl<'e, E: ExecutorView> StorageAdapter<'e, E> {
                     PartialVMError::new(StatusCode::TOO_MANY_TYPE_NODES).finish(Location::Undefined)
                 })?;
 
+            let key = StateKey::access_path(access_path);
             let buf = self
                 .executor_view
-                .get_resource_bytes(&StateKey::access_path(access_path), maybe_layout)
+                .get_resource_bytes(&key, maybe_layout)
                 .map_err(|_| {
                     PartialVMError::new(StatusCode::STORAGE_ERROR).finish(Location::Undefined)
                 })?;
             let buf_size = resource_size(&buf);
+            self.record_read(&key, buf_size);
             Ok((buf, buf_size))
         }
     }
@@ -223,6 +247,21 @@ impl<'e, E: ExecutorView> ResourceGroupResolver for StorageAdapter<'e, E> {
     }
 }
 
+impl<'e, E: ExecutorView> AccessTraceResolver for StorageAdapter<'e, E> {
+    fn enable_access_trace(&self) {
+        self.recorded_reads
+            .borrow_mut()
+            .get_or_insert_with(Vec::new);
+    }
+
+    fn take_recorded_reads(&self) -> Option<Vec<(StateKey, u64)>> {
+        self.recorded_reads
+            .borrow_mut()
+            .as_mut()
+            .map(std::mem::take)
+    }
+}
+
 impl<'e, E: ExecutorView> AptosMoveResolver for StorageAdapter<'e, E> {}
 
 impl<'e, E: ExecutorView> ResourceResolver for StorageAdapter<'e, E> {
@@ -253,13 +292,12 @@ impl<'e, E: ExecutorView> ModuleResolver for StorageAdapter<'e, E> {
     }
 
     fn get_module(&self, module_id: &ModuleId) -> Result<Option<Bytes>, Error> {
-        let access_path = AccessPath::from(module_id);
-        Ok(self
-            .executor_view
-            .get_module_bytes(&StateKey::access_path(access_path))
-            .map_err(|_| {
-                PartialVMError::new(StatusCode::STORAGE_ERROR).finish(Location::Undefined)
-            })?)
+        let key = StateKey::access_path(AccessPath::from(module_id));
+        let buf = self.executor_view.get_module_bytes(&key).map_err(|_| {
+            PartialVMError::new(StatusCode::STORAGE_ERROR).finish(Location::Undefined)
+        })?;
+        self.record_read(&key, resource_size(&buf));
+        Ok(buf)
     }
 }
 
@@ -270,10 +308,27 @@ impl<'e, E: ExecutorView> TableResolver for StorageAdapter<'e, E> {
         key: &[u8],
         layout: Option<&MoveTypeLayout>,
     ) -> Result<Option<Bytes>, Error> {
-        self.executor_view.get_resource_bytes(
-            &StateKey::table_item((*handle).into(), key.to_vec()),
-            layout,
-        )
+        let key = StateKey::table_item((*handle).into(), key.to_vec());
+        let buf = self.executor_view.get_resource_bytes(&key, layout)?;
+        self.record_read(&key, resource_size(&buf));
+        Ok(buf)
+    }
+
+    fn resolve_table_keys(
+        &self,
+        handle: &TableHandle,
+        start_after: Option<&[u8; 32]>,
+        limit: usize,
+    ) -> Result<Option<(Vec<Vec<u8>>, Option<[u8; 32]>)>, Error> {
+        if !self.table_key_iteration {
+            return Ok(None);
+        }
+        let page = self.executor_view.get_table_item_keys(
+            &(*handle).into(),
+            start_after.map(|hash| HashValue::new(*hash)),
+            limit,
+        )?;
+        Ok(page.map(|(keys, next)| (keys, next.map(|hash| *hash))))
     }
 }
 
@@ -403,10 +458,12 @@ impl<'e, E: ExecutorView> StateValueMetadataResolver for StorageAdapter<'e, E> {
 
     fn get_resource_group_state_value_metadata(
         &self,
-        _state_key: &StateKey,
+        state_key: &StateKey,
     ) -> anyhow::Result<Option<StateValueMetadataKind>> {
-        // TODO[agg_v2](fix): forward to self.executor_view.
-        unimplemented!("Resource group metadata handling not yet implemented");
+        // Resource group metadata is stored at the group StateKey, and can be obtained via the
+        // same interfaces as for a resource at a given StateKey.
+        self.executor_view
+            .get_resource_state_value_metadata(state_key)
     }
 }
 
@@ -427,14 +484,56 @@ impl<'e, E> AsResourceGroupView for StorageAdapter<'e, E> {
 #[cfg(test)]
 pub(crate) mod tests {
     use super::*;
-    use aptos_vm_types::resource_group_adapter::GroupSizeKind;
+    use aptos_vm_types::resource_group_adapter::{group_size_as_sum, GroupSizeKind};
+
+    /// Resource group view that reads the serialized groups from a state view, and is capable
+    /// of splitting resource groups in the change set (as the block executor does).
+    pub(crate) struct GroupViewFromStateView<'s, S>(pub(crate) &'s S);
+
+    impl<'s, S: StateView> GroupViewFromStateView<'s, S> {
+        fn get_group(&self, group_key: &StateKey) -> anyhow::Result<BTreeMap<StructTag, Bytes>> {
+            match self.0.get_state_value_bytes(group_key)? {
+                Some(bytes) => Ok(bcs::from_bytes(&bytes)?),
+                None => Ok(BTreeMap::new()),
+            }
+        }
+    }
+
+    impl<'s, S: StateView> TResourceGroupView for GroupViewFromStateView<'s, S> {
+        type GroupKey = StateKey;
+        type Layout = MoveTypeLayout;
+        type ResourceTag = StructTag;
+
+        fn is_resource_group_split_in_change_set_capable(&self) -> bool {
+            true
+        }
 
-    // Expose a method to create a storage adapter with a provided group size kind.
-    pub(crate) fn as_resolver_with_group_size_kind<S: StateView>(
-        state_view: &S,
+        fn resource_group_size(&self, group_key: &StateKey) -> anyhow::Result<u64> {
+            group_size_as_sum(self.get_group(group_key)?.iter())
+        }
+
+        fn get_resource_from_group(
+            &self,
+            group_key: &StateKey,
+            resource_tag: &StructTag,
+            _maybe_layout: Option<&MoveTypeLayout>,
+        ) -> anyhow::Result<Option<Bytes>> {
+            Ok(self.get_group(group_key)?.remove(resource_tag))
+        }
+    }
+
+    // Expose a method to create a storage adapter with a provided group size kind. AsSum
+    // requires a resource group view that is capable of splitting groups in the change set.
+    pub(crate) fn as_resolver_with_group_size_kind<'s, S: StateView>(
+        state_view: &'s S,
         group_size_kind: GroupSizeKind,
-    ) -> StorageAdapter<S> {
-        assert!(group_size_kind != GroupSizeKind::AsSum, "not yet supported");
+        maybe_resource_group_view: Option<&'s dyn ResourceGroupView>,
+    ) -> StorageAdapter<'s, S> {
+        assert_eq!(
+            group_size_kind == GroupSizeKind::AsSum,
+            maybe_resource_group_view.is_some(),
+            "resource group view must be provided iff group size kind is AsSum"
+        );
 
         let (gas_feature_version, resource_group_charge_as_size_sum_enabled) = match group_size_kind
         {
@@ -444,8 +543,7 @@ pub(crate) mod tests {
         };
 
         let group_adapter = ResourceGroupAdapter::new(
-            // TODO[agg_v2](fix) add a converter for StateView for tests that implements ResourceGroupView
-            None,
+            maybe_resource_group_view,
             state_view,
             gas_feature_version,
             resource_group_charge_as_size_sum_enabled,
@@ -0,0 +1,555 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Hooks for observing the execution of user transactions: function calls, resource reads and
+//! writes, and the gas consumed by each category of instructions.
+//!
+//! Tracing is only available with the `execution-tracing` feature, and is enabled by installing
+//! a factory with `AptosVM::set_execution_tracer_once`. The VM then creates a fresh tracer for
+//! every user transaction it executes, including the ones executed as part of
+//! `AptosVM::execute_block`. Note that with parallel execution a transaction can be executed
+//! multiple times, and every (possibly speculative) execution gets its own tracer.
+
+use aptos_gas_algebra::{Fee, FeePerGasUnit, InternalGas, NumArgs, NumBytes};
+use aptos_gas_meter::AptosGasMeter;
+use aptos_types::{
+    contract_event::ContractEvent,
+    state_store::state_key::StateKey,
+    transaction::{SignedTransaction, TransactionStatus},
+    write_set::WriteOp,
+};
+use aptos_vm_types::{change_set::GroupWrite, output::VMOutput};
+use move_binary_format::{
+    errors::{PartialVMResult, VMResult},
+    file_format::CodeOffset,
+};
+use move_core_types::{
+    account_address::AccountAddress,
+    language_storage::{ModuleId, TypeTag},
+};
+use move_vm_types::{
+    gas::{GasMeter as MoveGasMeter, SimpleInstruction},
+    views::{TypeView, ValueView},
+};
+use std::collections::BTreeMap;
+
+/// Categories of operations the consumed gas is attributed to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum InstructionBucket {
+    /// Arithmetic, casts, bitwise and other simple instructions, including returns.
+    Simple,
+    /// Branches.
+    ControlFlow,
+    /// Constants and operations on locals.
+    Locals,
+    /// Function calls and dropping of frames.
+    Call,
+    /// Packing, unpacking, references and equality checks.
+    Struct,
+    /// Global storage operations, including loading resources.
+    Global,
+    /// Vector operations.
+    Vector,
+    /// Native functions.
+    Native,
+    /// Intrinsic cost of the transaction.
+    Intrinsic,
+    /// IO gas and storage fees for the writes, events and the transaction itself.
+    Storage,
+}
+
+/// Callbacks invoked while executing a single user transaction. All callbacks default to no-ops.
+///
+/// The entry function (or script) itself is not reported as entered, only the calls made from
+/// it, and each reported entry is matched by exactly one exit unless execution is aborted.
+pub trait ExecutionTracer {
+    fn on_function_entry(&mut self, _module_id: &ModuleId, _func_name: &str) {}
+
+    fn on_function_exit(&mut self) {}
+
+    fn on_resource_read(&mut self, _addr: AccountAddress, _ty: &TypeTag, _bytes_loaded: NumBytes) {}
+
+    /// Called for every write of the transaction output once execution has finished, in the
+    /// order of state keys. Resource groups are reported with the op on the group itself.
+    fn on_resource_write(&mut self, _state_key: &StateKey, _op: &WriteOp) {}
+
+    /// Called once at the end of the transaction, with the gas consumed in each bucket.
+    fn on_transaction_end(
+        &mut self,
+        _status: &TransactionStatus,
+        _gas_by_bucket: &BTreeMap<InstructionBucket, InternalGas>,
+    ) {
+    }
+}
+
+/// Creates a tracer for every user transaction executed by the VM.
+pub trait ExecutionTracerFactory: Send + Sync {
+    fn new_tracer(&self, txn: &SignedTransaction) -> Box<dyn ExecutionTracer>;
+}
+
+/// Gas meter that reports execution to an `ExecutionTracer`, to be composed with a base gas
+/// meter.
+pub struct TracingGasMeter<G> {
+    base: G,
+
+    tracer: Box<dyn ExecutionTracer>,
+    call_depth: usize,
+    gas_by_bucket: BTreeMap<InstructionBucket, InternalGas>,
+}
+
+impl<G> TracingGasMeter<G>
+where
+    G: AptosGasMeter,
+{
+    pub fn new(base: G, tracer: Box<dyn ExecutionTracer>) -> Self {
+        Self {
+            base,
+            tracer,
+            call_depth: 0,
+            gas_by_bucket: BTreeMap::new(),
+        }
+    }
+
+    /// Reports the writes and the consumed gas of the finished transaction to the tracer.
+    pub fn finish(mut self, output: &VMOutput) -> G {
+        let change_set = output.change_set();
+        for (state_key, op) in change_set.write_set_iter() {
+            self.tracer.on_resource_write(state_key, op);
+        }
+        for (state_key, group_write) in change_set.resource_group_write_set() {
+            self.tracer
+                .on_resource_write(state_key, group_write.metadata_op());
+        }
+        self.tracer
+            .on_transaction_end(output.status(), &self.gas_by_bucket);
+        self.base
+    }
+
+    fn record_gas(&mut self, bucket: InstructionBucket, balance_before: InternalGas) {
+        // The balance is zeroed when running out of gas, so the difference is what was charged.
+        let amount = balance_before
+            .checked_sub(self.base.balance_internal())
+            .unwrap_or_else(|| 0.into());
+        if !amount.is_zero() {
+            *self.gas_by_bucket.entry(bucket).or_insert_with(|| 0.into()) += amount;
+        }
+    }
+
+    fn exit_function(&mut self) {
+        if self.call_depth > 0 {
+            self.call_depth -= 1;
+            self.tracer.on_function_exit();
+        }
+    }
+}
+
+macro_rules! delegate {
+    ($(
+        fn $fn: ident $(<$($lt: lifetime),*>)? (&self $(, $arg: ident : $ty: ty)* $(,)?) -> $ret_ty: ty;
+    )*) => {
+        $(fn $fn $(<$($lt)*>)? (&self, $($arg: $ty),*) -> $ret_ty {
+            self.base.$fn($($arg),*)
+        })*
+    };
+}
+
+macro_rules! delegate_traced {
+    ($(
+        [$bucket: ident] fn $fn: ident $(<$($lt: lifetime),*>)? (&mut self $(, $arg: ident : $ty: ty)* $(,)?) -> $ret_ty: ty;
+    )*) => {
+        $(fn $fn $(<$($lt)*>)? (&mut self, $($arg: $ty),*) -> $ret_ty {
+            let balance_before = self.base.balance_internal();
+            let res = self.base.$fn($($arg),*);
+            self.record_gas(InstructionBucket::$bucket, balance_before);
+            res
+        })*
+    };
+}
+
+impl<G> MoveGasMeter for TracingGasMeter<G>
+where
+    G: AptosGasMeter,
+{
+    delegate_traced! {
+        [ControlFlow] fn charge_br_true(&mut self, target_offset: Option<CodeOffset>) -> PartialVMResult<()>;
+
+        [ControlFlow] fn charge_br_false(&mut self, target_offset: Option<CodeOffset>) -> PartialVMResult<()>;
+
+        [ControlFlow] fn charge_branch(&mut self, target_offset: CodeOffset) -> PartialVMResult<()>;
+
+        [Locals] fn charge_pop(&mut self, popped_val: impl ValueView) -> PartialVMResult<()>;
+
+        [Locals] fn charge_ld_const(&mut self, size: NumBytes) -> PartialVMResult<()>;
+
+        [Locals] fn charge_ld_const_after_deserialization(&mut self, val: impl ValueView) -> PartialVMResult<()>;
+
+        [Locals] fn charge_copy_loc(&mut self, val: impl ValueView) -> PartialVMResult<()>;
+
+        [Locals] fn charge_move_loc(&mut self, val: impl ValueView) -> PartialVMResult<()>;
+
+        [Locals] fn charge_store_loc(&mut self, val: impl ValueView) -> PartialVMResult<()>;
+
+        [Struct] fn charge_pack(
+            &mut self,
+            is_generic: bool,
+            args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+
+        [Struct] fn charge_unpack(
+            &mut self,
+            is_generic: bool,
+            args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+
+        [Struct] fn charge_read_ref(&mut self, val: impl ValueView) -> PartialVMResult<()>;
+
+        [Struct] fn charge_write_ref(
+            &mut self,
+            new_val: impl ValueView,
+            old_val: impl ValueView,
+        ) -> PartialVMResult<()>;
+
+        [Struct] fn charge_eq(&mut self, lhs: impl ValueView, rhs: impl ValueView) -> PartialVMResult<()>;
+
+        [Struct] fn charge_neq(&mut self, lhs: impl ValueView, rhs: impl ValueView) -> PartialVMResult<()>;
+
+        [Global] fn charge_borrow_global(
+            &mut self,
+            is_mut: bool,
+            is_generic: bool,
+            ty: impl TypeView,
+            is_success: bool,
+        ) -> PartialVMResult<()>;
+
+        [Global] fn charge_exists(
+            &mut self,
+            is_generic: bool,
+            ty: impl TypeView,
+            exists: bool,
+        ) -> PartialVMResult<()>;
+
+        [Global] fn charge_move_from(
+            &mut self,
+            is_generic: bool,
+            ty: impl TypeView,
+            val: Option<impl ValueView>,
+        ) -> PartialVMResult<()>;
+
+        [Global] fn charge_move_to(
+            &mut self,
+            is_generic: bool,
+            ty: impl TypeView,
+            val: impl ValueView,
+            is_success: bool,
+        ) -> PartialVMResult<()>;
+
+        [Vector] fn charge_vec_pack<'a>(
+            &mut self,
+            ty: impl TypeView + 'a,
+            args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+
+        [Vector] fn charge_vec_len(&mut self, ty: impl TypeView) -> PartialVMResult<()>;
+
+        [Vector] fn charge_vec_borrow(
+            &mut self,
+            is_mut: bool,
+            ty: impl TypeView,
+            is_success: bool,
+        ) -> PartialVMResult<()>;
+
+        [Vector] fn charge_vec_push_back(
+            &mut self,
+            ty: impl TypeView,
+            val: impl ValueView,
+        ) -> PartialVMResult<()>;
+
+        [Vector] fn charge_vec_pop_back(
+            &mut self,
+            ty: impl TypeView,
+            val: Option<impl ValueView>,
+        ) -> PartialVMResult<()>;
+
+        [Vector] fn charge_vec_unpack(
+            &mut self,
+            ty: impl TypeView,
+            expect_num_elements: NumArgs,
+            elems: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+
+        [Vector] fn charge_vec_swap(&mut self, ty: impl TypeView) -> PartialVMResult<()>;
+
+        [Native] fn charge_native_function_before_execution(
+            &mut self,
+            ty_args: impl ExactSizeIterator<Item = impl TypeView> + Clone,
+            args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+
+        [Call] fn charge_drop_frame(
+            &mut self,
+            locals: impl Iterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+    }
+
+    #[inline]
+    fn balance_internal(&self) -> InternalGas {
+        self.base.balance_internal()
+    }
+
+    fn charge_simple_instr(&mut self, instr: SimpleInstruction) -> PartialVMResult<()> {
+        let balance_before = self.base.balance_internal();
+        let res = self.base.charge_simple_instr(instr);
+        self.record_gas(InstructionBucket::Simple, balance_before);
+
+        if matches!(instr, SimpleInstruction::Ret) {
+            self.exit_function();
+        }
+        res
+    }
+
+    fn charge_call(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        num_locals: NumArgs,
+    ) -> PartialVMResult<()> {
+        self.call_depth += 1;
+        self.tracer.on_function_entry(module_id, func_name);
+
+        let balance_before = self.base.balance_internal();
+        let res = self
+            .base
+            .charge_call(module_id, func_name, args, num_locals);
+        self.record_gas(InstructionBucket::Call, balance_before);
+        res
+    }
+
+    fn charge_call_generic(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        ty_args: impl ExactSizeIterator<Item = impl TypeView> + Clone,
+        args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        num_locals: NumArgs,
+    ) -> PartialVMResult<()> {
+        self.call_depth += 1;
+        self.tracer.on_function_entry(module_id, func_name);
+
+        let balance_before = self.base.balance_internal();
+        let res = self
+            .base
+            .charge_call_generic(module_id, func_name, ty_args, args, num_locals);
+        self.record_gas(InstructionBucket::Call, balance_before);
+        res
+    }
+
+    fn charge_native_function(
+        &mut self,
+        amount: InternalGas,
+        ret_vals: Option<impl ExactSizeIterator<Item = impl ValueView> + Clone>,
+    ) -> PartialVMResult<()> {
+        let balance_before = self.base.balance_internal();
+        let res = self.base.charge_native_function(amount, ret_vals);
+        self.record_gas(InstructionBucket::Native, balance_before);
+
+        // Natives do not execute a return instruction, they are done once they are charged for.
+        self.exit_function();
+        res
+    }
+
+    fn charge_load_resource(
+        &mut self,
+        addr: AccountAddress,
+        ty: impl TypeView,
+        val: Option<impl ValueView>,
+        bytes_loaded: NumBytes,
+    ) -> PartialVMResult<()> {
+        self.tracer
+            .on_resource_read(addr, &ty.to_type_tag(), bytes_loaded);
+
+        let balance_before = self.base.balance_internal();
+        let res = self.base.charge_load_resource(addr, ty, val, bytes_loaded);
+        self.record_gas(InstructionBucket::Global, balance_before);
+        res
+    }
+}
+
+impl<G> AptosGasMeter for TracingGasMeter<G>
+where
+    G: AptosGasMeter,
+{
+    type Algebra = G::Algebra;
+
+    delegate! {
+        fn algebra(&self) -> &Self::Algebra;
+
+        fn storage_fee_for_state_slot(&self, op: &WriteOp) -> Fee;
+
+        fn storage_fee_refund_for_state_slot(&self, op: &WriteOp) -> Fee;
+
+        fn storage_fee_for_state_bytes(&self, key: &StateKey, maybe_value_size: Option<u64>) -> Fee;
+
+        fn storage_fee_per_event(&self, event: &ContractEvent) -> Fee;
+
+        fn storage_discount_for_events(&self, total_cost: Fee) -> Fee;
+
+        fn storage_fee_for_transaction_storage(&self, txn_size: NumBytes) -> Fee;
+    }
+
+    delegate_traced! {
+        [Storage] fn charge_io_gas_for_write(&mut self, key: &StateKey, op: &WriteOp) -> VMResult<()>;
+
+        [Storage] fn charge_io_gas_for_group_write(&mut self, key: &StateKey, group_write: &GroupWrite) -> VMResult<()>;
+
+        [Storage] fn charge_storage_fee(
+            &mut self,
+            amount: Fee,
+            gas_unit_price: FeePerGasUnit,
+        ) -> PartialVMResult<()>;
+
+        [Intrinsic] fn charge_intrinsic_gas_for_transaction(&mut self, txn_size: NumBytes) -> VMResult<()>;
+    }
+
+    fn algebra_mut(&mut self) -> &mut Self::Algebra {
+        self.base.algebra_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_gas_meter::{StandardGasAlgebra, StandardGasMeter};
+    use aptos_gas_schedule::{AptosGasParameters, InitialGasSchedule, LATEST_GAS_FEATURE_VERSION};
+    use aptos_types::{fee_statement::FeeStatement, transaction::ExecutionStatus};
+    use aptos_vm_types::{
+        change_set::VMChangeSet,
+        storage::{ChangeSetConfigs, StorageGasParameters},
+    };
+    use move_core_types::ident_str;
+    use move_vm_types::values::Value;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, PartialEq, Eq)]
+    enum Event {
+        Entry(String),
+        Exit,
+        Read(AccountAddress),
+        Write(StateKey),
+        End(Vec<InstructionBucket>),
+    }
+
+    struct RecordingTracer(Arc<Mutex<Vec<Event>>>);
+
+    impl ExecutionTracer for RecordingTracer {
+        fn on_function_entry(&mut self, _module_id: &ModuleId, func_name: &str) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(Event::Entry(func_name.to_string()));
+        }
+
+        fn on_function_exit(&mut self) {
+            self.0.lock().unwrap().push(Event::Exit);
+        }
+
+        fn on_resource_read(
+            &mut self,
+            addr: AccountAddress,
+            _ty: &TypeTag,
+            _bytes_loaded: NumBytes,
+        ) {
+            self.0.lock().unwrap().push(Event::Read(addr));
+        }
+
+        fn on_resource_write(&mut self, state_key: &StateKey, _op: &WriteOp) {
+            self.0.lock().unwrap().push(Event::Write(state_key.clone()));
+        }
+
+        fn on_transaction_end(
+            &mut self,
+            _status: &TransactionStatus,
+            gas_by_bucket: &BTreeMap<InstructionBucket, InternalGas>,
+        ) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(Event::End(gas_by_bucket.keys().copied().collect()));
+        }
+    }
+
+    struct U64Type;
+
+    impl TypeView for U64Type {
+        fn to_type_tag(&self) -> TypeTag {
+            TypeTag::U64
+        }
+    }
+
+    #[test]
+    fn test_tracing_gas_meter() {
+        let gas_params = AptosGasParameters::initial();
+        let storage_gas_params =
+            StorageGasParameters::unlimited(gas_params.vm.txn.free_write_bytes_quota);
+        let base = StandardGasMeter::new(StandardGasAlgebra::new(
+            LATEST_GAS_FEATURE_VERSION,
+            gas_params.vm,
+            storage_gas_params,
+            1_000_000,
+        ));
+        let events = Arc::new(Mutex::new(vec![]));
+        let mut gas_meter = TracingGasMeter::new(base, Box::new(RecordingTracer(events.clone())));
+
+        let module_id = ModuleId::new(AccountAddress::ONE, ident_str!("m").to_owned());
+        let no_args = std::iter::empty::<Value>();
+
+        // Returning from the entry function is not reported, as its entry is not either.
+        gas_meter
+            .charge_call(&module_id, "f", no_args.clone(), 0.into())
+            .unwrap();
+        gas_meter
+            .charge_load_resource(AccountAddress::TWO, U64Type, Some(Value::u64(1)), 8.into())
+            .unwrap();
+        gas_meter
+            .charge_simple_instr(SimpleInstruction::Ret)
+            .unwrap();
+        gas_meter
+            .charge_simple_instr(SimpleInstruction::Ret)
+            .unwrap();
+
+        let state_key = StateKey::raw(vec![0]);
+        let change_set = VMChangeSet::new(
+            BTreeMap::from([(
+                state_key.clone(),
+                (WriteOp::Modification(vec![1].into()), None),
+            )]),
+            BTreeMap::new(),
+            BTreeMap::new(),
+            BTreeMap::new(),
+            BTreeMap::new(),
+            BTreeMap::new(),
+            BTreeMap::new(),
+            vec![],
+            &ChangeSetConfigs::unlimited_at_gas_feature_version(LATEST_GAS_FEATURE_VERSION),
+        )
+        .unwrap();
+        let output = VMOutput::new(
+            change_set,
+            FeeStatement::zero(),
+            TransactionStatus::Keep(ExecutionStatus::Success),
+        );
+        gas_meter.finish(&output);
+
+        assert_eq!(*events.lock().unwrap(), vec![
+            Event::Entry("f".to_string()),
+            Event::Read(AccountAddress::TWO),
+            Event::Exit,
+            Event::Write(state_key),
+            Event::End(vec![
+                InstructionBucket::Simple,
+                InstructionBucket::Call,
+                InstructionBucket::Global,
+            ]),
+        ]);
+    }
+}
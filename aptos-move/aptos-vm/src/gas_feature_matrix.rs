@@ -0,0 +1,159 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Test harness executing the same transaction under multiple gas feature versions, so that the
+//! effects of a gas schedule bump can be checked on many transactions at once instead of with
+//! hand-written tests for every version.
+
+use crate::{data_cache::StorageAdapter, AptosVM};
+use aptos_gas_schedule::{AptosGasParameters, InitialGasSchedule, LATEST_GAS_FEATURE_VERSION};
+use aptos_types::{
+    on_chain_config::GasScheduleV2,
+    transaction::{
+        signature_verified_transaction::SignatureVerifiedTransaction, SignedTransaction,
+        Transaction, TransactionOutput,
+    },
+    vm_status::VMStatus,
+};
+use aptos_vm_logging::log_schema::AdapterLogSchema;
+use aptos_vm_types::resolver::ExecutorView;
+use std::fmt;
+
+/// Output of executing the transaction under a single gas feature version.
+#[derive(Debug)]
+pub struct GasFeatureVersionOutput {
+    pub gas_feature_version: u64,
+    pub result: Result<(VMStatus, TransactionOutput), VMStatus>,
+}
+
+/// Part of the output that differs between two gas feature versions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DivergenceKind {
+    /// The transaction failed to execute under only one of the versions.
+    ExecutionError,
+    Status,
+    GasUsed,
+    WriteSet,
+    Events,
+}
+
+/// Divergence between the outputs of two consecutive gas feature versions in the matrix.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    pub from_version: u64,
+    pub to_version: u64,
+    pub kinds: Vec<DivergenceKind>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "gas feature version {} -> {}: {:?}",
+            self.from_version, self.to_version, self.kinds
+        )
+    }
+}
+
+/// Outputs of executing a transaction under a sequence of gas feature versions.
+pub struct GasFeatureMatrix {
+    outputs: Vec<GasFeatureVersionOutput>,
+}
+
+impl GasFeatureMatrix {
+    /// Executes the transaction under every gas feature version up to the latest one.
+    pub fn run_all_versions(txn: &SignedTransaction, executor_view: &impl ExecutorView) -> Self {
+        Self::run(txn, executor_view, 0..=LATEST_GAS_FEATURE_VERSION)
+    }
+
+    /// Executes the transaction on top of the given state under each of the given gas feature
+    /// versions, using the initial gas parameters of that version. The state is not modified.
+    pub fn run(
+        txn: &SignedTransaction,
+        executor_view: &impl ExecutorView,
+        gas_feature_versions: impl IntoIterator<Item = u64>,
+    ) -> Self {
+        let txn = SignatureVerifiedTransaction::from(Transaction::UserTransaction(txn.clone()));
+        let log_context = AdapterLogSchema::new(executor_view.id(), 0);
+        let outputs = gas_feature_versions
+            .into_iter()
+            .map(|gas_feature_version| {
+                let vm = AptosVM::new_with_gas_override(
+                    &StorageAdapter::from_borrowed(executor_view),
+                    gas_schedule_at_version(gas_feature_version),
+                );
+                let resolver = vm.as_move_resolver(executor_view);
+                let result = vm
+                    .execute_single_transaction(&txn, &resolver, &log_context)
+                    .and_then(|(vm_status, vm_output, _)| {
+                        Ok((vm_status, vm_output.try_into_transaction_output(&resolver)?))
+                    });
+                GasFeatureVersionOutput {
+                    gas_feature_version,
+                    result,
+                }
+            })
+            .collect();
+        Self { outputs }
+    }
+
+    pub fn outputs(&self) -> &[GasFeatureVersionOutput] {
+        &self.outputs
+    }
+
+    /// Returns the divergences between the outputs of consecutive versions in the matrix.
+    pub fn divergences(&self) -> Vec<Divergence> {
+        self.outputs
+            .windows(2)
+            .filter_map(|pair| {
+                let kinds = diverging_kinds(&pair[0].result, &pair[1].result);
+                (!kinds.is_empty()).then(|| Divergence {
+                    from_version: pair[0].gas_feature_version,
+                    to_version: pair[1].gas_feature_version,
+                    kinds,
+                })
+            })
+            .collect()
+    }
+
+    /// Renders the divergences, one per line.
+    pub fn report(&self) -> String {
+        self.divergences()
+            .iter()
+            .map(|divergence| format!("{}\n", divergence))
+            .collect()
+    }
+}
+
+fn gas_schedule_at_version(gas_feature_version: u64) -> GasScheduleV2 {
+    GasScheduleV2 {
+        feature_version: gas_feature_version,
+        entries: AptosGasParameters::initial().to_on_chain_gas_schedule(gas_feature_version),
+    }
+}
+
+fn diverging_kinds(
+    lhs: &Result<(VMStatus, TransactionOutput), VMStatus>,
+    rhs: &Result<(VMStatus, TransactionOutput), VMStatus>,
+) -> Vec<DivergenceKind> {
+    let (lhs, rhs) = match (lhs, rhs) {
+        (Ok((_, lhs)), Ok((_, rhs))) => (lhs, rhs),
+        (Err(lhs), Err(rhs)) if lhs == rhs => return vec![],
+        _ => return vec![DivergenceKind::ExecutionError],
+    };
+
+    let mut kinds = vec![];
+    if lhs.status() != rhs.status() {
+        kinds.push(DivergenceKind::Status);
+    }
+    if lhs.gas_used() != rhs.gas_used() {
+        kinds.push(DivergenceKind::GasUsed);
+    }
+    if lhs.write_set() != rhs.write_set() {
+        kinds.push(DivergenceKind::WriteSet);
+    }
+    if lhs.events() != rhs.events() {
+        kinds.push(DivergenceKind::Events);
+    }
+    kinds
+}
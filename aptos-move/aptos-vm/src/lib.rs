@@ -112,29 +112,44 @@ pub mod data_cache;
 pub mod aptos_vm;
 mod aptos_vm_impl;
 pub mod block_executor;
+pub mod change_set_inspector;
+pub mod change_set_utils;
 mod errors;
+#[cfg(feature = "execution-tracing")]
+pub mod execution_tracer;
+#[cfg(feature = "testing")]
+pub mod gas_feature_matrix;
 pub mod move_vm_ext;
 pub mod natives;
 pub mod sharded_block_executor;
+pub mod simulation_output_cache;
 pub mod system_module_names;
 pub mod testing;
 pub mod transaction_metadata;
 mod transaction_validation;
 mod verifier;
+pub mod view_function;
 
 pub use crate::aptos_vm::AptosVM;
 use crate::sharded_block_executor::{executor_client::ExecutorClient, ShardedBlockExecutor};
 use aptos_state_view::StateView;
 use aptos_types::{
     block_executor::partitioner::PartitionedTransactions,
+    on_chain_config::BlockExecutionLimit,
     transaction::{
-        signature_verified_transaction::SignatureVerifiedTransaction, SignedTransaction,
-        TransactionOutput, VMValidatorResult,
+        signature_verified_transaction::SignatureVerifiedTransaction, SignatureCheckedTransaction,
+        SignedTransaction, TransactionOutput, VMValidatorResult,
     },
     vm_status::VMStatus,
 };
 use std::{marker::Sync, sync::Arc};
-pub use verifier::view_function::determine_is_view;
+pub use verifier::{
+    module_compatibility::{
+        check_bundle_compatibility, check_module_compatibility, CompatibilityCategory,
+        CompatibilityReport, Incompatibility, ModuleVerificationError, PublishCompatibilityReport,
+    },
+    view_function::determine_is_view,
+};
 
 /// This trait describes the VM's validation interfaces.
 pub trait VMValidator {
@@ -144,6 +159,14 @@ pub trait VMValidator {
         transaction: SignedTransaction,
         state_view: &impl StateView,
     ) -> VMValidatorResult;
+
+    /// Same as `validate_transaction`, for a transaction whose signature was already checked,
+    /// e.g., as part of a batch.
+    fn validate_checked_transaction(
+        &self,
+        transaction: SignatureCheckedTransaction,
+        state_view: &impl StateView,
+    ) -> VMValidatorResult;
 }
 
 /// This trait describes the VM's execution interface.
@@ -157,7 +180,7 @@ pub trait VMExecutor: Send + Sync {
     fn execute_block(
         transactions: &[SignatureVerifiedTransaction],
         state_view: &(impl StateView + Sync),
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> Result<Vec<TransactionOutput>, VMStatus>;
 
     /// Executes a block of transactions using a sharded block executor and returns the results.
@@ -165,7 +188,7 @@ pub trait VMExecutor: Send + Sync {
         sharded_block_executor: &ShardedBlockExecutor<S, E>,
         transactions: PartitionedTransactions,
         state_view: Arc<S>,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> Result<Vec<TransactionOutput>, VMStatus>;
 }
 
@@ -8,7 +8,7 @@ use aptos_vm_types::resolver::{
     ExecutorView, ResourceGroupView, StateStorageView, StateValueMetadataResolver,
 };
 use bytes::Bytes;
-use move_core_types::{language_storage::StructTag, resolver::MoveResolver};
+use move_core_types::{effects::Op, language_storage::StructTag, resolver::MoveResolver};
 use std::collections::{BTreeMap, HashMap};
 
 /// A general resolver used by AptosVM. Allows to implement custom hooks on
@@ -25,9 +25,20 @@ pub trait AptosMoveResolver:
     + TableResolver
     + AsExecutorView
     + AsResourceGroupView
+    + AccessTraceResolver
 {
 }
 
+/// Allows to record the state reads issued through the resolver, used to build an
+/// `AccessTrace` for a transaction.
+pub trait AccessTraceResolver {
+    /// Starts recording keys and sizes of all state reads. Has no effect if already enabled.
+    fn enable_access_trace(&self);
+
+    /// Returns all reads recorded so far and clears them, or `None` if recording is not enabled.
+    fn take_recorded_reads(&self) -> Option<Vec<(StateKey, u64)>>;
+}
+
 pub trait ResourceGroupResolver {
     fn release_resource_group_cache(&self)
         -> Option<HashMap<StateKey, BTreeMap<StructTag, Bytes>>>;
@@ -45,6 +56,42 @@ pub trait ResourceGroupResolver {
         group_key: &StateKey,
         resource_tag: &StructTag,
     ) -> anyhow::Result<bool>;
+
+    /// Returns the (speculative) size of the group after applying the pending changes to its
+    /// members, where new and modified members carry their new serialized size. The size is
+    /// computed the same way as when the changes are converted into a `GroupWrite`, so it can
+    /// be used to quote storage fees for group mutations before they are made.
+    fn resource_group_size_after(
+        &self,
+        group_key: &StateKey,
+        pending_changes: &BTreeMap<StructTag, Op<u64>>,
+    ) -> anyhow::Result<u64> {
+        let size_underflow = || {
+            anyhow::anyhow!(
+                "Group size underflow while applying updates to {:?}",
+                group_key
+            )
+        };
+        pending_changes.iter().try_fold(
+            self.resource_group_size(group_key)?,
+            |cur_size, (tag, op)| {
+                let tag_size = bcs::serialized_size(tag)? as u64;
+                // Like during the conversion, the previous size of new members is not queried.
+                let cur_size = if !matches!(op, Op::New(_)) {
+                    let old_size = self.resource_size_in_group(group_key, tag)? + tag_size;
+                    cur_size.checked_sub(old_size).ok_or_else(size_underflow)?
+                } else {
+                    cur_size
+                };
+                match op {
+                    Op::Delete => Ok(cur_size),
+                    Op::New(size) | Op::Modify(size) => cur_size
+                        .checked_add(size + tag_size)
+                        .ok_or_else(size_underflow),
+                }
+            },
+        )
+    }
 }
 
 pub trait AsExecutorView {
@@ -27,6 +27,7 @@ use aptos_types::{
 };
 use aptos_vm_types::{
     change_set::VMChangeSet,
+    check_change_set::CheckChangeSet,
     resolver::{
         ExecutorView, ResourceGroupView, StateStorageView, TModuleView, TResourceGroupView,
         TResourceView,
@@ -73,28 +74,99 @@ impl<'r, 'l> RespawnedSession<'r, 'l> {
             base.as_resource_group_view(),
             previous_session_change_set,
         );
+        Ok(Self::build(vm, session_id, executor_view, storage_refund))
+    }
 
-        Ok(RespawnedSessionBuilder {
+    fn build(
+        vm: &'l AptosVM,
+        session_id: SessionId,
+        executor_view: ExecutorViewWithChangeSet<'r>,
+        storage_refund: Fee,
+    ) -> Self {
+        RespawnedSessionBuilder {
             executor_view,
             resolver_builder: |executor_view| vm.as_move_resolver(executor_view),
             session_builder: |resolver| Some(vm.0.new_session(resolver, session_id)),
             storage_refund,
         }
-        .build())
+        .build()
+    }
+
+    /// Spawns a session nested in this one, e.g., to run a user payload inside of an
+    /// orchestrating session. The nested session observes the state of this session as of its
+    /// last checkpoint (see `checkpoint`) and reports the same storage refund, and its changes
+    /// are only applied to this session via `squash_nested`. Dropping the nested session (e.g.,
+    /// on abort) rolls back all its changes without affecting this session. Like any respawned
+    /// session, nested sessions must not create new slots.
+    pub fn spawn_nested<'a>(
+        &'a self,
+        vm: &'l AptosVM,
+        session_id: SessionId,
+    ) -> RespawnedSession<'a, 'l> {
+        let parent_view = self.borrow_executor_view();
+        let executor_view =
+            ExecutorViewWithChangeSet::new(parent_view, parent_view, VMChangeSet::empty());
+        RespawnedSession::build(vm, session_id, executor_view, self.get_storage_fee_refund())
+    }
+
+    /// Finishes a nested session, returning only the changes made by it (and sessions nested in
+    /// it), to be passed to `squash_nested` of the parent.
+    pub fn finish_nested(
+        self,
+        change_set_configs: &ChangeSetConfigs,
+    ) -> Result<VMChangeSet, VMStatus> {
+        let (executor_view, _) =
+            self.finish_into_executor_view(VMChangeSet::empty(), change_set_configs)?;
+        Ok(executor_view.change_set)
+    }
+
+    /// Makes all changes of the current session visible to nested sessions spawned afterwards.
+    /// The current session is finished, and execution continues in a new session identified by
+    /// `session_id`.
+    pub fn checkpoint(
+        self,
+        vm: &'l AptosVM,
+        session_id: SessionId,
+        change_set_configs: &ChangeSetConfigs,
+    ) -> Result<Self, VMStatus> {
+        self.squash_nested(vm, session_id, VMChangeSet::empty(), change_set_configs)
+    }
+
+    /// Merges the changes of a finished nested session with the changes of this session. The
+    /// current session is finished first, and execution continues in a new session identified
+    /// by `session_id`. Fails if the nested session overwrites anything this session changed
+    /// after its last checkpoint, as the nested session didn't observe these changes.
+    pub fn squash_nested(
+        self,
+        vm: &'l AptosVM,
+        session_id: SessionId,
+        nested_change_set: VMChangeSet,
+        change_set_configs: &ChangeSetConfigs,
+    ) -> Result<Self, VMStatus> {
+        let (executor_view, storage_refund) =
+            self.finish_into_executor_view(nested_change_set, change_set_configs)?;
+        Ok(Self::build(vm, session_id, executor_view, storage_refund))
     }
 
     pub fn execute<R>(&mut self, fun: impl FnOnce(&mut SessionExt) -> R) -> R {
         self.with_session_mut(|session| fun(session.as_mut().unwrap()))
     }
 
-    pub fn finish(
+    pub fn finish(self, change_set_configs: &ChangeSetConfigs) -> Result<VMChangeSet, VMStatus> {
+        let (executor_view, _) =
+            self.finish_into_executor_view(VMChangeSet::empty(), change_set_configs)?;
+        Ok(executor_view.change_set)
+    }
+
+    fn finish_into_executor_view(
         mut self,
+        nested_change_set: VMChangeSet,
         change_set_configs: &ChangeSetConfigs,
-    ) -> Result<VMChangeSet, VMStatus> {
+    ) -> Result<(ExecutorViewWithChangeSet<'r>, Fee), VMStatus> {
         let additional_change_set = self.with_session_mut(|session| {
             session.take().unwrap().finish(&mut (), change_set_configs)
         })?;
-        if additional_change_set.has_creation() {
+        if additional_change_set.has_creation() || nested_change_set.has_creation() {
             // After respawning, for example, in the epilogue, there shouldn't be new slots
             // created, otherwise there's a potential vulnerability like this:
             // 1. slot created by the user
@@ -107,16 +179,15 @@ impl<'r, 'l> RespawnedSession<'r, 'l> {
                 err_msg("Unexpected storage allocation after respawning session."),
             ));
         }
-        let mut change_set = self.into_heads().executor_view.change_set;
-        change_set
-            .squash_additional_change_set(additional_change_set, change_set_configs)
-            .map_err(|_err| {
-                VMStatus::error(
-                    StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR,
-                    err_msg("Failed to squash VMChangeSet"),
-                )
-            })?;
-        Ok(change_set)
+        let heads = self.into_heads();
+        let mut executor_view = heads.executor_view;
+        squash_nested_change_set(
+            &mut executor_view.change_set,
+            additional_change_set,
+            nested_change_set,
+            change_set_configs,
+        )?;
+        Ok((executor_view, heads.storage_refund))
     }
 
     pub fn get_storage_fee_refund(&self) -> Fee {
@@ -124,6 +195,96 @@ impl<'r, 'l> RespawnedSession<'r, 'l> {
     }
 }
 
+/// Squashes the changes of a session made after its last checkpoint, and then the changes of
+/// a nested session spawned at that checkpoint, into the checkpointed changes.
+fn squash_nested_change_set(
+    change_set: &mut VMChangeSet,
+    uncheckpointed_change_set: VMChangeSet,
+    nested_change_set: VMChangeSet,
+    checker: &dyn CheckChangeSet,
+) -> Result<(), VMStatus> {
+    check_nested_conflicts(&uncheckpointed_change_set, &nested_change_set)?;
+    for additional_change_set in [uncheckpointed_change_set, nested_change_set] {
+        change_set
+            .squash_additional_change_set(additional_change_set, checker)
+            .map_err(|_err| squash_error())?;
+    }
+    Ok(())
+}
+
+/// A nested session observes its parent as of the parent's last checkpoint, so it must not
+/// overwrite anything the parent changed since then. Aggregator deltas commute with each other
+/// and are merged, but not with other changes of the same aggregator. Snapshots taken by the
+/// nested session depend on the aggregators they are taken from.
+fn check_nested_conflicts(
+    uncheckpointed_change_set: &VMChangeSet,
+    nested_change_set: &VMChangeSet,
+) -> Result<(), VMStatus> {
+    let changed_keys: HashSet<&StateKey> = uncheckpointed_change_set
+        .resource_write_set()
+        .keys()
+        .chain(uncheckpointed_change_set.resource_group_write_set().keys())
+        .chain(uncheckpointed_change_set.module_write_set().keys())
+        .chain(uncheckpointed_change_set.aggregator_v1_write_set().keys())
+        .chain(uncheckpointed_change_set.aggregator_v1_delta_set().keys())
+        .collect();
+    let overwrites_changed_key = nested_change_set
+        .resource_write_set()
+        .keys()
+        .chain(nested_change_set.resource_group_write_set().keys())
+        .chain(nested_change_set.module_write_set().keys())
+        .chain(nested_change_set.aggregator_v1_write_set().keys())
+        .any(|key| changed_keys.contains(key));
+    let applies_to_changed_aggregator_v1 =
+        nested_change_set
+            .aggregator_v1_delta_set()
+            .keys()
+            .any(|key| {
+                changed_keys.contains(key)
+                    && !uncheckpointed_change_set
+                        .aggregator_v1_delta_set()
+                        .contains_key(key)
+            });
+
+    let changed_delayed_fields = uncheckpointed_change_set.delayed_field_change_set();
+    let changes_changed_delayed_field =
+        nested_change_set
+            .delayed_field_change_set()
+            .iter()
+            .any(|(id, change)| {
+                let conflicts_with = |prev_change: &DelayedChange<DelayedFieldID>| {
+                    !matches!(
+                        (prev_change, change),
+                        (
+                            DelayedChange::Apply(DelayedApplyChange::AggregatorDelta { .. }),
+                            DelayedChange::Apply(DelayedApplyChange::AggregatorDelta { .. }),
+                        )
+                    )
+                };
+                changed_delayed_fields.get(id).map_or(false, conflicts_with)
+                    || change
+                        .get_merge_dependent_id()
+                        .map_or(false, |dependent_id| {
+                            changed_delayed_fields.contains_key(&dependent_id)
+                        })
+            });
+
+    if overwrites_changed_key || applies_to_changed_aggregator_v1 || changes_changed_delayed_field {
+        return Err(VMStatus::error(
+            StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR,
+            err_msg("Nested session overwrites changes made after the last checkpoint."),
+        ));
+    }
+    Ok(())
+}
+
+fn squash_error() -> VMStatus {
+    VMStatus::error(
+        StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR,
+        err_msg("Failed to squash VMChangeSet"),
+    )
+}
+
 /// Adapter to allow resolving the calls to `ExecutorView` via change set.
 pub struct ExecutorViewWithChangeSet<'r> {
     base_executor_view: &'r dyn ExecutorView,
@@ -343,14 +504,19 @@ mod test {
         data_cache::AsMoveResolver,
         move_vm_ext::resolver::{AsExecutorView, AsResourceGroupView},
     };
-    use aptos_aggregator::delta_change_set::{delta_add, serialize};
-    use aptos_language_e2e_tests::data_store::FakeDataStore;
-    use aptos_types::{account_address::AccountAddress, write_set::WriteOp};
-    use aptos_vm_types::{change_set::GroupWrite, check_change_set::CheckChangeSet};
+    use aptos_aggregator::delta_change_set::{delta_add, serialize, DeltaOp};
+    use aptos_gas_schedule::LATEST_GAS_FEATURE_VERSION;
+    use aptos_language_e2e_tests::data_store::{FakeDataStore, GENESIS_CHANGE_SET_HEAD};
+    use aptos_types::{
+        account_address::AccountAddress, account_config::CORE_CODE_ADDRESS, write_set::WriteOp,
+    };
+    use aptos_vm_types::change_set::GroupWrite;
     use move_core_types::{
-        identifier::Identifier,
-        language_storage::{StructTag, TypeTag},
+        ident_str,
+        identifier::{IdentStr, Identifier},
+        language_storage::{ModuleId, StructTag, TypeTag},
     };
+    use move_vm_types::gas::UnmeteredGasMeter;
     use std::collections::BTreeMap;
 
     /// A mock for testing. Always succeeds on checking a change set.
@@ -552,5 +718,297 @@ mod test {
         );
     }
 
+    fn change_set_with_resources(
+        resource_write_set: impl IntoIterator<Item = (StateKey, WriteOp)>,
+    ) -> VMChangeSet {
+        VMChangeSet::new(
+            resource_write_set
+                .into_iter()
+                .map(|(k, op)| (k, (op, None)))
+                .collect(),
+            BTreeMap::new(),
+            BTreeMap::new(),
+            BTreeMap::new(),
+            BTreeMap::new(),
+            BTreeMap::new(),
+            BTreeMap::new(),
+            vec![],
+            &NoOpChangeSetChecker,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_nested_change_set_views() {
+        let mut state_view = FakeDataStore::default();
+        state_view.set_legacy(key("base"), serialize(&10));
+        state_view.set_legacy(key("outer"), serialize(&20));
+        state_view.set_legacy(key("inner"), serialize(&30));
+
+        let resolver = state_view.as_move_resolver();
+        let outer_view = ExecutorViewWithChangeSet::new(
+            resolver.as_executor_view(),
+            resolver.as_resource_group_view(),
+            change_set_with_resources([(key("outer"), write(21)), (key("inner"), write(31))]),
+        );
+        let inner_change_set = change_set_with_resources([(key("inner"), write(32))]);
+
+        // The nested view sees its own changes on top of the changes of all enclosing levels.
+        {
+            let inner_view =
+                ExecutorViewWithChangeSet::new(&outer_view, &outer_view, inner_change_set.clone());
+            assert_eq!(read_resource(&inner_view, "base"), 10);
+            assert_eq!(read_resource(&inner_view, "outer"), 21);
+            assert_eq!(read_resource(&inner_view, "inner"), 32);
+        }
+
+        // Dropping the nested level rolls back its changes.
+        assert_eq!(read_resource(&outer_view, "inner"), 31);
+
+        // Squashing the nested level applies its changes to the enclosing one.
+        let mut outer_change_set = outer_view.change_set;
+        outer_change_set
+            .squash_additional_change_set(inner_change_set, &NoOpChangeSetChecker)
+            .unwrap();
+        let outer_view = ExecutorViewWithChangeSet::new(
+            resolver.as_executor_view(),
+            resolver.as_resource_group_view(),
+            outer_change_set,
+        );
+        assert_eq!(read_resource(&outer_view, "base"), 10);
+        assert_eq!(read_resource(&outer_view, "outer"), 21);
+        assert_eq!(read_resource(&outer_view, "inner"), 32);
+    }
+
+    fn call_account_function(session: &mut RespawnedSession, function: &IdentStr) -> Vec<u8> {
+        let account_module = ModuleId::new(CORE_CODE_ADDRESS, Identifier::new("account").unwrap());
+        let mut values = session
+            .execute(|session| {
+                session.execute_function_bypass_visibility(
+                    &account_module,
+                    function,
+                    vec![],
+                    vec![bcs::to_bytes(&CORE_CODE_ADDRESS).unwrap()],
+                    &mut UnmeteredGasMeter,
+                )
+            })
+            .unwrap();
+        values
+            .return_values
+            .pop()
+            .map_or(vec![], |(bytes, _)| bytes)
+    }
+
+    fn sequence_number(session: &mut RespawnedSession) -> u64 {
+        bcs::from_bytes(&call_account_function(
+            session,
+            ident_str!("get_sequence_number"),
+        ))
+        .unwrap()
+    }
+
+    fn increment_sequence_number(session: &mut RespawnedSession) {
+        call_account_function(session, ident_str!("increment_sequence_number"));
+    }
+
+    #[test]
+    fn test_nested_sessions() {
+        let mut state_view = FakeDataStore::default();
+        state_view.add_write_set(GENESIS_CHANGE_SET_HEAD.write_set());
+        let resolver = state_view.as_move_resolver();
+        let vm = AptosVM::new(&resolver);
+        let configs =
+            ChangeSetConfigs::unlimited_at_gas_feature_version(LATEST_GAS_FEATURE_VERSION);
+
+        let mut parent = RespawnedSession::spawn(
+            &vm,
+            SessionId::Void,
+            &resolver,
+            VMChangeSet::empty(),
+            7.into(),
+        )
+        .unwrap();
+        let initial = sequence_number(&mut parent);
+        increment_sequence_number(&mut parent);
+        let mut parent = parent.checkpoint(&vm, SessionId::Void, &configs).unwrap();
+
+        // Aborting a nested session rolls back its changes only.
+        {
+            let mut nested = parent.spawn_nested(&vm, SessionId::Void);
+            assert_eq!(nested.get_storage_fee_refund(), 7.into());
+            assert_eq!(sequence_number(&mut nested), initial + 1);
+            increment_sequence_number(&mut nested);
+            assert_eq!(sequence_number(&mut nested), initial + 2);
+        }
+        assert_eq!(sequence_number(&mut parent), initial + 1);
+
+        // Changes are applied level by level, rolling back the aborted innermost session.
+        let nested_change_set = {
+            let mut nested = parent.spawn_nested(&vm, SessionId::Void);
+            increment_sequence_number(&mut nested);
+            let mut nested = nested.checkpoint(&vm, SessionId::Void, &configs).unwrap();
+            {
+                let mut inner = nested.spawn_nested(&vm, SessionId::Void);
+                assert_eq!(inner.get_storage_fee_refund(), 7.into());
+                increment_sequence_number(&mut inner);
+                assert_eq!(sequence_number(&mut inner), initial + 3);
+            }
+            let inner_change_set = {
+                let mut inner = nested.spawn_nested(&vm, SessionId::Void);
+                increment_sequence_number(&mut inner);
+                increment_sequence_number(&mut inner);
+                inner.finish_nested(&configs).unwrap()
+            };
+            assert_eq!(sequence_number(&mut nested), initial + 2);
+            let mut nested = nested
+                .squash_nested(&vm, SessionId::Void, inner_change_set, &configs)
+                .unwrap();
+            assert_eq!(sequence_number(&mut nested), initial + 4);
+            nested.finish_nested(&configs).unwrap()
+        };
+        assert_eq!(sequence_number(&mut parent), initial + 1);
+        let mut parent = parent
+            .squash_nested(&vm, SessionId::Void, nested_change_set, &configs)
+            .unwrap();
+        assert_eq!(sequence_number(&mut parent), initial + 4);
+        assert_eq!(parent.get_storage_fee_refund(), 7.into());
+
+        // A nested session must not overwrite changes it didn't observe.
+        increment_sequence_number(&mut parent);
+        let nested_change_set = {
+            let mut nested = parent.spawn_nested(&vm, SessionId::Void);
+            assert_eq!(sequence_number(&mut nested), initial + 4);
+            increment_sequence_number(&mut nested);
+            nested.finish_nested(&configs).unwrap()
+        };
+        assert!(parent
+            .squash_nested(&vm, SessionId::Void, nested_change_set, &configs)
+            .is_err());
+    }
+
+    #[test]
+    fn test_nested_session_cannot_create_slots() {
+        let mut state_view = FakeDataStore::default();
+        state_view.add_write_set(GENESIS_CHANGE_SET_HEAD.write_set());
+        let resolver = state_view.as_move_resolver();
+        let vm = AptosVM::new(&resolver);
+        let configs =
+            ChangeSetConfigs::unlimited_at_gas_feature_version(LATEST_GAS_FEATURE_VERSION);
+
+        let parent = RespawnedSession::spawn(
+            &vm,
+            SessionId::Void,
+            &resolver,
+            VMChangeSet::empty(),
+            0.into(),
+        )
+        .unwrap();
+        let creation =
+            change_set_with_resources([(key("new"), WriteOp::Creation(serialize(&1).into()))]);
+        assert!(parent
+            .squash_nested(&vm, SessionId::Void, creation, &configs)
+            .is_err());
+    }
+
+    fn change_set_with_aggregators(
+        aggregator_v1_write_set: impl IntoIterator<Item = (StateKey, WriteOp)>,
+        aggregator_v1_delta_set: impl IntoIterator<Item = (StateKey, DeltaOp)>,
+        delayed_field_change_set: impl IntoIterator<
+            Item = (DelayedFieldID, DelayedChange<DelayedFieldID>),
+        >,
+    ) -> VMChangeSet {
+        VMChangeSet::new(
+            BTreeMap::new(),
+            BTreeMap::new(),
+            BTreeMap::new(),
+            aggregator_v1_write_set.into_iter().collect(),
+            aggregator_v1_delta_set.into_iter().collect(),
+            delayed_field_change_set.into_iter().collect(),
+            BTreeMap::new(),
+            vec![],
+            &NoOpChangeSetChecker,
+        )
+        .unwrap()
+    }
+
+    fn aggregator_delta(v: u128) -> DelayedChange<DelayedFieldID> {
+        DelayedChange::Apply(DelayedApplyChange::AggregatorDelta {
+            delta: DeltaWithMax::new(SignedU128::Positive(v), 100),
+        })
+    }
+
+    fn squash_nested(
+        uncheckpointed_change_set: VMChangeSet,
+        nested_change_set: VMChangeSet,
+    ) -> Result<VMChangeSet, VMStatus> {
+        let mut change_set = VMChangeSet::empty();
+        squash_nested_change_set(
+            &mut change_set,
+            uncheckpointed_change_set,
+            nested_change_set,
+            &NoOpChangeSetChecker,
+        )?;
+        Ok(change_set)
+    }
+
+    #[test]
+    fn test_nested_squash_with_aggregators() {
+        let aggregator = DelayedFieldID::new(1);
+        let snapshot = DelayedFieldID::new(2);
+
+        // Aggregator deltas of the nested session are merged with the deltas of its parent.
+        let change_set = squash_nested(
+            change_set_with_aggregators([], [(key("aggregator_v1"), delta_add(1, 100))], [(
+                aggregator,
+                aggregator_delta(1),
+            )]),
+            change_set_with_aggregators([], [(key("aggregator_v1"), delta_add(2, 100))], [(
+                aggregator,
+                aggregator_delta(2),
+            )]),
+        )
+        .unwrap();
+        assert_eq!(
+            change_set
+                .aggregator_v1_delta_set()
+                .get(&key("aggregator_v1"))
+                .unwrap()
+                .get_update(),
+            SignedU128::Positive(3)
+        );
+        assert_eq!(
+            change_set.delayed_field_change_set().get(&aggregator),
+            Some(&aggregator_delta(3))
+        );
+
+        // Deltas don't apply on top of values the nested session didn't observe.
+        assert!(squash_nested(
+            change_set_with_aggregators([(key("aggregator_v1"), write(10))], [], []),
+            change_set_with_aggregators([], [(key("aggregator_v1"), delta_add(2, 100))], []),
+        )
+        .is_err());
+        assert!(squash_nested(
+            change_set_with_aggregators([], [], [(
+                aggregator,
+                DelayedChange::Create(DelayedFieldValue::Aggregator(10))
+            )]),
+            change_set_with_aggregators([], [], [(aggregator, aggregator_delta(2))]),
+        )
+        .is_err());
+
+        // Snapshots of the nested session don't include the deltas it didn't observe.
+        assert!(squash_nested(
+            change_set_with_aggregators([], [], [(aggregator, aggregator_delta(1))]),
+            change_set_with_aggregators([], [], [(
+                snapshot,
+                DelayedChange::Apply(DelayedApplyChange::SnapshotDelta {
+                    base_aggregator: aggregator,
+                    delta: DeltaWithMax::new(SignedU128::Positive(2), 100),
+                })
+            )]),
+        )
+        .is_err());
+    }
+
     // TODO[agg_v2](tests) add delayed field tests
 }
@@ -4,7 +4,10 @@
 use crate::{
     access_path_cache::AccessPathCache,
     data_cache::get_resource_group_from_metadata,
-    move_vm_ext::{write_op_converter::WriteOpConverter, AptosMoveResolver},
+    move_vm_ext::{
+        write_op_converter::{ConversionContext, WriteOpConverter},
+        AptosMoveResolver,
+    },
     transaction_metadata::TransactionMetadata,
 };
 use aptos_crypto::{hash::CryptoHash, HashValue};
@@ -17,12 +20,15 @@ use aptos_framework::natives::{
 use aptos_table_natives::{NativeTableContext, TableChangeSet};
 use aptos_types::{
     block_metadata::BlockMetadata,
+    block_metadata_ext::BlockMetadataExt,
     contract_event::ContractEvent,
     on_chain_config::Features,
     state_store::state_key::StateKey,
     transaction::{SignatureCheckedTransaction, SignedTransaction},
 };
-use aptos_vm_types::{change_set::VMChangeSet, storage::ChangeSetConfigs};
+use aptos_vm_types::{
+    access_trace::AccessTrace, change_set::VMChangeSet, storage::ChangeSetConfigs,
+};
 use bytes::Bytes;
 use move_binary_format::errors::{Location, PartialVMError, PartialVMResult, VMResult};
 use move_core_types::{
@@ -32,7 +38,7 @@ use move_core_types::{
     value::MoveTypeLayout,
     vm_status::{StatusCode, VMStatus},
 };
-use move_vm_runtime::{move_vm::MoveVM, session::Session};
+use move_vm_runtime::session::Session;
 use move_vm_types::values::Value;
 use serde::{Deserialize, Serialize};
 use std::{
@@ -78,6 +84,10 @@ pub enum SessionId {
     },
     // For those runs that are not a transaction and the output of which won't be committed.
     Void,
+    BlockMetaExt {
+        // block id
+        id: HashValue,
+    },
 }
 
 impl SessionId {
@@ -103,6 +113,12 @@ impl SessionId {
         }
     }
 
+    pub fn block_meta_ext(block_meta_ext: &BlockMetadataExt) -> Self {
+        Self::BlockMetaExt {
+            id: block_meta_ext.id(),
+        }
+    }
+
     pub fn prologue(txn: &SignedTransaction) -> Self {
         Self::prologue_meta(&TransactionMetadata::new(&txn.clone()))
     }
@@ -155,6 +171,31 @@ impl<'r, 'l> SessionExt<'r, 'l> {
         }
     }
 
+    /// Starts recording all state reads of this session (and any later session sharing the
+    /// same resolver), so that an `AccessTrace` can be produced when finishing.
+    pub fn enable_access_trace(&self) {
+        self.remote.enable_access_trace();
+    }
+
+    /// Same as `finish`, but also returns the trace of reads recorded by the resolver since the
+    /// last time they were taken, followed by the writes of the produced change set. Returns no
+    /// trace if recording was not enabled via `enable_access_trace`.
+    pub fn finish_with_access_trace<C: AccessPathCache>(
+        self,
+        ap_cache: &mut C,
+        configs: &ChangeSetConfigs,
+    ) -> VMResult<(VMChangeSet, Option<AccessTrace>)> {
+        let remote = self.remote;
+        let change_set = self.finish(ap_cache, configs)?;
+        let access_trace = remote.take_recorded_reads().map(|reads| {
+            let mut trace = AccessTrace::new();
+            trace.extend_reads(reads);
+            trace.record_writes(&change_set);
+            trace
+        });
+        Ok((change_set, access_trace))
+    }
+
     pub fn finish<C: AccessPathCache>(
         self,
         ap_cache: &mut C,
@@ -179,8 +220,16 @@ impl<'r, 'l> SessionExt<'r, 'l> {
             .inner
             .finish_with_extensions_with_custom_effects(&resource_converter)?;
 
-        let (change_set, resource_group_change_set) =
-            Self::split_and_merge_resource_groups(move_vm, self.remote, change_set, ap_cache)?;
+        let (change_set, resource_group_change_set) = Self::split_and_merge_resource_groups(
+            |struct_tag| {
+                move_vm.with_module_metadata(&struct_tag.module_id(), |md| {
+                    get_resource_group_from_metadata(struct_tag, md)
+                })
+            },
+            self.remote,
+            change_set,
+            ap_cache,
+        )?;
 
         let table_context: NativeTableContext = extensions.remove();
         let table_change_set = table_context
@@ -197,6 +246,7 @@ impl<'r, 'l> SessionExt<'r, 'l> {
 
         let woc = WriteOpConverter::new(
             self.remote,
+            ConversionContext::Speculative,
             self.features.is_storage_slot_metadata_enabled(),
         );
 
@@ -295,8 +345,8 @@ impl<'r, 'l> SessionExt<'r, 'l> {
     /// V1 Resource group change set behavior keeps ops for individual resources separate, not
     /// merging them into the a single op corresponding to the whole resource group (V0).
     /// TODO[agg_v2](fix) Resource groups are currently not handled correctly in terms of propagating MoveTypeLayout
-    fn split_and_merge_resource_groups<C: AccessPathCache>(
-        runtime: &MoveVM,
+    pub(crate) fn split_and_merge_resource_groups<C: AccessPathCache>(
+        get_resource_group: impl Fn(&StructTag) -> Option<StructTag>,
         remote: &dyn AptosMoveResolver,
         change_set: ChangeSet,
         ap_cache: &mut C,
@@ -329,10 +379,7 @@ impl<'r, 'l> SessionExt<'r, 'l> {
             let (modules, resources) = account_changeset.into_inner();
 
             for (struct_tag, blob_op) in resources {
-                let resource_group_tag = runtime
-                    .with_module_metadata(&struct_tag.module_id(), |md| {
-                        get_resource_group_from_metadata(&struct_tag, md)
-                    });
+                let resource_group_tag = get_resource_group(&struct_tag);
 
                 if let Some(resource_group_tag) = resource_group_tag {
                     if resource_groups
@@ -472,11 +519,15 @@ impl<'r, 'l> SessionExt<'r, 'l> {
             }
         }
 
-        // We need to remove values that are already in the writes.
+        // We need to remove values that are already in the writes, including the writes to
+        // resource groups (where the members keep their layouts for the exchange).
         let reads_needing_exchange = aggregator_change_set
             .reads_needing_exchange
             .into_iter()
-            .filter(|(state_key, _)| !resource_write_set.contains_key(state_key))
+            .filter(|(state_key, _)| {
+                !resource_write_set.contains_key(state_key)
+                    && !resource_group_write_set.contains_key(state_key)
+            })
             .collect();
 
         VMChangeSet::new(
@@ -7,6 +7,7 @@ use aptos_framework::natives::{
     code::NativeCodeContext,
     cryptography::{algebra::AlgebraContext, ristretto255_point::NativeRistrettoPointContext},
     event::NativeEventContext,
+    randomness::RandomnessContext,
     state_storage::NativeStateStorageContext,
     transaction_context::NativeTransactionContext,
 };
@@ -101,6 +102,11 @@ impl MoveVmExt {
         // so they can be exchanged with identifiers during VM execution.
         let aggregator_v2_type_tagging = features.is_aggregator_v2_delayed_fields_enabled();
 
+        // Friends of a module are not needed to execute it, so they are only loaded if they are
+        // used themselves instead of as part of the closure of every loaded module.
+        let lazy_friend_loading = features.is_enabled(FeatureFlag::LAZY_FRIEND_LOADING);
+        let type_layout_cache = features.is_enabled(FeatureFlag::TYPE_LAYOUT_CACHE);
+
         let mut builder = SafeNativeBuilder::new(
             gas_feature_version,
             native_gas_params.clone(),
@@ -130,6 +136,8 @@ impl MoveVmExt {
                     type_base_cost,
                     type_byte_cost,
                     aggregator_v2_type_tagging,
+                    lazy_friend_loading,
+                    type_layout_cache,
                 },
                 resolver,
             )?,
@@ -228,6 +236,7 @@ impl MoveVmExt {
         extensions.add(NativeCodeContext::default());
         extensions.add(NativeStateStorageContext::new(resolver));
         extensions.add(NativeEventContext::default());
+        extensions.add(RandomnessContext::new());
 
         // The VM code loader has bugs around module upgrade. After a module upgrade, the internal
         // cache needs to be flushed to work around those bugs.
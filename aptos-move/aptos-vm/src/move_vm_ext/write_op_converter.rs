@@ -11,7 +11,7 @@ use aptos_types::{
     },
     write_set::WriteOp,
 };
-use aptos_vm_types::change_set::GroupWrite;
+use aptos_vm_types::{change_set::GroupWrite, storage_refund::StorageRefundCalculator};
 use bytes::Bytes;
 use move_core_types::{
     effects::Op as MoveStorageOp,
@@ -21,8 +21,21 @@ use move_core_types::{
 };
 use std::{collections::BTreeMap, sync::Arc};
 
+/// The context in which a change set is converted, which determines how inconsistencies between
+/// the change set and the state read through the resolver are reported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ConversionContext {
+    /// Under speculative (Block-STM) execution, reads can be outdated, so inconsistencies are
+    /// reported as speculative errors and the transaction is re-executed.
+    Speculative,
+    /// In sequential execution (e.g. replay) the state is final, so inconsistencies are
+    /// invariant violations.
+    Sequential,
+}
+
 pub(crate) struct WriteOpConverter<'r> {
     remote: &'r dyn AptosMoveResolver,
+    context: ConversionContext,
     new_slot_metadata: Option<StateValueMetadata>,
 }
 
@@ -40,6 +53,7 @@ macro_rules! convert_impl {
                 MoveStorageOp::Delete => MoveStorageOp::Delete,
             };
             self.convert(
+                state_key,
                 self.remote.$get_metadata_callback(state_key),
                 move_storage_op,
                 legacy_creation_as_modification,
@@ -55,19 +69,19 @@ impl<'r> WriteOpConverter<'r> {
 
     pub(crate) fn new(
         remote: &'r dyn AptosMoveResolver,
+        context: ConversionContext,
         is_storage_slot_metadata_enabled: bool,
     ) -> Self {
         let mut new_slot_metadata: Option<StateValueMetadata> = None;
         if is_storage_slot_metadata_enabled {
             if let Some(current_time) = CurrentTimeMicroseconds::fetch_config(remote) {
-                // The deposit on the metadata is a placeholder (0), it will be updated later when
-                // storage fee is charged.
-                new_slot_metadata = Some(StateValueMetadata::new(0, &current_time));
+                new_slot_metadata = Some(StorageRefundCalculator::new_slot_metadata(&current_time));
             }
         }
 
         Self {
             remote,
+            context,
             new_slot_metadata,
         }
     }
@@ -79,6 +93,7 @@ impl<'r> WriteOpConverter<'r> {
         legacy_creation_as_modification: bool,
     ) -> Result<(WriteOp, Option<Arc<MoveTypeLayout>>), VMStatus> {
         let result = self.convert(
+            state_key,
             self.remote.get_resource_state_value_metadata(state_key),
             move_storage_op.clone(),
             legacy_creation_as_modification,
@@ -90,14 +105,18 @@ impl<'r> WriteOpConverter<'r> {
         }
     }
 
+    /// Converts the granular updates to the members of a resource group into a `GroupWrite`.
+    /// Every member op keeps its own type layout, so that delayed fields inside the group can
+    /// be exchanged when the group is materialized. Members do not carry metadata: it is kept
+    /// (and charged) per group, on the metadata op that also encodes the new group size.
     pub(crate) fn convert_resource_group_v1(
         &self,
         state_key: &StateKey,
         group_changes: BTreeMap<StructTag, MoveStorageOp<BytesWithResourceLayout>>,
     ) -> Result<GroupWrite, VMStatus> {
-        // Resource group metadata is stored at the group StateKey, and can be obtained via the
-        // same interfaces at for a resource at a given StateKey.
-        let state_value_metadata_result = self.remote.get_resource_state_value_metadata(state_key);
+        let state_value_metadata_result = self
+            .remote
+            .get_resource_group_state_value_metadata(state_key);
         // Currently, due to read-before-write and a gas charge on the first read that is based
         // on the group size, this should simply re-read a cached (speculative) group size.
         let pre_group_size = self.remote.resource_group_size(state_key).map_err(|_| {
@@ -109,15 +128,14 @@ impl<'r> WriteOpConverter<'r> {
 
         let mut inner_ops = BTreeMap::new();
 
-        // We set SPECULATIVE_EXECUTION_ABORT_ERROR here, as the error can happen due to
-        // speculative reads (and in a non-speculative context, e.g. during commit, it
-        // is a more serious error and block execution must abort).
-        // BlockExecutor is responsible with handling this error.
+        // Under speculative execution, the error can happen due to speculative reads (and in
+        // a non-speculative context, e.g. during commit, it is a more serious error and block
+        // execution must abort). BlockExecutor is responsible with handling this error.
         let group_size_arithmetics_error = || {
-            VMStatus::error(
-                StatusCode::SPECULATIVE_EXECUTION_ABORT_ERROR,
-                err_msg("Group size underflow while applying updates"),
-            )
+            self.inconsistency_error(format!(
+                "Group size underflow while applying updates to {:?}",
+                state_key
+            ))
         };
         let tag_serialization_error = |_| {
             VMStatus::error(
@@ -179,7 +197,7 @@ impl<'r> WriteOpConverter<'r> {
         // except it encodes the (speculative) size of the group after applying the updates
         // which is used for charging storage fees. Moreover, the metadata computation occurs
         // fully backwards compatibly, and lets obtain final storage op by replacing bytes.
-        // TODO[agg_v2](fix) fix layout for RG
+        // The op has no layout, as the layouts are kept per member in the inner ops.
         let metadata_op = if post_group_size == 0 {
             MoveStorageOp::Delete
         } else if pre_group_size == 0 {
@@ -188,14 +206,26 @@ impl<'r> WriteOpConverter<'r> {
             MoveStorageOp::Modify((Bytes::new(), None))
         };
         Ok(GroupWrite::new(
-            self.convert(state_value_metadata_result, metadata_op, false)?,
+            self.convert(state_key, state_value_metadata_result, metadata_op, false)?,
             post_group_size,
             inner_ops,
         ))
     }
 
+    /// Returns the error for an op that is inconsistent with the state read via the resolver.
+    fn inconsistency_error(&self, msg: String) -> VMStatus {
+        let status_code = match self.context {
+            // Possible under speculative execution, returning speculative error waiting for
+            // re-execution.
+            ConversionContext::Speculative => StatusCode::SPECULATIVE_EXECUTION_ABORT_ERROR,
+            ConversionContext::Sequential => StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR,
+        };
+        VMStatus::error(status_code, Some(msg))
+    }
+
     fn convert(
         &self,
+        state_key: &StateKey,
         state_value_metadata_result: anyhow::Result<Option<StateValueMetadataKind>>,
         move_storage_op: MoveStorageOp<BytesWithResourceLayout>,
         legacy_creation_as_modification: bool,
@@ -211,19 +241,23 @@ impl<'r> WriteOpConverter<'r> {
         })?;
 
         let write_op = match (maybe_existing_metadata, move_storage_op) {
-            (None, Modify(_) | Delete) => {
-                return Err(VMStatus::error(
-                    // Possible under speculative execution, returning speculative error waiting for re-execution
-                    StatusCode::SPECULATIVE_EXECUTION_ABORT_ERROR,
-                    err_msg("When converting write op: updating non-existent value."),
-                ));
+            (None, Modify(_)) => {
+                return Err(self.inconsistency_error(format!(
+                    "When converting write op: modifying non-existent value at {:?}.",
+                    state_key,
+                )));
+            },
+            (None, Delete) => {
+                return Err(self.inconsistency_error(format!(
+                    "When converting write op: deleting non-existent value at {:?}.",
+                    state_key,
+                )));
             },
-            (Some(_), New(_)) => {
-                return Err(VMStatus::error(
-                    // Possible under speculative execution, returning speculative error waiting for re-execution
-                    StatusCode::SPECULATIVE_EXECUTION_ABORT_ERROR,
-                    err_msg("When converting write op: Recreating existing value."),
-                ));
+            (Some(existing_metadata), New(_)) => {
+                return Err(self.inconsistency_error(format!(
+                    "When converting write op: Recreating existing value at {:?} (existing metadata: {:?}).",
+                    state_key, existing_metadata,
+                )));
             },
             (None, New((data, _))) => match &self.new_slot_metadata {
                 None => {
@@ -264,7 +298,18 @@ impl<'r> WriteOpConverter<'r> {
         let maybe_existing_metadata = self
             .remote
             .get_aggregator_v1_state_value_metadata(state_key)
-            .map_err(|_| VMStatus::error(StatusCode::SPECULATIVE_EXECUTION_ABORT_ERROR, None))?;
+            .map_err(|e| match self.context {
+                ConversionContext::Speculative => {
+                    VMStatus::error(StatusCode::SPECULATIVE_EXECUTION_ABORT_ERROR, None)
+                },
+                ConversionContext::Sequential => VMStatus::error(
+                    StatusCode::STORAGE_ERROR,
+                    Some(format!(
+                        "Failed to read metadata of aggregator {:?}: {}",
+                        state_key, e
+                    )),
+                ),
+            })?;
         let data = serialize(&value).into();
 
         let op = match maybe_existing_metadata {
@@ -292,7 +337,7 @@ impl<'r> WriteOpConverter<'r> {
 mod tests {
     use super::*;
     use crate::{
-        data_cache::tests::as_resolver_with_group_size_kind,
+        data_cache::tests::{as_resolver_with_group_size_kind, GroupViewFromStateView},
         move_vm_ext::resolver::ResourceGroupResolver,
     };
     use aptos_state_view::TStateView;
@@ -361,9 +406,7 @@ mod tests {
         }
     }
 
-    // TODO[agg_v2](fix) make as_resolver_with_group_size_kind support AsSum
-    // #[test]
-    #[allow(unused)]
+    #[test]
     fn size_computation_delete_modify_ops() {
         let group: BTreeMap<StructTag, Bytes> = BTreeMap::from([
             (mock_tag_0(), vec![1].into()),
@@ -384,7 +427,9 @@ mod tests {
             + 6; // values bytes size: 1 + 2 + 3.
 
         let s = MockStateView::new(data);
-        let resolver = as_resolver_with_group_size_kind(&s, GroupSizeKind::AsSum);
+        let group_view = GroupViewFromStateView(&s);
+        let resolver =
+            as_resolver_with_group_size_kind(&s, GroupSizeKind::AsSum, Some(&group_view));
 
         assert_eq!(
             resolver.resource_group_size(&key).unwrap(),
@@ -398,7 +443,7 @@ mod tests {
                 MoveStorageOp::Modify((vec![5, 5, 5, 5, 5].into(), None)),
             ),
         ]);
-        let converter = WriteOpConverter::new(&resolver, false);
+        let converter = WriteOpConverter::new(&resolver, ConversionContext::Speculative, false);
         let group_write = converter
             .convert_resource_group_v1(&key, group_changes)
             .unwrap();
@@ -411,6 +456,17 @@ mod tests {
             bcs::from_bytes::<u64>(group_write.metadata_op().bytes().unwrap()).unwrap(),
             expected_new_size as u64
         );
+        // The size can also be quoted ahead of the conversion.
+        let pending_changes = BTreeMap::from([
+            (mock_tag_0(), MoveStorageOp::Delete),
+            (mock_tag_2(), MoveStorageOp::Modify(5)),
+        ]);
+        assert_eq!(
+            resolver
+                .resource_group_size_after(&key, &pending_changes)
+                .unwrap(),
+            expected_new_size as u64
+        );
         assert_eq!(group_write.inner_ops().len(), 2);
         assert_some_eq!(
             group_write.inner_ops().get(&mock_tag_0()),
@@ -422,9 +478,7 @@ mod tests {
         );
     }
 
-    // TODO[agg_v2](fix) make as_resolver_with_group_size_kind support AsSum
-    // #[test]
-    #[allow(unused)]
+    #[test]
     fn size_computation_new_op() {
         let group: BTreeMap<StructTag, Bytes> = BTreeMap::from([
             (mock_tag_0(), vec![1].into()),
@@ -439,13 +493,15 @@ mod tests {
         )]);
 
         let s = MockStateView::new(data);
-        let resolver = as_resolver_with_group_size_kind(&s, GroupSizeKind::AsSum);
+        let group_view = GroupViewFromStateView(&s);
+        let resolver =
+            as_resolver_with_group_size_kind(&s, GroupSizeKind::AsSum, Some(&group_view));
 
         let group_changes = BTreeMap::from([(
             mock_tag_2(),
             MoveStorageOp::New((vec![3, 3, 3].into(), None)),
         )]);
-        let converter = WriteOpConverter::new(&resolver, true);
+        let converter = WriteOpConverter::new(&resolver, ConversionContext::Speculative, true);
         let group_write = converter
             .convert_resource_group_v1(&key, group_changes)
             .unwrap();
@@ -466,18 +522,18 @@ mod tests {
         );
     }
 
-    // TODO[agg_v2](fix) make as_resolver_with_group_size_kind support AsSum
-    // #[test]
-    #[allow(unused)]
+    #[test]
     fn size_computation_new_group() {
         let s = MockStateView::new(BTreeMap::new());
-        let resolver = as_resolver_with_group_size_kind(&s, GroupSizeKind::AsSum);
+        let group_view = GroupViewFromStateView(&s);
+        let resolver =
+            as_resolver_with_group_size_kind(&s, GroupSizeKind::AsSum, Some(&group_view));
 
         // TODO: Layout hardcoded to None. Test with layout = Some(..)
         let group_changes =
             BTreeMap::from([(mock_tag_1(), MoveStorageOp::New((vec![2, 2].into(), None)))]);
         let key = StateKey::raw(vec![0]);
-        let converter = WriteOpConverter::new(&resolver, true);
+        let converter = WriteOpConverter::new(&resolver, ConversionContext::Speculative, true);
         let group_write = converter
             .convert_resource_group_v1(&key, group_changes)
             .unwrap();
@@ -495,9 +551,7 @@ mod tests {
         );
     }
 
-    // TODO[agg_v2](fix) make as_resolver_with_group_size_kind support AsSum
-    // #[test]
-    #[allow(unused)]
+    #[test]
     fn size_computation_delete_group() {
         let group: BTreeMap<StructTag, Bytes> = BTreeMap::from([
             (mock_tag_0(), vec![1].into()),
@@ -512,12 +566,14 @@ mod tests {
         )]);
 
         let s = MockStateView::new(data);
-        let resolver = as_resolver_with_group_size_kind(&s, GroupSizeKind::AsSum);
+        let group_view = GroupViewFromStateView(&s);
+        let resolver =
+            as_resolver_with_group_size_kind(&s, GroupSizeKind::AsSum, Some(&group_view));
         let group_changes = BTreeMap::from([
             (mock_tag_0(), MoveStorageOp::Delete),
             (mock_tag_1(), MoveStorageOp::Delete),
         ]);
-        let converter = WriteOpConverter::new(&resolver, true);
+        let converter = WriteOpConverter::new(&resolver, ConversionContext::Speculative, true);
         let group_write = converter
             .convert_resource_group_v1(&key, group_changes)
             .unwrap();
@@ -529,4 +585,94 @@ mod tests {
         });
         assert_none!(group_write.metadata_op().bytes());
     }
+
+    #[test]
+    fn layouts_are_kept_per_member() {
+        let group: BTreeMap<StructTag, Bytes> = BTreeMap::from([
+            (mock_tag_0(), vec![1].into()),
+            (mock_tag_1(), vec![2, 2].into()),
+        ]);
+        let key = StateKey::raw(vec![0]);
+        let data = BTreeMap::from([(
+            key.clone(),
+            StateValue::new_legacy(bcs::to_bytes(&group).unwrap().into()),
+        )]);
+
+        let s = MockStateView::new(data);
+        let group_view = GroupViewFromStateView(&s);
+        let resolver =
+            as_resolver_with_group_size_kind(&s, GroupSizeKind::AsSum, Some(&group_view));
+
+        let layout = Arc::new(MoveTypeLayout::U64);
+        let group_changes = BTreeMap::from([
+            (
+                mock_tag_0(),
+                MoveStorageOp::Modify((vec![3].into(), Some(layout.clone()))),
+            ),
+            (
+                mock_tag_1(),
+                MoveStorageOp::Modify((vec![4, 4].into(), None)),
+            ),
+            (
+                mock_tag_2(),
+                MoveStorageOp::New((vec![5].into(), Some(layout.clone()))),
+            ),
+        ]);
+        let converter = WriteOpConverter::new(&resolver, ConversionContext::Speculative, false);
+        let group_write = converter
+            .convert_resource_group_v1(&key, group_changes)
+            .unwrap();
+
+        assert_eq!(
+            group_write.metadata_op(),
+            &WriteOp::Modification(
+                bcs::to_bytes(
+                    &(bcs::serialized_size(&mock_tag_0()).unwrap()
+                        + bcs::serialized_size(&mock_tag_1()).unwrap()
+                        + bcs::serialized_size(&mock_tag_2()).unwrap()
+                        + 4) as u64 // values bytes size: 1 + 2 + 1.
+                )
+                .unwrap()
+                .into()
+            )
+        );
+        assert_eq!(group_write.inner_ops().len(), 3);
+        assert_some_eq!(
+            group_write.inner_ops().get(&mock_tag_0()),
+            &(WriteOp::Modification(vec![3].into()), Some(layout.clone()))
+        );
+        assert_some_eq!(
+            group_write.inner_ops().get(&mock_tag_1()),
+            &(WriteOp::Modification(vec![4, 4].into()), None)
+        );
+        assert_some_eq!(
+            group_write.inner_ops().get(&mock_tag_2()),
+            &(WriteOp::Creation(vec![5].into()), Some(layout))
+        );
+    }
+
+    #[test]
+    fn inconsistent_op_error_depends_on_context() {
+        let key = StateKey::raw(vec![0]);
+        let data = BTreeMap::from([(key.clone(), StateValue::new_legacy(vec![1].into()))]);
+
+        let s = MockStateView::new(data);
+        let resolver = as_resolver_with_group_size_kind(&s, GroupSizeKind::AsBlob, None);
+        for (context, expected_status_code) in [
+            (
+                ConversionContext::Speculative,
+                StatusCode::SPECULATIVE_EXECUTION_ABORT_ERROR,
+            ),
+            (
+                ConversionContext::Sequential,
+                StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR,
+            ),
+        ] {
+            let converter = WriteOpConverter::new(&resolver, context, false);
+            let status = converter
+                .convert_resource(&key, MoveStorageOp::New((vec![2].into(), None)), false)
+                .unwrap_err();
+            assert_eq!(status.status_code(), expected_status_code);
+        }
+    }
 }
@@ -50,7 +50,7 @@ use {
     aptos_framework::natives::{
         aggregator_natives::NativeAggregatorContext, code::NativeCodeContext,
         cryptography::ristretto255_point::NativeRistrettoPointContext,
-        transaction_context::NativeTransactionContext,
+        randomness::RandomnessContext, transaction_context::NativeTransactionContext,
     },
     move_vm_runtime::native_extensions::NativeContextExtensions,
     once_cell::sync::Lazy,
@@ -233,4 +233,8 @@ fn unit_test_extensions_hook(exts: &mut NativeContextExtensions) {
     exts.add(NativeRistrettoPointContext::new());
     exts.add(AlgebraContext::new());
     exts.add(NativeEventContext::default());
+    // Unit tests call test functions directly, like a transaction calling a private entry function
+    let mut randomness_context = RandomnessContext::new();
+    randomness_context.mark_unbiasable();
+    exts.add(randomness_context);
 }
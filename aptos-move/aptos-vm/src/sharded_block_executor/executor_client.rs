@@ -3,7 +3,8 @@
 
 use aptos_state_view::StateView;
 use aptos_types::{
-    block_executor::partitioner::PartitionedTransactions, transaction::TransactionOutput,
+    block_executor::partitioner::PartitionedTransactions, on_chain_config::BlockExecutionLimit,
+    transaction::TransactionOutput,
 };
 use move_core_types::vm_status::VMStatus;
 use std::sync::Arc;
@@ -40,7 +41,7 @@ pub trait ExecutorClient<S: StateView + Sync + Send + 'static>: Send + Sync {
         state_view: Arc<S>,
         transactions: PartitionedTransactions,
         concurrency_level_per_shard: usize,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> Result<ShardedExecutionOutput, VMStatus>;
 
     fn shutdown(&mut self);
@@ -18,6 +18,7 @@ use aptos_types::{
     block_executor::partitioner::{
         PartitionedTransactions, RoundId, ShardId, GLOBAL_ROUND_ID, MAX_ALLOWED_PARTITIONING_ROUNDS,
     },
+    on_chain_config::BlockExecutionLimit,
     transaction::TransactionOutput,
 };
 use crossbeam_channel::{unbounded, Receiver, Sender};
@@ -181,7 +182,7 @@ impl<S: StateView + Sync + Send + 'static> ExecutorClient<S> for LocalExecutorCl
         state_view: Arc<S>,
         transactions: PartitionedTransactions,
         concurrency_level_per_shard: usize,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> Result<ShardedExecutionOutput, VMStatus> {
         assert_eq!(transactions.num_shards(), self.num_shards());
         let (sub_blocks, global_txns) = transactions.into();
@@ -191,7 +192,7 @@ impl<S: StateView + Sync + Send + 'static> ExecutorClient<S> for LocalExecutorCl
                     state_view.clone(),
                     sub_blocks_for_shard,
                     concurrency_level_per_shard,
-                    maybe_block_gas_limit,
+                    block_execution_limit,
                 ))
                 .unwrap();
         }
@@ -203,7 +204,7 @@ impl<S: StateView + Sync + Send + 'static> ExecutorClient<S> for LocalExecutorCl
         let mut global_output = self.global_executor.execute_global_txns(
             global_txns,
             state_view.as_ref(),
-            maybe_block_gas_limit,
+            block_execution_limit,
         )?;
 
         let mut sharded_output = self.get_output_from_shards()?;
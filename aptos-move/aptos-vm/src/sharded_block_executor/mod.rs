@@ -13,6 +13,7 @@ use aptos_logger::info;
 use aptos_state_view::StateView;
 use aptos_types::{
     block_executor::partitioner::{PartitionedTransactions, SubBlocksForShard},
+    on_chain_config::BlockExecutionLimit,
     transaction::{analyzed_transaction::AnalyzedTransaction, TransactionOutput},
 };
 use move_core_types::vm_status::VMStatus;
@@ -42,7 +43,7 @@ pub enum ExecutorShardCommand<S> {
         Arc<S>,
         SubBlocksForShard<AnalyzedTransaction>,
         usize,
-        Option<u64>,
+        BlockExecutionLimit,
     ),
     Stop,
 }
@@ -70,7 +71,7 @@ impl<S: StateView + Sync + Send + 'static, C: ExecutorClient<S>> ShardedBlockExe
         state_view: Arc<S>,
         transactions: PartitionedTransactions,
         concurrency_level_per_shard: usize,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
         let _timer = SHARDED_BLOCK_EXECUTION_SECONDS.start_timer();
         let num_executor_shards = self.executor_client.num_shards();
@@ -87,7 +88,7 @@ impl<S: StateView + Sync + Send + 'static, C: ExecutorClient<S>> ShardedBlockExe
                 state_view,
                 transactions,
                 concurrency_level_per_shard,
-                maybe_block_gas_limit,
+                block_execution_limit,
             )?
             .into_inner();
         // wait for all remote executors to send the result back and append them in order by shard id
@@ -21,6 +21,7 @@ use aptos_types::{
     block_executor::partitioner::{
         ShardId, SubBlock, SubBlocksForShard, TransactionWithDependencies,
     },
+    on_chain_config::BlockExecutionLimit,
     transaction::{
         analyzed_transaction::AnalyzedTransaction,
         signature_verified_transaction::SignatureVerifiedTransaction, TransactionOutput,
@@ -71,7 +72,7 @@ impl<S: StateView + Sync + Send + 'static> ShardedExecutorService<S> {
         round: usize,
         state_view: &S,
         concurrency_level: usize,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
         disable_speculative_logging();
         trace!(
@@ -90,7 +91,7 @@ impl<S: StateView + Sync + Send + 'static> ShardedExecutorService<S> {
             round,
             state_view,
             concurrency_level,
-            maybe_block_gas_limit,
+            block_execution_limit,
         )
     }
 
@@ -103,7 +104,7 @@ impl<S: StateView + Sync + Send + 'static> ShardedExecutorService<S> {
         round: usize,
         state_view: &S,
         concurrency_level: usize,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
         let (callback, callback_receiver) = oneshot::channel();
 
@@ -140,8 +141,9 @@ impl<S: StateView + Sync + Send + 'static> ShardedExecutorService<S> {
                     &signature_verified_transactions,
                     aggr_overridden_state_view.as_ref(),
                     concurrency_level,
-                    maybe_block_gas_limit,
+                    block_execution_limit,
                     cross_shard_commit_sender,
+                    None,
                 );
                 if let Some(shard_id) = shard_id {
                     trace!(
@@ -176,7 +178,7 @@ impl<S: StateView + Sync + Send + 'static> ShardedExecutorService<S> {
         transactions: SubBlocksForShard<AnalyzedTransaction>,
         state_view: &S,
         concurrency_level: usize,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> Result<Vec<Vec<TransactionOutput>>, VMStatus> {
         let mut result = vec![];
         for (round, sub_block) in transactions.into_sub_blocks().into_iter().enumerate() {
@@ -197,7 +199,7 @@ impl<S: StateView + Sync + Send + 'static> ShardedExecutorService<S> {
                 round,
                 state_view,
                 concurrency_level,
-                maybe_block_gas_limit,
+                block_execution_limit,
             )?);
             trace!(
                 "Finished executing sub block for shard {} and round {}",
@@ -222,7 +224,7 @@ impl<S: StateView + Sync + Send + 'static> ShardedExecutorService<S> {
                     state_view,
                     transactions,
                     concurrency_level_per_shard,
-                    maybe_block_gas_limit,
+                    block_execution_limit,
                 ) => {
                     num_txns += transactions.num_txns();
                     trace!(
@@ -237,7 +239,7 @@ impl<S: StateView + Sync + Send + 'static> ShardedExecutorService<S> {
                         transactions,
                         state_view.as_ref(),
                         concurrency_level_per_shard,
-                        maybe_block_gas_limit,
+                        block_execution_limit,
                     );
                     drop(state_view);
                     drop(exe_timer);
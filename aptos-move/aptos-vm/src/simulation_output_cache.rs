@@ -0,0 +1,122 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Cache of simulated transaction outputs keyed by the transaction hash and the version of the
+//! state checkpoint the transaction was simulated against, so that simulating the same
+//! transaction again against an unchanged state does not need to start from scratch.
+//!
+//! Only simulations are served from the cache: block execution never consults it, as a
+//! transaction is rarely executed against the exact state it was simulated against, and
+//! simulation skips checks (e.g., signature verification) that execution has to perform.
+
+use aptos_crypto::HashValue;
+use aptos_infallible::Mutex;
+use aptos_types::transaction::{TransactionOutput, Version};
+use lru::LruCache;
+use move_core_types::vm_status::VMStatus;
+
+pub struct SimulationOutputCache {
+    inner: Mutex<Inner>,
+}
+
+struct Inner {
+    /// Version of the latest state checkpoint, outputs for older versions are invalidated.
+    checkpoint_version: Option<Version>,
+    outputs: LruCache<HashValue, (VMStatus, TransactionOutput)>,
+}
+
+impl SimulationOutputCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            inner: Mutex::new(Inner {
+                checkpoint_version: None,
+                outputs: LruCache::new(capacity),
+            }),
+        }
+    }
+
+    /// Returns the cached output of the transaction executed against the state at `version`.
+    pub fn get(
+        &self,
+        txn_hash: &HashValue,
+        version: Version,
+    ) -> Option<(VMStatus, TransactionOutput)> {
+        let mut inner = self.inner.lock();
+        if inner.checkpoint_version != Some(version) {
+            return None;
+        }
+        inner.outputs.get(txn_hash).cloned()
+    }
+
+    /// Caches the output of the transaction executed against the state at `version`. Outputs
+    /// for states older than the latest known checkpoint are not cached.
+    pub fn insert(
+        &self,
+        txn_hash: HashValue,
+        version: Version,
+        output: (VMStatus, TransactionOutput),
+    ) {
+        let mut inner = self.inner.lock();
+        inner.advance_checkpoint(version);
+        if inner.checkpoint_version == Some(version) {
+            inner.outputs.put(txn_hash, output);
+        }
+    }
+
+    /// Invalidates all outputs computed against states older than `version`.
+    pub fn advance_checkpoint(&self, version: Version) {
+        self.inner.lock().advance_checkpoint(version);
+    }
+}
+
+impl Inner {
+    fn advance_checkpoint(&mut self, version: Version) {
+        if self
+            .checkpoint_version
+            .map_or(true, |current| current < version)
+        {
+            self.checkpoint_version = Some(version);
+            self.outputs.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::{
+        transaction::{ExecutionStatus, TransactionStatus},
+        write_set::WriteSet,
+    };
+
+    fn output(gas_used: u64) -> (VMStatus, TransactionOutput) {
+        (
+            VMStatus::Executed,
+            TransactionOutput::new(
+                WriteSet::default(),
+                vec![],
+                gas_used,
+                TransactionStatus::Keep(ExecutionStatus::Success),
+            ),
+        )
+    }
+
+    #[test]
+    fn test_invalidation_on_checkpoint_advance() {
+        let cache = SimulationOutputCache::new(10);
+        let txn_hash = HashValue::random();
+
+        cache.insert(txn_hash, 5, output(1));
+        assert_eq!(cache.get(&txn_hash, 5).unwrap().1.gas_used(), 1);
+        assert!(cache.get(&txn_hash, 6).is_none());
+
+        // Outputs for older states are neither returned nor cached.
+        cache.advance_checkpoint(6);
+        assert!(cache.get(&txn_hash, 5).is_none());
+        cache.insert(txn_hash, 5, output(1));
+        assert!(cache.get(&txn_hash, 5).is_none());
+
+        cache.insert(txn_hash, 6, output(2));
+        assert_eq!(cache.get(&txn_hash, 6).unwrap().1.gas_used(), 2);
+    }
+}
@@ -55,6 +55,7 @@ impl TransactionMetadata {
                 TransactionPayload::Script(s) => HashValue::sha3_256_of(s.code()).to_vec(),
                 TransactionPayload::EntryFunction(_) => vec![],
                 TransactionPayload::Multisig(_) => vec![],
+                TransactionPayload::Batched(_) => vec![],
 
                 // Deprecated. Will be removed in the future.
                 TransactionPayload::ModuleBundle(_) => vec![],
@@ -0,0 +1,209 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Standalone module upgrade compatibility checking.
+//!
+//! The VM enforces upgrade compatibility via `Compatibility::check`, which only reports the
+//! first violated category as an opaque error. Tooling (e.g., the CLI or deployment pipelines)
+//! needs to explain *why* an upgrade is rejected, so this module collects every violation found
+//! by `Compatibility::incompatibilities` into a structured [`CompatibilityReport`].
+
+pub use move_binary_format::compatibility::{CompatibilityCategory, Incompatibility};
+use move_binary_format::{
+    compatibility::Compatibility,
+    errors::{Location, PartialVMError, VMResult},
+    normalized::Module,
+    CompiledModule,
+};
+use move_core_types::{
+    language_storage::ModuleId, resolver::ModuleResolver, vm_status::StatusCode,
+};
+use serde::{Deserialize, Serialize};
+
+/// All incompatibilities found when upgrading a single module.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompatibilityReport {
+    pub module_id: ModuleId,
+    pub incompatibilities: Vec<Incompatibility>,
+}
+
+impl CompatibilityReport {
+    /// Returns true if no rule is violated, i.e., the upgrade passes a full compatibility check.
+    pub fn is_fully_compatible(&self) -> bool {
+        self.incompatibilities.is_empty()
+    }
+
+    /// Returns true if any of the incompatibilities violates the given category.
+    pub fn violates(&self, category: CompatibilityCategory) -> bool {
+        self.incompatibilities
+            .iter()
+            .any(|i| i.categories().contains(&category))
+    }
+
+    /// Returns true if the upgrade is accepted under the given set of checks, mirroring the
+    /// flags of `Compatibility::new`.
+    pub fn is_compatible_with(
+        &self,
+        check_struct_and_pub_function_linking: bool,
+        check_struct_layout: bool,
+        check_friend_linking: bool,
+    ) -> bool {
+        !(check_struct_and_pub_function_linking
+            && self.violates(CompatibilityCategory::StructAndPublicFunctionLinking)
+            || check_struct_layout && self.violates(CompatibilityCategory::StructLayout)
+            || check_friend_linking && self.violates(CompatibilityCategory::FriendLinking))
+    }
+}
+
+/// A module of a package that failed bytecode verification.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModuleVerificationError {
+    pub module_id: ModuleId,
+    pub status_code: StatusCode,
+    pub message: Option<String>,
+}
+
+/// Result of a publishing dry run: all the problems that would make publishing a package fail
+/// bytecode verification or the upgrade compatibility check.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PublishCompatibilityReport {
+    pub verification_errors: Vec<ModuleVerificationError>,
+    /// One report per module which upgrades an already published module.
+    pub upgrades: Vec<CompatibilityReport>,
+    /// Whether friend linking is enforced on upgrades (it is not when friend functions are
+    /// treated as private).
+    pub check_friend_linking: bool,
+}
+
+impl PublishCompatibilityReport {
+    /// Returns true if all modules verify and all upgrades pass the compatibility check
+    /// enforced by the VM.
+    pub fn is_publishable(&self) -> bool {
+        self.verification_errors.is_empty()
+            && self
+                .upgrades
+                .iter()
+                .all(|report| report.is_compatible_with(true, true, self.check_friend_linking))
+    }
+}
+
+/// Collects all the incompatibilities of `new_module` with respect to `old_module`, as found by
+/// `Compatibility::check`.
+pub fn check_module_compatibility(old_module: &Module, new_module: &Module) -> CompatibilityReport {
+    CompatibilityReport {
+        module_id: old_module.module_id(),
+        incompatibilities: Compatibility::incompatibilities(old_module, new_module),
+    }
+}
+
+/// Checks every module of `bundle` against the version currently stored in `resolver`. Modules
+/// which do not exist yet are fresh publishes and are skipped. Returns one report per upgraded
+/// module.
+pub fn check_bundle_compatibility(
+    resolver: &impl ModuleResolver,
+    bundle: &[CompiledModule],
+) -> VMResult<Vec<CompatibilityReport>> {
+    let mut reports = vec![];
+    for new_module in bundle {
+        let module_id = new_module.self_id();
+        let old_bytes = resolver.get_module(&module_id).map_err(|e| {
+            PartialVMError::new(StatusCode::STORAGE_ERROR)
+                .with_message(e.to_string())
+                .finish(Location::Module(module_id.clone()))
+        })?;
+        if let Some(old_bytes) = old_bytes {
+            let old_module = CompiledModule::deserialize(&old_bytes)
+                .map_err(|e| e.finish(Location::Module(module_id.clone())))?;
+            reports.push(check_module_compatibility(
+                &Module::new(&old_module),
+                &Module::new(new_module),
+            ));
+        }
+    }
+    Ok(reports)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_binary_format::file_format::basic_test_module;
+    use move_core_types::identifier::Identifier;
+
+    fn assert_matches_vm_check(old: &Module, new: &Module, report: &CompatibilityReport) {
+        for (linking, layout, friend) in [
+            (true, true, true),
+            (true, true, false),
+            (false, true, false),
+            (true, false, true),
+        ] {
+            assert_eq!(
+                report.is_compatible_with(linking, layout, friend),
+                Compatibility::new(linking, layout, friend)
+                    .check(old, new)
+                    .is_ok()
+            );
+        }
+    }
+
+    #[test]
+    fn test_identical_module_is_compatible() {
+        let module = Module::new(&basic_test_module());
+        let report = check_module_compatibility(&module, &module);
+        assert!(report.is_fully_compatible());
+        assert_matches_vm_check(&module, &module, &report);
+    }
+
+    #[test]
+    fn test_all_incompatibilities_are_reported() {
+        let mut old = Module::new(&basic_test_module());
+        old.friends.push(ModuleId::new(
+            old.address,
+            Identifier::new("friend_module").unwrap(),
+        ));
+
+        let bar = Identifier::new("Bar").unwrap();
+        let mut new = old.clone();
+        new.structs.get_mut(&bar).unwrap().fields.clear();
+        new.friends.clear();
+
+        let report = check_module_compatibility(&old, &new);
+        assert_eq!(report.incompatibilities, vec![
+            Incompatibility::StructLayoutChanged { name: bar },
+            Incompatibility::FriendRemoved {
+                module: old.friends[0].clone()
+            },
+        ]);
+        assert!(report.violates(CompatibilityCategory::StructLayout));
+        assert!(report.violates(CompatibilityCategory::FriendLinking));
+        assert!(!report.violates(CompatibilityCategory::StructAndPublicFunctionLinking));
+        assert_matches_vm_check(&old, &new, &report);
+    }
+
+    #[test]
+    fn test_publishable_depends_on_friend_linking() {
+        let old = Module::new(&basic_test_module());
+        let mut new = old.clone();
+        new.friends.push(ModuleId::new(
+            old.address,
+            Identifier::new("friend_module").unwrap(),
+        ));
+        let upgrade = check_module_compatibility(&new, &old);
+        assert_eq!(upgrade.incompatibilities.len(), 1);
+
+        let mut report = PublishCompatibilityReport {
+            verification_errors: vec![],
+            upgrades: vec![upgrade],
+            check_friend_linking: true,
+        };
+        assert!(!report.is_publishable());
+        report.check_friend_linking = false;
+        assert!(report.is_publishable());
+
+        report.verification_errors.push(ModuleVerificationError {
+            module_id: old.module_id(),
+            status_code: StatusCode::LOOP_MAX_DEPTH_REACHED,
+            message: None,
+        });
+        assert!(!report.is_publishable());
+    }
+}
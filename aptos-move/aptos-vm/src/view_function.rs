@@ -0,0 +1,375 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Budgets enforced when executing view functions, which unlike transactions are not paid for.
+
+use aptos_gas_algebra::{InternalGas, NumArgs, NumBytes};
+use move_binary_format::{
+    errors::{PartialVMError, PartialVMResult, VMError},
+    file_format::CodeOffset,
+};
+use move_core_types::{
+    account_address::AccountAddress,
+    language_storage::ModuleId,
+    vm_status::{StatusCode, VMStatus},
+};
+use move_vm_types::{
+    gas::{GasMeter as MoveGasMeter, SimpleInstruction},
+    views::{TypeView, ValueView},
+};
+use std::fmt;
+
+/// Limits on the execution of a single view function.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ViewFunctionLimits {
+    /// Maximum amount of gas units the view function may consume.
+    pub max_gas: u64,
+    /// Maximum depth of the call stack, counting the view function itself.
+    pub max_stack_depth: u64,
+    /// Maximum total size of the serialized return values.
+    pub max_returned_bytes: u64,
+}
+
+impl ViewFunctionLimits {
+    /// Only bounds the gas, the VM still enforces its own limit on the call stack.
+    pub fn with_max_gas(max_gas: u64) -> Self {
+        Self {
+            max_gas,
+            max_stack_depth: u64::MAX,
+            max_returned_bytes: u64::MAX,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ViewFunctionError {
+    OutOfGas {
+        max_gas: u64,
+    },
+    MaxStackDepthExceeded {
+        max_stack_depth: u64,
+    },
+    MaxReturnedBytesExceeded {
+        returned_bytes: u64,
+        max_returned_bytes: u64,
+    },
+    /// The function could not be loaded, its arguments are invalid, or it failed to execute.
+    Execution(anyhow::Error),
+}
+
+impl ViewFunctionError {
+    /// Classifies an error returned by the execution of the view function.
+    pub(crate) fn from_execution_error(err: VMError, limits: &ViewFunctionLimits) -> Self {
+        match err.major_status() {
+            StatusCode::OUT_OF_GAS => Self::OutOfGas {
+                max_gas: limits.max_gas,
+            },
+            StatusCode::CALL_STACK_OVERFLOW => Self::MaxStackDepthExceeded {
+                max_stack_depth: limits.max_stack_depth,
+            },
+            _ => Self::Execution(anyhow::anyhow!("Failed to execute function: {:?}", err)),
+        }
+    }
+}
+
+impl fmt::Display for ViewFunctionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfGas { max_gas } => {
+                write!(f, "View function exceeded the gas budget of {}", max_gas)
+            },
+            Self::MaxStackDepthExceeded { max_stack_depth } => write!(
+                f,
+                "View function exceeded the maximum call stack depth of {}",
+                max_stack_depth
+            ),
+            Self::MaxReturnedBytesExceeded {
+                returned_bytes,
+                max_returned_bytes,
+            } => write!(
+                f,
+                "View function returned {} bytes, exceeding the maximum of {}",
+                returned_bytes, max_returned_bytes
+            ),
+            Self::Execution(err) => write!(f, "{:#}", err),
+        }
+    }
+}
+
+impl std::error::Error for ViewFunctionError {}
+
+impl From<VMError> for ViewFunctionError {
+    fn from(err: VMError) -> Self {
+        Self::Execution(err.into())
+    }
+}
+
+impl From<VMStatus> for ViewFunctionError {
+    fn from(err: VMStatus) -> Self {
+        Self::Execution(err.into())
+    }
+}
+
+/// Gas meter that fails with `CALL_STACK_OVERFLOW` once the call stack grows deeper than the
+/// given limit, to be composed with a base gas meter.
+pub(crate) struct StackDepthLimitedGasMeter<G> {
+    base: G,
+
+    max_stack_depth: u64,
+    stack_depth: u64,
+}
+
+impl<G> StackDepthLimitedGasMeter<G> {
+    pub(crate) fn new(base: G, max_stack_depth: u64) -> Self {
+        Self {
+            base,
+            max_stack_depth,
+            // The view function itself is not charged as a call.
+            stack_depth: 1,
+        }
+    }
+
+    fn enter_function(&mut self) -> PartialVMResult<()> {
+        self.stack_depth += 1;
+        if self.stack_depth > self.max_stack_depth {
+            return Err(
+                PartialVMError::new(StatusCode::CALL_STACK_OVERFLOW).with_message(format!(
+                    "Call stack depth exceeds the view function limit of {}",
+                    self.max_stack_depth
+                )),
+            );
+        }
+        Ok(())
+    }
+
+    fn exit_function(&mut self) {
+        self.stack_depth = self.stack_depth.saturating_sub(1);
+    }
+}
+
+macro_rules! delegate_mut {
+    ($(
+        fn $fn: ident $(<$($lt: lifetime),*>)? (&mut self $(, $arg: ident : $ty: ty)* $(,)?) -> $ret_ty: ty;
+    )*) => {
+        $(fn $fn $(<$($lt)*>)? (&mut self, $($arg: $ty),*) -> $ret_ty {
+            self.base.$fn($($arg),*)
+        })*
+    };
+}
+
+impl<G> MoveGasMeter for StackDepthLimitedGasMeter<G>
+where
+    G: MoveGasMeter,
+{
+    delegate_mut! {
+        fn charge_br_true(&mut self, target_offset: Option<CodeOffset>) -> PartialVMResult<()>;
+
+        fn charge_br_false(&mut self, target_offset: Option<CodeOffset>) -> PartialVMResult<()>;
+
+        fn charge_branch(&mut self, target_offset: CodeOffset) -> PartialVMResult<()>;
+
+        fn charge_pop(&mut self, popped_val: impl ValueView) -> PartialVMResult<()>;
+
+        fn charge_ld_const(&mut self, size: NumBytes) -> PartialVMResult<()>;
+
+        fn charge_ld_const_after_deserialization(&mut self, val: impl ValueView) -> PartialVMResult<()>;
+
+        fn charge_copy_loc(&mut self, val: impl ValueView) -> PartialVMResult<()>;
+
+        fn charge_move_loc(&mut self, val: impl ValueView) -> PartialVMResult<()>;
+
+        fn charge_store_loc(&mut self, val: impl ValueView) -> PartialVMResult<()>;
+
+        fn charge_pack(
+            &mut self,
+            is_generic: bool,
+            args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+
+        fn charge_unpack(
+            &mut self,
+            is_generic: bool,
+            args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+
+        fn charge_read_ref(&mut self, val: impl ValueView) -> PartialVMResult<()>;
+
+        fn charge_write_ref(
+            &mut self,
+            new_val: impl ValueView,
+            old_val: impl ValueView,
+        ) -> PartialVMResult<()>;
+
+        fn charge_eq(&mut self, lhs: impl ValueView, rhs: impl ValueView) -> PartialVMResult<()>;
+
+        fn charge_neq(&mut self, lhs: impl ValueView, rhs: impl ValueView) -> PartialVMResult<()>;
+
+        fn charge_borrow_global(
+            &mut self,
+            is_mut: bool,
+            is_generic: bool,
+            ty: impl TypeView,
+            is_success: bool,
+        ) -> PartialVMResult<()>;
+
+        fn charge_exists(
+            &mut self,
+            is_generic: bool,
+            ty: impl TypeView,
+            exists: bool,
+        ) -> PartialVMResult<()>;
+
+        fn charge_move_from(
+            &mut self,
+            is_generic: bool,
+            ty: impl TypeView,
+            val: Option<impl ValueView>,
+        ) -> PartialVMResult<()>;
+
+        fn charge_move_to(
+            &mut self,
+            is_generic: bool,
+            ty: impl TypeView,
+            val: impl ValueView,
+            is_success: bool,
+        ) -> PartialVMResult<()>;
+
+        fn charge_vec_pack<'a>(
+            &mut self,
+            ty: impl TypeView + 'a,
+            args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+
+        fn charge_vec_len(&mut self, ty: impl TypeView) -> PartialVMResult<()>;
+
+        fn charge_vec_borrow(
+            &mut self,
+            is_mut: bool,
+            ty: impl TypeView,
+            is_success: bool,
+        ) -> PartialVMResult<()>;
+
+        fn charge_vec_push_back(
+            &mut self,
+            ty: impl TypeView,
+            val: impl ValueView,
+        ) -> PartialVMResult<()>;
+
+        fn charge_vec_pop_back(
+            &mut self,
+            ty: impl TypeView,
+            val: Option<impl ValueView>,
+        ) -> PartialVMResult<()>;
+
+        fn charge_vec_unpack(
+            &mut self,
+            ty: impl TypeView,
+            expect_num_elements: NumArgs,
+            elems: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+
+        fn charge_vec_swap(&mut self, ty: impl TypeView) -> PartialVMResult<()>;
+
+        fn charge_native_function_before_execution(
+            &mut self,
+            ty_args: impl ExactSizeIterator<Item = impl TypeView> + Clone,
+            args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+
+        fn charge_drop_frame(
+            &mut self,
+            locals: impl Iterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+
+        fn charge_load_resource(
+            &mut self,
+            addr: AccountAddress,
+            ty: impl TypeView,
+            val: Option<impl ValueView>,
+            bytes_loaded: NumBytes,
+        ) -> PartialVMResult<()>;
+    }
+
+    #[inline]
+    fn balance_internal(&self) -> InternalGas {
+        self.base.balance_internal()
+    }
+
+    fn charge_simple_instr(&mut self, instr: SimpleInstruction) -> PartialVMResult<()> {
+        if matches!(instr, SimpleInstruction::Ret) {
+            self.exit_function();
+        }
+        self.base.charge_simple_instr(instr)
+    }
+
+    fn charge_call(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        num_locals: NumArgs,
+    ) -> PartialVMResult<()> {
+        self.enter_function()?;
+        self.base
+            .charge_call(module_id, func_name, args, num_locals)
+    }
+
+    fn charge_call_generic(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        ty_args: impl ExactSizeIterator<Item = impl TypeView> + Clone,
+        args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        num_locals: NumArgs,
+    ) -> PartialVMResult<()> {
+        self.enter_function()?;
+        self.base
+            .charge_call_generic(module_id, func_name, ty_args, args, num_locals)
+    }
+
+    fn charge_native_function(
+        &mut self,
+        amount: InternalGas,
+        ret_vals: Option<impl ExactSizeIterator<Item = impl ValueView> + Clone>,
+    ) -> PartialVMResult<()> {
+        // Natives do not execute a return instruction, they are done once they are charged for.
+        self.exit_function();
+        self.base.charge_native_function(amount, ret_vals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_vm_types::gas::UnmeteredGasMeter;
+
+    fn call(meter: &mut StackDepthLimitedGasMeter<UnmeteredGasMeter>) -> PartialVMResult<()> {
+        meter.charge_call(
+            &ModuleId::new(
+                AccountAddress::ONE,
+                move_core_types::ident_str!("m").to_owned(),
+            ),
+            "f",
+            std::iter::empty::<move_vm_types::values::Value>(),
+            NumArgs::zero(),
+        )
+    }
+
+    #[test]
+    fn test_stack_depth_limit() {
+        let mut meter = StackDepthLimitedGasMeter::new(UnmeteredGasMeter, 3);
+        call(&mut meter).unwrap();
+        call(&mut meter).unwrap();
+        assert_eq!(
+            call(&mut meter).unwrap_err().major_status(),
+            StatusCode::CALL_STACK_OVERFLOW
+        );
+
+        // Returning frees up the stack again.
+        let mut meter = StackDepthLimitedGasMeter::new(UnmeteredGasMeter, 3);
+        for _ in 0..10 {
+            call(&mut meter).unwrap();
+            meter.charge_simple_instr(SimpleInstruction::Ret).unwrap();
+        }
+    }
+}
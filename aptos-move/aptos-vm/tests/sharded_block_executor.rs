@@ -193,6 +193,7 @@ mod test_utils {
     };
     use aptos_types::{
         block_executor::partitioner::PartitionedTransactions,
+        on_chain_config::BlockExecutionLimit,
         transaction::{
             analyzed_transaction::AnalyzedTransaction,
             signature_verified_transaction::SignatureVerifiedTransaction, Transaction,
@@ -296,7 +297,7 @@ mod test_utils {
                 Arc::new(executor.data_store().clone()),
                 partitioned_txns.clone(),
                 2,
-                None,
+                BlockExecutionLimit::no_limit(),
             )
             .unwrap();
 
@@ -305,8 +306,12 @@ mod test_utils {
                 .into_iter()
                 .map(|t| t.into_txn())
                 .collect();
-        let unsharded_txn_output =
-            AptosVM::execute_block(&ordered_txns, executor.data_store(), None).unwrap();
+        let unsharded_txn_output = AptosVM::execute_block(
+            &ordered_txns,
+            executor.data_store(),
+            BlockExecutionLimit::no_limit(),
+        )
+        .unwrap();
         compare_txn_outputs(unsharded_txn_output, sharded_txn_output);
     }
 
@@ -350,12 +355,16 @@ mod test_utils {
                 Arc::new(executor.data_store().clone()),
                 partitioned_txns,
                 concurrency,
-                None,
+                BlockExecutionLimit::no_limit(),
             )
             .unwrap();
 
-        let unsharded_txn_output =
-            AptosVM::execute_block(&execution_ordered_txns, executor.data_store(), None).unwrap();
+        let unsharded_txn_output = AptosVM::execute_block(
+            &execution_ordered_txns,
+            executor.data_store(),
+            BlockExecutionLimit::no_limit(),
+        )
+        .unwrap();
         compare_txn_outputs(unsharded_txn_output, sharded_txn_output);
     }
 
@@ -403,12 +412,16 @@ mod test_utils {
                 Arc::new(executor.data_store().clone()),
                 partitioned_txns,
                 concurrency,
-                None,
+                BlockExecutionLimit::no_limit(),
             )
             .unwrap();
 
-        let unsharded_txn_output =
-            AptosVM::execute_block(&execution_ordered_txns, executor.data_store(), None).unwrap();
+        let unsharded_txn_output = AptosVM::execute_block(
+            &execution_ordered_txns,
+            executor.data_store(),
+            BlockExecutionLimit::no_limit(),
+        )
+        .unwrap();
         compare_txn_outputs(unsharded_txn_output, sharded_txn_output);
     }
 }
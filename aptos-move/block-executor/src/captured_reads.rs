@@ -3,6 +3,7 @@
 
 use anyhow::bail;
 use aptos_aggregator::{
+    delta_change_set::serialize,
     delta_math::DeltaHistory,
     types::{
         code_invariant_error, DelayedFieldValue, DelayedFieldsSpeculativeError, PanicOr,
@@ -36,11 +37,12 @@ use std::{
 };
 
 /// The enum variants should not be re-ordered, as it defines a relation
-/// Existence < Metadata < Value.
+/// Existence < Metadata < Size < Value.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub(crate) enum ReadKind {
     Exists,
     Metadata,
+    Size,
     Value,
 }
 
@@ -62,6 +64,9 @@ pub(crate) enum DataRead<V> {
         Option<Arc<MoveTypeLayout>>,
     ),
     Metadata(Option<StateValueMetadataKind>),
+    /// Metadata (as in the Metadata variant) along with the size of the value in bytes,
+    /// which is 0 if the value does not exist.
+    Size(Option<StateValueMetadataKind>, u64),
     Exists(bool),
     /// Read resolved an aggregatorV1 delta to a value.
     /// TODO[agg_v1](cleanup): deprecate.
@@ -83,13 +88,14 @@ enum DataReadComparison {
 }
 
 impl<V: TransactionWrite> DataRead<V> {
-    // Assigns highest rank to Versioned / Resolved, then Metadata, then Exists.
-    // (e.g. versioned read implies metadata and existence information, and
-    // metadata information implies existence information).
+    // Assigns highest rank to Versioned / Resolved, then Size, then Metadata, then Exists.
+    // (e.g. versioned read implies size, metadata and existence information, size
+    // information implies metadata, and metadata information implies existence).
     fn get_kind(&self) -> ReadKind {
         use DataRead::*;
         match self {
             Versioned(_, _, _) | Resolved(_) => ReadKind::Value,
+            Size(_, _) => ReadKind::Size,
             Metadata(_) => ReadKind::Metadata,
             Exists(_) => ReadKind::Exists,
         }
@@ -131,14 +137,25 @@ impl<V: TransactionWrite> DataRead<V> {
         }
 
         (self_kind > kind).then(|| match (self, &kind) {
+            (DataRead::Versioned(_, v, _), ReadKind::Size) => DataRead::Size(
+                v.as_state_value_metadata(),
+                v.bytes().map_or(0, |bytes| bytes.len() as u64),
+            ),
             (DataRead::Versioned(_, v, _), ReadKind::Metadata) => {
                 // For deletion, as_state_value_metadata returns None, also asserted by tests.
                 DataRead::Metadata(v.as_state_value_metadata())
             },
             (DataRead::Versioned(_, v, _), ReadKind::Exists) => DataRead::Exists(!v.is_deletion()),
+            (DataRead::Resolved(v), ReadKind::Size) => {
+                DataRead::Size(Some(None), serialize(v).len() as u64)
+            },
             (DataRead::Resolved(_), ReadKind::Metadata) => DataRead::Metadata(Some(None)),
             (DataRead::Resolved(_), ReadKind::Exists) => DataRead::Exists(true),
-            (DataRead::Metadata(maybe_metadata), ReadKind::Exists) => {
+            (DataRead::Size(maybe_metadata, _), ReadKind::Metadata) => {
+                DataRead::Metadata(maybe_metadata.clone())
+            },
+            (DataRead::Size(maybe_metadata, _), ReadKind::Exists)
+            | (DataRead::Metadata(maybe_metadata), ReadKind::Exists) => {
                 DataRead::Exists(maybe_metadata.is_some())
             },
             (_, _) => unreachable!("{:?}, {:?} must be covered", self_kind, kind),
@@ -418,8 +435,8 @@ impl<T: Transaction> CapturedReads<T> {
         kind: ReadKind,
     ) -> Option<DataRead<T::Value>> {
         assert!(
-            kind != ReadKind::Metadata || maybe_tag.is_none(),
-            "May not request metadata of a group member"
+            !matches!(kind, ReadKind::Metadata | ReadKind::Size) || maybe_tag.is_none(),
+            "May not request metadata or size of a group member"
         );
 
         match maybe_tag {
@@ -656,7 +673,8 @@ mod test {
     #[test]
     fn data_read_kind() {
         // Test the strict ordering of enum variants for the read kinds.
-        assert_gt!(ReadKind::Value, ReadKind::Metadata);
+        assert_gt!(ReadKind::Value, ReadKind::Size);
+        assert_gt!(ReadKind::Size, ReadKind::Metadata);
         assert_gt!(ReadKind::Metadata, ReadKind::Exists);
 
         // Test that get_kind returns the proper kind for data read instances.
@@ -674,6 +692,10 @@ mod test {
             DataRead::Resolved::<ValueType>(200).get_kind(),
             ReadKind::Value
         );
+        assert_eq!(
+            DataRead::Size::<ValueType>(Some(None), 1).get_kind(),
+            ReadKind::Size
+        );
         assert_eq!(
             DataRead::Metadata::<ValueType>(Some(None)).get_kind(),
             ReadKind::Metadata
@@ -800,6 +822,26 @@ mod test {
         assert_inconsistent_downcast!(legacy_metadata, not_exists);
         assert_inconsistent_downcast!(deletion_metadata, exists);
 
+        // Size reads contain metadata and existence information.
+        let legacy_size = DataRead::Size(Some(None), 1);
+        let deletion_size = DataRead::Size(None, 0);
+        let size = DataRead::Size(Some(raw_metadata(1)), 2);
+        assert_contains!(versioned_legacy, legacy_size);
+        assert_contains!(versioned_deletion, deletion_size);
+        assert_contains!(versioned_with_metadata, size);
+        assert_contains!(resolved, DataRead::Size(Some(None), 16));
+        assert_contains!(legacy_size, legacy_metadata);
+        assert_contains!(legacy_size, exists);
+        assert_contains!(deletion_size, deletion_metadata);
+        assert_contains!(deletion_size, not_exists);
+        assert_contains!(size, metadata);
+        assert_insufficient!(size, versioned_with_metadata);
+        assert_insufficient!(metadata, size);
+        // A different size at the same version is inconsistent.
+        assert_inconsistent_same_kind!(legacy_size, DataRead::Size(Some(None), 2));
+        assert_inconsistent_downcast!(versioned_legacy, DataRead::Size(Some(None), 2));
+        assert_inconsistent_downcast!(resolved, legacy_size);
+
         // Test that V is getting ignored in the comparison.
         assert_eq!(
             versioned_legacy,
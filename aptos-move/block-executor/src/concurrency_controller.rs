@@ -0,0 +1,81 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::counters;
+use aptos_logger::info;
+use std::{
+    cmp::{max, min},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+/// Above this many re-executions per committed transaction, most of the speculative work of
+/// the block was thrown away and fewer workers would have done as well.
+const HIGH_REEXECUTION_RATE: f64 = 0.5;
+/// Below this many re-executions per committed transaction, the block could have used more
+/// workers without wasting much speculative work.
+const LOW_REEXECUTION_RATE: f64 = 0.1;
+
+/// Feedback controller adjusting the concurrency level of parallel execution from block to
+/// block, based on the rate of re-executions (due to conflicts) observed in previous blocks.
+/// The concurrency level is halved on highly conflicting blocks and doubled on blocks with
+/// few conflicts, within the given bounds.
+#[derive(Debug)]
+pub struct ConcurrencyController {
+    min_concurrency_level: usize,
+    max_concurrency_level: usize,
+    concurrency_level: AtomicUsize,
+}
+
+impl ConcurrencyController {
+    /// Blocks are executed with the maximum concurrency level until conflicts are observed.
+    /// The minimum concurrency level is at least 2, as conflict rates are only measured for
+    /// parallel execution.
+    pub fn new(min_concurrency_level: usize, max_concurrency_level: usize) -> Self {
+        let min_concurrency_level = min(max(min_concurrency_level, 2), max_concurrency_level);
+        Self {
+            min_concurrency_level,
+            max_concurrency_level,
+            concurrency_level: AtomicUsize::new(max_concurrency_level),
+        }
+    }
+
+    /// The concurrency level to execute the next block with.
+    pub fn concurrency_level(&self) -> usize {
+        self.concurrency_level.load(Ordering::Relaxed)
+    }
+
+    /// Records the outcome of a block executed in parallel with the given concurrency level.
+    pub fn record_block(
+        &self,
+        concurrency_level: usize,
+        num_committed_txns: usize,
+        num_reexecutions: usize,
+    ) {
+        // Small blocks do not keep all workers busy, so their conflict rate is not meaningful.
+        if num_committed_txns < concurrency_level {
+            return;
+        }
+
+        let reexecution_rate = num_reexecutions as f64 / num_committed_txns as f64;
+        counters::BLOCK_REEXECUTION_RATE.observe(reexecution_rate);
+
+        let new_concurrency_level = if reexecution_rate > HIGH_REEXECUTION_RATE {
+            max(concurrency_level / 2, self.min_concurrency_level)
+        } else if reexecution_rate < LOW_REEXECUTION_RATE {
+            min(concurrency_level * 2, self.max_concurrency_level)
+        } else {
+            concurrency_level
+        };
+
+        if new_concurrency_level
+            != self
+                .concurrency_level
+                .swap(new_concurrency_level, Ordering::Relaxed)
+        {
+            info!(
+                "[BlockSTM]: {} re-executions per committed txn, concurrency level set to {}",
+                reexecution_rate, new_concurrency_level,
+            );
+        }
+    }
+}
@@ -82,6 +82,15 @@ pub static EXCEED_PER_BLOCK_GAS_LIMIT_COUNT: Lazy<IntCounterVec> = Lazy::new(||
     .unwrap()
 });
 
+pub static EXCEED_PER_BLOCK_OUTPUT_LIMIT_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_execution_output_limit_count",
+        "Count of times the BlockSTM is early halted due to exceeding the per-block output limit",
+        &["mode"]
+    )
+    .unwrap()
+});
+
 pub static PARALLEL_EXECUTION_SECONDS: Lazy<Histogram> = Lazy::new(|| {
     register_histogram!(
         // metric name
@@ -185,6 +194,15 @@ pub static BLOCK_COMMITTED_TXNS: Lazy<HistogramVec> = Lazy::new(|| {
     .unwrap()
 });
 
+pub static BLOCK_REEXECUTION_RATE: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        "aptos_execution_block_reexecution_rate",
+        "The per-block number of re-executions per committed txn (Block STM)",
+        exponential_buckets(/*start=*/ 0.01, /*factor=*/ 2.0, /*count=*/ 12).unwrap(),
+    )
+    .unwrap()
+});
+
 pub(crate) fn update_parallel_block_gas_counters(
     accumulated_fee_statement: &FeeStatement,
     num_committed: usize,
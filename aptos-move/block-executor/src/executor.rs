@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    concurrency_controller::ConcurrencyController,
     counters,
     counters::{
         PARALLEL_EXECUTION_SECONDS, RAYON_EXECUTION_SECONDS, TASK_EXECUTE_SECONDS,
@@ -34,6 +35,7 @@ use aptos_types::{
     contract_event::TransactionEvent,
     executable::Executable,
     fee_statement::FeeStatement,
+    on_chain_config::BlockExecutionLimit,
     transaction::BlockExecutableTransaction as Transaction,
     write_set::{TransactionWrite, WriteOp},
 };
@@ -49,7 +51,10 @@ use std::{
     cell::RefCell,
     collections::{BTreeMap, HashMap, HashSet},
     marker::{PhantomData, Sync},
-    sync::{atomic::AtomicU32, Arc},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
 };
 
 pub struct BlockExecutor<T, E, S, L, X> {
@@ -57,8 +62,9 @@ pub struct BlockExecutor<T, E, S, L, X> {
     // threads that may be concurrently participating in parallel execution.
     concurrency_level: usize,
     executor_thread_pool: Arc<ThreadPool>,
-    maybe_block_gas_limit: Option<u64>,
+    block_execution_limit: BlockExecutionLimit,
     transaction_commit_hook: Option<L>,
+    concurrency_controller: Option<Arc<ConcurrencyController>>,
     phantom: PhantomData<(T, E, S, L, X)>,
 }
 
@@ -75,7 +81,7 @@ where
     pub fn new(
         concurrency_level: usize,
         executor_thread_pool: Arc<ThreadPool>,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
         transaction_commit_hook: Option<L>,
     ) -> Self {
         assert!(
@@ -86,12 +92,23 @@ where
         Self {
             concurrency_level,
             executor_thread_pool,
-            maybe_block_gas_limit,
+            block_execution_limit,
             transaction_commit_hook,
+            concurrency_controller: None,
             phantom: PhantomData,
         }
     }
 
+    /// Reports the re-execution rate of each block executed in parallel to the controller,
+    /// which is expected to provide the concurrency level of the next executor.
+    pub fn with_concurrency_controller(
+        mut self,
+        concurrency_controller: Arc<ConcurrencyController>,
+    ) -> Self {
+        self.concurrency_controller = Some(concurrency_controller);
+        self
+    }
+
     fn execute(
         idx_to_execute: TxnIndex,
         incarnation: Incarnation,
@@ -413,7 +430,7 @@ where
     /// way, the materialization can be almost embarassingly parallelizable.
     fn prepare_and_queue_commit_ready_txns(
         &self,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
         scheduler: &Scheduler,
         versioned_cache: &MVHashMap<T::Key, T::Tag, T::Value, X, T::Identifier>,
         scheduler_task: &mut SchedulerTask,
@@ -421,22 +438,29 @@ where
         shared_commit_state: &ExplicitSyncWrapper<(
             FeeStatement,
             Vec<FeeStatement>,
+            u64,
             Option<Error<E::Error>>,
         )>,
         base_view: &S,
         start_shared_counter: u32,
         shared_counter: &AtomicU32,
+        num_reexecutions: &AtomicU32,
         executor: &E,
         block: &[T],
     ) -> ::std::result::Result<(), PanicOr<IntentionalFallbackToSequential>> {
         let mut shared_commit_state_guard = shared_commit_state.acquire();
-        let (accumulated_fee_statement, txn_fee_statements, shared_maybe_error) =
-            shared_commit_state_guard.dereference_mut();
+        let (
+            accumulated_fee_statement,
+            txn_fee_statements,
+            accumulated_output_size,
+            shared_maybe_error,
+        ) = shared_commit_state_guard.dereference_mut();
 
         let update_counters_and_log_info =
             |txn_idx: u32,
              accumulated_fee_statement: &mut FeeStatement,
-             txn_fee_statements: &mut Vec<FeeStatement>| {
+             txn_fee_statements: &mut Vec<FeeStatement>,
+             accumulated_output_size: u64| {
                 counters::update_parallel_block_gas_counters(
                     accumulated_fee_statement,
                     (txn_idx + 1) as usize,
@@ -447,17 +471,22 @@ where
                     + accumulated_fee_statement.io_gas_used();
                 info!(
                     "[BlockSTM]: Parallel execution completed. {} out of {} txns committed. \
-		         accumulated_non_storage_gas = {}, limit = {:?}",
+		         accumulated_non_storage_gas = {}, accumulated_output_size = {}, limit = {:?}",
                     txn_idx + 1,
                     scheduler.num_txns(),
                     accumulated_non_storage_gas,
-                    maybe_block_gas_limit,
+                    accumulated_output_size,
+                    block_execution_limit,
                 );
             };
 
         while let Some((txn_idx, incarnation)) = scheduler.try_commit() {
+            // Every incarnation after the first one is a re-execution.
+            num_reexecutions.fetch_add(incarnation, Ordering::Relaxed);
+
             if !Self::validate_commit_ready(txn_idx, versioned_cache, last_input_output)? {
                 // Transaction needs to be re-executed, one final time.
+                num_reexecutions.fetch_add(1, Ordering::Relaxed);
 
                 Self::update_transaction_on_abort(txn_idx, last_input_output, versioned_cache);
                 // We are going to skip reducing validation index here, as we
@@ -505,26 +534,18 @@ where
                 accumulated_fee_statement.add_fee_statement(&fee_statement);
                 txn_fee_statements.push(fee_statement);
 
-                if let Some(per_block_gas_limit) = maybe_block_gas_limit {
-                    // When the accumulated execution and io gas of the committed txns exceeds
-                    // PER_BLOCK_GAS_LIMIT, early halt BlockSTM. Storage gas does not count towards
-                    // the per block gas limit, as we measure execution related cost here.
-                    let accumulated_non_storage_gas = accumulated_fee_statement
-                        .execution_gas_used()
-                        + accumulated_fee_statement.io_gas_used();
-                    if accumulated_non_storage_gas >= per_block_gas_limit {
-                        counters::EXCEED_PER_BLOCK_GAS_LIMIT_COUNT
-                            .with_label_values(&[counters::Mode::PARALLEL])
-                            .inc();
-                        info!(
-                            "[BlockSTM]: Parallel execution early halted due to \
-                             accumulated_non_storage_gas {} >= PER_BLOCK_GAS_LIMIT {}",
-                            accumulated_non_storage_gas, per_block_gas_limit,
-                        );
+                *accumulated_output_size += last_input_output
+                    .output_approx_size(txn_idx)
+                    .expect("Output must be recorded for a committed txn with a fee statement");
 
-                        // Set the execution output status to be SkipRest, to skip the rest of the txns.
-                        last_input_output.update_to_skip_rest(txn_idx);
-                    }
+                if Self::exceeds_block_execution_limit(
+                    block_execution_limit,
+                    accumulated_fee_statement,
+                    *accumulated_output_size,
+                    counters::Mode::PARALLEL,
+                ) {
+                    // Set the execution output status to be SkipRest, to skip the rest of the txns.
+                    last_input_output.update_to_skip_rest(txn_idx);
                 }
             }
 
@@ -582,6 +603,7 @@ where
                         txn_idx,
                         accumulated_fee_statement,
                         txn_fee_statements,
+                        *accumulated_output_size,
                     );
                 } // else it's already halted
                 break;
@@ -604,6 +626,7 @@ where
                         txn_idx,
                         accumulated_fee_statement,
                         txn_fee_statements,
+                        *accumulated_output_size,
                     );
                 }
                 break;
@@ -874,9 +897,11 @@ where
         base_view: &S,
         start_shared_counter: u32,
         shared_counter: &AtomicU32,
+        num_reexecutions: &AtomicU32,
         shared_commit_state: &ExplicitSyncWrapper<(
             FeeStatement,
             Vec<FeeStatement>,
+            u64,
             Option<Error<E::Error>>,
         )>,
         final_results: &ExplicitSyncWrapper<Vec<E::Output>>,
@@ -910,7 +935,7 @@ where
             // Priorotize committing validated transactions
             while scheduler.should_coordinate_commits() {
                 self.prepare_and_queue_commit_ready_txns(
-                    self.maybe_block_gas_limit,
+                    self.block_execution_limit,
                     scheduler,
                     versioned_cache,
                     &mut scheduler_task,
@@ -919,6 +944,7 @@ where
                     base_view,
                     start_shared_counter,
                     shared_counter,
+                    num_reexecutions,
                     &executor,
                     block,
                 )?;
@@ -1007,6 +1033,7 @@ where
         let shared_commit_state = ExplicitSyncWrapper::new((
             FeeStatement::zero(),
             Vec::<FeeStatement>::with_capacity(num_txns),
+            0,
             None,
         ));
 
@@ -1022,6 +1049,7 @@ where
 
         let last_input_output = TxnLastInputOutput::new(num_txns);
         let scheduler = Scheduler::new(num_txns);
+        let num_reexecutions = AtomicU32::new(0);
 
         let timer = RAYON_EXECUTION_SECONDS.start_timer();
         self.executor_thread_pool.scope(|s| {
@@ -1036,12 +1064,14 @@ where
                         base_view,
                         start_shared_counter,
                         &shared_counter,
+                        &num_reexecutions,
                         &shared_commit_state,
                         &final_results,
                     ) {
                         if scheduler.halt() {
                             let mut shared_commit_state_guard = shared_commit_state.acquire();
-                            let (_, _, maybe_error) = shared_commit_state_guard.dereference_mut();
+                            let (_, _, _, maybe_error) =
+                                shared_commit_state_guard.dereference_mut();
                             *maybe_error = Some(Error::FallbackToSequential(e));
                         }
                     }
@@ -1058,10 +1088,19 @@ where
             drop(versioned_cache);
         });
 
-        let (_, _, maybe_error) = shared_commit_state.into_inner();
+        let (_, txn_fee_statements, _, maybe_error) = shared_commit_state.into_inner();
         match maybe_error {
             Some(err) => Err(err),
-            None => Ok(final_results.into_inner()),
+            None => {
+                if let Some(concurrency_controller) = &self.concurrency_controller {
+                    concurrency_controller.record_block(
+                        self.concurrency_level,
+                        txn_fee_statements.len(),
+                        num_reexecutions.into_inner() as usize,
+                    );
+                }
+                Ok(final_results.into_inner())
+            },
         }
     }
 
@@ -1160,6 +1199,7 @@ where
         let unsync_map = UnsyncMap::new();
         let mut ret = Vec::with_capacity(num_txns);
         let mut accumulated_fee_statement = FeeStatement::zero();
+        let mut accumulated_output_size = 0;
 
         for (idx, txn) in signature_verified_block.iter().enumerate() {
             let latest_view = LatestView::<T, S, X>::new(
@@ -1188,6 +1228,7 @@ where
                     let fee_statement = output.fee_statement();
                     accumulated_fee_statement.add_fee_statement(&fee_statement);
                     counters::update_sequential_txn_gas_counters(&fee_statement);
+                    accumulated_output_size += output.output_approx_size();
 
                     // Apply the writes.
                     // TODO[agg_v2](fix): return code invariant error if dynamic change set optimizations disabled.
@@ -1295,24 +1336,17 @@ where
                 break;
             }
 
-            if let Some(per_block_gas_limit) = self.maybe_block_gas_limit {
-                // When the accumulated gas of the committed txns
-                // exceeds per_block_gas_limit, halt sequential execution.
-                let accumulated_non_storage_gas = accumulated_fee_statement.execution_gas_used()
-                    + accumulated_fee_statement.io_gas_used();
-                if accumulated_non_storage_gas >= per_block_gas_limit {
-                    counters::EXCEED_PER_BLOCK_GAS_LIMIT_COUNT
-                        .with_label_values(&[counters::Mode::SEQUENTIAL])
-                        .inc();
-                    info!(
-                        "[Execution]: Sequential execution early halted due to \
-                        accumulated_non_storage_gas {} >= PER_BLOCK_GAS_LIMIT {}, {} txns committed.",
-                        accumulated_non_storage_gas,
-                        per_block_gas_limit,
-                        ret.len()
-                    );
-                    break;
-                }
+            if Self::exceeds_block_execution_limit(
+                self.block_execution_limit,
+                &accumulated_fee_statement,
+                accumulated_output_size,
+                counters::Mode::SEQUENTIAL,
+            ) {
+                info!(
+                    "[Execution]: Sequential execution early halted, {} txns committed.",
+                    ret.len()
+                );
+                break;
             }
         }
 
@@ -1321,11 +1355,13 @@ where
                 + accumulated_fee_statement.io_gas_used();
             info!(
                 "[Execution]: Sequential execution completed. \
-		 {} out of {} txns committed. accumulated_non_storage_gas = {}, limit = {:?}",
+		 {} out of {} txns committed. accumulated_non_storage_gas = {}, \
+		 accumulated_output_size = {}, limit = {:?}",
                 ret.len(),
                 num_txns,
                 accumulated_non_storage_gas,
-                self.maybe_block_gas_limit,
+                accumulated_output_size,
+                self.block_execution_limit,
             );
         }
 
@@ -1334,6 +1370,49 @@ where
         Ok(ret)
     }
 
+    /// Returns true if the transactions committed so far exceed a limit on the execution
+    /// of the block, in which case the rest of the transactions must be skipped.
+    fn exceeds_block_execution_limit(
+        block_execution_limit: BlockExecutionLimit,
+        accumulated_fee_statement: &FeeStatement,
+        accumulated_output_size: u64,
+        mode: &str,
+    ) -> bool {
+        if let Some(per_block_gas_limit) = block_execution_limit.block_gas_limit {
+            // Storage gas does not count towards the per block gas limit, as we measure
+            // execution related cost here.
+            let accumulated_non_storage_gas = accumulated_fee_statement.execution_gas_used()
+                + accumulated_fee_statement.io_gas_used();
+            if accumulated_non_storage_gas >= per_block_gas_limit {
+                counters::EXCEED_PER_BLOCK_GAS_LIMIT_COUNT
+                    .with_label_values(&[mode])
+                    .inc();
+                info!(
+                    "[BlockSTM]: Execution early halted due to \
+                     accumulated_non_storage_gas {} >= PER_BLOCK_GAS_LIMIT {} ({} mode)",
+                    accumulated_non_storage_gas, per_block_gas_limit, mode,
+                );
+                return true;
+            }
+        }
+
+        if let Some(max_block_output_bytes) = block_execution_limit.max_block_output_bytes {
+            if accumulated_output_size >= max_block_output_bytes {
+                counters::EXCEED_PER_BLOCK_OUTPUT_LIMIT_COUNT
+                    .with_label_values(&[mode])
+                    .inc();
+                info!(
+                    "[BlockSTM]: Execution early halted due to \
+                     accumulated_output_size {} >= MAX_BLOCK_OUTPUT_BYTES {} ({} mode)",
+                    accumulated_output_size, max_block_output_bytes, mode,
+                );
+                return true;
+            }
+        }
+
+        false
+    }
+
     pub fn execute_block(
         &self,
         executor_arguments: E::Argument,
@@ -1384,6 +1463,10 @@ where
                 // Clear by re-initializing the speculative logs.
                 init_speculative_logs(signature_verified_block.len());
 
+                if let Some(commit_hook) = &self.transaction_commit_hook {
+                    commit_hook.on_execution_restarted();
+                }
+
                 ret = self.execute_transactions_sequential(
                     executor_arguments,
                     signature_verified_block,
@@ -140,6 +140,7 @@ subsequent incarnation to finish.
 extern crate scopeguard;
 
 mod captured_reads;
+pub mod concurrency_controller;
 pub mod counters;
 pub mod errors;
 pub mod executor;
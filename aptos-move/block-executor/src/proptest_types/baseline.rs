@@ -18,7 +18,10 @@ use crate::{
     },
 };
 use aptos_aggregator::delta_change_set::serialize;
-use aptos_types::{contract_event::TransactionEvent, write_set::TransactionWrite};
+use aptos_types::{
+    contract_event::TransactionEvent, on_chain_config::BlockExecutionLimit,
+    write_set::TransactionWrite,
+};
 use aptos_vm_types::resource_group_adapter::group_size_as_sum;
 use bytes::Bytes;
 use claims::{assert_matches, assert_none, assert_some, assert_some_eq};
@@ -70,7 +73,7 @@ enum BaselineStatus {
     Success,
     Aborted,
     SkipRest,
-    GasLimitExceeded,
+    LimitExceeded,
 }
 
 /// Sequential baseline of execution result for dummy transaction, containing a vector
@@ -94,10 +97,11 @@ impl<K: Debug + Hash + Clone + Eq> BaselineOutput<K> {
     /// work with dynamic read/writes.
     pub(crate) fn generate<E: Debug + Clone + TransactionEvent>(
         txns: &[MockTransaction<K, E>],
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> Self {
         let mut current_world = HashMap::<K, BaselineValue>::new();
         let mut accumulated_gas = 0;
+        let mut accumulated_output_size = 0;
 
         let mut status = BaselineStatus::Success;
         let mut read_values = vec![];
@@ -188,13 +192,22 @@ impl<K: Debug + Hash + Clone + Eq> BaselineOutput<K> {
                                 .group_reads
                                 .clone()));
 
-                            // Apply gas.
+                            // Apply gas and output size.
                             accumulated_gas += incarnation_behaviors[last_incarnation].gas;
-                            if let Some(block_gas_limit) = maybe_block_gas_limit {
-                                if accumulated_gas >= block_gas_limit {
-                                    status = BaselineStatus::GasLimitExceeded;
-                                    break;
-                                }
+                            accumulated_output_size += incarnation_behaviors[last_incarnation]
+                                .writes
+                                .iter()
+                                .map(|(_, v)| v.bytes().map_or(0, |bytes| bytes.len() as u64))
+                                .sum::<u64>();
+                            if block_execution_limit
+                                .block_gas_limit
+                                .is_some_and(|limit| accumulated_gas >= limit)
+                                || block_execution_limit
+                                    .max_block_output_bytes
+                                    .is_some_and(|limit| accumulated_output_size >= limit)
+                            {
+                                status = BaselineStatus::LimitExceeded;
+                                break;
                             }
                         },
                         Err(_) => {
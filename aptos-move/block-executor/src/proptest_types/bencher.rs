@@ -13,7 +13,10 @@ use crate::{
     },
     txn_commit_hook::NoOpTransactionCommitHook,
 };
-use aptos_types::{contract_event::TransactionEvent, executable::ExecutableTestType};
+use aptos_types::{
+    contract_event::TransactionEvent, executable::ExecutableTestType,
+    on_chain_config::BlockExecutionLimit,
+};
 use criterion::{BatchSize, Bencher as CBencher};
 use num_cpus;
 use proptest::{
@@ -105,7 +108,8 @@ where
             .map(|txn_gen| txn_gen.materialize(&key_universe, (false, false)))
             .collect();
 
-        let baseline_output = BaselineOutput::generate(&transactions, None);
+        let baseline_output =
+            BaselineOutput::generate(&transactions, BlockExecutionLimit::no_limit());
 
         Self {
             transactions,
@@ -131,7 +135,12 @@ where
             EmptyDataView<KeyType<K>>,
             NoOpTransactionCommitHook<MockOutput<KeyType<K>, E>, usize>,
             ExecutableTestType,
-        >::new(num_cpus::get(), executor_thread_pool, None, None)
+        >::new(
+            num_cpus::get(),
+            executor_thread_pool,
+            BlockExecutionLimit::no_limit(),
+            None,
+        )
         .execute_transactions_parallel((), &self.transactions, &data_view);
 
         self.baseline_output.assert_output(&output);
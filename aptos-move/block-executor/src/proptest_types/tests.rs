@@ -16,7 +16,10 @@ use crate::{
     txn_commit_hook::NoOpTransactionCommitHook,
 };
 use aptos_aggregator::types::PanicOr;
-use aptos_types::{contract_event::TransactionEvent, executable::ExecutableTestType};
+use aptos_types::{
+    contract_event::TransactionEvent, executable::ExecutableTestType,
+    on_chain_config::BlockExecutionLimit,
+};
 use claims::assert_ok;
 use num_cpus;
 use proptest::{
@@ -37,7 +40,7 @@ fn run_transactions<K, V, E>(
     skip_rest_transactions: Vec<Index>,
     num_repeat: usize,
     module_access: (bool, bool),
-    maybe_block_gas_limit: Option<u64>,
+    block_execution_limit: BlockExecutionLimit,
 ) where
     K: Hash + Clone + Debug + Eq + Send + Sync + PartialOrd + Ord + 'static,
     V: Clone + Eq + Send + Sync + Arbitrary + 'static,
@@ -78,7 +81,7 @@ fn run_transactions<K, V, E>(
         >::new(
             num_cpus::get(),
             executor_thread_pool.clone(),
-            maybe_block_gas_limit,
+            block_execution_limit,
             None,
         )
         .execute_transactions_parallel((), &transactions, &data_view);
@@ -93,7 +96,7 @@ fn run_transactions<K, V, E>(
             continue;
         }
 
-        BaselineOutput::generate(&transactions, maybe_block_gas_limit).assert_output(&output);
+        BaselineOutput::generate(&transactions, block_execution_limit).assert_output(&output);
     }
 }
 
@@ -106,7 +109,7 @@ proptest! {
         abort_transactions in vec(any::<Index>(), 0),
         skip_rest_transactions in vec(any::<Index>(), 0),
     ) {
-        run_transactions::<[u8; 32], [u8; 32], MockEvent>(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), None);
+        run_transactions::<[u8; 32], [u8; 32], MockEvent>(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), BlockExecutionLimit::no_limit());
     }
 
     #[test]
@@ -116,7 +119,7 @@ proptest! {
         abort_transactions in vec(any::<Index>(), 5),
         skip_rest_transactions in vec(any::<Index>(), 0),
     ) {
-        run_transactions::<[u8; 32], [u8; 32], MockEvent>(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), None);
+        run_transactions::<[u8; 32], [u8; 32], MockEvent>(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), BlockExecutionLimit::no_limit());
     }
 
     #[test]
@@ -126,7 +129,7 @@ proptest! {
         abort_transactions in vec(any::<Index>(), 0),
         skip_rest_transactions in vec(any::<Index>(), 5),
     ) {
-        run_transactions::<[u8; 32], [u8; 32], MockEvent>(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), None);
+        run_transactions::<[u8; 32], [u8; 32], MockEvent>(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), BlockExecutionLimit::no_limit());
     }
 
     #[test]
@@ -136,7 +139,7 @@ proptest! {
         abort_transactions in vec(any::<Index>(), 5),
         skip_rest_transactions in vec(any::<Index>(), 5),
     ) {
-        run_transactions::<[u8; 32], [u8; 32], MockEvent>(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), None);
+        run_transactions::<[u8; 32], [u8; 32], MockEvent>(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), BlockExecutionLimit::no_limit());
     }
 
     #[test]
@@ -146,7 +149,7 @@ proptest! {
         abort_transactions in vec(any::<Index>(), 3),
         skip_rest_transactions in vec(any::<Index>(), 3),
     ) {
-        run_transactions::<[u8; 32], [u8; 32], MockEvent>(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), None);
+        run_transactions::<[u8; 32], [u8; 32], MockEvent>(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), BlockExecutionLimit::no_limit());
     }
 }
 
@@ -172,7 +175,36 @@ fn dynamic_read_writes_with_block_gas_limit(num_txns: usize, maybe_block_gas_lim
         vec![],
         100,
         (false, false),
-        maybe_block_gas_limit,
+        BlockExecutionLimit::with_block_gas_limit(maybe_block_gas_limit),
+    );
+}
+
+fn dynamic_read_writes_with_block_output_limit(num_txns: usize, max_block_output_bytes: u64) {
+    let mut runner = TestRunner::default();
+
+    let universe = vec(any::<[u8; 32]>(), 100)
+        .new_tree(&mut runner)
+        .expect("creating a new value should succeed")
+        .current();
+    let transaction_gen = vec(
+        any_with::<TransactionGen<[u8; 32]>>(TransactionGenParams::new_dynamic()),
+        num_txns,
+    )
+    .new_tree(&mut runner)
+    .expect("creating a new value should succeed")
+    .current();
+
+    run_transactions::<[u8; 32], [u8; 32], MockEvent>(
+        &universe,
+        transaction_gen,
+        vec![],
+        vec![],
+        100,
+        (false, false),
+        BlockExecutionLimit {
+            block_gas_limit: None,
+            max_block_output_bytes: Some(max_block_output_bytes),
+        },
     );
 }
 
@@ -218,12 +250,16 @@ fn deltas_writes_mixed_with_block_gas_limit(num_txns: usize, maybe_block_gas_lim
         >::new(
             num_cpus::get(),
             executor_thread_pool.clone(),
-            maybe_block_gas_limit,
+            BlockExecutionLimit::with_block_gas_limit(maybe_block_gas_limit),
             None,
         )
         .execute_transactions_parallel((), &transactions, &data_view);
 
-        BaselineOutput::generate(&transactions, maybe_block_gas_limit).assert_output(&output);
+        BaselineOutput::generate(
+            &transactions,
+            BlockExecutionLimit::with_block_gas_limit(maybe_block_gas_limit),
+        )
+        .assert_output(&output);
     }
 }
 
@@ -269,12 +305,16 @@ fn deltas_resolver_with_block_gas_limit(num_txns: usize, maybe_block_gas_limit:
         >::new(
             num_cpus::get(),
             executor_thread_pool.clone(),
-            maybe_block_gas_limit,
+            BlockExecutionLimit::with_block_gas_limit(maybe_block_gas_limit),
             None,
         )
         .execute_transactions_parallel((), &transactions, &data_view);
 
-        BaselineOutput::generate(&transactions, maybe_block_gas_limit).assert_output(&output);
+        BaselineOutput::generate(
+            &transactions,
+            BlockExecutionLimit::with_block_gas_limit(maybe_block_gas_limit),
+        )
+        .assert_output(&output);
     }
 }
 
@@ -304,7 +344,7 @@ fn dynamic_read_writes_contended_with_block_gas_limit(
         vec![],
         100,
         (false, false),
-        maybe_block_gas_limit,
+        BlockExecutionLimit::with_block_gas_limit(maybe_block_gas_limit),
     );
 }
 
@@ -333,7 +373,7 @@ fn module_publishing_fallback_with_block_gas_limit(
         vec![],
         2,
         (false, true),
-        maybe_block_gas_limit,
+        BlockExecutionLimit::with_block_gas_limit(maybe_block_gas_limit),
     );
     run_transactions::<[u8; 32], [u8; 32], MockEvent>(
         &universe,
@@ -342,7 +382,7 @@ fn module_publishing_fallback_with_block_gas_limit(
         vec![],
         2,
         (true, false),
-        maybe_block_gas_limit,
+        BlockExecutionLimit::with_block_gas_limit(maybe_block_gas_limit),
     );
     run_transactions::<[u8; 32], [u8; 32], MockEvent>(
         &universe,
@@ -351,7 +391,7 @@ fn module_publishing_fallback_with_block_gas_limit(
         vec![],
         2,
         (true, true),
-        maybe_block_gas_limit,
+        BlockExecutionLimit::with_block_gas_limit(maybe_block_gas_limit),
     );
 }
 
@@ -425,7 +465,7 @@ fn publishing_fixed_params_with_block_gas_limit(
     >::new(
         num_cpus::get(),
         executor_thread_pool,
-        maybe_block_gas_limit,
+        BlockExecutionLimit::with_block_gas_limit(maybe_block_gas_limit),
         None,
     )
     .execute_transactions_parallel((), &transactions, &data_view);
@@ -468,7 +508,9 @@ fn publishing_fixed_params_with_block_gas_limit(
         >::new(
             num_cpus::get(),
             executor_thread_pool.clone(),
-            Some(max(w_index, r_index) as u64 * MAX_GAS_PER_TXN + 1),
+            BlockExecutionLimit::with_block_gas_limit(Some(
+                max(w_index, r_index) as u64 * MAX_GAS_PER_TXN + 1,
+            )),
             None,
         ) // Ensure enough gas limit to commit the module txns (4 is maximum gas per txn)
         .execute_transactions_parallel((), &transactions, &data_view);
@@ -550,10 +592,16 @@ fn non_empty_group(
             NonEmptyGroupDataView<KeyType<[u8; 32]>>,
             NoOpTransactionCommitHook<MockOutput<KeyType<[u8; 32]>, MockEvent>, usize>,
             ExecutableTestType,
-        >::new(num_cpus::get(), executor_thread_pool.clone(), None, None)
+        >::new(
+            num_cpus::get(),
+            executor_thread_pool.clone(),
+            BlockExecutionLimit::no_limit(),
+            None,
+        )
         .execute_transactions_parallel((), &transactions, &data_view);
 
-        BaselineOutput::generate(&transactions, None).assert_output(&output);
+        BaselineOutput::generate(&transactions, BlockExecutionLimit::no_limit())
+            .assert_output(&output);
     }
 
     for _ in 0..num_repeat_sequential {
@@ -563,11 +611,17 @@ fn non_empty_group(
             NonEmptyGroupDataView<KeyType<[u8; 32]>>,
             NoOpTransactionCommitHook<MockOutput<KeyType<[u8; 32]>, MockEvent>, usize>,
             ExecutableTestType,
-        >::new(num_cpus::get(), executor_thread_pool.clone(), None, None)
+        >::new(
+            num_cpus::get(),
+            executor_thread_pool.clone(),
+            BlockExecutionLimit::no_limit(),
+            None,
+        )
         .execute_transactions_sequential((), &transactions, &data_view, true);
         // TODO: test dynamic disabled as well.
 
-        BaselineOutput::generate(&transactions, None).assert_output(&output);
+        BaselineOutput::generate(&transactions, BlockExecutionLimit::no_limit())
+            .assert_output(&output);
     }
 }
 
@@ -615,7 +669,7 @@ proptest! {
         abort_transactions in vec(any::<Index>(), 0),
         skip_rest_transactions in vec(any::<Index>(), 0),
     ) {
-        run_transactions::<[u8; 32], [u8; 32], MockEvent>(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), Some(rand::thread_rng().gen_range(0, 5000 * MAX_GAS_PER_TXN / 2)));
+        run_transactions::<[u8; 32], [u8; 32], MockEvent>(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), BlockExecutionLimit::with_block_gas_limit(Some(rand::thread_rng().gen_range(0, 5000 * MAX_GAS_PER_TXN / 2))));
     }
 
     #[test]
@@ -625,7 +679,7 @@ proptest! {
         abort_transactions in vec(any::<Index>(), 5),
         skip_rest_transactions in vec(any::<Index>(), 0),
     ) {
-        run_transactions::<[u8; 32], [u8; 32], MockEvent>(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), Some(rand::thread_rng().gen_range(0, 10 * MAX_GAS_PER_TXN / 2)));
+        run_transactions::<[u8; 32], [u8; 32], MockEvent>(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), BlockExecutionLimit::with_block_gas_limit(Some(rand::thread_rng().gen_range(0, 10 * MAX_GAS_PER_TXN / 2))));
     }
 
     #[test]
@@ -635,7 +689,7 @@ proptest! {
         abort_transactions in vec(any::<Index>(), 0),
         skip_rest_transactions in vec(any::<Index>(), 5),
     ) {
-        run_transactions::<[u8; 32], [u8; 32], MockEvent>(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), Some(rand::thread_rng().gen_range(0, 5000 * MAX_GAS_PER_TXN / 2)));
+        run_transactions::<[u8; 32], [u8; 32], MockEvent>(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), BlockExecutionLimit::with_block_gas_limit(Some(rand::thread_rng().gen_range(0, 5000 * MAX_GAS_PER_TXN / 2))));
     }
 
     #[test]
@@ -645,7 +699,7 @@ proptest! {
         abort_transactions in vec(any::<Index>(), 5),
         skip_rest_transactions in vec(any::<Index>(), 5),
     ) {
-        run_transactions::<[u8; 32], [u8; 32], MockEvent>(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), Some(rand::thread_rng().gen_range(0, 5000 * MAX_GAS_PER_TXN / 2)));
+        run_transactions::<[u8; 32], [u8; 32], MockEvent>(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), BlockExecutionLimit::with_block_gas_limit(Some(rand::thread_rng().gen_range(0, 5000 * MAX_GAS_PER_TXN / 2))));
     }
 
     #[test]
@@ -655,7 +709,7 @@ proptest! {
         abort_transactions in vec(any::<Index>(), 3),
         skip_rest_transactions in vec(any::<Index>(), 3),
     ) {
-        run_transactions::<[u8; 32], [u8; 32], MockEvent>(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), Some(rand::thread_rng().gen_range(0, 5000 * MAX_GAS_PER_TXN / 2)));
+        run_transactions::<[u8; 32], [u8; 32], MockEvent>(&universe, transaction_gen, abort_transactions, skip_rest_transactions, 1, (false, false), BlockExecutionLimit::with_block_gas_limit(Some(rand::thread_rng().gen_range(0, 5000 * MAX_GAS_PER_TXN / 2))));
     }
 }
 
@@ -669,6 +723,15 @@ fn dynamic_read_writes_with_block_gas_limit_test() {
     dynamic_read_writes_with_block_gas_limit(3000, Some(0));
 }
 
+#[test]
+fn dynamic_read_writes_with_block_output_limit_test() {
+    dynamic_read_writes_with_block_output_limit(
+        3000,
+        rand::thread_rng().gen_range(0, 3000 * 32) as u64,
+    );
+    dynamic_read_writes_with_block_output_limit(3000, 0);
+}
+
 #[test]
 fn deltas_writes_mixed_with_block_gas_limit_test() {
     deltas_writes_mixed_with_block_gas_limit(
@@ -1110,6 +1110,13 @@ where
             0,
         )
     }
+
+    fn output_approx_size(&self) -> u64 {
+        self.writes
+            .iter()
+            .map(|(_, v)| v.bytes().map_or(0, |bytes| bytes.len() as u64))
+            .sum()
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -170,4 +170,8 @@ pub trait TransactionOutput: Send + Sync + Debug {
 
     /// Return the fee statement of the transaction.
     fn fee_statement(&self) -> FeeStatement;
+
+    /// Return the approximate size of the write set of the transaction in bytes, which is
+    /// counted towards the per-block output limit.
+    fn output_approx_size(&self) -> u64;
 }
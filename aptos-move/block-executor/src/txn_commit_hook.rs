@@ -2,16 +2,26 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::task::TransactionOutput;
+use aptos_infallible::Mutex;
 use aptos_mvhashmap::types::TxnIndex;
+use crossbeam::channel::Sender;
+use std::{collections::BTreeMap, marker::PhantomData};
 
 /// An interface for listening to transaction commit events. The listener is called only once
-/// for each transaction commit.
+/// for each transaction commit. In parallel execution, the listener is called as committed
+/// transactions are materialized, which happens concurrently and hence not necessarily in the
+/// order of the transactions in the block.
 pub trait TransactionCommitHook: Send + Sync {
     type Output;
 
     fn on_transaction_committed(&self, txn_idx: TxnIndex, output: &Self::Output);
 
     fn on_execution_aborted(&self, txn_idx: TxnIndex);
+
+    /// Called when the block is about to be re-executed from the first transaction, e.g. on a
+    /// fallback to sequential execution. Transactions that were already reported may be
+    /// reported again with a different output.
+    fn on_execution_restarted(&self) {}
 }
 
 pub struct NoOpTransactionCommitHook<T, E> {
@@ -45,3 +55,76 @@ impl<T: TransactionOutput, E: Sync + Send> TransactionCommitHook
         // no-op
     }
 }
+
+/// Event streamed by [OrderedTransactionCommitHook].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TransactionCommitEvent<M> {
+    Committed(TxnIndex, M),
+    Aborted(TxnIndex),
+    /// The block is re-executed from scratch, all previously streamed events must be discarded.
+    Restarted,
+}
+
+/// Streams the commit events of a block to a channel, in the order of the transactions in the
+/// block, so that consumers can process outputs before the whole block finishes. Events of
+/// transactions that are materialized ahead of their predecessors are buffered until all the
+/// preceding transactions have been reported. Since outputs are owned by the executor, each
+/// output is converted into a message by the provided function.
+pub struct OrderedTransactionCommitHook<O, M, F> {
+    to_message: F,
+    sender: Sender<TransactionCommitEvent<M>>,
+    // Index of the next transaction to be streamed and the buffered events after it.
+    pending: Mutex<(TxnIndex, BTreeMap<TxnIndex, TransactionCommitEvent<M>>)>,
+    phantom: PhantomData<fn(&O)>,
+}
+
+impl<O, M, F> OrderedTransactionCommitHook<O, M, F>
+where
+    M: Send,
+    F: Fn(&O) -> M + Send + Sync,
+{
+    pub fn new(to_message: F, sender: Sender<TransactionCommitEvent<M>>) -> Self {
+        Self {
+            to_message,
+            sender,
+            pending: Mutex::new((0, BTreeMap::new())),
+            phantom: PhantomData,
+        }
+    }
+
+    fn stream(&self, txn_idx: TxnIndex, event: TransactionCommitEvent<M>) {
+        let mut pending = self.pending.lock();
+        let (next_idx, buffered) = &mut *pending;
+        buffered.insert(txn_idx, event);
+        while let Some(event) = buffered.remove(next_idx) {
+            // The consumer may have stopped listening, which must not affect execution.
+            let _ = self.sender.send(event);
+            *next_idx += 1;
+        }
+    }
+}
+
+impl<O, M, F> TransactionCommitHook for OrderedTransactionCommitHook<O, M, F>
+where
+    M: Send,
+    F: Fn(&O) -> M + Send + Sync,
+{
+    type Output = O;
+
+    fn on_transaction_committed(&self, txn_idx: TxnIndex, output: &Self::Output) {
+        self.stream(
+            txn_idx,
+            TransactionCommitEvent::Committed(txn_idx, (self.to_message)(output)),
+        );
+    }
+
+    fn on_execution_aborted(&self, txn_idx: TxnIndex) {
+        self.stream(txn_idx, TransactionCommitEvent::Aborted(txn_idx));
+    }
+
+    fn on_execution_restarted(&self) {
+        let mut pending = self.pending.lock();
+        *pending = (0, BTreeMap::new());
+        let _ = self.sender.send(TransactionCommitEvent::Restarted);
+    }
+}
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    concurrency_controller::ConcurrencyController,
     executor::BlockExecutor,
     proptest_types::{
         baseline::BaselineOutput,
@@ -14,7 +15,9 @@ use crate::{
     scheduler::{
         DependencyResult, ExecutionTaskType, Scheduler, SchedulerTask, TWaitForDependency,
     },
-    txn_commit_hook::NoOpTransactionCommitHook,
+    txn_commit_hook::{
+        NoOpTransactionCommitHook, OrderedTransactionCommitHook, TransactionCommitEvent,
+    },
 };
 use aptos_aggregator::{
     bounded_math::SignedU128,
@@ -25,6 +28,7 @@ use aptos_mvhashmap::types::TxnIndex;
 use aptos_types::{
     contract_event::TransactionEvent,
     executable::{ExecutableTestType, ModulePath},
+    on_chain_config::BlockExecutionLimit,
 };
 use claims::assert_matches;
 use rand::{prelude::*, random};
@@ -55,10 +59,15 @@ where
         DeltaDataView<K>,
         NoOpTransactionCommitHook<MockOutput<K, E>, usize>,
         ExecutableTestType,
-    >::new(num_cpus::get(), executor_thread_pool, None, None)
+    >::new(
+        num_cpus::get(),
+        executor_thread_pool,
+        BlockExecutionLimit::no_limit(),
+        None,
+    )
     .execute_transactions_parallel((), &transactions, &data_view);
 
-    let baseline = BaselineOutput::generate(&transactions, None);
+    let baseline = BaselineOutput::generate(&transactions, BlockExecutionLimit::no_limit());
     baseline.assert_output(&output);
 }
 
@@ -321,6 +330,64 @@ fn early_skips() {
     run_and_assert(transactions)
 }
 
+#[test]
+fn ordered_commit_hook() {
+    let keys: Vec<_> = (0..TXN_PER_BLOCK)
+        .map(|_| KeyType(random::<[u8; 32]>(), false))
+        .collect();
+    let transactions: Vec<_> = (0..NUM_BLOCKS)
+        .flat_map(|_| keys.iter())
+        .map(|key| {
+            MockTransaction::from_behavior(MockIncarnation::<KeyType<[u8; 32]>, MockEvent>::new(
+                vec![*key],                        // reads
+                vec![(*key, random_value(false))], // writes
+                vec![],
+                vec![],
+                1, // gas
+            ))
+        })
+        .collect();
+
+    let data_view = DeltaDataView::<KeyType<[u8; 32]>> {
+        phantom: PhantomData,
+    };
+    let executor_thread_pool = Arc::new(
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(num_cpus::get())
+            .build()
+            .unwrap(),
+    );
+    let (sender, receiver) = crossbeam::channel::unbounded();
+    let commit_hook = OrderedTransactionCommitHook::new(
+        |output: &MockOutput<KeyType<[u8; 32]>, MockEvent>| output.total_gas,
+        sender,
+    );
+
+    BlockExecutor::<
+        MockTransaction<KeyType<[u8; 32]>, MockEvent>,
+        MockTask<KeyType<[u8; 32]>, MockEvent>,
+        DeltaDataView<KeyType<[u8; 32]>>,
+        _,
+        ExecutableTestType,
+    >::new(
+        num_cpus::get(),
+        executor_thread_pool,
+        BlockExecutionLimit::no_limit(),
+        Some(commit_hook),
+    )
+    .execute_transactions_parallel((), &transactions, &data_view)
+    .unwrap();
+
+    // Events are streamed in the order of the block even though materialization is concurrent.
+    let events: Vec<_> = receiver.try_iter().collect();
+    assert_eq!(
+        events,
+        (0..transactions.len() as TxnIndex)
+            .map(|txn_idx| TransactionCommitEvent::Committed(txn_idx, 1))
+            .collect::<Vec<_>>()
+    );
+}
+
 #[test]
 fn scheduler_tasks() {
     let s = Scheduler::new(5);
@@ -891,3 +958,27 @@ fn no_conflict_task_count() {
         assert!(matches!(s.next_task(), SchedulerTask::Done));
     }
 }
+
+#[test]
+fn test_concurrency_level_adjustment() {
+    let controller = ConcurrencyController::new(1, 16);
+    assert_eq!(controller.concurrency_level(), 16);
+
+    // Highly conflicting blocks halve the concurrency level down to the minimum.
+    for expected in [8, 4, 2, 2] {
+        controller.record_block(controller.concurrency_level(), 100, 80);
+        assert_eq!(controller.concurrency_level(), expected);
+    }
+
+    // Moderately conflicting blocks and small blocks keep the concurrency level.
+    controller.record_block(2, 100, 30);
+    assert_eq!(controller.concurrency_level(), 2);
+    controller.record_block(2, 1, 0);
+    assert_eq!(controller.concurrency_level(), 2);
+
+    // Blocks with few conflicts double the concurrency level up to the maximum.
+    for expected in [4, 8, 16, 16] {
+        controller.record_block(controller.concurrency_level(), 100, 5);
+        assert_eq!(controller.concurrency_level(), expected);
+    }
+}
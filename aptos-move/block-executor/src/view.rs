@@ -69,6 +69,7 @@ use std::{
 pub(crate) enum ReadResult {
     Value(Option<StateValue>, Option<Arc<MoveTypeLayout>>),
     Metadata(Option<StateValueMetadataKind>),
+    Size(Option<u64>),
     Exists(bool),
     Uninitialized,
     // Must halt the execution of the calling transaction. This might be because
@@ -87,6 +88,7 @@ impl ReadResult {
                 ReadResult::Value(Some(StateValue::new_legacy(serialize(&v).into())), None)
             },
             DataRead::Metadata(maybe_metadata) => ReadResult::Metadata(maybe_metadata),
+            DataRead::Size(maybe_metadata, size) => ReadResult::Size(maybe_metadata.map(|_| size)),
             DataRead::Exists(exists) => ReadResult::Exists(exists),
         }
     }
@@ -958,6 +960,9 @@ impl<'a, T: Transaction, S: TStateView<Key = T::Key>, X: Executable> LatestView<
                     ReadKind::Metadata => {
                         ReadResult::Metadata(maybe_state_value.map(StateValue::into_metadata))
                     },
+                    ReadKind::Size => ReadResult::Size(
+                        maybe_state_value.map(|state_value| state_value.size() as u64),
+                    ),
                     ReadKind::Exists => ReadResult::Exists(maybe_state_value.is_some()),
                 })
             },
@@ -1026,6 +1031,17 @@ impl<'a, T: Transaction, S: TStateView<Key = T::Key>, X: Executable> TResourceVi
             })
     }
 
+    fn get_resource_state_value_size(&self, state_key: &Self::Key) -> anyhow::Result<Option<u64>> {
+        self.get_resource_state_value_impl(state_key, None, ReadKind::Size)
+            .map(|res| {
+                if let ReadResult::Size(v) = res {
+                    v
+                } else {
+                    unreachable!("Read result must be Size kind")
+                }
+            })
+    }
+
     fn resource_exists(&self, state_key: &Self::Key) -> anyhow::Result<bool> {
         self.get_resource_state_value_impl(state_key, None, ReadKind::Exists)
             .map(|res| {
@@ -0,0 +1,65 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{assert_success, MoveHarness};
+use aptos_cached_packages::aptos_stdlib;
+use aptos_types::{
+    account_address::AccountAddress,
+    account_config::CoinStoreResource,
+    on_chain_config::FeatureFlag,
+    transaction::{ExecutionStatus, TransactionPayload, TransactionStatus},
+    utility_coin::APTOS_COIN_TYPE,
+};
+use move_core_types::{move_resource::MoveStructType, vm_status::StatusCode};
+
+fn create_account_and_transfer(
+    create: AccountAddress,
+    transfer_to: AccountAddress,
+    amount: u64,
+) -> TransactionPayload {
+    TransactionPayload::Batched(vec![
+        aptos_stdlib::aptos_account_create_account(create).into_entry_function(),
+        aptos_stdlib::coin_transfer(APTOS_COIN_TYPE.clone(), transfer_to, amount)
+            .into_entry_function(),
+    ])
+}
+
+#[test]
+fn test_batched_payload_feature_gated() {
+    let mut h = MoveHarness::new();
+    let alice = h.new_account_at(AccountAddress::from_hex_literal("0xa11ce").unwrap());
+    let bob = AccountAddress::from_hex_literal("0xb0b").unwrap();
+
+    let status = h.run_transaction_payload(&alice, create_account_and_transfer(bob, bob, 1000));
+    assert_eq!(
+        status,
+        TransactionStatus::Discard(StatusCode::FEATURE_UNDER_GATING)
+    );
+}
+
+#[test]
+fn test_batched_payload_shares_session() {
+    let mut h = MoveHarness::new_with_features(vec![FeatureFlag::BATCHED_ENTRY_FUNCTIONS], vec![]);
+    let alice = h.new_account_at(AccountAddress::from_hex_literal("0xa11ce").unwrap());
+    let bob = AccountAddress::from_hex_literal("0xb0b").unwrap();
+
+    // The transfer only succeeds because it observes the coin store registered by the first call.
+    assert_success!(h.run_transaction_payload(&alice, create_account_and_transfer(bob, bob, 1000)));
+    assert_eq!(h.read_aptos_balance(&bob), 1000);
+}
+
+#[test]
+fn test_batched_payload_is_atomic() {
+    let mut h = MoveHarness::new_with_features(vec![FeatureFlag::BATCHED_ENTRY_FUNCTIONS], vec![]);
+    let alice = h.new_account_at(AccountAddress::from_hex_literal("0xa11ce").unwrap());
+    let bob = AccountAddress::from_hex_literal("0xb0b").unwrap();
+    let carol = AccountAddress::from_hex_literal("0xca501").unwrap();
+
+    // Carol has no coin store, so the transfer aborts and the account creation is rolled back.
+    let status = h.run_transaction_payload(&alice, create_account_and_transfer(bob, carol, 1000));
+    assert!(matches!(
+        status,
+        TransactionStatus::Keep(ExecutionStatus::MoveAbort { .. })
+    ));
+    assert!(!h.exists_resource(&bob, CoinStoreResource::struct_tag()));
+}
@@ -2,13 +2,21 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{assert_abort, assert_success, assert_vm_status, tests::common, MoveHarness};
-use aptos_framework::natives::code::{PackageRegistry, UpgradePolicy};
+use aptos_framework::{
+    natives::code::{PackageRegistry, UpgradePolicy},
+    BuildOptions, BuiltPackage,
+};
 use aptos_package_builder::PackageBuilder;
 use aptos_types::{
     account_address::{create_resource_address, AccountAddress},
     on_chain_config::FeatureFlag,
 };
-use move_core_types::{parser::parse_struct_tag, vm_status::StatusCode};
+use aptos_vm::{AptosVM, Incompatibility, PublishCompatibilityReport};
+use move_binary_format::file_format::Visibility;
+use move_core_types::{
+    identifier::Identifier, language_storage::ModuleId, parser::parse_struct_tag,
+    vm_status::StatusCode,
+};
 use rstest::rstest;
 use serde::{Deserialize, Serialize};
 
@@ -108,6 +116,52 @@ fn code_publishing_upgrade_fail_compat() {
     assert_vm_status!(status, StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE)
 }
 
+fn check_publish_compatibility(h: &MoveHarness, package: &str) -> PublishCompatibilityReport {
+    let package = BuiltPackage::build(
+        common::test_dir_path(&format!("code_publishing.data/{}", package)),
+        BuildOptions::default(),
+    )
+    .expect("building package must succeed");
+    AptosVM::check_publish_compatibility(h.executor.data_store(), package.extract_code()).unwrap()
+}
+
+#[test]
+fn code_publishing_check_compatibility() {
+    let mut h = MoveHarness::new();
+    let acc = h.new_account_at(AccountAddress::from_hex_literal("0xcafe").unwrap());
+
+    // A fresh publish has no upgrade to check
+    let report = check_publish_compatibility(&h, "pack_initial");
+    assert!(report.is_publishable());
+    assert!(report.verification_errors.is_empty());
+    assert!(report.upgrades.is_empty());
+
+    assert_success!(h.publish_package_cache_building(
+        &acc,
+        &common::test_dir_path("code_publishing.data/pack_initial"),
+    ));
+
+    let report = check_publish_compatibility(&h, "pack_upgrade_compat");
+    assert!(report.is_publishable());
+    assert_eq!(report.upgrades.len(), 1);
+    assert!(report.upgrades[0].is_fully_compatible());
+
+    // The report explains why publishing fails with BACKWARD_INCOMPATIBLE_MODULE_UPDATE
+    let report = check_publish_compatibility(&h, "pack_upgrade_incompat");
+    assert!(!report.is_publishable());
+    assert_eq!(report.upgrades.len(), 1);
+    assert_eq!(
+        report.upgrades[0].module_id,
+        ModuleId::new(*acc.address(), Identifier::new("test").unwrap())
+    );
+    assert_eq!(report.upgrades[0].incompatibilities, vec![
+        Incompatibility::FunctionSignatureChanged {
+            name: Identifier::new("hello").unwrap(),
+            visibility: Visibility::Public,
+        }
+    ]);
+}
+
 #[test]
 fn code_publishing_upgrade_fail_immutable() {
     let mut h = MoveHarness::new();
@@ -0,0 +1,41 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::MoveHarness;
+use aptos_cached_packages::aptos_stdlib;
+use aptos_gas_schedule::LATEST_GAS_FEATURE_VERSION;
+use aptos_types::{
+    account_address::AccountAddress,
+    transaction::{ExecutionStatus, TransactionStatus},
+};
+use aptos_vm::gas_feature_matrix::GasFeatureMatrix;
+
+#[test]
+fn execute_under_all_gas_feature_versions() {
+    let mut h = MoveHarness::new();
+    let sender = h.new_account_at(AccountAddress::from_hex_literal("0xbeef").unwrap());
+    let receiver = h.new_account_at(AccountAddress::from_hex_literal("0xcafe").unwrap());
+    let txn = h.create_transaction_payload(
+        &sender,
+        aptos_stdlib::aptos_coin_transfer(*receiver.address(), 1000),
+    );
+    let state_view = h.executor.data_store();
+
+    let matrix = GasFeatureMatrix::run_all_versions(&txn, state_view);
+    assert_eq!(
+        matrix.outputs().len() as u64,
+        LATEST_GAS_FEATURE_VERSION + 1
+    );
+    let (_, latest_output) = matrix.outputs().last().unwrap().result.as_ref().unwrap();
+    assert_eq!(
+        latest_output.status(),
+        &TransactionStatus::Keep(ExecutionStatus::Success)
+    );
+
+    // Execution under the same version is deterministic.
+    let matrix = GasFeatureMatrix::run(&txn, state_view, [
+        LATEST_GAS_FEATURE_VERSION,
+        LATEST_GAS_FEATURE_VERSION,
+    ]);
+    assert!(matrix.divergences().is_empty(), "{}", matrix.report());
+}
@@ -0,0 +1,51 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::MoveHarness;
+use aptos_cached_packages::aptos_stdlib;
+use aptos_types::{
+    account_address::AccountAddress,
+    account_config::CORE_CODE_ADDRESS,
+    on_chain_config::{GasScheduleV2, OnChainConfig},
+    transaction::{ExecutionStatus, TransactionStatus},
+};
+use aptos_vm::AptosVM;
+
+#[test]
+fn simulate_with_gas_schedule_override() {
+    let mut h = MoveHarness::new();
+    let sender = h.new_account_at(AccountAddress::from_hex_literal("0xbeef").unwrap());
+    let receiver = h.new_account_at(AccountAddress::from_hex_literal("0xcafe").unwrap());
+    let txn = h.create_transaction_payload(
+        &sender,
+        aptos_stdlib::aptos_coin_transfer(*receiver.address(), 1000),
+    );
+
+    let mut gas_schedule: GasScheduleV2 = h
+        .read_resource(&CORE_CODE_ADDRESS, GasScheduleV2::struct_tag())
+        .unwrap();
+    let on_chain_gas_schedule = gas_schedule.clone();
+    for (key, value) in gas_schedule.entries.iter_mut() {
+        if key == "txn.min_transaction_gas_units" {
+            *value *= 10;
+        }
+    }
+
+    let state_view = h.executor.data_store();
+    let (_, output) = AptosVM::simulate_signed_transaction(&txn, state_view);
+    let (_, output_with_override) =
+        AptosVM::simulate_signed_transaction_with_gas_override(&txn, state_view, gas_schedule);
+    for output in [&output, &output_with_override] {
+        assert_eq!(
+            output.status(),
+            &TransactionStatus::Keep(ExecutionStatus::Success)
+        );
+    }
+    assert!(output_with_override.gas_used() > output.gas_used());
+
+    // The on-chain gas schedule is untouched.
+    let gas_schedule: GasScheduleV2 = h
+        .read_resource(&CORE_CODE_ADDRESS, GasScheduleV2::struct_tag())
+        .unwrap();
+    assert_eq!(gas_schedule, on_chain_gas_schedule);
+}
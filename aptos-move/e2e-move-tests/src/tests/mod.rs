@@ -5,6 +5,7 @@ mod access_path_test;
 mod aggregator;
 mod aggregator_v2;
 mod attributes;
+mod batched_payload;
 mod chain_id;
 mod code_publishing;
 mod common;
@@ -13,6 +14,8 @@ mod error_map;
 mod fee_payer;
 mod fungible_asset;
 mod gas;
+mod gas_feature_matrix;
+mod gas_schedule_override;
 mod generate_upgrade_script;
 mod governance_updates;
 mod infinite_loop;
@@ -29,6 +32,7 @@ mod nft_dao;
 mod offer_rotation_capability;
 mod offer_signer_capability;
 mod per_category_gas_limits;
+mod randomness;
 mod resource_groups;
 mod rotate_auth_key;
 mod scripts;
@@ -43,4 +47,5 @@ mod token_objects;
 mod transaction_fee;
 mod type_too_large;
 mod vector_numeric_address;
+mod view_function_limits;
 mod vote;
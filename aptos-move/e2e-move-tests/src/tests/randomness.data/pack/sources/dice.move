@@ -0,0 +1,24 @@
+module 0xcafe::dice {
+    use std::signer;
+    use aptos_framework::randomness;
+
+    struct DiceRoll has drop, key {
+        value: u64,
+    }
+
+    entry fun roll(account: &signer) acquires DiceRoll {
+        roll_internal(account)
+    }
+
+    public entry fun roll_public(account: &signer) acquires DiceRoll {
+        roll_internal(account)
+    }
+
+    fun roll_internal(account: &signer) acquires DiceRoll {
+        let value = randomness::u64_range(1, 7);
+        if (exists<DiceRoll>(signer::address_of(account))) {
+            move_from<DiceRoll>(signer::address_of(account));
+        };
+        move_to(account, DiceRoll { value });
+    }
+}
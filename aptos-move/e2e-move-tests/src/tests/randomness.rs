@@ -0,0 +1,128 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{assert_abort, assert_success, tests::common, MoveHarness};
+use aptos_language_e2e_tests::account::Account;
+use aptos_types::{
+    account_address::AccountAddress,
+    on_chain_config::FeatureFlag,
+    transaction::{EntryFunction, TransactionPayload},
+};
+use move_core_types::{
+    identifier::Identifier, language_storage::ModuleId, parser::parse_struct_tag,
+};
+use serde::{Deserialize, Serialize};
+
+/// `error::invalid_state(ERANDOMNESS_NOT_AVAILABLE)`
+const ERANDOMNESS_NOT_AVAILABLE: u64 = 0x30001;
+/// `error::invalid_state(EAPI_USE_IS_BIASABLE)`
+const EAPI_USE_IS_BIASABLE: u64 = 0x30004;
+
+#[derive(Deserialize, Serialize)]
+struct PerBlockRandomness {
+    epoch: u64,
+    round: u64,
+    seed: Option<Vec<u8>>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct DiceRoll {
+    value: u64,
+}
+
+fn setup(h: &mut MoveHarness) -> Account {
+    let account = h.new_account_at(AccountAddress::from_hex_literal("0xcafe").unwrap());
+    assert_success!(
+        h.publish_package_cache_building(&account, &common::test_dir_path("randomness.data/pack"))
+    );
+    account
+}
+
+fn set_seed(h: &mut MoveHarness, seed: Option<Vec<u8>>) {
+    h.set_resource(
+        AccountAddress::ONE,
+        parse_struct_tag("0x1::randomness::PerBlockRandomness").unwrap(),
+        &PerBlockRandomness {
+            epoch: 1,
+            round: 1,
+            seed,
+        },
+    );
+}
+
+fn roll_payload(function: &str) -> TransactionPayload {
+    TransactionPayload::EntryFunction(EntryFunction::new(
+        ModuleId::new(
+            AccountAddress::from_hex_literal("0xcafe").unwrap(),
+            Identifier::new("dice").unwrap(),
+        ),
+        Identifier::new(function).unwrap(),
+        vec![],
+        vec![],
+    ))
+}
+
+fn read_roll(h: &MoveHarness, account: &Account) -> u64 {
+    h.read_resource::<DiceRoll>(
+        account.address(),
+        parse_struct_tag("0xcafe::dice::DiceRoll").unwrap(),
+    )
+    .unwrap()
+    .value
+}
+
+#[test]
+fn test_private_entry_function_consumes_randomness() {
+    let mut h = MoveHarness::new();
+    let account = setup(&mut h);
+    set_seed(&mut h, Some(vec![7; 32]));
+
+    for _ in 0..10 {
+        assert_success!(h.run_transaction_payload(&account, roll_payload("roll")));
+        let value = read_roll(&h, &account);
+        assert!((1..7).contains(&value));
+    }
+}
+
+#[test]
+fn test_randomness_not_available_without_seed() {
+    let mut h = MoveHarness::new();
+    let account = setup(&mut h);
+    set_seed(&mut h, None);
+
+    assert_abort!(
+        h.run_transaction_payload(&account, roll_payload("roll")),
+        ERANDOMNESS_NOT_AVAILABLE
+    );
+}
+
+#[test]
+fn test_public_entry_function_cannot_consume_randomness() {
+    let mut h = MoveHarness::new();
+    let account = setup(&mut h);
+    set_seed(&mut h, Some(vec![7; 32]));
+
+    // A public entry function can also be called by another module, which could test the
+    // outcome and abort.
+    assert_abort!(
+        h.run_transaction_payload(&account, roll_payload("roll_public")),
+        EAPI_USE_IS_BIASABLE
+    );
+}
+
+#[test]
+fn test_batched_payload_cannot_consume_randomness() {
+    let mut h = MoveHarness::new_with_features(vec![FeatureFlag::BATCHED_ENTRY_FUNCTIONS], vec![]);
+    let account = setup(&mut h);
+    set_seed(&mut h, Some(vec![7; 32]));
+
+    // A later call of the batch could test the outcome of the first one and abort.
+    let payload = TransactionPayload::Batched(vec![
+        roll_payload("roll").into_entry_function(),
+        roll_payload("roll").into_entry_function(),
+    ]);
+    assert_abort!(
+        h.run_transaction_payload(&account, payload),
+        EAPI_USE_IS_BIASABLE
+    );
+}
@@ -0,0 +1,69 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::MoveHarness;
+use aptos_types::utility_coin::APTOS_COIN_TYPE;
+use aptos_vm::view_function::{ViewFunctionError, ViewFunctionLimits};
+use move_core_types::{account_address::AccountAddress, ident_str, language_storage::ModuleId};
+
+fn coin_name(
+    h: &mut MoveHarness,
+    limits: ViewFunctionLimits,
+) -> Result<Vec<Vec<u8>>, ViewFunctionError> {
+    h.executor.execute_view_function_with_limits(
+        ModuleId::new(AccountAddress::ONE, ident_str!("coin").to_owned()),
+        ident_str!("name").to_owned(),
+        vec![APTOS_COIN_TYPE.clone()],
+        vec![],
+        limits,
+    )
+}
+
+#[test]
+fn test_view_function_limits() {
+    let mut h = MoveHarness::new();
+    let limits = ViewFunctionLimits {
+        max_gas: 2_000_000,
+        max_stack_depth: 1024,
+        max_returned_bytes: 1024,
+    };
+
+    let name = coin_name(&mut h, limits).unwrap();
+    assert_eq!(
+        bcs::from_bytes::<String>(&name[0]).unwrap(),
+        "Aptos Coin".to_string()
+    );
+
+    assert!(matches!(
+        coin_name(&mut h, ViewFunctionLimits {
+            max_gas: 1,
+            ..limits
+        }),
+        Err(ViewFunctionError::OutOfGas { max_gas: 1 })
+    ));
+
+    // `coin::name` calls `coin_address`, which calls the `type_info::type_of` native.
+    assert!(coin_name(&mut h, ViewFunctionLimits {
+        max_stack_depth: 3,
+        ..limits
+    })
+    .is_ok());
+    assert!(matches!(
+        coin_name(&mut h, ViewFunctionLimits {
+            max_stack_depth: 2,
+            ..limits
+        }),
+        Err(ViewFunctionError::MaxStackDepthExceeded { max_stack_depth: 2 })
+    ));
+
+    assert!(matches!(
+        coin_name(&mut h, ViewFunctionLimits {
+            max_returned_bytes: 4,
+            ..limits
+        }),
+        Err(ViewFunctionError::MaxReturnedBytesExceeded {
+            returned_bytes: 11,
+            max_returned_bytes: 4,
+        })
+    ));
+}
@@ -37,7 +37,8 @@ use aptos_types::{
     chain_id::ChainId,
     contract_event::ContractEvent,
     on_chain_config::{
-        Features, OnChainConfig, TimedFeatureOverride, TimedFeaturesBuilder, ValidatorSet, Version,
+        BlockExecutionLimit, Features, OnChainConfig, TimedFeatureOverride, TimedFeaturesBuilder,
+        ValidatorSet, Version,
     },
     state_store::{state_key::StateKey, state_value::StateValue},
     transaction::{
@@ -54,6 +55,7 @@ use aptos_vm::{
     block_executor::{AptosTransactionOutput, BlockAptosVM},
     data_cache::AsMoveResolver,
     move_vm_ext::{MoveVmExt, SessionId},
+    view_function::{ViewFunctionError, ViewFunctionLimits},
     AptosVM, VMExecutor, VMValidator,
 };
 use aptos_vm_genesis::{generate_genesis_change_set_for_testing_with_count, GenesisOptions};
@@ -471,6 +473,7 @@ impl FakeExecutor {
             txn_block,
             &self.data_store,
             usize::min(4, num_cpus::get()),
+            BlockExecutionLimit::no_limit(),
             None,
             None,
         )
@@ -507,7 +510,7 @@ impl FakeExecutor {
             Some(AptosVM::execute_block(
                 &sig_verified_block,
                 &self.data_store,
-                None,
+                BlockExecutionLimit::no_limit(),
             ))
         } else {
             None
@@ -619,6 +622,7 @@ impl FakeExecutor {
                     ),
                     TransactionPayload::ModuleBundle(..) => unreachable!("not supported"),
                     TransactionPayload::Multisig(..) => unimplemented!("not supported yet"),
+                    TransactionPayload::Batched(..) => unimplemented!("not supported yet"),
                 };
                 Ok(gas_profiler)
             },
@@ -1034,13 +1038,31 @@ impl FakeExecutor {
         arguments: Vec<Vec<u8>>,
     ) -> Result<Vec<Vec<u8>>, Error> {
         // No gas limit
+        self.execute_view_function_with_limits(
+            module_id,
+            func_name,
+            type_args,
+            arguments,
+            ViewFunctionLimits::with_max_gas(u64::MAX),
+        )
+        .map_err(Error::from)
+    }
+
+    pub fn execute_view_function_with_limits(
+        &mut self,
+        module_id: ModuleId,
+        func_name: Identifier,
+        type_args: Vec<TypeTag>,
+        arguments: Vec<Vec<u8>>,
+        limits: ViewFunctionLimits,
+    ) -> Result<Vec<Vec<u8>>, ViewFunctionError> {
         AptosVM::execute_view_function(
             self.get_state_view(),
             module_id,
             func_name,
             type_args,
             arguments,
-            u64::MAX,
+            limits,
         )
     }
 }
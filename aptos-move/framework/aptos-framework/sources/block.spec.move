@@ -32,6 +32,29 @@ spec aptos_framework::block {
         aborts_if false;
     }
 
+    spec block_prologue_ext {
+        use aptos_framework::chain_status;
+        use aptos_framework::coin::CoinInfo;
+        use aptos_framework::aptos_coin::AptosCoin;
+        use aptos_framework::transaction_fee;
+        use aptos_framework::staking_config;
+
+        pragma verify_duration_estimate = 120;
+
+        requires chain_status::is_operating();
+        requires system_addresses::is_vm(vm);
+        requires proposer == @vm_reserved || stake::spec_is_current_epoch_validator(proposer);
+        requires timestamp >= reconfiguration::last_reconfiguration_time();
+        requires (proposer == @vm_reserved) ==> (timestamp::spec_now_microseconds() == timestamp);
+        requires (proposer != @vm_reserved) ==> (timestamp::spec_now_microseconds() < timestamp);
+        requires exists<stake::ValidatorFees>(@aptos_framework);
+        requires exists<CoinInfo<AptosCoin>>(@aptos_framework);
+        include transaction_fee::RequiresCollectedFeesPerValueLeqBlockAptosSupply;
+        include staking_config::StakingRewardsConfigRequirement;
+
+        aborts_if false;
+    }
+
     spec emit_genesis_block_event {
         use aptos_framework::chain_status;
 
@@ -0,0 +1,294 @@
+/// This module provides access to secure randomness generated by the Aptos validators.
+///
+/// Every block carries a seed which is derived from a threshold signature of the validators on the
+/// block's epoch and round, using the key shares dealt by the distributed key generation (DKG) of the
+/// epoch. Since a quorum of validators is needed to compute the seed, no single validator can predict
+/// or bias it before the block is ordered.
+///
+/// Validators only produce seeds for the blocks of an epoch when the `RANDOMNESS` feature is enabled
+/// and the `DKGState` published on chain is for that epoch. Both are on-chain state, so all validators
+/// agree on whether blocks carry a seed. The API aborts if randomness is not available for the
+/// current block.
+///
+/// Code which can observe the outcome of a random draw could abort whenever the outcome is not in its
+/// favor, and retry until it is (a so-called test-and-abort attack). To prevent this, the API can only
+/// be used by transactions whose payload is a single call to a private (or `public(friend)`) entry
+/// function. No other code can call such a function and inspect what it did with the randomness.
+/// The API aborts with `EAPI_USE_IS_BIASABLE` when called from scripts, public functions called by
+/// other modules, view functions, or batched payloads. Entry functions consuming randomness should
+/// hence not be `public`, and must not let an unfavorable outcome cost less gas than a favorable one.
+module aptos_framework::randomness {
+    use std::error;
+    use std::hash;
+    use std::option::{Self, Option};
+    use std::vector;
+
+    use aptos_framework::system_addresses;
+    use aptos_framework::transaction_context;
+
+    friend aptos_framework::block;
+
+    const DST: vector<u8> = b"APTOS_RANDOMNESS";
+
+    /// Randomness is not available for the current block.
+    const ERANDOMNESS_NOT_AVAILABLE: u64 = 1;
+    /// The range to sample from is empty.
+    const EEMPTY_RANGE: u64 = 2;
+    /// The DKG threshold is zero or exceeds the number of public shares.
+    const EINVALID_THRESHOLD: u64 = 3;
+    /// Randomness is requested from a context where the caller could abort on unfavorable outcomes.
+    const EAPI_USE_IS_BIASABLE: u64 = 4;
+
+    /// 32-byte randomness seed unique to every block.
+    struct PerBlockRandomness has drop, key {
+        epoch: u64,
+        round: u64,
+        seed: Option<vector<u8>>,
+    }
+
+    /// Public key material dealt by the DKG of `epoch`, read by the validators at the start of every
+    /// epoch. `public_shares` is indexed by the validator index in the validator set of the epoch.
+    struct DKGState has drop, key {
+        epoch: u64,
+        threshold: u64,
+        public_shares: vector<vector<u8>>,
+    }
+
+    /// Called in genesis, or by governance on chains created before randomness was introduced.
+    public fun initialize(framework: &signer) {
+        system_addresses::assert_aptos_framework(framework);
+        if (!exists<PerBlockRandomness>(@aptos_framework)) {
+            move_to(framework, PerBlockRandomness {
+                epoch: 0,
+                round: 0,
+                seed: option::none(),
+            });
+        }
+    }
+
+    /// Publishes the result of the DKG of `epoch`. Called by governance; takes effect at the start of
+    /// `epoch`, validators ignore the state during any other epoch.
+    public fun set_dkg_state(
+        framework: &signer,
+        epoch: u64,
+        threshold: u64,
+        public_shares: vector<vector<u8>>
+    ) acquires DKGState {
+        system_addresses::assert_aptos_framework(framework);
+        assert!(
+            threshold > 0 && threshold <= vector::length(&public_shares),
+            error::invalid_argument(EINVALID_THRESHOLD)
+        );
+        if (exists<DKGState>(@aptos_framework)) {
+            let state = borrow_global_mut<DKGState>(@aptos_framework);
+            state.epoch = epoch;
+            state.threshold = threshold;
+            state.public_shares = public_shares;
+        } else {
+            move_to(framework, DKGState { epoch, threshold, public_shares });
+        }
+    }
+
+    /// Invoked in the block prologues to update the seed of the block.
+    public(friend) fun on_new_block(
+        vm: &signer,
+        epoch: u64,
+        round: u64,
+        seed_for_new_block: Option<vector<u8>>
+    ) acquires PerBlockRandomness {
+        system_addresses::assert_vm(vm);
+        if (exists<PerBlockRandomness>(@aptos_framework)) {
+            let randomness = borrow_global_mut<PerBlockRandomness>(@aptos_framework);
+            randomness.epoch = epoch;
+            randomness.round = round;
+            randomness.seed = seed_for_new_block;
+        }
+    }
+
+    /// Generate the next 32 random bytes. Repeated calls will yield different results, within the
+    /// same transaction as well as across transactions of the same block.
+    fun next_32_bytes(): vector<u8> acquires PerBlockRandomness {
+        assert!(is_unbiasable(), error::invalid_state(EAPI_USE_IS_BIASABLE));
+        assert!(exists<PerBlockRandomness>(@aptos_framework), error::invalid_state(ERANDOMNESS_NOT_AVAILABLE));
+        let randomness = borrow_global<PerBlockRandomness>(@aptos_framework);
+        assert!(option::is_some(&randomness.seed), error::invalid_state(ERANDOMNESS_NOT_AVAILABLE));
+
+        let input = DST;
+        vector::append(&mut input, *option::borrow(&randomness.seed));
+        vector::append(&mut input, transaction_context::get_txn_hash());
+        vector::append(&mut input, fetch_and_increment_txn_counter());
+        hash::sha3_256(input)
+    }
+
+    /// Generates a sequence of `n` uniformly random bytes.
+    public fun bytes(n: u64): vector<u8> acquires PerBlockRandomness {
+        let v = vector[];
+        let c = 0;
+        while (c < n) {
+            let blob = next_32_bytes();
+            vector::append(&mut v, blob);
+            c = c + 32;
+        };
+
+        if (c > n) {
+            vector::trim(&mut v, n);
+        };
+
+        v
+    }
+
+    /// Generates an u8 uniformly at random.
+    public fun u8_integer(): u8 acquires PerBlockRandomness {
+        let raw = next_32_bytes();
+        vector::pop_back(&mut raw)
+    }
+
+    /// Generates an u64 uniformly at random.
+    public fun u64_integer(): u64 acquires PerBlockRandomness {
+        let raw = next_32_bytes();
+        let i = 0;
+        let ret: u64 = 0;
+        while (i < 8) {
+            ret = ret * 256 + (vector::pop_back(&mut raw) as u64);
+            i = i + 1;
+        };
+        ret
+    }
+
+    /// Generates an u128 uniformly at random.
+    public fun u128_integer(): u128 acquires PerBlockRandomness {
+        let raw = next_32_bytes();
+        let i = 0;
+        let ret: u128 = 0;
+        while (i < 16) {
+            ret = ret * 256 + (vector::pop_back(&mut raw) as u128);
+            i = i + 1;
+        };
+        ret
+    }
+
+    /// Generates an u256 uniformly at random.
+    public fun u256_integer(): u256 acquires PerBlockRandomness {
+        let raw = next_32_bytes();
+        let i = 0;
+        let ret: u256 = 0;
+        while (i < 32) {
+            ret = ret * 256 + (vector::pop_back(&mut raw) as u256);
+            i = i + 1;
+        };
+        ret
+    }
+
+    /// Generates a number $n \in [min_incl, max_excl)$ uniformly at random.
+    ///
+    /// NOTE: The uniformity is not perfect, but it can be proved that the bias is negligible.
+    public fun u64_range(min_incl: u64, max_excl: u64): u64 acquires PerBlockRandomness {
+        assert!(min_incl < max_excl, error::invalid_argument(EEMPTY_RANGE));
+        let range = ((max_excl - min_incl) as u256);
+        let sample = ((u256_integer() % range) as u64);
+        min_incl + sample
+    }
+
+    /// Generate a permutation of `[0, 1, ..., n-1]` uniformly at random.
+    public fun permutation(n: u64): vector<u64> acquires PerBlockRandomness {
+        let values = vector[];
+
+        // Initialize into [0, 1, ..., n-1].
+        let i = 0;
+        while (i < n) {
+            vector::push_back(&mut values, i);
+            i = i + 1;
+        };
+
+        // Shuffle with Fisher-Yates.
+        let tail = n;
+        while (tail > 1) {
+            let pop_position = u64_range(0, tail);
+            vector::swap(&mut values, pop_position, tail - 1);
+            tail = tail - 1;
+        };
+
+        values
+    }
+
+    /// Returns the number of times randomness was requested in the current transaction, as
+    /// little-endian bytes, and increments it.
+    native fun fetch_and_increment_txn_counter(): vector<u8>;
+
+    /// Returns true if the current transaction is a single call to a private (or friend) entry
+    /// function, i.e. nothing can observe the randomness it consumes and abort on the outcome.
+    native fun is_unbiasable(): bool;
+
+    #[test_only]
+    public fun initialize_for_testing(framework: &signer) acquires PerBlockRandomness {
+        initialize(framework);
+        set_seed(x"0000000000000000000000000000000000000000000000000000000000000000");
+    }
+
+    #[test_only]
+    public fun set_seed(seed: vector<u8>) acquires PerBlockRandomness {
+        assert!(vector::length(&seed) == 32, 0);
+        let randomness = borrow_global_mut<PerBlockRandomness>(@aptos_framework);
+        randomness.seed = option::some(seed);
+    }
+
+    #[test(fx = @aptos_framework)]
+    fun test_bytes_are_fresh(fx: signer) acquires PerBlockRandomness {
+        initialize_for_testing(&fx);
+
+        let first = bytes(40);
+        let second = bytes(40);
+        assert!(vector::length(&first) == 40, 0);
+        assert!(vector::length(&second) == 40, 1);
+        assert!(first != second, 2);
+    }
+
+    #[test(fx = @aptos_framework)]
+    fun test_u64_range(fx: signer) acquires PerBlockRandomness {
+        initialize_for_testing(&fx);
+
+        let i = 0;
+        while (i < 100) {
+            let sample = u64_range(10, 20);
+            assert!(sample >= 10 && sample < 20, 0);
+            i = i + 1;
+        };
+    }
+
+    #[test(fx = @aptos_framework)]
+    fun test_permutation(fx: signer) acquires PerBlockRandomness {
+        initialize_for_testing(&fx);
+
+        let v = permutation(10);
+        let seen = vector[false, false, false, false, false, false, false, false, false, false];
+        let i = 0;
+        while (i < 10) {
+            let x = *vector::borrow(&v, i);
+            assert!(!*vector::borrow(&seen, x), 0);
+            *vector::borrow_mut(&mut seen, x) = true;
+            i = i + 1;
+        };
+    }
+
+    #[test(fx = @aptos_framework)]
+    fun test_set_dkg_state(fx: signer) acquires DKGState {
+        set_dkg_state(&fx, 1, 2, vector[x"01", x"02", x"03"]);
+        set_dkg_state(&fx, 2, 1, vector[x"04"]);
+        let state = borrow_global<DKGState>(@aptos_framework);
+        assert!(state.epoch == 2 && state.threshold == 1, 0);
+        assert!(state.public_shares == vector[x"04"], 1);
+    }
+
+    #[test(fx = @aptos_framework)]
+    #[expected_failure(abort_code = 0x10003, location = Self)]
+    fun test_set_dkg_state_threshold_too_high(fx: signer) acquires DKGState {
+        set_dkg_state(&fx, 1, 2, vector[x"01"]);
+    }
+
+    #[test(fx = @aptos_framework)]
+    #[expected_failure(abort_code = 0x30001, location = Self)]
+    fun test_randomness_not_available(fx: signer) acquires PerBlockRandomness {
+        initialize(&fx);
+        u64_integer();
+    }
+}
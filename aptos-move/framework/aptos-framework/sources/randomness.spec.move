@@ -0,0 +1,25 @@
+spec aptos_framework::randomness {
+    spec module {
+        pragma verify = false;
+    }
+
+    spec fetch_and_increment_txn_counter(): vector<u8> {
+        pragma opaque;
+        aborts_if [abstract] false;
+        ensures [abstract] result == spec_fetch_and_increment_txn_counter();
+    }
+    spec fun spec_fetch_and_increment_txn_counter(): vector<u8>;
+
+    spec is_unbiasable(): bool {
+        pragma opaque;
+        aborts_if [abstract] false;
+        ensures [abstract] result == spec_is_unbiasable();
+    }
+    spec fun spec_is_unbiasable(): bool;
+
+    spec on_new_block(vm: &signer, epoch: u64, round: u64, seed_for_new_block: Option<vector<u8>>) {
+        use std::signer;
+        aborts_if signer::address_of(vm) != @vm_reserved;
+        ensures exists<PerBlockRandomness>(@aptos_framework) ==> global<PerBlockRandomness>(@aptos_framework).seed == seed_for_new_block;
+    }
+}
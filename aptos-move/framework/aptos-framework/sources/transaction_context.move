@@ -2,6 +2,8 @@ module aptos_framework::transaction_context {
 
     use std::features;
 
+    friend aptos_framework::randomness;
+
     /// AUID feature is not supported.
     const EAUID_NOT_SUPPORTED: u64 = 1;
 
@@ -12,7 +14,7 @@ module aptos_framework::transaction_context {
     }
 
     /// Return the transaction hash of the current transaction.
-    native fun get_txn_hash(): vector<u8>;
+    public(friend) native fun get_txn_hash(): vector<u8>;
 
     /// Return the transaction hash of the current transaction.
     /// Internally calls the private function `get_txn_hash`.
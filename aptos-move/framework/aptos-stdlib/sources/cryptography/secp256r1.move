@@ -0,0 +1,98 @@
+/// This module implements ECDSA signatures based on the prime-order secp256r1 (a.k.a., NIST P-256) elliptic curve.
+/// These are the signatures produced by WebAuthn authenticators (e.g., passkeys) and most secure enclaves.
+
+module aptos_std::secp256r1 {
+    /// An error occurred while deserializing, for example due to wrong input size.
+    const E_DESERIALIZE: u64 = 1;
+
+    /// The size of a secp256r1-based ECDSA public key, in bytes.
+    const RAW_PUBLIC_KEY_NUM_BYTES: u64 = 64;
+
+    /// The size of a secp256r1-based ECDSA signature, in bytes.
+    const SIGNATURE_NUM_BYTES: u64 = 64;
+
+    /// A 64-byte ECDSA public key, i.e., the concatenation of the affine coordinates x and y.
+    struct ECDSARawPublicKey has copy, drop, store {
+        bytes: vector<u8>
+    }
+
+    /// A 64-byte ECDSA signature, i.e., the concatenation of r and s.
+    struct ECDSASignature has copy, drop, store {
+        bytes: vector<u8>
+    }
+
+    /// Constructs an ECDSASignature struct from the given 64 bytes.
+    public fun ecdsa_signature_from_bytes(bytes: vector<u8>): ECDSASignature {
+        assert!(std::vector::length(&bytes) == SIGNATURE_NUM_BYTES, std::error::invalid_argument(E_DESERIALIZE));
+        ECDSASignature { bytes }
+    }
+
+    /// Constructs an ECDSARawPublicKey struct, given a 64-byte raw representation.
+    public fun ecdsa_raw_public_key_from_64_bytes(bytes: vector<u8>): ECDSARawPublicKey {
+        assert!(std::vector::length(&bytes) == RAW_PUBLIC_KEY_NUM_BYTES, std::error::invalid_argument(E_DESERIALIZE));
+        ECDSARawPublicKey { bytes }
+    }
+
+    /// Serializes an ECDSARawPublicKey struct to 64-bytes.
+    public fun ecdsa_raw_public_key_to_bytes(pk: &ECDSARawPublicKey): vector<u8> {
+        pk.bytes
+    }
+
+    /// Serializes an ECDSASignature struct to 64-bytes.
+    public fun ecdsa_signature_to_bytes(sig: &ECDSASignature): vector<u8> {
+        sig.bytes
+    }
+
+    /// Returns `true` if `signature` verifies on `message` under `public_key`. The message is hashed with SHA2-256
+    /// before verification.
+    ///
+    /// Non-canonical signatures, i.e., signatures whose `s` is larger than half of the group order, are rejected, as
+    /// are public keys which are not points on the curve.
+    public fun ecdsa_verify(
+        message: vector<u8>,
+        public_key: &ECDSARawPublicKey,
+        signature: &ECDSASignature,
+    ): bool {
+        ecdsa_verify_internal(message, public_key.bytes, signature.bytes)
+    }
+
+    //
+    // Native functions
+    //
+
+    /// Returns `true` if `signature` verifies on `message` under `public_key` and `false` otherwise.
+    native fun ecdsa_verify_internal(
+        message: vector<u8>,
+        public_key: vector<u8>,
+        signature: vector<u8>
+    ): bool;
+
+    //
+    // Tests
+    //
+
+    #[test]
+    /// Test on a valid secp256r1 ECDSA signature created using sk = x"0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef"
+    fun test_ecdsa_verify() {
+        let pk = ecdsa_raw_public_key_from_64_bytes(x"d8cd12ea5c67f2f8a00c1124893edcfa6754c4d6cede6be13bdf2295c810a97fa5a89d2d2a360c0ca9a4d6c7c9ed4b28d3e199d6627f2e696d689c310a5b0f48");
+        let sig = ecdsa_signature_from_bytes(x"38e2fa521b45117114c65d56465094ca3746d751f707303fce555dcf6b1d1008163cf690eb8824f7de3cff609ff88e56eebbede310b459916e0cbf2b4d9d84ef");
+        assert!(ecdsa_verify(b"test aptos secp256r1", &pk, &sig), 1);
+
+        // Different message
+        assert!(!ecdsa_verify(b"test aptos secp256k1", &pk, &sig), 2);
+
+        // Flipped bits; signature becomes invalid
+        let sig = ecdsa_signature_from_bytes(x"f8e2fa521b45117114c65d56465094ca3746d751f707303fce555dcf6b1d1008163cf690eb8824f7de3cff609ff88e56eebbede310b459916e0cbf2b4d9d84ef");
+        assert!(!ecdsa_verify(b"test aptos secp256r1", &pk, &sig), 3);
+
+        // Not a point on the curve
+        let pk = ecdsa_raw_public_key_from_64_bytes(x"d8cd12ea5c67f2f8a00c1124893edcfa6754c4d6cede6be13bdf2295c810a97fa5a89d2d2a360c0ca9a4d6c7c9ed4b28d3e199d6627f2e696d689c310a5b0f49");
+        assert!(!ecdsa_verify(b"test aptos secp256r1", &pk, &sig), 4);
+    }
+
+    #[test]
+    #[expected_failure(abort_code = 0x10001, location = Self)]
+    fun test_ecdsa_signature_wrong_size() {
+        ecdsa_signature_from_bytes(x"0102");
+    }
+}
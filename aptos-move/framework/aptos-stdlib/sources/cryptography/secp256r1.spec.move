@@ -0,0 +1,42 @@
+spec aptos_std::secp256r1 {
+    spec ecdsa_signature_from_bytes(bytes: vector<u8>): ECDSASignature {
+        aborts_if len(bytes) != SIGNATURE_NUM_BYTES;
+        ensures result == ECDSASignature { bytes };
+    }
+
+    spec ecdsa_raw_public_key_from_64_bytes(bytes: vector<u8>): ECDSARawPublicKey {
+        aborts_if len(bytes) != RAW_PUBLIC_KEY_NUM_BYTES;
+        ensures result == ECDSARawPublicKey { bytes };
+    }
+
+    spec ecdsa_raw_public_key_to_bytes(pk: &ECDSARawPublicKey): vector<u8> {
+        aborts_if false;
+        ensures result == pk.bytes;
+    }
+
+    spec ecdsa_signature_to_bytes(sig: &ECDSASignature): vector<u8> {
+        aborts_if false;
+        ensures result == sig.bytes;
+    }
+
+    spec ecdsa_verify(
+        message: vector<u8>,
+        public_key: &ECDSARawPublicKey,
+        signature: &ECDSASignature,
+    ): bool {
+        aborts_if false;
+        ensures result == spec_ecdsa_verify_internal(message, public_key.bytes, signature.bytes);
+    }
+
+    spec ecdsa_verify_internal(
+        message: vector<u8>,
+        public_key: vector<u8>,
+        signature: vector<u8>
+    ): bool {
+        pragma opaque;
+        aborts_if false;
+        ensures result == spec_ecdsa_verify_internal(message, public_key, signature);
+    }
+
+    spec fun spec_ecdsa_verify_internal(message: vector<u8>, public_key: vector<u8>, signature: vector<u8>): bool;
+}
@@ -3,9 +3,14 @@
 ///
 /// It implements the Table type which supports individual table items to be represented by
 /// separate global state items. The number of items and a unique handle are tracked on the table
-/// struct itself, while the operations are implemented as native functions. No traversal is provided.
+/// struct itself, while the operations are implemented as native functions. Keys can be enumerated
+/// page by page in the order of the hashes of their serialized form, but only in view functions
+/// evaluated on nodes with the indexer enabled.
 
 module aptos_std::table {
+    use std::option::{Self, Option};
+    use std::vector;
+
     friend aptos_std::table_with_length;
 
     /// Type of tables
@@ -13,6 +18,11 @@ module aptos_std::table {
         handle: address,
     }
 
+    /// Position in an enumeration of a table's keys, returned by `keys_paginated` to continue from.
+    struct IterationHandle has copy, drop, store {
+        key_hash: vector<u8>,
+    }
+
     /// Create a new Table.
     public fun new<K: copy + drop, V: store>(): Table<K, V> {
         Table {
@@ -81,6 +91,50 @@ module aptos_std::table {
         contains_box<K, V, Box<V>>(table, key)
     }
 
+    /// Returns up to `num_keys` keys of `table` following `starting_after` (or from the start if it is
+    /// `none`), and the handle to pass in to get the next page, which is `none` once all keys have
+    /// been returned. Keys are ordered by the hash of their serialized form, so the order is
+    /// deterministic but otherwise arbitrary. A page may hold fewer than `num_keys` keys even if it
+    /// is not the last one, e.g. if keys have been removed. Gas is charged for all `num_keys` keys.
+    /// Only available in view functions, and aborts in transactions: iteration is served by the
+    /// indexer of the node, which only reflects the latest committed version. A view function also
+    /// aborts if the node has no indexer, or if the indexer has moved past the queried version.
+    public fun keys_paginated<K: copy + drop, V>(
+        table: &Table<K, V>,
+        starting_after: Option<IterationHandle>,
+        num_keys: u64,
+    ): (vector<K>, Option<IterationHandle>) {
+        let start = if (option::is_some(&starting_after)) {
+            option::destroy_some(starting_after).key_hash
+        } else {
+            vector[]
+        };
+        let (keys, next) = keys_box<K, V, Box<V>>(table, start, num_keys);
+        let next = if (vector::is_empty(&next)) {
+            option::none()
+        } else {
+            option::some(IterationHandle { key_hash: next })
+        };
+        (keys, next)
+    }
+
+    /// Like `keys_paginated`, but returns copies of the values alongside the keys.
+    public fun values_paginated<K: copy + drop, V: copy>(
+        table: &Table<K, V>,
+        starting_after: Option<IterationHandle>,
+        num_keys: u64,
+    ): (vector<K>, vector<V>, Option<IterationHandle>) {
+        let (keys, next) = keys_paginated(table, starting_after, num_keys);
+        let values = vector[];
+        let i = 0;
+        let len = vector::length(&keys);
+        while (i < len) {
+            vector::push_back(&mut values, *borrow(table, *vector::borrow(&keys, i)));
+            i = i + 1;
+        };
+        (keys, values, next)
+    }
+
     #[test_only]
     /// Testing only: allows to drop a table even if it is not empty.
     public fun drop_unchecked<K: copy + drop, V>(table: Table<K, V>) {
@@ -149,4 +203,10 @@ module aptos_std::table {
     native fun destroy_empty_box<K: copy + drop, V, B>(table: &Table<K, V>);
 
     native fun drop_unchecked_box<K: copy + drop, V, B>(table: Table<K, V>);
+
+    native fun keys_box<K: copy + drop, V, B>(
+        table: &Table<K, V>,
+        start_after: vector<u8>,
+        num_keys: u64
+    ): (vector<K>, vector<u8>);
 }
@@ -56,6 +56,18 @@ spec aptos_std::table {
         pragma intrinsic;
     }
 
+    spec keys_paginated {
+        pragma verify = false;
+    }
+
+    spec values_paginated {
+        pragma verify = false;
+    }
+
+    spec keys_box {
+        pragma opaque;
+    }
+
     // Specification functions for tables
     spec native fun spec_contains<K, V>(t: Table<K, V>, k: K): bool;
     spec native fun spec_remove<K, V>(t: Table<K, V>, k: K): Table<K, V>;
@@ -12,6 +12,7 @@ pub mod debug;
 pub mod event;
 pub mod hash;
 pub mod object;
+pub mod randomness;
 pub mod state_storage;
 pub mod string_utils;
 pub mod transaction_context;
@@ -54,6 +55,7 @@ pub fn all_natives(
     add_natives_from_module!("multi_ed25519", multi_ed25519::make_all(builder));
     add_natives_from_module!("bls12381", cryptography::bls12381::make_all(builder));
     add_natives_from_module!("secp256k1", cryptography::secp256k1::make_all(builder));
+    add_natives_from_module!("secp256r1", cryptography::secp256r1::make_all(builder));
     add_natives_from_module!("aptos_hash", hash::make_all(builder));
     add_natives_from_module!(
         "ristretto255",
@@ -77,6 +79,7 @@ pub fn all_natives(
     add_natives_from_module!("aggregator_factory", aggregator_factory::make_all(builder));
     add_natives_from_module!("aggregator_v2", aggregator_v2::make_all(builder));
     add_natives_from_module!("object", object::make_all(builder));
+    add_natives_from_module!("randomness", randomness::make_all(builder));
     add_natives_from_module!("debug", debug::make_all(builder));
     add_natives_from_module!("string_utils", string_utils::make_all(builder));
 
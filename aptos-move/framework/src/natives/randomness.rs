@@ -0,0 +1,96 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_gas_schedule::gas_params::natives::aptos_framework::*;
+use aptos_native_interface::{
+    RawSafeNative, SafeNativeBuilder, SafeNativeContext, SafeNativeResult,
+};
+use better_any::{Tid, TidAble};
+use move_vm_runtime::native_functions::NativeFunction;
+use move_vm_types::{loaded_data::runtime_types::Type, values::Value};
+use smallvec::{smallvec, SmallVec};
+use std::collections::VecDeque;
+
+/// The native randomness context extension. The seed of the block is stored on chain by the block
+/// prologue, this extension keeps the transaction-local state which makes sure that every call
+/// to the randomness API within the same transaction returns fresh bytes.
+#[derive(Tid, Default)]
+pub struct RandomnessContext {
+    /// Number of times randomness has been requested in this transaction.
+    txn_counter: u64,
+    /// Whether the transaction can consume randomness, i.e. it is a single call to a private
+    /// (or friend) entry function, whose outcome no other code can inspect and abort on.
+    unbiasable: bool,
+}
+
+impl RandomnessContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn mark_unbiasable(&mut self) {
+        self.unbiasable = true;
+    }
+
+    pub fn is_unbiasable(&self) -> bool {
+        self.unbiasable
+    }
+}
+
+/***************************************************************************************************
+ * native fun fetch_and_increment_txn_counter
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+fn native_fetch_and_increment_txn_counter(
+    context: &mut SafeNativeContext,
+    _ty_args: Vec<Type>,
+    _args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    context.charge(RANDOMNESS_FETCH_AND_INC_COUNTER)?;
+
+    let randomness_context = context.extensions_mut().get_mut::<RandomnessContext>();
+    let counter = randomness_context.txn_counter;
+    randomness_context.txn_counter += 1;
+
+    Ok(smallvec![Value::vector_u8(counter.to_le_bytes().to_vec())])
+}
+
+/***************************************************************************************************
+ * native fun is_unbiasable
+ *
+ *   gas cost: base_cost
+ *
+ **************************************************************************************************/
+fn native_is_unbiasable(
+    context: &mut SafeNativeContext,
+    _ty_args: Vec<Type>,
+    _args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    context.charge(RANDOMNESS_IS_UNBIASABLE)?;
+
+    let is_unbiasable = context
+        .extensions()
+        .get::<RandomnessContext>()
+        .is_unbiasable();
+    Ok(smallvec![Value::bool(is_unbiasable)])
+}
+
+/***************************************************************************************************
+ * module
+ *
+ **************************************************************************************************/
+pub fn make_all(
+    builder: &SafeNativeBuilder,
+) -> impl Iterator<Item = (String, NativeFunction)> + '_ {
+    let natives = [
+        (
+            "fetch_and_increment_txn_counter",
+            native_fetch_and_increment_txn_counter as RawSafeNative,
+        ),
+        ("is_unbiasable", native_is_unbiasable),
+    ];
+
+    builder.make_named_natives(natives)
+}
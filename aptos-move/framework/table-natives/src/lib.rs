@@ -18,8 +18,12 @@ use better_any::{Tid, TidAble};
 use bytes::Bytes;
 use move_binary_format::errors::{PartialVMError, PartialVMResult};
 use move_core_types::{
-    account_address::AccountAddress, effects::Op, gas_algebra::NumBytes, identifier::Identifier,
-    value::MoveTypeLayout, vm_status::StatusCode,
+    account_address::AccountAddress,
+    effects::Op,
+    gas_algebra::{NumArgs, NumBytes},
+    identifier::Identifier,
+    value::MoveTypeLayout,
+    vm_status::StatusCode,
 };
 // ===========================================================================================
 // Public Data Structures and Constants
@@ -27,7 +31,7 @@ pub use move_table_extension::{TableHandle, TableInfo, TableResolver};
 use move_vm_runtime::native_functions::NativeFunctionTable;
 use move_vm_types::{
     loaded_data::runtime_types::Type,
-    values::{GlobalValue, Reference, StructRef, Value},
+    values::{GlobalValue, Reference, StructRef, Value, Vector},
 };
 use sha3::{Digest, Sha3_256};
 use smallvec::{smallvec, SmallVec};
@@ -49,13 +53,15 @@ pub struct NativeTableContext<'a> {
 }
 
 // See stdlib/Error.move
-const _ECATEGORY_INVALID_STATE: u8 = 0;
+const ECATEGORY_INVALID_STATE: u8 = 0;
 const ECATEGORY_INVALID_ARGUMENT: u8 = 7;
 
 const ALREADY_EXISTS: u64 = (100 << 8) + ECATEGORY_INVALID_ARGUMENT as u64;
 const NOT_FOUND: u64 = (101 << 8) + ECATEGORY_INVALID_ARGUMENT as u64;
 // Move side raises this
-const _NOT_EMPTY: u64 = (102 << 8) + _ECATEGORY_INVALID_STATE as u64;
+const _NOT_EMPTY: u64 = (102 << 8) + ECATEGORY_INVALID_STATE as u64;
+const ITERATION_NOT_SUPPORTED: u64 = (103 << 8) + ECATEGORY_INVALID_STATE as u64;
+const INVALID_ITERATION_HANDLE: u64 = (104 << 8) + ECATEGORY_INVALID_ARGUMENT as u64;
 
 // ===========================================================================================
 // Private Data Structures and Constants
@@ -266,6 +272,7 @@ pub fn table_natives(
                 ("contains_box", native_contains_box),
                 ("destroy_empty_box", native_destroy_empty_box),
                 ("drop_unchecked_box", native_drop_unchecked_box),
+                ("keys_box", native_keys_box),
             ])
             .map(|(func_name, func)| {
                 (
@@ -512,9 +519,109 @@ fn native_drop_unchecked_box(
     Ok(smallvec![])
 }
 
+/// Returns up to `num_keys` keys of the table in the order of the hashes of their serialized
+/// form, starting after the key whose hash is `start_after` (or from the start if it is empty),
+/// together with the hash to continue from, which is empty once the iteration is complete.
+///
+/// Storage is enumerated through the resolver, and merged with the entries touched by the
+/// current session so that keys added or removed in this session are accounted for. Storage scans
+/// up to `num_keys` entries, including keys that no longer exist, so the per key cost is charged
+/// for all of them upfront.
+fn native_keys_box(
+    context: &mut SafeNativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> SafeNativeResult<SmallVec<[Value; 1]>> {
+    assert_eq!(ty_args.len(), 3);
+    assert_eq!(args.len(), 3);
+
+    context.charge(KEYS_BOX_BASE)?;
+
+    let num_keys = safely_pop_arg!(args, u64) as usize;
+    let start_after = safely_pop_arg!(args, Vec<u8>);
+    let handle = get_table_handle(&safely_pop_arg!(args, StructRef))?;
+
+    context.charge(KEYS_BOX_PER_KEY * NumArgs::new(num_keys as u64))?;
+
+    let start_after: Option<[u8; 32]> = if start_after.is_empty() {
+        None
+    } else {
+        Some(start_after.try_into().map_err(|_| SafeNativeError::Abort {
+            abort_code: INVALID_ITERATION_HANDLE,
+        })?)
+    };
+
+    let table_context = context.extensions().get::<NativeTableContext>();
+    let mut table_data = table_context.table_data.borrow_mut();
+
+    let table = table_data.get_or_create_table(context, handle, &ty_args[0], &ty_args[2])?;
+
+    let (stored_keys, stored_next) = match table_context
+        .resolver
+        .resolve_table_keys(&handle, start_after.as_ref(), num_keys)
+        .map_err(|err| partial_extension_error(format!("remote table resolver failure: {}", err)))?
+    {
+        Some(page) => page,
+        None => {
+            return Err(SafeNativeError::Abort {
+                abort_code: ITERATION_NOT_SUPPORTED,
+            })
+        },
+    };
+    // Entries of this session beyond the last scanned key have to wait for the next page, unless
+    // storage has nothing left.
+    let storage_exhausted = stored_next.is_none();
+
+    let mut keys = BTreeMap::new();
+    for key in stored_keys {
+        if !table.content.contains_key(&key) {
+            keys.insert(key_hash(&key), key);
+        }
+    }
+    for (key, gv) in table.content.iter() {
+        if !gv.exists()? {
+            continue;
+        }
+        let hash = key_hash(key);
+        let after_start = start_after.map_or(true, |start| hash > start);
+        let before_end = storage_exhausted || stored_next.map_or(false, |last| hash <= last);
+        if after_start && before_end {
+            keys.insert(hash, key.clone());
+        }
+    }
+
+    let truncated = keys.len() > num_keys;
+    let keys: Vec<_> = keys.into_iter().take(num_keys).collect();
+    let next = if truncated {
+        keys.last().map(|(hash, _)| *hash).or(start_after)
+    } else {
+        stored_next
+    }
+    .map_or_else(Vec::new, |hash| hash.to_vec());
+
+    let num_bytes: usize = keys.iter().map(|(_, key)| key.len()).sum();
+    let values = keys
+        .iter()
+        .map(|(_, key)| deserialize(&table.key_layout, key))
+        .collect::<PartialVMResult<Vec<_>>>()?;
+
+    drop(table_data);
+
+    context.charge(KEYS_BOX_PER_BYTE_SERIALIZED * NumBytes::new(num_bytes as u64))?;
+
+    Ok(smallvec![
+        Vector::pack(&ty_args[0], values)?,
+        Value::vector_u8(next)
+    ])
+}
+
 // =========================================================================================
 // Helpers
 
+fn key_hash(key: &[u8]) -> [u8; 32] {
+    Sha3_256::digest(key).into()
+}
+
 fn get_table_handle(table: &StructRef) -> PartialVMResult<TableHandle> {
     let handle = table
         .borrow_field(HANDLE_FIELD_INDEX)?
@@ -88,6 +88,9 @@ pub fn default_gas_schedule() -> GasScheduleV2 {
     }
 }
 
+/// Generates a genesis transaction that funds the given accounts, sets up the employee vesting
+/// pools and the validators, without any core resources account. The framework, the chain id and
+/// all on-chain configs are provided by the caller, so this can be used for any network.
 pub fn encode_aptos_mainnet_genesis_transaction(
     accounts: &[AccountBalance],
     employees: &[EmployeePool],
@@ -95,6 +98,10 @@ pub fn encode_aptos_mainnet_genesis_transaction(
     framework: &ReleaseBundle,
     chain_id: ChainId,
     genesis_config: &GenesisConfiguration,
+    consensus_config: &OnChainConsensusConfig,
+    execution_config: &OnChainExecutionConfig,
+    gas_schedule: &GasScheduleV2,
+    features: &[FeatureFlag],
 ) -> Transaction {
     assert!(!genesis_config.is_test, "This is mainnet!");
     validate_genesis_config(genesis_config);
@@ -119,18 +126,15 @@ pub fn encode_aptos_mainnet_genesis_transaction(
     let mut session = move_vm.new_session(&data_cache, SessionId::genesis(id1));
 
     // On-chain genesis process.
-    let consensus_config = OnChainConsensusConfig::default();
-    let execution_config = OnChainExecutionConfig::default_for_genesis();
-    let gas_schedule = default_gas_schedule();
     initialize(
         &mut session,
         chain_id,
         genesis_config,
-        &consensus_config,
-        &execution_config,
-        &gas_schedule,
+        consensus_config,
+        execution_config,
+        gas_schedule,
     );
-    initialize_features(&mut session);
+    initialize_features(&mut session, features);
     initialize_aptos_coin(&mut session);
     initialize_on_chain_governance(&mut session, genesis_config);
     create_accounts(&mut session, accounts);
@@ -183,6 +187,7 @@ pub fn encode_genesis_transaction(
     consensus_config: &OnChainConsensusConfig,
     execution_config: &OnChainExecutionConfig,
     gas_schedule: &GasScheduleV2,
+    features: &[FeatureFlag],
 ) -> Transaction {
     Transaction::GenesisTransaction(WriteSetPayload::Direct(encode_genesis_change_set(
         &aptos_root_key,
@@ -193,6 +198,7 @@ pub fn encode_genesis_transaction(
         consensus_config,
         execution_config,
         gas_schedule,
+        features,
     )))
 }
 
@@ -205,6 +211,7 @@ pub fn encode_genesis_change_set(
     consensus_config: &OnChainConsensusConfig,
     execution_config: &OnChainExecutionConfig,
     gas_schedule: &GasScheduleV2,
+    features: &[FeatureFlag],
 ) -> ChangeSet {
     validate_genesis_config(genesis_config);
 
@@ -236,7 +243,7 @@ pub fn encode_genesis_change_set(
         execution_config,
         gas_schedule,
     );
-    initialize_features(&mut session);
+    initialize_features(&mut session, features);
     if genesis_config.is_test {
         initialize_core_resources_and_aptos_coin(&mut session, core_resources_key);
     } else {
@@ -435,14 +442,12 @@ pub fn default_features() -> Vec<FeatureFlag> {
         FeatureFlag::SPONSORED_AUTOMATIC_ACCOUNT_CREATION,
         FeatureFlag::FEE_PAYER_ACCOUNT_OPTIONAL,
         FeatureFlag::LIMIT_MAX_IDENTIFIER_LENGTH,
+        FeatureFlag::WEBAUTHN_SIGNATURE,
     ]
 }
 
-fn initialize_features(session: &mut SessionExt) {
-    let features: Vec<u64> = default_features()
-        .into_iter()
-        .map(|feature| feature as u64)
-        .collect();
+fn initialize_features(session: &mut SessionExt, features: &[FeatureFlag]) {
+    let features: Vec<u64> = features.iter().map(|feature| *feature as u64).collect();
 
     let mut serialized_values = serialize_values(&vec![MoveValue::Signer(CORE_CODE_ADDRESS)]);
     serialized_values.push(bcs::to_bytes(&features).unwrap());
@@ -823,6 +828,7 @@ pub fn generate_test_genesis(
         &OnChainConsensusConfig::default(),
         &OnChainExecutionConfig::default_for_genesis(),
         &default_gas_schedule(),
+        &default_features(),
     );
     (genesis, test_validators)
 }
@@ -845,6 +851,7 @@ pub fn generate_mainnet_genesis(
         &OnChainConsensusConfig::default(),
         &OnChainExecutionConfig::default_for_genesis(),
         &default_gas_schedule(),
+        &default_features(),
     );
     (genesis, test_validators)
 }
@@ -1112,6 +1119,10 @@ pub fn test_mainnet_end_to_end() {
         aptos_cached_packages::head_release_bundle(),
         ChainId::mainnet(),
         &mainnet_genesis_config(),
+        &OnChainConsensusConfig::default(),
+        &OnChainExecutionConfig::default_for_genesis(),
+        &default_gas_schedule(),
+        &default_features(),
     );
 
     let direct_writeset = if let Transaction::GenesisTransaction(direct_writeset) = transaction {
@@ -1166,3 +1177,87 @@ pub fn test_mainnet_end_to_end() {
     // join_during_genesis = false.
     assert!(!validator_set_addresses.contains(&same_owner_validator_3_pool_address));
 }
+
+#[test]
+pub fn test_mainnet_genesis_with_custom_configs() {
+    use aptos_types::{
+        on_chain_config::{ConsensusConfigV1, OnChainConfig},
+        state_store::state_key::StateKey,
+        write_set::{TransactionWrite, WriteSet},
+    };
+
+    let owner = AccountAddress::from_hex_literal("0x44").unwrap();
+    let operator = AccountAddress::from_hex_literal("0x100").unwrap();
+    let voter = AccountAddress::from_hex_literal("0x200").unwrap();
+    let balance = 10_000_000 * APTOS_COINS_BASE_WITH_DECIMALS;
+    let accounts: Vec<_> = [owner, operator, voter]
+        .into_iter()
+        .map(|account_address| AccountBalance {
+            account_address,
+            balance,
+        })
+        .collect();
+
+    let mut validator = TestValidator::new_test_set(Some(1), Some(balance * 9 / 10))[0]
+        .data
+        .clone();
+    validator.owner_address = owner;
+    validator.operator_address = operator;
+    validator.voter_address = voter;
+    let validators = vec![ValidatorWithCommissionRate {
+        validator,
+        validator_commission_percentage: 10,
+        join_during_genesis: true,
+    }];
+
+    let consensus_config = OnChainConsensusConfig::V1(ConsensusConfigV1 {
+        exclude_round: 20,
+        ..ConsensusConfigV1::default()
+    });
+    let features = vec![
+        FeatureFlag::CODE_DEPENDENCY_CHECK,
+        FeatureFlag::VM_BINARY_FORMAT_V6,
+    ];
+
+    let transaction = encode_aptos_mainnet_genesis_transaction(
+        &accounts,
+        &[],
+        &validators,
+        aptos_cached_packages::head_release_bundle(),
+        ChainId::new(42),
+        &mainnet_genesis_config(),
+        &consensus_config,
+        &OnChainExecutionConfig::default_for_genesis(),
+        &default_gas_schedule(),
+        &features,
+    );
+
+    let changeset = match transaction {
+        Transaction::GenesisTransaction(WriteSetPayload::Direct(changeset)) => changeset,
+        _ => panic!("Invalid GenesisTransaction"),
+    };
+    let WriteSet::V0(writeset) = changeset.write_set();
+    let get_bytes = |state_key: StateKey| {
+        writeset
+            .get(&state_key)
+            .unwrap()
+            .extract_raw_bytes()
+            .unwrap()
+    };
+
+    let bytes = get_bytes(StateKey::access_path(
+        OnChainConsensusConfig::access_path().expect("access path in test"),
+    ));
+    assert_eq!(
+        OnChainConsensusConfig::deserialize_into_config(&bytes).unwrap(),
+        consensus_config
+    );
+
+    let bytes = get_bytes(StateKey::access_path(
+        Features::access_path().expect("access path in test"),
+    ));
+    let on_chain_features: Features = bcs::from_bytes(&bytes).unwrap();
+    for flag in default_features() {
+        assert_eq!(on_chain_features.is_enabled(flag), features.contains(&flag));
+    }
+}
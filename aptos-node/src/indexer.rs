@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_config::config::NodeConfig;
-use aptos_mempool::MempoolClientSender;
+use aptos_mempool::{BackPressureStatus, MempoolClientSender};
 use aptos_storage_interface::DbReader;
 use aptos_types::chain_id::ChainId;
 use std::sync::Arc;
@@ -14,10 +14,17 @@ pub fn bootstrap_indexer(
     chain_id: ChainId,
     aptos_db: Arc<dyn DbReader>,
     mp_client_sender: MempoolClientSender,
+    back_pressure_status: BackPressureStatus,
 ) -> Result<Option<Runtime>, anyhow::Error> {
     use aptos_indexer::runtime::bootstrap as bootstrap_indexer_stream;
 
-    match bootstrap_indexer_stream(&node_config, chain_id, aptos_db, mp_client_sender) {
+    match bootstrap_indexer_stream(
+        &node_config,
+        chain_id,
+        aptos_db,
+        mp_client_sender,
+        back_pressure_status,
+    ) {
         None => Ok(None),
         Some(res) => res.map(Some),
     }
@@ -29,6 +36,7 @@ pub fn bootstrap_indexer(
     _chain_id: ChainId,
     _aptos_db: Arc<dyn DbReader>,
     _mp_client_sender: MempoolClientSender,
+    _back_pressure_status: BackPressureStatus,
 ) -> Result<Option<Runtime>, anyhow::Error> {
     Ok(None)
 }
@@ -22,8 +22,9 @@ use aptos_build_info::build_information;
 use aptos_config::config::{merge_node_config, NodeConfig, PersistableConfig};
 use aptos_framework::ReleaseBundle;
 use aptos_logger::{prelude::*, telemetry_log_writer::TelemetryLog, Level, LoggerFilterUpdater};
+use aptos_mempool::BackPressureStatus;
 use aptos_state_sync_driver::driver_factory::StateSyncRuntimes;
-use aptos_types::chain_id::ChainId;
+use aptos_types::{chain_id::ChainId, on_chain_config::OnChainConsensusConfig};
 use clap::Parser;
 use futures::channel::mpsc;
 use hex::{FromHex, FromHexError};
@@ -188,6 +189,7 @@ pub struct AptosHandle {
     _indexer_grpc_runtime: Option<Runtime>,
     _indexer_runtime: Option<Runtime>,
     _mempool_runtime: Runtime,
+    _metrics_push_runtime: Option<Runtime>,
     _network_runtimes: Vec<Runtime>,
     _peer_monitoring_service_runtime: Runtime,
     _state_sync_runtimes: StateSyncRuntimes,
@@ -256,6 +258,7 @@ pub fn load_node_config<R>(
     test_dir: &Path,
     random_ports: bool,
     enable_lazy_mode: bool,
+    consensus_config: Option<OnChainConsensusConfig>,
     framework: &ReleaseBundle,
     rng: R,
 ) -> anyhow::Result<NodeConfig>
@@ -276,6 +279,7 @@ where
             test_dir,
             random_ports,
             enable_lazy_mode,
+            consensus_config,
             framework,
             rng,
         )?;
@@ -378,6 +382,7 @@ where
             &test_dir,
             random_ports,
             enable_lazy_mode,
+            None,
             framework,
             rng,
         )?,
@@ -388,13 +393,15 @@ where
 
 /// Creates a single node test config, with a few config tweaks to reduce
 /// the overhead of running the node on a local machine. It writes necessary
-/// configuration artifacts (e.g. the mint key) to disk.
+/// configuration artifacts (e.g. the mint key) to disk. If `consensus_config`
+/// is given, it is used as the on-chain consensus config at genesis.
 pub fn create_single_node_test_config<R>(
     config_path: &Option<PathBuf>,
     test_config_override_path: &Option<PathBuf>,
     test_dir: &Path,
     random_ports: bool,
     enable_lazy_mode: bool,
+    consensus_config: Option<OnChainConsensusConfig>,
     framework: &ReleaseBundle,
     rng: R,
 ) -> anyhow::Result<NodeConfig>
@@ -515,10 +522,13 @@ where
         .with_init_config(Some(Arc::new(move |_, config, _| {
             *config = node_config.clone();
         })))
-        .with_init_genesis_config(Some(Arc::new(|genesis_config| {
+        .with_init_genesis_config(Some(Arc::new(move |genesis_config| {
             genesis_config.allow_new_validators = true;
             genesis_config.epoch_duration_secs = EPOCH_LENGTH_SECS;
             genesis_config.recurring_lockup_duration_secs = 7200;
+            if let Some(consensus_config) = &consensus_config {
+                genesis_config.consensus_config = consensus_config.clone();
+            }
         })))
         .with_randomize_first_validator_ports(random_ports);
     let (root_key, _genesis, genesis_waypoint, mut validators) = builder.build(rng)?;
@@ -580,11 +590,15 @@ pub fn setup_environment_and_start_node(
         chain_id,
     );
 
-    // Create an event subscription service (and reconfig subscriptions for consensus and mempool)
+    // Start the metrics push service (if enabled)
+    let metrics_push_runtime = services::start_metrics_push_service(&node_config, chain_id);
+
+    // Create an event subscription service (and subscriptions for consensus, mempool and the API)
     let (
         mut event_subscription_service,
         mempool_reconfig_subscription,
         consensus_reconfig_subscription,
+        api_event_subscription,
     ) = state_sync::create_event_subscription_service(&node_config, &db_rw);
 
     // Set up the networks and gather the application network handles
@@ -592,15 +606,18 @@ pub fn setup_environment_and_start_node(
     let (
         network_runtimes,
         consensus_network_interfaces,
+        consensus_observer_network_interfaces,
         mempool_network_interfaces,
         peer_monitoring_service_network_interfaces,
         storage_service_network_interfaces,
+        network_deny_lists,
     ) = network::setup_networks_and_get_interfaces(
         &node_config,
         chain_id,
         peers_and_metadata.clone(),
         &mut event_subscription_service,
     );
+    admin_service.set_network_deny_lists(network_deny_lists);
 
     // Start the peer monitoring service
     let peer_monitoring_service_runtime = services::start_peer_monitoring_service(
@@ -626,9 +643,18 @@ pub fn setup_environment_and_start_node(
         peers_and_metadata.clone(),
     );
 
+    // Create the quorum store back pressure status, shared by consensus, mempool and the API
+    let back_pressure_status = BackPressureStatus::default();
+
     // Bootstrap the API and indexer
     let (mempool_client_receiver, api_runtime, indexer_runtime, indexer_grpc_runtime) =
-        services::bootstrap_api_and_indexer(&node_config, aptos_db, chain_id)?;
+        services::bootstrap_api_and_indexer(
+            &node_config,
+            aptos_db,
+            chain_id,
+            back_pressure_status.clone(),
+            api_event_subscription,
+        )?;
 
     // Create mempool and get the consensus to mempool sender
     let (mempool_runtime, consensus_to_mempool_sender) =
@@ -640,10 +666,12 @@ pub fn setup_environment_and_start_node(
             mempool_listener,
             mempool_client_receiver,
             peers_and_metadata,
+            back_pressure_status.clone(),
         );
 
     // Create the consensus runtime (this blocks on state sync first)
-    let consensus_runtime = consensus_network_interfaces.map(|consensus_network_interfaces| {
+    let consensus_runtime = if let Some(consensus_network_interfaces) = consensus_network_interfaces
+    {
         // Wait until state sync has been initialized
         debug!("Waiting until state sync is initialized!");
         state_sync_runtimes.block_until_initialized();
@@ -655,12 +683,33 @@ pub fn setup_environment_and_start_node(
             db_rw,
             consensus_reconfig_subscription,
             consensus_network_interfaces,
+            consensus_observer_network_interfaces,
             consensus_notifier,
             consensus_to_mempool_sender,
+            back_pressure_status,
         );
         admin_service.set_consensus_dbs(consensus_db, quorum_store_db);
-        runtime
-    });
+        Some(runtime)
+    } else if node_config.consensus_observer.observer_enabled {
+        // Wait until state sync has been initialized
+        debug!("Waiting until state sync is initialized!");
+        state_sync_runtimes.block_until_initialized();
+        debug!("State sync initialization complete.");
+
+        // Initialize and start the consensus observer
+        consensus_observer_network_interfaces.map(|consensus_observer_network_interfaces| {
+            services::start_consensus_observer_runtime(
+                &node_config,
+                db_rw,
+                consensus_reconfig_subscription,
+                consensus_observer_network_interfaces,
+                consensus_notifier,
+                consensus_to_mempool_sender,
+            )
+        })
+    } else {
+        None
+    };
 
     Ok(AptosHandle {
         _admin_service: admin_service,
@@ -670,6 +719,7 @@ pub fn setup_environment_and_start_node(
         _indexer_grpc_runtime: indexer_grpc_runtime,
         _indexer_runtime: indexer_runtime,
         _mempool_runtime: mempool_runtime,
+        _metrics_push_runtime: metrics_push_runtime,
         _network_runtimes: network_runtimes,
         _peer_monitoring_service_runtime: peer_monitoring_service_runtime,
         _state_sync_runtimes: state_sync_runtimes,
@@ -7,7 +7,10 @@ use aptos_config::{
     config::{NetworkConfig, NodeConfig},
     network_id::NetworkId,
 };
-use aptos_consensus::network_interface::{ConsensusMsg, DIRECT_SEND, RPC};
+use aptos_consensus::{
+    consensus_observer::network::ConsensusObserverMessage,
+    network_interface::{ConsensusMsg, DIRECT_SEND, RPC},
+};
 use aptos_event_notifications::EventSubscriptionService;
 use aptos_logger::debug;
 use aptos_mempool::network::MempoolSyncMsg;
@@ -16,6 +19,7 @@ use aptos_network::{
         interface::{NetworkClient, NetworkServiceEvents},
         storage::PeersAndMetadata,
     },
+    peer_manager::deny_list::DenyList,
     protocols::network::{
         NetworkApplicationConfig, NetworkClientConfig, NetworkEvents, NetworkSender,
         NetworkServiceConfig,
@@ -65,6 +69,31 @@ pub fn consensus_network_configuration(node_config: &NodeConfig) -> NetworkAppli
     NetworkApplicationConfig::new(network_client_config, network_service_config)
 }
 
+/// Returns the network application config for the consensus observer client and service
+pub fn consensus_observer_network_configuration(
+    node_config: &NodeConfig,
+) -> Option<NetworkApplicationConfig> {
+    let consensus_observer_config = node_config.consensus_observer;
+    if !consensus_observer_config.is_enabled() {
+        return None;
+    }
+    let direct_send_protocols = vec![ProtocolId::ConsensusObserver];
+    let rpc_protocols = vec![]; // The consensus observer does not use RPC
+    let max_network_channel_size = consensus_observer_config.max_network_channel_size as usize;
+
+    let network_client_config =
+        NetworkClientConfig::new(direct_send_protocols.clone(), rpc_protocols.clone());
+    let network_service_config = NetworkServiceConfig::new(
+        direct_send_protocols,
+        rpc_protocols,
+        aptos_channel::Config::new(max_network_channel_size).queue_style(QueueStyle::FIFO),
+    );
+    Some(NetworkApplicationConfig::new(
+        network_client_config,
+        network_service_config,
+    ))
+}
+
 /// Returns the network application config for the mempool client and service
 pub fn mempool_network_configuration(node_config: &NodeConfig) -> NetworkApplicationConfig {
     let direct_send_protocols = vec![ProtocolId::MempoolDirectSend];
@@ -190,9 +219,11 @@ pub fn setup_networks_and_get_interfaces(
 ) -> (
     Vec<Runtime>,
     Option<ApplicationNetworkInterfaces<ConsensusMsg>>,
+    Option<ApplicationNetworkInterfaces<ConsensusObserverMessage>>,
     ApplicationNetworkInterfaces<MempoolSyncMsg>,
     ApplicationNetworkInterfaces<PeerMonitoringServiceMessage>,
     ApplicationNetworkInterfaces<StorageServiceMessage>,
+    HashMap<NetworkId, DenyList>,
 ) {
     // Gather all network configs
     let network_configs = extract_network_configs(node_config);
@@ -200,10 +231,12 @@ pub fn setup_networks_and_get_interfaces(
     // Create each network and register the application handles
     let mut network_runtimes = vec![];
     let mut consensus_network_handle = None;
+    let mut consensus_observer_network_handles = vec![];
     let mut mempool_network_handles = vec![];
     let mut peer_monitoring_service_network_handles = vec![];
     let mut storage_service_network_handles = vec![];
     let mut netbench_handles = Vec::<ApplicationNetworkHandle<NetbenchMessage>>::new();
+    let mut network_deny_lists = HashMap::new();
     for network_config in network_configs.into_iter() {
         // Create a network runtime for the config
         let runtime = create_network_runtime(&network_config);
@@ -237,6 +270,19 @@ pub fn setup_networks_and_get_interfaces(
             }
         }
 
+        // Register the consensus observer (both client and server) with the fullnode networks
+        if !network_id.is_validator_network() {
+            if let Some(app_config) = consensus_observer_network_configuration(node_config) {
+                let consensus_observer_network_handle = register_client_and_service_with_network(
+                    &mut network_builder,
+                    network_id,
+                    &network_config,
+                    app_config,
+                );
+                consensus_observer_network_handles.push(consensus_observer_network_handle);
+            }
+        }
+
         // Register mempool (both client and server) with the network
         let mempool_network_handle = register_client_and_service_with_network(
             &mut network_builder,
@@ -275,6 +321,9 @@ pub fn setup_networks_and_get_interfaces(
             netbench_handles.push(netbench_handle);
         }
 
+        // Save the deny list of the network (so that it can be updated at runtime)
+        network_deny_lists.insert(network_id, network_builder.deny_list());
+
         // Build and start the network on the runtime
         network_builder.build(runtime.handle().clone());
         network_builder.start();
@@ -286,6 +335,14 @@ pub fn setup_networks_and_get_interfaces(
     }
 
     // Transform all network handles into application interfaces
+    let consensus_observer_interfaces =
+        consensus_observer_network_configuration(node_config).map(|app_config| {
+            create_network_interfaces(
+                consensus_observer_network_handles,
+                app_config,
+                peers_and_metadata.clone(),
+            )
+        });
     let (
         consensus_interfaces,
         mempool_interfaces,
@@ -316,9 +373,11 @@ pub fn setup_networks_and_get_interfaces(
     (
         network_runtimes,
         consensus_interfaces,
+        consensus_observer_interfaces,
         mempool_interfaces,
         peer_monitoring_service_interfaces,
         storage_service_interfaces,
+        network_deny_lists,
     )
 }
 
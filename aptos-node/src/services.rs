@@ -6,15 +6,19 @@ use aptos_admin_service::AdminService;
 use aptos_build_info::build_information;
 use aptos_config::config::NodeConfig;
 use aptos_consensus::{
-    network_interface::ConsensusMsg, persistent_liveness_storage::StorageWriteProxy,
-    quorum_store::quorum_store_db::QuorumStoreDB,
+    consensus_observer::network::ConsensusObserverMessage, network_interface::ConsensusMsg,
+    persistent_liveness_storage::StorageWriteProxy, quorum_store::quorum_store_db::QuorumStoreDB,
 };
 use aptos_consensus_notifications::ConsensusNotifier;
 use aptos_data_client::client::AptosDataClient;
-use aptos_event_notifications::{DbBackedOnChainConfig, ReconfigNotificationListener};
+use aptos_event_notifications::{
+    DbBackedOnChainConfig, EventNotificationListener, ReconfigNotificationListener,
+};
 use aptos_indexer_grpc_fullnode::runtime::bootstrap as bootstrap_indexer_grpc;
 use aptos_logger::{debug, telemetry_log_writer::TelemetryLog, LoggerFilterUpdater};
-use aptos_mempool::{network::MempoolSyncMsg, MempoolClientRequest, QuorumStoreRequest};
+use aptos_mempool::{
+    network::MempoolSyncMsg, BackPressureStatus, MempoolClientRequest, QuorumStoreRequest,
+};
 use aptos_mempool_notifications::MempoolNotificationListener;
 use aptos_network::application::{interface::NetworkClientInterface, storage::PeersAndMetadata};
 use aptos_network_benchmark::{run_netbench_service, NetbenchMessage};
@@ -39,6 +43,8 @@ pub fn bootstrap_api_and_indexer(
     node_config: &NodeConfig,
     aptos_db: Arc<dyn DbReader>,
     chain_id: ChainId,
+    back_pressure_status: BackPressureStatus,
+    api_event_subscription: Option<EventNotificationListener>,
 ) -> anyhow::Result<(
     Receiver<MempoolClientRequest>,
     Option<Runtime>,
@@ -56,6 +62,8 @@ pub fn bootstrap_api_and_indexer(
             chain_id,
             aptos_db.clone(),
             mempool_client_sender.clone(),
+            back_pressure_status.clone(),
+            api_event_subscription,
         )?)
     } else {
         None
@@ -67,11 +75,17 @@ pub fn bootstrap_api_and_indexer(
         chain_id,
         aptos_db.clone(),
         mempool_client_sender.clone(),
+        back_pressure_status.clone(),
     );
 
     // Create the indexer runtime
-    let indexer_runtime =
-        indexer::bootstrap_indexer(node_config, chain_id, aptos_db, mempool_client_sender)?;
+    let indexer_runtime = indexer::bootstrap_indexer(
+        node_config,
+        chain_id,
+        aptos_db,
+        mempool_client_sender,
+        back_pressure_status,
+    )?;
 
     Ok((
         mempool_client_receiver,
@@ -87,24 +101,66 @@ pub fn start_consensus_runtime(
     db_rw: DbReaderWriter,
     consensus_reconfig_subscription: Option<ReconfigNotificationListener<DbBackedOnChainConfig>>,
     consensus_network_interfaces: ApplicationNetworkInterfaces<ConsensusMsg>,
+    consensus_observer_network_interfaces: Option<
+        ApplicationNetworkInterfaces<ConsensusObserverMessage>,
+    >,
     consensus_notifier: ConsensusNotifier,
     consensus_to_mempool_sender: Sender<QuorumStoreRequest>,
+    back_pressure_status: BackPressureStatus,
 ) -> (Runtime, Arc<StorageWriteProxy>, Arc<QuorumStoreDB>) {
     let instant = Instant::now();
+    let (consensus_observer_network_client, consensus_observer_network_service_events) =
+        match consensus_observer_network_interfaces {
+            Some(interfaces) => (
+                Some(interfaces.network_client),
+                Some(interfaces.network_service_events),
+            ),
+            None => (None, None),
+        };
     let consensus = aptos_consensus::consensus_provider::start_consensus(
         node_config,
         consensus_network_interfaces.network_client,
         consensus_network_interfaces.network_service_events,
         Arc::new(consensus_notifier),
         consensus_to_mempool_sender,
+        back_pressure_status,
         db_rw,
         consensus_reconfig_subscription
             .expect("Consensus requires a reconfiguration subscription!"),
+        consensus_observer_network_client,
+        consensus_observer_network_service_events,
     );
     debug!("Consensus started in {} ms", instant.elapsed().as_millis());
     consensus
 }
 
+/// Creates and starts the consensus observer runtime (for fullnodes)
+pub fn start_consensus_observer_runtime(
+    node_config: &NodeConfig,
+    db_rw: DbReaderWriter,
+    consensus_reconfig_subscription: Option<ReconfigNotificationListener<DbBackedOnChainConfig>>,
+    consensus_observer_network_interfaces: ApplicationNetworkInterfaces<ConsensusObserverMessage>,
+    consensus_notifier: ConsensusNotifier,
+    consensus_to_mempool_sender: Sender<QuorumStoreRequest>,
+) -> Runtime {
+    let instant = Instant::now();
+    let runtime = aptos_consensus::consensus_provider::start_consensus_observer(
+        node_config,
+        consensus_observer_network_interfaces.network_client,
+        consensus_observer_network_interfaces.network_service_events,
+        Arc::new(consensus_notifier),
+        consensus_to_mempool_sender,
+        db_rw,
+        consensus_reconfig_subscription
+            .expect("The consensus observer requires a reconfiguration subscription!"),
+    );
+    debug!(
+        "Consensus observer started in {} ms",
+        instant.elapsed().as_millis()
+    );
+    runtime
+}
+
 /// Create the mempool runtime and start mempool
 pub fn start_mempool_runtime_and_get_consensus_sender(
     node_config: &mut NodeConfig,
@@ -114,6 +170,7 @@ pub fn start_mempool_runtime_and_get_consensus_sender(
     mempool_listener: MempoolNotificationListener,
     mempool_client_receiver: Receiver<MempoolClientRequest>,
     peers_and_metadata: Arc<PeersAndMetadata>,
+    back_pressure_status: BackPressureStatus,
 ) -> (Runtime, Sender<QuorumStoreRequest>) {
     // Create a communication channel between consensus and mempool
     let (consensus_to_mempool_sender, consensus_to_mempool_receiver) =
@@ -131,6 +188,7 @@ pub fn start_mempool_runtime_and_get_consensus_sender(
         mempool_listener,
         mempool_reconfig_subscription,
         peers_and_metadata,
+        back_pressure_status,
     );
     debug!("Mempool started in {} ms", instant.elapsed().as_millis());
 
@@ -214,6 +272,11 @@ pub fn start_netbench_service(
     ));
 }
 
+/// Starts the metrics push service (if enabled in the node config)
+pub fn start_metrics_push_service(node_config: &NodeConfig, chain_id: ChainId) -> Option<Runtime> {
+    aptos_telemetry::metrics_push::start_metrics_push_service(node_config, chain_id)
+}
+
 /// Starts the telemetry service and grabs the build information
 pub fn start_telemetry_service(
     node_config: &NodeConfig,
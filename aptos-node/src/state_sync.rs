@@ -10,7 +10,8 @@ use aptos_data_streaming_service::{
     streaming_service::DataStreamingService,
 };
 use aptos_event_notifications::{
-    DbBackedOnChainConfig, EventSubscriptionService, ReconfigNotificationListener,
+    DbBackedOnChainConfig, EventNotificationListener, EventSubscriptionService,
+    ReconfigNotificationListener,
 };
 use aptos_executor::chunk_executor::ChunkExecutor;
 use aptos_infallible::RwLock;
@@ -45,6 +46,7 @@ pub fn create_event_subscription_service(
     EventSubscriptionService,
     ReconfigNotificationListener<DbBackedOnChainConfig>,
     Option<ReconfigNotificationListener<DbBackedOnChainConfig>>,
+    Option<EventNotificationListener>,
 ) {
     // Create the event subscription service
     let mut event_subscription_service =
@@ -55,8 +57,11 @@ pub fn create_event_subscription_service(
         .subscribe_to_reconfigurations()
         .expect("Mempool must subscribe to reconfigurations");
 
-    // Create a reconfiguration subscription for consensus (if this is a validator)
-    let consensus_reconfig_subscription = if node_config.base.role.is_validator() {
+    // Create a reconfiguration subscription for consensus (if this is a validator),
+    // or for the consensus observer (if it is enabled)
+    let consensus_reconfig_subscription = if node_config.base.role.is_validator()
+        || node_config.consensus_observer.observer_enabled
+    {
         Some(
             event_subscription_service
                 .subscribe_to_reconfigurations()
@@ -66,10 +71,23 @@ pub fn create_event_subscription_service(
         None
     };
 
+    // Create a subscription to all events for the API event stream (if it is enabled)
+    let api_event_subscription = if node_config.api.enabled && node_config.api.event_stream_enabled
+    {
+        Some(
+            event_subscription_service
+                .subscribe_to_all_events()
+                .expect("The API must subscribe to all events"),
+        )
+    } else {
+        None
+    };
+
     (
         event_subscription_service,
         mempool_reconfig_subscription,
         consensus_reconfig_subscription,
+        api_event_subscription,
     )
 }
 
@@ -90,6 +90,7 @@ fn test_create_single_node_test_config() {
         &test_dir,
         false,
         false,
+        None,
         aptos_cached_packages::head_release_bundle(),
         rand::rngs::StdRng::from_entropy(),
     )
@@ -50,6 +50,17 @@ pub fn fetch_chain_id(db: &DbReaderWriter) -> anyhow::Result<ChainId> {
 pub fn set_aptos_vm_configurations(node_config: &NodeConfig) {
     AptosVM::set_paranoid_type_checks(node_config.execution.paranoid_type_verification);
     AptosVM::set_concurrency_level_once(node_config.execution.concurrency_level as usize);
+    AptosVM::set_block_gas_profiling_once(node_config.execution.block_gas_profiling);
+    if node_config.execution.simulation_output_cache_size > 0 {
+        AptosVM::set_simulation_output_cache_once(
+            node_config.execution.simulation_output_cache_size,
+        );
+    }
+    if node_config.execution.adaptive_concurrency {
+        AptosVM::set_adaptive_concurrency_once(
+            node_config.execution.min_concurrency_level as usize,
+        );
+    }
     AptosVM::set_num_proof_reading_threads_once(
         node_config.execution.num_proof_reading_threads as usize,
     );
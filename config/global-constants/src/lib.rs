@@ -12,6 +12,7 @@
 /// Definitions of global cryptographic keys (e.g., as held in secure storage)
 pub const CONSENSUS_KEY: &str = "consensus";
 pub const OWNER_ACCOUNT: &str = "owner_account";
+pub const RANDOMNESS_KEYS: &str = "randomness_keys";
 
 /// Definitions of global data items (e.g., as held in secure storage)
 pub const SAFETY_DATA: &str = "safety_data";
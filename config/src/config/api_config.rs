@@ -5,7 +5,9 @@
 use crate::{
     config::{
         config_sanitizer::ConfigSanitizer, gas_estimation_config::GasEstimationConfig,
-        node_config_loader::NodeType, Error, NodeConfig,
+        graphql_config::GraphQlConfig, node_config_loader::NodeType,
+        request_quota_config::RequestQuotaConfig, view_function_config::ViewFunctionConfig, Error,
+        NodeConfig,
     },
     utils,
 };
@@ -58,10 +60,10 @@ pub struct ApiConfig {
     pub max_account_resources_page_size: u16,
     /// Maximum page size for module paginated APIs
     pub max_account_modules_page_size: u16,
-    /// Maximum gas unit limit for view functions
-    ///
-    /// This limits the execution length of a view function to the given gas used.
-    pub max_gas_view_function: u64,
+    /// Maximum number of resources that can be read with the batch view state API
+    pub max_view_state_resources: usize,
+    /// Limits on the execution of view functions
+    pub view_function: ViewFunctionConfig,
     /// Optional: Maximum number of worker threads for the API.
     ///
     /// If not set, `runtime_worker_multiplier` will multiply times the number of CPU cores on the machine
@@ -72,6 +74,21 @@ pub struct ApiConfig {
     pub runtime_worker_multiplier: usize,
     /// Configs for computing unit gas price estimation
     pub gas_estimation: GasEstimationConfig,
+    /// Maximum time a request waiting for a transaction status change is held
+    pub wait_transaction_status_timeout_ms: u64,
+    /// Enables the WebSocket event streaming API
+    #[serde(default = "default_disabled")]
+    pub event_stream_enabled: bool,
+    /// Maximum number of concurrent connections to the event streaming API
+    pub max_event_stream_connections: usize,
+    /// URL of an archival node keeping the data pruned by this node, returned to the
+    /// clients querying pruned data
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archival_node_url: Option<String>,
+    /// Quotas on the requests of each client
+    pub request_quota: RequestQuotaConfig,
+    /// Configs of the GraphQL API
+    pub graphql: GraphQlConfig,
 }
 
 const DEFAULT_ADDRESS: &str = "127.0.0.1";
@@ -81,7 +98,8 @@ pub const DEFAULT_MAX_SUBMIT_TRANSACTION_BATCH_SIZE: usize = 10;
 pub const DEFAULT_MAX_PAGE_SIZE: u16 = 100;
 const DEFAULT_MAX_ACCOUNT_RESOURCES_PAGE_SIZE: u16 = 9999;
 const DEFAULT_MAX_ACCOUNT_MODULES_PAGE_SIZE: u16 = 9999;
-const DEFAULT_MAX_VIEW_GAS: u64 = 2_000_000; // We keep this value the same as the max number of gas allowed for one single transaction defined in aptos-gas.
+const DEFAULT_MAX_VIEW_STATE_RESOURCES: usize = 100;
+const DEFAULT_MAX_EVENT_STREAM_CONNECTIONS: usize = 100;
 
 fn default_enabled() -> bool {
     true
@@ -112,10 +130,17 @@ impl Default for ApiConfig {
             max_events_page_size: DEFAULT_MAX_PAGE_SIZE,
             max_account_resources_page_size: DEFAULT_MAX_ACCOUNT_RESOURCES_PAGE_SIZE,
             max_account_modules_page_size: DEFAULT_MAX_ACCOUNT_MODULES_PAGE_SIZE,
-            max_gas_view_function: DEFAULT_MAX_VIEW_GAS,
+            max_view_state_resources: DEFAULT_MAX_VIEW_STATE_RESOURCES,
+            view_function: ViewFunctionConfig::default(),
             max_runtime_workers: None,
             runtime_worker_multiplier: 2,
             gas_estimation: GasEstimationConfig::default(),
+            wait_transaction_status_timeout_ms: 10_000,
+            event_stream_enabled: default_disabled(),
+            max_event_stream_connections: DEFAULT_MAX_EVENT_STREAM_CONNECTIONS,
+            archival_node_url: None,
+            request_quota: RequestQuotaConfig::default(),
+            graphql: GraphQlConfig::default(),
         }
     }
 }
@@ -168,6 +193,15 @@ impl ConfigSanitizer for ApiConfig {
         // Sanitize the gas estimation config
         GasEstimationConfig::sanitize(node_config, node_type, chain_id)?;
 
+        // Sanitize the view function config
+        ViewFunctionConfig::sanitize(node_config, node_type, chain_id)?;
+
+        // Sanitize the request quota config
+        RequestQuotaConfig::sanitize(node_config, node_type, chain_id)?;
+
+        // Sanitize the GraphQL config
+        GraphQlConfig::sanitize(node_config, node_type, chain_id)?;
+
         Ok(())
     }
 }
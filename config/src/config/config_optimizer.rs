@@ -275,6 +275,7 @@ fn build_seed_peer(
         addresses: vec![network_address],
         keys: hashset! {public_key},
         role: PeerRole::Upstream,
+        relay_peers: vec![],
     };
 
     // Return the account address and peer
@@ -35,6 +35,12 @@ pub struct ConsensusConfig {
     pub round_initial_timeout_ms: u64,
     pub round_timeout_backoff_exponent_base: f64,
     pub round_timeout_backoff_max_exponent: usize,
+    // When adaptive round timeouts are enabled on-chain, the weight of the latest round duration
+    // in the moving average of the round durations
+    pub round_timeout_adaptive_weight: f64,
+    // When adaptive round timeouts are enabled on-chain, the round timeout is the moving average
+    // of the round durations times this multiplier
+    pub round_timeout_adaptive_multiplier: f64,
     pub safety_rules: SafetyRulesConfig,
     // Only sync committed transactions but not vote for any pending blocks. This is useful when
     // validators coordinate on the latest version to apply a manual transaction.
@@ -159,6 +165,8 @@ impl Default for ConsensusConfig {
             // Timeout goes from initial_timeout to initial_timeout*3 in 6 steps
             round_timeout_backoff_exponent_base: 1.2,
             round_timeout_backoff_max_exponent: 6,
+            round_timeout_adaptive_weight: 0.1,
+            round_timeout_adaptive_multiplier: 3.0,
             safety_rules: SafetyRulesConfig::default(),
             sync_only: false,
             channel_size: 30, // hard-coded
@@ -0,0 +1,94 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::{
+    config_sanitizer::ConfigSanitizer, node_config_loader::NodeType, Error, NodeConfig,
+};
+use aptos_types::chain_id::ChainId;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConsensusObserverConfig {
+    /// Whether the node subscribes to ordered blocks and commit decisions from its upstream
+    /// peers, and applies them instead of relying on state sync (fullnodes only)
+    pub observer_enabled: bool,
+    /// Whether the node publishes its ordered blocks and commit decisions to its subscribers
+    pub publisher_enabled: bool,
+
+    /// Max num of pending network messages
+    pub max_network_channel_size: u64,
+    /// Max num of ordered blocks the observer keeps while waiting for their commit decision
+    pub max_num_pending_blocks: u64,
+    /// Max num of upstream peers the observer subscribes to
+    pub max_concurrent_subscriptions: u64,
+    /// The interval (ms) at which the observer refreshes its subscriptions
+    pub subscription_refresh_interval_ms: u64,
+}
+
+impl Default for ConsensusObserverConfig {
+    fn default() -> Self {
+        Self {
+            observer_enabled: false,
+            publisher_enabled: false,
+            max_network_channel_size: 1000,
+            max_num_pending_blocks: 100,
+            max_concurrent_subscriptions: 2,
+            subscription_refresh_interval_ms: 5_000, // 5 seconds
+        }
+    }
+}
+
+impl ConsensusObserverConfig {
+    /// Returns true iff the node needs to register the consensus observer protocol
+    pub fn is_enabled(&self) -> bool {
+        self.observer_enabled || self.publisher_enabled
+    }
+}
+
+impl ConfigSanitizer for ConsensusObserverConfig {
+    fn sanitize(
+        node_config: &NodeConfig,
+        node_type: NodeType,
+        _chain_id: Option<ChainId>,
+    ) -> Result<(), Error> {
+        let sanitizer_name = Self::get_sanitizer_name();
+        let observer_config = &node_config.consensus_observer;
+
+        // Validators participate in consensus, so they cannot observe it
+        if node_type.is_validator() && observer_config.observer_enabled {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The consensus observer cannot be enabled on validators!".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_observer_on_validator() {
+        // Create a node config with the observer enabled
+        let node_config = NodeConfig {
+            consensus_observer: ConsensusObserverConfig {
+                observer_enabled: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that sanitization fails for validators
+        let error =
+            ConsensusObserverConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Verify that sanitization passes for fullnodes
+        ConsensusObserverConfig::sanitize(&node_config, NodeType::ValidatorFullnode, None).unwrap();
+        ConsensusObserverConfig::sanitize(&node_config, NodeType::PublicFullnode, None).unwrap();
+    }
+}
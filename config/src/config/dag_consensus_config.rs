@@ -16,6 +16,12 @@ pub struct DagPayloadConfig {
     pub max_receiving_size_per_round_bytes: u64,
 
     pub payload_pull_max_poll_time_ms: u64,
+
+    /// Fraction of the sending limits used for a node while quorum store reports a backlog.
+    pub backlog_payload_fraction: f64,
+    /// Lower bounds on the sending limits while quorum store reports a backlog.
+    pub backlog_min_sending_txns_per_round: u64,
+    pub backlog_min_sending_size_per_round_bytes: u64,
 }
 
 impl Default for DagPayloadConfig {
@@ -27,6 +33,10 @@ impl Default for DagPayloadConfig {
             max_receiving_size_per_round_bytes: 20 * 1024 * 1024,
 
             payload_pull_max_poll_time_ms: 1000,
+
+            backlog_payload_fraction: 0.5,
+            backlog_min_sending_txns_per_round: 160,
+            backlog_min_sending_size_per_round_bytes: 1024 * 1024,
         }
     }
 }
@@ -43,6 +53,18 @@ impl ConfigSanitizer for DagPayloadConfig {
         // Sanitize the payload size limits
         Self::sanitize_payload_size_limits(&sanitizer_name, dag_node_payload_config)?;
 
+        // Sanitize the backlog fraction
+        let fraction = dag_node_payload_config.backlog_payload_fraction;
+        if fraction <= 0.0 || fraction > 1.0 {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "Backlog payload fraction must be in (0, 1], got {}",
+                    fraction
+                ),
+            ));
+        }
+
         Ok(())
     }
 }
@@ -83,6 +105,9 @@ pub struct DagFetcherConfig {
     pub rpc_timeout_ms: u64,
     pub min_concurrent_responders: u32,
     pub max_concurrent_responders: u32,
+    /// Maximum number of rounds pulled by a single ranged fetch. Nodes lagging by more rounds
+    /// catch up by ranges before fetching the missing parents of a node.
+    pub max_rounds_per_range_fetch: u64,
 }
 
 impl Default for DagFetcherConfig {
@@ -92,6 +117,7 @@ impl Default for DagFetcherConfig {
             rpc_timeout_ms: 1000,
             min_concurrent_responders: 1,
             max_concurrent_responders: 4,
+            max_rounds_per_range_fetch: 10,
         }
     }
 }
@@ -202,4 +228,24 @@ mod tests {
             DagPayloadConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
+
+    #[test]
+    fn test_sanitize_backlog_payload_fraction() {
+        // Create a node config with an invalid backlog fraction
+        let node_config = NodeConfig {
+            dag_consensus: DagConsensusConfig {
+                node_payload_config: DagPayloadConfig {
+                    backlog_payload_fraction: 0.0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error =
+            DagPayloadConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
 }
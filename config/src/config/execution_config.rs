@@ -26,6 +26,11 @@ pub struct ExecutionConfig {
     pub genesis_file_location: PathBuf,
     /// Number of threads to run execution
     pub concurrency_level: u16,
+    /// Adapts the number of threads of each block to the conflicts observed in previous
+    /// blocks, between `min_concurrency_level` and `concurrency_level`
+    pub adaptive_concurrency: bool,
+    /// Minimum number of threads to run execution with, if `adaptive_concurrency` is enabled
+    pub min_concurrency_level: u16,
     /// Number of threads to read proofs
     pub num_proof_reading_threads: u16,
     /// Enables paranoid mode for types, which adds extra runtime VM checks
@@ -36,6 +41,10 @@ pub struct ExecutionConfig {
     pub processed_transactions_detailed_counters: bool,
     /// Enables filtering of transactions before they are sent to execution
     pub transaction_filter: Filter,
+    /// Enables profiling the gas usage of each executed block, which forces sequential execution
+    pub block_gas_profiling: bool,
+    /// Number of simulated transaction outputs to cache for the latest state (0 disables it)
+    pub simulation_output_cache_size: usize,
 }
 
 impl std::fmt::Debug for ExecutionConfig {
@@ -61,11 +70,15 @@ impl Default for ExecutionConfig {
             genesis_file_location: PathBuf::new(),
             // Parallel execution by default.
             concurrency_level: 8,
+            adaptive_concurrency: false,
+            min_concurrency_level: 2,
             num_proof_reading_threads: 32,
             paranoid_type_verification: true,
             paranoid_hot_potato_verification: true,
             processed_transactions_detailed_counters: false,
             transaction_filter: Filter::empty(),
+            block_gas_profiling: false,
+            simulation_output_cache_size: 0,
         }
     }
 }
@@ -137,6 +150,20 @@ impl ConfigSanitizer for ExecutionConfig {
         let sanitizer_name = Self::get_sanitizer_name();
         let execution_config = &node_config.execution;
 
+        // Verify that the concurrency bounds are consistent
+        if execution_config.adaptive_concurrency
+            && (execution_config.min_concurrency_level < 2
+                || execution_config.min_concurrency_level > execution_config.concurrency_level)
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "min_concurrency_level {} must be at least 2 and at most concurrency_level {}!",
+                    execution_config.min_concurrency_level, execution_config.concurrency_level
+                ),
+            ));
+        }
+
         // If this is a mainnet node, ensure that additional verifiers are enabled
         if let Some(chain_id) = chain_id {
             if chain_id.is_mainnet() {
@@ -186,6 +213,26 @@ mod test {
             .unwrap();
     }
 
+    #[test]
+    fn test_sanitize_invalid_concurrency_bounds() {
+        // Create a node config with a minimum concurrency level above the maximum
+        let node_config = NodeConfig {
+            execution: ExecutionConfig {
+                concurrency_level: 4,
+                adaptive_concurrency: true,
+                min_concurrency_level: 8,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error =
+            ExecutionConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::testnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
     #[test]
     fn test_sanitize_hot_potato_mainnet() {
         // Create a node config with missing paranoid_hot_potato_verification on mainnet
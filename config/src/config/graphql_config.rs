@@ -0,0 +1,103 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::{
+    config_sanitizer::ConfigSanitizer, node_config_loader::NodeType, Error, NodeConfig,
+};
+use aptos_types::chain_id::ChainId;
+use serde::{Deserialize, Serialize};
+
+const DEFAULT_MAX_DEPTH: usize = 10;
+const DEFAULT_MAX_COMPLEXITY: usize = 10_000;
+const DEFAULT_MAX_BATCH_SIZE: usize = 10;
+
+/// The GraphQL API, only served by nodes built with the `graphql` feature of the API
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GraphQlConfig {
+    /// Enables the GraphQL API
+    pub enabled: bool,
+    /// Maximum nesting depth of the fields of a query
+    pub max_depth: usize,
+    /// Maximum complexity of a query. Each field counts as 1, and the fields of a list count
+    /// as many times as the requested page size.
+    pub max_complexity: usize,
+    /// Maximum number of queries in a batch
+    pub max_batch_size: usize,
+}
+
+impl Default for GraphQlConfig {
+    fn default() -> GraphQlConfig {
+        GraphQlConfig {
+            enabled: false,
+            max_depth: DEFAULT_MAX_DEPTH,
+            max_complexity: DEFAULT_MAX_COMPLEXITY,
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+        }
+    }
+}
+
+impl ConfigSanitizer for GraphQlConfig {
+    fn sanitize(
+        node_config: &NodeConfig,
+        _node_type: NodeType,
+        _chain_id: Option<ChainId>,
+    ) -> Result<(), Error> {
+        let sanitizer_name = Self::get_sanitizer_name();
+        let graphql_config = &node_config.api.graphql;
+
+        // If the GraphQL API is disabled, the limits are never used
+        if !graphql_config.enabled {
+            return Ok(());
+        }
+
+        // Verify that queries can be served at all
+        if graphql_config.max_depth == 0
+            || graphql_config.max_complexity == 0
+            || graphql_config.max_batch_size == 0
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "max depth {}, max complexity {}, max batch size {} must be > 0",
+                    graphql_config.max_depth,
+                    graphql_config.max_complexity,
+                    graphql_config.max_batch_size
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ApiConfig;
+
+    #[test]
+    fn test_sanitize_zero_batch_size() {
+        // Create a node config with the GraphQL API enabled and a zero batch size
+        let node_config = NodeConfig {
+            api: ApiConfig {
+                graphql: GraphQlConfig {
+                    enabled: true,
+                    max_batch_size: 0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = GraphQlConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::mainnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+}
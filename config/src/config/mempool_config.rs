@@ -7,7 +7,7 @@ use crate::config::{
     node_config_loader::NodeType, Error, NodeConfig, MAX_APPLICATION_MESSAGE_SIZE,
 };
 use aptos_global_constants::DEFAULT_BUCKETS;
-use aptos_types::chain_id::ChainId;
+use aptos_types::{account_address::AccountAddress, chain_id::ChainId};
 use serde::{Deserialize, Serialize};
 use serde_yaml::Value;
 
@@ -20,6 +20,13 @@ pub struct MempoolConfig {
     pub capacity_bytes: usize,
     /// Maximum number of transactions allowed in the Mempool per user
     pub capacity_per_user: usize,
+    /// Maximum number of parked transactions (i.e., waiting for a sequence number gap to be
+    /// filled) allowed in the Mempool per user. Beyond it, parked transactions of the user are
+    /// evicted by lowest gas price, then oldest.
+    pub max_parked_txns_per_user: usize,
+    /// Minimum increase of the gas unit price, in percent, for a transaction to replace a
+    /// pending transaction with the same sender and sequence number
+    pub min_gas_price_bump_percent: u64,
     /// Number of failover peers to broadcast to when the primary network is alive
     pub default_failovers: usize,
     /// The maximum number of broadcasts sent to a single peer that are pending a response ACK at any point.
@@ -51,8 +58,39 @@ pub struct MempoolConfig {
     ///
     /// Overriding this won't make much of a difference if the upstream nodes don't match.
     pub broadcast_buckets: Vec<u64>,
+    /// Broadcast fresh transactions before the pending rebroadcasts to a peer, when they are in a
+    /// higher gas price bucket. If disabled, rebroadcasts are always sent first.
+    pub prioritize_broadcast_by_gas_price: bool,
     pub eager_expire_threshold_ms: Option<u64>,
     pub eager_expire_time_ms: u64,
+    /// Reject client submitted transactions with a busy status while consensus reports quorum
+    /// store back pressure, instead of accepting them into an already saturated pipeline.
+    pub reject_client_txns_on_back_pressure: bool,
+    /// Rules checked on the submitted transactions before their validation and insertion into
+    /// the Mempool. Rejected transactions are neither inserted nor broadcast.
+    pub admission_control: MempoolAdmissionControlConfig,
+    /// Window over which the rate of transactions leaving the Mempool is measured, to estimate
+    /// when the clients rejected by a full Mempool should retry.
+    pub load_shedding_window_secs: u64,
+    /// Upper bound of the retry-after estimate returned to the clients of a full Mempool.
+    pub max_retry_after_secs: u64,
+    /// Maximum number of clients concurrently waiting for a transaction status change. Beyond
+    /// it, new waiters are turned away.
+    pub max_transaction_status_waiters: usize,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MempoolAdmissionControlConfig {
+    /// If not empty, only the transactions of these senders are admitted
+    pub allowed_senders: Vec<AccountAddress>,
+    /// The transactions of these senders are rejected
+    pub denied_senders: Vec<AccountAddress>,
+    /// The transactions calling these entry functions, given as (module address, module name,
+    /// function name), are rejected
+    pub denied_entry_functions: Vec<(AccountAddress, String, String)>,
+    /// The transactions with a higher max gas amount are rejected
+    pub max_gas_amount: Option<u64>,
 }
 
 impl Default for MempoolConfig {
@@ -70,13 +108,21 @@ impl Default for MempoolConfig {
             capacity: 2_000_000,
             capacity_bytes: 2 * 1024 * 1024 * 1024,
             capacity_per_user: 100,
+            max_parked_txns_per_user: 50,
+            min_gas_price_bump_percent: 10,
             default_failovers: 1,
             shared_mempool_peer_update_interval_ms: 1_000,
             system_transaction_timeout_secs: 600,
             system_transaction_gc_interval_ms: 60_000,
             broadcast_buckets: DEFAULT_BUCKETS.to_vec(),
+            prioritize_broadcast_by_gas_price: true,
             eager_expire_threshold_ms: Some(10_000),
             eager_expire_time_ms: 3_000,
+            reject_client_txns_on_back_pressure: true,
+            admission_control: MempoolAdmissionControlConfig::default(),
+            load_shedding_window_secs: 10,
+            max_retry_after_secs: 60,
+            max_transaction_status_waiters: 10_000,
         }
     }
 }
@@ -0,0 +1,148 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::{
+    config_sanitizer::ConfigSanitizer, node_config_loader::NodeType, Error, NodeConfig,
+};
+use aptos_types::chain_id::ChainId;
+use serde::{Deserialize, Serialize};
+use url::Url;
+
+/// Configuration for periodically pushing a compact snapshot of the node's
+/// own metrics to an HTTP endpoint (e.g., for operators that do not run a
+/// full Prometheus stack).
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MetricsPushConfig {
+    /// Whether or not to push metric snapshots
+    pub enabled: bool,
+    /// The endpoint to POST the JSON encoded snapshots to
+    pub endpoint: Option<Url>,
+    /// The interval (secs) between pushes
+    pub push_interval_secs: u64,
+    /// The timeout (ms) of each push
+    pub push_timeout_ms: u64,
+    /// Only metrics with names starting with one of these prefixes are pushed
+    pub metric_prefixes: Vec<String>,
+}
+
+impl Default for MetricsPushConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: None,
+            push_interval_secs: 60,
+            push_timeout_ms: 10_000,
+            metric_prefixes: vec![
+                "aptos_consensus_".into(),
+                "aptos_quorum_store_".into(),
+                "quorum_store_".into(),
+                "aptos_core_mempool_".into(),
+                "aptos_mempool_".into(),
+                "aptos_storage_".into(),
+                "aptos_schemadb_".into(),
+            ],
+        }
+    }
+}
+
+impl ConfigSanitizer for MetricsPushConfig {
+    fn sanitize(
+        node_config: &NodeConfig,
+        _node_type: NodeType,
+        _chain_id: Option<ChainId>,
+    ) -> Result<(), Error> {
+        let sanitizer_name = Self::get_sanitizer_name();
+        let metrics_push_config = &node_config.metrics_push;
+
+        // Nothing needs to be verified if pushing is disabled
+        if !metrics_push_config.enabled {
+            return Ok(());
+        }
+
+        // Verify that the endpoint is set
+        if metrics_push_config.endpoint.is_none() {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The metrics push endpoint must be set when pushing is enabled!".into(),
+            ));
+        }
+
+        // Verify that the push interval is non-zero
+        if metrics_push_config.push_interval_secs == 0 {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The metrics push interval must be greater than zero!".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_disabled() {
+        // Create a node config with pushing disabled and no endpoint
+        let node_config = NodeConfig {
+            metrics_push: MetricsPushConfig {
+                enabled: false,
+                endpoint: None,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config passes sanitization
+        MetricsPushConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::testnet()))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_sanitize_missing_endpoint() {
+        // Create a node config with pushing enabled but no endpoint
+        let node_config = NodeConfig {
+            metrics_push: MetricsPushConfig {
+                enabled: true,
+                endpoint: None,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that sanitization fails
+        let error = MetricsPushConfig::sanitize(
+            &node_config,
+            NodeType::Validator,
+            Some(ChainId::testnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_zero_interval() {
+        // Create a node config with a zero push interval
+        let node_config = NodeConfig {
+            metrics_push: MetricsPushConfig {
+                enabled: true,
+                endpoint: Some(Url::parse("http://127.0.0.1:9999/metrics").unwrap()),
+                push_interval_secs: 0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that sanitization fails
+        let error = MetricsPushConfig::sanitize(
+            &node_config,
+            NodeType::Validator,
+            Some(ChainId::testnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+}
@@ -9,16 +9,19 @@ mod base_config;
 mod config_optimizer;
 mod config_sanitizer;
 mod consensus_config;
+mod consensus_observer_config;
 mod dag_consensus_config;
 mod error;
 mod execution_config;
 mod gas_estimation_config;
+mod graphql_config;
 mod identity_config;
 mod indexer_config;
 mod indexer_grpc_config;
 mod inspection_service_config;
 mod logger_config;
 mod mempool_config;
+mod metrics_push_config;
 mod netbench;
 mod network_config;
 mod node_config;
@@ -27,28 +30,33 @@ mod override_node_config;
 mod peer_monitoring_config;
 mod persistable_config;
 mod quorum_store_config;
+mod request_quota_config;
 mod safety_rules_config;
 mod secure_backend_config;
 mod state_sync_config;
 mod storage_config;
 pub mod transaction_filter_type;
 mod utils;
+mod view_function_config;
 
 // All public usage statements should be declared below
 pub use admin_service_config::*;
 pub use api_config::*;
 pub use base_config::*;
 pub use consensus_config::*;
+pub use consensus_observer_config::*;
 pub use dag_consensus_config::*;
 pub use error::*;
 pub use execution_config::*;
 pub use gas_estimation_config::*;
+pub use graphql_config::*;
 pub use identity_config::*;
 pub use indexer_config::*;
 pub use indexer_grpc_config::*;
 pub use inspection_service_config::*;
 pub use logger_config::*;
 pub use mempool_config::*;
+pub use metrics_push_config::*;
 pub use netbench::*;
 pub use network_config::*;
 pub use node_config::*;
@@ -57,7 +65,9 @@ pub use override_node_config::*;
 pub use peer_monitoring_config::*;
 pub use persistable_config::*;
 pub use quorum_store_config::*;
+pub use request_quota_config::*;
 pub use safety_rules_config::*;
 pub use secure_backend_config::*;
 pub use state_sync_config::*;
 pub use storage_config::*;
+pub use view_function_config::*;
@@ -17,6 +17,7 @@ use aptos_types::{
     account_address::from_identity_public_key, network_address::NetworkAddress,
     transaction::authenticator::AuthenticationKey, PeerId,
 };
+use ipnet::IpNet;
 use rand::{
     rngs::{OsRng, StdRng},
     Rng, SeedableRng,
@@ -117,10 +118,27 @@ pub struct NetworkConfig {
     pub max_outbound_connections: usize,
     /// Maximum number of outbound connections, limited by PeerManager
     pub max_inbound_connections: usize,
+    /// Weights of the peer utility scores, used to pick the inbound peer to evict when a more
+    /// useful peer connects at the inbound connection limit
+    pub peer_utility_weights: PeerUtilityWeights,
     /// Inbound rate limiting configuration, if not specified, no rate limiting
     pub inbound_rate_limit_config: Option<RateLimitConfig>,
     /// Outbound rate limiting configuration, if not specified, no rate limiting
     pub outbound_rate_limit_config: Option<RateLimitConfig>,
+    /// Outbound bandwidth limits of the application protocols, applied to each peer. Keyed by
+    /// protocol name, e.g., "StorageServiceRpc" to cap the state sync serving bandwidth. The
+    /// other protocols aren't rate limited.
+    pub outbound_protocol_rate_limits: HashMap<String, ProtocolRateLimitConfig>,
+    /// The application protocols whose messages are zstd compressed, keyed by protocol name, with
+    /// the minimum size (in bytes) of the messages to compress. Compression is only used with the
+    /// peers that also enable it for the protocol.
+    pub zstd_compression_thresholds: HashMap<String, usize>,
+    /// Relay-assisted connection reversal, for the peers that can only dial out (e.g., behind a
+    /// NAT) to accept connections brokered through their relay peers
+    pub nat_config: NatConfig,
+    /// The peers and IP ranges that may not connect to (or be dialed by) this node. The deny list
+    /// can be replaced at runtime through the admin service.
+    pub deny_list: DenyListConfig,
     /// The maximum size of an inbound or outbound message (it may be divided into multiple frame)
     pub max_message_size: usize,
     /// The maximum number of parallel message deserialization tasks that can run (per application)
@@ -158,8 +176,13 @@ impl NetworkConfig {
             ping_failures_tolerated: PING_FAILURES_TOLERATED,
             max_outbound_connections: MAX_FULLNODE_OUTBOUND_CONNECTIONS,
             max_inbound_connections: MAX_INBOUND_CONNECTIONS,
+            peer_utility_weights: PeerUtilityWeights::default(),
             inbound_rate_limit_config: None,
             outbound_rate_limit_config: None,
+            outbound_protocol_rate_limits: HashMap::new(),
+            zstd_compression_thresholds: HashMap::new(),
+            nat_config: NatConfig::default(),
+            deny_list: DenyListConfig::default(),
             max_message_size: MAX_MESSAGE_SIZE,
             inbound_rx_buffer_size_bytes: None,
             inbound_tx_buffer_size_bytes: None,
@@ -389,6 +412,84 @@ impl Default for RateLimitConfig {
     }
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ProtocolRateLimitConfig {
+    /// Maximum number of bytes/s sent to a peer
+    pub byte_bucket_rate: usize,
+    /// Maximum burst of bytes sent to a peer
+    pub byte_bucket_size: usize,
+}
+
+/// Connection reversal through relay peers: a peer that wants to connect to an undialable peer
+/// (one advertising no addresses, only relay peers) asks a connected relay to forward its
+/// addresses to the undialable peer, which dials it back. Both peers must be connected to the
+/// relay, and the peer requesting the connection must be dialable itself.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NatConfig {
+    /// Whether to ask the relay peers of undialable peers for connections
+    pub request_relayed_connections: bool,
+    /// Whether to forward the connection requests of connected peers (e.g., on seed peers)
+    pub enable_relay: bool,
+    /// Whether to dial back the peers whose connection requests are forwarded by a relay. Only
+    /// the requests of relays with a known role are accepted.
+    pub accept_relayed_connections: bool,
+    /// Timeout of a relayed connection request, including the dial back
+    pub relay_request_timeout_ms: u64,
+}
+
+impl Default for NatConfig {
+    fn default() -> Self {
+        Self {
+            request_relayed_connections: false,
+            enable_relay: false,
+            accept_relayed_connections: false,
+            relay_request_timeout_ms: 10_000, // 10 seconds
+        }
+    }
+}
+
+/// The peers and IP ranges denied on a network. Connections with denied peers are closed, in
+/// both directions, and denied peers are never dialed.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DenyListConfig {
+    /// The denied peers
+    pub denied_peers: HashSet<PeerId>,
+    /// The denied IP ranges, in CIDR notation (e.g., "10.0.0.0/8" or "2001:db8::/32")
+    pub denied_ip_ranges: Vec<IpNet>,
+}
+
+/// The utility score of a peer is the weight of its role, plus the recency weight scaled down
+/// linearly with the time since the peer last sent us a message (or connected).
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PeerUtilityWeights {
+    /// Weight of the validator peers
+    pub validator_weight: u64,
+    /// Weight of the validator fullnode peers
+    pub vfn_weight: u64,
+    /// Weight of the other (public fullnode) peers
+    pub pfn_weight: u64,
+    /// Weight of a peer that just sent us a message
+    pub recency_weight: u64,
+    /// Time after which an idle peer loses its recency weight
+    pub recency_window_secs: u64,
+}
+
+impl Default for PeerUtilityWeights {
+    fn default() -> Self {
+        Self {
+            validator_weight: 4,
+            vfn_weight: 2,
+            pfn_weight: 1,
+            recency_weight: 1,
+            recency_window_secs: 600, // 10 minutes
+        }
+    }
+}
+
 pub type PeerSet = HashMap<PeerId, Peer>;
 
 // TODO: Combine with RoleType?
@@ -463,6 +564,8 @@ pub struct Peer {
     pub addresses: Vec<NetworkAddress>,
     pub keys: HashSet<x25519::PublicKey>,
     pub role: PeerRole,
+    /// The peers relaying connection requests to this peer, if it can't be dialed directly
+    pub relay_peers: Vec<PeerId>,
 }
 
 impl Peer {
@@ -480,6 +583,7 @@ impl Peer {
             addresses,
             keys,
             role,
+            relay_peers: Vec::new(),
         }
     }
 
@@ -494,6 +598,7 @@ impl Peer {
         }
         self.addresses.extend(other.addresses);
         self.keys.extend(other.keys);
+        self.relay_peers.extend(other.relay_peers);
         Ok(())
     }
 
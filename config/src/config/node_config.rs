@@ -6,9 +6,10 @@ use crate::{
     config::{
         netbench::NetbenchConfig, node_config_loader::NodeConfigLoader,
         persistable_config::PersistableConfig, utils::RootPath, AdminServiceConfig, ApiConfig,
-        BaseConfig, ConsensusConfig, Error, ExecutionConfig, IndexerConfig, IndexerGrpcConfig,
-        InspectionServiceConfig, LoggerConfig, MempoolConfig, NetworkConfig,
-        PeerMonitoringServiceConfig, SafetyRulesTestConfig, StateSyncConfig, StorageConfig,
+        BaseConfig, ConsensusConfig, ConsensusObserverConfig, Error, ExecutionConfig,
+        IndexerConfig, IndexerGrpcConfig, InspectionServiceConfig, LoggerConfig, MempoolConfig,
+        MetricsPushConfig, NetworkConfig, PeerMonitoringServiceConfig, SafetyRulesTestConfig,
+        StateSyncConfig, StorageConfig,
     },
     network_id::NetworkId,
 };
@@ -38,6 +39,8 @@ pub struct NodeConfig {
     #[serde(default)]
     pub consensus: ConsensusConfig,
     #[serde(default)]
+    pub consensus_observer: ConsensusObserverConfig,
+    #[serde(default)]
     pub dag_consensus: DagConsensusConfig,
     #[serde(default)]
     pub execution: ExecutionConfig,
@@ -56,6 +59,8 @@ pub struct NodeConfig {
     #[serde(default)]
     pub mempool: MempoolConfig,
     #[serde(default)]
+    pub metrics_push: MetricsPushConfig,
+    #[serde(default)]
     pub netbench: Option<NetbenchConfig>,
     #[serde(default)]
     pub peer_monitoring_service: PeerMonitoringServiceConfig,
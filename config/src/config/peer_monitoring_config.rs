@@ -21,6 +21,7 @@ pub struct PeerMonitoringServiceConfig {
     pub metadata_update_interval_ms: u64, // The interval (ms) between metadata updates
     pub network_monitoring: NetworkMonitoringConfig,
     pub node_monitoring: NodeMonitoringConfig,
+    pub peer_history: PeerHistoryConfig,
     pub peer_monitor_interval_usec: u64, // The interval (usec) between peer monitor executions
     pub performance_monitoring: PerformanceMonitoringConfig,
 }
@@ -37,6 +38,7 @@ impl Default for PeerMonitoringServiceConfig {
             metadata_update_interval_ms: 5000,  // 5 seconds
             network_monitoring: NetworkMonitoringConfig::default(),
             node_monitoring: NodeMonitoringConfig::default(),
+            peer_history: PeerHistoryConfig::default(),
             peer_monitor_interval_usec: 1_000_000, // 1 second
             performance_monitoring: PerformanceMonitoringConfig::default(),
         }
@@ -79,6 +81,28 @@ impl Default for NetworkMonitoringConfig {
     }
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PeerHistoryConfig {
+    pub enable_peer_history: bool, // Whether or not to keep the latency and availability history
+    pub history_window_secs: u64,  // The window (secs) of history retained for each peer
+    pub max_num_peers: usize,      // The max number of peers with a retained history
+    pub max_num_samples_per_peer: usize, // The max number of latency pings retained per peer
+    pub persist_interval_ms: u64,  // The interval (ms) between writes of the history to disk
+}
+
+impl Default for PeerHistoryConfig {
+    fn default() -> Self {
+        Self {
+            enable_peer_history: true,
+            history_window_secs: 6 * 60 * 60, // 6 hours
+            max_num_peers: 1000,
+            max_num_samples_per_peer: 720, // A latency ping every 30 seconds for 6 hours
+            persist_interval_ms: 60_000,   // 1 minute
+        }
+    }
+}
+
 // TODO: add support for direct send test mode!
 
 // Note: to enable performance monitoring, the compilation feature "network-perf-test" is required.
@@ -38,6 +38,80 @@ impl Default for QuorumStoreBackPressureConfig {
     }
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct QuorumStoreBatchRequestScoringConfig {
+    /// If enabled, batch requests are biased towards peers that recently responded
+    /// successfully and fast, instead of being spread uniformly over the signers.
+    pub enabled: bool,
+    /// Weight of the latest response in the moving averages of a peer's success rate and
+    /// latency.
+    pub moving_average_weight: f64,
+    /// Lower bound on a peer's score, so that badly performing peers are eventually retried.
+    pub min_score: f64,
+    /// Maximum number of outstanding batch requests to a single peer, 0 for no limit.
+    pub max_inflight_requests_per_peer: usize,
+}
+
+impl Default for QuorumStoreBatchRequestScoringConfig {
+    fn default() -> QuorumStoreBatchRequestScoringConfig {
+        QuorumStoreBatchRequestScoringConfig {
+            enabled: true,
+            moving_average_weight: 0.2,
+            min_score: 0.05,
+            max_inflight_requests_per_peer: 50,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct QuorumStoreDynamicQuotaConfig {
+    /// If enabled, the per-validator batch store quotas are periodically recomputed from
+    /// the observed memory and disk availability, instead of using the static quotas.
+    pub enabled: bool,
+    pub sample_interval_ms: u64,
+    /// Fraction of the memory available to the node that the batch store may use in total.
+    pub max_memory_fraction: f64,
+    /// Fraction of the disk space available to the quorum store db that the batch store may
+    /// use in total.
+    pub max_disk_fraction: f64,
+    pub min_memory_quota: usize,
+    pub max_memory_quota: usize,
+    pub min_db_quota: usize,
+    pub max_db_quota: usize,
+    pub min_batch_quota: usize,
+    pub max_batch_quota: usize,
+}
+
+impl Default for QuorumStoreDynamicQuotaConfig {
+    fn default() -> QuorumStoreDynamicQuotaConfig {
+        QuorumStoreDynamicQuotaConfig {
+            enabled: false,
+            sample_interval_ms: 10_000,
+            max_memory_fraction: 0.25,
+            max_disk_fraction: 0.25,
+            min_memory_quota: 20_000_000,
+            max_memory_quota: 500_000_000,
+            min_db_quota: 50_000_000,
+            max_db_quota: 1_000_000_000,
+            min_batch_quota: 50_000,
+            max_batch_quota: 1_000_000,
+        }
+    }
+}
+
+/// The order in which proofs of store are pulled from the proof queue into a block proposal.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProofPullPolicy {
+    /// Round-robin across (shuffled) batch authors, each author's highest gas bucket first.
+    AuthorRoundRobin,
+    /// Highest gas bucket first across all authors, round-robin across authors within a
+    /// gas bucket. Gets high-fee transactions into blocks faster during congestion.
+    HighestGasBucketFirst,
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct QuorumStoreConfig {
@@ -60,15 +134,21 @@ pub struct QuorumStoreConfig {
     pub batch_request_retry_limit: usize,
     pub batch_request_retry_interval_ms: usize,
     pub batch_request_rpc_timeout_ms: usize,
+    pub batch_request_scoring: QuorumStoreBatchRequestScoringConfig,
     /// Used when setting up the expiration time for the batch initation.
     pub batch_expiry_gap_when_init_usecs: u64,
     pub memory_quota: usize,
     pub db_quota: usize,
     pub batch_quota: usize,
+    /// Interval at which expired batches are garbage collected and the expiration
+    /// watermark is persisted.
+    pub batch_gc_interval_ms: u64,
+    pub dynamic_quota: QuorumStoreDynamicQuotaConfig,
     pub mempool_txn_pull_max_bytes: u64,
     pub back_pressure: QuorumStoreBackPressureConfig,
     pub num_workers_for_remote_batches: usize,
     pub batch_buckets: Vec<u64>,
+    pub proof_pull_policy: ProofPullPolicy,
 }
 
 impl Default for QuorumStoreConfig {
@@ -93,15 +173,19 @@ impl Default for QuorumStoreConfig {
             batch_request_retry_limit: 10,
             batch_request_retry_interval_ms: 1000,
             batch_request_rpc_timeout_ms: 5000,
+            batch_request_scoring: QuorumStoreBatchRequestScoringConfig::default(),
             batch_expiry_gap_when_init_usecs: Duration::from_secs(60).as_micros() as u64,
             memory_quota: 120_000_000,
             db_quota: 300_000_000,
             batch_quota: 300_000,
+            batch_gc_interval_ms: 60_000,
+            dynamic_quota: QuorumStoreDynamicQuotaConfig::default(),
             mempool_txn_pull_max_bytes: 4 * 1024 * 1024,
             back_pressure: QuorumStoreBackPressureConfig::default(),
             // number of batch coordinators to handle QS batch messages, should be >= 1
             num_workers_for_remote_batches: 10,
             batch_buckets: DEFAULT_BUCKETS.to_vec(),
+            proof_pull_policy: ProofPullPolicy::AuthorRoundRobin,
         }
     }
 }
@@ -180,6 +264,76 @@ impl QuorumStoreConfig {
         }
         Ok(())
     }
+
+    fn sanitize_batch_request_scoring(
+        sanitizer_name: &str,
+        config: &QuorumStoreBatchRequestScoringConfig,
+    ) -> Result<(), Error> {
+        let fractions = [
+            (config.moving_average_weight, "moving_average_weight"),
+            (config.min_score, "min_score"),
+        ];
+        for (fraction, label) in &fractions {
+            if *fraction <= 0.0 || *fraction > 1.0 {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name.to_owned(),
+                    format!(
+                        "Failed batch request scoring {}: {} not in (0, 1]",
+                        label, *fraction
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn sanitize_dynamic_quota(
+        sanitizer_name: &str,
+        config: &QuorumStoreDynamicQuotaConfig,
+    ) -> Result<(), Error> {
+        if !config.enabled {
+            return Ok(());
+        }
+
+        let min_max_pairs = [
+            (
+                config.min_memory_quota,
+                config.max_memory_quota,
+                "memory_quota",
+            ),
+            (config.min_db_quota, config.max_db_quota, "db_quota"),
+            (
+                config.min_batch_quota,
+                config.max_batch_quota,
+                "batch_quota",
+            ),
+        ];
+        for (min, max, label) in &min_max_pairs {
+            if *min > *max {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name.to_owned(),
+                    format!("Failed dynamic {}: min {} > max {}", label, *min, *max),
+                ));
+            }
+        }
+
+        let fractions = [
+            (config.max_memory_fraction, "max_memory_fraction"),
+            (config.max_disk_fraction, "max_disk_fraction"),
+        ];
+        for (fraction, label) in &fractions {
+            if *fraction <= 0.0 || *fraction > 1.0 {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name.to_owned(),
+                    format!(
+                        "Failed dynamic quota {}: {} not in (0, 1]",
+                        label, *fraction
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
 }
 
 impl ConfigSanitizer for QuorumStoreConfig {
@@ -199,6 +353,18 @@ impl ConfigSanitizer for QuorumStoreConfig {
         // Sanitize the batch total limits
         Self::sanitize_batch_total_limits(&sanitizer_name, &node_config.consensus.quorum_store)?;
 
+        // Sanitize the batch request scoring parameters
+        Self::sanitize_batch_request_scoring(
+            &sanitizer_name,
+            &node_config.consensus.quorum_store.batch_request_scoring,
+        )?;
+
+        // Sanitize the dynamic quota bounds
+        Self::sanitize_dynamic_quota(
+            &sanitizer_name,
+            &node_config.consensus.quorum_store.dynamic_quota,
+        )?;
+
         Ok(())
     }
 }
@@ -396,4 +562,56 @@ mod test {
             QuorumStoreConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
+
+    #[test]
+    fn test_dynamic_quota_bounds() {
+        // Create a node config with an invalid dynamic memory quota range
+        let node_config = NodeConfig {
+            consensus: ConsensusConfig {
+                quorum_store: QuorumStoreConfig {
+                    dynamic_quota: QuorumStoreDynamicQuotaConfig {
+                        enabled: true,
+                        min_memory_quota: 200,
+                        max_memory_quota: 100,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = QuorumStoreConfig::sanitize(
+            &node_config,
+            NodeType::Validator,
+            Some(ChainId::mainnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_batch_request_scoring_bounds() {
+        // Create a node config with an invalid batch request scoring weight
+        let node_config = NodeConfig {
+            consensus: ConsensusConfig {
+                quorum_store: QuorumStoreConfig {
+                    batch_request_scoring: QuorumStoreBatchRequestScoringConfig {
+                        moving_average_weight: 1.5,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error =
+            QuorumStoreConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
 }
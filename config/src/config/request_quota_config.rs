@@ -0,0 +1,166 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::{
+    config_sanitizer::ConfigSanitizer, node_config_loader::NodeType, Error, NodeConfig,
+};
+use aptos_types::chain_id::ChainId;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const DEFAULT_READ_BUCKET_RATE: usize = 100;
+const DEFAULT_READ_BUCKET_SIZE: usize = 200;
+const DEFAULT_WRITE_BUCKET_RATE: usize = 10;
+const DEFAULT_WRITE_BUCKET_SIZE: usize = 20;
+
+/// Quotas on the requests served by the API to each client, enforced by token buckets
+/// refilled every second. Clients are identified by their API key, if it's configured,
+/// and otherwise by their IP address.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RequestQuotaConfig {
+    /// Enables the request quotas
+    pub enabled: bool,
+    /// Budget of each client IP address
+    pub ip_budget: RequestBudgetConfig,
+    /// Budget of each API key, sent by the clients in the `x-aptos-api-key` header
+    pub api_key_budgets: BTreeMap<String, RequestBudgetConfig>,
+}
+
+/// The budget of a single client. Writes (i.e., transaction submissions) are budgeted
+/// separately from all the other requests.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RequestBudgetConfig {
+    /// Maximum number of read requests/s
+    pub read_bucket_rate: usize,
+    /// Maximum burst of read requests
+    pub read_bucket_size: usize,
+    /// Maximum number of write requests/s
+    pub write_bucket_rate: usize,
+    /// Maximum burst of write requests
+    pub write_bucket_size: usize,
+}
+
+impl Default for RequestBudgetConfig {
+    fn default() -> RequestBudgetConfig {
+        RequestBudgetConfig {
+            read_bucket_rate: DEFAULT_READ_BUCKET_RATE,
+            read_bucket_size: DEFAULT_READ_BUCKET_SIZE,
+            write_bucket_rate: DEFAULT_WRITE_BUCKET_RATE,
+            write_bucket_size: DEFAULT_WRITE_BUCKET_SIZE,
+        }
+    }
+}
+
+impl RequestBudgetConfig {
+    /// Token buckets can't be empty, nor refilled faster than their size
+    fn is_valid(&self) -> bool {
+        self.read_bucket_rate > 0
+            && self.read_bucket_size >= self.read_bucket_rate
+            && self.write_bucket_rate > 0
+            && self.write_bucket_size >= self.write_bucket_rate
+    }
+}
+
+impl ConfigSanitizer for RequestQuotaConfig {
+    fn sanitize(
+        node_config: &NodeConfig,
+        _node_type: NodeType,
+        _chain_id: Option<ChainId>,
+    ) -> Result<(), Error> {
+        let sanitizer_name = Self::get_sanitizer_name();
+        let request_quota_config = &node_config.api.request_quota;
+
+        // If the quotas are disabled, the budgets are never used
+        if !request_quota_config.enabled {
+            return Ok(());
+        }
+
+        if !request_quota_config.ip_budget.is_valid() {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "The IP budget {:?} must have rates > 0 and bucket sizes >= rates",
+                    request_quota_config.ip_budget
+                ),
+            ));
+        }
+        if request_quota_config
+            .api_key_budgets
+            .values()
+            .any(|budget| !budget.is_valid())
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The API key budgets must have rates > 0 and bucket sizes >= rates".into(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ApiConfig;
+
+    #[test]
+    fn test_sanitize_invalid_api_key_budget() {
+        // Create a node config with an API key budget refilled faster than its size
+        let node_config = NodeConfig {
+            api: ApiConfig {
+                request_quota: RequestQuotaConfig {
+                    enabled: true,
+                    api_key_budgets: [("key".to_owned(), RequestBudgetConfig {
+                        write_bucket_rate: 10,
+                        write_bucket_size: 5,
+                        ..Default::default()
+                    })]
+                    .into_iter()
+                    .collect(),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = RequestQuotaConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::mainnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_disabled_quota() {
+        // Create a node config with an invalid IP budget, but with the quotas disabled
+        let node_config = NodeConfig {
+            api: ApiConfig {
+                request_quota: RequestQuotaConfig {
+                    enabled: false,
+                    ip_budget: RequestBudgetConfig {
+                        read_bucket_rate: 0,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it succeeds
+        RequestQuotaConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::mainnet()),
+        )
+        .unwrap();
+    }
+}
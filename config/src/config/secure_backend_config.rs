@@ -3,7 +3,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::config::Error;
-use aptos_secure_storage::{InMemoryStorage, Namespaced, OnDiskStorage, Storage, VaultStorage};
+use aptos_secure_storage::{
+    InMemoryStorage, Namespaced, OnDiskStorage, Pkcs11Storage, Storage, VaultStorage,
+};
 use serde::{Deserialize, Serialize};
 use std::{
     fs::File,
@@ -20,15 +22,15 @@ pub enum SecureBackend {
     InMemoryStorage,
     Vault(VaultConfig),
     OnDiskStorage(OnDiskStorageConfig),
+    Pkcs11(Pkcs11Config),
 }
 
 impl SecureBackend {
     pub fn namespace(&self) -> Option<&str> {
         match self {
             SecureBackend::Vault(VaultConfig { namespace, .. })
-            | SecureBackend::OnDiskStorage(OnDiskStorageConfig { namespace, .. }) => {
-                namespace.as_deref()
-            },
+            | SecureBackend::OnDiskStorage(OnDiskStorageConfig { namespace, .. })
+            | SecureBackend::Pkcs11(Pkcs11Config { namespace, .. }) => namespace.as_deref(),
             SecureBackend::InMemoryStorage => None,
         }
     }
@@ -36,7 +38,8 @@ impl SecureBackend {
     pub fn clear_namespace(&mut self) {
         match self {
             SecureBackend::Vault(VaultConfig { namespace, .. })
-            | SecureBackend::OnDiskStorage(OnDiskStorageConfig { namespace, .. }) => {
+            | SecureBackend::OnDiskStorage(OnDiskStorageConfig { namespace, .. })
+            | SecureBackend::Pkcs11(Pkcs11Config { namespace, .. }) => {
                 *namespace = None;
             },
             SecureBackend::InMemoryStorage => {},
@@ -97,6 +100,24 @@ pub struct OnDiskStorageConfig {
     data_dir: PathBuf,
 }
 
+/// Keys and data are held by a hardware security module (HSM), accessed through its PKCS#11
+/// module. Ed25519 keys never leave the device, but the consensus key is stored as readable data
+/// and signs in memory; see `Pkcs11Storage` for details.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Pkcs11Config {
+    /// Full path to the PKCS#11 module of the HSM vendor, e.g., /usr/lib/softhsm/libsofthsm2.so
+    pub library_path: PathBuf,
+    /// The slot holding the token to use
+    pub slot: u64,
+    /// The user PIN of the token
+    pub pin: Token,
+    /// A namespace is an optional prefix of the labels of the objects stored on the token. For
+    /// example, a key, S, without a namespace would be labeled S, with a namespace, N, it would be
+    /// labeled N/S.
+    pub namespace: Option<String>,
+}
+
 /// Tokens can either be directly within this config or stored somewhere on disk.
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -191,6 +212,18 @@ impl From<&SecureBackend> for Storage {
                     storage
                 }
             },
+            SecureBackend::Pkcs11(config) => {
+                let pin = config.pin.read_token().expect("Unable to read PIN");
+                let storage = Storage::from(
+                    Pkcs11Storage::new(&config.library_path, config.slot, pin.trim().to_string())
+                        .expect("Unable to open PKCS#11 token"),
+                );
+                if let Some(namespace) = &config.namespace {
+                    Storage::from(Namespaced::new(namespace, Box::new(storage)))
+                } else {
+                    storage
+                }
+            },
         }
     }
 }
@@ -290,6 +323,30 @@ vault:
         serde_yaml::to_string(&from_disk).unwrap();
     }
 
+    #[test]
+    fn test_pkcs11_parsing() {
+        let pkcs11 = SecureBackend::Pkcs11(Pkcs11Config {
+            library_path: PathBuf::from("/usr/lib/softhsm/libsofthsm2.so"),
+            slot: 7,
+            pin: Token::FromDisk(PathBuf::from("/pin")),
+            namespace: Some("safety_rules".to_string()),
+        });
+
+        let text_pkcs11 = r#"
+type: pkcs11
+library_path: "/usr/lib/softhsm/libsofthsm2.so"
+slot: 7
+pin:
+    from_disk: "/pin"
+namespace: "safety_rules"
+        "#;
+
+        let de_pkcs11: SecureBackend = serde_yaml::from_str(text_pkcs11).unwrap();
+        assert_eq!(de_pkcs11, pkcs11);
+        assert_eq!(de_pkcs11.namespace(), Some("safety_rules"));
+        serde_yaml::to_string(&pkcs11).unwrap();
+    }
+
     #[test]
     fn test_token_reading() {
         let temppath = aptos_temppath::TempPath::new();
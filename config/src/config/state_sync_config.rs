@@ -118,6 +118,9 @@ pub struct StateSyncDriverConfig {
     pub max_stream_wait_time_ms: u64,
     /// The maximum time (ms) allowed for mempool to ack a commit notification
     pub mempool_commit_ack_timeout_ms: u64,
+    /// The number of workers verifying the proofs of transaction output
+    /// chunks, ahead of (and in parallel with) applying earlier chunks.
+    pub num_proof_verification_workers: u64,
     /// The version lag we'll tolerate before snapshot syncing
     pub num_versions_to_skip_snapshot_sync: u64,
 }
@@ -139,6 +142,7 @@ impl Default for StateSyncDriverConfig {
             max_pending_data_chunks: 100,
             max_stream_wait_time_ms: 5000,
             mempool_commit_ack_timeout_ms: 5000, // 5 seconds
+            num_proof_verification_workers: 8,
             num_versions_to_skip_snapshot_sync: 100_000_000, // At 5k TPS, this allows a node to fail for about 6 hours.
         }
     }
@@ -149,6 +153,8 @@ impl Default for StateSyncDriverConfig {
 pub struct StorageServiceConfig {
     /// Maximum number of concurrent storage server tasks
     pub max_concurrent_requests: u64,
+    /// Maximum number of concurrent requests served (per peer)
+    pub max_concurrent_requests_per_peer: u64,
     /// Maximum number of epoch ending ledger infos per chunk
     pub max_epoch_chunk_size: u64,
     /// Maximum number of invalid requests per peer
@@ -163,6 +169,8 @@ pub struct StorageServiceConfig {
     pub max_num_active_subscriptions: u64,
     /// Maximum period (ms) of pending optimistic fetch requests
     pub max_optimistic_fetch_period_ms: u64,
+    /// Maximum number of bytes served per second before peers are limited to their fair share
+    pub max_serving_bytes_per_second: u64,
     /// Maximum number of state keys and values per chunk
     pub max_state_chunk_size: u64,
     /// Maximum period (ms) of pending subscription requests
@@ -183,6 +191,7 @@ impl Default for StorageServiceConfig {
     fn default() -> Self {
         Self {
             max_concurrent_requests: 4000,
+            max_concurrent_requests_per_peer: 100,
             max_epoch_chunk_size: MAX_EPOCH_CHUNK_SIZE,
             max_invalid_requests_per_peer: 500,
             max_lru_cache_size: 500, // At ~0.6MiB per chunk, this should take no more than 0.5GiB
@@ -190,6 +199,7 @@ impl Default for StorageServiceConfig {
             max_network_chunk_bytes: MAX_MESSAGE_SIZE as u64,
             max_num_active_subscriptions: 30,
             max_optimistic_fetch_period_ms: 5000, // 5 seconds
+            max_serving_bytes_per_second: 100 * 1024 * 1024, // 100 MiB/s
             max_state_chunk_size: MAX_STATE_CHUNK_SIZE,
             max_subscription_period_ms: 30_000, // 30 seconds
             max_transaction_chunk_size: MAX_TRANSACTION_CHUNK_SIZE,
@@ -295,6 +305,9 @@ impl Default for AptosDataPollerConfig {
 pub struct AptosDataClientConfig {
     /// The aptos data poller config for the data client
     pub data_poller_config: AptosDataPollerConfig,
+    /// Whether or not to select peers for data requests by their observed
+    /// throughput and error rates (instead of only by their latencies)
+    pub enable_throughput_peer_selection: bool,
     /// The reduction factor for latency filtering when selecting peers
     pub latency_filtering_reduction_factor: u64,
     /// The interval (milliseconds) at which to refresh the latency monitor
@@ -305,6 +318,8 @@ pub struct AptosDataClientConfig {
     pub max_num_output_reductions: u64,
     /// Maximum lag (in seconds) we'll tolerate when sending optimistic fetch requests
     pub max_optimistic_fetch_lag_secs: u64,
+    /// Maximum error rate (in percent) of a peer before it is put on probation
+    pub max_peer_error_rate_percent: u64,
     /// Maximum timeout (in ms) when waiting for a response (after exponential increases)
     pub max_response_timeout_ms: u64,
     /// Maximum number of state keys and values per chunk
@@ -321,8 +336,14 @@ pub struct AptosDataClientConfig {
     pub min_peers_for_latency_filtering: u64,
     /// Timeout (in ms) when waiting for an optimistic fetch response
     pub optimistic_fetch_timeout_ms: u64,
+    /// Duration (in seconds) a peer is not selected for data requests after being put on probation
+    pub peer_probation_duration_secs: u64,
+    /// Duration (in seconds) the selected peer is preferred for data requests
+    pub peer_stickiness_duration_secs: u64,
     /// First timeout (in ms) when waiting for a response
     pub response_timeout_ms: u64,
+    /// The multiplier of the preferred (sticky) peer's weight when selecting peers
+    pub sticky_peer_weight_multiplier: u64,
     /// Timeout (in ms) when waiting for a subscription response
     pub subscription_response_timeout_ms: u64,
     /// Whether or not to request compression for incoming data
@@ -333,12 +354,14 @@ impl Default for AptosDataClientConfig {
     fn default() -> Self {
         Self {
             data_poller_config: AptosDataPollerConfig::default(),
+            enable_throughput_peer_selection: true,
             latency_filtering_reduction_factor: 2, // Only consider the best 50% of peers
             latency_monitor_loop_interval_ms: 100,
             max_epoch_chunk_size: MAX_EPOCH_CHUNK_SIZE,
             max_num_output_reductions: 0,
             max_optimistic_fetch_lag_secs: 30, // 30 seconds
-            max_response_timeout_ms: 60_000,   // 60 seconds
+            max_peer_error_rate_percent: 50,
+            max_response_timeout_ms: 60_000, // 60 seconds
             max_state_chunk_size: MAX_STATE_CHUNK_SIZE,
             max_subscription_lag_secs: 30, // 30 seconds
             max_transaction_chunk_size: MAX_TRANSACTION_CHUNK_SIZE,
@@ -346,7 +369,10 @@ impl Default for AptosDataClientConfig {
             min_peer_ratio_for_latency_filtering: 5, // Only filter if we have at least 5 potential peers per request
             min_peers_for_latency_filtering: 10, // Only filter if we have at least 10 total peers
             optimistic_fetch_timeout_ms: 5000,   // 5 seconds
+            peer_probation_duration_secs: 60,    // 1 minute
+            peer_stickiness_duration_secs: 30,   // 30 seconds
             response_timeout_ms: 10_000,         // 10 seconds
+            sticky_peer_weight_multiplier: 4,
             subscription_response_timeout_ms: 20_000, // 20 seconds (must be longer than a regular timeout because of pre-fetching)
             use_compression: true,
         }
@@ -384,6 +410,14 @@ impl ConfigSanitizer for StateSyncDriverConfig {
             ));
         }
 
+        // Verify that there's at least one proof verification worker
+        if state_sync_driver_config.num_proof_verification_workers == 0 {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The number of proof verification workers must be greater than 0!".to_string(),
+            ));
+        }
+
         Ok(())
     }
 }
@@ -689,6 +723,27 @@ mod tests {
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
 
+    #[test]
+    fn test_sanitize_no_proof_verification_workers() {
+        // Create a node config without proof verification workers
+        let node_config = NodeConfig {
+            state_sync: StateSyncConfig {
+                state_sync_driver: StateSyncDriverConfig {
+                    num_proof_verification_workers: 0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that sanitization fails
+        let error =
+            StateSyncConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::testnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
     /// Creates and returns a node config with the syncing modes set to execution
     fn create_execution_mode_config() -> NodeConfig {
         NodeConfig {
@@ -23,6 +23,16 @@ pub const DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD: usize = 1 << 13;
 
 pub const BUFFERED_STATE_TARGET_ITEMS: usize = 100_000;
 
+// The smallest prune windows that still let the node serve the data state sync needs (the state
+// merkle window only has to cover the in-flight versions). The sanitizer warns about smaller
+// windows, and they are rejected when the pruners are reconfigured at runtime.
+pub const MIN_LEDGER_PRUNE_WINDOW: u64 = 50_000_000;
+pub const MIN_STATE_MERKLE_PRUNE_WINDOW: u64 = 100_000;
+pub const MIN_EPOCH_SNAPSHOT_PRUNE_WINDOW: u64 = 50_000_000;
+
+// Bloom filters with more bits per key barely lower the false positive rate further.
+pub const MAX_BLOOM_FILTER_BITS_PER_KEY: u32 = 64;
+
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct DbPathConfig {
@@ -127,6 +137,8 @@ pub struct RocksdbConfigs {
     pub index_db_config: RocksdbConfig,
     // Note: Not ready for production use yet.
     pub enable_storage_sharding: bool,
+    /// Overrides of the options of individual column families
+    pub column_family_configs: RocksdbCfConfigs,
 }
 
 impl Default for RocksdbConfigs {
@@ -140,10 +152,54 @@ impl Default for RocksdbConfigs {
                 ..Default::default()
             },
             enable_storage_sharding: false,
+            column_family_configs: RocksdbCfConfigs::default(),
         }
     }
 }
 
+/// Column families whose RocksDB options can be tuned individually. The options of a column
+/// family apply to whichever db it's in (e.g., the state values are in the ledger db without
+/// sharding, and in the state kv db with it).
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RocksdbCfConfigs {
+    pub state_value: RocksdbCfConfig,
+    pub jellyfish_merkle_node: RocksdbCfConfig,
+    pub transaction: RocksdbCfConfig,
+}
+
+impl RocksdbCfConfigs {
+    fn iter(&self) -> impl Iterator<Item = (&'static str, &RocksdbCfConfig)> {
+        [
+            ("state_value", &self.state_value),
+            ("jellyfish_merkle_node", &self.jellyfish_merkle_node),
+            ("transaction", &self.transaction),
+        ]
+        .into_iter()
+    }
+}
+
+/// The options of a column family, which default to the ones of its db when not set.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RocksdbCfConfig {
+    /// Size of a block cache dedicated to the column family, instead of the one of the db
+    pub block_cache_size: Option<u64>,
+    /// Compression of the column family, lz4 by default
+    pub compression: Option<RocksdbCompressionType>,
+    /// Bits per key of a bloom filter on the column family, no filter by default
+    pub bloom_filter_bits_per_key: Option<u32>,
+}
+
+/// The compressions RocksDB is built with.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RocksdbCompressionType {
+    None,
+    Lz4,
+    Lz4hc,
+}
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct StorageConfig {
@@ -170,10 +226,18 @@ pub struct StorageConfig {
     /// since genesis. To recover operation after data loss, or to bootstrap a node in fast sync
     /// mode, the indexer db needs to be copied in from another node.
     pub enable_indexer: bool,
+    /// Index the events by type in AptosDB, so that the events of a type can be queried across
+    /// all accounts without running the indexer. Only the versions committed since it was last
+    /// enabled can be queried.
+    pub enable_event_type_index: bool,
     /// Fine grained control for db paths of individal databases/shards.
     /// If not specificed, will use `dir` as default.
     /// Only allowed when sharding is enabled.
     pub db_path_overrides: Option<DbPathConfig>,
+    /// Moving old ledger data to a secondary (cold) db, for archival nodes
+    pub ledger_tiering_config: LedgerTieringConfig,
+    /// Auditing the consistency of the state kv db and the state merkle db in the background
+    pub state_consistency_auditor_config: StateConsistencyAuditorConfig,
 }
 
 pub const NO_OP_STORAGE_PRUNER_CONFIG: PrunerConfig = PrunerConfig {
@@ -195,6 +259,13 @@ pub const NO_OP_STORAGE_PRUNER_CONFIG: PrunerConfig = PrunerConfig {
     },
 };
 
+pub const NO_OP_LEDGER_TIERING_CONFIG: LedgerTieringConfig = LedgerTieringConfig {
+    enable: false,
+    hot_window: 0,
+    batch_size: 0,
+    cold_db_path: None,
+};
+
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct LedgerPrunerConfig {
@@ -305,6 +376,60 @@ impl Default for EpochSnapshotPrunerConfig {
     }
 }
 
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LedgerTieringConfig {
+    /// Boolean to enable/disable moving the transactions and write sets older than the hot window
+    /// to the cold ledger db. Reads are served transparently from both dbs. This is meant for
+    /// archival nodes, so it can't be combined with the ledger pruner. Once enabled, it must stay
+    /// enabled (with the same cold db path), as the moved data is only read from the cold db.
+    pub enable: bool,
+    /// The number of latest versions whose transactions and write sets stay in the ledger db.
+    pub hot_window: u64,
+    /// Batch size of the versions moved to the cold ledger db at once.
+    pub batch_size: usize,
+    /// The directory of the cold ledger db, e.g., on a bigger and cheaper volume. If not
+    /// specified, the cold ledger db is put next to the ledger db.
+    pub cold_db_path: Option<PathBuf>,
+}
+
+impl Default for LedgerTieringConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            // Recent data stays in the ledger db, as that's what state sync and the API read most.
+            hot_window: 100_000_000,
+            batch_size: 10_000,
+            cold_db_path: None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StateConsistencyAuditorConfig {
+    /// Boolean to enable/disable periodically recomputing the state root hash of a random recent
+    /// state checkpoint from the state kv db, and comparing it with the root hash in the state
+    /// merkle db. Each audit reads all the state items, and keeps the hashes of the items of a
+    /// shard in memory, so it's expensive and meant to be run infrequently.
+    pub enable: bool,
+    /// The interval (minutes) between two audits.
+    pub audit_interval_mins: u64,
+    /// The audited state checkpoint is picked among this many latest versions. It should be well
+    /// below the prune windows, so the audited data isn't pruned during the audit.
+    pub max_audit_version_lag: u64,
+}
+
+impl Default for StateConsistencyAuditorConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            audit_interval_mins: 60,
+            max_audit_version_lag: 100_000,
+        }
+    }
+}
+
 impl Default for StorageConfig {
     fn default() -> StorageConfig {
         StorageConfig {
@@ -320,9 +445,12 @@ impl Default for StorageConfig {
             data_dir: PathBuf::from("/opt/aptos/data"),
             rocksdb_configs: RocksdbConfigs::default(),
             enable_indexer: false,
+            enable_event_type_index: false,
             db_path_overrides: None,
             buffered_state_target_items: BUFFERED_STATE_TARGET_ITEMS,
             max_num_nodes_per_lru_cache_shard: DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+            ledger_tiering_config: LedgerTieringConfig::default(),
+            state_consistency_auditor_config: StateConsistencyAuditorConfig::default(),
         }
     }
 }
@@ -494,13 +622,13 @@ impl ConfigSanitizer for StorageConfig {
             .ledger_pruner_config
             .user_pruning_window_offset;
 
-        if ledger_prune_window < 50_000_000 {
+        if ledger_prune_window < MIN_LEDGER_PRUNE_WINDOW {
             warn!("Ledger prune_window is too small, harming network data availability.");
         }
-        if state_merkle_prune_window < 100_000 {
+        if state_merkle_prune_window < MIN_STATE_MERKLE_PRUNE_WINDOW {
             warn!("State Merkle prune_window is too small, node might stop functioning.");
         }
-        if epoch_snapshot_prune_window < 50_000_000 {
+        if epoch_snapshot_prune_window < MIN_EPOCH_SNAPSHOT_PRUNE_WINDOW {
             warn!("Epoch snapshot prune_window is too small, harming network data availability.");
         }
         if user_pruning_window_offset > 1_000_000 {
@@ -516,6 +644,77 @@ impl ConfigSanitizer for StorageConfig {
             ));
         }
 
+        let ledger_tiering_config = &config.ledger_tiering_config;
+        if ledger_tiering_config.enable {
+            if config.storage_pruner_config.ledger_pruner_config.enable {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    "Ledger tiering is meant for archival nodes, the ledger pruner must be disabled."
+                        .to_string(),
+                ));
+            }
+            if ledger_tiering_config.batch_size == 0 {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    "The ledger tiering batch_size must be positive.".to_string(),
+                ));
+            }
+            if let Some(cold_db_path) = ledger_tiering_config.cold_db_path.as_ref() {
+                if !cold_db_path.is_absolute() {
+                    return Err(Error::ConfigSanitizerFailed(
+                        sanitizer_name,
+                        format!(
+                            "The cold ledger db path {cold_db_path:?} is not an absolute path."
+                        ),
+                    ));
+                }
+            }
+            if ledger_tiering_config.hot_window < MIN_LEDGER_PRUNE_WINDOW {
+                warn!("Ledger tiering hot_window is small, state sync will often read from the cold ledger db.");
+            }
+        }
+
+        let state_consistency_auditor_config = &config.state_consistency_auditor_config;
+        if state_consistency_auditor_config.enable {
+            if state_consistency_auditor_config.audit_interval_mins == 0 {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    "The state consistency audit_interval_mins must be positive.".to_string(),
+                ));
+            }
+            if config
+                .storage_pruner_config
+                .state_merkle_pruner_config
+                .enable
+                && state_consistency_auditor_config.max_audit_version_lag
+                    >= state_merkle_prune_window
+            {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    "The state consistency max_audit_version_lag must be smaller than the state merkle prune window.".to_string(),
+                ));
+            }
+        }
+
+        for (cf_name, cf_config) in config.rocksdb_configs.column_family_configs.iter() {
+            if cf_config.block_cache_size == Some(0) {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    format!("The block_cache_size of column family {cf_name} must be positive."),
+                ));
+            }
+            if let Some(bits_per_key) = cf_config.bloom_filter_bits_per_key {
+                if bits_per_key == 0 || bits_per_key > MAX_BLOOM_FILTER_BITS_PER_KEY {
+                    return Err(Error::ConfigSanitizerFailed(
+                        sanitizer_name,
+                        format!(
+                            "The bloom_filter_bits_per_key of column family {cf_name} must be between 1 and {MAX_BLOOM_FILTER_BITS_PER_KEY}."
+                        ),
+                    ));
+                }
+            }
+        }
+
         if let Some(db_path_overrides) = config.db_path_overrides.as_ref() {
             if !config.rocksdb_configs.enable_storage_sharding {
                 return Err(Error::ConfigSanitizerFailed(
@@ -573,7 +772,11 @@ impl ConfigSanitizer for StorageConfig {
 
 #[cfg(test)]
 mod test {
-    use crate::config::{PrunerConfig, ShardPathConfig, ShardedDbPathConfig};
+    use crate::config::{
+        config_sanitizer::ConfigSanitizer, node_config_loader::NodeType, Error, NodeConfig,
+        PrunerConfig, RocksdbCfConfig, RocksdbCompressionType, ShardPathConfig,
+        ShardedDbPathConfig, StorageConfig,
+    };
 
     #[test]
     pub fn test_default_prune_window() {
@@ -649,4 +852,45 @@ mod test {
 
         assert!(path_overrides.get_shard_paths().is_err());
     }
+
+    #[test]
+    pub fn test_sanitize_column_family_configs() {
+        let mut node_config = NodeConfig::default();
+        node_config
+            .storage
+            .rocksdb_configs
+            .column_family_configs
+            .state_value = RocksdbCfConfig {
+            block_cache_size: Some(1 << 30),
+            compression: Some(RocksdbCompressionType::None),
+            bloom_filter_bits_per_key: Some(10),
+        };
+        StorageConfig::sanitize(&node_config, NodeType::Validator, None).unwrap();
+
+        // An empty block cache is rejected
+        node_config
+            .storage
+            .rocksdb_configs
+            .column_family_configs
+            .transaction
+            .block_cache_size = Some(0);
+        let error = StorageConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // So is a bloom filter without any bits
+        node_config
+            .storage
+            .rocksdb_configs
+            .column_family_configs
+            .transaction
+            .block_cache_size = None;
+        node_config
+            .storage
+            .rocksdb_configs
+            .column_family_configs
+            .jellyfish_merkle_node
+            .bloom_filter_bits_per_key = Some(0);
+        let error = StorageConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
 }
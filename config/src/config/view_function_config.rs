@@ -0,0 +1,107 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::{
+    config_sanitizer::ConfigSanitizer, node_config_loader::NodeType, Error, NodeConfig,
+};
+use aptos_types::chain_id::ChainId;
+use serde::{Deserialize, Serialize};
+
+// We keep this value the same as the max number of gas allowed for one single transaction defined in aptos-gas.
+const DEFAULT_MAX_VIEW_GAS: u64 = 2_000_000;
+// Same as the call stack limit of the Move VM.
+const DEFAULT_MAX_VIEW_STACK_DEPTH: u64 = 1024;
+const DEFAULT_MAX_VIEW_RETURNED_BYTES: u64 = 4 * 1024 * 1024; // 4 MB
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ViewFunctionConfig {
+    /// Maximum gas unit limit for view functions
+    ///
+    /// This limits the execution length of a view function to the given gas used.
+    pub max_gas: u64,
+    /// Maximum depth of the call stack of a view function, counting the view function itself
+    pub max_stack_depth: u64,
+    /// Maximum total size in bytes of the BCS encoded values returned by a view function
+    pub max_returned_bytes: u64,
+}
+
+impl Default for ViewFunctionConfig {
+    fn default() -> ViewFunctionConfig {
+        ViewFunctionConfig {
+            max_gas: DEFAULT_MAX_VIEW_GAS,
+            max_stack_depth: DEFAULT_MAX_VIEW_STACK_DEPTH,
+            max_returned_bytes: DEFAULT_MAX_VIEW_RETURNED_BYTES,
+        }
+    }
+}
+
+impl ConfigSanitizer for ViewFunctionConfig {
+    fn sanitize(
+        node_config: &NodeConfig,
+        _node_type: NodeType,
+        _chain_id: Option<ChainId>,
+    ) -> Result<(), Error> {
+        let sanitizer_name = Self::get_sanitizer_name();
+        let view_function_config = &node_config.api.view_function;
+
+        // Verify that no view function can be executed with a zero budget
+        if view_function_config.max_gas == 0
+            || view_function_config.max_stack_depth == 0
+            || view_function_config.max_returned_bytes == 0
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "max gas {}, max stack depth {}, max returned bytes {} must be > 0",
+                    view_function_config.max_gas,
+                    view_function_config.max_stack_depth,
+                    view_function_config.max_returned_bytes
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ApiConfig;
+
+    #[test]
+    fn test_sanitize_zero_stack_depth() {
+        // Create a node config with a zero stack depth
+        let node_config = NodeConfig {
+            api: ApiConfig {
+                view_function: ViewFunctionConfig {
+                    max_stack_depth: 0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = ViewFunctionConfig::sanitize(
+            &node_config,
+            NodeType::Validator,
+            Some(ChainId::mainnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_default_config() {
+        // Sanitize the default config and verify that it succeeds
+        ViewFunctionConfig::sanitize(
+            &NodeConfig::default(),
+            NodeType::Validator,
+            Some(ChainId::mainnet()),
+        )
+        .unwrap();
+    }
+}
@@ -15,8 +15,11 @@ use aptos_types::{
     account_address::AccountAddress,
     block_info::BlockInfo,
     block_metadata::BlockMetadata,
+    block_metadata_ext::BlockMetadataExt,
     epoch_state::EpochState,
     ledger_info::LedgerInfo,
+    on_chain_config::BlockExecutionLimit,
+    randomness::Randomness,
     transaction::{SignedTransaction, Transaction, Version},
     validator_signer::ValidatorSigner,
     validator_verifier::ValidatorVerifier,
@@ -377,25 +380,46 @@ impl Block {
         &self,
         validators: &[AccountAddress],
         txns: Vec<SignedTransaction>,
-        block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
+        randomness: Option<Randomness>,
     ) -> Vec<Transaction> {
-        if block_gas_limit.is_some() {
+        let block_metadata_txn = self.new_block_metadata_txn(validators, randomness);
+        if block_execution_limit.is_limited() {
             // After the per-block gas limit change, StateCheckpoint txn
             // is inserted after block execution
-            once(Transaction::BlockMetadata(
-                self.new_block_metadata(validators),
-            ))
-            .chain(txns.into_iter().map(Transaction::UserTransaction))
-            .collect()
+            once(block_metadata_txn)
+                .chain(txns.into_iter().map(Transaction::UserTransaction))
+                .collect()
         } else {
             // Before the per-block gas limit change, StateCheckpoint txn
             // is inserted here for compatibility.
-            once(Transaction::BlockMetadata(
-                self.new_block_metadata(validators),
-            ))
-            .chain(txns.into_iter().map(Transaction::UserTransaction))
-            .chain(once(Transaction::StateCheckpoint(self.id)))
-            .collect()
+            once(block_metadata_txn)
+                .chain(txns.into_iter().map(Transaction::UserTransaction))
+                .chain(once(Transaction::StateCheckpoint(self.id)))
+                .collect()
+        }
+    }
+
+    /// Blocks produced with randomness carry it in the extended block metadata, all other blocks
+    /// keep using the plain block metadata.
+    fn new_block_metadata_txn(
+        &self,
+        validators: &[AccountAddress],
+        randomness: Option<Randomness>,
+    ) -> Transaction {
+        let block_metadata = self.new_block_metadata(validators);
+        match randomness {
+            Some(randomness) => Transaction::BlockMetadataExt(BlockMetadataExt::new_v1(
+                block_metadata.id(),
+                block_metadata.epoch(),
+                block_metadata.round(),
+                block_metadata.proposer(),
+                block_metadata.previous_block_votes_bitvec().clone(),
+                block_metadata.failed_proposer_indices().clone(),
+                block_metadata.timestamp_usecs(),
+                Some(randomness),
+            )),
+            None => Transaction::BlockMetadata(block_metadata),
         }
     }
 
@@ -8,19 +8,23 @@ use crate::{
         Block,
     },
     common::{Author, Payload},
+    executed_block::ExecutedBlock,
     quorum_cert::QuorumCert,
     vote_data::VoteData,
 };
 use aptos_bitvec::BitVec;
 use aptos_crypto::hash::HashValue;
+use aptos_executor_types::StateComputeResult;
 use aptos_types::{
     account_address::AccountAddress,
     aggregate_signature::PartialSignatures,
     block_info::{BlockInfo, Round},
     ledger_info::{LedgerInfo, LedgerInfoWithPartialSignatures},
-    on_chain_config::ValidatorSet,
+    on_chain_config::{BlockExecutionLimit, ValidatorSet},
+    transaction::{ExecutionStatus, Transaction, TransactionStatus},
     validator_signer::ValidatorSigner,
     validator_verifier::{random_validator_verifier, ValidatorVerifier},
+    vm_status::StatusCode,
 };
 use std::sync::Arc;
 
@@ -314,3 +318,55 @@ fn test_failed_authors_well_formed() {
         .verify_well_formed()
         .is_err());
 }
+
+#[test]
+fn test_transactions_to_commit_drops_retried_and_discarded_txns() {
+    let signer = ValidatorSigner::random(None);
+    let payload = random_payload(3);
+    let txns = match &payload {
+        Payload::DirectMempool(txns) => txns.clone(),
+        _ => unreachable!(),
+    };
+    let block = Block::new_proposal(
+        payload,
+        1,
+        aptos_infallible::duration_since_epoch().as_micros() as u64,
+        certificate_for_genesis(),
+        &signer,
+        Vec::new(),
+    )
+    .unwrap();
+    // The block metadata, the user transactions and the state checkpoint
+    let compute_status = vec![
+        TransactionStatus::Keep(ExecutionStatus::Success),
+        TransactionStatus::Keep(ExecutionStatus::Success),
+        TransactionStatus::Discard(StatusCode::SEQUENCE_NUMBER_TOO_OLD),
+        TransactionStatus::Retry,
+        TransactionStatus::Keep(ExecutionStatus::Success),
+    ];
+    let executed_block = ExecutedBlock::new(
+        block,
+        StateComputeResult::new(
+            HashValue::zero(),
+            vec![],
+            0,
+            vec![],
+            0,
+            None,
+            compute_status,
+            vec![],
+            vec![],
+        ),
+    );
+
+    // The recent commit cache of the proposer relies on only seeing the kept user transactions
+    let committed_user_txns: Vec<_> = executed_block
+        .transactions_to_commit(&[], txns.clone(), BlockExecutionLimit::no_limit())
+        .into_iter()
+        .filter_map(|txn| match txn {
+            Transaction::UserTransaction(txn) => Some(txn),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(committed_user_txns, vec![txns[0].clone()]);
+}
@@ -14,6 +14,8 @@ use aptos_types::{
     account_address::AccountAddress,
     block_info::BlockInfo,
     contract_event::ContractEvent,
+    on_chain_config::BlockExecutionLimit,
+    randomness::Randomness,
     transaction::{SignedTransaction, Transaction, TransactionStatus},
 };
 use std::fmt::{Debug, Display, Formatter};
@@ -29,6 +31,8 @@ pub struct ExecutedBlock {
     /// the tree. The execution results are not persisted: they're recalculated again for the
     /// pending blocks upon restart.
     state_compute_result: StateComputeResult,
+    /// The randomness of the block, set once the validators aggregated it, before execution.
+    randomness: Option<Randomness>,
 }
 
 impl ExecutedBlock {
@@ -55,9 +59,19 @@ impl ExecutedBlock {
         Self {
             block,
             state_compute_result,
+            randomness: None,
         }
     }
 
+    pub fn set_randomness(&mut self, randomness: Randomness) {
+        assert!(self.randomness.is_none());
+        self.randomness = Some(randomness);
+    }
+
+    pub fn randomness(&self) -> Option<&Randomness> {
+        self.randomness.as_ref()
+    }
+
     pub fn block(&self) -> &Block {
         &self.block
     }
@@ -115,7 +129,7 @@ impl ExecutedBlock {
         &self,
         validators: &[AccountAddress],
         txns: Vec<SignedTransaction>,
-        block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> Vec<Transaction> {
         // reconfiguration suffix don't execute
 
@@ -123,10 +137,13 @@ impl ExecutedBlock {
             return vec![];
         }
 
-        let mut txns_with_state_checkpoint =
-            self.block
-                .transactions_to_execute(validators, txns, block_gas_limit);
-        if block_gas_limit.is_some() && !self.state_compute_result.has_reconfiguration() {
+        let mut txns_with_state_checkpoint = self.block.transactions_to_execute(
+            validators,
+            txns,
+            block_execution_limit,
+            self.randomness.clone(),
+        );
+        if block_execution_limit.is_limited() && !self.state_compute_result.has_reconfiguration() {
             // After the per-block gas limit change,
             // insert state checkpoint at the position
             // 1) after last txn if there is no Retry
@@ -238,6 +238,7 @@ pub enum SignedBatchInfoError {
     WrongInfo,
     DuplicatedSignature,
     InvalidAuthor,
+    FailedToAggregate,
 }
 
 #[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
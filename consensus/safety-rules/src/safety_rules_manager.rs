@@ -11,13 +11,20 @@ use crate::{
     thread::ThreadService,
     SafetyRules, TSafetyRules,
 };
-use aptos_config::config::{InitialSafetyRulesConfig, SafetyRulesConfig, SafetyRulesService};
+use aptos_config::config::{
+    InitialSafetyRulesConfig, SafetyRulesConfig, SafetyRulesService, SecureBackend,
+};
 use aptos_infallible::RwLock;
+use aptos_logger::warn;
 use aptos_secure_storage::{KVStorage, Storage};
 use std::{convert::TryInto, net::SocketAddr, sync::Arc};
 
 pub fn storage(config: &SafetyRulesConfig) -> PersistentSafetyStorage {
     let backend = &config.backend;
+    if let SecureBackend::Pkcs11(_) = backend {
+        // PKCS#11 can't sign with BLS12-381 keys, so the consensus key doesn't stay on the device
+        warn!("The consensus key is read from the PKCS#11 token and signs in memory");
+    }
     let internal_storage: Storage = backend.try_into().expect("Unable to initialize storage");
     if let Err(error) = internal_storage.available() {
         panic!("Storage is not available: {:?}", error);
@@ -0,0 +1,74 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_metrics_core::{
+    register_int_counter, register_int_counter_vec, register_int_gauge, IntCounter, IntCounterVec,
+    IntGauge,
+};
+use once_cell::sync::Lazy;
+
+/// Counter of the messages received by the observer, by message type
+pub static OBSERVER_RECEIVED_MESSAGES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_consensus_observer_received_messages",
+        "Counters of the messages received by the consensus observer",
+        &["message_type"]
+    )
+    .unwrap()
+});
+
+/// Counter of the messages dropped by the observer, by reason
+pub static OBSERVER_DROPPED_MESSAGES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_consensus_observer_dropped_messages",
+        "Counters of the messages dropped by the consensus observer",
+        &["reason"]
+    )
+    .unwrap()
+});
+
+/// Number of ordered blocks executed by the observer and waiting for a commit decision
+pub static OBSERVER_PENDING_BLOCKS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_consensus_observer_pending_blocks",
+        "Number of ordered blocks waiting for a commit decision"
+    )
+    .unwrap()
+});
+
+/// Round of the last commit decision applied by the observer
+pub static OBSERVER_COMMITTED_ROUND: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_consensus_observer_committed_round",
+        "Round of the last commit decision applied by the consensus observer"
+    )
+    .unwrap()
+});
+
+/// Counter of the times the observer fell back to state sync
+pub static OBSERVER_STATE_SYNC_FALLBACKS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_consensus_observer_state_sync_fallbacks",
+        "Counter of the times the consensus observer fell back to state sync"
+    )
+    .unwrap()
+});
+
+/// Counter of the messages sent by the publisher, by message type
+pub static PUBLISHER_SENT_MESSAGES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_consensus_publisher_sent_messages",
+        "Counters of the messages sent by the consensus publisher",
+        &["message_type"]
+    )
+    .unwrap()
+});
+
+/// Number of peers subscribed to the publisher
+pub static PUBLISHER_NUM_SUBSCRIBERS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_consensus_publisher_num_subscribers",
+        "Number of peers subscribed to the consensus publisher"
+    )
+    .unwrap()
+});
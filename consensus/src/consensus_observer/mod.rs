@@ -0,0 +1,15 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! The consensus observer lets fullnodes follow consensus without participating in it.
+//!
+//! Publishers (validators, or observers that republish to their own downstream peers)
+//! forward the blocks ordered by consensus, along with their transactions, and the commit
+//! decisions to their subscribers. Observers execute the ordered blocks as soon as they
+//! arrive, and commit them once the matching commit decision is received. Whenever an
+//! observer misses blocks, it falls back to state sync up to the latest commit decision.
+
+mod counters;
+pub mod network;
+pub mod observer;
+pub mod publisher;
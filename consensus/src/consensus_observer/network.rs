@@ -0,0 +1,335 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::quorum_store::types::BatchPayload;
+use anyhow::ensure;
+use aptos_consensus_types::{block::Block, common::Payload};
+use aptos_crypto::hash::CryptoHash;
+use aptos_types::{
+    epoch_state::EpochState, ledger_info::LedgerInfoWithSignatures, randomness::Randomness,
+    transaction::SignedTransaction,
+};
+use serde::{Deserialize, Serialize};
+
+/// Messages exchanged between consensus publishers and observers
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum ConsensusObserverMessage {
+    /// Requests the publisher to forward its updates. Publishers answer with the latest
+    /// epoch ending commit decision, so that lagging observers can move to the current epoch.
+    Subscribe,
+    /// Requests the publisher to stop forwarding its updates
+    Unsubscribe,
+    /// Blocks ordered by consensus, which are not committed yet
+    OrderedBlock(OrderedBlock),
+    /// The commit proof of the ordered blocks up to its round
+    CommitDecision(LedgerInfoWithSignatures),
+}
+
+impl ConsensusObserverMessage {
+    /// Returns a summary label for the message
+    pub fn name(&self) -> &'static str {
+        match self {
+            ConsensusObserverMessage::Subscribe => "subscribe",
+            ConsensusObserverMessage::Unsubscribe => "unsubscribe",
+            ConsensusObserverMessage::OrderedBlock(_) => "ordered_block",
+            ConsensusObserverMessage::CommitDecision(_) => "commit_decision",
+        }
+    }
+}
+
+/// A batch of blocks ordered together, along with what the observers need to execute them
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct OrderedBlock {
+    pub blocks: Vec<Block>,
+    /// The transactions of each block, since quorum store blocks only reference batches.
+    /// Empty for blocks carrying their transactions directly.
+    pub transactions: Vec<Vec<SignedTransaction>>,
+    /// The randomness of each block, if it was produced with randomness
+    pub randomness: Vec<Option<Randomness>>,
+    pub ordered_proof: LedgerInfoWithSignatures,
+}
+
+impl OrderedBlock {
+    /// Checks that the blocks form a chain in the given epoch, that ends at the block
+    /// certified by the ordered proof, that the proof is signed by a quorum, and that the
+    /// transactions are the ones of the blocks.
+    pub fn verify(&self, epoch_state: &EpochState) -> anyhow::Result<()> {
+        ensure!(!self.blocks.is_empty(), "Ordered block without blocks");
+        ensure!(
+            self.blocks.len() == self.transactions.len()
+                && self.blocks.len() == self.randomness.len(),
+            "Mismatched number of blocks ({}), transactions ({}) and randomness ({})",
+            self.blocks.len(),
+            self.transactions.len(),
+            self.randomness.len()
+        );
+        ensure!(
+            self.ordered_proof.ledger_info().epoch() == epoch_state.epoch,
+            "Ordered proof epoch {} doesn't match the current epoch {}",
+            self.ordered_proof.ledger_info().epoch(),
+            epoch_state.epoch
+        );
+        for block in &self.blocks {
+            ensure!(
+                block.epoch() == epoch_state.epoch,
+                "Block {} is not in the current epoch {}",
+                block.id(),
+                epoch_state.epoch
+            );
+        }
+        for (parent, child) in self.blocks.iter().zip(self.blocks.iter().skip(1)) {
+            ensure!(
+                child.parent_id() == parent.id(),
+                "Block {} doesn't extend block {}",
+                child.id(),
+                parent.id()
+            );
+        }
+        ensure!(
+            self.last_block().id() == self.ordered_proof.commit_info().id(),
+            "Last block {} is not the ordered block {}",
+            self.last_block().id(),
+            self.ordered_proof.commit_info().id()
+        );
+        self.verify_transactions()?;
+        self.ordered_proof
+            .verify_signatures(&epoch_state.verifier)
+            .map_err(|error| anyhow::anyhow!("Invalid ordered proof: {:?}", error))
+    }
+
+    /// Checks that the transactions of each quorum store block are the transactions of its
+    /// batches, i.e., that they hash to the digests certified by the proofs of store. Batches
+    /// that expired before the block are skipped by execution, so they are not sent either.
+    fn verify_transactions(&self) -> anyhow::Result<()> {
+        for (block, txns) in self.blocks.iter().zip(self.transactions.iter()) {
+            let proofs = match block.payload() {
+                Some(Payload::InQuorumStore(proof_with_data)) => &proof_with_data.proofs,
+                _ => {
+                    ensure!(
+                        txns.is_empty(),
+                        "Unexpected transactions for block {} without quorum store payload",
+                        block.id()
+                    );
+                    continue;
+                },
+            };
+            let mut remaining_txns = txns.as_slice();
+            for proof in proofs
+                .iter()
+                .filter(|proof| block.timestamp_usecs() <= proof.expiration())
+            {
+                let num_txns = proof.num_txns() as usize;
+                ensure!(
+                    remaining_txns.len() >= num_txns,
+                    "Missing transactions of batch {} in block {}",
+                    proof.digest(),
+                    block.id()
+                );
+                let (batch_txns, next_txns) = remaining_txns.split_at(num_txns);
+                let digest = BatchPayload::new(proof.author(), batch_txns.to_vec()).hash();
+                ensure!(
+                    digest == *proof.digest(),
+                    "Transactions of batch {} in block {} don't match its digest ({})",
+                    proof.digest(),
+                    block.id(),
+                    digest
+                );
+                remaining_txns = next_txns;
+            }
+            ensure!(
+                remaining_txns.is_empty(),
+                "Unexpected transactions after the batches of block {}",
+                block.id()
+            );
+        }
+        Ok(())
+    }
+
+    pub fn first_block(&self) -> &Block {
+        self.blocks.first().expect("Ordered blocks are not empty")
+    }
+
+    pub fn last_block(&self) -> &Block {
+        self.blocks.last().expect("Ordered blocks are not empty")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::quorum_store::{tests::utils::create_vec_signed_transactions, types::Batch};
+    use aptos_consensus_types::{
+        block::block_test_utils::{certificate_for_genesis, placeholder_certificate_for_block},
+        common::ProofWithData,
+        proof_of_store::{BatchId, ProofOfStore},
+    };
+    use aptos_crypto::HashValue;
+    use aptos_types::{
+        aggregate_signature::AggregateSignature,
+        ledger_info::{generate_ledger_info_with_sig, LedgerInfo},
+        validator_signer::ValidatorSigner,
+        validator_verifier::random_validator_verifier,
+    };
+
+    fn make_ordered_block(signers: &[ValidatorSigner], blocks: Vec<Block>) -> OrderedBlock {
+        let last_block = blocks.last().unwrap();
+        let ordered_proof = generate_ledger_info_with_sig(
+            signers,
+            LedgerInfo::new(
+                last_block.gen_block_info(HashValue::zero(), 0, None),
+                HashValue::zero(),
+            ),
+        );
+        OrderedBlock {
+            transactions: vec![vec![]; blocks.len()],
+            randomness: vec![None; blocks.len()],
+            blocks,
+            ordered_proof,
+        }
+    }
+
+    #[test]
+    fn test_verify_ordered_block() {
+        let (signers, verifier) = random_validator_verifier(4, None, false);
+        let epoch_state = EpochState { epoch: 1, verifier };
+
+        let genesis_qc = certificate_for_genesis();
+        let block_1 = Block::new_proposal(
+            Payload::empty(false),
+            1,
+            1,
+            genesis_qc.clone(),
+            &signers[0],
+            vec![],
+        )
+        .unwrap();
+        let block_2 = Block::new_proposal(
+            Payload::empty(false),
+            2,
+            2,
+            placeholder_certificate_for_block(
+                &signers,
+                block_1.id(),
+                1,
+                genesis_qc.certified_block().id(),
+                0,
+            ),
+            &signers[1],
+            vec![],
+        )
+        .unwrap();
+
+        // A chain of blocks with a quorum signed proof is valid
+        let ordered_block = make_ordered_block(&signers, vec![block_1.clone(), block_2.clone()]);
+        ordered_block.verify(&epoch_state).unwrap();
+
+        // Blocks that don't form a chain are rejected
+        let ordered_block = make_ordered_block(&signers, vec![block_2.clone(), block_1.clone()]);
+        assert!(ordered_block.verify(&epoch_state).is_err());
+
+        // Proofs that don't certify the last block are rejected
+        let mut ordered_block = make_ordered_block(&signers, vec![block_1.clone()]);
+        ordered_block.blocks.push(block_2.clone());
+        ordered_block.transactions.push(vec![]);
+        ordered_block.randomness.push(None);
+        assert!(ordered_block.verify(&epoch_state).is_err());
+
+        // Proofs without a quorum are rejected
+        let mut ordered_block = make_ordered_block(&signers, vec![block_1.clone()]);
+        ordered_block.ordered_proof = LedgerInfoWithSignatures::new(
+            ordered_block.ordered_proof.ledger_info().clone(),
+            AggregateSignature::empty(),
+        );
+        assert!(ordered_block.verify(&epoch_state).is_err());
+
+        // Blocks from another epoch are rejected
+        let ordered_block = make_ordered_block(&signers, vec![block_1, block_2]);
+        let next_epoch_state = EpochState {
+            epoch: 2,
+            verifier: epoch_state.verifier,
+        };
+        assert!(ordered_block.verify(&next_epoch_state).is_err());
+    }
+
+    #[test]
+    fn test_verify_quorum_store_transactions() {
+        let (signers, verifier) = random_validator_verifier(4, None, false);
+        let epoch_state = EpochState { epoch: 1, verifier };
+
+        let batches: Vec<_> = (0..2)
+            .map(|i| {
+                Batch::new(
+                    BatchId::new_for_test(i),
+                    create_vec_signed_transactions(3),
+                    1,
+                    u64::MAX,
+                    signers[i as usize].author(),
+                    0,
+                )
+            })
+            .collect();
+        let proofs = batches
+            .iter()
+            .map(|batch| ProofOfStore::new(batch.batch_info().clone(), AggregateSignature::empty()))
+            .collect();
+        let block = Block::new_proposal(
+            Payload::InQuorumStore(ProofWithData::new(proofs)),
+            1,
+            1,
+            certificate_for_genesis(),
+            &signers[0],
+            vec![],
+        )
+        .unwrap();
+        let txns: Vec<_> = batches
+            .into_iter()
+            .flat_map(|batch| batch.into_transactions())
+            .collect();
+        let with_transactions = |txns: Vec<SignedTransaction>| {
+            let mut ordered_block = make_ordered_block(&signers, vec![block.clone()]);
+            ordered_block.transactions = vec![txns];
+            ordered_block
+        };
+
+        // The transactions of all the batches, in order, are valid
+        with_transactions(txns.clone())
+            .verify(&epoch_state)
+            .unwrap();
+
+        // Missing transactions are rejected
+        assert!(with_transactions(txns[..5].to_vec())
+            .verify(&epoch_state)
+            .is_err());
+        assert!(with_transactions(vec![]).verify(&epoch_state).is_err());
+
+        // Extra transactions are rejected
+        let mut extra_txns = txns.clone();
+        extra_txns.push(create_vec_signed_transactions(1).remove(0));
+        assert!(with_transactions(extra_txns).verify(&epoch_state).is_err());
+
+        // Replaced or reordered transactions are rejected
+        let mut replaced_txns = txns.clone();
+        replaced_txns[1] = create_vec_signed_transactions(1).remove(0);
+        assert!(with_transactions(replaced_txns)
+            .verify(&epoch_state)
+            .is_err());
+        let mut reordered_txns = txns.clone();
+        reordered_txns.swap(0, 1);
+        assert!(with_transactions(reordered_txns)
+            .verify(&epoch_state)
+            .is_err());
+
+        // Transactions are rejected for blocks carrying their own transactions
+        let mut ordered_block = make_ordered_block(&signers, vec![Block::new_proposal(
+            Payload::empty(false),
+            1,
+            1,
+            certificate_for_genesis(),
+            &signers[0],
+            vec![],
+        )
+        .unwrap()]);
+        ordered_block.transactions = vec![txns];
+        assert!(ordered_block.verify(&epoch_state).is_err());
+    }
+}
@@ -0,0 +1,485 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    consensus_observer::{
+        counters,
+        network::{ConsensusObserverMessage, OrderedBlock},
+        publisher::ConsensusPublisher,
+    },
+    payload_manager::PayloadManager,
+    state_replication::StateComputer,
+    transaction_deduper::create_transaction_deduper,
+    transaction_shuffler::create_transaction_shuffler,
+};
+use aptos_config::{
+    config::ConsensusObserverConfig,
+    network_id::{NetworkId, PeerNetworkId},
+};
+use aptos_consensus_types::{block::Block, common::Payload, executed_block::ExecutedBlock};
+use aptos_crypto::HashValue;
+use aptos_event_notifications::ReconfigNotificationListener;
+use aptos_infallible::Mutex;
+use aptos_logger::prelude::*;
+use aptos_network::{
+    application::interface::{NetworkClient, NetworkClientInterface, NetworkServiceEvents},
+    protocols::network::Event,
+};
+use aptos_storage_interface::DbReader;
+use aptos_types::{
+    block_info::Round,
+    epoch_state::EpochState,
+    ledger_info::LedgerInfoWithSignatures,
+    on_chain_config::{OnChainConfigProvider, OnChainExecutionConfig, ValidatorSet},
+    transaction::SignedTransaction,
+};
+use futures::{stream::select_all, StreamExt};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
+
+/// Follows consensus by executing the ordered blocks received from the subscribed publishers,
+/// and committing them with the matching commit decisions.
+pub struct ConsensusObserver<P: OnChainConfigProvider> {
+    config: ConsensusObserverConfig,
+    network_client: NetworkClient<ConsensusObserverMessage>,
+    db_reader: Arc<dyn DbReader>,
+    execution_client: Arc<dyn StateComputer>,
+    reconfig_events: ReconfigNotificationListener<P>,
+    // republishes the received updates to the downstream observers, if enabled
+    consensus_publisher: Option<Arc<ConsensusPublisher>>,
+
+    // the transactions of the quorum store blocks, read by the payload manager at execution
+    block_payloads: Arc<Mutex<HashMap<HashValue, Vec<SignedTransaction>>>>,
+    epoch_state: Option<Arc<EpochState>>,
+    // the latest committed ledger info
+    root: LedgerInfoWithSignatures,
+    // the executed blocks waiting for their commit decision, by the round of their last block
+    pending_blocks: BTreeMap<Round, Vec<ExecutedBlock>>,
+    subscriptions: HashSet<PeerNetworkId>,
+}
+
+impl<P: OnChainConfigProvider> ConsensusObserver<P> {
+    pub fn new(
+        config: ConsensusObserverConfig,
+        network_client: NetworkClient<ConsensusObserverMessage>,
+        db_reader: Arc<dyn DbReader>,
+        execution_client: Arc<dyn StateComputer>,
+        reconfig_events: ReconfigNotificationListener<P>,
+        consensus_publisher: Option<Arc<ConsensusPublisher>>,
+    ) -> Self {
+        let root = db_reader
+            .get_latest_ledger_info()
+            .expect("Failed to read the latest ledger info");
+        Self {
+            config,
+            network_client,
+            db_reader,
+            execution_client,
+            reconfig_events,
+            consensus_publisher,
+            block_payloads: Arc::new(Mutex::new(HashMap::new())),
+            epoch_state: None,
+            root,
+            pending_blocks: BTreeMap::new(),
+            subscriptions: HashSet::new(),
+        }
+    }
+
+    fn epoch_state(&self) -> &EpochState {
+        self.epoch_state
+            .as_ref()
+            .expect("ConsensusObserver not started yet")
+    }
+
+    /// The id of the block the next ordered blocks extend, if no block is pending
+    fn root_block_id(&self) -> HashValue {
+        if self.root.ledger_info().ends_epoch() {
+            Block::make_genesis_block_from_ledger_info(self.root.ledger_info()).id()
+        } else {
+            self.root.commit_info().id()
+        }
+    }
+
+    /// The round of the last committed block in the current epoch
+    fn root_round(&self) -> Round {
+        if self.root.ledger_info().ends_epoch() {
+            0
+        } else {
+            self.root.commit_info().round()
+        }
+    }
+
+    fn last_block(&self) -> Option<&ExecutedBlock> {
+        self.pending_blocks
+            .values()
+            .next_back()
+            .and_then(|blocks| blocks.last())
+    }
+
+    fn drop_message(&self, reason: &str) {
+        counters::OBSERVER_DROPPED_MESSAGES
+            .with_label_values(&[reason])
+            .inc();
+    }
+
+    fn clear_pending_blocks(&mut self) {
+        self.pending_blocks.clear();
+        self.block_payloads.lock().clear();
+        counters::OBSERVER_PENDING_BLOCKS.set(0);
+    }
+
+    async fn await_new_epoch(&mut self) {
+        let reconfig_notification = self
+            .reconfig_events
+            .next()
+            .await
+            .expect("Reconfig sender dropped, unable to start new epoch");
+        let payload = reconfig_notification.on_chain_configs;
+        let validator_set: ValidatorSet = payload
+            .get()
+            .expect("failed to get ValidatorSet from payload");
+        let epoch_state = EpochState {
+            epoch: payload.epoch(),
+            verifier: (&validator_set).into(),
+        };
+        let execution_config = payload
+            .get::<OnChainExecutionConfig>()
+            .unwrap_or_else(|_| OnChainExecutionConfig::default_if_missing());
+
+        self.execution_client.new_epoch(
+            &epoch_state,
+            Arc::new(PayloadManager::ConsensusObserver(
+                self.block_payloads.clone(),
+            )),
+            create_transaction_shuffler(execution_config.transaction_shuffler_type()),
+            execution_config.block_execution_limit(),
+            create_transaction_deduper(execution_config.transaction_deduper_type()),
+        );
+        self.root = self
+            .db_reader
+            .get_latest_ledger_info()
+            .expect("Failed to read the latest ledger info");
+        info!(
+            "[ConsensusObserver] Started epoch {}, root {}",
+            epoch_state.epoch,
+            self.root.commit_info()
+        );
+        self.epoch_state = Some(Arc::new(epoch_state));
+    }
+
+    async fn end_epoch(&mut self) {
+        self.clear_pending_blocks();
+        self.execution_client.end_epoch();
+        self.await_new_epoch().await;
+    }
+
+    /// Keeps up to the max number of subscriptions, preferring the validator fullnode network
+    fn refresh_subscriptions(&mut self) {
+        let available_peers: HashSet<_> = match self.network_client.get_available_peers() {
+            Ok(peers) => peers.into_iter().collect(),
+            Err(error) => {
+                warn!(
+                    "[ConsensusObserver] Failed to get the available peers: {:?}",
+                    error
+                );
+                return;
+            },
+        };
+        self.subscriptions
+            .retain(|peer| available_peers.contains(peer));
+
+        let mut candidates: Vec<_> = available_peers
+            .into_iter()
+            .filter(|peer| !self.subscriptions.contains(peer))
+            .collect();
+        candidates.sort_by_key(|peer| peer.network_id() != NetworkId::Vfn);
+        for peer in candidates {
+            if self.subscriptions.len() as u64 >= self.config.max_concurrent_subscriptions {
+                break;
+            }
+            info!("[ConsensusObserver] Subscribing to {}", peer);
+            self.subscribe(peer);
+        }
+    }
+
+    fn subscribe(&mut self, peer: PeerNetworkId) {
+        match self
+            .network_client
+            .send_to_peer(ConsensusObserverMessage::Subscribe, peer)
+        {
+            Ok(()) => {
+                self.subscriptions.insert(peer);
+            },
+            Err(error) => {
+                warn!(
+                    "[ConsensusObserver] Failed to subscribe to {}: {:?}",
+                    peer, error
+                );
+            },
+        }
+    }
+
+    fn republish(&self, message: ConsensusObserverMessage) {
+        if let Some(consensus_publisher) = &self.consensus_publisher {
+            consensus_publisher.publish_message(message);
+        }
+    }
+
+    async fn process_message(&mut self, peer: PeerNetworkId, message: ConsensusObserverMessage) {
+        counters::OBSERVER_RECEIVED_MESSAGES
+            .with_label_values(&[message.name()])
+            .inc();
+        match message {
+            ConsensusObserverMessage::Subscribe | ConsensusObserverMessage::Unsubscribe => {
+                match &self.consensus_publisher {
+                    Some(consensus_publisher) => {
+                        consensus_publisher.handle_subscription_message(peer, message)
+                    },
+                    None => self.drop_message("publisher_disabled"),
+                }
+            },
+            _ if !self.subscriptions.contains(&peer) => self.drop_message("not_subscribed"),
+            ConsensusObserverMessage::OrderedBlock(ordered_block) => {
+                self.process_ordered_block(ordered_block).await
+            },
+            ConsensusObserverMessage::CommitDecision(commit_proof) => {
+                self.process_commit_decision(peer, commit_proof).await
+            },
+        }
+    }
+
+    async fn process_ordered_block(&mut self, ordered_block: OrderedBlock) {
+        if ordered_block.ordered_proof.ledger_info().epoch() != self.epoch_state().epoch {
+            return self.drop_message("wrong_epoch");
+        }
+        if let Err(error) = ordered_block.verify(self.epoch_state()) {
+            warn!("[ConsensusObserver] Invalid ordered block: {:?}", error);
+            return self.drop_message("invalid");
+        }
+
+        // The blocks must extend the last pending block, otherwise some blocks were missed and
+        // the observer catches up through state sync on the next commit decision.
+        let (last_round, last_block_id) = match self.last_block() {
+            Some(block) => (block.round(), block.id()),
+            None => (self.root_round(), self.root_block_id()),
+        };
+        if ordered_block.last_block().round() <= last_round {
+            return self.drop_message("old");
+        }
+        if ordered_block.first_block().parent_id() != last_block_id {
+            return self.drop_message("missing_parent");
+        }
+        if self.pending_blocks.len() as u64 >= self.config.max_num_pending_blocks {
+            return self.drop_message("too_many_pending");
+        }
+
+        {
+            let mut block_payloads = self.block_payloads.lock();
+            for (block, txns) in ordered_block
+                .blocks
+                .iter()
+                .zip(ordered_block.transactions.iter())
+            {
+                if let Some(Payload::InQuorumStore(_)) = block.payload() {
+                    block_payloads.insert(block.id(), txns.clone());
+                }
+            }
+        }
+
+        let mut executed_blocks = Vec::with_capacity(ordered_block.blocks.len());
+        let mut parent_id = last_block_id;
+        for (block, randomness) in ordered_block
+            .blocks
+            .iter()
+            .zip(ordered_block.randomness.iter())
+        {
+            let result = self
+                .execution_client
+                .schedule_compute(block, parent_id, randomness.clone())
+                .await
+                .await;
+            match result {
+                Ok(state_compute_result) => {
+                    let mut executed_block =
+                        ExecutedBlock::new(block.clone(), state_compute_result);
+                    if let Some(randomness) = randomness {
+                        executed_block.set_randomness(randomness.clone());
+                    }
+                    executed_blocks.push(executed_block);
+                    parent_id = block.id();
+                },
+                Err(error) => {
+                    error!(
+                        "[ConsensusObserver] Failed to execute block {}: {:?}",
+                        block.id(),
+                        error
+                    );
+                    let mut block_payloads = self.block_payloads.lock();
+                    for block in &ordered_block.blocks {
+                        block_payloads.remove(&block.id());
+                    }
+                    return self.drop_message("execution_failure");
+                },
+            }
+        }
+
+        self.pending_blocks
+            .insert(ordered_block.last_block().round(), executed_blocks);
+        counters::OBSERVER_PENDING_BLOCKS.set(self.pending_blocks.len() as i64);
+        self.republish(ConsensusObserverMessage::OrderedBlock(ordered_block));
+    }
+
+    async fn process_commit_decision(
+        &mut self,
+        peer: PeerNetworkId,
+        commit_proof: LedgerInfoWithSignatures,
+    ) {
+        let epoch = self.epoch_state().epoch;
+        if commit_proof.ledger_info().epoch() > epoch {
+            // The observer missed an epoch change. Resubscribing makes the publisher resend its
+            // latest epoch ending commit decision.
+            warn!(
+                "[ConsensusObserver] Received a commit decision for the future epoch {}, resubscribing to {}",
+                commit_proof.ledger_info().epoch(),
+                peer
+            );
+            self.subscribe(peer);
+            return self.drop_message("future_epoch");
+        }
+        if commit_proof.ledger_info().epoch() < epoch
+            || commit_proof.commit_info().round() <= self.root_round()
+        {
+            return self.drop_message("old");
+        }
+        if let Err(error) = commit_proof.verify_signatures(&self.epoch_state().verifier) {
+            warn!("[ConsensusObserver] Invalid commit decision: {:?}", error);
+            return self.drop_message("invalid");
+        }
+
+        if !self.commit_pending_blocks(&commit_proof).await {
+            info!(
+                "[ConsensusObserver] Falling back to state sync to {}",
+                commit_proof.commit_info()
+            );
+            counters::OBSERVER_STATE_SYNC_FALLBACKS.inc();
+            self.clear_pending_blocks();
+            if let Err(error) = self.execution_client.sync_to(commit_proof.clone()).await {
+                error!(
+                    "[ConsensusObserver] Failed to sync to the commit decision: {:?}",
+                    error
+                );
+                return;
+            }
+            self.root = commit_proof.clone();
+        }
+        counters::OBSERVER_COMMITTED_ROUND.set(commit_proof.commit_info().round() as i64);
+        self.republish(ConsensusObserverMessage::CommitDecision(
+            commit_proof.clone(),
+        ));
+
+        if commit_proof.ledger_info().ends_epoch() {
+            self.end_epoch().await;
+        }
+    }
+
+    /// Commits the pending blocks up to the committed block, if it was executed to the same state
+    async fn commit_pending_blocks(&mut self, commit_proof: &LedgerInfoWithSignatures) -> bool {
+        let commit_info = commit_proof.commit_info();
+        let mut blocks_to_commit = vec![];
+        let mut committed_entry = None;
+        for (round, blocks) in self.pending_blocks.iter() {
+            for block in blocks {
+                blocks_to_commit.push(Arc::new(block.clone()));
+                if block.id() == commit_info.id() {
+                    let block_info = block.block_info();
+                    if block_info.executed_state_id() != commit_info.executed_state_id()
+                        || block_info.version() != commit_info.version()
+                    {
+                        error!(
+                            "[ConsensusObserver] Block {} was executed to {}, but committed to {}",
+                            block.id(),
+                            block_info,
+                            commit_info
+                        );
+                        return false;
+                    }
+                    committed_entry = Some(*round);
+                    break;
+                }
+            }
+            if committed_entry.is_some() {
+                break;
+            }
+        }
+        let Some(committed_round) = committed_entry else {
+            return false;
+        };
+
+        if let Err(error) = self
+            .execution_client
+            .commit(&blocks_to_commit, commit_proof.clone(), Box::new(|_, _| {}))
+            .await
+        {
+            error!("[ConsensusObserver] Failed to commit blocks: {:?}", error);
+            return false;
+        }
+
+        // Keep the blocks of a partially committed entry that extend the committed block
+        let remaining = self.pending_blocks.split_off(&(committed_round + 1));
+        let mut committed = std::mem::replace(&mut self.pending_blocks, remaining);
+        if let Some(blocks) = committed.remove(&committed_round) {
+            let uncommitted: Vec<_> = blocks
+                .into_iter()
+                .filter(|block| block.round() > commit_info.round())
+                .collect();
+            if !uncommitted.is_empty() {
+                self.pending_blocks.insert(committed_round, uncommitted);
+            }
+        }
+        {
+            let mut block_payloads = self.block_payloads.lock();
+            for block in &blocks_to_commit {
+                block_payloads.remove(&block.id());
+            }
+        }
+        counters::OBSERVER_PENDING_BLOCKS.set(self.pending_blocks.len() as i64);
+        self.root = commit_proof.clone();
+        true
+    }
+
+    pub async fn start(
+        mut self,
+        network_service_events: NetworkServiceEvents<ConsensusObserverMessage>,
+    ) {
+        self.await_new_epoch().await;
+
+        let network_events: Vec<_> = network_service_events
+            .into_network_and_events()
+            .into_iter()
+            .map(|(network_id, events)| events.map(move |event| (network_id, event)))
+            .collect();
+        let mut network_events = select_all(network_events).fuse();
+        let mut refresh_interval = tokio::time::interval(Duration::from_millis(
+            self.config.subscription_refresh_interval_ms,
+        ));
+
+        loop {
+            tokio::select! {
+                Some((network_id, event)) = network_events.next() => {
+                    if let Event::Message(peer_id, message) = event {
+                        self.process_message(PeerNetworkId::new(network_id, peer_id), message)
+                            .await;
+                    }
+                },
+                _ = refresh_interval.tick() => {
+                    self.refresh_subscriptions();
+                },
+                else => break,
+            }
+        }
+        info!("[ConsensusObserver] Stopped");
+    }
+}
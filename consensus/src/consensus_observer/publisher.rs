@@ -0,0 +1,234 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    consensus_observer::{
+        counters,
+        network::{ConsensusObserverMessage, OrderedBlock},
+    },
+    payload_manager::PayloadManager,
+};
+use aptos_config::network_id::PeerNetworkId;
+use aptos_consensus_types::{block::Block, executed_block::ExecutedBlock};
+use aptos_infallible::{Mutex, RwLock};
+use aptos_logger::prelude::*;
+use aptos_network::{
+    application::interface::{NetworkClient, NetworkClientInterface, NetworkServiceEvents},
+    protocols::network::Event,
+};
+use aptos_types::{ledger_info::LedgerInfoWithSignatures, randomness::Randomness};
+use futures::{
+    channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender},
+    stream::select_all,
+    StreamExt,
+};
+use std::{collections::HashSet, sync::Arc};
+
+/// An update to publish to the subscribers
+pub enum PublishRequest {
+    /// Ordered blocks, whose transactions still need to be fetched from the payload manager
+    OrderedBlocks {
+        blocks: Vec<Block>,
+        randomness: Vec<Option<Randomness>>,
+        ordered_proof: LedgerInfoWithSignatures,
+    },
+    /// A message ready to be sent as is
+    Message(ConsensusObserverMessage),
+}
+
+/// Forwards the ordered blocks and commit decisions of the node to its subscribers
+pub struct ConsensusPublisher {
+    network_client: NetworkClient<ConsensusObserverMessage>,
+    subscribers: RwLock<HashSet<PeerNetworkId>>,
+    /// The payload manager of the current epoch, to fetch the transactions of the blocks
+    payload_manager: Mutex<Option<Arc<PayloadManager>>>,
+    /// Sent to every new subscriber, so it can catch up with the current epoch
+    last_epoch_ending_proof: Mutex<Option<LedgerInfoWithSignatures>>,
+    publish_tx: UnboundedSender<PublishRequest>,
+}
+
+impl ConsensusPublisher {
+    pub fn new(
+        network_client: NetworkClient<ConsensusObserverMessage>,
+    ) -> (Arc<Self>, UnboundedReceiver<PublishRequest>) {
+        let (publish_tx, publish_rx) = unbounded();
+        let publisher = Self {
+            network_client,
+            subscribers: RwLock::new(HashSet::new()),
+            payload_manager: Mutex::new(None),
+            last_epoch_ending_proof: Mutex::new(None),
+            publish_tx,
+        };
+        (Arc::new(publisher), publish_rx)
+    }
+
+    pub fn set_payload_manager(&self, payload_manager: Arc<PayloadManager>) {
+        self.payload_manager.lock().replace(payload_manager);
+    }
+
+    pub fn publish_ordered_blocks(
+        &self,
+        ordered_blocks: &[ExecutedBlock],
+        ordered_proof: &LedgerInfoWithSignatures,
+    ) {
+        if self.subscribers.read().is_empty() {
+            return;
+        }
+        self.send_request(PublishRequest::OrderedBlocks {
+            blocks: ordered_blocks.iter().map(|b| b.block().clone()).collect(),
+            randomness: ordered_blocks
+                .iter()
+                .map(|b| b.randomness().cloned())
+                .collect(),
+            ordered_proof: ordered_proof.clone(),
+        });
+    }
+
+    pub fn publish_commit_decision(&self, commit_proof: &LedgerInfoWithSignatures) {
+        self.publish_message(ConsensusObserverMessage::CommitDecision(
+            commit_proof.clone(),
+        ));
+    }
+
+    /// Publishes a message as is, e.g., an ordered block received by an observer
+    pub fn publish_message(&self, message: ConsensusObserverMessage) {
+        if let ConsensusObserverMessage::CommitDecision(commit_proof) = &message {
+            if commit_proof.ledger_info().ends_epoch() {
+                self.last_epoch_ending_proof
+                    .lock()
+                    .replace(commit_proof.clone());
+            }
+        }
+        if self.subscribers.read().is_empty() {
+            return;
+        }
+        self.send_request(PublishRequest::Message(message));
+    }
+
+    fn send_request(&self, request: PublishRequest) {
+        if self.publish_tx.unbounded_send(request).is_err() {
+            warn!("[ConsensusPublisher] The publisher is not running");
+        }
+    }
+
+    /// Handles the subscription requests of the downstream observers
+    pub fn handle_subscription_message(
+        &self,
+        peer: PeerNetworkId,
+        message: ConsensusObserverMessage,
+    ) {
+        match message {
+            ConsensusObserverMessage::Subscribe => {
+                if self.subscribers.write().insert(peer) {
+                    info!("[ConsensusPublisher] New subscriber {}", peer);
+                }
+                // Resubscribing observers may be stuck in an older epoch
+                let last_epoch_ending_proof = self.last_epoch_ending_proof.lock().clone();
+                if let Some(commit_proof) = last_epoch_ending_proof {
+                    self.send_to_peer(ConsensusObserverMessage::CommitDecision(commit_proof), peer);
+                }
+            },
+            ConsensusObserverMessage::Unsubscribe => {
+                if self.subscribers.write().remove(&peer) {
+                    info!("[ConsensusPublisher] Subscriber {} left", peer);
+                }
+            },
+            message => {
+                warn!(
+                    "[ConsensusPublisher] Unexpected {} message from {}",
+                    message.name(),
+                    peer
+                );
+            },
+        }
+        counters::PUBLISHER_NUM_SUBSCRIBERS.set(self.subscribers.read().len() as i64);
+    }
+
+    fn send_to_peer(&self, message: ConsensusObserverMessage, peer: PeerNetworkId) {
+        counters::PUBLISHER_SENT_MESSAGES
+            .with_label_values(&[message.name()])
+            .inc();
+        if let Err(error) = self.network_client.send_to_peer(message, peer) {
+            warn!(
+                "[ConsensusPublisher] Failed to send to {}, dropping the subscriber: {:?}",
+                peer, error
+            );
+            self.subscribers.write().remove(&peer);
+            counters::PUBLISHER_NUM_SUBSCRIBERS.set(self.subscribers.read().len() as i64);
+        }
+    }
+
+    async fn process_request(&self, request: PublishRequest) {
+        let message = match request {
+            PublishRequest::OrderedBlocks {
+                blocks,
+                randomness,
+                ordered_proof,
+            } => {
+                let payload_manager = match self.payload_manager.lock().clone() {
+                    Some(payload_manager) => payload_manager,
+                    None => return,
+                };
+                let mut transactions = Vec::with_capacity(blocks.len());
+                for block in &blocks {
+                    match payload_manager.fetch_transactions(block).await {
+                        Ok(txns) => transactions.push(txns),
+                        Err(error) => {
+                            warn!(
+                                "[ConsensusPublisher] Failed to fetch the transactions of block {}: {:?}",
+                                block.id(),
+                                error
+                            );
+                            return;
+                        },
+                    }
+                }
+                ConsensusObserverMessage::OrderedBlock(OrderedBlock {
+                    blocks,
+                    transactions,
+                    randomness,
+                    ordered_proof,
+                })
+            },
+            PublishRequest::Message(message) => message,
+        };
+
+        let subscribers: Vec<_> = self.subscribers.read().iter().copied().collect();
+        for peer in subscribers {
+            self.send_to_peer(message.clone(), peer);
+        }
+    }
+
+    /// Forwards the published updates to the subscribers, in order. When the node isn't an
+    /// observer itself, the publisher also owns the consensus observer network events.
+    pub async fn start(
+        self: Arc<Self>,
+        network_service_events: Option<NetworkServiceEvents<ConsensusObserverMessage>>,
+        mut publish_rx: UnboundedReceiver<PublishRequest>,
+    ) {
+        if let Some(network_service_events) = network_service_events {
+            let publisher = self.clone();
+            tokio::spawn(async move {
+                let network_events: Vec<_> = network_service_events
+                    .into_network_and_events()
+                    .into_iter()
+                    .map(|(network_id, events)| events.map(move |event| (network_id, event)))
+                    .collect();
+                let mut network_events = select_all(network_events);
+                while let Some((network_id, event)) = network_events.next().await {
+                    if let Event::Message(peer_id, message) = event {
+                        publisher.handle_subscription_message(
+                            PeerNetworkId::new(network_id, peer_id),
+                            message,
+                        );
+                    }
+                }
+            });
+        }
+
+        while let Some(request) = publish_rx.next().await {
+            self.process_request(request).await;
+        }
+        info!("[ConsensusPublisher] Stopped");
+    }
+}
@@ -3,12 +3,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    consensus_observer::{
+        network::ConsensusObserverMessage, observer::ConsensusObserver,
+        publisher::ConsensusPublisher,
+    },
     counters,
     epoch_manager::EpochManager,
     network::NetworkTask,
     network_interface::{ConsensusMsg, ConsensusNetworkClient},
     persistent_liveness_storage::StorageWriteProxy,
     quorum_store::quorum_store_db::QuorumStoreDB,
+    recent_commit_cache::RecentCommitCache,
     state_computer::ExecutionProxy,
     transaction_filter::TransactionFilter,
     txn_notifier::MempoolNotifier,
@@ -20,7 +25,7 @@ use aptos_consensus_notifications::ConsensusNotificationSender;
 use aptos_event_notifications::{DbBackedOnChainConfig, ReconfigNotificationListener};
 use aptos_executor::block_executor::BlockExecutor;
 use aptos_logger::prelude::*;
-use aptos_mempool::QuorumStoreRequest;
+use aptos_mempool::{BackPressureStatus, QuorumStoreRequest};
 use aptos_network::application::interface::{NetworkClient, NetworkServiceEvents};
 use aptos_storage_interface::DbReaderWriter;
 use aptos_vm::AptosVM;
@@ -35,8 +40,13 @@ pub fn start_consensus(
     network_service_events: NetworkServiceEvents<ConsensusMsg>,
     state_sync_notifier: Arc<dyn ConsensusNotificationSender>,
     consensus_to_mempool_sender: mpsc::Sender<QuorumStoreRequest>,
+    back_pressure_status: BackPressureStatus,
     aptos_db: DbReaderWriter,
     reconfig_events: ReconfigNotificationListener<DbBackedOnChainConfig>,
+    consensus_observer_network_client: Option<NetworkClient<ConsensusObserverMessage>>,
+    consensus_observer_network_service_events: Option<
+        NetworkServiceEvents<ConsensusObserverMessage>,
+    >,
 ) -> (Runtime, Arc<StorageWriteProxy>, Arc<QuorumStoreDB>) {
     let runtime = aptos_runtimes::spawn_named_runtime("consensus".into(), None);
     let storage = Arc::new(StorageWriteProxy::new(node_config, aptos_db.reader.clone()));
@@ -47,12 +57,15 @@ pub fn start_consensus(
         node_config.consensus.mempool_executed_txn_timeout_ms,
     ));
 
+    // Shared between the commit path and the proposer, to drop already committed transactions
+    let recent_commit_cache = Arc::new(RecentCommitCache::default());
     let state_computer = Arc::new(ExecutionProxy::new(
         Arc::new(BlockExecutor::<AptosVM>::new(aptos_db)),
         txn_notifier,
         state_sync_notifier,
         runtime.handle(),
         TransactionFilter::new(node_config.execution.transaction_filter.clone()),
+        recent_commit_cache.clone(),
     ));
 
     let time_service = Arc::new(ClockTimeService::new(runtime.handle().clone()));
@@ -61,6 +74,19 @@ pub fn start_consensus(
         aptos_channels::new(1_024, &counters::PENDING_ROUND_TIMEOUTS);
     let (self_sender, self_receiver) = aptos_channels::new(1_024, &counters::PENDING_SELF_MESSAGES);
 
+    // Publish the ordered blocks and commit decisions to the consensus observers (if enabled)
+    let consensus_publisher = consensus_observer_network_client
+        .filter(|_| node_config.consensus_observer.publisher_enabled)
+        .map(|network_client| {
+            let (consensus_publisher, publish_rx) = ConsensusPublisher::new(network_client);
+            runtime.spawn(
+                consensus_publisher
+                    .clone()
+                    .start(consensus_observer_network_service_events, publish_rx),
+            );
+            consensus_publisher
+        });
+
     let consensus_network_client = ConsensusNetworkClient::new(network_client);
     let bounded_executor = BoundedExecutor::new(8, runtime.handle().clone());
     let epoch_mgr = EpochManager::new(
@@ -70,12 +96,15 @@ pub fn start_consensus(
         consensus_network_client,
         timeout_sender,
         consensus_to_mempool_sender,
+        back_pressure_status,
         state_computer,
         storage.clone(),
         quorum_store_db.clone(),
         reconfig_events,
         bounded_executor,
         aptos_time_service::TimeService::real(),
+        consensus_publisher,
+        recent_commit_cache,
     );
 
     let (network_task, network_receiver) = NetworkTask::new(network_service_events, self_receiver);
@@ -86,3 +115,50 @@ pub fn start_consensus(
     debug!("Consensus started.");
     (runtime, storage, quorum_store_db)
 }
+
+/// Helper function to start the consensus observer on a fullnode and return the runtime
+pub fn start_consensus_observer(
+    node_config: &NodeConfig,
+    network_client: NetworkClient<ConsensusObserverMessage>,
+    network_service_events: NetworkServiceEvents<ConsensusObserverMessage>,
+    state_sync_notifier: Arc<dyn ConsensusNotificationSender>,
+    consensus_to_mempool_sender: mpsc::Sender<QuorumStoreRequest>,
+    aptos_db: DbReaderWriter,
+    reconfig_events: ReconfigNotificationListener<DbBackedOnChainConfig>,
+) -> Runtime {
+    let runtime = aptos_runtimes::spawn_named_runtime("observer".into(), None);
+
+    let txn_notifier = Arc::new(MempoolNotifier::new(
+        consensus_to_mempool_sender,
+        node_config.consensus.mempool_executed_txn_timeout_ms,
+    ));
+
+    let execution_client = Arc::new(ExecutionProxy::new(
+        Arc::new(BlockExecutor::<AptosVM>::new(aptos_db.clone())),
+        txn_notifier,
+        state_sync_notifier,
+        runtime.handle(),
+        TransactionFilter::new(node_config.execution.transaction_filter.clone()),
+        Arc::new(RecentCommitCache::default()),
+    ));
+
+    // Republish the received updates to the downstream observers (if enabled)
+    let consensus_publisher = node_config.consensus_observer.publisher_enabled.then(|| {
+        let (consensus_publisher, publish_rx) = ConsensusPublisher::new(network_client.clone());
+        runtime.spawn(consensus_publisher.clone().start(None, publish_rx));
+        consensus_publisher
+    });
+
+    let consensus_observer = ConsensusObserver::new(
+        node_config.consensus_observer,
+        network_client,
+        aptos_db.reader,
+        execution_client,
+        reconfig_events,
+        consensus_publisher,
+    );
+    runtime.spawn(consensus_observer.start(network_service_events));
+
+    debug!("Consensus observer started.");
+    runtime
+}
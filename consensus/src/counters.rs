@@ -299,6 +299,16 @@ pub static PROPOSER_PENDING_BLOCKS_FILL_FRACTION: Lazy<Gauge> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Count of the proposed payload (txns or proofs) dropped by the pre-validation, by reason
+pub static PROPOSER_PRE_VALIDATION_FILTERED: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_proposer_pre_validation_filtered",
+        "Count of the proposed payload (txns or proofs) dropped by the pre-validation, by reason",
+        &["reason"]
+    )
+    .unwrap()
+});
+
 /// Next set of counters are computed at leader election time, with some delay.
 
 /// Current voting power fraction that participated in consensus
@@ -676,6 +686,16 @@ pub static BUFFER_MANAGER_MSGS: Lazy<IntCounterVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Counters(queued,dequeued,dropped) related to randomness generation messages
+pub static RAND_MANAGER_MSGS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_consensus_rand_manager_msgs_count",
+        "Counters(queued,dequeued,dropped) related to randomness generation messages",
+        &["state"]
+    )
+    .unwrap()
+});
+
 /// Counters(queued,dequeued,dropped) related to consensus channel
 pub static CONSENSUS_CHANNEL_MSGS: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
@@ -17,6 +17,20 @@ pub trait TChainHealthBackoff: Send + Sync {
     fn get_round_payload_limits(&self, round: Round) -> (f64, Option<(u64, u64)>);
 }
 
+/// Chain health backoff used when anchors are not elected by reputation, in which case
+/// there is no participation history to derive the chain health from.
+pub struct NoChainHealthBackoff {}
+
+impl TChainHealthBackoff for NoChainHealthBackoff {
+    fn get_round_backoff(&self, _round: Round) -> (f64, Option<Duration>) {
+        (1.0, None)
+    }
+
+    fn get_round_payload_limits(&self, _round: Round) -> (f64, Option<(u64, u64)>) {
+        (1.0, None)
+    }
+}
+
 mod leader_reputation_adapter;
 mod round_robin;
 
@@ -2,9 +2,11 @@
 
 use super::{
     adapter::{OrderedNotifierAdapter, TLedgerInfoProvider},
-    anchor_election::TChainHealthBackoff,
+    anchor_election::{AnchorElection, TChainHealthBackoff},
     dag_driver::DagDriver,
-    dag_fetcher::{DagFetcher, DagFetcherService, FetchRequestHandler},
+    dag_fetcher::{
+        DagFetcher, DagFetcherService, FetchNodesByRoundRangeHandler, FetchRequestHandler,
+    },
     dag_handler::NetworkHandler,
     dag_network::TDAGNetworkSender,
     dag_state_sync::{DagStateSynchronizer, StateSyncTrigger},
@@ -18,7 +20,10 @@ use super::{
 use crate::{
     dag::{
         adapter::{compute_initial_block_and_ledger_info, LedgerInfoProvider},
-        anchor_election::{LeaderReputationAdapter, MetadataBackendAdapter},
+        anchor_election::{
+            LeaderReputationAdapter, MetadataBackendAdapter, NoChainHealthBackoff,
+            RoundRobinAnchorElection,
+        },
         dag_state_sync::StateSyncStatus,
         observability::logging::{LogEvent, LogSchema},
         round_state::{AdaptiveResponsive, RoundState},
@@ -40,9 +45,12 @@ use aptos_config::config::{DagConsensusConfig, DagRoundStateConfig, ReliableBroa
 use aptos_consensus_types::common::{Author, Round};
 use aptos_infallible::RwLock;
 use aptos_logger::{debug, error};
+use aptos_mempool::BackPressureStatus;
 use aptos_reliable_broadcast::{RBNetworkSender, ReliableBroadcast};
 use aptos_types::{
-    block_info::BlockInfo, epoch_state::EpochState, on_chain_config::DagConsensusConfigV1,
+    block_info::BlockInfo,
+    epoch_state::EpochState,
+    on_chain_config::{AnchorElectionMode, DagConsensusConfigV2, LeaderReputationType},
     validator_signer::ValidatorSigner,
 };
 use futures_channel::{
@@ -56,7 +64,7 @@ use tokio_retry::strategy::ExponentialBackoff;
 pub struct DagBootstrapper {
     self_peer: Author,
     config: DagConsensusConfig,
-    onchain_config: DagConsensusConfigV1,
+    onchain_config: DagConsensusConfigV2,
     signer: Arc<ValidatorSigner>,
     epoch_state: Arc<EpochState>,
     storage: Arc<dyn DAGStorage>,
@@ -67,13 +75,14 @@ pub struct DagBootstrapper {
     payload_manager: Arc<PayloadManager>,
     payload_client: Arc<dyn PayloadClient>,
     state_computer: Arc<dyn StateComputer>,
+    back_pressure_status: BackPressureStatus,
 }
 
 impl DagBootstrapper {
     pub fn new(
         self_peer: Author,
         config: DagConsensusConfig,
-        onchain_config: DagConsensusConfigV1,
+        onchain_config: DagConsensusConfigV2,
         signer: Arc<ValidatorSigner>,
         epoch_state: Arc<EpochState>,
         storage: Arc<dyn DAGStorage>,
@@ -84,6 +93,7 @@ impl DagBootstrapper {
         payload_manager: Arc<PayloadManager>,
         payload_client: Arc<dyn PayloadClient>,
         state_computer: Arc<dyn StateComputer>,
+        back_pressure_status: BackPressureStatus,
     ) -> Self {
         Self {
             self_peer,
@@ -99,6 +109,7 @@ impl DagBootstrapper {
             payload_manager,
             payload_client,
             state_computer,
+            back_pressure_status,
         }
     }
 
@@ -136,10 +147,55 @@ impl DagBootstrapper {
             ledger_info_provider.clone(),
         ));
 
+        let (anchor_election, chain_health_backoff) = self.build_anchor_election();
+
+        let order_rule = OrderRule::new(
+            self.epoch_state.clone(),
+            commit_round + 1,
+            dag.clone(),
+            anchor_election,
+            notifier,
+            self.storage.clone(),
+            self.onchain_config.dag_ordering_causal_history_window as Round,
+        );
+
+        (dag, order_rule, chain_health_backoff)
+    }
+
+    fn build_anchor_election(&self) -> (Arc<dyn AnchorElection>, Arc<dyn TChainHealthBackoff>) {
+        match &self.onchain_config.anchor_election_mode {
+            AnchorElectionMode::RoundRobin => {
+                let anchor_election: Arc<dyn AnchorElection> =
+                    Arc::new(RoundRobinAnchorElection::new(
+                        self.epoch_state.verifier.get_ordered_account_addresses(),
+                    ));
+                let chain_health_backoff: Arc<dyn TChainHealthBackoff> =
+                    Arc::new(NoChainHealthBackoff {});
+                (anchor_election, chain_health_backoff)
+            },
+            AnchorElectionMode::LeaderReputation(leader_reputation_type) => {
+                let reputation_adapter =
+                    Arc::new(self.build_leader_reputation_adapter(leader_reputation_type));
+                (reputation_adapter.clone(), reputation_adapter)
+            },
+        }
+    }
+
+    fn build_leader_reputation_adapter(
+        &self,
+        leader_reputation_type: &LeaderReputationType,
+    ) -> LeaderReputationAdapter {
         let num_validators = self.epoch_state.verifier.len();
+        let config = match leader_reputation_type {
+            LeaderReputationType::ProposerAndVoter(config)
+            | LeaderReputationType::ProposerAndVoterV2(config) => config,
+        };
+        let proposer_window_size =
+            num_validators * config.proposer_window_num_validators_multiplier;
+        let voter_window_size = num_validators * config.voter_window_num_validators_multiplier;
         // TODO: support multiple epochs
         let metadata_adapter = Arc::new(MetadataBackendAdapter::new(
-            num_validators * 10,
+            std::cmp::max(proposer_window_size, voter_window_size),
             HashMap::from([(
                 self.epoch_state.epoch,
                 self.epoch_state
@@ -148,25 +204,27 @@ impl DagBootstrapper {
                     .clone(),
             )]),
         ));
-        // TODO: use onchain config
         let heuristic: Box<dyn ReputationHeuristic> = Box::new(ProposerAndVoterHeuristic::new(
             self.self_peer,
-            1000,
-            10,
-            1,
-            10,
-            num_validators,
-            num_validators * 10,
-            false,
+            config.active_weight,
+            config.inactive_weight,
+            config.failed_weight,
+            config.failure_threshold_percent,
+            voter_window_size,
+            proposer_window_size,
+            leader_reputation_type.use_reputation_window_from_stale_end(),
         ));
 
-        let voting_power: Vec<u64> = self
-            .epoch_state
-            .verifier
-            .get_ordered_account_addresses_iter()
-            .map(|p| self.epoch_state.verifier.get_voting_power(&p).unwrap())
-            .collect();
-        let anchor_election = Arc::new(LeaderReputationAdapter::new(
+        let voting_power: Vec<u64> = if config.weight_by_voting_power {
+            self.epoch_state
+                .verifier
+                .get_ordered_account_addresses_iter()
+                .map(|p| self.epoch_state.verifier.get_voting_power(&p).unwrap())
+                .collect()
+        } else {
+            vec![1; num_validators]
+        };
+        LeaderReputationAdapter::new(
             self.epoch_state.epoch,
             HashMap::from([(
                 self.epoch_state.epoch,
@@ -177,19 +235,7 @@ impl DagBootstrapper {
             heuristic,
             100,
             ChainHealthBackoffConfig::new(self.config.chain_backoff_config.clone()),
-        ));
-
-        let order_rule = OrderRule::new(
-            self.epoch_state.clone(),
-            commit_round + 1,
-            dag.clone(),
-            anchor_election.clone(),
-            notifier,
-            self.storage.clone(),
-            self.onchain_config.dag_ordering_causal_history_window as Round,
-        );
-
-        (dag, order_rule, anchor_election)
+        )
     }
 
     fn bootstrap_components(
@@ -253,6 +299,7 @@ impl DagBootstrapper {
             self.onchain_config.dag_ordering_causal_history_window as Round,
             self.config.node_payload_config.clone(),
             chain_health_backoff,
+            self.back_pressure_status.clone(),
         );
         let rb_handler = NodeBroadcastHandler::new(
             dag.clone(),
@@ -262,13 +309,19 @@ impl DagBootstrapper {
             fetch_requester,
             self.config.node_payload_config.clone(),
         );
-        let fetch_handler = FetchRequestHandler::new(dag, self.epoch_state.clone());
+        let fetch_handler = FetchRequestHandler::new(dag.clone(), self.epoch_state.clone());
+        let range_fetch_handler = FetchNodesByRoundRangeHandler::new(
+            dag,
+            self.epoch_state.clone(),
+            self.config.fetcher_config.max_rounds_per_range_fetch,
+        );
 
         let dag_handler = NetworkHandler::new(
             self.epoch_state.clone(),
             rb_handler,
             dag_driver,
             fetch_handler,
+            range_fetch_handler,
             node_fetch_waiter,
             certified_node_fetch_waiter,
             state_sync_trigger,
@@ -416,7 +469,7 @@ pub(super) fn bootstrap_dag_for_test(
     let bootstraper = DagBootstrapper::new(
         self_peer,
         DagConsensusConfig::default(),
-        DagConsensusConfigV1::default(),
+        DagConsensusConfigV2::default(),
         signer.into(),
         epoch_state.clone(),
         storage.clone(),
@@ -427,6 +480,7 @@ pub(super) fn bootstrap_dag_for_test(
         payload_manager,
         payload_client,
         state_computer,
+        BackPressureStatus::default(),
     );
 
     let ledger_info_from_storage = storage
@@ -30,6 +30,7 @@ use aptos_config::config::DagPayloadConfig;
 use aptos_consensus_types::common::{Author, PayloadFilter};
 use aptos_infallible::RwLock;
 use aptos_logger::{debug, error};
+use aptos_mempool::BackPressureStatus;
 use aptos_reliable_broadcast::ReliableBroadcast;
 use aptos_time_service::{TimeService, TimeServiceTrait};
 use aptos_types::{block_info::Round, epoch_state::EpochState};
@@ -59,6 +60,7 @@ pub(crate) struct DagDriver {
     window_size_config: Round,
     payload_config: DagPayloadConfig,
     chain_backoff: Arc<dyn TChainHealthBackoff>,
+    back_pressure_status: BackPressureStatus,
 }
 
 impl DagDriver {
@@ -79,6 +81,7 @@ impl DagDriver {
         window_size_config: Round,
         payload_config: DagPayloadConfig,
         chain_backoff: Arc<dyn TChainHealthBackoff>,
+        back_pressure_status: BackPressureStatus,
     ) -> Self {
         let pending_node = storage
             .get_pending_node()
@@ -103,6 +106,7 @@ impl DagDriver {
             window_size_config,
             payload_config,
             chain_backoff,
+            back_pressure_status,
         };
 
         // If we were broadcasting the node for the round already, resume it
@@ -308,6 +312,8 @@ impl DagDriver {
                 )
             }
         };
+        let (max_txns_per_round, max_size_per_round_bytes) =
+            self.apply_backlog_backpressure(max_txns_per_round, max_size_per_round_bytes);
         // TODO: warn/panic if division yields 0 txns
         let max_txns = max_txns_per_round
             .saturating_div(
@@ -322,6 +328,27 @@ impl DagDriver {
 
         (max_txns, max_txn_size_bytes)
     }
+
+    /// Shrinks the per round limits while quorum store has a backlog of transactions or
+    /// proofs waiting to be ordered, without going under the configured minimums.
+    fn apply_backlog_backpressure(&self, max_txns: u64, max_size_bytes: u64) -> (u64, u64) {
+        let backlogged =
+            self.back_pressure_status.txn_count() || self.back_pressure_status.proof_count();
+        counters::PAYLOAD_BACKLOG_BACKPRESSURE.set(backlogged as i64);
+        if !backlogged {
+            return (max_txns, max_size_bytes);
+        }
+
+        let fraction = self.payload_config.backlog_payload_fraction;
+        (
+            ((max_txns as f64 * fraction) as u64)
+                .max(self.payload_config.backlog_min_sending_txns_per_round)
+                .min(max_txns),
+            ((max_size_bytes as f64 * fraction) as u64)
+                .max(self.payload_config.backlog_min_sending_size_per_round_bytes)
+                .min(max_size_bytes),
+        )
+    }
 }
 
 #[async_trait]
@@ -7,7 +7,10 @@ use crate::dag::{
     dag_store::Dag,
     errors::FetchRequestHandleError,
     observability::logging::{LogEvent, LogSchema},
-    types::{CertifiedNode, FetchResponse, Node, NodeMetadata, RemoteFetchRequest},
+    types::{
+        CertifiedNode, FetchNodesByRoundRange, FetchResponse, Node, NodeMetadata,
+        RemoteFetchRequest,
+    },
     RpcHandler, RpcWithFallback,
 };
 use anyhow::{anyhow, ensure};
@@ -190,6 +193,10 @@ impl DagFetcherService {
         node: &Node,
         responders: Vec<Author>,
     ) -> anyhow::Result<()> {
+        if let Err(err) = self.catch_up_by_round_range(node, &responders).await {
+            info!(error = ?err, "unable to catch up by round range");
+        }
+
         let remote_request = {
             let dag_reader = self.dag.read();
             ensure!(
@@ -218,6 +225,30 @@ impl DagFetcherService {
             .fetch(remote_request, responders, self.dag.clone())
             .await
     }
+
+    /// Pulls the missing rounds below the parents of `node` by ranges, when there are too many
+    /// of them to be fetched along with the parents.
+    async fn catch_up_by_round_range(
+        &self,
+        node: &Node,
+        responders: &[Author],
+    ) -> anyhow::Result<()> {
+        let max_rounds = self.inner.config.max_rounds_per_range_fetch.max(1);
+        let target_round = node.round().saturating_sub(1);
+        let mut start_round = self.dag.read().lowest_incomplete_round();
+        while start_round + max_rounds <= target_round {
+            let end_round = start_round + max_rounds - 1;
+            let request = FetchNodesByRoundRange::new(
+                node.metadata().epoch(),
+                self.dag.read().bitmask_for_range(start_round, end_round),
+            );
+            self.inner
+                .fetch_round_range(request, responders.to_vec(), self.dag.clone())
+                .await?;
+            start_round = end_round + 1;
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -253,6 +284,65 @@ impl DagFetcher {
     }
 }
 
+impl DagFetcher {
+    pub(crate) async fn fetch_round_range(
+        &self,
+        request: FetchNodesByRoundRange,
+        responders: Vec<Author>,
+        dag: Arc<RwLock<Dag>>,
+    ) -> anyhow::Result<()> {
+        debug!(
+            LogSchema::new(LogEvent::FetchNodes),
+            start_round = request.start_round(),
+            end_round = request.end_round(),
+            missing_nodes = request.exists_bitmask().num_missing(),
+        );
+        let mut rpc = RpcWithFallback::new(
+            responders,
+            request.clone().into(),
+            Duration::from_millis(self.config.retry_interval_ms),
+            Duration::from_millis(self.config.rpc_timeout_ms),
+            self.network.clone(),
+            self.time_service.clone(),
+            self.config.min_concurrent_responders,
+            self.config.max_concurrent_responders,
+        );
+
+        while let Some(RpcResultWithResponder { responder, result }) = rpc.next().await {
+            match result {
+                Ok(DAGRpcResult(Ok(response))) => {
+                    match FetchResponse::try_from(response).and_then(|response| {
+                        response.verify_range(&request, &self.epoch_state.verifier)
+                    }) {
+                        Ok(fetch_response) => {
+                            let mut certified_nodes = fetch_response.certified_nodes();
+                            // Parents must be added before their children.
+                            certified_nodes.sort_by_key(|node| node.round());
+                            let mut dag_writer = dag.write();
+                            for node in certified_nodes {
+                                if let Err(e) = dag_writer.add_node(node) {
+                                    error!(error = ?e, "failed to add node");
+                                }
+                            }
+                            return Ok(());
+                        },
+                        Err(err) => {
+                            info!(error = ?err, "failure parsing/verifying range fetch response from {}", responder);
+                        },
+                    };
+                },
+                Ok(DAGRpcResult(Err(dag_rpc_error))) => {
+                    info!(error = ?dag_rpc_error, responder = responder, "range fetch failure: target {} returned error", responder);
+                },
+                Err(err) => {
+                    info!(error = ?err, responder = responder, "rpc failed to {}", responder);
+                },
+            }
+        }
+        Err(anyhow!("Fetch by round range with fallback failed"))
+    }
+}
+
 #[async_trait]
 impl TDagFetcher for DagFetcher {
     async fn fetch(
@@ -392,3 +482,60 @@ impl RpcHandler for FetchRequestHandler {
         Ok(FetchResponse::new(message.epoch(), certified_nodes))
     }
 }
+
+pub struct FetchNodesByRoundRangeHandler {
+    dag: Arc<RwLock<Dag>>,
+    author_to_index: HashMap<Author, usize>,
+    max_rounds: u64,
+}
+
+impl FetchNodesByRoundRangeHandler {
+    pub fn new(dag: Arc<RwLock<Dag>>, epoch_state: Arc<EpochState>, max_rounds: u64) -> Self {
+        Self {
+            dag,
+            author_to_index: epoch_state.verifier.address_to_validator_index().clone(),
+            max_rounds,
+        }
+    }
+}
+
+#[async_trait]
+impl RpcHandler for FetchNodesByRoundRangeHandler {
+    type Request = FetchNodesByRoundRange;
+    type Response = FetchResponse;
+
+    async fn process(&mut self, message: Self::Request) -> anyhow::Result<Self::Response> {
+        let dag_reader = self.dag.read();
+
+        debug!(
+            LogSchema::new(LogEvent::ReceiveFetchNodes).round(dag_reader.highest_round()),
+            start_round = message.start_round(),
+            end_round = message.end_round(),
+        );
+        ensure!(
+            message.num_rounds() as u64 <= self.max_rounds,
+            FetchRequestHandleError::TooManyRounds(message.num_rounds(), self.max_rounds)
+        );
+        ensure!(
+            dag_reader.lowest_round() <= message.start_round(),
+            FetchRequestHandleError::GarbageCollected(
+                message.start_round(),
+                dag_reader.lowest_round()
+            ),
+        );
+
+        let certified_nodes: Vec<_> = dag_reader
+            .get_nodes_in_round_range(message.start_round(), message.end_round())
+            .filter(|node| {
+                self.author_to_index
+                    .get(node.author())
+                    .map_or(false, |author_idx| {
+                        !message.exists_bitmask().has(node.round(), *author_idx)
+                    })
+            })
+            .map(|node| node.as_ref().clone())
+            .collect();
+
+        Ok(FetchResponse::new(message.epoch(), certified_nodes))
+    }
+}
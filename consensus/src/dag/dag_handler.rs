@@ -3,7 +3,7 @@
 use crate::{
     dag::{
         dag_driver::DagDriver,
-        dag_fetcher::{FetchRequestHandler, FetchWaiter},
+        dag_fetcher::{FetchNodesByRoundRangeHandler, FetchRequestHandler, FetchWaiter},
         dag_network::RpcHandler,
         dag_state_sync::{StateSyncStatus, StateSyncTrigger},
         errors::{
@@ -31,6 +31,7 @@ pub(crate) struct NetworkHandler {
     node_receiver: NodeBroadcastHandler,
     dag_driver: DagDriver,
     fetch_receiver: FetchRequestHandler,
+    range_fetch_receiver: FetchNodesByRoundRangeHandler,
     node_fetch_waiter: FetchWaiter<Node>,
     certified_node_fetch_waiter: FetchWaiter<CertifiedNode>,
     state_sync_trigger: StateSyncTrigger,
@@ -43,6 +44,7 @@ impl NetworkHandler {
         node_receiver: NodeBroadcastHandler,
         dag_driver: DagDriver,
         fetch_receiver: FetchRequestHandler,
+        range_fetch_receiver: FetchNodesByRoundRangeHandler,
         node_fetch_waiter: FetchWaiter<Node>,
         certified_node_fetch_waiter: FetchWaiter<CertifiedNode>,
         state_sync_trigger: StateSyncTrigger,
@@ -53,6 +55,7 @@ impl NetworkHandler {
             node_receiver,
             dag_driver,
             fetch_receiver,
+            range_fetch_receiver,
             node_fetch_waiter,
             certified_node_fetch_waiter,
             state_sync_trigger,
@@ -159,6 +162,15 @@ impl NetworkHandler {
                             err.downcast::<FetchRequestHandleError>()
                                 .map_or(DAGError::Unknown, DAGError::FetchRequestHandleError)
                         }),
+                    DAGMessage::FetchNodesByRoundRange(request) => self
+                        .range_fetch_receiver
+                        .process(request)
+                        .await
+                        .map(|r| r.into())
+                        .map_err(|err| {
+                            err.downcast::<FetchRequestHandleError>()
+                                .map_or(DAGError::Unknown, DAGError::FetchRequestHandleError)
+                        }),
                     _ => unreachable!("verification must catch this error"),
                 },
                 Err(err) => {
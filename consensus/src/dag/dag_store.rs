@@ -202,6 +202,17 @@ impl Dag {
         round_ref[*index].as_mut()
     }
 
+    pub fn get_nodes_in_round_range(
+        &self,
+        start_round: Round,
+        end_round: Round,
+    ) -> impl Iterator<Item = &Arc<CertifiedNode>> {
+        self.nodes_by_round
+            .range(start_round..=end_round)
+            .flat_map(|(_, round_ref)| round_ref.iter().flatten())
+            .map(|node_status| node_status.as_node())
+    }
+
     fn get_round_iter(&self, round: Round) -> Option<impl Iterator<Item = &NodeStatus>> {
         self.nodes_by_round
             .get(&round)
@@ -336,8 +347,14 @@ impl Dag {
     }
 
     pub fn bitmask(&self, target_round: Round) -> DagSnapshotBitmask {
-        let lowest_round = self.lowest_incomplete_round();
+        self.bitmask_for_range(self.lowest_incomplete_round(), target_round)
+    }
 
+    pub fn bitmask_for_range(
+        &self,
+        lowest_round: Round,
+        target_round: Round,
+    ) -> DagSnapshotBitmask {
         let mut bitmask: Vec<_> = self
             .nodes_by_round
             .range(lowest_round..=target_round)
@@ -28,6 +28,8 @@ pub enum FetchRequestHandleError {
     TargetsMissing(BitVec),
     #[error("garbage collected, request round {0}, lowest round {1}")]
     GarbageCollected(Round, Round),
+    #[error("too many rounds requested {0}, max {1}")]
+    TooManyRounds(usize, u64),
 }
 
 #[derive(Clone, Debug, ThisError, Serialize, Deserialize)]
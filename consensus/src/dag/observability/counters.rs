@@ -32,3 +32,12 @@ pub static CURRENT_ROUND: Lazy<IntGauge> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+/// This counter is set to 1 while node payloads are reduced due to the quorum store backlog.
+pub static PAYLOAD_BACKLOG_BACKPRESSURE: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_consensus_dag_payload_backlog_backpressure",
+        "Whether the dag driver reduces node payloads due to the quorum store backlog."
+    )
+    .unwrap()
+});
@@ -25,6 +25,7 @@ use crate::{
 use aptos_config::config::{DagFetcherConfig, DagPayloadConfig};
 use aptos_consensus_types::common::{Author, Round};
 use aptos_infallible::RwLock;
+use aptos_mempool::BackPressureStatus;
 use aptos_reliable_broadcast::{RBNetworkSender, ReliableBroadcast};
 use aptos_time_service::TimeService;
 use aptos_types::{
@@ -177,6 +178,7 @@ async fn test_certified_node_handler() {
         TEST_DAG_WINDOW as Round,
         DagPayloadConfig::default(),
         Arc::new(MockChainHealthBackoff {}),
+        BackPressureStatus::default(),
     );
 
     let first_round_node = new_certified_node(1, signers[0].author(), vec![]);
@@ -2,15 +2,15 @@
 
 use super::dag_test::MockStorage;
 use crate::dag::{
-    dag_fetcher::FetchRequestHandler,
+    dag_fetcher::{FetchNodesByRoundRangeHandler, FetchRequestHandler},
     dag_store::Dag,
     tests::helpers::{new_certified_node, TEST_DAG_WINDOW},
-    types::{DagSnapshotBitmask, FetchResponse, RemoteFetchRequest},
+    types::{DagSnapshotBitmask, FetchNodesByRoundRange, FetchResponse, RemoteFetchRequest},
     RpcHandler,
 };
 use aptos_infallible::RwLock;
 use aptos_types::{epoch_state::EpochState, validator_verifier::random_validator_verifier};
-use claims::assert_ok_eq;
+use claims::{assert_err, assert_ok, assert_ok_eq};
 use std::sync::Arc;
 
 #[tokio::test]
@@ -61,3 +61,63 @@ async fn test_dag_fetcher_receiver() {
 }
 
 // TODO: add more tests after commit rule tests
+
+#[tokio::test]
+async fn test_fetch_nodes_by_round_range_handler() {
+    let (signers, validator_verifier) = random_validator_verifier(4, None, false);
+    let epoch_state = Arc::new(EpochState {
+        epoch: 1,
+        verifier: validator_verifier,
+    });
+    let storage = Arc::new(MockStorage::new());
+    let dag = Arc::new(RwLock::new(Dag::new(
+        epoch_state.clone(),
+        storage,
+        0,
+        TEST_DAG_WINDOW,
+    )));
+
+    let mut handler = FetchNodesByRoundRangeHandler::new(dag.clone(), epoch_state.clone(), 2);
+
+    // Round 1 - nodes 0, 1, 2 links to vec![]
+    let mut first_round_nodes = vec![];
+    for signer in &signers[0..3] {
+        let node = new_certified_node(1, signer.author(), vec![]);
+        assert!(dag.write().add_node(node.clone()).is_ok());
+        first_round_nodes.push(node);
+    }
+
+    // Round 2 - nodes 0, 1 link to all of round 1
+    let parents: Vec<_> = first_round_nodes
+        .iter()
+        .map(|node| node.certificate())
+        .collect();
+    let mut second_round_nodes = vec![];
+    for signer in &signers[0..2] {
+        let node = new_certified_node(2, signer.author(), parents.clone());
+        assert!(dag.write().add_node(node.clone()).is_ok());
+        second_round_nodes.push(node);
+    }
+
+    let request = FetchNodesByRoundRange::new(
+        1,
+        DagSnapshotBitmask::new(1, vec![vec![true, false, true, false], vec![
+            false, true, false, false,
+        ]]),
+    );
+    assert_ok!(request.verify(&epoch_state.verifier));
+    assert_ok_eq!(
+        handler.process(request).await,
+        FetchResponse::new(1, vec![
+            first_round_nodes[1].clone(),
+            second_round_nodes[0].clone()
+        ])
+    );
+
+    // Requesting more rounds than allowed fails.
+    let request = FetchNodesByRoundRange::new(
+        1,
+        DagSnapshotBitmask::new(1, vec![vec![false; 4], vec![false; 4], vec![false; 4]]),
+    );
+    assert_err!(handler.process(request).await);
+}
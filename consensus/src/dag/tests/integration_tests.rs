@@ -6,13 +6,14 @@ use crate::{
     experimental::buffer_manager::OrderedBlocks,
     network::{IncomingDAGRequest, NetworkSender},
     network_interface::{ConsensusMsg, ConsensusNetworkClient, DIRECT_SEND, RPC},
-    network_tests::{NetworkPlayground, TwinId},
+    network_tests::{NetworkPlayground, PartitionPhase, TwinId},
     payload_manager::PayloadManager,
     test_utils::{consensus_runtime, EmptyStateComputer, MockPayloadManager, MockStorage},
 };
 use aptos_channels::{aptos_channel, message_queues::QueueStyle};
 use aptos_config::network_id::{NetworkId, PeerNetworkId};
 use aptos_consensus_types::common::Author;
+use aptos_crypto::HashValue;
 use aptos_logger::debug;
 use aptos_network::{
     application::interface::NetworkClient,
@@ -38,7 +39,7 @@ use futures::{
 };
 use futures_channel::mpsc::UnboundedReceiver;
 use maplit::hashmap;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use tokio::task::JoinHandle;
 
 struct DagBootstrapUnit {
@@ -163,27 +164,43 @@ fn create_network(
     (network, all_network_events)
 }
 
+/// Bootstraps a node per signer, plus a twin for each of the first `num_twins` signers.
+/// Twins share the signer of their original node and are returned after all the other nodes.
 fn bootstrap_nodes(
     playground: &mut NetworkPlayground,
     signers: Vec<ValidatorSigner>,
     validators: ValidatorVerifier,
-) -> (Vec<DagBootstrapUnit>, Vec<UnboundedReceiver<OrderedBlocks>>) {
+    num_twins: usize,
+) -> (
+    Vec<DagBootstrapUnit>,
+    Vec<UnboundedReceiver<OrderedBlocks>>,
+    Vec<TwinId>,
+) {
     let peers_and_metadata = playground.peer_protocols();
+    for signer in &signers {
+        let peer_id = signer.author();
+        let mut conn_meta = ConnectionMetadata::mock(peer_id);
+        conn_meta.application_protocols = ProtocolIdSet::from_iter([
+            ProtocolId::ConsensusDirectSendJson,
+            ProtocolId::ConsensusDirectSendBcs,
+            ProtocolId::ConsensusRpcBcs,
+        ]);
+        let peer_network_id = PeerNetworkId::new(NetworkId::Validator, peer_id);
+        peers_and_metadata
+            .insert_connection_metadata(peer_network_id, conn_meta)
+            .unwrap();
+    }
+
+    let mut twin_ids = vec![];
     let (nodes, ordered_node_receivers) = signers
         .iter()
+        .chain(signers.iter().take(num_twins))
         .enumerate()
         .map(|(id, signer)| {
-            let peer_id = signer.author();
-            let mut conn_meta = ConnectionMetadata::mock(peer_id);
-            conn_meta.application_protocols = ProtocolIdSet::from_iter([
-                ProtocolId::ConsensusDirectSendJson,
-                ProtocolId::ConsensusDirectSendBcs,
-                ProtocolId::ConsensusRpcBcs,
-            ]);
-            let peer_network_id = PeerNetworkId::new(NetworkId::Validator, peer_id);
-            peers_and_metadata
-                .insert_connection_metadata(peer_network_id, conn_meta)
-                .unwrap();
+            twin_ids.push(TwinId {
+                id,
+                author: signer.author(),
+            });
 
             let (_, storage) = MockStorage::start_for_testing((&validators).into());
             let (network, network_events) =
@@ -202,7 +219,35 @@ fn bootstrap_nodes(
         })
         .unzip();
 
-    (nodes, ordered_node_receivers)
+    (nodes, ordered_node_receivers, twin_ids)
+}
+
+/// Receives ordered blocks until at least `count` blocks have been ordered in total.
+async fn receive_ordered_blocks(
+    receiver: &mut UnboundedReceiver<OrderedBlocks>,
+    ordered: &mut Vec<HashValue>,
+    count: usize,
+) {
+    while ordered.len() < count {
+        let blocks = receiver.next().await.unwrap();
+        ordered.extend(blocks.ordered_blocks.iter().map(|block| block.id()));
+    }
+}
+
+/// Safety check: the sequence of blocks ordered by any node must be a prefix of the
+/// sequence ordered by any other node.
+fn assert_ordering_consistent(all_ordered: &[Vec<HashValue>]) {
+    let longest = all_ordered
+        .iter()
+        .max_by_key(|ordered| ordered.len())
+        .unwrap();
+    for ordered in all_ordered {
+        assert_eq!(
+            ordered.as_slice(),
+            &longest[..ordered.len()],
+            "ordering diverged"
+        );
+    }
 }
 
 #[tokio::test]
@@ -212,7 +257,8 @@ async fn test_dag_e2e() {
     let mut playground = NetworkPlayground::new(runtime.handle().clone());
     let (signers, validators) = random_validator_verifier(num_nodes, None, false);
 
-    let (nodes, mut ordered_node_receivers) = bootstrap_nodes(&mut playground, signers, validators);
+    let (nodes, mut ordered_node_receivers, _) =
+        bootstrap_nodes(&mut playground, signers, validators, 0);
     for node in nodes {
         runtime.spawn(node.start());
     }
@@ -235,3 +281,142 @@ async fn test_dag_e2e() {
     }
     runtime.shutdown_background();
 }
+
+#[tokio::test]
+async fn test_dag_partition_heals() {
+    let num_nodes = 7;
+    let runtime = consensus_runtime();
+    let mut playground = NetworkPlayground::new(runtime.handle().clone());
+    let conditions = playground.network_conditions();
+    let (signers, validators) = random_validator_verifier(num_nodes, None, false);
+
+    let (nodes, mut ordered_node_receivers, twin_ids) =
+        bootstrap_nodes(&mut playground, signers, validators, 0);
+    for node in nodes {
+        runtime.spawn(node.start());
+    }
+
+    // The majority partition holds a quorum of 5 out of 7 and keeps ordering.
+    let (majority, minority) = twin_ids.split_at(5);
+    assert!(conditions.split_network(&[majority.to_vec(), minority.to_vec()]));
+
+    runtime.spawn(playground.start());
+
+    let mut all_ordered = vec![vec![]; num_nodes];
+    for (receiver, ordered) in ordered_node_receivers
+        .iter_mut()
+        .zip(all_ordered.iter_mut())
+        .take(majority.len())
+    {
+        receive_ordered_blocks(receiver, ordered, 5).await;
+    }
+    assert!(all_ordered[majority.len()..].iter().all(Vec::is_empty));
+
+    // Once healed, the minority catches up and every node keeps ordering the same blocks.
+    conditions.heal_network();
+    let target = all_ordered.iter().map(Vec::len).max().unwrap() + 5;
+    for (receiver, ordered) in ordered_node_receivers
+        .iter_mut()
+        .zip(all_ordered.iter_mut())
+    {
+        receive_ordered_blocks(receiver, ordered, target).await;
+    }
+    assert_ordering_consistent(&all_ordered);
+    runtime.shutdown_background();
+}
+
+#[tokio::test]
+async fn test_dag_partition_schedule_with_delays() {
+    let num_nodes = 7;
+    let runtime = consensus_runtime();
+    let mut playground = NetworkPlayground::new(runtime.handle().clone());
+    let conditions = playground.network_conditions();
+    let (signers, validators) = random_validator_verifier(num_nodes, None, false);
+
+    let (nodes, mut ordered_node_receivers, twin_ids) =
+        bootstrap_nodes(&mut playground, signers, validators, 0);
+    for node in nodes {
+        runtime.spawn(node.start());
+    }
+
+    // A slow node, and a rotating partition that never leaves a quorum connected.
+    conditions.delay_messages_from(twin_ids[6], Some(Duration::from_millis(300)));
+    let schedule = vec![
+        PartitionPhase {
+            partitions: vec![twin_ids.clone()],
+            duration: Duration::from_secs(1),
+        },
+        PartitionPhase {
+            partitions: vec![twin_ids[..3].to_vec(), twin_ids[3..].to_vec()],
+            duration: Duration::from_secs(2),
+        },
+        PartitionPhase {
+            partitions: vec![twin_ids[..4].to_vec(), twin_ids[4..].to_vec()],
+            duration: Duration::from_secs(2),
+        },
+    ];
+    let schedule_handle = runtime.spawn(conditions.clone().run_partition_schedule(schedule));
+
+    runtime.spawn(playground.start());
+
+    schedule_handle.await.unwrap();
+    conditions.delay_messages_from(twin_ids[6], None);
+
+    let mut all_ordered = vec![vec![]; num_nodes];
+    for (receiver, ordered) in ordered_node_receivers
+        .iter_mut()
+        .zip(all_ordered.iter_mut())
+    {
+        receive_ordered_blocks(receiver, ordered, 10).await;
+    }
+    assert_ordering_consistent(&all_ordered);
+    runtime.shutdown_background();
+}
+
+#[tokio::test]
+async fn test_dag_twins_safety() {
+    let num_nodes = 7;
+    let runtime = consensus_runtime();
+    let mut playground = NetworkPlayground::new(runtime.handle().clone());
+    let conditions = playground.network_conditions();
+    let (signers, validators) = random_validator_verifier(num_nodes, None, false);
+
+    // Node 0 and its twin (node 7) equivocate, each one talking to a different partition.
+    let (nodes, mut ordered_node_receivers, twin_ids) =
+        bootstrap_nodes(&mut playground, signers, validators, 1);
+    for node in nodes {
+        runtime.spawn(node.start());
+    }
+
+    let twin = twin_ids[num_nodes];
+    assert!(conditions.split_network(&[twin_ids[..5].to_vec(), vec![
+        twin,
+        twin_ids[5],
+        twin_ids[6],
+    ]]));
+
+    runtime.spawn(playground.start());
+
+    let mut all_ordered = vec![vec![]; num_nodes];
+    for (receiver, ordered) in ordered_node_receivers
+        .iter_mut()
+        .zip(all_ordered.iter_mut())
+        .take(5)
+        .skip(1)
+    {
+        receive_ordered_blocks(receiver, ordered, 5).await;
+    }
+
+    // Heal the network for the honest nodes, the twin stays isolated.
+    assert!(conditions.split_network(&[twin_ids[..num_nodes].to_vec(), vec![twin]]));
+    let target = all_ordered.iter().map(Vec::len).max().unwrap() + 5;
+    for (receiver, ordered) in ordered_node_receivers
+        .iter_mut()
+        .zip(all_ordered.iter_mut())
+        .skip(1)
+    {
+        receive_ordered_blocks(receiver, ordered, target).await;
+    }
+    assert_ordering_consistent(&all_ordered[1..]);
+    runtime.shutdown_background();
+}
@@ -682,6 +682,57 @@ impl RemoteFetchRequest {
     }
 }
 
+/// Represents a request to fetch the certified nodes of all rounds covered by `exists_bitmask`,
+/// without a target node. Used by a lagging validator to pull missing rounds in bulk,
+/// `exists_bitmask` marks the nodes it already has.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct FetchNodesByRoundRange {
+    epoch: u64,
+    exists_bitmask: DagSnapshotBitmask,
+}
+
+impl FetchNodesByRoundRange {
+    pub fn new(epoch: u64, exists_bitmask: DagSnapshotBitmask) -> Self {
+        Self {
+            epoch,
+            exists_bitmask,
+        }
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    pub fn exists_bitmask(&self) -> &DagSnapshotBitmask {
+        &self.exists_bitmask
+    }
+
+    pub fn start_round(&self) -> Round {
+        self.exists_bitmask.first_round()
+    }
+
+    pub fn end_round(&self) -> Round {
+        self.exists_bitmask.first_round() + self.exists_bitmask.len() as u64 - 1
+    }
+
+    pub fn num_rounds(&self) -> usize {
+        self.exists_bitmask.len()
+    }
+
+    pub fn verify(&self, verifier: &ValidatorVerifier) -> anyhow::Result<()> {
+        ensure!(!self.exists_bitmask.is_empty(), "Round range is empty");
+        ensure!(
+            self.exists_bitmask
+                .bitmask
+                .iter()
+                .all(|round| round.len() == verifier.len()),
+            "invalid bitmask: each round length is not equal to validator count"
+        );
+
+        Ok(())
+    }
+}
+
 /// Represents a response to FetchRequest, `certified_nodes` are indexed by [round][validator_index]
 /// It should fill in gaps from the `exists_bitmask` according to the parents from the `target_digest` node.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
@@ -730,6 +781,36 @@ impl FetchResponse {
 
         Ok(self)
     }
+
+    pub fn verify_range(
+        self,
+        request: &FetchNodesByRoundRange,
+        validator_verifier: &ValidatorVerifier,
+    ) -> anyhow::Result<Self> {
+        ensure!(
+            self.certified_nodes.iter().all(|node| {
+                let round = node.round();
+                if round < request.start_round() || round > request.end_round() {
+                    return false;
+                }
+                validator_verifier
+                    .address_to_validator_index()
+                    .get(node.author())
+                    .map_or(false, |author_idx| {
+                        !request.exists_bitmask.has(round, *author_idx)
+                    })
+            }),
+            "nodes don't match requested round range"
+        );
+        ensure!(
+            self.certified_nodes
+                .iter()
+                .all(|node| node.verify(validator_verifier).is_ok()),
+            "unable to verify certified nodes"
+        );
+
+        Ok(self)
+    }
 }
 
 #[derive(Serialize, Deserialize, Clone)]
@@ -756,6 +837,7 @@ pub enum DAGMessage {
     CertifiedAckMsg(CertifiedAck),
     FetchRequest(RemoteFetchRequest),
     FetchResponse(FetchResponse),
+    FetchNodesByRoundRange(FetchNodesByRoundRange),
 
     #[cfg(test)]
     TestMessage(TestMessage),
@@ -772,6 +854,7 @@ impl DAGMessage {
             DAGMessage::CertifiedAckMsg(_) => "CertifiedAckMsg",
             DAGMessage::FetchRequest(_) => "FetchRequest",
             DAGMessage::FetchResponse(_) => "FetchResponse",
+            DAGMessage::FetchNodesByRoundRange(_) => "FetchNodesByRoundRange",
             #[cfg(test)]
             DAGMessage::TestMessage(_) => "TestMessage",
             #[cfg(test)]
@@ -792,6 +875,7 @@ impl DAGMessage {
             DAGMessage::NodeMsg(node) => node.verify(sender, verifier),
             DAGMessage::CertifiedNodeMsg(certified_node) => certified_node.verify(sender, verifier),
             DAGMessage::FetchRequest(fetch_request) => fetch_request.verify(verifier),
+            DAGMessage::FetchNodesByRoundRange(request) => request.verify(verifier),
             DAGMessage::VoteMsg(_)
             | DAGMessage::CertifiedAckMsg(_)
             | DAGMessage::FetchResponse(_) => {
@@ -816,6 +900,7 @@ impl TConsensusMsg for DAGMessage {
             DAGMessage::CertifiedAckMsg(ack) => ack.epoch,
             DAGMessage::FetchRequest(req) => req.epoch,
             DAGMessage::FetchResponse(res) => res.epoch,
+            DAGMessage::FetchNodesByRoundRange(req) => req.epoch,
             #[cfg(test)]
             DAGMessage::TestMessage(_) => 1,
             #[cfg(test)]
@@ -929,4 +1014,8 @@ impl DagSnapshotBitmask {
     pub fn len(&self) -> usize {
         self.bitmask.len()
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.bitmask.is_empty()
+    }
 }
@@ -7,6 +7,7 @@ use crate::{
         tracing::{observe_block, BlockStage},
         BlockStore,
     },
+    consensus_observer::publisher::ConsensusPublisher,
     counters,
     dag::{DagBootstrapper, DagCommitSigner, StorageAdapter},
     error::{error_kind, DbError},
@@ -28,14 +29,17 @@ use crate::{
         proposer_election::ProposerElection,
         rotating_proposer_election::{choose_leader, RotatingProposer},
         round_proposer_election::RoundProposer,
-        round_state::{ExponentialTimeInterval, RoundState},
+        round_state::{
+            AdaptiveTimeInterval, ExponentialTimeInterval, RoundState, RoundTimeInterval,
+        },
     },
     logging::{LogEvent, LogSchema},
     metrics_safety_rules::MetricsSafetyRules,
     monitor,
     network::{
         IncomingBatchRetrievalRequest, IncomingBlockRetrievalRequest, IncomingCommitRequest,
-        IncomingDAGRequest, IncomingRpcRequest, NetworkReceivers, NetworkSender,
+        IncomingDAGRequest, IncomingRandGenRequest, IncomingRpcRequest, NetworkReceivers,
+        NetworkSender,
     },
     network_interface::{ConsensusMsg, ConsensusNetworkClient},
     payload_client::QuorumStoreClient,
@@ -46,6 +50,11 @@ use crate::{
         quorum_store_coordinator::CoordinatorCommand,
         quorum_store_db::QuorumStoreStorage,
     },
+    rand::{
+        rand_manager::RandManager,
+        types::{RandConfig, RandKeys},
+    },
+    recent_commit_cache::RecentCommitCache,
     recovery_manager::RecoveryManager,
     round_manager::{RoundManager, UnverifiedEvent, VerifiedEvent},
     state_replication::{PayloadClient, StateComputer},
@@ -66,10 +75,10 @@ use aptos_consensus_types::{
     epoch_retrieval::EpochRetrievalRequest,
 };
 use aptos_event_notifications::ReconfigNotificationListener;
-use aptos_global_constants::CONSENSUS_KEY;
+use aptos_global_constants::{CONSENSUS_KEY, RANDOMNESS_KEYS};
 use aptos_infallible::{duration_since_epoch, Mutex};
 use aptos_logger::prelude::*;
-use aptos_mempool::QuorumStoreRequest;
+use aptos_mempool::{BackPressureStatus, QuorumStoreRequest};
 use aptos_network::{application::interface::NetworkClient, protocols::network::Event};
 use aptos_safety_rules::SafetyRulesManager;
 use aptos_secure_storage::{KVStorage, Storage};
@@ -78,8 +87,8 @@ use aptos_types::{
     epoch_change::EpochChangeProof,
     epoch_state::EpochState,
     on_chain_config::{
-        LeaderReputationType, OnChainConfigPayload, OnChainConfigProvider, OnChainConsensusConfig,
-        OnChainExecutionConfig, ProposerElectionType, ValidatorSet,
+        DKGState, Features, LeaderReputationType, OnChainConfigPayload, OnChainConfigProvider,
+        OnChainConsensusConfig, OnChainExecutionConfig, ProposerElectionType, ValidatorSet,
     },
     validator_signer::ValidatorSigner,
     validator_verifier::ValidatorVerifier,
@@ -129,6 +138,7 @@ pub struct EpochManager<P: OnChainConfigProvider> {
     timeout_sender: aptos_channels::Sender<Round>,
     quorum_store_enabled: bool,
     quorum_store_to_mempool_sender: Sender<QuorumStoreRequest>,
+    back_pressure_status: BackPressureStatus,
     commit_state_computer: Arc<dyn StateComputer>,
     storage: Arc<dyn PersistentLivenessStorage>,
     safety_rules_manager: SafetyRulesManager,
@@ -136,6 +146,9 @@ pub struct EpochManager<P: OnChainConfigProvider> {
     // channels to buffer manager
     buffer_manager_msg_tx: Option<aptos_channel::Sender<AccountAddress, IncomingCommitRequest>>,
     buffer_manager_reset_tx: Option<UnboundedSender<ResetRequest>>,
+    // channel to rand manager, only set when randomness is enabled for the epoch
+    rand_manager_msg_tx: Option<aptos_channel::Sender<AccountAddress, IncomingRandGenRequest>>,
+    rand_config: Option<RandConfig>,
     // channels to round manager
     round_manager_tx: Option<
         aptos_channel::Sender<(Author, Discriminant<VerifiedEvent>), (Author, VerifiedEvent)>,
@@ -158,6 +171,8 @@ pub struct EpochManager<P: OnChainConfigProvider> {
     dag_rpc_tx: Option<aptos_channel::Sender<AccountAddress, IncomingDAGRequest>>,
     dag_shutdown_tx: Option<oneshot::Sender<oneshot::Sender<()>>>,
     dag_config: DagConsensusConfig,
+    consensus_publisher: Option<Arc<ConsensusPublisher>>,
+    recent_commit_cache: Arc<RecentCommitCache>,
 }
 
 impl<P: OnChainConfigProvider> EpochManager<P> {
@@ -168,12 +183,15 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         network_sender: ConsensusNetworkClient<NetworkClient<ConsensusMsg>>,
         timeout_sender: aptos_channels::Sender<Round>,
         quorum_store_to_mempool_sender: Sender<QuorumStoreRequest>,
+        back_pressure_status: BackPressureStatus,
         commit_state_computer: Arc<dyn StateComputer>,
         storage: Arc<dyn PersistentLivenessStorage>,
         quorum_store_storage: Arc<dyn QuorumStoreStorage>,
         reconfig_events: ReconfigNotificationListener<P>,
         bounded_executor: BoundedExecutor,
         aptos_time_service: aptos_time_service::TimeService,
+        consensus_publisher: Option<Arc<ConsensusPublisher>>,
+        recent_commit_cache: Arc<RecentCommitCache>,
     ) -> Self {
         let author = node_config.validator_network.as_ref().unwrap().peer_id();
         let config = node_config.consensus.clone();
@@ -192,12 +210,15 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             // This default value is updated at epoch start
             quorum_store_enabled: false,
             quorum_store_to_mempool_sender,
+            back_pressure_status,
             commit_state_computer,
             storage,
             safety_rules_manager,
             reconfig_events,
             buffer_manager_msg_tx: None,
             buffer_manager_reset_tx: None,
+            rand_manager_msg_tx: None,
+            rand_config: None,
             round_manager_tx: None,
             round_manager_close_tx: None,
             buffered_proposal_tx: None,
@@ -213,6 +234,8 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             dag_shutdown_tx: None,
             aptos_time_service,
             dag_config,
+            consensus_publisher,
+            recent_commit_cache,
         }
     }
 
@@ -232,12 +255,23 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         timeout_sender: aptos_channels::Sender<Round>,
         delayed_qc_tx: UnboundedSender<DelayedQcMsg>,
         qc_aggregator_type: QcAggregatorType,
+        onchain_consensus_config: &OnChainConsensusConfig,
     ) -> RoundState {
-        let time_interval = Box::new(ExponentialTimeInterval::new(
-            Duration::from_millis(self.config.round_initial_timeout_ms),
-            self.config.round_timeout_backoff_exponent_base,
-            self.config.round_timeout_backoff_max_exponent,
-        ));
+        let time_interval: Box<dyn RoundTimeInterval> =
+            match onchain_consensus_config.round_timeout_config() {
+                Some(round_timeout_config) => Box::new(AdaptiveTimeInterval::new(
+                    Duration::from_millis(self.config.round_initial_timeout_ms),
+                    Duration::from_millis(round_timeout_config.min_round_timeout_ms),
+                    Duration::from_millis(round_timeout_config.max_round_timeout_ms),
+                    self.config.round_timeout_adaptive_weight,
+                    self.config.round_timeout_adaptive_multiplier,
+                )),
+                None => Box::new(ExponentialTimeInterval::new(
+                    Duration::from_millis(self.config.round_initial_timeout_ms),
+                    self.config.round_timeout_backoff_exponent_base,
+                    self.config.round_timeout_backoff_max_exponent,
+                )),
+            };
         RoundState::new(
             time_interval,
             time_service,
@@ -552,6 +586,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             self.self_sender.clone(),
             verifier.clone(),
         );
+        let rand_network_sender = network_sender.clone();
 
         let (block_tx, block_rx) = unbounded::<OrderedBlocks>();
         let (reset_tx, reset_rx) = unbounded::<ResetRequest>();
@@ -582,6 +617,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             block_rx,
             reset_rx,
             verifier,
+            self.consensus_publisher.clone(),
         );
 
         tokio::spawn(execution_schedule_phase.start());
@@ -590,7 +626,26 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         tokio::spawn(persisting_phase.start());
         tokio::spawn(buffer_manager.start());
 
-        (block_tx, reset_tx)
+        let Some(rand_config) = self.rand_config.clone() else {
+            return (block_tx, reset_tx);
+        };
+
+        // Ordered blocks go through the rand manager first, which attaches the randomness for
+        // each block before handing them to the buffer manager.
+        let (ordered_block_tx, ordered_block_rx) = unbounded::<OrderedBlocks>();
+        let (rand_reset_tx, rand_reset_rx) = unbounded::<ResetRequest>();
+        let (rand_msg_tx, rand_msg_rx) = aptos_channel::new::<AccountAddress, IncomingRandGenRequest>(
+            QueueStyle::FIFO,
+            100,
+            Some(&counters::RAND_MANAGER_MSGS),
+        );
+        self.rand_manager_msg_tx = Some(rand_msg_tx);
+        self.buffer_manager_reset_tx = Some(rand_reset_tx.clone());
+
+        let rand_manager = RandManager::new(rand_config, rand_network_sender, block_tx, reset_tx);
+        tokio::spawn(rand_manager.start(ordered_block_rx, rand_msg_rx, rand_reset_rx));
+
+        (ordered_block_tx, rand_reset_tx)
     }
 
     async fn shutdown_current_processor(&mut self) {
@@ -620,6 +675,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
 
         // Shutdown the previous buffer manager, to release the SafetyRule client
         self.buffer_manager_msg_tx = None;
+        self.rand_manager_msg_tx = None;
         if let Some(mut tx) = self.buffer_manager_reset_tx.take() {
             let (ack_tx, ack_rx) = oneshot::channel();
             tx.send(ResetRequest {
@@ -697,6 +753,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
                 epoch_state.verifier.clone(),
                 self.config.safety_rules.backend.clone(),
                 self.quorum_store_storage.clone(),
+                self.back_pressure_status.clone(),
             ))
         } else {
             info!("Building DirectMempool");
@@ -727,14 +784,14 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
     ) {
         let transaction_shuffler =
             create_transaction_shuffler(onchain_execution_config.transaction_shuffler_type());
-        let block_gas_limit = onchain_execution_config.block_gas_limit();
+        let block_execution_limit = onchain_execution_config.block_execution_limit();
         let transaction_deduper =
             create_transaction_deduper(onchain_execution_config.transaction_deduper_type());
         self.commit_state_computer.new_epoch(
             epoch_state,
             payload_manager,
             transaction_shuffler,
-            block_gas_limit,
+            block_execution_limit,
             transaction_deduper,
         );
     }
@@ -815,6 +872,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             self.timeout_sender.clone(),
             delayed_qc_tx,
             self.config.qc_aggregator_type.clone(),
+            &onchain_consensus_config,
         );
 
         info!(epoch = epoch, "Create ProposerElection");
@@ -863,6 +921,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             pipeline_backpressure_config,
             chain_health_backoff_config,
             self.quorum_store_enabled,
+            self.recent_commit_cache.clone(),
         );
         let (round_manager_tx, round_manager_rx) = aptos_channel::new(
             QueueStyle::LIFO,
@@ -945,6 +1004,16 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
 
         self.epoch_state = Some(Arc::new(epoch_state.clone()));
 
+        let features: Features = payload.get().unwrap_or_default();
+        let dkg_state: Option<DKGState> = payload.get().ok();
+        self.rand_config = RandConfig::for_epoch(
+            self.author,
+            &epoch_state,
+            features.is_randomness_enabled(),
+            dkg_state.as_ref(),
+            || load_rand_keys_from_storage(&self.config.safety_rules.backend),
+        );
+
         let consensus_config = onchain_consensus_config.unwrap_or_default();
         let execution_config = onchain_execution_config
             .unwrap_or_else(|_| OnChainExecutionConfig::default_if_missing());
@@ -987,6 +1056,9 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             .await;
 
         self.init_commit_state_computer(epoch_state, payload_manager.clone(), execution_config);
+        if let Some(consensus_publisher) = &self.consensus_publisher {
+            consensus_publisher.set_payload_manager(payload_manager.clone());
+        }
         self.start_quorum_store(quorum_store_builder);
         (network_sender, Arc::new(payload_client), payload_manager)
     }
@@ -1044,7 +1116,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             reset_tx,
         ));
 
-        let onchain_dag_consensus_config = onchain_consensus_config.unwrap_dag_config_v1();
+        let onchain_dag_consensus_config = onchain_consensus_config.unwrap_dag_config();
         let epoch_to_validators = self.extract_epoch_proposers(
             &epoch_state,
             onchain_dag_consensus_config.dag_ordering_causal_history_window as u32,
@@ -1064,7 +1136,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         let bootstrapper = DagBootstrapper::new(
             self.author,
             self.dag_config.clone(),
-            onchain_dag_consensus_config.clone(),
+            onchain_dag_consensus_config,
             signer,
             Arc::new(epoch_state),
             dag_storage,
@@ -1075,6 +1147,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             payload_manager,
             payload_client,
             state_computer,
+            self.back_pressure_status.clone(),
         );
 
         let (dag_rpc_tx, dag_rpc_rx) = aptos_channel::new(QueueStyle::FIFO, 10, None);
@@ -1338,6 +1411,22 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
                     Err(anyhow::anyhow!("Buffer manager not started"))
                 }
             },
+            IncomingRpcRequest::RandGenRequest(request) => {
+                let rand_msg_epoch = request.req.epoch();
+
+                if rand_msg_epoch == self.epoch() {
+                    if let Some(tx) = &self.rand_manager_msg_tx {
+                        tx.push(peer_id, request)
+                    } else {
+                        Err(anyhow::anyhow!("Rand manager not started"))
+                    }
+                } else {
+                    monitor!(
+                        "process_different_epoch_rand_msg",
+                        self.process_different_epoch(rand_msg_epoch, peer_id)
+                    )
+                }
+            },
         }
     }
 
@@ -1404,7 +1493,12 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
     }
 }
 
-#[allow(dead_code)]
+fn load_rand_keys_from_storage(backend: &SecureBackend) -> anyhow::Result<RandKeys> {
+    let storage = Storage::from(backend);
+    storage.available()?;
+    Ok(storage.get::<RandKeys>(RANDOMNESS_KEYS)?.value)
+}
+
 fn new_signer_from_storage(author: Author, backend: &SecureBackend) -> Arc<ValidatorSigner> {
     let storage: Storage = backend.try_into().expect("Unable to initialize storage");
     if let Err(error) = storage.available() {
@@ -13,6 +13,7 @@ use aptos_experimental_runtimes::thread_manager::optimal_min_len;
 use aptos_logger::{debug, error};
 use aptos_types::{
     block_executor::partitioner::ExecutableBlock,
+    on_chain_config::BlockExecutionLimit,
     transaction::{signature_verified_transaction::SignatureVerifiedTransaction, Transaction},
 };
 use fail::fail_point;
@@ -57,14 +58,14 @@ impl ExecutionPipeline {
         block_id: HashValue,
         parent_block_id: HashValue,
         txns_to_execute: Vec<Transaction>,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> StateComputeResultFut {
         let (result_tx, result_rx) = oneshot::channel();
         self.prepare_block_tx
             .send(PrepareBlockCommand {
                 block_id,
                 txns_to_execute,
-                maybe_block_gas_limit,
+                block_execution_limit,
                 parent_block_id,
                 result_tx,
             })
@@ -89,7 +90,7 @@ impl ExecutionPipeline {
         while let Some(PrepareBlockCommand {
             block_id,
             txns_to_execute,
-            maybe_block_gas_limit,
+            block_execution_limit,
             parent_block_id,
             result_tx,
         }) = prepare_block_rx.recv().await
@@ -118,7 +119,7 @@ impl ExecutionPipeline {
                 .send(ExecuteBlockCommand {
                     block: (block_id, sig_verified_txns).into(),
                     parent_block_id,
-                    maybe_block_gas_limit,
+                    block_execution_limit,
                     result_tx,
                 })
                 .expect("Failed to send block to execution pipeline.");
@@ -133,7 +134,7 @@ impl ExecutionPipeline {
         while let Some(ExecuteBlockCommand {
             block,
             parent_block_id,
-            maybe_block_gas_limit,
+            block_execution_limit,
             result_tx,
         }) = block_rx.recv().await
         {
@@ -151,7 +152,7 @@ impl ExecutionPipeline {
                     executor.execute_and_state_checkpoint(
                         block,
                         parent_block_id,
-                        maybe_block_gas_limit,
+                        block_execution_limit,
                     )
                 })
                 .await
@@ -208,7 +209,7 @@ impl ExecutionPipeline {
 struct PrepareBlockCommand {
     block_id: HashValue,
     txns_to_execute: Vec<Transaction>,
-    maybe_block_gas_limit: Option<u64>,
+    block_execution_limit: BlockExecutionLimit,
     // The parent block id.
     parent_block_id: HashValue,
     result_tx: oneshot::Sender<ExecutorResult<StateComputeResult>>,
@@ -217,7 +218,7 @@ struct PrepareBlockCommand {
 struct ExecuteBlockCommand {
     block: ExecutableBlock,
     parent_block_id: HashValue,
-    maybe_block_gas_limit: Option<u64>,
+    block_execution_limit: BlockExecutionLimit,
     result_tx: oneshot::Sender<ExecutorResult<StateComputeResult>>,
 }
 
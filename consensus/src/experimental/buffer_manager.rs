@@ -4,6 +4,7 @@
 
 use crate::{
     block_storage::tracing::{observe_block, BlockStage},
+    consensus_observer::publisher::ConsensusPublisher,
     counters,
     experimental::{
         buffer::{Buffer, Cursor},
@@ -118,6 +119,9 @@ pub struct BufferManager {
     // being updated on-chain.
     end_epoch_timestamp: OnceCell<u64>,
     previous_commit_time: Instant,
+
+    // forwards the ordered blocks and commit decisions to the consensus observers
+    consensus_publisher: Option<Arc<ConsensusPublisher>>,
 }
 
 impl BufferManager {
@@ -139,6 +143,7 @@ impl BufferManager {
         reset_rx: UnboundedReceiver<ResetRequest>,
         verifier: ValidatorVerifier,
         ongoing_tasks: Arc<AtomicU64>,
+        consensus_publisher: Option<Arc<ConsensusPublisher>>,
     ) -> Self {
         let buffer = Buffer::<BufferItem>::new();
 
@@ -181,6 +186,8 @@ impl BufferManager {
             ongoing_tasks,
             end_epoch_timestamp: OnceCell::new(),
             previous_commit_time: Instant::now(),
+
+            consensus_publisher,
         }
     }
 
@@ -228,6 +235,10 @@ impl BufferManager {
             self.buffer.len() + 1,
         );
 
+        if let Some(consensus_publisher) = &self.consensus_publisher {
+            consensus_publisher.publish_ordered_blocks(&ordered_blocks, &ordered_proof);
+        }
+
         let request = self.create_new_request(ExecutionRequest {
             ordered_blocks: ordered_blocks.clone(),
             lifetime_guard: self.create_new_request(()),
@@ -334,6 +345,9 @@ impl BufferManager {
                     self.commit_proof_rb_handle
                         .replace(self.do_reliable_broadcast(commit_decision));
                 }
+                if let Some(consensus_publisher) = &self.consensus_publisher {
+                    consensus_publisher.publish_commit_decision(&aggregated_item.commit_proof);
+                }
                 if aggregated_item.commit_proof.ledger_info().ends_epoch() {
                     self.commit_msg_tx
                         .send_epoch_change(EpochChangeProof::new(
@@ -18,7 +18,10 @@ use aptos_consensus_types::{block::Block, executed_block::ExecutedBlock};
 use aptos_crypto::HashValue;
 use aptos_executor_types::{ExecutorResult, StateComputeResult};
 use aptos_logger::prelude::*;
-use aptos_types::{epoch_state::EpochState, ledger_info::LedgerInfoWithSignatures};
+use aptos_types::{
+    epoch_state::EpochState, ledger_info::LedgerInfoWithSignatures,
+    on_chain_config::BlockExecutionLimit,
+};
 use async_trait::async_trait;
 use fail::fail_point;
 use futures::{
@@ -128,7 +131,7 @@ impl StateComputer for OrderingStateComputer {
         _: &EpochState,
         _payload_manager: Arc<PayloadManager>,
         _: Arc<dyn TransactionShuffler>,
-        _: Option<u64>,
+        _: BlockExecutionLimit,
         _: Arc<dyn TransactionDeduper>,
     ) {
     }
@@ -195,7 +198,7 @@ impl StateComputer for DagStateSyncComputer {
         _epoch_state: &EpochState,
         _payload_manager: Arc<PayloadManager>,
         _transaction_shuffler: Arc<dyn TransactionShuffler>,
-        _block_gas_limit: Option<u64>,
+        _block_execution_limit: BlockExecutionLimit,
         _transaction_deduper: Arc<dyn TransactionDeduper>,
     ) {
         unimplemented!("method not supported");
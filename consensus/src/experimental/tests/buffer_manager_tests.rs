@@ -154,6 +154,7 @@ pub fn prepare_buffer_manager() -> (
         block_rx,
         buffer_reset_rx,
         validators.clone(),
+        None,
     );
 
     (
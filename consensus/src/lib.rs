@@ -33,6 +33,8 @@ mod payload_client;
 mod pending_votes;
 pub mod persistent_liveness_storage;
 pub mod quorum_store;
+mod rand;
+mod recent_commit_cache;
 mod recovery_manager;
 mod round_manager;
 mod state_computer;
@@ -44,6 +46,8 @@ mod twins;
 mod txn_notifier;
 pub mod util;
 
+/// Lets fullnodes follow consensus without participating in it
+pub mod consensus_observer;
 /// AptosBFT implementation
 pub mod consensus_provider;
 /// Required by the telemetry service
@@ -53,6 +57,8 @@ mod execution_pipeline;
 pub mod network_interface;
 mod payload_manager;
 mod qc_aggregator;
+/// Required by the inspection service
+pub mod round_telemetry;
 mod sender_aware_shuffler;
 mod transaction_deduper;
 mod transaction_filter;
@@ -10,8 +10,9 @@ use crate::{
     counters::{
         CHAIN_HEALTH_BACKOFF_TRIGGERED, PIPELINE_BACKPRESSURE_ON_PROPOSAL_TRIGGERED,
         PROPOSER_DELAY_PROPOSAL, PROPOSER_PENDING_BLOCKS_COUNT,
-        PROPOSER_PENDING_BLOCKS_FILL_FRACTION,
+        PROPOSER_PENDING_BLOCKS_FILL_FRACTION, PROPOSER_PRE_VALIDATION_FILTERED,
     },
+    recent_commit_cache::RecentCommitCache,
     state_replication::PayloadClient,
     util::time_service::TimeService,
 };
@@ -20,7 +21,7 @@ use aptos_config::config::{ChainHealthBackoffValues, PipelineBackpressureValues}
 use aptos_consensus_types::{
     block::Block,
     block_data::BlockData,
-    common::{Author, Payload, PayloadFilter, Round},
+    common::{Author, Payload, PayloadFilter, ProofWithData, Round},
     quorum_cert::QuorumCert,
 };
 use aptos_logger::{error, sample, sample::SampleRate, warn};
@@ -133,6 +134,60 @@ impl PipelineBackpressureConfig {
     }
 }
 
+/// Drops the parts of the payload that can't be committed in a block with the given timestamp:
+/// the transactions that are expired or already committed, and the expired proofs of store.
+/// Such transactions would only be discarded after wasting execution.
+///
+/// The transactions inside the batches of quorum store are left alone: a proof of store certifies
+/// the whole batch, so they can't be dropped one by one without invalidating it. They are
+/// discarded at execution instead.
+pub(crate) fn pre_validate_payload(
+    payload: Payload,
+    timestamp_usecs: u64,
+    recent_commit_cache: &RecentCommitCache,
+) -> Payload {
+    match payload {
+        Payload::DirectMempool(txns) => {
+            let timestamp_secs = timestamp_usecs / 1_000_000;
+            let (mut num_expired, mut num_committed) = (0, 0);
+            let txns = txns
+                .into_iter()
+                .filter(|txn| {
+                    // The prologue requires the block timestamp to be before the expiration
+                    if txn.expiration_timestamp_secs() <= timestamp_secs {
+                        num_expired += 1;
+                        false
+                    } else if recent_commit_cache.is_committed(txn) {
+                        num_committed += 1;
+                        false
+                    } else {
+                        true
+                    }
+                })
+                .collect();
+            PROPOSER_PRE_VALIDATION_FILTERED
+                .with_label_values(&["expired_txn"])
+                .inc_by(num_expired);
+            PROPOSER_PRE_VALIDATION_FILTERED
+                .with_label_values(&["committed_txn"])
+                .inc_by(num_committed);
+            Payload::DirectMempool(txns)
+        },
+        Payload::InQuorumStore(proof_with_data) => {
+            let num_proofs = proof_with_data.proofs.len();
+            let proofs: Vec<_> = proof_with_data
+                .proofs
+                .into_iter()
+                .filter(|proof| proof.expiration() > timestamp_usecs)
+                .collect();
+            PROPOSER_PRE_VALIDATION_FILTERED
+                .with_label_values(&["expired_proof"])
+                .inc_by((num_proofs - proofs.len()) as u64);
+            Payload::InQuorumStore(ProofWithData::new(proofs))
+        },
+    }
+}
+
 /// ProposalGenerator is responsible for generating the proposed block on demand: it's typically
 /// used by a validator that believes it's a valid candidate for serving as a proposer at a given
 /// round.
@@ -168,6 +223,8 @@ pub struct ProposalGenerator {
     // Last round that a proposal was generated
     last_round_generated: Round,
     quorum_store_enabled: bool,
+    // Recently committed transactions, to drop them from the pulled payload
+    recent_commit_cache: Arc<RecentCommitCache>,
 }
 
 impl ProposalGenerator {
@@ -183,6 +240,7 @@ impl ProposalGenerator {
         pipeline_backpressure_config: PipelineBackpressureConfig,
         chain_health_backoff_config: ChainHealthBackoffConfig,
         quorum_store_enabled: bool,
+        recent_commit_cache: Arc<RecentCommitCache>,
     ) -> Self {
         Self {
             author,
@@ -197,6 +255,7 @@ impl ProposalGenerator {
             chain_health_backoff_config,
             last_round_generated: 0,
             quorum_store_enabled,
+            recent_commit_cache,
         }
     }
 
@@ -323,8 +382,10 @@ impl ProposalGenerator {
                 )
                 .await
                 .context("Fail to retrieve payload")?;
+            let timestamp_usecs = timestamp.as_micros() as u64;
+            let payload = pre_validate_payload(payload, timestamp_usecs, &self.recent_commit_cache);
 
-            (payload, timestamp.as_micros() as u64)
+            (payload, timestamp_usecs)
         };
 
         let quorum_cert = hqc.as_ref().clone();
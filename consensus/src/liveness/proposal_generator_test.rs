@@ -6,19 +6,29 @@ use crate::{
     block_storage::BlockReader,
     liveness::{
         proposal_generator::{
-            ChainHealthBackoffConfig, PipelineBackpressureConfig, ProposalGenerator,
+            pre_validate_payload, ChainHealthBackoffConfig, PipelineBackpressureConfig,
+            ProposalGenerator,
         },
         rotating_proposer_election::RotatingProposer,
         unequivocal_proposer_election::UnequivocalProposerElection,
     },
+    recent_commit_cache::RecentCommitCache,
     test_utils::{build_empty_tree, MockPayloadManager, TreeInserter},
     util::mock_time_service::SimulatedTimeService,
 };
 use aptos_consensus_types::{
     block::{block_test_utils::certificate_for_genesis, Block},
-    common::Author,
+    common::{Author, Payload, ProofWithData},
+    proof_of_store::{BatchId, BatchInfo, ProofOfStore},
+};
+use aptos_crypto::{ed25519::Ed25519PrivateKey, HashValue, PrivateKey, SigningKey, Uniform};
+use aptos_types::{
+    account_address::AccountAddress,
+    aggregate_signature::AggregateSignature,
+    chain_id::ChainId,
+    transaction::{RawTransaction, Script, SignedTransaction, Transaction, TransactionPayload},
+    validator_signer::ValidatorSigner,
 };
-use aptos_types::validator_signer::ValidatorSigner;
 use futures::{future::BoxFuture, FutureExt};
 use std::{sync::Arc, time::Duration};
 
@@ -42,6 +52,7 @@ async fn test_proposal_generation_empty_tree() {
         PipelineBackpressureConfig::new_no_backoff(),
         ChainHealthBackoffConfig::new_no_backoff(),
         false,
+        Arc::new(RecentCommitCache::default()),
     );
     let mut proposer_election =
         UnequivocalProposerElection::new(Arc::new(RotatingProposer::new(vec![signer.author()], 1)));
@@ -82,6 +93,7 @@ async fn test_proposal_generation_parent() {
         PipelineBackpressureConfig::new_no_backoff(),
         ChainHealthBackoffConfig::new_no_backoff(),
         false,
+        Arc::new(RecentCommitCache::default()),
     );
     let mut proposer_election = UnequivocalProposerElection::new(Arc::new(RotatingProposer::new(
         vec![inserter.signer().author()],
@@ -154,6 +166,7 @@ async fn test_old_proposal_generation() {
         PipelineBackpressureConfig::new_no_backoff(),
         ChainHealthBackoffConfig::new_no_backoff(),
         false,
+        Arc::new(RecentCommitCache::default()),
     );
     let mut proposer_election = UnequivocalProposerElection::new(Arc::new(RotatingProposer::new(
         vec![inserter.signer().author()],
@@ -191,6 +204,7 @@ async fn test_correct_failed_authors() {
         PipelineBackpressureConfig::new_no_backoff(),
         ChainHealthBackoffConfig::new_no_backoff(),
         false,
+        Arc::new(RecentCommitCache::default()),
     );
     let mut proposer_election = UnequivocalProposerElection::new(Arc::new(RotatingProposer::new(
         vec![author, peer1, peer2],
@@ -213,3 +227,87 @@ async fn test_correct_failed_authors() {
     assert_eq!(result.failed_authors().unwrap()[3], (4, peer1));
     assert_eq!(result.failed_authors().unwrap()[4], (5, peer2));
 }
+
+fn create_signed_transaction(
+    sender: AccountAddress,
+    sequence_number: u64,
+    expiration_timestamp_secs: u64,
+) -> SignedTransaction {
+    let private_key = Ed25519PrivateKey::generate_for_testing();
+    let raw_transaction = RawTransaction::new(
+        sender,
+        sequence_number,
+        TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+        0,
+        0,
+        expiration_timestamp_secs,
+        ChainId::new(10),
+    );
+    SignedTransaction::new(
+        raw_transaction.clone(),
+        private_key.public_key(),
+        private_key.sign(&raw_transaction).unwrap(),
+    )
+}
+
+#[test]
+fn test_pre_validate_payload() {
+    let timestamp_usecs = 100_000_000;
+    let sender = AccountAddress::random();
+    let recent_commit_cache = RecentCommitCache::default();
+    recent_commit_cache.update(&[Transaction::UserTransaction(create_signed_transaction(
+        sender, 1, 200,
+    ))]);
+
+    // Expired and committed transactions are dropped
+    let committed_txn = create_signed_transaction(sender, 1, 200);
+    let expired_txn = create_signed_transaction(sender, 2, 100);
+    let valid_txn = create_signed_transaction(sender, 3, 200);
+    let other_sender_txn = create_signed_transaction(AccountAddress::random(), 0, 101);
+    let payload = pre_validate_payload(
+        Payload::DirectMempool(vec![
+            committed_txn,
+            expired_txn,
+            valid_txn.clone(),
+            other_sender_txn.clone(),
+        ]),
+        timestamp_usecs,
+        &recent_commit_cache,
+    );
+    match payload {
+        Payload::DirectMempool(txns) => assert_eq!(txns, vec![valid_txn, other_sender_txn]),
+        _ => unreachable!("Unexpected payload type"),
+    }
+
+    // Expired proofs are dropped
+    let create_proof = |expiration| {
+        ProofOfStore::new(
+            BatchInfo::new(
+                AccountAddress::random(),
+                BatchId::new_for_test(1),
+                1,
+                expiration,
+                HashValue::random(),
+                1,
+                1,
+                0,
+            ),
+            AggregateSignature::empty(),
+        )
+    };
+    let valid_proof = create_proof(timestamp_usecs + 1);
+    let payload = pre_validate_payload(
+        Payload::InQuorumStore(ProofWithData::new(vec![
+            create_proof(timestamp_usecs),
+            valid_proof.clone(),
+        ])),
+        timestamp_usecs,
+        &recent_commit_cache,
+    );
+    match payload {
+        Payload::InQuorumStore(proof_with_data) => {
+            assert_eq!(proof_with_data.proofs, vec![valid_proof])
+        },
+        _ => unreachable!("Unexpected payload type"),
+    }
+}
@@ -74,6 +74,9 @@ pub trait RoundTimeInterval: Send + Sync + 'static {
     /// to calculate the round duration of round 6 and the highest committed round is 3 (meaning
     /// the highest round to commit a block is round 5, then the round index is 0.
     fn get_round_duration(&self, round_index_after_committed_qc: usize) -> Duration;
+
+    /// Records how long a round took, from its start until the next round started
+    fn record_round_duration(&mut self, _round_duration: Duration) {}
 }
 
 /// Round durations increase exponentially
@@ -125,6 +128,64 @@ impl RoundTimeInterval for ExponentialTimeInterval {
     }
 }
 
+/// Round durations follow an exponentially weighted moving average (EWMA) of the recent round
+/// durations, scaled by a multiplier and bounded by the on-chain min/max round timeouts.
+/// Unlike the exponential backoff, a few slow rounds only raise the timeout gradually, and the
+/// timeout comes back down as soon as rounds are fast again.
+#[derive(Clone)]
+pub struct AdaptiveTimeInterval {
+    min_ms: u64,
+    max_ms: u64,
+    // Weight of the latest round duration in the moving average
+    weight: f64,
+    // The round timeout is the moving average times the multiplier
+    multiplier: f64,
+    average_round_duration_ms: f64,
+}
+
+impl AdaptiveTimeInterval {
+    pub fn new(
+        initial_timeout: Duration,
+        min_timeout: Duration,
+        max_timeout: Duration,
+        weight: f64,
+        multiplier: f64,
+    ) -> Self {
+        assert!(
+            weight > 0.0 && weight <= 1.0,
+            "weight for AdaptiveTimeInterval should be in (0, 1]"
+        );
+        assert!(
+            multiplier >= 1.0,
+            "multiplier for AdaptiveTimeInterval should be >= 1"
+        );
+        AdaptiveTimeInterval {
+            min_ms: min_timeout.as_millis() as u64,
+            // The bounds come from the on-chain config, so they are not trusted to be ordered
+            max_ms: max_timeout.max(min_timeout).as_millis() as u64,
+            weight,
+            multiplier,
+            // Start from the initial timeout
+            average_round_duration_ms: initial_timeout.as_millis() as f64 / multiplier,
+        }
+    }
+}
+
+impl RoundTimeInterval for AdaptiveTimeInterval {
+    fn get_round_duration(&self, _round_index_after_committed_qc: usize) -> Duration {
+        let duration_ms = (self.average_round_duration_ms * self.multiplier).ceil() as u64;
+        Duration::from_millis(duration_ms.clamp(self.min_ms, self.max_ms))
+    }
+
+    fn record_round_duration(&mut self, round_duration: Duration) {
+        // Rounds never last much longer than the max timeout, so this keeps the average within
+        // reach of the min timeout after a long stall
+        let round_duration_ms = (round_duration.as_millis() as f64).min(self.max_ms as f64);
+        self.average_round_duration_ms =
+            self.weight * round_duration_ms + (1.0 - self.weight) * self.average_round_duration_ms;
+    }
+}
+
 /// `RoundState` contains information about a specific round and moves forward when
 /// receives new certificates.
 ///
@@ -149,6 +210,8 @@ pub struct RoundState {
     highest_committed_round: Round,
     // Current round is max{highest_qc, highest_tc} + 1.
     current_round: Round,
+    // When the current round started, as Duration since UNIX_EPOCH.
+    current_round_start: Duration,
     // The deadline for the next local timeout event. It is reset every time a new round start, or
     // a previous deadline expires.
     // Represents as Duration since UNIX_EPOCH.
@@ -213,6 +276,7 @@ impl RoundState {
             time_interval,
             highest_committed_round: 0,
             current_round: 0,
+            current_round_start: time_service.get_current_timestamp(),
             current_round_deadline: time_service.get_current_timestamp(),
             time_service,
             timeout_sender,
@@ -261,8 +325,19 @@ impl RoundState {
         if new_round > self.current_round {
             let (prev_round_votes, prev_round_timeout_votes) = self.pending_votes.drain_votes();
 
+            // Record the average duration of the rounds since the previous round started
+            let now = self.time_service.get_current_timestamp();
+            if self.current_round > 0 {
+                let num_rounds = (new_round - self.current_round) as u32;
+                if let Some(elapsed) = now.checked_sub(self.current_round_start) {
+                    self.time_interval
+                        .record_round_duration(elapsed / num_rounds);
+                }
+            }
+
             // Start a new round.
             self.current_round = new_round;
+            self.current_round_start = now;
             self.pending_votes = PendingVotes::new(
                 self.time_service.clone(),
                 self.delayed_qc_tx.clone(),
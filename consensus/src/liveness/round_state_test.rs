@@ -4,7 +4,8 @@
 
 use crate::{
     liveness::round_state::{
-        ExponentialTimeInterval, NewRoundEvent, NewRoundReason, RoundState, RoundTimeInterval,
+        AdaptiveTimeInterval, ExponentialTimeInterval, NewRoundEvent, NewRoundReason, RoundState,
+        RoundTimeInterval,
     },
     util::mock_time_service::SimulatedTimeService,
 };
@@ -39,6 +40,40 @@ fn test_round_time_interval() {
     assert_eq!(6750, interval.get_round_duration(1000).as_millis());
 }
 
+#[test]
+fn test_adaptive_round_time_interval() {
+    let mut interval = AdaptiveTimeInterval::new(
+        Duration::from_millis(1500),
+        Duration::from_millis(1000),
+        Duration::from_millis(5000),
+        0.5,
+        3.0,
+    );
+    // Starts from the initial timeout, regardless of the rounds since the last commit
+    assert_eq!(1500, interval.get_round_duration(0).as_millis());
+    assert_eq!(1500, interval.get_round_duration(10).as_millis());
+
+    // Fast rounds lower the timeout, down to the min timeout
+    interval.record_round_duration(Duration::from_millis(200));
+    assert_eq!(
+        1050,
+        /* (0.5 * 200 + 0.5 * 500) * 3 */ interval.get_round_duration(0).as_millis()
+    );
+    interval.record_round_duration(Duration::from_millis(200));
+    assert_eq!(1000, interval.get_round_duration(0).as_millis());
+
+    // Slow rounds raise the timeout, up to the max timeout
+    interval.record_round_duration(Duration::from_secs(60));
+    assert_eq!(5000, interval.get_round_duration(0).as_millis());
+
+    // A single slow round doesn't keep the timeout high
+    interval.record_round_duration(Duration::from_millis(100));
+    assert_eq!(
+        4107, /* (0.5 * 100 + 0.5 * 2637.5) * 3 */
+        interval.get_round_duration(0).as_millis()
+    );
+}
+
 #[tokio::test]
 /// Verify that RoundState properly outputs local timeout events upon timeout
 async fn test_basic_timeout() {
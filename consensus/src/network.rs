@@ -14,6 +14,7 @@ use crate::{
     monitor,
     network_interface::{ConsensusMsg, ConsensusNetworkClient, RPC},
     quorum_store::types::{Batch, BatchMsg, BatchRequest},
+    rand::types::{RandMessage, RandShare},
 };
 use anyhow::{anyhow, bail, ensure};
 use aptos_channels::{self, aptos_channel, message_queues::QueueStyle};
@@ -98,12 +99,19 @@ pub struct IncomingCommitRequest {
     pub response_sender: oneshot::Sender<Result<Bytes, RpcError>>,
 }
 
+#[derive(Debug)]
+pub struct IncomingRandGenRequest {
+    pub req: RandMessage,
+    pub sender: Author,
+}
+
 #[derive(Debug)]
 pub enum IncomingRpcRequest {
     BlockRetrieval(IncomingBlockRetrievalRequest),
     BatchRetrieval(IncomingBatchRetrievalRequest),
     DAGRequest(IncomingDAGRequest),
     CommitRequest(IncomingCommitRequest),
+    RandGenRequest(IncomingRandGenRequest),
 }
 
 /// Just a convenience struct to keep all the network proxy receiving queues in one place.
@@ -371,6 +379,13 @@ impl NetworkSender {
         self.send(msg, vec![self.author]).await
     }
 
+    /// Broadcasts our randomness share for a block to all validators, including ourselves.
+    pub async fn broadcast_rand_share(&mut self, share: RandShare) {
+        fail_point!("consensus::send::broadcast_rand_share", |_| ());
+        let msg = ConsensusMsg::RandGenMessage(Box::new(RandMessage::Share(share)));
+        self.broadcast(msg).await
+    }
+
     /// Sends the ledger info to self buffer manager
     pub async fn send_commit_proof(&self, ledger_info: LedgerInfoWithSignatures) {
         fail_point!("consensus::send::commit_decision", |_| ());
@@ -638,6 +653,18 @@ impl NetworkTask {
                                 warn!(error = ?e, "aptos channel closed");
                             };
                         },
+                        ConsensusMsg::RandGenMessage(msg) => {
+                            let req = IncomingRpcRequest::RandGenRequest(IncomingRandGenRequest {
+                                req: *msg,
+                                sender: peer_id,
+                            });
+                            if let Err(e) = self
+                                .rpc_tx
+                                .push((peer_id, discriminant(&req)), (peer_id, req))
+                            {
+                                warn!(error = ?e, "aptos channel closed");
+                            };
+                        },
                         consensus_msg @ (ConsensusMsg::ProposalMsg(_)
                         | ConsensusMsg::VoteMsg(_)
                         | ConsensusMsg::SyncInfo(_)
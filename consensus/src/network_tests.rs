@@ -60,6 +60,9 @@ pub struct TwinId {
 /// Currently, RPC messages are delivered immediately and are not controlled by
 /// `wait_for_messages` or `deliver_messages` for delivery. They are also not
 /// currently dropped according to the `NetworkPlayground`'s drop config.
+///
+/// Once started, the network conditions (partitions and message delays) can still be
+/// changed through the `NetworkConditions` handle returned by `network_conditions`.
 pub struct NetworkPlayground {
     /// Maps each Author to a Sender of their inbound network notifications.
     /// These events will usually be handled by the event loop spawned in
@@ -79,6 +82,8 @@ pub struct NetworkPlayground {
     drop_config: Arc<RwLock<DropConfig>>,
     /// Allow test code to drop direct-send messages between peers per round.
     drop_config_round: DropConfigRound,
+    /// Allow test code to delay the messages sent by peers.
+    delay_config: Arc<RwLock<DelayConfig>>,
     /// An executor for spawning node outbound network event handlers
     executor: Handle,
     /// Maps authors to twins IDs
@@ -98,6 +103,7 @@ impl NetworkPlayground {
             outbound_msgs_rx,
             drop_config: Arc::new(RwLock::new(DropConfig::default())),
             drop_config_round: DropConfigRound::default(),
+            delay_config: Arc::new(RwLock::new(DelayConfig::default())),
             executor,
             author_to_twin_ids: Arc::new(RwLock::new(AuthorToTwinIds::default())),
             peers_and_metadata: PeersAndMetadata::new(&[NetworkId::Validator]),
@@ -113,6 +119,14 @@ impl NetworkPlayground {
         self.peers_and_metadata.clone()
     }
 
+    /// Handle to change the network conditions, including after the playground is started.
+    pub fn network_conditions(&self) -> NetworkConditions {
+        NetworkConditions {
+            drop_config: self.drop_config.clone(),
+            delay_config: self.delay_config.clone(),
+        }
+    }
+
     /// Create a new async task that handles outbound messages sent by a node.
     ///
     /// All non-rpc messages are forwarded to the NetworkPlayground's
@@ -123,6 +137,7 @@ impl NetworkPlayground {
     /// they don't block.
     async fn start_node_outbound_handler(
         drop_config: Arc<RwLock<DropConfig>>,
+        delay_config: Arc<RwLock<DelayConfig>>,
         src_twin_id: TwinId,
         mut network_reqs_rx: aptos_channel::Receiver<(PeerId, ProtocolId), PeerManagerRequest>,
         mut outbound_msgs_tx: mpsc::Sender<(TwinId, PeerManagerRequest)>,
@@ -169,12 +184,18 @@ impl NetworkPlayground {
                         res_tx: outbound_req.res_tx,
                     };
 
-                    node_consensus_tx
-                        .push(
-                            (src_twin_id.author, ProtocolId::ConsensusRpcBcs),
-                            PeerManagerNotification::RecvRpc(src_twin_id.author, inbound_req),
-                        )
-                        .unwrap();
+                    let key = (src_twin_id.author, ProtocolId::ConsensusRpcBcs);
+                    let notification =
+                        PeerManagerNotification::RecvRpc(src_twin_id.author, inbound_req);
+                    match delay_config.read().get_delay(&src_twin_id) {
+                        Some(delay) => {
+                            tokio::spawn(async move {
+                                tokio::time::sleep(delay).await;
+                                let _ = node_consensus_tx.push(key, notification);
+                            });
+                        },
+                        None => node_consensus_tx.push(key, notification).unwrap(),
+                    }
                 },
                 // Other PeerManagerRequest get buffered for `deliver_messages` to
                 // synchronously drain.
@@ -200,6 +221,7 @@ impl NetworkPlayground {
 
         let fut1 = NetworkPlayground::start_node_outbound_handler(
             Arc::clone(&self.drop_config),
+            Arc::clone(&self.delay_config),
             twin_id,
             network_reqs_rx,
             self.outbound_msgs_tx.clone(),
@@ -397,9 +419,30 @@ impl NetworkPlayground {
                 let consensus_msg = msg.to_message().unwrap();
 
                 // Deliver and copy message it if it's not dropped
-                if !self.is_message_dropped(&src_twin_id, dst_twin_id, consensus_msg) {
-                    self.deliver_message(src_twin_id, *dst_twin_id, msg_notif)
-                        .await;
+                if self.is_message_dropped(&src_twin_id, dst_twin_id, consensus_msg) {
+                    continue;
+                }
+                let delay = self.delay_config.read().get_delay(&src_twin_id);
+                match delay {
+                    Some(delay) => {
+                        let node_consensus_tx = self
+                            .node_consensus_txs
+                            .lock()
+                            .get(dst_twin_id)
+                            .unwrap()
+                            .clone();
+                        self.executor.spawn(async move {
+                            tokio::time::sleep(delay).await;
+                            let _ = node_consensus_tx.push(
+                                (src_twin_id.author, ProtocolId::ConsensusDirectSendBcs),
+                                msg_notif,
+                            );
+                        });
+                    },
+                    None => {
+                        self.deliver_message(src_twin_id, *dst_twin_id, msg_notif)
+                            .await;
+                    },
                 }
             }
         }
@@ -451,6 +494,77 @@ impl DropConfig {
     fn add_node(&mut self, src: TwinId) {
         self.0.insert(src, HashSet::new());
     }
+
+    fn heal_network(&mut self) {
+        self.0.values_mut().for_each(HashSet::clear);
+    }
+}
+
+/// Per node delay applied to all the messages it sends
+#[derive(Default)]
+struct DelayConfig(HashMap<TwinId, Duration>);
+
+impl DelayConfig {
+    fn get_delay(&self, src: &TwinId) -> Option<Duration> {
+        self.0.get(src).copied()
+    }
+
+    fn set_delay(&mut self, src: TwinId, delay: Option<Duration>) {
+        match delay {
+            Some(delay) => self.0.insert(src, delay),
+            None => self.0.remove(&src),
+        };
+    }
+}
+
+/// A network partition held for `duration` by `NetworkConditions::run_partition_schedule`.
+/// No message is delivered between nodes of different partitions, an empty list of
+/// partitions heals the network.
+#[derive(Clone, Debug)]
+pub struct PartitionPhase {
+    pub partitions: Vec<Vec<TwinId>>,
+    pub duration: Duration,
+}
+
+/// Handle to the drop and delay configurations of a `NetworkPlayground`, which applies to
+/// both direct-send and rpc messages once the playground is started.
+#[derive(Clone)]
+pub struct NetworkConditions {
+    drop_config: Arc<RwLock<DropConfig>>,
+    delay_config: Arc<RwLock<DelayConfig>>,
+}
+
+impl NetworkConditions {
+    /// Replaces the current partitions by the given ones.
+    pub fn split_network(&self, partitions: &[Vec<TwinId>]) -> bool {
+        let mut drop_config = self.drop_config.write();
+        drop_config.heal_network();
+        let mut ret = true;
+        for (i, p1) in partitions.iter().enumerate() {
+            for p2 in partitions.iter().skip(i + 1) {
+                ret &= drop_config.split_network(p1, p2);
+            }
+        }
+        ret
+    }
+
+    pub fn heal_network(&self) {
+        self.drop_config.write().heal_network();
+    }
+
+    /// Delays all the messages sent by `src`, or stops delaying them if `delay` is None.
+    pub fn delay_messages_from(&self, src: TwinId, delay: Option<Duration>) {
+        self.delay_config.write().set_delay(src, delay);
+    }
+
+    /// Applies the partition phases one after the other, and heals the network at the end.
+    pub async fn run_partition_schedule(self, schedule: Vec<PartitionPhase>) {
+        for phase in schedule {
+            self.split_network(&phase.partitions);
+            tokio::time::sleep(phase.duration).await;
+        }
+        self.heal_network();
+    }
 }
 
 /// Table of per round message dropping rules
@@ -16,10 +16,11 @@ use aptos_consensus_types::{
 };
 use aptos_crypto::HashValue;
 use aptos_executor_types::{ExecutorError::DataNotFound, *};
+use aptos_infallible::Mutex;
 use aptos_logger::prelude::*;
 use aptos_types::transaction::SignedTransaction;
 use futures::{channel::mpsc::Sender, SinkExt};
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 use tokio::sync::oneshot;
 
 /// Responsible to extract the transactions out of the payload and notify QuorumStore about commits.
@@ -27,6 +28,9 @@ use tokio::sync::oneshot;
 pub enum PayloadManager {
     DirectMempool,
     InQuorumStore(Arc<BatchStore<NetworkSender>>, Sender<CoordinatorCommand>),
+    /// Used by consensus observers, which receive the transactions of quorum store blocks from
+    /// the publishers along with the blocks, keyed by block id.
+    ConsensusObserver(Arc<Mutex<HashMap<HashValue, Vec<SignedTransaction>>>>),
 }
 
 impl PayloadManager {
@@ -58,7 +62,7 @@ impl PayloadManager {
     ///Pass commit information to BatchReader and QuorumStore wrapper for their internal cleanups.
     pub async fn notify_commit(&self, block_timestamp: u64, payloads: Vec<Payload>) {
         match self {
-            PayloadManager::DirectMempool => {},
+            PayloadManager::DirectMempool | PayloadManager::ConsensusObserver(_) => {},
             PayloadManager::InQuorumStore(batch_store, coordinator_tx) => {
                 // TODO: move this to somewhere in quorum store, so this can be a batch reader
                 batch_store
@@ -97,7 +101,7 @@ impl PayloadManager {
     /// Called from consensus to pre-fetch the transaction behind the batches in the block.
     pub fn prefetch_payload_data(&self, payload: &Payload, timestamp: u64) {
         match self {
-            PayloadManager::DirectMempool => {},
+            PayloadManager::DirectMempool | PayloadManager::ConsensusObserver(_) => {},
             PayloadManager::InQuorumStore(batch_store, _) => match payload {
                 Payload::InQuorumStore(proof_with_status) => {
                     if proof_with_status.status.lock().is_none() {
@@ -119,6 +123,36 @@ impl PayloadManager {
         }
     }
 
+    /// Fetches the transactions of a block for the consensus publisher, without touching the
+    /// data status used by execution.
+    pub async fn fetch_transactions(
+        &self,
+        block: &Block,
+    ) -> ExecutorResult<Vec<SignedTransaction>> {
+        match (self, block.payload()) {
+            (
+                PayloadManager::InQuorumStore(batch_store, _),
+                Some(Payload::InQuorumStore(proof_with_data)),
+            ) => {
+                let receivers = PayloadManager::request_transactions(
+                    proof_with_data.proofs.clone(),
+                    block.timestamp_usecs(),
+                    batch_store,
+                );
+                let mut txns = Vec::new();
+                for (digest, rx) in receivers {
+                    match rx.await {
+                        Ok(data) => txns.extend(data?),
+                        Err(_) => return Err(DataNotFound(digest)),
+                    }
+                }
+                Ok(txns)
+            },
+            // Direct mempool payloads already carry their transactions
+            _ => Ok(Vec::new()),
+        }
+    }
+
     /// Extract transaction from a given block
     /// Assumes it is never called for the same block concurrently. Otherwise status can be None.
     pub async fn get_transactions(&self, block: &Block) -> ExecutorResult<Vec<SignedTransaction>> {
@@ -129,6 +163,16 @@ impl PayloadManager {
 
         match (self, payload) {
             (PayloadManager::DirectMempool, Payload::DirectMempool(txns)) => Ok(txns.clone()),
+            (PayloadManager::ConsensusObserver(_), Payload::DirectMempool(txns)) => {
+                Ok(txns.clone())
+            },
+            (PayloadManager::ConsensusObserver(block_payloads), Payload::InQuorumStore(_)) => {
+                block_payloads
+                    .lock()
+                    .get(&block.id())
+                    .cloned()
+                    .ok_or(DataNotFound(block.id()))
+            },
             (
                 PayloadManager::InQuorumStore(batch_store, _),
                 Payload::InQuorumStore(proof_with_data),
@@ -29,7 +29,7 @@ pub trait PersistentLivenessStorage: Send + Sync {
     /// Delete the corresponding blocks and quorum certs atomically.
     fn prune_tree(&self, block_ids: Vec<HashValue>) -> Result<()>;
 
-    /// Persist consensus' state
+    /// Persist consensus' state: the last vote sent, along with its timeout if the round timed out
     fn save_vote(&self, vote: &Vote) -> Result<()>;
 
     /// Construct data that can be recovered from ledger
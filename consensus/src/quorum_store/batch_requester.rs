@@ -6,21 +6,141 @@ use crate::{
     network::QuorumStoreSender,
     quorum_store::{counters, types::BatchRequest},
 };
+use aptos_config::config::QuorumStoreBatchRequestScoringConfig;
 use aptos_crypto::HashValue;
 use aptos_executor_types::*;
+use aptos_infallible::Mutex;
 use aptos_logger::prelude::*;
 use aptos_types::{transaction::SignedTransaction, PeerId};
 use futures::{stream::FuturesUnordered, StreamExt};
 use rand::Rng;
-use std::time::Duration;
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tokio::{sync::oneshot, time};
 
+/// Moving averages of the outcomes of the batch requests sent to a peer.
+#[derive(Debug)]
+struct PeerScore {
+    success_rate: f64,
+    latency_ms: f64,
+    num_inflight: usize,
+}
+
+impl PeerScore {
+    fn new() -> Self {
+        // Peers without history are assumed to be healthy.
+        Self {
+            success_rate: 1.0,
+            latency_ms: 0.0,
+            num_inflight: 0,
+        }
+    }
+}
+
+/// Scores peers by the success rate and latency of their batch responses, and limits the
+/// number of outstanding batch requests per peer.
+pub(crate) struct BatchRequestPeerScores {
+    config: QuorumStoreBatchRequestScoringConfig,
+    rpc_timeout_ms: f64,
+    peers: Mutex<HashMap<PeerId, PeerScore>>,
+}
+
+impl BatchRequestPeerScores {
+    pub(crate) fn new(config: QuorumStoreBatchRequestScoringConfig, rpc_timeout_ms: usize) -> Self {
+        Self {
+            config,
+            rpc_timeout_ms: rpc_timeout_ms.max(1) as f64,
+            peers: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Score in [min_score, 1], decreasing with failures and with latency.
+    pub(crate) fn score(&self, peer: &PeerId) -> f64 {
+        self.peers
+            .lock()
+            .get(peer)
+            .map_or(1.0, |peer_score| {
+                peer_score.success_rate * self.rpc_timeout_ms
+                    / (self.rpc_timeout_ms + peer_score.latency_ms)
+            })
+            .max(self.config.min_score)
+    }
+
+    pub(crate) fn record_response(&self, peer: PeerId, success: bool, latency: Duration) {
+        let weight = self.config.moving_average_weight;
+        let mut peers = self.peers.lock();
+        let peer_score = peers.entry(peer).or_insert_with(PeerScore::new);
+        peer_score.success_rate =
+            peer_score.success_rate * (1.0 - weight) + if success { weight } else { 0.0 };
+        peer_score.latency_ms =
+            peer_score.latency_ms * (1.0 - weight) + latency.as_millis() as f64 * weight;
+    }
+
+    /// Orders the peers by weighted random sampling without replacement, so that peers with
+    /// higher scores are likely to be requested first.
+    pub(crate) fn order_peers(&self, peers: Vec<PeerId>) -> Vec<PeerId> {
+        let mut rng = rand::thread_rng();
+        let mut keyed_peers: Vec<_> = peers
+            .into_iter()
+            .map(|peer| {
+                let key = rng.gen::<f64>().powf(1.0 / self.score(&peer));
+                (key, peer)
+            })
+            .collect();
+        keyed_peers.sort_by(|(a, _), (b, _)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+        keyed_peers.into_iter().map(|(_, peer)| peer).collect()
+    }
+
+    /// Returns None if the peer already has the maximum number of outstanding requests.
+    pub(crate) fn try_acquire(self: &Arc<Self>, peer: PeerId) -> Option<InflightRequestGuard> {
+        let mut peers = self.peers.lock();
+        let peer_score = peers.entry(peer).or_insert_with(PeerScore::new);
+        let max_inflight = self.config.max_inflight_requests_per_peer;
+        if max_inflight > 0 && peer_score.num_inflight >= max_inflight {
+            return None;
+        }
+        peer_score.num_inflight += 1;
+        Some(InflightRequestGuard {
+            peer_scores: self.clone(),
+            peer,
+        })
+    }
+
+    #[cfg(test)]
+    pub(crate) fn num_inflight(&self, peer: &PeerId) -> usize {
+        self.peers
+            .lock()
+            .get(peer)
+            .map_or(0, |peer_score| peer_score.num_inflight)
+    }
+}
+
+/// Releases the outstanding request slot of a peer once the request completes or is dropped.
+pub(crate) struct InflightRequestGuard {
+    peer_scores: Arc<BatchRequestPeerScores>,
+    peer: PeerId,
+}
+
+impl Drop for InflightRequestGuard {
+    fn drop(&mut self) {
+        if let Some(peer_score) = self.peer_scores.peers.lock().get_mut(&self.peer) {
+            peer_score.num_inflight = peer_score.num_inflight.saturating_sub(1);
+        }
+    }
+}
+
 struct BatchRequesterState {
     signers: Vec<PeerId>,
     next_index: usize,
     ret_tx: oneshot::Sender<ExecutorResult<Vec<SignedTransaction>>>,
     num_retries: usize,
     retry_limit: usize,
+    // If the signers are already ordered by preference, start from the first one.
+    ordered: bool,
 }
 
 impl BatchRequesterState {
@@ -28,6 +148,7 @@ impl BatchRequesterState {
         signers: Vec<PeerId>,
         ret_tx: oneshot::Sender<ExecutorResult<Vec<SignedTransaction>>>,
         retry_limit: usize,
+        ordered: bool,
     ) -> Self {
         Self {
             signers,
@@ -35,14 +156,17 @@ impl BatchRequesterState {
             ret_tx,
             num_retries: 0,
             retry_limit,
+            ordered,
         }
     }
 
     fn next_request_peers(&mut self, num_peers: usize) -> Option<Vec<PeerId>> {
         if self.num_retries == 0 {
-            let mut rng = rand::thread_rng();
-            // make sure nodes request from the different set of nodes
-            self.next_index = rng.gen::<usize>() % self.signers.len();
+            if !self.ordered {
+                let mut rng = rand::thread_rng();
+                // make sure nodes request from the different set of nodes
+                self.next_index = rng.gen::<usize>() % self.signers.len();
+            }
             counters::SENT_BATCH_REQUEST_COUNT.inc_by(num_peers as u64);
         } else {
             counters::SENT_BATCH_REQUEST_RETRY_COUNT.inc_by(num_peers as u64);
@@ -103,6 +227,7 @@ pub(crate) struct BatchRequester<T> {
     retry_interval_ms: usize,
     rpc_timeout_ms: usize,
     network_sender: T,
+    peer_scores: Option<Arc<BatchRequestPeerScores>>,
 }
 
 impl<T: QuorumStoreSender + Sync + 'static> BatchRequester<T> {
@@ -114,7 +239,11 @@ impl<T: QuorumStoreSender + Sync + 'static> BatchRequester<T> {
         retry_interval_ms: usize,
         rpc_timeout_ms: usize,
         network_sender: T,
+        scoring_config: QuorumStoreBatchRequestScoringConfig,
     ) -> Self {
+        let peer_scores = scoring_config
+            .enabled
+            .then(|| Arc::new(BatchRequestPeerScores::new(scoring_config, rpc_timeout_ms)));
         Self {
             epoch,
             my_peer_id,
@@ -123,6 +252,7 @@ impl<T: QuorumStoreSender + Sync + 'static> BatchRequester<T> {
             retry_interval_ms,
             rpc_timeout_ms,
             network_sender,
+            peer_scores,
         }
     }
 
@@ -132,7 +262,13 @@ impl<T: QuorumStoreSender + Sync + 'static> BatchRequester<T> {
         signers: Vec<PeerId>,
         ret_tx: oneshot::Sender<ExecutorResult<Vec<SignedTransaction>>>,
     ) {
-        let mut request_state = BatchRequesterState::new(signers, ret_tx, self.retry_limit);
+        let peer_scores = self.peer_scores.clone();
+        let signers = match &peer_scores {
+            Some(peer_scores) => peer_scores.order_peers(signers),
+            None => signers,
+        };
+        let mut request_state =
+            BatchRequesterState::new(signers, ret_tx, self.retry_limit, peer_scores.is_some());
         let network_sender = self.network_sender.clone();
         let request_num_peers = self.request_num_peers;
         let my_peer_id = self.my_peer_id;
@@ -151,14 +287,33 @@ impl<T: QuorumStoreSender + Sync + 'static> BatchRequester<T> {
                             // send batch request to a set of peers of size request_num_peers
                             if let Some(request_peers) = request_state.next_request_peers(request_num_peers) {
                                 for peer in request_peers {
-                                    futures.push(network_sender.request_batch(request.clone(), peer, rpc_timeout));
+                                    let guard = match &peer_scores {
+                                        Some(peer_scores) => match peer_scores.try_acquire(peer) {
+                                            Some(guard) => Some(guard),
+                                            None => {
+                                                counters::BATCH_REQUEST_RATE_LIMITED_COUNT.inc();
+                                                continue;
+                                            },
+                                        },
+                                        None => None,
+                                    };
+                                    let response = network_sender.request_batch(request.clone(), peer, rpc_timeout);
+                                    futures.push(async move {
+                                        let start = Instant::now();
+                                        let response = response.await;
+                                        drop(guard);
+                                        (peer, response, start.elapsed())
+                                    });
                                 }
                             } else if futures.is_empty() {
                                 // end the loop when the futures are drained
                                 break;
                             }
                         }
-                        Some(response) = futures.next() => {
+                        Some((peer, response, latency)) = futures.next() => {
+                            if let Some(peer_scores) = &peer_scores {
+                                peer_scores.record_response(peer, response.is_ok(), latency);
+                            }
                             if let Ok(batch) = response {
                                 counters::RECEIVED_BATCH_RESPONSE_COUNT.inc();
                                 let digest = *batch.digest();
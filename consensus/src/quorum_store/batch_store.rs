@@ -29,18 +29,35 @@ use once_cell::sync::OnceCell;
 use std::{
     sync::{
         atomic::{AtomicU64, Ordering},
-        Arc, Mutex,
+        Arc, Mutex, Weak,
     },
     time::Duration,
 };
 use tokio::sync::oneshot;
 
+/// Per-author limits on the batches stored in the batch store.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Quotas {
+    pub(crate) memory_quota: usize,
+    pub(crate) db_quota: usize,
+    pub(crate) batch_quota: usize,
+}
+
+/// Resources used by the batches stored in the batch store.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct QuotaUsage {
+    pub(crate) memory_bytes: usize,
+    pub(crate) db_bytes: usize,
+    pub(crate) num_batches: usize,
+}
+
 // Pub(crate) for testing only.
 pub(crate) struct QuotaManager {
-    memory_balance: usize,
-    db_balance: usize,
-    batch_balance: usize,
-    // Recording the provided quotas for asserts.
+    memory_usage: usize,
+    db_usage: usize,
+    batch_usage: usize,
+    // The quotas may be updated at runtime, and become lower than the current usage,
+    // in which case new batches are rejected until enough usage is freed.
     memory_quota: usize,
     db_quota: usize,
     batch_quota: usize,
@@ -50,9 +67,9 @@ impl QuotaManager {
     pub(crate) fn new(db_quota: usize, memory_quota: usize, batch_quota: usize) -> Self {
         assert!(db_quota >= memory_quota);
         Self {
-            memory_balance: memory_quota,
-            db_balance: db_quota,
-            batch_balance: batch_quota,
+            memory_usage: 0,
+            db_usage: 0,
+            batch_usage: 0,
             memory_quota,
             db_quota,
             batch_quota,
@@ -60,17 +77,17 @@ impl QuotaManager {
     }
 
     pub(crate) fn update_quota(&mut self, num_bytes: usize) -> anyhow::Result<StorageMode> {
-        if self.batch_balance == 0 {
+        if self.batch_usage >= self.batch_quota {
             counters::EXCEEDED_BATCH_QUOTA_COUNT.inc();
             bail!("Batch quota exceeded ");
         }
 
-        if self.db_balance >= num_bytes {
-            self.batch_balance -= 1;
-            self.db_balance -= num_bytes;
+        if self.db_usage + num_bytes <= self.db_quota {
+            self.batch_usage += 1;
+            self.db_usage += num_bytes;
 
-            if self.memory_balance >= num_bytes {
-                self.memory_balance -= num_bytes;
+            if self.memory_usage + num_bytes <= self.memory_quota {
+                self.memory_usage += num_bytes;
                 Ok(StorageMode::MemoryAndPersisted)
             } else {
                 Ok(StorageMode::PersistedOnly)
@@ -81,27 +98,41 @@ impl QuotaManager {
         }
     }
 
-    fn assert_quota(balance: usize, to_free: usize, quota: usize, kind: &str) {
+    fn assert_usage(usage: usize, to_free: usize, kind: &str) {
         assert!(
-            balance + to_free <= quota,
-            "Balance {} + to_free {} more than {} quota {}",
-            balance,
+            usage >= to_free,
+            "Usage {} less than to_free {} for {} quota",
+            usage,
             to_free,
             kind,
-            quota,
         );
     }
 
     pub(crate) fn free_quota(&mut self, num_bytes: usize, storage_mode: StorageMode) {
-        Self::assert_quota(self.batch_balance, 1, self.batch_quota, "Batch");
-        self.batch_balance += 1;
+        Self::assert_usage(self.batch_usage, 1, "Batch");
+        self.batch_usage -= 1;
 
-        Self::assert_quota(self.db_balance, num_bytes, self.db_quota, "DB");
-        self.db_balance += num_bytes;
+        Self::assert_usage(self.db_usage, num_bytes, "DB");
+        self.db_usage -= num_bytes;
 
         if matches!(storage_mode, StorageMode::MemoryAndPersisted) {
-            Self::assert_quota(self.memory_balance, num_bytes, self.memory_quota, "Memory");
-            self.memory_balance += num_bytes;
+            Self::assert_usage(self.memory_usage, num_bytes, "Memory");
+            self.memory_usage -= num_bytes;
+        }
+    }
+
+    pub(crate) fn set_quotas(&mut self, quotas: Quotas) {
+        assert!(quotas.db_quota >= quotas.memory_quota);
+        self.memory_quota = quotas.memory_quota;
+        self.db_quota = quotas.db_quota;
+        self.batch_quota = quotas.batch_quota;
+    }
+
+    pub(crate) fn usage(&self) -> QuotaUsage {
+        QuotaUsage {
+            memory_bytes: self.memory_usage,
+            db_bytes: self.db_usage,
+            num_batches: self.batch_usage,
         }
     }
 }
@@ -115,9 +146,10 @@ pub struct BatchStore<T> {
     peer_quota: DashMap<PeerId, QuotaManager>,
     expirations: Mutex<TimeExpirations<HashValue>>,
     db: Arc<dyn QuorumStoreStorage>,
-    memory_quota: usize,
-    db_quota: usize,
-    batch_quota: usize,
+    // Expired batches that could not be deleted from the db yet, retried by the periodic GC.
+    pending_db_deletions: Mutex<Vec<HashValue>>,
+    // Quotas for the authors, may be updated at runtime by the QuotaController.
+    quotas: Mutex<Quotas>,
     batch_requester: BatchRequester<T>,
     validator_signer: ValidatorSigner,
     validator_verifier: ValidatorVerifier,
@@ -143,13 +175,24 @@ impl<T: QuorumStoreSender + Clone + Send + Sync + 'static> BatchStore<T> {
             peer_quota: DashMap::new(),
             expirations: Mutex::new(TimeExpirations::new()),
             db,
-            memory_quota,
-            db_quota,
-            batch_quota,
+            pending_db_deletions: Mutex::new(Vec::new()),
+            quotas: Mutex::new(Quotas {
+                memory_quota,
+                db_quota,
+                batch_quota,
+            }),
             batch_requester,
             validator_signer,
             validator_verifier,
         };
+        // Batches expired before the persisted watermark were already certified as expired,
+        // even if the latest ledger info is behind it.
+        let expiration_watermark = db_clone
+            .get_expiration_watermark()
+            .expect("failed to read expiration watermark from db")
+            .map_or(last_certified_time, |watermark| {
+                watermark.max(last_certified_time)
+            });
         let db_content = db_clone
             .get_all_batches()
             .expect("failed to read data from db");
@@ -169,7 +212,7 @@ impl<T: QuorumStoreSender + Clone + Send + Sync + 'static> BatchStore<T> {
                 digest
             );
 
-            if last_certified_time >= expiration {
+            if expiration_watermark >= expiration {
                 expired_keys.push(digest);
             } else {
                 batch_store
@@ -198,6 +241,30 @@ impl<T: QuorumStoreSender + Clone + Send + Sync + 'static> BatchStore<T> {
         quota_manager.free_quota(value.num_bytes() as usize, value.payload_storage_mode());
     }
 
+    pub(crate) fn quotas(&self) -> Quotas {
+        *self.quotas.lock().unwrap()
+    }
+
+    // Applies the quotas to all current and future authors. Note: the quotas lock is not
+    // held while accessing peer_quota, as insert_to_cache reads the quotas before that.
+    pub(crate) fn update_quotas(&self, quotas: Quotas) {
+        *self.quotas.lock().unwrap() = quotas;
+        for mut quota_manager in self.peer_quota.iter_mut() {
+            quota_manager.set_quotas(quotas);
+        }
+    }
+
+    pub(crate) fn quota_usage(&self) -> QuotaUsage {
+        self.peer_quota
+            .iter()
+            .map(|quota_manager| quota_manager.usage())
+            .fold(QuotaUsage::default(), |total, usage| QuotaUsage {
+                memory_bytes: total.memory_bytes + usage.memory_bytes,
+                db_bytes: total.db_bytes + usage.db_bytes,
+                num_batches: total.num_batches + usage.num_batches,
+            })
+    }
+
     // Inserts a PersistedValue into the in-memory db_cache. If an entry with a higher
     // value is already in the db_cache, Ok(false) is returned. If there was no entry
     // Ok(true) is returned after the successful insertion. Finally, the method returns
@@ -210,6 +277,7 @@ impl<T: QuorumStoreSender + Clone + Send + Sync + 'static> BatchStore<T> {
         let digest = *value.digest();
         let author = value.author();
         let expiration_time = value.expiration();
+        let quotas = *self.quotas.lock().unwrap();
 
         {
             // Acquire dashmap internal lock on the entry corresponding to the digest.
@@ -228,11 +296,9 @@ impl<T: QuorumStoreSender + Clone + Send + Sync + 'static> BatchStore<T> {
             if self
                 .peer_quota
                 .entry(author)
-                .or_insert(QuotaManager::new(
-                    self.db_quota,
-                    self.memory_quota,
-                    self.batch_quota,
-                ))
+                .or_insert_with(|| {
+                    QuotaManager::new(quotas.db_quota, quotas.memory_quota, quotas.batch_quota)
+                })
                 .update_quota(value.num_bytes() as usize)?
                 == StorageMode::PersistedOnly
             {
@@ -301,6 +367,8 @@ impl<T: QuorumStoreSender + Clone + Send + Sync + 'static> BatchStore<T> {
             };
             // No longer holding the lock on db_cache entry.
             if let Some(value) = removed_value {
+                counters::GC_RECLAIMED_BATCH_COUNT.inc();
+                counters::GC_RECLAIMED_BATCH_BYTES.inc_by(value.num_bytes() as u64);
                 self.free_quota(value);
                 ret.push(h);
             }
@@ -353,11 +421,48 @@ impl<T: QuorumStoreSender + Clone + Send + Sync + 'static> BatchStore<T> {
         );
 
         let expired_keys = self.clear_expired_payload(certified_time);
-        if let Err(e) = self.db.delete_batches(expired_keys) {
-            debug!("Error deleting batches: {:?}", e)
+        self.delete_from_db(expired_keys);
+    }
+
+    // Deletes the digests, along with the previously failed deletions, from the db. Returns
+    // false if the deletion failed, in which case it is retried by the next GC.
+    fn delete_from_db(&self, digests: Vec<HashValue>) -> bool {
+        let mut pending_db_deletions = self.pending_db_deletions.lock().unwrap();
+        pending_db_deletions.extend(digests);
+        if pending_db_deletions.is_empty() {
+            return true;
+        }
+        match self.db.delete_batches(pending_db_deletions.clone()) {
+            Ok(()) => {
+                pending_db_deletions.clear();
+                true
+            },
+            Err(e) => {
+                debug!("Error deleting batches: {:?}", e);
+                false
+            },
+        }
+    }
+
+    /// Removes the batches expired by the last certified time from memory and db, and persists
+    /// the certified time as the expiration watermark once all of them are deleted.
+    pub(crate) fn gc_expired_batches(&self) {
+        let watermark = self.last_certified_time();
+        let expired_keys = self.clear_expired_payload(watermark);
+        if !self.delete_from_db(expired_keys) {
+            return;
+        }
+        match self.db.save_expiration_watermark(watermark) {
+            Ok(()) => counters::GC_EXPIRATION_WATERMARK.set(watermark as i64),
+            Err(e) => debug!("Error saving expiration watermark: {:?}", e),
         }
     }
 
+    // pub(crate) for testing
+    pub(crate) fn num_pending_db_deletions(&self) -> usize {
+        self.pending_db_deletions.lock().unwrap().len()
+    }
+
     fn last_certified_time(&self) -> u64 {
         self.last_certified_time.load(Ordering::Relaxed)
     }
@@ -391,6 +496,24 @@ impl<T: QuorumStoreSender + Clone + Send + Sync + 'static> BatchStore<T> {
     }
 }
 
+/// Periodically garbage collects the expired batches, until the batch store is dropped at the
+/// end of the epoch.
+pub(crate) async fn gc_expired_batches_periodically<T>(
+    batch_store: Weak<BatchStore<T>>,
+    gc_interval: Duration,
+) where
+    T: QuorumStoreSender + Clone + Send + Sync + 'static,
+{
+    let mut interval = tokio::time::interval(gc_interval);
+    loop {
+        interval.tick().await;
+        match batch_store.upgrade() {
+            Some(batch_store) => batch_store.gc_expired_batches(),
+            None => break,
+        }
+    }
+}
+
 pub trait BatchReader: Send + Sync {
     /// Check if the batch corresponding to the digest exists, return the batch author if true
     fn exists(&self, digest: &HashValue) -> Option<PeerId>;
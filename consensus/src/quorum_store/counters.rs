@@ -3,8 +3,9 @@
 
 use aptos_metrics_core::{
     exponential_buckets, op_counters::DurationHistogram, register_avg_counter, register_histogram,
-    register_histogram_vec, register_int_counter, register_int_counter_vec, Histogram,
-    HistogramVec, IntCounter, IntCounterVec,
+    register_histogram_vec, register_int_counter, register_int_counter_vec, register_int_gauge,
+    register_int_gauge_vec, Histogram, HistogramVec, IntCounter, IntCounterVec, IntGauge,
+    IntGaugeVec,
 };
 use once_cell::sync::Lazy;
 use std::time::Duration;
@@ -182,6 +183,53 @@ pub static NUM_BATCH_EXPIRED_WHEN_SAVE: Lazy<IntCounter> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Count of the number of expired batches removed from the batch store.
+pub static GC_RECLAIMED_BATCH_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "quorum_store_gc_reclaimed_batch_count",
+        "Count of the number of expired batches removed from the batch store."
+    )
+    .unwrap()
+});
+
+/// Total size of the expired batches removed from the batch store.
+pub static GC_RECLAIMED_BATCH_BYTES: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "quorum_store_gc_reclaimed_batch_bytes",
+        "Total size of the expired batches removed from the batch store."
+    )
+    .unwrap()
+});
+
+/// Certified time up to which all expired batches were deleted from the db.
+pub static GC_EXPIRATION_WATERMARK: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "quorum_store_gc_expiration_watermark",
+        "Certified time up to which all expired batches were deleted from the db."
+    )
+    .unwrap()
+});
+
+/// Per-validator batch store quotas, as currently computed by the dynamic quota controller.
+pub static BATCH_STORE_EFFECTIVE_QUOTA: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "quorum_store_batch_store_effective_quota",
+        "Per-validator batch store quotas, as currently computed by the dynamic quota controller",
+        &["kind"]
+    )
+    .unwrap()
+});
+
+/// Resources used by the batch store, summed over all validators.
+pub static BATCH_STORE_QUOTA_USAGE: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "quorum_store_batch_store_quota_usage",
+        "Resources used by the batch store, summed over all validators",
+        &["kind"]
+    )
+    .unwrap()
+});
+
 /// Histogram for the gaps between expiration time and the current block timestamp on commit, and expiration round is lower.
 pub static GAP_BETWEEN_BATCH_EXPIRATION_AND_CURRENT_TIME_WHEN_COMMIT: Lazy<Histogram> = Lazy::new(
     || {
@@ -491,6 +539,15 @@ pub static RECEIVED_BATCH_REQUEST_TIMEOUT_COUNT: Lazy<IntCounter> = Lazy::new(||
     .unwrap()
 });
 
+/// Count of the number of batch requests not sent, because the peer had too many outstanding requests.
+pub static BATCH_REQUEST_RATE_LIMITED_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "quorum_store_batch_request_rate_limited_count",
+        "Count of the number of batch requests not sent, because the peer had too many outstanding requests."
+    )
+    .unwrap()
+});
+
 /// Count of the number of batches received from other nodes.
 pub static RECEIVED_BATCH_RESPONSE_COUNT: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
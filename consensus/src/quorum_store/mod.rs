@@ -15,9 +15,10 @@ pub(crate) mod proof_manager;
 pub(crate) mod quorum_store_builder;
 pub(crate) mod quorum_store_coordinator;
 pub mod quorum_store_db;
+pub(crate) mod quota_controller;
 pub mod types;
 pub(crate) mod utils;
 
 mod schema;
 #[cfg(test)]
-mod tests;
+pub(crate) mod tests;
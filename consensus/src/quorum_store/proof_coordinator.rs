@@ -11,16 +11,13 @@ use crate::{
 use aptos_consensus_types::proof_of_store::{
     BatchInfo, ProofOfStore, SignedBatchInfo, SignedBatchInfoError, SignedBatchInfoMsg,
 };
-use aptos_crypto::{bls12381, HashValue};
+use aptos_crypto::HashValue;
 use aptos_logger::prelude::*;
 use aptos_types::{
-    aggregate_signature::PartialSignatures, validator_verifier::ValidatorVerifier, PeerId,
-};
-use std::{
-    collections::{BTreeMap, HashMap},
-    sync::Arc,
-    time::Duration,
+    aggregate_signature::IncrementalAggregateSignature, validator_verifier::ValidatorVerifier,
+    PeerId,
 };
+use std::{collections::HashMap, sync::Arc, time::Duration};
 use tokio::{
     sync::{mpsc::Receiver, oneshot as TokioOneshot},
     time,
@@ -34,17 +31,19 @@ pub(crate) enum ProofCoordinatorCommand {
 
 struct IncrementalProofState {
     info: BatchInfo,
-    aggregated_signature: BTreeMap<PeerId, bls12381::Signature>,
+    // Signatures are aggregated as they arrive, so the memory per batch does not grow with the
+    // number of signers.
+    aggregated_signature: IncrementalAggregateSignature,
     aggregated_voting_power: u128,
     self_voted: bool,
     completed: bool,
 }
 
 impl IncrementalProofState {
-    fn new(info: BatchInfo) -> Self {
+    fn new(info: BatchInfo, num_validators: usize) -> Self {
         Self {
             info,
-            aggregated_signature: BTreeMap::new(),
+            aggregated_signature: IncrementalAggregateSignature::new(num_validators as u16),
             aggregated_voting_power: 0,
             self_voted: false,
             completed: false,
@@ -60,30 +59,29 @@ impl IncrementalProofState {
             return Err(SignedBatchInfoError::WrongInfo);
         }
 
-        if self
-            .aggregated_signature
-            .contains_key(&signed_batch_info.signer())
-        {
-            return Err(SignedBatchInfoError::DuplicatedSignature);
-        }
-
-        match validator_verifier.get_voting_power(&signed_batch_info.signer()) {
-            Some(voting_power) => {
-                let signer = signed_batch_info.signer();
-                if self
+        let signer = signed_batch_info.signer();
+        match validator_verifier.address_to_validator_index().get(&signer) {
+            Some(index) => {
+                let index = *index as u16;
+                if self.aggregated_signature.contains(index) {
+                    return Err(SignedBatchInfoError::DuplicatedSignature);
+                }
+                let voting_power = validator_verifier
+                    .get_voting_power(&signer)
+                    .expect("Validator with an index must have a voting power");
+                if let Err(e) = self
                     .aggregated_signature
-                    .insert(signer, signed_batch_info.signature())
-                    .is_none()
+                    .add_signature(index, signed_batch_info.signature())
                 {
-                    self.aggregated_voting_power += voting_power as u128;
-                    if signer == self.info.author() {
-                        self.self_voted = true;
-                    }
-                } else {
                     error!(
-                        "Author already in aggregated_signatures right after rechecking: {}",
-                        signer
+                        "Failed to aggregate signature from author {}: {:?}",
+                        signer, e
                     );
+                    return Err(SignedBatchInfoError::FailedToAggregate);
+                }
+                self.aggregated_voting_power += voting_power as u128;
+                if signer == self.info.author() {
+                    self.self_voted = true;
                 }
             },
             None => {
@@ -100,8 +98,11 @@ impl IncrementalProofState {
 
     fn ready(&self, validator_verifier: &ValidatorVerifier) -> bool {
         if self.aggregated_voting_power >= validator_verifier.quorum_voting_power() {
-            let recheck =
-                validator_verifier.check_voting_power(self.aggregated_signature.keys(), true);
+            let signers = self
+                .aggregated_signature
+                .to_aggregate_signature()
+                .get_signers_addresses(&validator_verifier.get_ordered_account_addresses());
+            let recheck = validator_verifier.check_voting_power(signers.iter(), true);
             if recheck.is_err() {
                 error!("Unexpected discrepancy: aggregated_voting_power is {}, while rechecking we get {:?}", self.aggregated_voting_power, recheck);
             }
@@ -111,18 +112,16 @@ impl IncrementalProofState {
         }
     }
 
-    fn take(&mut self, validator_verifier: &ValidatorVerifier) -> ProofOfStore {
+    fn take(&mut self) -> ProofOfStore {
         if self.completed {
             panic!("Cannot call take twice, unexpected issue occurred");
         }
         self.completed = true;
 
-        match validator_verifier
-            .aggregate_signatures(&PartialSignatures::new(self.aggregated_signature.clone()))
-        {
-            Ok(sig) => ProofOfStore::new(self.info.clone(), sig),
-            Err(e) => unreachable!("Cannot aggregate signatures on digest err = {:?}", e),
-        }
+        ProofOfStore::new(
+            self.info.clone(),
+            self.aggregated_signature.to_aggregate_signature(),
+        )
     }
 }
 
@@ -159,6 +158,7 @@ impl ProofCoordinator {
     fn init_proof(
         &mut self,
         signed_batch_info: &SignedBatchInfo,
+        validator_verifier: &ValidatorVerifier,
     ) -> Result<(), SignedBatchInfoError> {
         // Check if the signed digest corresponding to our batch
         if signed_batch_info.author() != self.peer_id {
@@ -178,7 +178,10 @@ impl ProofCoordinator {
         );
         self.digest_to_proof.insert(
             *signed_batch_info.digest(),
-            IncrementalProofState::new(signed_batch_info.batch_info().clone()),
+            IncrementalProofState::new(
+                signed_batch_info.batch_info().clone(),
+                validator_verifier.len(),
+            ),
         );
         self.digest_to_time
             .entry(*signed_batch_info.digest())
@@ -195,13 +198,13 @@ impl ProofCoordinator {
             .digest_to_proof
             .contains_key(signed_batch_info.digest())
         {
-            self.init_proof(&signed_batch_info)?;
+            self.init_proof(&signed_batch_info, validator_verifier)?;
         }
         let digest = *signed_batch_info.digest();
         if let Some(value) = self.digest_to_proof.get_mut(signed_batch_info.digest()) {
             value.add_signature(signed_batch_info, validator_verifier)?;
             if !value.completed && value.ready(validator_verifier) {
-                let proof = value.take(validator_verifier);
+                let proof = value.take();
                 // quorum store measurements
                 let duration = chrono::Utc::now().naive_utc().timestamp_micros() as u64
                     - self
@@ -232,7 +235,7 @@ impl ProofCoordinator {
                     counters::TIMEOUT_BATCHES_COUNT.inc();
                 }
                 counters::BATCH_RECEIVED_REPLIES_COUNT
-                    .observe(state.aggregated_signature.len() as f64);
+                    .observe(state.aggregated_signature.get_num_voters() as f64);
                 counters::BATCH_RECEIVED_REPLIES_VOTING_POWER
                     .observe(state.aggregated_voting_power as f64);
                 counters::BATCH_SUCCESSFUL_CREATION
@@ -5,12 +5,14 @@ use crate::{
     monitor,
     quorum_store::{batch_generator::BackPressure, counters, utils::ProofQueue},
 };
+use aptos_config::config::ProofPullPolicy;
 use aptos_consensus_types::{
     common::{Payload, PayloadFilter, ProofWithData},
     proof_of_store::{BatchInfo, ProofOfStore, ProofOfStoreMsg},
     request_response::{GetPayloadCommand, GetPayloadResponse},
 };
 use aptos_logger::prelude::*;
+use aptos_mempool::BackPressureStatus;
 use aptos_types::PeerId;
 use futures::StreamExt;
 use futures_channel::mpsc::Receiver;
@@ -36,9 +38,10 @@ impl ProofManager {
         my_peer_id: PeerId,
         back_pressure_total_txn_limit: u64,
         back_pressure_total_proof_limit: u64,
+        proof_pull_policy: ProofPullPolicy,
     ) -> Self {
         Self {
-            proofs_for_consensus: ProofQueue::new(my_peer_id),
+            proofs_for_consensus: ProofQueue::new(my_peer_id, proof_pull_policy),
             back_pressure_total_txn_limit,
             remaining_total_txn_num: 0,
             back_pressure_total_proof_limit,
@@ -126,6 +129,7 @@ impl ProofManager {
     pub async fn start(
         mut self,
         back_pressure_tx: tokio::sync::mpsc::Sender<BackPressure>,
+        back_pressure_status: BackPressureStatus,
         mut proposal_rx: Receiver<GetPayloadCommand>,
         mut proof_rx: tokio::sync::mpsc::Receiver<ProofManagerCommand>,
     ) {
@@ -144,6 +148,7 @@ impl ProofManager {
                         let updated_back_pressure = self.qs_back_pressure();
                         if updated_back_pressure != back_pressure {
                             back_pressure = updated_back_pressure;
+                            back_pressure_status.update(back_pressure.txn_count, back_pressure.proof_count);
                             if back_pressure_tx.send(back_pressure).await.is_err() {
                                 debug!("Failed to send back_pressure for proposal");
                             }
@@ -153,6 +158,8 @@ impl ProofManager {
                         monitor!("proof_manager_handle_command", {
                         match msg {
                             ProofManagerCommand::Shutdown(ack_tx) => {
+                                // The next epoch starts without back pressure.
+                                back_pressure_status.update(false, false);
                                 ack_tx
                                     .send(())
                                     .expect("Failed to send shutdown ack to QuorumStore");
@@ -171,6 +178,7 @@ impl ProofManager {
                         let updated_back_pressure = self.qs_back_pressure();
                         if updated_back_pressure != back_pressure {
                             back_pressure = updated_back_pressure;
+                            back_pressure_status.update(back_pressure.txn_count, back_pressure.proof_count);
                             if back_pressure_tx.send(back_pressure).await.is_err() {
                                 debug!("Failed to send back_pressure for commit notification");
                             }
@@ -11,13 +11,14 @@ use crate::{
         batch_coordinator::{BatchCoordinator, BatchCoordinatorCommand},
         batch_generator::{BackPressure, BatchGenerator, BatchGeneratorCommand},
         batch_requester::BatchRequester,
-        batch_store::BatchStore,
+        batch_store::{gc_expired_batches_periodically, BatchStore},
         counters,
         direct_mempool_quorum_store::DirectMempoolQuorumStore,
         network_listener::NetworkListener,
         proof_coordinator::{ProofCoordinator, ProofCoordinatorCommand},
         proof_manager::{ProofManager, ProofManagerCommand},
         quorum_store_coordinator::{CoordinatorCommand, QuorumStoreCoordinator},
+        quota_controller::{QuotaController, SystemResourceSampler},
     },
     round_manager::VerifiedEvent,
 };
@@ -26,7 +27,7 @@ use aptos_config::config::{QuorumStoreConfig, SecureBackend};
 use aptos_consensus_types::{common::Author, request_response::GetPayloadCommand};
 use aptos_global_constants::CONSENSUS_KEY;
 use aptos_logger::prelude::*;
-use aptos_mempool::QuorumStoreRequest;
+use aptos_mempool::{BackPressureStatus, QuorumStoreRequest};
 use aptos_secure_storage::{KVStorage, Storage};
 use aptos_storage_interface::DbReader;
 use aptos_types::{
@@ -138,6 +139,7 @@ pub struct InnerBuilder {
     remote_batch_coordinator_cmd_tx: Vec<tokio::sync::mpsc::Sender<BatchCoordinatorCommand>>,
     remote_batch_coordinator_cmd_rx: Vec<tokio::sync::mpsc::Receiver<BatchCoordinatorCommand>>,
     batch_store: Option<Arc<BatchStore<NetworkSender>>>,
+    back_pressure_status: BackPressureStatus,
 }
 
 impl InnerBuilder {
@@ -154,6 +156,7 @@ impl InnerBuilder {
         verifier: ValidatorVerifier,
         backend: SecureBackend,
         quorum_store_storage: Arc<dyn QuorumStoreStorage>,
+        back_pressure_status: BackPressureStatus,
     ) -> Self {
         let (coordinator_tx, coordinator_rx) = futures_channel::mpsc::channel(config.channel_size);
         let (batch_generator_cmd_tx, batch_generator_cmd_rx) =
@@ -206,6 +209,7 @@ impl InnerBuilder {
             remote_batch_coordinator_cmd_tx,
             remote_batch_coordinator_cmd_rx,
             batch_store: None,
+            back_pressure_status,
         }
     }
 
@@ -235,6 +239,7 @@ impl InnerBuilder {
             self.config.batch_request_retry_interval_ms,
             self.config.batch_request_rpc_timeout_ms,
             self.network_sender.clone(),
+            self.config.batch_request_scoring,
         );
         let batch_store = Arc::new(BatchStore::new(
             self.epoch,
@@ -249,6 +254,25 @@ impl InnerBuilder {
         ));
         self.batch_store = Some(batch_store.clone());
 
+        spawn_named!(
+            "batch_store_gc",
+            gc_expired_batches_periodically(
+                Arc::downgrade(&batch_store),
+                Duration::from_millis(self.config.batch_gc_interval_ms),
+            )
+        );
+
+        if self.config.dynamic_quota.enabled {
+            let quota_controller = QuotaController::new(
+                self.config.dynamic_quota,
+                batch_store.quotas(),
+                self.num_validators as usize,
+                Arc::downgrade(&batch_store),
+                SystemResourceSampler::new(self.quorum_store_storage.db_path()),
+            );
+            spawn_named!("quota_controller", quota_controller.start());
+        }
+
         batch_store
     }
 
@@ -341,11 +365,13 @@ impl InnerBuilder {
                 .back_pressure
                 .backlog_per_validator_batch_limit_count
                 * self.num_validators,
+            self.config.proof_pull_policy,
         );
         spawn_named!(
             "proof_manager",
             proof_manager.start(
                 self.back_pressure_tx.clone(),
+                self.back_pressure_status.clone(),
                 self.consensus_to_quorum_store_receiver,
                 proof_manager_cmd_rx,
             )
@@ -4,7 +4,10 @@
 use crate::{
     error::DbError,
     quorum_store::{
-        schema::{BatchIdSchema, BatchSchema, BATCH_CF_NAME, BATCH_ID_CF_NAME},
+        schema::{
+            BatchIdSchema, BatchSchema, ExpirationWatermarkSchema, BATCH_CF_NAME, BATCH_ID_CF_NAME,
+            EXPIRATION_WATERMARK_CF_NAME,
+        },
         types::PersistedValue,
     },
 };
@@ -13,7 +16,11 @@ use aptos_consensus_types::proof_of_store::BatchId;
 use aptos_crypto::HashValue;
 use aptos_logger::prelude::*;
 use aptos_schemadb::{Options, ReadOptions, SchemaBatch, DB};
-use std::{collections::HashMap, path::Path, time::Instant};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::Instant,
+};
 
 pub trait QuorumStoreStorage: Sync + Send {
     fn delete_batches(&self, digests: Vec<HashValue>) -> Result<(), DbError>;
@@ -29,6 +36,15 @@ pub trait QuorumStoreStorage: Sync + Send {
     fn clean_and_get_batch_id(&self, current_epoch: u64) -> Result<Option<BatchId>, DbError>;
 
     fn save_batch_id(&self, epoch: u64, batch_id: BatchId) -> Result<(), DbError>;
+
+    fn get_expiration_watermark(&self) -> Result<Option<u64>, DbError>;
+
+    fn save_expiration_watermark(&self, watermark: u64) -> Result<(), DbError>;
+
+    /// The on-disk location of the storage, if any. Used to observe the available disk space.
+    fn db_path(&self) -> Option<&Path> {
+        None
+    }
 }
 
 /// The name of the quorum store db file
@@ -36,11 +52,16 @@ pub const QUORUM_STORE_DB_NAME: &str = "quorumstoreDB";
 
 pub struct QuorumStoreDB {
     db: DB,
+    path: PathBuf,
 }
 
 impl QuorumStoreDB {
     pub(crate) fn new<P: AsRef<Path> + Clone>(db_root_path: P) -> Self {
-        let column_families = vec![BATCH_CF_NAME, BATCH_ID_CF_NAME];
+        let column_families = vec![
+            BATCH_CF_NAME,
+            BATCH_ID_CF_NAME,
+            EXPIRATION_WATERMARK_CF_NAME,
+        ];
 
         // TODO: this fails twins tests because it assumes a unique path per process
         let path = db_root_path.as_ref().join(QUORUM_STORE_DB_NAME);
@@ -57,7 +78,7 @@ impl QuorumStoreDB {
             instant.elapsed().as_millis()
         );
 
-        Self { db }
+        Self { db, path }
     }
 }
 
@@ -118,6 +139,18 @@ impl QuorumStoreStorage for QuorumStoreDB {
     fn save_batch_id(&self, epoch: u64, batch_id: BatchId) -> Result<(), DbError> {
         Ok(self.db.put::<BatchIdSchema>(&epoch, &batch_id)?)
     }
+
+    fn get_expiration_watermark(&self) -> Result<Option<u64>, DbError> {
+        Ok(self.db.get::<ExpirationWatermarkSchema>(&())?)
+    }
+
+    fn save_expiration_watermark(&self, watermark: u64) -> Result<(), DbError> {
+        Ok(self.db.put::<ExpirationWatermarkSchema>(&(), &watermark)?)
+    }
+
+    fn db_path(&self) -> Option<&Path> {
+        Some(&self.path)
+    }
 }
 
 pub(crate) struct MockQuorumStoreDB {}
@@ -157,4 +190,12 @@ impl QuorumStoreStorage for MockQuorumStoreDB {
     fn save_batch_id(&self, _: u64, _: BatchId) -> Result<(), DbError> {
         Ok(())
     }
+
+    fn get_expiration_watermark(&self) -> Result<Option<u64>, DbError> {
+        Ok(None)
+    }
+
+    fn save_expiration_watermark(&self, _: u64) -> Result<(), DbError> {
+        Ok(())
+    }
 }
@@ -0,0 +1,205 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    network::QuorumStoreSender,
+    quorum_store::{
+        batch_store::{BatchStore, QuotaUsage, Quotas},
+        counters,
+    },
+};
+use aptos_config::config::QuorumStoreDynamicQuotaConfig;
+use aptos_logger::prelude::*;
+use std::{
+    path::{Path, PathBuf},
+    sync::Weak,
+    time::Duration,
+};
+use sysinfo::{DiskExt, RefreshKind, System, SystemExt};
+
+/// Resources observed on the node, which are not currently in use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct AvailableResources {
+    pub(crate) memory_bytes: u64,
+    /// None if the disk backing the quorum store db could not be determined.
+    pub(crate) disk_bytes: Option<u64>,
+}
+
+pub(crate) trait ResourceSampler: Send {
+    fn sample(&mut self) -> AvailableResources;
+}
+
+pub(crate) struct SystemResourceSampler {
+    system: System,
+    db_path: Option<PathBuf>,
+}
+
+impl SystemResourceSampler {
+    pub(crate) fn new(db_path: Option<&Path>) -> Self {
+        let db_path =
+            db_path.map(|path| std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf()));
+        Self {
+            system: System::new_with_specifics(
+                RefreshKind::new()
+                    .with_memory()
+                    .with_disks_list()
+                    .with_disks(),
+            ),
+            db_path,
+        }
+    }
+}
+
+impl ResourceSampler for SystemResourceSampler {
+    fn sample(&mut self) -> AvailableResources {
+        self.system.refresh_memory();
+        self.system.refresh_disks_list();
+        self.system.refresh_disks();
+
+        // The db lives on the disk with the most specific mount point containing its path.
+        let disk_bytes = self.db_path.as_ref().and_then(|db_path| {
+            self.system
+                .disks()
+                .iter()
+                .filter(|disk| db_path.starts_with(disk.mount_point()))
+                .max_by_key(|disk| disk.mount_point().as_os_str().len())
+                .map(|disk| disk.available_space())
+        });
+
+        AvailableResources {
+            memory_bytes: self.system.available_memory(),
+            disk_bytes,
+        }
+    }
+}
+
+fn fraction_per_validator(bytes: u64, fraction: f64, num_validators: usize) -> usize {
+    (bytes as f64 * fraction / num_validators.max(1) as f64) as usize
+}
+
+/// Computes the per-validator quotas from the available resources. The resources already
+/// used by the batch store are counted as available, so that the quotas do not shrink as
+/// the batch store fills up.
+pub(crate) fn compute_quotas(
+    config: &QuorumStoreDynamicQuotaConfig,
+    static_quotas: Quotas,
+    num_validators: usize,
+    available: AvailableResources,
+    usage: QuotaUsage,
+) -> Quotas {
+    let memory_quota = fraction_per_validator(
+        available
+            .memory_bytes
+            .saturating_add(usage.memory_bytes as u64),
+        config.max_memory_fraction,
+        num_validators,
+    )
+    .clamp(config.min_memory_quota, config.max_memory_quota);
+
+    let db_quota = match available.disk_bytes {
+        Some(disk_bytes) => fraction_per_validator(
+            disk_bytes.saturating_add(usage.db_bytes as u64),
+            config.max_disk_fraction,
+            num_validators,
+        ),
+        None => static_quotas.db_quota,
+    }
+    .clamp(config.min_db_quota, config.max_db_quota)
+    // Everything stored in memory is also persisted.
+    .max(memory_quota);
+
+    // Scale the number of batches with the db quota, keeping the static bytes per batch.
+    let batch_quota = (static_quotas.batch_quota as u128 * db_quota as u128
+        / static_quotas.db_quota.max(1) as u128) as usize;
+    let batch_quota = batch_quota.clamp(config.min_batch_quota, config.max_batch_quota);
+
+    Quotas {
+        memory_quota,
+        db_quota,
+        batch_quota,
+    }
+}
+
+/// Periodically recomputes the batch store quotas from the observed memory and disk
+/// availability. Stops once the batch store is dropped at the end of the epoch.
+pub(crate) struct QuotaController<T, S> {
+    config: QuorumStoreDynamicQuotaConfig,
+    static_quotas: Quotas,
+    num_validators: usize,
+    batch_store: Weak<BatchStore<T>>,
+    sampler: S,
+}
+
+impl<T, S> QuotaController<T, S>
+where
+    T: QuorumStoreSender + Clone + Send + Sync + 'static,
+    S: ResourceSampler,
+{
+    pub(crate) fn new(
+        config: QuorumStoreDynamicQuotaConfig,
+        static_quotas: Quotas,
+        num_validators: usize,
+        batch_store: Weak<BatchStore<T>>,
+        sampler: S,
+    ) -> Self {
+        Self {
+            config,
+            static_quotas,
+            num_validators,
+            batch_store,
+            sampler,
+        }
+    }
+
+    fn update(&mut self, batch_store: &BatchStore<T>) {
+        let usage = batch_store.quota_usage();
+        let available = self.sampler.sample();
+        let quotas = compute_quotas(
+            &self.config,
+            self.static_quotas,
+            self.num_validators,
+            available,
+            usage,
+        );
+        if quotas != batch_store.quotas() {
+            debug!(
+                "QS: updating batch store quotas to {:?}, available {:?}, usage {:?}",
+                quotas, available, usage
+            );
+            batch_store.update_quotas(quotas);
+        }
+
+        for (kind, quota) in [
+            ("memory", quotas.memory_quota),
+            ("db", quotas.db_quota),
+            ("batch", quotas.batch_quota),
+        ] {
+            counters::BATCH_STORE_EFFECTIVE_QUOTA
+                .with_label_values(&[kind])
+                .set(quota as i64);
+        }
+        for (kind, used) in [
+            ("memory", usage.memory_bytes),
+            ("db", usage.db_bytes),
+            ("batch", usage.num_batches),
+        ] {
+            counters::BATCH_STORE_QUOTA_USAGE
+                .with_label_values(&[kind])
+                .set(used as i64);
+        }
+    }
+
+    pub(crate) async fn start(mut self) {
+        info!("QS: dynamic quota controller starts");
+        let mut interval =
+            tokio::time::interval(Duration::from_millis(self.config.sample_interval_ms));
+        loop {
+            interval.tick().await;
+            match self.batch_store.upgrade() {
+                Some(batch_store) => self.update(&batch_store),
+                None => break,
+            }
+        }
+        info!("QS: dynamic quota controller stops");
+    }
+}
@@ -12,6 +12,7 @@ use aptos_schemadb::{
 
 pub(crate) const BATCH_CF_NAME: ColumnFamilyName = "batch";
 pub(crate) const BATCH_ID_CF_NAME: ColumnFamilyName = "batch_ID";
+pub(crate) const EXPIRATION_WATERMARK_CF_NAME: ColumnFamilyName = "expiration_watermark";
 
 #[derive(Debug)]
 pub(crate) struct BatchSchema;
@@ -72,3 +73,34 @@ impl ValueCodec<BatchIdSchema> for BatchId {
         Ok(bcs::from_bytes(data)?)
     }
 }
+
+/// Stores the certified time up to which all expired batches were deleted from the db.
+#[derive(Debug)]
+pub(crate) struct ExpirationWatermarkSchema;
+
+impl Schema for ExpirationWatermarkSchema {
+    type Key = ();
+    type Value = u64;
+
+    const COLUMN_FAMILY_NAME: aptos_schemadb::ColumnFamilyName = EXPIRATION_WATERMARK_CF_NAME;
+}
+
+impl KeyCodec<ExpirationWatermarkSchema> for () {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(Vec::new())
+    }
+
+    fn decode_key(_data: &[u8]) -> Result<Self> {
+        Ok(())
+    }
+}
+
+impl ValueCodec<ExpirationWatermarkSchema> for u64 {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(bcs::to_bytes(&self)?)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Ok(bcs::from_bytes(data)?)
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::quorum_store::batch_requester::BatchRequestPeerScores;
+use aptos_config::config::QuorumStoreBatchRequestScoringConfig;
+use aptos_types::PeerId;
+use std::{sync::Arc, time::Duration};
+
+fn peer_scores_for_test(max_inflight_requests_per_peer: usize) -> Arc<BatchRequestPeerScores> {
+    Arc::new(BatchRequestPeerScores::new(
+        QuorumStoreBatchRequestScoringConfig {
+            enabled: true,
+            moving_average_weight: 0.5,
+            min_score: 0.1,
+            max_inflight_requests_per_peer,
+        },
+        1000,
+    ))
+}
+
+#[test]
+fn test_peer_score_success_and_latency() {
+    let peer_scores = peer_scores_for_test(0);
+    let peer = PeerId::random();
+    assert_eq!(peer_scores.score(&peer), 1.0);
+
+    // A fast successful response keeps the peer at the top score.
+    peer_scores.record_response(peer, true, Duration::from_millis(0));
+    assert_eq!(peer_scores.score(&peer), 1.0);
+
+    // latency: 500ms, success rate: 1
+    peer_scores.record_response(peer, true, Duration::from_millis(1000));
+    assert_eq!(peer_scores.score(&peer), 1000.0 / 1500.0);
+
+    // latency: 250ms, success rate: 0.5
+    peer_scores.record_response(peer, false, Duration::from_millis(0));
+    assert_eq!(peer_scores.score(&peer), 0.5 * 1000.0 / 1250.0);
+}
+
+#[test]
+fn test_peer_score_min_score() {
+    let peer_scores = peer_scores_for_test(0);
+    let peer = PeerId::random();
+    for _ in 0..10 {
+        peer_scores.record_response(peer, false, Duration::from_millis(1000));
+    }
+    assert_eq!(peer_scores.score(&peer), 0.1);
+}
+
+#[test]
+fn test_order_peers_prefers_healthy_peers() {
+    let peer_scores = peer_scores_for_test(0);
+    let healthy_peer = PeerId::random();
+    let failing_peer = PeerId::random();
+    for _ in 0..10 {
+        peer_scores.record_response(healthy_peer, true, Duration::from_millis(0));
+        peer_scores.record_response(failing_peer, false, Duration::from_millis(1000));
+    }
+
+    let num_trials = 1000;
+    let healthy_first = (0..num_trials)
+        .filter(|_| {
+            let peers = peer_scores.order_peers(vec![failing_peer, healthy_peer]);
+            assert_eq!(peers.len(), 2);
+            peers[0] == healthy_peer
+        })
+        .count();
+    // The healthy peer is first with probability 1 / (1 + 0.1), the failing peer is
+    // still requested first occasionally.
+    assert!(healthy_first > num_trials * 8 / 10);
+    assert!(healthy_first < num_trials);
+}
+
+#[test]
+fn test_inflight_request_limit() {
+    let peer_scores = peer_scores_for_test(2);
+    let peer = PeerId::random();
+
+    let first = peer_scores.try_acquire(peer).unwrap();
+    let second = peer_scores.try_acquire(peer).unwrap();
+    assert!(peer_scores.try_acquire(peer).is_none());
+    // The limit is per peer.
+    assert!(peer_scores.try_acquire(PeerId::random()).is_some());
+    assert_eq!(peer_scores.num_inflight(&peer), 2);
+
+    drop(first);
+    assert_eq!(peer_scores.num_inflight(&peer), 1);
+    let _third = peer_scores.try_acquire(peer).unwrap();
+    assert!(peer_scores.try_acquire(peer).is_none());
+
+    drop(second);
+    assert_eq!(peer_scores.num_inflight(&peer), 1);
+}
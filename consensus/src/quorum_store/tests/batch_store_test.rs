@@ -4,12 +4,13 @@
 use crate::{
     quorum_store::{
         batch_requester::BatchRequester,
-        batch_store::{BatchStore, QuotaManager},
-        quorum_store_db::QuorumStoreDB,
+        batch_store::{BatchStore, QuotaManager, QuotaUsage, Quotas},
+        quorum_store_db::{QuorumStoreDB, QuorumStoreStorage},
         types::{PersistedValue, StorageMode},
     },
     test_utils::mock_quorum_store_sender::MockQuorumStoreSender,
 };
+use aptos_config::config::QuorumStoreBatchRequestScoringConfig;
 use aptos_consensus_types::proof_of_store::{BatchId, BatchInfo};
 use aptos_crypto::HashValue;
 use aptos_temppath::TempPath;
@@ -31,6 +32,14 @@ static TEST_REQUEST_ACCOUNT: Lazy<AccountAddress> = Lazy::new(AccountAddress::ra
 fn batch_store_for_test(memory_quota: usize) -> Arc<BatchStore<MockQuorumStoreSender>> {
     let tmp_dir = TempPath::new();
     let db = Arc::new(QuorumStoreDB::new(&tmp_dir));
+    batch_store_with_db(db, 10, memory_quota)
+}
+
+fn batch_store_with_db(
+    db: Arc<QuorumStoreDB>,
+    last_certified_time: u64,
+    memory_quota: usize,
+) -> Arc<BatchStore<MockQuorumStoreSender>> {
     let (tx, _rx) = channel(10);
     let requester = BatchRequester::new(
         10,
@@ -40,12 +49,13 @@ fn batch_store_for_test(memory_quota: usize) -> Arc<BatchStore<MockQuorumStoreSe
         1,
         1,
         MockQuorumStoreSender::new(tx),
+        QuorumStoreBatchRequestScoringConfig::default(),
     );
     let (signers, validator_verifier) = random_validator_verifier(4, None, false);
 
     Arc::new(BatchStore::new(
         10, // epoch
-        10, // last committed round
+        last_certified_time,
         db,
         memory_quota, // memory_quota
         2001,         // db quota
@@ -295,3 +305,108 @@ fn test_get_local_batch() {
     assert_err!(store.get_batch_from_local(&digest_2));
     assert_err!(store.get_batch_from_local(&digest_3));
 }
+
+#[test]
+fn test_quota_manager_set_quotas() {
+    let mut qm = QuotaManager::new(20, 10, 7);
+    assert_ok_eq!(qm.update_quota(8), StorageMode::MemoryAndPersisted);
+    assert_ok_eq!(qm.update_quota(8), StorageMode::PersistedOnly);
+
+    // Lower the quotas below the current usage.
+    qm.set_quotas(Quotas {
+        memory_quota: 5,
+        db_quota: 10,
+        batch_quota: 7,
+    });
+    assert_err!(qm.update_quota(1));
+
+    qm.free_quota(8, StorageMode::PersistedOnly);
+    // db usage: 8, memory usage: 8
+    assert_ok_eq!(qm.update_quota(2), StorageMode::PersistedOnly);
+    assert_err!(qm.update_quota(1));
+
+    // Raise the quotas again.
+    qm.set_quotas(Quotas {
+        memory_quota: 12,
+        db_quota: 30,
+        batch_quota: 3,
+    });
+    assert_ok_eq!(qm.update_quota(4), StorageMode::MemoryAndPersisted);
+    // Exceed batch quota
+    assert_err!(qm.update_quota(1));
+    assert_eq!(qm.usage(), QuotaUsage {
+        memory_bytes: 12,
+        db_bytes: 14,
+        num_batches: 3,
+    });
+}
+
+#[test]
+fn test_batch_store_update_quotas() {
+    let batch_store = batch_store_for_test(30);
+
+    assert_ok_eq!(
+        batch_store.insert_to_cache(request_for_test(&HashValue::random(), 15, 10, None)),
+        true
+    );
+
+    let quotas = Quotas {
+        memory_quota: 10,
+        db_quota: 25,
+        batch_quota: 2001,
+    };
+    batch_store.update_quotas(quotas);
+    assert_eq!(batch_store.quotas(), quotas);
+
+    // Memory quota is used up, so the batch is only persisted.
+    assert_ok_eq!(
+        batch_store.insert_to_cache(request_for_test(&HashValue::random(), 15, 10, None)),
+        true
+    );
+    assert_eq!(batch_store.quota_usage(), QuotaUsage {
+        memory_bytes: 10,
+        db_bytes: 20,
+        num_batches: 2,
+    });
+
+    // Exceed the updated db quota.
+    assert_err!(batch_store.insert_to_cache(request_for_test(&HashValue::random(), 15, 10, None)));
+}
+
+#[test]
+fn test_gc_persists_expiration_watermark() {
+    let tmp_dir = TempPath::new();
+    let db = Arc::new(QuorumStoreDB::new(&tmp_dir));
+    let store = batch_store_with_db(db.clone(), 10, 30);
+
+    let digest_1 = HashValue::random();
+    let digest_2 = HashValue::random();
+    assert!(!store
+        .persist(vec![request_for_test(&digest_1, 50, 20, Some(vec![]))])
+        .is_empty());
+    assert!(!store
+        .persist(vec![request_for_test(&digest_2, 60, 20, Some(vec![]))])
+        .is_empty());
+    assert_ok_eq!(db.get_expiration_watermark(), None);
+
+    block_on(store.update_certified_timestamp(55));
+    store.gc_expired_batches();
+    assert_eq!(store.num_pending_db_deletions(), 0);
+    assert_ok_eq!(db.get_expiration_watermark(), Some(55));
+    assert_ok_eq!(db.get_batch(&digest_1), None);
+    assert_ok!(store.get_batch_from_local(&digest_2));
+
+    // A batch left behind in the db, expired according to the watermark.
+    let digest_3 = HashValue::random();
+    assert_ok!(db.save_batch(request_for_test(&digest_3, 52, 20, Some(vec![]))));
+
+    drop(store);
+    drop(db);
+
+    // Reopen with a ledger info behind the watermark.
+    let db = Arc::new(QuorumStoreDB::new(&tmp_dir));
+    let store = batch_store_with_db(db.clone(), 10, 30);
+    assert_ok!(store.get_batch_from_local(&digest_2));
+    assert_err!(store.get_batch_from_local(&digest_3));
+    assert_ok_eq!(db.get_batch(&digest_3), None);
+}
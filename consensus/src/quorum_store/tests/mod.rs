@@ -2,10 +2,12 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod batch_generator_test;
+mod batch_requester_test;
 mod batch_store_test;
 mod direct_mempool_quorum_store_test;
 mod proof_coordinator_test;
 mod proof_manager_test;
 mod quorum_store_db_test;
+mod quota_controller_test;
 mod types_test;
-mod utils;
+pub(crate) mod utils;
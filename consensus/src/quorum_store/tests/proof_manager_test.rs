@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::quorum_store::proof_manager::ProofManager;
+use aptos_config::config::ProofPullPolicy;
 use aptos_consensus_types::{
     common::{Payload, PayloadFilter},
     proof_of_store::{BatchId, BatchInfo, ProofOfStore},
@@ -13,7 +14,7 @@ use futures::channel::oneshot;
 use std::collections::HashSet;
 
 fn create_proof_manager() -> ProofManager {
-    ProofManager::new(PeerId::random(), 10, 10)
+    ProofManager::new(PeerId::random(), 10, 10, ProofPullPolicy::AuthorRoundRobin)
 }
 
 fn create_proof(author: PeerId, expiration: u64, batch_sequence: u64) -> ProofOfStore {
@@ -177,6 +178,52 @@ async fn test_proposal_fairness() {
     get_proposal_and_assert(&mut proof_manager, 2, &filter, &peer0_proofs[3..4]).await;
 }
 
+#[tokio::test]
+async fn test_proposal_gas_bucket_priority_across_peers() {
+    let mut proof_manager = ProofManager::new(
+        PeerId::random(),
+        10,
+        10,
+        ProofPullPolicy::HighestGasBucketFirst,
+    );
+    let peer0 = PeerId::random();
+    let peer1 = PeerId::random();
+
+    let peer0_proof0 = create_proof_with_gas(peer0, 10, 1, 0);
+    let peer0_proof1 = create_proof_with_gas(peer0, 10, 2, 500);
+    let peer1_proof0 = create_proof_with_gas(peer1, 10, 1, 1000);
+    let peer1_proof1 = create_proof_with_gas(peer1, 10, 2, 500);
+    let peer1_proof2 = create_proof_with_gas(peer1, 10, 3, 0);
+    proof_manager.receive_proofs(vec![peer0_proof0.clone(), peer0_proof1.clone()]);
+    proof_manager.receive_proofs(vec![
+        peer1_proof0.clone(),
+        peer1_proof1.clone(),
+        peer1_proof2.clone(),
+    ]);
+
+    // The highest gas bucket is pulled first, regardless of the author
+    get_proposal_and_assert(&mut proof_manager, 1, &[], &vec![peer1_proof0.clone()]).await;
+
+    // The next bucket is pulled fairly from both peers
+    get_proposal_and_assert(&mut proof_manager, 3, &[], &vec![
+        peer1_proof0.clone(),
+        peer0_proof1.clone(),
+        peer1_proof1.clone(),
+    ])
+    .await;
+
+    // The lowest bucket is pulled last
+    let filter: Vec<_> = [&peer1_proof0, &peer0_proof1, &peer1_proof1]
+        .into_iter()
+        .map(|proof| proof.info().clone())
+        .collect();
+    get_proposal_and_assert(&mut proof_manager, 2, &filter, &vec![
+        peer0_proof0.clone(),
+        peer1_proof2.clone(),
+    ])
+    .await;
+}
+
 #[tokio::test]
 async fn test_duplicate_batches_on_commit() {
     let mut proof_manager = create_proof_manager();
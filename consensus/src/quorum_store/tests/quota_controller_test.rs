@@ -0,0 +1,125 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::quorum_store::{
+    batch_store::{QuotaUsage, Quotas},
+    quota_controller::{compute_quotas, AvailableResources},
+};
+use aptos_config::config::QuorumStoreDynamicQuotaConfig;
+
+const STATIC_QUOTAS: Quotas = Quotas {
+    memory_quota: 120_000_000,
+    db_quota: 300_000_000,
+    batch_quota: 300_000,
+};
+
+fn config_for_test() -> QuorumStoreDynamicQuotaConfig {
+    QuorumStoreDynamicQuotaConfig {
+        enabled: true,
+        max_memory_fraction: 0.5,
+        max_disk_fraction: 0.5,
+        min_memory_quota: 1_000,
+        max_memory_quota: 1_000_000,
+        min_db_quota: 2_000,
+        max_db_quota: 2_000_000,
+        min_batch_quota: 10,
+        max_batch_quota: 10_000,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_compute_quotas_scales_with_available_resources() {
+    let quotas = compute_quotas(
+        &config_for_test(),
+        STATIC_QUOTAS,
+        10,
+        AvailableResources {
+            memory_bytes: 6_000_000,
+            disk_bytes: Some(18_000_000),
+        },
+        QuotaUsage {
+            memory_bytes: 2_000_000,
+            db_bytes: 2_000_000,
+            num_batches: 100,
+        },
+    );
+    assert_eq!(quotas, Quotas {
+        memory_quota: 400_000,
+        db_quota: 1_000_000,
+        batch_quota: 1_000,
+    });
+}
+
+#[test]
+fn test_compute_quotas_clamped() {
+    let config = config_for_test();
+
+    let low = compute_quotas(
+        &config,
+        STATIC_QUOTAS,
+        10,
+        AvailableResources {
+            memory_bytes: 0,
+            disk_bytes: Some(0),
+        },
+        QuotaUsage::default(),
+    );
+    assert_eq!(low, Quotas {
+        memory_quota: 1_000,
+        db_quota: 2_000,
+        batch_quota: 10,
+    });
+
+    let high = compute_quotas(
+        &config,
+        STATIC_QUOTAS,
+        10,
+        AvailableResources {
+            memory_bytes: u64::MAX,
+            disk_bytes: Some(u64::MAX),
+        },
+        QuotaUsage::default(),
+    );
+    assert_eq!(high, Quotas {
+        memory_quota: 1_000_000,
+        db_quota: 2_000_000,
+        batch_quota: 2_000,
+    });
+}
+
+#[test]
+fn test_compute_quotas_without_disk() {
+    let quotas = compute_quotas(
+        &config_for_test(),
+        STATIC_QUOTAS,
+        10,
+        AvailableResources {
+            memory_bytes: 200_000_000,
+            disk_bytes: None,
+        },
+        QuotaUsage::default(),
+    );
+    // Without a disk sample, the static db quota is used, clamped to the configured range,
+    // and never below the memory quota.
+    assert_eq!(quotas, Quotas {
+        memory_quota: 1_000_000,
+        db_quota: 2_000_000,
+        batch_quota: 2_000,
+    });
+
+    let quotas = compute_quotas(
+        &QuorumStoreDynamicQuotaConfig {
+            max_db_quota: 500_000,
+            ..config_for_test()
+        },
+        STATIC_QUOTAS,
+        10,
+        AvailableResources {
+            memory_bytes: 200_000_000,
+            disk_bytes: None,
+        },
+        QuotaUsage::default(),
+    );
+    assert_eq!(quotas.db_quota, quotas.memory_quota);
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{monitor, quorum_store::counters};
+use aptos_config::config::ProofPullPolicy;
 use aptos_consensus_types::{
     common::{TransactionInProgress, TransactionSummary},
     proof_of_store::{BatchId, BatchInfo, ProofOfStore},
@@ -15,7 +16,7 @@ use move_core_types::account_address::AccountAddress;
 use rand::{seq::SliceRandom, thread_rng};
 use std::{
     cmp::{Ordering, Reverse},
-    collections::{BTreeMap, BinaryHeap, HashMap, HashSet, VecDeque},
+    collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque},
     hash::Hash,
     time::{Duration, Instant},
 };
@@ -193,8 +194,18 @@ impl Ord for BatchSortKey {
     }
 }
 
+#[derive(Default)]
+struct ProofPullState {
+    proofs: Vec<ProofOfStore>,
+    cur_bytes: u64,
+    cur_txns: u64,
+    excluded_txns: u64,
+    full: bool,
+}
+
 pub struct ProofQueue {
     my_peer_id: PeerId,
+    pull_policy: ProofPullPolicy,
     // Queue per peer to ensure fairness between peers and priority within peer
     author_to_batches: HashMap<PeerId, BTreeMap<BatchSortKey, BatchInfo>>,
     // ProofOfStore and insertion_time. None if committed
@@ -209,9 +220,10 @@ pub struct ProofQueue {
 }
 
 impl ProofQueue {
-    pub(crate) fn new(my_peer_id: PeerId) -> Self {
+    pub(crate) fn new(my_peer_id: PeerId, pull_policy: ProofPullPolicy) -> Self {
         Self {
             my_peer_id,
+            pull_policy,
             author_to_batches: HashMap::new(),
             batch_to_proof: HashMap::new(),
             expirations: TimeExpirations::new(),
@@ -284,42 +296,112 @@ impl ProofQueue {
         max_bytes: u64,
         return_non_full: bool,
     ) -> Vec<ProofOfStore> {
-        let mut ret = vec![];
-        let mut cur_bytes = 0;
-        let mut cur_txns = 0;
-        let mut excluded_txns = 0;
-        let mut full = false;
-
-        let mut iters = vec![];
-        for (_, batches) in self.author_to_batches.iter() {
-            iters.push(batches.iter().rev());
+        let mut state = ProofPullState::default();
+
+        match self.pull_policy {
+            ProofPullPolicy::AuthorRoundRobin => {
+                let iters = self
+                    .author_to_batches
+                    .values()
+                    .map(|batches| batches.iter().rev())
+                    .collect();
+                self.pull_round_robin(iters, excluded_batches, max_txns, max_bytes, &mut state);
+            },
+            ProofPullPolicy::HighestGasBucketFirst => {
+                let buckets: BTreeSet<u64> = self
+                    .author_to_batches
+                    .values()
+                    .flat_map(|batches| batches.keys().map(|sort_key| sort_key.gas_bucket_start))
+                    .collect();
+                for bucket in buckets.into_iter().rev() {
+                    if state.full {
+                        break;
+                    }
+                    // Per author, the batches are sorted by gas bucket, so the batches of the
+                    // bucket are contiguous.
+                    let iters = self
+                        .author_to_batches
+                        .values()
+                        .map(|batches| {
+                            batches
+                                .iter()
+                                .rev()
+                                .skip_while(move |(sort_key, _)| sort_key.gas_bucket_start > bucket)
+                                .take_while(move |(sort_key, _)| {
+                                    sort_key.gas_bucket_start == bucket
+                                })
+                        })
+                        .collect();
+                    self.pull_round_robin(iters, excluded_batches, max_txns, max_bytes, &mut state);
+                }
+            },
         }
 
+        let ProofPullState {
+            proofs: ret,
+            cur_bytes,
+            cur_txns,
+            excluded_txns,
+            full,
+        } = state;
+        info!(
+            // before non full check
+            byte_size = cur_bytes,
+            block_size = cur_txns,
+            batch_count = ret.len(),
+            full = full,
+            return_non_full = return_non_full,
+            "Pull payloads from QuorumStore: internal"
+        );
+
+        if full || return_non_full {
+            counters::BLOCK_SIZE_WHEN_PULL.observe(cur_txns as f64);
+            counters::BLOCK_BYTES_WHEN_PULL.observe(cur_bytes as f64);
+            counters::PROOF_SIZE_WHEN_PULL.observe(ret.len() as f64);
+            counters::EXCLUDED_TXNS_WHEN_PULL.observe(excluded_txns as f64);
+            ret
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Pulls proofs from the given per-author iterators in a shuffled round-robin, until the
+    /// iterators are exhausted or the limits are reached.
+    fn pull_round_robin<'a, I>(
+        &'a self,
+        mut iters: Vec<I>,
+        excluded_batches: &HashSet<BatchInfo>,
+        max_txns: u64,
+        max_bytes: u64,
+        state: &mut ProofPullState,
+    ) where
+        I: Iterator<Item = (&'a BatchSortKey, &'a BatchInfo)>,
+    {
         while !iters.is_empty() {
             iters.shuffle(&mut thread_rng());
             iters.retain_mut(|iter| {
-                if full {
+                if state.full {
                     return false;
                 }
                 if let Some((sort_key, batch)) = iter.next() {
                     if excluded_batches.contains(batch) {
-                        excluded_txns += batch.num_txns();
+                        state.excluded_txns += batch.num_txns();
                     } else if let Some(Some((proof, insertion_time))) =
                         self.batch_to_proof.get(&sort_key.batch_key)
                     {
-                        cur_bytes += batch.num_bytes();
-                        cur_txns += batch.num_txns();
-                        if cur_bytes > max_bytes || cur_txns > max_txns {
+                        state.cur_bytes += batch.num_bytes();
+                        state.cur_txns += batch.num_txns();
+                        if state.cur_bytes > max_bytes || state.cur_txns > max_txns {
                             // Exceeded the limit for requested bytes or number of transactions.
-                            full = true;
+                            state.full = true;
                             return false;
                         }
                         let bucket = proof.gas_bucket_start();
-                        ret.push(proof.clone());
+                        state.proofs.push(proof.clone());
                         counters::pos_to_pull(bucket, insertion_time.elapsed().as_secs_f64());
-                        if cur_bytes == max_bytes || cur_txns == max_txns {
+                        if state.cur_bytes == max_bytes || state.cur_txns == max_txns {
                             // Exactly the limit for requested bytes or number of transactions.
-                            full = true;
+                            state.full = true;
                             return false;
                         }
                     }
@@ -329,25 +411,6 @@ impl ProofQueue {
                 }
             })
         }
-        info!(
-            // before non full check
-            byte_size = cur_bytes,
-            block_size = cur_txns,
-            batch_count = ret.len(),
-            full = full,
-            return_non_full = return_non_full,
-            "Pull payloads from QuorumStore: internal"
-        );
-
-        if full || return_non_full {
-            counters::BLOCK_SIZE_WHEN_PULL.observe(cur_txns as f64);
-            counters::BLOCK_BYTES_WHEN_PULL.observe(cur_bytes as f64);
-            counters::PROOF_SIZE_WHEN_PULL.observe(ret.len() as f64);
-            counters::EXCLUDED_TXNS_WHEN_PULL.observe(excluded_txns as f64);
-            ret
-        } else {
-            Vec::new()
-        }
     }
 
     pub(crate) fn handle_updated_block_timestamp(&mut self, block_timestamp: u64) {
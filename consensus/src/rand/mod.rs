@@ -0,0 +1,18 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! On-chain randomness generation.
+//!
+//! Validators derive per-block randomness from threshold BLS signature shares (see
+//! `aptos_crypto::bls12381::bls12381_threshold`) over the block's `RandMetadata`, using the key
+//! shares dealt by the DKG for the current epoch. Running the DKG itself is out of scope here: its
+//! public result is published on chain in `DKGState`, and each validator reads its secret share
+//! from secure storage at the start of the epoch. Randomness is generated when the `RANDOMNESS`
+//! feature is enabled and `DKGState` is for the current epoch; a validator without a usable secret
+//! share then produces no shares of its own and aggregates the shares of its peers.
+
+pub mod rand_manager;
+pub mod rand_store;
+#[cfg(test)]
+mod tests;
+pub mod types;
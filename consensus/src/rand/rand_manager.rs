@@ -0,0 +1,196 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    experimental::buffer_manager::{OrderedBlocks, ResetAck, ResetRequest},
+    network::{IncomingRandGenRequest, NetworkSender},
+    rand::{
+        rand_store::RandStore,
+        types::{RandConfig, RandMessage},
+    },
+};
+use aptos_channels::aptos_channel;
+use aptos_logger::prelude::*;
+use aptos_types::{
+    account_address::AccountAddress,
+    randomness::{RandMetadata, Randomness},
+};
+use futures::{
+    channel::{
+        mpsc::{UnboundedReceiver, UnboundedSender},
+        oneshot,
+    },
+    FutureExt, SinkExt, StreamExt,
+};
+use std::collections::VecDeque;
+use tokio::time::Duration;
+
+const REBROADCAST_INTERVAL_MS: u64 = 1000;
+
+/// Sits between the ordering state computer and the buffer manager: ordered blocks are held back
+/// until the randomness for each of them has been aggregated from the validators' shares, and are
+/// then forwarded in order with the randomness attached.
+pub struct RandManager {
+    config: RandConfig,
+    rand_store: RandStore,
+    network_sender: NetworkSender,
+    block_queue: VecDeque<OrderedBlocks>,
+    outgoing_blocks: UnboundedSender<OrderedBlocks>,
+    buffer_manager_reset_tx: UnboundedSender<ResetRequest>,
+    stop: bool,
+}
+
+impl RandManager {
+    pub fn new(
+        config: RandConfig,
+        network_sender: NetworkSender,
+        outgoing_blocks: UnboundedSender<OrderedBlocks>,
+        buffer_manager_reset_tx: UnboundedSender<ResetRequest>,
+    ) -> Self {
+        Self {
+            rand_store: RandStore::new(config.clone()),
+            config,
+            network_sender,
+            block_queue: VecDeque::new(),
+            outgoing_blocks,
+            buffer_manager_reset_tx,
+            stop: false,
+        }
+    }
+
+    async fn process_incoming_blocks(&mut self, mut blocks: OrderedBlocks) {
+        for block in blocks.ordered_blocks.iter_mut() {
+            let metadata = RandMetadata::new(block.epoch(), block.round());
+            self.rand_store.update_highest_known_round(block.round());
+            if let Some(randomness) = self.rand_store.get_randomness(&metadata) {
+                block.set_randomness(randomness.clone());
+            } else if !self.rand_store.has_self_share(&metadata) {
+                // Validators without a secret share wait for the shares of their peers
+                if let Some(share) = self.config.generate_share(metadata) {
+                    match self.rand_store.add_share(share.clone()) {
+                        Ok(Some(randomness)) => block.set_randomness(randomness),
+                        Ok(None) => (),
+                        Err(e) => warn!(error = ?e, "[RandManager] Failed to add self share"),
+                    }
+                    self.network_sender.broadcast_rand_share(share).await;
+                }
+            }
+        }
+        self.block_queue.push_back(blocks);
+        self.forward_ready_blocks();
+    }
+
+    fn process_incoming_message(&mut self, request: IncomingRandGenRequest) {
+        let IncomingRandGenRequest { req, sender } = request;
+        match req {
+            RandMessage::Share(share) => {
+                if let Err(e) = share.verify(sender, &self.config) {
+                    warn!(error = ?e, remote_peer = sender, "[RandManager] Invalid share");
+                    return;
+                }
+                match self.rand_store.add_share(share) {
+                    Ok(Some(randomness)) => self.process_randomness(randomness),
+                    Ok(None) => (),
+                    Err(e) => {
+                        debug!(error = ?e, remote_peer = sender, "[RandManager] Share dropped")
+                    },
+                }
+            },
+        }
+    }
+
+    fn process_randomness(&mut self, randomness: Randomness) {
+        for blocks in self.block_queue.iter_mut() {
+            for block in blocks.ordered_blocks.iter_mut() {
+                if block.round() == randomness.round() && block.randomness().is_none() {
+                    block.set_randomness(randomness.clone());
+                }
+            }
+        }
+        self.forward_ready_blocks();
+    }
+
+    /// Forwards the longest prefix of the queue whose blocks all have randomness attached.
+    fn forward_ready_blocks(&mut self) {
+        while let Some(blocks) = self.block_queue.front() {
+            if blocks
+                .ordered_blocks
+                .iter()
+                .any(|block| block.randomness().is_none())
+            {
+                break;
+            }
+            let blocks = self.block_queue.pop_front().expect("checked above");
+            if let Some(last) = blocks.ordered_blocks.last() {
+                self.rand_store.clear_up_to(last.round());
+            }
+            if self.outgoing_blocks.unbounded_send(blocks).is_err() {
+                warn!("[RandManager] Buffer manager is gone");
+            }
+        }
+    }
+
+    async fn rebroadcast_pending_shares(&mut self) {
+        if !self.config.has_secret_share() {
+            return;
+        }
+        let pending: Vec<_> = self
+            .block_queue
+            .iter()
+            .flat_map(|blocks| blocks.ordered_blocks.iter())
+            .filter(|block| block.randomness().is_none())
+            .map(|block| RandMetadata::new(block.epoch(), block.round()))
+            .collect();
+        for share in pending
+            .into_iter()
+            .filter_map(|metadata| self.config.generate_share(metadata))
+        {
+            self.network_sender.broadcast_rand_share(share).await;
+        }
+    }
+
+    async fn process_reset(&mut self, request: ResetRequest) {
+        let ResetRequest { tx, stop } = request;
+        info!("[RandManager] Receive reset");
+
+        self.stop = stop;
+        self.block_queue.clear();
+        let (ack_tx, ack_rx) = oneshot::channel();
+        self.buffer_manager_reset_tx
+            .send(ResetRequest { tx: ack_tx, stop })
+            .await
+            .expect("[RandManager] Fail to reset buffer manager");
+        ack_rx
+            .await
+            .expect("[RandManager] Fail to reset buffer manager");
+        tx.send(ResetAck::default()).unwrap();
+        info!("[RandManager] Reset finishes");
+    }
+
+    pub async fn start(
+        mut self,
+        mut incoming_blocks: UnboundedReceiver<OrderedBlocks>,
+        mut incoming_rand_msgs: aptos_channel::Receiver<AccountAddress, IncomingRandGenRequest>,
+        mut reset_rx: UnboundedReceiver<ResetRequest>,
+    ) {
+        info!("RandManager starts.");
+        let mut interval = tokio::time::interval(Duration::from_millis(REBROADCAST_INTERVAL_MS));
+        while !self.stop {
+            ::futures::select! {
+                blocks = incoming_blocks.select_next_some() => {
+                    self.process_incoming_blocks(blocks).await;
+                },
+                request = incoming_rand_msgs.select_next_some() => {
+                    self.process_incoming_message(request);
+                },
+                reset = reset_rx.select_next_some() => {
+                    self.process_reset(reset).await;
+                },
+                _ = interval.tick().fuse() => {
+                    self.rebroadcast_pending_shares().await;
+                },
+            }
+        }
+        info!("RandManager stops.");
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::rand::types::{RandConfig, RandShare};
+use anyhow::ensure;
+use aptos_consensus_types::common::{Author, Round};
+use aptos_types::randomness::{RandMetadata, Randomness};
+use std::collections::{BTreeMap, HashMap};
+
+/// Shares for rounds further than this ahead of the highest ordered round are rejected, which
+/// bounds the memory a faulty validator can make us spend.
+pub const FUTURE_ROUNDS_TO_ACCEPT: Round = 200;
+
+enum RandItem {
+    Pending(HashMap<Author, RandShare>),
+    Decided(Randomness),
+}
+
+/// Collects verified shares per block until enough of them can be aggregated.
+pub struct RandStore {
+    config: RandConfig,
+    highest_known_round: Round,
+    items: BTreeMap<RandMetadata, RandItem>,
+}
+
+impl RandStore {
+    pub fn new(config: RandConfig) -> Self {
+        Self {
+            config,
+            highest_known_round: 0,
+            items: BTreeMap::new(),
+        }
+    }
+
+    pub fn update_highest_known_round(&mut self, round: Round) {
+        self.highest_known_round = std::cmp::max(self.highest_known_round, round);
+    }
+
+    /// Adds an already verified share, returning the randomness the first time the threshold is
+    /// reached for its block.
+    pub fn add_share(&mut self, share: RandShare) -> anyhow::Result<Option<Randomness>> {
+        let metadata = *share.metadata();
+        ensure!(
+            metadata.epoch == self.config.epoch(),
+            "share is for epoch {}, current epoch is {}",
+            metadata.epoch,
+            self.config.epoch()
+        );
+        ensure!(
+            metadata.round <= self.highest_known_round + FUTURE_ROUNDS_TO_ACCEPT,
+            "share for round {} is too far ahead of round {}",
+            metadata.round,
+            self.highest_known_round
+        );
+        let item = self
+            .items
+            .entry(metadata)
+            .or_insert_with(|| RandItem::Pending(HashMap::new()));
+        let RandItem::Pending(shares) = item else {
+            return Ok(None);
+        };
+        shares.insert(*share.author(), share);
+        if shares.len() < self.config.threshold() {
+            return Ok(None);
+        }
+        let randomness = Randomness::new(metadata, self.config.aggregate_shares(shares.values())?);
+        *item = RandItem::Decided(randomness.clone());
+        Ok(Some(randomness))
+    }
+
+    pub fn get_randomness(&self, metadata: &RandMetadata) -> Option<&Randomness> {
+        match self.items.get(metadata) {
+            Some(RandItem::Decided(randomness)) => Some(randomness),
+            _ => None,
+        }
+    }
+
+    /// Returns whether our own share for the block has already been recorded.
+    pub fn has_self_share(&self, metadata: &RandMetadata) -> bool {
+        match self.items.get(metadata) {
+            Some(RandItem::Pending(shares)) => shares.contains_key(&self.config.author()),
+            Some(RandItem::Decided(_)) => true,
+            None => false,
+        }
+    }
+
+    /// Drops everything at or below the given round once its blocks have been handed on.
+    pub fn clear_up_to(&mut self, round: Round) {
+        self.items = self
+            .items
+            .split_off(&RandMetadata::new(self.config.epoch(), round + 1));
+    }
+}
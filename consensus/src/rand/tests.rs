@@ -0,0 +1,264 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::rand::{
+    rand_store::{RandStore, FUTURE_ROUNDS_TO_ACCEPT},
+    types::{RandConfig, RandKeys, RandPublicParams, RandShare},
+};
+use aptos_crypto::bls12381::bls12381_threshold;
+use aptos_types::{
+    epoch_state::EpochState, on_chain_config::DKGState, randomness::RandMetadata,
+    validator_signer::ValidatorSigner, validator_verifier::random_validator_verifier,
+};
+use rand::rngs::OsRng;
+
+const NUM_VALIDATORS: usize = 4;
+const THRESHOLD: usize = 3;
+const EPOCH: u64 = 1;
+
+/// Deals threshold key shares to the validators, returning the on-chain DKG state and the local
+/// keys of every signer.
+fn deal() -> (Vec<ValidatorSigner>, EpochState, DKGState, Vec<RandKeys>) {
+    let (signers, verifier) = random_validator_verifier(NUM_VALIDATORS, None, false);
+    let threshold_keys = bls12381_threshold::deal(&mut OsRng, THRESHOLD, NUM_VALIDATORS).unwrap();
+    let public_shares = threshold_keys
+        .public_key_shares
+        .iter()
+        .map(|share| share.public_key().to_bytes().to_vec())
+        .collect();
+    let keys = signers
+        .iter()
+        .map(|signer| {
+            let index = verifier.address_to_validator_index()[&signer.author()];
+            RandKeys {
+                epoch: EPOCH,
+                secret_share: threshold_keys.secret_key_shares[index]
+                    .private_key()
+                    .to_bytes()
+                    .to_vec(),
+            }
+        })
+        .collect();
+    let dkg_state = DKGState {
+        epoch: EPOCH,
+        threshold: THRESHOLD as u64,
+        public_shares,
+    };
+    let epoch_state = EpochState {
+        epoch: EPOCH,
+        verifier,
+    };
+    (signers, epoch_state, dkg_state, keys)
+}
+
+/// Builds a config per validator.
+fn setup() -> (Vec<ValidatorSigner>, Vec<RandConfig>) {
+    let (signers, epoch_state, dkg_state, keys) = deal();
+    let configs = signers
+        .iter()
+        .zip(keys.iter())
+        .map(|(signer, keys)| {
+            let params = RandPublicParams::new(EPOCH, &dkg_state, &epoch_state.verifier).unwrap();
+            RandConfig::new(signer.author(), params, keys, epoch_state.verifier.clone()).unwrap()
+        })
+        .collect();
+    (signers, configs)
+}
+
+#[test]
+fn test_rand_config_for_epoch() {
+    let (signers, epoch_state, dkg_state, keys) = deal();
+    let author = signers[0].author();
+    let for_epoch = |enabled: bool, dkg_state: Option<&DKGState>| {
+        RandConfig::for_epoch(author, &epoch_state, enabled, dkg_state, || {
+            Ok(keys[0].clone())
+        })
+    };
+    let config = for_epoch(true, Some(&dkg_state)).unwrap();
+    assert_eq!(config.author(), author);
+    assert_eq!(config.threshold(), THRESHOLD);
+
+    // Randomness is disabled, or there is no DKG result for this epoch.
+    assert!(for_epoch(false, Some(&dkg_state)).is_none());
+    assert!(for_epoch(true, None).is_none());
+    let stale = DKGState {
+        epoch: EPOCH - 1,
+        ..dkg_state.clone()
+    };
+    assert!(for_epoch(true, Some(&stale)).is_none());
+    // Malformed on-chain state disables randomness on all validators alike.
+    let malformed = DKGState {
+        public_shares: dkg_state.public_shares[1..].to_vec(),
+        ..dkg_state.clone()
+    };
+    assert!(for_epoch(true, Some(&malformed)).is_none());
+
+    // Nodes outside of the validator set never need keys.
+    let (outsider, _) = random_validator_verifier(1, None, false);
+    assert!(RandConfig::for_epoch(
+        outsider[0].author(),
+        &epoch_state,
+        true,
+        Some(&dkg_state),
+        || unreachable!()
+    )
+    .is_none());
+}
+
+#[test]
+fn test_rand_config_for_epoch_without_usable_keys() {
+    let (signers, epoch_state, dkg_state, keys) = deal();
+    let for_epoch = |load_keys: Box<dyn FnOnce() -> anyhow::Result<RandKeys>>| {
+        RandConfig::for_epoch(
+            signers[0].author(),
+            &epoch_state,
+            true,
+            Some(&dkg_state),
+            load_keys,
+        )
+        .unwrap()
+    };
+    // Missing keys, or a share which doesn't match the published one, still enable randomness
+    // in the epoch, but the validator produces no shares.
+    let without_keys = for_epoch(Box::new(|| anyhow::bail!("randomness keys not found")));
+    let wrong_share = for_epoch(Box::new(move || Ok(keys[1].clone())));
+    for config in [without_keys, wrong_share] {
+        assert!(!config.has_secret_share());
+        assert_eq!(config.threshold(), THRESHOLD);
+        assert!(config.generate_share(RandMetadata::new(EPOCH, 1)).is_none());
+    }
+}
+
+#[test]
+fn test_aggregate_without_secret_share() {
+    let (signers, epoch_state, dkg_state, keys) = deal();
+    let params = RandPublicParams::new(EPOCH, &dkg_state, &epoch_state.verifier).unwrap();
+    let configs: Vec<_> = signers
+        .iter()
+        .zip(keys.iter())
+        .map(|(signer, keys)| {
+            RandConfig::new(
+                signer.author(),
+                params.clone(),
+                keys,
+                epoch_state.verifier.clone(),
+            )
+            .unwrap()
+        })
+        .collect();
+    let config =
+        RandConfig::without_secret_share(signers[0].author(), params, epoch_state.verifier.clone());
+
+    // The shares of the peers are verified and aggregated into the same randomness
+    let metadata = RandMetadata::new(EPOCH, 1);
+    let mut store = RandStore::new(config.clone());
+    let mut peers_store = RandStore::new(configs[1].clone());
+    for (signer, peer) in signers[1..].iter().zip(&configs[1..]) {
+        let share = peer.generate_share(metadata).unwrap();
+        share.verify(signer.author(), &config).unwrap();
+        store.add_share(share.clone()).unwrap();
+        peers_store.add_share(share).unwrap();
+    }
+    assert!(!store.has_self_share(&metadata));
+    assert_eq!(
+        store.get_randomness(&metadata),
+        peers_store.get_randomness(&metadata)
+    );
+    assert!(store.get_randomness(&metadata).is_some());
+}
+
+#[test]
+fn test_any_threshold_subset_yields_same_randomness() {
+    let (_, configs) = setup();
+    let metadata = RandMetadata::new(EPOCH, 5);
+    let shares: Vec<RandShare> = configs
+        .iter()
+        .map(|config| config.generate_share(metadata).unwrap())
+        .collect();
+    for share in &shares {
+        share.verify(*share.author(), &configs[0]).unwrap();
+    }
+
+    let aggregate = |subset: &[usize]| {
+        let mut store = RandStore::new(configs[0].clone());
+        let mut result = None;
+        for i in subset {
+            result = store.add_share(shares[*i].clone()).unwrap();
+        }
+        result.unwrap()
+    };
+    let first = aggregate(&[0, 1, 2]);
+    assert_eq!(first, aggregate(&[1, 2, 3]));
+    assert_eq!(first, aggregate(&[3, 0, 2]));
+    assert_eq!(first.randomness().len(), 32);
+
+    let other_round = RandMetadata::new(EPOCH, 6);
+    let mut store = RandStore::new(configs[0].clone());
+    for config in &configs[..THRESHOLD] {
+        store
+            .add_share(config.generate_share(other_round).unwrap())
+            .unwrap();
+    }
+    assert_ne!(
+        store.get_randomness(&other_round).unwrap().randomness(),
+        first.randomness()
+    );
+}
+
+#[test]
+fn test_share_verification() {
+    let (signers, configs) = setup();
+    let share = configs[0]
+        .generate_share(RandMetadata::new(EPOCH, 1))
+        .unwrap();
+    assert!(share.verify(signers[0].author(), &configs[1]).is_ok());
+    // wrong sender
+    assert!(share.verify(signers[1].author(), &configs[1]).is_err());
+    // share for a different block
+    let other = configs[0]
+        .generate_share(RandMetadata::new(EPOCH, 2))
+        .unwrap();
+    let forged = RandShare::new(
+        signers[0].author(),
+        RandMetadata::new(EPOCH, 1),
+        other.share().to_vec(),
+    );
+    assert!(forged.verify(signers[0].author(), &configs[1]).is_err());
+}
+
+#[test]
+fn test_rand_store() {
+    let (_, configs) = setup();
+    let mut store = RandStore::new(configs[0].clone());
+    let metadata = RandMetadata::new(EPOCH, 1);
+    for config in &configs[..THRESHOLD - 1] {
+        assert!(store
+            .add_share(config.generate_share(metadata).unwrap())
+            .unwrap()
+            .is_none());
+    }
+    assert!(store.get_randomness(&metadata).is_none());
+    let randomness = store
+        .add_share(configs[THRESHOLD - 1].generate_share(metadata).unwrap())
+        .unwrap()
+        .unwrap();
+    // randomness is only reported once
+    assert!(store
+        .add_share(configs[THRESHOLD].generate_share(metadata).unwrap())
+        .unwrap()
+        .is_none());
+    assert_eq!(store.get_randomness(&metadata), Some(&randomness));
+
+    // shares too far in the future are rejected
+    let far = RandMetadata::new(EPOCH, FUTURE_ROUNDS_TO_ACCEPT + 1);
+    assert!(store
+        .add_share(configs[0].generate_share(far).unwrap())
+        .is_err());
+    store.update_highest_known_round(1);
+    assert!(store
+        .add_share(configs[0].generate_share(far).unwrap())
+        .is_ok());
+
+    store.clear_up_to(1);
+    assert!(store.get_randomness(&metadata).is_none());
+}
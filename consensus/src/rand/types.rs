@@ -0,0 +1,352 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, bail, ensure};
+use aptos_consensus_types::common::Author;
+use aptos_crypto::{
+    bls12381::{self, PublicKeyShare, SecretKeyShare, SignatureShare},
+    HashValue,
+};
+use aptos_logger::error;
+use aptos_types::{
+    epoch_state::EpochState, on_chain_config::DKGState, randomness::RandMetadata,
+    validator_verifier::ValidatorVerifier,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt::{Debug, Formatter},
+    sync::Arc,
+};
+
+/// This validator's threshold BLS secret key share dealt by the DKG for one epoch, persisted in
+/// secure storage under `RANDOMNESS_KEYS` as a serialized `bls12381::PrivateKey`. The public key
+/// shares of all validators are published on chain in `DKGState`.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RandKeys {
+    pub epoch: u64,
+    #[serde(with = "serde_bytes")]
+    pub secret_share: Vec<u8>,
+}
+
+/// Public parameters of the randomness generation of an epoch, parsed from the on-chain
+/// `DKGState`. Parsing only depends on on-chain state, so it succeeds or fails on all validators
+/// alike.
+#[derive(Clone)]
+pub struct RandPublicParams {
+    epoch: u64,
+    threshold: usize,
+    public_shares: Vec<PublicKeyShare>,
+}
+
+impl RandPublicParams {
+    pub fn new(
+        epoch: u64,
+        dkg_state: &DKGState,
+        verifier: &ValidatorVerifier,
+    ) -> anyhow::Result<Self> {
+        ensure!(
+            dkg_state.epoch == epoch,
+            "DKG state is for epoch {}, current epoch is {}",
+            dkg_state.epoch,
+            epoch
+        );
+        ensure!(
+            dkg_state.public_shares.len() == verifier.len(),
+            "expected {} public shares, got {}",
+            verifier.len(),
+            dkg_state.public_shares.len()
+        );
+        let threshold = dkg_state.threshold as usize;
+        ensure!(
+            threshold > 0 && threshold <= verifier.len(),
+            "invalid threshold {}",
+            threshold
+        );
+        let public_shares = dkg_state
+            .public_shares
+            .iter()
+            .enumerate()
+            .map(|(player, bytes)| {
+                let public_key = bls12381::PublicKey::try_from(bytes.as_slice())?;
+                public_key.subgroup_check()?;
+                Ok(PublicKeyShare::new(player, public_key))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(Self {
+            epoch,
+            threshold,
+            public_shares,
+        })
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+}
+
+/// Per-epoch configuration of the randomness generation protocol.
+#[derive(Clone)]
+pub struct RandConfig {
+    author: Author,
+    epoch: u64,
+    threshold: usize,
+    /// This validator's secret key share, if it has one. Validators without a share produce no
+    /// shares, but still verify and aggregate the shares of their peers.
+    secret_share: Option<Arc<SecretKeyShare>>,
+    public_shares: Vec<PublicKeyShare>,
+    verifier: ValidatorVerifier,
+}
+
+impl Debug for RandConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RandConfig")
+            .field("author", &self.author)
+            .field("epoch", &self.epoch)
+            .field("threshold", &self.threshold)
+            .field("has_secret_share", &self.secret_share.is_some())
+            .finish()
+    }
+}
+
+impl RandConfig {
+    pub fn new(
+        author: Author,
+        params: RandPublicParams,
+        keys: &RandKeys,
+        verifier: ValidatorVerifier,
+    ) -> anyhow::Result<Self> {
+        let RandPublicParams {
+            epoch,
+            threshold,
+            public_shares,
+        } = params;
+        ensure!(
+            keys.epoch == epoch,
+            "randomness keys are for epoch {}, current epoch is {}",
+            keys.epoch,
+            epoch
+        );
+        let Some(index) = verifier.address_to_validator_index().get(&author).copied() else {
+            bail!("{} is not a validator in epoch {}", author, epoch);
+        };
+        let private_key = bls12381::PrivateKey::try_from(keys.secret_share.as_slice())?;
+        let secret_share = SecretKeyShare::new(index, private_key);
+        ensure!(
+            public_shares[index] == secret_share.public_key_share(),
+            "secret share doesn't match the published public share"
+        );
+        Ok(Self {
+            author,
+            epoch,
+            threshold,
+            secret_share: Some(Arc::new(secret_share)),
+            public_shares,
+            verifier,
+        })
+    }
+
+    /// Builds the config of a validator without a secret key share, which only aggregates the
+    /// shares of its peers.
+    pub fn without_secret_share(
+        author: Author,
+        params: RandPublicParams,
+        verifier: ValidatorVerifier,
+    ) -> Self {
+        let RandPublicParams {
+            epoch,
+            threshold,
+            public_shares,
+        } = params;
+        Self {
+            author,
+            epoch,
+            threshold,
+            secret_share: None,
+            public_shares,
+            verifier,
+        }
+    }
+
+    /// Decides whether the blocks of the epoch carry randomness and, if so, builds the config of
+    /// `author`. The decision only depends on the `RANDOMNESS` feature and the on-chain
+    /// `DKGState`, so all validators agree on it. A validator of the epoch without a usable
+    /// secret share (e.g. the shares weren't handed out yet) logs an error and takes part without
+    /// producing shares, so randomness is aggregated as long as a threshold of its peers has them.
+    pub fn for_epoch(
+        author: Author,
+        epoch_state: &EpochState,
+        randomness_enabled: bool,
+        dkg_state: Option<&DKGState>,
+        load_keys: impl FnOnce() -> anyhow::Result<RandKeys>,
+    ) -> Option<Self> {
+        let epoch = epoch_state.epoch;
+        let dkg_state = dkg_state.filter(|state| randomness_enabled && state.epoch == epoch)?;
+        let params = match RandPublicParams::new(epoch, dkg_state, &epoch_state.verifier) {
+            Ok(params) => params,
+            Err(e) => {
+                error!(
+                    epoch = epoch,
+                    error = ?e,
+                    "[RandConfig] Invalid on-chain DKG state, no randomness in this epoch"
+                );
+                return None;
+            },
+        };
+        if !epoch_state
+            .verifier
+            .address_to_validator_index()
+            .contains_key(&author)
+        {
+            return None;
+        }
+        let verifier = epoch_state.verifier.clone();
+        match load_keys()
+            .and_then(|keys| Self::new(author, params.clone(), &keys, verifier.clone()))
+        {
+            Ok(config) => Some(config),
+            Err(e) => {
+                error!(
+                    epoch = epoch,
+                    error = ?e,
+                    "[RandConfig] No usable key share, only aggregating the shares of peers"
+                );
+                Some(Self::without_secret_share(author, params, verifier))
+            },
+        }
+    }
+
+    pub fn author(&self) -> Author {
+        self.author
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn validator_index(&self, author: &Author) -> Option<usize> {
+        self.verifier
+            .address_to_validator_index()
+            .get(author)
+            .copied()
+    }
+
+    pub fn has_secret_share(&self) -> bool {
+        self.secret_share.is_some()
+    }
+
+    /// Signs the metadata with this validator's secret share, if it has one.
+    pub fn generate_share(&self, metadata: RandMetadata) -> Option<RandShare> {
+        let share = self
+            .secret_share
+            .as_ref()?
+            .sign(&metadata)
+            .expect("Signing randomness metadata must not fail");
+        Some(RandShare::new(
+            self.author,
+            metadata,
+            share.signature().to_bytes().to_vec(),
+        ))
+    }
+
+    /// Interpolates `threshold` verified shares of a block into the threshold signature of its
+    /// metadata, which is unique for the epoch's DKG, and hashes it into 32 bytes of randomness.
+    pub fn aggregate_shares<'a>(
+        &self,
+        shares: impl IntoIterator<Item = &'a RandShare>,
+    ) -> anyhow::Result<Vec<u8>> {
+        let shares = shares
+            .into_iter()
+            .map(|share| {
+                let index = self
+                    .validator_index(share.author())
+                    .ok_or_else(|| anyhow!("{} is not a validator", share.author()))?;
+                Ok(SignatureShare::new(index, share.signature()?))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let signature = SignatureShare::aggregate(self.threshold, &shares)?;
+        Ok(HashValue::sha3_256_of(&signature.to_bytes()).to_vec())
+    }
+}
+
+/// A validator's share of the randomness for one block.
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RandShare {
+    author: Author,
+    metadata: RandMetadata,
+    #[serde(with = "serde_bytes")]
+    share: Vec<u8>,
+}
+
+impl Debug for RandShare {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RandShare")
+            .field("author", &self.author)
+            .field("metadata", &self.metadata)
+            .finish()
+    }
+}
+
+impl RandShare {
+    pub fn new(author: Author, metadata: RandMetadata, share: Vec<u8>) -> Self {
+        Self {
+            author,
+            metadata,
+            share,
+        }
+    }
+
+    pub fn author(&self) -> &Author {
+        &self.author
+    }
+
+    pub fn metadata(&self) -> &RandMetadata {
+        &self.metadata
+    }
+
+    pub fn share(&self) -> &[u8] {
+        &self.share
+    }
+
+    fn signature(&self) -> anyhow::Result<bls12381::Signature> {
+        Ok(bls12381::Signature::try_from(self.share.as_slice())?)
+    }
+
+    pub fn verify(&self, sender: Author, config: &RandConfig) -> anyhow::Result<()> {
+        ensure!(
+            self.author == sender,
+            "Author {} doesn't match sender {}",
+            self.author,
+            sender
+        );
+        ensure!(
+            self.metadata.epoch == config.epoch(),
+            "share is for epoch {}, current epoch is {}",
+            self.metadata.epoch,
+            config.epoch()
+        );
+        let Some(index) = config.validator_index(&self.author) else {
+            bail!("{} is not a validator", self.author);
+        };
+        config.public_shares[index].verify(
+            &self.metadata,
+            &SignatureShare::new(index, self.signature()?),
+        )
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum RandMessage {
+    Share(RandShare),
+}
+
+impl RandMessage {
+    pub fn epoch(&self) -> u64 {
+        match self {
+            RandMessage::Share(share) => share.metadata().epoch,
+        }
+    }
+}
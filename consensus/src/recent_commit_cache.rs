@@ -0,0 +1,57 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_infallible::Mutex;
+use aptos_types::{
+    account_address::AccountAddress,
+    transaction::{SignedTransaction, Transaction},
+};
+use lru::LruCache;
+
+/// Number of senders tracked by the cache, the least recently committed ones are evicted first
+pub const RECENT_COMMIT_CACHE_CAPACITY: usize = 100_000;
+
+/// Keeps the highest committed sequence number of the recently active senders, so that the
+/// proposer can drop the transactions committed since their batches were pulled. The cache
+/// only lags behind the ledger, so it never considers an uncommitted transaction as committed.
+pub struct RecentCommitCache {
+    committed_sequence_numbers: Mutex<LruCache<AccountAddress, u64>>,
+}
+
+impl RecentCommitCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            committed_sequence_numbers: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    /// Records the user transactions of the committed blocks. Must only be given the transactions
+    /// kept in the ledger, as returned by `ExecutedBlock::transactions_to_commit`.
+    pub fn update(&self, committed_txns: &[Transaction]) {
+        let mut committed_sequence_numbers = self.committed_sequence_numbers.lock();
+        for txn in committed_txns {
+            if let Transaction::UserTransaction(txn) = txn {
+                let sequence_number = committed_sequence_numbers
+                    .peek(&txn.sender())
+                    .map_or(txn.sequence_number(), |committed| {
+                        (*committed).max(txn.sequence_number())
+                    });
+                committed_sequence_numbers.put(txn.sender(), sequence_number);
+            }
+        }
+    }
+
+    /// Returns true if a transaction with the same sender and sequence number was committed
+    pub fn is_committed(&self, txn: &SignedTransaction) -> bool {
+        self.committed_sequence_numbers
+            .lock()
+            .peek(&txn.sender())
+            .map_or(false, |committed| txn.sequence_number() <= *committed)
+    }
+}
+
+impl Default for RecentCommitCache {
+    fn default() -> Self {
+        Self::new(RECENT_COMMIT_CACHE_CAPACITY)
+    }
+}
@@ -23,6 +23,7 @@ use crate::{
     pending_votes::VoteReceptionResult,
     persistent_liveness_storage::PersistentLivenessStorage,
     quorum_store::types::BatchMsg,
+    round_telemetry::{self, RoundTimeoutReason},
 };
 use anyhow::{bail, ensure, Context};
 use aptos_channels::aptos_channel;
@@ -268,6 +269,12 @@ impl RoundManager {
             self.new_log(LogEvent::NewRound),
             reason = new_round_event.reason
         );
+        round_telemetry::record_round_start(
+            self.epoch_state.epoch,
+            new_round_event.round,
+            self.proposer_election
+                .get_valid_proposer(new_round_event.round),
+        );
 
         if self
             .proposer_election
@@ -561,6 +568,18 @@ impl RoundManager {
             return Ok(());
         }
 
+        let voted_for_proposal = self.round_state.vote_sent().map_or(false, |vote| {
+            vote.vote_data().proposed().round() == round && !vote.vote_data().is_for_nil()
+        });
+        let timeout_reason = if self.sync_only() {
+            RoundTimeoutReason::SyncOnly
+        } else if voted_for_proposal {
+            RoundTimeoutReason::NoQuorumCertificate
+        } else {
+            RoundTimeoutReason::NoProposal
+        };
+        round_telemetry::record_timeout(self.epoch_state.epoch, round, timeout_reason);
+
         if self.sync_only() {
             self.network
                 .broadcast_sync_info(self.block_store.sync_info())
@@ -599,6 +618,10 @@ impl RoundManager {
                 )
                 .context("[RoundManager] SafetyRules signs 2-chain timeout")?;
             timeout_vote.add_2chain_timeout(timeout, signature);
+            // Persist the timeout, so that it can be sent again after a restart
+            self.storage
+                .save_vote(&timeout_vote)
+                .context("[RoundManager] Fail to persist last timeout vote")?;
         }
 
         self.round_state.record_vote(timeout_vote.clone());
@@ -872,6 +895,7 @@ impl RoundManager {
         {
             return Ok(());
         }
+        round_telemetry::record_vote(self.epoch_state.epoch, round, vote.author());
         let vote_reception_result = self
             .round_state
             .insert_vote(vote, &self.epoch_state.verifier);
@@ -887,6 +911,7 @@ impl RoundManager {
         let round = vote.vote_data().proposed().round();
         match result {
             VoteReceptionResult::NewQuorumCertificate(qc) => {
+                round_telemetry::record_quorum_cert(self.epoch_state.epoch, round);
                 if !vote.is_timeout() {
                     observe_block(
                         qc.certified_block().timestamp_usecs(),
@@ -947,6 +972,16 @@ impl RoundManager {
         if let Err(e) = self.process_new_round_event(new_round_event).await {
             warn!(error = ?e, "[RoundManager] Error during start");
         }
+        // The round timed out before the restart: the timeout may not have reached the others,
+        // send it again instead of waiting for another local timeout.
+        if let Some(timeout_vote) = self.round_state.vote_sent().filter(Vote::is_timeout) {
+            info!(
+                self.new_log(LogEvent::Timeout),
+                "Resending the timeout vote recovered from storage"
+            );
+            let timeout_vote_msg = VoteMsg::new(timeout_vote, self.block_store.sync_info());
+            self.network.broadcast_timeout_vote(timeout_vote_msg).await;
+        }
     }
 
     /// Inspect the current consensus state.
@@ -19,6 +19,7 @@ use crate::{
     network_tests::{NetworkPlayground, TwinId},
     payload_manager::PayloadManager,
     persistent_liveness_storage::RecoveryData,
+    recent_commit_cache::RecentCommitCache,
     round_manager::RoundManager,
     test_utils::{
         consensus_runtime, timed_block_on, MockPayloadManager, MockStateComputer, MockStorage,
@@ -261,6 +262,7 @@ impl NodeSetup {
             PipelineBackpressureConfig::new_no_backoff(),
             ChainHealthBackoffConfig::new_no_backoff(),
             false,
+            Arc::new(RecentCommitCache::default()),
         );
 
         let round_state = Self::create_round_state(time_service);
@@ -1322,6 +1324,37 @@ fn vote_resent_on_timeout() {
     });
 }
 
+#[test]
+/// The timeout vote is persisted, and sent again after a restart.
+fn timeout_vote_resent_on_restart() {
+    let runtime = consensus_runtime();
+    let mut playground = NetworkPlayground::new(runtime.handle().clone());
+    let mut node = NodeSetup::create_nodes(&mut playground, runtime.handle().clone(), 1, None)
+        .pop()
+        .unwrap();
+    let timeout_vote = timed_block_on(&runtime, async {
+        let proposal_msg = node.next_proposal().await;
+        node.round_manager
+            .process_proposal_msg(proposal_msg)
+            .await
+            .unwrap();
+        node.next_vote().await;
+        node.round_manager
+            .process_local_timeout(1)
+            .await
+            .unwrap_err();
+        node.next_vote().await.vote().clone()
+    });
+    assert!(timeout_vote.is_timeout());
+
+    node = node.restart(&mut playground, runtime.handle().clone());
+    timed_block_on(&runtime, async {
+        // Having voted in round 1, the restarted node can't propose again and only resends its timeout
+        let vote_msg = node.next_vote().await;
+        assert_eq!(vote_msg.vote(), &timeout_vote);
+    });
+}
+
 #[test]
 #[ignore] // TODO: this test needs to be fixed!
 fn sync_on_partial_newer_sync_info() {
@@ -0,0 +1,192 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_consensus_types::common::{Author, Round};
+use aptos_infallible::{duration_since_epoch, Mutex};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::{collections::VecDeque, time::Instant};
+
+/// Number of recent rounds kept for inspection
+pub const MAX_RECORDED_ROUNDS: usize = 100;
+
+/// The telemetry of the most recent rounds of this node
+static RECENT_ROUNDS: Lazy<Mutex<RoundTelemetryStore>> =
+    Lazy::new(|| Mutex::new(RoundTelemetryStore::new(MAX_RECORDED_ROUNDS)));
+
+/// Why the local round timer expired
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub enum RoundTimeoutReason {
+    /// No proposal was voted for in the round
+    NoProposal,
+    /// The proposal was voted for, but no quorum certificate was formed in time
+    NoQuorumCertificate,
+    /// The node is in sync only mode and doesn't vote
+    SyncOnly,
+}
+
+/// A vote received for the round, relative to the local start of the round
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct VoteArrival {
+    pub author: Author,
+    pub arrival_ms: u64,
+}
+
+/// What happened in a round, as observed locally
+#[derive(Clone, Debug, Serialize)]
+pub struct RoundTelemetry {
+    pub epoch: u64,
+    pub round: Round,
+    pub proposer: Author,
+    pub start_timestamp_ms: u64,
+    /// Set once the next round starts
+    pub duration_ms: Option<u64>,
+    /// Only the votes sent to this node: regular votes go to the next proposer
+    pub vote_arrivals: Vec<VoteArrival>,
+    /// Set if this node aggregated the quorum certificate of the round
+    pub qc_formation_latency_ms: Option<u64>,
+    pub timeout_reason: Option<RoundTimeoutReason>,
+    #[serde(skip)]
+    started_at: Instant,
+}
+
+impl RoundTelemetry {
+    fn elapsed_ms(&self) -> u64 {
+        self.started_at.elapsed().as_millis() as u64
+    }
+}
+
+/// A bounded history of rounds, oldest first
+struct RoundTelemetryStore {
+    rounds: VecDeque<RoundTelemetry>,
+    max_rounds: usize,
+}
+
+impl RoundTelemetryStore {
+    fn new(max_rounds: usize) -> Self {
+        Self {
+            rounds: VecDeque::with_capacity(max_rounds),
+            max_rounds,
+        }
+    }
+
+    fn start_round(&mut self, epoch: u64, round: Round, proposer: Author) {
+        if let Some(previous) = self.rounds.back_mut() {
+            if previous.duration_ms.is_none() {
+                previous.duration_ms = Some(previous.elapsed_ms());
+            }
+        }
+        if self.rounds.len() == self.max_rounds {
+            self.rounds.pop_front();
+        }
+        self.rounds.push_back(RoundTelemetry {
+            epoch,
+            round,
+            proposer,
+            start_timestamp_ms: duration_since_epoch().as_millis() as u64,
+            duration_ms: None,
+            vote_arrivals: vec![],
+            qc_formation_latency_ms: None,
+            timeout_reason: None,
+            started_at: Instant::now(),
+        });
+    }
+
+    fn get_mut(&mut self, epoch: u64, round: Round) -> Option<&mut RoundTelemetry> {
+        self.rounds
+            .iter_mut()
+            .rev()
+            .find(|telemetry| telemetry.epoch == epoch && telemetry.round == round)
+    }
+
+    fn record_vote(&mut self, epoch: u64, round: Round, author: Author) {
+        if let Some(telemetry) = self.get_mut(epoch, round) {
+            // Only the first vote counts, timeout votes may follow
+            if telemetry
+                .vote_arrivals
+                .iter()
+                .all(|arrival| arrival.author != author)
+            {
+                let arrival_ms = telemetry.elapsed_ms();
+                telemetry
+                    .vote_arrivals
+                    .push(VoteArrival { author, arrival_ms });
+            }
+        }
+    }
+
+    fn record_quorum_cert(&mut self, epoch: u64, round: Round) {
+        if let Some(telemetry) = self.get_mut(epoch, round) {
+            if telemetry.qc_formation_latency_ms.is_none() {
+                telemetry.qc_formation_latency_ms = Some(telemetry.elapsed_ms());
+            }
+        }
+    }
+
+    fn record_timeout(&mut self, epoch: u64, round: Round, reason: RoundTimeoutReason) {
+        if let Some(telemetry) = self.get_mut(epoch, round) {
+            telemetry.timeout_reason.get_or_insert(reason);
+        }
+    }
+}
+
+/// Starts recording a new round, and closes the previous one
+pub(crate) fn record_round_start(epoch: u64, round: Round, proposer: Author) {
+    RECENT_ROUNDS.lock().start_round(epoch, round, proposer);
+}
+
+pub(crate) fn record_vote(epoch: u64, round: Round, author: Author) {
+    RECENT_ROUNDS.lock().record_vote(epoch, round, author);
+}
+
+pub(crate) fn record_quorum_cert(epoch: u64, round: Round) {
+    RECENT_ROUNDS.lock().record_quorum_cert(epoch, round);
+}
+
+pub(crate) fn record_timeout(epoch: u64, round: Round, reason: RoundTimeoutReason) {
+    RECENT_ROUNDS.lock().record_timeout(epoch, round, reason);
+}
+
+/// Returns the telemetry of the recent rounds, oldest first
+pub fn recent_rounds() -> Vec<RoundTelemetry> {
+    RECENT_ROUNDS.lock().rounds.iter().cloned().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_telemetry_store() {
+        let mut store = RoundTelemetryStore::new(10);
+        let (proposer, voter) = (Author::random(), Author::random());
+        store.start_round(1, 1, proposer);
+        store.record_vote(1, 1, voter);
+        store.record_vote(1, 1, voter);
+        store.record_quorum_cert(1, 1);
+        // Updates of unknown rounds are ignored
+        store.record_vote(1, 5, voter);
+        store.start_round(1, 2, proposer);
+        store.record_timeout(1, 2, RoundTimeoutReason::NoProposal);
+        store.record_timeout(1, 2, RoundTimeoutReason::NoQuorumCertificate);
+
+        let (first, second) = (&store.rounds[0], &store.rounds[1]);
+        assert_eq!((first.round, first.proposer), (1, proposer));
+        assert!(first.duration_ms.is_some());
+        assert_eq!(first.vote_arrivals.len(), 1);
+        assert_eq!(first.vote_arrivals[0].author, voter);
+        assert!(first.qc_formation_latency_ms.is_some());
+        assert_eq!(first.timeout_reason, None);
+        assert_eq!(second.round, 2);
+        assert!(second.duration_ms.is_none());
+        assert!(second.vote_arrivals.is_empty());
+        assert_eq!(second.timeout_reason, Some(RoundTimeoutReason::NoProposal));
+
+        // Only the most recent rounds are kept
+        for round in 3..=20 {
+            store.start_round(1, round, proposer);
+        }
+        assert_eq!(store.rounds.len(), 10);
+        assert_eq!(store.rounds.front().unwrap().round, 11);
+    }
+}
@@ -9,6 +9,7 @@ use crate::{
     execution_pipeline::ExecutionPipeline,
     monitor,
     payload_manager::PayloadManager,
+    recent_commit_cache::RecentCommitCache,
     state_replication::{StateComputer, StateComputerCommitCallBackType},
     transaction_deduper::TransactionDeduper,
     transaction_filter::TransactionFilter,
@@ -24,7 +25,8 @@ use aptos_infallible::Mutex;
 use aptos_logger::prelude::*;
 use aptos_types::{
     account_address::AccountAddress, contract_event::ContractEvent, epoch_state::EpochState,
-    ledger_info::LedgerInfoWithSignatures, transaction::Transaction,
+    ledger_info::LedgerInfoWithSignatures, on_chain_config::BlockExecutionLimit,
+    randomness::Randomness, transaction::Transaction,
 };
 use fail::fail_point;
 use futures::{future::BoxFuture, SinkExt, StreamExt};
@@ -62,10 +64,11 @@ pub struct ExecutionProxy {
     write_mutex: AsyncMutex<LogicalTime>,
     payload_manager: Mutex<Option<Arc<PayloadManager>>>,
     transaction_shuffler: Mutex<Option<Arc<dyn TransactionShuffler>>>,
-    maybe_block_gas_limit: Mutex<Option<u64>>,
+    block_execution_limit: Mutex<BlockExecutionLimit>,
     transaction_deduper: Mutex<Option<Arc<dyn TransactionDeduper>>>,
     transaction_filter: TransactionFilter,
     execution_pipeline: ExecutionPipeline,
+    recent_commit_cache: Arc<RecentCommitCache>,
 }
 
 impl ExecutionProxy {
@@ -75,6 +78,7 @@ impl ExecutionProxy {
         state_sync_notifier: Arc<dyn ConsensusNotificationSender>,
         handle: &tokio::runtime::Handle,
         txn_filter: TransactionFilter,
+        recent_commit_cache: Arc<RecentCommitCache>,
     ) -> Self {
         let (tx, mut rx) =
             aptos_channels::new::<NotificationType>(10, &counters::PENDING_STATE_SYNC_NOTIFICATION);
@@ -101,10 +105,11 @@ impl ExecutionProxy {
             write_mutex: AsyncMutex::new(LogicalTime::new(0, 0)),
             payload_manager: Mutex::new(None),
             transaction_shuffler: Mutex::new(None),
-            maybe_block_gas_limit: Mutex::new(None),
+            block_execution_limit: Mutex::new(BlockExecutionLimit::no_limit()),
             transaction_deduper: Mutex::new(None),
             transaction_filter: txn_filter,
             execution_pipeline,
+            recent_commit_cache,
         }
     }
 }
@@ -118,6 +123,7 @@ impl StateComputer for ExecutionProxy {
         block: &Block,
         // The parent block id.
         parent_block_id: HashValue,
+        randomness: Option<Randomness>,
     ) -> StateComputeResultFut {
         let block_id = block.id();
         debug!(
@@ -141,14 +147,15 @@ impl StateComputer for ExecutionProxy {
         let deduped_txns = txn_deduper.dedup(filtered_txns);
         let shuffled_txns = txn_shuffler.shuffle(deduped_txns);
 
-        let maybe_block_gas_limit = *self.maybe_block_gas_limit.lock();
+        let block_execution_limit = *self.block_execution_limit.lock();
 
         // TODO: figure out error handling for the prologue txn
         let timestamp = block.timestamp_usecs();
         let transactions_to_execute = block.transactions_to_execute(
             &self.validators.lock(),
             shuffled_txns.clone(),
-            maybe_block_gas_limit,
+            block_execution_limit,
+            randomness,
         );
 
         let fut = self
@@ -157,7 +164,7 @@ impl StateComputer for ExecutionProxy {
                 block_id,
                 parent_block_id,
                 transactions_to_execute,
-                maybe_block_gas_limit,
+                block_execution_limit,
             )
             .await;
 
@@ -206,7 +213,7 @@ impl StateComputer for ExecutionProxy {
         let txn_deduper = self.transaction_deduper.lock().as_ref().unwrap().clone();
         let txn_shuffler = self.transaction_shuffler.lock().as_ref().unwrap().clone();
 
-        let block_gas_limit = *self.maybe_block_gas_limit.lock();
+        let block_execution_limit = *self.block_execution_limit.lock();
 
         for block in blocks {
             block_ids.push(block.id());
@@ -225,7 +232,7 @@ impl StateComputer for ExecutionProxy {
             txns.extend(block.transactions_to_commit(
                 &self.validators.lock(),
                 shuffled_txns,
-                block_gas_limit,
+                block_execution_limit,
             ));
             reconfig_events.extend(block.reconfig_event());
         }
@@ -242,6 +249,9 @@ impl StateComputer for ExecutionProxy {
             .await
         )
         .expect("spawn_blocking failed");
+        // `transactions_to_commit` only returns the transactions with a Keep status, the retried
+        // and discarded ones didn't consume their sequence number and may still be committed.
+        self.recent_commit_cache.update(&txns);
 
         let blocks = blocks.to_vec();
         let wrapped_callback = move || {
@@ -319,7 +329,7 @@ impl StateComputer for ExecutionProxy {
         epoch_state: &EpochState,
         payload_manager: Arc<PayloadManager>,
         transaction_shuffler: Arc<dyn TransactionShuffler>,
-        block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
         transaction_deduper: Arc<dyn TransactionDeduper>,
     ) {
         *self.validators.lock() = epoch_state
@@ -330,7 +340,7 @@ impl StateComputer for ExecutionProxy {
         self.transaction_shuffler
             .lock()
             .replace(transaction_shuffler);
-        *self.maybe_block_gas_limit.lock() = block_gas_limit;
+        *self.block_execution_limit.lock() = block_execution_limit;
         self.transaction_deduper.lock().replace(transaction_deduper);
     }
 
@@ -377,7 +387,7 @@ async fn test_commit_sync_race() {
             &self,
             _block: ExecutableBlock,
             _parent_block_id: HashValue,
-            _maybe_block_gas_limit: Option<u64>,
+            _block_execution_limit: BlockExecutionLimit,
         ) -> ExecutorResult<StateComputeResult> {
             Ok(StateComputeResult::new_dummy())
         }
@@ -386,7 +396,7 @@ async fn test_commit_sync_race() {
             &self,
             _block: ExecutableBlock,
             _parent_block_id: HashValue,
-            _maybe_block_gas_limit: Option<u64>,
+            _block_execution_limit: BlockExecutionLimit,
         ) -> ExecutorResult<StateCheckpointOutput> {
             todo!()
         }
@@ -472,12 +482,13 @@ async fn test_commit_sync_race() {
         recorded_commit.clone(),
         &tokio::runtime::Handle::current(),
         TransactionFilter::new(Filter::empty()),
+        Arc::new(RecentCommitCache::default()),
     );
     executor.new_epoch(
         &EpochState::empty(),
         Arc::new(PayloadManager::DirectMempool),
         create_transaction_shuffler(TransactionShufflerType::NoShuffling),
-        None,
+        BlockExecutionLimit::no_limit(),
         create_transaction_deduper(TransactionDeduperType::NoDedup),
     );
     executor
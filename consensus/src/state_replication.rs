@@ -17,7 +17,10 @@ use aptos_consensus_types::{
 };
 use aptos_crypto::HashValue;
 use aptos_executor_types::{ExecutorResult, StateComputeResult};
-use aptos_types::{epoch_state::EpochState, ledger_info::LedgerInfoWithSignatures};
+use aptos_types::{
+    epoch_state::EpochState, ledger_info::LedgerInfoWithSignatures,
+    on_chain_config::BlockExecutionLimit, randomness::Randomness,
+};
 use futures::future::BoxFuture;
 use std::{sync::Arc, time::Duration};
 
@@ -58,7 +61,9 @@ pub trait StateComputer: Send + Sync {
         // The parent block root hash.
         parent_block_id: HashValue,
     ) -> ExecutorResult<StateComputeResult> {
-        self.schedule_compute(block, parent_block_id).await.await
+        self.schedule_compute(block, parent_block_id, None)
+            .await
+            .await
     }
 
     async fn schedule_compute(
@@ -67,6 +72,8 @@ pub trait StateComputer: Send + Sync {
         _block: &Block,
         // The parent block root hash.
         _parent_block_id: HashValue,
+        // The randomness of the block, if it was produced with randomness.
+        _randomness: Option<Randomness>,
     ) -> StateComputeResultFut {
         unimplemented!("This state computer does not support scheduling");
     }
@@ -91,7 +98,7 @@ pub trait StateComputer: Send + Sync {
         epoch_state: &EpochState,
         payload_manager: Arc<PayloadManager>,
         transaction_shuffler: Arc<dyn TransactionShuffler>,
-        block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
         transaction_deduper: Arc<dyn TransactionDeduper>,
     );
 
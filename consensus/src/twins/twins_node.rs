@@ -11,6 +11,7 @@ use crate::{
     network_tests::{NetworkPlayground, TwinId},
     payload_manager::PayloadManager,
     quorum_store::quorum_store_db::MockQuorumStoreDB,
+    recent_commit_cache::RecentCommitCache,
     test_utils::{MockStateComputer, MockStorage},
     util::time_service::ClockTimeService,
 };
@@ -23,7 +24,7 @@ use aptos_config::{
 };
 use aptos_consensus_types::common::{Author, Round};
 use aptos_event_notifications::{ReconfigNotification, ReconfigNotificationListener};
-use aptos_mempool::mocks::MockSharedMempool;
+use aptos_mempool::{mocks::MockSharedMempool, BackPressureStatus};
 use aptos_network::{
     application::interface::{NetworkClient, NetworkServiceEvents},
     peer_manager::{conn_notifs_channel, ConnectionRequestSender, PeerManagerRequestSender},
@@ -152,12 +153,15 @@ impl SMRNode {
             consensus_network_client,
             timeout_sender,
             quorum_store_to_mempool_sender,
+            BackPressureStatus::default(),
             state_computer.clone(),
             storage.clone(),
             quorum_store_storage,
             reconfig_listener,
             bounded_executor,
             aptos_time_service::TimeService::real(),
+            None,
+            Arc::new(RecentCommitCache::default()),
         );
         let (network_task, network_receiver) =
             NetworkTask::new(network_service_events, self_receiver);
@@ -2,18 +2,20 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::server::utils::reply_with_status;
-use aptos_config::config::NodeConfig;
+use aptos_config::{config::NodeConfig, network_id::NetworkId};
 use aptos_consensus::{
     persistent_liveness_storage::StorageWriteProxy, quorum_store::quorum_store_db::QuorumStoreDB,
 };
 use aptos_infallible::RwLock;
 use aptos_logger::info;
+use aptos_network::peer_manager::deny_list::DenyList;
 use aptos_storage_interface::DbReaderWriter;
 use hyper::{
     service::{make_service_fn, service_fn},
     Body, Request, Response, Server, StatusCode,
 };
 use std::{
+    collections::HashMap,
     convert::Infallible,
     net::{SocketAddr, ToSocketAddrs},
     sync::Arc,
@@ -21,8 +23,10 @@ use std::{
 use tokio::runtime::Runtime;
 
 mod consensus;
+mod network;
 #[cfg(target_os = "linux")]
 mod profiling;
+mod storage;
 #[cfg(target_os = "linux")]
 mod thread_dump;
 mod utils;
@@ -32,6 +36,7 @@ pub struct Context {
     aptos_db: RwLock<Option<Arc<DbReaderWriter>>>,
     consensus_db: RwLock<Option<Arc<StorageWriteProxy>>>,
     quorum_store_db: RwLock<Option<Arc<QuorumStoreDB>>>,
+    network_deny_lists: RwLock<HashMap<NetworkId, DenyList>>,
 }
 
 impl Context {
@@ -47,6 +52,10 @@ impl Context {
         *self.consensus_db.write() = Some(consensus_db);
         *self.quorum_store_db.write() = Some(quorum_store_db);
     }
+
+    fn set_network_deny_lists(&self, network_deny_lists: HashMap<NetworkId, DenyList>) {
+        *self.network_deny_lists.write() = network_deny_lists;
+    }
 }
 
 pub struct AdminService {
@@ -102,6 +111,10 @@ impl AdminService {
             .set_consensus_dbs(consensus_db, quorum_store_db)
     }
 
+    pub fn set_network_deny_lists(&self, network_deny_lists: HashMap<NetworkId, DenyList>) {
+        self.context.set_network_deny_lists(network_deny_lists)
+    }
+
     fn start(&self, address: SocketAddr, enabled: bool) {
         let context = self.context.clone();
         self.runtime.spawn(async move {
@@ -175,6 +188,25 @@ impl AdminService {
                     ))
                 }
             },
+            (hyper::Method::GET, "/network/deny_list") => {
+                let deny_lists = context.network_deny_lists.read().clone();
+                network::handle_get_deny_list_request(req, deny_lists).await
+            },
+            (hyper::Method::POST, "/network/deny_list") => {
+                let deny_lists = context.network_deny_lists.read().clone();
+                network::handle_update_deny_list_request(req, deny_lists).await
+            },
+            (hyper::Method::POST, "/debug/storage/pruner") => {
+                let aptos_db = context.aptos_db.read().clone();
+                if let Some(aptos_db) = aptos_db {
+                    storage::handle_update_pruner_config_request(req, aptos_db).await
+                } else {
+                    Ok(reply_with_status(
+                        StatusCode::NOT_FOUND,
+                        "AptosDB is not available.",
+                    ))
+                }
+            },
             _ => Ok(reply_with_status(StatusCode::NOT_FOUND, "Not found.")),
         }
     }
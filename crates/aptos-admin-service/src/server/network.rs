@@ -0,0 +1,82 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::server::utils::reply_with_status;
+use aptos_config::{config::DenyListConfig, network_id::NetworkId};
+use aptos_logger::info;
+use aptos_network::peer_manager::deny_list::DenyList;
+use hyper::{Body, Request, Response, StatusCode};
+use std::collections::HashMap;
+
+/// Returns the deny list of the network given by the "network" query parameter (as YAML)
+pub async fn handle_get_deny_list_request(
+    req: Request<Body>,
+    deny_lists: HashMap<NetworkId, DenyList>,
+) -> hyper::Result<Response<Body>> {
+    let deny_list = match get_deny_list(&req, &deny_lists) {
+        Ok(deny_list) => deny_list,
+        Err(response) => return Ok(response),
+    };
+
+    match serde_yaml::to_string(&deny_list.get_config()) {
+        Ok(deny_list_config) => Ok(reply_with_status(StatusCode::OK, deny_list_config)),
+        Err(e) => Ok(reply_with_status(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            e.to_string(),
+        )),
+    }
+}
+
+/// Replaces the deny list of the network given by the "network" query parameter with the deny
+/// list in the request body (in the YAML format of the node config). The connections with the
+/// newly denied peers are closed.
+pub async fn handle_update_deny_list_request(
+    req: Request<Body>,
+    deny_lists: HashMap<NetworkId, DenyList>,
+) -> hyper::Result<Response<Body>> {
+    let deny_list = match get_deny_list(&req, &deny_lists) {
+        Ok(deny_list) => deny_list,
+        Err(response) => return Ok(response),
+    };
+
+    let body = hyper::body::to_bytes(req.into_body()).await?;
+    let deny_list_config: DenyListConfig = match serde_yaml::from_slice(&body) {
+        Ok(deny_list_config) => deny_list_config,
+        Err(e) => return Ok(reply_with_status(StatusCode::BAD_REQUEST, e.to_string())),
+    };
+
+    info!(
+        "Updating the network deny list: {} denied peers, {} denied IP ranges.",
+        deny_list_config.denied_peers.len(),
+        deny_list_config.denied_ip_ranges.len()
+    );
+    deny_list.update(deny_list_config);
+    Ok(reply_with_status(StatusCode::OK, "Deny list updated."))
+}
+
+fn get_deny_list(
+    req: &Request<Body>,
+    deny_lists: &HashMap<NetworkId, DenyList>,
+) -> Result<DenyList, Response<Body>> {
+    let query = req.uri().query().unwrap_or("");
+    let query_pairs: HashMap<_, _> = url::form_urlencoded::parse(query.as_bytes()).collect();
+
+    let network_id: NetworkId = match query_pairs.get("network") {
+        Some(val) => val
+            .parse()
+            .map_err(|err: &str| reply_with_status(StatusCode::BAD_REQUEST, err))?,
+        None => {
+            return Err(reply_with_status(
+                StatusCode::BAD_REQUEST,
+                "The network must be given, e.g., network=public.",
+            ))
+        },
+    };
+
+    deny_lists.get(&network_id).cloned().ok_or_else(|| {
+        reply_with_status(
+            StatusCode::NOT_FOUND,
+            format!("Network {} is not available.", network_id),
+        )
+    })
+}
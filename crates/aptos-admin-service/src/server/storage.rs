@@ -0,0 +1,67 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::server::utils::reply_with_status;
+use aptos_logger::info;
+use aptos_storage_interface::{DbReaderWriter, PrunerType};
+use hyper::{Body, Request, Response, StatusCode};
+use std::{borrow::Cow, collections::HashMap, str::FromStr, sync::Arc};
+
+/// Updates the prune window and the batch size of a pruner without restarting the node, e.g.,
+/// pruner=ledger&prune_window=100000000&batch_size=5000. The pruner is one of ledger (which also
+/// covers the state kv pruner), state_merkle and epoch_snapshot. Windows below the minimum needed
+/// to serve state sync are rejected.
+pub async fn handle_update_pruner_config_request(
+    req: Request<Body>,
+    aptos_db: Arc<DbReaderWriter>,
+) -> hyper::Result<Response<Body>> {
+    let query = req.uri().query().unwrap_or("");
+    let query_pairs: HashMap<_, _> = url::form_urlencoded::parse(query.as_bytes()).collect();
+
+    let pruner_type: PrunerType = match parse_query_param(&query_pairs, "pruner") {
+        Ok(pruner_type) => pruner_type,
+        Err(response) => return Ok(response),
+    };
+    let prune_window: u64 = match parse_query_param(&query_pairs, "prune_window") {
+        Ok(prune_window) => prune_window,
+        Err(response) => return Ok(response),
+    };
+    let batch_size: usize = match parse_query_param(&query_pairs, "batch_size") {
+        Ok(batch_size) => batch_size,
+        Err(response) => return Ok(response),
+    };
+
+    info!(
+        "Updating the {:?} pruner config: prune_window {}, batch_size {}.",
+        pruner_type, prune_window, batch_size
+    );
+    match aptos_db
+        .writer
+        .update_pruner_config(pruner_type, prune_window, batch_size)
+    {
+        Ok(()) => Ok(reply_with_status(StatusCode::OK, "Pruner config updated.")),
+        Err(e) => Ok(reply_with_status(StatusCode::BAD_REQUEST, e.to_string())),
+    }
+}
+
+fn parse_query_param<T>(
+    query_pairs: &HashMap<Cow<'_, str>, Cow<'_, str>>,
+    name: &str,
+) -> Result<T, Response<Body>>
+where
+    T: FromStr,
+    T::Err: ToString,
+{
+    match query_pairs.get(name) {
+        Some(val) => val.parse().map_err(|err: T::Err| {
+            reply_with_status(
+                StatusCode::BAD_REQUEST,
+                format!("Invalid {}: {}", name, err.to_string()),
+            )
+        }),
+        None => Err(reply_with_status(
+            StatusCode::BAD_REQUEST,
+            format!("The {} must be given.", name),
+        )),
+    }
+}
@@ -0,0 +1,318 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module provides APIs for threshold BLS signatures [^Bold03] on top of the BLS12-381
+//! signatures in this crate.
+//!
+//! A secret key `s` is Shamir-shared among `n` players with a random polynomial `f` of degree
+//! `t - 1` such that `f(0) = s`. Player `i` receives the secret key share `f(i + 1)`, which is a
+//! regular `bls12381::PrivateKey`: signature shares are computed and verified exactly like
+//! individual BLS signatures. Any `t` signature shares from distinct players are then interpolated
+//! at zero into the signature of `s`, which verifies as a normal `bls12381::Signature` under the
+//! threshold public key `s * g1`.
+//!
+//! WARNING: Whoever calls `deal` learns the secret key `s`. Dealing is therefore only meant for
+//! tests and trusted setups; a distributed key generation (DKG) protocol yields shares of this
+//! same form without any party learning `s`.
+//!
+//! Since the public key shares are derived from the secret key shares by the dealer, they do not
+//! come with proofs-of-possession: callers must obtain them from a trusted dealer or DKG transcript.
+//!
+//! [^Bold03]: Threshold Signatures, Multisignatures and Blind Signatures Based on the Gap-Diffie-Hellman-Group Signature Scheme; by Boldyreva, Alexandra; in PKC 2003; 2002
+
+use crate::{
+    bls12381::{PrivateKey, PublicKey, Signature},
+    hash::CryptoHash,
+    traits::Signature as _,
+    CryptoMaterialError, SigningKey, Uniform,
+};
+use anyhow::{anyhow, ensure, Result};
+use ark_bls12_381::{Fr, G2Affine, G2Projective};
+use ark_ec::CurveGroup;
+use ark_ff::{BigInteger, Field, One, PrimeField, Zero};
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use rand::{CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeSet, convert::TryFrom};
+
+/// The share of the threshold secret key held by a single player
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SecretKeyShare {
+    player: usize,
+    private_key: PrivateKey,
+}
+
+/// The public key of a player's secret key share, used to verify the player's signature shares
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct PublicKeyShare {
+    player: usize,
+    public_key: PublicKey,
+}
+
+/// A signature share computed by a single player with its secret key share
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SignatureShare {
+    player: usize,
+    signature: Signature,
+}
+
+/// The output of dealing a secret key among `num_players` players with a given threshold
+#[derive(Debug)]
+pub struct ThresholdKeys {
+    /// The number of signature shares needed to produce a signature
+    pub threshold: usize,
+    /// The public key that aggregated signatures verify under
+    pub public_key: PublicKey,
+    /// The public key shares of every player, indexed by player
+    pub public_key_shares: Vec<PublicKeyShare>,
+    /// The secret key shares of every player, indexed by player
+    pub secret_key_shares: Vec<SecretKeyShare>,
+}
+
+/// Shares a fresh random secret key among `num_players` players, such that any `threshold` of
+/// them can sign.
+pub fn deal<R>(rng: &mut R, threshold: usize, num_players: usize) -> Result<ThresholdKeys>
+where
+    R: RngCore + CryptoRng,
+{
+    let secret_key = PrivateKey::generate(rng);
+    deal_with_secret_key(rng, &secret_key, threshold, num_players)
+}
+
+/// Shares an existing secret key among `num_players` players, such that any `threshold` of them
+/// can sign on its behalf.
+pub fn deal_with_secret_key<R>(
+    rng: &mut R,
+    secret_key: &PrivateKey,
+    threshold: usize,
+    num_players: usize,
+) -> Result<ThresholdKeys>
+where
+    R: RngCore + CryptoRng,
+{
+    let coefficients = std::iter::once(to_scalar(secret_key))
+        .chain((1..threshold).map(|_| to_scalar(&PrivateKey::generate(rng))))
+        .collect();
+    deal_with_coefficients(coefficients, num_players)
+}
+
+/// Shares `coefficients[0]` using the polynomial with the given coefficients, lowest degree first.
+pub(crate) fn deal_with_coefficients(
+    coefficients: Vec<Fr>,
+    num_players: usize,
+) -> Result<ThresholdKeys> {
+    let threshold = coefficients.len();
+    ensure!(threshold > 0, "threshold must be positive");
+    ensure!(
+        threshold <= num_players,
+        "threshold {} exceeds the number of players {}",
+        threshold,
+        num_players
+    );
+
+    let secret_key = from_scalar(&coefficients[0])?;
+    let secret_key_shares = (0..num_players)
+        .map(|player| {
+            // Horner's rule at x = player + 1
+            let x = evaluation_point(player);
+            let share = coefficients
+                .iter()
+                .rev()
+                .fold(Fr::zero(), |acc, coefficient| acc * x + coefficient);
+            Ok(SecretKeyShare {
+                player,
+                private_key: from_scalar(&share)?,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let public_key_shares = secret_key_shares
+        .iter()
+        .map(SecretKeyShare::public_key_share)
+        .collect();
+
+    Ok(ThresholdKeys {
+        threshold,
+        public_key: PublicKey::from(&secret_key),
+        public_key_shares,
+        secret_key_shares,
+    })
+}
+
+impl SecretKeyShare {
+    /// Creates the secret key share of a player, e.g., from a DKG transcript.
+    pub fn new(player: usize, private_key: PrivateKey) -> Self {
+        Self {
+            player,
+            private_key,
+        }
+    }
+
+    /// The index of the player holding this share
+    pub fn player(&self) -> usize {
+        self.player
+    }
+
+    /// The secret key share itself
+    pub fn private_key(&self) -> &PrivateKey {
+        &self.private_key
+    }
+
+    /// Derives the public key share that this player's signature shares verify under.
+    pub fn public_key_share(&self) -> PublicKeyShare {
+        PublicKeyShare {
+            player: self.player,
+            public_key: PublicKey::from(&self.private_key),
+        }
+    }
+
+    /// Computes this player's signature share on a message.
+    pub fn sign<T: CryptoHash + Serialize>(
+        &self,
+        message: &T,
+    ) -> Result<SignatureShare, CryptoMaterialError> {
+        Ok(SignatureShare {
+            player: self.player,
+            signature: self.private_key.sign(message)?,
+        })
+    }
+
+    /// Computes this player's signature share on arbitrary bytes.
+    #[cfg(any(test, feature = "fuzzing"))]
+    pub fn sign_arbitrary_message(&self, message: &[u8]) -> SignatureShare {
+        SignatureShare {
+            player: self.player,
+            signature: self.private_key.sign_arbitrary_message(message),
+        }
+    }
+}
+
+impl PublicKeyShare {
+    /// Creates the public key share of a player, e.g., from a DKG transcript.
+    pub fn new(player: usize, public_key: PublicKey) -> Self {
+        Self { player, public_key }
+    }
+
+    /// The index of the player holding the matching secret key share
+    pub fn player(&self) -> usize {
+        self.player
+    }
+
+    /// The public key share itself
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    /// Verifies a signature share on a message, including that it comes from this player.
+    pub fn verify<T: CryptoHash + Serialize>(
+        &self,
+        message: &T,
+        share: &SignatureShare,
+    ) -> Result<()> {
+        self.ensure_same_player(share)?;
+        share.signature.verify(message, &self.public_key)
+    }
+
+    /// Verifies a signature share on arbitrary bytes, including that it comes from this player.
+    pub fn verify_arbitrary_msg(&self, message: &[u8], share: &SignatureShare) -> Result<()> {
+        self.ensure_same_player(share)?;
+        share
+            .signature
+            .verify_arbitrary_msg(message, &self.public_key)
+    }
+
+    fn ensure_same_player(&self, share: &SignatureShare) -> Result<()> {
+        ensure!(
+            self.player == share.player,
+            "signature share from player {} checked against the public key share of player {}",
+            share.player,
+            self.player
+        );
+        Ok(())
+    }
+}
+
+impl SignatureShare {
+    /// Creates the signature share of a player, e.g., when received over the network.
+    pub fn new(player: usize, signature: Signature) -> Self {
+        Self { player, signature }
+    }
+
+    /// The index of the player that computed this share
+    pub fn player(&self) -> usize {
+        self.player
+    }
+
+    /// The signature share itself
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    /// Interpolates the first `threshold` signature shares into a signature under the threshold
+    /// public key.
+    ///
+    /// WARNING: This function assumes the shares have been verified via `PublicKeyShare::verify`.
+    /// Otherwise, a single bad share yields a signature that fails verification.
+    pub fn aggregate(threshold: usize, shares: &[SignatureShare]) -> Result<Signature> {
+        ensure!(threshold > 0, "threshold must be positive");
+        ensure!(
+            shares.len() >= threshold,
+            "{} signature shares are not enough to reach the threshold of {}",
+            shares.len(),
+            threshold
+        );
+        let shares = &shares[..threshold];
+        let players: BTreeSet<_> = shares.iter().map(|share| share.player).collect();
+        ensure!(
+            players.len() == threshold,
+            "signature shares must come from distinct players"
+        );
+
+        let xs: Vec<Fr> = shares
+            .iter()
+            .map(|share| evaluation_point(share.player))
+            .collect();
+        let mut aggregated = G2Projective::zero();
+        for (i, share) in shares.iter().enumerate() {
+            let sigma = G2Affine::deserialize_compressed(&share.signature.to_bytes()[..])
+                .map_err(|e| anyhow!("{:?}", e))?;
+            aggregated += sigma * lagrange_coefficient_at_zero(&xs, i);
+        }
+
+        let mut bytes = vec![];
+        aggregated
+            .into_affine()
+            .serialize_compressed(&mut bytes)
+            .map_err(|e| anyhow!("{:?}", e))?;
+        Ok(Signature::try_from(bytes.as_slice())?)
+    }
+}
+
+/// Players are evaluated at `player + 1`, since the secret itself is the evaluation at zero.
+fn evaluation_point(player: usize) -> Fr {
+    Fr::from(player as u64 + 1)
+}
+
+/// Computes `prod_{j != i} x_j / (x_j - x_i)`.
+fn lagrange_coefficient_at_zero(xs: &[Fr], i: usize) -> Fr {
+    let (numerator, denominator) = xs
+        .iter()
+        .enumerate()
+        .filter(|(j, _)| *j != i)
+        .fold((Fr::one(), Fr::one()), |(num, den), (_, x_j)| {
+            (num * x_j, den * (*x_j - xs[i]))
+        });
+    // The evaluation points are distinct, so the denominator is non-zero.
+    numerator
+        * denominator
+            .inverse()
+            .expect("evaluation points should be distinct")
+}
+
+fn to_scalar(private_key: &PrivateKey) -> Fr {
+    Fr::from_be_bytes_mod_order(&private_key.to_bytes())
+}
+
+fn from_scalar(scalar: &Fr) -> Result<PrivateKey> {
+    PrivateKey::try_from(scalar.into_bigint().to_bytes_be().as_slice())
+        .map_err(|_| anyhow!("secret key or secret key share is zero"))
+}
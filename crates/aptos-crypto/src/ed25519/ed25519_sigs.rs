@@ -8,9 +8,10 @@ use crate::{
     hash::CryptoHash,
     traits::*,
 };
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, ensure, Result};
 use aptos_crypto_derive::{DeserializeKey, SerializeKey};
 use core::convert::TryFrom;
+use curve25519_dalek::edwards::CompressedEdwardsY;
 use serde::Serialize;
 use std::{cmp::Ordering, fmt};
 
@@ -71,6 +72,48 @@ impl Ed25519Signature {
         Ok(())
     }
 
+    /// Verifies a batch of signatures, each on its own message under its own public key, which is
+    /// notably cheaper than verifying them one by one.
+    ///
+    /// Every signature goes through the same scalar malleability and small subgroup checks as in
+    /// [Ed25519Signature::verify_arbitrary_msg][Ed25519Signature::verify_arbitrary_msg], and then
+    /// the batch is checked with a random linear combination of the verification equations.
+    ///
+    /// WARNING: Because of the random coefficients, a signature deliberately built from points
+    /// with a small torsion component can be accepted here while it is rejected by individual
+    /// verification. This function should therefore only be used where the outcome is checked
+    /// again later (e.g., mempool admission), and never where nodes must agree on the outcome
+    /// (e.g., transaction execution).
+    ///
+    /// If the batch fails, the caller can verify the signatures individually to find the bad ones.
+    pub fn batch_verify_arbitrary_msgs(
+        messages: &[&[u8]],
+        public_keys: &[&Ed25519PublicKey],
+        signatures: &[&Ed25519Signature],
+    ) -> Result<()> {
+        ensure!(
+            messages.len() == signatures.len() && public_keys.len() == signatures.len(),
+            "expected as many messages and public keys as signatures"
+        );
+        for (public_key, signature) in public_keys.iter().zip(signatures.iter()) {
+            let bytes = signature.to_bytes();
+            Ed25519Signature::check_s_malleability(&bytes)?;
+            ensure!(
+                !is_small_order(&bytes[..32]),
+                "signature R component is of small order"
+            );
+            ensure!(
+                !is_small_order(&public_key.to_bytes()),
+                "public key is of small order"
+            );
+        }
+
+        let dalek_signatures: Vec<_> = signatures.iter().map(|signature| signature.0).collect();
+        let dalek_public_keys: Vec<_> = public_keys.iter().map(|public_key| public_key.0).collect();
+        ed25519_dalek::verify_batch(messages, &dalek_signatures, &dalek_public_keys)
+            .map_err(|e| anyhow!("{}", e))
+    }
+
     /// Check if S < L to capture invalid signatures.
     fn check_s_lt_l(s: &[u8]) -> bool {
         for i in (0..32).rev() {
@@ -135,6 +178,16 @@ impl Signature for Ed25519Signature {
     }
 }
 
+/// Returns whether `point` encodes a point in a small subgroup. Points that fail to decode are
+/// left for the verification itself to reject.
+fn is_small_order(point: &[u8]) -> bool {
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(point);
+    CompressedEdwardsY(bytes)
+        .decompress()
+        .map_or(false, |point| point.is_small_order())
+}
+
 impl Length for Ed25519Signature {
     fn length(&self) -> usize {
         ED25519_SIGNATURE_LENGTH
@@ -0,0 +1,153 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    bls12381::{
+        bls12381_threshold::{deal, deal_with_coefficients, deal_with_secret_key},
+        PrivateKey, PublicKey, SignatureShare,
+    },
+    test_utils::TestAptosCrypto,
+    Signature, SigningKey, Uniform,
+};
+use ark_bls12_381::Fr;
+use rand::{seq::SliceRandom, thread_rng};
+use rand_core::OsRng;
+use std::convert::TryFrom;
+
+/// Tests that any `threshold` signature shares aggregate into a signature that verifies under the
+/// threshold public key, and that the aggregate does not depend on which shares were used.
+#[test]
+fn bls12381_threshold_sign_and_aggregate() {
+    let mut rng = OsRng;
+    let (threshold, num_players) = (3, 5);
+    let message = TestAptosCrypto("Hello threshold".to_string());
+
+    let keys = deal(&mut rng, threshold, num_players).unwrap();
+    assert_eq!(keys.secret_key_shares.len(), num_players);
+    assert_eq!(keys.public_key_shares.len(), num_players);
+
+    let mut shares: Vec<_> = keys
+        .secret_key_shares
+        .iter()
+        .map(|sk_share| sk_share.sign(&message).unwrap())
+        .collect();
+    for (pk_share, share) in keys.public_key_shares.iter().zip(shares.iter()) {
+        assert!(pk_share.verify(&message, share).is_ok());
+    }
+
+    let signature = SignatureShare::aggregate(threshold, &shares).unwrap();
+    assert!(signature.verify(&message, &keys.public_key).is_ok());
+
+    shares.shuffle(&mut thread_rng());
+    let other_signature = SignatureShare::aggregate(threshold, &shares).unwrap();
+    assert_eq!(signature, other_signature);
+}
+
+/// Tests that sharing an existing secret key yields the same signature as the key itself.
+#[test]
+fn bls12381_threshold_deal_with_secret_key() {
+    let mut rng = OsRng;
+    let message = b"Hello threshold";
+    let secret_key = PrivateKey::generate(&mut rng);
+
+    let keys = deal_with_secret_key(&mut rng, &secret_key, 2, 4).unwrap();
+    assert_eq!(keys.public_key, PublicKey::from(&secret_key));
+
+    let shares: Vec<_> = keys.secret_key_shares[2..]
+        .iter()
+        .map(|sk_share| sk_share.sign_arbitrary_message(message))
+        .collect();
+    assert_eq!(
+        SignatureShare::aggregate(2, &shares).unwrap(),
+        secret_key.sign_arbitrary_message(message)
+    );
+}
+
+/// Tests that shares are rejected when they come from the wrong player, are too few or repeat a
+/// player.
+#[test]
+fn bls12381_threshold_invalid_shares() {
+    let mut rng = OsRng;
+    let message = b"Hello threshold";
+
+    assert!(deal(&mut rng, 0, 3).is_err());
+    assert!(deal(&mut rng, 4, 3).is_err());
+
+    let keys = deal(&mut rng, 2, 3).unwrap();
+    let shares: Vec<_> = keys
+        .secret_key_shares
+        .iter()
+        .map(|sk_share| sk_share.sign_arbitrary_message(message))
+        .collect();
+
+    // A share does not verify under the public key share of another player, even if relabeled
+    assert!(keys.public_key_shares[1]
+        .verify_arbitrary_msg(message, &shares[0])
+        .is_err());
+    let relabeled = SignatureShare::new(1, shares[0].signature().clone());
+    assert!(keys.public_key_shares[1]
+        .verify_arbitrary_msg(message, &relabeled)
+        .is_err());
+    assert!(keys.public_key_shares[0]
+        .verify_arbitrary_msg(b"Wello Hreshold", &shares[0])
+        .is_err());
+
+    assert!(SignatureShare::aggregate(2, &shares[..1]).is_err());
+    assert!(SignatureShare::aggregate(2, &[shares[0].clone(), shares[0].clone()]).is_err());
+}
+
+/// Test vectors for the shares of the secret key 2 with the polynomial f(x) = 2 + 3x, i.e., the
+/// secret key shares 5, 8 and 11.
+#[test]
+fn bls12381_threshold_test_vectors() {
+    let keys = deal_with_coefficients(vec![Fr::from(2u64), Fr::from(3u64)], 3).unwrap();
+
+    assert_eq!(
+        hex::encode(keys.public_key.to_bytes()),
+        "a572cbea904d67468808c8eb50a9450c9721db309128012543902d0ac358a62ae28f75bb8f1c7c42c39a8c5529bf0f4e"
+    );
+    let expected_shares = [
+        ("0000000000000000000000000000000000000000000000000000000000000005", "b0e7791fb972fe014159aa33a98622da3cdc98ff707965e536d8636b5fcc5ac7a91a8c46e59a00dca575af0f18fb13dc"),
+        ("0000000000000000000000000000000000000000000000000000000000000008", "a85ae765588126f5e860d019c0e26235f567a9c0c0b2d8ff30f3e8d436b1082596e5e7462d20f5be3764fd473e57f9cf"),
+        ("000000000000000000000000000000000000000000000000000000000000000b", "80fd75ebcc0a21649e3177bcce15426da0e4f25d6828fbf4038d4d7ed3bd4421de3ef61d70f794687b12b2d571971a55"),
+    ];
+    for (player, (sk, pk)) in expected_shares.iter().enumerate() {
+        let sk_share = &keys.secret_key_shares[player];
+        let pk_share = &keys.public_key_shares[player];
+        assert_eq!(sk_share.player(), player);
+        assert_eq!(hex::encode(sk_share.private_key().to_bytes()), *sk);
+        assert_eq!(pk_share.player(), player);
+        assert_eq!(hex::encode(pk_share.public_key().to_bytes()), *pk);
+    }
+
+    // Shares serialize as the player index followed by the key material.
+    assert_eq!(
+        hex::encode(bcs::to_bytes(&keys.public_key_shares[1]).unwrap()),
+        format!("010000000000000030{}", expected_shares[1].1)
+    );
+    let bytes = bcs::to_bytes(&keys.public_key_shares[2]).unwrap();
+    assert_eq!(
+        bcs::from_bytes(&bytes).ok(),
+        Some(keys.public_key_shares[2].clone())
+    );
+
+    // Interpolating the shares of players 0 and 2 gives back the signature of the secret key 2.
+    let message = b"Hello threshold";
+    let shares = [
+        keys.secret_key_shares[2].sign_arbitrary_message(message),
+        keys.secret_key_shares[0].sign_arbitrary_message(message),
+    ];
+    let mut secret_key_bytes = [0u8; PrivateKey::LENGTH];
+    secret_key_bytes[PrivateKey::LENGTH - 1] = 2;
+    let secret_key = PrivateKey::try_from(secret_key_bytes.as_ref()).unwrap();
+    assert_eq!(
+        SignatureShare::aggregate(2, &shares).unwrap(),
+        secret_key.sign_arbitrary_message(message)
+    );
+
+    let bytes = bcs::to_bytes(&shares[0]).unwrap();
+    assert_eq!(
+        bcs::from_bytes::<SignatureShare>(&bytes).unwrap(),
+        shares[0]
+    );
+}
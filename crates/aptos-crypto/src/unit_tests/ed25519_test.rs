@@ -325,6 +325,27 @@ proptest! {
         prop_assert!(Ed25519Signature::batch_verify(&message, signatures).is_err());
     }
 
+    #[test]
+    fn test_batch_verify_arbitrary_msgs(
+        messages in proptest::array::uniform10(vec(any::<u8>(), 0..64)),
+        keypairs in proptest::array::uniform10(uniform_keypair_strategy::<Ed25519PrivateKey, Ed25519PublicKey>())
+    ) {
+        let messages: Vec<&[u8]> = messages.iter().map(|message| message.as_slice()).collect();
+        let public_keys: Vec<_> = keypairs.iter().map(|keypair| &keypair.public_key).collect();
+        let mut signatures: Vec<_> = keypairs.iter().zip(messages.iter()).map(|(keypair, message)| {
+            keypair.private_key.sign_arbitrary_message(message)
+        }).collect();
+        let signature_refs: Vec<_> = signatures.iter().collect();
+        prop_assert!(Ed25519Signature::batch_verify_arbitrary_msgs(&messages, &public_keys, &signature_refs).is_ok());
+        // A batch needs one message and one public key per signature
+        prop_assert!(Ed25519Signature::batch_verify_arbitrary_msgs(&messages[1..], &public_keys, &signature_refs).is_err());
+
+        // We sign a different message for the first element, resulting in an incorrect signature
+        signatures[0] = keypairs[0].private_key.sign_arbitrary_message(b"a different message");
+        let signature_refs: Vec<_> = signatures.iter().collect();
+        prop_assert!(Ed25519Signature::batch_verify_arbitrary_msgs(&messages, &public_keys, &signature_refs).is_err());
+    }
+
     #[test]
     fn test_keys_custom_serialisation(
         keypair in uniform_keypair_strategy::<Ed25519PrivateKey, Ed25519PublicKey>()
@@ -496,6 +517,9 @@ proptest! {
         // calls ed25519-dalek's verify_strict
         let sig = Ed25519Signature::from_bytes_unchecked(sig_bytes.as_ref()).unwrap();
         prop_assert!(pk.verify_struct_signature(&m, &sig).is_err());
+
+        // We expect batch verification to reject it as well
+        prop_assert!(Ed25519PublicKey::batch_verify(&m, vec![(pk, sig)]).is_err());
     }
 }
 
@@ -0,0 +1,154 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    test_utils::uniform_keypair_strategy,
+    traits::*,
+    vrf::ecvrf::{Output, Proof, VRFPrivateKey, VRFPublicKey, PROOF_LENGTH},
+};
+use core::convert::TryFrom;
+use curve25519_dalek::constants::EIGHT_TORSION;
+use proptest::prelude::*;
+
+/// The ECVRF-EDWARDS25519-SHA512-TAI test vectors from appendix B.3 of RFC9381, as
+/// (SK, PK, alpha, pi, beta).
+const TEST_VECTORS: &[(&str, &str, &str, &str, &str)] = &[
+    (
+        "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60",
+        "d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+        "",
+        "8657106690b5526245a92b003bb079ccd1a92130477671f6fc01ad16f26f723f26f8a57ccaed74ee1b190bed1f479d9727d2d0f9b005a6e456a35d4fb0daab1268a1b0db10836d9826a528ca76567805",
+        "90cf1df3b703cce59e2a35b925d411164068269d7b2d29f3301c03dd757876ff66b71dda49d2de59d03450451af026798e8f81cd2e333de5cdf4f3e140fdd8ae",
+    ),
+    (
+        "4ccd089b28ff96da9db6c346ec114e0f5b8a319f35aba624da8cf6ed4fb8a6fb",
+        "3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c",
+        "72",
+        "f3141cd382dc42909d19ec5110469e4feae18300e94f304590abdced48aed5933bf0864a62558b3ed7f2fea45c92a465301b3bbf5e3e54ddf2d935be3b67926da3ef39226bbc355bdc9850112c8f4b02",
+        "eb4440665d3891d668e7e0fcaf587f1b4bd7fbfe99d0eb2211ccec90496310eb5e33821bc613efb94db5e5b54c70a848a0bef4553a41befc57663b56373a5031",
+    ),
+    (
+        "c5aa8df43f9f837bedb7442f31dcb7b166d38535076f094b85ce3a2e0b4458f7",
+        "fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025",
+        "af82",
+        "9bc0f79119cc5604bf02d23b4caede71393cedfbb191434dd016d30177ccbf8096bb474e53895c362d8628ee9f9ea3c0e52c7a5c691b6c18c9979866568add7a2d41b00b05081ed0f58ee5e31b3a970e",
+        "645427e5d00c62a23fb703732fa5d892940935942101e456ecca7bb217c61c452118fec1219202a0edcf038bb6373241578be7217ba85a2687f7a0310b2df19f",
+    ),
+];
+
+#[test]
+fn test_rfc9381_vectors() {
+    for (sk, pk, alpha, pi, beta) in TEST_VECTORS {
+        let private_key = VRFPrivateKey::try_from(&hex::decode(sk).unwrap()[..]).unwrap();
+        let public_key = VRFPublicKey::from(&private_key);
+        assert_eq!(hex::encode(public_key.to_bytes()), *pk);
+
+        let alpha = hex::decode(alpha).unwrap();
+        let proof = private_key.prove(&alpha);
+        assert_eq!(hex::encode(proof.to_bytes()), *pi);
+
+        let proof = Proof::try_from(&hex::decode(pi).unwrap()[..]).unwrap();
+        let public_key = VRFPublicKey::try_from(&hex::decode(pk).unwrap()[..]).unwrap();
+        let output = public_key.verify(&proof, &alpha).unwrap();
+        assert_eq!(hex::encode(output.to_bytes()), *beta);
+        assert_eq!(
+            output,
+            Output::try_from(&hex::decode(beta).unwrap()[..]).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_invalid_encodings() {
+    // Points of small order, including the identity, are not valid public keys
+    for torsion in EIGHT_TORSION.iter() {
+        assert_eq!(
+            VRFPublicKey::try_from(&torsion.compress().to_bytes()[..]),
+            Err(CryptoMaterialError::SmallSubgroupError)
+        );
+    }
+    // A non-canonical encoding of the y-coordinate 1, i.e., p + 1
+    let mut non_canonical = [0xFF; 32];
+    non_canonical[0] = 0xEE;
+    non_canonical[31] = 0x7F;
+    assert_eq!(
+        VRFPublicKey::try_from(&non_canonical[..]),
+        Err(CryptoMaterialError::DeserializationError)
+    );
+
+    // s must be reduced modulo the group order
+    let (_, _, _, pi, _) = TEST_VECTORS[0];
+    let mut proof = hex::decode(pi).unwrap();
+    proof[PROOF_LENGTH - 1] = 0xFF;
+    assert_eq!(
+        Proof::try_from(&proof[..]),
+        Err(CryptoMaterialError::CanonicalRepresentationError)
+    );
+    assert_eq!(
+        Proof::try_from(&proof[1..]),
+        Err(CryptoMaterialError::WrongLengthError)
+    );
+}
+
+proptest! {
+    #[test]
+    fn test_prove_and_verify(
+        keypair in uniform_keypair_strategy::<VRFPrivateKey, VRFPublicKey>(),
+        other_keypair in uniform_keypair_strategy::<VRFPrivateKey, VRFPublicKey>(),
+        alpha in any::<Vec<u8>>(),
+        other_alpha in any::<Vec<u8>>(),
+    ) {
+        prop_assume!(keypair.public_key != other_keypair.public_key);
+        prop_assume!(alpha != other_alpha);
+
+        let proof = keypair.private_key.prove(&alpha);
+        let output = keypair.public_key.verify(&proof, &alpha).unwrap();
+        prop_assert_eq!(output, proof.to_output());
+        // Proofs are deterministic
+        prop_assert_eq!(&proof, &keypair.private_key.prove(&alpha));
+
+        prop_assert!(keypair.public_key.verify(&proof, &other_alpha).is_err());
+        prop_assert!(other_keypair.public_key.verify(&proof, &alpha).is_err());
+        prop_assert_ne!(output, keypair.private_key.prove(&other_alpha).to_output());
+        prop_assert_ne!(output, other_keypair.private_key.prove(&alpha).to_output());
+    }
+
+    #[test]
+    fn test_tampered_proof(
+        keypair in uniform_keypair_strategy::<VRFPrivateKey, VRFPublicKey>(),
+        alpha in any::<Vec<u8>>(),
+        index in 0..PROOF_LENGTH,
+        mask in 1u8..,
+    ) {
+        let mut bytes = keypair.private_key.prove(&alpha).to_bytes();
+        bytes[index] ^= mask;
+        // The tampered proof either fails to deserialize or to verify
+        if let Ok(proof) = Proof::try_from(&bytes[..]) {
+            prop_assert!(keypair.public_key.verify(&proof, &alpha).is_err());
+        }
+    }
+
+    #[test]
+    fn test_serialization(
+        keypair in uniform_keypair_strategy::<VRFPrivateKey, VRFPublicKey>(),
+        alpha in any::<Vec<u8>>(),
+    ) {
+        let proof = keypair.private_key.prove(&alpha);
+
+        let serialized = bcs::to_bytes(&proof).unwrap();
+        prop_assert_eq!(&bcs::from_bytes::<Proof>(&serialized).unwrap(), &proof);
+        let encoded = proof.to_encoded_string().unwrap();
+        prop_assert_eq!(&Proof::from_encoded_string(&encoded).unwrap(), &proof);
+
+        let serialized = bcs::to_bytes(&keypair.public_key).unwrap();
+        prop_assert_eq!(
+            &bcs::from_bytes::<VRFPublicKey>(&serialized).unwrap(),
+            &keypair.public_key
+        );
+        let serialized = bcs::to_bytes(&keypair.private_key).unwrap();
+        prop_assert_eq!(
+            &bcs::from_bytes::<VRFPrivateKey>(&serialized).unwrap(),
+            &keypair.private_key
+        );
+    }
+}
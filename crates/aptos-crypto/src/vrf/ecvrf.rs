@@ -0,0 +1,420 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module implements the ECVRF-EDWARDS25519-SHA512-TAI ciphersuite of the elliptic curve
+//! VRF defined in [RFC9381](https://www.rfc-editor.org/rfc/rfc9381.html).
+//!
+//! Private keys are regular Ed25519 seeds, from which the scalar and nonce key are derived exactly
+//! as in [RFC8032](https://tools.ietf.org/html/rfc8032). Nonetheless, the same key must NOT be used
+//! for both Ed25519 signatures and VRF proofs.
+//!
+//! Public keys are validated upon deserialization: non-canonical encodings and points of small
+//! order are rejected, as recommended for applications where the key holder may be adversarial
+//! (e.g., leader election), since a small-order public key would let its holder bias the output.
+
+use crate::traits::*;
+use anyhow::{anyhow, ensure, Result};
+use aptos_crypto_derive::{DeserializeKey, SerializeKey, SilentDebug, SilentDisplay};
+use core::convert::TryFrom;
+use curve25519_dalek::{
+    constants::ED25519_BASEPOINT_TABLE,
+    edwards::{CompressedEdwardsY, EdwardsPoint},
+    scalar::Scalar,
+};
+use sha2::{Digest, Sha512};
+use std::fmt;
+
+/// The length of a VRFPrivateKey
+pub const VRF_PRIVATE_KEY_LENGTH: usize = ed25519_dalek::SECRET_KEY_LENGTH;
+/// The length of a VRFPublicKey
+pub const VRF_PUBLIC_KEY_LENGTH: usize = 32;
+/// The length of a Proof: the encoded point gamma, followed by the challenge c and the scalar s
+pub const PROOF_LENGTH: usize = POINT_LENGTH + CHALLENGE_LENGTH + SCALAR_LENGTH;
+/// The length of an Output
+pub const OUTPUT_LENGTH: usize = 64;
+
+const POINT_LENGTH: usize = 32;
+const CHALLENGE_LENGTH: usize = 16;
+const SCALAR_LENGTH: usize = 32;
+
+/// The suite_string of ECVRF-EDWARDS25519-SHA512-TAI
+const SUITE_STRING: u8 = 0x03;
+const ENCODE_TO_CURVE_DOMAIN_SEPARATOR_FRONT: u8 = 0x01;
+const CHALLENGE_GENERATION_DOMAIN_SEPARATOR_FRONT: u8 = 0x02;
+const PROOF_TO_HASH_DOMAIN_SEPARATOR_FRONT: u8 = 0x03;
+const DOMAIN_SEPARATOR_BACK: u8 = 0x00;
+
+/// A VRF private key
+#[derive(DeserializeKey, SerializeKey, SilentDebug, SilentDisplay)]
+pub struct VRFPrivateKey(ed25519_dalek::SecretKey);
+
+#[cfg(feature = "assert-private-keys-not-cloneable")]
+static_assertions::assert_not_impl_any!(VRFPrivateKey: Clone);
+
+#[cfg(any(test, feature = "cloneable-private-keys"))]
+impl Clone for VRFPrivateKey {
+    fn clone(&self) -> Self {
+        VRFPrivateKey::try_from(&self.to_bytes()[..]).unwrap()
+    }
+}
+
+/// A VRF public key
+#[derive(DeserializeKey, Clone, SerializeKey)]
+pub struct VRFPublicKey(EdwardsPoint);
+
+/// A VRF proof, from which anyone holding the public key can check and derive the VRF output
+#[derive(DeserializeKey, Clone, SerializeKey)]
+pub struct Proof {
+    gamma: EdwardsPoint,
+    c: Scalar,
+    s: Scalar,
+}
+
+/// The output of the VRF, which is only known to be correct once its proof has been verified
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct Output([u8; OUTPUT_LENGTH]);
+
+impl VRFPrivateKey {
+    /// Serialize a VRFPrivateKey.
+    pub fn to_bytes(&self) -> [u8; VRF_PRIVATE_KEY_LENGTH] {
+        self.0.to_bytes()
+    }
+
+    /// Computes the VRF proof for the input `alpha`, following section 5.1 of RFC9381.
+    pub fn prove(&self, alpha: &[u8]) -> Proof {
+        let (x, nonce_key) = self.expand();
+        let public_key = VRFPublicKey(&ED25519_BASEPOINT_TABLE * &x);
+        let h = public_key.encode_to_curve(alpha);
+        let h_string = h.compress();
+        let gamma = h * x;
+
+        let k = Scalar::from_hash(Sha512::new().chain(nonce_key).chain(h_string.as_bytes()));
+        let c = challenge_generation(&[
+            &public_key.0,
+            &h,
+            &gamma,
+            &(&ED25519_BASEPOINT_TABLE * &k),
+            &(h * k),
+        ]);
+        let s = k + c * x;
+        Proof { gamma, c, s }
+    }
+
+    /// Derives the secret scalar and the nonce key from the seed, as in section 5.1.5 of RFC8032.
+    fn expand(&self) -> (Scalar, [u8; 32]) {
+        let hash = Sha512::digest(self.0.as_bytes());
+        let mut scalar_bytes = [0u8; 32];
+        let mut nonce_key = [0u8; 32];
+        scalar_bytes.copy_from_slice(&hash[..32]);
+        nonce_key.copy_from_slice(&hash[32..]);
+        scalar_bytes[0] &= 248;
+        scalar_bytes[31] &= 127;
+        scalar_bytes[31] |= 64;
+        (Scalar::from_bytes_mod_order(scalar_bytes), nonce_key)
+    }
+}
+
+impl VRFPublicKey {
+    /// Serialize a VRFPublicKey.
+    pub fn to_bytes(&self) -> [u8; VRF_PUBLIC_KEY_LENGTH] {
+        self.0.compress().to_bytes()
+    }
+
+    /// Verifies that `proof` was computed for the input `alpha` with the private key of this
+    /// public key, following section 5.3 of RFC9381, and returns the corresponding VRF output.
+    pub fn verify(&self, proof: &Proof, alpha: &[u8]) -> Result<Output> {
+        let h = self.encode_to_curve(alpha);
+        let u = EdwardsPoint::vartime_double_scalar_mul_basepoint(&-proof.c, &self.0, &proof.s);
+        let v = h * proof.s - proof.gamma * proof.c;
+        let c = challenge_generation(&[&self.0, &h, &proof.gamma, &u, &v]);
+        ensure!(c == proof.c, "VRF proof verification failed");
+        Ok(proof.to_output())
+    }
+
+    /// Hashes `alpha` to a point of the prime-order subgroup with the try-and-increment method of
+    /// section 5.4.1.1 of RFC9381, using the public key as salt.
+    fn encode_to_curve(&self, alpha: &[u8]) -> EdwardsPoint {
+        let public_key = self.to_bytes();
+        (0..=u8::MAX)
+            .find_map(|ctr| {
+                let hash = Sha512::new()
+                    .chain([SUITE_STRING, ENCODE_TO_CURVE_DOMAIN_SEPARATOR_FRONT])
+                    .chain(public_key)
+                    .chain(alpha)
+                    .chain([ctr, DOMAIN_SEPARATOR_BACK])
+                    .finalize();
+                decode_point(&hash[..POINT_LENGTH]).map(|point| point.mul_by_cofactor())
+            })
+            // Each attempt fails with probability about 1/2, so this never happens in practice
+            .expect("no valid point found after 256 attempts")
+    }
+}
+
+impl Proof {
+    /// Serialize a Proof.
+    pub fn to_bytes(&self) -> [u8; PROOF_LENGTH] {
+        let mut bytes = [0u8; PROOF_LENGTH];
+        let (gamma, rest) = bytes.split_at_mut(POINT_LENGTH);
+        let (c, s) = rest.split_at_mut(CHALLENGE_LENGTH);
+        gamma.copy_from_slice(self.gamma.compress().as_bytes());
+        c.copy_from_slice(&self.c.as_bytes()[..CHALLENGE_LENGTH]);
+        s.copy_from_slice(self.s.as_bytes());
+        bytes
+    }
+
+    /// Computes the VRF output of this proof, following section 5.2 of RFC9381.
+    ///
+    /// WARNING: The output is only meaningful once the proof has been verified, in which case it
+    /// is also returned by `VRFPublicKey::verify`.
+    pub fn to_output(&self) -> Output {
+        let hash = Sha512::new()
+            .chain([SUITE_STRING, PROOF_TO_HASH_DOMAIN_SEPARATOR_FRONT])
+            .chain(self.gamma.mul_by_cofactor().compress().as_bytes())
+            .chain([DOMAIN_SEPARATOR_BACK])
+            .finalize();
+        let mut output = [0u8; OUTPUT_LENGTH];
+        output.copy_from_slice(&hash);
+        Output(output)
+    }
+}
+
+impl Output {
+    /// Serialize an Output.
+    pub fn to_bytes(&self) -> [u8; OUTPUT_LENGTH] {
+        self.0
+    }
+}
+
+/// Hashes the given points into a challenge, following section 5.4.3 of RFC9381.
+fn challenge_generation(points: &[&EdwardsPoint]) -> Scalar {
+    let hash = points
+        .iter()
+        .fold(
+            Sha512::new().chain([SUITE_STRING, CHALLENGE_GENERATION_DOMAIN_SEPARATOR_FRONT]),
+            |hasher, point| hasher.chain(point.compress().as_bytes()),
+        )
+        .chain([DOMAIN_SEPARATOR_BACK])
+        .finalize();
+    let mut challenge = [0u8; 32];
+    challenge[..CHALLENGE_LENGTH].copy_from_slice(&hash[..CHALLENGE_LENGTH]);
+    Scalar::from_bits(challenge)
+}
+
+/// Decodes a point as in section 5.1.3 of RFC8032, which rejects non-canonical encodings.
+fn decode_point(bytes: &[u8]) -> Option<EdwardsPoint> {
+    let compressed = CompressedEdwardsY::from_slice(bytes);
+    let point = compressed.decompress()?;
+    // curve25519-dalek reduces the y-coordinate and ignores the sign of a zero x-coordinate
+    if point.compress() == compressed {
+        Some(point)
+    } else {
+        None
+    }
+}
+
+///////////////////////
+// PrivateKey Traits //
+///////////////////////
+
+impl PrivateKey for VRFPrivateKey {
+    type PublicKeyMaterial = VRFPublicKey;
+}
+
+impl Uniform for VRFPrivateKey {
+    fn generate<R>(rng: &mut R) -> Self
+    where
+        R: ::rand::RngCore + ::rand::CryptoRng + ::rand_core::CryptoRng + ::rand_core::RngCore,
+    {
+        VRFPrivateKey(ed25519_dalek::SecretKey::generate(rng))
+    }
+}
+
+impl PartialEq<Self> for VRFPrivateKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_bytes() == other.to_bytes()
+    }
+}
+
+impl Eq for VRFPrivateKey {}
+
+impl TryFrom<&[u8]> for VRFPrivateKey {
+    type Error = CryptoMaterialError;
+
+    /// Deserialize a VRFPrivateKey. Any 32-byte seed is a valid private key.
+    fn try_from(bytes: &[u8]) -> std::result::Result<VRFPrivateKey, CryptoMaterialError> {
+        ed25519_dalek::SecretKey::from_bytes(bytes)
+            .map(VRFPrivateKey)
+            .map_err(|_| CryptoMaterialError::WrongLengthError)
+    }
+}
+
+impl Length for VRFPrivateKey {
+    fn length(&self) -> usize {
+        VRF_PRIVATE_KEY_LENGTH
+    }
+}
+
+impl ValidCryptoMaterial for VRFPrivateKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+//////////////////////
+// PublicKey Traits //
+//////////////////////
+
+impl From<&VRFPrivateKey> for VRFPublicKey {
+    fn from(private_key: &VRFPrivateKey) -> Self {
+        let (x, _) = private_key.expand();
+        VRFPublicKey(&ED25519_BASEPOINT_TABLE * &x)
+    }
+}
+
+impl PublicKey for VRFPublicKey {
+    type PrivateKeyMaterial = VRFPrivateKey;
+}
+
+impl std::hash::Hash for VRFPublicKey {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        state.write(&self.to_bytes());
+    }
+}
+
+impl PartialEq for VRFPublicKey {
+    fn eq(&self, other: &VRFPublicKey) -> bool {
+        self.to_bytes() == other.to_bytes()
+    }
+}
+
+impl Eq for VRFPublicKey {}
+
+impl TryFrom<&[u8]> for VRFPublicKey {
+    type Error = CryptoMaterialError;
+
+    /// Deserialize a VRFPublicKey. This method rejects non-canonical encodings and points of small
+    /// order, as required when validating keys in section 5.4.5 of RFC9381.
+    fn try_from(bytes: &[u8]) -> std::result::Result<VRFPublicKey, CryptoMaterialError> {
+        if bytes.len() != VRF_PUBLIC_KEY_LENGTH {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        let point = decode_point(bytes).ok_or(CryptoMaterialError::DeserializationError)?;
+        if point.is_small_order() {
+            return Err(CryptoMaterialError::SmallSubgroupError);
+        }
+        Ok(VRFPublicKey(point))
+    }
+}
+
+impl Length for VRFPublicKey {
+    fn length(&self) -> usize {
+        VRF_PUBLIC_KEY_LENGTH
+    }
+}
+
+impl ValidCryptoMaterial for VRFPublicKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+impl fmt::Display for VRFPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(self.to_bytes()))
+    }
+}
+
+impl fmt::Debug for VRFPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "VRFPublicKey({})", self)
+    }
+}
+
+//////////////////
+// Proof Traits //
+//////////////////
+
+impl PartialEq for Proof {
+    fn eq(&self, other: &Proof) -> bool {
+        self.to_bytes()[..] == other.to_bytes()[..]
+    }
+}
+
+impl Eq for Proof {}
+
+impl TryFrom<&[u8]> for Proof {
+    type Error = CryptoMaterialError;
+
+    /// Deserialize a Proof, following section 5.4.4 of RFC9381. This method rejects non-canonical
+    /// encodings of gamma and s.
+    fn try_from(bytes: &[u8]) -> std::result::Result<Proof, CryptoMaterialError> {
+        if bytes.len() != PROOF_LENGTH {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        let (gamma, rest) = bytes.split_at(POINT_LENGTH);
+        let (c, s) = rest.split_at(CHALLENGE_LENGTH);
+
+        let gamma = decode_point(gamma).ok_or(CryptoMaterialError::DeserializationError)?;
+        let mut c_bytes = [0u8; 32];
+        c_bytes[..CHALLENGE_LENGTH].copy_from_slice(c);
+        let mut s_bytes = [0u8; SCALAR_LENGTH];
+        s_bytes.copy_from_slice(s);
+        let s = Scalar::from_canonical_bytes(s_bytes)
+            .ok_or(CryptoMaterialError::CanonicalRepresentationError)?;
+        Ok(Proof {
+            gamma,
+            c: Scalar::from_bits(c_bytes),
+            s,
+        })
+    }
+}
+
+impl Length for Proof {
+    fn length(&self) -> usize {
+        PROOF_LENGTH
+    }
+}
+
+impl ValidCryptoMaterial for Proof {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes().to_vec()
+    }
+}
+
+impl fmt::Display for Proof {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(self.to_bytes()))
+    }
+}
+
+impl fmt::Debug for Proof {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Proof({})", self)
+    }
+}
+
+///////////////////
+// Output Traits //
+///////////////////
+
+impl TryFrom<&[u8]> for Output {
+    type Error = anyhow::Error;
+
+    fn try_from(bytes: &[u8]) -> Result<Output> {
+        <[u8; OUTPUT_LENGTH]>::try_from(bytes)
+            .map(Output)
+            .map_err(|_| anyhow!("VRF output must be {} bytes", OUTPUT_LENGTH))
+    }
+}
+
+impl fmt::Display for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(self.0))
+    }
+}
+
+impl fmt::Debug for Output {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Output({})", self)
+    }
+}
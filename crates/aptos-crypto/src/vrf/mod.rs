@@ -0,0 +1,27 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module provides verifiable random functions (VRFs): the holder of a private key computes
+//! a pseudorandom output for any input, together with a proof that anyone can check against the
+//! public key. Outputs are unique, i.e., the holder of the key cannot choose between several
+//! valid outputs for the same input, which makes them suitable for leader election and randomness
+//! beacons.
+//!
+//! # Examples
+//!
+//! ```
+//! use aptos_crypto::{vrf::ecvrf::*, traits::Uniform};
+//! use rand_core::OsRng;
+//!
+//! let private_key = VRFPrivateKey::generate(&mut OsRng);
+//! let public_key = VRFPublicKey::from(&private_key);
+//!
+//! let proof = private_key.prove(b"round 42");
+//! let output = public_key.verify(&proof, b"round 42").unwrap();
+//! assert_eq!(output, proof.to_output());
+//! assert!(public_key.verify(&proof, b"round 43").is_err());
+//! ```
+
+pub mod ecvrf;
+
+pub use ecvrf::{Output, Proof, VRFPrivateKey, VRFPublicKey};
@@ -14,7 +14,8 @@ pub mod test_utils;
 use crate::{builder::GenesisConfiguration, config::ValidatorConfiguration};
 use aptos_config::config::{
     RocksdbConfigs, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
-    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
+    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_LEDGER_TIERING_CONFIG,
+    NO_OP_STORAGE_PRUNER_CONFIG,
 };
 use aptos_crypto::ed25519::Ed25519PublicKey;
 use aptos_db::AptosDB;
@@ -23,7 +24,7 @@ use aptos_storage_interface::DbReaderWriter;
 use aptos_temppath::TempPath;
 use aptos_types::{
     chain_id::ChainId,
-    on_chain_config::{GasScheduleV2, OnChainConsensusConfig, OnChainExecutionConfig},
+    on_chain_config::{FeatureFlag, GasScheduleV2, OnChainConsensusConfig, OnChainExecutionConfig},
     transaction::Transaction,
     waypoint::Waypoint,
 };
@@ -70,6 +71,7 @@ pub struct GenesisInfo {
     pub consensus_config: OnChainConsensusConfig,
     pub execution_config: OnChainExecutionConfig,
     pub gas_schedule: GasScheduleV2,
+    pub initial_features: Vec<FeatureFlag>,
 }
 
 impl GenesisInfo {
@@ -106,6 +108,7 @@ impl GenesisInfo {
             consensus_config: genesis_config.consensus_config.clone(),
             execution_config: genesis_config.execution_config.clone(),
             gas_schedule: genesis_config.gas_schedule.clone(),
+            initial_features: genesis_config.initial_features.clone(),
         })
     }
 
@@ -142,6 +145,7 @@ impl GenesisInfo {
             &self.consensus_config,
             &self.execution_config,
             &self.gas_schedule,
+            &self.initial_features,
         )
     }
 
@@ -152,8 +156,10 @@ impl GenesisInfo {
             StorageDirPaths::from_path(path),
             false,
             NO_OP_STORAGE_PRUNER_CONFIG,
+            &NO_OP_LEDGER_TIERING_CONFIG,
             RocksdbConfigs::default(),
             false,
+            false,
             BUFFERED_STATE_TARGET_ITEMS,
             DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
         )?;
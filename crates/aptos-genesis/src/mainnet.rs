@@ -4,17 +4,25 @@
 use crate::{builder::GenesisConfiguration, config::ValidatorConfiguration};
 use aptos_config::config::{
     RocksdbConfigs, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
-    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
+    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_LEDGER_TIERING_CONFIG,
+    NO_OP_STORAGE_PRUNER_CONFIG,
 };
 use aptos_db::AptosDB;
 use aptos_framework::ReleaseBundle;
 use aptos_storage_interface::DbReaderWriter;
 use aptos_temppath::TempPath;
-use aptos_types::{chain_id::ChainId, transaction::Transaction, waypoint::Waypoint};
+use aptos_types::{
+    chain_id::ChainId,
+    on_chain_config::{FeatureFlag, GasScheduleV2, OnChainConsensusConfig, OnChainExecutionConfig},
+    transaction::Transaction,
+    waypoint::Waypoint,
+};
 use aptos_vm::AptosVM;
 use aptos_vm_genesis::{AccountBalance, EmployeePool, ValidatorWithCommissionRate};
 
-/// Holder object for all pieces needed to generate a genesis transaction
+/// Holder object for all pieces needed to generate a genesis transaction with initial account
+/// balances, employee vesting pools and validators, but without a core resources account.
+/// Despite its name, it can be used for any chain, e.g. with a custom framework release.
 #[derive(Clone)]
 pub struct MainnetGenesisInfo {
     /// ChainId for identifying the network
@@ -43,6 +51,11 @@ pub struct MainnetGenesisInfo {
     /// Percent of current epoch's total voting power that can be added in this epoch.
     pub voting_power_increase_limit: u64,
 
+    pub consensus_config: OnChainConsensusConfig,
+    pub execution_config: OnChainExecutionConfig,
+    pub gas_schedule: GasScheduleV2,
+    pub initial_features: Vec<FeatureFlag>,
+
     // MAINNET SPECIFIC FIELDS.
     /// Initial accounts and balances.
     accounts: Vec<AccountBalance>,
@@ -91,6 +104,10 @@ impl MainnetGenesisInfo {
             rewards_apy_percentage: genesis_config.rewards_apy_percentage,
             voting_duration_secs: genesis_config.voting_duration_secs,
             voting_power_increase_limit: genesis_config.voting_power_increase_limit,
+            consensus_config: genesis_config.consensus_config.clone(),
+            execution_config: genesis_config.execution_config.clone(),
+            gas_schedule: genesis_config.gas_schedule.clone(),
+            initial_features: genesis_config.initial_features.clone(),
             employee_vesting_start,
             employee_vesting_period_duration,
         })
@@ -127,6 +144,10 @@ impl MainnetGenesisInfo {
                 employee_vesting_start: self.employee_vesting_start,
                 employee_vesting_period_duration: self.employee_vesting_period_duration,
             },
+            &self.consensus_config,
+            &self.execution_config,
+            &self.gas_schedule,
+            &self.initial_features,
         )
     }
 
@@ -137,8 +158,10 @@ impl MainnetGenesisInfo {
             StorageDirPaths::from_path(path),
             false,
             NO_OP_STORAGE_PRUNER_CONFIG,
+            &NO_OP_LEDGER_TIERING_CONFIG,
             RocksdbConfigs::default(),
             false,
+            false,
             BUFFERED_STATE_TARGET_ITEMS,
             DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
         )?;
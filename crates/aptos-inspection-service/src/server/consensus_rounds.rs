@@ -0,0 +1,39 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::server::utils::{CONTENT_TYPE_JSON, CONTENT_TYPE_TEXT};
+use aptos_config::config::NodeConfig;
+use hyper::{Body, StatusCode};
+
+// The message to display when the consensus rounds endpoint is disabled
+pub const CONSENSUS_ROUNDS_DISABLED_MESSAGE: &str =
+    "This endpoint is disabled! Enable it in the node config at inspection_service.expose_consensus_rounds: true";
+
+/// Handles a new consensus rounds request
+pub fn handle_consensus_rounds_request(node_config: &NodeConfig) -> (StatusCode, Body, String) {
+    // Only return the consensus rounds if the endpoint is enabled
+    if node_config.inspection_service.expose_consensus_rounds {
+        (
+            StatusCode::OK,
+            Body::from(get_consensus_rounds_json()),
+            CONTENT_TYPE_JSON.into(),
+        )
+    } else {
+        (
+            StatusCode::FORBIDDEN,
+            Body::from(CONSENSUS_ROUNDS_DISABLED_MESSAGE),
+            CONTENT_TYPE_TEXT.into(),
+        )
+    }
+}
+
+/// Returns the telemetry of the recent consensus rounds (proposer, duration, vote
+/// arrivals, QC formation latency and timeout reason) as a JSON array, oldest first.
+/// The array is empty if the node doesn't participate in consensus.
+fn get_consensus_rounds_json() -> String {
+    let recent_rounds = aptos_consensus::round_telemetry::recent_rounds();
+    match serde_json::to_string(&recent_rounds) {
+        Ok(recent_rounds) => recent_rounds,
+        Err(error) => format!("Failed to get the consensus rounds! Error: {}", error),
+    }
+}
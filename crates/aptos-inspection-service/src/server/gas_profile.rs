@@ -0,0 +1,37 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::server::utils::CONTENT_TYPE_TEXT;
+use aptos_config::config::NodeConfig;
+use hyper::{Body, StatusCode};
+
+// The message to display when block gas profiling is disabled
+pub const GAS_PROFILE_DISABLED_MESSAGE: &str =
+    "This endpoint is disabled! Enable it in the node config at execution.block_gas_profiling: true";
+
+/// Handles a new gas profile request
+pub fn handle_gas_profile_request(node_config: &NodeConfig) -> (StatusCode, Body, String) {
+    // Only return the gas profile if block gas profiling is enabled
+    if node_config.execution.block_gas_profiling {
+        (
+            StatusCode::OK,
+            Body::from(get_gas_profile()),
+            CONTENT_TYPE_TEXT.into(),
+        )
+    } else {
+        (
+            StatusCode::FORBIDDEN,
+            Body::from(GAS_PROFILE_DISABLED_MESSAGE),
+            CONTENT_TYPE_TEXT.into(),
+        )
+    }
+}
+
+/// Returns the execution and IO costs of the latest executed block in folded stack
+/// format, which can be rendered as a flamegraph (e.g., using inferno or flamegraph.pl)
+fn get_gas_profile() -> String {
+    match aptos_gas_profiling::latest_block_gas_profile() {
+        Some(profile) => profile.execution_and_io_folded_stacks(),
+        None => String::new(), // No block has been executed yet
+    }
+}
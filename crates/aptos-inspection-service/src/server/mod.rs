@@ -18,6 +18,8 @@ use std::{
 };
 
 mod configuration;
+mod consensus_rounds;
+mod gas_profile;
 mod index;
 mod json_encoder;
 mod metrics;
@@ -30,7 +32,9 @@ mod tests;
 
 // The list of endpoints offered by the inspection service
 pub const CONFIGURATION_PATH: &str = "/configuration";
+pub const CONSENSUS_ROUNDS_PATH: &str = "/debug/consensus/rounds";
 pub const FORGE_METRICS_PATH: &str = "/forge_metrics";
+pub const GAS_PROFILE_PATH: &str = "/gas_profile";
 pub const INDEX_PATH: &str = "/";
 pub const JSON_METRICS_PATH: &str = "/json_metrics";
 pub const METRICS_PATH: &str = "/metrics";
@@ -111,11 +115,21 @@ async fn serve_requests(
             // Exposes the node configuration
             configuration::handle_configuration_request(&node_config)
         },
+        CONSENSUS_ROUNDS_PATH => {
+            // /debug/consensus/rounds
+            // Exposes the telemetry of the recent consensus rounds
+            consensus_rounds::handle_consensus_rounds_request(&node_config)
+        },
         FORGE_METRICS_PATH => {
             // /forge_metrics
             // Exposes forge encoded metrics
             metrics::handle_forge_metrics()
         },
+        GAS_PROFILE_PATH => {
+            // /gas_profile
+            // Exposes the gas profile of the latest executed block
+            gas_profile::handle_gas_profile_request(&node_config)
+        },
         INDEX_PATH => {
             // /
             // Exposes the index and list of available endpoints
@@ -83,6 +83,14 @@ fn get_peer_information(
     );
     peer_information_output.push("\n".into());
 
+    // Display the latency and availability history for each peer
+    display_peer_history_summaries(
+        &mut peer_information_output,
+        &all_peers,
+        peers_and_metadata.deref(),
+    );
+    peer_information_output.push("\n".into());
+
     // Display state sync metadata for each peer
     display_state_sync_metadata(&mut peer_information_output, &all_peers, aptos_data_client);
     peer_information_output.push("\n".into());
@@ -182,6 +190,28 @@ fn display_peer_information_summary(
     peer_information_output.push(format!("\t- Peers and network IDs: {:?}", all_peers));
 }
 
+/// Displays the latency and availability history for each peer
+fn display_peer_history_summaries(
+    peer_information_output: &mut Vec<String>,
+    all_peers: &Vec<PeerNetworkId>,
+    peers_and_metadata: &PeersAndMetadata,
+) {
+    peer_information_output.push("Latency and availability history for each peer:".into());
+
+    // Fetch and display the history summary for each peer (if any)
+    for peer in all_peers {
+        if let Ok(peer_metadata) = peers_and_metadata.get_metadata_for_peer(*peer) {
+            let peer_monitoring_metadata = peer_metadata.get_peer_monitoring_metadata();
+            if let Some(peer_history_summary) = peer_monitoring_metadata.peer_history_summary {
+                peer_information_output.push(format!(
+                    "\t- Peer: {}, history summary: {}",
+                    peer, peer_history_summary
+                ));
+            }
+        }
+    }
+}
+
 /// Displays peer monitoring metadata for each peer
 fn display_peer_monitoring_metadata(
     peer_information_output: &mut Vec<String>,
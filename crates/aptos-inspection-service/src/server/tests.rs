@@ -4,11 +4,12 @@
 use crate::{
     server::{
         configuration::CONFIGURATION_DISABLED_MESSAGE,
-        peer_information::PEER_INFO_DISABLED_MESSAGE, serve_requests,
-        system_information::SYS_INFO_DISABLED_MESSAGE, utils::get_all_metrics,
+        consensus_rounds::CONSENSUS_ROUNDS_DISABLED_MESSAGE,
+        gas_profile::GAS_PROFILE_DISABLED_MESSAGE, peer_information::PEER_INFO_DISABLED_MESSAGE,
+        serve_requests, system_information::SYS_INFO_DISABLED_MESSAGE, utils::get_all_metrics,
     },
-    CONFIGURATION_PATH, FORGE_METRICS_PATH, INDEX_PATH, JSON_METRICS_PATH, METRICS_PATH,
-    PEER_INFORMATION_PATH, SYSTEM_INFORMATION_PATH,
+    CONFIGURATION_PATH, CONSENSUS_ROUNDS_PATH, FORGE_METRICS_PATH, GAS_PROFILE_PATH, INDEX_PATH,
+    JSON_METRICS_PATH, METRICS_PATH, PEER_INFORMATION_PATH, SYSTEM_INFORMATION_PATH,
 };
 use aptos_config::config::{AptosDataClientConfig, BaseConfig, NodeConfig};
 use aptos_data_client::client::AptosDataClient;
@@ -57,6 +58,31 @@ async fn test_inspect_configuration() {
     assert!(response_body_string.contains("expose_configuration: true"));
 }
 
+#[tokio::test]
+async fn test_inspect_consensus_rounds() {
+    // Create a validator config
+    let mut config = NodeConfig::get_default_validator_config();
+
+    // Disable the consensus rounds endpoint and ping it
+    config.inspection_service.expose_consensus_rounds = false;
+    let mut response = send_get_request_to_path(&config, CONSENSUS_ROUNDS_PATH).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+
+    // Verify that the response contains an error
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(response_body, CONSENSUS_ROUNDS_DISABLED_MESSAGE);
+
+    // Enable the consensus rounds endpoint and ping it
+    config.inspection_service.expose_consensus_rounds = true;
+    let mut response = send_get_request_to_path(&config, CONSENSUS_ROUNDS_PATH).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+    let response_body_string = read_to_string(response_body.as_ref()).unwrap();
+
+    // Verify that the response contains the (empty) list of rounds
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response_body_string, "[]");
+}
+
 #[tokio::test]
 async fn test_inspect_forge_metrics() {
     // Create a VFN config
@@ -73,6 +99,28 @@ async fn test_inspect_forge_metrics() {
     assert!(response_body_string.contains(INT_COUNTER_NAME));
 }
 
+#[tokio::test]
+async fn test_inspect_gas_profile() {
+    // Create a validator node config
+    let mut config = NodeConfig::get_default_validator_config();
+
+    // Disable block gas profiling and ping the gas profile endpoint
+    config.execution.block_gas_profiling = false;
+    let mut response = send_get_request_to_path(&config, GAS_PROFILE_PATH).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+
+    // Verify that the response contains an error
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(response_body, GAS_PROFILE_DISABLED_MESSAGE);
+
+    // Enable block gas profiling and ping the gas profile endpoint
+    config.execution.block_gas_profiling = true;
+    let response = send_get_request_to_path(&config, GAS_PROFILE_PATH).await;
+
+    // Verify that the request succeeds
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
 #[tokio::test]
 async fn test_inspect_index() {
     // Create a PFN config
@@ -86,7 +134,9 @@ async fn test_inspect_index() {
     // Verify that the response contains all the endpoints
     assert_eq!(response.status(), StatusCode::OK);
     assert!(response_body_string.contains(CONFIGURATION_PATH));
+    assert!(response_body_string.contains(CONSENSUS_ROUNDS_PATH));
     assert!(response_body_string.contains(FORGE_METRICS_PATH));
+    assert!(response_body_string.contains(GAS_PROFILE_PATH));
     assert!(response_body_string.contains(JSON_METRICS_PATH));
     assert!(response_body_string.contains(METRICS_PATH));
     assert!(response_body_string.contains(PEER_INFORMATION_PATH));
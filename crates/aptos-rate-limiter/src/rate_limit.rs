@@ -174,6 +174,20 @@ impl<Key: Eq + Hash + Clone + Debug> TokenBucketRateLimiter<Key> {
         }
         remove
     }
+
+    /// Garbage collects the unused buckets that are refilled to their size, for keys that
+    /// are never known to be gone (e.g., clients of an API). Such buckets are equivalent to
+    /// new ones, as long as new buckets start full.
+    pub fn garbage_collect_full_buckets(&self) {
+        self.buckets.write().retain(|_, bucket| {
+            if Arc::strong_count(bucket) > 1 {
+                return true;
+            }
+            let mut bucket = bucket.lock();
+            bucket.refill();
+            bucket.tokens < bucket.size
+        });
+    }
 }
 
 /// A token bucket object that keeps track of everything related to a key
@@ -346,6 +360,16 @@ impl Bucket {
         tokens_allowed
     }
 
+    /// Current number of available tokens, as of the last refill
+    pub fn tokens(&self) -> usize {
+        self.tokens
+    }
+
+    /// Maximum number of tokens in the bucket
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
     /// Tells us when the next refill is
     pub fn time_of_next_refill(&self) -> Instant {
         self.last_refresh_time + ONE_SEC
@@ -549,4 +573,25 @@ mod tests {
         assert!(!rate_limiter.try_garbage_collect_key(&key_to_keep));
         assert_num_keys(&rate_limiter, 1);
     }
+
+    #[test]
+    fn test_garbage_collect_full_buckets() {
+        let full_key = "full";
+        let used_key = "used";
+        let rate_limiter = TokenBucketRateLimiter::test(5, 1);
+
+        // Create a full bucket, and a bucket with missing tokens
+        rate_limiter.bucket(full_key);
+        rate_limiter
+            .bucket(used_key)
+            .lock()
+            .acquire_all_tokens(5)
+            .unwrap();
+        assert_num_keys(&rate_limiter, 2);
+
+        // Only the full bucket should be garbage collected
+        rate_limiter.garbage_collect_full_buckets();
+        assert_num_keys(&rate_limiter, 1);
+        assert!(rate_limiter.buckets.read().contains_key(used_key));
+    }
 }
@@ -56,6 +56,10 @@ impl FaucetClientError {
         matches!(self.inner.kind, Kind::NeedSync)
     }
 
+    pub fn is_rate_limited(&self) -> bool {
+        matches!(self.inner.kind, Kind::HttpStatus(429))
+    }
+
     //
     // Private Constructors
     //
@@ -349,6 +349,32 @@ impl Client {
         self.json(response).await
     }
 
+    /// Simulates a transaction publishing a package with `0x1::code::publish_package_txn`, also
+    /// returning the problems which would make publishing fail
+    pub async fn simulate_with_publish_diagnostics(
+        &self,
+        txn: &SignedTransaction,
+        estimate_max_gas_amount: bool,
+        estimate_max_gas_unit_price: bool,
+    ) -> AptosResult<Response<Vec<UserTransaction>>> {
+        let txn_payload = bcs::to_bytes(txn)?;
+
+        let url = self.build_path(&format!(
+            "transactions/simulate?estimate_max_gas_amount={}&estimate_gas_unit_price={}&with_publish_diagnostics=true",
+            estimate_max_gas_amount, estimate_max_gas_unit_price
+        ))?;
+
+        let response = self
+            .inner
+            .post(url)
+            .header(CONTENT_TYPE, BCS_CONTENT_TYPE)
+            .body(txn_payload)
+            .send()
+            .await?;
+
+        self.json(response).await
+    }
+
     pub async fn simulate_bcs(
         &self,
         txn: &SignedTransaction,
@@ -282,10 +282,20 @@ impl From<RestError> for ApiError {
                 },
                 AptosErrorCode::VersionPruned => ApiError::VersionPruned(Some(err.error.message)),
                 AptosErrorCode::BlockPruned => ApiError::BlockPruned(Some(err.error.message)),
+                AptosErrorCode::StatePruned => ApiError::VersionPruned(Some(err.error.message)),
                 AptosErrorCode::InvalidInput => ApiError::InvalidInput(Some(err.error.message)),
+                AptosErrorCode::ViewFunctionLimitExceeded => {
+                    ApiError::InvalidInput(Some(err.error.message))
+                },
                 AptosErrorCode::InvalidTransactionUpdate => {
                     ApiError::InvalidInput(Some(err.error.message))
                 },
+                AptosErrorCode::InsufficientGasPriceBump => {
+                    ApiError::InvalidInput(Some(err.error.message))
+                },
+                AptosErrorCode::TransactionRejectedByFilter => {
+                    ApiError::InvalidInput(Some(err.error.message))
+                },
                 AptosErrorCode::SequenceNumberTooOld => {
                     ApiError::SequenceNumberTooOld(Some(err.error.message))
                 },
@@ -294,6 +304,9 @@ impl From<RestError> for ApiError {
                     ApiError::InternalError(Some(err.error.message))
                 },
                 AptosErrorCode::MempoolIsFull => ApiError::MempoolIsFull(Some(err.error.message)),
+                AptosErrorCode::RequestQuotaExceeded => {
+                    ApiError::InternalError(Some(err.error.message))
+                },
                 AptosErrorCode::WebFrameworkError => {
                     ApiError::InternalError(Some(err.error.message))
                 },
@@ -117,3 +117,31 @@ pub(crate) fn increment_log_ingest_too_large_by(v: u64) {
 pub(crate) fn increment_log_ingest_failures_by(v: u64) {
     APTOS_LOG_INGEST_FAILURE.inc_by(v);
 }
+
+/// Counter for metric snapshots successfully pushed to the configured endpoint
+pub(crate) static APTOS_METRICS_PUSH_SUCCESS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_metrics_push_success",
+        "Number of metric snapshots successfully pushed"
+    )
+    .unwrap()
+});
+
+/// Counter for metric snapshots that failed to push to the configured endpoint
+pub(crate) static APTOS_METRICS_PUSH_FAILURE: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_metrics_push_failure",
+        "Number of metric snapshots that failed to push"
+    )
+    .unwrap()
+});
+
+/// Increments the number of successfully pushed metric snapshots
+pub(crate) fn increment_metrics_push_successes() {
+    APTOS_METRICS_PUSH_SUCCESS.inc();
+}
+
+/// Increments the number of metric snapshots that failed to push
+pub(crate) fn increment_metrics_push_failures() {
+    APTOS_METRICS_PUSH_FAILURE.inc();
+}
@@ -0,0 +1,319 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+use crate::metrics;
+use anyhow::anyhow;
+use aptos_config::config::{MetricsPushConfig, NodeConfig};
+use aptos_logger::prelude::*;
+use aptos_types::chain_id::ChainId;
+use prometheus::{
+    default_registry,
+    proto::{MetricFamily, MetricType},
+    Registry,
+};
+use reqwest::{Client, Url};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::{runtime::Runtime, time};
+
+// Suffixes for the aggregated histogram and summary series
+const COUNT_SUFFIX: &str = "_count";
+const SUM_SUFFIX: &str = "_sum";
+
+/// A compact snapshot of the node's metrics. Each metric is aggregated
+/// across all of its label values into a single series.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct MetricsSnapshot {
+    pub peer_id: String,
+    pub chain_id: String,
+    pub timestamp_usecs: u64,
+    pub metrics: BTreeMap<String, f64>,
+}
+
+/// Starts the metrics push service and returns the execution runtime.
+/// Note: The service will not be created if metrics pushing is disabled.
+pub fn start_metrics_push_service(node_config: &NodeConfig, chain_id: ChainId) -> Option<Runtime> {
+    let metrics_push_config = node_config.metrics_push.clone();
+    if !metrics_push_config.enabled {
+        return None;
+    }
+
+    // The sanitizer guarantees the endpoint is set when pushing is enabled
+    let endpoint = metrics_push_config.endpoint.clone()?;
+    let peer_id = node_config
+        .get_peer_id()
+        .map(|peer_id| peer_id.to_string())
+        .unwrap_or_default();
+
+    // Create the runtime and spawn the push loop
+    let runtime = aptos_runtimes::spawn_named_runtime("metrics-push".into(), Some(1));
+    runtime.spawn(async move {
+        let client = Client::new();
+        let mut interval =
+            time::interval(Duration::from_secs(metrics_push_config.push_interval_secs));
+        loop {
+            interval.tick().await;
+            let snapshot = create_metrics_snapshot(
+                default_registry(),
+                &metrics_push_config.metric_prefixes,
+                peer_id.clone(),
+                chain_id,
+            );
+            match push_metrics_snapshot(&client, &endpoint, &metrics_push_config, &snapshot).await {
+                Ok(()) => {
+                    metrics::increment_metrics_push_successes();
+                    debug!("Pushed a snapshot of {} metrics", snapshot.metrics.len());
+                },
+                Err(error) => {
+                    metrics::increment_metrics_push_failures();
+                    warn!("Failed to push the metrics snapshot: {}", error);
+                },
+            }
+        }
+    });
+
+    info!("Metrics push service started!");
+    Some(runtime)
+}
+
+/// Scrapes the given registry and aggregates all metrics matching one
+/// of the given prefixes into a snapshot.
+pub fn create_metrics_snapshot(
+    registry: &Registry,
+    metric_prefixes: &[String],
+    peer_id: String,
+    chain_id: ChainId,
+) -> MetricsSnapshot {
+    let metrics = registry
+        .gather()
+        .iter()
+        .filter(|family| {
+            metric_prefixes
+                .iter()
+                .any(|prefix| family.get_name().starts_with(prefix.as_str()))
+        })
+        .flat_map(aggregate_metric_family)
+        .collect();
+
+    let timestamp_usecs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time should be after the unix epoch")
+        .as_micros() as u64;
+
+    MetricsSnapshot {
+        peer_id,
+        chain_id: chain_id.to_string(),
+        timestamp_usecs,
+        metrics,
+    }
+}
+
+/// Aggregates a metric family into one series (counters and gauges) or
+/// two series (the count and sum of histograms and summaries).
+fn aggregate_metric_family(family: &MetricFamily) -> Vec<(String, f64)> {
+    let name = family.get_name();
+    let metrics = family.get_metric();
+    match family.get_field_type() {
+        MetricType::COUNTER => vec![(
+            name.to_string(),
+            metrics.iter().map(|m| m.get_counter().get_value()).sum(),
+        )],
+        MetricType::GAUGE => vec![(
+            name.to_string(),
+            metrics.iter().map(|m| m.get_gauge().get_value()).sum(),
+        )],
+        MetricType::UNTYPED => vec![(
+            name.to_string(),
+            metrics.iter().map(|m| m.get_untyped().get_value()).sum(),
+        )],
+        MetricType::HISTOGRAM => vec![
+            (
+                format!("{}{}", name, COUNT_SUFFIX),
+                metrics
+                    .iter()
+                    .map(|m| m.get_histogram().get_sample_count() as f64)
+                    .sum(),
+            ),
+            (
+                format!("{}{}", name, SUM_SUFFIX),
+                metrics
+                    .iter()
+                    .map(|m| m.get_histogram().get_sample_sum())
+                    .sum(),
+            ),
+        ],
+        MetricType::SUMMARY => vec![
+            (
+                format!("{}{}", name, COUNT_SUFFIX),
+                metrics
+                    .iter()
+                    .map(|m| m.get_summary().get_sample_count() as f64)
+                    .sum(),
+            ),
+            (
+                format!("{}{}", name, SUM_SUFFIX),
+                metrics
+                    .iter()
+                    .map(|m| m.get_summary().get_sample_sum())
+                    .sum(),
+            ),
+        ],
+    }
+}
+
+/// Posts the JSON encoded snapshot to the given endpoint
+async fn push_metrics_snapshot(
+    client: &Client,
+    endpoint: &Url,
+    metrics_push_config: &MetricsPushConfig,
+    snapshot: &MetricsSnapshot,
+) -> Result<(), anyhow::Error> {
+    let response = client
+        .post(endpoint.clone())
+        .json(snapshot)
+        .timeout(Duration::from_millis(metrics_push_config.push_timeout_ms))
+        .send()
+        .await?;
+
+    if response.status().is_success() {
+        Ok(())
+    } else {
+        Err(anyhow!(
+            "Metrics push failed with response: {}, body: {}",
+            response.status(),
+            response.text().await.unwrap_or_default()
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::MockServer;
+    use prometheus::{
+        register_histogram_vec_with_registry, register_int_counter_vec_with_registry,
+        register_int_gauge_with_registry,
+    };
+
+    fn create_test_registry() -> Registry {
+        let registry = Registry::new();
+        let counter = register_int_counter_vec_with_registry!(
+            "aptos_consensus_test_counter",
+            "test counter",
+            &["label"],
+            registry
+        )
+        .unwrap();
+        counter.with_label_values(&["a"]).inc_by(2);
+        counter.with_label_values(&["b"]).inc_by(3);
+
+        let gauge =
+            register_int_gauge_with_registry!("aptos_storage_test_gauge", "test gauge", registry)
+                .unwrap();
+        gauge.set(7);
+
+        let histogram = register_histogram_vec_with_registry!(
+            "quorum_store_test_histogram",
+            "test histogram",
+            &["label"],
+            registry
+        )
+        .unwrap();
+        histogram.with_label_values(&["a"]).observe(1.5);
+        histogram.with_label_values(&["b"]).observe(2.5);
+
+        let ignored = register_int_gauge_with_registry!(
+            "aptos_network_test_gauge",
+            "ignored gauge",
+            registry
+        )
+        .unwrap();
+        ignored.set(1);
+
+        registry
+    }
+
+    #[test]
+    fn test_create_metrics_snapshot() {
+        let registry = create_test_registry();
+        let snapshot = create_metrics_snapshot(
+            &registry,
+            &MetricsPushConfig::default().metric_prefixes,
+            "peer".into(),
+            ChainId::test(),
+        );
+
+        let expected_metrics: BTreeMap<String, f64> = [
+            ("aptos_consensus_test_counter".to_string(), 5.0),
+            ("aptos_storage_test_gauge".to_string(), 7.0),
+            ("quorum_store_test_histogram_count".to_string(), 2.0),
+            ("quorum_store_test_histogram_sum".to_string(), 4.0),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(snapshot.metrics, expected_metrics);
+        assert_eq!(snapshot.peer_id, "peer");
+        assert_eq!(snapshot.chain_id, ChainId::test().to_string());
+    }
+
+    #[tokio::test]
+    async fn test_push_metrics_snapshot() {
+        let registry = create_test_registry();
+        let snapshot = create_metrics_snapshot(
+            &registry,
+            &["aptos_consensus_".to_string()],
+            "peer".into(),
+            ChainId::test(),
+        );
+
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method("POST")
+                .path("/metrics")
+                .json_body_obj(&snapshot);
+            then.status(200);
+        });
+
+        let endpoint = Url::parse(&server.url("/metrics")).unwrap();
+        let result = push_metrics_snapshot(
+            &Client::new(),
+            &endpoint,
+            &MetricsPushConfig::default(),
+            &snapshot,
+        )
+        .await;
+
+        mock.assert();
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_push_metrics_snapshot_failure() {
+        let registry = create_test_registry();
+        let snapshot = create_metrics_snapshot(&registry, &[], "peer".into(), ChainId::test());
+        assert!(snapshot.metrics.is_empty());
+
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method("POST").path("/metrics");
+            then.status(500);
+        });
+
+        let endpoint = Url::parse(&server.url("/metrics")).unwrap();
+        let result = push_metrics_snapshot(
+            &Client::new(),
+            &endpoint,
+            &MetricsPushConfig::default(),
+            &snapshot,
+        )
+        .await;
+
+        mock.assert();
+        assert!(result.is_err());
+    }
+}
@@ -3,23 +3,31 @@
 
 use crate::{
     account::create::DEFAULT_FUNDED_COINS,
-    common::types::{CliCommand, CliTypedResult, FaucetOptions, ProfileOptions, RestOptions},
+    common::types::{
+        CliCommand, CliError, CliTypedResult, FaucetOptions, ProfileOptions, RestOptions,
+    },
 };
+use aptos_rest_client::Client;
 use aptos_types::account_address::AccountAddress;
 use async_trait::async_trait;
 use clap::Parser;
+use futures::{stream, StreamExt};
 
 /// Fund an account with tokens from a faucet
 ///
 /// This will create an account if it doesn't exist with the faucet.  This is mostly useful
 /// for local development and devnet.
+///
+/// Multiple accounts can be funded at once by repeating `--account`, in which case the
+/// requests are made concurrently and rate limited requests are retried with backoff.
 #[derive(Debug, Parser)]
 pub struct FundWithFaucet {
     /// Address to fund
     ///
-    /// If the account wasn't previously created, it will be created when being funded
-    #[clap(long, value_parser = crate::common::types::load_account_arg)]
-    pub(crate) account: Option<AccountAddress>,
+    /// If the account wasn't previously created, it will be created when being funded.
+    /// Can be repeated to fund multiple accounts.
+    #[clap(long = "account", value_parser = crate::common::types::load_account_arg)]
+    pub(crate) accounts: Vec<AccountAddress>,
 
     /// Number of Octas to fund the account from the faucet
     ///
@@ -28,6 +36,20 @@ pub struct FundWithFaucet {
     #[clap(long, default_value_t = DEFAULT_FUNDED_COINS)]
     pub(crate) amount: u64,
 
+    /// Number of times to request `amount` from the faucet for each account
+    ///
+    /// Useful to fund accounts with more than the faucet gives out per request.
+    #[clap(long, default_value_t = 1)]
+    pub(crate) repeat: u64,
+
+    /// Maximum number of faucet requests in flight at a time
+    #[clap(long, default_value_t = 8)]
+    pub(crate) max_concurrency: usize,
+
+    /// Maximum number of times a rate limited or failed faucet request is retried
+    #[clap(long, default_value_t = 5)]
+    pub(crate) max_retries: u32,
+
     #[clap(flatten)]
     pub(crate) faucet_options: FaucetOptions,
     #[clap(flatten)]
@@ -36,6 +58,23 @@ pub struct FundWithFaucet {
     pub(crate) profile_options: ProfileOptions,
 }
 
+impl FundWithFaucet {
+    async fn fund(&self, client: Client, address: AccountAddress) -> CliTypedResult<()> {
+        for _ in 0..self.repeat {
+            self.faucet_options
+                .fund_account_with_retries(
+                    client.clone(),
+                    &self.profile_options,
+                    self.amount,
+                    address,
+                    self.max_retries,
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
+
 #[async_trait]
 impl CliCommand<String> for FundWithFaucet {
     fn command_name(&self) -> &'static str {
@@ -43,18 +82,44 @@ impl CliCommand<String> for FundWithFaucet {
     }
 
     async fn execute(self) -> CliTypedResult<String> {
-        let address = if let Some(account) = self.account {
-            account
+        let addresses = if self.accounts.is_empty() {
+            vec![self.profile_options.account_address()?]
         } else {
-            self.profile_options.account_address()?
+            self.accounts.clone()
         };
         let client = self.rest_options.client(&self.profile_options)?;
-        self.faucet_options
-            .fund_account(client, &self.profile_options, self.amount, address)
-            .await?;
-        return Ok(format!(
-            "Added {} Octas to account {}",
-            self.amount, address
-        ));
+
+        let outcomes: Vec<_> = stream::iter(addresses)
+            .map(|address| {
+                let client = client.clone();
+                let this = &self;
+                async move { (address, this.fund(client, address).await) }
+            })
+            .buffered(self.max_concurrency.max(1))
+            .collect()
+            .await;
+
+        let total = self.amount * self.repeat;
+        let mut failures = 0;
+        let report = outcomes
+            .iter()
+            .map(|(address, result)| match result {
+                Ok(_) => format!("Added {} Octas to account {}", total, address),
+                Err(err) => {
+                    failures += 1;
+                    format!("Failed to fund account {}: {}", address, err)
+                },
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        if failures > 0 {
+            return Err(CliError::ApiError(format!(
+                "Failed to fund {} of {} accounts\n{}",
+                failures,
+                outcomes.len(),
+                report
+            )));
+        }
+        Ok(report)
     }
 }
@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use super::utils::fund_account;
+use super::utils::{fund_account, fund_account_with_retries};
 use crate::{
     common::{
         init::Network,
@@ -29,7 +29,7 @@ use aptos_keygen::KeyGen;
 use aptos_logger::Level;
 use aptos_move_debugger::aptos_debugger::AptosDebugger;
 use aptos_rest_client::{
-    aptos_api_types::{EntryFunctionId, HashValue, MoveType, ViewRequest},
+    aptos_api_types::{EntryFunctionId, HashValue, MoveType, UserTransaction, ViewRequest},
     error::RestError,
     AptosBaseUrl, Client, Transaction,
 };
@@ -38,6 +38,7 @@ use aptos_sdk::{
     types::{HardwareWalletAccount, HardwareWalletType, LocalAccount, TransactionSigner},
 };
 use aptos_types::{
+    account_config::CORE_CODE_ADDRESS,
     chain_id::ChainId,
     transaction::{
         authenticator::AuthenticationKey, EntryFunction, MultisigTransactionPayload, Script,
@@ -1412,6 +1413,26 @@ impl FaucetOptions {
         )
         .await
     }
+
+    /// Fund an account with the faucet, retrying when rate limited or on transient errors.
+    pub async fn fund_account_with_retries(
+        &self,
+        rest_client: Client,
+        profile: &ProfileOptions,
+        num_octas: u64,
+        address: AccountAddress,
+        max_retries: u32,
+    ) -> CliTypedResult<()> {
+        fund_account_with_retries(
+            rest_client,
+            self.faucet_url(profile)?,
+            self.faucet_auth_token.as_deref(),
+            address,
+            num_octas,
+            max_retries,
+        )
+        .await
+    }
 }
 
 /// Gas price options for manipulating how to prioritize transactions
@@ -1493,6 +1514,39 @@ pub struct TransactionOptions {
     pub(crate) profile_gas: bool,
 }
 
+/// Whether the payload publishes a package with `0x1::code::publish_package_txn`
+fn is_package_publish(payload: &TransactionPayload) -> bool {
+    match payload {
+        TransactionPayload::EntryFunction(entry_function) => {
+            *entry_function.module().address() == CORE_CODE_ADDRESS
+                && entry_function.module().name().as_str() == "code"
+                && entry_function.function().as_str() == "publish_package_txn"
+        },
+        _ => false,
+    }
+}
+
+/// Describes why a simulated transaction failed, with the problems of the published package if
+/// the node returned them
+fn simulation_failure(simulated_txn: &UserTransaction) -> String {
+    let mut message = simulated_txn.info.vm_status.clone();
+    if let Some(diagnostics) = &simulated_txn.publish_diagnostics {
+        for error in &diagnostics.verification_errors {
+            message.push_str(&format!(
+                "\n  {} failed verification: {}",
+                error.module, error.message
+            ));
+        }
+        for incompatibility in &diagnostics.incompatibilities {
+            message.push_str(&format!(
+                "\n  {} is incompatible: {}",
+                incompatibility.module, incompatibility.message
+            ));
+        }
+    }
+    message
+}
+
 impl TransactionOptions {
     /// Builds a rest client
     fn rest_client(&self) -> CliTypedResult<Client> {
@@ -1634,17 +1688,22 @@ impl TransactionOptions {
                 Ed25519Signature::try_from([0u8; 64].as_ref()).unwrap(),
             );
 
-            let txns = client
-                .simulate_with_gas_estimation(&signed_transaction, true, false)
-                .await?
-                .into_inner();
+            // The node explains why publishing a package would fail
+            let txns = if is_package_publish(&payload) {
+                client
+                    .simulate_with_publish_diagnostics(&signed_transaction, true, false)
+                    .await?
+            } else {
+                client
+                    .simulate_with_gas_estimation(&signed_transaction, true, false)
+                    .await?
+            }
+            .into_inner();
             let simulated_txn = txns.first().unwrap();
 
             // Check if the transaction will pass, if it doesn't then fail
             if !simulated_txn.info.success {
-                return Err(CliError::SimulationError(
-                    simulated_txn.info.vm_status.clone(),
-                ));
+                return Err(CliError::SimulationError(simulation_failure(simulated_txn)));
             }
 
             // Take the gas used and use a headroom factor on it
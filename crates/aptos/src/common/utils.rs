@@ -13,7 +13,9 @@ use aptos_build_info::build_information;
 use aptos_crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey};
 use aptos_keygen::KeyGen;
 use aptos_logger::{debug, Level};
-use aptos_rest_client::{aptos_api_types::HashValue, Account, Client, FaucetClient, State};
+use aptos_rest_client::{
+    aptos_api_types::HashValue, error::FaucetClientError, Account, Client, FaucetClient, State,
+};
 use aptos_telemetry::service::telemetry_is_disabled;
 use aptos_types::{
     account_address::create_multisig_account_address,
@@ -447,6 +449,43 @@ pub async fn fund_account(
         .map_err(|err| CliError::ApiError(format!("Faucet issue: {:#}", err)))
 }
 
+const FAUCET_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const FAUCET_MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Like `fund_account`, but retries with exponential backoff when the faucet is rate limiting
+/// requests or fails with a transient error.
+pub async fn fund_account_with_retries(
+    rest_client: Client,
+    faucet_url: Url,
+    faucet_auth_token: Option<&str>,
+    address: AccountAddress,
+    num_octas: u64,
+    max_retries: u32,
+) -> CliTypedResult<()> {
+    let mut client = FaucetClient::new_from_rest_client(faucet_url, rest_client);
+    if let Some(token) = faucet_auth_token {
+        client = client.with_auth_token(token.to_string());
+    }
+    let mut backoff = FAUCET_INITIAL_BACKOFF;
+    let mut retries = 0;
+    loop {
+        match client.fund(address, num_octas).await {
+            Ok(()) => return Ok(()),
+            Err(err) => {
+                let retriable = err
+                    .downcast_ref::<FaucetClientError>()
+                    .map_or(false, |err| err.is_rate_limited() || err.is_retriable());
+                if !retriable || retries >= max_retries {
+                    return Err(CliError::ApiError(format!("Faucet issue: {:#}", err)));
+                }
+            },
+        }
+        tokio::time::sleep(backoff).await;
+        backoff = std::cmp::min(backoff * 2, FAUCET_MAX_BACKOFF);
+        retries += 1;
+    }
+}
+
 /// Wait for transactions, returning an error if any of them fail.
 pub async fn wait_for_transactions(
     client: &aptos_rest_client::Client,
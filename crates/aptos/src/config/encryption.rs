@@ -0,0 +1,124 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Passphrase based encryption of exported profiles.
+//!
+//! The key is derived from the passphrase with PBKDF2-HMAC-SHA256 over a random salt, and the
+//! payload is sealed with AES-256-GCM, so a wrong passphrase or a tampered file fails to open
+//! rather than producing garbage.
+
+use crate::common::types::{CliError, CliTypedResult};
+use ring::{
+    aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN},
+    pbkdf2,
+    rand::{SecureRandom, SystemRandom},
+};
+use serde::{Deserialize, Serialize};
+use std::num::NonZeroU32;
+
+const VERSION: u8 = 1;
+const KDF_ITERATIONS: u32 = 600_000;
+const SALT_LEN: usize = 16;
+const KEY_LEN: usize = 32;
+
+/// An encrypted payload along with everything but the passphrase needed to decrypt it
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EncryptedPayload {
+    pub version: u8,
+    pub kdf_iterations: u32,
+    /// Hex encoded PBKDF2 salt
+    pub salt: String,
+    /// Hex encoded AES-GCM nonce
+    pub nonce: String,
+    /// Base64 encoded ciphertext, including the authentication tag
+    pub ciphertext: String,
+}
+
+impl EncryptedPayload {
+    pub fn seal(passphrase: &str, plaintext: &[u8]) -> CliTypedResult<Self> {
+        let rng = SystemRandom::new();
+        let mut salt = [0u8; SALT_LEN];
+        let mut nonce = [0u8; NONCE_LEN];
+        rng.fill(&mut salt)
+            .and_then(|_| rng.fill(&mut nonce))
+            .map_err(|_| CliError::UnexpectedError("Failed to generate randomness".to_string()))?;
+
+        let key = derive_key(passphrase, &salt, KDF_ITERATIONS)?;
+        let mut in_out = plaintext.to_vec();
+        key.seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from([VERSION]),
+            &mut in_out,
+        )
+        .map_err(|_| CliError::UnexpectedError("Failed to encrypt".to_string()))?;
+
+        Ok(Self {
+            version: VERSION,
+            kdf_iterations: KDF_ITERATIONS,
+            salt: hex::encode(salt),
+            nonce: hex::encode(nonce),
+            ciphertext: base64::encode(in_out),
+        })
+    }
+
+    pub fn open(&self, passphrase: &str) -> CliTypedResult<Vec<u8>> {
+        if self.version != VERSION {
+            return Err(CliError::CommandArgumentError(format!(
+                "Unsupported encrypted file version {}",
+                self.version
+            )));
+        }
+        let salt = hex::decode(&self.salt)
+            .map_err(|err| CliError::UnableToParse("salt", err.to_string()))?;
+        let nonce: [u8; NONCE_LEN] = hex::decode(&self.nonce)
+            .map_err(|err| CliError::UnableToParse("nonce", err.to_string()))?
+            .try_into()
+            .map_err(|_| CliError::UnableToParse("nonce", "invalid length".to_string()))?;
+        let mut in_out = base64::decode(&self.ciphertext)
+            .map_err(|err| CliError::UnableToParse("ciphertext", err.to_string()))?;
+
+        let key = derive_key(passphrase, &salt, self.kdf_iterations)?;
+        let plaintext = key
+            .open_in_place(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from([self.version]),
+                &mut in_out,
+            )
+            .map_err(|_| {
+                CliError::CommandArgumentError(
+                    "Failed to decrypt, the passphrase is wrong or the file is corrupted"
+                        .to_string(),
+                )
+            })?;
+        Ok(plaintext.to_vec())
+    }
+}
+
+fn derive_key(passphrase: &str, salt: &[u8], iterations: u32) -> CliTypedResult<LessSafeKey> {
+    let iterations = NonZeroU32::new(iterations).ok_or_else(|| {
+        CliError::CommandArgumentError("KDF iterations must be non-zero".to_string())
+    })?;
+    let mut key = [0u8; KEY_LEN];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+    let key = UnboundKey::new(&AES_256_GCM, &key)
+        .map_err(|_| CliError::UnexpectedError("Failed to create key".to_string()))?;
+    Ok(LessSafeKey::new(key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seal_and_open() {
+        let payload = EncryptedPayload::seal("passphrase", b"profiles").unwrap();
+        assert_eq!(payload.open("passphrase").unwrap(), b"profiles");
+        assert!(payload.open("wrong passphrase").is_err());
+    }
+}
@@ -5,10 +5,14 @@ use crate::{
     common::{
         types::{
             CliCommand, CliConfig, CliError, CliResult, CliTypedResult, ConfigSearchMode,
-            ProfileSummary, CONFIG_FOLDER,
+            ProfileConfig, ProfileSummary, PromptOptions, SaveFile, CONFIG_FOLDER,
+        },
+        utils::{
+            create_dir_if_not_exist, current_dir, prompt_yes_with_override, read_from_file,
+            read_line, write_to_user_only_file,
         },
-        utils::{create_dir_if_not_exist, current_dir, read_from_file, write_to_user_only_file},
     },
+    config::encryption::EncryptedPayload,
     genesis::git::{from_yaml, to_yaml},
     Tool,
 };
@@ -19,6 +23,8 @@ use clap_complete::Shell;
 use serde::{Deserialize, Serialize};
 use std::{collections::BTreeMap, fmt::Formatter, path::PathBuf, str::FromStr};
 
+mod encryption;
+
 /// Tool for interacting with configuration of the Aptos CLI tool
 ///
 /// This tool handles the global configuration of the CLI tool for
@@ -29,6 +35,8 @@ pub enum ConfigTool {
     SetGlobalConfig(SetGlobalConfig),
     ShowGlobalConfig(ShowGlobalConfig),
     ShowProfiles(ShowProfiles),
+    ExportProfile(ExportProfile),
+    ImportProfile(ImportProfile),
 }
 
 impl ConfigTool {
@@ -38,6 +46,8 @@ impl ConfigTool {
             ConfigTool::SetGlobalConfig(tool) => tool.execute_serialized().await,
             ConfigTool::ShowGlobalConfig(tool) => tool.execute_serialized().await,
             ConfigTool::ShowProfiles(tool) => tool.execute_serialized().await,
+            ConfigTool::ExportProfile(tool) => tool.execute_serialized().await,
+            ConfigTool::ImportProfile(tool) => tool.execute_serialized().await,
         }
     }
 }
@@ -150,6 +160,139 @@ impl CliCommand<BTreeMap<String, ProfileSummary>> for ShowProfiles {
     }
 }
 
+/// Exports profiles to a passphrase encrypted file
+///
+/// The file contains the full profiles, including private keys, and can be
+/// imported on another machine with `aptos config import-profile`.
+#[derive(Parser, Debug)]
+pub struct ExportProfile {
+    /// Profiles to export
+    ///
+    /// If not provided, all profiles are exported
+    #[clap(long = "profile")]
+    profiles: Vec<String>,
+
+    /// File to read the passphrase from, instead of prompting for it
+    #[clap(long, value_parser)]
+    passphrase_file: Option<PathBuf>,
+
+    #[clap(flatten)]
+    save_file: SaveFile,
+}
+
+#[async_trait]
+impl CliCommand<Vec<String>> for ExportProfile {
+    fn command_name(&self) -> &'static str {
+        "ExportProfile"
+    }
+
+    async fn execute(self) -> CliTypedResult<Vec<String>> {
+        self.save_file.check_file()?;
+        let mut profiles = CliConfig::load(ConfigSearchMode::CurrentDir)?
+            .profiles
+            .unwrap_or_default();
+        if !self.profiles.is_empty() {
+            for profile in &self.profiles {
+                if !profiles.contains_key(profile) {
+                    return Err(CliError::CommandArgumentError(format!(
+                        "Profile {} not found",
+                        profile
+                    )));
+                }
+            }
+            profiles.retain(|name, _| self.profiles.contains(name));
+        }
+
+        let passphrase = if self.passphrase_file.is_some() {
+            read_passphrase(&self.passphrase_file)?
+        } else {
+            let passphrase = read_passphrase(&None)?;
+            eprintln!("Confirm the passphrase:");
+            if read_line("passphrase")?.trim() != passphrase {
+                return Err(CliError::CommandArgumentError(
+                    "Passphrases don't match".to_string(),
+                ));
+            }
+            passphrase
+        };
+        let payload = EncryptedPayload::seal(&passphrase, to_yaml(&profiles)?.as_bytes())?;
+        self.save_file
+            .save_to_file_confidential("Encrypted profiles", to_yaml(&payload)?.as_bytes())?;
+        Ok(profiles.into_keys().collect())
+    }
+}
+
+/// Imports profiles from a file created by `aptos config export-profile`
+///
+/// Profiles are added to the config in the current directory. Existing profiles
+/// with the same name are only replaced after confirmation.
+#[derive(Parser, Debug)]
+pub struct ImportProfile {
+    /// Encrypted file to import profiles from
+    #[clap(long, value_parser)]
+    input_file: PathBuf,
+
+    /// File to read the passphrase from, instead of prompting for it
+    #[clap(long, value_parser)]
+    passphrase_file: Option<PathBuf>,
+
+    #[clap(flatten)]
+    prompt_options: PromptOptions,
+}
+
+#[async_trait]
+impl CliCommand<Vec<String>> for ImportProfile {
+    fn command_name(&self) -> &'static str {
+        "ImportProfile"
+    }
+
+    async fn execute(self) -> CliTypedResult<Vec<String>> {
+        let payload: EncryptedPayload = from_yaml(
+            &String::from_utf8(read_from_file(self.input_file.as_path())?)
+                .map_err(CliError::from)?,
+        )?;
+        let passphrase = read_passphrase(&self.passphrase_file)?;
+        let imported: BTreeMap<String, ProfileConfig> =
+            from_yaml(&String::from_utf8(payload.open(&passphrase)?).map_err(CliError::from)?)?;
+
+        let mut config = if CliConfig::config_exists(ConfigSearchMode::CurrentDir) {
+            CliConfig::load(ConfigSearchMode::CurrentDir)?
+        } else {
+            CliConfig::default()
+        };
+        let profiles = config.profiles.get_or_insert_with(BTreeMap::new);
+        for name in imported.keys() {
+            if profiles.contains_key(name) {
+                prompt_yes_with_override(
+                    &format!("Profile {} already exists, replace it?", name),
+                    self.prompt_options,
+                )?;
+            }
+        }
+        let names = imported.keys().cloned().collect();
+        profiles.extend(imported);
+        config.save()?;
+        Ok(names)
+    }
+}
+
+/// Reads the passphrase from the given file, or prompts for it on stdin
+fn read_passphrase(passphrase_file: &Option<PathBuf>) -> CliTypedResult<String> {
+    let passphrase = if let Some(passphrase_file) = passphrase_file {
+        String::from_utf8(read_from_file(passphrase_file.as_path())?).map_err(CliError::from)?
+    } else {
+        eprintln!("Enter the passphrase:");
+        read_line("passphrase")?
+    };
+    let passphrase = passphrase.trim().to_string();
+    if passphrase.is_empty() {
+        return Err(CliError::CommandArgumentError(
+            "Passphrase must not be empty".to_string(),
+        ));
+    }
+    Ok(passphrase)
+}
+
 /// Shows the properties in the global config
 #[derive(Parser, Debug)]
 pub struct ShowGlobalConfig {}
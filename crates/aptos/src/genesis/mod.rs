@@ -36,7 +36,7 @@ use aptos_types::{
     account_address::{AccountAddress, AccountAddressWithChecks},
     on_chain_config::{OnChainConsensusConfig, OnChainExecutionConfig},
 };
-use aptos_vm_genesis::{default_gas_schedule, AccountBalance, EmployeePool};
+use aptos_vm_genesis::{default_features, default_gas_schedule, AccountBalance, EmployeePool};
 use async_trait::async_trait;
 use clap::Parser;
 use std::{
@@ -257,6 +257,7 @@ pub fn fetch_mainnet_genesis_info(git_options: GitOptions) -> CliTypedResult<Mai
             consensus_config: OnChainConsensusConfig::default(),
             execution_config: OnChainExecutionConfig::default_for_genesis(),
             gas_schedule: default_gas_schedule(),
+            initial_features: default_features(),
         },
     )?)
 }
@@ -297,6 +298,7 @@ pub fn fetch_genesis_info(git_options: GitOptions) -> CliTypedResult<GenesisInfo
             consensus_config: layout.on_chain_consensus_config,
             execution_config: layout.on_chain_execution_config,
             gas_schedule: default_gas_schedule(),
+            initial_features: default_features(),
         },
     )?)
 }
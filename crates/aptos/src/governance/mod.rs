@@ -23,6 +23,7 @@ use aptos_cached_packages::aptos_stdlib;
 use aptos_crypto::HashValue;
 use aptos_framework::{BuildOptions, BuiltPackage, ReleasePackage};
 use aptos_logger::warn;
+use aptos_move_debugger::aptos_debugger::AptosDebugger;
 use aptos_rest_client::{
     aptos_api_types::{Address, HexEncodedBytes, U128, U64},
     Client, Transaction,
@@ -33,16 +34,26 @@ use aptos_types::{
     event::EventHandle,
     governance::VotingRecords,
     stake_pool::StakePool,
-    state_store::table::TableHandle,
+    state_store::{state_value::StateValue, table::TableHandle},
     transaction::{Script, TransactionPayload},
+    write_set::{TransactionWrite, WriteOp, WriteOpKind},
 };
+use aptos_vm::move_vm_ext::{SessionExt, SessionId};
+use aptos_vm_types::change_set::VMChangeSet;
 use async_trait::async_trait;
 use clap::Parser;
-use move_core_types::transaction_argument::TransactionArgument;
+use move_binary_format::errors::VMResult;
+use move_core_types::{
+    ident_str,
+    language_storage::{ModuleId, StructTag, TypeTag},
+    transaction_argument::TransactionArgument,
+    value::MoveValue,
+};
+use move_vm_types::gas::UnmeteredGasMeter;
 use reqwest::Url;
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, HashMap},
     fmt::Formatter,
     fs,
     path::{Path, PathBuf},
@@ -62,6 +73,7 @@ pub enum GovernanceTool {
     ListProposals(ListProposals),
     VerifyProposal(VerifyProposal),
     ExecuteProposal(ExecuteProposal),
+    SimulateProposal(SimulateProposal),
     GenerateUpgradeProposal(GenerateUpgradeProposal),
     ApproveExecutionHash(ApproveExecutionHash),
     #[clap(subcommand)]
@@ -75,6 +87,7 @@ impl GovernanceTool {
             Propose(tool) => tool.execute_serialized().await,
             Vote(tool) => tool.execute_serialized().await,
             ExecuteProposal(tool) => tool.execute_serialized().await,
+            SimulateProposal(tool) => tool.execute_serialized().await,
             GenerateUpgradeProposal(tool) => tool.execute_serialized_success().await,
             ShowProposal(tool) => tool.execute_serialized().await,
             ListProposals(tool) => tool.execute_serialized().await,
@@ -878,6 +891,249 @@ impl CliCommand<TransactionSummary> for ExecuteProposal {
     }
 }
 
+/// Simulate the execution of a proposal script against the current on-chain state
+///
+/// A passing proposal for the script is set up locally, so the script is executed exactly
+/// as it would be after a successful vote, including framework upgrades. Nothing is
+/// submitted to the network.
+#[derive(Parser)]
+pub struct SimulateProposal {
+    /// Whether the script resolves a multi-step proposal
+    #[clap(long)]
+    pub(crate) is_multi_step: bool,
+    #[clap(flatten)]
+    pub(crate) compile_proposal_args: CompileScriptFunction,
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+    #[clap(flatten)]
+    pub(crate) prompt_options: PromptOptions,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProposalSimulation {
+    /// Version of the state the proposal was executed against
+    pub version: u64,
+    pub write_set: Vec<SimulatedWrite>,
+    pub events: Vec<SimulatedEvent>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SimulatedWrite {
+    pub state_key: String,
+    pub op: String,
+    /// Size of the written value in bytes, if it is known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SimulatedEvent {
+    pub type_tag: String,
+    pub data: HexEncodedBytes,
+}
+
+#[async_trait]
+impl CliCommand<ProposalSimulation> for SimulateProposal {
+    fn command_name(&self) -> &'static str {
+        "SimulateProposal"
+    }
+
+    async fn execute(self) -> CliTypedResult<ProposalSimulation> {
+        let (bytecode, script_hash) = self
+            .compile_proposal_args
+            .compile("SimulateProposal", self.prompt_options)?;
+        let client = self.rest_options.client(&self.profile_options)?;
+        let debugger = AptosDebugger::rest_client(client)?;
+        let version = debugger
+            .get_latest_version()
+            .await
+            .map_err(|err| CliError::ApiError(err.to_string()))?;
+        // The debugger reads state as of the version before the given one.
+        let state_version = version + 1;
+
+        // Create a proposal for the script and vote it through in a first session, then
+        // execute the script on top of the resulting state so only its own effects are reported.
+        let mut proposal_id = 0;
+        let setup = debugger
+            .run_session_at_version(state_version, |session| {
+                proposal_id =
+                    create_passing_proposal(session, script_hash.to_vec(), self.is_multi_step)?;
+                Ok(())
+            })
+            .map_err(|err| {
+                CliError::UnexpectedError(format!("Failed to set up the proposal: {}", err))
+            })?;
+        let overrides: HashMap<_, StateValue> = setup
+            .write_set_iter()
+            .filter_map(|(key, op)| op.as_state_value().map(|value| (key.clone(), value)))
+            .collect();
+
+        let session_id = SessionId::Txn {
+            sender: CORE_CODE_ADDRESS,
+            sequence_number: 0,
+            script_hash: script_hash.to_vec(),
+        };
+        let change_set = debugger
+            .run_session_at_version_with_overrides(
+                state_version,
+                overrides,
+                session_id,
+                |session| {
+                    session
+                        .execute_script(
+                            bytecode,
+                            vec![],
+                            vec![bcs::to_bytes(&proposal_id).unwrap()],
+                            &mut UnmeteredGasMeter,
+                        )
+                        .map(|_| ())
+                },
+            )
+            .map_err(|err| {
+                CliError::SimulationError(format!("Proposal execution failed: {}", err))
+            })?;
+
+        Ok(ProposalSimulation {
+            version,
+            write_set: simulated_writes(&change_set),
+            events: change_set
+                .events()
+                .iter()
+                .map(|(event, _)| SimulatedEvent {
+                    type_tag: event.type_tag().to_canonical_string(),
+                    data: HexEncodedBytes::from(event.event_data().to_vec()),
+                })
+                .collect(),
+        })
+    }
+}
+
+/// Creates a proposal with the given execution hash at the framework's voting forum, votes it
+/// through and advances the clock so it can be resolved, returning the proposal id.
+fn create_passing_proposal(
+    session: &mut SessionExt,
+    execution_hash: Vec<u8>,
+    is_multi_step: bool,
+) -> VMResult<u64> {
+    let timestamp = ModuleId::new(CORE_CODE_ADDRESS, ident_str!("timestamp").to_owned());
+    let voting = ModuleId::new(CORE_CODE_ADDRESS, ident_str!("voting").to_owned());
+    let proposal_type = TypeTag::Struct(Box::new(StructTag {
+        address: CORE_CODE_ADDRESS,
+        module: ident_str!("governance_proposal").to_owned(),
+        name: ident_str!("GovernanceProposal").to_owned(),
+        type_params: vec![],
+    }));
+    // `GovernanceProposal` has no fields, so it is serialized as its dummy boolean field.
+    let proposal_content = bcs::to_bytes(&false).unwrap();
+
+    let now_microseconds: u64 = {
+        let values = session.execute_function_bypass_visibility(
+            &timestamp,
+            ident_str!("now_microseconds"),
+            vec![],
+            Vec::<Vec<u8>>::new(),
+            &mut UnmeteredGasMeter,
+        )?;
+        bcs::from_bytes(&values.return_values[0].0).unwrap()
+    };
+    let now_seconds = now_microseconds / 1_000_000;
+
+    let values = session.execute_function_bypass_visibility(
+        &voting,
+        ident_str!("create_proposal_v2"),
+        vec![proposal_type.clone()],
+        vec![
+            bcs::to_bytes(&CORE_CODE_ADDRESS).unwrap(),
+            bcs::to_bytes(&CORE_CODE_ADDRESS).unwrap(),
+            proposal_content.clone(),
+            bcs::to_bytes(&execution_hash).unwrap(),
+            // min_vote_threshold
+            bcs::to_bytes(&1u128).unwrap(),
+            // expiration_secs
+            bcs::to_bytes(&now_seconds).unwrap(),
+            // early_resolution_vote_threshold
+            bcs::to_bytes(&Option::<u128>::None).unwrap(),
+            // metadata, an empty SimpleMap
+            bcs::to_bytes(&Vec::<(String, Vec<u8>)>::new()).unwrap(),
+            bcs::to_bytes(&is_multi_step).unwrap(),
+        ],
+        &mut UnmeteredGasMeter,
+    )?;
+    let proposal_id: u64 = bcs::from_bytes(&values.return_values[0].0).unwrap();
+
+    session.execute_function_bypass_visibility(
+        &voting,
+        ident_str!("vote"),
+        vec![proposal_type],
+        vec![
+            proposal_content,
+            bcs::to_bytes(&CORE_CODE_ADDRESS).unwrap(),
+            bcs::to_bytes(&proposal_id).unwrap(),
+            bcs::to_bytes(&1u64).unwrap(),
+            bcs::to_bytes(&true).unwrap(),
+        ],
+        &mut UnmeteredGasMeter,
+    )?;
+
+    // Voting closes after the expiration, and resolution has to happen after the last vote.
+    session.execute_function_bypass_visibility(
+        &timestamp,
+        ident_str!("update_global_time"),
+        vec![],
+        vec![
+            MoveValue::Signer(AccountAddress::ZERO)
+                .simple_serialize()
+                .unwrap(),
+            bcs::to_bytes(&CORE_CODE_ADDRESS).unwrap(),
+            bcs::to_bytes(&(now_microseconds + 2_000_000)).unwrap(),
+        ],
+        &mut UnmeteredGasMeter,
+    )?;
+    Ok(proposal_id)
+}
+
+fn write_op_name(op: &WriteOp) -> &'static str {
+    match op.write_op_kind() {
+        WriteOpKind::Creation => "creation",
+        WriteOpKind::Modification => "modification",
+        WriteOpKind::Deletion => "deletion",
+    }
+}
+
+fn simulated_writes(change_set: &VMChangeSet) -> Vec<SimulatedWrite> {
+    let mut writes: Vec<_> = change_set
+        .write_set_iter()
+        .map(|(key, op)| SimulatedWrite {
+            state_key: format!("{:?}", key.inner()),
+            op: write_op_name(op).to_string(),
+            size: op.bytes().map(|bytes| bytes.len()),
+        })
+        .collect();
+    writes.extend(
+        change_set
+            .resource_group_write_set()
+            .iter()
+            .map(|(key, group)| SimulatedWrite {
+                state_key: format!("{:?}", key.inner()),
+                op: write_op_name(group.metadata_op()).to_string(),
+                size: group.encoded_group_size().map(|size| size as usize),
+            }),
+    );
+    writes.extend(
+        change_set
+            .aggregator_v1_delta_set()
+            .keys()
+            .map(|key| SimulatedWrite {
+                state_key: format!("{:?}", key.inner()),
+                op: "delta".to_string(),
+                size: None,
+            }),
+    );
+    writes
+}
+
 /// Compile a specified script.
 #[derive(Parser)]
 pub struct CompileScriptFunction {
@@ -1,17 +1,30 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::common::types::{CliCommand, CliError, CliResult, CliTypedResult, MovePackageDir};
+use crate::common::types::{
+    CliCommand, CliError, CliResult, CliTypedResult, MovePackageDir, ProfileOptions, RestOptions,
+};
 use aptos_framework::extended_checks;
+use aptos_types::account_address::AccountAddress;
 use async_trait::async_trait;
 use clap::{Parser, Subcommand};
+use move_binary_format::{
+    access::ModuleAccess,
+    file_format::{Bytecode, CodeOffset, CodeUnit},
+    CompiledModule,
+};
 use move_compiler::compiled_unit::{CompiledUnit, NamedCompiledModule};
+use move_core_types::identifier::IdentStr;
 use move_coverage::{
-    coverage_map::CoverageMap, format_csv_summary, format_human_summary,
-    source_coverage::SourceCoverageBuilder, summary::summarize_inst_cov,
+    coverage_map::{CoverageMap, FunctionCoverage, ModuleCoverageMap},
+    format_csv_summary, format_human_summary,
+    source_coverage::SourceCoverageBuilder,
+    summary::summarize_inst_cov,
 };
 use move_disassembler::disassembler::Disassembler;
 use move_package::{compilation::compiled_package::CompiledPackage, BuildConfig, CompilerConfig};
+use serde::Serialize;
+use std::collections::BTreeMap;
 
 /// Display a coverage summary for all modules in a package
 ///
@@ -142,6 +155,221 @@ impl CliCommand<()> for BytecodeCoverage {
     }
 }
 
+/// Display coverage of the modules deployed at an address
+///
+/// Every module published at the account is matched by name against the local package, and the
+/// coverage of the last unit test run is mapped onto its functions. Coverage can only be
+/// attributed to a deployed function if its bytecode is identical to the local build; functions
+/// where it differs are reported as `bytecode_mismatch`.
+#[derive(Debug, Parser)]
+pub struct DeployedCoverage {
+    /// Address the modules are published at
+    #[clap(long, value_parser = crate::common::types::load_account_arg)]
+    pub account: AccountAddress,
+    #[clap(flatten)]
+    pub move_options: MovePackageDir,
+    #[clap(flatten)]
+    pub rest_options: RestOptions,
+    #[clap(flatten)]
+    pub profile_options: ProfileOptions,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeployedCoverageReport {
+    pub account: AccountAddress,
+    pub modules: Vec<DeployedModuleCoverage>,
+    /// Deployed functions that no unit test executes, as `module::function`
+    pub untested_functions: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeployedModuleCoverage {
+    pub module: String,
+    /// Whether the local package contains a module with the same name
+    pub in_package: bool,
+    pub functions: Vec<DeployedFunctionCoverage>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DeployedFunctionCoverage {
+    pub function: String,
+    pub status: DeployedFunctionStatus,
+    pub instructions: u64,
+    pub covered_instructions: u64,
+    /// Number of conditional branch instructions
+    pub branches: u64,
+    /// Number of conditional branches for which both successors were executed
+    pub covered_branches: u64,
+    /// Code offsets of the conditional branches with a successor that was never executed
+    pub untested_branches: Vec<CodeOffset>,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeployedFunctionStatus {
+    Covered,
+    Partial,
+    Untested,
+    Native,
+    BytecodeMismatch,
+}
+
+#[async_trait]
+impl CliCommand<DeployedCoverageReport> for DeployedCoverage {
+    fn command_name(&self) -> &'static str {
+        "DeployedCoverage"
+    }
+
+    async fn execute(self) -> CliTypedResult<DeployedCoverageReport> {
+        let client = self.rest_options.client(&self.profile_options)?;
+        let deployed_modules = client
+            .get_account_modules_bcs(self.account)
+            .await
+            .map_err(|err| CliError::ApiError(err.to_string()))?
+            .into_inner();
+        let (coverage_map, package) = compile_coverage(self.move_options)?;
+        let coverage_map = coverage_map.to_unified_exec_map();
+        let local_modules: BTreeMap<_, _> = package
+            .root_modules()
+            .filter_map(|unit| match &unit.unit {
+                CompiledUnit::Module(NamedCompiledModule { module, .. }) => {
+                    Some((module.self_id().name().to_owned(), module))
+                },
+                _ => None,
+            })
+            .collect();
+
+        let mut modules = vec![];
+        let mut untested_functions = vec![];
+        for bytes in deployed_modules.values() {
+            let deployed = CompiledModule::deserialize(bytes).map_err(|err| {
+                CliError::UnexpectedError(format!("Failed to deserialize deployed module {}", err))
+            })?;
+            let module_name = deployed.self_id().name().to_owned();
+            let local = local_modules.get(&module_name).copied();
+            let module_map = local.and_then(|module| {
+                let id = module.self_id();
+                coverage_map
+                    .module_maps
+                    .get(&(*id.address(), id.name().to_owned()))
+            });
+            let module_coverage = deployed_module_coverage(&deployed, local, module_map);
+            untested_functions.extend(
+                module_coverage
+                    .functions
+                    .iter()
+                    .filter(|function| {
+                        matches!(
+                            function.status,
+                            DeployedFunctionStatus::Untested
+                                | DeployedFunctionStatus::BytecodeMismatch
+                        )
+                    })
+                    .map(|function| format!("{}::{}", module_name, function.function)),
+            );
+            modules.push(module_coverage);
+        }
+
+        Ok(DeployedCoverageReport {
+            account: self.account,
+            modules,
+            untested_functions,
+        })
+    }
+}
+
+fn deployed_module_coverage(
+    deployed: &CompiledModule,
+    local: Option<&CompiledModule>,
+    module_map: Option<&ModuleCoverageMap>,
+) -> DeployedModuleCoverage {
+    let local_code: BTreeMap<&IdentStr, Option<&CodeUnit>> = local
+        .map(|module| {
+            module
+                .function_defs()
+                .iter()
+                .map(|def| {
+                    let handle = module.function_handle_at(def.function);
+                    (module.identifier_at(handle.name), def.code.as_ref())
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let functions = deployed
+        .function_defs()
+        .iter()
+        .map(|def| {
+            let name = deployed.identifier_at(deployed.function_handle_at(def.function).name);
+            let matches_local = match (&def.code, local_code.get(name)) {
+                (Some(code), Some(Some(local))) => code.code == local.code,
+                _ => false,
+            };
+            let executed = if matches_local {
+                module_map.and_then(|map| map.get_function_coverage(name))
+            } else {
+                None
+            };
+            function_coverage(name, def.code.as_ref(), matches_local, executed)
+        })
+        .collect();
+
+    DeployedModuleCoverage {
+        module: deployed.self_id().name().to_string(),
+        in_package: local.is_some(),
+        functions,
+    }
+}
+
+fn function_coverage(
+    name: &IdentStr,
+    code: Option<&CodeUnit>,
+    matches_local: bool,
+    executed: Option<&FunctionCoverage>,
+) -> DeployedFunctionCoverage {
+    let mut coverage = DeployedFunctionCoverage {
+        function: name.to_string(),
+        status: DeployedFunctionStatus::Native,
+        instructions: 0,
+        covered_instructions: 0,
+        branches: 0,
+        covered_branches: 0,
+        untested_branches: vec![],
+    };
+    let Some(code) = code else {
+        return coverage;
+    };
+
+    let is_executed = |pc: u64| executed.map_or(false, |map| map.contains_key(&pc));
+    coverage.instructions = code.code.len() as u64;
+    coverage.covered_instructions = executed.map_or(0, |map| map.len() as u64);
+    for (pc, instr) in code.code.iter().enumerate() {
+        let target = match instr {
+            Bytecode::BrTrue(target) | Bytecode::BrFalse(target) => *target,
+            _ => continue,
+        };
+        coverage.branches += 1;
+        if is_executed(target as u64) && is_executed(pc as u64 + 1) {
+            coverage.covered_branches += 1;
+        } else {
+            coverage.untested_branches.push(pc as CodeOffset);
+        }
+    }
+
+    coverage.status = if !matches_local {
+        DeployedFunctionStatus::BytecodeMismatch
+    } else if coverage.covered_instructions == 0 {
+        DeployedFunctionStatus::Untested
+    } else if coverage.covered_instructions == coverage.instructions
+        && coverage.untested_branches.is_empty()
+    {
+        DeployedFunctionStatus::Covered
+    } else {
+        DeployedFunctionStatus::Partial
+    };
+    coverage
+}
+
 fn compile_coverage(
     move_options: MovePackageDir,
 ) -> CliTypedResult<(CoverageMap, CompiledPackage)> {
@@ -178,6 +406,7 @@ pub enum CoveragePackage {
     Summary(SummaryCoverage),
     Source(SourceCoverage),
     Bytecode(BytecodeCoverage),
+    Deployed(DeployedCoverage),
 }
 
 impl CoveragePackage {
@@ -186,6 +415,7 @@ impl CoveragePackage {
             Self::Summary(tool) => tool.execute_serialized_success().await,
             Self::Source(tool) => tool.execute_serialized_success().await,
             Self::Bytecode(tool) => tool.execute_serialized_success().await,
+            Self::Deployed(tool) => tool.execute_serialized().await,
         }
     }
 }
@@ -8,6 +8,7 @@ mod manifest;
 pub mod package_hooks;
 mod show;
 pub mod stored_package;
+mod watch;
 
 use crate::{
     account::derive_resource_account::ResourceAccountSeed,
@@ -28,20 +29,24 @@ use crate::{
         coverage::SummaryCoverage,
         disassembler::Disassemble,
         manifest::{Dependency, ManifestNamedAddress, MovePackageManifest, PackageInfo},
+        watch::{ModuleGraph, SourceSnapshot, POLL_INTERVAL},
     },
     CliCommand, CliResult,
 };
 use aptos_crypto::HashValue;
 use aptos_framework::{
     docgen::DocgenOptions, extended_checks, natives::code::UpgradePolicy, prover::ProverOptions,
-    BuildOptions, BuiltPackage,
+    BuildOptions, BuiltPackage, ReleaseBundle,
 };
 use aptos_gas_schedule::{MiscGasParameters, NativeGasParameters};
+use aptos_move_debugger::aptos_debugger::AptosDebugger;
 use aptos_rest_client::aptos_api_types::{
     EntryFunctionId, HexEncodedBytes, IdentifierWrapper, MoveModuleId,
 };
 use aptos_types::{
+    access_path::AccessPath,
     account_address::{create_resource_address, AccountAddress},
+    state_store::{state_key::StateKey, state_value::StateValue},
     transaction::{TransactionArgument, TransactionPayload},
 };
 use async_trait::async_trait;
@@ -58,7 +63,7 @@ pub use package_hooks::*;
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet, HashMap},
     fmt::{Display, Formatter},
     path::{Path, PathBuf},
     str::FromStr,
@@ -87,6 +92,7 @@ pub enum MoveTool {
     List(ListPackage),
     Prove(ProvePackage),
     Publish(PublishPackage),
+    Replay(Replay),
     Run(RunFunction),
     RunScript(RunScript),
     #[clap(subcommand, hide = true)]
@@ -114,6 +120,7 @@ impl MoveTool {
             MoveTool::List(tool) => tool.execute_serialized().await,
             MoveTool::Prove(tool) => tool.execute_serialized().await,
             MoveTool::Publish(tool) => tool.execute_serialized().await,
+            MoveTool::Replay(tool) => tool.execute_serialized().await,
             MoveTool::Run(tool) => tool.execute_serialized().await,
             MoveTool::RunScript(tool) => tool.execute_serialized().await,
             MoveTool::Show(tool) => tool.execute_serialized().await,
@@ -439,17 +446,19 @@ pub struct TestPackage {
     /// Dump storage state on failure.
     #[clap(long = "dump")]
     pub dump_state: bool,
-}
 
-#[async_trait]
-impl CliCommand<&'static str> for TestPackage {
-    fn command_name(&self) -> &'static str {
-        "TestPackage"
-    }
+    /// Keep running, and re-run the affected tests whenever a source file changes
+    ///
+    /// Only the tests in modules that were changed, or that depend on a changed module,
+    /// are run again.
+    #[clap(long, conflicts_with = "compute_coverage")]
+    pub watch: bool,
+}
 
-    async fn execute(self) -> CliTypedResult<&'static str> {
+impl TestPackage {
+    fn build_config(&self) -> BuildConfig {
         let known_attributes = extended_checks::get_all_attribute_names();
-        let mut config = BuildConfig {
+        BuildConfig {
             dev_mode: self.move_options.dev,
             additional_named_addresses: self.move_options.named_addresses(),
             test_mode: true,
@@ -463,14 +472,21 @@ impl CliCommand<&'static str> for TestPackage {
                 ..Default::default()
             },
             ..Default::default()
-        };
+        }
+    }
 
-        let path = self.move_options.get_package_path()?;
-        let result = move_cli::base::test::run_move_unit_tests(
-            path.as_path(),
-            config.clone(),
+    fn run_tests(
+        &self,
+        path: &Path,
+        config: BuildConfig,
+        module_filter: Option<BTreeSet<String>>,
+    ) -> CliTypedResult<UnitTestResult> {
+        move_cli::base::test::run_move_unit_tests(
+            path,
+            config,
             UnitTestingConfig {
                 filter: self.filter.clone(),
+                module_filter,
                 report_stacktrace_on_abort: true,
                 report_storage_on_error: self.dump_state,
                 ignore_compile_warnings: self.ignore_compile_warnings,
@@ -485,7 +501,74 @@ impl CliCommand<&'static str> for TestPackage {
             self.compute_coverage,
             &mut std::io::stdout(),
         )
-        .map_err(|err| CliError::UnexpectedError(format!("Failed to run tests: {:#}", err)))?;
+        .map_err(|err| CliError::UnexpectedError(format!("Failed to run tests: {:#}", err)))
+    }
+
+    async fn watch(self, path: PathBuf) -> CliTypedResult<&'static str> {
+        let mut config = self.build_config();
+        // Match the config the test runner compiles with.
+        config.dev_mode = true;
+        let mut snapshot = SourceSnapshot::take(&path);
+        let mut graph: Option<ModuleGraph> = None;
+        // Everything runs on the first iteration.
+        let mut pending = Some(None);
+        loop {
+            if let Some(module_filter) = pending.take() {
+                // The test runner exits the process on compilation errors, so compile first to
+                // keep watching instead.
+                match config
+                    .clone()
+                    .compile_package_no_exit(path.as_path(), &mut std::io::stdout())
+                {
+                    Ok((package, _)) => {
+                        graph = Some(ModuleGraph::new(&package));
+                        if let Err(err) = self.run_tests(&path, config.clone(), module_filter) {
+                            eprintln!("{}", err);
+                        }
+                    },
+                    Err(err) => eprintln!("Failed to compile the package: {:#}", err),
+                }
+                // Dependencies have been fetched by now, don't fetch them again on every change.
+                config.skip_fetch_latest_git_deps = true;
+                eprintln!("Waiting for changes...");
+            }
+
+            tokio::time::sleep(POLL_INTERVAL).await;
+            let current = SourceSnapshot::take(&path);
+            let changed_files = current.changed_files(&snapshot);
+            if changed_files.is_empty() {
+                continue;
+            }
+            snapshot = current;
+            let module_filter = graph
+                .as_ref()
+                .and_then(|graph| graph.impacted_modules(&changed_files));
+            match &module_filter {
+                Some(modules) => eprintln!(
+                    "Re-running tests in {}",
+                    modules.iter().map(String::as_str).join(", ")
+                ),
+                None => eprintln!("Re-running all tests"),
+            }
+            pending = Some(module_filter);
+        }
+    }
+}
+
+#[async_trait]
+impl CliCommand<&'static str> for TestPackage {
+    fn command_name(&self) -> &'static str {
+        "TestPackage"
+    }
+
+    async fn execute(self) -> CliTypedResult<&'static str> {
+        let path = self.move_options.get_package_path()?;
+        if self.watch {
+            return self.watch(path).await;
+        }
+
+        let mut config = self.build_config();
+        let result = self.run_tests(path.as_path(), config.clone(), None)?;
 
         // Print coverage summary if --coverage is set
         if self.compute_coverage {
@@ -1247,6 +1330,107 @@ impl CliCommand<Vec<serde_json::Value>> for ViewFunction {
     }
 }
 
+/// Replay a committed transaction locally
+///
+/// The transaction is executed against the state right before its version, as served by a
+/// fullnode, and the output is compared with the one recorded on chain. This is useful to debug
+/// unexpected aborts, or to check how a transaction behaves with a modified framework.
+#[derive(Parser)]
+pub struct Replay {
+    /// Version of the transaction to replay
+    #[clap(long)]
+    pub(crate) txn_version: u64,
+
+    /// Framework release bundle (`.mrb`) to use instead of the framework on chain
+    ///
+    /// Note that the replayed output is then expected to differ from the on-chain one.
+    #[clap(long, value_parser)]
+    pub(crate) framework_bundle: Option<PathBuf>,
+
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+/// Outcome of replaying a committed transaction
+#[derive(Debug, Serialize)]
+pub struct ReplayResult {
+    pub version: u64,
+    pub status: String,
+    pub gas_used: u64,
+    pub expected_status: String,
+    pub expected_gas_used: u64,
+    /// Every difference between the replayed output and the on-chain one (status, gas used,
+    /// writes and events), one per line. Empty if the outputs match.
+    pub diff: Vec<String>,
+}
+
+#[async_trait]
+impl CliCommand<ReplayResult> for Replay {
+    fn command_name(&self) -> &'static str {
+        "Replay"
+    }
+
+    async fn execute(self) -> CliTypedResult<ReplayResult> {
+        let client = self.rest_options.client(&self.profile_options)?;
+        let debugger = AptosDebugger::rest_client(client)?;
+
+        let overrides = match &self.framework_bundle {
+            Some(path) => {
+                let bundle = ReleaseBundle::read(path.clone())?;
+                bundle
+                    .code_and_compiled_modules()
+                    .into_iter()
+                    .map(|(code, module)| {
+                        (
+                            StateKey::access_path(AccessPath::code_access_path(module.self_id())),
+                            StateValue::new_legacy(code.to_vec().into()),
+                        )
+                    })
+                    .collect()
+            },
+            None => HashMap::new(),
+        };
+
+        let (txn, txn_info) = debugger
+            .get_committed_transaction_at_version(self.txn_version)
+            .await
+            .map_err(|err| CliError::ApiError(err.to_string()))?;
+        let expected_write_set = debugger
+            .get_committed_write_set(self.txn_version)
+            .await
+            .map_err(|err| CliError::ApiError(err.to_string()))?;
+        let expected_events = debugger
+            .get_committed_events(self.txn_version)
+            .await
+            .map_err(|err| CliError::ApiError(err.to_string()))?;
+        let output = debugger
+            .execute_transactions_at_version_with_overrides(self.txn_version, vec![txn], overrides)?
+            .pop()
+            .ok_or_else(|| {
+                CliError::UnexpectedError("Replay did not produce any output".to_string())
+            })?;
+
+        let result = aptos_replay::ReplayResult::new(
+            self.txn_version,
+            &txn_info,
+            &output,
+            &expected_write_set,
+            &expected_events,
+        );
+
+        Ok(ReplayResult {
+            version: self.txn_version,
+            status: format!("{:?}", result.status),
+            gas_used: result.gas_used,
+            expected_status: format!("{:?}", result.expected_status),
+            expected_gas_used: result.expected_gas_used,
+            diff: result.to_string().lines().map(str::to_string).collect(),
+        })
+    }
+}
+
 /// Run a Move script
 #[derive(Parser)]
 pub struct RunScript {
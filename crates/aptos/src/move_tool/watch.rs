@@ -0,0 +1,123 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Change detection for `aptos move test --watch`.
+
+use move_binary_format::access::ModuleAccess;
+use move_compiler::compiled_unit::{CompiledUnit, NamedCompiledModule};
+use move_core_types::language_storage::ModuleId;
+use move_package::compilation::compiled_package::CompiledPackage;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+use walkdir::WalkDir;
+
+pub(crate) const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Modification times of the sources and manifest of a package
+pub(crate) struct SourceSnapshot(BTreeMap<PathBuf, SystemTime>);
+
+impl SourceSnapshot {
+    pub fn take(package_path: &Path) -> Self {
+        let files = WalkDir::new(package_path)
+            .into_iter()
+            // Skip build artifacts, which include copies of the dependencies' sources.
+            .filter_entry(|entry| entry.depth() == 0 || entry.file_name() != "build")
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file() && is_package_file(entry.path()))
+            .filter_map(|entry| {
+                let modified = entry.metadata().ok()?.modified().ok()?;
+                Some((canonicalize(entry.path()), modified))
+            })
+            .collect();
+        Self(files)
+    }
+
+    /// Returns the files added, removed or modified since `previous`
+    pub fn changed_files(&self, previous: &Self) -> BTreeSet<PathBuf> {
+        let mut changed: BTreeSet<_> = self
+            .0
+            .iter()
+            .filter(|(path, modified)| previous.0.get(*path) != Some(*modified))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.extend(
+            previous
+                .0
+                .keys()
+                .filter(|path| !self.0.contains_key(*path))
+                .cloned(),
+        );
+        changed
+    }
+}
+
+/// Dependencies between the modules of a package, used to find the modules whose tests can be
+/// affected by a change
+pub(crate) struct ModuleGraph {
+    modules_by_file: BTreeMap<PathBuf, BTreeSet<ModuleId>>,
+    dependents: BTreeMap<ModuleId, BTreeSet<ModuleId>>,
+}
+
+impl ModuleGraph {
+    pub fn new(package: &CompiledPackage) -> Self {
+        let mut modules_by_file: BTreeMap<_, BTreeSet<_>> = BTreeMap::new();
+        let mut dependents: BTreeMap<_, BTreeSet<_>> = BTreeMap::new();
+        for unit in package.root_modules() {
+            if let CompiledUnit::Module(NamedCompiledModule { module, .. }) = &unit.unit {
+                let id = module.self_id();
+                for dependency in module.immediate_dependencies() {
+                    dependents.entry(dependency).or_default().insert(id.clone());
+                }
+                modules_by_file
+                    .entry(canonicalize(&unit.source_path))
+                    .or_default()
+                    .insert(id);
+            }
+        }
+        Self {
+            modules_by_file,
+            dependents,
+        }
+    }
+
+    /// Returns the names of the modules defined in the changed files and of all modules that
+    /// depend on them, directly or not. Returns `None` if a changed file doesn't define any known
+    /// module, e.g. because it was just added or is the manifest, in which case all tests need
+    /// to run again.
+    pub fn impacted_modules(&self, changed_files: &BTreeSet<PathBuf>) -> Option<BTreeSet<String>> {
+        let mut queue = vec![];
+        for file in changed_files {
+            queue.extend(self.modules_by_file.get(file)?.iter().cloned());
+        }
+        let mut impacted = BTreeSet::new();
+        while let Some(id) = queue.pop() {
+            if let Some(dependents) = self.dependents.get(&id) {
+                queue.extend(
+                    dependents
+                        .iter()
+                        .filter(|dependent| !impacted.contains(*dependent))
+                        .cloned(),
+                );
+            }
+            impacted.insert(id);
+        }
+        Some(
+            impacted
+                .into_iter()
+                .map(|id| id.name().to_string())
+                .collect(),
+        )
+    }
+}
+
+fn is_package_file(path: &Path) -> bool {
+    path.extension().map_or(false, |ext| ext == "move")
+        || path.file_name().map_or(false, |name| name == "Move.toml")
+}
+
+fn canonicalize(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
@@ -6,8 +6,9 @@ use crate::node::local_testnet::utils::socket_addr_to_url;
 use anyhow::{anyhow, Context, Result};
 use aptos_config::config::{NodeConfig, DEFAULT_GRPC_STREAM_PORT};
 use aptos_node::{load_node_config, start_test_environment_node};
+use aptos_types::on_chain_config::{DagConsensusConfigV1, OnChainConsensusConfig};
 use async_trait::async_trait;
-use clap::Parser;
+use clap::{Parser, ValueEnum};
 use maplit::hashset;
 use rand::{rngs::StdRng, SeedableRng};
 use reqwest::Url;
@@ -48,6 +49,33 @@ pub struct NodeArgs {
     /// The port at which to expose the grpc transaction stream.
     #[clap(long, default_value_t = DEFAULT_GRPC_STREAM_PORT)]
     txn_stream_port: u16,
+
+    /// The consensus protocol the node orders blocks with.
+    ///
+    /// This is written into the genesis of the local testnet, so it only takes effect
+    /// when the testnet is created, e.g. together with --force-restart.
+    #[clap(long, value_enum, default_value_t = ConsensusMode::Jolteon)]
+    consensus: ConsensusMode,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+pub enum ConsensusMode {
+    /// Jolteon with quorum store, as used by the public networks.
+    Jolteon,
+    /// DAG based ordering.
+    Dag,
+}
+
+impl ConsensusMode {
+    fn on_chain_config(&self) -> Option<OnChainConsensusConfig> {
+        match self {
+            // Genesis defaults to Jolteon.
+            ConsensusMode::Jolteon => None,
+            ConsensusMode::Dag => Some(OnChainConsensusConfig::DagV1(
+                DagConsensusConfigV1::default(),
+            )),
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -73,6 +101,7 @@ impl NodeManager {
             &test_dir,
             false,
             false,
+            args.node_args.consensus.on_chain_config(),
             aptos_cached_packages::head_release_bundle(),
             rng,
         )
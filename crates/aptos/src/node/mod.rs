@@ -26,6 +26,7 @@ use aptos_backup_cli::{
 };
 use aptos_cached_packages::aptos_stdlib;
 use aptos_crypto::{bls12381, bls12381::PublicKey, x25519, ValidCryptoMaterialStringExt};
+use aptos_db::db_debugger::examine::analyze_storage::{analyze_storage, StorageReport};
 use aptos_genesis::config::{HostAndPort, OperatorConfiguration};
 use aptos_logger::Level;
 use aptos_network_checker::args::{
@@ -64,6 +65,7 @@ const SECS_TO_MICROSECS: u64 = 1_000_000;
 /// identify issues with nodes, and show related information.
 #[derive(Parser)]
 pub enum NodeTool {
+    AnalyzeStorage(AnalyzeStorage),
     AnalyzeValidatorPerformance(AnalyzeValidatorPerformance),
     BootstrapDb(BootstrapDb),
     CheckNetworkConnectivity(CheckNetworkConnectivity),
@@ -85,6 +87,7 @@ impl NodeTool {
     pub async fn execute(self) -> CliResult {
         use NodeTool::*;
         match self {
+            AnalyzeStorage(tool) => tool.execute_serialized().await,
             AnalyzeValidatorPerformance(tool) => tool.execute_serialized().await,
             BootstrapDb(tool) => {
                 tool.execute_serialized_with_logging_level(Level::Info)
@@ -1321,6 +1324,51 @@ impl CliCommand<()> for BootstrapDb {
     }
 }
 
+/// Report what is taking up disk space in a node's DB
+///
+/// The DB is opened read-only. Space is reported per column family, state values are broken
+/// down by kind and by the accounts and tables holding the most bytes, and the range of versions
+/// kept by each pruner is shown.
+#[derive(Parser)]
+pub struct AnalyzeStorage {
+    /// Directory of the node's DB, i.e. the `db` directory under the node's data directory
+    #[clap(long, value_parser)]
+    pub(crate) db_dir: PathBuf,
+
+    /// Set if the node runs with storage sharding enabled
+    #[clap(long)]
+    pub(crate) enable_storage_sharding: bool,
+
+    /// Number of accounts and tables to list in the state breakdown
+    #[clap(long, default_value_t = 10)]
+    pub(crate) top_n: usize,
+
+    /// Skip scanning the state values, which reads all of them and can take a long time
+    #[clap(long)]
+    pub(crate) skip_state_scan: bool,
+}
+
+#[async_trait]
+impl CliCommand<StorageReport> for AnalyzeStorage {
+    fn command_name(&self) -> &'static str {
+        "AnalyzeStorage"
+    }
+
+    async fn execute(self) -> CliTypedResult<StorageReport> {
+        tokio::task::spawn_blocking(move || {
+            analyze_storage(
+                self.db_dir,
+                self.enable_storage_sharding,
+                self.top_n,
+                !self.skip_state_scan,
+            )
+        })
+        .await
+        .map_err(|err| CliError::UnexpectedError(err.to_string()))?
+        .map_err(|err| CliError::UnexpectedError(format!("Failed to analyze DB: {:#}", err)))
+    }
+}
+
 /// Checks the network connectivity of a node
 ///
 /// Checks network connectivity by dialing the node and attempting
@@ -224,9 +224,12 @@ impl CliTestFramework {
     pub async fn fund_account(&self, index: usize, amount: Option<u64>) -> CliTypedResult<String> {
         FundWithFaucet {
             profile_options: Default::default(),
-            account: Some(self.account_id(index)),
+            accounts: vec![self.account_id(index)],
             faucet_options: self.faucet_options(),
             amount: amount.unwrap_or(DEFAULT_FUNDED_COINS),
+            repeat: 1,
+            max_concurrency: 1,
+            max_retries: 5,
             rest_options: self.rest_options(),
         }
         .execute()
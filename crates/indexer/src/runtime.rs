@@ -16,7 +16,7 @@ use crate::{
 use aptos_api::context::Context;
 use aptos_config::config::{IndexerConfig, NodeConfig};
 use aptos_logger::{error, info};
-use aptos_mempool::MempoolClientSender;
+use aptos_mempool::{BackPressureStatus, MempoolClientSender};
 use aptos_storage_interface::DbReader;
 use aptos_types::chain_id::ChainId;
 use std::{collections::VecDeque, sync::Arc};
@@ -79,6 +79,7 @@ pub fn bootstrap(
     chain_id: ChainId,
     db: Arc<dyn DbReader>,
     mp_sender: MempoolClientSender,
+    back_pressure_status: BackPressureStatus,
 ) -> Option<anyhow::Result<Runtime>> {
     if !config.indexer.enabled {
         return None;
@@ -90,7 +91,13 @@ pub fn bootstrap(
     let node_config = config.clone();
 
     runtime.spawn(async move {
-        let context = Arc::new(Context::new(chain_id, db, mp_sender, node_config));
+        let context = Arc::new(Context::new(
+            chain_id,
+            db,
+            mp_sender,
+            node_config,
+            back_pressure_status,
+        ));
         run_forever(indexer_config, context).await;
     });
 
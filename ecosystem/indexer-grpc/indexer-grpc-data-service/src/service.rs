@@ -17,6 +17,7 @@ use aptos_indexer_grpc_utils::{
     },
     file_store_operator::{FileStoreOperator, GcsFileStoreOperator, LocalFileStoreOperator},
     time_diff_since_pb_timestamp_in_secs,
+    transaction_filter::TransactionFilter,
     types::RedisUrl,
     EncodedTransactionWithVersion,
 };
@@ -133,6 +134,13 @@ impl RawData for RawDataServerWrapper {
                 return Result::Err(Status::aborted("Starting version is not set"));
             },
         };
+        let transaction_filter = match &request.transaction_filter {
+            Some(filter) => match TransactionFilter::try_from(filter) {
+                Ok(filter) => filter,
+                Err(e) => return Result::Err(Status::invalid_argument(format!("{:#}", e))),
+            },
+            None => TransactionFilter::default(),
+        };
 
         let file_store_operator: Box<dyn FileStoreOperator> = match &self.file_store_config {
             IndexerGrpcFileStoreConfig::GcsFileStore(gcs_file_store) => {
@@ -261,6 +269,13 @@ impl RawData for RawDataServerWrapper {
                             continue;
                         },
                     };
+                    // The stream resumes after the fetched batch, even if some of it is filtered out.
+                    let end_of_fetched_version = transaction_data.as_slice().last().unwrap().1;
+                    if !transaction_filter.is_empty() {
+                        transaction_data.retain(|(encoded, _)| {
+                            transaction_filter.matches(&decode_transaction(encoded))
+                        });
+                    }
                     if let Some(count) = transactions_count {
                         if count == 0 {
                             // End the data stream.
@@ -275,6 +290,11 @@ impl RawData for RawDataServerWrapper {
                             transactions_count = Some(count - transaction_data.len() as u64);
                         }
                     };
+                    if transaction_data.is_empty() {
+                        // Nothing in the batch matches the filter; move on to the next one.
+                        current_version = end_of_fetched_version + 1;
+                        continue;
+                    }
                     // Note: this is not the actual bytes transferred to the client.
                     // This is the bytes consumed internally by the server
                     // and ready to be transferred to the client.
@@ -357,7 +377,7 @@ impl RawData for RawDataServerWrapper {
                     }
                     // 3. Update the current version and record current tps.
                     tps_calculator.tick_now(current_batch_size as u64);
-                    current_version = end_of_batch_version + 1;
+                    current_version = end_of_fetched_version + 1;
                     sample!(
                         SampleRate::Duration(Duration::from_secs(15)),
                         info!(
@@ -399,11 +419,7 @@ fn get_transactions_responses_builder(
 ) -> Vec<TransactionsResponse> {
     let transactions: Vec<Transaction> = data
         .into_iter()
-        .map(|(encoded, _)| {
-            let decoded_transaction = base64::decode(encoded).unwrap();
-            let transaction = Transaction::decode(&*decoded_transaction);
-            transaction.unwrap()
-        })
+        .map(|(encoded, _)| decode_transaction(&encoded))
         .collect();
     let chunks = chunk_transactions(transactions, MESSAGE_SIZE_LIMIT);
     chunks
@@ -415,6 +431,11 @@ fn get_transactions_responses_builder(
         .collect::<Vec<TransactionsResponse>>()
 }
 
+fn decode_transaction(encoded: &str) -> Transaction {
+    let decoded_transaction = base64::decode(encoded).unwrap();
+    Transaction::decode(&*decoded_transaction).unwrap()
+}
+
 /// Fetches data from cache or the file store. It returns the data if it is ready in the cache or file store.
 /// Otherwise, it returns the status of the data fetching.
 async fn data_fetch(
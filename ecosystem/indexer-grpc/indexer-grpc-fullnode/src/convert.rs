@@ -164,6 +164,11 @@ pub fn convert_transaction_payload(
                 convert_multisig_payload(mp),
             )),
         },
+        // Batched payloads are not part of the protobuf schema yet.
+        TransactionPayload::BatchedPayload(_) => transaction::TransactionPayload {
+            r#type: transaction::transaction_payload::Type::Unspecified as i32,
+            payload: None,
+        },
 
         // Deprecated. Will be removed in the future.
         TransactionPayload::ModuleBundlePayload(mbp) => transaction::TransactionPayload {
@@ -589,6 +594,11 @@ fn convert_signature(signature: &Signature) -> transaction::AnySignature {
             r#type: transaction::any_signature::Type::Secp256k1Ecdsa as i32,
             signature: s.0.clone(),
         },
+        // Not yet represented in the protobuf schema, the raw bytes are still passed through.
+        Signature::WebAuthn(s) => transaction::AnySignature {
+            r#type: transaction::any_signature::Type::Unspecified as i32,
+            signature: s.0.clone(),
+        },
     }
 }
 
@@ -602,6 +612,11 @@ fn convert_public_key(public_key: &PublicKey) -> transaction::AnyPublicKey {
             r#type: transaction::any_public_key::Type::Secp256k1Ecdsa as i32,
             public_key: p.0.clone(),
         },
+        // Not yet represented in the protobuf schema, the raw bytes are still passed through.
+        PublicKey::Secp256r1Ecdsa(p) => transaction::AnyPublicKey {
+            r#type: transaction::any_public_key::Type::Unspecified as i32,
+            public_key: p.0.clone(),
+        },
     }
 }
 
@@ -30,6 +30,15 @@ pub static FETCHED_TRANSACTION: Lazy<IntCounter> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Number of transactions skipped by the server-side transaction filters
+pub static FILTERED_TRANSACTION: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "indexer_grpc_fullnode_filtered_transaction_count",
+        "Number of transactions skipped by the server-side transaction filters"
+    )
+    .unwrap()
+});
+
 /// Data latency for fullnode fetched the data from storage.
 pub static FETCHED_LATENCY_IN_SECS: Lazy<Gauge> = Lazy::new(|| {
     register_gauge!(
@@ -1,6 +1,7 @@
 // Copyright © Aptos Foundation
 
 use crate::{stream_coordinator::IndexerStreamCoordinator, ServiceContext};
+use aptos_indexer_grpc_utils::transaction_filter::TransactionFilter;
 use aptos_logger::{error, info};
 use aptos_moving_average::MovingAverage;
 use aptos_protos::internal::fullnode::v1::{
@@ -44,6 +45,11 @@ impl FullnodeData for FullnodeDataService {
         // Gets configs for the stream, partly from the request and partly from the node config
         let r = req.into_inner();
         let starting_version = r.starting_version.expect("Starting version must be set");
+        let transaction_filter = match r.transaction_filter.as_ref() {
+            Some(filter) => TransactionFilter::try_from(filter)
+                .map_err(|e| Status::invalid_argument(format!("{:#}", e)))?,
+            None => TransactionFilter::default(),
+        };
         let processor_task_count = self.service_context.processor_task_count;
         let processor_batch_size = self.service_context.processor_batch_size;
         let output_batch_size = self.service_context.output_batch_size;
@@ -67,6 +73,7 @@ impl FullnodeData for FullnodeDataService {
                 processor_task_count,
                 processor_batch_size,
                 output_batch_size,
+                transaction_filter,
                 tx.clone(),
             );
             // Sends init message (one time per request) to the client in the with chain id and starting version. Basically a handshake
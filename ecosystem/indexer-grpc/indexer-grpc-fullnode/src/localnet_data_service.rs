@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{stream_coordinator::IndexerStreamCoordinator, ServiceContext};
+use aptos_indexer_grpc_utils::transaction_filter::TransactionFilter;
 use aptos_logger::error;
 use aptos_protos::{
     indexer::v1::{raw_data_server::RawData, GetTransactionsRequest, TransactionsResponse},
@@ -40,6 +41,11 @@ impl RawData for LocalnetDataService {
         let context = self.service_context.context.clone();
         let r = req.into_inner();
         let starting_version = r.starting_version.expect("Starting version must be set");
+        let transaction_filter = match r.transaction_filter.as_ref() {
+            Some(filter) => TransactionFilter::try_from(filter)
+                .map_err(|e| Status::invalid_argument(format!("{:#}", e)))?,
+            None => TransactionFilter::default(),
+        };
         let processor_batch_size = self.service_context.processor_batch_size;
         let output_batch_size = self.service_context.output_batch_size;
         let ledger_chain_id = context.chain_id().id();
@@ -58,6 +64,7 @@ impl RawData for LocalnetDataService {
                 1,
                 processor_batch_size,
                 output_batch_size,
+                transaction_filter,
                 tx.clone(),
             );
             loop {
@@ -8,7 +8,7 @@ use crate::{
 use aptos_api::context::Context;
 use aptos_config::config::NodeConfig;
 use aptos_logger::info;
-use aptos_mempool::MempoolClientSender;
+use aptos_mempool::{BackPressureStatus, MempoolClientSender};
 use aptos_protos::{
     indexer::v1::{
         raw_data_server::RawDataServer, FILE_DESCRIPTOR_SET as INDEXER_V1_FILE_DESCRIPTOR_SET,
@@ -34,6 +34,7 @@ pub fn bootstrap(
     chain_id: ChainId,
     db: Arc<dyn DbReader>,
     mp_sender: MempoolClientSender,
+    back_pressure_status: BackPressureStatus,
 ) -> Option<Runtime> {
     if !config.indexer_grpc.enabled {
         return None;
@@ -50,7 +51,13 @@ pub fn bootstrap(
     let output_batch_size = node_config.indexer_grpc.output_batch_size;
 
     runtime.spawn(async move {
-        let context = Arc::new(Context::new(chain_id, db, mp_sender, node_config));
+        let context = Arc::new(Context::new(
+            chain_id,
+            db,
+            mp_sender,
+            node_config,
+            back_pressure_status,
+        ));
         let service_context = ServiceContext {
             context: context.clone(),
             processor_task_count,
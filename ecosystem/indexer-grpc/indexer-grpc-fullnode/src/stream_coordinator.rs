@@ -3,12 +3,17 @@
 
 use crate::{
     convert::convert_transaction,
-    counters::{FETCHED_LATENCY_IN_SECS, FETCHED_TRANSACTION, UNABLE_TO_FETCH_TRANSACTION},
+    counters::{
+        FETCHED_LATENCY_IN_SECS, FETCHED_TRANSACTION, FILTERED_TRANSACTION,
+        UNABLE_TO_FETCH_TRANSACTION,
+    },
     runtime::{DEFAULT_NUM_RETRIES, RETRY_TIME_MILLIS},
 };
 use aptos_api::context::Context;
 use aptos_api_types::{AsConverter, Transaction as APITransaction, TransactionOnChainData};
-use aptos_indexer_grpc_utils::{chunk_transactions, constants::MESSAGE_SIZE_LIMIT};
+use aptos_indexer_grpc_utils::{
+    chunk_transactions, constants::MESSAGE_SIZE_LIMIT, transaction_filter::TransactionFilter,
+};
 use aptos_logger::{error, info, sample, sample::SampleRate};
 use aptos_protos::{
     internal::fullnode::v1::{
@@ -33,6 +38,7 @@ pub struct IndexerStreamCoordinator {
     pub processor_batch_size: u16,
     pub output_batch_size: u16,
     pub highest_known_version: u64,
+    pub transaction_filter: Arc<TransactionFilter>,
     pub context: Arc<Context>,
     pub transactions_sender: mpsc::Sender<Result<TransactionsFromNodeResponse, tonic::Status>>,
 }
@@ -51,6 +57,7 @@ impl IndexerStreamCoordinator {
         processor_task_count: u16,
         processor_batch_size: u16,
         output_batch_size: u16,
+        transaction_filter: TransactionFilter,
         transactions_sender: mpsc::Sender<Result<TransactionsFromNodeResponse, tonic::Status>>,
     ) -> Self {
         Self {
@@ -59,6 +66,7 @@ impl IndexerStreamCoordinator {
             processor_batch_size,
             output_batch_size,
             highest_known_version: 0,
+            transaction_filter: Arc::new(transaction_filter),
             context,
             transactions_sender,
         }
@@ -67,11 +75,12 @@ impl IndexerStreamCoordinator {
     /// Fans out a bunch of threads and processes transactions in parallel.
     /// Pushes results in parallel to the stream, but only return that the batch is
     /// fully completed if every job in the batch is successful
-    /// Processing transactions in 4 stages:
+    /// Processing transactions in 5 stages:
     /// 1. Fetch transactions from storage
     /// 2. Convert transactions to rust objects (for example stringifying move structs into json)
-    /// 3. Convert into protobuf objects
-    /// 4. Encode protobuf objects (base64)
+    /// 3. Filter out transactions not requested by the client
+    /// 4. Convert into protobuf objects
+    /// 5. Encode protobuf objects (base64)
     pub async fn process_next_batch(&mut self) -> Vec<Result<EndVersion, Status>> {
         let ledger_chain_id = self.context.chain_id().id();
         let mut tasks = vec![];
@@ -82,6 +91,7 @@ impl IndexerStreamCoordinator {
             let context = self.context.clone();
             let ledger_version = self.highest_known_version;
             let transaction_sender = self.transactions_sender.clone();
+            let transaction_filter = self.transaction_filter.clone();
 
             let task = tokio::spawn(async move {
                 // Fetch and convert transactions from API
@@ -89,7 +99,11 @@ impl IndexerStreamCoordinator {
                     Self::fetch_raw_txns_with_retries(context.clone(), ledger_version, batch).await;
                 let api_txns = Self::convert_to_api_txns(context, raw_txns).await;
                 api_txns.last().map(record_fetched_transaction_latency);
+                // The batch ends at the last fetched version, even if that transaction is
+                // filtered out, so that the client can still check for gaps.
+                let end_version = api_txns.last().unwrap().version().unwrap();
                 let pb_txns = Self::convert_to_pb_txns(api_txns);
+                let pb_txns = Self::filter_pb_txns(&transaction_filter, pb_txns);
                 // Wrap in stream response object and send to channel
                 for chunk in pb_txns.chunks(output_batch_size as usize) {
                     for chunk in chunk_transactions(chunk.to_vec(), MESSAGE_SIZE_LIMIT) {
@@ -110,7 +124,7 @@ impl IndexerStreamCoordinator {
                         }
                     }
                 }
-                Ok(pb_txns.last().unwrap().version)
+                Ok(end_version)
             });
             tasks.push(task);
         }
@@ -323,6 +337,22 @@ impl IndexerStreamCoordinator {
         transactions
     }
 
+    fn filter_pb_txns(
+        transaction_filter: &TransactionFilter,
+        pb_txns: Vec<TransactionPB>,
+    ) -> Vec<TransactionPB> {
+        if transaction_filter.is_empty() {
+            return pb_txns;
+        }
+        let num_fetched = pb_txns.len();
+        let filtered_txns: Vec<_> = pb_txns
+            .into_iter()
+            .filter(|txn| transaction_filter.matches(txn))
+            .collect();
+        FILTERED_TRANSACTION.inc_by((num_fetched - filtered_txns.len()) as u64);
+        filtered_txns
+    }
+
     fn convert_to_pb_txns(api_txns: Vec<APITransaction>) -> Vec<TransactionPB> {
         api_txns
             .iter()
@@ -5,6 +5,7 @@ pub mod cache_operator;
 pub mod config;
 pub mod constants;
 pub mod file_store_operator;
+pub mod transaction_filter;
 pub mod types;
 
 use anyhow::{Context, Result};
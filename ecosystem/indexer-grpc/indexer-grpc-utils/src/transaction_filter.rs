@@ -0,0 +1,425 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, Context};
+use aptos_protos::{
+    indexer::v1::TransactionFilter as RawDataTransactionFilterPB,
+    internal::fullnode::v1::TransactionFilter as TransactionFilterPB,
+    transaction::v1::{
+        multisig_transaction_payload, transaction::TxnData, transaction_payload,
+        write_set_change::Change, Event, Transaction,
+    },
+};
+use move_core_types::{
+    account_address::AccountAddress, identifier::Identifier, language_storage::StructTag,
+};
+use std::str::FromStr;
+
+/// A server-side filter on the transactions of a single stream, evaluated on
+/// the protobuf transactions sent to the client. A transaction matches if one
+/// of its events, written resources or its entry function matches one of the
+/// criteria. An empty filter matches every transaction.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TransactionFilter {
+    event_types: Vec<StructTag>,
+    write_resource_types: Vec<StructTag>,
+    /// Entry functions, or modules (without function name) whose entry functions all match
+    entry_functions: Vec<(AccountAddress, Identifier, Option<Identifier>)>,
+}
+
+impl TransactionFilter {
+    /// Returns true iff the filter has no criteria (i.e., everything matches)
+    pub fn is_empty(&self) -> bool {
+        self.event_types.is_empty()
+            && self.write_resource_types.is_empty()
+            && self.entry_functions.is_empty()
+    }
+
+    /// Returns true iff the given transaction should be sent to the client
+    pub fn matches(&self, txn: &Transaction) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+
+        let events: &[Event] = match &txn.txn_data {
+            Some(TxnData::User(txn)) => &txn.events,
+            Some(TxnData::Genesis(txn)) => &txn.events,
+            Some(TxnData::BlockMetadata(txn)) => &txn.events,
+            Some(TxnData::StateCheckpoint(_)) | None => &[],
+        };
+        if events
+            .iter()
+            .any(|event| matches_any_struct_tag(&self.event_types, &event.type_str))
+        {
+            return true;
+        }
+
+        if let Some(info) = &txn.info {
+            if info.changes.iter().any(|change| match &change.change {
+                Some(Change::WriteResource(write_resource)) => {
+                    matches_any_struct_tag(&self.write_resource_types, &write_resource.type_str)
+                },
+                _ => false,
+            }) {
+                return true;
+            }
+        }
+
+        match get_entry_function_id(txn) {
+            Some((address, module, function)) => {
+                self.matches_entry_function(address, module, function)
+            },
+            None => false,
+        }
+    }
+
+    fn matches_entry_function(&self, address: &str, module: &str, function: &str) -> bool {
+        let address = match AccountAddress::from_str(address) {
+            Ok(address) => address,
+            Err(_) => return false,
+        };
+        self.entry_functions
+            .iter()
+            .any(|(filter_address, filter_module, filter_function)| {
+                *filter_address == address
+                    && filter_module.as_str() == module
+                    && filter_function
+                        .as_ref()
+                        .map_or(true, |filter_function| filter_function.as_str() == function)
+            })
+    }
+
+    /// Parses the criteria of a filter request. Entry functions are either
+    /// functions (e.g. `0x1::coin::transfer`) or modules (e.g. `0x1::coin`).
+    fn parse(
+        event_types: &[String],
+        write_resource_types: &[String],
+        entry_functions: &[String],
+    ) -> anyhow::Result<Self> {
+        let parse_struct_tags = |tags: &[String]| {
+            tags.iter()
+                .map(|tag| {
+                    StructTag::from_str(tag)
+                        .with_context(|| format!("Invalid struct tag in the filter: {}", tag))
+                })
+                .collect::<anyhow::Result<Vec<_>>>()
+        };
+        let entry_functions = entry_functions
+            .iter()
+            .map(|function| {
+                parse_entry_function(function).with_context(|| {
+                    format!(
+                        "Invalid entry function or module in the filter: {}",
+                        function
+                    )
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Ok(Self {
+            event_types: parse_struct_tags(event_types)?,
+            write_resource_types: parse_struct_tags(write_resource_types)?,
+            entry_functions,
+        })
+    }
+}
+
+impl TryFrom<&TransactionFilterPB> for TransactionFilter {
+    type Error = anyhow::Error;
+
+    fn try_from(filter: &TransactionFilterPB) -> Result<Self, Self::Error> {
+        Self::parse(
+            &filter.event_types,
+            &filter.write_resource_types,
+            &filter.entry_functions,
+        )
+    }
+}
+
+impl TryFrom<&RawDataTransactionFilterPB> for TransactionFilter {
+    type Error = anyhow::Error;
+
+    fn try_from(filter: &RawDataTransactionFilterPB) -> Result<Self, Self::Error> {
+        Self::parse(&filter.event_types, &[], &filter.entry_functions)
+    }
+}
+
+/// Returns true iff the type is a struct matching one of the filter tags.
+/// Filter tags without type arguments match every instantiation of the struct.
+fn matches_any_struct_tag(filter_tags: &[StructTag], type_str: &str) -> bool {
+    if filter_tags.is_empty() {
+        return false;
+    }
+    // Values of non-struct types never match
+    let tag = match StructTag::from_str(type_str) {
+        Ok(tag) => tag,
+        Err(_) => return false,
+    };
+    filter_tags.iter().any(|filter_tag| {
+        filter_tag.address == tag.address
+            && filter_tag.module == tag.module
+            && filter_tag.name == tag.name
+            && (filter_tag.type_params.is_empty() || filter_tag.type_params == tag.type_params)
+    })
+}
+
+fn parse_entry_function(
+    function: &str,
+) -> anyhow::Result<(AccountAddress, Identifier, Option<Identifier>)> {
+    let parts: Vec<_> = function.split("::").collect();
+    let (address, module, function) = match parts.as_slice() {
+        [address, module] => (address, module, None),
+        [address, module, function] => (address, module, Some(Identifier::new(*function)?)),
+        _ => bail!("expected <address>::<module>[::<function>]"),
+    };
+    Ok((
+        AccountAddress::from_str(address)?,
+        Identifier::new(*module)?,
+        function,
+    ))
+}
+
+/// Returns the address, module and name of the entry function called by the
+/// transaction (if any)
+fn get_entry_function_id(txn: &Transaction) -> Option<(&str, &str, &str)> {
+    let payload = match &txn.txn_data {
+        Some(TxnData::User(txn)) => txn.request.as_ref()?.payload.as_ref()?.payload.as_ref()?,
+        _ => return None,
+    };
+    let payload = match payload {
+        transaction_payload::Payload::EntryFunctionPayload(payload) => payload,
+        transaction_payload::Payload::MultisigPayload(payload) => {
+            match payload.transaction_payload.as_ref()?.payload.as_ref()? {
+                multisig_transaction_payload::Payload::EntryFunctionPayload(payload) => payload,
+            }
+        },
+        _ => return None,
+    };
+    let function = payload.function.as_ref()?;
+    let module = function.module.as_ref()?;
+    Some((&module.address, &module.name, &function.name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_protos::transaction::v1::{
+        EntryFunctionId, EntryFunctionPayload, MoveModuleId, MultisigPayload,
+        MultisigTransactionPayload, TransactionInfo, TransactionPayload, UserTransaction,
+        UserTransactionRequest, WriteResource, WriteSetChange,
+    };
+
+    fn create_filter(
+        event_types: &[&str],
+        write_resource_types: &[&str],
+        entry_functions: &[&str],
+    ) -> TransactionFilter {
+        let to_strings = |values: &[&str]| values.iter().map(|value| value.to_string()).collect();
+        TransactionFilter::try_from(&TransactionFilterPB {
+            event_types: to_strings(event_types),
+            write_resource_types: to_strings(write_resource_types),
+            entry_functions: to_strings(entry_functions),
+        })
+        .unwrap()
+    }
+
+    fn entry_function_payload(address: &str, module: &str, name: &str) -> EntryFunctionPayload {
+        EntryFunctionPayload {
+            function: Some(EntryFunctionId {
+                module: Some(MoveModuleId {
+                    address: address.into(),
+                    name: module.into(),
+                }),
+                name: name.into(),
+            }),
+            ..Default::default()
+        }
+    }
+
+    fn create_user_transaction(
+        event_type: &str,
+        write_resource_type: &str,
+        payload: transaction_payload::Payload,
+    ) -> Transaction {
+        Transaction {
+            version: 1,
+            info: Some(TransactionInfo {
+                changes: vec![WriteSetChange {
+                    change: Some(Change::WriteResource(WriteResource {
+                        address: "0x1".into(),
+                        type_str: write_resource_type.into(),
+                        ..Default::default()
+                    })),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            txn_data: Some(TxnData::User(UserTransaction {
+                request: Some(UserTransactionRequest {
+                    payload: Some(TransactionPayload {
+                        payload: Some(payload),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                }),
+                events: vec![Event {
+                    type_str: event_type.into(),
+                    ..Default::default()
+                }],
+            })),
+            ..Default::default()
+        }
+    }
+
+    fn create_coin_transfer() -> Transaction {
+        create_user_transaction(
+            "0x1::coin::CoinDeposit<0x1::aptos_coin::AptosCoin>",
+            "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>",
+            transaction_payload::Payload::EntryFunctionPayload(entry_function_payload(
+                "0x1", "coin", "transfer",
+            )),
+        )
+    }
+
+    #[test]
+    fn test_empty_filter() {
+        let filter = TransactionFilter::default();
+        assert!(filter.is_empty());
+        assert!(filter.matches(&create_coin_transfer()));
+        assert!(filter.matches(&Transaction::default()));
+    }
+
+    #[test]
+    fn test_event_type_filter() {
+        let txn = create_coin_transfer();
+        assert!(create_filter(&["0x1::coin::CoinDeposit"], &[], &[]).matches(&txn));
+        assert!(create_filter(
+            &["0x1::coin::CoinDeposit<0x1::aptos_coin::AptosCoin>"],
+            &[],
+            &[]
+        )
+        .matches(&txn));
+        // Addresses are normalized before matching
+        assert!(create_filter(
+            &["0x0000000000000000000000000000000000000000000000000000000000000001::coin::CoinDeposit"],
+            &[],
+            &[]
+        )
+        .matches(&txn));
+        assert!(
+            !create_filter(&["0x1::coin::CoinDeposit<0x1::test::Coin>"], &[], &[]).matches(&txn)
+        );
+        assert!(!create_filter(&["0x1::coin::CoinWithdraw"], &[], &[]).matches(&txn));
+    }
+
+    #[test]
+    fn test_write_resource_type_filter() {
+        let txn = create_coin_transfer();
+        assert!(create_filter(&[], &["0x1::coin::CoinStore"], &[]).matches(&txn));
+        assert!(create_filter(
+            &[],
+            &["0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>"],
+            &[]
+        )
+        .matches(&txn));
+        assert!(!create_filter(&[], &["0x1::coin::CoinStore<0x1::test::Coin>"], &[]).matches(&txn));
+        assert!(!create_filter(&[], &["0x1::account::Account"], &[]).matches(&txn));
+    }
+
+    #[test]
+    fn test_entry_function_filter() {
+        let txn = create_coin_transfer();
+        assert!(create_filter(&[], &[], &["0x1::coin::transfer"]).matches(&txn));
+        assert!(create_filter(&[], &[], &["0x1::coin"]).matches(&txn));
+        assert!(!create_filter(&[], &[], &["0x1::aptos_account::transfer"]).matches(&txn));
+        assert!(!create_filter(&[], &[], &["0x2::coin"]).matches(&txn));
+
+        let multisig = create_user_transaction(
+            "0x1::multisig_account::TransactionExecutionSucceeded",
+            "0x1::multisig_account::MultisigAccount",
+            transaction_payload::Payload::MultisigPayload(MultisigPayload {
+                transaction_payload: Some(MultisigTransactionPayload {
+                    payload: Some(multisig_transaction_payload::Payload::EntryFunctionPayload(
+                        entry_function_payload("0x1", "coin", "transfer"),
+                    )),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+        );
+        assert!(create_filter(&[], &[], &["0x1::coin::transfer"]).matches(&multisig));
+    }
+
+    #[test]
+    fn test_raw_data_filter() {
+        let txn = create_coin_transfer();
+        let filter = TransactionFilter::try_from(&RawDataTransactionFilterPB {
+            event_types: vec!["0x1::coin::CoinWithdraw".into()],
+            entry_functions: vec!["0x1::aptos_account".into(), "0x1::coin::transfer".into()],
+        })
+        .unwrap();
+        assert!(filter.matches(&txn));
+
+        let filter = TransactionFilter::try_from(&RawDataTransactionFilterPB {
+            event_types: vec!["0x1::coin::CoinWithdraw".into()],
+            entry_functions: vec!["0x1::aptos_account".into()],
+        })
+        .unwrap();
+        assert!(!filter.matches(&txn));
+    }
+
+    #[test]
+    fn test_any_criteria_matches() {
+        let txn = create_coin_transfer();
+        assert!(
+            create_filter(&["0x1::coin::CoinWithdraw"], &["0x1::account::Account"], &[
+                "0x1::coin"
+            ])
+            .matches(&txn)
+        );
+        assert!(
+            create_filter(&["0x1::coin::CoinWithdraw"], &["0x1::coin::CoinStore"], &[
+                "0x1::aptos_account"
+            ])
+            .matches(&txn)
+        );
+        assert!(
+            create_filter(&["0x1::coin::CoinDeposit"], &["0x1::account::Account"], &[
+                "0x1::aptos_account"
+            ])
+            .matches(&txn)
+        );
+        assert!(
+            !create_filter(&["0x1::coin::CoinWithdraw"], &["0x1::account::Account"], &[
+                "0x1::aptos_account"
+            ])
+            .matches(&txn)
+        );
+        // Transactions without events, changes or entry function only match the empty filter
+        assert!(!create_filter(&[], &[], &["0x1::coin"]).matches(&Transaction::default()));
+    }
+
+    #[test]
+    fn test_invalid_filter() {
+        for filter in [
+            TransactionFilterPB {
+                event_types: vec!["0x1::coin".into()],
+                ..Default::default()
+            },
+            TransactionFilterPB {
+                write_resource_types: vec!["not a struct tag".into()],
+                ..Default::default()
+            },
+            TransactionFilterPB {
+                entry_functions: vec!["transfer".into()],
+                ..Default::default()
+            },
+            TransactionFilterPB {
+                entry_functions: vec!["0x1::coin::transfer::all".into()],
+                ..Default::default()
+            },
+        ] {
+            assert!(TransactionFilter::try_from(&filter).is_err());
+        }
+    }
+}
@@ -6,7 +6,8 @@ use crate::{add_accounts_impl, PipelineConfig};
 use aptos_config::{
     config::{
         PrunerConfig, RocksdbConfigs, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
-        DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
+        DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_LEDGER_TIERING_CONFIG,
+        NO_OP_STORAGE_PRUNER_CONFIG,
     },
     utils::get_genesis_txn,
 };
@@ -70,8 +71,10 @@ fn bootstrap_with_genesis(db_dir: impl AsRef<Path>, enable_storage_sharding: boo
             StorageDirPaths::from_path(db_dir),
             false, /* readonly */
             NO_OP_STORAGE_PRUNER_CONFIG,
+            &NO_OP_LEDGER_TIERING_CONFIG,
             rocksdb_configs,
             false, /* indexer */
+            false, /* event type index */
             BUFFERED_STATE_TARGET_ITEMS,
             DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
         )
@@ -62,8 +62,10 @@ where
             config.storage.get_dir_paths(),
             false, /* readonly */
             config.storage.storage_pruner_config,
+            &config.storage.ledger_tiering_config,
             config.storage.rocksdb_configs,
             false,
+            config.storage.enable_event_type_index,
             config.storage.buffered_state_target_items,
             config.storage.max_num_nodes_per_lru_cache_shard,
         )
@@ -7,7 +7,9 @@ use aptos_crypto::hash::HashValue;
 use aptos_executor::block_executor::{BlockExecutor, TransactionBlockExecutor};
 use aptos_executor_types::BlockExecutorTrait;
 use aptos_logger::info;
-use aptos_types::block_executor::partitioner::ExecutableBlock;
+use aptos_types::{
+    block_executor::partitioner::ExecutableBlock, on_chain_config::BlockExecutionLimit,
+};
 use std::{
     sync::{mpsc, Arc},
     time::{Duration, Instant},
@@ -57,7 +59,11 @@ where
         let num_txns = executable_block.transactions.num_transactions();
         let output = self
             .executor
-            .execute_and_state_checkpoint(executable_block, self.parent_block_id, None)
+            .execute_and_state_checkpoint(
+                executable_block,
+                self.parent_block_id,
+                BlockExecutionLimit::no_limit(),
+            )
             .unwrap();
 
         assert_eq!(output.txn_statuses().len(), num_txns);
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 use aptos_types::{
     block_executor::partitioner::{ShardId, SubBlocksForShard},
+    on_chain_config::BlockExecutionLimit,
     state_store::{state_key::StateKey, state_value::StateValue},
     transaction::{analyzed_transaction::AnalyzedTransaction, TransactionOutput},
     vm_status::VMStatus,
@@ -45,15 +46,21 @@ pub enum RemoteExecutionRequest {
 pub struct ExecuteBlockCommand {
     pub(crate) sub_blocks: SubBlocksForShard<AnalyzedTransaction>,
     pub(crate) concurrency_level: usize,
-    pub(crate) maybe_block_gas_limit: Option<u64>,
+    pub(crate) block_execution_limit: BlockExecutionLimit,
 }
 
 impl ExecuteBlockCommand {
-    pub fn into(self) -> (SubBlocksForShard<AnalyzedTransaction>, usize, Option<u64>) {
+    pub fn into(
+        self,
+    ) -> (
+        SubBlocksForShard<AnalyzedTransaction>,
+        usize,
+        BlockExecutionLimit,
+    ) {
         (
             self.sub_blocks,
             self.concurrency_level,
-            self.maybe_block_gas_limit,
+            self.block_execution_limit,
         )
     }
 }
@@ -8,6 +8,7 @@ use aptos_language_e2e_tests::{
 use aptos_types::{
     account_address::AccountAddress,
     block_executor::partitioner::PartitionedTransactions,
+    on_chain_config::BlockExecutionLimit,
     state_store::state_key::StateKeyInner,
     transaction::{
         analyzed_transaction::AnalyzedTransaction,
@@ -125,7 +126,7 @@ pub fn test_sharded_block_executor_no_conflict<E: ExecutorClient<FakeDataStore>>
             Arc::new(executor.data_store().clone()),
             partitioned_txns.clone(),
             2,
-            None,
+            BlockExecutionLimit::no_limit(),
         )
         .unwrap();
     let txns: Vec<SignatureVerifiedTransaction> =
@@ -133,7 +134,12 @@ pub fn test_sharded_block_executor_no_conflict<E: ExecutorClient<FakeDataStore>>
             .into_iter()
             .map(|t| t.into_txn())
             .collect();
-    let unsharded_txn_output = AptosVM::execute_block(&txns, executor.data_store(), None).unwrap();
+    let unsharded_txn_output = AptosVM::execute_block(
+        &txns,
+        executor.data_store(),
+        BlockExecutionLimit::no_limit(),
+    )
+    .unwrap();
     compare_txn_outputs(unsharded_txn_output, sharded_txn_output);
     sharded_block_executor.shutdown();
 }
@@ -182,12 +188,16 @@ pub fn sharded_block_executor_with_conflict<E: ExecutorClient<FakeDataStore>>(
             Arc::new(executor.data_store().clone()),
             partitioned_txns,
             concurrency,
-            None,
+            BlockExecutionLimit::no_limit(),
         )
         .unwrap();
 
-    let unsharded_txn_output =
-        AptosVM::execute_block(&execution_ordered_txns, executor.data_store(), None).unwrap();
+    let unsharded_txn_output = AptosVM::execute_block(
+        &execution_ordered_txns,
+        executor.data_store(),
+        BlockExecutionLimit::no_limit(),
+    )
+    .unwrap();
     compare_txn_outputs(unsharded_txn_output, sharded_txn_output);
     sharded_block_executor.shutdown();
 }
@@ -15,6 +15,7 @@ use aptos_types::{
     contract_event::ContractEvent,
     epoch_state::EpochState,
     ledger_info::LedgerInfoWithSignatures,
+    on_chain_config::BlockExecutionLimit,
     proof::{AccumulatorExtensionProof, SparseMerkleProofExt},
     state_store::{state_key::StateKey, state_value::StateValue},
     transaction::{
@@ -106,6 +107,25 @@ pub trait ChunkExecutorTrait: Send + Sync {
         epoch_change_li: Option<&LedgerInfoWithSignatures>,
     ) -> Result<()>;
 
+    /// Verifies the transaction outputs based on the provided proofs and ledger info, without
+    /// applying them. This doesn't depend on the previous chunks, so it can run ahead of (and in
+    /// parallel with) applying them.
+    fn verify_chunk_by_transaction_outputs(
+        &self,
+        txn_output_list_with_proof: &TransactionOutputListWithProof,
+        // Target LI that has been verified independently: the proofs are relative to this version.
+        verified_target_li: &LedgerInfoWithSignatures,
+    ) -> Result<()>;
+
+    /// Similar to `enqueue_chunk_by_transaction_outputs`, but the transaction outputs have already
+    /// been verified by `verify_chunk_by_transaction_outputs`, so the verification is skipped.
+    fn enqueue_verified_chunk_by_transaction_outputs(
+        &self,
+        verified_txn_output_list_with_proof: TransactionOutputListWithProof,
+        verified_target_li: &LedgerInfoWithSignatures,
+        epoch_change_li: Option<&LedgerInfoWithSignatures>,
+    ) -> Result<()>;
+
     /// As a separate stage, calculate the transaction accumulator changes, prepare for db commission.
     fn update_ledger(&self) -> Result<()>;
 
@@ -138,11 +158,11 @@ pub trait BlockExecutorTrait: Send + Sync {
         &self,
         block: ExecutableBlock,
         parent_block_id: HashValue,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> ExecutorResult<StateComputeResult> {
         let block_id = block.block_id;
         let state_checkpoint_output =
-            self.execute_and_state_checkpoint(block, parent_block_id, maybe_block_gas_limit)?;
+            self.execute_and_state_checkpoint(block, parent_block_id, block_execution_limit)?;
         self.ledger_update(block_id, parent_block_id, state_checkpoint_output)
     }
 
@@ -151,7 +171,7 @@ pub trait BlockExecutorTrait: Send + Sync {
         &self,
         block: ExecutableBlock,
         parent_block_id: HashValue,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> ExecutorResult<StateCheckpointOutput>;
 
     fn ledger_update(
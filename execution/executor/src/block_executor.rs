@@ -33,6 +33,7 @@ use aptos_storage_interface::{
 use aptos_types::{
     block_executor::partitioner::{ExecutableBlock, ExecutableTransactions},
     ledger_info::LedgerInfoWithSignatures,
+    on_chain_config::BlockExecutionLimit,
     state_store::state_value::StateValue,
 };
 use aptos_vm::AptosVM;
@@ -43,7 +44,7 @@ pub trait TransactionBlockExecutor: Send + Sync {
     fn execute_transaction_block(
         transactions: ExecutableTransactions,
         state_view: CachedStateView,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> Result<ChunkOutput>;
 }
 
@@ -51,12 +52,12 @@ impl TransactionBlockExecutor for AptosVM {
     fn execute_transaction_block(
         transactions: ExecutableTransactions,
         state_view: CachedStateView,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> Result<ChunkOutput> {
         ChunkOutput::by_transaction_execution::<AptosVM>(
             transactions,
             state_view,
-            maybe_block_gas_limit,
+            block_execution_limit,
         )
     }
 }
@@ -115,14 +116,14 @@ where
         &self,
         block: ExecutableBlock,
         parent_block_id: HashValue,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> ExecutorResult<StateCheckpointOutput> {
         self.maybe_initialize()?;
         self.inner
             .read()
             .as_ref()
             .expect("BlockExecutor is not reset")
-            .execute_and_state_checkpoint(block, parent_block_id, maybe_block_gas_limit)
+            .execute_and_state_checkpoint(block, parent_block_id, block_execution_limit)
     }
 
     fn ledger_update(
@@ -193,7 +194,7 @@ where
         &self,
         block: ExecutableBlock,
         parent_block_id: HashValue,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> ExecutorResult<StateCheckpointOutput> {
         let _timer = APTOS_EXECUTOR_EXECUTE_BLOCK_SECONDS.start_timer();
         let ExecutableBlock {
@@ -246,7 +247,7 @@ where
                             "Injected error in vm_execute_block"
                         )))
                     });
-                    V::execute_transaction_block(transactions, state_view, maybe_block_gas_limit)?
+                    V::execute_transaction_block(transactions, state_view, block_execution_limit)?
                 };
 
                 let _timer = APTOS_EXECUTOR_OTHER_TIMERS_SECONDS
@@ -256,7 +257,7 @@ where
                 THREAD_MANAGER.get_exe_cpu_pool().install(|| {
                     chunk_output.into_state_checkpoint_output(
                         parent_output.state(),
-                        maybe_block_gas_limit.map(|_| block_id),
+                        block_execution_limit.is_limited().then_some(block_id),
                     )
                 })?
             };
@@ -426,6 +427,7 @@ where
         self.block_tree
             .prune(ledger_info_with_sigs.ledger_info())
             .expect("Failure pruning block tree.");
+        AptosVM::advance_simulation_output_cache(target_version);
 
         Ok(())
     }
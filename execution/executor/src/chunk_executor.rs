@@ -35,6 +35,7 @@ use aptos_storage_interface::{
 use aptos_types::{
     contract_event::ContractEvent,
     ledger_info::LedgerInfoWithSignatures,
+    on_chain_config::BlockExecutionLimit,
     transaction::{
         signature_verified_transaction::SignatureVerifiedTransaction, Transaction, TransactionInfo,
         TransactionListWithProof, TransactionOutput, TransactionOutputListWithProof,
@@ -112,6 +113,31 @@ impl<V: VMExecutor> ChunkExecutorTrait for ChunkExecutor<V> {
             )
     }
 
+    fn verify_chunk_by_transaction_outputs(
+        &self,
+        txn_output_list_with_proof: &TransactionOutputListWithProof,
+        verified_target_li: &LedgerInfoWithSignatures,
+    ) -> Result<()> {
+        verify_output_chunk(txn_output_list_with_proof, verified_target_li)
+    }
+
+    fn enqueue_verified_chunk_by_transaction_outputs(
+        &self,
+        verified_txn_output_list_with_proof: TransactionOutputListWithProof,
+        verified_target_li: &LedgerInfoWithSignatures,
+        epoch_change_li: Option<&LedgerInfoWithSignatures>,
+    ) -> Result<()> {
+        self.inner
+            .read()
+            .as_ref()
+            .expect("not reset")
+            .enqueue_verified_chunk_by_transaction_outputs(
+                verified_txn_output_list_with_proof,
+                verified_target_li,
+                epoch_change_li,
+            )
+    }
+
     fn update_ledger(&self) -> Result<()> {
         self.inner
             .read()
@@ -272,7 +298,11 @@ impl<V: VMExecutor> ChunkExecutorInner<V> {
         let chunk_output = {
             let _timer = APTOS_EXECUTOR_VM_EXECUTE_CHUNK_SECONDS.start_timer();
             // State sync executor shouldn't have block gas limit.
-            ChunkOutput::by_transaction_execution::<V>(sig_verified_txns.into(), state_view, None)?
+            ChunkOutput::by_transaction_execution::<V>(
+                sig_verified_txns.into(),
+                state_view,
+                BlockExecutionLimit::no_limit(),
+            )?
         };
 
         // Calcualte state snapshot
@@ -312,12 +342,35 @@ impl<V: VMExecutor> ChunkExecutorInner<V> {
         txn_output_list_with_proof: TransactionOutputListWithProof,
         verified_target_li: &LedgerInfoWithSignatures,
         epoch_change_li: Option<&LedgerInfoWithSignatures>,
+    ) -> Result<()> {
+        {
+            let _timer = APTOS_CHUNK_EXECUTOR_OTHER_SECONDS.timer_with(&["apply_chunk__verify"]);
+            // Verify input transaction list.
+            THREAD_MANAGER
+                .get_exe_cpu_pool()
+                .install(|| verify_output_chunk(&txn_output_list_with_proof, verified_target_li))?;
+        }
+
+        self.enqueue_verified_chunk_by_transaction_outputs(
+            txn_output_list_with_proof,
+            verified_target_li,
+            epoch_change_li,
+        )
+    }
+
+    fn enqueue_verified_chunk_by_transaction_outputs(
+        &self,
+        verified_txn_output_list_with_proof: TransactionOutputListWithProof,
+        verified_target_li: &LedgerInfoWithSignatures,
+        epoch_change_li: Option<&LedgerInfoWithSignatures>,
     ) -> Result<()> {
         let _timer = APTOS_EXECUTOR_APPLY_CHUNK_SECONDS.start_timer();
 
-        let num_txns = txn_output_list_with_proof.transactions_and_outputs.len();
+        let num_txns = verified_txn_output_list_with_proof
+            .transactions_and_outputs
+            .len();
         ensure!(num_txns != 0, "Empty transaction list!");
-        let first_version_in_request = txn_output_list_with_proof
+        let first_version_in_request = verified_txn_output_list_with_proof
             .first_transaction_output_version
             .ok_or_else(|| anyhow!("Non-empty chunk with first_version == None."))?;
         let parent_state = self.commit_queue.lock().latest_state();
@@ -328,23 +381,11 @@ impl<V: VMExecutor> ChunkExecutorInner<V> {
             parent_state.current_version,
         );
 
-        {
-            let _timer = APTOS_CHUNK_EXECUTOR_OTHER_SECONDS.timer_with(&["apply_chunk__verify"]);
-            // Verify input transaction list.
-            THREAD_MANAGER
-                .get_exe_cpu_pool()
-                .install(|| -> Result<()> {
-                    txn_output_list_with_proof.verify(
-                        verified_target_li.ledger_info(),
-                        Some(first_version_in_request),
-                    )
-                })?;
-        }
         let TransactionOutputListWithProof {
             transactions_and_outputs,
             first_transaction_output_version: _,
             proof: txn_infos_with_proof,
-        } = txn_output_list_with_proof;
+        } = verified_txn_output_list_with_proof;
         let verified_target_li = verified_target_li.clone();
         let epoch_change_li = epoch_change_li.cloned();
         let known_state_checkpoints: Vec<_> = txn_infos_with_proof
@@ -497,6 +538,17 @@ fn verify_chunk(
     Ok(())
 }
 
+/// Verifies the transaction output list proof against the ledger info.
+fn verify_output_chunk(
+    txn_output_list_with_proof: &TransactionOutputListWithProof,
+    verified_target_li: &LedgerInfoWithSignatures,
+) -> Result<()> {
+    txn_output_list_with_proof.verify(
+        verified_target_li.ledger_info(),
+        txn_output_list_with_proof.first_transaction_output_version,
+    )
+}
+
 impl<V: VMExecutor> TransactionReplayer for ChunkExecutor<V> {
     fn replay(
         &self,
@@ -674,8 +726,11 @@ impl<V: VMExecutor> ChunkExecutorInner<V> {
             .collect::<Vec<SignatureVerifiedTransaction>>();
 
         // State sync executor shouldn't have block gas limit.
-        let chunk_output =
-            ChunkOutput::by_transaction_execution::<V>(txns.into(), state_view, None)?;
+        let chunk_output = ChunkOutput::by_transaction_execution::<V>(
+            txns.into(),
+            state_view,
+            BlockExecutionLimit::no_limit(),
+        )?;
         // not `zip_eq`, deliberately
         for (version, txn_out, txn_info, write_set, events) in multizip((
             begin_version..end_version,
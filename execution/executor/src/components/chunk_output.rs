@@ -23,6 +23,7 @@ use aptos_types::{
     block_executor::partitioner::{ExecutableTransactions, PartitionedTransactions},
     contract_event::ContractEvent,
     epoch_state::EpochState,
+    on_chain_config::BlockExecutionLimit,
     transaction::{
         signature_verified_transaction::{SignatureVerifiedTransaction, TransactionProvider},
         ExecutionStatus, Transaction, TransactionOutput, TransactionOutputProvider,
@@ -49,18 +50,18 @@ impl ChunkOutput {
     pub fn by_transaction_execution<V: VMExecutor>(
         transactions: ExecutableTransactions,
         state_view: CachedStateView,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> Result<Self> {
         match transactions {
             ExecutableTransactions::Unsharded(txns) => {
                 Self::by_transaction_execution_unsharded::<V>(
                     txns,
                     state_view,
-                    maybe_block_gas_limit,
+                    block_execution_limit,
                 )
             },
             ExecutableTransactions::Sharded(txns) => {
-                Self::by_transaction_execution_sharded::<V>(txns, state_view, maybe_block_gas_limit)
+                Self::by_transaction_execution_sharded::<V>(txns, state_view, block_execution_limit)
             },
         }
     }
@@ -68,10 +69,10 @@ impl ChunkOutput {
     fn by_transaction_execution_unsharded<V: VMExecutor>(
         transactions: Vec<SignatureVerifiedTransaction>,
         state_view: CachedStateView,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> Result<Self> {
         let transaction_outputs =
-            Self::execute_block::<V>(&transactions, &state_view, maybe_block_gas_limit)?;
+            Self::execute_block::<V>(&transactions, &state_view, block_execution_limit)?;
 
         Ok(Self {
             transactions: transactions.into_iter().map(|t| t.into_inner()).collect(),
@@ -83,13 +84,13 @@ impl ChunkOutput {
     pub fn by_transaction_execution_sharded<V: VMExecutor>(
         transactions: PartitionedTransactions,
         state_view: CachedStateView,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> Result<Self> {
         let state_view_arc = Arc::new(state_view);
         let transaction_outputs = Self::execute_block_sharded::<V>(
             transactions.clone(),
             state_view_arc.clone(),
-            maybe_block_gas_limit,
+            block_execution_limit,
         )?;
 
         // TODO(skedia) add logic to emit counters per shard instead of doing it globally.
@@ -174,21 +175,21 @@ impl ChunkOutput {
     fn execute_block_sharded<V: VMExecutor>(
         partitioned_txns: PartitionedTransactions,
         state_view: Arc<CachedStateView>,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> Result<Vec<TransactionOutput>> {
         if !get_remote_addresses().is_empty() {
             Ok(V::execute_block_sharded(
                 REMOTE_SHARDED_BLOCK_EXECUTOR.lock().deref(),
                 partitioned_txns,
                 state_view,
-                maybe_block_gas_limit,
+                block_execution_limit,
             )?)
         } else {
             Ok(V::execute_block_sharded(
                 SHARDED_BLOCK_EXECUTOR.lock().deref(),
                 partitioned_txns,
                 state_view,
-                maybe_block_gas_limit,
+                block_execution_limit,
             )?)
         }
     }
@@ -199,12 +200,12 @@ impl ChunkOutput {
     fn execute_block<V: VMExecutor>(
         transactions: &[SignatureVerifiedTransaction],
         state_view: &CachedStateView,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> Result<Vec<TransactionOutput>> {
         Ok(V::execute_block(
             transactions,
             state_view,
-            maybe_block_gas_limit,
+            block_execution_limit,
         )?)
     }
 
@@ -216,7 +217,7 @@ impl ChunkOutput {
     fn execute_block<V: VMExecutor>(
         transactions: &[SignatureVerifiedTransaction],
         state_view: &CachedStateView,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> Result<Vec<TransactionOutput>> {
         use aptos_state_view::{StateViewId, TStateView};
         use aptos_types::write_set::WriteSet;
@@ -224,7 +225,7 @@ impl ChunkOutput {
         let transaction_outputs = match state_view.id() {
             // this state view ID implies a genesis block in non-test cases.
             StateViewId::Miscellaneous => {
-                V::execute_block(transactions, state_view, maybe_block_gas_limit)?
+                V::execute_block(transactions, state_view, block_execution_limit)?
             },
             _ => transactions
                 .iter()
@@ -326,6 +327,7 @@ pub fn update_counters_for_processed_chunk<T, O>(
             Some(Transaction::UserTransaction(_)) => "user_transaction",
             Some(Transaction::GenesisTransaction(_)) => "genesis",
             Some(Transaction::BlockMetadata(_)) => "block_metadata",
+            Some(Transaction::BlockMetadataExt(_)) => "block_metadata_ext",
             Some(Transaction::StateCheckpoint(_)) => "state_checkpoint",
             None => "unknown",
         };
@@ -390,6 +392,11 @@ pub fn update_counters_for_processed_chunk<T, O>(
                         .with_label_values(&[process_type, "multisig", state])
                         .inc();
                 },
+                aptos_types::transaction::TransactionPayload::Batched(_) => {
+                    metrics::APTOS_PROCESSED_USER_TRANSACTIONS_PAYLOAD_TYPE
+                        .with_label_values(&[process_type, "batched", state])
+                        .inc();
+                },
 
                 // Deprecated. Will be removed in the future.
                 aptos_types::transaction::TransactionPayload::ModuleBundle(_module) => {
@@ -27,7 +27,6 @@ use aptos_types::{
 use arr_macro::arr;
 use bytes::Bytes;
 use dashmap::DashMap;
-use itertools::zip_eq;
 use rayon::prelude::*;
 use std::collections::HashMap;
 
@@ -151,9 +150,9 @@ impl InMemoryStateCalculatorV2 {
 
         let (updates_before_last_checkpoint, updates_after_last_checkpoint) =
             if let Some(index) = last_checkpoint_index {
-                (
-                    Self::calculate_updates(&state_updates_vec[..=index]),
-                    Self::calculate_updates(&state_updates_vec[index + 1..]),
+                rayon::join(
+                    || Self::calculate_updates(&state_updates_vec[..=index]),
+                    || Self::calculate_updates(&state_updates_vec[index + 1..]),
                 )
             } else {
                 (
@@ -237,11 +236,11 @@ impl InMemoryStateCalculatorV2 {
             updates_after_last_checkpoint
         } else {
             let mut updates_since_latest_checkpoint = base.updates_since_base.clone();
-            zip_eq(
-                updates_since_latest_checkpoint.iter_mut(),
-                updates_after_last_checkpoint,
-            )
-            .for_each(|(base, delta)| base.extend(delta));
+            // The shards are independent, so they are updated in parallel.
+            updates_since_latest_checkpoint
+                .par_iter_mut()
+                .zip_eq(updates_after_last_checkpoint.into_par_iter())
+                .for_each(|(base, delta)| base.extend(delta));
             updates_since_latest_checkpoint
         };
 
@@ -20,7 +20,7 @@ use aptos_types::{
     aggregate_signature::AggregateSignature,
     block_info::{BlockInfo, GENESIS_EPOCH, GENESIS_ROUND, GENESIS_TIMESTAMP_USECS},
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
-    on_chain_config::ConfigurationResource,
+    on_chain_config::{BlockExecutionLimit, ConfigurationResource},
     state_store::state_key::StateKey,
     timestamp::TimestampResource,
     transaction::{Transaction, Version},
@@ -149,7 +149,7 @@ pub fn calculate_genesis<V: VMExecutor>(
     let (mut output, _, _) = ChunkOutput::by_transaction_execution::<V>(
         vec![genesis_txn.clone().into()].into(),
         base_state_view,
-        None,
+        BlockExecutionLimit::no_limit(),
     )?
     .apply_to_ledger(&executed_trees, None, None)?;
     ensure!(
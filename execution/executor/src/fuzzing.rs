@@ -18,8 +18,9 @@ use aptos_storage_interface::{
 use aptos_types::{
     block_executor::partitioner::{ExecutableTransactions, PartitionedTransactions},
     ledger_info::LedgerInfoWithSignatures,
+    on_chain_config::BlockExecutionLimit,
     state_store::ShardedStateUpdates,
-    test_helpers::transaction_test_helpers::BLOCK_GAS_LIMIT,
+    test_helpers::transaction_test_helpers::BLOCK_EXECUTION_LIMIT,
     transaction::{
         signature_verified_transaction::{
             into_signature_verified_block, SignatureVerifiedTransaction,
@@ -55,7 +56,7 @@ pub fn fuzz_execute_and_commit_blocks(
         let _execution_results = executor.execute_block(
             (block_id, sig_verified_block).into(),
             parent_block_id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_EXECUTION_LIMIT,
         );
         parent_block_id = block_id;
         block_ids.push(block_id);
@@ -70,12 +71,12 @@ impl TransactionBlockExecutor for FakeVM {
     fn execute_transaction_block(
         transactions: ExecutableTransactions,
         state_view: CachedStateView,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> Result<ChunkOutput> {
         ChunkOutput::by_transaction_execution::<FakeVM>(
             transactions,
             state_view,
-            maybe_block_gas_limit,
+            block_execution_limit,
         )
     }
 }
@@ -85,7 +86,7 @@ impl VMExecutor for FakeVM {
         _sharded_block_executor: &ShardedBlockExecutor<S, E>,
         _transactions: PartitionedTransactions,
         _state_view: Arc<S>,
-        _maybe_block_gas_limit: Option<u64>,
+        _block_execution_limit: BlockExecutionLimit,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
         Ok(Vec::new())
     }
@@ -93,7 +94,7 @@ impl VMExecutor for FakeVM {
     fn execute_block(
         _transactions: &[SignatureVerifiedTransaction],
         _state_view: &impl StateView,
-        _maybe_block_gas_limit: Option<u64>,
+        _block_execution_limit: BlockExecutionLimit,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
         Ok(Vec::new())
     }
@@ -8,6 +8,7 @@ use aptos_state_view::TStateView;
 use aptos_types::{
     account_address::AccountAddress,
     bytes::NumToBytes,
+    on_chain_config::BlockExecutionLimit,
     state_store::{
         state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
     },
@@ -83,8 +84,12 @@ fn test_mock_vm_same_sender() {
         txns.push(encode_mint_transaction(sender, amount));
     }
 
-    let outputs = MockVM::execute_block(&into_signature_verified_block(txns), &MockStateView, None)
-        .expect("MockVM should not fail to start");
+    let outputs = MockVM::execute_block(
+        &into_signature_verified_block(txns),
+        &MockStateView,
+        BlockExecutionLimit::no_limit(),
+    )
+    .expect("MockVM should not fail to start");
 
     for (i, output) in outputs.iter().enumerate() {
         assert_eq!(
@@ -117,8 +122,12 @@ fn test_mock_vm_payment() {
         encode_transfer_transaction(gen_address(0), gen_address(1), 50),
     ];
 
-    let output = MockVM::execute_block(&into_signature_verified_block(txns), &MockStateView, None)
-        .expect("MockVM should not fail to start");
+    let output = MockVM::execute_block(
+        &into_signature_verified_block(txns),
+        &MockStateView,
+        BlockExecutionLimit::no_limit(),
+    )
+    .expect("MockVM should not fail to start");
 
     let mut output_iter = output.iter();
     output_iter.next();
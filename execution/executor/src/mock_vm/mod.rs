@@ -20,8 +20,8 @@ use aptos_types::{
     contract_event::ContractEvent,
     event::EventKey,
     on_chain_config::{
-        access_path_for_config, new_epoch_event_key, ConfigurationResource, OnChainConfig,
-        ValidatorSet,
+        access_path_for_config, new_epoch_event_key, BlockExecutionLimit, ConfigurationResource,
+        OnChainConfig, ValidatorSet,
     },
     state_store::state_key::StateKey,
     transaction::{
@@ -66,12 +66,12 @@ impl TransactionBlockExecutor for MockVM {
     fn execute_transaction_block(
         transactions: ExecutableTransactions,
         state_view: CachedStateView,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> Result<ChunkOutput> {
         ChunkOutput::by_transaction_execution::<MockVM>(
             transactions,
             state_view,
-            maybe_block_gas_limit,
+            block_execution_limit,
         )
     }
 }
@@ -80,7 +80,7 @@ impl VMExecutor for MockVM {
     fn execute_block(
         transactions: &[SignatureVerifiedTransaction],
         state_view: &impl StateView,
-        _maybe_block_gas_limit: Option<u64>,
+        _block_execution_limit: BlockExecutionLimit,
     ) -> Result<Vec<TransactionOutput>, VMStatus> {
         // output_cache is used to store the output of transactions so they are visible to later
         // transactions.
@@ -195,7 +195,7 @@ impl VMExecutor for MockVM {
         _sharded_block_executor: &ShardedBlockExecutor<S, E>,
         _transactions: PartitionedTransactions,
         _state_view: Arc<S>,
-        _maybe_block_gas_limit: Option<u64>,
+        _block_execution_limit: BlockExecutionLimit,
     ) -> std::result::Result<Vec<TransactionOutput>, VMStatus> {
         todo!()
     }
@@ -412,6 +412,9 @@ fn decode_transaction(txn: &SignedTransaction) -> MockVMTransaction {
         TransactionPayload::Multisig(_) => {
             unimplemented!("MockVM does not support multisig transaction payload.")
         },
+        TransactionPayload::Batched(_) => {
+            unimplemented!("MockVM does not support batched transaction payload.")
+        },
         // Deprecated. Will be removed in the future.
         TransactionPayload::ModuleBundle(_) => {
             unimplemented!("MockVM does not support Module transaction payload.")
@@ -17,7 +17,8 @@ use aptos_executor_types::{BlockExecutorTrait, ChunkExecutorTrait};
 use aptos_storage_interface::DbReaderWriter;
 use aptos_types::{
     ledger_info::LedgerInfoWithSignatures,
-    test_helpers::transaction_test_helpers::{block, BLOCK_GAS_LIMIT},
+    on_chain_config::BlockExecutionLimit,
+    test_helpers::transaction_test_helpers::{block, BLOCK_EXECUTION_LIMIT},
     transaction::TransactionListWithProof,
 };
 use rand::Rng;
@@ -235,9 +236,9 @@ fn test_executor_execute_and_commit_chunk_local_result_mismatch() {
             .collect::<Vec<_>>();
         let output = executor
             .execute_block(
-                (block_id, block(txns, BLOCK_GAS_LIMIT)).into(),
+                (block_id, block(txns, BLOCK_EXECUTION_LIMIT)).into(),
                 parent_block_id,
-                BLOCK_GAS_LIMIT,
+                BLOCK_EXECUTION_LIMIT,
             )
             .unwrap();
         let ledger_info = tests::gen_ledger_info(5 + 1, output.root_hash(), block_id, 1);
@@ -285,7 +286,11 @@ fn test_executor_execute_and_commit_chunk_without_verify() {
             .map(|_| encode_mint_transaction(tests::gen_address(rng.gen::<u64>()), 100))
             .collect::<Vec<_>>();
         let output = executor
-            .execute_block((block_id, block(txns, None)).into(), parent_block_id, None)
+            .execute_block(
+                (block_id, block(txns, BlockExecutionLimit::no_limit())).into(),
+                parent_block_id,
+                BlockExecutionLimit::no_limit(),
+            )
             .unwrap();
         let ledger_info = tests::gen_ledger_info(6, output.root_hash(), block_id, 1);
         executor.commit_blocks(vec![block_id], ledger_info).unwrap();
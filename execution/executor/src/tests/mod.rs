@@ -28,9 +28,10 @@ use aptos_types::{
     bytes::NumToBytes,
     chain_id::ChainId,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    on_chain_config::BlockExecutionLimit,
     proof::definition::LeafCount,
     state_store::{state_key::StateKey, state_value::StateValue},
-    test_helpers::transaction_test_helpers::{block, BLOCK_GAS_LIMIT},
+    test_helpers::transaction_test_helpers::{block, BLOCK_EXECUTION_LIMIT},
     transaction::{
         signature_verified_transaction::SignatureVerifiedTransaction, ExecutionStatus,
         RawTransaction, Script, SignedTransaction, Transaction, TransactionListWithProof,
@@ -53,9 +54,9 @@ fn execute_and_commit_block(
 
     let output = executor
         .execute_block(
-            (id, block(vec![txn], BLOCK_GAS_LIMIT)).into(),
+            (id, block(vec![txn], BLOCK_EXECUTION_LIMIT)).into(),
             parent_block_id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_EXECUTION_LIMIT,
         )
         .unwrap();
     let version = 2 * (txn_index + 1);
@@ -152,9 +153,13 @@ fn test_executor_status() {
 
     let output = executor
         .execute_block(
-            (block_id, block(vec![txn0, txn1, txn2], BLOCK_GAS_LIMIT)).into(),
+            (
+                block_id,
+                block(vec![txn0, txn1, txn2], BLOCK_EXECUTION_LIMIT),
+            )
+                .into(),
             parent_block_id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_EXECUTION_LIMIT,
         )
         .unwrap();
 
@@ -182,9 +187,13 @@ fn test_executor_status_consensus_only() {
 
     let output = executor
         .execute_block(
-            (block_id, block(vec![txn0, txn1, txn2], BLOCK_GAS_LIMIT)).into(),
+            (
+                block_id,
+                block(vec![txn0, txn1, txn2], BLOCK_EXECUTION_LIMIT),
+            )
+                .into(),
             parent_block_id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_EXECUTION_LIMIT,
         )
         .unwrap();
 
@@ -212,9 +221,9 @@ fn test_executor_one_block() {
         .collect::<Vec<_>>();
     let output = executor
         .execute_block(
-            (block_id, block(txns, BLOCK_GAS_LIMIT)).into(),
+            (block_id, block(txns, BLOCK_EXECUTION_LIMIT)).into(),
             parent_block_id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_EXECUTION_LIMIT,
         )
         .unwrap();
     let version = num_user_txns + 1;
@@ -258,16 +267,16 @@ fn test_executor_two_blocks_with_failed_txns() {
         .collect::<Vec<_>>();
     let _output1 = executor
         .execute_block(
-            (block1_id, block(block1_txns, BLOCK_GAS_LIMIT)).into(),
+            (block1_id, block(block1_txns, BLOCK_EXECUTION_LIMIT)).into(),
             parent_block_id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_EXECUTION_LIMIT,
         )
         .unwrap();
     let output2 = executor
         .execute_block(
-            (block2_id, block(block2_txns, BLOCK_GAS_LIMIT)).into(),
+            (block2_id, block(block2_txns, BLOCK_EXECUTION_LIMIT)).into(),
             block1_id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_EXECUTION_LIMIT,
         )
         .unwrap();
 
@@ -287,9 +296,9 @@ fn test_executor_commit_twice() {
     let block1_id = gen_block_id(1);
     let output1 = executor
         .execute_block(
-            (block1_id, block(block1_txns, BLOCK_GAS_LIMIT)).into(),
+            (block1_id, block(block1_txns, BLOCK_EXECUTION_LIMIT)).into(),
             parent_block_id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_EXECUTION_LIMIT,
         )
         .unwrap();
     let ledger_info = gen_ledger_info(6, output1.root_hash(), block1_id, 1);
@@ -317,9 +326,9 @@ fn test_executor_execute_same_block_multiple_times() {
     for _i in 0..100 {
         let output = executor
             .execute_block(
-                (block_id, block(txns.clone(), BLOCK_GAS_LIMIT)).into(),
+                (block_id, block(txns.clone(), BLOCK_EXECUTION_LIMIT)).into(),
                 parent_block_id,
-                BLOCK_GAS_LIMIT,
+                BLOCK_EXECUTION_LIMIT,
             )
             .unwrap();
         responses.push(output);
@@ -328,10 +337,13 @@ fn test_executor_execute_same_block_multiple_times() {
     assert_eq!(responses.len(), 1);
 }
 
-fn ledger_version_from_block_size(block_size: usize, maybe_block_gas_limit: Option<u64>) -> usize {
+fn ledger_version_from_block_size(
+    block_size: usize,
+    block_execution_limit: BlockExecutionLimit,
+) -> usize {
     // With block gas limit, StateCheckpoint txn is inserted to block after execution.
     // So the ledger_info version needs to block_size + 1 with block gas limit.
-    block_size + maybe_block_gas_limit.map(|_| 1).unwrap_or(0)
+    block_size + usize::from(block_execution_limit.is_limited())
 }
 
 /// Generates a list of `TransactionListWithProof`s according to the given ranges.
@@ -357,7 +369,7 @@ fn create_transaction_chunks(
         let txn = encode_mint_transaction(gen_address(i), 100);
         txns.push(txn.into());
     }
-    if BLOCK_GAS_LIMIT.is_none() {
+    if !BLOCK_EXECUTION_LIMIT.is_limited() {
         txns.push(Transaction::StateCheckpoint(HashValue::random()).into());
     }
     let id = gen_block_id(1);
@@ -366,11 +378,11 @@ fn create_transaction_chunks(
         .execute_block(
             (id, txns.clone()).into(),
             executor.committed_block_id(),
-            BLOCK_GAS_LIMIT,
+            BLOCK_EXECUTION_LIMIT,
         )
         .unwrap();
 
-    let ledger_version = ledger_version_from_block_size(txns.len(), BLOCK_GAS_LIMIT) as u64;
+    let ledger_version = ledger_version_from_block_size(txns.len(), BLOCK_EXECUTION_LIMIT) as u64;
     let ledger_info = gen_ledger_info(ledger_version, output.root_hash(), id, 1);
     executor
         .commit_blocks(vec![id], ledger_info.clone())
@@ -406,16 +418,16 @@ fn test_noop_block_after_reconfiguration() {
         .execute_block(
             (first_block_id, vec![first_txn]).into(),
             parent_block_id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_EXECUTION_LIMIT,
         )
         .unwrap();
     parent_block_id = first_block_id;
-    let second_block = TestBlock::new(10, 10, gen_block_id(2), BLOCK_GAS_LIMIT);
+    let second_block = TestBlock::new(10, 10, gen_block_id(2), BLOCK_EXECUTION_LIMIT);
     let output2 = executor
         .execute_block(
             (second_block.id, second_block.txns).into(),
             parent_block_id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_EXECUTION_LIMIT,
         )
         .unwrap();
     assert_eq!(output1.root_hash(), output2.root_hash());
@@ -608,11 +620,16 @@ fn test_reconfig_suffix_empty_blocks() {
         db: _,
         executor,
     } = TestExecutor::new();
-    let block_a = TestBlock::new(10000, 1, gen_block_id(1), BLOCK_GAS_LIMIT);
+    let block_a = TestBlock::new(10000, 1, gen_block_id(1), BLOCK_EXECUTION_LIMIT);
     // add block gas limit to be consistent with block executor that will add state checkpoint txn
-    let mut block_b = TestBlock::new(10000, 1, gen_block_id(2), Some(0));
-    let block_c = TestBlock::new(1, 1, gen_block_id(3), BLOCK_GAS_LIMIT);
-    let block_d = TestBlock::new(1, 1, gen_block_id(4), BLOCK_GAS_LIMIT);
+    let mut block_b = TestBlock::new(
+        10000,
+        1,
+        gen_block_id(2),
+        BlockExecutionLimit::with_block_gas_limit(Some(0)),
+    );
+    let block_c = TestBlock::new(1, 1, gen_block_id(3), BLOCK_EXECUTION_LIMIT);
+    let block_d = TestBlock::new(1, 1, gen_block_id(4), BLOCK_EXECUTION_LIMIT);
     block_b
         .txns
         .push(encode_reconfiguration_transaction().into());
@@ -621,28 +638,28 @@ fn test_reconfig_suffix_empty_blocks() {
         .execute_block(
             (block_a.id, block_a.txns).into(),
             parent_block_id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_EXECUTION_LIMIT,
         )
         .unwrap();
     let output = executor
         .execute_block(
             (block_b.id, block_b.txns).into(),
             block_a.id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_EXECUTION_LIMIT,
         )
         .unwrap();
     executor
         .execute_block(
             (block_c.id, block_c.txns).into(),
             block_b.id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_EXECUTION_LIMIT,
         )
         .unwrap();
     executor
         .execute_block(
             (block_d.id, block_d.txns).into(),
             block_c.id,
-            BLOCK_GAS_LIMIT,
+            BLOCK_EXECUTION_LIMIT,
         )
         .unwrap();
 
@@ -666,7 +683,7 @@ impl TestBlock {
         num_user_txns: u64,
         amount: u32,
         id: HashValue,
-        maybe_block_gas_limit: Option<u64>,
+        block_execution_limit: BlockExecutionLimit,
     ) -> Self {
         let txns = if num_user_txns == 0 {
             Vec::new()
@@ -675,7 +692,7 @@ impl TestBlock {
                 (0..num_user_txns)
                     .map(|index| encode_mint_transaction(gen_address(index), u64::from(amount)))
                     .collect(),
-                maybe_block_gas_limit,
+                block_execution_limit,
             )
         };
         TestBlock { txns, id }
@@ -690,7 +707,7 @@ impl TestBlock {
 // the root hash after all transactions are committed.
 fn run_transactions_naive(
     transactions: Vec<SignatureVerifiedTransaction>,
-    maybe_block_gas_limit: Option<u64>,
+    block_execution_limit: BlockExecutionLimit,
 ) -> HashValue {
     let executor = TestExecutor::new();
     let db = &executor.db;
@@ -706,7 +723,7 @@ fn run_transactions_naive(
                     Arc::new(AsyncProofFetcher::new(db.reader.clone())),
                 )
                 .unwrap(),
-            maybe_block_gas_limit,
+            block_execution_limit,
         )
         .unwrap();
         let (executed, _, _) = out.apply_to_ledger(&ledger_view, None, None).unwrap();
@@ -758,13 +775,13 @@ proptest! {
             let executor = TestExecutor::new();
 
             let block_id = gen_block_id(1);
-            let mut block = TestBlock::new(num_user_txns, 10, block_id, BLOCK_GAS_LIMIT);
+            let mut block = TestBlock::new(num_user_txns, 10, block_id, BLOCK_EXECUTION_LIMIT);
             let num_txns = block.txns.len() as LeafCount;
             block.txns[reconfig_txn_index as usize] = encode_reconfiguration_transaction().into();
 
             let parent_block_id = executor.committed_block_id();
             let output = executor.execute_block(
-                (block_id, block.txns.clone()).into(), parent_block_id, BLOCK_GAS_LIMIT
+                (block_id, block.txns.clone()).into(), parent_block_id, BLOCK_EXECUTION_LIMIT
             ).unwrap();
 
             // assert: txns after the reconfiguration are with status "Retry"
@@ -783,11 +800,11 @@ proptest! {
             // retry txns after reconfiguration
             let retry_block_id = gen_block_id(2);
             let retry_output = executor.execute_block(
-                (retry_block_id, block.txns.iter().skip(reconfig_txn_index as usize + 1).cloned().collect::<Vec<SignatureVerifiedTransaction>>()).into(), parent_block_id, BLOCK_GAS_LIMIT
+                (retry_block_id, block.txns.iter().skip(reconfig_txn_index as usize + 1).cloned().collect::<Vec<SignatureVerifiedTransaction>>()).into(), parent_block_id, BLOCK_EXECUTION_LIMIT
             ).unwrap();
             prop_assert!(retry_output.compute_status().iter().all(|s| matches!(*s, TransactionStatus::Keep(_))));
 
-            let ledger_version = ledger_version_from_block_size(num_txns as usize, BLOCK_GAS_LIMIT) as u64;
+            let ledger_version = ledger_version_from_block_size(num_txns as usize, BLOCK_EXECUTION_LIMIT) as u64;
 
             // commit
             let ledger_info = gen_ledger_info(ledger_version, retry_output.root_hash(), retry_block_id, 12345 /* timestamp */);
@@ -819,8 +836,8 @@ proptest! {
     fn test_executor_restart(a_size in 1..30u64, b_size in 1..30u64, amount in any::<u32>()) {
         let TestExecutor { _path, db, executor } = TestExecutor::new();
 
-        let block_a = TestBlock::new(a_size, amount, gen_block_id(1), BLOCK_GAS_LIMIT);
-        let block_b = TestBlock::new(b_size, amount, gen_block_id(2), BLOCK_GAS_LIMIT);
+        let block_a = TestBlock::new(a_size, amount, gen_block_id(1), BLOCK_EXECUTION_LIMIT);
+        let block_b = TestBlock::new(b_size, amount, gen_block_id(2), BLOCK_EXECUTION_LIMIT);
 
         let mut parent_block_id;
         let mut root_hash;
@@ -829,10 +846,10 @@ proptest! {
         {
             parent_block_id = executor.committed_block_id();
             let output_a = executor.execute_block(
-                (block_a.id, block_a.txns.clone()).into(), parent_block_id, BLOCK_GAS_LIMIT
+                (block_a.id, block_a.txns.clone()).into(), parent_block_id, BLOCK_EXECUTION_LIMIT
             ).unwrap();
             root_hash = output_a.root_hash();
-            let ledger_info = gen_ledger_info(ledger_version_from_block_size(block_a.txns.len(), BLOCK_GAS_LIMIT) as u64, root_hash, block_a.id, 1);
+            let ledger_info = gen_ledger_info(ledger_version_from_block_size(block_a.txns.len(), BLOCK_EXECUTION_LIMIT) as u64, root_hash, block_a.id, 1);
             executor.commit_blocks(vec![block_a.id], ledger_info).unwrap();
             parent_block_id = block_a.id;
             drop(executor);
@@ -841,10 +858,10 @@ proptest! {
         // Now we construct a new executor and run one more block.
         {
             let executor = BlockExecutor::<MockVM>::new(db);
-            let output_b = executor.execute_block((block_b.id, block_b.txns.clone()).into(), parent_block_id, BLOCK_GAS_LIMIT).unwrap();
+            let output_b = executor.execute_block((block_b.id, block_b.txns.clone()).into(), parent_block_id, BLOCK_EXECUTION_LIMIT).unwrap();
             root_hash = output_b.root_hash();
             let ledger_info = gen_ledger_info(
-                (ledger_version_from_block_size(block_a.txns.len(), BLOCK_GAS_LIMIT) + ledger_version_from_block_size(block_b.txns.len(), BLOCK_GAS_LIMIT)) as u64,
+                (ledger_version_from_block_size(block_a.txns.len(), BLOCK_EXECUTION_LIMIT) + ledger_version_from_block_size(block_b.txns.len(), BLOCK_EXECUTION_LIMIT)) as u64,
                 root_hash,
                 block_b.id,
                 2,
@@ -855,15 +872,15 @@ proptest! {
         let expected_root_hash = run_transactions_naive({
             let mut txns = vec![];
             txns.extend(block_a.txns.iter().cloned());
-            if BLOCK_GAS_LIMIT.is_some() {
+            if BLOCK_EXECUTION_LIMIT.is_limited() {
                 txns.push(Transaction::StateCheckpoint(block_a.id).into());
             }
             txns.extend(block_b.txns.iter().cloned());
-            if BLOCK_GAS_LIMIT.is_some() {
+            if BLOCK_EXECUTION_LIMIT.is_limited() {
                 txns.push(Transaction::StateCheckpoint(block_b.id).into());
             }
             txns
-        }, BLOCK_GAS_LIMIT);
+        }, BLOCK_EXECUTION_LIMIT);
         prop_assert_eq!(root_hash, expected_root_hash);
     }
 }
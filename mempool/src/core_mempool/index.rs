@@ -12,13 +12,12 @@ use crate::{
 use aptos_consensus_types::common::TransactionSummary;
 use aptos_logger::prelude::*;
 use aptos_types::account_address::AccountAddress;
-use rand::seq::SliceRandom;
 use std::{
     cmp::Ordering,
     collections::{btree_set::Iter, BTreeMap, BTreeSet, HashMap},
     iter::Rev,
     ops::Bound,
-    time::Duration,
+    time::{Duration, SystemTime},
 };
 
 pub type AccountTransactions = BTreeMap<u64, MempoolTransaction>;
@@ -239,6 +238,15 @@ impl TimelineIndex {
             .collect()
     }
 
+    /// Returns true if the timeline has transactions from `start_id` (exclusive) to `end_id`
+    /// (inclusive).
+    fn has_txns_in_range(&self, start_id: u64, end_id: u64) -> bool {
+        self.timeline
+            .range((Bound::Excluded(start_id), Bound::Included(end_id)))
+            .next()
+            .is_some()
+    }
+
     pub(crate) fn insert(&mut self, txn: &mut MempoolTransaction) {
         self.timeline.insert(
             self.timeline_id,
@@ -341,6 +349,16 @@ impl MultiBucketTimelineIndex {
         all_txns
     }
 
+    /// Returns the highest bucket with transactions from `start_id` (exclusive) to `end_id`
+    /// (inclusive).
+    pub(crate) fn highest_bucket_in_range(&self, start_end_pairs: &[(u64, u64)]) -> Option<usize> {
+        assert_eq!(start_end_pairs.len(), self.timelines.len());
+
+        self.timelines.iter().zip(start_end_pairs.iter()).rposition(
+            |(timeline, &(start_id, end_id))| timeline.has_txns_in_range(start_id, end_id),
+        )
+    }
+
     #[inline]
     fn get_timeline(&mut self, ranking_score: u64) -> &mut TimelineIndex {
         let index = self
@@ -387,96 +405,116 @@ impl MultiBucketTimelineIndex {
 /// ParkingLotIndex keeps track of "not_ready" transactions, e.g., transactions that
 /// can't be included in the next block because their sequence number is too high.
 /// We keep a separate index to be able to efficiently evict them when Mempool is full.
+/// Transactions are evicted by lowest gas price first, then oldest first.
 pub struct ParkingLotIndex {
     // DS invariants:
     // 1. for each entry (account, txns) in `data`, `txns` is never empty
-    // 2. for all accounts, data.get(account_indices.get(`account`)) == (account, sequence numbers of account's txns)
-    data: Vec<(AccountAddress, BTreeSet<u64>)>,
-    account_indices: HashMap<AccountAddress, usize>,
-    size: usize,
+    // 2. `eviction_order` contains exactly the txns of all the accounts in `data`
+    data: HashMap<AccountAddress, BTreeSet<ParkedTxnKey>>,
+    eviction_order: BTreeSet<ParkedTxnKey>,
+}
+
+/// Logical reference to a parked transaction, ordered by eviction priority
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+struct ParkedTxnKey {
+    gas_unit_price: u64,
+    insertion_time: SystemTime,
+    sender: AccountAddress,
+    sequence_number: u64,
+}
+
+impl From<&MempoolTransaction> for ParkedTxnKey {
+    fn from(txn: &MempoolTransaction) -> Self {
+        Self {
+            gas_unit_price: txn.get_gas_price(),
+            insertion_time: txn.insertion_info.insertion_time,
+            sender: txn.get_sender(),
+            sequence_number: txn.sequence_info.transaction_sequence_number,
+        }
+    }
+}
+
+impl From<&ParkedTxnKey> for TxnPointer {
+    fn from(key: &ParkedTxnKey) -> Self {
+        Self {
+            sender: key.sender,
+            sequence_number: key.sequence_number,
+        }
+    }
 }
 
 impl ParkingLotIndex {
     pub(crate) fn new() -> Self {
         Self {
-            data: vec![],
-            account_indices: HashMap::new(),
-            size: 0,
+            data: HashMap::new(),
+            eviction_order: BTreeSet::new(),
         }
     }
 
-    pub(crate) fn insert(&mut self, txn: &MempoolTransaction) {
-        let sender = &txn.txn.sender();
-        let sequence_number = txn.txn.sequence_number();
-        let is_new_entry = match self.account_indices.get(sender) {
-            Some(index) => {
-                if let Some((_account, seq_nums)) = self.data.get_mut(*index) {
-                    seq_nums.insert(sequence_number)
-                } else {
-                    counters::CORE_MEMPOOL_INVARIANT_VIOLATION_COUNT.inc();
-                    error!(
-                        LogSchema::new(LogEntry::InvariantViolated),
-                        "Parking lot invariant violated: for account {}, account index exists but missing entry in data",
-                        sender
-                    );
-                    return;
-                }
-            },
-            None => {
-                let seq_nums = [sequence_number].iter().cloned().collect::<BTreeSet<_>>();
-                self.data.push((*sender, seq_nums));
-                self.account_indices.insert(*sender, self.data.len() - 1);
-                true
-            },
-        };
-        if is_new_entry {
-            self.size += 1;
+    /// Parks the transaction, returns false if it was already parked
+    pub(crate) fn insert(&mut self, txn: &MempoolTransaction) -> bool {
+        let key = ParkedTxnKey::from(txn);
+        if self.eviction_order.insert(key) {
+            self.data.entry(key.sender).or_default().insert(key);
+            counters::CORE_MEMPOOL_PARKED_TXNS.inc();
+            true
+        } else {
+            false
         }
     }
 
     pub(crate) fn remove(&mut self, txn: &MempoolTransaction) {
-        let sender = &txn.txn.sender();
-        if let Some(index) = self.account_indices.get(sender).cloned() {
-            if let Some((_account, txns)) = self.data.get_mut(index) {
-                if txns.remove(&txn.txn.sequence_number()) {
-                    self.size -= 1;
-                }
+        let key = ParkedTxnKey::from(txn);
+        if self.eviction_order.remove(&key) {
+            if let Some(txns) = self.data.get_mut(&key.sender) {
+                txns.remove(&key);
 
                 // maintain DS invariant
                 if txns.is_empty() {
                     // remove account with no more txns
-                    self.data.swap_remove(index);
-                    self.account_indices.remove(sender);
-
-                    // update DS for account that was swapped in `swap_remove`
-                    if let Some((swapped_account, _)) = self.data.get(index) {
-                        self.account_indices.insert(*swapped_account, index);
-                    }
+                    self.data.remove(&key.sender);
                 }
+            } else {
+                counters::CORE_MEMPOOL_INVARIANT_VIOLATION_COUNT.inc();
+                error!(
+                    LogSchema::new(LogEntry::InvariantViolated),
+                    "Parking lot invariant violated: for account {}, txn is parked but missing entry in data",
+                    key.sender
+                );
             }
         }
     }
 
     pub(crate) fn contains(&self, account: &AccountAddress, seq_num: &u64) -> bool {
-        self.account_indices
-            .get(account)
-            .and_then(|idx| self.data.get(*idx))
-            .map_or(false, |(_account, txns)| txns.contains(seq_num))
+        self.data.get(account).map_or(false, |txns| {
+            txns.iter().any(|key| key.sequence_number == *seq_num)
+        })
     }
 
-    /// Returns a random "non-ready" transaction (with highest sequence number for that account).
+    /// Returns the "non-ready" transaction to evict first: the one with the lowest gas price
+    /// and, among those, the oldest.
     pub(crate) fn get_poppable(&self) -> Option<TxnPointer> {
-        let mut rng = rand::thread_rng();
-        self.data.choose(&mut rng).and_then(|(sender, txns)| {
-            txns.iter().next_back().map(|seq_num| TxnPointer {
-                sender: *sender,
-                sequence_number: *seq_num,
-            })
-        })
+        self.eviction_order.iter().next().map(TxnPointer::from)
+    }
+
+    /// Returns the "non-ready" transaction of the account to evict first, along with its gas price.
+    pub(crate) fn get_poppable_for_account(
+        &self,
+        account: &AccountAddress,
+    ) -> Option<(TxnPointer, u64)> {
+        self.data
+            .get(account)
+            .and_then(|txns| txns.iter().next())
+            .map(|key| (TxnPointer::from(key), key.gas_unit_price))
+    }
+
+    /// Returns the number of "non-ready" transactions of the account
+    pub(crate) fn account_size(&self, account: &AccountAddress) -> usize {
+        self.data.get(account).map_or(0, BTreeSet::len)
     }
 
     pub(crate) fn size(&self) -> usize {
-        self.size
+        self.eviction_order.len()
     }
 }
 
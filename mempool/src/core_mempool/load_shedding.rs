@@ -0,0 +1,121 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{collections::VecDeque, time::Duration};
+
+/// The transactions that left Mempool, and the ones rejected because it was full, in one second
+#[derive(Debug)]
+struct LoadSample {
+    second: u64,
+    removed_txns: u64,
+    shed_txns: u64,
+}
+
+/// Estimates when the clients rejected by a full Mempool should retry. Over the recent window,
+/// the transactions that were shed wait for the space freed by the transactions leaving Mempool
+/// (committed, rejected, expired or evicted), so a client waits for its turn in that backlog.
+pub(crate) struct RetryAfterEstimator {
+    window_secs: u64,
+    max_retry_after_secs: u64,
+    /// One sample per second with activity, oldest first
+    samples: VecDeque<LoadSample>,
+}
+
+impl RetryAfterEstimator {
+    pub(crate) fn new(window_secs: u64, max_retry_after_secs: u64) -> Self {
+        Self {
+            window_secs: window_secs.max(1),
+            max_retry_after_secs: max_retry_after_secs.max(1),
+            samples: VecDeque::new(),
+        }
+    }
+
+    pub(crate) fn record_removed(&mut self, now: Duration, num_txns: u64) {
+        if num_txns > 0 {
+            self.sample_mut(now).removed_txns += num_txns;
+        }
+    }
+
+    pub(crate) fn record_shed(&mut self, now: Duration) {
+        self.sample_mut(now).shed_txns += 1;
+    }
+
+    /// Returns the number of seconds a client rejected now should wait before retrying
+    pub(crate) fn retry_after_secs(&mut self, now: Duration) -> u64 {
+        self.prune(now.as_secs());
+        let (removed_txns, shed_txns) =
+            self.samples.iter().fold((0, 0), |(removed, shed), sample| {
+                (removed + sample.removed_txns, shed + sample.shed_txns)
+            });
+        if removed_txns == 0 {
+            return self.max_retry_after_secs;
+        }
+        // The backlog ahead of the client, drained at the recent removal rate
+        let backlog = shed_txns + 1;
+        let retry_after_secs = (backlog * self.window_secs + removed_txns - 1) / removed_txns;
+        retry_after_secs.clamp(1, self.max_retry_after_secs)
+    }
+
+    fn sample_mut(&mut self, now: Duration) -> &mut LoadSample {
+        let second = now.as_secs();
+        self.prune(second);
+        if self
+            .samples
+            .back()
+            .map_or(true, |sample| sample.second < second)
+        {
+            self.samples.push_back(LoadSample {
+                second,
+                removed_txns: 0,
+                shed_txns: 0,
+            });
+        }
+        self.samples.back_mut().expect("A sample was just added")
+    }
+
+    fn prune(&mut self, second: u64) {
+        while self
+            .samples
+            .front()
+            .map_or(false, |sample| sample.second + self.window_secs <= second)
+        {
+            self.samples.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::RetryAfterEstimator;
+    use std::time::Duration;
+
+    #[test]
+    fn test_retry_after_estimate() {
+        let mut estimator = RetryAfterEstimator::new(10, 60);
+        let now = Duration::from_secs(1_000);
+
+        // Nothing left Mempool recently
+        assert_eq!(estimator.retry_after_secs(now), 60);
+
+        // 20 txns left Mempool in the window, i.e., 2 per second
+        estimator.record_removed(now, 10);
+        estimator.record_removed(now + Duration::from_secs(5), 10);
+        assert_eq!(estimator.retry_after_secs(now + Duration::from_secs(5)), 1);
+
+        // The shed txns are ahead of the next client
+        for _ in 0..9 {
+            estimator.record_shed(now + Duration::from_secs(5));
+        }
+        assert_eq!(estimator.retry_after_secs(now + Duration::from_secs(5)), 5);
+
+        // Only the samples of the window count
+        assert_eq!(
+            estimator.retry_after_secs(now + Duration::from_secs(10)),
+            10
+        );
+        assert_eq!(
+            estimator.retry_after_secs(now + Duration::from_secs(15)),
+            60
+        );
+    }
+}
@@ -8,6 +8,9 @@ use crate::{
     core_mempool::{
         index::TxnPointer,
         transaction::{InsertionInfo, MempoolTransaction, TimelineState},
+        transaction_events::{
+            MempoolTransactionStatus, TransactionStatusReceiver, TransactionStatusWaiters,
+        },
         transaction_store::TransactionStore,
     },
     counters,
@@ -171,6 +174,26 @@ impl Mempool {
         self.transactions.get_by_hash(hash)
     }
 
+    /// Returns the status of a transaction still in Mempool, and waits for its next status change
+    pub(crate) fn wait_for_status_change(
+        &self,
+        hash: HashValue,
+    ) -> (
+        Option<MempoolTransactionStatus>,
+        Option<TransactionStatusReceiver>,
+    ) {
+        self.transactions.wait_for_status_change(hash)
+    }
+
+    pub(crate) fn status_waiters(&self) -> &TransactionStatusWaiters {
+        self.transactions.status_waiters()
+    }
+
+    /// Estimates when the clients turned away by a full Mempool should retry
+    pub(crate) fn retry_after_secs(&mut self) -> u64 {
+        self.transactions.retry_after_secs()
+    }
+
     /// Used to add a transaction to the Mempool.
     /// Performs basic validation: checks account's sequence number.
     pub(crate) fn add_txn(
@@ -429,6 +452,12 @@ impl Mempool {
         self.transactions.timeline_range(start_end_pairs)
     }
 
+    /// Returns the highest gas price bucket with transactions in the timeline ranges, from
+    /// `start_id` (exclusive) to `end_id` (inclusive).
+    pub(crate) fn highest_bucket_in_range(&self, start_end_pairs: &[(u64, u64)]) -> Option<usize> {
+        self.transactions.highest_bucket_in_range(start_end_pairs)
+    }
+
     pub fn gen_snapshot(&self) -> TxnsLog {
         self.transactions.gen_snapshot()
     }
@@ -3,13 +3,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod index;
+mod load_shedding;
 mod mempool;
 mod transaction;
+mod transaction_events;
 mod transaction_store;
 
 pub use self::{
     index::TxnPointer,
     mempool::Mempool as CoreMempool,
     transaction::{MempoolTransaction, SubmittedBy, TimelineState},
+    transaction_events::{
+        MempoolTransactionStatus, TransactionStatusReceiver, TransactionStatusWaiters,
+    },
     transaction_store::TXN_INDEX_ESTIMATED_BYTES,
 };
@@ -0,0 +1,138 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::core_mempool::index::TxnPointer;
+use aptos_crypto::HashValue;
+use aptos_infallible::Mutex;
+use futures::channel::oneshot;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, sync::Arc};
+
+/// The lifecycle of a transaction in Mempool
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum MempoolTransactionStatus {
+    /// The transaction is ready to be pulled by consensus
+    Accepted,
+    /// The transaction waits for the transactions with lower sequence numbers of its account
+    Parked,
+    /// The transaction was broadcast to a peer
+    Broadcast,
+    /// The transaction expired before being committed, and was removed
+    Expired,
+    /// The transaction was rejected by consensus, and was removed
+    Rejected,
+    /// A transaction with the same sender and sequence number was committed
+    Committed,
+}
+
+impl MempoolTransactionStatus {
+    /// Returns true if the transaction was removed from Mempool
+    pub fn is_final(&self) -> bool {
+        matches!(
+            self,
+            MempoolTransactionStatus::Expired
+                | MempoolTransactionStatus::Rejected
+                | MempoolTransactionStatus::Committed
+        )
+    }
+}
+
+/// Receives the next status of a transaction. It is canceled if the transaction is replaced in
+/// Mempool by another transaction with the same sender and sequence number.
+pub type TransactionStatusReceiver = oneshot::Receiver<MempoolTransactionStatus>;
+
+#[derive(Default)]
+struct Waiters {
+    // The waiters of each transaction, notified once on its next status change
+    by_hash: HashMap<HashValue, Vec<oneshot::Sender<MempoolTransactionStatus>>>,
+    // The transactions in Mempool which have waiters, so that their status changes are matched
+    // without hashing them
+    by_txn: HashMap<TxnPointer, HashValue>,
+    num_waiters: usize,
+}
+
+impl Waiters {
+    /// Drops the waiters which gave up, e.g., after their request timed out
+    fn remove_canceled(&mut self) {
+        self.by_hash.retain(|_, senders| {
+            senders.retain(|sender| !sender.is_canceled());
+            !senders.is_empty()
+        });
+        let by_hash = &self.by_hash;
+        self.by_txn.retain(|_, hash| by_hash.contains_key(hash));
+        self.num_waiters = self.by_hash.values().map(Vec::len).sum();
+    }
+}
+
+/// Notifies the clients waiting for the next status change of a transaction. Only the
+/// transactions with waiters are looked up, by sender and sequence number, so that Mempool
+/// doesn't have to hash the transactions whose status changes.
+#[derive(Clone)]
+pub struct TransactionStatusWaiters {
+    waiters: Arc<Mutex<Waiters>>,
+    max_waiters: usize,
+}
+
+impl TransactionStatusWaiters {
+    pub fn new(max_waiters: usize) -> Self {
+        Self {
+            waiters: Arc::new(Mutex::new(Waiters::default())),
+            max_waiters,
+        }
+    }
+
+    /// Waits for the next status change of the transaction, `txn` points to it if it's already in
+    /// Mempool. Returns None if too many clients are waiting already.
+    pub(crate) fn wait(
+        &self,
+        hash: HashValue,
+        txn: Option<TxnPointer>,
+    ) -> Option<TransactionStatusReceiver> {
+        let mut waiters = self.waiters.lock();
+        if waiters.num_waiters >= self.max_waiters {
+            waiters.remove_canceled();
+            if waiters.num_waiters >= self.max_waiters {
+                return None;
+            }
+        }
+
+        let (sender, receiver) = oneshot::channel();
+        waiters.by_hash.entry(hash).or_default().push(sender);
+        waiters.num_waiters += 1;
+        if let Some(txn) = txn {
+            waiters.by_txn.insert(txn, hash);
+        }
+        Some(receiver)
+    }
+
+    /// Tracks the transaction inserted into Mempool if it has waiters, the hash is the one
+    /// computed by Mempool to index the transaction
+    pub(crate) fn on_insert(&self, hash: HashValue, txn: TxnPointer) {
+        let mut waiters = self.waiters.lock();
+        let previous = if waiters.by_hash.contains_key(&hash) {
+            waiters.by_txn.insert(txn, hash)
+        } else {
+            waiters.by_txn.remove(&txn)
+        };
+        // The transaction replaces another one, whose waiters are canceled
+        if let Some(replaced) = previous.filter(|replaced| *replaced != hash) {
+            if let Some(senders) = waiters.by_hash.remove(&replaced) {
+                waiters.num_waiters -= senders.len();
+            }
+        }
+    }
+
+    /// Notifies the waiters of the transaction of its new status
+    pub(crate) fn notify(&self, txn: &TxnPointer, status: MempoolTransactionStatus) {
+        let mut waiters = self.waiters.lock();
+        if let Some(hash) = waiters.by_txn.remove(txn) {
+            if let Some(senders) = waiters.by_hash.remove(&hash) {
+                waiters.num_waiters -= senders.len();
+                for sender in senders {
+                    // Sending only fails if the waiter gave up
+                    let _ = sender.send(status);
+                }
+            }
+        }
+    }
+}
@@ -8,8 +8,12 @@ use crate::{
             AccountTransactions, MultiBucketTimelineIndex, ParkingLotIndex, PriorityIndex,
             PriorityQueueIter, TTLIndex,
         },
+        load_shedding::RetryAfterEstimator,
         mempool::Mempool,
         transaction::{InsertionInfo, MempoolTransaction, TimelineState},
+        transaction_events::{
+            MempoolTransactionStatus, TransactionStatusReceiver, TransactionStatusWaiters,
+        },
         TxnPointer,
     },
     counters,
@@ -69,11 +73,17 @@ pub struct TransactionStore {
     size_bytes: usize,
     // keeps track of txns that were resubmitted with higher gas
     gas_upgraded_index: HashMap<TxnPointer, u64>,
+    // clients waiting for the status changes of txns
+    status_waiters: TransactionStatusWaiters,
+    // estimates when the txns rejected by a full mempool can be retried
+    retry_after_estimator: RetryAfterEstimator,
 
     // configuration
     capacity: usize,
     capacity_bytes: usize,
     capacity_per_user: usize,
+    max_parked_txns_per_user: usize,
+    min_gas_price_bump_percent: u64,
     max_batch_bytes: u64,
 
     // eager expiration
@@ -101,11 +111,18 @@ impl TransactionStore {
             // estimated size in bytes
             size_bytes: 0,
             gas_upgraded_index: HashMap::new(),
+            status_waiters: TransactionStatusWaiters::new(config.max_transaction_status_waiters),
+            retry_after_estimator: RetryAfterEstimator::new(
+                config.load_shedding_window_secs,
+                config.max_retry_after_secs,
+            ),
 
             // configuration
             capacity: config.capacity,
             capacity_bytes: config.capacity_bytes,
             capacity_per_user: config.capacity_per_user,
+            max_parked_txns_per_user: config.max_parked_txns_per_user,
+            min_gas_price_bump_percent: config.min_gas_price_bump_percent,
             max_batch_bytes: config.shared_mempool_max_batch_bytes,
 
             // eager expiration
@@ -156,6 +173,42 @@ impl TransactionStore {
         }
     }
 
+    /// Returns the status of a transaction in Mempool, if it's still there
+    fn get_status_by_hash(&self, hash: HashValue) -> Option<MempoolTransactionStatus> {
+        let (address, sequence_number) = self.hash_index.get(&hash)?;
+        if self.parking_lot_index.contains(address, sequence_number) {
+            Some(MempoolTransactionStatus::Parked)
+        } else {
+            Some(MempoolTransactionStatus::Accepted)
+        }
+    }
+
+    /// Returns the status of a transaction in Mempool, if it's still there, and waits for its next
+    /// status change. No receiver is returned if too many clients are waiting already.
+    pub(crate) fn wait_for_status_change(
+        &self,
+        hash: HashValue,
+    ) -> (
+        Option<MempoolTransactionStatus>,
+        Option<TransactionStatusReceiver>,
+    ) {
+        let txn = self
+            .hash_index
+            .get(&hash)
+            .map(|(sender, sequence_number)| TxnPointer {
+                sender: *sender,
+                sequence_number: *sequence_number,
+            });
+        (
+            self.get_status_by_hash(hash),
+            self.status_waiters.wait(hash, txn),
+        )
+    }
+
+    pub(crate) fn status_waiters(&self) -> &TransactionStatusWaiters {
+        &self.status_waiters
+    }
+
     pub(crate) fn get_insertion_info_and_bucket(
         &self,
         address: &AccountAddress,
@@ -191,6 +244,13 @@ impl TransactionStore {
         &self.gas_upgraded_index
     }
 
+    /// Returns the lowest gas unit price a transaction needs to replace a pending transaction
+    /// with the given gas unit price. A replacement always needs to increase the gas unit price.
+    fn min_replacement_gas_price(current_gas_price: u64, min_bump_percent: u64) -> u64 {
+        let bump = (current_gas_price as u128 * min_bump_percent as u128 + 99) / 100;
+        current_gas_price.saturating_add(bump.clamp(1, u64::MAX as u128) as u64)
+    }
+
     /// Insert transaction into TransactionStore. Performs validation checks and updates indexes.
     pub(crate) fn insert(&mut self, txn: MempoolTransaction) -> MempoolStatus {
         let address = txn.get_sender();
@@ -221,35 +281,60 @@ impl TransactionStore {
                         "Transaction already in mempool with a different max gas amount"
                             .to_string(),
                     );
-                } else if current_version.get_gas_price() < txn.get_gas_price() {
-                    // Update txn if gas unit price is a larger value than before
-                    if let Some(txn) = txns.remove(&txn_seq_num) {
-                        self.index_remove(&txn);
-                    };
-                    gas_upgraded = true;
-                } else if current_version.get_gas_price() > txn.get_gas_price() {
-                    return MempoolStatus::new(MempoolStatusCode::InvalidUpdate).with_message(
-                        "Transaction already in mempool with a higher gas price".to_string(),
-                    );
-                } else {
+                } else if current_version.get_gas_price() == txn.get_gas_price() {
                     // If the transaction is the same, it's an idempotent call
                     // Updating signers is not supported, the previous submission must fail
                     counters::CORE_MEMPOOL_IDEMPOTENT_TXNS.inc();
                     return MempoolStatus::new(MempoolStatusCode::Accepted);
                 }
+
+                // Replace the txn only if the gas unit price is bumped enough, so that
+                // replacements can't be used to flood the network at no cost
+                let min_gas_price = Self::min_replacement_gas_price(
+                    current_version.get_gas_price(),
+                    self.min_gas_price_bump_percent,
+                );
+                if txn.get_gas_price() < min_gas_price {
+                    return MempoolStatus::new(MempoolStatusCode::InsufficientGasPriceBump)
+                        .with_message(format!(
+                            "Transaction already in mempool with gas unit price {}, a replacement requires at least {}",
+                            current_version.get_gas_price(),
+                            min_gas_price,
+                        ));
+                }
+                if let Some(txn) = txns.remove(&txn_seq_num) {
+                    self.index_remove(&txn);
+                };
+                gas_upgraded = true;
             }
         }
 
         if self.check_is_full_after_eviction(&txn, acc_seq_num) {
-            return MempoolStatus::new(MempoolStatusCode::MempoolIsFull).with_message(format!(
-                "Mempool is full. Mempool size: {}, Capacity: {}",
-                self.system_ttl_index.size(),
-                self.capacity,
-            ));
+            let now = aptos_infallible::duration_since_epoch();
+            let retry_after_secs = self.retry_after_estimator.retry_after_secs(now);
+            self.retry_after_estimator.record_shed(now);
+            counters::CORE_MEMPOOL_SHED_TXNS.inc();
+            return MempoolStatus::new(MempoolStatusCode::MempoolIsFull)
+                .with_message(format!(
+                    "Mempool is full. Mempool size: {}, Capacity: {}",
+                    self.system_ttl_index.size(),
+                    self.capacity,
+                ))
+                .with_retry_after_secs(retry_after_secs);
         }
 
         self.clean_committed_transactions(&address, acc_seq_num);
 
+        if self.check_too_many_parked_after_eviction(&txn, acc_seq_num) {
+            return MempoolStatus::new(MempoolStatusCode::TooManyTransactions).with_message(
+                format!(
+                    "Mempool over parking capacity for account. Number of parked transactions from account: {} Max parked per account: {}",
+                    self.parking_lot_index.account_size(&address),
+                    self.max_parked_txns_per_user,
+                ),
+            );
+        }
+
         self.transactions
             .entry(address)
             .or_insert_with(AccountTransactions::new);
@@ -269,8 +354,10 @@ impl TransactionStore {
             // insert into storage and other indexes
             self.system_ttl_index.insert(&txn);
             self.expiration_time_index.insert(&txn);
+            let hash = txn.get_committed_hash();
             self.hash_index
-                .insert(txn.get_committed_hash(), (txn.get_sender(), txn_seq_num));
+                .insert(hash, (txn.get_sender(), txn_seq_num));
+            self.status_waiters.on_insert(hash, TxnPointer::from(&txn));
             self.sequence_numbers.insert(txn.get_sender(), acc_seq_num);
             self.size_bytes += txn.get_estimated_bytes();
             if gas_upgraded {
@@ -328,24 +415,65 @@ impl TransactionStore {
         if self.is_full() && self.check_txn_ready(txn, curr_sequence_number) {
             // try to free some space in Mempool from ParkingLot by evicting a non-ready txn
             if let Some(txn_pointer) = self.parking_lot_index.get_poppable() {
-                if let Some(txn) = self
-                    .transactions
-                    .get_mut(&txn_pointer.sender)
-                    .and_then(|txns| txns.remove(&txn_pointer.sequence_number))
-                {
-                    debug!(
-                        LogSchema::new(LogEntry::MempoolFullEvictedTxn).txns(TxnsLog::new_txn(
-                            txn.get_sender(),
-                            txn.sequence_info.transaction_sequence_number
-                        ))
-                    );
-                    self.index_remove(&txn);
-                }
+                self.evict_parked_txn(txn_pointer, counters::EVICTED_MEMPOOL_FULL_LABEL);
             }
         }
         self.is_full()
     }
 
+    /// Checks if the account already has the max number of parked txns, and the txn would be
+    /// parked as well upon insertion.
+    /// If so, tries to make room by evicting the account's parked txn with the lowest gas price
+    /// (the oldest one among those), unless the gas price of the new txn is lower.
+    fn check_too_many_parked_after_eviction(
+        &mut self,
+        txn: &MempoolTransaction,
+        curr_sequence_number: u64,
+    ) -> bool {
+        let address = txn.get_sender();
+        if self.parking_lot_index.account_size(&address) < self.max_parked_txns_per_user
+            || self.check_txn_ready(txn, curr_sequence_number)
+        {
+            return false;
+        }
+        if let Some((txn_pointer, gas_unit_price)) =
+            self.parking_lot_index.get_poppable_for_account(&address)
+        {
+            if gas_unit_price <= txn.get_gas_price() {
+                self.evict_parked_txn(txn_pointer, counters::EVICTED_MAX_PARKED_PER_USER_LABEL);
+            }
+        }
+        self.parking_lot_index.account_size(&address) >= self.max_parked_txns_per_user
+    }
+
+    fn evict_parked_txn(&mut self, txn_pointer: TxnPointer, reason: &str) {
+        if let Some(txn) = self
+            .transactions
+            .get_mut(&txn_pointer.sender)
+            .and_then(|txns| txns.remove(&txn_pointer.sequence_number))
+        {
+            debug!(
+                LogSchema::new(LogEntry::MempoolFullEvictedTxn).txns(TxnsLog::new_txn(
+                    txn.get_sender(),
+                    txn.sequence_info.transaction_sequence_number
+                )),
+                reason = reason
+            );
+            counters::CORE_MEMPOOL_PARKING_LOT_EVICTED_TXNS
+                .with_label_values(&[reason])
+                .inc();
+            self.index_remove(&txn);
+            self.retry_after_estimator
+                .record_removed(aptos_infallible::duration_since_epoch(), 1);
+        }
+    }
+
+    /// Returns the number of seconds after which a client should retry, while Mempool is full
+    pub(crate) fn retry_after_secs(&mut self) -> u64 {
+        self.retry_after_estimator
+            .retry_after_secs(aptos_infallible::duration_since_epoch())
+    }
+
     fn is_full(&self) -> bool {
         self.system_ttl_index.size() >= self.capacity || self.size_bytes >= self.capacity_bytes
     }
@@ -448,6 +576,8 @@ impl TransactionStore {
                         txn.insertion_info,
                         process_broadcast_ready,
                     );
+                    self.status_waiters
+                        .notify(&TxnPointer::from(&*txn), MempoolTransactionStatus::Accepted);
                 }
 
                 // Remove txn from parking lot after it has been promoted to
@@ -461,7 +591,10 @@ impl TransactionStore {
                 match txn.timeline_state {
                     TimelineState::Ready(_) => {},
                     _ => {
-                        self.parking_lot_index.insert(txn);
+                        if self.parking_lot_index.insert(txn) {
+                            self.status_waiters
+                                .notify(&TxnPointer::from(&*txn), MempoolTransactionStatus::Parked);
+                        }
                         txn.was_parked = true;
                         parking_lot_txns += 1;
                     },
@@ -499,7 +632,15 @@ impl TransactionStore {
                     transaction.sequence_info.transaction_sequence_number,
                 );
                 self.index_remove(transaction);
+                self.status_waiters.notify(
+                    &TxnPointer::from(transaction),
+                    MempoolTransactionStatus::Committed,
+                );
             }
+            self.retry_after_estimator.record_removed(
+                aptos_infallible::duration_since_epoch(),
+                txns_for_removal.len() as u64,
+            );
             trace!(
                 LogSchema::new(LogEntry::CleanCommittedTxn).txns(rm_txns),
                 "txns cleaned with committing tx {}:{}",
@@ -537,6 +678,12 @@ impl TransactionStore {
                 txns.remove(&sequence_number);
             }
             self.index_remove(&txn_to_remove);
+            self.status_waiters.notify(
+                &TxnPointer::from(&txn_to_remove),
+                MempoolTransactionStatus::Rejected,
+            );
+            self.retry_after_estimator
+                .record_removed(aptos_infallible::duration_since_epoch(), 1);
 
             if aptos_logger::enabled!(Level::Trace) {
                 let mut txns_log = TxnsLog::new();
@@ -624,6 +771,10 @@ impl TransactionStore {
         (batch, last_timeline_id.into())
     }
 
+    pub(crate) fn highest_bucket_in_range(&self, start_end_pairs: &[(u64, u64)]) -> Option<usize> {
+        self.timeline_index.highest_bucket_in_range(start_end_pairs)
+    }
+
     pub(crate) fn timeline_range(
         &self,
         start_end_pairs: &Vec<(u64, u64)>,
@@ -708,6 +859,7 @@ impl TransactionStore {
             true => TxnsLog::new(),
             false => TxnsLog::new_with_max(10),
         };
+        let mut num_removed_txns = 0;
         while let Some(key) = gc_iter.next() {
             if let Some(txns) = self.transactions.get_mut(&key.address) {
                 let park_range_start = Bound::Excluded(key.sequence_number);
@@ -719,7 +871,10 @@ impl TransactionStore {
                     });
                 // mark all following txns as non-ready, i.e. park them
                 for (_, t) in txns.range_mut((park_range_start, park_range_end)) {
-                    self.parking_lot_index.insert(t);
+                    if self.parking_lot_index.insert(t) {
+                        self.status_waiters
+                            .notify(&TxnPointer::from(&*t), MempoolTransactionStatus::Parked);
+                    }
                     t.was_parked = true;
                     self.priority_index.remove(t);
                     self.timeline_index.remove(t);
@@ -747,10 +902,16 @@ impl TransactionStore {
 
                     // remove txn
                     self.index_remove(&txn);
+                    num_removed_txns += 1;
+                    self.status_waiters
+                        .notify(&TxnPointer::from(&txn), MempoolTransactionStatus::Expired);
                 }
             }
         }
 
+        self.retry_after_estimator
+            .record_removed(aptos_infallible::duration_since_epoch(), num_removed_txns);
+
         if !gc_txns_log.is_empty() {
             debug!(LogSchema::event_log(LogEntry::GCRemoveTxns, log_event).txns(gc_txns_log));
         } else {
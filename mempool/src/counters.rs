@@ -41,6 +41,10 @@ pub const GC_CLIENT_EXP_LABEL: &str = "client_expiration";
 pub const GC_ACTIVE_TXN_LABEL: &str = "active";
 pub const GC_PARKED_TXN_LABEL: &str = "parked";
 
+// Core mempool parking lot eviction reason labels
+pub const EVICTED_MEMPOOL_FULL_LABEL: &str = "mempool_full";
+pub const EVICTED_MAX_PARKED_PER_USER_LABEL: &str = "max_parked_per_user";
+
 // Mempool service request type labels
 pub const GET_BLOCK_LABEL: &str = "get_block";
 pub const GET_BLOCK_LOCK_LABEL: &str = "get_block_lock";
@@ -64,6 +68,7 @@ pub const SUCCESS_LABEL: &str = "success";
 // Bounded executor task labels
 pub const CLIENT_EVENT_LABEL: &str = "client_event";
 pub const CLIENT_EVENT_GET_TXN_LABEL: &str = "client_event_get_txn";
+pub const CLIENT_EVENT_SUBSCRIBE_TXN_STATUS_LABEL: &str = "client_event_subscribe_txn_status";
 pub const RECONFIG_EVENT_LABEL: &str = "reconfig";
 pub const PEER_BROADCAST_EVENT_LABEL: &str = "peer_broadcast";
 
@@ -174,6 +179,15 @@ pub static CORE_MEMPOOL_REMOVED_TXNS: Lazy<IntCounter> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Counter tracking number of txns rejected because core mempool is full
+pub static CORE_MEMPOOL_SHED_TXNS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_core_mempool_shed_txns_count",
+        "Number of txns rejected because core mempool is full"
+    )
+    .unwrap()
+});
+
 /// Counter tracking number of txns received that are idempotent duplicates
 pub static CORE_MEMPOOL_IDEMPOTENT_TXNS: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
@@ -183,6 +197,35 @@ pub static CORE_MEMPOOL_IDEMPOTENT_TXNS: Lazy<IntCounter> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Counter of txns parked because of a gap in the sequence numbers of their account
+pub static CORE_MEMPOOL_PARKED_TXNS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_core_mempool_parked_txns_count",
+        "Number of txns parked because of a gap in the sequence numbers of their account"
+    )
+    .unwrap()
+});
+
+/// Counter of parked txns evicted from mempool, by reason
+pub static CORE_MEMPOOL_PARKING_LOT_EVICTED_TXNS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_core_mempool_parking_lot_evicted_txns_count",
+        "Number of parked txns evicted from mempool, by reason",
+        &["reason"]
+    )
+    .unwrap()
+});
+
+/// Counter of submitted txns rejected by the admission control, by filter
+pub static ADMISSION_CONTROL_REJECTED_TXNS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_mempool_admission_control_rejected_txns_count",
+        "Number of submitted txns rejected by the admission control, by filter",
+        &["filter"]
+    )
+    .unwrap()
+});
+
 pub fn core_mempool_txn_commit_latency(
     stage: &'static str,
     submitted_by: &'static str,
@@ -565,6 +608,15 @@ pub static CLIENT_CALLBACK_FAIL: Lazy<IntCounter> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Counter for client submitted transactions rejected due to quorum store back pressure
+pub static CLIENT_BACK_PRESSURE_REJECTED_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_mempool_client_back_pressure_rejected_count",
+        "Number of client submitted transactions rejected due to quorum store back pressure"
+    )
+    .unwrap()
+});
+
 /// Counter for how many ACKs were received with an invalid request_id that this node's mempool
 /// did not send
 static INVALID_ACK_RECEIVED_COUNT: Lazy<IntCounterVec> = Lazy::new(|| {
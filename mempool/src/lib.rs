@@ -38,7 +38,12 @@
 //! to current for account). This queue is ordered by gas price so that if a client is willing to
 //! pay more (than other clients) per unit of execution, then they can enter consensus earlier. Note
 //! that although global ordering is maintained by gas price, for a single account, transactions are
-//! ordered by sequence number.
+//! ordered by sequence number. A pending transaction can be replaced by a transaction with the
+//! same sender and sequence number, if it bumps the gas price by a configurable percentage.
+//!
+//! Before their validation, submitted transactions go through the admission control, whose
+//! `TransactionFilter`s let operators reject transactions, e.g., of given senders or entry
+//! functions.
 //!
 //! All transactions that are not ready to be included in the next block are part of separate
 //! `ParkingLotIndex`. They will be moved to the ordered queue once some event unblocks them. For
@@ -48,22 +53,35 @@
 //! Such event “unblocks” local transaction and txn4 will be moved to OrderedQueue.
 //!
 //! Mempool only holds a limited number of transactions to prevent OOMing the system. Additionally
-//! there's a limit of number of transactions per account to prevent different abuses/attacks
+//! there's a limit of number of transactions per account to prevent different abuses/attacks, and
+//! a lower limit of parked transactions per account, so that a single account can't fill the
+//! `ParkingLotIndex`. Parked transactions are evicted by lowest gas price, then oldest.
+//! Transactions rejected by a full Mempool come with an estimate of when to retry, based on the
+//! rate at which transactions recently left Mempool.
 //!
 //! Transactions in Mempool have two types of expirations: systemTTL and client-specified
 //! expiration. Once we hit either of those, the transaction is removed from Mempool. SystemTTL is
 //! checked periodically in the background, while the client-specified expiration is checked on
 //! every Consensus commit request. We use a separate system TTL to ensure that a transaction won't
 //! remain stuck in Mempool forever, even if Consensus doesn't make progress
+//!
+//! Clients can wait for the next status change of a transaction (accepted, parked, broadcast,
+//! expired, rejected or committed). Only the transactions with waiters are tracked, and the number
+//! of concurrent waiters is capped.
 
 #[cfg(any(test, feature = "fuzzing"))]
 mod tests;
+pub use core_mempool::{MempoolTransactionStatus, TransactionStatusReceiver};
 pub use shared_mempool::{
     bootstrap, network,
     network::MempoolSyncMsg,
+    transaction_filter::{
+        AdmissionControl, EntryFunctionDenyList, MaxGasAmountFilter, SenderFilter,
+        TransactionFilter,
+    },
     types::{
-        MempoolClientRequest, MempoolClientSender, MempoolEventsReceiver, QuorumStoreRequest,
-        QuorumStoreResponse, SubmissionStatus,
+        BackPressureStatus, MempoolClientRequest, MempoolClientSender, MempoolEventsReceiver,
+        QuorumStoreRequest, QuorumStoreResponse, SubmissionStatus,
     },
 };
 #[cfg(any(test, feature = "fuzzing"))]
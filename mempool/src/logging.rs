@@ -156,6 +156,7 @@ pub enum LogEntry {
     ReconfigUpdate,
     JsonRpc,
     GetTransaction,
+    SubscribeTransactionStatus,
     GetBlock,
     QuorumStore,
     StateSyncCommit,
@@ -5,6 +5,7 @@
 //! Interface between Mempool and Network layers.
 
 use crate::{
+    core_mempool::{MempoolTransactionStatus, TxnPointer},
     counters,
     logging::{LogEntry, LogEvent, LogSchema},
     shared_mempool::{
@@ -371,31 +372,47 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
         }
         let retry_batch_id = state.broadcast_info.retry_batches.iter().next_back();
 
-        let (batch_id, transactions, metric_label) =
-            match std::cmp::max(expired_batch_id, retry_batch_id) {
-                Some(id) => {
-                    let metric_label = if Some(id) == expired_batch_id {
-                        Some(counters::EXPIRED_BROADCAST_LABEL)
-                    } else {
-                        Some(counters::RETRY_BROADCAST_LABEL)
-                    };
-
-                    let txns = mempool.timeline_range(&id.0);
-                    (id.clone(), txns, metric_label)
-                },
-                None => {
-                    // Fresh broadcast
-                    let (txns, new_timeline_id) = mempool.read_timeline(
-                        &state.timeline_id,
-                        self.mempool_config.shared_mempool_batch_size,
-                    );
-                    (
-                        MultiBatchId::from_timeline_ids(&state.timeline_id, &new_timeline_id),
-                        txns,
-                        None,
-                    )
-                },
-            };
+        // Rebroadcasts go first, unless fresh transactions with a higher gas price are waiting.
+        // The deferred batch remains expired or retriable, so it is sent by a later broadcast.
+        let rebroadcast_batch_id =
+            std::cmp::max(expired_batch_id, retry_batch_id).filter(|batch_id| {
+                if !self.mempool_config.prioritize_broadcast_by_gas_price {
+                    return true;
+                }
+                let fresh_ranges: Vec<_> = state
+                    .timeline_id
+                    .id_per_bucket
+                    .iter()
+                    .map(|&timeline_id| (timeline_id, u64::MAX))
+                    .collect();
+                mempool.highest_bucket_in_range(&batch_id.0)
+                    >= mempool.highest_bucket_in_range(&fresh_ranges)
+            });
+
+        let (batch_id, transactions, metric_label) = match rebroadcast_batch_id {
+            Some(id) => {
+                let metric_label = if Some(id) == expired_batch_id {
+                    Some(counters::EXPIRED_BROADCAST_LABEL)
+                } else {
+                    Some(counters::RETRY_BROADCAST_LABEL)
+                };
+
+                let txns = mempool.timeline_range(&id.0);
+                (id.clone(), txns, metric_label)
+            },
+            None => {
+                // Fresh broadcast
+                let (txns, new_timeline_id) = mempool.read_timeline(
+                    &state.timeline_id,
+                    self.mempool_config.shared_mempool_batch_size,
+                );
+                (
+                    MultiBatchId::from_timeline_ids(&state.timeline_id, &new_timeline_id),
+                    txns,
+                    None,
+                )
+            },
+        };
 
         if transactions.is_empty() {
             return Err(BroadcastError::NoTransactions(peer));
@@ -472,12 +489,23 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
             self.determine_broadcast_batch(peer, scheduled_backoff, smp)?;
 
         let num_txns = transactions.len();
+        let broadcast_txns: Vec<_> = transactions
+            .iter()
+            .map(|txn| TxnPointer {
+                sender: txn.sender(),
+                sequence_number: txn.sequence_number(),
+            })
+            .collect();
         let send_time = SystemTime::now();
         self.send_batch_to_peer(peer, batch_id.clone(), transactions)
             .await?;
         let num_pending_broadcasts =
             self.update_broadcast_state(peer, batch_id.clone(), send_time)?;
         notify_subscribers(SharedMempoolNotification::Broadcast, &smp.subscribers);
+        for txn in &broadcast_txns {
+            smp.status_waiters
+                .notify(txn, MempoolTransactionStatus::Broadcast);
+        }
 
         // Log all the metrics
         let latency = start_time.elapsed();
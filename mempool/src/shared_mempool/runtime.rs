@@ -7,7 +7,9 @@ use crate::{
     network::MempoolSyncMsg,
     shared_mempool::{
         coordinator::{coordinator, gc_coordinator, snapshot_job},
-        types::{MempoolEventsReceiver, SharedMempool, SharedMempoolNotification},
+        types::{
+            BackPressureStatus, MempoolEventsReceiver, SharedMempool, SharedMempoolNotification,
+        },
     },
     QuorumStoreRequest,
 };
@@ -46,6 +48,7 @@ pub(crate) fn start_shared_mempool<TransactionValidator, ConfigProvider>(
     validator: Arc<RwLock<TransactionValidator>>,
     subscribers: Vec<UnboundedSender<SharedMempoolNotification>>,
     peers_and_metadata: Arc<PeersAndMetadata>,
+    back_pressure_status: BackPressureStatus,
 ) where
     TransactionValidator: TransactionValidation + 'static,
     ConfigProvider: OnChainConfigProvider,
@@ -59,6 +62,7 @@ pub(crate) fn start_shared_mempool<TransactionValidator, ConfigProvider>(
             validator,
             subscribers,
             config.base.role,
+            back_pressure_status,
         );
 
     executor.spawn(coordinator(
@@ -96,6 +100,7 @@ pub fn bootstrap(
     mempool_listener: MempoolNotificationListener,
     mempool_reconfig_events: ReconfigNotificationListener<DbBackedOnChainConfig>,
     peers_and_metadata: Arc<PeersAndMetadata>,
+    back_pressure_status: BackPressureStatus,
 ) -> Runtime {
     let runtime = aptos_runtimes::spawn_named_runtime("shared-mem".into(), None);
     let mempool = Arc::new(Mutex::new(CoreMempool::new(config)));
@@ -114,6 +119,7 @@ pub fn bootstrap(
         vm_validator,
         vec![],
         peers_and_metadata,
+        back_pressure_status,
     );
     runtime
 }
@@ -4,7 +4,9 @@
 
 //! Tasks that are executed by coordinators (short-lived compared to coordinators)
 use crate::{
-    core_mempool::{CoreMempool, TimelineState},
+    core_mempool::{
+        CoreMempool, MempoolTransactionStatus, TimelineState, TransactionStatusReceiver,
+    },
     counters,
     logging::{LogEntry, LogEvent, LogSchema},
     network::{BroadcastError, MempoolSyncMsg},
@@ -119,6 +121,25 @@ pub(crate) async fn process_client_transaction_submission<NetworkClient, Transac
 {
     timer.stop_and_record();
     let _timer = counters::process_txn_submit_latency_timer_client();
+
+    if smp.config.reject_client_txns_on_back_pressure && smp.back_pressure_status.is_busy() {
+        counters::CLIENT_BACK_PRESSURE_REJECTED_COUNT.inc();
+        let retry_after_secs = smp.mempool.lock().retry_after_secs();
+        let status = MempoolStatus::new(MempoolStatusCode::MempoolIsFull)
+            .with_message(
+                "System is busy: consensus is back pressured, please retry later".to_string(),
+            )
+            .with_retry_after_secs(retry_after_secs);
+        if callback.send(Ok((status, None))).is_err() {
+            warn!(LogSchema::event_log(
+                LogEntry::JsonRpc,
+                LogEvent::CallbackFail
+            ));
+            counters::CLIENT_CALLBACK_FAIL.inc();
+        }
+        return;
+    }
+
     let ineligible_for_broadcast =
         smp.network_interface.is_validator() && !smp.broadcast_within_validator_network();
     let timeline_state = if ineligible_for_broadcast {
@@ -163,6 +184,35 @@ pub(crate) async fn process_client_get_transaction<NetworkClient, TransactionVal
     }
 }
 
+/// Processes transaction status subscription request by client.
+pub(crate) async fn process_client_subscribe_transaction_status<
+    NetworkClient,
+    TransactionValidator,
+>(
+    smp: SharedMempool<NetworkClient, TransactionValidator>,
+    hash: HashValue,
+    callback: oneshot::Sender<(
+        Option<MempoolTransactionStatus>,
+        Option<TransactionStatusReceiver>,
+    )>,
+    timer: HistogramTimer,
+) where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
+    TransactionValidator: TransactionValidation,
+{
+    timer.stop_and_record();
+    // Wait while holding the lock, so that no status change is missed
+    let subscription = smp.mempool.lock().wait_for_status_change(hash);
+
+    if callback.send(subscription).is_err() {
+        warn!(LogSchema::event_log(
+            LogEntry::SubscribeTransactionStatus,
+            LogEvent::CallbackFail
+        ));
+        counters::CLIENT_CALLBACK_FAIL.inc();
+    }
+}
+
 /// Processes transactions from other nodes.
 pub(crate) async fn process_transaction_broadcast<NetworkClient, TransactionValidator>(
     smp: SharedMempool<NetworkClient, TransactionValidator>,
@@ -292,7 +342,16 @@ where
         .into_iter()
         .enumerate()
         .filter_map(|(idx, t)| {
-            if let Ok(sequence_num) = seq_numbers[idx] {
+            if let Some(reason) = smp.admission_control.reject_reason(&t) {
+                statuses.push((
+                    t,
+                    (
+                        MempoolStatus::new(MempoolStatusCode::RejectedByFilter)
+                            .with_message(reason),
+                        None,
+                    ),
+                ));
+            } else if let Ok(sequence_num) = seq_numbers[idx] {
                 if t.sequence_number() >= sequence_num {
                     return Some((t, sequence_num));
                 } else {
@@ -329,6 +388,11 @@ where
     statuses
 }
 
+/// Number of invalid signatures isolated in a broadcast from a peer before the remaining
+/// signatures of the broadcast are rejected without being checked.
+#[cfg(not(feature = "consensus-only-perf-test"))]
+const MAX_INVALID_SIGNATURES_PER_BROADCAST: usize = 1;
+
 /// Perfoms VM validation on the transactions and inserts those that passes
 /// validation into the mempool.
 #[cfg(not(feature = "consensus-only-perf-test"))]
@@ -346,9 +410,38 @@ fn validate_and_add_transactions<NetworkClient, TransactionValidator>(
     let vm_validation_timer = counters::PROCESS_TXN_BREAKDOWN_LATENCY
         .with_label_values(&[counters::VM_VALIDATION_LABEL])
         .start_timer();
-    let validation_results = transactions
-        .iter()
-        .map(|t| smp.validator.read().validate_transaction(t.0.clone()))
+    let signature_check_results: Vec<_> = if timeline_state == TimelineState::NonQualified {
+        // The batched check isolates the invalid signatures of a broadcast cheaply. Honest peers
+        // only broadcast individually verified transactions, so the rest of a broadcast is
+        // dropped once it is known to contain an invalid signature. Batch verification may
+        // accept signatures which individual verification rejects though, so the accepted
+        // transactions are verified individually before they can be pulled into blocks.
+        SignedTransaction::check_signatures_batched(
+            transactions.iter().map(|t| t.0.clone()).collect(),
+            if client_submitted {
+                usize::MAX
+            } else {
+                MAX_INVALID_SIGNATURES_PER_BROADCAST
+            },
+        )
+        .into_iter()
+        .zip(&transactions)
+        .map(|(result, (txn, _))| result.and_then(|_| txn.clone().check_signature()))
+        .collect()
+    } else {
+        transactions
+            .iter()
+            .map(|t| t.0.clone().check_signature())
+            .collect()
+    };
+    let validation_results = signature_check_results
+        .into_iter()
+        .map(|result| match result {
+            Ok(txn) => smp.validator.read().validate_checked_transaction(txn),
+            Err(_) => Ok(aptos_types::transaction::VMValidatorResult::error(
+                StatusCode::INVALID_SIGNATURE,
+            )),
+        })
         .collect::<Vec<_>>();
     vm_validation_timer.stop_and_record();
     {
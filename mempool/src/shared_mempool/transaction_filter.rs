@@ -0,0 +1,163 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Admission control of the transactions submitted to Mempool
+use crate::counters;
+use aptos_config::config::MempoolAdmissionControlConfig;
+use aptos_types::{
+    account_address::AccountAddress,
+    transaction::{SignedTransaction, TransactionPayload},
+};
+use std::collections::HashSet;
+
+/// A check on the transactions submitted to Mempool, evaluated before their validation and
+/// insertion into the core mempool
+pub trait TransactionFilter: Send + Sync {
+    /// The name of the filter, used in the metrics
+    fn name(&self) -> &'static str;
+
+    /// Returns the reason the transaction is rejected, if it is
+    fn reject_reason(&self, txn: &SignedTransaction) -> Option<String>;
+}
+
+/// Admits the transactions of the allowed senders only, if any, and rejects the transactions of
+/// the denied senders
+pub struct SenderFilter {
+    allowed_senders: HashSet<AccountAddress>,
+    denied_senders: HashSet<AccountAddress>,
+}
+
+impl SenderFilter {
+    pub fn new(allowed_senders: &[AccountAddress], denied_senders: &[AccountAddress]) -> Self {
+        Self {
+            allowed_senders: allowed_senders.iter().copied().collect(),
+            denied_senders: denied_senders.iter().copied().collect(),
+        }
+    }
+}
+
+impl TransactionFilter for SenderFilter {
+    fn name(&self) -> &'static str {
+        "sender"
+    }
+
+    fn reject_reason(&self, txn: &SignedTransaction) -> Option<String> {
+        let sender = txn.sender();
+        if self.denied_senders.contains(&sender) {
+            Some(format!("Sender {} is denied", sender))
+        } else if !self.allowed_senders.is_empty() && !self.allowed_senders.contains(&sender) {
+            Some(format!("Sender {} is not allowed", sender))
+        } else {
+            None
+        }
+    }
+}
+
+/// Rejects the transactions calling the denied entry functions
+pub struct EntryFunctionDenyList {
+    denied_entry_functions: HashSet<(AccountAddress, String, String)>,
+}
+
+impl EntryFunctionDenyList {
+    pub fn new(denied_entry_functions: &[(AccountAddress, String, String)]) -> Self {
+        Self {
+            denied_entry_functions: denied_entry_functions.iter().cloned().collect(),
+        }
+    }
+}
+
+impl TransactionFilter for EntryFunctionDenyList {
+    fn name(&self) -> &'static str {
+        "entry_function"
+    }
+
+    fn reject_reason(&self, txn: &SignedTransaction) -> Option<String> {
+        match txn.payload() {
+            TransactionPayload::EntryFunction(entry_function) => {
+                let module = entry_function.module();
+                let function = (
+                    *module.address(),
+                    module.name().to_string(),
+                    entry_function.function().to_string(),
+                );
+                self.denied_entry_functions.contains(&function).then(|| {
+                    format!(
+                        "Entry function {}::{}::{} is denied",
+                        function.0, function.1, function.2
+                    )
+                })
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Rejects the transactions with a max gas amount above the cap
+pub struct MaxGasAmountFilter {
+    max_gas_amount: u64,
+}
+
+impl MaxGasAmountFilter {
+    pub fn new(max_gas_amount: u64) -> Self {
+        Self { max_gas_amount }
+    }
+}
+
+impl TransactionFilter for MaxGasAmountFilter {
+    fn name(&self) -> &'static str {
+        "max_gas_amount"
+    }
+
+    fn reject_reason(&self, txn: &SignedTransaction) -> Option<String> {
+        (txn.max_gas_amount() > self.max_gas_amount).then(|| {
+            format!(
+                "Max gas amount {} is above the limit {}",
+                txn.max_gas_amount(),
+                self.max_gas_amount
+            )
+        })
+    }
+}
+
+/// The filters a transaction needs to pass to be admitted into Mempool
+#[derive(Default)]
+pub struct AdmissionControl {
+    filters: Vec<Box<dyn TransactionFilter>>,
+}
+
+impl AdmissionControl {
+    /// Creates the built-in filters enabled in the config
+    pub fn new(config: &MempoolAdmissionControlConfig) -> Self {
+        let mut admission_control = Self::default();
+        if !config.allowed_senders.is_empty() || !config.denied_senders.is_empty() {
+            admission_control.add_filter(Box::new(SenderFilter::new(
+                &config.allowed_senders,
+                &config.denied_senders,
+            )));
+        }
+        if !config.denied_entry_functions.is_empty() {
+            admission_control.add_filter(Box::new(EntryFunctionDenyList::new(
+                &config.denied_entry_functions,
+            )));
+        }
+        if let Some(max_gas_amount) = config.max_gas_amount {
+            admission_control.add_filter(Box::new(MaxGasAmountFilter::new(max_gas_amount)));
+        }
+        admission_control
+    }
+
+    pub fn add_filter(&mut self, filter: Box<dyn TransactionFilter>) {
+        self.filters.push(filter);
+    }
+
+    /// Returns the reason of the first filter rejecting the transaction, if any
+    pub fn reject_reason(&self, txn: &SignedTransaction) -> Option<String> {
+        self.filters.iter().find_map(|filter| {
+            let reason = filter.reject_reason(txn)?;
+            counters::ADMISSION_CONTROL_REJECTED_TXNS
+                .with_label_values(&[filter.name()])
+                .inc();
+            Some(reason)
+        })
+    }
+}
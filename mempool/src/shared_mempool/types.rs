@@ -4,8 +4,11 @@
 
 //! Objects used by/related to shared mempool
 use crate::{
-    core_mempool::CoreMempool,
+    core_mempool::{
+        CoreMempool, MempoolTransactionStatus, TransactionStatusReceiver, TransactionStatusWaiters,
+    },
     network::{MempoolNetworkInterface, MempoolSyncMsg},
+    shared_mempool::transaction_filter::AdmissionControl,
 };
 use anyhow::Result;
 use aptos_config::{
@@ -36,7 +39,10 @@ use std::{
     collections::{BTreeMap, BTreeSet},
     fmt,
     pin::Pin,
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering as AtomicOrdering},
+        Arc,
+    },
     task::Waker,
     time::{Instant, SystemTime},
 };
@@ -52,6 +58,9 @@ pub(crate) struct SharedMempool<NetworkClient, TransactionValidator> {
     pub validator: Arc<RwLock<TransactionValidator>>,
     pub subscribers: Vec<UnboundedSender<SharedMempoolNotification>>,
     pub broadcast_within_validator_network: Arc<RwLock<bool>>,
+    pub back_pressure_status: BackPressureStatus,
+    pub admission_control: Arc<AdmissionControl>,
+    pub status_waiters: TransactionStatusWaiters,
 }
 
 impl<
@@ -67,8 +76,11 @@ impl<
         validator: Arc<RwLock<TransactionValidator>>,
         subscribers: Vec<UnboundedSender<SharedMempoolNotification>>,
         role: RoleType,
+        back_pressure_status: BackPressureStatus,
     ) -> Self {
         let network_interface = MempoolNetworkInterface::new(network_client, role, config.clone());
+        let admission_control = Arc::new(AdmissionControl::new(&config.admission_control));
+        let status_waiters = mempool.lock().status_waiters().clone();
         SharedMempool {
             mempool,
             config,
@@ -77,6 +89,9 @@ impl<
             validator,
             subscribers,
             broadcast_within_validator_network: Arc::new(RwLock::new(true)),
+            back_pressure_status,
+            admission_control,
+            status_waiters,
         }
     }
 
@@ -232,9 +247,48 @@ pub type SubmissionStatusBundle = (SignedTransaction, SubmissionStatus);
 pub enum MempoolClientRequest {
     SubmitTransaction(SignedTransaction, oneshot::Sender<Result<SubmissionStatus>>),
     GetTransactionByHash(HashValue, oneshot::Sender<Option<SignedTransaction>>),
+    /// Follows the status of a transaction: returns its current status in Mempool, if it's there,
+    /// and a receiver of its next status change, unless too many clients are waiting already
+    SubscribeTransactionStatus(
+        HashValue,
+        oneshot::Sender<(
+            Option<MempoolTransactionStatus>,
+            Option<TransactionStatusReceiver>,
+        )>,
+    ),
 }
 
 pub type MempoolClientSender = mpsc::Sender<MempoolClientRequest>;
+
+/// Quorum store back pressure, as last reported by consensus. Shared with the client facing
+/// services, so that they can report the node as busy instead of silently adding latency.
+#[derive(Clone, Debug, Default)]
+pub struct BackPressureStatus {
+    txn_count: Arc<AtomicBool>,
+    proof_count: Arc<AtomicBool>,
+}
+
+impl BackPressureStatus {
+    pub fn update(&self, txn_count: bool, proof_count: bool) {
+        self.txn_count.store(txn_count, AtomicOrdering::Relaxed);
+        self.proof_count.store(proof_count, AtomicOrdering::Relaxed);
+    }
+
+    /// Too many transactions are waiting in quorum store to be included in blocks.
+    pub fn txn_count(&self) -> bool {
+        self.txn_count.load(AtomicOrdering::Relaxed)
+    }
+
+    /// Too many proofs of store are waiting to be included in blocks.
+    pub fn proof_count(&self) -> bool {
+        self.proof_count.load(AtomicOrdering::Relaxed)
+    }
+
+    /// The node cannot keep up with the submitted transactions.
+    pub fn is_busy(&self) -> bool {
+        self.txn_count()
+    }
+}
 pub type MempoolEventsReceiver = mpsc::Receiver<MempoolClientRequest>;
 
 /// State of last sync with peer:
@@ -3,7 +3,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    core_mempool::{CoreMempool, MempoolTransaction, SubmittedBy, TimelineState},
+    core_mempool::{
+        CoreMempool, MempoolTransaction, MempoolTransactionStatus, SubmittedBy, TimelineState,
+    },
     tests::common::{
         add_signed_txn, add_txn, add_txns_to_mempool, setup_mempool,
         setup_mempool_with_broadcast_buckets, TestTransaction,
@@ -173,6 +175,38 @@ fn test_update_invalid_transaction_in_mempool() {
     assert_eq!(next_tnx[0].gas_unit_price(), 1);
 }
 
+#[test]
+fn test_replace_transaction_with_gas_price_bump() {
+    let mut config = NodeConfig::generate_random_config();
+    config.mempool.min_gas_price_bump_percent = 10;
+    let mut pool = CoreMempool::new(&config);
+    let submit = |pool: &mut CoreMempool, gas_unit_price| {
+        let txn = TestTransaction::new(0, 0, gas_unit_price).make_signed_transaction();
+        pool.add_txn(txn, gas_unit_price, 0, TimelineState::NotReady, false)
+            .code
+    };
+    assert_eq!(submit(&mut pool, 100), MempoolStatusCode::Accepted);
+
+    // The gas unit price must be bumped by at least 10%
+    assert_eq!(
+        submit(&mut pool, 90),
+        MempoolStatusCode::InsufficientGasPriceBump
+    );
+    assert_eq!(
+        submit(&mut pool, 109),
+        MempoolStatusCode::InsufficientGasPriceBump
+    );
+    assert_eq!(submit(&mut pool, 110), MempoolStatusCode::Accepted);
+
+    // The replacement is the only version of the transaction left
+    let batch = pool.get_batch(10, 10240, true, false, btreemap![]);
+    assert_eq!(batch.len(), 1);
+    assert_eq!(batch[0].gas_unit_price(), 110);
+
+    // Resubmitting the replacement is still idempotent
+    assert_eq!(submit(&mut pool, 110), MempoolStatusCode::Accepted);
+}
+
 #[test]
 fn test_commit_transaction() {
     let (mut pool, mut consensus) = setup_mempool();
@@ -475,6 +509,41 @@ fn test_multi_bucket_gas_ranking_update() {
     assert_eq!(view(timeline), vec![3]);
 }
 
+#[test]
+fn test_multi_bucket_highest_bucket_in_range() {
+    let mut pool = setup_mempool_with_broadcast_buckets(vec![0, 101, 201]).0;
+    add_txns_to_mempool(&mut pool, vec![
+        TestTransaction::new(0, 0, 1),   // bucket 0
+        TestTransaction::new(1, 0, 150), // bucket 1
+        TestTransaction::new(2, 0, 250), // bucket 2
+    ]);
+
+    let unbounded = (0, u64::MAX);
+    assert_eq!(
+        pool.highest_bucket_in_range(&[unbounded, unbounded, unbounded]),
+        Some(2)
+    );
+    assert_eq!(
+        pool.highest_bucket_in_range(&[unbounded, unbounded, (1, u64::MAX)]),
+        Some(1)
+    );
+    assert_eq!(
+        pool.highest_bucket_in_range(&[(0, 1), (1, 1), (1, 1)]),
+        Some(0)
+    );
+    assert_eq!(
+        pool.highest_bucket_in_range(&[(1, 1), (1, 1), (1, 1)]),
+        None
+    );
+
+    // Committed transactions are not in the timeline anymore
+    pool.commit_transaction(&TestTransaction::get_address(2), 0);
+    assert_eq!(
+        pool.highest_bucket_in_range(&[(0, 1), (0, 1), (0, 1)]),
+        Some(1)
+    );
+}
+
 #[test]
 fn test_multi_bucket_removal() {
     let mut pool = setup_mempool_with_broadcast_buckets(vec![0, 101, 201]).0;
@@ -582,6 +651,7 @@ fn test_capacity_bytes() {
                 false,
             );
             assert_eq!(status.code, MempoolStatusCode::MempoolIsFull);
+            assert!(status.retry_after_secs.is_some());
         }
         // Check that GC returns size to zero.
         pool.gc();
@@ -659,6 +729,43 @@ fn test_parking_lot_evict_only_for_ready_txn_insertion() {
     }
 }
 
+#[test]
+fn test_parking_lot_max_parked_per_user() {
+    let mut config = NodeConfig::generate_random_config();
+    config.mempool.max_parked_txns_per_user = 2;
+    let mut pool = CoreMempool::new(&config);
+    // Park two transactions behind a sequence number gap.
+    add_txn(&mut pool, TestTransaction::new(1, 2, 2)).unwrap();
+    add_txn(&mut pool, TestTransaction::new(1, 3, 1)).unwrap();
+    assert_eq!(pool.get_parking_lot_size(), 2);
+
+    // A transaction paying less than all the parked ones can't be parked.
+    assert!(add_txn(&mut pool, TestTransaction::new(1, 4, 0)).is_err());
+    assert_eq!(pool.get_parking_lot_size(), 2);
+
+    // Otherwise the parked transaction with the lowest gas price is evicted.
+    add_txn(&mut pool, TestTransaction::new(1, 5, 2)).unwrap();
+    assert_eq!(pool.get_parking_lot_size(), 2);
+
+    // Ready transactions are not limited. Filling the gap readies txn 2, but not the evicted txn 3.
+    for seq in &[0, 1] {
+        add_txn(&mut pool, TestTransaction::new(1, *seq, 1)).unwrap();
+    }
+    let mut txns: Vec<_> = pool
+        .get_batch(10, 5120, true, false, btreemap![])
+        .iter()
+        .map(SignedTransaction::sequence_number)
+        .collect();
+    txns.sort_unstable();
+    assert_eq!(txns, vec![0, 1, 2]);
+    assert_eq!(pool.get_parking_lot_size(), 1);
+
+    // Other accounts are not affected.
+    add_txn(&mut pool, TestTransaction::new(0, 1, 1)).unwrap();
+    add_txn(&mut pool, TestTransaction::new(0, 2, 1)).unwrap();
+    assert_eq!(pool.get_parking_lot_size(), 3);
+}
+
 #[test]
 fn test_gc_ready_transaction() {
     let mut pool = setup_mempool().0;
@@ -731,6 +838,57 @@ fn test_get_transaction_by_hash() {
     assert!(ret.is_none());
 }
 
+#[test]
+fn test_transaction_status_waiters() {
+    let mut pool = setup_mempool().0;
+
+    // The waited txn is not in mempool yet, and is parked when inserted
+    let parked_txn = TestTransaction::new(1, 1, 1).make_signed_transaction();
+    let parked = parked_txn.clone().committed_hash();
+    let (status, receiver) = pool.wait_for_status_change(parked);
+    assert_eq!(status, None);
+    let mut receiver = receiver.unwrap();
+    add_signed_txn(&mut pool, parked_txn).unwrap();
+    assert_eq!(
+        receiver.try_recv().unwrap(),
+        Some(MempoolTransactionStatus::Parked)
+    );
+
+    // The parked txn is accepted once the sequence number gap is filled
+    let (status, receiver) = pool.wait_for_status_change(parked);
+    assert_eq!(status, Some(MempoolTransactionStatus::Parked));
+    let mut receiver = receiver.unwrap();
+    let txns = add_txns_to_mempool(&mut pool, vec![TestTransaction::new(1, 0, 1)]);
+    assert_eq!(
+        receiver.try_recv().unwrap(),
+        Some(MempoolTransactionStatus::Accepted)
+    );
+
+    let ready = txns[0].clone().committed_hash();
+    let mut receiver = pool.wait_for_status_change(ready).1.unwrap();
+    pool.commit_transaction(&txns[0].sender(), 0);
+    assert_eq!(
+        receiver.try_recv().unwrap(),
+        Some(MempoolTransactionStatus::Committed)
+    );
+    assert_eq!(pool.wait_for_status_change(ready).0, None);
+}
+
+#[test]
+fn test_transaction_status_waiters_are_capped() {
+    let mut config = NodeConfig::generate_random_config();
+    config.mempool.max_transaction_status_waiters = 1;
+    let pool = CoreMempool::new(&config);
+
+    let hash = HashValue::random();
+    let receiver = pool.wait_for_status_change(hash).1.unwrap();
+    assert!(pool.wait_for_status_change(hash).1.is_none());
+
+    // Waiters which gave up are not counted
+    drop(receiver);
+    assert!(pool.wait_for_status_change(hash).1.is_some());
+}
+
 #[test]
 fn test_get_transaction_by_hash_after_the_txn_is_updated() {
     let mut pool = setup_mempool().0;
@@ -5,7 +5,10 @@
 use crate::{
     core_mempool::{CoreMempool, TimelineState},
     network::MempoolSyncMsg,
-    shared_mempool::{tasks, types::SharedMempool},
+    shared_mempool::{
+        tasks,
+        types::{BackPressureStatus, SharedMempool},
+    },
 };
 use aptos_config::{config::NodeConfig, network_id::NetworkId};
 use aptos_infallible::{Mutex, RwLock};
@@ -55,6 +58,7 @@ pub fn test_mempool_process_incoming_transactions_impl(
         vm_validator,
         vec![],
         config.base.role,
+        BackPressureStatus::default(),
     );
 
     let _ = tasks::process_incoming_transactions(&smp, txns, timeline_state, false);
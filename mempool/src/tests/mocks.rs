@@ -5,7 +5,7 @@
 use crate::{
     core_mempool::{CoreMempool, TimelineState},
     shared_mempool::start_shared_mempool,
-    MempoolClientSender, QuorumStoreRequest,
+    BackPressureStatus, MempoolClientSender, QuorumStoreRequest,
 };
 use anyhow::{format_err, Result};
 use aptos_channels::{self, aptos_channel, message_queues::QueueStyle};
@@ -52,6 +52,7 @@ pub struct MockSharedMempool {
     pub mempool: Arc<Mutex<CoreMempool>>,
     pub consensus_to_mempool_sender: mpsc::Sender<QuorumStoreRequest>,
     pub mempool_notifier: MempoolNotifier,
+    pub back_pressure_status: BackPressureStatus,
 }
 
 impl MockSharedMempool {
@@ -61,11 +62,12 @@ impl MockSharedMempool {
     pub fn new() -> Self {
         let runtime = aptos_runtimes::spawn_named_runtime("shared-mem".into(), None);
         let _entered_runtime = runtime.enter();
-        let (ac_client, mempool, quorum_store_sender, mempool_notifier) = Self::start(
-            runtime.handle(),
-            &DbReaderWriter::new(MockDbReaderWriter),
-            MockVMValidator,
-        );
+        let (ac_client, mempool, quorum_store_sender, mempool_notifier, back_pressure_status) =
+            Self::start(
+                runtime.handle(),
+                &DbReaderWriter::new(MockDbReaderWriter),
+                MockVMValidator,
+            );
         Self {
             _runtime: Some(runtime),
             _handle: None,
@@ -73,6 +75,7 @@ impl MockSharedMempool {
             mempool,
             consensus_to_mempool_sender: quorum_store_sender,
             mempool_notifier,
+            back_pressure_status,
         }
     }
 
@@ -83,7 +86,7 @@ impl MockSharedMempool {
         validator: V,
     ) -> Self {
         let handle = Handle::current();
-        let (ac_client, mempool, quorum_store_sender, mempool_notifier) =
+        let (ac_client, mempool, quorum_store_sender, mempool_notifier, back_pressure_status) =
             Self::start(&handle, db, validator);
         Self {
             _runtime: None,
@@ -92,6 +95,7 @@ impl MockSharedMempool {
             mempool,
             consensus_to_mempool_sender: quorum_store_sender,
             mempool_notifier,
+            back_pressure_status,
         }
     }
 
@@ -104,6 +108,7 @@ impl MockSharedMempool {
         Arc<Mutex<CoreMempool>>,
         mpsc::Sender<QuorumStoreRequest>,
         MempoolNotifier,
+        BackPressureStatus,
     ) {
         let mut config = NodeConfig::generate_random_config();
         config.validator_network = Some(NetworkConfig::network_with_id(NetworkId::Validator));
@@ -143,6 +148,7 @@ impl MockSharedMempool {
             network_senders,
             peers_and_metadata.clone(),
         );
+        let back_pressure_status = BackPressureStatus::default();
         let network_and_events = hashmap! {NetworkId::Validator => network_events};
         let network_service_events = NetworkServiceEvents::new(network_and_events);
 
@@ -160,9 +166,16 @@ impl MockSharedMempool {
             Arc::new(RwLock::new(validator)),
             vec![],
             peers_and_metadata,
+            back_pressure_status.clone(),
         );
 
-        (ac_client, mempool, quorum_store_sender, mempool_notifier)
+        (
+            ac_client,
+            mempool,
+            quorum_store_sender,
+            mempool_notifier,
+            back_pressure_status,
+        )
     }
 
     pub fn add_txns(&self, txns: Vec<SignedTransaction>) -> Result<()> {
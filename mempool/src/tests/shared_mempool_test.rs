@@ -5,11 +5,13 @@
 use crate::{
     mocks::MockSharedMempool,
     tests::common::{batch_add_signed_txn, TestTransaction},
-    QuorumStoreRequest,
+    MempoolClientRequest, QuorumStoreRequest,
 };
 use aptos_consensus_types::common::RejectedTransactionSummary;
 use aptos_mempool_notifications::MempoolNotificationSender;
-use aptos_types::{transaction::Transaction, vm_status::DiscardedVMStatus};
+use aptos_types::{
+    mempool_status::MempoolStatusCode, transaction::Transaction, vm_status::DiscardedVMStatus,
+};
 use futures::{channel::oneshot, executor::block_on, sink::SinkExt};
 
 #[test]
@@ -96,3 +98,25 @@ fn test_mempool_notify_committed_txns() {
     assert_eq!(timeline.len(), 1);
     assert_eq!(timeline.first().unwrap(), &kept_txn);
 }
+
+#[test]
+fn test_client_submission_rejected_on_back_pressure() {
+    let smp = MockSharedMempool::new();
+    smp.back_pressure_status.update(true, false);
+
+    let txn = TestTransaction::new(0, 0, 1).make_signed_transaction();
+    let (callback, callback_rcv) = oneshot::channel();
+    let req = MempoolClientRequest::SubmitTransaction(txn.clone(), callback);
+    let mut ac_client = smp.ac_client.clone();
+    let (mempool_status, vm_status) = block_on(async {
+        assert!(ac_client.send(req).await.is_ok());
+        callback_rcv.await.unwrap().unwrap()
+    });
+    assert_eq!(mempool_status.code, MempoolStatusCode::MempoolIsFull);
+    assert!(vm_status.is_none());
+    assert!(smp
+        .mempool
+        .lock()
+        .get_by_hash(txn.committed_hash())
+        .is_none());
+}
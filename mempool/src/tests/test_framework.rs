@@ -6,7 +6,8 @@ use crate::{
     core_mempool::CoreMempool,
     shared_mempool::{start_shared_mempool, types::MultiBatchId},
     tests::{common, common::TestTransaction},
-    MempoolClientRequest, MempoolClientSender, MempoolSyncMsg, QuorumStoreRequest,
+    BackPressureStatus, MempoolClientRequest, MempoolClientSender, MempoolSyncMsg,
+    QuorumStoreRequest,
 };
 use aptos_channels::{aptos_channel, message_queues::QueueStyle};
 use aptos_config::{
@@ -619,6 +620,7 @@ fn setup_mempool(
         vm_validator,
         vec![sender],
         peers_and_metadata,
+        BackPressureStatus::default(),
     );
 
     (
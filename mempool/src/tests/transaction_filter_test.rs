@@ -0,0 +1,124 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    shared_mempool::transaction_filter::{AdmissionControl, TransactionFilter},
+    tests::common::TestTransaction,
+};
+use aptos_config::config::MempoolAdmissionControlConfig;
+use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, Uniform};
+use aptos_types::{
+    chain_id::ChainId,
+    move_utils::MemberId,
+    transaction::{EntryFunction, RawTransaction, SignedTransaction, TransactionPayload},
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+fn make_entry_function_transaction(function: &str) -> SignedTransaction {
+    let MemberId {
+        module_id,
+        member_id,
+    } = str::parse(function).unwrap();
+    let raw_txn = RawTransaction::new(
+        TestTransaction::get_address(0),
+        0,
+        TransactionPayload::EntryFunction(EntryFunction::new(module_id, member_id, vec![], vec![])),
+        100,
+        1,
+        u64::MAX,
+        ChainId::test(),
+    );
+    let privkey = Ed25519PrivateKey::generate(&mut StdRng::from_seed([0u8; 32]));
+    raw_txn
+        .sign(&privkey, privkey.public_key())
+        .expect("Failed to sign raw transaction.")
+        .into_inner()
+}
+
+#[test]
+fn test_no_filter() {
+    let admission_control = AdmissionControl::new(&MempoolAdmissionControlConfig::default());
+    let txn = TestTransaction::new(0, 0, 1).make_signed_transaction_with_max_gas_amount(u64::MAX);
+    assert_eq!(admission_control.reject_reason(&txn), None);
+}
+
+#[test]
+fn test_sender_filter() {
+    let allowed_sender = TestTransaction::get_address(0);
+    let denied_sender = TestTransaction::get_address(1);
+    let admission_control = AdmissionControl::new(&MempoolAdmissionControlConfig {
+        allowed_senders: vec![allowed_sender, denied_sender],
+        denied_senders: vec![denied_sender],
+        ..MempoolAdmissionControlConfig::default()
+    });
+
+    let txn = TestTransaction::new(0, 0, 1).make_signed_transaction();
+    assert_eq!(admission_control.reject_reason(&txn), None);
+    // Denied senders are rejected, even if they are allowed
+    let txn = TestTransaction::new(1, 0, 1).make_signed_transaction();
+    assert!(admission_control.reject_reason(&txn).is_some());
+    // Senders that are not allowed are rejected
+    let txn = TestTransaction::new(2, 0, 1).make_signed_transaction();
+    assert!(admission_control.reject_reason(&txn).is_some());
+}
+
+#[test]
+fn test_entry_function_deny_list() {
+    let admission_control = AdmissionControl::new(&MempoolAdmissionControlConfig {
+        denied_entry_functions: vec![(
+            str::parse("0x1").unwrap(),
+            "test".to_string(),
+            "spam".to_string(),
+        )],
+        ..MempoolAdmissionControlConfig::default()
+    });
+
+    let txn = make_entry_function_transaction("0x1::test::spam");
+    assert!(admission_control.reject_reason(&txn).is_some());
+    let txn = make_entry_function_transaction("0x1::test::transfer");
+    assert_eq!(admission_control.reject_reason(&txn), None);
+    let txn = make_entry_function_transaction("0x2::test::spam");
+    assert_eq!(admission_control.reject_reason(&txn), None);
+    // Scripts are not entry function calls
+    let txn = TestTransaction::new(0, 0, 1).make_signed_transaction();
+    assert_eq!(admission_control.reject_reason(&txn), None);
+}
+
+#[test]
+fn test_max_gas_amount_filter() {
+    let admission_control = AdmissionControl::new(&MempoolAdmissionControlConfig {
+        max_gas_amount: Some(1_000),
+        ..MempoolAdmissionControlConfig::default()
+    });
+
+    let txn = TestTransaction::new(0, 0, 1).make_signed_transaction_with_max_gas_amount(1_000);
+    assert_eq!(admission_control.reject_reason(&txn), None);
+    let txn = TestTransaction::new(0, 0, 1).make_signed_transaction_with_max_gas_amount(1_001);
+    assert!(admission_control.reject_reason(&txn).is_some());
+}
+
+#[test]
+fn test_custom_filter() {
+    struct SequenceNumberFilter;
+
+    impl TransactionFilter for SequenceNumberFilter {
+        fn name(&self) -> &'static str {
+            "sequence_number"
+        }
+
+        fn reject_reason(&self, txn: &SignedTransaction) -> Option<String> {
+            (txn.sequence_number() > 10).then(|| "Sequence number too high".to_string())
+        }
+    }
+
+    let mut admission_control = AdmissionControl::new(&MempoolAdmissionControlConfig::default());
+    admission_control.add_filter(Box::new(SequenceNumberFilter));
+
+    let txn = TestTransaction::new(0, 10, 1).make_signed_transaction();
+    assert_eq!(admission_control.reject_reason(&txn), None);
+    let txn = TestTransaction::new(0, 11, 1).make_signed_transaction();
+    assert_eq!(
+        admission_control.reject_reason(&txn),
+        Some("Sequence number too high".to_string())
+    );
+}
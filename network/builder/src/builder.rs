@@ -12,10 +12,10 @@
 //! long as the latter is in its trusted peers set.
 use aptos_config::{
     config::{
-        DiscoveryMethod, NetworkConfig, Peer, PeerRole, PeerSet, RoleType, CONNECTION_BACKOFF_BASE,
-        CONNECTIVITY_CHECK_INTERVAL_MS, MAX_CONCURRENT_NETWORK_REQS, MAX_CONNECTION_DELAY_MS,
-        MAX_FRAME_SIZE, MAX_FULLNODE_OUTBOUND_CONNECTIONS, MAX_INBOUND_CONNECTIONS,
-        NETWORK_CHANNEL_SIZE,
+        DiscoveryMethod, NatConfig, NetworkConfig, Peer, PeerRole, PeerSet, RoleType,
+        CONNECTION_BACKOFF_BASE, CONNECTIVITY_CHECK_INTERVAL_MS, HANDSHAKE_VERSION,
+        MAX_CONCURRENT_NETWORK_REQS, MAX_CONNECTION_DELAY_MS, MAX_FRAME_SIZE,
+        MAX_FULLNODE_OUTBOUND_CONNECTIONS, MAX_INBOUND_CONNECTIONS, NETWORK_CHANNEL_SIZE,
     },
     network_id::NetworkContext,
 };
@@ -30,8 +30,10 @@ use aptos_network::{
     connectivity_manager::{builder::ConnectivityManagerBuilder, ConnectivityRequest},
     constants::MAX_MESSAGE_SIZE,
     logging::NetworkSchema,
+    nat::{self, builder::NatRelayBuilder},
     peer_manager::{
         builder::{AuthenticationMode, PeerManagerBuilder},
+        deny_list::DenyList,
         ConnectionRequestSender,
     },
     protocols::{
@@ -41,6 +43,7 @@ use aptos_network::{
             NewNetworkSender,
         },
     },
+    ProtocolId,
 };
 use aptos_network_discovery::DiscoveryChangeListener;
 use aptos_time_service::TimeService;
@@ -68,6 +71,7 @@ pub struct NetworkBuilder {
     discovery_listeners: Option<Vec<DiscoveryChangeListener<DbBackedOnChainConfig>>>,
     connectivity_manager_builder: Option<ConnectivityManagerBuilder>,
     health_checker_builder: Option<HealthCheckerBuilder>,
+    nat_relay_builder: Option<NatRelayBuilder>,
     peer_manager_builder: PeerManagerBuilder,
     peers_and_metadata: Arc<PeersAndMetadata>,
 }
@@ -117,6 +121,7 @@ impl NetworkBuilder {
             discovery_listeners: None,
             connectivity_manager_builder: None,
             health_checker_builder: None,
+            nat_relay_builder: None,
             peer_manager_builder,
             peers_and_metadata,
         }
@@ -212,6 +217,55 @@ impl NetworkBuilder {
             config.max_parallel_deserialization_tasks,
         );
 
+        let outbound_protocol_rate_limits = config
+            .outbound_protocol_rate_limits
+            .iter()
+            .map(|(protocol_name, rate_limit)| {
+                let protocol_id =
+                    protocol_id_from_name(protocol_name, "outbound protocol rate limits");
+                assert!(
+                    rate_limit.byte_bucket_size >= rate_limit.byte_bucket_rate,
+                    "The byte bucket size of {} must be at least its rate",
+                    protocol_name
+                );
+                (protocol_id, *rate_limit)
+            })
+            .collect();
+        network_builder
+            .peer_manager_builder
+            .set_peer_utility_weights(config.peer_utility_weights)
+            .add_outbound_protocol_rate_limits(outbound_protocol_rate_limits)
+            .set_deny_list(config.deny_list.clone());
+
+        let zstd_compression_thresholds = config
+            .zstd_compression_thresholds
+            .iter()
+            .map(|(protocol_name, threshold)| {
+                let protocol_id =
+                    protocol_id_from_name(protocol_name, "zstd compression thresholds");
+                (protocol_id, *threshold)
+            })
+            .collect();
+        network_builder
+            .peer_manager_builder
+            .add_zstd_compression_thresholds(zstd_compression_thresholds);
+
+        let nat_config = config.nat_config;
+        if nat_config.request_relayed_connections
+            || nat_config.enable_relay
+            || nat_config.accept_relayed_connections
+        {
+            let advertised_addr = config
+                .listen_address
+                .clone()
+                .append_prod_protos(pubkey, HANDSHAKE_VERSION);
+            network_builder.add_nat_relay(
+                nat_config,
+                vec![advertised_addr],
+                config.max_parallel_deserialization_tasks,
+            );
+        }
+
         // Always add a connectivity manager to keep track of known peers
         let seeds = merge_seeds(config);
 
@@ -301,6 +355,14 @@ impl NetworkBuilder {
             );
         }
 
+        if let Some(nat_relay_builder) = self.nat_relay_builder.as_mut() {
+            nat_relay_builder.start(executor);
+            debug!(
+                NetworkSchema::new(&self.network_context),
+                "{} Started NAT relay", self.network_context
+            );
+        }
+
         if let Some(discovery_listeners) = self.discovery_listeners.take() {
             discovery_listeners
                 .into_iter()
@@ -323,6 +385,12 @@ impl NetworkBuilder {
         self.peer_manager_builder.listen_address()
     }
 
+    /// Returns the deny list of the network, to update it at runtime (e.g., from the admin
+    /// service)
+    pub fn deny_list(&self) -> DenyList {
+        self.peer_manager_builder.deny_list()
+    }
+
     /// Add a `network::connectivity_manager::ConnectivityManager` to the network.
     ///
     /// `network::connectivity_manager::ConnectivityManager` is responsible for ensuring that we are connected
@@ -364,6 +432,9 @@ impl NetworkBuilder {
             pm_conn_mgr_notifs_rx,
             outbound_connection_limit,
             mutual_authentication,
+            self.nat_relay_builder
+                .as_ref()
+                .and_then(|nat_relay_builder| nat_relay_builder.client()),
         ));
         self
     }
@@ -442,6 +513,33 @@ impl NetworkBuilder {
         self
     }
 
+    /// Add the NAT relay service to the network, and the client requesting the connections to
+    /// the undialable peers through their relays (used by the connectivity manager).
+    fn add_nat_relay(
+        &mut self,
+        nat_config: NatConfig,
+        advertised_addrs: Vec<NetworkAddress>,
+        max_parallel_deserialization_tasks: Option<usize>,
+    ) -> &mut Self {
+        let (nat_network_tx, nat_network_rx) = self.add_client_and_service(
+            &nat::nat_relay_network_config(),
+            max_parallel_deserialization_tasks,
+        );
+        self.nat_relay_builder = Some(NatRelayBuilder::new(
+            self.network_context(),
+            nat_config,
+            advertised_addrs,
+            nat_network_tx,
+            nat_network_rx,
+            self.peers_and_metadata.clone(),
+        ));
+        debug!(
+            NetworkSchema::new(&self.network_context),
+            "{} Created NAT relay", self.network_context
+        );
+        self
+    }
+
     /// Register a new client and service application with the network. Return
     /// the client interface for sending messages and the service interface
     /// for handling network requests.
@@ -484,6 +582,12 @@ impl NetworkBuilder {
     }
 }
 
+/// Parse the protocol name of a configuration entry
+fn protocol_id_from_name(protocol_name: &str, config_name: &str) -> ProtocolId {
+    ProtocolId::from_name(protocol_name)
+        .unwrap_or_else(|| panic!("Unknown protocol in the {}: {}", config_name, protocol_name))
+}
+
 /// Retrieve and merge seeds so that they have all keys associated
 fn merge_seeds(config: &NetworkConfig) -> PeerSet {
     config.verify_seeds().expect("Seeds must be well formed");
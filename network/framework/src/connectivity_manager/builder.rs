@@ -6,6 +6,7 @@ use crate::{
     application::storage::PeersAndMetadata,
     connectivity_manager::{ConnectivityManager, ConnectivityRequest},
     counters,
+    nat::NatRelayClient,
     peer_manager::{conn_notifs_channel, ConnectionRequestSender},
 };
 use aptos_config::{config::PeerSet, network_id::NetworkContext};
@@ -35,6 +36,7 @@ impl ConnectivityManagerBuilder {
         connection_notifs_rx: conn_notifs_channel::Receiver,
         outbound_connection_limit: Option<usize>,
         mutual_authentication: bool,
+        nat_relay_client: Option<NatRelayClient>,
     ) -> Self {
         let (conn_mgr_reqs_tx, conn_mgr_reqs_rx) = aptos_channels::new(
             channel_size,
@@ -56,6 +58,7 @@ impl ConnectivityManagerBuilder {
                 Duration::from_millis(max_connection_delay_ms),
                 outbound_connection_limit,
                 mutual_authentication,
+                nat_relay_client,
             )),
         }
     }
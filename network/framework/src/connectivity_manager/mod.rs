@@ -31,6 +31,7 @@ use crate::{
     application::storage::PeersAndMetadata,
     counters,
     logging::NetworkSchema,
+    nat::NatRelayClient,
     peer_manager::{self, conn_notifs_channel, ConnectionRequestSender, PeerManagerError},
     transport::ConnectionMetadata,
 };
@@ -119,6 +120,8 @@ pub struct ConnectivityManager<TBackoff> {
     rng: SmallRng,
     /// Whether we are using mutual authentication or not
     mutual_authentication: bool,
+    /// Requests the connections to the peers that can't be dialed, through their relays
+    nat_relay_client: Option<NatRelayClient>,
 }
 
 /// Different sources for peer addresses, ordered by priority (Onchain=highest,
@@ -202,6 +205,7 @@ struct DiscoveredPeer {
     role: PeerRole,
     addrs: Addresses,
     keys: PublicKeys,
+    relay_peers: RelayPeers,
     /// The last time the node was dialed
     last_dial_time: SystemTime,
 }
@@ -212,6 +216,7 @@ impl DiscoveredPeer {
             role,
             addrs: Addresses::default(),
             keys: PublicKeys::default(),
+            relay_peers: RelayPeers::default(),
             last_dial_time: SystemTime::UNIX_EPOCH,
         }
     }
@@ -226,6 +231,11 @@ impl DiscoveredPeer {
         self.is_eligible() && !self.addrs.is_empty()
     }
 
+    /// Peers without addresses can still connect to us through their relays
+    pub fn is_eligible_for_relayed_connection(&self) -> bool {
+        self.is_eligible() && self.addrs.is_empty() && !self.relay_peers.is_empty()
+    }
+
     /// Updates the last time we tried to connect to this node
     pub fn set_last_dial_time(&mut self, time: SystemTime) {
         self.last_dial_time = time;
@@ -259,7 +269,9 @@ impl PartialOrd for DiscoveredPeer {
 
 impl From<&DiscoveredPeer> for Peer {
     fn from(peer: &DiscoveredPeer) -> Self {
-        Peer::new(peer.addrs.union(), peer.keys.union(), peer.role)
+        let mut new_peer = Peer::new(peer.addrs.union(), peer.keys.union(), peer.role);
+        new_peer.relay_peers = peer.relay_peers.union();
+        new_peer
     }
 }
 
@@ -273,6 +285,10 @@ struct Addresses([Vec<NetworkAddress>; DiscoverySource::NUM_VARIANTS]);
 #[derive(Clone, Default, PartialEq, Serialize)]
 struct PublicKeys([HashSet<x25519::PublicKey>; DiscoverySource::NUM_VARIANTS]);
 
+/// The relay peers of a single peer, bucketed by DiscoverySource in priority order.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+struct RelayPeers([Vec<PeerId>; DiscoverySource::NUM_VARIANTS]);
+
 #[derive(Debug)]
 enum DialResult {
     Success,
@@ -313,6 +329,7 @@ where
         max_delay: Duration,
         outbound_connection_limit: Option<usize>,
         mutual_authentication: bool,
+        nat_relay_client: Option<NatRelayClient>,
     ) -> Self {
         // Verify that the trusted peers set exists and that it is empty
         let trusted_peers = peers_and_metadata
@@ -349,6 +366,7 @@ where
             outbound_connection_limit,
             rng: SmallRng::from_entropy(),
             mutual_authentication,
+            nat_relay_client,
         };
 
         // set the initial config addresses and pubkeys
@@ -669,6 +687,67 @@ where
         // Dial peers which are eligible but are neither connected nor queued for dialing in the
         // future.
         self.dial_eligible_peers(pending_dials);
+        // Ask the relays of the peers that can't be dialed to have them connect to us.
+        self.request_relayed_connections(pending_dials);
+    }
+
+    fn request_relayed_connections<'a>(
+        &'a mut self,
+        pending_dials: &'a mut FuturesUnordered<BoxFuture<'static, PeerId>>,
+    ) {
+        let nat_relay_client = match &self.nat_relay_client {
+            Some(nat_relay_client) => nat_relay_client.clone(),
+            None => return,
+        };
+        let roles_to_dial = self
+            .network_context
+            .network_id()
+            .upstream_roles(&self.network_context.role());
+        let to_request: Vec<_> = self
+            .discovered_peers
+            .0
+            .iter()
+            .filter(|(peer_id, peer)| {
+                peer.is_eligible_for_relayed_connection()
+                    && !self.connected.contains_key(peer_id)
+                    && !self.dial_queue.contains_key(peer_id)
+                    && roles_to_dial.contains(&peer.role)
+                    && !peer.has_dialed_recently(self.max_delay)
+            })
+            .map(|(peer_id, peer)| (*peer_id, peer.relay_peers.union()))
+            .collect();
+
+        for (peer_id, relay_peers) in to_request {
+            let nat_relay_client = nat_relay_client.clone();
+            let (cancel_tx, cancel_rx) = oneshot::channel();
+            let network_context = self.network_context;
+            // The request completes once the peer has dialed us back, or on cancellation (e.g.,
+            // when the peer connects).
+            let f = async move {
+                futures::select! {
+                    result = nat_relay_client.request_connection(peer_id, relay_peers).fuse() => {
+                        if let Err(error) = result {
+                            info!(
+                                NetworkSchema::new(&network_context).remote_peer(&peer_id),
+                                error = %error,
+                                "{} Failed to request a relayed connection from peer {}: {}",
+                                network_context,
+                                peer_id.short_str(),
+                                error
+                            );
+                        }
+                    },
+                    _ = cancel_rx.fuse() => {},
+                }
+                peer_id
+            };
+            pending_dials.push(f.boxed());
+
+            if let Some(discovered_peer) = self.discovered_peers.get_mut(&peer_id) {
+                discovered_peer.set_last_dial_time(SystemTime::now())
+            }
+            self.dial_queue.insert(peer_id, cancel_tx);
+        }
     }
 
     fn reset_dial_state(&mut self, peer_id: &PeerId) {
@@ -732,10 +811,14 @@ where
                 if new_peer.addresses.is_empty() {
                     peer.addrs.clear_src(src);
                 }
+                if new_peer.relay_peers.is_empty() {
+                    peer.relay_peers.clear_src(src);
+                }
                 new_peer.addresses.is_empty() && new_peer.keys.is_empty()
             } else {
                 keys_updated |= peer.keys.clear_src(src);
                 peer.addrs.clear_src(src);
+                peer.relay_peers.clear_src(src);
                 true
             };
             if check_remove {
@@ -791,6 +874,18 @@ where
                 peer_updated = true;
             }
 
+            // Update peer's relay peers
+            if peer.relay_peers.update(src, discovered_peer.relay_peers) {
+                info!(
+                    NetworkSchema::new(&self.network_context).remote_peer(&peer_id),
+                    "{} relay peers updated for peer: {}, update src: {:?}, relay peers: {:?}",
+                    self.network_context,
+                    peer_id.short_str(),
+                    src,
+                    &peer.relay_peers,
+                );
+            }
+
             // If we're currently trying to dial this peer, we reset their
             // dial state. As a result, we will begin our next dial attempt
             // from the first address (which might have changed) and from a
@@ -1043,6 +1138,41 @@ impl fmt::Debug for PublicKeys {
     }
 }
 
+////////////////
+// RelayPeers //
+////////////////
+
+impl RelayPeers {
+    fn is_empty(&self) -> bool {
+        self.0.iter().all(Vec::is_empty)
+    }
+
+    fn update(&mut self, src: DiscoverySource, relay_peers: Vec<PeerId>) -> bool {
+        let src_idx = src.as_usize();
+        if self.0[src_idx] != relay_peers {
+            self.0[src_idx] = relay_peers;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn clear_src(&mut self, src: DiscoverySource) -> bool {
+        self.update(src, Vec::new())
+    }
+
+    /// The relay peers in priority order, without duplicates
+    fn union(&self) -> Vec<PeerId> {
+        let mut relay_peers = Vec::new();
+        for relay_peer in self.0.iter().flatten() {
+            if !relay_peers.contains(relay_peer) {
+                relay_peers.push(*relay_peer);
+            }
+        }
+        relay_peers
+    }
+}
+
 ///////////////
 // DialState //
 ///////////////
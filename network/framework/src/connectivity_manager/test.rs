@@ -106,6 +106,7 @@ impl TestHarness {
             MAX_CONNECTION_DELAY,
             Some(MAX_TEST_CONNECTIONS),
             true, /* mutual_authentication */
+            None, /* nat_relay_client */
         );
         let mock = Self {
             network_context,
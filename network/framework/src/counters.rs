@@ -16,6 +16,7 @@ use once_cell::sync::Lazy;
 // some type labels
 pub const REQUEST_LABEL: &str = "request";
 pub const RESPONSE_LABEL: &str = "response";
+pub const DIRECT_SEND_LABEL: &str = "direct_send";
 
 // some state labels
 pub const CANCELED_LABEL: &str = "canceled";
@@ -72,6 +73,44 @@ pub fn connections_rejected(
     ])
 }
 
+pub static APTOS_CONNECTIONS_EVICTED: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_connections_evicted",
+        "Number of inbound connections evicted for more useful peers",
+        &["role_type", "network_id", "peer_id"]
+    )
+    .unwrap()
+});
+
+pub fn connections_evicted(network_context: &NetworkContext) -> IntCounter {
+    APTOS_CONNECTIONS_EVICTED.with_label_values(&[
+        network_context.role().as_str(),
+        network_context.network_id().as_str(),
+        network_context.peer_id().short_str().as_str(),
+    ])
+}
+
+pub static APTOS_CONNECTIONS_DENIED: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_connections_denied",
+        "Number of connections closed (or dials refused) because of the deny list",
+        &["role_type", "network_id", "peer_id", "direction"]
+    )
+    .unwrap()
+});
+
+pub fn connections_denied(
+    network_context: &NetworkContext,
+    origin: ConnectionOrigin,
+) -> IntCounter {
+    APTOS_CONNECTIONS_DENIED.with_label_values(&[
+        network_context.role().as_str(),
+        network_context.network_id().as_str(),
+        network_context.peer_id().short_str().as_str(),
+        origin.as_str(),
+    ])
+}
+
 pub static APTOS_NETWORK_PEER_CONNECTED: Lazy<IntGaugeVec> = Lazy::new(|| {
     register_int_gauge_vec!(
         "aptos_network_peer_connected",
@@ -348,6 +387,15 @@ pub static PENDING_NETWORK_REQUESTS: Lazy<IntCounterVec> = Lazy::new(|| {
 });
 
 /// Counter of pending network events to Health Checker.
+pub static PENDING_NAT_RELAY_NETWORK_EVENTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_pending_nat_relay_events",
+        "Number of pending NAT relay events by state",
+        &["state"]
+    )
+    .unwrap()
+});
+
 pub static PENDING_HEALTH_CHECKER_NETWORK_EVENTS: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
         "aptos_network_pending_health_check_events",
@@ -516,6 +564,140 @@ pub static NETWORK_RATE_LIMIT_METRICS: Lazy<HistogramVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Counter of the application bytes exchanged with each peer, by protocol
+pub static APTOS_NETWORK_PEER_PROTOCOL_BYTES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_peer_protocol_bytes",
+        "Number of application bytes exchanged with a peer, by protocol",
+        &[
+            "role_type",
+            "network_id",
+            "peer_id",
+            "remote_peer_id",
+            "protocol_id",
+            "direction"
+        ]
+    )
+    .unwrap()
+});
+
+pub fn peer_protocol_bytes(
+    network_context: &NetworkContext,
+    remote_peer_id: &PeerId,
+    protocol_id: ProtocolId,
+    direction_label: &str,
+    num_bytes: u64,
+) {
+    // Only the validator network has a bounded set of peers
+    if network_context.network_id().is_validator_network() {
+        APTOS_NETWORK_PEER_PROTOCOL_BYTES
+            .with_label_values(&[
+                network_context.role().as_str(),
+                network_context.network_id().as_str(),
+                network_context.peer_id().short_str().as_str(),
+                remote_peer_id.short_str().as_str(),
+                protocol_id.as_str(),
+                direction_label,
+            ])
+            .inc_by(num_bytes)
+    }
+}
+
+/// Counter of the bytes of the messages sent zstd compressed, before and after compression
+pub static APTOS_NETWORK_ZSTD_COMPRESSION_BYTES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_zstd_compression_bytes",
+        "Number of bytes of the messages sent zstd compressed, before and after compression",
+        &[
+            "role_type",
+            "network_id",
+            "peer_id",
+            "protocol_id",
+            "metric"
+        ]
+    )
+    .unwrap()
+});
+
+pub fn zstd_compression_bytes(
+    network_context: &NetworkContext,
+    protocol_id: ProtocolId,
+    raw_bytes: usize,
+    compressed_bytes: usize,
+) {
+    for (metric, num_bytes) in [("raw", raw_bytes), ("compressed", compressed_bytes)] {
+        APTOS_NETWORK_ZSTD_COMPRESSION_BYTES
+            .with_label_values(&[
+                network_context.role().as_str(),
+                network_context.network_id().as_str(),
+                network_context.peer_id().short_str().as_str(),
+                protocol_id.as_str(),
+                metric,
+            ])
+            .inc_by(num_bytes as u64);
+    }
+}
+
+/// Counter of the outbound messages dropped by the protocol rate limits
+pub static APTOS_NETWORK_RATE_LIMITED_MESSAGES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_rate_limited_messages",
+        "Number of outbound messages dropped by the protocol rate limits",
+        &[
+            "role_type",
+            "network_id",
+            "peer_id",
+            "protocol_id",
+            "message_type"
+        ]
+    )
+    .unwrap()
+});
+
+pub fn rate_limited_messages(
+    network_context: &NetworkContext,
+    protocol_id: ProtocolId,
+    message_type_label: &str,
+) -> IntCounter {
+    APTOS_NETWORK_RATE_LIMITED_MESSAGES.with_label_values(&[
+        network_context.role().as_str(),
+        network_context.network_id().as_str(),
+        network_context.peer_id().short_str().as_str(),
+        protocol_id.as_str(),
+        message_type_label,
+    ])
+}
+
+/// Counter of the relayed connection requests, by the part this node plays in them
+pub static APTOS_NETWORK_NAT_RELAY_REQUESTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_nat_relay_requests",
+        "Number of relayed connection requests sent, relayed or served",
+        &[
+            "role_type",
+            "network_id",
+            "peer_id",
+            "request_type",
+            "result"
+        ]
+    )
+    .unwrap()
+});
+
+pub fn nat_relay_requests(
+    network_context: &NetworkContext,
+    request_type_label: &str,
+    result_label: &str,
+) -> IntCounter {
+    APTOS_NETWORK_NAT_RELAY_REQUESTS.with_label_values(&[
+        network_context.role().as_str(),
+        network_context.network_id().as_str(),
+        network_context.peer_id().short_str().as_str(),
+        request_type_label,
+        result_label,
+    ])
+}
+
 pub static NETWORK_APPLICATION_INBOUND_METRIC: Lazy<HistogramVec> = Lazy::new(|| {
     register_histogram_vec!(
         "aptos_network_app_inbound_traffic",
@@ -0,0 +1,66 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    application::storage::PeersAndMetadata,
+    nat::{NatRelayClient, NatRelayMsg, NatRelayNetworkEvents, NatRelayService},
+    protocols::network::NetworkSender,
+};
+use aptos_config::{config::NatConfig, network_id::NetworkContext};
+use aptos_logger::prelude::*;
+use aptos_types::network_address::NetworkAddress;
+use std::{sync::Arc, time::Duration};
+use tokio::runtime::Handle;
+
+pub struct NatRelayBuilder {
+    client: Option<NatRelayClient>,
+    service: Option<NatRelayService>,
+}
+
+impl NatRelayBuilder {
+    pub fn new(
+        network_context: NetworkContext,
+        nat_config: NatConfig,
+        advertised_addrs: Vec<NetworkAddress>,
+        network_sender: NetworkSender<NatRelayMsg>,
+        network_rx: NatRelayNetworkEvents,
+        peers_and_metadata: Arc<PeersAndMetadata>,
+    ) -> Self {
+        let request_timeout = Duration::from_millis(nat_config.relay_request_timeout_ms);
+        let client = if nat_config.request_relayed_connections {
+            Some(NatRelayClient::new(
+                network_context,
+                network_sender.clone(),
+                peers_and_metadata.clone(),
+                advertised_addrs,
+                request_timeout,
+            ))
+        } else {
+            None
+        };
+        let service = NatRelayService::new(
+            network_context,
+            network_sender,
+            network_rx,
+            peers_and_metadata,
+            nat_config.enable_relay,
+            nat_config.accept_relayed_connections,
+            request_timeout,
+        );
+        Self {
+            client,
+            service: Some(service),
+        }
+    }
+
+    /// Returns the client requesting relayed connections, if enabled
+    pub fn client(&self) -> Option<NatRelayClient> {
+        self.client.clone()
+    }
+
+    pub fn start(&mut self, executor: &Handle) {
+        if let Some(service) = self.service.take() {
+            spawn_named!("[Network] NAT relay", executor, service.start());
+        }
+    }
+}
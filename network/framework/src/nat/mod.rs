@@ -0,0 +1,512 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Relay-assisted connection reversal, for the peers that can only dial out (e.g., behind a NAT)
+//!
+//! A peer that can't accept inbound connections advertises no addresses in its discovery note,
+//! only the relay peers it stays connected to (e.g., its seed peers). A peer that wants to connect
+//! to it sends a `RelayConnectRequest` with its own addresses to one of these relays, which
+//! forwards them in a `ReverseConnectRequest` to the undialable peer, which then dials back.
+//!
+//! The relay substitutes the IP it observes on the connection of the requesting peer for the
+//! unspecified IPs of its addresses (e.g., a `0.0.0.0` listen address), so the requesting peer
+//! must be reachable at that IP. Connecting two peers that are both behind a NAT (i.e., hole
+//! punching through simultaneous dials) is not supported: the noise handshake requires one side
+//! to be the dialer.
+use crate::{
+    application::storage::PeersAndMetadata,
+    constants::NETWORK_CHANNEL_SIZE,
+    counters::{self, FAILED_LABEL, SUCCEEDED_LABEL},
+    logging::NetworkSchema,
+    protocols::{
+        network::{
+            Event, NetworkApplicationConfig, NetworkClientConfig, NetworkEvents, NetworkSender,
+            NetworkServiceConfig,
+        },
+        rpc::error::RpcError,
+    },
+    ProtocolId,
+};
+use aptos_channels::{aptos_channel, message_queues::QueueStyle};
+use aptos_config::{
+    config::PeerRole,
+    network_id::{NetworkContext, PeerNetworkId},
+};
+use aptos_logger::prelude::*;
+use aptos_short_hex_str::AsShortHexStr;
+use aptos_types::{
+    network_address::{NetworkAddress, Protocol},
+    PeerId,
+};
+use bytes::Bytes;
+use futures::{
+    channel::oneshot,
+    future::{BoxFuture, FutureExt},
+    stream::{FuturesUnordered, StreamExt},
+};
+use serde::{Deserialize, Serialize};
+use std::{net::IpAddr, sync::Arc, time::Duration};
+use thiserror::Error;
+
+pub mod builder;
+
+/// The maximum number of relayed connection requests served concurrently
+const MAX_PENDING_REQUESTS: usize = 16;
+
+/// Labels of the relayed connection requests, by the part this node plays in them
+const REQUEST_LABEL: &str = "request";
+const RELAY_LABEL: &str = "relay";
+const REVERSE_CONNECT_LABEL: &str = "reverse_connect";
+
+/// Returns a network application config for the NAT relay client and service
+pub fn nat_relay_network_config() -> NetworkApplicationConfig {
+    let direct_send_protocols = vec![]; // The NAT relay doesn't use direct send
+    let rpc_protocols = vec![ProtocolId::NatRelayRpc];
+
+    let network_client_config =
+        NetworkClientConfig::new(direct_send_protocols.clone(), rpc_protocols.clone());
+    let network_service_config = NetworkServiceConfig::new(
+        direct_send_protocols,
+        rpc_protocols,
+        aptos_channel::Config::new(NETWORK_CHANNEL_SIZE)
+            .queue_style(QueueStyle::FIFO)
+            .counters(&counters::PENDING_NAT_RELAY_NETWORK_EVENTS),
+    );
+    NetworkApplicationConfig::new(network_client_config, network_service_config)
+}
+
+pub type NatRelayNetworkEvents = NetworkEvents<NatRelayMsg>;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum NatRelayMsg {
+    RelayConnectRequest(RelayConnectRequest),
+    ReverseConnectRequest(ReverseConnectRequest),
+    Response(Result<(), NatRelayError>),
+}
+
+/// Sent to a relay, to forward the addresses of the sender to the target
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct RelayConnectRequest {
+    pub target: PeerId,
+    pub initiator_addrs: Vec<NetworkAddress>,
+}
+
+/// Sent by a relay, for the receiver to dial the initiator at the given addresses
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ReverseConnectRequest {
+    pub initiator: PeerId,
+    pub initiator_addrs: Vec<NetworkAddress>,
+}
+
+#[derive(Clone, Debug, Deserialize, Error, PartialEq, Eq, Serialize)]
+pub enum NatRelayError {
+    #[error("No relay peer is connected")]
+    NoRelayConnected,
+    #[error("The peer doesn't relay connection requests")]
+    RelayDisabled,
+    #[error("The target peer isn't connected to the relay")]
+    TargetNotConnected,
+    #[error("The peer doesn't accept relayed connection requests")]
+    RelayedConnectionsDisabled,
+    #[error("The relay has an unknown role")]
+    UnknownRelay,
+    #[error("The initiator has no dialable address")]
+    InvalidAddresses,
+    #[error("Too many pending relayed connection requests")]
+    TooManyRequests,
+    #[error("Failed to relay the request: {0}")]
+    RelayFailed(String),
+    #[error("Failed to dial the initiator: {0}")]
+    DialFailed(String),
+}
+
+/// Requests the connections to the undialable peers through their relays
+#[derive(Clone, Debug)]
+pub struct NatRelayClient {
+    network_context: NetworkContext,
+    network_sender: NetworkSender<NatRelayMsg>,
+    peers_and_metadata: Arc<PeersAndMetadata>,
+    /// The addresses at which the undialable peers dial us back
+    advertised_addrs: Vec<NetworkAddress>,
+    request_timeout: Duration,
+}
+
+impl NatRelayClient {
+    pub fn new(
+        network_context: NetworkContext,
+        network_sender: NetworkSender<NatRelayMsg>,
+        peers_and_metadata: Arc<PeersAndMetadata>,
+        advertised_addrs: Vec<NetworkAddress>,
+        request_timeout: Duration,
+    ) -> Self {
+        Self {
+            network_context,
+            network_sender,
+            peers_and_metadata,
+            advertised_addrs,
+            request_timeout,
+        }
+    }
+
+    /// Asks the first connected relay of the target to have the target dial us back
+    pub async fn request_connection(
+        &self,
+        target: PeerId,
+        relay_peers: Vec<PeerId>,
+    ) -> Result<(), NatRelayError> {
+        let result = self.send_relay_connect_request(target, relay_peers).await;
+        counters::nat_relay_requests(&self.network_context, REQUEST_LABEL, result_label(&result))
+            .inc();
+        result
+    }
+
+    async fn send_relay_connect_request(
+        &self,
+        target: PeerId,
+        relay_peers: Vec<PeerId>,
+    ) -> Result<(), NatRelayError> {
+        let relay = relay_peers
+            .into_iter()
+            .find(|relay| {
+                is_connected_with_nat_relay(&self.peers_and_metadata, &self.network_context, *relay)
+            })
+            .ok_or(NatRelayError::NoRelayConnected)?;
+
+        let request = NatRelayMsg::RelayConnectRequest(RelayConnectRequest {
+            target,
+            initiator_addrs: self.advertised_addrs.clone(),
+        });
+        match self
+            .network_sender
+            .send_rpc(
+                relay,
+                ProtocolId::NatRelayRpc,
+                request,
+                self.request_timeout,
+            )
+            .await
+        {
+            Ok(NatRelayMsg::Response(result)) => result,
+            Ok(response) => Err(NatRelayError::RelayFailed(format!(
+                "Unexpected response: {:?}",
+                response
+            ))),
+            Err(error) => Err(NatRelayError::RelayFailed(error.to_string())),
+        }
+    }
+}
+
+/// Serves the relayed connection requests: forwards them to the targets when acting as a relay,
+/// and dials back the initiators when acting as a target.
+pub struct NatRelayService {
+    network_context: NetworkContext,
+    network_sender: NetworkSender<NatRelayMsg>,
+    network_events: NatRelayNetworkEvents,
+    peers_and_metadata: Arc<PeersAndMetadata>,
+    enable_relay: bool,
+    accept_relayed_connections: bool,
+    request_timeout: Duration,
+}
+
+impl NatRelayService {
+    pub fn new(
+        network_context: NetworkContext,
+        network_sender: NetworkSender<NatRelayMsg>,
+        network_events: NatRelayNetworkEvents,
+        peers_and_metadata: Arc<PeersAndMetadata>,
+        enable_relay: bool,
+        accept_relayed_connections: bool,
+        request_timeout: Duration,
+    ) -> Self {
+        Self {
+            network_context,
+            network_sender,
+            network_events,
+            peers_and_metadata,
+            enable_relay,
+            accept_relayed_connections,
+            request_timeout,
+        }
+    }
+
+    pub async fn start(mut self) {
+        let mut pending_requests = FuturesUnordered::new();
+        info!(
+            NetworkSchema::new(&self.network_context),
+            "{} NAT relay service started", self.network_context
+        );
+
+        loop {
+            futures::select! {
+                maybe_event = self.network_events.next() => {
+                    // Shutdown the service when this network instance shuts down
+                    let event = match maybe_event {
+                        Some(event) => event,
+                        None => break,
+                    };
+
+                    match event {
+                        Event::RpcRequest(peer_id, msg, protocol, res_tx) => {
+                            let response = if pending_requests.len() >= MAX_PENDING_REQUESTS {
+                                Err(NatRelayError::TooManyRequests)
+                            } else {
+                                match msg {
+                                    NatRelayMsg::RelayConnectRequest(request) => {
+                                        self.handle_relay_connect_request(peer_id, request)
+                                    },
+                                    NatRelayMsg::ReverseConnectRequest(request) => {
+                                        self.handle_reverse_connect_request(peer_id, request)
+                                    },
+                                    NatRelayMsg::Response(_) => {
+                                        warn!(
+                                            NetworkSchema::new(&self.network_context).remote_peer(&peer_id),
+                                            "{} Unexpected NAT relay response from {}",
+                                            self.network_context,
+                                            peer_id.short_str()
+                                        );
+                                        continue;
+                                    },
+                                }
+                            };
+                            pending_requests.push(
+                                Self::respond(self.network_context, response, protocol, res_tx)
+                            );
+                        },
+                        Event::Message(peer_id, msg) => {
+                            warn!(
+                                NetworkSchema::new(&self.network_context).remote_peer(&peer_id),
+                                "{} Unexpected direct send from {} msg {:?}",
+                                self.network_context,
+                                peer_id.short_str(),
+                                msg,
+                            );
+                        },
+                        Event::NewPeer(_) | Event::LostPeer(_) => {},
+                    }
+                },
+                _ = pending_requests.select_next_some() => {},
+            }
+        }
+        warn!(
+            NetworkSchema::new(&self.network_context),
+            "{} NAT relay service terminated", self.network_context
+        );
+    }
+
+    /// Forwards the request of the initiator to the target
+    fn handle_relay_connect_request(
+        &self,
+        initiator: PeerId,
+        request: RelayConnectRequest,
+    ) -> Result<BoxFuture<'static, Result<(), NatRelayError>>, NatRelayError> {
+        if !self.enable_relay {
+            return Err(NatRelayError::RelayDisabled);
+        }
+        let target = request.target;
+        if !is_connected_with_nat_relay(&self.peers_and_metadata, &self.network_context, target) {
+            return Err(NatRelayError::TargetNotConnected);
+        }
+
+        // Fill in the unspecified IPs with the one we observe on the connection
+        let observed_ip = self
+            .peers_and_metadata
+            .get_metadata_for_peer(PeerNetworkId::new(
+                self.network_context.network_id(),
+                initiator,
+            ))
+            .ok()
+            .and_then(|metadata| metadata.get_connection_metadata().addr.find_ip_addr())
+            .ok_or(NatRelayError::InvalidAddresses)?;
+        let initiator_addrs = request
+            .initiator_addrs
+            .iter()
+            .map(|addr| substitute_unspecified_ip(addr, observed_ip))
+            .collect();
+
+        trace!(
+            NetworkSchema::new(&self.network_context).remote_peer(&initiator),
+            "{} Relaying connection request from {} to {}",
+            self.network_context,
+            initiator.short_str(),
+            target.short_str()
+        );
+        let network_context = self.network_context;
+        let network_sender = self.network_sender.clone();
+        let request = NatRelayMsg::ReverseConnectRequest(ReverseConnectRequest {
+            initiator,
+            initiator_addrs,
+        });
+        let request_timeout = self.request_timeout;
+        Ok(async move {
+            let result = match network_sender
+                .send_rpc(target, ProtocolId::NatRelayRpc, request, request_timeout)
+                .await
+            {
+                Ok(NatRelayMsg::Response(result)) => result,
+                Ok(response) => Err(NatRelayError::RelayFailed(format!(
+                    "Unexpected response: {:?}",
+                    response
+                ))),
+                Err(error) => Err(NatRelayError::RelayFailed(error.to_string())),
+            };
+            counters::nat_relay_requests(&network_context, RELAY_LABEL, result_label(&result))
+                .inc();
+            result
+        }
+        .boxed())
+    }
+
+    /// Dials back the initiator of a request forwarded by a relay
+    fn handle_reverse_connect_request(
+        &self,
+        relay: PeerId,
+        request: ReverseConnectRequest,
+    ) -> Result<BoxFuture<'static, Result<(), NatRelayError>>, NatRelayError> {
+        if !self.accept_relayed_connections {
+            return Err(NatRelayError::RelayedConnectionsDisabled);
+        }
+
+        // Only known relays can make us dial out, to avoid being used to dial arbitrary addresses
+        let relay_role = self
+            .peers_and_metadata
+            .get_metadata_for_peer(PeerNetworkId::new(self.network_context.network_id(), relay))
+            .map(|metadata| metadata.get_connection_metadata().role)
+            .unwrap_or(PeerRole::Unknown);
+        if relay_role == PeerRole::Unknown {
+            return Err(NatRelayError::UnknownRelay);
+        }
+
+        let initiator = request.initiator;
+        let addr = request
+            .initiator_addrs
+            .into_iter()
+            .find(|addr| addr.is_aptosnet_addr())
+            .ok_or(NatRelayError::InvalidAddresses)?;
+
+        info!(
+            NetworkSchema::new(&self.network_context)
+                .remote_peer(&initiator)
+                .network_address(&addr),
+            "{} Dialing back peer {} at {}, on the request relayed by {}",
+            self.network_context,
+            initiator.short_str(),
+            addr,
+            relay.short_str()
+        );
+        let network_context = self.network_context;
+        let network_sender = self.network_sender.clone();
+        Ok(async move {
+            let result = network_sender
+                .dial_peer(initiator, addr)
+                .await
+                .map_err(|error| NatRelayError::DialFailed(error.to_string()));
+            counters::nat_relay_requests(
+                &network_context,
+                REVERSE_CONNECT_LABEL,
+                result_label(&result),
+            )
+            .inc();
+            result
+        }
+        .boxed())
+    }
+
+    fn respond(
+        network_context: NetworkContext,
+        response: Result<BoxFuture<'static, Result<(), NatRelayError>>, NatRelayError>,
+        protocol: ProtocolId,
+        res_tx: oneshot::Sender<Result<Bytes, RpcError>>,
+    ) -> BoxFuture<'static, ()> {
+        async move {
+            let result = match response {
+                Ok(request) => request.await,
+                Err(error) => Err(error),
+            };
+            match protocol.to_bytes(&NatRelayMsg::Response(result)) {
+                Ok(message) => {
+                    let _ = res_tx.send(Ok(message.into()));
+                },
+                Err(error) => {
+                    warn!(
+                        NetworkSchema::new(&network_context),
+                        error = ?error,
+                        "{} Unable to serialize NAT relay response: {}", network_context, error
+                    );
+                },
+            }
+        }
+        .boxed()
+    }
+}
+
+/// Returns true iff the peer is connected and supports the NAT relay protocol
+fn is_connected_with_nat_relay(
+    peers_and_metadata: &PeersAndMetadata,
+    network_context: &NetworkContext,
+    peer_id: PeerId,
+) -> bool {
+    peers_and_metadata
+        .get_metadata_for_peer(PeerNetworkId::new(network_context.network_id(), peer_id))
+        .map_or(false, |metadata| {
+            metadata.is_connected() && metadata.supports_protocol(ProtocolId::NatRelayRpc)
+        })
+}
+
+/// Replaces the unspecified IP of the address (e.g., `0.0.0.0`) with the given one
+fn substitute_unspecified_ip(addr: &NetworkAddress, ip: IpAddr) -> NetworkAddress {
+    let protocols = addr
+        .as_slice()
+        .iter()
+        .map(|protocol| match protocol {
+            Protocol::Ip4(addr) if addr.is_unspecified() => ip_protocol(ip),
+            Protocol::Ip6(addr) if addr.is_unspecified() => ip_protocol(ip),
+            protocol => protocol.clone(),
+        })
+        .collect();
+    NetworkAddress::from_protocols(protocols).unwrap_or_else(|_| addr.clone())
+}
+
+fn ip_protocol(ip: IpAddr) -> Protocol {
+    match ip {
+        IpAddr::V4(ip) => Protocol::Ip4(ip),
+        IpAddr::V6(ip) => Protocol::Ip6(ip),
+    }
+}
+
+fn result_label(result: &Result<(), NatRelayError>) -> &'static str {
+    if result.is_ok() {
+        SUCCEEDED_LABEL
+    } else {
+        FAILED_LABEL
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::substitute_unspecified_ip;
+    use aptos_types::network_address::NetworkAddress;
+    use std::{net::IpAddr, str::FromStr};
+
+    #[test]
+    fn test_substitute_unspecified_ip() {
+        let observed_ip = IpAddr::from_str("1.2.3.4").unwrap();
+        let pubkey = "080e287879c918794170e258bfaddd75acac5b3e350419044655e4983a487120";
+
+        // The unspecified IP is replaced, the rest of the address is kept
+        let addr = NetworkAddress::from_str(&format!(
+            "/ip4/0.0.0.0/tcp/6182/noise-ik/{}/handshake/0",
+            pubkey
+        ))
+        .unwrap();
+        assert_eq!(
+            substitute_unspecified_ip(&addr, observed_ip).to_string(),
+            format!("/ip4/1.2.3.4/tcp/6182/noise-ik/0x{}/handshake/0", pubkey)
+        );
+
+        // Specified IPs and DNS names are kept
+        for addr in ["/ip4/5.6.7.8/tcp/6182", "/dns/example.com/tcp/6182"] {
+            let addr = NetworkAddress::from_str(addr).unwrap();
+            assert_eq!(substitute_unspecified_ip(&addr, observed_ip), addr);
+        }
+    }
+}
@@ -0,0 +1,128 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    counters::{self, INBOUND_LABEL, OUTBOUND_LABEL},
+    ProtocolId,
+};
+use aptos_config::{config::ProtocolRateLimitConfig, network_id::NetworkContext};
+use aptos_rate_limiter::rate_limit::Bucket;
+use aptos_short_hex_str::AsShortHexStr;
+use aptos_types::PeerId;
+use std::collections::HashMap;
+
+/// Accounts the application bytes exchanged with a single peer per protocol, and limits the
+/// outbound bandwidth of the rate limited protocols so that a single protocol can't starve the
+/// others on a saturated link.
+pub struct ProtocolBandwidth {
+    network_context: NetworkContext,
+    remote_peer_id: PeerId,
+    /// The outbound token buckets (in bytes) of the rate limited protocols
+    outbound_buckets: HashMap<ProtocolId, Bucket>,
+}
+
+impl ProtocolBandwidth {
+    pub fn new(
+        network_context: NetworkContext,
+        remote_peer_id: PeerId,
+        outbound_rate_limits: HashMap<ProtocolId, ProtocolRateLimitConfig>,
+    ) -> Self {
+        let outbound_buckets = outbound_rate_limits
+            .into_iter()
+            .map(|(protocol_id, rate_limit)| {
+                let bucket = Bucket::new(
+                    network_context.to_string(),
+                    remote_peer_id.short_str().to_string(),
+                    protocol_id.as_str().to_string(),
+                    rate_limit.byte_bucket_size,
+                    rate_limit.byte_bucket_size,
+                    rate_limit.byte_bucket_rate,
+                    None,
+                );
+                (protocol_id, bucket)
+            })
+            .collect();
+        Self {
+            network_context,
+            remote_peer_id,
+            outbound_buckets,
+        }
+    }
+
+    /// Accounts the bytes received for the given protocol
+    pub fn record_inbound(&self, protocol_id: ProtocolId, num_bytes: usize) {
+        counters::peer_protocol_bytes(
+            &self.network_context,
+            &self.remote_peer_id,
+            protocol_id,
+            INBOUND_LABEL,
+            num_bytes as u64,
+        );
+    }
+
+    /// Returns true iff the message can be sent under the protocol's rate limit, in which case
+    /// its bytes are accounted. A message larger than the bucket is let through whenever the
+    /// bucket isn't empty, which drains it.
+    pub fn try_outbound(&mut self, protocol_id: ProtocolId, num_bytes: usize) -> bool {
+        if let Some(bucket) = self.outbound_buckets.get_mut(&protocol_id) {
+            let allowed = match bucket.acquire_all_tokens(num_bytes) {
+                Ok(()) => true,
+                Err(Some(_)) => false,
+                Err(None) => bucket.acquire_tokens(num_bytes).is_ok(),
+            };
+            if !allowed {
+                return false;
+            }
+        }
+
+        counters::peer_protocol_bytes(
+            &self.network_context,
+            &self.remote_peer_id,
+            protocol_id,
+            OUTBOUND_LABEL,
+            num_bytes as u64,
+        );
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::ProtocolBandwidth;
+    use crate::ProtocolId;
+    use aptos_config::{config::ProtocolRateLimitConfig, network_id::NetworkContext};
+    use aptos_types::PeerId;
+    use maplit::hashmap;
+
+    #[test]
+    fn test_outbound_rate_limit() {
+        let rate_limits = hashmap! {
+            ProtocolId::StorageServiceRpc => ProtocolRateLimitConfig {
+                byte_bucket_rate: 100,
+                byte_bucket_size: 100,
+            },
+        };
+        let mut bandwidth =
+            ProtocolBandwidth::new(NetworkContext::mock(), PeerId::random(), rate_limits);
+
+        // The rate limited protocol is capped by its bucket
+        assert!(bandwidth.try_outbound(ProtocolId::StorageServiceRpc, 60));
+        assert!(!bandwidth.try_outbound(ProtocolId::StorageServiceRpc, 60));
+        assert!(bandwidth.try_outbound(ProtocolId::StorageServiceRpc, 40));
+        assert!(!bandwidth.try_outbound(ProtocolId::StorageServiceRpc, 1));
+
+        // The other protocols aren't affected
+        assert!(bandwidth.try_outbound(ProtocolId::ConsensusRpcBcs, 1_000));
+
+        // A message larger than the bucket drains it
+        let mut bandwidth =
+            ProtocolBandwidth::new(NetworkContext::mock(), PeerId::random(), hashmap! {
+                ProtocolId::MempoolDirectSend => ProtocolRateLimitConfig {
+                    byte_bucket_rate: 100,
+                    byte_bucket_size: 100,
+                },
+            });
+        assert!(bandwidth.try_outbound(ProtocolId::MempoolDirectSend, 1_000));
+        assert!(!bandwidth.try_outbound(ProtocolId::MempoolDirectSend, 1));
+    }
+}
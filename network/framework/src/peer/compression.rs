@@ -0,0 +1,174 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    counters,
+    protocols::wire::handshake::v1::{ProtocolId, ProtocolIdSet},
+};
+use anyhow::{anyhow, Error};
+use aptos_config::network_id::NetworkContext;
+use std::collections::HashMap;
+
+/// The prefixes of the messages of the protocols negotiated with zstd compression
+const UNCOMPRESSED_PREFIX: u8 = 0;
+const ZSTD_COMPRESSED_PREFIX: u8 = 1;
+
+/// The zstd compression level, favouring speed over ratio
+const ZSTD_COMPRESSION_LEVEL: i32 = 3;
+
+/// Compresses the large messages of the protocols for which both ends of the connection
+/// advertised zstd support in the handshake. The messages of these protocols are prefixed
+/// with a byte marking whether the rest of the message is compressed.
+pub struct MessageCompression {
+    network_context: NetworkContext,
+    /// The compression thresholds (in bytes) of the protocols negotiated with zstd compression
+    zstd_thresholds: HashMap<ProtocolId, usize>,
+    /// The maximum size of a decompressed message
+    max_message_size: usize,
+}
+
+impl MessageCompression {
+    pub fn new(
+        network_context: NetworkContext,
+        zstd_thresholds: HashMap<ProtocolId, usize>,
+        negotiated_protocols: &ProtocolIdSet,
+        max_message_size: usize,
+    ) -> Self {
+        let zstd_thresholds = zstd_thresholds
+            .into_iter()
+            .filter(|(protocol_id, _)| negotiated_protocols.supports_zstd_compression(*protocol_id))
+            .collect();
+        Self {
+            network_context,
+            zstd_thresholds,
+            max_message_size,
+        }
+    }
+
+    /// Returns true iff the messages of the protocol are exchanged with zstd compression
+    pub fn is_negotiated(&self, protocol_id: ProtocolId) -> bool {
+        self.zstd_thresholds.contains_key(&protocol_id)
+    }
+
+    /// Prepares an outbound message of the given protocol for the wire
+    pub fn compress(&self, protocol_id: ProtocolId, data: Vec<u8>) -> Vec<u8> {
+        let threshold = match self.zstd_thresholds.get(&protocol_id) {
+            Some(threshold) => *threshold,
+            None => return data,
+        };
+
+        // Only send the compressed message if it's actually smaller
+        if data.len() >= threshold {
+            if let Ok(compressed_data) = zstd::bulk::compress(&data, ZSTD_COMPRESSION_LEVEL) {
+                if compressed_data.len() < data.len() {
+                    counters::zstd_compression_bytes(
+                        &self.network_context,
+                        protocol_id,
+                        data.len(),
+                        compressed_data.len(),
+                    );
+                    return prefixed(ZSTD_COMPRESSED_PREFIX, &compressed_data);
+                }
+            }
+        }
+        prefixed(UNCOMPRESSED_PREFIX, &data)
+    }
+
+    /// Restores an inbound message of the given protocol read off the wire
+    pub fn decompress(&self, protocol_id: ProtocolId, data: Vec<u8>) -> Result<Vec<u8>, Error> {
+        if !self.is_negotiated(protocol_id) {
+            return Ok(data);
+        }
+
+        match data.split_first() {
+            Some((&UNCOMPRESSED_PREFIX, message)) => Ok(message.to_vec()),
+            Some((&ZSTD_COMPRESSED_PREFIX, compressed_message)) => {
+                zstd::bulk::decompress(compressed_message, self.max_message_size).map_err(|error| {
+                    anyhow!(
+                        "Failed to decompress a message of protocol {}: {}",
+                        protocol_id,
+                        error
+                    )
+                })
+            },
+            _ => Err(anyhow!(
+                "Invalid compression prefix of a message of protocol {}",
+                protocol_id
+            )),
+        }
+    }
+}
+
+fn prefixed(prefix: u8, data: &[u8]) -> Vec<u8> {
+    let mut prefixed_data = Vec::with_capacity(data.len() + 1);
+    prefixed_data.push(prefix);
+    prefixed_data.extend_from_slice(data);
+    prefixed_data
+}
+
+#[cfg(test)]
+mod test {
+    use super::MessageCompression;
+    use crate::protocols::wire::handshake::v1::{ProtocolId, ProtocolIdSet};
+    use aptos_config::network_id::NetworkContext;
+    use maplit::hashmap;
+
+    #[test]
+    fn test_zstd_compression() {
+        let mut negotiated_protocols = ProtocolIdSet::from_iter([
+            ProtocolId::ConsensusDirectSendBcs,
+            ProtocolId::StorageServiceRpc,
+        ]);
+        negotiated_protocols.insert_zstd_compression(ProtocolId::ConsensusDirectSendBcs);
+        let compression = MessageCompression::new(
+            NetworkContext::mock(),
+            hashmap! {
+                ProtocolId::ConsensusDirectSendBcs => 100,
+                ProtocolId::StorageServiceRpc => 100,
+            },
+            &negotiated_protocols,
+            1024,
+        );
+
+        // Large messages of the negotiated protocols are compressed
+        let message = vec![7; 1000];
+        let compressed_message =
+            compression.compress(ProtocolId::ConsensusDirectSendBcs, message.clone());
+        assert!(compressed_message.len() < message.len());
+        assert_eq!(
+            compression
+                .decompress(ProtocolId::ConsensusDirectSendBcs, compressed_message)
+                .unwrap(),
+            message
+        );
+
+        // Small messages are only prefixed
+        let message = vec![7; 10];
+        let prefixed_message =
+            compression.compress(ProtocolId::ConsensusDirectSendBcs, message.clone());
+        assert_eq!(prefixed_message.len(), message.len() + 1);
+        assert_eq!(
+            compression
+                .decompress(ProtocolId::ConsensusDirectSendBcs, prefixed_message)
+                .unwrap(),
+            message
+        );
+
+        // Protocols not negotiated with the peer are untouched
+        let message = vec![7; 1000];
+        assert_eq!(
+            compression.compress(ProtocolId::StorageServiceRpc, message.clone()),
+            message
+        );
+
+        // Messages decompressing beyond the max message size are rejected
+        let message = vec![7; 2000];
+        let compressed_message = compression.compress(ProtocolId::ConsensusDirectSendBcs, message);
+        compression
+            .decompress(ProtocolId::ConsensusDirectSendBcs, compressed_message)
+            .unwrap_err();
+        compression
+            .decompress(ProtocolId::ConsensusDirectSendBcs, vec![])
+            .unwrap_err();
+    }
+}
@@ -18,24 +18,25 @@
 use crate::{
     counters::{
         self, network_application_inbound_traffic, network_application_outbound_traffic,
-        FAILED_LABEL, RECEIVED_LABEL, SENT_LABEL,
+        DIRECT_SEND_LABEL, FAILED_LABEL, RECEIVED_LABEL, REQUEST_LABEL, RESPONSE_LABEL, SENT_LABEL,
     },
     logging::NetworkSchema,
+    peer::{bandwidth::ProtocolBandwidth, compression::MessageCompression},
     peer_manager::{PeerManagerError, TransportNotification},
     protocols::{
         direct_send::Message,
-        rpc::{InboundRpcRequest, InboundRpcs, OutboundRpcRequest, OutboundRpcs},
+        rpc::{error::RpcError, InboundRpcRequest, InboundRpcs, OutboundRpcRequest, OutboundRpcs},
         stream::{InboundStreamBuffer, OutboundStream, StreamMessage},
         wire::messaging::v1::{
             DirectSendMsg, ErrorCode, MultiplexMessage, MultiplexMessageSink,
-            MultiplexMessageStream, NetworkMessage, Priority, ReadError, WriteError,
+            MultiplexMessageStream, NetworkMessage, Priority, ReadError, RpcResponse, WriteError,
         },
     },
     transport::{self, Connection, ConnectionMetadata},
     ProtocolId,
 };
 use aptos_channels::aptos_channel;
-use aptos_config::network_id::NetworkContext;
+use aptos_config::{config::ProtocolRateLimitConfig, network_id::NetworkContext};
 use aptos_logger::prelude::*;
 use aptos_short_hex_str::AsShortHexStr;
 use aptos_time_service::{TimeService, TimeServiceTrait};
@@ -50,12 +51,14 @@ use futures::{
 };
 use futures_util::stream::select;
 use serde::Serialize;
-use std::{fmt, panic, time::Duration};
+use std::{collections::HashMap, fmt, panic, time::Duration};
 use tokio::runtime::Handle;
 use tokio_util::compat::{
     FuturesAsyncReadCompatExt, TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt,
 };
 
+mod bandwidth;
+mod compression;
 #[cfg(test)]
 mod test;
 
@@ -138,6 +141,10 @@ pub struct Peer<TSocket> {
     max_message_size: usize,
     /// Inbound stream buffer
     inbound_stream: InboundStreamBuffer,
+    /// Bandwidth accounting and outbound rate limits per protocol
+    bandwidth: ProtocolBandwidth,
+    /// Compression of the messages of the protocols negotiated with zstd compression
+    compression: MessageCompression,
 }
 
 impl<TSocket> Peer<TSocket>
@@ -158,6 +165,8 @@ where
         max_concurrent_outbound_rpcs: u32,
         max_frame_size: usize,
         max_message_size: usize,
+        outbound_protocol_rate_limits: HashMap<ProtocolId, ProtocolRateLimitConfig>,
+        zstd_compression_thresholds: HashMap<ProtocolId, usize>,
     ) -> Self {
         let Connection {
             metadata: connection_metadata,
@@ -165,6 +174,12 @@ where
         } = connection;
         let remote_peer_id = connection_metadata.remote_peer_id;
         let max_fragments = max_message_size / max_frame_size;
+        let compression = MessageCompression::new(
+            network_context,
+            zstd_compression_thresholds,
+            &connection_metadata.application_protocols,
+            max_message_size,
+        );
         Self {
             network_context,
             executor,
@@ -191,6 +206,12 @@ where
             max_frame_size,
             max_message_size,
             inbound_stream: InboundStreamBuffer::new(max_fragments),
+            bandwidth: ProtocolBandwidth::new(
+                network_context,
+                remote_peer_id,
+                outbound_protocol_rate_limits,
+            ),
+            compression,
         }
     }
 
@@ -270,13 +291,7 @@ where
                 // Drive the queue of pending inbound rpcs. When one is fulfilled
                 // by an upstream protocol, send the response to the remote peer.
                 maybe_response = self.inbound_rpcs.next_completed_response() => {
-                    if let Err(err) = self.inbound_rpcs.send_outbound_response(&mut write_reqs_tx, maybe_response).await {
-                        warn!(
-                            NetworkSchema::new(&self.network_context).connection_metadata(&self.connection_metadata),
-                            error = %err,
-                            "{} Error in handling inbound rpc request, error: {}", self.network_context, err,
-                        );
-                    }
+                    self.handle_completed_inbound_rpc(maybe_response, &mut write_reqs_tx).await;
                 },
                 // Poll the queue of pending outbound rpc tasks for the next
                 // successfully or unsuccessfully completed request.
@@ -412,7 +427,7 @@ where
         message: NetworkMessage,
     ) -> Result<(), PeerManagerError> {
         match message {
-            NetworkMessage::DirectSendMsg(message) => self.handle_inbound_direct_send(message),
+            NetworkMessage::DirectSendMsg(message) => self.handle_inbound_direct_send(message)?,
             NetworkMessage::Error(error_msg) => {
                 warn!(
                     NetworkSchema::new(&self.network_context)
@@ -424,7 +439,12 @@ where
                     error_msg,
                 );
             },
-            NetworkMessage::RpcRequest(request) => {
+            NetworkMessage::RpcRequest(mut request) => {
+                self.bandwidth
+                    .record_inbound(request.protocol_id, request.raw_request.len());
+                request.raw_request = self
+                    .compression
+                    .decompress(request.protocol_id, request.raw_request)?;
                 if let Err(err) = self
                     .inbound_rpcs
                     .handle_inbound_request(&mut self.peer_notifs_tx, request)
@@ -439,7 +459,16 @@ where
                     );
                 }
             },
-            NetworkMessage::RpcResponse(response) => {
+            NetworkMessage::RpcResponse(mut response) => {
+                if let Some(protocol_id) =
+                    self.outbound_rpcs.pending_protocol_id(response.request_id)
+                {
+                    self.bandwidth
+                        .record_inbound(protocol_id, response.raw_response.len());
+                    response.raw_response = self
+                        .compression
+                        .decompress(protocol_id, response.raw_response)?;
+                }
                 self.outbound_rpcs.handle_inbound_response(response)
             },
         };
@@ -510,7 +539,10 @@ where
     /// Handle an inbound DirectSendMsg from the remote peer. There's not much to
     /// do here other than bump some counters and forward the message up to the
     /// PeerManager.
-    fn handle_inbound_direct_send(&mut self, message: DirectSendMsg) {
+    fn handle_inbound_direct_send(
+        &mut self,
+        message: DirectSendMsg,
+    ) -> Result<(), PeerManagerError> {
         let peer_id = self.remote_peer_id();
         let protocol_id = message.protocol_id;
         let data = message.raw_msg;
@@ -524,6 +556,8 @@ where
             protocol_id
         );
         self.update_inbound_direct_send_metrics(message.protocol_id, data.len() as u64);
+        self.bandwidth.record_inbound(protocol_id, data.len());
+        let data = self.compression.decompress(protocol_id, data)?;
 
         let notif = PeerNotification::RecvMessage(Message {
             protocol_id,
@@ -539,6 +573,7 @@ where
                 err
             );
         }
+        Ok(())
     }
 
     /// Updates the inbound direct send metrics (e.g., messages and bytes received)
@@ -568,10 +603,22 @@ where
                 // Create the direct send message
                 let message_len = message.mdata.len();
                 let protocol_id = message.protocol_id;
+                let raw_msg = self
+                    .compression
+                    .compress(protocol_id, Vec::from(message.mdata.as_ref()));
+                if !self.bandwidth.try_outbound(protocol_id, raw_msg.len()) {
+                    counters::rate_limited_messages(
+                        &self.network_context,
+                        protocol_id,
+                        DIRECT_SEND_LABEL,
+                    )
+                    .inc();
+                    return;
+                }
                 let message = NetworkMessage::DirectSendMsg(DirectSendMsg {
                     protocol_id,
                     priority: Priority::default(),
-                    raw_msg: Vec::from(message.mdata.as_ref()),
+                    raw_msg,
                 });
 
                 match write_reqs_tx.send(message).await {
@@ -592,8 +639,25 @@ where
                     },
                 }
             },
-            PeerRequest::SendRpc(request) => {
+            PeerRequest::SendRpc(mut request) => {
                 let protocol_id = request.protocol_id;
+                if self.compression.is_negotiated(protocol_id) {
+                    request.data = Bytes::from(
+                        self.compression
+                            .compress(protocol_id, Vec::from(request.data.as_ref())),
+                    );
+                }
+                if !self.bandwidth.try_outbound(protocol_id, request.data.len()) {
+                    counters::rate_limited_messages(
+                        &self.network_context,
+                        protocol_id,
+                        REQUEST_LABEL,
+                    )
+                    .inc();
+                    // Notify the application that its request was dropped
+                    let _ = request.res_tx.send(Err(RpcError::RateLimited(protocol_id)));
+                    return;
+                }
                 if let Err(e) = self
                     .outbound_rpcs
                     .handle_outbound_request(request, write_reqs_tx)
@@ -613,6 +677,48 @@ where
         }
     }
 
+    /// Sends the response of a completed inbound rpc to the remote peer, unless the protocol's
+    /// outbound bandwidth is rate limited. In that case, the response is dropped and the remote
+    /// peer's request times out.
+    async fn handle_completed_inbound_rpc(
+        &mut self,
+        mut maybe_response: Result<(RpcResponse, ProtocolId), RpcError>,
+        write_reqs_tx: &mut aptos_channels::Sender<NetworkMessage>,
+    ) {
+        if let Ok((response, protocol_id)) = &mut maybe_response {
+            response.raw_response = self
+                .compression
+                .compress(*protocol_id, std::mem::take(&mut response.raw_response));
+            if !self
+                .bandwidth
+                .try_outbound(*protocol_id, response.raw_response.len())
+            {
+                counters::rate_limited_messages(
+                    &self.network_context,
+                    *protocol_id,
+                    RESPONSE_LABEL,
+                )
+                .inc();
+                return;
+            }
+        }
+
+        if let Err(err) = self
+            .inbound_rpcs
+            .send_outbound_response(write_reqs_tx, maybe_response)
+            .await
+        {
+            warn!(
+                NetworkSchema::new(&self.network_context)
+                    .connection_metadata(&self.connection_metadata),
+                error = %err,
+                "{} Error in handling inbound rpc request, error: {}",
+                self.network_context,
+                err,
+            );
+        }
+    }
+
     /// Updates the outbound direct send metrics (e.g., messages and bytes sent)
     fn update_outbound_direct_send_metrics(&mut self, protocol_id: ProtocolId, data_len: u64) {
         // Update the metrics for the sent direct send message
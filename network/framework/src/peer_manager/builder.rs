@@ -7,8 +7,10 @@ use crate::{
     counters,
     noise::{stream::NoiseStream, HandshakeAuthMode},
     peer_manager::{
-        conn_notifs_channel, ConnectionRequest, ConnectionRequestSender, PeerManager,
-        PeerManagerNotification, PeerManagerRequest, PeerManagerRequestSender,
+        conn_notifs_channel,
+        deny_list::{DenyList, DenyListUpdates},
+        ConnectionRequest, ConnectionRequestSender, PeerManager, PeerManagerNotification,
+        PeerManagerRequest, PeerManagerRequestSender,
     },
     protocols::{
         network::{NetworkClientConfig, NetworkServiceConfig},
@@ -18,7 +20,10 @@ use crate::{
     ProtocolId,
 };
 use aptos_channels::{self, aptos_channel, message_queues::QueueStyle};
-use aptos_config::{config::HANDSHAKE_VERSION, network_id::NetworkContext};
+use aptos_config::{
+    config::{DenyListConfig, PeerUtilityWeights, ProtocolRateLimitConfig, HANDSHAKE_VERSION},
+    network_id::NetworkContext,
+};
 use aptos_crypto::x25519;
 use aptos_logger::prelude::*;
 #[cfg(any(test, feature = "testing", feature = "fuzzing"))]
@@ -79,6 +84,11 @@ struct PeerManagerContext {
     max_message_size: usize,
     inbound_connection_limit: usize,
     tcp_buffer_cfg: TCPBufferCfg,
+    peer_utility_weights: PeerUtilityWeights,
+    outbound_protocol_rate_limits: HashMap<ProtocolId, ProtocolRateLimitConfig>,
+    zstd_compression_thresholds: HashMap<ProtocolId, usize>,
+    deny_list: DenyList,
+    deny_list_updates: DenyListUpdates,
 }
 
 impl PeerManagerContext {
@@ -103,6 +113,7 @@ impl PeerManagerContext {
         inbound_connection_limit: usize,
         tcp_buffer_cfg: TCPBufferCfg,
     ) -> Self {
+        let (deny_list, deny_list_updates) = DenyList::new(DenyListConfig::default());
         Self {
             pm_reqs_tx,
             pm_reqs_rx,
@@ -119,6 +130,11 @@ impl PeerManagerContext {
             max_message_size,
             inbound_connection_limit,
             tcp_buffer_cfg,
+            peer_utility_weights: PeerUtilityWeights::default(),
+            outbound_protocol_rate_limits: HashMap::new(),
+            zstd_compression_thresholds: HashMap::new(),
+            deny_list,
+            deny_list_updates,
         }
     }
 
@@ -158,6 +174,8 @@ pub struct PeerManagerBuilder {
     peer_manager: Option<TransportPeerManager>,
     // ListenAddress will be updated when the PeerManager is built
     listen_address: NetworkAddress,
+    // The deny list enforced by the PeerManager (shared with the admin service)
+    deny_list: DenyList,
 }
 
 impl PeerManagerBuilder {
@@ -188,6 +206,23 @@ impl PeerManagerBuilder {
         let (connection_reqs_tx, connection_reqs_rx) =
             aptos_channel::new(QueueStyle::FIFO, channel_size, None);
 
+        let peer_manager_context = PeerManagerContext::new(
+            pm_reqs_tx,
+            pm_reqs_rx,
+            connection_reqs_tx,
+            connection_reqs_rx,
+            peers_and_metadata.clone(),
+            HashMap::new(),
+            Vec::new(),
+            max_concurrent_network_reqs,
+            channel_size,
+            max_frame_size,
+            max_message_size,
+            inbound_connection_limit,
+            tcp_buffer_cfg,
+        );
+        let deny_list = peer_manager_context.deny_list.clone();
+
         Self {
             network_context,
             time_service,
@@ -195,26 +230,13 @@ impl PeerManagerBuilder {
                 chain_id,
                 supported_protocols: ProtocolIdSet::empty(),
                 authentication_mode,
-                peers_and_metadata: peers_and_metadata.clone(),
+                peers_and_metadata,
                 enable_proxy_protocol,
             }),
-            peer_manager_context: Some(PeerManagerContext::new(
-                pm_reqs_tx,
-                pm_reqs_rx,
-                connection_reqs_tx,
-                connection_reqs_rx,
-                peers_and_metadata,
-                HashMap::new(),
-                Vec::new(),
-                max_concurrent_network_reqs,
-                channel_size,
-                max_frame_size,
-                max_message_size,
-                inbound_connection_limit,
-                tcp_buffer_cfg,
-            )),
+            peer_manager_context: Some(peer_manager_context),
             peer_manager: None,
             listen_address,
+            deny_list,
         }
     }
 
@@ -346,6 +368,11 @@ impl PeerManagerBuilder {
             pm_context.max_frame_size,
             pm_context.max_message_size,
             pm_context.inbound_connection_limit,
+            pm_context.peer_utility_weights,
+            pm_context.outbound_protocol_rate_limits,
+            pm_context.zstd_compression_thresholds,
+            pm_context.deny_list,
+            pm_context.deny_list_updates,
         );
 
         // PeerManager constructor appends a public key to the listen_address.
@@ -386,6 +413,51 @@ impl PeerManagerBuilder {
             .add_connection_event_listener()
     }
 
+    /// Set the weights of the peer utility scores used for inbound connection eviction
+    pub fn set_peer_utility_weights(&mut self, weights: PeerUtilityWeights) -> &mut Self {
+        self.peer_manager_context().peer_utility_weights = weights;
+        self
+    }
+
+    /// Limit the outbound bandwidth of the given protocols to each peer
+    pub fn add_outbound_protocol_rate_limits(
+        &mut self,
+        rate_limits: HashMap<ProtocolId, ProtocolRateLimitConfig>,
+    ) -> &mut Self {
+        self.peer_manager_context()
+            .outbound_protocol_rate_limits
+            .extend(rate_limits);
+        self
+    }
+
+    /// Compress the messages of the given protocols with zstd (above the thresholds, in bytes),
+    /// and advertise it in the handshake
+    pub fn add_zstd_compression_thresholds(
+        &mut self,
+        thresholds: HashMap<ProtocolId, usize>,
+    ) -> &mut Self {
+        for protocol_id in thresholds.keys() {
+            self.transport_context()
+                .supported_protocols
+                .insert_zstd_compression(*protocol_id);
+        }
+        self.peer_manager_context()
+            .zstd_compression_thresholds
+            .extend(thresholds);
+        self
+    }
+
+    /// Set the peers and IP ranges that are denied initially
+    pub fn set_deny_list(&mut self, deny_list_config: DenyListConfig) -> &mut Self {
+        self.deny_list.update(deny_list_config);
+        self
+    }
+
+    /// Returns the deny list enforced by the PeerManager, to update it at runtime
+    pub fn deny_list(&self) -> DenyList {
+        self.deny_list.clone()
+    }
+
     pub fn get_tcp_buffers_cfg(&self) -> TCPBufferCfg {
         self.peer_manager_context
             .as_ref()
@@ -0,0 +1,116 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! The operator-managed deny list of a network. The PeerManager closes the connections with (and
+//! refuses to dial) the denied peers and IP ranges. The deny list is shared with the admin
+//! service, which replaces it at runtime; the PeerManager is notified of every update, to close
+//! the connections that became denied.
+
+use aptos_channels::{aptos_channel, message_queues::QueueStyle};
+use aptos_config::config::DenyListConfig;
+use aptos_infallible::RwLock;
+use aptos_types::{network_address::NetworkAddress, PeerId};
+use std::{net::IpAddr, sync::Arc};
+
+/// Notifies the PeerManager of deny list updates (only the latest update is kept)
+pub type DenyListUpdates = aptos_channel::Receiver<(), ()>;
+
+#[derive(Clone)]
+pub struct DenyList {
+    deny_list_config: Arc<RwLock<DenyListConfig>>,
+    update_notifier: aptos_channel::Sender<(), ()>,
+}
+
+impl DenyList {
+    pub fn new(deny_list_config: DenyListConfig) -> (Self, DenyListUpdates) {
+        let (update_notifier, deny_list_updates) = aptos_channel::new(QueueStyle::LIFO, 1, None);
+        let deny_list = Self {
+            deny_list_config: Arc::new(RwLock::new(deny_list_config)),
+            update_notifier,
+        };
+        (deny_list, deny_list_updates)
+    }
+
+    /// Returns a copy of the current deny list
+    pub fn get_config(&self) -> DenyListConfig {
+        self.deny_list_config.read().clone()
+    }
+
+    /// Replaces the deny list, and notifies the PeerManager
+    pub fn update(&self, deny_list_config: DenyListConfig) {
+        *self.deny_list_config.write() = deny_list_config;
+
+        // The PeerManager may have stopped, in which case there is nothing left to enforce
+        let _ = self.update_notifier.push((), ());
+    }
+
+    /// Returns true iff the peer, or the IP address of the given network address, is denied
+    pub fn is_denied(&self, peer_id: &PeerId, addr: &NetworkAddress) -> bool {
+        let deny_list_config = self.deny_list_config.read();
+        if deny_list_config.denied_peers.contains(peer_id) {
+            return true;
+        }
+
+        // DNS addresses are only denied by peer ID, as they aren't resolved here
+        match addr.find_ip_addr().map(canonical_ip_addr) {
+            Some(ip_addr) => deny_list_config
+                .denied_ip_ranges
+                .iter()
+                .any(|ip_range| ip_range.contains(&ip_addr)),
+            None => false,
+        }
+    }
+}
+
+/// Converts IPv4-mapped IPv6 addresses (e.g., of the inbound connections accepted by a dual-stack
+/// listener) to IPv4, so that they match the denied IPv4 ranges.
+fn canonical_ip_addr(ip_addr: IpAddr) -> IpAddr {
+    match ip_addr {
+        IpAddr::V6(ipv6_addr) => ipv6_addr.to_ipv4_mapped().map_or(ip_addr, IpAddr::V4),
+        IpAddr::V4(_) => ip_addr,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::DenyList;
+    use aptos_config::config::DenyListConfig;
+    use aptos_types::{network_address::NetworkAddress, PeerId};
+    use futures::{FutureExt, StreamExt};
+
+    #[test]
+    fn test_deny_list() {
+        let (deny_list, mut deny_list_updates) = DenyList::new(DenyListConfig::default());
+        let peer_id = PeerId::random();
+        let addr: NetworkAddress = "/ip4/10.1.2.3/tcp/6180".parse().unwrap();
+        let mapped_addr: NetworkAddress = "/ip6/::ffff:10.1.2.3/tcp/6180".parse().unwrap();
+        let dns_addr: NetworkAddress = "/dns/example.com/tcp/6180".parse().unwrap();
+        assert!(!deny_list.is_denied(&peer_id, &addr));
+
+        // Deny the peer
+        deny_list.update(DenyListConfig {
+            denied_peers: [peer_id].into_iter().collect(),
+            denied_ip_ranges: vec![],
+        });
+        assert_eq!(
+            deny_list_updates.select_next_some().now_or_never(),
+            Some(())
+        );
+        assert!(deny_list.is_denied(&peer_id, &dns_addr));
+        assert!(!deny_list.is_denied(&PeerId::random(), &addr));
+
+        // Deny an IP range instead
+        deny_list.update(DenyListConfig {
+            denied_peers: Default::default(),
+            denied_ip_ranges: vec!["10.0.0.0/8".parse().unwrap()],
+        });
+        assert_eq!(
+            deny_list_updates.select_next_some().now_or_never(),
+            Some(())
+        );
+        assert!(deny_list.is_denied(&peer_id, &addr));
+        assert!(deny_list.is_denied(&peer_id, &mapped_addr));
+        assert!(!deny_list.is_denied(&peer_id, &dns_addr));
+        assert!(!deny_list.is_denied(&peer_id, &"/ip4/11.1.2.3/tcp/6180".parse().unwrap()));
+    }
+}
@@ -29,6 +29,9 @@ pub enum PeerManagerError {
     #[error("Already connected at {0}")]
     AlreadyConnected(NetworkAddress),
 
+    #[error("Peer {0} at {1} is denied")]
+    Denied(PeerId, NetworkAddress),
+
     #[error("Sending end of oneshot dropped")]
     OneshotSenderDropped,
 
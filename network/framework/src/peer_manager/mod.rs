@@ -45,7 +45,9 @@ use tokio::runtime::Handle;
 
 pub mod builder;
 pub mod conn_notifs_channel;
+pub mod deny_list;
 mod error;
+mod peer_utility;
 mod senders;
 #[cfg(test)]
 mod tests;
@@ -55,10 +57,14 @@ mod types;
 pub use self::error::PeerManagerError;
 use crate::{
     application::{error::Error, storage::PeersAndMetadata},
-    peer_manager::transport::{TransportHandler, TransportRequest},
+    peer_manager::{
+        deny_list::{DenyList, DenyListUpdates},
+        peer_utility::{LastMessageTime, PeerUtility},
+        transport::{TransportHandler, TransportRequest},
+    },
     protocols::network::SerializedRequest,
 };
-use aptos_config::config::PeerRole;
+use aptos_config::config::{PeerRole, PeerUtilityWeights, ProtocolRateLimitConfig};
 use aptos_types::account_address::AccountAddress;
 pub use senders::*;
 pub use types::*;
@@ -119,6 +125,16 @@ where
     max_message_size: usize,
     /// Inbound connection limit separate of outbound connections
     inbound_connection_limit: usize,
+    /// Utility scores of the connected peers, to pick the inbound peer to evict at the limit
+    peer_utility: PeerUtility,
+    /// Outbound bandwidth limits of the rate limited protocols, applied to each peer
+    outbound_protocol_rate_limits: HashMap<ProtocolId, ProtocolRateLimitConfig>,
+    /// Message size thresholds (in bytes) of the protocols compressed with zstd
+    zstd_compression_thresholds: HashMap<ProtocolId, usize>,
+    /// The peers and IP ranges whose connections are closed (and that are never dialed)
+    deny_list: DenyList,
+    /// Notifications of the deny list updates, to close the connections that became denied
+    deny_list_updates: DenyListUpdates,
 }
 
 impl<TTransport, TSocket> PeerManager<TTransport, TSocket>
@@ -147,6 +163,11 @@ where
         max_frame_size: usize,
         max_message_size: usize,
         inbound_connection_limit: usize,
+        peer_utility_weights: PeerUtilityWeights,
+        outbound_protocol_rate_limits: HashMap<ProtocolId, ProtocolRateLimitConfig>,
+        zstd_compression_thresholds: HashMap<ProtocolId, usize>,
+        deny_list: DenyList,
+        deny_list_updates: DenyListUpdates,
     ) -> Self {
         let (transport_notifs_tx, transport_notifs_rx) = aptos_channels::new(
             channel_size,
@@ -170,6 +191,7 @@ where
         Self {
             network_context,
             executor,
+            peer_utility: PeerUtility::new(peer_utility_weights, time_service.clone()),
             time_service,
             listen_addr,
             transport_handler: Some(transport_handler),
@@ -189,6 +211,10 @@ where
             max_frame_size,
             max_message_size,
             inbound_connection_limit,
+            outbound_protocol_rate_limits,
+            zstd_compression_thresholds,
+            deny_list,
+            deny_list_updates,
         }
     }
 
@@ -252,6 +278,9 @@ where
                 request = self.requests_rx.select_next_some() => {
                     self.handle_outbound_request(request).await;
                 }
+                _ = self.deny_list_updates.select_next_some() => {
+                    self.disconnect_denied_peers();
+                }
                 complete => {
                     break;
                 }
@@ -323,6 +352,7 @@ where
                 // Notify upstream if there's still no active connection. This might be redundant,
                 // but does not affect correctness.
                 if !self.active_peers.contains_key(&peer_id) {
+                    self.peer_utility.remove_peer(&peer_id);
                     let notif = ConnectionNotification::LostPeer(
                         lost_conn_metadata,
                         self.network_context,
@@ -336,6 +366,23 @@ where
 
     /// Handles a new connection event
     fn handle_new_connection_event(&mut self, conn: Connection<TSocket>) {
+        // Close the connections with denied peers, in both directions
+        if self
+            .deny_list
+            .is_denied(&conn.metadata.remote_peer_id, &conn.metadata.addr)
+        {
+            info!(
+                NetworkSchema::new(&self.network_context)
+                    .connection_metadata_with_address(&conn.metadata),
+                "{} Connection rejected due to the deny list: {}",
+                self.network_context,
+                conn.metadata
+            );
+            counters::connections_denied(&self.network_context, conn.metadata.origin).inc();
+            self.disconnect(conn);
+            return;
+        }
+
         // Get the trusted peers
         let trusted_peers = match self
             .peers_and_metadata
@@ -361,7 +408,7 @@ where
             if conn.metadata.role == PeerRole::Unknown {
                 // TODO: Keep track of somewhere else to not take this hit in case of DDoS
                 // Count unknown inbound connections
-                let unknown_inbound_conns = self
+                let unknown_inbound_peers: Vec<_> = self
                     .active_peers
                     .iter()
                     .filter(|(peer_id, (metadata, _))| {
@@ -370,15 +417,18 @@ where
                                 .get(peer_id)
                                 .map_or(true, |peer| peer.role == PeerRole::Unknown)
                     })
-                    .count();
+                    .map(|(peer_id, _)| *peer_id)
+                    .collect();
 
-                // Reject excessive inbound connections made by unknown peers
+                // Reject excessive inbound connections made by unknown peers, unless a less
+                // useful unknown inbound peer can be evicted to make room.
                 // We control outbound connections with Connectivity manager before we even send them
                 // and we must allow connections that already exist to pass through tie breaking.
                 if !self
                     .active_peers
                     .contains_key(&conn.metadata.remote_peer_id)
-                    && unknown_inbound_conns + 1 > self.inbound_connection_limit
+                    && unknown_inbound_peers.len() + 1 > self.inbound_connection_limit
+                    && !self.evict_less_useful_peer(&conn.metadata, &unknown_inbound_peers)
                 {
                     info!(
                         NetworkSchema::new(&self.network_context)
@@ -425,6 +475,71 @@ where
         }
     }
 
+    /// Closes the connections with the peers denied by the (updated) deny list
+    fn disconnect_denied_peers(&mut self) {
+        let denied_peers: Vec<_> = self
+            .active_peers
+            .iter()
+            .filter(|(peer_id, (metadata, _))| self.deny_list.is_denied(peer_id, &metadata.addr))
+            .map(|(peer_id, _)| *peer_id)
+            .collect();
+        for peer_id in denied_peers {
+            // Drop the send end of the PeerRequest channel to close the connection
+            if let Some((conn_metadata, sender)) = self.active_peers.remove(&peer_id) {
+                info!(
+                    NetworkSchema::new(&self.network_context)
+                        .connection_metadata_with_address(&conn_metadata),
+                    "{} Disconnecting peer {} due to the deny list",
+                    self.network_context,
+                    peer_id.short_str()
+                );
+                self.remove_peer_from_metadata(peer_id, conn_metadata.connection_id);
+                counters::connections_denied(&self.network_context, conn_metadata.origin).inc();
+                drop(sender);
+            }
+        }
+    }
+
+    /// Evicts the least useful of the given peers if the new connection's peer is more useful.
+    /// Returns true iff a peer was evicted.
+    fn evict_less_useful_peer(
+        &mut self,
+        new_conn_metadata: &ConnectionMetadata,
+        candidate_peers: &[PeerId],
+    ) -> bool {
+        let new_peer_score = self.peer_utility.new_peer_score(new_conn_metadata.role);
+        let least_useful_peer = candidate_peers
+            .iter()
+            .filter_map(|peer_id| {
+                self.active_peers.get(peer_id).map(|(metadata, _)| {
+                    (*peer_id, self.peer_utility.score(peer_id, metadata.role))
+                })
+            })
+            .min_by_key(|(_, score)| *score);
+        let (peer_id, score) = match least_useful_peer {
+            Some((peer_id, score)) if score < new_peer_score => (peer_id, score),
+            _ => return false,
+        };
+
+        // Drop the send end of the PeerRequest channel to close the connection
+        if let Some((conn_metadata, sender)) = self.active_peers.remove(&peer_id) {
+            info!(
+                NetworkSchema::new(&self.network_context)
+                    .connection_metadata_with_address(&conn_metadata),
+                "{} Evicting peer {} (utility score: {}) for peer {} (utility score: {})",
+                self.network_context,
+                peer_id.short_str(),
+                score,
+                new_conn_metadata.remote_peer_id.short_str(),
+                new_peer_score
+            );
+            self.remove_peer_from_metadata(peer_id, conn_metadata.connection_id);
+            counters::connections_evicted(&self.network_context).inc();
+            drop(sender);
+        }
+        true
+    }
+
     async fn handle_outbound_connection_request(&mut self, request: ConnectionRequest) {
         trace!(
             NetworkSchema::new(&self.network_context),
@@ -458,6 +573,27 @@ where
                             send_err
                         );
                     }
+                } else if self.deny_list.is_denied(&requested_peer_id, &addr) {
+                    debug!(
+                        NetworkSchema::new(&self.network_context).remote_peer(&requested_peer_id),
+                        "{} Peer {} is denied. Not dialing address {}",
+                        self.network_context,
+                        requested_peer_id.short_str(),
+                        addr
+                    );
+                    counters::connections_denied(&self.network_context, ConnectionOrigin::Outbound)
+                        .inc();
+                    let error = PeerManagerError::Denied(requested_peer_id, addr);
+                    if let Err(send_err) = response_tx.send(Err(error)) {
+                        info!(
+                            NetworkSchema::new(&self.network_context)
+                                .remote_peer(&requested_peer_id),
+                            "{} Failed to notify that peer is denied for Peer {}: {:?}",
+                            self.network_context,
+                            requested_peer_id.short_str(),
+                            send_err
+                        );
+                    }
                 } else {
                     let request = TransportRequest::DialPeer(requested_peer_id, addr, response_tx);
                     self.transport_reqs_tx.send(request).await.unwrap();
@@ -672,12 +808,15 @@ where
             constants::MAX_CONCURRENT_OUTBOUND_RPCS,
             self.max_frame_size,
             self.max_message_size,
+            self.outbound_protocol_rate_limits.clone(),
+            self.zstd_compression_thresholds.clone(),
         );
         self.executor.spawn(peer.start());
 
         // Start background task to handle events (RPCs and DirectSend messages) received from
         // peer.
-        self.spawn_peer_network_events_handler(peer_id, peer_notifs_rx);
+        let last_message_time = self.peer_utility.add_peer(peer_id);
+        self.spawn_peer_network_events_handler(peer_id, peer_notifs_rx, last_message_time);
         // Save PeerRequest sender to `active_peers`.
         self.active_peers
             .insert(peer_id, (conn_meta.clone(), peer_reqs_tx));
@@ -717,6 +856,7 @@ where
         &self,
         peer_id: PeerId,
         network_events: aptos_channel::Receiver<ProtocolId, PeerNotification>,
+        last_message_time: LastMessageTime,
     ) {
         let mut upstream_handlers = self.upstream_handlers.clone();
         let network_context = self.network_context;
@@ -728,6 +868,7 @@ where
                     inbound_event,
                     peer_id,
                     &mut upstream_handlers,
+                    &last_message_time,
                 );
                 futures::future::ready(())
             },
@@ -744,6 +885,7 @@ fn handle_inbound_request(
         ProtocolId,
         aptos_channel::Sender<(PeerId, ProtocolId), PeerManagerNotification>,
     >,
+    last_message_time: &LastMessageTime,
 ) {
     let (protocol_id, notification) = match inbound_event {
         PeerNotification::RecvMessage(msg) => (
@@ -766,6 +908,8 @@ fn handle_inbound_request(
                 "{} Upstream handler unable to handle message for protocol: {}. Error: {:?}",
                 network_context, protocol_id, err
             );
+        } else {
+            last_message_time.update();
         }
     } else {
         debug!(
@@ -0,0 +1,160 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_config::config::{PeerRole, PeerUtilityWeights};
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use aptos_types::PeerId;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+/// Records when a peer last sent a message that was delivered to the applications
+#[derive(Clone, Debug)]
+pub struct LastMessageTime {
+    time_service: TimeService,
+    unix_time_ms: Arc<AtomicU64>,
+}
+
+impl LastMessageTime {
+    fn new(time_service: TimeService) -> Self {
+        let unix_time_ms = Arc::new(AtomicU64::new(0));
+        let last_message_time = Self {
+            time_service,
+            unix_time_ms,
+        };
+        last_message_time.update();
+        last_message_time
+    }
+
+    pub fn update(&self) {
+        let now_ms = self.time_service.now_unix_time().as_millis() as u64;
+        self.unix_time_ms.store(now_ms, Ordering::Relaxed);
+    }
+
+    fn unix_time_ms(&self) -> u64 {
+        self.unix_time_ms.load(Ordering::Relaxed)
+    }
+}
+
+/// Scores how useful the connected peers are, to decide which inbound peer to evict when the
+/// inbound connection limit is reached. Validators are worth more than validator fullnodes, which
+/// are worth more than the other peers, and peers that recently sent us messages are worth more
+/// than idle ones.
+pub struct PeerUtility {
+    weights: PeerUtilityWeights,
+    time_service: TimeService,
+    last_message_times: HashMap<PeerId, LastMessageTime>,
+}
+
+impl PeerUtility {
+    pub fn new(weights: PeerUtilityWeights, time_service: TimeService) -> Self {
+        Self {
+            weights,
+            time_service,
+            last_message_times: HashMap::new(),
+        }
+    }
+
+    /// Starts tracking a newly connected peer. The returned handle records the peer's messages.
+    pub fn add_peer(&mut self, peer_id: PeerId) -> LastMessageTime {
+        let last_message_time = LastMessageTime::new(self.time_service.clone());
+        self.last_message_times
+            .insert(peer_id, last_message_time.clone());
+        last_message_time
+    }
+
+    pub fn remove_peer(&mut self, peer_id: &PeerId) {
+        self.last_message_times.remove(peer_id);
+    }
+
+    /// Returns the utility score of a connected peer
+    pub fn score(&self, peer_id: &PeerId, role: PeerRole) -> u64 {
+        let now_ms = self.time_service.now_unix_time().as_millis() as u64;
+        let idle_ms = self
+            .last_message_times
+            .get(peer_id)
+            .map_or(u64::MAX, |last_message_time| {
+                now_ms.saturating_sub(last_message_time.unix_time_ms())
+            });
+        self.score_with_idle_time(role, idle_ms)
+    }
+
+    /// Returns the utility score of a peer that is connecting
+    pub fn new_peer_score(&self, role: PeerRole) -> u64 {
+        self.score_with_idle_time(role, 0)
+    }
+
+    fn score_with_idle_time(&self, role: PeerRole, idle_ms: u64) -> u64 {
+        let role_weight = match role {
+            PeerRole::Validator => self.weights.validator_weight,
+            PeerRole::ValidatorFullNode => self.weights.vfn_weight,
+            _ => self.weights.pfn_weight,
+        };
+
+        // Scale the recency weight down linearly over the window. The scores are in thousandths
+        // to keep the precision of the scaling.
+        let window_ms = self
+            .weights
+            .recency_window_secs
+            .saturating_mul(1_000)
+            .max(1);
+        let remaining_ms = window_ms.saturating_sub(idle_ms) as u128;
+        let recency_score =
+            (self.weights.recency_weight as u128 * 1_000 * remaining_ms / window_ms as u128) as u64;
+        role_weight
+            .saturating_mul(1_000)
+            .saturating_add(recency_score)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PeerUtility;
+    use aptos_config::config::{PeerRole, PeerUtilityWeights};
+    use aptos_time_service::TimeService;
+    use aptos_types::PeerId;
+    use std::time::Duration;
+
+    #[test]
+    fn test_peer_utility_scores() {
+        let time_service = TimeService::mock();
+        let mut peer_utility =
+            PeerUtility::new(PeerUtilityWeights::default(), time_service.clone());
+
+        // Validators are worth more than VFNs, which are worth more than PFNs
+        let validator = PeerId::random();
+        let vfn = PeerId::random();
+        let pfn = PeerId::random();
+        for peer_id in [validator, vfn, pfn] {
+            peer_utility.add_peer(peer_id);
+        }
+        assert_eq!(peer_utility.score(&validator, PeerRole::Validator), 5_000);
+        assert_eq!(peer_utility.score(&vfn, PeerRole::ValidatorFullNode), 3_000);
+        assert_eq!(peer_utility.score(&pfn, PeerRole::Unknown), 2_000);
+
+        // Idle peers lose their recency weight over the window
+        let mock_time = time_service.into_mock();
+        mock_time.advance(Duration::from_secs(300));
+        assert_eq!(peer_utility.score(&pfn, PeerRole::Unknown), 1_500);
+        mock_time.advance(Duration::from_secs(600));
+        assert_eq!(peer_utility.score(&pfn, PeerRole::Unknown), 1_000);
+        assert!(
+            peer_utility.score(&pfn, PeerRole::Unknown)
+                < peer_utility.new_peer_score(PeerRole::Unknown)
+        );
+
+        // Peers sending messages keep it
+        let last_message_time = peer_utility.add_peer(pfn);
+        mock_time.advance(Duration::from_secs(600));
+        last_message_time.update();
+        assert_eq!(peer_utility.score(&pfn, PeerRole::Unknown), 2_000);
+
+        // Untracked peers have no recency weight
+        peer_utility.remove_peer(&pfn);
+        assert_eq!(peer_utility.score(&pfn, PeerRole::Unknown), 1_000);
+    }
+}
@@ -7,8 +7,9 @@ use crate::{
     constants,
     peer::DisconnectReason,
     peer_manager::{
-        conn_notifs_channel, error::PeerManagerError, ConnectionNotification, ConnectionRequest,
-        PeerManager, PeerManagerNotification, PeerManagerRequest, TransportNotification,
+        conn_notifs_channel, deny_list::DenyList, error::PeerManagerError, ConnectionNotification,
+        ConnectionRequest, PeerManager, PeerManagerNotification, PeerManagerRequest,
+        TransportNotification,
     },
     protocols::wire::{
         handshake::v1::{MessagingProtocolVersion, ProtocolIdSet},
@@ -24,7 +25,7 @@ use crate::{
 use anyhow::anyhow;
 use aptos_channels::{aptos_channel, message_queues::QueueStyle};
 use aptos_config::{
-    config::{PeerRole, MAX_INBOUND_CONNECTIONS},
+    config::{DenyListConfig, PeerRole, PeerUtilityWeights, MAX_INBOUND_CONNECTIONS},
     network_id::{NetworkContext, NetworkId},
 };
 use aptos_memsocket::MemorySocket;
@@ -35,7 +36,7 @@ use aptos_time_service::TimeService;
 use aptos_types::{network_address::NetworkAddress, PeerId};
 use bytes::Bytes;
 use futures::{channel::oneshot, io::AsyncWriteExt, stream::StreamExt};
-use std::error::Error;
+use std::{collections::HashMap, error::Error};
 use tokio::runtime::Handle;
 use tokio_util::compat::{
     FuturesAsyncReadCompatExt, TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt,
@@ -97,6 +98,7 @@ fn build_test_peer_manager(
     let (connection_reqs_tx, connection_reqs_rx) = aptos_channel::new(QueueStyle::FIFO, 1, None);
     let (hello_tx, hello_rx) = aptos_channel::new(QueueStyle::FIFO, 1, None);
     let (conn_status_tx, conn_status_rx) = conn_notifs_channel::new();
+    let (deny_list, deny_list_updates) = DenyList::new(DenyListConfig::default());
 
     let network_id = NetworkId::Validator;
     let peer_manager = PeerManager::new(
@@ -118,6 +120,11 @@ fn build_test_peer_manager(
         constants::MAX_FRAME_SIZE,
         constants::MAX_MESSAGE_SIZE,
         MAX_INBOUND_CONNECTIONS,
+        PeerUtilityWeights::default(),
+        HashMap::new(),
+        HashMap::new(),
+        deny_list,
+        deny_list_updates,
     );
 
     (
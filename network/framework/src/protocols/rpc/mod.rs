@@ -681,6 +681,13 @@ impl OutboundRpcs {
         }
     }
 
+    /// Returns the protocol of the pending outbound rpc with the given request id, if any
+    pub fn pending_protocol_id(&self, request_id: RequestId) -> Option<ProtocolId> {
+        self.pending_outbound_rpcs
+            .get(&request_id)
+            .map(|(protocol_id, _)| *protocol_id)
+    }
+
     /// Handle a new inbound `RpcResponse` message. If we have a pending request
     /// with a matching request id in the `pending_outbound_rpcs` map, this will
     /// trigger that corresponding task to wake up and complete in
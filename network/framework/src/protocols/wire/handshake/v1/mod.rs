@@ -59,6 +59,8 @@ pub enum ProtocolId {
     ConsensusDirectSendCompressed = 12,
     NetbenchDirectSend = 13,
     NetbenchRpc = 14,
+    ConsensusObserver = 15,
+    NatRelayRpc = 16,
 }
 
 /// The encoding types for Protocols
@@ -87,6 +89,8 @@ impl ProtocolId {
             ConsensusDirectSendCompressed => "ConsensusDirectSendCompressed",
             NetbenchDirectSend => "NetbenchDirectSend",
             NetbenchRpc => "NetbenchRpc",
+            ConsensusObserver => "ConsensusObserver",
+            NatRelayRpc => "NatRelayRpc",
         }
     }
 
@@ -108,9 +112,19 @@ impl ProtocolId {
             ProtocolId::ConsensusDirectSendCompressed,
             ProtocolId::NetbenchDirectSend,
             ProtocolId::NetbenchRpc,
+            ProtocolId::ConsensusObserver,
+            ProtocolId::NatRelayRpc,
         ]
     }
 
+    /// Returns the protocol ID with the given name (see `as_str`), if any
+    pub fn from_name(name: &str) -> Option<ProtocolId> {
+        ProtocolId::all()
+            .iter()
+            .find(|protocol_id| protocol_id.as_str() == name)
+            .copied()
+    }
+
     /// Specifies how to encode messages for a given `ProtocolId`
     fn encoding(self) -> Encoding {
         match self {
@@ -225,12 +239,17 @@ impl fmt::Display for ProtocolId {
 // ProtocolIdSet
 //
 
+/// The offset of the bits advertising the support of zstd compressed messages
+/// for a [`ProtocolId`]. Nodes that don't know about it ignore these bits.
+const ZSTD_COMPRESSION_BIT_OFFSET: u16 = 128;
+
 /// A compact representation for a set of [`ProtocolId`]s. Internally, this is a
 /// bitvec which supports at most 256 bits.
 ///
 /// These sets are sent over-the-wire in the initial [`HandshakeMsg`] to other
 /// AptosNet peers in order to negotiate the set of common supported protocols for
-/// use on a new AptosNet connection.
+/// use on a new AptosNet connection. The set also negotiates which protocols
+/// exchange zstd compressed messages (see `insert_zstd_compression`).
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
 pub struct ProtocolIdSet(aptos_bitvec::BitVec);
@@ -280,6 +299,16 @@ impl ProtocolIdSet {
     pub fn insert(&mut self, protocol: ProtocolId) {
         self.0.set(protocol as u16)
     }
+
+    /// Returns if zstd compressed messages are supported for the protocol.
+    pub fn supports_zstd_compression(&self, protocol: ProtocolId) -> bool {
+        self.0.is_set(ZSTD_COMPRESSION_BIT_OFFSET + protocol as u16)
+    }
+
+    /// Advertise the support of zstd compressed messages for the protocol.
+    pub fn insert_zstd_compression(&mut self, protocol: ProtocolId) {
+        self.0.set(ZSTD_COMPRESSION_BIT_OFFSET + protocol as u16)
+    }
 }
 
 impl FromIterator<ProtocolId> for ProtocolIdSet {
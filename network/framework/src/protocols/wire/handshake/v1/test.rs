@@ -183,3 +183,25 @@ fn ignore_unknown_protocols() {
         ProtocolIdSet::empty(),
     );
 }
+
+#[test]
+fn zstd_compression_negotiation() {
+    let mut our_protocols =
+        ProtocolIdSet::from_iter([ProtocolId::ConsensusRpcBcs, ProtocolId::StorageServiceRpc]);
+    our_protocols.insert_zstd_compression(ProtocolId::ConsensusRpcBcs);
+    our_protocols.insert_zstd_compression(ProtocolId::StorageServiceRpc);
+    let mut their_protocols =
+        ProtocolIdSet::from_iter([ProtocolId::ConsensusRpcBcs, ProtocolId::StorageServiceRpc]);
+    their_protocols.insert_zstd_compression(ProtocolId::StorageServiceRpc);
+
+    // Only the protocols both ends compress are negotiated with compression
+    let common_protocols = our_protocols.intersect(&their_protocols);
+    assert!(!common_protocols.supports_zstd_compression(ProtocolId::ConsensusRpcBcs));
+    assert!(common_protocols.supports_zstd_compression(ProtocolId::StorageServiceRpc));
+
+    // The compression bits aren't protocols
+    assert_eq!(common_protocols.iter().collect::<Vec<_>>(), vec![
+        ProtocolId::ConsensusRpcBcs,
+        ProtocolId::StorageServiceRpc
+    ]);
+}
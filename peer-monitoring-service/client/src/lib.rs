@@ -19,6 +19,7 @@ use aptos_time_service::{TimeService, TimeServiceTrait};
 use error::Error;
 use futures::StreamExt;
 use network::PeerMonitoringServiceClient;
+use peer_history::{PeerHistory, PEER_HISTORY_FILE_NAME};
 use peer_states::peer_state::PeerState;
 use std::{collections::HashMap, sync::Arc, time::Duration};
 use thiserror::Error;
@@ -28,6 +29,7 @@ mod error;
 mod logging;
 mod metrics;
 mod network;
+pub mod peer_history;
 pub mod peer_states;
 #[cfg(test)]
 mod tests;
@@ -36,13 +38,19 @@ mod tests;
 #[derive(Clone, Debug, Default)]
 pub struct PeerMonitorState {
     peer_states: Arc<RwLock<HashMap<PeerNetworkId, PeerState>>>, // Map of peers to states
+    peer_history: Arc<RwLock<PeerHistory>>, // The latency and availability history of peers
     request_id_generator: Arc<U64IdGenerator>, // Used for generating request/response IDs
 }
 
 impl PeerMonitorState {
     pub fn new() -> Self {
+        Self::new_with_peer_history(PeerHistory::default())
+    }
+
+    pub fn new_with_peer_history(peer_history: PeerHistory) -> Self {
         Self {
             peer_states: Arc::new(RwLock::new(HashMap::new())),
+            peer_history: Arc::new(RwLock::new(peer_history)),
             request_id_generator: Arc::new(U64IdGenerator::new()),
         }
     }
@@ -61,12 +69,19 @@ pub async fn start_peer_monitor(
     network_client: NetworkClient<PeerMonitoringServiceMessage>,
     runtime: Option<Handle>,
 ) {
+    // Load the peer history from the storage directory
+    let time_service = TimeService::real();
+    let peer_history = PeerHistory::new_with_history_file(
+        node_config.peer_monitoring_service.peer_history,
+        node_config.storage.dir().join(PEER_HISTORY_FILE_NAME),
+        time_service.now_unix_time(),
+    );
+
     // Create a new monitoring client and peer monitor state
     let peer_monitoring_client = PeerMonitoringServiceClient::new(network_client);
-    let peer_monitor_state = PeerMonitorState::new();
+    let peer_monitor_state = PeerMonitorState::new_with_peer_history(peer_history);
 
     // Spawn the peer metadata updater
-    let time_service = TimeService::real();
     spawn_peer_metadata_updater(
         node_config.peer_monitoring_service,
         peer_monitor_state.clone(),
@@ -239,8 +254,9 @@ pub(crate) fn spawn_peer_metadata_updater(
             };
 
             // Update the latest peer monitoring metadata
+            let unix_time = time_service.now_unix_time();
             for peer_network_id in all_peers {
-                let peer_monitoring_metadata =
+                let mut peer_monitoring_metadata =
                     match peer_monitor_state.peer_states.read().get(&peer_network_id) {
                         Some(peer_state) => {
                             peer_state
@@ -256,6 +272,10 @@ pub(crate) fn spawn_peer_metadata_updater(
                         },
                         None => PeerMonitoringMetadata::default(), // Use the default
                     };
+                peer_monitoring_metadata.peer_history_summary = peer_monitor_state
+                    .peer_history
+                    .read()
+                    .get_peer_history_summary(&peer_network_id, unix_time);
 
                 // Insert the latest peer monitoring metadata into peers and metadata
                 if let Err(error) = peers_and_metadata
@@ -267,6 +287,16 @@ pub(crate) fn spawn_peer_metadata_updater(
                         .error(&error.into()));
                 }
             }
+
+            // Prune and persist the peer history
+            let mut peer_history = peer_monitor_state.peer_history.write();
+            peer_history.garbage_collect(unix_time);
+            if let Err(error) = peer_history.persist_if_due(unix_time) {
+                warn!(LogSchema::new(LogEntry::PeerHistory)
+                    .event(LogEvent::UnexpectedErrorEncountered)
+                    .error(&error)
+                    .message("Failed to persist the peer history!"));
+            }
         }
     };
 
@@ -44,6 +44,7 @@ pub enum LogEntry {
     MetadataUpdateLoop,
     NetworkInfoRequest,
     NodeInfoRequest,
+    PeerHistory,
     PeerMonitorLoop,
     SendRequest,
 
@@ -0,0 +1,378 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{Error, LogEntry, LogEvent, LogSchema};
+use aptos_config::{config::PeerHistoryConfig, network_id::PeerNetworkId};
+use aptos_logger::{info, warn};
+use aptos_peer_monitoring_service_types::PeerHistorySummary;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    fs,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// The name of the file holding the peer history (in the storage directory)
+pub const PEER_HISTORY_FILE_NAME: &str = "peer_monitoring_history.json";
+
+/// A single latency ping sent to a peer
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+struct LatencyPingSample {
+    unix_time_secs: u64,        // The time at which the ping completed
+    latency_usecs: Option<u64>, // The ping latency, if the ping succeeded
+}
+
+/// The history of a peer, as persisted to disk
+#[derive(Debug, Deserialize, Serialize)]
+struct PeerHistoryEntry {
+    peer_network_id: PeerNetworkId,
+    latency_ping_samples: VecDeque<LatencyPingSample>,
+}
+
+/// A rolling window of the latency pings sent to each peer (including the
+/// peers that are no longer connected), that is periodically persisted to
+/// disk so that it survives node restarts.
+#[derive(Debug, Default)]
+pub struct PeerHistory {
+    peer_history_config: PeerHistoryConfig,
+    history_file_path: Option<PathBuf>, // The file the history is persisted to (if any)
+    latency_ping_samples: HashMap<PeerNetworkId, VecDeque<LatencyPingSample>>, // Samples (oldest first)
+    last_persist_time: Duration, // The (unix) time the history was last persisted
+}
+
+impl PeerHistory {
+    /// Creates a peer history that is only held in memory
+    pub fn new(peer_history_config: PeerHistoryConfig) -> Self {
+        Self {
+            peer_history_config,
+            history_file_path: None,
+            latency_ping_samples: HashMap::new(),
+            last_persist_time: Duration::ZERO,
+        }
+    }
+
+    /// Creates a peer history that is persisted to the given file, and
+    /// loads the existing history from it (if any).
+    pub fn new_with_history_file(
+        peer_history_config: PeerHistoryConfig,
+        history_file_path: PathBuf,
+        unix_time: Duration,
+    ) -> Self {
+        let mut peer_history = Self::new(peer_history_config);
+        if peer_history_config.enable_peer_history && history_file_path.exists() {
+            match read_history_file(&history_file_path) {
+                Ok(peer_history_entries) => {
+                    info!(LogSchema::new(LogEntry::PeerHistory).message(&format!(
+                        "Loaded the history of {} peers!",
+                        peer_history_entries.len()
+                    )));
+                    for peer_history_entry in peer_history_entries {
+                        peer_history.latency_ping_samples.insert(
+                            peer_history_entry.peer_network_id,
+                            peer_history_entry.latency_ping_samples,
+                        );
+                    }
+                    peer_history.garbage_collect(unix_time);
+                },
+                Err(error) => {
+                    warn!(LogSchema::new(LogEntry::PeerHistory)
+                        .event(LogEvent::UnexpectedErrorEncountered)
+                        .error(&error)
+                        .message("Failed to load the peer history! Starting from scratch."));
+                },
+            }
+        }
+        peer_history.history_file_path = Some(history_file_path);
+        peer_history.last_persist_time = unix_time;
+        peer_history
+    }
+
+    /// Records the result of a latency ping sent to the peer
+    pub fn record_latency_ping(
+        &mut self,
+        peer_network_id: &PeerNetworkId,
+        unix_time: Duration,
+        latency_secs: Option<f64>,
+    ) {
+        if !self.peer_history_config.enable_peer_history {
+            return;
+        }
+
+        // Make room for new peers by evicting the least recently pinged peer
+        if !self.latency_ping_samples.contains_key(peer_network_id)
+            && self.latency_ping_samples.len() >= self.peer_history_config.max_num_peers
+        {
+            let least_recent_peer = self
+                .latency_ping_samples
+                .iter()
+                .min_by_key(|(_, samples)| samples.back().map(|sample| sample.unix_time_secs))
+                .map(|(peer_network_id, _)| *peer_network_id);
+            if let Some(least_recent_peer) = least_recent_peer {
+                self.latency_ping_samples.remove(&least_recent_peer);
+            } else {
+                return; // The max number of peers is zero
+            }
+        }
+
+        // Save the sample and drop the oldest one (if there are too many)
+        let samples = self
+            .latency_ping_samples
+            .entry(*peer_network_id)
+            .or_default();
+        samples.push_back(LatencyPingSample {
+            unix_time_secs: unix_time.as_secs(),
+            latency_usecs: latency_secs
+                .map(|latency_secs| (latency_secs * 1_000_000.0).round() as u64),
+        });
+        if samples.len() > self.peer_history_config.max_num_samples_per_peer {
+            samples.pop_front();
+        }
+    }
+
+    /// Returns the aggregated history of the peer over the history window.
+    /// If no latency pings were sent to the peer, None is returned.
+    pub fn get_peer_history_summary(
+        &self,
+        peer_network_id: &PeerNetworkId,
+        unix_time: Duration,
+    ) -> Option<PeerHistorySummary> {
+        let window_start_secs = self.get_window_start_secs(unix_time);
+        let samples: Vec<&LatencyPingSample> = self
+            .latency_ping_samples
+            .get(peer_network_id)?
+            .iter()
+            .filter(|sample| sample.unix_time_secs >= window_start_secs)
+            .collect();
+        if samples.is_empty() {
+            return None;
+        }
+
+        // Sort the latencies of the successful pings
+        let mut latencies_usecs: Vec<u64> = samples
+            .iter()
+            .filter_map(|sample| sample.latency_usecs)
+            .collect();
+        latencies_usecs.sort_unstable();
+
+        let num_latency_pings = samples.len() as u64;
+        Some(PeerHistorySummary {
+            num_latency_pings,
+            availability: latencies_usecs.len() as f64 / num_latency_pings as f64,
+            latency_p50_secs: get_percentile_secs(&latencies_usecs, 50),
+            latency_p90_secs: get_percentile_secs(&latencies_usecs, 90),
+            latency_p99_secs: get_percentile_secs(&latencies_usecs, 99),
+        })
+    }
+
+    /// Removes the samples that have fallen out of the history window
+    pub fn garbage_collect(&mut self, unix_time: Duration) {
+        let window_start_secs = self.get_window_start_secs(unix_time);
+        self.latency_ping_samples.retain(|_, samples| {
+            while samples
+                .front()
+                .map_or(false, |sample| sample.unix_time_secs < window_start_secs)
+            {
+                samples.pop_front();
+            }
+            !samples.is_empty()
+        });
+    }
+
+    /// Persists the history to disk, if there is a history file and the
+    /// persist interval has elapsed since the last write.
+    pub fn persist_if_due(&mut self, unix_time: Duration) -> Result<(), Error> {
+        let history_file_path = match &self.history_file_path {
+            Some(history_file_path) => history_file_path.clone(),
+            None => return Ok(()),
+        };
+        let persist_interval = Duration::from_millis(self.peer_history_config.persist_interval_ms);
+        if !self.peer_history_config.enable_peer_history
+            || unix_time.saturating_sub(self.last_persist_time) < persist_interval
+        {
+            return Ok(());
+        }
+        self.last_persist_time = unix_time;
+
+        // Write the history to a temporary file first, so that the
+        // existing history is never left partially written.
+        self.garbage_collect(unix_time);
+        let peer_history_entries: Vec<PeerHistoryEntry> = self
+            .latency_ping_samples
+            .iter()
+            .map(|(peer_network_id, samples)| PeerHistoryEntry {
+                peer_network_id: *peer_network_id,
+                latency_ping_samples: samples.clone(),
+            })
+            .collect();
+        let history_bytes = serde_json::to_vec(&peer_history_entries).map_err(|error| {
+            Error::UnexpectedError(format!("Failed to serialize the peer history: {:?}", error))
+        })?;
+        let temporary_file_path = history_file_path.with_extension("tmp");
+        fs::write(&temporary_file_path, history_bytes)
+            .and_then(|_| fs::rename(&temporary_file_path, &history_file_path))
+            .map_err(|error| {
+                Error::UnexpectedError(format!(
+                    "Failed to write the peer history to {:?}: {:?}",
+                    history_file_path, error
+                ))
+            })
+    }
+
+    /// Returns the (unix) time in secs at which the history window starts
+    fn get_window_start_secs(&self, unix_time: Duration) -> u64 {
+        unix_time
+            .as_secs()
+            .saturating_sub(self.peer_history_config.history_window_secs)
+    }
+}
+
+/// Returns the given percentile of the sorted latencies (in secs), using the
+/// nearest-rank method. If there are no latencies, None is returned.
+fn get_percentile_secs(sorted_latencies_usecs: &[u64], percentile: usize) -> Option<f64> {
+    if sorted_latencies_usecs.is_empty() {
+        return None;
+    }
+    let rank = (percentile * sorted_latencies_usecs.len() + 99) / 100;
+    let index = rank.saturating_sub(1);
+    Some(sorted_latencies_usecs[index] as f64 / 1_000_000.0)
+}
+
+/// Reads the persisted peer history from the given file
+fn read_history_file(history_file_path: &Path) -> Result<Vec<PeerHistoryEntry>, Error> {
+    let history_bytes = fs::read(history_file_path).map_err(|error| {
+        Error::UnexpectedError(format!(
+            "Failed to read the peer history from {:?}: {:?}",
+            history_file_path, error
+        ))
+    })?;
+    serde_json::from_slice(&history_bytes).map_err(|error| {
+        Error::UnexpectedError(format!(
+            "Failed to deserialize the peer history: {:?}",
+            error
+        ))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::{PeerHistory, PEER_HISTORY_FILE_NAME};
+    use aptos_config::{
+        config::PeerHistoryConfig,
+        network_id::{NetworkId, PeerNetworkId},
+    };
+    use aptos_temppath::TempPath;
+    use aptos_types::PeerId;
+    use std::time::Duration;
+
+    #[test]
+    fn test_peer_history_summary() {
+        // Create a peer history with a 100 second window
+        let peer_history_config = PeerHistoryConfig {
+            history_window_secs: 100,
+            ..Default::default()
+        };
+        let mut peer_history = PeerHistory::new(peer_history_config);
+        let peer_network_id = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+
+        // Verify there is no summary for unknown peers
+        let unix_time = Duration::from_secs(1_000);
+        assert!(peer_history
+            .get_peer_history_summary(&peer_network_id, unix_time)
+            .is_none());
+
+        // Record 100 successful pings (1 to 100 ms) and 100 failed pings
+        for latency_ms in 1..=100 {
+            peer_history.record_latency_ping(
+                &peer_network_id,
+                unix_time,
+                Some(latency_ms as f64 / 1_000.0),
+            );
+            peer_history.record_latency_ping(&peer_network_id, unix_time, None);
+        }
+
+        // Verify the summary
+        let summary = peer_history
+            .get_peer_history_summary(&peer_network_id, unix_time)
+            .unwrap();
+        assert_eq!(summary.num_latency_pings, 200);
+        assert_eq!(summary.availability, 0.5);
+        assert_eq!(summary.latency_p50_secs, Some(0.05));
+        assert_eq!(summary.latency_p90_secs, Some(0.09));
+        assert_eq!(summary.latency_p99_secs, Some(0.099));
+
+        // Verify the samples fall out of the window
+        let unix_time = unix_time + Duration::from_secs(101);
+        assert!(peer_history
+            .get_peer_history_summary(&peer_network_id, unix_time)
+            .is_none());
+        peer_history.garbage_collect(unix_time);
+        assert!(peer_history.latency_ping_samples.is_empty());
+    }
+
+    #[test]
+    fn test_peer_history_limits() {
+        // Create a peer history that retains 2 peers and 3 samples per peer
+        let peer_history_config = PeerHistoryConfig {
+            max_num_peers: 2,
+            max_num_samples_per_peer: 3,
+            ..Default::default()
+        };
+        let mut peer_history = PeerHistory::new(peer_history_config);
+
+        // Record several pings for each peer
+        let peers: Vec<_> = (0..3)
+            .map(|_| PeerNetworkId::new(NetworkId::Public, PeerId::random()))
+            .collect();
+        for (index, peer_network_id) in peers.iter().enumerate() {
+            for _ in 0..5 {
+                let unix_time = Duration::from_secs(1_000 + index as u64);
+                peer_history.record_latency_ping(peer_network_id, unix_time, Some(0.1));
+            }
+        }
+
+        // Verify the least recently pinged peer was evicted
+        let unix_time = Duration::from_secs(1_010);
+        assert!(peer_history
+            .get_peer_history_summary(&peers[0], unix_time)
+            .is_none());
+        for peer_network_id in &peers[1..] {
+            let summary = peer_history
+                .get_peer_history_summary(peer_network_id, unix_time)
+                .unwrap();
+            assert_eq!(summary.num_latency_pings, 3);
+        }
+    }
+
+    #[test]
+    fn test_peer_history_persistence() {
+        // Create a peer history backed by a file
+        let temp_path = TempPath::new();
+        temp_path.create_as_dir().unwrap();
+        let history_file_path = temp_path.path().join(PEER_HISTORY_FILE_NAME);
+        let peer_history_config = PeerHistoryConfig::default();
+        let unix_time = Duration::from_secs(1_000);
+        let mut peer_history = PeerHistory::new_with_history_file(
+            peer_history_config,
+            history_file_path.clone(),
+            unix_time,
+        );
+
+        // Record a ping and verify nothing is written before the persist interval
+        let peer_network_id = PeerNetworkId::new(NetworkId::Validator, PeerId::random());
+        peer_history.record_latency_ping(&peer_network_id, unix_time, Some(0.2));
+        peer_history.persist_if_due(unix_time).unwrap();
+        assert!(!history_file_path.exists());
+
+        // Persist the history and verify it is loaded on restart
+        let unix_time = unix_time + Duration::from_millis(peer_history_config.persist_interval_ms);
+        peer_history.persist_if_due(unix_time).unwrap();
+        let peer_history =
+            PeerHistory::new_with_history_file(peer_history_config, history_file_path, unix_time);
+        let summary = peer_history
+            .get_peer_history_summary(&peer_network_id, unix_time)
+            .unwrap();
+        assert_eq!(summary.num_latency_pings, 1);
+        assert_eq!(summary.latency_p50_secs, Some(0.2));
+    }
+}
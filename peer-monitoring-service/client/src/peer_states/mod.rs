@@ -65,6 +65,7 @@ pub fn refresh_peer_states(
                     *peer_network_id,
                     peer_metadata.clone(),
                     peer_monitor_state.request_id_generator.clone(),
+                    peer_monitor_state.peer_history.clone(),
                     time_service.clone(),
                     runtime.clone(),
                 )?;
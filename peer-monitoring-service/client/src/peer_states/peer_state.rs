@@ -3,6 +3,7 @@
 
 use crate::{
     metrics, network,
+    peer_history::PeerHistory,
     peer_states::{
         key_value::{PeerStateKey, PeerStateValue, StateValueInterface},
         latency_info::LatencyInfoState,
@@ -74,6 +75,7 @@ impl PeerState {
         peer_network_id: PeerNetworkId,
         peer_metadata: PeerMetadata,
         request_id_generator: Arc<U64IdGenerator>,
+        peer_history: Arc<RwLock<PeerHistory>>,
         time_service: TimeService,
         runtime: Option<Handle>,
     ) -> Result<JoinHandle<()>, Error> {
@@ -94,6 +96,9 @@ impl PeerState {
         // Get the max message size for the response
         let max_num_response_bytes = monitoring_service_config.max_num_response_bytes;
 
+        // Only latency pings are recorded in the peer history
+        let record_peer_history = *peer_state_key == PeerStateKey::LatencyInfo;
+
         // Create the request task
         let request_task = async move {
             // Add some amount of jitter before sending the request.
@@ -127,6 +132,13 @@ impl PeerState {
                     peer_state_value
                         .write()
                         .handle_monitoring_service_response_error(&peer_network_id, error);
+                    if record_peer_history {
+                        peer_history.write().record_latency_ping(
+                            &peer_network_id,
+                            time_service.now_unix_time(),
+                            None,
+                        );
+                    }
                     return;
                 },
             };
@@ -138,9 +150,25 @@ impl PeerState {
                 peer_state_value
                     .write()
                     .handle_monitoring_service_response_error(&peer_network_id, error);
+                if record_peer_history {
+                    peer_history.write().record_latency_ping(
+                        &peer_network_id,
+                        time_service.now_unix_time(),
+                        None,
+                    );
+                }
                 return;
             }
 
+            // Record the latency ping in the peer history
+            if record_peer_history {
+                peer_history.write().record_latency_ping(
+                    &peer_network_id,
+                    time_service.now_unix_time(),
+                    Some(request_duration_secs),
+                );
+            }
+
             // Handle the monitoring service response
             peer_state_value.write().handle_monitoring_service_response(
                 &peer_network_id,
@@ -47,6 +47,7 @@ pub struct PeerMonitoringMetadata {
     pub latest_network_info_response: Option<NetworkInformationResponse>, // The latest network info response
     pub latest_node_info_response: Option<NodeInformationResponse>, // The latest node info response
     pub internal_client_state: Option<String>, // A detailed client state string for debugging and logging
+    pub peer_history_summary: Option<PeerHistorySummary>, // The aggregated latency and availability history
 }
 
 /// We must manually define this because f64 doesn't implement Eq. Instead,
@@ -65,19 +66,45 @@ impl PeerMonitoringMetadata {
             latest_network_info_response,
             latest_node_info_response,
             internal_client_state,
+            peer_history_summary: None,
         }
     }
 }
 
+/// The aggregated latency and availability history of a peer (over the history window)
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct PeerHistorySummary {
+    pub num_latency_pings: u64, // The number of latency pings sent to the peer
+    pub availability: f64,      // The fraction of latency pings that succeeded
+    pub latency_p50_secs: Option<f64>, // The median latency of the successful pings
+    pub latency_p90_secs: Option<f64>, // The 90th percentile latency of the successful pings
+    pub latency_p99_secs: Option<f64>, // The 99th percentile latency of the successful pings
+}
+
+impl Display for PeerHistorySummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{{ num_latency_pings: {}, availability: {:.3}, latency_p50_secs: {}, latency_p90_secs: {}, latency_p99_secs: {} }}",
+            self.num_latency_pings,
+            self.availability,
+            display_format_option(&self.latency_p50_secs),
+            display_format_option(&self.latency_p90_secs),
+            display_format_option(&self.latency_p99_secs),
+        )
+    }
+}
+
 // Display formatting includes basic monitoring metadata
 impl Display for PeerMonitoringMetadata {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{ average_ping_latency_secs: {}, latest_network_info_response: {}, latest_node_info_response: {} }}",
+            "{{ average_ping_latency_secs: {}, latest_network_info_response: {}, latest_node_info_response: {}, peer_history_summary: {} }}",
             display_format_option(&self.average_ping_latency_secs),
             display_format_option(&self.latest_network_info_response),
             display_format_option(&self.latest_node_info_response),
+            display_format_option(&self.peer_history_summary),
         )
     }
 }
@@ -88,10 +115,11 @@ impl Debug for PeerMonitoringMetadata {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            "{{ average_ping_latency_secs: {}, latest_network_info_response: {}, latest_node_info_response: {} }}",
+            "{{ average_ping_latency_secs: {}, latest_network_info_response: {}, latest_node_info_response: {}, peer_history_summary: {} }}",
             debug_format_option(&self.average_ping_latency_secs),
             debug_format_option(&self.latest_network_info_response),
             debug_format_option(&self.latest_node_info_response),
+            debug_format_option(&self.peer_history_summary),
         )
     }
 }
@@ -11,19 +11,39 @@ from google.protobuf.internal import containers as _containers
 
 DESCRIPTOR: _descriptor.FileDescriptor
 
+class TransactionFilter(_message.Message):
+    __slots__ = ["event_types", "entry_functions"]
+    EVENT_TYPES_FIELD_NUMBER: _ClassVar[int]
+    ENTRY_FUNCTIONS_FIELD_NUMBER: _ClassVar[int]
+    event_types: _containers.RepeatedScalarFieldContainer[str]
+    entry_functions: _containers.RepeatedScalarFieldContainer[str]
+    def __init__(
+        self,
+        event_types: _Optional[_Iterable[str]] = ...,
+        entry_functions: _Optional[_Iterable[str]] = ...,
+    ) -> None: ...
+
 class GetTransactionsRequest(_message.Message):
-    __slots__ = ["starting_version", "transactions_count", "batch_size"]
+    __slots__ = [
+        "starting_version",
+        "transactions_count",
+        "batch_size",
+        "transaction_filter",
+    ]
     STARTING_VERSION_FIELD_NUMBER: _ClassVar[int]
     TRANSACTIONS_COUNT_FIELD_NUMBER: _ClassVar[int]
     BATCH_SIZE_FIELD_NUMBER: _ClassVar[int]
+    TRANSACTION_FILTER_FIELD_NUMBER: _ClassVar[int]
     starting_version: int
     transactions_count: int
     batch_size: int
+    transaction_filter: TransactionFilter
     def __init__(
         self,
         starting_version: _Optional[int] = ...,
         transactions_count: _Optional[int] = ...,
         batch_size: _Optional[int] = ...,
+        transaction_filter: _Optional[_Union[TransactionFilter, _Mapping]] = ...,
     ) -> None: ...
 
 class TransactionsResponse(_message.Message):
@@ -17,7 +17,7 @@ from aptos.transaction.v1 import (
 )
 
 DESCRIPTOR = _descriptor_pool.Default().AddSerializedFile(
-    b'\n.aptos/internal/fullnode/v1/fullnode_data.proto\x12\x1a\x61ptos.internal.fullnode.v1\x1a&aptos/transaction/v1/transaction.proto"M\n\x12TransactionsOutput\x12\x37\n\x0ctransactions\x18\x01 \x03(\x0b\x32!.aptos.transaction.v1.Transaction"\xf2\x01\n\x0cStreamStatus\x12\x41\n\x04type\x18\x01 \x01(\x0e\x32\x33.aptos.internal.fullnode.v1.StreamStatus.StatusType\x12\x15\n\rstart_version\x18\x02 \x01(\x04\x12\x1c\n\x0b\x65nd_version\x18\x03 \x01(\x04\x42\x02\x30\x01H\x00\x88\x01\x01"Z\n\nStatusType\x12\x1b\n\x17STATUS_TYPE_UNSPECIFIED\x10\x00\x12\x14\n\x10STATUS_TYPE_INIT\x10\x01\x12\x19\n\x15STATUS_TYPE_BATCH_END\x10\x02\x42\x0e\n\x0c_end_version"\x94\x01\n\x1eGetTransactionsFromNodeRequest\x12!\n\x10starting_version\x18\x01 \x01(\x04\x42\x02\x30\x01H\x00\x88\x01\x01\x12#\n\x12transactions_count\x18\x02 \x01(\x04\x42\x02\x30\x01H\x01\x88\x01\x01\x42\x13\n\x11_starting_versionB\x15\n\x13_transactions_count"\xb8\x01\n\x1cTransactionsFromNodeResponse\x12:\n\x06status\x18\x01 \x01(\x0b\x32(.aptos.internal.fullnode.v1.StreamStatusH\x00\x12>\n\x04\x64\x61ta\x18\x02 \x01(\x0b\x32..aptos.internal.fullnode.v1.TransactionsOutputH\x00\x12\x10\n\x08\x63hain_id\x18\x03 \x01(\rB\n\n\x08response2\xa2\x01\n\x0c\x46ullnodeData\x12\x91\x01\n\x17GetTransactionsFromNode\x12:.aptos.internal.fullnode.v1.GetTransactionsFromNodeRequest\x1a\x38.aptos.internal.fullnode.v1.TransactionsFromNodeResponse0\x01\x62\x06proto3'
+    b'\n.aptos/internal/fullnode/v1/fullnode_data.proto\x12\x1a\x61ptos.internal.fullnode.v1\x1a&aptos/transaction/v1/transaction.proto"M\n\x12TransactionsOutput\x12\x37\n\x0ctransactions\x18\x01 \x03(\x0b\x32!.aptos.transaction.v1.Transaction"\xf2\x01\n\x0cStreamStatus\x12\x41\n\x04type\x18\x01 \x01(\x0e\x32\x33.aptos.internal.fullnode.v1.StreamStatus.StatusType\x12\x15\n\rstart_version\x18\x02 \x01(\x04\x12\x1c\n\x0b\x65nd_version\x18\x03 \x01(\x04\x42\x02\x30\x01H\x00\x88\x01\x01"Z\n\nStatusType\x12\x1b\n\x17STATUS_TYPE_UNSPECIFIED\x10\x00\x12\x14\n\x10STATUS_TYPE_INIT\x10\x01\x12\x19\n\x15STATUS_TYPE_BATCH_END\x10\x02\x42\x0e\n\x0c_end_version"_\n\x11TransactionFilter\x12\x13\n\x0b\x65vent_types\x18\x01 \x03(\t\x12\x1c\n\x14write_resource_types\x18\x02 \x03(\t\x12\x17\n\x0f\x65ntry_functions\x18\x03 \x03(\t"\xdf\x01\n\x1eGetTransactionsFromNodeRequest\x12!\n\x10starting_version\x18\x01 \x01(\x04\x42\x02\x30\x01H\x00\x88\x01\x01\x12#\n\x12transactions_count\x18\x02 \x01(\x04\x42\x02\x30\x01H\x01\x88\x01\x01\x12I\n\x12transaction_filter\x18\x03 \x01(\x0b\x32-.aptos.internal.fullnode.v1.TransactionFilterB\x13\n\x11_starting_versionB\x15\n\x13_transactions_count"\xb8\x01\n\x1cTransactionsFromNodeResponse\x12:\n\x06status\x18\x01 \x01(\x0b\x32(.aptos.internal.fullnode.v1.StreamStatusH\x00\x12>\n\x04\x64\x61ta\x18\x02 \x01(\x0b\x32..aptos.internal.fullnode.v1.TransactionsOutputH\x00\x12\x10\n\x08\x63hain_id\x18\x03 \x01(\rB\n\n\x08response2\xa2\x01\n\x0c\x46ullnodeData\x12\x91\x01\n\x17GetTransactionsFromNode\x12:.aptos.internal.fullnode.v1.GetTransactionsFromNodeRequest\x1a\x38.aptos.internal.fullnode.v1.TransactionsFromNodeResponse0\x01\x62\x06proto3'
 )
 
 _globals = globals()
@@ -43,10 +43,12 @@ if _descriptor._USE_C_DESCRIPTORS == False:
     _globals["_STREAMSTATUS"]._serialized_end = 440
     _globals["_STREAMSTATUS_STATUSTYPE"]._serialized_start = 334
     _globals["_STREAMSTATUS_STATUSTYPE"]._serialized_end = 424
-    _globals["_GETTRANSACTIONSFROMNODEREQUEST"]._serialized_start = 443
-    _globals["_GETTRANSACTIONSFROMNODEREQUEST"]._serialized_end = 591
-    _globals["_TRANSACTIONSFROMNODERESPONSE"]._serialized_start = 594
-    _globals["_TRANSACTIONSFROMNODERESPONSE"]._serialized_end = 778
-    _globals["_FULLNODEDATA"]._serialized_start = 781
-    _globals["_FULLNODEDATA"]._serialized_end = 943
+    _globals["_TRANSACTIONFILTER"]._serialized_start = 442
+    _globals["_TRANSACTIONFILTER"]._serialized_end = 537
+    _globals["_GETTRANSACTIONSFROMNODEREQUEST"]._serialized_start = 540
+    _globals["_GETTRANSACTIONSFROMNODEREQUEST"]._serialized_end = 763
+    _globals["_TRANSACTIONSFROMNODERESPONSE"]._serialized_start = 766
+    _globals["_TRANSACTIONSFROMNODERESPONSE"]._serialized_end = 950
+    _globals["_FULLNODEDATA"]._serialized_start = 953
+    _globals["_FULLNODEDATA"]._serialized_end = 1115
 # @@protoc_insertion_point(module_scope)
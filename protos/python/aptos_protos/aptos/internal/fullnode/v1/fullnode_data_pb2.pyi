@@ -49,16 +49,34 @@ class StreamStatus(_message.Message):
         end_version: _Optional[int] = ...,
     ) -> None: ...
 
+class TransactionFilter(_message.Message):
+    __slots__ = ["event_types", "write_resource_types", "entry_functions"]
+    EVENT_TYPES_FIELD_NUMBER: _ClassVar[int]
+    WRITE_RESOURCE_TYPES_FIELD_NUMBER: _ClassVar[int]
+    ENTRY_FUNCTIONS_FIELD_NUMBER: _ClassVar[int]
+    event_types: _containers.RepeatedScalarFieldContainer[str]
+    write_resource_types: _containers.RepeatedScalarFieldContainer[str]
+    entry_functions: _containers.RepeatedScalarFieldContainer[str]
+    def __init__(
+        self,
+        event_types: _Optional[_Iterable[str]] = ...,
+        write_resource_types: _Optional[_Iterable[str]] = ...,
+        entry_functions: _Optional[_Iterable[str]] = ...,
+    ) -> None: ...
+
 class GetTransactionsFromNodeRequest(_message.Message):
-    __slots__ = ["starting_version", "transactions_count"]
+    __slots__ = ["starting_version", "transactions_count", "transaction_filter"]
     STARTING_VERSION_FIELD_NUMBER: _ClassVar[int]
     TRANSACTIONS_COUNT_FIELD_NUMBER: _ClassVar[int]
+    TRANSACTION_FILTER_FIELD_NUMBER: _ClassVar[int]
     starting_version: int
     transactions_count: int
+    transaction_filter: TransactionFilter
     def __init__(
         self,
         starting_version: _Optional[int] = ...,
         transactions_count: _Optional[int] = ...,
+        transaction_filter: _Optional[_Union[TransactionFilter, _Mapping]] = ...,
     ) -> None: ...
 
 class TransactionsFromNodeResponse(_message.Message):
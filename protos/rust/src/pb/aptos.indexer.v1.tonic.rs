@@ -87,7 +87,7 @@ pub mod raw_data_client {
             self.inner = self.inner.max_encoding_message_size(limit);
             self
         }
-        /** Get transactions batch without any filtering from starting version and end if transaction count is present.
+        /** Get transactions batch from starting version and end if transaction count is present. Transactions are only filtered if the request has a filter.
 */
         pub async fn get_transactions(
             &mut self,
@@ -129,7 +129,7 @@ pub mod raw_data_server {
             >
             + Send
             + 'static;
-        /** Get transactions batch without any filtering from starting version and end if transaction count is present.
+        /** Get transactions batch from starting version and end if transaction count is present. Transactions are only filtered if the request has a filter.
 */
         async fn get_transactions(
             &self,
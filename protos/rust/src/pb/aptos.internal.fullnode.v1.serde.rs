@@ -15,6 +15,9 @@ impl serde::Serialize for GetTransactionsFromNodeRequest {
         if self.transactions_count.is_some() {
             len += 1;
         }
+        if self.transaction_filter.is_some() {
+            len += 1;
+        }
         let mut struct_ser = serializer.serialize_struct("aptos.internal.fullnode.v1.GetTransactionsFromNodeRequest", len)?;
         if let Some(v) = self.starting_version.as_ref() {
             struct_ser.serialize_field("startingVersion", ToString::to_string(&v).as_str())?;
@@ -22,6 +25,9 @@ impl serde::Serialize for GetTransactionsFromNodeRequest {
         if let Some(v) = self.transactions_count.as_ref() {
             struct_ser.serialize_field("transactionsCount", ToString::to_string(&v).as_str())?;
         }
+        if let Some(v) = self.transaction_filter.as_ref() {
+            struct_ser.serialize_field("transactionFilter", v)?;
+        }
         struct_ser.end()
     }
 }
@@ -36,12 +42,15 @@ impl<'de> serde::Deserialize<'de> for GetTransactionsFromNodeRequest {
             "startingVersion",
             "transactions_count",
             "transactionsCount",
+            "transaction_filter",
+            "transactionFilter",
         ];
 
         #[allow(clippy::enum_variant_names)]
         enum GeneratedField {
             StartingVersion,
             TransactionsCount,
+            TransactionFilter,
         }
         impl<'de> serde::Deserialize<'de> for GeneratedField {
             fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
@@ -65,6 +74,7 @@ impl<'de> serde::Deserialize<'de> for GetTransactionsFromNodeRequest {
                         match value {
                             "startingVersion" | "starting_version" => Ok(GeneratedField::StartingVersion),
                             "transactionsCount" | "transactions_count" => Ok(GeneratedField::TransactionsCount),
+                            "transactionFilter" | "transaction_filter" => Ok(GeneratedField::TransactionFilter),
                             _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
                         }
                     }
@@ -86,6 +96,7 @@ impl<'de> serde::Deserialize<'de> for GetTransactionsFromNodeRequest {
             {
                 let mut starting_version__ = None;
                 let mut transactions_count__ = None;
+                let mut transaction_filter__ = None;
                 while let Some(k) = map.next_key()? {
                     match k {
                         GeneratedField::StartingVersion => {
@@ -104,11 +115,18 @@ impl<'de> serde::Deserialize<'de> for GetTransactionsFromNodeRequest {
                                 map.next_value::<::std::option::Option<::pbjson::private::NumberDeserialize<_>>>()?.map(|x| x.0)
                             ;
                         }
+                        GeneratedField::TransactionFilter => {
+                            if transaction_filter__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("transactionFilter"));
+                            }
+                            transaction_filter__ = map.next_value()?;
+                        }
                     }
                 }
                 Ok(GetTransactionsFromNodeRequest {
                     starting_version: starting_version__,
                     transactions_count: transactions_count__,
+                    transaction_filter: transaction_filter__,
                 })
             }
         }
@@ -324,6 +342,134 @@ impl<'de> serde::Deserialize<'de> for stream_status::StatusType {
         deserializer.deserialize_any(GeneratedVisitor)
     }
 }
+impl serde::Serialize for TransactionFilter {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.event_types.is_empty() {
+            len += 1;
+        }
+        if !self.write_resource_types.is_empty() {
+            len += 1;
+        }
+        if !self.entry_functions.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.internal.fullnode.v1.TransactionFilter", len)?;
+        if !self.event_types.is_empty() {
+            struct_ser.serialize_field("eventTypes", &self.event_types)?;
+        }
+        if !self.write_resource_types.is_empty() {
+            struct_ser.serialize_field("writeResourceTypes", &self.write_resource_types)?;
+        }
+        if !self.entry_functions.is_empty() {
+            struct_ser.serialize_field("entryFunctions", &self.entry_functions)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for TransactionFilter {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "event_types",
+            "eventTypes",
+            "write_resource_types",
+            "writeResourceTypes",
+            "entry_functions",
+            "entryFunctions",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            EventTypes,
+            WriteResourceTypes,
+            EntryFunctions,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "eventTypes" | "event_types" => Ok(GeneratedField::EventTypes),
+                            "writeResourceTypes" | "write_resource_types" => Ok(GeneratedField::WriteResourceTypes),
+                            "entryFunctions" | "entry_functions" => Ok(GeneratedField::EntryFunctions),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = TransactionFilter;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.internal.fullnode.v1.TransactionFilter")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<TransactionFilter, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut event_types__ = None;
+                let mut write_resource_types__ = None;
+                let mut entry_functions__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::EventTypes => {
+                            if event_types__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("eventTypes"));
+                            }
+                            event_types__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::WriteResourceTypes => {
+                            if write_resource_types__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("writeResourceTypes"));
+                            }
+                            write_resource_types__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::EntryFunctions => {
+                            if entry_functions__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("entryFunctions"));
+                            }
+                            entry_functions__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(TransactionFilter {
+                    event_types: event_types__.unwrap_or_default(),
+                    write_resource_types: write_resource_types__.unwrap_or_default(),
+                    entry_functions: entry_functions__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.internal.fullnode.v1.TransactionFilter", FIELDS, GeneratedVisitor)
+    }
+}
 impl serde::Serialize for TransactionsFromNodeResponse {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -12,6 +12,25 @@ import Long from "long";
 import _m0 from "protobufjs/minimal";
 import { Transaction } from "../../transaction/v1/transaction";
 
+/**
+ * Filters applied by the server before transactions are sent. A transaction is sent
+ * if it matches any of the criteria; an empty filter matches every transaction.
+ */
+export interface TransactionFilter {
+  /**
+   * Struct tags of emitted events, e.g. `0x1::coin::DepositEvent`. A tag without
+   * type arguments matches every instantiation of the struct.
+   */
+  eventTypes?:
+    | string[]
+    | undefined;
+  /**
+   * Entry functions called by user transactions, e.g. `0x1::coin::transfer`, or
+   * modules, e.g. `0x1::coin`, to match every entry function of the module.
+   */
+  entryFunctions?: string[] | undefined;
+}
+
 export interface GetTransactionsRequest {
   /** Required; start version of current stream. */
   startingVersion?:
@@ -28,7 +47,14 @@ export interface GetTransactionsRequest {
    * Optional; number of transactions in each `TransactionsResponse` for current stream.
    * If not present, default to 1000. If larger than 1000, request will be rejected.
    */
-  batchSize?: bigint | undefined;
+  batchSize?:
+    | bigint
+    | undefined;
+  /**
+   * Optional; if set, only transactions matching the filter are sent, and
+   * `transactions_count` counts the transactions sent.
+   */
+  transactionFilter?: TransactionFilter | undefined;
 }
 
 /** TransactionsResponse is a batch of transactions. */
@@ -41,8 +67,127 @@ export interface TransactionsResponse {
   chainId?: bigint | undefined;
 }
 
+function createBaseTransactionFilter(): TransactionFilter {
+  return { eventTypes: [], entryFunctions: [] };
+}
+
+export const TransactionFilter = {
+  encode(message: TransactionFilter, writer: _m0.Writer = _m0.Writer.create()): _m0.Writer {
+    if (message.eventTypes !== undefined && message.eventTypes.length !== 0) {
+      for (const v of message.eventTypes) {
+        writer.uint32(10).string(v!);
+      }
+    }
+    if (message.entryFunctions !== undefined && message.entryFunctions.length !== 0) {
+      for (const v of message.entryFunctions) {
+        writer.uint32(18).string(v!);
+      }
+    }
+    return writer;
+  },
+
+  decode(input: _m0.Reader | Uint8Array, length?: number): TransactionFilter {
+    const reader = input instanceof _m0.Reader ? input : _m0.Reader.create(input);
+    let end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseTransactionFilter();
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1:
+          if (tag !== 10) {
+            break;
+          }
+
+          message.eventTypes!.push(reader.string());
+          continue;
+        case 2:
+          if (tag !== 18) {
+            break;
+          }
+
+          message.entryFunctions!.push(reader.string());
+          continue;
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skipType(tag & 7);
+    }
+    return message;
+  },
+
+  // encodeTransform encodes a source of message objects.
+  // Transform<TransactionFilter, Uint8Array>
+  async *encodeTransform(
+    source: AsyncIterable<TransactionFilter | TransactionFilter[]> | Iterable<TransactionFilter | TransactionFilter[]>,
+  ): AsyncIterable<Uint8Array> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [TransactionFilter.encode(p).finish()];
+        }
+      } else {
+        yield* [TransactionFilter.encode(pkt as any).finish()];
+      }
+    }
+  },
+
+  // decodeTransform decodes a source of encoded messages.
+  // Transform<Uint8Array, TransactionFilter>
+  async *decodeTransform(
+    source: AsyncIterable<Uint8Array | Uint8Array[]> | Iterable<Uint8Array | Uint8Array[]>,
+  ): AsyncIterable<TransactionFilter> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [TransactionFilter.decode(p)];
+        }
+      } else {
+        yield* [TransactionFilter.decode(pkt as any)];
+      }
+    }
+  },
+
+  fromJSON(object: any): TransactionFilter {
+    return {
+      eventTypes: globalThis.Array.isArray(object?.eventTypes)
+        ? object.eventTypes.map((e: any) => globalThis.String(e))
+        : [],
+      entryFunctions: globalThis.Array.isArray(object?.entryFunctions)
+        ? object.entryFunctions.map((e: any) => globalThis.String(e))
+        : [],
+    };
+  },
+
+  toJSON(message: TransactionFilter): unknown {
+    const obj: any = {};
+    if (message.eventTypes?.length) {
+      obj.eventTypes = message.eventTypes;
+    }
+    if (message.entryFunctions?.length) {
+      obj.entryFunctions = message.entryFunctions;
+    }
+    return obj;
+  },
+
+  create(base?: DeepPartial<TransactionFilter>): TransactionFilter {
+    return TransactionFilter.fromPartial(base ?? {});
+  },
+  fromPartial(object: DeepPartial<TransactionFilter>): TransactionFilter {
+    const message = createBaseTransactionFilter();
+    message.eventTypes = object.eventTypes?.map((e) => e) || [];
+    message.entryFunctions = object.entryFunctions?.map((e) => e) || [];
+    return message;
+  },
+};
+
 function createBaseGetTransactionsRequest(): GetTransactionsRequest {
-  return { startingVersion: undefined, transactionsCount: undefined, batchSize: undefined };
+  return {
+    startingVersion: undefined,
+    transactionsCount: undefined,
+    batchSize: undefined,
+    transactionFilter: undefined,
+  };
 }
 
 export const GetTransactionsRequest = {
@@ -65,6 +210,9 @@ export const GetTransactionsRequest = {
       }
       writer.uint32(24).uint64(message.batchSize.toString());
     }
+    if (message.transactionFilter !== undefined) {
+      TransactionFilter.encode(message.transactionFilter, writer.uint32(34).fork()).ldelim();
+    }
     return writer;
   },
 
@@ -96,6 +244,13 @@ export const GetTransactionsRequest = {
 
           message.batchSize = longToBigint(reader.uint64() as Long);
           continue;
+        case 4:
+          if (tag !== 34) {
+            break;
+          }
+
+          message.transactionFilter = TransactionFilter.decode(reader, reader.uint32());
+          continue;
       }
       if ((tag & 7) === 4 || tag === 0) {
         break;
@@ -144,6 +299,9 @@ export const GetTransactionsRequest = {
       startingVersion: isSet(object.startingVersion) ? BigInt(object.startingVersion) : undefined,
       transactionsCount: isSet(object.transactionsCount) ? BigInt(object.transactionsCount) : undefined,
       batchSize: isSet(object.batchSize) ? BigInt(object.batchSize) : undefined,
+      transactionFilter: isSet(object.transactionFilter)
+        ? TransactionFilter.fromJSON(object.transactionFilter)
+        : undefined,
     };
   },
 
@@ -158,6 +316,9 @@ export const GetTransactionsRequest = {
     if (message.batchSize !== undefined) {
       obj.batchSize = message.batchSize.toString();
     }
+    if (message.transactionFilter !== undefined) {
+      obj.transactionFilter = TransactionFilter.toJSON(message.transactionFilter);
+    }
     return obj;
   },
 
@@ -169,6 +330,9 @@ export const GetTransactionsRequest = {
     message.startingVersion = object.startingVersion ?? undefined;
     message.transactionsCount = object.transactionsCount ?? undefined;
     message.batchSize = object.batchSize ?? undefined;
+    message.transactionFilter = (object.transactionFilter !== undefined && object.transactionFilter !== null)
+      ? TransactionFilter.fromPartial(object.transactionFilter)
+      : undefined;
     return message;
   },
 };
@@ -290,7 +454,7 @@ export const TransactionsResponse = {
 
 export type RawDataService = typeof RawDataService;
 export const RawDataService = {
-  /** Get transactions batch without any filtering from starting version and end if transaction count is present. */
+  /** Get transactions batch from starting version and end if transaction count is present. Transactions are only filtered if the request has a filter. */
   getTransactions: {
     path: "/aptos.indexer.v1.RawData/GetTransactions",
     requestStream: false,
@@ -303,12 +467,12 @@ export const RawDataService = {
 } as const;
 
 export interface RawDataServer extends UntypedServiceImplementation {
-  /** Get transactions batch without any filtering from starting version and end if transaction count is present. */
+  /** Get transactions batch from starting version and end if transaction count is present. Transactions are only filtered if the request has a filter. */
   getTransactions: handleServerStreamingCall<GetTransactionsRequest, TransactionsResponse>;
 }
 
 export interface RawDataClient extends Client {
-  /** Get transactions batch without any filtering from starting version and end if transaction count is present. */
+  /** Get transactions batch from starting version and end if transaction count is present. Transactions are only filtered if the request has a filter. */
   getTransactions(
     request: GetTransactionsRequest,
     options?: Partial<CallOptions>,
@@ -69,6 +69,33 @@ export function streamStatus_StatusTypeToJSON(object: StreamStatus_StatusType):
   }
 }
 
+/**
+ * Filters applied by the fullnode before transactions are converted and sent. A
+ * transaction is sent if it matches any of the criteria; an empty filter matches
+ * every transaction.
+ */
+export interface TransactionFilter {
+  /**
+   * Struct tags of emitted events, e.g. `0x1::coin::DepositEvent`. A tag without
+   * type arguments matches every instantiation of the struct.
+   */
+  eventTypes?:
+    | string[]
+    | undefined;
+  /**
+   * Struct tags of written resources, e.g. `0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>`.
+   * A tag without type arguments matches every instantiation of the struct.
+   */
+  writeResourceTypes?:
+    | string[]
+    | undefined;
+  /**
+   * Entry functions called by user transactions, e.g. `0x1::coin::transfer`, or
+   * modules, e.g. `0x1::coin`, to match every entry function of the module.
+   */
+  entryFunctions?: string[] | undefined;
+}
+
 export interface GetTransactionsFromNodeRequest {
   /**
    * Required; start version of current stream.
@@ -81,7 +108,14 @@ export interface GetTransactionsFromNodeRequest {
    * Optional; number of transactions to return in current stream.
    * If not set, response streams infinitely.
    */
-  transactionsCount?: bigint | undefined;
+  transactionsCount?:
+    | bigint
+    | undefined;
+  /**
+   * Optional; if set, only transactions matching the filter are sent. Batch end
+   * statuses still cover every version, so clients can keep checking for gaps.
+   */
+  transactionFilter?: TransactionFilter | undefined;
 }
 
 export interface TransactionsFromNodeResponse {
@@ -317,8 +351,141 @@ export const StreamStatus = {
   },
 };
 
+function createBaseTransactionFilter(): TransactionFilter {
+  return { eventTypes: [], writeResourceTypes: [], entryFunctions: [] };
+}
+
+export const TransactionFilter = {
+  encode(message: TransactionFilter, writer: _m0.Writer = _m0.Writer.create()): _m0.Writer {
+    if (message.eventTypes !== undefined && message.eventTypes.length !== 0) {
+      for (const v of message.eventTypes) {
+        writer.uint32(10).string(v!);
+      }
+    }
+    if (message.writeResourceTypes !== undefined && message.writeResourceTypes.length !== 0) {
+      for (const v of message.writeResourceTypes) {
+        writer.uint32(18).string(v!);
+      }
+    }
+    if (message.entryFunctions !== undefined && message.entryFunctions.length !== 0) {
+      for (const v of message.entryFunctions) {
+        writer.uint32(26).string(v!);
+      }
+    }
+    return writer;
+  },
+
+  decode(input: _m0.Reader | Uint8Array, length?: number): TransactionFilter {
+    const reader = input instanceof _m0.Reader ? input : _m0.Reader.create(input);
+    let end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseTransactionFilter();
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1:
+          if (tag !== 10) {
+            break;
+          }
+
+          message.eventTypes!.push(reader.string());
+          continue;
+        case 2:
+          if (tag !== 18) {
+            break;
+          }
+
+          message.writeResourceTypes!.push(reader.string());
+          continue;
+        case 3:
+          if (tag !== 26) {
+            break;
+          }
+
+          message.entryFunctions!.push(reader.string());
+          continue;
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skipType(tag & 7);
+    }
+    return message;
+  },
+
+  // encodeTransform encodes a source of message objects.
+  // Transform<TransactionFilter, Uint8Array>
+  async *encodeTransform(
+    source: AsyncIterable<TransactionFilter | TransactionFilter[]> | Iterable<TransactionFilter | TransactionFilter[]>,
+  ): AsyncIterable<Uint8Array> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [TransactionFilter.encode(p).finish()];
+        }
+      } else {
+        yield* [TransactionFilter.encode(pkt as any).finish()];
+      }
+    }
+  },
+
+  // decodeTransform decodes a source of encoded messages.
+  // Transform<Uint8Array, TransactionFilter>
+  async *decodeTransform(
+    source: AsyncIterable<Uint8Array | Uint8Array[]> | Iterable<Uint8Array | Uint8Array[]>,
+  ): AsyncIterable<TransactionFilter> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [TransactionFilter.decode(p)];
+        }
+      } else {
+        yield* [TransactionFilter.decode(pkt as any)];
+      }
+    }
+  },
+
+  fromJSON(object: any): TransactionFilter {
+    return {
+      eventTypes: globalThis.Array.isArray(object?.eventTypes)
+        ? object.eventTypes.map((e: any) => globalThis.String(e))
+        : [],
+      writeResourceTypes: globalThis.Array.isArray(object?.writeResourceTypes)
+        ? object.writeResourceTypes.map((e: any) => globalThis.String(e))
+        : [],
+      entryFunctions: globalThis.Array.isArray(object?.entryFunctions)
+        ? object.entryFunctions.map((e: any) => globalThis.String(e))
+        : [],
+    };
+  },
+
+  toJSON(message: TransactionFilter): unknown {
+    const obj: any = {};
+    if (message.eventTypes?.length) {
+      obj.eventTypes = message.eventTypes;
+    }
+    if (message.writeResourceTypes?.length) {
+      obj.writeResourceTypes = message.writeResourceTypes;
+    }
+    if (message.entryFunctions?.length) {
+      obj.entryFunctions = message.entryFunctions;
+    }
+    return obj;
+  },
+
+  create(base?: DeepPartial<TransactionFilter>): TransactionFilter {
+    return TransactionFilter.fromPartial(base ?? {});
+  },
+  fromPartial(object: DeepPartial<TransactionFilter>): TransactionFilter {
+    const message = createBaseTransactionFilter();
+    message.eventTypes = object.eventTypes?.map((e) => e) || [];
+    message.writeResourceTypes = object.writeResourceTypes?.map((e) => e) || [];
+    message.entryFunctions = object.entryFunctions?.map((e) => e) || [];
+    return message;
+  },
+};
+
 function createBaseGetTransactionsFromNodeRequest(): GetTransactionsFromNodeRequest {
-  return { startingVersion: undefined, transactionsCount: undefined };
+  return { startingVersion: undefined, transactionsCount: undefined, transactionFilter: undefined };
 }
 
 export const GetTransactionsFromNodeRequest = {
@@ -335,6 +502,9 @@ export const GetTransactionsFromNodeRequest = {
       }
       writer.uint32(16).uint64(message.transactionsCount.toString());
     }
+    if (message.transactionFilter !== undefined) {
+      TransactionFilter.encode(message.transactionFilter, writer.uint32(26).fork()).ldelim();
+    }
     return writer;
   },
 
@@ -359,6 +529,13 @@ export const GetTransactionsFromNodeRequest = {
 
           message.transactionsCount = longToBigint(reader.uint64() as Long);
           continue;
+        case 3:
+          if (tag !== 26) {
+            break;
+          }
+
+          message.transactionFilter = TransactionFilter.decode(reader, reader.uint32());
+          continue;
       }
       if ((tag & 7) === 4 || tag === 0) {
         break;
@@ -406,6 +583,9 @@ export const GetTransactionsFromNodeRequest = {
     return {
       startingVersion: isSet(object.startingVersion) ? BigInt(object.startingVersion) : undefined,
       transactionsCount: isSet(object.transactionsCount) ? BigInt(object.transactionsCount) : undefined,
+      transactionFilter: isSet(object.transactionFilter)
+        ? TransactionFilter.fromJSON(object.transactionFilter)
+        : undefined,
     };
   },
 
@@ -417,6 +597,9 @@ export const GetTransactionsFromNodeRequest = {
     if (message.transactionsCount !== undefined) {
       obj.transactionsCount = message.transactionsCount.toString();
     }
+    if (message.transactionFilter !== undefined) {
+      obj.transactionFilter = TransactionFilter.toJSON(message.transactionFilter);
+    }
     return obj;
   },
 
@@ -427,6 +610,9 @@ export const GetTransactionsFromNodeRequest = {
     const message = createBaseGetTransactionsFromNodeRequest();
     message.startingVersion = object.startingVersion ?? undefined;
     message.transactionsCount = object.transactionsCount ?? undefined;
+    message.transactionFilter = (object.transactionFilter !== undefined && object.transactionFilter !== null)
+      ? TransactionFilter.fromPartial(object.transactionFilter)
+      : undefined;
     return message;
   },
 };
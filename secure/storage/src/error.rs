@@ -63,3 +63,9 @@ impl From<aptos_vault_client::Error> for Error {
         }
     }
 }
+
+impl From<cryptoki::error::Error> for Error {
+    fn from(error: cryptoki::error::Error) -> Self {
+        Self::InternalError(format!("PKCS#11 error: {}", error))
+    }
+}
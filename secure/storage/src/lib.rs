@@ -11,6 +11,7 @@ mod in_memory;
 mod kv_storage;
 mod namespaced;
 mod on_disk;
+mod pkcs11;
 mod policy;
 mod storage;
 mod vault;
@@ -23,6 +24,7 @@ pub use crate::{
     kv_storage::{GetResponse, KVStorage},
     namespaced::Namespaced,
     on_disk::OnDiskStorage,
+    pkcs11::Pkcs11Storage,
     policy::{Capability, Identity, Permission, Policy},
     storage::Storage,
     vault::VaultStorage,
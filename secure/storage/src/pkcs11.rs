@@ -0,0 +1,407 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{CryptoStorage, Error, GetResponse, KVStorage, PublicKeyResponse};
+use aptos_crypto::{
+    ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature, ED25519_PUBLIC_KEY_LENGTH},
+    hash::CryptoHash,
+    traits::signing_message,
+    PrivateKey,
+};
+use aptos_infallible::Mutex;
+use aptos_metrics_core::{exponential_buckets, register_histogram_vec, HistogramVec};
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use cryptoki::{
+    context::{CInitializeArgs, Pkcs11},
+    mechanism::Mechanism,
+    object::{Attribute, AttributeType, KeyType, ObjectClass, ObjectHandle},
+    session::{Session, UserType},
+    types::AuthPin,
+};
+use once_cell::sync::Lazy;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{convert::TryFrom, path::Path};
+
+/// Marks the data objects created by this storage, to tell them apart from other data on the token
+const APPLICATION: &[u8] = b"aptos-secure-storage";
+/// Suffix of the label of the previous version of a key, which is kept after a rotation
+const PREVIOUS_VERSION_SUFFIX: &str = "__previous";
+/// DER encoding of the Ed25519 curve OID (1.3.101.112), as expected in CKA_EC_PARAMS
+const ED25519_EC_PARAMS: &[u8] = &[0x06, 0x03, 0x2B, 0x65, 0x70];
+/// DER tag of an OCTET STRING, which some tokens wrap CKA_EC_POINT with
+const DER_OCTET_STRING_TAG: u8 = 0x04;
+
+static PKCS11_OPERATION_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "aptos_secure_storage_pkcs11_operation_latency_seconds",
+        "Latency of the operations performed on the PKCS#11 token",
+        &["operation"],
+        exponential_buckets(/*start=*/ 1e-4, /*factor=*/ 2.0, /*count=*/ 20).unwrap(),
+    )
+    .unwrap()
+});
+
+/// Pkcs11Storage delegates key management and signing to a hardware security module (HSM)
+/// through its PKCS#11 module. Ed25519 keys are generated on the token as sensitive,
+/// non-extractable objects: they can sign, but never leave the device, so exporting them fails.
+/// Key/value data is kept in private data objects on the token, which are only readable after
+/// logging in with the user PIN.
+///
+/// Only the Ed25519 keys get the protection of the device. PKCS#11 has no standard mechanism for
+/// BLS12-381, so the consensus key is stored as key/value data: anyone holding the PIN can read
+/// it, and safety rules loads it into memory to sign. Similarly, the network identity can't be
+/// kept here, as the network needs the raw key, which can't be exported.
+pub struct Pkcs11Storage {
+    session: Mutex<Session>,
+    time_service: TimeService,
+}
+
+impl Pkcs11Storage {
+    /// Loads the PKCS#11 module at `library_path`, and logs into the token in slot `slot_id` with
+    /// the user `pin`.
+    pub fn new(library_path: &Path, slot_id: u64, pin: String) -> Result<Self, Error> {
+        let context = Pkcs11::new(library_path)?;
+        context.initialize(CInitializeArgs::OsThreads)?;
+        let slot = context
+            .get_slots_with_token()?
+            .into_iter()
+            .find(|slot| slot.id() == slot_id)
+            .ok_or_else(|| {
+                Error::InternalError(format!("No PKCS#11 token found in slot {}", slot_id))
+            })?;
+        let session = context.open_rw_session(slot)?;
+        session.login(UserType::User, Some(&AuthPin::new(pin)))?;
+        Ok(Self {
+            session: Mutex::new(session),
+            time_service: TimeService::real(),
+        })
+    }
+
+    /// Runs `f` on the session, recording its latency under `operation`.
+    fn with_session<T>(
+        &self,
+        operation: &'static str,
+        f: impl FnOnce(&Session) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        let _timer = PKCS11_OPERATION_LATENCY
+            .with_label_values(&[operation])
+            .start_timer();
+        let session = self.session.lock();
+        f(&session)
+    }
+
+    fn find_data(session: &Session, key: &str) -> Result<Option<ObjectHandle>, Error> {
+        find_one(session, &[
+            Attribute::Class(ObjectClass::DATA),
+            Attribute::Application(APPLICATION.to_vec()),
+            Attribute::Label(key.as_bytes().to_vec()),
+        ])
+    }
+
+    fn find_key(
+        session: &Session,
+        class: ObjectClass,
+        label: &str,
+    ) -> Result<Option<ObjectHandle>, Error> {
+        find_one(session, &[
+            Attribute::Class(class),
+            Attribute::KeyType(KeyType::EC_EDWARDS),
+            Attribute::Label(label.as_bytes().to_vec()),
+        ])
+    }
+
+    fn find_key_or_err(
+        session: &Session,
+        class: ObjectClass,
+        label: &str,
+    ) -> Result<ObjectHandle, Error> {
+        Self::find_key(session, class, label)?.ok_or_else(|| Error::KeyNotSet(label.to_string()))
+    }
+
+    fn read_public_key(session: &Session, handle: ObjectHandle) -> Result<Ed25519PublicKey, Error> {
+        let point = match get_attribute(session, handle, AttributeType::EcPoint)? {
+            Attribute::EcPoint(point) => point,
+            _ => return Err(unexpected_attribute("CKA_EC_POINT")),
+        };
+        // The point is either raw, or DER encoded as an OCTET STRING
+        let bytes = match point.as_slice() {
+            [DER_OCTET_STRING_TAG, len, bytes @ ..]
+                if *len as usize == ED25519_PUBLIC_KEY_LENGTH
+                    && bytes.len() == ED25519_PUBLIC_KEY_LENGTH =>
+            {
+                bytes
+            },
+            bytes => bytes,
+        };
+        Ed25519PublicKey::try_from(bytes)
+            .map_err(|e| Error::SerializationError(format!("Invalid public key: {:?}", e)))
+    }
+
+    /// The last update of a key is kept in the CKA_ID of its public key object.
+    fn read_last_update(session: &Session, handle: ObjectHandle) -> Result<u64, Error> {
+        match get_attribute(session, handle, AttributeType::Id)? {
+            Attribute::Id(id) => Ok(<[u8; 8]>::try_from(id.as_slice())
+                .map(u64::from_be_bytes)
+                .unwrap_or(0)),
+            _ => Err(unexpected_attribute("CKA_ID")),
+        }
+    }
+
+    fn generate_key(&self, session: &Session, label: &str) -> Result<Ed25519PublicKey, Error> {
+        let id = self.time_service.now_secs().to_be_bytes().to_vec();
+        let (public_key, _) = session.generate_key_pair(
+            &Mechanism::EccEdwardsKeyPairGen,
+            &[
+                Attribute::Token(true),
+                Attribute::Private(false),
+                Attribute::Verify(true),
+                Attribute::EcParams(ED25519_EC_PARAMS.to_vec()),
+                Attribute::Label(label.as_bytes().to_vec()),
+                Attribute::Id(id.clone()),
+            ],
+            &[
+                Attribute::Token(true),
+                Attribute::Private(true),
+                Attribute::Sensitive(true),
+                Attribute::Extractable(false),
+                Attribute::Sign(true),
+                Attribute::Label(label.as_bytes().to_vec()),
+                Attribute::Id(id),
+            ],
+        )?;
+        Self::read_public_key(session, public_key)
+    }
+
+    /// Destroys both halves of the key pair with the given label, if any.
+    fn destroy_key(session: &Session, label: &str) -> Result<(), Error> {
+        for class in [ObjectClass::PUBLIC_KEY, ObjectClass::PRIVATE_KEY] {
+            if let Some(handle) = Self::find_key(session, class, label)? {
+                session.destroy_object(handle)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn sign_with(
+        session: &Session,
+        label: &str,
+        message: &[u8],
+    ) -> Result<Ed25519Signature, Error> {
+        let private_key = Self::find_key_or_err(session, ObjectClass::PRIVATE_KEY, label)?;
+        let signature = session.sign(&Mechanism::Eddsa, private_key, message)?;
+        Ed25519Signature::try_from(signature.as_slice())
+            .map_err(|e| Error::SerializationError(format!("Invalid signature: {:?}", e)))
+    }
+}
+
+impl KVStorage for Pkcs11Storage {
+    fn available(&self) -> Result<(), Error> {
+        self.with_session("available", |session| {
+            session.get_session_info()?;
+            Ok(())
+        })
+    }
+
+    fn get<V: DeserializeOwned>(&self, key: &str) -> Result<GetResponse<V>, Error> {
+        self.with_session("get", |session| {
+            let handle =
+                Self::find_data(session, key)?.ok_or_else(|| Error::KeyNotSet(key.to_string()))?;
+            match get_attribute(session, handle, AttributeType::Value)? {
+                Attribute::Value(value) => Ok(serde_json::from_slice(&value)?),
+                _ => Err(unexpected_attribute("CKA_VALUE")),
+            }
+        })
+    }
+
+    fn set<V: Serialize>(&mut self, key: &str, value: V) -> Result<(), Error> {
+        let now = self.time_service.now_secs();
+        let value = serde_json::to_vec(&GetResponse::new(value, now))?;
+        self.with_session("set", |session| {
+            // Updating the existing object in place, rather than replacing it, ensures a crash can
+            // never leave the key without any value.
+            match Self::find_data(session, key)? {
+                Some(handle) => session.update_attributes(handle, &[Attribute::Value(value)])?,
+                None => {
+                    session.create_object(&[
+                        Attribute::Class(ObjectClass::DATA),
+                        Attribute::Token(true),
+                        Attribute::Private(true),
+                        Attribute::Modifiable(true),
+                        Attribute::Application(APPLICATION.to_vec()),
+                        Attribute::Label(key.as_bytes().to_vec()),
+                        Attribute::Value(value),
+                    ])?;
+                },
+            }
+            Ok(())
+        })
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    fn reset_and_clear(&mut self) -> Result<(), Error> {
+        self.with_session("reset_and_clear", |session| {
+            let mut handles = session.find_objects(&[
+                Attribute::Class(ObjectClass::DATA),
+                Attribute::Application(APPLICATION.to_vec()),
+            ])?;
+            for class in [ObjectClass::PUBLIC_KEY, ObjectClass::PRIVATE_KEY] {
+                handles.extend(session.find_objects(&[
+                    Attribute::Class(class),
+                    Attribute::KeyType(KeyType::EC_EDWARDS),
+                ])?);
+            }
+            for handle in handles {
+                session.destroy_object(handle)?;
+            }
+            Ok(())
+        })
+    }
+}
+
+impl CryptoStorage for Pkcs11Storage {
+    fn create_key(&mut self, name: &str) -> Result<Ed25519PublicKey, Error> {
+        self.with_session("create_key", |session| {
+            Self::destroy_key(session, name)?;
+            Self::destroy_key(session, &previous_version(name))?;
+            self.generate_key(session, name)
+        })
+    }
+
+    fn export_private_key(&self, _name: &str) -> Result<Ed25519PrivateKey, Error> {
+        // Keys are generated as non-extractable, so they never leave the token
+        Err(Error::PermissionDenied)
+    }
+
+    fn import_private_key(&mut self, name: &str, key: Ed25519PrivateKey) -> Result<(), Error> {
+        let id = self.time_service.now_secs().to_be_bytes().to_vec();
+        self.with_session("import_private_key", |session| {
+            Self::destroy_key(session, name)?;
+            Self::destroy_key(session, &previous_version(name))?;
+            let public_key = key.public_key().to_bytes();
+            let mut point = vec![DER_OCTET_STRING_TAG, public_key.len() as u8];
+            point.extend_from_slice(&public_key);
+            session.create_object(&[
+                Attribute::Class(ObjectClass::PUBLIC_KEY),
+                Attribute::KeyType(KeyType::EC_EDWARDS),
+                Attribute::Token(true),
+                Attribute::Private(false),
+                Attribute::Verify(true),
+                Attribute::EcParams(ED25519_EC_PARAMS.to_vec()),
+                Attribute::EcPoint(point),
+                Attribute::Label(name.as_bytes().to_vec()),
+                Attribute::Id(id.clone()),
+            ])?;
+            session.create_object(&[
+                Attribute::Class(ObjectClass::PRIVATE_KEY),
+                Attribute::KeyType(KeyType::EC_EDWARDS),
+                Attribute::Token(true),
+                Attribute::Private(true),
+                Attribute::Sensitive(true),
+                Attribute::Extractable(false),
+                Attribute::Sign(true),
+                Attribute::EcParams(ED25519_EC_PARAMS.to_vec()),
+                Attribute::Value(key.to_bytes().to_vec()),
+                Attribute::Label(name.as_bytes().to_vec()),
+                Attribute::Id(id),
+            ])?;
+            Ok(())
+        })
+    }
+
+    fn export_private_key_for_version(
+        &self,
+        _name: &str,
+        _version: Ed25519PublicKey,
+    ) -> Result<Ed25519PrivateKey, Error> {
+        Err(Error::PermissionDenied)
+    }
+
+    fn get_public_key(&self, name: &str) -> Result<PublicKeyResponse, Error> {
+        self.with_session("get_public_key", |session| {
+            let handle = Self::find_key_or_err(session, ObjectClass::PUBLIC_KEY, name)?;
+            Ok(PublicKeyResponse {
+                last_update: Self::read_last_update(session, handle)?,
+                public_key: Self::read_public_key(session, handle)?,
+            })
+        })
+    }
+
+    fn get_public_key_previous_version(&self, name: &str) -> Result<Ed25519PublicKey, Error> {
+        self.with_session("get_public_key_previous_version", |session| {
+            let handle =
+                Self::find_key_or_err(session, ObjectClass::PUBLIC_KEY, &previous_version(name))?;
+            Self::read_public_key(session, handle)
+        })
+    }
+
+    fn rotate_key(&mut self, name: &str) -> Result<Ed25519PublicKey, Error> {
+        self.with_session("rotate_key", |session| {
+            let public_key = Self::find_key_or_err(session, ObjectClass::PUBLIC_KEY, name)?;
+            let private_key = Self::find_key_or_err(session, ObjectClass::PRIVATE_KEY, name)?;
+            let previous = previous_version(name);
+            Self::destroy_key(session, &previous)?;
+            for handle in [public_key, private_key] {
+                session
+                    .update_attributes(handle, &[Attribute::Label(previous.as_bytes().to_vec())])?;
+            }
+            self.generate_key(session, name)
+        })
+    }
+
+    fn sign<T: CryptoHash + Serialize>(
+        &self,
+        name: &str,
+        message: &T,
+    ) -> Result<Ed25519Signature, Error> {
+        let message =
+            signing_message(message).map_err(|e| Error::SerializationError(format!("{:?}", e)))?;
+        self.with_session("sign", |session| Self::sign_with(session, name, &message))
+    }
+
+    fn sign_using_version<T: CryptoHash + Serialize>(
+        &self,
+        name: &str,
+        version: Ed25519PublicKey,
+        message: &T,
+    ) -> Result<Ed25519Signature, Error> {
+        let message =
+            signing_message(message).map_err(|e| Error::SerializationError(format!("{:?}", e)))?;
+        self.with_session("sign_using_version", |session| {
+            for label in [name.to_string(), previous_version(name)] {
+                if let Some(handle) = Self::find_key(session, ObjectClass::PUBLIC_KEY, &label)? {
+                    if Self::read_public_key(session, handle)? == version {
+                        return Self::sign_with(session, &label, &message);
+                    }
+                }
+            }
+            Err(Error::KeyVersionNotFound(
+                name.to_string(),
+                version.to_string(),
+            ))
+        })
+    }
+}
+
+fn previous_version(name: &str) -> String {
+    format!("{}{}", name, PREVIOUS_VERSION_SUFFIX)
+}
+
+fn find_one(session: &Session, template: &[Attribute]) -> Result<Option<ObjectHandle>, Error> {
+    Ok(session.find_objects(template)?.into_iter().next())
+}
+
+fn get_attribute(
+    session: &Session,
+    handle: ObjectHandle,
+    attribute: AttributeType,
+) -> Result<Attribute, Error> {
+    session
+        .get_attributes(handle, &[attribute])?
+        .into_iter()
+        .next()
+        .ok_or_else(|| unexpected_attribute("requested attribute"))
+}
+
+fn unexpected_attribute(name: &str) -> Error {
+    Error::InternalError(format!("PKCS#11 token did not return {}", name))
+}
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 use crate::{
     CryptoStorage, Error, GetResponse, InMemoryStorage, KVStorage, Namespaced, OnDiskStorage,
-    PublicKeyResponse, VaultStorage,
+    Pkcs11Storage, PublicKeyResponse, VaultStorage,
 };
 use aptos_crypto::ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature};
 use enum_dispatch::enum_dispatch;
@@ -21,6 +21,7 @@ pub enum Storage {
     InMemoryStorage(InMemoryStorage),
     NamespacedStorage(Namespaced<Box<Storage>>),
     OnDiskStorage(OnDiskStorage),
+    Pkcs11Storage(Pkcs11Storage),
 }
 
 impl KVStorage for Box<Storage> {
@@ -0,0 +1,117 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{CryptoStorage, Error, KVStorage, Pkcs11Storage, Storage};
+use aptos_crypto::{
+    ed25519::Ed25519PrivateKey, test_utils::TestAptosCrypto, PrivateKey, Signature, Uniform,
+};
+use rand::rngs::OsRng;
+use std::path::PathBuf;
+
+/// Environment variables pointing the tests at a PKCS#11 token, e.g., a SoftHSM one
+const LIBRARY_ENV: &str = "APTOS_PKCS11_LIBRARY";
+const SLOT_ENV: &str = "APTOS_PKCS11_SLOT";
+const PIN_ENV: &str = "APTOS_PKCS11_PIN";
+
+const CRYPTO_NAME: &str = "crypto_name";
+const KV_NAME: &str = "kv_name";
+
+/// A test for verifying Pkcs11Storage properly implements the SecureStorage API, except for
+/// exporting private keys, which never leave the token. This test depends on a PKCS#11 token,
+/// which can be created with SoftHSM:
+/// `softhsm2-util --init-token --free --label aptos --so-pin 1234 --pin 1234`
+#[test]
+fn execute_storage_tests_pkcs11() {
+    let mut storage = match create_pkcs11() {
+        Some(storage) => Storage::from(storage),
+        None => return,
+    };
+    storage.reset_and_clear().unwrap();
+
+    test_kv(&mut storage);
+    test_create_sign_and_rotate(&mut storage);
+    test_import_key(&mut storage);
+
+    storage.reset_and_clear().unwrap();
+}
+
+fn create_pkcs11() -> Option<Pkcs11Storage> {
+    let library = PathBuf::from(std::env::var(LIBRARY_ENV).ok()?);
+    let slot = std::env::var(SLOT_ENV).ok()?.parse().unwrap();
+    let pin = std::env::var(PIN_ENV).ok()?;
+    Some(Pkcs11Storage::new(&library, slot, pin).unwrap())
+}
+
+fn test_kv(storage: &mut Storage) {
+    storage.available().unwrap();
+    assert_eq!(
+        storage.get::<u64>(KV_NAME).unwrap_err(),
+        Error::KeyNotSet(KV_NAME.to_string())
+    );
+
+    storage.set(KV_NAME, 1u64).unwrap();
+    assert_eq!(storage.get::<u64>(KV_NAME).unwrap().value, 1);
+    storage.set(KV_NAME, 2u64).unwrap();
+    assert_eq!(storage.get::<u64>(KV_NAME).unwrap().value, 2);
+}
+
+fn test_create_sign_and_rotate(storage: &mut Storage) {
+    let message = TestAptosCrypto("Hello, World".to_string());
+
+    let public_key = storage.create_key(CRYPTO_NAME).unwrap();
+    assert_eq!(
+        storage.get_public_key(CRYPTO_NAME).unwrap().public_key,
+        public_key
+    );
+    assert_eq!(
+        storage.export_private_key(CRYPTO_NAME).unwrap_err(),
+        Error::PermissionDenied
+    );
+
+    let signature = storage.sign(CRYPTO_NAME, &message).unwrap();
+    signature.verify(&message, &public_key).unwrap();
+
+    let new_public_key = storage.rotate_key(CRYPTO_NAME).unwrap();
+    assert_ne!(new_public_key, public_key);
+    assert_eq!(
+        storage
+            .get_public_key_previous_version(CRYPTO_NAME)
+            .unwrap(),
+        public_key
+    );
+    storage
+        .sign(CRYPTO_NAME, &message)
+        .unwrap()
+        .verify(&message, &new_public_key)
+        .unwrap();
+    storage
+        .sign_using_version(CRYPTO_NAME, public_key.clone(), &message)
+        .unwrap()
+        .verify(&message, &public_key)
+        .unwrap();
+
+    // Only the last two versions are kept
+    storage.rotate_key(CRYPTO_NAME).unwrap();
+    assert!(storage
+        .sign_using_version(CRYPTO_NAME, public_key, &message)
+        .is_err());
+}
+
+fn test_import_key(storage: &mut Storage) {
+    let message = TestAptosCrypto("Hello, World".to_string());
+    let private_key = Ed25519PrivateKey::generate(&mut OsRng);
+    let public_key = private_key.public_key();
+
+    storage
+        .import_private_key(CRYPTO_NAME, private_key)
+        .unwrap();
+    assert_eq!(
+        storage.get_public_key(CRYPTO_NAME).unwrap().public_key,
+        public_key
+    );
+    storage
+        .sign(CRYPTO_NAME, &message)
+        .unwrap()
+        .verify(&message, &public_key)
+        .unwrap();
+}
@@ -43,7 +43,7 @@ use aptos_storage_service_types::{
     responses::{StorageServerSummary, StorageServiceResponse, TransactionOrOutputListWithProof},
     Epoch, StorageServiceMessage,
 };
-use aptos_time_service::TimeService;
+use aptos_time_service::{TimeService, TimeServiceTrait};
 use aptos_types::{
     epoch_change::EpochChangeProof,
     ledger_info::LedgerInfoWithSignatures,
@@ -53,7 +53,13 @@ use aptos_types::{
 use arc_swap::ArcSwap;
 use async_trait::async_trait;
 use maplit::hashset;
-use std::{collections::HashSet, fmt, ops::Deref, sync::Arc, time::Duration};
+use std::{
+    collections::HashSet,
+    fmt,
+    ops::Deref,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use tokio::runtime::Handle;
 
 // Useful constants
@@ -89,6 +95,8 @@ pub struct AptosDataClient {
     active_subscription_state: Arc<Mutex<Option<SubscriptionState>>>,
     /// All of the data-client specific data we have on each network peer.
     peer_states: Arc<PeerStates>,
+    /// The peer currently preferred for data requests (and the time it was chosen).
+    sticky_data_peer: Arc<Mutex<Option<(PeerNetworkId, Instant)>>>,
     /// A cached, aggregate data summary of all unbanned peers' data summaries.
     global_summary_cache: Arc<ArcSwap<GlobalDataSummary>>,
     /// Used for generating the next request/response id.
@@ -117,6 +125,7 @@ impl AptosDataClient {
             storage_service_client: storage_service_client.clone(),
             active_subscription_state: Arc::new(Mutex::new(None)),
             peer_states: Arc::new(PeerStates::new(data_client_config.clone())),
+            sticky_data_peer: Arc::new(Mutex::new(None)),
             global_summary_cache: Arc::new(ArcSwap::from(Arc::new(GlobalDataSummary::empty()))),
             response_id_generator: Arc::new(U64IdGenerator::new()),
             time_service: time_service.clone(),
@@ -225,6 +234,9 @@ impl AptosDataClient {
         } else if request.data_request.is_optimistic_fetch() {
             // Choose a peer to handle the optimistic fetch request
             self.choose_random_peer_by_distance_and_latency(request, serviceable_peers)
+        } else if self.data_client_config.enable_throughput_peer_selection {
+            // Choose the peer randomly weighted by throughput
+            self.choose_random_peer_by_throughput(request, serviceable_peers)
         } else {
             // Choose the peer randomly weighted by latency
             self.choose_random_peer_by_latency(request, serviceable_peers)
@@ -326,6 +338,58 @@ impl AptosDataClient {
         self.choose_random_peer(request, serviceable_peers)
     }
 
+    /// Chooses a peer randomly weighted by throughput from the given set of
+    /// serviceable peers. Peers on probation are avoided (unless all peers are
+    /// on probation), and the sticky peer is preferred until it expires. If no
+    /// peers have been measured yet, we fall back to selecting by latency.
+    fn choose_random_peer_by_throughput(
+        &self,
+        request: &StorageServiceRequest,
+        serviceable_peers: HashSet<PeerNetworkId>,
+    ) -> Result<PeerNetworkId, Error> {
+        // Avoid the peers on probation (if possible)
+        let now = self.time_service.now();
+        let peers_not_on_probation: HashSet<_> = serviceable_peers
+            .iter()
+            .filter(|peer| !self.peer_states.is_on_probation(peer, now))
+            .cloned()
+            .collect();
+        let candidate_peers = if peers_not_on_probation.is_empty() {
+            serviceable_peers
+        } else {
+            peers_not_on_probation
+        };
+
+        // Identify the sticky peer (if it hasn't expired and is still a candidate)
+        let mut sticky_data_peer = self.sticky_data_peer.lock();
+        let stickiness_duration =
+            Duration::from_secs(self.data_client_config.peer_stickiness_duration_secs);
+        let sticky_peer = sticky_data_peer
+            .filter(|(peer, selection_time)| {
+                candidate_peers.contains(peer)
+                    && now.duration_since(*selection_time) < stickiness_duration
+            })
+            .map(|(peer, _)| peer);
+
+        // Choose a peer weighted by throughput (or by latency if no peers have been measured)
+        let peer = match utils::choose_random_peer_by_throughput(
+            candidate_peers.clone(),
+            self.peer_states.clone(),
+            sticky_peer,
+            self.data_client_config.sticky_peer_weight_multiplier,
+        ) {
+            Some(peer) => peer,
+            None => self.choose_random_peer_by_latency(request, candidate_peers)?,
+        };
+
+        // If there's no sticky peer, the chosen peer becomes sticky
+        if sticky_peer.is_none() {
+            *sticky_data_peer = Some((peer, now));
+        }
+
+        Ok(peer)
+    }
+
     /// Identifies the peers in the given set of prospective peers
     /// that can service the specified request.
     fn identify_serviceable(
@@ -474,6 +538,7 @@ impl AptosDataClient {
         self.update_sent_request_metrics(peer, &request);
 
         // Send the request and process the result
+        let request_start_time = self.time_service.now();
         let result = self
             .storage_service_client
             .send_request(
@@ -503,6 +568,14 @@ impl AptosDataClient {
                 // feels simpler for the consumer.
                 self.peer_states.update_score_success(peer);
 
+                // Update the peer's throughput and error rate
+                self.update_peer_throughput_and_error_rate(
+                    peer,
+                    &request,
+                    &response,
+                    request_start_time,
+                );
+
                 // Package up all of the context needed to fully report an error
                 // with this RPC.
                 let response_callback = AptosNetResponseCallback {
@@ -567,6 +640,53 @@ impl AptosDataClient {
         error_type: ErrorType,
     ) {
         self.peer_states.update_score_error(peer, error_type);
+        self.peer_states
+            .update_error_rate(peer, true, self.time_service.now());
+    }
+
+    /// Updates the throughput and error rate of the peer that successfully
+    /// responded to the given request (sent at the given start time).
+    fn update_peer_throughput_and_error_rate(
+        &self,
+        peer: PeerNetworkId,
+        request: &StorageServiceRequest,
+        response: &StorageServiceResponse,
+        request_start_time: Instant,
+    ) {
+        // Update the peer's error rate
+        let now = self.time_service.now();
+        self.peer_states.update_error_rate(peer, false, now);
+
+        // Only data chunk responses are useful for measuring throughput. Optimistic
+        // fetches and subscriptions are held by the peer until new data arrives.
+        let data_request = &request.data_request;
+        if data_request.is_storage_summary_request()
+            || data_request.is_protocol_version_request()
+            || data_request.is_optimistic_fetch()
+            || data_request.is_subscription_request()
+        {
+            return;
+        }
+
+        // Update the peer's throughput
+        match response.get_num_bytes() {
+            Ok(num_bytes) => {
+                let request_duration = now.duration_since(request_start_time);
+                self.peer_states
+                    .update_throughput(peer, num_bytes, request_duration);
+            },
+            Err(error) => {
+                warn!(
+                    (LogSchema::new(LogEntry::StorageServiceResponse)
+                        .event(LogEvent::UnexpectedError)
+                        .message(&format!(
+                            "Unable to calculate the response size! Error: {:?}",
+                            error
+                        ))
+                        .peer(&peer))
+                );
+            },
+        }
     }
 
     /// Creates a storage service request using the given data request
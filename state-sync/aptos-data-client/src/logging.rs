@@ -56,6 +56,7 @@ pub enum LogEvent {
     NoPeersToPoll,
     PeerIgnored,
     PeerNoLongerIgnored,
+    PeerOnProbation,
     PeerPollingError,
     PeerRequestResponseCounts,
     PeerSelectionError,
@@ -18,7 +18,7 @@ use std::{
     cmp::min,
     collections::{BTreeMap, HashSet},
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 // Useful constants
@@ -39,6 +39,9 @@ const MALICIOUS_MULTIPLIER: f64 = 0.8;
 /// Ignore a peer when their score dips below this threshold.
 const IGNORE_PEER_THRESHOLD: f64 = 25.0;
 
+/// The weight of the newest sample in the peer throughput and error rate moving averages.
+const MOVING_AVERAGE_SAMPLE_WEIGHT: f64 = 0.2;
+
 pub enum ErrorType {
     /// A response or error that's not actively malicious but also doesn't help
     /// us make progress, e.g., timeouts, remote errors, invalid data, etc...
@@ -70,6 +73,14 @@ pub struct PeerState {
     storage_summary: Option<StorageServerSummary>,
     /// For now, a simplified port of the original state-sync v1 scoring system.
     score: f64,
+    /// The moving average of the observed response throughput (in bytes per
+    /// second), or `None` if we haven't measured a data response yet.
+    throughput_bytes_per_sec: Option<f64>,
+    /// The moving average of the observed error rate (between 0 and 1).
+    error_rate: f64,
+    /// The time at which the peer's probation ends, or `None` if the peer
+    /// is not on probation.
+    probation_end_time: Option<Instant>,
 }
 
 impl Default for PeerState {
@@ -79,6 +90,9 @@ impl Default for PeerState {
             sent_requests_by_type: Arc::new(DashMap::new()),
             storage_summary: None,
             score: STARTING_SCORE,
+            throughput_bytes_per_sec: None,
+            error_rate: 0.0,
+            probation_end_time: None,
         }
     }
 }
@@ -105,6 +119,22 @@ impl PeerState {
         self.score
     }
 
+    /// Returns the peer's observed throughput (if it has been measured)
+    pub fn get_throughput_bytes_per_sec(&self) -> Option<f64> {
+        self.throughput_bytes_per_sec
+    }
+
+    /// Returns the peer's observed error rate
+    pub fn get_error_rate(&self) -> f64 {
+        self.error_rate
+    }
+
+    /// Returns true iff the peer is on probation at the given time
+    pub fn is_on_probation(&self, now: Instant) -> bool {
+        self.probation_end_time
+            .map_or(false, |probation_end_time| now < probation_end_time)
+    }
+
     /// Returns the storage summary for the peer
     pub fn get_storage_summary(&self) -> Option<StorageServerSummary> {
         self.storage_summary.clone()
@@ -152,6 +182,56 @@ impl PeerState {
         self.score = f64::max(self.score * multiplier, MIN_SCORE);
     }
 
+    /// Updates the throughput of the peer using a response of the given
+    /// size that took the given duration.
+    fn update_throughput(&mut self, num_bytes: u64, duration: Duration) {
+        // We can't measure the throughput of instantaneous responses
+        let duration_secs = duration.as_secs_f64();
+        if duration_secs <= 0.0 {
+            return;
+        }
+
+        // Update the moving average
+        let throughput = num_bytes as f64 / duration_secs;
+        self.throughput_bytes_per_sec = Some(match self.throughput_bytes_per_sec {
+            Some(average_throughput) => update_moving_average(average_throughput, throughput),
+            None => throughput,
+        });
+    }
+
+    /// Updates the error rate of the peer according to the result of a
+    /// request, and puts the peer on probation if the error rate is too
+    /// high. Returns true iff the peer was put on probation.
+    fn update_error_rate(
+        &mut self,
+        is_error: bool,
+        now: Instant,
+        data_client_config: &AptosDataClientConfig,
+    ) -> bool {
+        // If the probation has ended, give the peer a fresh start
+        if let Some(probation_end_time) = self.probation_end_time {
+            if now >= probation_end_time {
+                self.probation_end_time = None;
+                self.error_rate = 0.0;
+            }
+        }
+
+        // Update the moving average
+        let sample = if is_error { 1.0 } else { 0.0 };
+        self.error_rate = update_moving_average(self.error_rate, sample);
+
+        // Put the peer on probation if the error rate is too high
+        let max_error_rate = data_client_config.max_peer_error_rate_percent as f64 / 100.0;
+        if self.probation_end_time.is_none() && self.error_rate > max_error_rate {
+            let probation_duration =
+                Duration::from_secs(data_client_config.peer_probation_duration_secs);
+            self.probation_end_time = Some(now + probation_duration);
+            return true;
+        }
+
+        false
+    }
+
     /// Updates the storage summary for the peer
     fn update_storage_summary(&mut self, storage_summary: StorageServerSummary) {
         self.storage_summary = Some(storage_summary);
@@ -294,6 +374,51 @@ impl PeerStates {
         }
     }
 
+    /// Updates the throughput of the peer using a response of the given
+    /// size that took the given duration.
+    pub fn update_throughput(&self, peer: PeerNetworkId, num_bytes: u64, duration: Duration) {
+        if let Some(mut entry) = self.peer_to_state.get_mut(&peer) {
+            entry.update_throughput(num_bytes, duration);
+        }
+    }
+
+    /// Updates the error rate of the peer according to the result of a request
+    pub fn update_error_rate(&self, peer: PeerNetworkId, is_error: bool, now: Instant) {
+        if let Some(mut entry) = self.peer_to_state.get_mut(&peer) {
+            // Update the peer's error rate
+            let put_on_probation = entry.update_error_rate(is_error, now, &self.data_client_config);
+
+            // Log if the peer is now on probation
+            if put_on_probation {
+                info!(
+                    (LogSchema::new(LogEntry::PeerStates)
+                        .event(LogEvent::PeerOnProbation)
+                        .message(&format!(
+                            "Peer will be on probation. Error rate: {:?}",
+                            entry.error_rate
+                        ))
+                        .peer(&peer))
+                );
+            }
+        }
+    }
+
+    /// Returns true iff the given peer is on probation at the given time
+    pub fn is_on_probation(&self, peer: &PeerNetworkId, now: Instant) -> bool {
+        self.peer_to_state
+            .get(peer)
+            .map_or(false, |peer_state| peer_state.is_on_probation(now))
+    }
+
+    /// Returns the throughput weight of the given peer, i.e., the observed
+    /// throughput discounted by the observed error rate. If the throughput
+    /// of the peer hasn't been measured yet, None is returned.
+    pub fn get_throughput_weight(&self, peer: &PeerNetworkId) -> Option<f64> {
+        let peer_state = self.peer_to_state.get(peer)?;
+        let throughput = peer_state.get_throughput_bytes_per_sec()?;
+        Some(throughput * (1.0 - peer_state.get_error_rate()))
+    }
+
     /// Updates the storage summary for the given peer
     pub fn update_summary(&self, peer: PeerNetworkId, storage_summary: StorageServerSummary) {
         self.peer_to_state
@@ -428,6 +553,11 @@ fn median_or_max<T: Ord + Copy>(mut values: Vec<T>, max_value: T) -> T {
     min(median.unwrap_or(max_value), max_value)
 }
 
+/// Updates the given moving average with the new sample
+fn update_moving_average(average: f64, sample: f64) -> f64 {
+    (MOVING_AVERAGE_SAMPLE_WEIGHT * sample) + ((1.0 - MOVING_AVERAGE_SAMPLE_WEIGHT) * average)
+}
+
 /// Returns the bucket ID for the given peer. This is useful
 /// for grouping peers together to avoid metric explosion.
 pub fn get_bucket_id_for_peer(peer: PeerNetworkId) -> u8 {
@@ -11,10 +11,11 @@ use crate::{
 };
 use aptos_config::{config::AptosDataClientConfig, network_id::PeerNetworkId};
 use aptos_storage_service_types::{
-    requests::DataRequest,
+    requests::{DataRequest, StorageServiceRequest, TransactionOutputsWithProofRequest},
     responses::{CompleteDataRange, DataResponse, StorageServerSummary, StorageServiceResponse},
     StorageServiceError,
 };
+use aptos_time_service::TimeServiceTrait;
 use aptos_types::transaction::TransactionListWithProof;
 use claims::{assert_err, assert_matches};
 use maplit::hashset;
@@ -287,6 +288,63 @@ async fn bad_peer_is_eventually_added_back() {
     }
 }
 
+#[tokio::test]
+async fn bad_peer_is_put_on_probation() {
+    // Create the mock network and client
+    let data_client_config = AptosDataClientConfig::default();
+    let (mut mock_network, mock_time, client, _) =
+        MockNetwork::new(None, Some(data_client_config), None);
+
+    // Add a good and bad peer that both advertise the data
+    let good_peer = mock_network.add_peer(true);
+    let bad_peer = mock_network.add_peer(true);
+    client.update_peer_storage_summary(good_peer, utils::create_storage_summary(100));
+    client.update_peer_storage_summary(bad_peer, utils::create_storage_summary(100));
+
+    // Give both peers the same throughput
+    let peer_states = client.get_peer_states();
+    for peer in [good_peer, bad_peer] {
+        peer_states.update_throughput(peer, 1_000_000, Duration::from_secs(1));
+    }
+
+    // Keep failing requests to the bad peer until it is put on probation
+    for _ in 0..10 {
+        peer_states.update_error_rate(good_peer, false, mock_time.now());
+        peer_states.update_error_rate(bad_peer, true, mock_time.now());
+    }
+    assert!(!peer_states.is_on_probation(&good_peer, mock_time.now()));
+    assert!(peer_states.is_on_probation(&bad_peer, mock_time.now()));
+
+    // Verify the bad peer is never selected while on probation
+    let storage_request = StorageServiceRequest::new(
+        DataRequest::GetTransactionOutputsWithProof(TransactionOutputsWithProofRequest {
+            proof_version: 100,
+            start_version: 0,
+            end_version: 100,
+        }),
+        false,
+    );
+    for _ in 0..100 {
+        let selected_peer = client.choose_peer_for_request(&storage_request).unwrap();
+        assert_eq!(selected_peer, good_peer);
+    }
+
+    // Elapse the probation and verify the bad peer is given a fresh start
+    mock_time
+        .advance_secs_async(data_client_config.peer_probation_duration_secs)
+        .await;
+    peer_states.update_error_rate(bad_peer, false, mock_time.now());
+    assert!(!peer_states.is_on_probation(&bad_peer, mock_time.now()));
+    assert_eq!(
+        peer_states
+            .get_peer_to_states()
+            .get(&bad_peer)
+            .unwrap()
+            .get_error_rate(),
+        0.0
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn disconnected_peers_garbage_collection() {
     // Ensure the properties hold for both priority and non-priority peers
@@ -4,6 +4,7 @@
 use crate::{
     error::Error,
     logging::{LogEntry, LogEvent, LogSchema},
+    peer_states::PeerStates,
 };
 use aptos_config::{
     config::{AptosDataClientConfig, BaseConfig},
@@ -106,6 +107,65 @@ pub fn choose_random_peer_by_distance_and_latency(
     None
 }
 
+/// Chooses a single peer weighted by the observed throughput of the peers
+/// (discounted by their observed error rates). Peers that haven't been
+/// measured yet are given the median weight of the measured peers, so that
+/// they can still be selected (and measured). The weight of the sticky peer
+/// (if any) is multiplied by the given multiplier.
+///
+/// If no peer has been measured yet, None is returned.
+pub fn choose_random_peer_by_throughput(
+    peers: HashSet<PeerNetworkId>,
+    peer_states: Arc<PeerStates>,
+    sticky_peer: Option<PeerNetworkId>,
+    sticky_peer_weight_multiplier: u64,
+) -> Option<PeerNetworkId> {
+    // Gather the throughput weights for all measured peers
+    let mut measured_peers_and_weights = vec![];
+    let mut unmeasured_peers = vec![];
+    for peer in peers {
+        match peer_states.get_throughput_weight(&peer) {
+            Some(throughput_weight) => measured_peers_and_weights.push((peer, throughput_weight)),
+            None => unmeasured_peers.push(peer),
+        }
+    }
+
+    // If no peers have been measured, we can't select a peer by throughput
+    if measured_peers_and_weights.is_empty() {
+        return None;
+    }
+
+    // Calculate the median weight of the measured peers
+    let mut measured_weights = measured_peers_and_weights
+        .iter()
+        .map(|(_, weight)| OrderedFloat(*weight))
+        .collect::<Vec<_>>();
+    measured_weights.sort_unstable();
+    let median_weight = measured_weights[measured_weights.len() / 2].into_inner();
+
+    // Give the unmeasured peers the median weight and boost the sticky peer
+    let peers_and_weights = measured_peers_and_weights
+        .into_iter()
+        .chain(
+            unmeasured_peers
+                .into_iter()
+                .map(|peer| (peer, median_weight)),
+        )
+        .map(|(peer, weight)| {
+            if Some(peer) == sticky_peer {
+                (peer, weight * sticky_peer_weight_multiplier as f64)
+            } else {
+                (peer, weight)
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // Select a single peer by throughput weights
+    choose_random_peers_by_weight(1, peers_and_weights)
+        .into_iter()
+        .next()
+}
+
 /// Selects the specified number of peers from the list of potential
 /// peers. Peer selection is weighted by peer latencies (i.e., the
 /// lower the latency, the higher the probability of selection).
@@ -201,7 +261,7 @@ pub fn choose_random_peers_by_weight(
             LogSchema::new(LogEntry::PeerStates)
                 .event(LogEvent::PeerSelectionError)
                 .message(&format!(
-                    "Unable to select peer by latencies! Error: {:?}",
+                    "Unable to select peer by weights! Error: {:?}",
                     error
                 )),
         );
@@ -307,20 +367,26 @@ fn log_warning_with_sample(log: LogSchema) {
 
 #[cfg(test)]
 mod tests {
-    use crate::utils::{
-        choose_random_peer, choose_random_peers, choose_random_peers_by_weight, is_priority_peer,
+    use crate::{
+        peer_states::PeerStates,
+        utils::{
+            choose_random_peer, choose_random_peer_by_throughput, choose_random_peers,
+            choose_random_peers_by_weight, is_priority_peer,
+        },
     };
     use aptos_config::{
-        config::{BaseConfig, PeerRole, RoleType},
+        config::{AptosDataClientConfig, BaseConfig, PeerRole, RoleType},
         network_id::{NetworkId, PeerNetworkId},
     };
     use aptos_netcore::transport::ConnectionOrigin;
     use aptos_network::{application::storage::PeersAndMetadata, transport::ConnectionMetadata};
+    use aptos_storage_service_types::responses::StorageServerSummary;
     use aptos_types::PeerId;
     use maplit::hashset;
     use std::{
         collections::{HashMap, HashSet},
         sync::Arc,
+        time::Duration,
     };
 
     #[test]
@@ -449,6 +515,69 @@ mod tests {
         assert!(peer_count_2 > peer_count_3);
     }
 
+    #[test]
+    fn test_choose_random_peer_by_throughput() {
+        // Create the peer states and add several peers
+        let peer_states = Arc::new(PeerStates::new(Arc::new(AptosDataClientConfig::default())));
+        let peers = (0..3)
+            .map(|_| create_random_peer_network_id())
+            .collect::<Vec<_>>();
+        for peer in &peers {
+            peer_states.update_summary(*peer, StorageServerSummary::default());
+        }
+
+        // Verify that no peer is chosen when no throughputs have been measured
+        let chosen_peer = choose_random_peer_by_throughput(
+            peers.iter().cloned().collect(),
+            peer_states.clone(),
+            None,
+            1,
+        );
+        assert!(chosen_peer.is_none());
+
+        // Update the throughputs of the first two peers (the third remains unmeasured)
+        let (peer_1, peer_2, peer_3) = (peers[0], peers[1], peers[2]);
+        peer_states.update_throughput(peer_1, 10_000_000, Duration::from_secs(1));
+        peer_states.update_throughput(peer_2, 10_000, Duration::from_secs(1));
+
+        // Choose a single peer multiple times and track the selection counts
+        let mut chosen_peers_and_counts = HashMap::new();
+        for _ in 0..10_000 {
+            let chosen_peer = choose_random_peer_by_throughput(
+                peers.iter().cloned().collect(),
+                peer_states.clone(),
+                None,
+                1,
+            )
+            .unwrap();
+            *chosen_peers_and_counts.entry(chosen_peer).or_insert(0) += 1;
+        }
+
+        // Verify that the fastest peer is chosen the most, and that
+        // the unmeasured peer (with the median weight) is still chosen.
+        let peer_count_1 = chosen_peers_and_counts.get(&peer_1).unwrap_or(&0);
+        let peer_count_2 = chosen_peers_and_counts.get(&peer_2).unwrap_or(&0);
+        let peer_count_3 = chosen_peers_and_counts.get(&peer_3).unwrap_or(&0);
+        assert!(peer_count_1 > peer_count_2);
+        assert!(*peer_count_3 > 0);
+
+        // Make the slow peer sticky and verify it is chosen more often
+        let mut sticky_peer_count = 0;
+        for _ in 0..10_000 {
+            let chosen_peer = choose_random_peer_by_throughput(
+                peers.iter().cloned().collect(),
+                peer_states.clone(),
+                Some(peer_2),
+                1000,
+            )
+            .unwrap();
+            if chosen_peer == peer_2 {
+                sticky_peer_count += 1;
+            }
+        }
+        assert!(sticky_peer_count > *peer_count_2);
+    }
+
     #[test]
     fn test_is_priority_peer_validator() {
         // Create a base config for a validator node
@@ -79,6 +79,9 @@ pub struct EventSubscriptionService {
     event_key_subscriptions: HashMap<EventKey, HashSet<SubscriptionId>>,
     subscription_id_to_event_subscription: HashMap<SubscriptionId, EventSubscription>,
 
+    // All events subscription registry
+    all_events_subscriptions: HashMap<SubscriptionId, EventSubscription>,
+
     // Reconfig subscription registry
     reconfig_subscriptions: HashMap<SubscriptionId, ReconfigSubscription>,
 
@@ -94,6 +97,7 @@ impl EventSubscriptionService {
         Self {
             event_key_subscriptions: HashMap::new(),
             subscription_id_to_event_subscription: HashMap::new(),
+            all_events_subscriptions: HashMap::new(),
             reconfig_subscriptions: HashMap::new(),
             storage,
             subscription_id_generator: U64IdGenerator::new(),
@@ -151,6 +155,37 @@ impl EventSubscriptionService {
         })
     }
 
+    /// Returns an EventNotificationListener that can be monitored for all
+    /// events. The EventNotificationListener will be sent a notification with
+    /// all the events every time new events are committed on-chain. Note: if
+    /// the notification buffer fills up too quickly, older notifications will
+    /// be dropped. As such, it is the responsibility of the subscriber to
+    /// ensure notifications are processed in a timely manner.
+    pub fn subscribe_to_all_events(&mut self) -> Result<EventNotificationListener, Error> {
+        let (notification_sender, notification_receiver) =
+            aptos_channel::new(QueueStyle::KLAST, EVENT_NOTIFICATION_CHANNEL_SIZE, None);
+
+        // Create and store the new subscription
+        let subscription_id = self.get_new_subscription_id();
+        let event_subscription = EventSubscription {
+            notification_sender,
+            event_buffer: vec![],
+        };
+        if let Some(old_subscription) = self
+            .all_events_subscriptions
+            .insert(subscription_id, event_subscription)
+        {
+            return Err(Error::UnexpectedErrorEncountered(format!(
+                "Duplicate all events subscription found! This should not occur! ID: {}, subscription: {:?}",
+                subscription_id, old_subscription
+            )));
+        }
+
+        Ok(EventNotificationListener {
+            notification_receiver,
+        })
+    }
+
     /// Returns a ReconfigNotificationListener that can be monitored for
     /// reconfiguration events. Subscribers will be sent a notification
     /// containing all new on-chain configuration values whenever a new epoch
@@ -231,6 +266,14 @@ impl EventSubscriptionService {
             }
         }
 
+        // Notify all events subscribers of the new events
+        for event_subscription in self.all_events_subscriptions.values_mut() {
+            for event in events.iter() {
+                event_subscription.buffer_event(event.clone());
+            }
+            event_subscription.notify_subscriber_of_events(version)?;
+        }
+
         // Notify event subscribers of the new events
         for event_subscription_id in event_subscription_ids_to_notify {
             if let Some(event_subscription) = self
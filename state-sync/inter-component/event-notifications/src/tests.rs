@@ -348,6 +348,43 @@ fn test_event_subscribers() {
     verify_no_event_notifications(vec![&mut listener_1]);
 }
 
+#[test]
+fn test_all_events_subscribers() {
+    // Create subscription service and mock database
+    let mut event_service = create_event_subscription_service();
+
+    // Subscribe to all events, and to a single event key
+    let event_key_1 = create_random_event_key();
+    let event_key_2 = create_random_event_key();
+    let mut all_events_listener = event_service.subscribe_to_all_events().unwrap();
+    let mut event_listener = event_service
+        .subscribe_to_events(vec![event_key_1])
+        .unwrap();
+
+    // Notify the subscription service of new events (with event_key_1 and event_key_2)
+    let version = 50;
+    let event_1 = create_test_event(event_key_1);
+    let event_2 = create_test_event(event_key_2);
+    notify_events(&mut event_service, version, vec![
+        event_1.clone(),
+        event_2.clone(),
+    ]);
+
+    // Verify the all events listener gets all the events, and the other listener only its event
+    verify_event_notification_received(vec![&mut all_events_listener], version, vec![
+        event_1.clone(),
+        event_2.clone(),
+    ]);
+    verify_event_notification_received(vec![&mut event_listener], version, vec![event_1]);
+
+    // Notify the subscription service of a new event (with event_key_2) and verify only the
+    // all events listener gets it.
+    let version = 51;
+    notify_events(&mut event_service, version, vec![event_2.clone()]);
+    verify_event_notification_received(vec![&mut all_events_listener], version, vec![event_2]);
+    verify_no_event_notifications(vec![&mut all_events_listener, &mut event_listener]);
+}
+
 #[test]
 fn test_no_events_no_subscribers() {
     // Create subscription service and mock database
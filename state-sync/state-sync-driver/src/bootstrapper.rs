@@ -6,7 +6,7 @@ use crate::{
     driver::DriverConfiguration,
     error::Error,
     logging::{LogEntry, LogSchema},
-    metadata_storage::MetadataStorageInterface,
+    metadata_storage::{FastSyncManifest, MetadataStorageInterface},
     metrics,
     metrics::ExecutingComponent,
     storage_synchronizer::StorageSynchronizerInterface,
@@ -241,6 +241,9 @@ impl VerifiedEpochStates {
 
 /// A simple container to manage data related to state value snapshot syncing
 pub(crate) struct StateValueSyncer {
+    // Whether or not we've checked for a fast sync manifest (persisted by a previous run)
+    checked_fast_sync_manifest: bool,
+
     // Whether or not a state snapshot receiver has been initialized
     initialized_state_snapshot_receiver: bool,
 
@@ -258,6 +261,7 @@ pub(crate) struct StateValueSyncer {
 impl StateValueSyncer {
     pub fn new() -> Self {
         Self {
+            checked_fast_sync_manifest: false,
             initialized_state_snapshot_receiver: false,
             ledger_info_to_sync: None,
             next_state_index_to_process: 0,
@@ -456,8 +460,9 @@ impl<
         // Reset the chunk executor to flush any invalid state currently held in-memory
         self.storage_synchronizer.reset_chunk_executor()?;
 
-        // Always fetch the new epoch ending ledger infos first
-        if self.should_fetch_epoch_ending_ledger_infos() {
+        // Always fetch the new epoch ending ledger infos first (unless
+        // we're resuming a fast sync that was interrupted by a restart).
+        if self.should_fetch_epoch_ending_ledger_infos() && !self.restore_fast_sync_manifest()? {
             return self
                 .fetch_epoch_ending_ledger_infos(global_data_summary)
                 .await;
@@ -506,6 +511,130 @@ impl<
         }
     }
 
+    /// Attempts to resume a fast sync that was interrupted by a restart, using
+    /// the fast sync manifest persisted by the previous run. Returns true iff
+    /// the manifest was restored (i.e., the epoch ending ledger infos and the
+    /// transaction output to sync don't need to be fetched again).
+    fn restore_fast_sync_manifest(&mut self) -> Result<bool, Error> {
+        // The manifest is only checked once (when fast syncing)
+        if !self.get_bootstrapping_mode().is_fast_sync()
+            || self.state_value_syncer.checked_fast_sync_manifest
+        {
+            return Ok(false);
+        }
+        self.state_value_syncer.checked_fast_sync_manifest = true;
+
+        // The manifest is only relevant if the node hasn't synced any state yet
+        let highest_synced_version = utils::fetch_latest_synced_version(self.storage.clone())?;
+        if highest_synced_version != GENESIS_TRANSACTION_VERSION {
+            return Ok(false);
+        }
+        let fast_sync_manifest = match self.metadata_storage.get_fast_sync_manifest()? {
+            Some(fast_sync_manifest) => fast_sync_manifest,
+            None => return Ok(false),
+        };
+
+        // Verify the manifest again (it may have been written by a different
+        // node configuration, e.g., with another waypoint).
+        let verified_epoch_states = match self.verify_fast_sync_manifest(&fast_sync_manifest) {
+            Ok(verified_epoch_states) => verified_epoch_states,
+            Err(error) => {
+                warn!(LogSchema::new(LogEntry::Bootstrapper).message(&format!(
+                    "Failed to verify the fast sync manifest, ignoring it! Error: {:?}",
+                    error
+                )));
+                return Ok(false);
+            },
+        };
+
+        // Restore the verified epoch states and the data to sync
+        info!(LogSchema::new(LogEntry::Bootstrapper).message(&format!(
+            "Resuming the fast sync from the persisted manifest! Target: {:?}",
+            fast_sync_manifest.target_ledger_info
+        )));
+        self.verified_epoch_states = verified_epoch_states;
+        self.verified_epoch_states
+            .set_fetched_epoch_ending_ledger_infos();
+        self.state_value_syncer
+            .set_ledger_info_to_sync(fast_sync_manifest.target_ledger_info);
+        self.state_value_syncer
+            .set_transaction_output_to_sync(fast_sync_manifest.transaction_output_to_sync);
+
+        Ok(true)
+    }
+
+    /// Verifies the given fast sync manifest against the latest epoch state in
+    /// storage and the waypoint. Returns the verified epoch states.
+    fn verify_fast_sync_manifest(
+        &self,
+        fast_sync_manifest: &FastSyncManifest,
+    ) -> Result<VerifiedEpochStates, Error> {
+        // Verify the epoch ending ledger infos and the waypoint
+        let latest_epoch_state = utils::fetch_latest_epoch_state(self.storage.clone())?;
+        let mut verified_epoch_states = VerifiedEpochStates::new(latest_epoch_state);
+        let waypoint = &self.driver_configuration.waypoint;
+        let latest_ledger_info = utils::fetch_latest_synced_ledger_info(self.storage.clone())?;
+        if latest_ledger_info.ledger_info().version() >= waypoint.version() {
+            verified_epoch_states.set_verified_waypoint(waypoint.version());
+        }
+        for epoch_ending_ledger_info in &fast_sync_manifest.epoch_ending_ledger_infos {
+            verified_epoch_states
+                .update_verified_epoch_states(epoch_ending_ledger_info, waypoint)?;
+        }
+        if !verified_epoch_states.verified_waypoint() {
+            return Err(Error::VerificationError(
+                "The fast sync manifest does not verify the waypoint!".into(),
+            ));
+        }
+
+        // Verify the target is the highest known ledger info
+        let highest_known_ledger_info = verified_epoch_states
+            .get_highest_known_ledger_info()?
+            .unwrap_or(latest_ledger_info);
+        let target_ledger_info = &fast_sync_manifest.target_ledger_info;
+        if &highest_known_ledger_info != target_ledger_info {
+            return Err(Error::VerificationError(format!(
+                "The fast sync target is not the highest known ledger info! Target: {:?}, highest known: {:?}",
+                target_ledger_info, highest_known_ledger_info
+            )));
+        }
+
+        // Verify the transaction output to sync
+        let target_version = target_ledger_info.ledger_info().version();
+        fast_sync_manifest
+            .transaction_output_to_sync
+            .verify(target_ledger_info.ledger_info(), Some(target_version))
+            .map_err(|error| {
+                Error::VerificationError(format!(
+                    "Transaction outputs with proof is invalid! Error: {:?}",
+                    error
+                ))
+            })?;
+
+        Ok(verified_epoch_states)
+    }
+
+    /// Persists the fast sync manifest (so that the fast sync can resume
+    /// from the verified target if the node restarts).
+    fn persist_fast_sync_manifest(
+        &self,
+        target_ledger_info: LedgerInfoWithSignatures,
+        transaction_output_to_sync: TransactionOutputListWithProof,
+    ) -> Result<(), Error> {
+        let epoch_ending_ledger_infos =
+            if target_ledger_info.ledger_info().version() == GENESIS_TRANSACTION_VERSION {
+                vec![] // The target is the genesis ledger info in storage
+            } else {
+                self.verified_epoch_states.all_epoch_ending_ledger_infos()
+            };
+        self.metadata_storage
+            .update_fast_sync_manifest(FastSyncManifest {
+                target_ledger_info,
+                epoch_ending_ledger_infos,
+                transaction_output_to_sync,
+            })
+    }
+
     /// Fetches all missing state snapshot data in order to bootstrap the node
     async fn fetch_missing_state_snapshot_data(
         &mut self,
@@ -1279,7 +1408,13 @@ impl<
                 ) {
                     Ok(()) => {
                         self.state_value_syncer
-                            .set_transaction_output_to_sync(transaction_outputs_with_proof);
+                            .set_transaction_output_to_sync(transaction_outputs_with_proof.clone());
+
+                        // Persist the manifest so that we can resume after a restart
+                        self.persist_fast_sync_manifest(
+                            ledger_info_to_sync,
+                            transaction_outputs_with_proof,
+                        )?;
                     },
                     Err(error) => {
                         self.reset_active_stream(Some(NotificationAndFeedback::new(
@@ -162,6 +162,7 @@ impl DriverFactory {
             node_config.state_sync.state_sync_driver,
             node_config.base.role,
             waypoint,
+            node_config.consensus_observer.observer_enabled,
         );
 
         // Create the state sync driver
@@ -12,7 +12,9 @@ use aptos_schemadb::{
     schema::{KeyCodec, ValueCodec},
     ColumnFamilyName, Options, SchemaBatch, DB,
 };
-use aptos_types::ledger_info::LedgerInfoWithSignatures;
+use aptos_types::{
+    ledger_info::LedgerInfoWithSignatures, transaction::TransactionOutputListWithProof,
+};
 use serde::{Deserialize, Serialize};
 use std::{path::Path, sync::Arc, time::Instant};
 
@@ -47,6 +49,14 @@ pub trait MetadataStorageInterface {
         last_persisted_state_value_index: u64,
         snapshot_sync_completed: bool,
     ) -> Result<(), Error>;
+
+    /// Returns the manifest of any fast sync that has previously verified its
+    /// target. If no such fast sync exists, None is returned.
+    fn get_fast_sync_manifest(&self) -> Result<Option<FastSyncManifest>, Error>;
+
+    /// Persists the manifest of the fast sync. This allows the fast sync to
+    /// resume (without refetching the manifest data) if the node restarts.
+    fn update_fast_sync_manifest(&self, fast_sync_manifest: FastSyncManifest) -> Result<(), Error>;
 }
 
 /// The name of the state sync db file
@@ -106,10 +116,13 @@ impl PersistentMetadataStorage {
                     ))
                 })?;
         match maybe_metadata_value {
-            Some(metadata_value) => {
-                let MetadataValue::StateSnapshotSync(snapshot_progress) = metadata_value;
+            Some(MetadataValue::StateSnapshotSync(snapshot_progress)) => {
                 Ok(Some(snapshot_progress))
             },
+            Some(metadata_value) => Err(Error::StorageError(format!(
+                "Unexpected metadata value found for key: {:?}. Value: {:?}",
+                metadata_key, metadata_value
+            ))),
             None => Ok(None),
         }
     }
@@ -137,6 +150,30 @@ impl PersistentMetadataStorage {
         }
     }
 
+    /// Returns the existing fast sync manifest. Returns None if no manifest is found.
+    fn read_fast_sync_manifest(&self) -> Result<Option<FastSyncManifest>, Error> {
+        let metadata_key = MetadataKey::FastSyncManifest;
+        let maybe_metadata_value =
+            self.database
+                .get::<MetadataSchema>(&metadata_key)
+                .map_err(|error| {
+                    Error::StorageError(format!(
+                        "Failed to read metadata value for key: {:?}. Error: {:?}",
+                        metadata_key, error
+                    ))
+                })?;
+        match maybe_metadata_value {
+            Some(MetadataValue::FastSyncManifest(fast_sync_manifest)) => {
+                Ok(Some(fast_sync_manifest))
+            },
+            Some(metadata_value) => Err(Error::StorageError(format!(
+                "Unexpected metadata value found for key: {:?}. Value: {:?}",
+                metadata_key, metadata_value
+            ))),
+            None => Ok(None),
+        }
+    }
+
     /// Write the key value pair to the database
     fn commit_key_value(
         &self,
@@ -224,6 +261,27 @@ impl MetadataStorageInterface for PersistentMetadataStorage {
         // Insert the new key/value pair
         self.commit_key_value(metadata_key, metadata_value)
     }
+
+    fn get_fast_sync_manifest(&self) -> Result<Option<FastSyncManifest>, Error> {
+        self.read_fast_sync_manifest()
+    }
+
+    fn update_fast_sync_manifest(&self, fast_sync_manifest: FastSyncManifest) -> Result<(), Error> {
+        // Ensure that if any previous snapshot progress exists, it has the same target
+        if let Some(snapshot_progress) = self.get_snapshot_progress()? {
+            if fast_sync_manifest.target_ledger_info != snapshot_progress.target_ledger_info {
+                return Err(Error::StorageError(format!("Failed to update the fast sync manifest! \
+                The given target does not match the previously stored snapshot target. Given target: {:?}, stored target: {:?}",
+                    fast_sync_manifest.target_ledger_info, snapshot_progress.target_ledger_info
+                )));
+            }
+        }
+
+        // Insert the new key/value pair
+        let metadata_key = MetadataKey::FastSyncManifest;
+        let metadata_value = MetadataValue::FastSyncManifest(fast_sync_manifest);
+        self.commit_key_value(metadata_key, metadata_value)
+    }
 }
 
 /// A simple struct for recording the progress of a state snapshot sync
@@ -234,6 +292,16 @@ pub struct StateSnapshotProgress {
     pub snapshot_sync_completed: bool,
 }
 
+/// A manifest of the data verified by a fast sync before it starts syncing
+/// state values (i.e., the target ledger info, the epoch ending ledger infos
+/// that prove the target, and the transaction output at the target).
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct FastSyncManifest {
+    pub target_ledger_info: LedgerInfoWithSignatures,
+    pub epoch_ending_ledger_infos: Vec<LedgerInfoWithSignatures>,
+    pub transaction_output_to_sync: TransactionOutputListWithProof,
+}
+
 /// The raw schema format used by the database
 pub mod database_schema {
     use super::*;
@@ -252,6 +320,7 @@ pub mod database_schema {
     #[repr(u8)]
     pub enum MetadataKey {
         StateSnapshotSync, // A state snapshot sync that was started
+        FastSyncManifest,  // A fast sync that has verified its target
     }
 
     /// A metadata value that can be inserted into the database
@@ -259,6 +328,7 @@ pub mod database_schema {
     #[repr(u8)]
     pub enum MetadataValue {
         StateSnapshotSync(StateSnapshotProgress), // A state snapshot sync progress marker
+        FastSyncManifest(FastSyncManifest),       // A fast sync manifest
     }
 
     impl KeyCodec<MetadataSchema> for MetadataKey {
@@ -15,6 +15,7 @@ pub const STORAGE_SYNCHRONIZER_PENDING_DATA: &str = "storage_synchronizer_pendin
 pub const STORAGE_SYNCHRONIZER_APPLY_CHUNK: &str = "apply_chunk";
 pub const STORAGE_SYNCHRONIZER_EXECUTE_CHUNK: &str = "execute_chunk";
 pub const STORAGE_SYNCHRONIZER_UPDATE_LEDGER: &str = "update_ledger";
+pub const STORAGE_SYNCHRONIZER_VERIFY_CHUNK: &str = "verify_chunk";
 pub const STORAGE_SYNCHRONIZER_COMMIT_CHUNK: &str = "commit_chunk";
 
 /// An enum representing the component currently executing
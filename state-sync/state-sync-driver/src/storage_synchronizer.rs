@@ -205,11 +205,23 @@ impl<
         // Create a shared pending data chunk counter
         let pending_data_chunks = Arc::new(AtomicU64::new(0));
 
+        // Create a thread pool to verify the proofs of output chunks
+        let num_proof_verification_workers = driver_config.num_proof_verification_workers as usize;
+        let proof_verification_pool = Arc::new(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(num_proof_verification_workers)
+                .thread_name(|index| format!("state-sync-proof-verifier-{}", index))
+                .build()
+                .expect("Failed to create the proof verification pool!"),
+        );
+
         // Spawn the executor that executes/applies storage data chunks
         let runtime = runtime.map(|runtime| runtime.handle().clone());
         let executor_handle = spawn_executor(
             chunk_executor.clone(),
             error_notification_sender.clone(),
+            proof_verification_pool,
+            num_proof_verification_workers,
             executor_listener,
             ledger_updater_notifier,
             pending_data_chunks.clone(),
@@ -405,18 +417,34 @@ enum StorageDataChunk {
     ),
 }
 
-/// Spawns a dedicated executor that executes/applies storage data chunks
+/// Spawns a dedicated executor that executes/applies storage data chunks.
+/// The proofs of the output chunks are verified ahead of time (in parallel)
+/// on the proof verification pool, while the chunks are applied in order.
 fn spawn_executor<ChunkExecutor: ChunkExecutorTrait + 'static>(
     chunk_executor: Arc<ChunkExecutor>,
     error_notification_sender: mpsc::UnboundedSender<ErrorNotification>,
-    mut executor_listener: mpsc::Receiver<StorageDataChunk>,
+    proof_verification_pool: Arc<rayon::ThreadPool>,
+    num_proof_verification_workers: usize,
+    executor_listener: mpsc::Receiver<StorageDataChunk>,
     mut ledger_updater_notifier: mpsc::Sender<NotificationId>,
     pending_data_chunks: Arc<AtomicU64>,
     runtime: Option<Handle>,
 ) -> JoinHandle<()> {
     // Create an executor
     let executor = async move {
-        while let Some(storage_data_chunk) = executor_listener.next().await {
+        let verifier_chunk_executor = chunk_executor.clone();
+        let mut verified_chunk_listener = executor_listener
+            .map(move |storage_data_chunk| {
+                verify_storage_data_chunk(
+                    verifier_chunk_executor.clone(),
+                    proof_verification_pool.clone(),
+                    storage_data_chunk,
+                )
+            })
+            .buffered(num_proof_verification_workers);
+        while let Some((storage_data_chunk, verification_result)) =
+            verified_chunk_listener.next().await
+        {
             // Execute/apply the storage data chunk
             let (notification_id, result, executed_chunk) = match storage_data_chunk {
                 StorageDataChunk::Transactions(
@@ -472,13 +500,18 @@ fn spawn_executor<ChunkExecutor: ChunkExecutorTrait + 'static>(
                         metrics::STORAGE_SYNCHRONIZER_APPLY_CHUNK,
                     );
                     let num_outputs = outputs_with_proof.transactions_and_outputs.len();
-                    let result = apply_output_chunk(
-                        chunk_executor.clone(),
-                        outputs_with_proof,
-                        target_ledger_info,
-                        end_of_epoch_ledger_info,
-                    )
-                    .await;
+                    let result = match verification_result {
+                        Ok(()) => {
+                            apply_output_chunk(
+                                chunk_executor.clone(),
+                                outputs_with_proof,
+                                target_ledger_info,
+                                end_of_epoch_ledger_info,
+                            )
+                            .await
+                        },
+                        Err(error) => Err(error),
+                    };
                     if result.is_ok() {
                         info!(
                             LogSchema::new(LogEntry::StorageSynchronizer).message(&format!(
@@ -840,9 +873,48 @@ fn spawn_state_snapshot_receiver<
     spawn(runtime, receiver)
 }
 
-/// Spawns a dedicated task that applies the given output chunk. We use
+/// Spawns a dedicated task that verifies the proofs of the given storage
+/// data chunk (if it's an output chunk) on the proof verification pool,
+/// and returns the chunk alongside the verification result. We use
 /// `spawn_blocking` so that the heavy synchronous function doesn't
 /// block the async thread.
+async fn verify_storage_data_chunk<ChunkExecutor: ChunkExecutorTrait + 'static>(
+    chunk_executor: Arc<ChunkExecutor>,
+    proof_verification_pool: Arc<rayon::ThreadPool>,
+    storage_data_chunk: StorageDataChunk,
+) -> (StorageDataChunk, anyhow::Result<()>) {
+    match storage_data_chunk {
+        StorageDataChunk::TransactionOutputs(
+            notification_id,
+            outputs_with_proof,
+            target_ledger_info,
+            end_of_epoch_ledger_info,
+        ) => tokio::task::spawn_blocking(move || {
+            let _timer = metrics::start_timer(
+                &metrics::STORAGE_SYNCHRONIZER_LATENCIES,
+                metrics::STORAGE_SYNCHRONIZER_VERIFY_CHUNK,
+            );
+            let result = proof_verification_pool.install(|| {
+                chunk_executor
+                    .verify_chunk_by_transaction_outputs(&outputs_with_proof, &target_ledger_info)
+            });
+            let storage_data_chunk = StorageDataChunk::TransactionOutputs(
+                notification_id,
+                outputs_with_proof,
+                target_ledger_info,
+                end_of_epoch_ledger_info,
+            );
+            (storage_data_chunk, result)
+        })
+        .await
+        .expect("Spawn_blocking(verify_storage_data_chunk) failed!"),
+        storage_data_chunk => (storage_data_chunk, Ok(())),
+    }
+}
+
+/// Spawns a dedicated task that applies the given (verified) output chunk.
+/// We use `spawn_blocking` so that the heavy synchronous function doesn't
+/// block the async thread.
 async fn apply_output_chunk<ChunkExecutor: ChunkExecutorTrait + 'static>(
     chunk_executor: Arc<ChunkExecutor>,
     outputs_with_proof: TransactionOutputListWithProof,
@@ -850,7 +922,7 @@ async fn apply_output_chunk<ChunkExecutor: ChunkExecutorTrait + 'static>(
     end_of_epoch_ledger_info: Option<LedgerInfoWithSignatures>,
 ) -> anyhow::Result<()> {
     tokio::task::spawn_blocking(move || {
-        chunk_executor.enqueue_chunk_by_transaction_outputs(
+        chunk_executor.enqueue_verified_chunk_by_transaction_outputs(
             outputs_with_proof,
             &target_ledger_info,
             end_of_epoch_ledger_info.as_ref(),
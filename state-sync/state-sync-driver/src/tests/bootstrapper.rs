@@ -5,6 +5,7 @@ use crate::{
     bootstrapper::{Bootstrapper, GENESIS_TRANSACTION_VERSION},
     driver::DriverConfiguration,
     error::Error,
+    metadata_storage::FastSyncManifest,
     tests::{
         mocks::{
             create_mock_db_reader, create_mock_streaming_client, create_ready_storage_synchronizer,
@@ -739,6 +740,9 @@ async fn test_snapshot_sync_epoch_change_genesis() {
     metadata_storage
         .expect_previous_snapshot_sync_target()
         .returning(move || Ok(None));
+    metadata_storage
+        .expect_get_fast_sync_manifest()
+        .returning(|| Ok(None));
 
     // Create the bootstrapper
     let mut bootstrapper = create_bootstrapper_with_storage(
@@ -805,6 +809,9 @@ async fn test_snapshot_sync_epoch_change_genesis_restart() {
     metadata_storage
         .expect_get_last_persisted_state_value_index()
         .returning(move |_| Ok(last_persisted_index_clone));
+    metadata_storage
+        .expect_get_fast_sync_manifest()
+        .returning(|| Ok(None));
 
     // Create the bootstrapper
     let mut bootstrapper = create_bootstrapper_with_storage(
@@ -991,6 +998,66 @@ async fn test_snapshot_sync_fresh_state() {
         .unwrap();
 }
 
+#[tokio::test]
+async fn test_snapshot_sync_invalid_manifest() {
+    // Create test data
+    let synced_version = GENESIS_TRANSACTION_VERSION; // Genesis is the highest synced
+    let target_version = 1000;
+    let target_ledger_info = create_random_epoch_ending_ledger_info(target_version, 0);
+
+    // Create a driver configuration with a genesis waypoint and state syncing
+    let mut driver_configuration = create_full_node_driver_configuration();
+    driver_configuration.config.bootstrapping_mode = BootstrappingMode::DownloadLatestStates;
+    driver_configuration.config.max_stream_wait_time_ms = 1000;
+
+    // Create the mock streaming client and expect the epoch ending
+    // ledger infos to be fetched again (the manifest is ignored).
+    let mut mock_streaming_client = create_mock_streaming_client();
+    let (_notification_sender, data_stream_listener) = create_data_stream_listener();
+    mock_streaming_client
+        .expect_get_all_epoch_ending_ledger_infos()
+        .times(1)
+        .with(eq(1))
+        .return_once(move |_| Ok(data_stream_listener));
+
+    // Create the mock metadata storage with a manifest that contains an
+    // invalid transaction output.
+    let mut metadata_storage = MockMetadataStorage::new();
+    metadata_storage
+        .expect_get_fast_sync_manifest()
+        .times(1)
+        .return_once(move || {
+            Ok(Some(FastSyncManifest {
+                target_ledger_info: target_ledger_info.clone(),
+                epoch_ending_ledger_infos: vec![target_ledger_info],
+                transaction_output_to_sync: create_output_list_with_proof(),
+            }))
+        });
+
+    // Create the bootstrapper
+    let mut bootstrapper = create_bootstrapper_with_storage(
+        driver_configuration,
+        mock_streaming_client,
+        metadata_storage,
+        synced_version,
+        true,
+    );
+
+    // Create a global data summary where epoch 0 and 1 have ended
+    let global_data_summary = create_global_summary(1);
+
+    // Drive progress to initialize the epoch ending data stream
+    drive_progress(&mut bootstrapper, &global_data_summary, false)
+        .await
+        .unwrap();
+
+    // Drive progress again and verify we're still waiting for epoch ending ledger infos
+    let error = drive_progress(&mut bootstrapper, &global_data_summary, false)
+        .await
+        .unwrap_err();
+    assert_matches!(error, Error::DataStreamNotificationTimeout(_));
+}
+
 #[tokio::test]
 #[should_panic(
     expected = "The snapshot sync for the target was marked as complete but the highest synced version is genesis!"
@@ -1290,6 +1357,9 @@ fn create_bootstrapper(
     metadata_storage
         .expect_previous_snapshot_sync_target()
         .returning(|| Ok(None));
+    metadata_storage
+        .expect_get_fast_sync_manifest()
+        .returning(|| Ok(None));
 
     // Create the mock db reader with only genesis loaded
     let mut mock_database_reader = create_mock_db_reader();
@@ -5,7 +5,8 @@ use crate::{driver_factory::DriverFactory, metadata_storage::PersistentMetadataS
 use aptos_config::{
     config::{
         RocksdbConfigs, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
-        DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
+        DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_LEDGER_TIERING_CONFIG,
+        NO_OP_STORAGE_PRUNER_CONFIG,
     },
     utils::get_genesis_txn,
 };
@@ -36,8 +37,10 @@ fn test_new_initialized_configs() {
         StorageDirPaths::from_path(&tmp_dir),
         false,
         NO_OP_STORAGE_PRUNER_CONFIG,
+        &NO_OP_LEDGER_TIERING_CONFIG,
         RocksdbConfigs::default(),
         false,
+        false,
         BUFFERED_STATE_TARGET_ITEMS,
         DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
     )
@@ -4,9 +4,13 @@
 use crate::{
     metadata_storage::{
         database_schema::{MetadataKey, MetadataSchema, MetadataValue},
-        MetadataStorageInterface, PersistentMetadataStorage, StateSnapshotProgress,
+        FastSyncManifest, MetadataStorageInterface, PersistentMetadataStorage,
+        StateSnapshotProgress,
+    },
+    tests::utils::{
+        create_epoch_ending_ledger_info, create_ledger_info_at_version,
+        create_output_list_with_proof,
     },
-    tests::utils::{create_epoch_ending_ledger_info, create_ledger_info_at_version},
 };
 use aptos_schemadb::schema::fuzzing::assert_encode_decode;
 use aptos_temppath::TempPath;
@@ -89,6 +93,58 @@ fn test_create_then_open() {
     );
 }
 
+#[test]
+fn test_fast_sync_manifest() {
+    // Create a new metadata storage
+    let tmp_dir = TempPath::new();
+    let metadata_storage = PersistentMetadataStorage::new(tmp_dir.path());
+
+    // Verify the storage is empty
+    assert_none!(metadata_storage.get_fast_sync_manifest().unwrap());
+
+    // Insert a fast sync manifest
+    let target_ledger_info = create_ledger_info_at_version(12345);
+    let fast_sync_manifest = FastSyncManifest {
+        target_ledger_info: target_ledger_info.clone(),
+        epoch_ending_ledger_infos: vec![
+            create_ledger_info_at_version(100),
+            target_ledger_info.clone(),
+        ],
+        transaction_output_to_sync: create_output_list_with_proof(),
+    };
+    metadata_storage
+        .update_fast_sync_manifest(fast_sync_manifest.clone())
+        .unwrap();
+
+    // Insert a state value entry for the target
+    metadata_storage
+        .update_last_persisted_state_value_index(&target_ledger_info, 1000, false)
+        .unwrap();
+
+    // Drop the handle to the storage (mimic a reboot)
+    drop(metadata_storage);
+
+    // Create another storage (it should reopen the existing file) and verify the manifest
+    let metadata_storage = PersistentMetadataStorage::new(tmp_dir.path());
+    assert_eq!(
+        Some(fast_sync_manifest.clone()),
+        metadata_storage.get_fast_sync_manifest().unwrap()
+    );
+    assert_eq!(
+        Some(target_ledger_info),
+        metadata_storage.previous_snapshot_sync_target().unwrap()
+    );
+
+    // Verify a manifest with a different target can't be inserted
+    let mut invalid_manifest = fast_sync_manifest.clone();
+    invalid_manifest.target_ledger_info = create_ledger_info_at_version(99999);
+    assert_err!(metadata_storage.update_fast_sync_manifest(invalid_manifest));
+    assert_eq!(
+        Some(fast_sync_manifest),
+        metadata_storage.get_fast_sync_manifest().unwrap()
+    );
+}
+
 #[test]
 fn test_metadata_schema_encode_decode() {
     assert_encode_decode::<MetadataSchema>(
@@ -99,6 +155,14 @@ fn test_metadata_schema_encode_decode() {
             snapshot_sync_completed: false,
         }),
     );
+    assert_encode_decode::<MetadataSchema>(
+        &MetadataKey::FastSyncManifest,
+        &MetadataValue::FastSyncManifest(FastSyncManifest {
+            target_ledger_info: create_epoch_ending_ledger_info(),
+            epoch_ending_ledger_infos: vec![create_epoch_ending_ledger_info()],
+            transaction_output_to_sync: create_output_list_with_proof(),
+        }),
+    );
 }
 
 #[test]
@@ -3,7 +3,7 @@
 
 use crate::{
     error::Error,
-    metadata_storage::MetadataStorageInterface,
+    metadata_storage::{FastSyncManifest, MetadataStorageInterface},
     storage_synchronizer::StorageSynchronizerInterface,
     tests::utils::{create_empty_epoch_state, create_epoch_ending_ledger_info},
 };
@@ -163,6 +163,19 @@ mock! {
             epoch_change_li: Option<&'a LedgerInfoWithSignatures>,
         ) -> Result<()>;
 
+        fn verify_chunk_by_transaction_outputs(
+            &self,
+            txn_output_list_with_proof: &TransactionOutputListWithProof,
+            verified_target_li: &LedgerInfoWithSignatures,
+        ) -> Result<()>;
+
+        fn enqueue_verified_chunk_by_transaction_outputs<'a>(
+            &self,
+            verified_txn_output_list_with_proof: TransactionOutputListWithProof,
+            verified_target_li: &LedgerInfoWithSignatures,
+            epoch_change_li: Option<&'a LedgerInfoWithSignatures>,
+        ) -> Result<()>;
+
         fn update_ledger(&self) -> Result<()>;
 
         fn commit_chunk(&self) -> Result<ChunkCommitNotification>;
@@ -353,6 +366,13 @@ mock! {
             last_persisted_state_value_index: u64,
             snapshot_sync_completed: bool,
         ) -> Result<(), Error>;
+
+        fn get_fast_sync_manifest(&self) -> Result<Option<FastSyncManifest>, Error>;
+
+        fn update_fast_sync_manifest(
+            &self,
+            fast_sync_manifest: FastSyncManifest,
+        ) -> Result<(), Error>;
     }
 
     impl Clone for MetadataStorage {
@@ -49,7 +49,11 @@ async fn test_apply_transaction_outputs() {
     // Setup the mock executor
     let mut chunk_executor = create_mock_executor();
     chunk_executor
-        .expect_enqueue_chunk_by_transaction_outputs()
+        .expect_verify_chunk_by_transaction_outputs()
+        .with(always(), always())
+        .returning(|_, _| Ok(()));
+    chunk_executor
+        .expect_enqueue_verified_chunk_by_transaction_outputs()
         .with(always(), always(), always())
         .returning(|_, _, _| Ok(()));
     chunk_executor.expect_update_ledger().returning(|| Ok(()));
@@ -117,7 +121,11 @@ async fn test_apply_transaction_outputs_error() {
     // Setup the mock executor
     let mut chunk_executor = create_mock_executor();
     chunk_executor
-        .expect_enqueue_chunk_by_transaction_outputs()
+        .expect_verify_chunk_by_transaction_outputs()
+        .with(always(), always())
+        .returning(|_, _| Ok(()));
+    chunk_executor
+        .expect_enqueue_verified_chunk_by_transaction_outputs()
         .with(always(), always(), always())
         .returning(|_, _, _| Err(format_err!("Failed to apply chunk!")));
 
@@ -142,12 +150,49 @@ async fn test_apply_transaction_outputs_error() {
     verify_no_pending_data(&storage_synchronizer);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_apply_transaction_outputs_verification_error() {
+    // Setup the mock executor
+    let mut chunk_executor = create_mock_executor();
+    chunk_executor
+        .expect_verify_chunk_by_transaction_outputs()
+        .with(always(), always())
+        .returning(|_, _| Err(format_err!("Failed to verify chunk!")));
+    chunk_executor
+        .expect_enqueue_verified_chunk_by_transaction_outputs()
+        .never();
+
+    // Create the storage synchronizer
+    let (_, mut error_listener, _, _, _, mut storage_synchronizer, _, _, _) =
+        create_storage_synchronizer(chunk_executor, create_mock_reader_writer(None, None));
+
+    // Attempt to apply a chunk of outputs
+    let notification_id = 100;
+    storage_synchronizer
+        .apply_transaction_outputs(
+            notification_id,
+            create_output_list_with_proof(),
+            create_epoch_ending_ledger_info(),
+            None,
+        )
+        .await
+        .unwrap();
+
+    // Verify we get an error notification and that there's no pending data
+    verify_error_notification(&mut error_listener, notification_id).await;
+    verify_no_pending_data(&storage_synchronizer);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_apply_transaction_outputs_send_error() {
     // Setup the mock executor
     let mut chunk_executor = create_mock_executor();
     chunk_executor
-        .expect_enqueue_chunk_by_transaction_outputs()
+        .expect_verify_chunk_by_transaction_outputs()
+        .with(always(), always())
+        .returning(|_, _| Ok(()));
+    chunk_executor
+        .expect_enqueue_verified_chunk_by_transaction_outputs()
         .with(always(), always(), always())
         .returning(|_, _, _| Ok(()));
 
@@ -180,7 +225,11 @@ async fn test_apply_transaction_outputs_update_error() {
     // Setup the mock executor
     let mut chunk_executor = create_mock_executor();
     chunk_executor
-        .expect_enqueue_chunk_by_transaction_outputs()
+        .expect_verify_chunk_by_transaction_outputs()
+        .with(always(), always())
+        .returning(|_, _| Ok(()));
+    chunk_executor
+        .expect_enqueue_verified_chunk_by_transaction_outputs()
         .with(always(), always(), always())
         .returning(|_, _, _| Ok(()));
     chunk_executor
@@ -247,7 +296,11 @@ async fn test_commit_chunk_error_output_application() {
     // Setup the mock executor
     let mut chunk_executor = create_mock_executor();
     chunk_executor
-        .expect_enqueue_chunk_by_transaction_outputs()
+        .expect_verify_chunk_by_transaction_outputs()
+        .with(always(), always())
+        .returning(|_, _| Ok(()));
+    chunk_executor
+        .expect_enqueue_verified_chunk_by_transaction_outputs()
         .with(always(), always(), always())
         .returning(|_, _, _| Ok(()));
     chunk_executor.expect_update_ledger().returning(|| Ok(()));
@@ -281,7 +334,11 @@ async fn test_commit_chunk_apply_send_error() {
     // Setup the mock executor
     let mut chunk_executor = create_mock_executor();
     chunk_executor
-        .expect_enqueue_chunk_by_transaction_outputs()
+        .expect_verify_chunk_by_transaction_outputs()
+        .with(always(), always())
+        .returning(|_, _| Ok(()));
+    chunk_executor
+        .expect_enqueue_verified_chunk_by_transaction_outputs()
         .with(always(), always(), always())
         .returning(|_, _, _| Ok(()));
     chunk_executor.expect_update_ledger().returning(|| Ok(()));
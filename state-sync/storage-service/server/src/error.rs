@@ -12,6 +12,8 @@ pub enum Error {
     StorageErrorEncountered(String),
     #[error("Too many invalid requests: {0}")]
     TooManyInvalidRequests(String),
+    #[error("Too many requests: {0}")]
+    TooManyRequests(String),
     #[error("Unexpected error encountered: {0}")]
     UnexpectedErrorEncountered(String),
 }
@@ -23,6 +25,7 @@ impl Error {
             Error::InvalidRequest(_) => "invalid_request",
             Error::StorageErrorEncountered(_) => "storage_error",
             Error::TooManyInvalidRequests(_) => "too_many_invalid_requests",
+            Error::TooManyRequests(_) => "too_many_requests",
             Error::UnexpectedErrorEncountered(_) => "unexpected_error",
         }
     }
@@ -12,6 +12,7 @@ use crate::{
     moderator::RequestModerator,
     network::ResponseSender,
     optimistic_fetch::OptimisticFetchRequest,
+    scheduler::RequestScheduler,
     storage::StorageReaderInterface,
     subscription::{SubscriptionRequest, SubscriptionStreamRequests},
 };
@@ -83,6 +84,7 @@ impl<T: StorageReaderInterface> Handler<T> {
         peer_network_id: PeerNetworkId,
         request: StorageServiceRequest,
         response_sender: ResponseSender,
+        request_scheduler: Arc<RequestScheduler>,
     ) {
         // Update the request count
         increment_counter(
@@ -108,8 +110,52 @@ impl<T: StorageReaderInterface> Handler<T> {
             return;
         }
 
+        // Ensure the peer hasn't exceeded its fair share of the serving capacity
+        let request_permit = match request_scheduler.try_acquire_permit(&peer_network_id) {
+            Ok(request_permit) => request_permit,
+            Err(error) => {
+                self.handle_throttled_request(peer_network_id, request, error, response_sender);
+                return;
+            },
+        };
+
         // Process the request and return the response to the client
         let response = self.process_request(&peer_network_id, request.clone(), false);
+        if let Ok(response) = &response {
+            if let Ok(num_bytes) = response.get_num_bytes() {
+                request_permit.record_bytes_sent(num_bytes);
+            }
+        }
+        self.send_response(request, response, response_sender);
+    }
+
+    /// Handles a request that was throttled by the request scheduler
+    fn handle_throttled_request(
+        &self,
+        peer_network_id: PeerNetworkId,
+        request: StorageServiceRequest,
+        error: Error,
+        response_sender: ResponseSender,
+    ) {
+        // Update the error counter
+        increment_counter(
+            &metrics::STORAGE_ERRORS_ENCOUNTERED,
+            peer_network_id.network_id(),
+            error.get_label().into(),
+        );
+
+        // Periodically log the throttled request
+        sample!(
+            SampleRate::Duration(Duration::from_secs(INVALID_REQUEST_LOG_FREQUENCY_SECS)),
+            warn!(LogSchema::new(LogEntry::StorageServiceError)
+                .error(&error)
+                .peer_network_id(&peer_network_id)
+                .request(&request)
+                .message("Throttled the storage request!"));
+        );
+
+        // Notify the client of the error
+        let response = Err(StorageServiceError::InternalError(error.to_string()));
         self.send_response(request, response, response_sender);
     }
 
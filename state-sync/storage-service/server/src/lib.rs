@@ -31,6 +31,7 @@ use handler::Handler;
 use mini_moka::sync::Cache;
 use moderator::RequestModerator;
 use optimistic_fetch::OptimisticFetchRequest;
+use scheduler::RequestScheduler;
 use std::{ops::Deref, sync::Arc, time::Duration};
 use storage::StorageReaderInterface;
 use thiserror::Error;
@@ -43,6 +44,7 @@ pub mod metrics;
 mod moderator;
 pub mod network;
 mod optimistic_fetch;
+mod scheduler;
 pub mod storage;
 mod subscription;
 mod utils;
@@ -83,6 +85,9 @@ pub struct StorageServiceServer<T> {
     // A moderator for incoming peer requests
     request_moderator: Arc<RequestModerator>,
 
+    // A scheduler that enforces per-peer fairness when serving requests
+    request_scheduler: Arc<RequestScheduler>,
+
     // The listener for notifications from state sync
     storage_service_listener: Option<StorageServiceNotificationListener>,
 }
@@ -118,6 +123,10 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
             storage_service_config,
             time_service.clone(),
         ));
+        let request_scheduler = Arc::new(RequestScheduler::new(
+            storage_service_config,
+            time_service.clone(),
+        ));
         let storage_service_listener = Some(storage_service_listener);
 
         Self {
@@ -131,6 +140,7 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
             optimistic_fetches,
             subscriptions,
             request_moderator,
+            request_scheduler,
             storage_service_listener,
         }
     }
@@ -418,6 +428,7 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
             let subscriptions = self.subscriptions.clone();
             let lru_response_cache = self.lru_response_cache.clone();
             let request_moderator = self.request_moderator.clone();
+            let request_scheduler = self.request_scheduler.clone();
             let time_service = self.time_service.clone();
             self.bounded_executor
                 .spawn_blocking(move || {
@@ -435,6 +446,7 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
                         peer_network_id,
                         storage_service_request,
                         network_request.response_sender,
+                        request_scheduler,
                     );
                 })
                 .await;
@@ -447,6 +459,12 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
         self.request_moderator.clone()
     }
 
+    #[cfg(test)]
+    /// Returns a copy of the request scheduler for test purposes
+    pub(crate) fn get_request_scheduler(&self) -> Arc<RequestScheduler> {
+        self.request_scheduler.clone()
+    }
+
     #[cfg(test)]
     /// Returns a copy of the active optimistic fetches for test purposes
     pub(crate) fn get_optimistic_fetches(
@@ -0,0 +1,180 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::Error;
+use aptos_config::{config::StorageServiceConfig, network_id::PeerNetworkId};
+use aptos_infallible::Mutex;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// The duration of each bandwidth accounting window
+const BANDWIDTH_WINDOW_DURATION: Duration = Duration::from_secs(1);
+
+/// The serving state shared by the scheduler and all outstanding permits
+#[derive(Debug)]
+struct SchedulerState {
+    bytes_sent_by_peer: HashMap<PeerNetworkId, u64>, // The bytes sent to each peer in the window
+    in_flight_requests: HashMap<PeerNetworkId, u64>, // The number of in-flight requests per peer
+    total_bytes_sent: u64, // The total bytes sent to all peers in the window
+    window_start_time: Instant, // The start time of the current bandwidth window
+}
+
+impl SchedulerState {
+    fn new(window_start_time: Instant) -> Self {
+        Self {
+            bytes_sent_by_peer: HashMap::new(),
+            in_flight_requests: HashMap::new(),
+            total_bytes_sent: 0,
+            window_start_time,
+        }
+    }
+
+    /// Starts a new bandwidth window if the current one has elapsed
+    fn maybe_reset_window(&mut self, now: Instant) {
+        if now.duration_since(self.window_start_time) >= BANDWIDTH_WINDOW_DURATION {
+            self.bytes_sent_by_peer.clear();
+            self.total_bytes_sent = 0;
+            self.window_start_time = now;
+        }
+    }
+
+    /// Returns the number of peers currently being served (i.e., peers
+    /// with in-flight requests or bytes sent in the current window).
+    fn get_num_active_peers(&self) -> u64 {
+        let num_other_peers = self
+            .in_flight_requests
+            .keys()
+            .filter(|peer| !self.bytes_sent_by_peer.contains_key(peer))
+            .count();
+        (self.bytes_sent_by_peer.len() + num_other_peers) as u64
+    }
+}
+
+/// The request scheduler enforces per-peer fairness when serving requests.
+/// Each peer is limited to a maximum number of concurrent requests, and
+/// when the total serving bandwidth is exhausted, peers that have already
+/// consumed more than their fair share of the bandwidth are throttled
+/// until the next window (allowing the other peers to make progress).
+pub struct RequestScheduler {
+    state: Arc<Mutex<SchedulerState>>,
+    storage_service_config: StorageServiceConfig,
+    time_service: TimeService,
+}
+
+impl RequestScheduler {
+    pub fn new(storage_service_config: StorageServiceConfig, time_service: TimeService) -> Self {
+        let state = Arc::new(Mutex::new(SchedulerState::new(time_service.now())));
+        Self {
+            state,
+            storage_service_config,
+            time_service,
+        }
+    }
+
+    /// Attempts to acquire a permit to serve a request for the given peer.
+    /// If the peer has too many in-flight requests, or has exceeded its
+    /// share of the exhausted serving bandwidth, an error is returned.
+    pub fn try_acquire_permit(
+        &self,
+        peer_network_id: &PeerNetworkId,
+    ) -> Result<RequestPermit, Error> {
+        let mut state = self.state.lock();
+        state.maybe_reset_window(self.time_service.now());
+
+        // Verify the peer doesn't have too many in-flight requests
+        let num_in_flight_requests = state
+            .in_flight_requests
+            .get(peer_network_id)
+            .copied()
+            .unwrap_or(0);
+        let max_concurrent_requests_per_peer =
+            self.storage_service_config.max_concurrent_requests_per_peer;
+        if num_in_flight_requests >= max_concurrent_requests_per_peer {
+            return Err(Error::TooManyRequests(format!(
+                "Peer {:?} has too many in-flight requests: {:?}, max: {:?}",
+                peer_network_id, num_in_flight_requests, max_concurrent_requests_per_peer
+            )));
+        }
+
+        // If the serving bandwidth is exhausted, verify the peer is still within its fair share
+        let max_serving_bytes_per_second = self.storage_service_config.max_serving_bytes_per_second;
+        if state.total_bytes_sent >= max_serving_bytes_per_second {
+            let bytes_sent_to_peer = state
+                .bytes_sent_by_peer
+                .get(peer_network_id)
+                .copied()
+                .unwrap_or(0);
+            let fair_share_bytes =
+                max_serving_bytes_per_second / state.get_num_active_peers().max(1);
+            if bytes_sent_to_peer >= fair_share_bytes {
+                return Err(Error::TooManyRequests(format!(
+                    "Peer {:?} has exceeded its share of the serving bandwidth! Bytes sent: {:?}, fair share: {:?}",
+                    peer_network_id, bytes_sent_to_peer, fair_share_bytes
+                )));
+            }
+        }
+
+        // Acquire the permit
+        *state
+            .in_flight_requests
+            .entry(*peer_network_id)
+            .or_insert(0) += 1;
+        Ok(RequestPermit {
+            peer_network_id: *peer_network_id,
+            state: self.state.clone(),
+            time_service: self.time_service.clone(),
+        })
+    }
+
+    /// Returns the number of in-flight requests for the given peer
+    #[cfg(test)]
+    pub(crate) fn get_num_in_flight_requests(&self, peer_network_id: &PeerNetworkId) -> u64 {
+        self.state
+            .lock()
+            .in_flight_requests
+            .get(peer_network_id)
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+/// A permit to serve a single request for a peer. The permit is
+/// released (i.e., the in-flight request count is decremented) on drop.
+#[derive(Debug)]
+pub struct RequestPermit {
+    peer_network_id: PeerNetworkId,
+    state: Arc<Mutex<SchedulerState>>,
+    time_service: TimeService,
+}
+
+impl RequestPermit {
+    /// Records the number of bytes sent to the peer for the request
+    pub fn record_bytes_sent(&self, num_bytes: u64) {
+        let mut state = self.state.lock();
+        state.maybe_reset_window(self.time_service.now());
+
+        *state
+            .bytes_sent_by_peer
+            .entry(self.peer_network_id)
+            .or_insert(0) += num_bytes;
+        state.total_bytes_sent = state.total_bytes_sent.saturating_add(num_bytes);
+    }
+}
+
+impl Drop for RequestPermit {
+    fn drop(&mut self) {
+        let mut state = self.state.lock();
+        if let Some(num_in_flight_requests) =
+            state.in_flight_requests.get_mut(&self.peer_network_id)
+        {
+            *num_in_flight_requests = num_in_flight_requests.saturating_sub(1);
+            if *num_in_flight_requests == 0 {
+                state.in_flight_requests.remove(&self.peer_network_id);
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{error::Error, scheduler::RequestScheduler};
+use aptos_config::{
+    config::StorageServiceConfig,
+    network_id::{NetworkId, PeerNetworkId},
+};
+use aptos_time_service::TimeService;
+use aptos_types::PeerId;
+use claims::{assert_matches, assert_ok};
+use std::time::Duration;
+
+#[test]
+fn test_request_scheduler_concurrency_limit() {
+    // Create a request scheduler with a small per-peer concurrency limit
+    let max_concurrent_requests_per_peer = 5;
+    let storage_service_config = StorageServiceConfig {
+        max_concurrent_requests_per_peer,
+        ..Default::default()
+    };
+    let request_scheduler = RequestScheduler::new(storage_service_config, TimeService::mock());
+
+    // Acquire the maximum number of permits for a peer
+    let peer_network_id = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+    let mut request_permits = vec![];
+    for _ in 0..max_concurrent_requests_per_peer {
+        request_permits.push(assert_ok!(
+            request_scheduler.try_acquire_permit(&peer_network_id)
+        ));
+    }
+    assert_eq!(
+        request_scheduler.get_num_in_flight_requests(&peer_network_id),
+        max_concurrent_requests_per_peer
+    );
+
+    // Verify the peer can't acquire any more permits
+    assert_matches!(
+        request_scheduler.try_acquire_permit(&peer_network_id),
+        Err(Error::TooManyRequests(_))
+    );
+
+    // Verify another peer can still acquire permits
+    let other_peer_network_id = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+    let other_request_permit =
+        assert_ok!(request_scheduler.try_acquire_permit(&other_peer_network_id));
+
+    // Release one of the permits and verify the peer can acquire another permit
+    request_permits.pop();
+    assert_ok!(request_scheduler.try_acquire_permit(&peer_network_id));
+
+    // Release all the permits and verify the in-flight requests are cleared
+    request_permits.clear();
+    drop(other_request_permit);
+    assert_eq!(
+        request_scheduler.get_num_in_flight_requests(&peer_network_id),
+        0
+    );
+    assert_eq!(
+        request_scheduler.get_num_in_flight_requests(&other_peer_network_id),
+        0
+    );
+}
+
+#[test]
+fn test_request_scheduler_bandwidth_fairness() {
+    // Create a request scheduler with a small serving bandwidth
+    let max_serving_bytes_per_second = 1000;
+    let storage_service_config = StorageServiceConfig {
+        max_serving_bytes_per_second,
+        ..Default::default()
+    };
+    let time_service = TimeService::mock();
+    let request_scheduler = RequestScheduler::new(storage_service_config, time_service.clone());
+
+    // Have an aggressive peer and a regular peer send requests
+    let aggressive_peer = PeerNetworkId::new(NetworkId::Vfn, PeerId::random());
+    let regular_peer = PeerNetworkId::new(NetworkId::Vfn, PeerId::random());
+    let aggressive_permit = assert_ok!(request_scheduler.try_acquire_permit(&aggressive_peer));
+    let regular_permit = assert_ok!(request_scheduler.try_acquire_permit(&regular_peer));
+
+    // Have the aggressive peer consume most of the bandwidth
+    aggressive_permit.record_bytes_sent(max_serving_bytes_per_second - 100);
+    drop(aggressive_permit);
+
+    // Verify the aggressive peer isn't throttled (the bandwidth isn't exhausted)
+    let aggressive_permit = assert_ok!(request_scheduler.try_acquire_permit(&aggressive_peer));
+
+    // Exhaust the bandwidth and verify the aggressive peer is now throttled
+    aggressive_permit.record_bytes_sent(100);
+    drop(aggressive_permit);
+    assert_matches!(
+        request_scheduler.try_acquire_permit(&aggressive_peer),
+        Err(Error::TooManyRequests(_))
+    );
+
+    // Verify the regular peer (still within its fair share) isn't throttled
+    regular_permit.record_bytes_sent(100);
+    drop(regular_permit);
+    assert_ok!(request_scheduler.try_acquire_permit(&regular_peer));
+
+    // Elapse the bandwidth window and verify the aggressive peer is no longer throttled
+    time_service.into_mock().advance(Duration::from_secs(1));
+    assert_ok!(request_scheduler.try_acquire_permit(&aggressive_peer));
+}
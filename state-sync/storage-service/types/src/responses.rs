@@ -113,6 +113,21 @@ impl StorageServiceResponse {
     pub fn is_compressed(&self) -> bool {
         matches!(self, Self::CompressedResponse(_, _))
     }
+
+    /// Returns the size of the response data (in bytes), i.e.,
+    /// the compressed size if the data response is compressed.
+    pub fn get_num_bytes(&self) -> Result<u64, Error> {
+        match self {
+            StorageServiceResponse::CompressedResponse(_, compressed_data) => {
+                Ok(compressed_data.len() as u64)
+            },
+            StorageServiceResponse::RawResponse(data_response) => {
+                let num_bytes = bcs::serialized_size(data_response)
+                    .map_err(|error| Error::UnexpectedErrorEncountered(error.to_string()))?;
+                Ok(num_bytes as u64)
+            },
+        }
+    }
 }
 
 /// A useful type to hold optional transaction data
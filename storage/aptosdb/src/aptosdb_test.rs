@@ -4,19 +4,25 @@
 use crate::{
     get_first_seq_num_and_limit,
     pruner::{LedgerPrunerManager, StateMerklePrunerManager},
+    schema::{transaction::TransactionSchema, write_set::WriteSetSchema},
     test_helper,
     test_helper::{arb_blocks_to_commit, put_as_state_root, put_transaction_info},
     AptosDB, PrunerManager, StaleNodeIndexSchema,
 };
 use aptos_config::config::{
-    EpochSnapshotPrunerConfig, LedgerPrunerConfig, PrunerConfig, RocksdbConfigs,
-    StateMerklePrunerConfig, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
-    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    EpochSnapshotPrunerConfig, LedgerPrunerConfig, LedgerTieringConfig, PrunerConfig,
+    RocksdbConfigs, StateMerklePrunerConfig, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
+    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, MIN_EPOCH_SNAPSHOT_PRUNE_WINDOW,
+    MIN_LEDGER_PRUNE_WINDOW, NO_OP_LEDGER_TIERING_CONFIG, NO_OP_STORAGE_PRUNER_CONFIG,
 };
 use aptos_crypto::{hash::CryptoHash, HashValue};
-use aptos_storage_interface::{DbReader, ExecutedTrees, Order};
+use aptos_storage_interface::{
+    DbReader, DbWriter, ExecutedTrees, Order, PrunerType, MAX_REQUEST_LIMIT,
+};
 use aptos_temppath::TempPath;
 use aptos_types::{
+    account_address::AccountAddress,
+    contract_event::EventWithVersion,
     ledger_info::LedgerInfoWithSignatures,
     proof::SparseMerkleLeafNode,
     state_store::{
@@ -24,6 +30,7 @@ use aptos_types::{
     },
     transaction::{ExecutionStatus, TransactionInfo, TransactionToCommit, Version},
 };
+use move_core_types::language_storage::TypeTag;
 use proptest::prelude::*;
 use std::{collections::HashSet, sync::Arc};
 use test_helper::{test_save_blocks_impl, test_sync_transactions_impl};
@@ -84,6 +91,30 @@ fn test_too_many_requested() {
     assert!(db.get_transaction_outputs(0, 1001 /* limit */, 0).is_err());
 }
 
+#[test]
+fn test_get_account_transactions_in_time_range_invalid_args() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+
+    assert!(db
+        .get_account_transactions_in_time_range(
+            AccountAddress::ONE,
+            0,
+            1,
+            MAX_REQUEST_LIMIT + 1,
+            true,
+            0
+        )
+        .is_err());
+    assert!(db
+        .get_account_transactions_in_time_range(AccountAddress::ONE, 2, 1, 10, true, 0)
+        .is_err());
+    assert!(db
+        .get_account_transactions_in_time_range(AccountAddress::ONE, 0, 1, 10, true, 0)
+        .unwrap()
+        .is_empty());
+}
+
 #[test]
 fn test_pruner_config() {
     let tmp_dir = TempPath::new();
@@ -112,6 +143,65 @@ fn test_pruner_config() {
     }
 }
 
+#[test]
+fn test_update_pruner_config() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::open(
+        StorageDirPaths::from_path(tmp_dir),
+        /*readonly=*/ false,
+        PrunerConfig::default(),
+        &NO_OP_LEDGER_TIERING_CONFIG,
+        RocksdbConfigs::default(),
+        false, /* enable_indexer */
+        false, /* enable_event_type_index */
+        BUFFERED_STATE_TARGET_ITEMS,
+        DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    )
+    .unwrap();
+
+    // The ledger config applies to both the ledger and the state kv pruners
+    db.update_pruner_config(PrunerType::Ledger, MIN_LEDGER_PRUNE_WINDOW, 100)
+        .unwrap();
+    assert_eq!(
+        db.get_ledger_prune_window().unwrap() as u64,
+        MIN_LEDGER_PRUNE_WINDOW
+    );
+    assert_eq!(
+        db.state_store.state_kv_pruner.get_prune_window(),
+        MIN_LEDGER_PRUNE_WINDOW
+    );
+
+    db.update_pruner_config(
+        PrunerType::EpochSnapshot,
+        MIN_EPOCH_SNAPSHOT_PRUNE_WINDOW + 1,
+        1,
+    )
+    .unwrap();
+    assert_eq!(
+        db.get_epoch_snapshot_prune_window().unwrap() as u64,
+        MIN_EPOCH_SNAPSHOT_PRUNE_WINDOW + 1
+    );
+
+    // Windows below the minimum and empty batches are rejected
+    assert!(db
+        .update_pruner_config(PrunerType::StateMerkle, 1_000, 1)
+        .is_err());
+    assert!(db
+        .update_pruner_config(PrunerType::Ledger, MIN_LEDGER_PRUNE_WINDOW, 0)
+        .is_err());
+    assert_eq!(
+        db.get_ledger_prune_window().unwrap() as u64,
+        MIN_LEDGER_PRUNE_WINDOW
+    );
+
+    // Disabled pruners can't be reconfigured
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    assert!(db
+        .update_pruner_config(PrunerType::Ledger, MIN_LEDGER_PRUNE_WINDOW, 100)
+        .is_err());
+}
+
 #[test]
 fn test_error_if_version_pruned() {
     let tmp_dir = TempPath::new();
@@ -199,8 +289,10 @@ pub fn test_state_merkle_pruning_impl(
                 batch_size: 1,
             },
         },
+        &NO_OP_LEDGER_TIERING_CONFIG,
         RocksdbConfigs::default(),
         false, /* enable_indexer */
+        false, /* enable_event_type_index */
         BUFFERED_STATE_TARGET_ITEMS,
         DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
     )
@@ -288,4 +380,273 @@ proptest! {
         aptos_logger::Logger::new().init();
         test_state_merkle_pruning_impl(input);
     }
+
+    #[test]
+    fn test_ledger_tiering(input in arb_blocks_to_commit()) {
+        test_ledger_tiering_impl(input);
+    }
+
+    #[test]
+    fn test_open_as_secondary_following(input in arb_blocks_to_commit()) {
+        test_open_as_secondary_following_impl(input);
+    }
+
+    #[test]
+    fn test_event_type_index(input in arb_blocks_to_commit()) {
+        test_event_type_index_impl(input);
+    }
+}
+
+pub fn test_event_type_index_impl(
+    input: Vec<(Vec<TransactionToCommit>, LedgerInfoWithSignatures)>,
+) {
+    let tmp_dir = TempPath::new();
+    let open_db = |enable_event_type_index| {
+        AptosDB::open(
+            StorageDirPaths::from_path(&tmp_dir),
+            /*readonly=*/ false,
+            NO_OP_STORAGE_PRUNER_CONFIG,
+            &NO_OP_LEDGER_TIERING_CONFIG,
+            RocksdbConfigs::default(),
+            false, /* enable_indexer */
+            enable_event_type_index,
+            BUFFERED_STATE_TARGET_ITEMS,
+            DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+        )
+        .unwrap()
+    };
+
+    let db = open_db(true);
+    let mut in_memory_state = db
+        .state_store
+        .buffered_state()
+        .lock()
+        .current_state()
+        .clone();
+    let mut committed_txns = vec![];
+    for (txns_to_commit, ledger_info_with_sigs) in input.iter() {
+        let next_ver = committed_txns.len() as Version;
+        test_helper::update_in_memory_state(&mut in_memory_state, txns_to_commit.as_slice());
+        db.save_transactions_for_test(
+            txns_to_commit,
+            next_ver,                /* first_version */
+            next_ver.checked_sub(1), /* base_state_version */
+            Some(ledger_info_with_sigs),
+            true, /* sync_commit */
+            in_memory_state.clone(),
+        )
+        .unwrap();
+        committed_txns.extend(txns_to_commit.iter());
+    }
+    let latest_version = committed_txns.len() as Version - 1;
+
+    // Every event can be found by its type.
+    for (version, txn_to_commit) in committed_txns.iter().enumerate() {
+        for (index, event) in txn_to_commit.events().iter().enumerate() {
+            let events = db
+                .get_events_by_type(
+                    event.type_tag(),
+                    version as Version,
+                    index as u64,
+                    1, /* limit */
+                    latest_version,
+                )
+                .unwrap();
+            assert_eq!(events, vec![EventWithVersion::new(
+                version as Version,
+                event.clone()
+            )]);
+        }
+    }
+    for txn_to_commit in &committed_txns {
+        for event in txn_to_commit.events() {
+            let expected: Vec<_> = committed_txns
+                .iter()
+                .enumerate()
+                .flat_map(|(version, txn_to_commit)| {
+                    txn_to_commit
+                        .events()
+                        .iter()
+                        .filter(|e| e.type_tag() == event.type_tag())
+                        .map(move |e| EventWithVersion::new(version as Version, e.clone()))
+                })
+                .take(MAX_REQUEST_LIMIT as usize)
+                .collect();
+            let events = db
+                .get_events_by_type(event.type_tag(), 0, 0, MAX_REQUEST_LIMIT, latest_version)
+                .unwrap();
+            assert_eq!(events, expected);
+        }
+    }
+    drop(db);
+
+    // Disabling the index makes it unavailable.
+    let db = open_db(false);
+    assert!(db
+        .get_events_by_type(&TypeTag::Bool, 0, 0, 1, latest_version)
+        .is_err());
+    drop(db);
+
+    // Enabling it again only covers the versions committed from then on.
+    let db = open_db(true);
+    assert!(db
+        .get_events_by_type(&TypeTag::Bool, 0, 0, 1, latest_version)
+        .is_err());
+    assert!(db
+        .get_events_by_type(&TypeTag::Bool, latest_version + 1, 0, 1, latest_version)
+        .unwrap()
+        .is_empty());
+}
+
+pub fn test_ledger_tiering_impl(input: Vec<(Vec<TransactionToCommit>, LedgerInfoWithSignatures)>) {
+    // set up DB keeping the latest 5 versions in the ledger db, moving 2 versions at a time
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::open(
+        StorageDirPaths::from_path(tmp_dir),
+        /*readonly=*/ false,
+        NO_OP_STORAGE_PRUNER_CONFIG,
+        &LedgerTieringConfig {
+            enable: true,
+            hot_window: 5,
+            batch_size: 2,
+            cold_db_path: None,
+        },
+        RocksdbConfigs::default(),
+        false, /* enable_indexer */
+        false, /* enable_event_type_index */
+        BUFFERED_STATE_TARGET_ITEMS,
+        DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    )
+    .unwrap();
+
+    let mut in_memory_state = db
+        .state_store
+        .buffered_state()
+        .lock()
+        .current_state()
+        .clone();
+    let mut committed_txns = vec![];
+    for (txns_to_commit, ledger_info_with_sigs) in input.iter() {
+        let next_ver = committed_txns.len() as Version;
+        test_helper::update_in_memory_state(&mut in_memory_state, txns_to_commit.as_slice());
+        db.save_transactions_for_test(
+            txns_to_commit,
+            next_ver,                /* first_version */
+            next_ver.checked_sub(1), /* base_state_version */
+            Some(ledger_info_with_sigs),
+            true, /* sync_commit */
+            in_memory_state.clone(),
+        )
+        .unwrap();
+        committed_txns.extend(txns_to_commit.iter());
+        db.ledger_tiering_manager
+            .as_ref()
+            .unwrap()
+            .wait_for_pruner()
+            .unwrap();
+
+        // Everything older than the hot window (give or take a batch) is moved.
+        let num_txns = committed_txns.len() as Version;
+        let horizon = db.ledger_db.cold_ledger_db().unwrap().horizon();
+        assert!(horizon + 5 + 2 >= num_txns);
+        for version in 0..horizon {
+            assert!(db
+                .ledger_db
+                .transaction_db()
+                .get::<TransactionSchema>(&version)
+                .unwrap()
+                .is_none());
+            assert!(db
+                .ledger_db
+                .write_set_db()
+                .get::<WriteSetSchema>(&version)
+                .unwrap()
+                .is_none());
+        }
+
+        // All the data is still readable, from either db.
+        let txns: Vec<_> = db
+            .transaction_store
+            .get_transaction_iter(0, num_txns as usize)
+            .unwrap()
+            .collect::<anyhow::Result<_>>()
+            .unwrap();
+        let write_sets = db.transaction_store.get_write_sets(0, num_txns).unwrap();
+        for (version, txn_to_commit) in committed_txns.iter().enumerate() {
+            assert_eq!(&txns[version], txn_to_commit.transaction());
+            assert_eq!(&write_sets[version], txn_to_commit.write_set());
+            assert_eq!(
+                &db.transaction_store
+                    .get_transaction(version as Version)
+                    .unwrap(),
+                txn_to_commit.transaction()
+            );
+        }
+    }
+}
+
+pub fn test_open_as_secondary_following_impl(
+    input: Vec<(Vec<TransactionToCommit>, LedgerInfoWithSignatures)>,
+) {
+    let tmp_dir = TempPath::new();
+    let secondary_tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+
+    let mut in_memory_state = db
+        .state_store
+        .buffered_state()
+        .lock()
+        .current_state()
+        .clone();
+    let mut committed_txns = vec![];
+    let mut secondary_db = None;
+    for (i, (txns_to_commit, ledger_info_with_sigs)) in input.iter().enumerate() {
+        let next_ver = committed_txns.len() as Version;
+        test_helper::update_in_memory_state(&mut in_memory_state, txns_to_commit.as_slice());
+        db.save_transactions_for_test(
+            txns_to_commit,
+            next_ver,                /* first_version */
+            next_ver.checked_sub(1), /* base_state_version */
+            Some(ledger_info_with_sigs),
+            true, /* sync_commit */
+            in_memory_state.clone(),
+        )
+        .unwrap();
+        committed_txns.extend(txns_to_commit.iter());
+
+        // Open the secondary halfway, so that it has both data from before and after opening.
+        if i == input.len() / 2 {
+            secondary_db = Some(
+                AptosDB::open_as_secondary_following(
+                    StorageDirPaths::from_path(&tmp_dir),
+                    &secondary_tmp_dir,
+                    RocksdbConfigs::default(),
+                    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+                    // Long enough for the test to catch up explicitly.
+                    std::time::Duration::from_secs(3600),
+                )
+                .unwrap(),
+            );
+        }
+
+        if let Some(secondary_db) = secondary_db.as_ref() {
+            secondary_db.try_catch_up_with_primary().unwrap();
+            assert_eq!(
+                &secondary_db.get_latest_ledger_info().unwrap(),
+                ledger_info_with_sigs
+            );
+            for (version, txn_to_commit) in committed_txns.iter().enumerate() {
+                assert_eq!(
+                    &secondary_db
+                        .transaction_store
+                        .get_transaction(version as Version)
+                        .unwrap(),
+                    txn_to_commit.transaction()
+                );
+            }
+        }
+    }
+
+    // The DB opened normally can't be caught up.
+    assert!(db.try_catch_up_with_primary().is_err());
 }
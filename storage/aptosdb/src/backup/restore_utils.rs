@@ -14,7 +14,7 @@ use crate::{
         transaction_accumulator::TransactionAccumulatorSchema,
     },
     state_store::StateStore,
-    transaction_store::TransactionStore,
+    transaction_store::{get_block_timestamps_usecs, TransactionStore},
     ShardedStateKvSchemaBatch,
 };
 use anyhow::{ensure, Result};
@@ -236,10 +236,17 @@ pub(crate) fn save_transactions_impl(
     state_kv_metadata_batch: &SchemaBatch,
     kv_replay: bool,
 ) -> Result<()> {
-    for (idx, txn) in txns.iter().enumerate() {
+    let previous_block_timestamp_usecs = if first_version == 0 {
+        None
+    } else {
+        event_store.get_block_timestamp_usecs_if_indexed(first_version - 1)?
+    };
+    let block_timestamps_usecs = get_block_timestamps_usecs(txns, previous_block_timestamp_usecs);
+    for (idx, (txn, block_timestamp_usecs)) in txns.iter().zip(block_timestamps_usecs).enumerate() {
         transaction_store.put_transaction(
             first_version + idx as Version,
             txn,
+            block_timestamp_usecs,
             /*skip_index=*/ false,
             &ledger_db_batch.transaction_db_batches,
         )?;
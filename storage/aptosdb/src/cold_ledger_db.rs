@@ -0,0 +1,91 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! The cold tier of the ledger db. With ledger tiering enabled, the transactions and write sets
+//! older than the hot window are moved from the ledger db to the cold ledger db (see
+//! `LedgerTieringManager`), which can live on a separate, cheaper volume. The versions below the
+//! horizon are read from the cold ledger db, the rest from the ledger db.
+
+use crate::{
+    db_options::{cold_ledger_db_column_families, gen_cold_ledger_cfds},
+    schema::db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
+};
+use anyhow::Result;
+use aptos_config::config::RocksdbConfigs;
+use aptos_logger::prelude::info;
+use aptos_rocksdb_options::gen_rocksdb_options;
+use aptos_schemadb::{SchemaBatch, DB};
+use aptos_types::transaction::{AtomicVersion, Version};
+use std::{path::Path, sync::atomic::Ordering};
+
+pub const COLD_LEDGER_DB_NAME: &str = "cold_ledger_db";
+
+#[derive(Debug)]
+pub struct ColdLedgerDb {
+    db: DB,
+    /// All the transactions and write sets below this version are in the cold ledger db.
+    horizon: AtomicVersion,
+}
+
+impl ColdLedgerDb {
+    pub(crate) fn new<P: AsRef<Path>>(
+        path: P,
+        rocksdb_configs: &RocksdbConfigs,
+        readonly: bool,
+    ) -> Result<Self> {
+        let rocksdb_config = &rocksdb_configs.ledger_db_config;
+        let db = if readonly {
+            DB::open_cf_readonly(
+                &gen_rocksdb_options(rocksdb_config, true),
+                path.as_ref(),
+                COLD_LEDGER_DB_NAME,
+                cold_ledger_db_column_families(),
+            )?
+        } else {
+            DB::open_cf(
+                &gen_rocksdb_options(rocksdb_config, false),
+                path.as_ref(),
+                COLD_LEDGER_DB_NAME,
+                gen_cold_ledger_cfds(rocksdb_config, &rocksdb_configs.column_family_configs),
+            )?
+        };
+
+        let horizon = db
+            .get::<DbMetadataSchema>(&DbMetadataKey::LedgerTieringProgress)?
+            .map_or(0, |v| v.expect_version());
+
+        info!(
+            path = path.as_ref(),
+            horizon = horizon,
+            "Opened cold ledger db!"
+        );
+
+        Ok(Self {
+            db,
+            horizon: AtomicVersion::new(horizon),
+        })
+    }
+
+    pub(crate) fn db(&self) -> &DB {
+        &self.db
+    }
+
+    /// Returns the version below which the transactions and write sets are in the cold ledger db
+    pub fn horizon(&self) -> Version {
+        self.horizon.load(Ordering::SeqCst)
+    }
+
+    /// Writes the data moved from the ledger db, and advances the horizon to `horizon`. The data
+    /// must only be deleted from the ledger db after this returns.
+    pub(crate) fn write_schemas(&self, batch: SchemaBatch, horizon: Version) -> Result<()> {
+        batch.put::<DbMetadataSchema>(
+            &DbMetadataKey::LedgerTieringProgress,
+            &DbMetadataValue::Version(horizon),
+        )?;
+        self.db.write_schemas(batch)?;
+        self.horizon.store(horizon, Ordering::SeqCst);
+        Ok(())
+    }
+}
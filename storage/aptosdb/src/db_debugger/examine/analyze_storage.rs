@@ -0,0 +1,412 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    db_debugger::ShardingConfig,
+    db_options::{
+        event_db_column_families, ledger_db_column_families, ledger_metadata_db_column_families,
+        state_kv_db_column_families, state_merkle_db_column_families,
+        transaction_accumulator_db_column_families, transaction_db_column_families,
+        transaction_info_db_column_families, write_set_db_column_families,
+    },
+    ledger_db::LedgerDb,
+    schema::{
+        db_metadata::{DbMetadataKey, DbMetadataSchema},
+        state_value::StateValueSchema,
+    },
+    state_kv_db::StateKvDb,
+    state_merkle_db::StateMerkleDb,
+    utils::truncation_helper::get_overall_commit_progress,
+    AptosDB, NUM_STATE_SHARDS,
+};
+use anyhow::Result;
+use aptos_config::config::{RocksdbConfigs, StorageDirPaths};
+use aptos_schemadb::{ReadOptions, DB};
+use aptos_types::{
+    access_path::Path,
+    account_address::AccountAddress,
+    state_store::{state_key::StateKeyInner, table::TableHandle},
+    transaction::Version,
+};
+use clap::Parser;
+use serde::Serialize;
+use std::{collections::HashMap, path::PathBuf};
+
+#[derive(Parser)]
+#[clap(about = "Report what is taking up space in the DB.")]
+pub struct Cmd {
+    #[clap(long, value_parser)]
+    db_dir: PathBuf,
+
+    #[clap(flatten)]
+    sharding_config: ShardingConfig,
+
+    /// Number of accounts and tables to list in the state breakdown.
+    #[clap(long, default_value_t = 10)]
+    top_n: usize,
+
+    /// Skip scanning the state values, which reads the whole state KV DB and can take a long
+    /// time on big DBs.
+    #[clap(long)]
+    skip_state_scan: bool,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let report = analyze_storage(
+            self.db_dir,
+            self.sharding_config.enable_storage_sharding,
+            self.top_n,
+            !self.skip_state_scan,
+        )?;
+
+        println!("Latest version: {:?}", report.latest_version);
+        println!();
+        println!("Column families (bytes in SST files, estimated number of keys):");
+        for cf in &report.column_families {
+            println!(
+                "  {:<12} {:<36} {:>16} {:>14}",
+                cf.db, cf.cf_name, cf.sst_files_size, cf.estimated_num_keys
+            );
+        }
+        println!();
+        println!("Prune windows (first readable version, number of versions kept):");
+        for window in &report.prune_windows {
+            println!(
+                "  {:<36} {:>14} {:>14}",
+                window.name, window.min_readable_version, window.num_versions
+            );
+        }
+        if let Some(state) = &report.state {
+            println!();
+            println!(
+                "State values: {} entries, {} bytes",
+                state.num_entries, state.total_bytes
+            );
+            for (kind, usage) in [
+                ("Resources", &state.resources),
+                ("Resource groups", &state.resource_groups),
+                ("Modules", &state.modules),
+                ("Table items", &state.table_items),
+                ("Others", &state.others),
+            ] {
+                println!(
+                    "  {:<16} {:>14} entries {:>16} bytes",
+                    kind, usage.num_entries, usage.num_bytes
+                );
+            }
+            println!("Top accounts by bytes (resources and modules):");
+            for account in &state.top_accounts {
+                println!(
+                    "  {} {:>14} entries {:>16} bytes",
+                    account.owner, account.usage.num_entries, account.usage.num_bytes
+                );
+            }
+            println!("Top tables by bytes:");
+            for table in &state.top_tables {
+                println!(
+                    "  {} {:>14} entries {:>16} bytes",
+                    table.owner, table.usage.num_entries, table.usage.num_bytes
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct StorageReport {
+    pub latest_version: Option<Version>,
+    pub column_families: Vec<ColumnFamilyUsage>,
+    pub prune_windows: Vec<PruneWindow>,
+    /// Only present if the state values were scanned.
+    pub state: Option<StateUsage>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ColumnFamilyUsage {
+    pub db: String,
+    pub cf_name: String,
+    pub sst_files_size: u64,
+    pub estimated_num_keys: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PruneWindow {
+    pub name: String,
+    pub min_readable_version: Version,
+    pub num_versions: u64,
+}
+
+#[derive(Clone, Copy, Debug, Default, Serialize)]
+pub struct Usage {
+    pub num_entries: u64,
+    pub num_bytes: u64,
+}
+
+impl Usage {
+    fn add(&mut self, num_bytes: usize) {
+        self.num_entries += 1;
+        self.num_bytes += num_bytes as u64;
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct OwnerUsage {
+    pub owner: String,
+    pub usage: Usage,
+}
+
+/// Breakdown of the state KV DB, counting every stored version of every state value, since
+/// that's what occupies the disk until pruned.
+#[derive(Debug, Default, Serialize)]
+pub struct StateUsage {
+    pub num_entries: u64,
+    pub total_bytes: u64,
+    pub resources: Usage,
+    pub resource_groups: Usage,
+    pub modules: Usage,
+    pub table_items: Usage,
+    pub others: Usage,
+    pub top_accounts: Vec<OwnerUsage>,
+    pub top_tables: Vec<OwnerUsage>,
+}
+
+pub fn analyze_storage(
+    db_dir: PathBuf,
+    enable_storage_sharding: bool,
+    top_n: usize,
+    scan_state: bool,
+) -> Result<StorageReport> {
+    let rocksdb_config = RocksdbConfigs {
+        enable_storage_sharding,
+        ..Default::default()
+    };
+    let (ledger_db, state_merkle_db, state_kv_db) = AptosDB::open_dbs(
+        &StorageDirPaths::from_path(db_dir),
+        rocksdb_config,
+        /*readonly=*/ true,
+        /*max_num_nodes_per_lru_cache_shard=*/ 0,
+    )?;
+
+    let latest_version = get_overall_commit_progress(ledger_db.metadata_db())?;
+    let column_families = column_family_usages(&ledger_db, &state_merkle_db, &state_kv_db)?;
+    let prune_windows = prune_windows(&ledger_db, &state_merkle_db, &state_kv_db, latest_version)?;
+    let state = if scan_state {
+        Some(state_usage(&state_kv_db, top_n)?)
+    } else {
+        None
+    };
+
+    Ok(StorageReport {
+        latest_version,
+        column_families,
+        prune_windows,
+        state,
+    })
+}
+
+fn column_family_usages(
+    ledger_db: &LedgerDb,
+    state_merkle_db: &StateMerkleDb,
+    state_kv_db: &StateKvDb,
+) -> Result<Vec<ColumnFamilyUsage>> {
+    let mut usages = vec![];
+    let mut add_db = |name: String, db: &DB, cfs: Vec<&str>| -> Result<()> {
+        for cf_name in cfs {
+            usages.push(ColumnFamilyUsage {
+                db: name.clone(),
+                cf_name: cf_name.to_string(),
+                sst_files_size: db.get_property(cf_name, "rocksdb.total-sst-files-size")?,
+                estimated_num_keys: db.get_property(cf_name, "rocksdb.estimate-num-keys")?,
+            });
+        }
+        Ok(())
+    };
+
+    if state_kv_db.enabled_sharding() {
+        add_db(
+            "ledger_metadata".to_string(),
+            ledger_db.metadata_db(),
+            ledger_metadata_db_column_families(),
+        )?;
+        add_db(
+            "event".to_string(),
+            ledger_db.event_db(),
+            event_db_column_families(),
+        )?;
+        add_db(
+            "txn_accumulator".to_string(),
+            ledger_db.transaction_accumulator_db(),
+            transaction_accumulator_db_column_families(),
+        )?;
+        add_db(
+            "transaction".to_string(),
+            ledger_db.transaction_db(),
+            transaction_db_column_families(),
+        )?;
+        add_db(
+            "txn_info".to_string(),
+            ledger_db.transaction_info_db(),
+            transaction_info_db_column_families(),
+        )?;
+        add_db(
+            "write_set".to_string(),
+            ledger_db.write_set_db(),
+            write_set_db_column_families(),
+        )?;
+        add_db(
+            "state_kv".to_string(),
+            state_kv_db.metadata_db(),
+            state_kv_db_column_families(),
+        )?;
+        for shard in 0..NUM_STATE_SHARDS {
+            add_db(
+                format!("state_kv_{}", shard),
+                state_kv_db.db_shard(shard as u8),
+                state_kv_db_column_families(),
+            )?;
+        }
+    } else {
+        add_db(
+            "ledger".to_string(),
+            ledger_db.metadata_db(),
+            ledger_db_column_families(),
+        )?;
+    }
+
+    add_db(
+        "state_merkle".to_string(),
+        state_merkle_db.metadata_db(),
+        state_merkle_db_column_families(),
+    )?;
+    if state_merkle_db.sharding_enabled() {
+        for shard in 0..NUM_STATE_SHARDS {
+            add_db(
+                format!("state_merkle_{}", shard),
+                state_merkle_db.db_shard(shard as u8),
+                state_merkle_db_column_families(),
+            )?;
+        }
+    }
+
+    Ok(usages)
+}
+
+fn prune_windows(
+    ledger_db: &LedgerDb,
+    state_merkle_db: &StateMerkleDb,
+    state_kv_db: &StateKvDb,
+    latest_version: Option<Version>,
+) -> Result<Vec<PruneWindow>> {
+    let get_progress = |db: &DB, key: DbMetadataKey| -> Result<Version> {
+        Ok(db
+            .get::<DbMetadataSchema>(&key)?
+            .map_or(0, |v| v.expect_version()))
+    };
+
+    Ok([
+        (
+            "Ledger",
+            get_progress(ledger_db.metadata_db(), DbMetadataKey::LedgerPrunerProgress)?,
+        ),
+        (
+            "State values",
+            get_progress(
+                state_kv_db.metadata_db(),
+                DbMetadataKey::StateKvPrunerProgress,
+            )?,
+        ),
+        (
+            "State Merkle tree",
+            get_progress(
+                state_merkle_db.metadata_db(),
+                DbMetadataKey::StateMerklePrunerProgress,
+            )?,
+        ),
+        (
+            "Epoch ending state Merkle tree",
+            get_progress(
+                state_merkle_db.metadata_db(),
+                DbMetadataKey::EpochEndingStateMerklePrunerProgress,
+            )?,
+        ),
+    ]
+    .into_iter()
+    .map(|(name, min_readable_version)| PruneWindow {
+        name: name.to_string(),
+        min_readable_version,
+        num_versions: latest_version.map_or(0, |latest| {
+            (latest + 1).saturating_sub(min_readable_version)
+        }),
+    })
+    .collect())
+}
+
+fn state_usage(state_kv_db: &StateKvDb, top_n: usize) -> Result<StateUsage> {
+    let mut usage = StateUsage::default();
+    let mut accounts: HashMap<AccountAddress, Usage> = HashMap::new();
+    let mut tables: HashMap<TableHandle, Usage> = HashMap::new();
+
+    let dbs: Vec<&DB> = if state_kv_db.enabled_sharding() {
+        (0..NUM_STATE_SHARDS)
+            .map(|shard| state_kv_db.db_shard(shard as u8))
+            .collect()
+    } else {
+        vec![state_kv_db.metadata_db()]
+    };
+
+    for db in dbs {
+        let mut iter = db.iter::<StateValueSchema>(ReadOptions::default())?;
+        iter.seek_to_first();
+        for item in iter {
+            let ((state_key, _version), value) = item?;
+            let num_bytes =
+                state_key.size() + value.as_ref().map_or(0, |value| value.bytes().len());
+            usage.num_entries += 1;
+            usage.total_bytes += num_bytes as u64;
+
+            match state_key.inner() {
+                StateKeyInner::AccessPath(access_path) => {
+                    match access_path.get_path() {
+                        Path::Code(_) => usage.modules.add(num_bytes),
+                        Path::Resource(_) => usage.resources.add(num_bytes),
+                        Path::ResourceGroup(_) => usage.resource_groups.add(num_bytes),
+                    }
+                    accounts
+                        .entry(access_path.address)
+                        .or_default()
+                        .add(num_bytes);
+                },
+                StateKeyInner::TableItem { handle, .. } => {
+                    usage.table_items.add(num_bytes);
+                    tables.entry(*handle).or_default().add(num_bytes);
+                },
+                StateKeyInner::Raw(_) => usage.others.add(num_bytes),
+            }
+        }
+    }
+
+    usage.top_accounts = top_by_bytes(accounts, top_n, |address| address.to_hex_literal());
+    usage.top_tables = top_by_bytes(tables, top_n, |handle| handle.0.to_hex_literal());
+
+    Ok(usage)
+}
+
+fn top_by_bytes<K>(
+    usages: HashMap<K, Usage>,
+    top_n: usize,
+    name: impl Fn(&K) -> String,
+) -> Vec<OwnerUsage> {
+    let mut usages: Vec<_> = usages.into_iter().collect();
+    usages.sort_by(|(_, a), (_, b)| b.num_bytes.cmp(&a.num_bytes));
+    usages
+        .into_iter()
+        .take(top_n)
+        .map(|(owner, usage)| OwnerUsage {
+            owner: name(&owner),
+            usage,
+        })
+        .collect()
+}
@@ -1,6 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod analyze_storage;
 mod print_db_versions;
 
 use anyhow::Result;
@@ -8,12 +9,14 @@ use anyhow::Result;
 #[derive(clap::Subcommand)]
 #[clap(about = "Examine databases.")]
 pub enum Cmd {
+    AnalyzeStorage(analyze_storage::Cmd),
     PrintDbVersions(print_db_versions::Cmd),
 }
 
 impl Cmd {
     pub fn run(self) -> Result<()> {
         match self {
+            Self::AnalyzeStorage(cmd) => cmd.run(),
             Self::PrintDbVersions(cmd) => cmd.run(),
         }
     }
@@ -3,8 +3,9 @@
 
 pub mod checkpoint;
 mod common;
-mod examine;
+pub mod examine;
 pub mod ledger;
+pub mod reshard;
 pub mod state_tree;
 pub mod truncate;
 
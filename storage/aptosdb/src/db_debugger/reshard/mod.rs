@@ -0,0 +1,439 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    jellyfish_merkle_node::JellyfishMerkleNodeSchema,
+    ledger_db::{LedgerDb, LEDGER_DB_FOLDER_NAME},
+    schema::{
+        block_index::BlockIndexSchema,
+        db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
+        epoch_by_version::EpochByVersionSchema,
+        event::EventSchema,
+        event_accumulator::EventAccumulatorSchema,
+        event_by_key::EventByKeySchema,
+        event_by_type::EventByTypeSchema,
+        event_by_version::EventByVersionSchema,
+        ledger_info::LedgerInfoSchema,
+        stale_node_index::StaleNodeIndexSchema,
+        stale_node_index_cross_epoch::StaleNodeIndexCrossEpochSchema,
+        stale_state_value_index::StaleStateValueIndexSchema,
+        state_value::StateValueSchema,
+        transaction::TransactionSchema,
+        transaction_accumulator::TransactionAccumulatorSchema,
+        transaction_by_account::TransactionByAccountSchema,
+        transaction_by_account_time::TransactionByAccountTimeSchema,
+        transaction_by_hash::TransactionByHashSchema,
+        transaction_info::TransactionInfoSchema,
+        version_data::VersionDataSchema,
+        write_set::WriteSetSchema,
+    },
+    state_kv_db::{StateKvDb, STATE_KV_DB_FOLDER_NAME},
+    state_merkle_db::{StateMerkleDb, STATE_MERKLE_DB_NAME},
+    utils::truncation_helper::{
+        get_current_version_in_state_merkle_db, get_state_kv_commit_progress,
+    },
+    AptosDB, NUM_STATE_SHARDS,
+};
+use anyhow::{anyhow, ensure, Result};
+use aptos_config::config::{RocksdbConfigs, StorageDirPaths};
+use aptos_jellyfish_merkle::node_type::{Node, NodeKey};
+use aptos_schemadb::{schema::Schema, ReadOptions, SchemaBatch, DB};
+use aptos_types::account_config::{new_block_event_key, NewBlockEvent};
+use clap::Parser;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[derive(Parser)]
+#[clap(
+    about = "Migrate an unsharded DB to the sharded storage layout in place, so that sharding can \
+    be enabled without resyncing. The number of shards is fixed (16), by the first nibble of the \
+    hashes of the state keys."
+)]
+#[clap(group(clap::ArgGroup::new("backup")
+        .required(true)
+        .args(&["backup_checkpoint_dir", "opt_out_backup_checkpoint"]),
+))]
+pub struct Cmd {
+    // TODO(grao): Support db_path_overrides here.
+    #[clap(long, value_parser)]
+    db_dir: PathBuf,
+
+    #[clap(long, default_value_t = 10000)]
+    batch_size: usize,
+
+    #[clap(long, value_parser, group = "backup")]
+    backup_checkpoint_dir: Option<PathBuf>,
+
+    #[clap(long, group = "backup")]
+    opt_out_backup_checkpoint: bool,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let unsharded_ledger_db_dir = self.db_dir.join(LEDGER_DB_FOLDER_NAME);
+        let unsharded_state_merkle_db_dir = self.db_dir.join(STATE_MERKLE_DB_NAME);
+        ensure!(
+            unsharded_ledger_db_dir.join("CURRENT").exists(),
+            "No unsharded DB found at {:?}.",
+            self.db_dir
+        );
+        // The state kv db only exists in the sharded layout.
+        ensure!(
+            !self.db_dir.join(STATE_KV_DB_FOLDER_NAME).exists(),
+            "The sharded layout already exists at {:?}. If it's left by an interrupted migration, \
+            restore the DB from the backup checkpoint.",
+            self.db_dir
+        );
+
+        if !self.opt_out_backup_checkpoint {
+            let backup_checkpoint_dir = self.backup_checkpoint_dir.unwrap();
+            ensure!(
+                !backup_checkpoint_dir.exists(),
+                "Backup dir already exists."
+            );
+            println!("Creating backup at: {:?}", &backup_checkpoint_dir);
+            fs::create_dir_all(&backup_checkpoint_dir)?;
+            AptosDB::create_checkpoint(
+                &self.db_dir,
+                backup_checkpoint_dir,
+                /*sharding=*/ false,
+            )?;
+            println!("Done!");
+        } else {
+            println!("Opted out backup creation!.");
+        }
+
+        {
+            let db_paths = StorageDirPaths::from_path(&self.db_dir);
+            let (ledger_db, state_merkle_db, _) = AptosDB::open_dbs(
+                &db_paths,
+                RocksdbConfigs::default(),
+                /*readonly=*/ true,
+                /*max_num_nodes_per_lru_cache_shard=*/ 0,
+            )?;
+            // The sharded dbs are in sub directories of the unsharded ones.
+            let (sharded_ledger_db, sharded_state_merkle_db, sharded_state_kv_db) =
+                AptosDB::open_dbs(
+                    &db_paths,
+                    RocksdbConfigs {
+                        enable_storage_sharding: true,
+                        ..Default::default()
+                    },
+                    /*readonly=*/ false,
+                    /*max_num_nodes_per_lru_cache_shard=*/ 0,
+                )?;
+
+            println!("Copying ledger db and state kv db...");
+            let num_state_values = self.copy_ledger_db(
+                ledger_db.metadata_db(),
+                &sharded_ledger_db,
+                &sharded_state_kv_db,
+            )?;
+            println!("Done!");
+
+            println!("Copying state merkle db...");
+            self.copy_state_merkle_db(state_merkle_db.metadata_db(), &sharded_state_merkle_db)?;
+            println!("Done!");
+
+            println!("Verifying shards...");
+            verify_state_kv_shards(&sharded_state_kv_db, num_state_values)?;
+            verify_state_merkle_shards(&state_merkle_db, &sharded_state_merkle_db)?;
+            println!("Done!");
+        }
+
+        println!("Removing the unsharded dbs...");
+        remove_db_files(&unsharded_ledger_db_dir)?;
+        remove_db_files(&unsharded_state_merkle_db_dir)?;
+        println!("Done! Enable storage sharding in the node config before restarting the node.");
+
+        Ok(())
+    }
+
+    /// Copies the unsharded ledger db, which also holds the state values, to the sharded ledger db
+    /// and state kv db. Returns the number of state values copied.
+    fn copy_ledger_db(
+        &self,
+        ledger_db: &DB,
+        sharded_ledger_db: &LedgerDb,
+        sharded_state_kv_db: &StateKvDb,
+    ) -> Result<usize> {
+        let metadata_db = sharded_ledger_db.metadata_db();
+        self.copy_schema::<EpochByVersionSchema>(ledger_db, |_| metadata_db)?;
+        self.copy_schema::<LedgerInfoSchema>(ledger_db, |_| metadata_db)?;
+        self.copy_schema::<VersionDataSchema>(ledger_db, |_| metadata_db)?;
+        self.copy_schema::<DbMetadataSchema>(ledger_db, |key| match key {
+            DbMetadataKey::EventPrunerProgress | DbMetadataKey::EventTypeIndexStartVersion => {
+                sharded_ledger_db.event_db()
+            },
+            DbMetadataKey::TransactionAccumulatorPrunerProgress => {
+                sharded_ledger_db.transaction_accumulator_db()
+            },
+            DbMetadataKey::TransactionInfoPrunerProgress => sharded_ledger_db.transaction_info_db(),
+            DbMetadataKey::TransactionPrunerProgress => sharded_ledger_db.transaction_db(),
+            DbMetadataKey::WriteSetPrunerProgress => sharded_ledger_db.write_set_db(),
+            DbMetadataKey::StateKvCommitProgress | DbMetadataKey::StateKvPrunerProgress => {
+                sharded_state_kv_db.metadata_db()
+            },
+            DbMetadataKey::StateKvShardCommitProgress(shard_id)
+            | DbMetadataKey::StateKvShardPrunerProgress(shard_id) => {
+                sharded_state_kv_db.db_shard(*shard_id as u8)
+            },
+            _ => metadata_db,
+        })?;
+
+        let event_db = sharded_ledger_db.event_db();
+        self.copy_schema::<EventSchema>(ledger_db, |_| event_db)?;
+        self.copy_schema::<EventAccumulatorSchema>(ledger_db, |_| event_db)?;
+        self.copy_schema::<EventByKeySchema>(ledger_db, |_| event_db)?;
+        self.copy_schema::<EventByVersionSchema>(ledger_db, |_| event_db)?;
+        self.copy_schema::<EventByTypeSchema>(ledger_db, |_| event_db)?;
+        // Only the sharded layout indexes blocks by height, so the index has to be built here.
+        self.rebuild_block_index(ledger_db, metadata_db)?;
+
+        let transaction_db = sharded_ledger_db.transaction_db();
+        self.copy_schema::<TransactionSchema>(ledger_db, |_| transaction_db)?;
+        self.copy_schema::<TransactionByAccountSchema>(ledger_db, |_| transaction_db)?;
+        self.copy_schema::<TransactionByAccountTimeSchema>(ledger_db, |_| transaction_db)?;
+        self.copy_schema::<TransactionByHashSchema>(ledger_db, |_| transaction_db)?;
+
+        self.copy_schema::<TransactionAccumulatorSchema>(ledger_db, |_| {
+            sharded_ledger_db.transaction_accumulator_db()
+        })?;
+        self.copy_schema::<TransactionInfoSchema>(ledger_db, |_| {
+            sharded_ledger_db.transaction_info_db()
+        })?;
+        self.copy_schema::<WriteSetSchema>(ledger_db, |_| sharded_ledger_db.write_set_db())?;
+
+        self.copy_schema::<StaleStateValueIndexSchema>(ledger_db, |index| {
+            sharded_state_kv_db.db_shard(index.state_key.get_shard_id())
+        })?;
+        self.copy_schema::<StateValueSchema>(ledger_db, |(state_key, _)| {
+            sharded_state_kv_db.db_shard(state_key.get_shard_id())
+        })
+    }
+
+    /// Builds the block index of the sharded ledger db from the new block events in the unsharded
+    /// ledger db, in batches. Returns the number of blocks indexed.
+    fn rebuild_block_index(&self, ledger_db: &DB, metadata_db: &DB) -> Result<usize> {
+        let event_key = new_block_event_key();
+        let mut iter = ledger_db.iter::<EventByKeySchema>(ReadOptions::default())?;
+        iter.seek(&(event_key, 0))?;
+
+        let mut batch = SchemaBatch::new();
+        let mut num_blocks = 0;
+        for res in iter {
+            let ((key, _seq_num), (version, index)) = res?;
+            if key != event_key {
+                break;
+            }
+            let event = ledger_db
+                .get::<EventSchema>(&(version, index))?
+                .ok_or_else(|| anyhow!("Event {} of txn {} is missing.", index, version))?;
+            let new_block_event = NewBlockEvent::try_from_bytes(event.event_data())?;
+            batch.put::<BlockIndexSchema>(&new_block_event.height(), &version)?;
+            num_blocks += 1;
+
+            if num_blocks % self.batch_size == 0 {
+                metadata_db.write_schemas(std::mem::replace(&mut batch, SchemaBatch::new()))?;
+            }
+        }
+        metadata_db.write_schemas(batch)?;
+
+        println!("Indexed {} blocks.", num_blocks);
+        Ok(num_blocks)
+    }
+
+    /// Copies the unsharded state merkle db to the sharded one, where the nodes below the root are
+    /// in the shard of the first nibble of their path.
+    fn copy_state_merkle_db(
+        &self,
+        state_merkle_db: &DB,
+        sharded_state_merkle_db: &StateMerkleDb,
+    ) -> Result<()> {
+        let db_by_key = |node_key: &NodeKey| match node_key.get_shard_id() {
+            Some(shard_id) => sharded_state_merkle_db.db_shard(shard_id),
+            None => sharded_state_merkle_db.metadata_db(),
+        };
+        self.copy_schema::<JellyfishMerkleNodeSchema>(state_merkle_db, db_by_key)?;
+        self.copy_schema::<StaleNodeIndexSchema>(state_merkle_db, |index| {
+            db_by_key(&index.node_key)
+        })?;
+        self.copy_schema::<StaleNodeIndexCrossEpochSchema>(state_merkle_db, |index| {
+            db_by_key(&index.node_key)
+        })?;
+        self.copy_schema::<DbMetadataSchema>(state_merkle_db, |key| match key {
+            DbMetadataKey::StateMerkleShardCommitProgress(shard_id)
+            | DbMetadataKey::StateMerkleShardPrunerProgress(shard_id)
+            | DbMetadataKey::EpochEndingStateMerkleShardPrunerProgress(shard_id)
+            | DbMetadataKey::StateMerkleShardRestoreProgress(shard_id, _) => {
+                sharded_state_merkle_db.db_shard(*shard_id as u8)
+            },
+            _ => sharded_state_merkle_db.metadata_db(),
+        })?;
+
+        Ok(())
+    }
+
+    /// Copies all the rows of `S` in `source` to the db `target_db` picks for each of them, in
+    /// batches. Returns the number of rows copied.
+    fn copy_schema<'a, S: Schema>(
+        &self,
+        source: &DB,
+        target_db: impl Fn(&S::Key) -> &'a DB,
+    ) -> Result<usize> {
+        let mut iter = source.iter::<S>(ReadOptions::default())?;
+        iter.seek_to_first();
+
+        let mut batches: Vec<(&DB, SchemaBatch)> = Vec::new();
+        let mut num_rows = 0;
+        let mut num_rows_in_batches = 0;
+        for res in iter {
+            let (key, value) = res?;
+            let db = target_db(&key);
+            let index = match batches.iter().position(|(d, _)| std::ptr::eq(*d, db)) {
+                Some(index) => index,
+                None => {
+                    batches.push((db, SchemaBatch::new()));
+                    batches.len() - 1
+                },
+            };
+            batches[index].1.put::<S>(&key, &value)?;
+            num_rows += 1;
+            num_rows_in_batches += 1;
+
+            if num_rows_in_batches >= self.batch_size {
+                write_batches(&mut batches)?;
+                num_rows_in_batches = 0;
+            }
+        }
+        write_batches(&mut batches)?;
+
+        println!("Copied {} rows of {}.", num_rows, S::COLUMN_FAMILY_NAME);
+        Ok(num_rows)
+    }
+}
+
+fn write_batches(batches: &mut Vec<(&DB, SchemaBatch)>) -> Result<()> {
+    for (db, batch) in batches.drain(..) {
+        db.write_schemas(batch)?;
+    }
+    Ok(())
+}
+
+/// Checks that every state value is in its own shard, that none is lost, and that all the shards
+/// are committed to the same version.
+fn verify_state_kv_shards(state_kv_db: &StateKvDb, num_state_values: usize) -> Result<()> {
+    let commit_progress = get_state_kv_commit_progress(state_kv_db)?;
+    let mut num_state_values_in_shards = 0;
+    for shard_id in 0..NUM_STATE_SHARDS as u8 {
+        let db_shard = state_kv_db.db_shard(shard_id);
+        let shard_commit_progress = db_shard
+            .get::<DbMetadataSchema>(&DbMetadataKey::StateKvShardCommitProgress(
+                shard_id as usize,
+            ))?
+            .map(DbMetadataValue::expect_version);
+        ensure!(
+            shard_commit_progress == commit_progress,
+            "State kv shard {} is committed to {:?}, while the state kv db is committed to {:?}.",
+            shard_id,
+            shard_commit_progress,
+            commit_progress,
+        );
+
+        let mut iter = db_shard.iter::<StateValueSchema>(ReadOptions::default())?;
+        iter.seek_to_first();
+        for res in iter {
+            let ((state_key, version), _) = res?;
+            ensure!(
+                state_key.get_shard_id() == shard_id,
+                "State value of {:?} at version {} is in shard {}.",
+                state_key,
+                version,
+                shard_id,
+            );
+            num_state_values_in_shards += 1;
+        }
+    }
+    ensure!(
+        num_state_values_in_shards == num_state_values,
+        "The state kv shards have {} state values, expected {}.",
+        num_state_values_in_shards,
+        num_state_values,
+    );
+    Ok(())
+}
+
+/// Checks that the latest tree has the same root as the unsharded one, and that each of its
+/// children is the root of the subtree in the corresponding shard.
+fn verify_state_merkle_shards(
+    state_merkle_db: &StateMerkleDb,
+    sharded_state_merkle_db: &StateMerkleDb,
+) -> Result<()> {
+    let version = match get_current_version_in_state_merkle_db(state_merkle_db)? {
+        Some(version) => version,
+        None => {
+            println!("The state merkle db is empty, nothing to verify.");
+            return Ok(());
+        },
+    };
+    let root_key = NodeKey::new_empty_path(version);
+    let root = state_merkle_db
+        .metadata_db()
+        .get::<JellyfishMerkleNodeSchema>(&root_key)?
+        .ok_or_else(|| anyhow!("Root of the state merkle tree at version {version} is missing."))?;
+    let sharded_root = sharded_state_merkle_db
+        .metadata_db()
+        .get::<JellyfishMerkleNodeSchema>(&root_key)?
+        .ok_or_else(|| {
+            anyhow!("Root of the sharded state merkle tree at version {version} is missing.")
+        })?;
+    ensure!(
+        sharded_root.hash() == root.hash(),
+        "The root hash of the sharded state merkle tree at version {} is {}, expected {}.",
+        version,
+        sharded_root.hash(),
+        root.hash(),
+    );
+
+    if let Node::Internal(internal_node) = &sharded_root {
+        for (nibble, child) in internal_node.children_sorted() {
+            let shard_id = u8::from(*nibble);
+            let shard_root = sharded_state_merkle_db
+                .db_shard(shard_id)
+                .get::<JellyfishMerkleNodeSchema>(
+                    &root_key.gen_child_node_key(child.version, *nibble),
+                )?
+                .ok_or_else(|| {
+                    anyhow!(
+                        "Root of state merkle shard {} at version {} is missing.",
+                        shard_id,
+                        child.version
+                    )
+                })?;
+            ensure!(
+                shard_root.hash() == child.hash,
+                "The root hash of state merkle shard {} is {}, expected {}.",
+                shard_id,
+                shard_root.hash(),
+                child.hash,
+            );
+        }
+    }
+    println!("Verified the shards of the state merkle tree at version {version}.");
+    Ok(())
+}
+
+/// Removes the files of the RocksDB instance at `path`, leaving the sub directories holding other
+/// dbs. `CURRENT` goes first, so a partially removed instance can't be opened.
+fn remove_db_files(path: &Path) -> Result<()> {
+    fs::remove_file(path.join("CURRENT"))?;
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        if entry.file_type()?.is_file() {
+            fs::remove_file(entry.path())?;
+        }
+    }
+    Ok(())
+}
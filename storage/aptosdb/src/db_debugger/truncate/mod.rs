@@ -4,6 +4,7 @@
 use crate::{
     db_debugger::ShardingConfig,
     jellyfish_merkle_node::JellyfishMerkleNodeSchema,
+    ledger_store::LedgerStore,
     schema::{
         db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
         epoch_by_version::EpochByVersionSchema,
@@ -30,7 +31,7 @@ use std::{fs, path::PathBuf, sync::Arc};
 #[clap(about = "Delete all data after the provided version.")]
 #[clap(group(clap::ArgGroup::new("backup")
         .required(true)
-        .args(&["backup_checkpoint_dir", "opt_out_backup_checkpoint"]),
+        .args(&["backup_checkpoint_dir", "opt_out_backup_checkpoint", "dry_run"]),
 ))]
 pub struct Cmd {
     // TODO(grao): Support db_path_overrides here.
@@ -49,13 +50,19 @@ pub struct Cmd {
     #[clap(long, group = "backup")]
     opt_out_backup_checkpoint: bool,
 
+    /// Only verifies and prints the versions the DB would be truncated to, without changing it.
+    #[clap(long, group = "backup")]
+    dry_run: bool,
+
     #[clap(flatten)]
     sharding_config: ShardingConfig,
 }
 
 impl Cmd {
     pub fn run(self) -> Result<()> {
-        if !self.opt_out_backup_checkpoint {
+        if self.dry_run {
+            println!("Dry run, the DB won't be changed.");
+        } else if !self.opt_out_backup_checkpoint {
             let backup_checkpoint_dir = self.backup_checkpoint_dir.unwrap();
             ensure!(
                 !backup_checkpoint_dir.exists(),
@@ -80,7 +87,7 @@ impl Cmd {
         let (ledger_db, state_merkle_db, state_kv_db) = AptosDB::open_dbs(
             &StorageDirPaths::from_path(&self.db_dir),
             rocksdb_config,
-            /*readonly=*/ false,
+            /*readonly=*/ self.dry_run,
             /*max_num_nodes_per_lru_cache_shard=*/ 0,
         )?;
 
@@ -136,8 +143,8 @@ impl Cmd {
 
         // TODO(grao): We are using a brute force implementation for now. We might be able to make
         // it faster, since our data is append only.
-        if target_version < state_merkle_db_version {
-            let state_merkle_target_version = Self::find_tree_root_at_or_before(
+        let state_merkle_target_version = if target_version < state_merkle_db_version {
+            Self::find_tree_root_at_or_before(
                 ledger_db.metadata_db(),
                 &state_merkle_db,
                 target_version,
@@ -147,8 +154,27 @@ impl Cmd {
                     "Could not find a valid root before or at version {}, maybe it was pruned?",
                     target_version
                 )
-            });
+            })
+        } else {
+            state_merkle_db_version
+        };
+
+        Self::verify_versions_to_keep(
+            &LedgerStore::new(Arc::clone(&ledger_db)),
+            &state_merkle_db,
+            target_version,
+            state_merkle_target_version,
+        )?;
+
+        if self.dry_run {
+            println!(
+                "Would truncate ledger db and state kv db to version {}, and state merkle db to version {}.",
+                target_version, state_merkle_target_version,
+            );
+            return Ok(());
+        }
 
+        if state_merkle_target_version < state_merkle_db_version {
             println!(
                 "Starting state merkle db truncation... target_version: {}",
                 state_merkle_target_version
@@ -188,6 +214,53 @@ impl Cmd {
         Ok(())
     }
 
+    /// Checks the data at the versions the DB is truncated to is not corrupted, by verifying the
+    /// TransactionInfo at `target_version` against the latest LedgerInfo, and the state tree
+    /// root at `state_merkle_target_version` against its TransactionInfo.
+    fn verify_versions_to_keep(
+        ledger_store: &LedgerStore,
+        state_merkle_db: &StateMerkleDb,
+        target_version: Version,
+        state_merkle_target_version: Version,
+    ) -> Result<()> {
+        let ledger_info_with_sigs = ledger_store.get_latest_ledger_info()?;
+        let ledger_info = ledger_info_with_sigs.ledger_info();
+        if ledger_info.version() >= target_version {
+            ledger_store
+                .get_transaction_info_with_proof(target_version, ledger_info.version())?
+                .verify(ledger_info, target_version)?;
+            println!(
+                "Verified TransactionInfo at version {} against the LedgerInfo at version {}.",
+                target_version,
+                ledger_info.version(),
+            );
+        } else {
+            println!(
+                "Latest LedgerInfo is at version {}, skipped verifying TransactionInfo at version {}.",
+                ledger_info.version(),
+                target_version,
+            );
+        }
+
+        let state_checkpoint_hash = ledger_store
+            .get_transaction_info(state_merkle_target_version)?
+            .state_checkpoint_hash();
+        let root_hash = state_merkle_db.get_root_hash(state_merkle_target_version)?;
+        ensure!(
+            state_checkpoint_hash == Some(root_hash),
+            "State tree root hash {} at version {} doesn't match the state checkpoint hash {:?} in TransactionInfo.",
+            root_hash,
+            state_merkle_target_version,
+            state_checkpoint_hash,
+        );
+        println!(
+            "Verified state tree root hash at version {}.",
+            state_merkle_target_version
+        );
+
+        Ok(())
+    }
+
     fn find_tree_root_at_or_before(
         ledger_metadata_db: &DB,
         state_merkle_db: &StateMerkleDb,
@@ -282,11 +355,27 @@ mod test {
 
             let mut target_version = db_version - 70;
 
+            let dry_run_cmd = Cmd {
+                db_dir: tmp_dir.path().to_path_buf(),
+                target_version,
+                ledger_db_batch_size: 15,
+                opt_out_backup_checkpoint: false,
+                dry_run: true,
+                backup_checkpoint_dir: None,
+                sharding_config: sharding_config.clone(),
+            };
+            dry_run_cmd.run().unwrap();
+
+            let db = if input.1 { AptosDB::new_for_test_with_sharding(&tmp_dir, DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD) } else { AptosDB::new_for_test(&tmp_dir) };
+            prop_assert_eq!(db.get_latest_version().unwrap(), version - 1);
+            drop(db);
+
             let cmd = Cmd {
                 db_dir: tmp_dir.path().to_path_buf(),
                 target_version,
                 ledger_db_batch_size: 15,
                 opt_out_backup_checkpoint: true,
+                dry_run: false,
                 backup_checkpoint_dir: None,
                 sharding_config: sharding_config.clone(),
             };
@@ -2,21 +2,91 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::schema::*;
-use aptos_config::config::RocksdbConfig;
+use anyhow::Result;
+use aptos_config::config::{
+    RocksdbCfConfig, RocksdbCfConfigs, RocksdbCompressionType, RocksdbConfig,
+};
+use aptos_rocksdb_options::gen_rocksdb_options;
 use aptos_schemadb::{
     BlockBasedOptions, Cache, ColumnFamilyDescriptor, ColumnFamilyName, DBCompressionType, Options,
-    SliceTransform, DEFAULT_COLUMN_FAMILY_NAME,
+    SliceTransform, DB, DEFAULT_COLUMN_FAMILY_NAME,
 };
 use aptos_types::transaction::Version;
+use std::path::PathBuf;
 
 const VERSION_SIZE: usize = std::mem::size_of::<Version>();
 
+/// How the RocksDB instances of AptosDB are opened.
+#[derive(Clone, Debug)]
+pub(super) enum DbOpenMode {
+    ReadWrite,
+    ReadOnly,
+    /// Read only, following a primary instance (which may be running) that owns the DB. Each
+    /// secondary instance keeps its own info logs under `secondary_root`.
+    Secondary {
+        secondary_root: PathBuf,
+    },
+}
+
+impl DbOpenMode {
+    pub fn from_readonly(readonly: bool) -> Self {
+        if readonly {
+            Self::ReadOnly
+        } else {
+            Self::ReadWrite
+        }
+    }
+
+    pub fn is_secondary(&self) -> bool {
+        matches!(self, Self::Secondary { .. })
+    }
+
+    /// Opens the RocksDB instance at `path`. `column_families` are the ones to open in read only
+    /// modes, and `gen_cfds` generates the descriptors of the ones to open or create otherwise.
+    pub fn open_db(
+        &self,
+        path: PathBuf,
+        name: &str,
+        rocksdb_config: &RocksdbConfig,
+        column_families: Vec<ColumnFamilyName>,
+        gen_cfds: impl FnOnce() -> Vec<ColumnFamilyDescriptor>,
+    ) -> Result<DB> {
+        match self {
+            Self::ReadWrite => DB::open_cf(
+                &gen_rocksdb_options(rocksdb_config, false),
+                path,
+                name,
+                gen_cfds(),
+            ),
+            Self::ReadOnly => DB::open_cf_readonly(
+                &gen_rocksdb_options(rocksdb_config, true),
+                path,
+                name,
+                column_families,
+            ),
+            Self::Secondary { secondary_root } => {
+                let mut db_opts = gen_rocksdb_options(rocksdb_config, true);
+                // The primary instance may delete any file, so all of them must be kept open.
+                db_opts.set_max_open_files(-1);
+                DB::open_cf_as_secondary(
+                    &db_opts,
+                    path,
+                    secondary_root.join(name),
+                    name,
+                    column_families,
+                )
+            },
+        }
+    }
+}
+
 pub(super) fn ledger_db_column_families() -> Vec<ColumnFamilyName> {
     vec![
         /* empty cf */ DEFAULT_COLUMN_FAMILY_NAME,
         EPOCH_BY_VERSION_CF_NAME,
         EVENT_ACCUMULATOR_CF_NAME,
         EVENT_BY_KEY_CF_NAME,
+        EVENT_BY_TYPE_CF_NAME,
         EVENT_BY_VERSION_CF_NAME,
         EVENT_CF_NAME,
         LEDGER_INFO_CF_NAME,
@@ -25,6 +95,7 @@ pub(super) fn ledger_db_column_families() -> Vec<ColumnFamilyName> {
         TRANSACTION_CF_NAME,
         TRANSACTION_ACCUMULATOR_CF_NAME,
         TRANSACTION_BY_ACCOUNT_CF_NAME,
+        TRANSACTION_BY_ACCOUNT_TIME_CF_NAME,
         TRANSACTION_BY_HASH_CF_NAME,
         TRANSACTION_INFO_CF_NAME,
         VERSION_DATA_CF_NAME,
@@ -39,6 +110,7 @@ pub(super) fn event_db_column_families() -> Vec<ColumnFamilyName> {
         DB_METADATA_CF_NAME,
         EVENT_ACCUMULATOR_CF_NAME,
         EVENT_BY_KEY_CF_NAME,
+        EVENT_BY_TYPE_CF_NAME,
         EVENT_BY_VERSION_CF_NAME,
         EVENT_CF_NAME,
     ]
@@ -58,6 +130,7 @@ pub(super) fn transaction_db_column_families() -> Vec<ColumnFamilyName> {
         DB_METADATA_CF_NAME,
         TRANSACTION_CF_NAME,
         TRANSACTION_BY_ACCOUNT_CF_NAME,
+        TRANSACTION_BY_ACCOUNT_TIME_CF_NAME,
         TRANSACTION_BY_HASH_CF_NAME,
     ]
 }
@@ -78,6 +151,15 @@ pub(super) fn write_set_db_column_families() -> Vec<ColumnFamilyName> {
     ]
 }
 
+pub(super) fn cold_ledger_db_column_families() -> Vec<ColumnFamilyName> {
+    vec![
+        /* empty cf */ DEFAULT_COLUMN_FAMILY_NAME,
+        DB_METADATA_CF_NAME,
+        TRANSACTION_CF_NAME,
+        WRITE_SET_CF_NAME,
+    ]
+}
+
 pub(super) fn ledger_metadata_db_column_families() -> Vec<ColumnFamilyName> {
     vec![
         /* empty cf */ DEFAULT_COLUMN_FAMILY_NAME,
@@ -111,21 +193,38 @@ pub(super) fn state_kv_db_column_families() -> Vec<ColumnFamilyName> {
 
 fn gen_cfds<F>(
     rocksdb_config: &RocksdbConfig,
+    cf_configs: &RocksdbCfConfigs,
     cfs: Vec<ColumnFamilyName>,
     cf_opts_post_processor: F,
 ) -> Vec<ColumnFamilyDescriptor>
 where
     F: Fn(ColumnFamilyName, &mut Options),
 {
-    let mut table_options = BlockBasedOptions::default();
-    table_options.set_cache_index_and_filter_blocks(rocksdb_config.cache_index_and_filter_blocks);
-    table_options.set_block_size(rocksdb_config.block_size as usize);
+    // The column families without a dedicated block cache share the one of the db.
     let cache = Cache::new_lru_cache(rocksdb_config.block_cache_size as usize);
-    table_options.set_block_cache(&cache);
     let mut cfds = Vec::with_capacity(cfs.len());
     for cf_name in cfs {
+        let cf_config = get_cf_config(cf_configs, cf_name);
+        let mut table_options = BlockBasedOptions::default();
+        table_options
+            .set_cache_index_and_filter_blocks(rocksdb_config.cache_index_and_filter_blocks);
+        table_options.set_block_size(rocksdb_config.block_size as usize);
+        match cf_config.block_cache_size {
+            Some(block_cache_size) => {
+                table_options.set_block_cache(&Cache::new_lru_cache(block_cache_size as usize))
+            },
+            None => table_options.set_block_cache(&cache),
+        }
+        if let Some(bits_per_key) = cf_config.bloom_filter_bits_per_key {
+            table_options.set_bloom_filter(bits_per_key as f64, false);
+        }
+
         let mut cf_opts = Options::default();
-        cf_opts.set_compression_type(DBCompressionType::Lz4);
+        cf_opts.set_compression_type(
+            cf_config
+                .compression
+                .map_or(DBCompressionType::Lz4, to_db_compression_type),
+        );
         cf_opts.set_block_based_table_factory(&table_options);
         cf_opts_post_processor(cf_name, &mut cf_opts);
         cfds.push(ColumnFamilyDescriptor::new((*cf_name).to_string(), cf_opts));
@@ -133,6 +232,23 @@ where
     cfds
 }
 
+fn get_cf_config(cf_configs: &RocksdbCfConfigs, cf_name: ColumnFamilyName) -> RocksdbCfConfig {
+    match cf_name {
+        STATE_VALUE_CF_NAME => cf_configs.state_value,
+        JELLYFISH_MERKLE_NODE_CF_NAME => cf_configs.jellyfish_merkle_node,
+        TRANSACTION_CF_NAME => cf_configs.transaction,
+        _ => RocksdbCfConfig::default(),
+    }
+}
+
+fn to_db_compression_type(compression: RocksdbCompressionType) -> DBCompressionType {
+    match compression {
+        RocksdbCompressionType::None => DBCompressionType::None,
+        RocksdbCompressionType::Lz4 => DBCompressionType::Lz4,
+        RocksdbCompressionType::Lz4hc => DBCompressionType::Lz4hc,
+    }
+}
+
 fn with_state_key_extractor_processor(cf_name: ColumnFamilyName, cf_opts: &mut Options) {
     if cf_name == STATE_VALUE_CF_NAME {
         let prefix_extractor =
@@ -141,55 +257,94 @@ fn with_state_key_extractor_processor(cf_name: ColumnFamilyName, cf_opts: &mut O
     }
 }
 
-pub(super) fn gen_event_cfds(rocksdb_config: &RocksdbConfig) -> Vec<ColumnFamilyDescriptor> {
+pub(super) fn gen_event_cfds(
+    rocksdb_config: &RocksdbConfig,
+    cf_configs: &RocksdbCfConfigs,
+) -> Vec<ColumnFamilyDescriptor> {
     let cfs = event_db_column_families();
-    gen_cfds(rocksdb_config, cfs, |_, _| {})
+    gen_cfds(rocksdb_config, cf_configs, cfs, |_, _| {})
 }
 
 pub(super) fn gen_transaction_accumulator_cfds(
     rocksdb_config: &RocksdbConfig,
+    cf_configs: &RocksdbCfConfigs,
 ) -> Vec<ColumnFamilyDescriptor> {
     let cfs = transaction_accumulator_db_column_families();
-    gen_cfds(rocksdb_config, cfs, |_, _| {})
+    gen_cfds(rocksdb_config, cf_configs, cfs, |_, _| {})
 }
 
-pub(super) fn gen_transaction_cfds(rocksdb_config: &RocksdbConfig) -> Vec<ColumnFamilyDescriptor> {
+pub(super) fn gen_transaction_cfds(
+    rocksdb_config: &RocksdbConfig,
+    cf_configs: &RocksdbCfConfigs,
+) -> Vec<ColumnFamilyDescriptor> {
     let cfs = transaction_db_column_families();
-    gen_cfds(rocksdb_config, cfs, |_, _| {})
+    gen_cfds(rocksdb_config, cf_configs, cfs, |_, _| {})
 }
 
 pub(super) fn gen_transaction_info_cfds(
     rocksdb_config: &RocksdbConfig,
+    cf_configs: &RocksdbCfConfigs,
 ) -> Vec<ColumnFamilyDescriptor> {
     let cfs = transaction_info_db_column_families();
-    gen_cfds(rocksdb_config, cfs, |_, _| {})
+    gen_cfds(rocksdb_config, cf_configs, cfs, |_, _| {})
 }
 
-pub(super) fn gen_write_set_cfds(rocksdb_config: &RocksdbConfig) -> Vec<ColumnFamilyDescriptor> {
+pub(super) fn gen_write_set_cfds(
+    rocksdb_config: &RocksdbConfig,
+    cf_configs: &RocksdbCfConfigs,
+) -> Vec<ColumnFamilyDescriptor> {
     let cfs = write_set_db_column_families();
-    gen_cfds(rocksdb_config, cfs, |_, _| {})
+    gen_cfds(rocksdb_config, cf_configs, cfs, |_, _| {})
+}
+
+pub(super) fn gen_cold_ledger_cfds(
+    rocksdb_config: &RocksdbConfig,
+    cf_configs: &RocksdbCfConfigs,
+) -> Vec<ColumnFamilyDescriptor> {
+    let cfs = cold_ledger_db_column_families();
+    gen_cfds(rocksdb_config, cf_configs, cfs, |_, _| {})
 }
 
 pub(super) fn gen_ledger_metadata_cfds(
     rocksdb_config: &RocksdbConfig,
+    cf_configs: &RocksdbCfConfigs,
 ) -> Vec<ColumnFamilyDescriptor> {
     let cfs = ledger_metadata_db_column_families();
-    gen_cfds(rocksdb_config, cfs, |_, _| {})
+    gen_cfds(rocksdb_config, cf_configs, cfs, |_, _| {})
 }
 
-pub(super) fn gen_ledger_cfds(rocksdb_config: &RocksdbConfig) -> Vec<ColumnFamilyDescriptor> {
+pub(super) fn gen_ledger_cfds(
+    rocksdb_config: &RocksdbConfig,
+    cf_configs: &RocksdbCfConfigs,
+) -> Vec<ColumnFamilyDescriptor> {
     let cfs = ledger_db_column_families();
-    gen_cfds(rocksdb_config, cfs, with_state_key_extractor_processor)
+    gen_cfds(
+        rocksdb_config,
+        cf_configs,
+        cfs,
+        with_state_key_extractor_processor,
+    )
 }
 
-pub(super) fn gen_state_merkle_cfds(rocksdb_config: &RocksdbConfig) -> Vec<ColumnFamilyDescriptor> {
+pub(super) fn gen_state_merkle_cfds(
+    rocksdb_config: &RocksdbConfig,
+    cf_configs: &RocksdbCfConfigs,
+) -> Vec<ColumnFamilyDescriptor> {
     let cfs = state_merkle_db_column_families();
-    gen_cfds(rocksdb_config, cfs, |_, _| {})
+    gen_cfds(rocksdb_config, cf_configs, cfs, |_, _| {})
 }
 
-pub(super) fn gen_state_kv_cfds(rocksdb_config: &RocksdbConfig) -> Vec<ColumnFamilyDescriptor> {
+pub(super) fn gen_state_kv_cfds(
+    rocksdb_config: &RocksdbConfig,
+    cf_configs: &RocksdbCfConfigs,
+) -> Vec<ColumnFamilyDescriptor> {
     let cfs = state_kv_db_column_families();
-    gen_cfds(rocksdb_config, cfs, with_state_key_extractor_processor)
+    gen_cfds(
+        rocksdb_config,
+        cf_configs,
+        cfs,
+        with_state_key_extractor_processor,
+    )
 }
 
 fn state_key_extractor(state_value_raw_key: &[u8]) -> &[u8] {
@@ -10,8 +10,12 @@ use super::AptosDB;
 use crate::{
     errors::AptosDbError,
     schema::{
-        event::EventSchema, event_accumulator::EventAccumulatorSchema,
-        event_by_key::EventByKeySchema, event_by_version::EventByVersionSchema,
+        db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
+        event::EventSchema,
+        event_accumulator::EventAccumulatorSchema,
+        event_by_key::EventByKeySchema,
+        event_by_type::EventByTypeSchema,
+        event_by_version::EventByVersionSchema,
     },
     utils::iterators::EventsByVersionIter,
 };
@@ -30,6 +34,7 @@ use aptos_types::{
     proof::position::Position,
     transaction::Version,
 };
+use move_core_types::language_storage::TypeTag;
 use std::{
     collections::{hash_map::Entry, HashMap, HashSet},
     convert::{TryFrom, TryInto},
@@ -40,11 +45,68 @@ use std::{
 #[derive(Debug)]
 pub struct EventStore {
     event_db: Arc<DB>,
+    /// Whether the events being put are indexed by type in `EventByTypeSchema`.
+    enable_type_index: bool,
 }
 
 impl EventStore {
-    pub fn new(event_db: Arc<DB>) -> Self {
-        Self { event_db }
+    pub fn new(event_db: Arc<DB>, enable_type_index: bool) -> Self {
+        Self {
+            event_db,
+            enable_type_index,
+        }
+    }
+
+    /// Returns the first version from which on the events are indexed by type, or `None` if the
+    /// index is disabled. The index doesn't cover the versions committed before it was enabled.
+    pub fn get_type_index_start_version(&self) -> Result<Option<Version>> {
+        Ok(self
+            .event_db
+            .get::<DbMetadataSchema>(&DbMetadataKey::EventTypeIndexStartVersion)?
+            .map(|v| v.expect_version()))
+    }
+
+    /// Records where the type index starts when it's enabled, with `next_version` being the first
+    /// version to be committed, and forgets it when it's disabled, so that a gap left by running
+    /// with the index disabled is never served.
+    pub(crate) fn init_type_index(&self, next_version: Version) -> Result<()> {
+        let start_version = self.get_type_index_start_version()?;
+        if self.enable_type_index && start_version.is_none() {
+            self.event_db.put::<DbMetadataSchema>(
+                &DbMetadataKey::EventTypeIndexStartVersion,
+                &DbMetadataValue::Version(next_version),
+            )?;
+        } else if !self.enable_type_index && start_version.is_some() {
+            let batch = SchemaBatch::new();
+            batch.delete::<DbMetadataSchema>(&DbMetadataKey::EventTypeIndexStartVersion)?;
+            self.event_db.write_schemas(batch)?;
+        }
+        Ok(())
+    }
+
+    /// Gets an iterator that yields the `(version, index)` of the events of type `type_tag`, from
+    /// the event at (`start_version`, `start_index`) on, ending before the first one with
+    /// `version > ledger_version`.
+    pub fn get_events_by_type_iter(
+        &self,
+        type_tag: &TypeTag,
+        start_version: Version,
+        start_index: u64,
+        ledger_version: Version,
+    ) -> Result<impl Iterator<Item = Result<(Version, u64)>> + '_> {
+        let type_tag_hash = hash_type_tag(type_tag)?;
+        let mut iter = self
+            .event_db
+            .iter::<EventByTypeSchema>(ReadOptions::default())?;
+        iter.seek(&(type_tag_hash, start_version, start_index))?;
+
+        Ok(iter
+            .map(|res| res.map(|(key, ())| key))
+            .take_while(move |res| match res {
+                Ok((hash, version, _index)) => *hash == type_tag_hash && *version <= ledger_version,
+                Err(_) => true,
+            })
+            .map(|res| res.map(|(_hash, version, index)| (version, index))))
     }
 
     /// Get all of the events given a transaction version.
@@ -303,6 +365,20 @@ impl EventStore {
         Ok((first_version, payload))
     }
 
+    /// Returns the timestamp of the block `version` is in, or `None` if its `NewBlockEvent` isn't
+    /// indexed (e.g. when the event index is skipped).
+    pub fn get_block_timestamp_usecs_if_indexed(&self, version: Version) -> Result<Option<u64>> {
+        match self.lookup_event_before_or_at_version(&new_block_event_key(), version)? {
+            Some((block_version, event_index, _seq_num)) => {
+                let new_block_event =
+                    self.get_event_by_version_and_index(block_version, event_index)?;
+                let payload: NewBlockEvent = bcs::from_bytes(new_block_event.event_data())?;
+                Ok(Some(payload.proposed_time()))
+            },
+            None => Ok(None),
+        }
+    }
+
     /// Save contract events yielded by the transaction at `version` and return root hash of the
     /// event accumulator formed by these events.
     pub fn put_events(
@@ -329,6 +405,12 @@ impl EventStore {
                         )?;
                     }
                 }
+                if self.enable_type_index {
+                    batch.put::<EventByTypeSchema>(
+                        &(hash_type_tag(event.type_tag())?, version, idx as u64),
+                        &(),
+                    )?;
+                }
                 batch.put::<EventSchema>(&(version, idx as u64), event)
             })?;
 
@@ -482,6 +564,12 @@ impl EventStore {
         let mut current_version = start;
         for events in self.get_events_by_version_iter(start, (end - start) as usize)? {
             for (idx, event) in (events?).into_iter().enumerate() {
+                // Deleted even if the type index is disabled now, in case it was enabled before.
+                db_batch.delete::<EventByTypeSchema>(&(
+                    hash_type_tag(event.type_tag())?,
+                    current_version,
+                    idx as u64,
+                ))?;
                 if let ContractEvent::V1(v1) = event {
                     db_batch.delete::<EventByVersionSchema>(&(
                         *v1.key(),
@@ -499,6 +587,11 @@ impl EventStore {
     }
 }
 
+/// The key of a type in `EventByTypeSchema`.
+fn hash_type_tag(type_tag: &TypeTag) -> Result<HashValue> {
+    Ok(HashValue::sha3_256_of(&bcs::to_bytes(type_tag)?))
+}
+
 struct EventHashReader<'a> {
     store: &'a EventStore,
     version: Version,
@@ -359,3 +359,59 @@ proptest! {
         test_get_last_version_before_timestamp_impl(new_block_events)
     }
 }
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(10))]
+
+    #[test]
+    fn test_type_index(
+        event_batches in vec(vec(any::<ContractEvent>().no_shrink(), 1..10), 1..10),
+        ledger_version in 0_u64..10,
+        num_pruned in 0_u64..10,
+    ) {
+        let tmp_dir = TempPath::new();
+        let db = AptosDB::new_for_test(&tmp_dir);
+        let store = EventStore::new(db.ledger_db.event_db_arc(), /*enable_type_index=*/ true);
+        for (version, events) in event_batches.iter().enumerate() {
+            save(&store, version as Version, events);
+        }
+
+        let expected_positions = |type_tag: &TypeTag, min_version: Version| {
+            event_batches
+                .iter()
+                .enumerate()
+                .flat_map(|(version, events)| {
+                    events
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, event)| event.type_tag() == type_tag)
+                        .map(move |(index, _)| (version as Version, index as u64))
+                })
+                .filter(|(version, _)| *version >= min_version && *version <= ledger_version)
+                .collect::<Vec<_>>()
+        };
+        let actual_positions = |type_tag: &TypeTag| {
+            store
+                .get_events_by_type_iter(type_tag, 0, 0, ledger_version)
+                .unwrap()
+                .collect::<Result<Vec<_>>>()
+                .unwrap()
+        };
+
+        for event in event_batches.iter().flatten() {
+            prop_assert_eq!(actual_positions(event.type_tag()), expected_positions(event.type_tag(), 0));
+        }
+
+        let num_pruned = std::cmp::min(num_pruned, event_batches.len() as Version);
+        let batch = SchemaBatch::new();
+        store.prune_events(0, num_pruned, &batch).unwrap();
+        store.event_db.write_schemas(batch).unwrap();
+
+        for event in event_batches.iter().flatten() {
+            prop_assert_eq!(
+                actual_positions(event.type_tag()),
+                expected_positions(event.type_tag(), num_pruned)
+            );
+        }
+    }
+}
@@ -16,7 +16,7 @@ use aptos_crypto::{
 use aptos_infallible::Mutex;
 use aptos_storage_interface::{
     cached_state_view::ShardedStateCache, state_delta::StateDelta, DbReader, DbWriter,
-    ExecutedTrees, MAX_REQUEST_LIMIT,
+    ExecutedTrees, PrunerType, MAX_REQUEST_LIMIT,
 };
 use aptos_types::{
     access_path::AccessPath,
@@ -48,7 +48,7 @@ use aptos_types::{
 };
 use dashmap::DashMap;
 use itertools::zip_eq;
-use move_core_types::move_resource::MoveStructType;
+use move_core_types::{language_storage::TypeTag, move_resource::MoveStructType};
 use std::{
     borrow::Borrow,
     collections::HashMap,
@@ -399,6 +399,16 @@ impl DbWriter for FakeAptosDB {
             latest_in_memory_state,
         )
     }
+
+    fn update_pruner_config(
+        &self,
+        pruner_type: PrunerType,
+        prune_window: Version,
+        batch_size: usize,
+    ) -> Result<()> {
+        self.inner
+            .update_pruner_config(pruner_type, prune_window, batch_size)
+    }
 }
 
 impl DbReader for FakeAptosDB {
@@ -520,6 +530,10 @@ impl DbReader for FakeAptosDB {
         self.inner.get_first_write_set_version()
     }
 
+    fn get_first_state_kv_version(&self) -> Result<Version> {
+        self.inner.get_first_state_kv_version()
+    }
+
     fn get_transaction_outputs(
         &self,
         start_version: Version,
@@ -585,6 +599,18 @@ impl DbReader for FakeAptosDB {
             .get_events(event_key, start, order, limit, ledger_version)
     }
 
+    fn get_events_by_type(
+        &self,
+        type_tag: &TypeTag,
+        start_version: Version,
+        start_index: u64,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<EventWithVersion>> {
+        self.inner
+            .get_events_by_type(type_tag, start_version, start_index, limit, ledger_version)
+    }
+
     fn get_block_timestamp(&self, version: Version) -> Result<u64> {
         gauged_api("get_block_timestamp", || {
             ensure!(version <= self.get_latest_version()?);
@@ -680,6 +706,25 @@ impl DbReader for FakeAptosDB {
             .get_account_transactions(address, seq_num, limit, include_events, ledger_version)
     }
 
+    fn get_account_transactions_in_time_range(
+        &self,
+        address: aptos_types::PeerId,
+        start_timestamp_usecs: u64,
+        end_timestamp_usecs: u64,
+        limit: u64,
+        include_events: bool,
+        ledger_version: Version,
+    ) -> Result<aptos_types::transaction::AccountTransactionsWithProof> {
+        self.inner.get_account_transactions_in_time_range(
+            address,
+            start_timestamp_usecs,
+            end_timestamp_usecs,
+            limit,
+            include_events,
+            ledger_version,
+        )
+    }
+
     fn get_state_proof_with_ledger_info(
         &self,
         known_version: u64,
@@ -841,6 +886,17 @@ impl DbReader for FakeAptosDB {
         self.inner.get_table_info(handle)
     }
 
+    fn get_table_item_keys(
+        &self,
+        handle: table::TableHandle,
+        version: Version,
+        start_after: Option<HashValue>,
+        limit: usize,
+    ) -> Result<Option<Vec<Vec<u8>>>> {
+        self.inner
+            .get_table_item_keys(handle, version, start_after, limit)
+    }
+
     fn indexer_enabled(&self) -> bool {
         self.inner.indexer_enabled()
     }
@@ -3,11 +3,11 @@
 
 use crate::AptosDB;
 use anyhow::{anyhow, Result};
-use aptos_config::config::{NodeConfig, StorageDirPaths};
+use aptos_config::config::{NodeConfig, StorageDirPaths, NO_OP_LEDGER_TIERING_CONFIG};
 use aptos_crypto::HashValue;
 use aptos_infallible::RwLock;
 use aptos_storage_interface::{
-    cached_state_view::ShardedStateCache, state_delta::StateDelta, DbReader, DbWriter,
+    cached_state_view::ShardedStateCache, state_delta::StateDelta, DbReader, DbWriter, PrunerType,
     StateSnapshotReceiver,
 };
 use aptos_types::{
@@ -42,16 +42,21 @@ impl FastSyncStorageWrapper {
     /// If the db is empty and configured to do fast sync, we return a FastSyncStorageWrapper
     /// Otherwise, we returns AptosDB directly and the FastSyncStorageWrapper is None
     pub fn initialize_dbs(config: &NodeConfig) -> Result<Either<AptosDB, Self>> {
-        let db_main = AptosDB::open(
+        let mut db_main = AptosDB::open(
             config.storage.get_dir_paths(),
             /*readonly=*/ false,
             config.storage.storage_pruner_config,
+            &config.storage.ledger_tiering_config,
             config.storage.rocksdb_configs,
             config.storage.enable_indexer,
+            config.storage.enable_event_type_index,
             config.storage.buffered_state_target_items,
             config.storage.max_num_nodes_per_lru_cache_shard,
         )
         .map_err(|err| anyhow!("fast sync DB failed to open {}", err))?;
+        // The main db holds the synced state, whether it's fast synced or not.
+        db_main
+            .maybe_start_state_consistency_auditor(config.storage.state_consistency_auditor_config);
 
         let mut db_dir = config.storage.dir();
         // when the db is empty and configured to do fast sync, we will create a second DB
@@ -67,8 +72,10 @@ impl FastSyncStorageWrapper {
                 StorageDirPaths::from_path(db_dir.as_path()),
                 /*readonly=*/ false,
                 config.storage.storage_pruner_config,
+                &NO_OP_LEDGER_TIERING_CONFIG,
                 config.storage.rocksdb_configs,
                 config.storage.enable_indexer,
+                config.storage.enable_event_type_index,
                 config.storage.buffered_state_target_items,
                 config.storage.max_num_nodes_per_lru_cache_shard,
             )
@@ -176,6 +183,17 @@ impl DbWriter for FastSyncStorageWrapper {
             sharded_state_cache,
         )
     }
+
+    fn update_pruner_config(
+        &self,
+        pruner_type: PrunerType,
+        prune_window: Version,
+        batch_size: usize,
+    ) -> Result<()> {
+        // The temporary db only holds genesis, so only the main db is pruned
+        self.db_for_fast_sync
+            .update_pruner_config(pruner_type, prune_window, batch_size)
+    }
 }
 
 impl DbReader for FastSyncStorageWrapper {
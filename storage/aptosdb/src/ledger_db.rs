@@ -5,19 +5,19 @@
 #![allow(dead_code)]
 
 use crate::{
+    cold_ledger_db::ColdLedgerDb,
     db_options::{
         event_db_column_families, gen_event_cfds, gen_ledger_cfds, gen_ledger_metadata_cfds,
         gen_transaction_accumulator_cfds, gen_transaction_cfds, gen_transaction_info_cfds,
         gen_write_set_cfds, ledger_db_column_families, ledger_metadata_db_column_families,
         transaction_accumulator_db_column_families, transaction_db_column_families,
-        transaction_info_db_column_families, write_set_db_column_families,
+        transaction_info_db_column_families, write_set_db_column_families, DbOpenMode,
     },
     schema::db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
 };
 use anyhow::Result;
-use aptos_config::config::{RocksdbConfig, RocksdbConfigs};
+use aptos_config::config::RocksdbConfigs;
 use aptos_logger::prelude::info;
-use aptos_rocksdb_options::gen_rocksdb_options;
 use aptos_schemadb::{ColumnFamilyDescriptor, ColumnFamilyName, SchemaBatch, DB};
 use aptos_types::transaction::Version;
 use std::{
@@ -71,13 +71,16 @@ pub struct LedgerDb {
     transaction_db: Arc<DB>,
     transaction_info_db: Arc<DB>,
     write_set_db: Arc<DB>,
+    /// The transactions and write sets older than the tiering horizon, if ledger tiering is
+    /// enabled.
+    cold_ledger_db: Option<Arc<ColdLedgerDb>>,
 }
 
 impl LedgerDb {
     pub(crate) fn new<P: AsRef<Path>>(
         db_root_path: P,
         rocksdb_configs: RocksdbConfigs,
-        readonly: bool,
+        open_mode: &DbOpenMode,
     ) -> Result<Self> {
         let sharding = rocksdb_configs.enable_storage_sharding;
         let ledger_metadata_db_path = Self::metadata_db_path(db_root_path.as_ref(), sharding);
@@ -88,8 +91,8 @@ impl LedgerDb {
             } else {
                 LEDGER_DB_NAME
             },
-            &rocksdb_configs.ledger_db_config,
-            readonly,
+            &rocksdb_configs,
+            open_mode,
         )?);
 
         info!(
@@ -107,6 +110,7 @@ impl LedgerDb {
                 transaction_db: Arc::clone(&ledger_metadata_db),
                 transaction_info_db: Arc::clone(&ledger_metadata_db),
                 write_set_db: Arc::clone(&ledger_metadata_db),
+                cold_ledger_db: None,
             });
         }
 
@@ -115,36 +119,36 @@ impl LedgerDb {
         let event_db = Arc::new(Self::open_rocksdb(
             ledger_db_folder.join(EVENT_DB_NAME),
             EVENT_DB_NAME,
-            &rocksdb_configs.ledger_db_config,
-            readonly,
+            &rocksdb_configs,
+            open_mode,
         )?);
 
         let transaction_accumulator_db = Arc::new(Self::open_rocksdb(
             ledger_db_folder.join(TRANSACTION_ACCUMULATOR_DB_NAME),
             TRANSACTION_ACCUMULATOR_DB_NAME,
-            &rocksdb_configs.ledger_db_config,
-            readonly,
+            &rocksdb_configs,
+            open_mode,
         )?);
 
         let transaction_db = Arc::new(Self::open_rocksdb(
             ledger_db_folder.join(TRANSACTION_DB_NAME),
             TRANSACTION_DB_NAME,
-            &rocksdb_configs.ledger_db_config,
-            readonly,
+            &rocksdb_configs,
+            open_mode,
         )?);
 
         let transaction_info_db = Arc::new(Self::open_rocksdb(
             ledger_db_folder.join(TRANSACTION_INFO_DB_NAME),
             TRANSACTION_INFO_DB_NAME,
-            &rocksdb_configs.ledger_db_config,
-            readonly,
+            &rocksdb_configs,
+            open_mode,
         )?);
 
         let write_set_db = Arc::new(Self::open_rocksdb(
             ledger_db_folder.join(WRITE_SET_DB_NAME),
             WRITE_SET_DB_NAME,
-            &rocksdb_configs.ledger_db_config,
-            readonly,
+            &rocksdb_configs,
+            open_mode,
         )?);
 
         // TODO(grao): Handle data inconsistency.
@@ -156,9 +160,25 @@ impl LedgerDb {
             transaction_db,
             transaction_info_db,
             write_set_db,
+            cold_ledger_db: None,
         })
     }
 
+    /// Opens the cold ledger db, from which the versions below its horizon are read.
+    pub(crate) fn open_cold_ledger_db<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        rocksdb_configs: &RocksdbConfigs,
+        readonly: bool,
+    ) -> Result<()> {
+        self.cold_ledger_db = Some(Arc::new(ColdLedgerDb::new(
+            path,
+            rocksdb_configs,
+            readonly,
+        )?));
+        Ok(())
+    }
+
     pub(crate) fn create_checkpoint(
         db_root_path: impl AsRef<Path>,
         cp_root_path: impl AsRef<Path>,
@@ -168,7 +188,7 @@ impl LedgerDb {
             enable_storage_sharding: sharding,
             ..Default::default()
         };
-        let ledger_db = Self::new(db_root_path, rocksdb_configs, /*readonly=*/ false)?;
+        let ledger_db = Self::new(db_root_path, rocksdb_configs, &DbOpenMode::ReadWrite)?;
         let cp_ledger_db_folder = cp_root_path.as_ref().join(LEDGER_DB_FOLDER_NAME);
 
         info!(
@@ -235,6 +255,18 @@ impl LedgerDb {
         )
     }
 
+    /// Catches up with the primary instance, for a ledger db opened as secondary. The metadata
+    /// db, which holds the ledger infos, is caught up last, as it's written last on commit.
+    pub(crate) fn try_catch_up_with_primary(&self) -> Result<()> {
+        self.write_set_db.try_catch_up_with_primary()?;
+        self.transaction_info_db.try_catch_up_with_primary()?;
+        self.transaction_db.try_catch_up_with_primary()?;
+        self.event_db.try_catch_up_with_primary()?;
+        self.transaction_accumulator_db
+            .try_catch_up_with_primary()?;
+        self.ledger_metadata_db.try_catch_up_with_primary()
+    }
+
     pub fn metadata_db(&self) -> &DB {
         &self.ledger_metadata_db
     }
@@ -283,27 +315,27 @@ impl LedgerDb {
         Arc::clone(&self.write_set_db)
     }
 
+    pub(crate) fn cold_ledger_db(&self) -> Option<&ColdLedgerDb> {
+        self.cold_ledger_db.as_deref()
+    }
+
+    pub(crate) fn cold_ledger_db_arc(&self) -> Option<Arc<ColdLedgerDb>> {
+        self.cold_ledger_db.clone()
+    }
+
     fn open_rocksdb(
         path: PathBuf,
         name: &str,
-        db_config: &RocksdbConfig,
-        readonly: bool,
+        rocksdb_configs: &RocksdbConfigs,
+        open_mode: &DbOpenMode,
     ) -> Result<DB> {
-        let db = if readonly {
-            DB::open_cf_readonly(
-                &gen_rocksdb_options(db_config, true),
-                path.clone(),
-                name,
-                Self::get_column_families_by_name(name),
-            )?
-        } else {
-            DB::open_cf(
-                &gen_rocksdb_options(db_config, false),
-                path.clone(),
-                name,
-                Self::gen_cfds_by_name(db_config, name),
-            )?
-        };
+        let db = open_mode.open_db(
+            path.clone(),
+            name,
+            &rocksdb_configs.ledger_db_config,
+            Self::get_column_families_by_name(name),
+            || Self::gen_cfds_by_name(rocksdb_configs, name),
+        )?;
 
         info!("Opened {name} at {path:?}!");
 
@@ -323,15 +355,22 @@ impl LedgerDb {
         }
     }
 
-    fn gen_cfds_by_name(db_config: &RocksdbConfig, name: &str) -> Vec<ColumnFamilyDescriptor> {
+    fn gen_cfds_by_name(
+        rocksdb_configs: &RocksdbConfigs,
+        name: &str,
+    ) -> Vec<ColumnFamilyDescriptor> {
+        let db_config = &rocksdb_configs.ledger_db_config;
+        let cf_configs = &rocksdb_configs.column_family_configs;
         match name {
-            LEDGER_DB_NAME => gen_ledger_cfds(db_config),
-            LEDGER_METADATA_DB_NAME => gen_ledger_metadata_cfds(db_config),
-            EVENT_DB_NAME => gen_event_cfds(db_config),
-            TRANSACTION_ACCUMULATOR_DB_NAME => gen_transaction_accumulator_cfds(db_config),
-            TRANSACTION_DB_NAME => gen_transaction_cfds(db_config),
-            TRANSACTION_INFO_DB_NAME => gen_transaction_info_cfds(db_config),
-            WRITE_SET_DB_NAME => gen_write_set_cfds(db_config),
+            LEDGER_DB_NAME => gen_ledger_cfds(db_config, cf_configs),
+            LEDGER_METADATA_DB_NAME => gen_ledger_metadata_cfds(db_config, cf_configs),
+            EVENT_DB_NAME => gen_event_cfds(db_config, cf_configs),
+            TRANSACTION_ACCUMULATOR_DB_NAME => {
+                gen_transaction_accumulator_cfds(db_config, cf_configs)
+            },
+            TRANSACTION_DB_NAME => gen_transaction_cfds(db_config, cf_configs),
+            TRANSACTION_INFO_DB_NAME => gen_transaction_info_cfds(db_config, cf_configs),
+            WRITE_SET_DB_NAME => gen_write_set_cfds(db_config, cf_configs),
             _ => unreachable!(),
         }
     }
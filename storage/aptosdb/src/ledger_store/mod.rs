@@ -51,17 +51,8 @@ pub struct LedgerStore {
 impl LedgerStore {
     pub fn new(ledger_db: Arc<LedgerDb>) -> Self {
         // Upon restart, read the latest ledger info and signatures and cache them in memory.
-        let ledger_info = {
-            let mut iter = ledger_db
-                .metadata_db()
-                .iter::<LedgerInfoSchema>(ReadOptions::default())
-                .expect("Constructing iterator should work.");
-            iter.seek_to_last();
-            iter.next()
-                .transpose()
-                .expect("Reading latest ledger info from DB should work.")
-                .map(|kv| kv.1)
-        };
+        let ledger_info = Self::read_latest_ledger_info(&ledger_db)
+            .expect("Reading latest ledger info from DB should work.");
 
         Self {
             ledger_db,
@@ -69,6 +60,22 @@ impl LedgerStore {
         }
     }
 
+    fn read_latest_ledger_info(ledger_db: &LedgerDb) -> Result<Option<LedgerInfoWithSignatures>> {
+        let mut iter = ledger_db
+            .metadata_db()
+            .iter::<LedgerInfoSchema>(ReadOptions::default())?;
+        iter.seek_to_last();
+        Ok(iter.next().transpose()?.map(|kv| kv.1))
+    }
+
+    /// Reloads the cached latest ledger info from the DB, for a DB written by another instance.
+    pub fn refresh_latest_ledger_info(&self) -> Result<()> {
+        if let Some(ledger_info_with_sigs) = Self::read_latest_ledger_info(&self.ledger_db)? {
+            self.set_latest_ledger_info(ledger_info_with_sigs);
+        }
+        Ok(())
+    }
+
     pub fn get_epoch(&self, version: Version) -> Result<u64> {
         let mut iter = self
             .ledger_db
@@ -23,14 +23,17 @@ pub mod schema;
 pub mod state_restore;
 pub mod utils;
 
+mod cold_ledger_db;
 mod db_options;
 mod event_store;
 mod ledger_db;
 mod ledger_store;
 mod lru_node_cache;
 mod pruner;
+mod state_consistency_auditor;
 mod state_kv_db;
 mod state_merkle_db;
+mod state_proof_cache;
 mod state_store;
 mod transaction_store;
 mod versioned_node_cache;
@@ -47,12 +50,13 @@ use crate::state_store::buffered_state::BufferedState;
 use crate::{
     backup::{backup_handler::BackupHandler, restore_handler::RestoreHandler, restore_utils},
     block_index::BlockIndexSchema,
+    cold_ledger_db::COLD_LEDGER_DB_NAME,
     db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
     db_options::{
         event_db_column_families, ledger_db_column_families, ledger_metadata_db_column_families,
         state_kv_db_column_families, state_merkle_db_column_families,
         transaction_accumulator_db_column_families, transaction_db_column_families,
-        transaction_info_db_column_families, write_set_db_column_families,
+        transaction_info_db_column_families, write_set_db_column_families, DbOpenMode,
     },
     errors::AptosDbError,
     event_store::EventStore,
@@ -62,22 +66,29 @@ use crate::{
         API_LATENCY_SECONDS, COMMITTED_TXNS, LATEST_TXN_VERSION, LEDGER_VERSION, NEXT_BLOCK_EPOCH,
         OTHER_TIMERS_SECONDS, ROCKSDB_PROPERTIES,
     },
-    pruner::{LedgerPrunerManager, PrunerManager, StateKvPrunerManager, StateMerklePrunerManager},
+    pruner::{
+        LedgerPrunerManager, LedgerTieringManager, PrunerManager, StateKvPrunerManager,
+        StateMerklePrunerManager,
+    },
     schema::*,
     stale_node_index::StaleNodeIndexSchema,
     stale_node_index_cross_epoch::StaleNodeIndexCrossEpochSchema,
+    state_consistency_auditor::StateConsistencyAuditor,
     state_kv_db::StateKvDb,
     state_merkle_db::StateMerkleDb,
     state_store::StateStore,
-    transaction_store::TransactionStore,
+    transaction_store::{get_block_timestamps_usecs, TransactionStore},
 };
 use anyhow::{anyhow, bail, ensure, Result};
 use aptos_config::config::{
-    PrunerConfig, RocksdbConfig, RocksdbConfigs, StorageDirPaths, NO_OP_STORAGE_PRUNER_CONFIG,
+    LedgerTieringConfig, PrunerConfig, RocksdbConfig, RocksdbConfigs,
+    StateConsistencyAuditorConfig, StorageDirPaths, MIN_EPOCH_SNAPSHOT_PRUNE_WINDOW,
+    MIN_LEDGER_PRUNE_WINDOW, MIN_STATE_MERKLE_PRUNE_WINDOW, NO_OP_STORAGE_PRUNER_CONFIG,
 };
 #[cfg(any(test, feature = "fuzzing"))]
 use aptos_config::config::{
     BUFFERED_STATE_TARGET_ITEMS, DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    NO_OP_LEDGER_TIERING_CONFIG,
 };
 use aptos_crypto::HashValue;
 use aptos_db_indexer::Indexer;
@@ -89,7 +100,7 @@ use aptos_schemadb::{ReadOptions, SchemaBatch, DB};
 use aptos_scratchpad::SparseMerkleTree;
 use aptos_storage_interface::{
     cached_state_view::ShardedStateCache, state_delta::StateDelta, state_view::DbStateView,
-    DbReader, DbWriter, ExecutedTrees, Order, StateSnapshotReceiver, MAX_REQUEST_LIMIT,
+    DbReader, DbWriter, ExecutedTrees, Order, PrunerType, StateSnapshotReceiver, MAX_REQUEST_LIMIT,
 };
 use aptos_types::{
     account_address::AccountAddress,
@@ -123,6 +134,7 @@ use aptos_types::{
 };
 use aptos_vm::data_cache::AsMoveResolver;
 use arr_macro::arr;
+use move_core_types::language_storage::TypeTag;
 use move_resource_viewer::MoveValueAnnotator;
 use once_cell::sync::Lazy;
 use rayon::prelude::*;
@@ -317,6 +329,71 @@ impl RocksdbPropertyReporter {
     }
 }
 
+/// Keeps the RocksDB secondary instances of an AptosDB caught up with the primary instance.
+#[derive(Debug)]
+struct SecondaryCatchUpWorker {
+    sender: Mutex<mpsc::Sender<()>>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+impl SecondaryCatchUpWorker {
+    fn new(
+        ledger_db: Arc<LedgerDb>,
+        state_merkle_db: Arc<StateMerkleDb>,
+        state_kv_db: Arc<StateKvDb>,
+        ledger_store: Arc<LedgerStore>,
+        catch_up_interval: Duration,
+    ) -> Self {
+        let (send, recv) = mpsc::channel();
+        let join_handle = Some(thread::spawn(move || loop {
+            match recv.recv_timeout(catch_up_interval) {
+                Ok(_) => break,
+                Err(mpsc::RecvTimeoutError::Timeout) => (),
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+
+            if let Err(e) =
+                catch_up_with_primary(&ledger_db, &state_merkle_db, &state_kv_db, &ledger_store)
+            {
+                warn!(
+                    error = ?e,
+                    "Catching up with the primary DB failed."
+                );
+            }
+        }));
+        Self {
+            sender: Mutex::new(send),
+            join_handle,
+        }
+    }
+}
+
+impl Drop for SecondaryCatchUpWorker {
+    fn drop(&mut self) {
+        // Notify the catch up thread to exit
+        self.sender.lock().send(()).unwrap();
+        self.join_handle
+            .take()
+            .expect("Secondary catch up thread must exist.")
+            .join()
+            .expect("Secondary catch up thread should join peacefully.");
+    }
+}
+
+/// Catches up the secondary instances with the primary. The state is caught up before the ledger,
+/// so that the state is readable at all the versions up to the latest ledger info.
+fn catch_up_with_primary(
+    ledger_db: &LedgerDb,
+    state_merkle_db: &StateMerkleDb,
+    state_kv_db: &StateKvDb,
+    ledger_store: &LedgerStore,
+) -> Result<()> {
+    state_kv_db.try_catch_up_with_primary()?;
+    state_merkle_db.try_catch_up_with_primary()?;
+    ledger_db.try_catch_up_with_primary()?;
+    ledger_store.refresh_latest_ledger_info()
+}
+
 impl Drop for RocksdbPropertyReporter {
     fn drop(&mut self) {
         // Notify the property reporting thread to exit
@@ -339,7 +416,10 @@ pub struct AptosDB {
     pub(crate) state_store: Arc<StateStore>,
     pub(crate) transaction_store: Arc<TransactionStore>,
     ledger_pruner: LedgerPrunerManager,
+    ledger_tiering_manager: Option<LedgerTieringManager>,
     _rocksdb_property_reporter: RocksdbPropertyReporter,
+    secondary_catch_up_worker: Option<SecondaryCatchUpWorker>,
+    state_consistency_auditor: Option<StateConsistencyAuditor>,
     ledger_commit_lock: std::sync::Mutex<()>,
     indexer: Option<Indexer>,
     skip_index_and_usage: bool,
@@ -355,6 +435,7 @@ impl AptosDB {
         hack_for_tests: bool,
         empty_buffered_state_for_restore: bool,
         skip_index_and_usage: bool,
+        enable_event_type_index: bool,
     ) -> Self {
         let ledger_db = Arc::new(ledger_db);
         let state_merkle_db = Arc::new(state_merkle_db);
@@ -388,16 +469,22 @@ impl AptosDB {
         AptosDB {
             ledger_db: Arc::clone(&ledger_db),
             state_kv_db: Arc::clone(&state_kv_db),
-            event_store: Arc::new(EventStore::new(ledger_db.event_db_arc())),
+            event_store: Arc::new(EventStore::new(
+                ledger_db.event_db_arc(),
+                enable_event_type_index,
+            )),
             ledger_store: Arc::new(LedgerStore::new(Arc::clone(&ledger_db))),
             state_store,
             transaction_store: Arc::new(TransactionStore::new(Arc::clone(&ledger_db))),
             ledger_pruner,
+            ledger_tiering_manager: None,
             _rocksdb_property_reporter: RocksdbPropertyReporter::new(
                 ledger_db,
                 state_merkle_db,
                 state_kv_db,
             ),
+            secondary_catch_up_worker: None,
+            state_consistency_auditor: None,
             ledger_commit_lock: std::sync::Mutex::new(()),
             indexer: None,
             skip_index_and_usage,
@@ -408,8 +495,10 @@ impl AptosDB {
         db_paths: &StorageDirPaths,
         readonly: bool,
         pruner_config: PrunerConfig,
+        ledger_tiering_config: &LedgerTieringConfig,
         rocksdb_configs: RocksdbConfigs,
         enable_indexer: bool,
+        enable_event_type_index: bool,
         buffered_state_target_items: usize,
         max_num_nodes_per_lru_cache_shard: usize,
         empty_buffered_state_for_restore: bool,
@@ -419,13 +508,25 @@ impl AptosDB {
             "Do not set prune_window when opening readonly.",
         );
 
-        let (ledger_db, state_merkle_db, state_kv_db) = Self::open_dbs(
+        let (mut ledger_db, state_merkle_db, state_kv_db) = Self::open_dbs(
             db_paths,
             rocksdb_configs,
             readonly,
             max_num_nodes_per_lru_cache_shard,
         )?;
 
+        if ledger_tiering_config.enable {
+            ensure!(
+                !pruner_config.ledger_pruner_config.enable,
+                "Ledger tiering can't be enabled together with the ledger pruner.",
+            );
+            let cold_ledger_db_path = ledger_tiering_config
+                .cold_db_path
+                .clone()
+                .unwrap_or_else(|| db_paths.ledger_db_root_path().join(COLD_LEDGER_DB_NAME));
+            ledger_db.open_cold_ledger_db(cold_ledger_db_path, &rocksdb_configs, readonly)?;
+        }
+
         let mut myself = Self::new_with_dbs(
             ledger_db,
             state_merkle_db,
@@ -435,8 +536,21 @@ impl AptosDB {
             readonly,
             empty_buffered_state_for_restore,
             rocksdb_configs.enable_storage_sharding,
+            enable_event_type_index,
         );
 
+        if !readonly {
+            let next_version = myself.get_latest_version().map_or(0, |v| v + 1);
+            myself.event_store.init_type_index(next_version)?;
+        }
+
+        if !readonly && ledger_tiering_config.enable {
+            myself.ledger_tiering_manager = Some(LedgerTieringManager::new(
+                Arc::clone(&myself.ledger_db),
+                ledger_tiering_config,
+            ));
+        }
+
         if !readonly && enable_indexer {
             myself.open_indexer(
                 db_paths.default_root_path(),
@@ -451,8 +565,10 @@ impl AptosDB {
         db_paths: StorageDirPaths,
         readonly: bool,
         pruner_config: PrunerConfig,
+        ledger_tiering_config: &LedgerTieringConfig,
         rocksdb_configs: RocksdbConfigs,
         enable_indexer: bool,
+        enable_event_type_index: bool,
         buffered_state_target_items: usize,
         max_num_nodes_per_lru_cache_shard: usize,
     ) -> Result<Self> {
@@ -460,8 +576,10 @@ impl AptosDB {
             &db_paths,
             readonly,
             pruner_config,
+            ledger_tiering_config,
             rocksdb_configs,
             enable_indexer,
+            enable_event_type_index,
             buffered_state_target_items,
             max_num_nodes_per_lru_cache_shard,
             false,
@@ -481,31 +599,122 @@ impl AptosDB {
             &db_paths,
             readonly,
             pruner_config,
+            &LedgerTieringConfig::default(),
             rocksdb_configs,
             enable_indexer,
+            false, /* enable_event_type_index */
             buffered_state_target_items,
             max_num_nodes_per_lru_cache_shard,
             true,
         )
     }
 
+    /// Opens the DB of a node, which may be running, as RocksDB secondary instances, which never
+    /// write to it. This allows analytics and debugging tools to read the DB of a running node
+    /// without stopping it or risking its corruption. The secondary instances keep their own info
+    /// logs under `secondary_root_path`, which must not be shared with other secondary instances.
+    ///
+    /// The DB follows the node by catching up with its writes every `catch_up_interval` (see
+    /// `try_catch_up_with_primary`), and the ledger is readable up to the latest ledger info
+    /// caught up with. The in-memory state isn't followed, so the latest state checkpoint is
+    /// the one persisted when the DB was opened; the state is read by version instead. Neither are
+    /// the pruning and the ledger tiering of the node, so pruned data and data moved to the cold
+    /// ledger db read as not found.
+    pub fn open_as_secondary_following(
+        db_paths: StorageDirPaths,
+        secondary_root_path: impl AsRef<Path>,
+        rocksdb_configs: RocksdbConfigs,
+        max_num_nodes_per_lru_cache_shard: usize,
+        catch_up_interval: Duration,
+    ) -> Result<Self> {
+        let (ledger_db, state_merkle_db, state_kv_db) = Self::open_dbs_with_mode(
+            &db_paths,
+            rocksdb_configs,
+            &DbOpenMode::Secondary {
+                secondary_root: secondary_root_path.as_ref().to_path_buf(),
+            },
+            max_num_nodes_per_lru_cache_shard,
+        )?;
+
+        let mut myself = Self::new_with_dbs(
+            ledger_db,
+            state_merkle_db,
+            state_kv_db,
+            NO_OP_STORAGE_PRUNER_CONFIG,
+            0,     /* buffered_state_target_items, nothing is committed */
+            true,  /* hack_for_tests, nothing is written on opening, like when readonly */
+            false, /* empty_buffered_state_for_restore */
+            rocksdb_configs.enable_storage_sharding,
+            false, /* enable_event_type_index, nothing is committed */
+        );
+        myself.secondary_catch_up_worker = Some(SecondaryCatchUpWorker::new(
+            Arc::clone(&myself.ledger_db),
+            Arc::clone(&myself.state_store.state_db.state_merkle_db),
+            Arc::clone(&myself.state_kv_db),
+            Arc::clone(&myself.ledger_store),
+            catch_up_interval,
+        ));
+
+        Ok(myself)
+    }
+
+    /// Starts auditing the consistency of the state kv db and the state merkle db in the
+    /// background, if enabled (see `StateConsistencyAuditor`).
+    pub fn maybe_start_state_consistency_auditor(&mut self, config: StateConsistencyAuditorConfig) {
+        if config.enable && self.state_consistency_auditor.is_none() {
+            self.state_consistency_auditor = Some(StateConsistencyAuditor::new(
+                Arc::clone(&self.state_store.state_db),
+                config,
+            ));
+        }
+    }
+
+    /// Catches up with the writes of the primary instance now, for a DB opened with
+    /// `open_as_secondary_following`.
+    pub fn try_catch_up_with_primary(&self) -> Result<()> {
+        ensure!(
+            self.secondary_catch_up_worker.is_some(),
+            "The DB is not opened as secondary."
+        );
+        catch_up_with_primary(
+            &self.ledger_db,
+            &self.state_store.state_db.state_merkle_db,
+            &self.state_kv_db,
+            &self.ledger_store,
+        )
+    }
+
     pub fn open_dbs(
         db_paths: &StorageDirPaths,
         rocksdb_configs: RocksdbConfigs,
         readonly: bool,
         max_num_nodes_per_lru_cache_shard: usize,
     ) -> Result<(LedgerDb, StateMerkleDb, StateKvDb)> {
-        let ledger_db = LedgerDb::new(db_paths.ledger_db_root_path(), rocksdb_configs, readonly)?;
+        Self::open_dbs_with_mode(
+            db_paths,
+            rocksdb_configs,
+            &DbOpenMode::from_readonly(readonly),
+            max_num_nodes_per_lru_cache_shard,
+        )
+    }
+
+    fn open_dbs_with_mode(
+        db_paths: &StorageDirPaths,
+        rocksdb_configs: RocksdbConfigs,
+        open_mode: &DbOpenMode,
+        max_num_nodes_per_lru_cache_shard: usize,
+    ) -> Result<(LedgerDb, StateMerkleDb, StateKvDb)> {
+        let ledger_db = LedgerDb::new(db_paths.ledger_db_root_path(), rocksdb_configs, open_mode)?;
         let state_kv_db = StateKvDb::new(
             db_paths,
             rocksdb_configs,
-            readonly,
+            open_mode,
             ledger_db.metadata_db_arc(),
         )?;
         let state_merkle_db = StateMerkleDb::new(
             db_paths,
             rocksdb_configs,
-            readonly,
+            open_mode,
             max_num_nodes_per_lru_cache_shard,
         )?;
 
@@ -549,10 +758,41 @@ impl AptosDB {
         }
         info!("AptosDB Indexer caught up.");
 
+        if let Err(err) = self.catch_up_table_key_index(&indexer, ledger_next_version) {
+            warn!(
+                error = ?err,
+                "Failed to build the table key index, table iteration will be unavailable.",
+            );
+        }
+
         self.indexer = Some(indexer);
         Ok(())
     }
 
+    fn catch_up_table_key_index(
+        &self,
+        indexer: &Indexer,
+        ledger_next_version: Version,
+    ) -> Result<()> {
+        const BATCH_SIZE: Version = 10000;
+        let mut next_version = indexer.table_keys_next_version();
+        while next_version < ledger_next_version {
+            info!(
+                next_version = next_version,
+                "AptosDB table key index catching up."
+            );
+            let end_version = std::cmp::min(ledger_next_version, next_version + BATCH_SIZE);
+            let write_sets = self
+                .transaction_store
+                .get_write_sets(next_version, end_version)?;
+            let write_sets_ref: Vec<_> = write_sets.iter().collect();
+            indexer.index_table_keys(next_version, &write_sets_ref)?;
+
+            next_version = end_version;
+        }
+        Ok(())
+    }
+
     #[cfg(any(test, feature = "fuzzing"))]
     fn new_without_pruner<P: AsRef<Path> + Clone>(
         db_root_path: P,
@@ -565,8 +805,10 @@ impl AptosDB {
             StorageDirPaths::from_path(db_root_path),
             readonly,
             NO_OP_STORAGE_PRUNER_CONFIG, /* pruner */
+            &NO_OP_LEDGER_TIERING_CONFIG,
             RocksdbConfigs::default(),
             enable_indexer,
+            false,
             buffered_state_target_items,
             max_num_nodes_per_lru_cache_shard,
         )
@@ -599,8 +841,10 @@ impl AptosDB {
             StorageDirPaths::from_path(db_root_path),
             false,
             NO_OP_STORAGE_PRUNER_CONFIG, /* pruner */
+            &NO_OP_LEDGER_TIERING_CONFIG,
             db_config,
             false,
+            false,
             BUFFERED_STATE_TARGET_ITEMS,
             max_node_cache,
         )
@@ -1118,18 +1362,34 @@ impl AptosDB {
         let _timer = OTHER_TIMERS_SECONDS
             .with_label_values(&["commit_transactions"])
             .start_timer();
+        let block_timestamps_usecs = if skip_index {
+            vec![None; txns_to_commit.len()]
+        } else {
+            let previous_block_timestamp_usecs = if first_version == 0 {
+                None
+            } else {
+                self.event_store
+                    .get_block_timestamp_usecs_if_indexed(first_version - 1)?
+            };
+            get_block_timestamps_usecs(
+                txns_to_commit.iter().map(TransactionToCommit::transaction),
+                previous_block_timestamp_usecs,
+            )
+        };
         let chunk_size = 512;
         let batches = txns_to_commit
             .par_chunks(chunk_size)
             .enumerate()
             .map(|(chunk_index, txns_in_chunk)| -> Result<SchemaBatch> {
                 let batch = SchemaBatch::new();
-                let chunk_first_version = first_version + (chunk_size * chunk_index) as u64;
+                let chunk_first_index = chunk_size * chunk_index;
+                let chunk_first_version = first_version + chunk_first_index as u64;
                 txns_in_chunk.iter().enumerate().try_for_each(
                     |(i, txn_to_commit)| -> Result<()> {
                         self.transaction_store.put_transaction(
                             chunk_first_version + i as u64,
                             txn_to_commit.transaction(),
+                            block_timestamps_usecs[chunk_first_index + i],
                             skip_index,
                             &batch,
                         )?;
@@ -1315,6 +1575,9 @@ impl AptosDB {
             self.state_store
                 .state_kv_pruner
                 .maybe_set_pruner_target_db_version(last_version);
+            if let Some(ledger_tiering_manager) = &self.ledger_tiering_manager {
+                ledger_tiering_manager.maybe_set_target_db_version(last_version);
+            }
         }
 
         // Note: this must happen after txns have been saved to db because types can be newly
@@ -1479,6 +1742,43 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_account_transactions_in_time_range(
+        &self,
+        address: AccountAddress,
+        start_timestamp_usecs: u64,
+        end_timestamp_usecs: u64,
+        limit: u64,
+        include_events: bool,
+        ledger_version: Version,
+    ) -> Result<AccountTransactionsWithProof> {
+        gauged_api("get_account_transactions_in_time_range", || {
+            error_if_too_many_requested(limit, MAX_REQUEST_LIMIT)?;
+            ensure!(
+                start_timestamp_usecs <= end_timestamp_usecs,
+                "Invalid time range [{}, {})",
+                start_timestamp_usecs,
+                end_timestamp_usecs,
+            );
+
+            let txns_with_proofs = self
+                .transaction_store
+                .get_account_transaction_version_iter_by_time(
+                    address,
+                    start_timestamp_usecs,
+                    end_timestamp_usecs,
+                    ledger_version,
+                )?
+                .take(limit as usize)
+                .map(|result| {
+                    let (_seq_num, txn_version) = result?;
+                    self.get_transaction_with_proof(txn_version, ledger_version, include_events)
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            Ok(AccountTransactionsWithProof::new(txns_with_proofs))
+        })
+    }
+
     /// This API is best-effort in that it CANNOT provide absence proof.
     fn get_transaction_by_hash(
         &self,
@@ -1585,6 +1885,13 @@ impl DbReader for AptosDB {
         })
     }
 
+    /// Get the first version at which the state values are not pruned.
+    fn get_first_state_kv_version(&self) -> Result<Version> {
+        gauged_api("get_first_state_kv_version", || {
+            Ok(self.state_store.state_kv_pruner.get_min_readable_version())
+        })
+    }
+
     /// Returns a batch of transactions for the purpose of synchronizing state to another node.
     ///
     /// If any version beyond ledger_version is requested, it is ignored.
@@ -1655,6 +1962,42 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_events_by_type(
+        &self,
+        type_tag: &TypeTag,
+        start_version: Version,
+        start_index: u64,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<EventWithVersion>> {
+        gauged_api("get_events_by_type", || {
+            error_if_too_many_requested(limit, MAX_REQUEST_LIMIT)?;
+            let index_start_version = self
+                .event_store
+                .get_type_index_start_version()?
+                .ok_or_else(|| anyhow!("The event type index is not enabled."))?;
+            ensure!(
+                start_version >= index_start_version,
+                "The event type index starts at version {}, requested {}.",
+                index_start_version,
+                start_version,
+            );
+            self.error_if_ledger_pruned("Event", start_version)?;
+
+            self.event_store
+                .get_events_by_type_iter(type_tag, start_version, start_index, ledger_version)?
+                .take(limit as usize)
+                .map(|res| {
+                    let (version, index) = res?;
+                    let event = self
+                        .event_store
+                        .get_event_by_version_and_index(version, index)?;
+                    Ok(EventWithVersion::new(version, event))
+                })
+                .collect()
+        })
+    }
+
     fn get_transaction_iterator(
         &self,
         start_version: Version,
@@ -2126,6 +2469,19 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_table_item_keys(
+        &self,
+        handle: TableHandle,
+        version: Version,
+        start_after: Option<HashValue>,
+        limit: usize,
+    ) -> Result<Option<Vec<Vec<u8>>>> {
+        gauged_api("get_table_item_keys", || match &self.indexer {
+            Some(indexer) => indexer.get_table_item_keys(handle, version, start_after, limit),
+            None => Ok(None),
+        })
+    }
+
     /// Returns whether the indexer DB has been enabled or not
     fn indexer_enabled(&self) -> bool {
         self.indexer.is_some()
@@ -2341,6 +2697,71 @@ impl DbWriter for AptosDB {
             Ok(())
         })
     }
+
+    fn update_pruner_config(
+        &self,
+        pruner_type: PrunerType,
+        prune_window: Version,
+        batch_size: usize,
+    ) -> Result<()> {
+        gauged_api("update_pruner_config", || {
+            ensure!(batch_size > 0, "The pruning batch size must be positive.");
+
+            let (is_pruner_enabled, min_prune_window) = match pruner_type {
+                PrunerType::Ledger => (
+                    self.ledger_pruner.is_pruner_enabled(),
+                    MIN_LEDGER_PRUNE_WINDOW,
+                ),
+                PrunerType::StateMerkle => (
+                    self.state_store.state_merkle_pruner.is_pruner_enabled(),
+                    MIN_STATE_MERKLE_PRUNE_WINDOW,
+                ),
+                PrunerType::EpochSnapshot => (
+                    self.state_store.epoch_snapshot_pruner.is_pruner_enabled(),
+                    MIN_EPOCH_SNAPSHOT_PRUNE_WINDOW,
+                ),
+            };
+            ensure!(
+                is_pruner_enabled,
+                "The {:?} pruner is not enabled.",
+                pruner_type
+            );
+            // A smaller window would prune the data other nodes state sync from us.
+            ensure!(
+                prune_window >= min_prune_window,
+                "The {:?} prune window ({}) is below the minimum of {}.",
+                pruner_type,
+                prune_window,
+                min_prune_window,
+            );
+
+            match pruner_type {
+                PrunerType::Ledger => {
+                    self.ledger_pruner
+                        .set_prune_window_and_batch_size(prune_window, batch_size);
+                    // The state kv pruner follows the ledger pruner config.
+                    self.state_store
+                        .state_kv_pruner
+                        .set_prune_window_and_batch_size(prune_window, batch_size);
+                },
+                PrunerType::StateMerkle => self
+                    .state_store
+                    .state_merkle_pruner
+                    .set_prune_window_and_batch_size(prune_window, batch_size),
+                PrunerType::EpochSnapshot => self
+                    .state_store
+                    .epoch_snapshot_pruner
+                    .set_prune_window_and_batch_size(prune_window, batch_size),
+            }
+            info!(
+                pruner_type = ?pruner_type,
+                prune_window = prune_window,
+                batch_size = batch_size,
+                "Pruner config updated."
+            );
+            Ok(())
+        })
+    }
 }
 
 // Convert requested range and order to a range in ascending order.
@@ -13,18 +13,21 @@ use anyhow::Result;
 use aptos_config::config::LedgerPrunerConfig;
 use aptos_infallible::Mutex;
 use aptos_types::transaction::{AtomicVersion, Version};
-use std::sync::{atomic::Ordering, Arc};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
 
 /// The `PrunerManager` for `LedgerPruner`.
 pub(crate) struct LedgerPrunerManager {
     ledger_db: Arc<LedgerDb>,
     /// DB version window, which dictates how many version of other stores like transaction, ledger
     /// info, events etc to keep.
-    prune_window: Version,
+    prune_window: AtomicVersion,
     /// It is None iff the pruner is not enabled.
     pruner_worker: Option<PrunerWorker>,
     /// Ideal batch size of the versions to be sent to the ledger pruner
-    pruning_batch_size: usize,
+    pruning_batch_size: AtomicUsize,
     /// latest version
     latest_version: Arc<Mutex<Version>>,
     /// Offset for displaying to users
@@ -41,7 +44,7 @@ impl PrunerManager for LedgerPrunerManager {
     }
 
     fn get_prune_window(&self) -> Version {
-        self.prune_window
+        self.prune_window.load(Ordering::SeqCst)
     }
 
     fn get_min_readable_version(&self) -> Version {
@@ -52,7 +55,7 @@ impl PrunerManager for LedgerPrunerManager {
         let min_version = self.get_min_readable_version();
         if self.is_pruner_enabled() {
             let adjusted_window = self
-                .prune_window
+                .get_prune_window()
                 .saturating_sub(self.user_pruning_window_offset);
             let adjusted_cutoff = self.latest_version.lock().saturating_sub(adjusted_window);
             std::cmp::max(min_version, adjusted_cutoff)
@@ -70,7 +73,9 @@ impl PrunerManager for LedgerPrunerManager {
         // versions.
         if self.is_pruner_enabled()
             && latest_version
-                >= min_readable_version + self.pruning_batch_size as u64 + self.prune_window
+                >= min_readable_version
+                    + self.pruning_batch_size.load(Ordering::SeqCst) as u64
+                    + self.get_prune_window()
         {
             self.set_pruner_target_db_version(latest_version);
         }
@@ -93,6 +98,22 @@ impl PrunerManager for LedgerPrunerManager {
             .map_or(false, |w| w.is_pruning_pending())
     }
 
+    fn set_prune_window_and_batch_size(&self, prune_window: Version, batch_size: usize) {
+        self.prune_window.store(prune_window, Ordering::SeqCst);
+        self.pruning_batch_size.store(batch_size, Ordering::SeqCst);
+        if let Some(pruner_worker) = &self.pruner_worker {
+            pruner_worker.set_batch_size(batch_size);
+        }
+
+        PRUNER_WINDOW
+            .with_label_values(&["ledger_pruner"])
+            .set(prune_window as i64);
+
+        PRUNER_BATCH_SIZE
+            .with_label_values(&["ledger_pruner"])
+            .set(batch_size as i64);
+    }
+
     #[cfg(test)]
     fn set_worker_target_version(&self, target_version: Version) {
         self.pruner_worker
@@ -123,9 +144,9 @@ impl LedgerPrunerManager {
 
         Self {
             ledger_db,
-            prune_window: ledger_pruner_config.prune_window,
+            prune_window: AtomicVersion::new(ledger_pruner_config.prune_window),
             pruner_worker,
-            pruning_batch_size: ledger_pruner_config.batch_size,
+            pruning_batch_size: AtomicUsize::new(ledger_pruner_config.batch_size),
             latest_version: Arc::new(Mutex::new(min_readable_version)),
             user_pruning_window_offset: ledger_pruner_config.user_pruning_window_offset,
             min_readable_version: AtomicVersion::new(min_readable_version),
@@ -152,7 +173,7 @@ impl LedgerPrunerManager {
 
     fn set_pruner_target_db_version(&self, latest_version: Version) {
         assert!(self.pruner_worker.is_some());
-        let min_readable_version = latest_version.saturating_sub(self.prune_window);
+        let min_readable_version = latest_version.saturating_sub(self.get_prune_window());
         self.min_readable_version
             .store(min_readable_version, Ordering::SeqCst);
 
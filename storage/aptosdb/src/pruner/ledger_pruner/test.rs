@@ -240,6 +240,7 @@ fn put_txn_in_store(
             .put_transaction(
                 i as u64,
                 txns.get(i).unwrap(),
+                /*block_timestamp_usecs=*/ None,
                 /*skip_index=*/ false,
                 &transaction_batch,
             )
@@ -0,0 +1,93 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ledger_db::LedgerDb,
+    metrics::{PRUNER_BATCH_SIZE, PRUNER_WINDOW},
+    pruner::{
+        ledger_tiering::{LedgerTieringPruner, LEDGER_TIERING_NAME},
+        pruner_worker::PrunerWorker,
+    },
+};
+use aptos_config::config::LedgerTieringConfig;
+use aptos_types::transaction::Version;
+use std::sync::Arc;
+
+/// Moves the ledger data older than the hot window to the cold ledger db in the background, by
+/// running a `LedgerTieringPruner` on the ledger db.
+pub(crate) struct LedgerTieringManager {
+    ledger_db: Arc<LedgerDb>,
+    /// The number of latest versions kept in the ledger db.
+    hot_window: Version,
+    /// Ideal batch size of the versions to be moved to the cold ledger db.
+    batch_size: usize,
+    pruner_worker: PrunerWorker,
+}
+
+impl LedgerTieringManager {
+    /// Creates a worker thread moving the data. The cold ledger db must be open.
+    pub fn new(ledger_db: Arc<LedgerDb>, ledger_tiering_config: &LedgerTieringConfig) -> Self {
+        let pruner = Arc::new(
+            LedgerTieringPruner::new(Arc::clone(&ledger_db))
+                .expect("Failed to create ledger tiering pruner."),
+        );
+
+        PRUNER_WINDOW
+            .with_label_values(&[LEDGER_TIERING_NAME])
+            .set(ledger_tiering_config.hot_window as i64);
+
+        PRUNER_BATCH_SIZE
+            .with_label_values(&[LEDGER_TIERING_NAME])
+            .set(ledger_tiering_config.batch_size as i64);
+
+        let pruner_worker = PrunerWorker::new(
+            pruner,
+            ledger_tiering_config.batch_size,
+            LEDGER_TIERING_NAME,
+        );
+
+        Self {
+            ledger_db,
+            hot_window: ledger_tiering_config.hot_window,
+            batch_size: ledger_tiering_config.batch_size,
+            pruner_worker,
+        }
+    }
+
+    /// Sets the target version of the worker when there is a batch to move.
+    pub fn maybe_set_target_db_version(&self, latest_version: Version) {
+        let horizon = self
+            .ledger_db
+            .cold_ledger_db()
+            .expect("The cold ledger db must be open.")
+            .horizon();
+        if latest_version >= horizon + self.batch_size as u64 + self.hot_window {
+            self.pruner_worker
+                .set_target_db_version(latest_version - self.hot_window);
+        }
+    }
+
+    pub fn is_pruning_pending(&self) -> bool {
+        self.pruner_worker.is_pruning_pending()
+    }
+
+    /// (For tests only.) Waits for the worker to move all the data below its target version.
+    #[cfg(test)]
+    pub fn wait_for_pruner(&self) -> anyhow::Result<()> {
+        use std::{
+            thread::sleep,
+            time::{Duration, Instant},
+        };
+
+        const TIMEOUT: Duration = Duration::from_secs(60);
+        let end = Instant::now() + TIMEOUT;
+
+        while Instant::now() < end {
+            if !self.is_pruning_pending() {
+                return Ok(());
+            }
+            sleep(Duration::from_millis(1));
+        }
+        anyhow::bail!("Timeout waiting for ledger tiering worker.");
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+pub(crate) mod ledger_tiering_manager;
+
+use crate::{
+    cold_ledger_db::ColdLedgerDb,
+    ledger_db::LedgerDb,
+    metrics::PRUNER_VERSIONS,
+    pruner::db_pruner::DBPruner,
+    schema::{transaction::TransactionSchema, write_set::WriteSetSchema},
+};
+use anyhow::Result;
+use aptos_logger::info;
+use aptos_schemadb::{schema::Schema, ReadOptions, SchemaBatch, DB};
+use aptos_types::transaction::{AtomicVersion, Version};
+use std::{
+    cmp::min,
+    sync::{atomic::Ordering, Arc},
+};
+
+pub const LEDGER_TIERING_NAME: &str = "ledger_tiering";
+
+/// Moves the transactions and write sets below the target version from the ledger db to the cold
+/// ledger db. Its progress is the horizon of the cold ledger db.
+pub(crate) struct LedgerTieringPruner {
+    ledger_db: Arc<LedgerDb>,
+    cold_ledger_db: Arc<ColdLedgerDb>,
+    /// Keeps track of the target version that the pruner needs to achieve.
+    target_version: AtomicVersion,
+    progress: AtomicVersion,
+}
+
+impl DBPruner for LedgerTieringPruner {
+    fn name(&self) -> &'static str {
+        LEDGER_TIERING_NAME
+    }
+
+    fn prune(&self, max_versions: usize) -> Result<Version> {
+        let mut progress = self.progress();
+        let target_version = self.target_version();
+
+        while progress < target_version {
+            let current_batch_target_version =
+                min(progress + max_versions as Version, target_version);
+
+            info!(
+                progress = progress,
+                target_version = current_batch_target_version,
+                "Moving ledger data to the cold ledger db."
+            );
+            self.move_to_cold_ledger_db(progress, current_batch_target_version)?;
+
+            progress = current_batch_target_version;
+            self.record_progress(progress);
+        }
+
+        Ok(target_version)
+    }
+
+    fn progress(&self) -> Version {
+        self.progress.load(Ordering::SeqCst)
+    }
+
+    fn set_target_version(&self, target_version: Version) {
+        self.target_version.store(target_version, Ordering::SeqCst);
+        PRUNER_VERSIONS
+            .with_label_values(&[LEDGER_TIERING_NAME, "target"])
+            .set(target_version as i64);
+    }
+
+    fn target_version(&self) -> Version {
+        self.target_version.load(Ordering::SeqCst)
+    }
+
+    fn record_progress(&self, progress: Version) {
+        self.progress.store(progress, Ordering::SeqCst);
+        PRUNER_VERSIONS
+            .with_label_values(&[LEDGER_TIERING_NAME, "progress"])
+            .set(progress as i64);
+    }
+}
+
+impl LedgerTieringPruner {
+    pub fn new(ledger_db: Arc<LedgerDb>) -> Result<Self> {
+        let cold_ledger_db = ledger_db
+            .cold_ledger_db_arc()
+            .expect("The cold ledger db must be open.");
+        let horizon = cold_ledger_db.horizon();
+
+        let pruner = LedgerTieringPruner {
+            ledger_db,
+            cold_ledger_db,
+            target_version: AtomicVersion::new(horizon),
+            progress: AtomicVersion::new(horizon),
+        };
+
+        // The node may have stopped after writing the last batch to the cold ledger db, but before
+        // deleting it from the ledger db. Only the versions still in the ledger db are moved.
+        info!(horizon = horizon, "Catching up ledger tiering.");
+        pruner.move_to_cold_ledger_db(0, horizon)?;
+        pruner.record_progress(horizon);
+
+        Ok(pruner)
+    }
+
+    /// Moves the versions in `[begin, end)` to the cold ledger db, and advances its horizon to
+    /// `end`. The data is deleted from the ledger db only after the cold ledger db is written,
+    /// so it's always readable from one of them.
+    fn move_to_cold_ledger_db(&self, begin: Version, end: Version) -> Result<()> {
+        let cold_batch = SchemaBatch::new();
+        let transaction_batch = SchemaBatch::new();
+        let write_set_batch = SchemaBatch::new();
+        Self::move_versions::<TransactionSchema>(
+            self.ledger_db.transaction_db(),
+            begin,
+            end,
+            &cold_batch,
+            &transaction_batch,
+        )?;
+        Self::move_versions::<WriteSetSchema>(
+            self.ledger_db.write_set_db(),
+            begin,
+            end,
+            &cold_batch,
+            &write_set_batch,
+        )?;
+
+        self.cold_ledger_db.write_schemas(cold_batch, end)?;
+        self.ledger_db
+            .transaction_db()
+            .write_schemas(transaction_batch)?;
+        self.ledger_db.write_set_db().write_schemas(write_set_batch)
+    }
+
+    /// Adds the versions in `[begin, end)` that are in `hot_db` to the cold batch, and their
+    /// deletion to the hot batch. Versions that don't exist (e.g., before the first version of a
+    /// fast synced db) are skipped.
+    fn move_versions<S: Schema<Key = Version>>(
+        hot_db: &DB,
+        begin: Version,
+        end: Version,
+        cold_batch: &SchemaBatch,
+        hot_batch: &SchemaBatch,
+    ) -> Result<()> {
+        let mut iter = hot_db.iter::<S>(ReadOptions::default())?;
+        iter.seek(&begin)?;
+        for res in iter {
+            let (version, value) = res?;
+            if version >= end {
+                break;
+            }
+            cold_batch.put::<S>(&version, &value)?;
+            hot_batch.delete::<S>(&version)?;
+        }
+        Ok(())
+    }
+}
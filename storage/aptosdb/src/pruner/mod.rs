@@ -5,6 +5,7 @@
 mod db_pruner;
 mod db_sub_pruner;
 mod ledger_pruner;
+mod ledger_tiering;
 mod pruner_manager;
 mod pruner_utils;
 mod pruner_worker;
@@ -12,6 +13,7 @@ mod state_kv_pruner;
 mod state_merkle_pruner;
 
 pub(crate) use ledger_pruner::ledger_pruner_manager::LedgerPrunerManager;
+pub(crate) use ledger_tiering::ledger_tiering_manager::LedgerTieringManager;
 pub(crate) use pruner_manager::PrunerManager;
 pub(crate) use state_kv_pruner::state_kv_pruner_manager::StateKvPrunerManager;
 pub(crate) use state_merkle_pruner::state_merkle_pruner_manager::StateMerklePrunerManager;
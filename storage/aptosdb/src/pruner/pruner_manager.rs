@@ -35,6 +35,11 @@ pub trait PrunerManager: Sync {
 
     fn is_pruning_pending(&self) -> bool;
 
+    /// Updates the prune window and the batch size of an enabled pruner, which take effect the
+    /// next time the pruner target version is set. Growing the window never moves the min
+    /// readable version backward: the pruner just waits for the ledger to grow into it.
+    fn set_prune_window_and_batch_size(&self, prune_window: Version, batch_size: usize);
+
     /// (For tests only.) Notifies the worker thread and waits for it to finish its job by polling
     /// an internal counter.
     #[cfg(test)]
@@ -9,7 +9,7 @@ use aptos_logger::{
 use aptos_types::transaction::Version;
 use std::{
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicUsize, Ordering},
         Arc,
     },
     thread::{sleep, JoinHandle},
@@ -33,7 +33,7 @@ pub struct PrunerWorkerInner {
     /// The pruner.
     pruner: Arc<dyn DBPruner>,
     /// A threshold to control how many items we prune for each batch.
-    batch_size: usize,
+    batch_size: AtomicUsize,
     /// Indicates whether the pruning loop should be running. Will only be set to true on pruner
     /// destruction.
     quit_worker: AtomicBool,
@@ -44,7 +44,7 @@ impl PrunerWorkerInner {
         Arc::new(Self {
             pruning_time_interval_in_ms: if cfg!(test) { 100 } else { 1 },
             pruner,
-            batch_size,
+            batch_size: AtomicUsize::new(batch_size),
             quit_worker: AtomicBool::new(false),
         })
     }
@@ -52,7 +52,7 @@ impl PrunerWorkerInner {
     // Loop that does the real pruning job.
     fn work(&self) {
         while !self.quit_worker.load(Ordering::SeqCst) {
-            let pruner_result = self.pruner.prune(self.batch_size);
+            let pruner_result = self.pruner.prune(self.batch_size.load(Ordering::SeqCst));
             if pruner_result.is_err() {
                 sample!(
                     SampleRate::Duration(Duration::from_secs(1)),
@@ -99,6 +99,11 @@ impl PrunerWorker {
     pub fn is_pruning_pending(&self) -> bool {
         self.inner.pruner.is_pruning_pending()
     }
+
+    /// Sets the batch size, which is used from the next batch on.
+    pub fn set_batch_size(&self, batch_size: usize) {
+        self.inner.batch_size.store(batch_size, Ordering::SeqCst);
+    }
 }
 
 impl Drop for PrunerWorker {
@@ -12,17 +12,20 @@ use crate::{
 use anyhow::Result;
 use aptos_config::config::LedgerPrunerConfig;
 use aptos_types::transaction::{AtomicVersion, Version};
-use std::sync::{atomic::Ordering, Arc};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
 
 /// The `PrunerManager` for `StateKvPruner`.
 pub(crate) struct StateKvPrunerManager {
     state_kv_db: Arc<StateKvDb>,
     /// DB version window, which dictates how many version of state values to keep.
-    prune_window: Version,
+    prune_window: AtomicVersion,
     /// It is None iff the pruner is not enabled.
     pruner_worker: Option<PrunerWorker>,
     /// Ideal batch size of the versions to be sent to the state kv pruner.
-    pruning_batch_size: usize,
+    pruning_batch_size: AtomicUsize,
     /// The minimal readable version for the ledger data.
     min_readable_version: AtomicVersion,
 }
@@ -35,7 +38,7 @@ impl PrunerManager for StateKvPrunerManager {
     }
 
     fn get_prune_window(&self) -> Version {
-        self.prune_window
+        self.prune_window.load(Ordering::SeqCst)
     }
 
     fn get_min_readable_version(&self) -> Version {
@@ -48,7 +51,9 @@ impl PrunerManager for StateKvPrunerManager {
         // Only wake up the state kv pruner if there are `ledger_pruner_pruning_batch_size` pending
         if self.is_pruner_enabled()
             && latest_version
-                >= min_readable_version + self.pruning_batch_size as u64 + self.prune_window
+                >= min_readable_version
+                    + self.pruning_batch_size.load(Ordering::SeqCst) as u64
+                    + self.get_prune_window()
         {
             self.set_pruner_target_db_version(latest_version);
         }
@@ -71,6 +76,22 @@ impl PrunerManager for StateKvPrunerManager {
             .map_or(false, |w| w.is_pruning_pending())
     }
 
+    fn set_prune_window_and_batch_size(&self, prune_window: Version, batch_size: usize) {
+        self.prune_window.store(prune_window, Ordering::SeqCst);
+        self.pruning_batch_size.store(batch_size, Ordering::SeqCst);
+        if let Some(pruner_worker) = &self.pruner_worker {
+            pruner_worker.set_batch_size(batch_size);
+        }
+
+        PRUNER_WINDOW
+            .with_label_values(&["state_kv_pruner"])
+            .set(prune_window as i64);
+
+        PRUNER_BATCH_SIZE
+            .with_label_values(&["state_kv_pruner"])
+            .set(batch_size as i64);
+    }
+
     #[cfg(test)]
     fn set_worker_target_version(&self, target_version: Version) {
         self.pruner_worker
@@ -100,9 +121,9 @@ impl StateKvPrunerManager {
 
         Self {
             state_kv_db,
-            prune_window: state_kv_pruner_config.prune_window,
+            prune_window: AtomicVersion::new(state_kv_pruner_config.prune_window),
             pruner_worker,
-            pruning_batch_size: state_kv_pruner_config.batch_size,
+            pruning_batch_size: AtomicUsize::new(state_kv_pruner_config.batch_size),
             min_readable_version: AtomicVersion::new(min_readable_version),
         }
     }
@@ -127,7 +148,7 @@ impl StateKvPrunerManager {
 
     fn set_pruner_target_db_version(&self, latest_version: Version) {
         assert!(self.pruner_worker.is_some());
-        let min_readable_version = latest_version.saturating_sub(self.prune_window);
+        let min_readable_version = latest_version.saturating_sub(self.get_prune_window());
         self.min_readable_version
             .store(min_readable_version, Ordering::SeqCst);
 
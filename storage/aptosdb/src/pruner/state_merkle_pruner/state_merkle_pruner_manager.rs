@@ -36,7 +36,7 @@ where
 {
     state_merkle_db: Arc<StateMerkleDb>,
     /// DB version window, which dictates how many versions of state merkle data to keep.
-    prune_window: Version,
+    prune_window: AtomicVersion,
     /// It is None iff the pruner is not enabled.
     pruner_worker: Option<PrunerWorker>,
     /// The minimal readable version for the state merkle data.
@@ -56,7 +56,7 @@ where
     }
 
     fn get_prune_window(&self) -> Version {
-        self.prune_window
+        self.prune_window.load(Ordering::SeqCst)
     }
 
     fn get_min_readable_version(&self) -> Version {
@@ -66,7 +66,9 @@ where
     /// Sets pruner target version when necessary.
     fn maybe_set_pruner_target_db_version(&self, latest_version: Version) {
         let min_readable_version = self.get_min_readable_version();
-        if self.is_pruner_enabled() && latest_version >= min_readable_version + self.prune_window {
+        if self.is_pruner_enabled()
+            && latest_version >= min_readable_version + self.get_prune_window()
+        {
             self.set_pruner_target_db_version(latest_version);
         }
     }
@@ -89,6 +91,21 @@ where
             .map_or(false, |w| w.is_pruning_pending())
     }
 
+    fn set_prune_window_and_batch_size(&self, prune_window: Version, batch_size: usize) {
+        self.prune_window.store(prune_window, Ordering::SeqCst);
+        if let Some(pruner_worker) = &self.pruner_worker {
+            pruner_worker.set_batch_size(batch_size);
+        }
+
+        PRUNER_WINDOW
+            .with_label_values(&[S::name()])
+            .set(prune_window as i64);
+
+        PRUNER_BATCH_SIZE
+            .with_label_values(&[S::name()])
+            .set(batch_size as i64);
+    }
+
     #[cfg(test)]
     fn set_worker_target_version(&self, target_version: Version) {
         self.pruner_worker
@@ -125,7 +142,7 @@ where
 
         Self {
             state_merkle_db,
-            prune_window: state_merkle_pruner_config.prune_window,
+            prune_window: AtomicVersion::new(state_merkle_pruner_config.prune_window),
             pruner_worker,
             min_readable_version: AtomicVersion::new(min_readable_version),
             _phantom: PhantomData,
@@ -159,7 +176,7 @@ where
     fn set_pruner_target_db_version(&self, latest_version: Version) {
         assert!(self.pruner_worker.is_some());
 
-        let min_readable_version = latest_version.saturating_sub(self.prune_window);
+        let min_readable_version = latest_version.saturating_sub(self.get_prune_window());
         self.min_readable_version
             .store(min_readable_version, Ordering::SeqCst);
 
@@ -66,6 +66,8 @@ pub enum DbMetadataKey {
     EpochEndingStateMerkleShardPrunerProgress(ShardId),
     StateKvShardPrunerProgress(ShardId),
     StateMerkleShardRestoreProgress(ShardId, Version),
+    LedgerTieringProgress,
+    EventTypeIndexStartVersion,
 }
 
 define_schema!(
@@ -0,0 +1,72 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for a transaction index via which the versions of
+//! the transactions sent by `account_address` in the blocks within a time range can be found. The
+//! timestamp is the one of the block the transaction is in, in microseconds. Since a sender's
+//! transactions are committed in sequence number order, the index is ordered by sequence number
+//! within each account as well.
+//!
+//! ```text
+//! |<-------------key------------->|<-value->|
+//! | address | timestamp | seq_num | txn_ver |
+//! ```
+
+use crate::schema::{ensure_slice_len_eq, TRANSACTION_BY_ACCOUNT_TIME_CF_NAME};
+use anyhow::Result;
+use aptos_schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use aptos_types::{account_address::AccountAddress, transaction::Version};
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::{convert::TryFrom, mem::size_of};
+
+define_schema!(
+    TransactionByAccountTimeSchema,
+    Key,
+    Version,
+    TRANSACTION_BY_ACCOUNT_TIME_CF_NAME
+);
+
+type TimestampUsecs = u64;
+type SeqNum = u64;
+type Key = (AccountAddress, TimestampUsecs, SeqNum);
+
+impl KeyCodec<TransactionByAccountTimeSchema> for Key {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let (ref account_address, timestamp_usecs, seq_num) = *self;
+
+        let mut encoded = account_address.to_vec();
+        encoded.write_u64::<BigEndian>(timestamp_usecs)?;
+        encoded.write_u64::<BigEndian>(seq_num)?;
+
+        Ok(encoded)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, size_of::<Self>())?;
+
+        let address = AccountAddress::try_from(&data[..AccountAddress::LENGTH])?;
+        let mut rest = &data[AccountAddress::LENGTH..];
+        let timestamp_usecs = rest.read_u64::<BigEndian>()?;
+        let seq_num = rest.read_u64::<BigEndian>()?;
+
+        Ok((address, timestamp_usecs, seq_num))
+    }
+}
+
+impl ValueCodec<TransactionByAccountTimeSchema> for Version {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(self.to_be_bytes().to_vec())
+    }
+
+    fn decode_value(mut data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, size_of::<Self>())?;
+
+        Ok(data.read_u64::<BigEndian>()?)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,23 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn test_encode_decode(
+        address in any::<AccountAddress>(),
+        timestamp_usecs in any::<u64>(),
+        seq_num in any::<u64>(),
+        version in any::<Version>(),
+    ) {
+        assert_encode_decode::<TransactionByAccountTimeSchema>(
+            &(address, timestamp_usecs, seq_num),
+            &version,
+        );
+    }
+}
+
+test_no_panic_decoding!(TransactionByAccountTimeSchema);
@@ -0,0 +1,329 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! Detects the corruption of the state early, rather than at restore or verification time. The
+//! `StateConsistencyAuditor` periodically recomputes the state root hash of a random recent state
+//! checkpoint from the state kv db, and compares it with the root hash in the state merkle db.
+
+use crate::{
+    metrics::{STATE_CONSISTENCY_AUDITS, STATE_CONSISTENCY_AUDIT_VERSION},
+    pruner::PrunerManager,
+    schema::state_value::StateValueSchema,
+    state_store::StateDb,
+    NUM_STATE_SHARDS,
+};
+use anyhow::Result;
+use aptos_config::config::StateConsistencyAuditorConfig;
+use aptos_crypto::{
+    hash::{CryptoHash, SPARSE_MERKLE_PLACEHOLDER_HASH},
+    HashValue,
+};
+use aptos_infallible::Mutex;
+use aptos_jellyfish_merkle::{
+    node_type::{Node, NodeKey},
+    TreeReader,
+};
+use aptos_logger::prelude::*;
+use aptos_schemadb::{ReadOptions, DB};
+use aptos_types::{
+    nibble::Nibble,
+    proof::{SparseMerkleInternalNode, SparseMerkleLeafNode},
+    state_store::state_key::StateKey,
+    transaction::Version,
+};
+use rand::Rng;
+use std::{
+    sync::{mpsc, Arc},
+    thread,
+    thread::JoinHandle,
+    time::Duration,
+};
+
+/// The result of auditing the state at a version.
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) enum AuditResult {
+    /// The state root hash recomputed from the state kv db matches the state merkle db.
+    Consistent,
+    /// The state root hash recomputed from the state kv db doesn't match the state merkle db.
+    Inconsistent {
+        expected_root_hash: HashValue,
+        actual_root_hash: HashValue,
+        mismatched_shards: Vec<u8>,
+    },
+    /// The state at the version was pruned during the audit, so the result is unknown.
+    Inconclusive,
+}
+
+/// Audits the state at a random recent state checkpoint every audit interval.
+#[derive(Debug)]
+pub(crate) struct StateConsistencyAuditor {
+    sender: Mutex<mpsc::Sender<()>>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+impl StateConsistencyAuditor {
+    pub fn new(state_db: Arc<StateDb>, config: StateConsistencyAuditorConfig) -> Self {
+        let audit_interval = Duration::from_secs(config.audit_interval_mins * 60);
+        let (send, recv) = mpsc::channel();
+        let join_handle = Some(thread::spawn(move || loop {
+            match recv.recv_timeout(audit_interval) {
+                Ok(_) => break,
+                Err(mpsc::RecvTimeoutError::Timeout) => (),
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+
+            match audit_random_version(&state_db, config.max_audit_version_lag) {
+                Ok(Some((version, AuditResult::Consistent))) => {
+                    info!(version = version, "The state is consistent.");
+                    STATE_CONSISTENCY_AUDITS
+                        .with_label_values(&["consistent"])
+                        .inc();
+                    STATE_CONSISTENCY_AUDIT_VERSION.set(version as i64);
+                },
+                Ok(Some((
+                    version,
+                    AuditResult::Inconsistent {
+                        expected_root_hash,
+                        actual_root_hash,
+                        mismatched_shards,
+                    },
+                ))) => {
+                    error!(
+                        version = version,
+                        expected_root_hash = %expected_root_hash,
+                        actual_root_hash = %actual_root_hash,
+                        mismatched_shards = ?mismatched_shards,
+                        "The state kv db is inconsistent with the state merkle db!"
+                    );
+                    STATE_CONSISTENCY_AUDITS
+                        .with_label_values(&["inconsistent"])
+                        .inc();
+                    STATE_CONSISTENCY_AUDIT_VERSION.set(version as i64);
+                },
+                Ok(Some((_, AuditResult::Inconclusive))) | Ok(None) => {
+                    STATE_CONSISTENCY_AUDITS
+                        .with_label_values(&["skipped"])
+                        .inc();
+                },
+                Err(e) => {
+                    warn!(
+                        error = ?e,
+                        "Auditing the state consistency failed."
+                    );
+                    STATE_CONSISTENCY_AUDITS.with_label_values(&["error"]).inc();
+                },
+            }
+        }));
+        Self {
+            sender: Mutex::new(send),
+            join_handle,
+        }
+    }
+}
+
+impl Drop for StateConsistencyAuditor {
+    fn drop(&mut self) {
+        // Notify the audit thread to exit
+        self.sender.lock().send(()).unwrap();
+        self.join_handle
+            .take()
+            .expect("State consistency audit thread must exist.")
+            .join()
+            .expect("State consistency audit thread should join peacefully.");
+    }
+}
+
+/// The versions below this are possibly pruned from either the state kv db or the state merkle db.
+fn get_min_readable_version(state_db: &StateDb) -> Version {
+    std::cmp::max(
+        state_db.state_kv_pruner.get_min_readable_version(),
+        state_db.state_merkle_pruner.get_min_readable_version(),
+    )
+}
+
+/// Audits a random recent state checkpoint, if there's any.
+fn audit_random_version(
+    state_db: &StateDb,
+    max_audit_version_lag: Version,
+) -> Result<Option<(Version, AuditResult)>> {
+    match pick_audit_version(state_db, max_audit_version_lag)? {
+        Some(version) => Ok(Some((version, audit_state_consistency(state_db, version)?))),
+        None => Ok(None),
+    }
+}
+
+/// Picks a random state checkpoint among the `max_audit_version_lag` latest versions, or returns
+/// `None` if there's no such checkpoint.
+fn pick_audit_version(
+    state_db: &StateDb,
+    max_audit_version_lag: Version,
+) -> Result<Option<Version>> {
+    let latest_version = match state_db
+        .state_merkle_db
+        .get_state_snapshot_version_before(Version::MAX)?
+    {
+        Some(version) => version,
+        None => return Ok(None),
+    };
+    let min_version = std::cmp::max(
+        latest_version.saturating_sub(max_audit_version_lag),
+        get_min_readable_version(state_db),
+    );
+    if min_version > latest_version {
+        return Ok(None);
+    }
+
+    let random_version = rand::thread_rng().gen_range(min_version..=latest_version);
+    Ok(state_db
+        .state_merkle_db
+        .get_state_snapshot_version_before(random_version + 1)?
+        .filter(|version| *version >= min_version))
+}
+
+/// Recomputes the state root hash at `version` from the state kv db, and compares it with the root
+/// hash in the state merkle db. The state is audited shard by shard, and the mismatched shards are
+/// reported by comparing the subtree root hashes with the children of the root node.
+pub(crate) fn audit_state_consistency(state_db: &StateDb, version: Version) -> Result<AuditResult> {
+    let expected_root_hash = state_db.state_merkle_db.get_root_hash(version)?;
+
+    // With sharding disabled, all the shards are in one db, so it's read once.
+    let state_kv_db = &state_db.state_kv_db;
+    let mut shard_summaries = Vec::with_capacity(NUM_STATE_SHARDS);
+    if state_kv_db.enabled_sharding() {
+        for shard_id in 0..NUM_STATE_SHARDS {
+            let mut state_item_hashes =
+                collect_state_item_hashes(state_kv_db.db_shard(shard_id as u8), version)?;
+            shard_summaries.push(ShardSummary::new(std::mem::take(
+                &mut state_item_hashes[shard_id],
+            )));
+        }
+    } else {
+        shard_summaries = collect_state_item_hashes(state_kv_db.metadata_db(), version)?
+            .into_iter()
+            .map(ShardSummary::new)
+            .collect();
+    }
+
+    // The data read is not reliable if it was pruned in the meantime.
+    if version < get_min_readable_version(state_db) {
+        return Ok(AuditResult::Inconclusive);
+    }
+
+    let (actual_root_hash, _) = combine_shard_summaries(&shard_summaries);
+    if actual_root_hash == expected_root_hash {
+        return Ok(AuditResult::Consistent);
+    }
+
+    let mismatched_shards = match state_db
+        .state_merkle_db
+        .get_node_option(&NodeKey::new_empty_path(version), "state_consistency_audit")?
+    {
+        Some(Node::Internal(root_node)) => shard_summaries
+            .iter()
+            .enumerate()
+            .filter(|(shard_id, summary)| {
+                let expected_shard_root_hash = root_node
+                    .child(Nibble::from(*shard_id as u8))
+                    .map_or(*SPARSE_MERKLE_PLACEHOLDER_HASH, |child| child.hash);
+                summary.root_hash != expected_shard_root_hash
+            })
+            .map(|(shard_id, _)| shard_id as u8)
+            .collect(),
+        // The tree has at most one leaf, so the mismatch can't be narrowed down.
+        _ => (0..NUM_STATE_SHARDS as u8).collect(),
+    };
+    Ok(AuditResult::Inconsistent {
+        expected_root_hash,
+        actual_root_hash,
+        mismatched_shards,
+    })
+}
+
+/// Collects the (key hash, value hash) of all the state items at `version` in `db`, by shard.
+fn collect_state_item_hashes(
+    db: &DB,
+    version: Version,
+) -> Result<Vec<Vec<(HashValue, HashValue)>>> {
+    let mut state_item_hashes = vec![vec![]; NUM_STATE_SHARDS];
+
+    let mut read_opts = ReadOptions::default();
+    // Iterate over all the keys, not only the ones with the same prefix.
+    read_opts.set_total_order_seek(true);
+    let mut iter = db.iter::<StateValueSchema>(read_opts)?;
+    iter.seek_to_first();
+    let mut prev_key: Option<StateKey> = None;
+    while let Some(((state_key, value_version), state_value_opt)) = iter.next().transpose()? {
+        // In case the previous seek() ends on the same key with version 0.
+        if Some(&state_key) == prev_key.as_ref() {
+            continue;
+        }
+        if value_version > version {
+            iter.seek(&(state_key.clone(), version))?;
+            continue;
+        }
+
+        // This is the latest value of the key at `version`, skip the older ones.
+        iter.seek(&(state_key.clone(), 0))?;
+        if let Some(state_value) = state_value_opt {
+            let key_hash = CryptoHash::hash(&state_key);
+            state_item_hashes[key_hash.nibble(0) as usize].push((key_hash, state_value.hash()));
+        }
+        prev_key = Some(state_key);
+    }
+
+    Ok(state_item_hashes)
+}
+
+/// The root hash and the number of leaves of the subtree of a shard.
+struct ShardSummary {
+    root_hash: HashValue,
+    num_leaves: usize,
+}
+
+impl ShardSummary {
+    fn new(mut state_item_hashes: Vec<(HashValue, HashValue)>) -> Self {
+        state_item_hashes.sort_unstable_by_key(|(key_hash, _)| *key_hash);
+        Self {
+            // The shard subtrees are below the first nibble.
+            root_hash: compute_subtree_root_hash(&state_item_hashes, 4),
+            num_leaves: state_item_hashes.len(),
+        }
+    }
+}
+
+/// Computes the root hash of the subtree at `depth` holding the given leaves, sorted by key hash.
+/// Like in the jellyfish merkle tree, a subtree with a single leaf is represented by the leaf.
+fn compute_subtree_root_hash(leaves: &[(HashValue, HashValue)], depth: usize) -> HashValue {
+    match leaves {
+        [] => *SPARSE_MERKLE_PLACEHOLDER_HASH,
+        [(key_hash, value_hash)] => SparseMerkleLeafNode::new(*key_hash, *value_hash).hash(),
+        _ => {
+            let split = leaves.partition_point(|(key_hash, _)| !key_hash.bit(depth));
+            SparseMerkleInternalNode::new(
+                compute_subtree_root_hash(&leaves[..split], depth + 1),
+                compute_subtree_root_hash(&leaves[split..], depth + 1),
+            )
+            .hash()
+        },
+    }
+}
+
+/// Computes the root hash and the number of leaves of the tree made of the given shard subtrees.
+fn combine_shard_summaries(shard_summaries: &[ShardSummary]) -> (HashValue, usize) {
+    if let [shard_summary] = shard_summaries {
+        return (shard_summary.root_hash, shard_summary.num_leaves);
+    }
+
+    let (left, right) = shard_summaries.split_at(shard_summaries.len() / 2);
+    let (left_hash, left_num_leaves) = combine_shard_summaries(left);
+    let (right_hash, right_num_leaves) = combine_shard_summaries(right);
+    let hash = match (left_num_leaves, right_num_leaves) {
+        (0, 0) => *SPARSE_MERKLE_PLACEHOLDER_HASH,
+        (1, 0) => left_hash,
+        (0, 1) => right_hash,
+        _ => SparseMerkleInternalNode::new(left_hash, right_hash).hash(),
+    };
+    (hash, left_num_leaves + right_num_leaves)
+}
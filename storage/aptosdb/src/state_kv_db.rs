@@ -5,16 +5,15 @@
 
 use crate::{
     db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
-    db_options::{gen_state_kv_cfds, state_kv_db_column_families},
+    db_options::{gen_state_kv_cfds, state_kv_db_column_families, DbOpenMode},
     metrics::OTHER_TIMERS_SECONDS,
     utils::truncation_helper::{get_state_kv_commit_progress, truncate_state_kv_db_shards},
     NUM_STATE_SHARDS,
 };
 use anyhow::Result;
-use aptos_config::config::{RocksdbConfig, RocksdbConfigs, StorageDirPaths};
+use aptos_config::config::{RocksdbConfigs, StorageDirPaths};
 use aptos_experimental_runtimes::thread_manager::THREAD_MANAGER;
 use aptos_logger::prelude::info;
-use aptos_rocksdb_options::gen_rocksdb_options;
 use aptos_schemadb::{SchemaBatch, DB};
 use aptos_types::transaction::Version;
 use arr_macro::arr;
@@ -36,7 +35,7 @@ impl StateKvDb {
     pub(crate) fn new(
         db_paths: &StorageDirPaths,
         rocksdb_configs: RocksdbConfigs,
-        readonly: bool,
+        open_mode: &DbOpenMode,
         ledger_db: Arc<DB>,
     ) -> Result<Self> {
         let sharding = rocksdb_configs.enable_storage_sharding;
@@ -49,13 +48,13 @@ impl StateKvDb {
             });
         }
 
-        Self::open(db_paths, rocksdb_configs.state_kv_db_config, readonly)
+        Self::open(db_paths, rocksdb_configs, open_mode)
     }
 
     pub(crate) fn open(
         db_paths: &StorageDirPaths,
-        state_kv_db_config: RocksdbConfig,
-        readonly: bool,
+        rocksdb_configs: RocksdbConfigs,
+        open_mode: &DbOpenMode,
     ) -> Result<Self> {
         let state_kv_metadata_db_path =
             Self::metadata_db_path(db_paths.state_kv_db_metadata_root_path());
@@ -63,8 +62,8 @@ impl StateKvDb {
         let state_kv_metadata_db = Arc::new(Self::open_db(
             state_kv_metadata_db_path.clone(),
             STATE_KV_METADATA_DB_NAME,
-            &state_kv_db_config,
-            readonly,
+            &rocksdb_configs,
+            open_mode,
         )?);
 
         info!(
@@ -76,7 +75,7 @@ impl StateKvDb {
         let state_kv_db_shards = {
             arr![{
                 let shard_root_path = db_paths.state_kv_db_shard_root_path(shard_id as u8);
-                let db = Self::open_shard(shard_root_path, shard_id as u8, &state_kv_db_config, readonly)?;
+                let db = Self::open_shard(shard_root_path, shard_id as u8, &rocksdb_configs, open_mode)?;
                 shard_id += 1;
                 Arc::new(db)
             }; 16]
@@ -88,8 +87,11 @@ impl StateKvDb {
             enabled_sharding: true,
         };
 
-        if let Some(overall_kv_commit_progress) = get_state_kv_commit_progress(&state_kv_db)? {
-            truncate_state_kv_db_shards(&state_kv_db, overall_kv_commit_progress, None)?;
+        // A secondary instance can't write, and may see the primary in the middle of a commit.
+        if !open_mode.is_secondary() {
+            if let Some(overall_kv_commit_progress) = get_state_kv_commit_progress(&state_kv_db)? {
+                truncate_state_kv_db_shards(&state_kv_db, overall_kv_commit_progress, None)?;
+            }
         }
 
         Ok(state_kv_db)
@@ -153,8 +155,8 @@ impl StateKvDb {
         // TODO(grao): Support path override here.
         let state_kv_db = Self::open(
             &StorageDirPaths::from_path(db_root_path),
-            RocksdbConfig::default(),
-            false,
+            RocksdbConfigs::default(),
+            &DbOpenMode::ReadWrite,
         )?;
         let cp_state_kv_db_path = cp_root_path.as_ref().join(STATE_KV_DB_FOLDER_NAME);
 
@@ -176,6 +178,19 @@ impl StateKvDb {
         Ok(())
     }
 
+    /// Catches up with the primary instance, for a state kv db opened as secondary. The shards
+    /// are caught up before the metadata db, which holds the commit progress.
+    pub(crate) fn try_catch_up_with_primary(&self) -> Result<()> {
+        // Without sharding, this is the ledger db, which is caught up on its own.
+        if !self.enabled_sharding {
+            return Ok(());
+        }
+        for db_shard in &self.state_kv_db_shards {
+            db_shard.try_catch_up_with_primary()?;
+        }
+        self.state_kv_metadata_db.try_catch_up_with_primary()
+    }
+
     pub(crate) fn metadata_db(&self) -> &DB {
         &self.state_kv_metadata_db
     }
@@ -212,39 +227,36 @@ impl StateKvDb {
     fn open_shard<P: AsRef<Path>>(
         db_root_path: P,
         shard_id: u8,
-        state_kv_db_config: &RocksdbConfig,
-        readonly: bool,
+        rocksdb_configs: &RocksdbConfigs,
+        open_mode: &DbOpenMode,
     ) -> Result<DB> {
         let db_name = format!("state_kv_db_shard_{}", shard_id);
         Self::open_db(
             Self::db_shard_path(db_root_path, shard_id),
             &db_name,
-            state_kv_db_config,
-            readonly,
+            rocksdb_configs,
+            open_mode,
         )
     }
 
     fn open_db(
         path: PathBuf,
         name: &str,
-        state_kv_db_config: &RocksdbConfig,
-        readonly: bool,
+        rocksdb_configs: &RocksdbConfigs,
+        open_mode: &DbOpenMode,
     ) -> Result<DB> {
-        Ok(if readonly {
-            DB::open_cf_readonly(
-                &gen_rocksdb_options(state_kv_db_config, true),
-                path,
-                name,
-                state_kv_db_column_families(),
-            )?
-        } else {
-            DB::open_cf(
-                &gen_rocksdb_options(state_kv_db_config, false),
-                path,
-                name,
-                gen_state_kv_cfds(state_kv_db_config),
-            )?
-        })
+        open_mode.open_db(
+            path,
+            name,
+            &rocksdb_configs.state_kv_db_config,
+            state_kv_db_column_families(),
+            || {
+                gen_state_kv_cfds(
+                    &rocksdb_configs.state_kv_db_config,
+                    &rocksdb_configs.column_family_configs,
+                )
+            },
+        )
     }
 
     fn db_shard_path<P: AsRef<Path>>(db_root_path: P, shard_id: u8) -> PathBuf {
@@ -3,18 +3,19 @@
 
 use crate::{
     db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
-    db_options::{gen_state_merkle_cfds, state_merkle_db_column_families},
+    db_options::{gen_state_merkle_cfds, state_merkle_db_column_families, DbOpenMode},
     lru_node_cache::LruNodeCache,
     metrics::NODE_CACHE_SECONDS,
     schema::jellyfish_merkle_node::JellyfishMerkleNodeSchema,
     stale_node_index::StaleNodeIndexSchema,
     stale_node_index_cross_epoch::StaleNodeIndexCrossEpochSchema,
+    state_proof_cache::StateProofCache,
     utils::truncation_helper::{get_state_merkle_commit_progress, truncate_state_merkle_db_shards},
     versioned_node_cache::VersionedNodeCache,
     NUM_STATE_SHARDS, OTHER_TIMERS_SECONDS,
 };
 use anyhow::{ensure, Result};
-use aptos_config::config::{RocksdbConfig, RocksdbConfigs, StorageDirPaths};
+use aptos_config::config::{RocksdbConfigs, StorageDirPaths};
 use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_experimental_runtimes::thread_manager::{optimal_min_len, THREAD_MANAGER};
 use aptos_jellyfish_merkle::{
@@ -22,7 +23,6 @@ use aptos_jellyfish_merkle::{
     JellyfishMerkleTree, TreeReader, TreeUpdateBatch, TreeWriter,
 };
 use aptos_logger::prelude::*;
-use aptos_rocksdb_options::gen_rocksdb_options;
 use aptos_schemadb::{SchemaBatch, DB};
 #[cfg(test)]
 use aptos_scratchpad::get_state_shard_id;
@@ -60,17 +60,17 @@ pub struct StateMerkleDb {
     // shard_id -> cache.
     version_caches: HashMap<Option<u8>, VersionedNodeCache>,
     lru_cache: LruNodeCache,
+    proof_cache: StateProofCache,
 }
 
 impl StateMerkleDb {
     pub(crate) fn new(
         db_paths: &StorageDirPaths,
         rocksdb_configs: RocksdbConfigs,
-        readonly: bool,
+        open_mode: &DbOpenMode,
         max_nodes_per_lru_cache_shard: usize,
     ) -> Result<Self> {
         let sharding = rocksdb_configs.enable_storage_sharding;
-        let state_merkle_db_config = rocksdb_configs.state_merkle_db_config;
         // TODO(grao): Currently when this value is set to 0 we disable both caches. This is
         // hacky, need to revisit.
         let enable_cache = max_nodes_per_lru_cache_shard > 0;
@@ -86,8 +86,8 @@ impl StateMerkleDb {
             let db = Arc::new(Self::open_db(
                 state_merkle_db_path,
                 STATE_MERKLE_DB_NAME,
-                &state_merkle_db_config,
-                readonly,
+                &rocksdb_configs,
+                open_mode,
             )?);
             return Ok(Self {
                 state_merkle_metadata_db: Arc::clone(&db),
@@ -96,13 +96,14 @@ impl StateMerkleDb {
                 enable_cache,
                 version_caches,
                 lru_cache,
+                proof_cache: StateProofCache::new(),
             });
         }
 
         Self::open(
             db_paths,
-            state_merkle_db_config,
-            readonly,
+            rocksdb_configs,
+            open_mode,
             enable_cache,
             version_caches,
             lru_cache,
@@ -168,7 +169,7 @@ impl StateMerkleDb {
         let state_merkle_db = Self::new(
             &StorageDirPaths::from_path(db_root_path),
             rocksdb_configs,
-            /*readonly=*/ false,
+            &DbOpenMode::ReadWrite,
             /*max_nodes_per_lru_cache_shard=*/ 0,
         )?;
         let cp_state_merkle_db_path = cp_root_path.as_ref().join(STATE_MERKLE_DB_FOLDER_NAME);
@@ -198,6 +199,17 @@ impl StateMerkleDb {
         Ok(())
     }
 
+    /// Catches up with the primary instance, for a state merkle db opened as secondary. The
+    /// shards are caught up before the metadata db, which holds the commit progress.
+    pub(crate) fn try_catch_up_with_primary(&self) -> Result<()> {
+        if self.enable_sharding {
+            for db_shard in &self.state_merkle_db_shards {
+                db_shard.try_catch_up_with_primary()?;
+            }
+        }
+        self.state_merkle_metadata_db.try_catch_up_with_primary()
+    }
+
     pub(crate) fn metadata_db(&self) -> &DB {
         &self.state_merkle_metadata_db
     }
@@ -245,7 +257,26 @@ impl StateMerkleDb {
         Option<(HashValue, (StateKey, Version))>,
         SparseMerkleProofExt,
     )> {
-        JellyfishMerkleTree::new(self).get_with_proof_ext(state_key.hash(), version)
+        let key_hash = state_key.hash();
+        if !self.enable_cache {
+            return JellyfishMerkleTree::new(self).get_with_proof_ext(key_hash, version);
+        }
+
+        let start_time = Instant::now();
+        if let Some(leaf_and_proof) = self.proof_cache.get(&key_hash, version) {
+            NODE_CACHE_SECONDS
+                .with_label_values(&["get_with_proof_ext", "proof_cache_hit"])
+                .observe(start_time.elapsed().as_secs_f64());
+            return Ok(leaf_and_proof);
+        }
+        let leaf_and_proof =
+            JellyfishMerkleTree::new(self).get_with_proof_ext(key_hash, version)?;
+        self.proof_cache
+            .put(key_hash, version, leaf_and_proof.clone());
+        NODE_CACHE_SECONDS
+            .with_label_values(&["get_with_proof_ext", "proof_cache_miss"])
+            .observe(start_time.elapsed().as_secs_f64());
+        Ok(leaf_and_proof)
     }
 
     pub fn get_range_proof(
@@ -540,8 +571,8 @@ impl StateMerkleDb {
 
     fn open(
         db_paths: &StorageDirPaths,
-        state_merkle_db_config: RocksdbConfig,
-        readonly: bool,
+        rocksdb_configs: RocksdbConfigs,
+        open_mode: &DbOpenMode,
         enable_cache: bool,
         version_caches: HashMap<Option<u8>, VersionedNodeCache>,
         lru_cache: LruNodeCache,
@@ -554,8 +585,8 @@ impl StateMerkleDb {
         let state_merkle_metadata_db = Arc::new(Self::open_db(
             state_merkle_metadata_db_path.clone(),
             STATE_MERKLE_METADATA_DB_NAME,
-            &state_merkle_db_config,
-            readonly,
+            &rocksdb_configs,
+            open_mode,
         )?);
 
         info!(
@@ -566,7 +597,7 @@ impl StateMerkleDb {
         let mut shard_id: usize = 0;
         let state_merkle_db_shards = arr![{
             let shard_root_path = db_paths.state_merkle_db_shard_root_path(shard_id as u8);
-            let db = Self::open_shard(shard_root_path, shard_id as u8, &state_merkle_db_config, readonly)?;
+            let db = Self::open_shard(shard_root_path, shard_id as u8, &rocksdb_configs, open_mode)?;
             shard_id += 1;
             Arc::new(db)
         }; 16];
@@ -578,15 +609,19 @@ impl StateMerkleDb {
             enable_cache,
             version_caches,
             lru_cache,
+            proof_cache: StateProofCache::new(),
         };
 
-        if let Some(overall_state_merkle_commit_progress) =
-            get_state_merkle_commit_progress(&state_merkle_db)?
-        {
-            truncate_state_merkle_db_shards(
-                &state_merkle_db,
-                overall_state_merkle_commit_progress,
-            )?;
+        // A secondary instance can't write, and may see the primary in the middle of a commit.
+        if !open_mode.is_secondary() {
+            if let Some(overall_state_merkle_commit_progress) =
+                get_state_merkle_commit_progress(&state_merkle_db)?
+            {
+                truncate_state_merkle_db_shards(
+                    &state_merkle_db,
+                    overall_state_merkle_commit_progress,
+                )?;
+            }
         }
 
         Ok(state_merkle_db)
@@ -595,39 +630,36 @@ impl StateMerkleDb {
     fn open_shard<P: AsRef<Path>>(
         db_root_path: P,
         shard_id: u8,
-        state_merkle_db_config: &RocksdbConfig,
-        readonly: bool,
+        rocksdb_configs: &RocksdbConfigs,
+        open_mode: &DbOpenMode,
     ) -> Result<DB> {
         let db_name = format!("state_merkle_db_shard_{}", shard_id);
         Self::open_db(
             Self::db_shard_path(db_root_path, shard_id),
             &db_name,
-            state_merkle_db_config,
-            readonly,
+            rocksdb_configs,
+            open_mode,
         )
     }
 
     fn open_db(
         path: PathBuf,
         name: &str,
-        state_merkle_db_config: &RocksdbConfig,
-        readonly: bool,
+        rocksdb_configs: &RocksdbConfigs,
+        open_mode: &DbOpenMode,
     ) -> Result<DB> {
-        Ok(if readonly {
-            DB::open_cf_readonly(
-                &gen_rocksdb_options(state_merkle_db_config, true),
-                path,
-                name,
-                state_merkle_db_column_families(),
-            )?
-        } else {
-            DB::open_cf(
-                &gen_rocksdb_options(state_merkle_db_config, false),
-                path,
-                name,
-                gen_state_merkle_cfds(state_merkle_db_config),
-            )?
-        })
+        open_mode.open_db(
+            path,
+            name,
+            &rocksdb_configs.state_merkle_db_config,
+            state_merkle_db_column_families(),
+            || {
+                gen_state_merkle_cfds(
+                    &rocksdb_configs.state_merkle_db_config,
+                    &rocksdb_configs.column_family_configs,
+                )
+            },
+        )
     }
 
     fn db_shard_path<P: AsRef<Path>>(db_root_path: P, shard_id: u8) -> PathBuf {
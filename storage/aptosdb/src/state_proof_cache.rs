@@ -0,0 +1,111 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_crypto::HashValue;
+use aptos_infallible::Mutex;
+use aptos_types::{
+    proof::SparseMerkleProofExt, state_store::state_key::StateKey, transaction::Version,
+};
+use lru::LruCache;
+use std::fmt;
+
+const NUM_SHARDS: usize = 16;
+const MAX_PROOFS_PER_SHARD: usize = 256;
+
+pub(crate) type LeafAndProof = (
+    Option<(HashValue, (StateKey, Version))>,
+    SparseMerkleProofExt,
+);
+
+struct Shard {
+    version: Option<Version>,
+    proofs: LruCache<HashValue, LeafAndProof>,
+}
+
+/// Recent sparse merkle proofs, keyed by the state key hash, at the latest version they are
+/// requested at. A hot resource requested with proofs repeatedly is proven only once per version.
+pub(crate) struct StateProofCache {
+    shards: [Mutex<Shard>; NUM_SHARDS],
+}
+
+impl fmt::Debug for StateProofCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "StateProofCache with {NUM_SHARDS} shards.")
+    }
+}
+
+impl StateProofCache {
+    pub fn new() -> Self {
+        Self {
+            // `arr!()` doesn't allow a const in place of the integer literal
+            shards: arr_macro::arr![Mutex::new(Shard {
+                version: None,
+                proofs: LruCache::new(MAX_PROOFS_PER_SHARD),
+            }); 16],
+        }
+    }
+
+    fn shard(key_hash: &HashValue) -> usize {
+        key_hash[0] as usize % NUM_SHARDS
+    }
+
+    /// Returns the cached proof of the key at `version`, if `version` is the latest version the
+    /// shard has seen.
+    pub fn get(&self, key_hash: &HashValue, version: Version) -> Option<LeafAndProof> {
+        let mut shard = self.shards[Self::shard(key_hash)].lock();
+        if shard.version == Some(version) {
+            shard.proofs.get(key_hash).cloned()
+        } else {
+            None
+        }
+    }
+
+    /// Caches the proof of the key at `version`. A newer version invalidates all the proofs in
+    /// the shard, while the proofs at older versions are not cached.
+    pub fn put(&self, key_hash: HashValue, version: Version, leaf_and_proof: LeafAndProof) {
+        let mut shard = self.shards[Self::shard(&key_hash)].lock();
+        match shard.version {
+            Some(v) if v > version => return,
+            Some(v) if v == version => (),
+            _ => {
+                shard.proofs.clear();
+                shard.version = Some(version);
+            },
+        }
+        shard.proofs.put(key_hash, leaf_and_proof);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn leaf_and_proof(version: Version) -> LeafAndProof {
+        (
+            Some((
+                HashValue::sha3_256_of(&version.to_be_bytes()),
+                (StateKey::raw(version.to_be_bytes().to_vec()), version),
+            )),
+            SparseMerkleProofExt::new(None, vec![]),
+        )
+    }
+
+    #[test]
+    fn test_invalidated_on_version_advance() {
+        let cache = StateProofCache::new();
+        let key = HashValue::random();
+
+        cache.put(key, 1, leaf_and_proof(1));
+        assert_eq!(cache.get(&key, 1), Some(leaf_and_proof(1)));
+        assert!(cache.get(&key, 2).is_none());
+
+        cache.put(key, 2, leaf_and_proof(2));
+        assert!(cache.get(&key, 1).is_none());
+        assert_eq!(cache.get(&key, 2), Some(leaf_and_proof(2)));
+
+        // A proof at an older version doesn't evict the newer ones.
+        cache.put(key, 1, leaf_and_proof(1));
+        assert!(cache.get(&key, 1).is_none());
+        assert_eq!(cache.get(&key, 2), Some(leaf_and_proof(2)));
+    }
+}
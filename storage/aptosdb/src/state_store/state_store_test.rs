@@ -6,6 +6,7 @@ use super::*;
 use crate::{
     jellyfish_merkle_node::JellyfishMerkleNodeSchema,
     new_sharded_kv_schema_batch,
+    state_consistency_auditor::{audit_state_consistency, AuditResult},
     state_restore::StateSnapshotRestore,
     test_helper::{arb_state_kv_sets, update_store},
     AptosDB,
@@ -339,6 +340,62 @@ pub fn test_get_state_snapshot_before() {
     );
 }
 
+#[test]
+fn test_audit_state_consistency() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let store = &db.state_store;
+
+    let kv: Vec<_> = (0..100u8)
+        .map(|i| (StateKey::raw(vec![i]), StateValue::from(vec![i])))
+        .collect();
+    put_value_set(store, kv.clone(), 0, None);
+
+    // hack: VersionData expected on every version, so duplicate the data at version 1
+    let usage = store.get_usage(Some(0)).unwrap();
+    store
+        .ledger_db
+        .metadata_db()
+        .put::<VersionDataSchema>(&1, &usage.into())
+        .unwrap();
+
+    // Update some of the values at version 2, the state at version 0 is audited as of version 0.
+    let updated_kv: Vec<_> = kv[..10]
+        .iter()
+        .map(|(key, _)| (key.clone(), StateValue::from(b"updated".to_vec())))
+        .collect();
+    put_value_set(store, updated_kv, 2, Some(0));
+    assert_eq!(
+        audit_state_consistency(&store.state_db, 0).unwrap(),
+        AuditResult::Consistent
+    );
+    assert_eq!(
+        audit_state_consistency(&store.state_db, 2).unwrap(),
+        AuditResult::Consistent
+    );
+
+    // Corrupt a value in the state kv db, only the shard of its key is reported.
+    let (key, _) = &kv[50];
+    store
+        .state_kv_db
+        .db_shard(key.get_shard_id())
+        .put::<StateValueSchema>(
+            &(key.clone(), 2),
+            &Some(StateValue::from(b"corrupted".to_vec())),
+        )
+        .unwrap();
+    assert_eq!(
+        audit_state_consistency(&store.state_db, 0).unwrap(),
+        AuditResult::Consistent
+    );
+    match audit_state_consistency(&store.state_db, 2).unwrap() {
+        AuditResult::Inconsistent {
+            mismatched_shards, ..
+        } => assert_eq!(mismatched_shards, vec![key.get_shard_id()]),
+        result => panic!("Unexpected audit result: {:?}", result),
+    }
+}
+
 proptest! {
     #![proptest_config(ProptestConfig::with_cases(10))]
 
@@ -9,22 +9,25 @@ use crate::{
     ledger_db::LedgerDb,
     schema::{
         transaction::TransactionSchema, transaction_by_account::TransactionByAccountSchema,
+        transaction_by_account_time::TransactionByAccountTimeSchema,
         transaction_by_hash::TransactionByHashSchema, write_set::WriteSetSchema,
     },
     transaction_accumulator::TransactionAccumulatorSchema,
     transaction_info::TransactionInfoSchema,
-    utils::iterators::{AccountTransactionVersionIter, ExpectContinuousVersions},
+    utils::iterators::{
+        AccountTransactionTimeVersionIter, AccountTransactionVersionIter, ExpectContinuousVersions,
+    },
 };
 use anyhow::{ensure, format_err, Result};
 use aptos_crypto::{hash::CryptoHash, HashValue};
-use aptos_schemadb::{ReadOptions, SchemaBatch};
+use aptos_schemadb::{schema::Schema, ReadOptions, SchemaBatch, DB};
 use aptos_types::{
     account_address::AccountAddress,
     proof::position::Position,
     transaction::{Transaction, Version},
     write_set::WriteSet,
 };
-use std::sync::Arc;
+use std::{cmp::max, sync::Arc};
 
 #[cfg(test)]
 mod test;
@@ -105,11 +108,32 @@ impl TransactionStore {
         ))
     }
 
+    /// Gets an iterator that yields `(sequence_number, version)` for each transaction sent by an
+    /// account in the blocks with timestamps in `[start_timestamp_usecs, end_timestamp_usecs)`,
+    /// in sequence number order, stopping at the first one with `version > ledger_version`.
+    pub fn get_account_transaction_version_iter_by_time(
+        &self,
+        address: AccountAddress,
+        start_timestamp_usecs: u64,
+        end_timestamp_usecs: u64,
+        ledger_version: Version,
+    ) -> Result<AccountTransactionTimeVersionIter> {
+        let mut iter = self
+            .ledger_db
+            .transaction_db()
+            .iter::<TransactionByAccountTimeSchema>(ReadOptions::default())?;
+        iter.seek(&(address, start_timestamp_usecs, 0))?;
+        Ok(AccountTransactionTimeVersionIter::new(
+            iter,
+            address,
+            end_timestamp_usecs,
+            ledger_version,
+        ))
+    }
+
     /// Get signed transaction given `version`
     pub fn get_transaction(&self, version: Version) -> Result<Transaction> {
-        self.ledger_db
-            .transaction_db()
-            .get::<TransactionSchema>(&version)?
+        self.get_tiered::<TransactionSchema>(self.ledger_db.transaction_db(), version)?
             .ok_or_else(|| AptosDbError::NotFound(format!("Txn {}", version)).into())
     }
 
@@ -119,12 +143,8 @@ impl TransactionStore {
         start_version: Version,
         num_transactions: usize,
     ) -> Result<impl Iterator<Item = Result<Transaction>> + '_> {
-        let mut iter = self
-            .ledger_db
-            .transaction_db()
-            .iter::<TransactionSchema>(ReadOptions::default())?;
-        iter.seek(&start_version)?;
-        iter.expect_continuous_versions(start_version, num_transactions)
+        self.get_tiered_iter::<TransactionSchema>(self.ledger_db.transaction_db(), start_version)?
+            .expect_continuous_versions(start_version, num_transactions)
     }
 
     /// Gets an iterator that yields `num_transactions` write sets starting from `start_version`.
@@ -133,19 +153,17 @@ impl TransactionStore {
         start_version: Version,
         num_transactions: usize,
     ) -> Result<impl Iterator<Item = Result<WriteSet>> + '_> {
-        let mut iter = self
-            .ledger_db
-            .write_set_db()
-            .iter::<WriteSetSchema>(ReadOptions::default())?;
-        iter.seek(&start_version)?;
-        iter.expect_continuous_versions(start_version, num_transactions)
+        self.get_tiered_iter::<WriteSetSchema>(self.ledger_db.write_set_db(), start_version)?
+            .expect_continuous_versions(start_version, num_transactions)
     }
 
-    /// Save signed transaction at `version`
+    /// Save signed transaction at `version`. A user transaction is added to the time index only
+    /// if the timestamp of its block, `block_timestamp_usecs`, is known.
     pub fn put_transaction(
         &self,
         version: Version,
         transaction: &Transaction,
+        block_timestamp_usecs: Option<u64>,
         skip_index: bool,
         batch: &SchemaBatch,
     ) -> Result<()> {
@@ -155,6 +173,12 @@ impl TransactionStore {
                     &(txn.sender(), txn.sequence_number()),
                     &version,
                 )?;
+                if let Some(timestamp_usecs) = block_timestamp_usecs {
+                    batch.put::<TransactionByAccountTimeSchema>(
+                        &(txn.sender(), timestamp_usecs, txn.sequence_number()),
+                        &version,
+                    )?;
+                }
             }
         }
         batch.put::<TransactionByHashSchema>(&transaction.hash(), &version)?;
@@ -165,9 +189,7 @@ impl TransactionStore {
 
     /// Get executed transaction vm output given `version`
     pub fn get_write_set(&self, version: Version) -> Result<WriteSet> {
-        self.ledger_db
-            .write_set_db()
-            .get::<WriteSetSchema>(&version)?
+        self.get_tiered::<WriteSetSchema>(self.ledger_db.write_set_db(), version)?
             .ok_or_else(|| {
                 AptosDbError::NotFound(format!("WriteSet at version {}", version)).into()
             })
@@ -191,11 +213,8 @@ impl TransactionStore {
             end_version
         );
 
-        let mut iter = self
-            .ledger_db
-            .write_set_db()
-            .iter::<WriteSetSchema>(Default::default())?;
-        iter.seek(&begin_version)?;
+        let mut iter =
+            self.get_tiered_iter::<WriteSetSchema>(self.ledger_db.write_set_db(), begin_version)?;
 
         let mut ret = Vec::with_capacity((end_version - begin_version) as usize);
         for current_version in begin_version..end_version {
@@ -215,6 +234,55 @@ impl TransactionStore {
         Ok(ret)
     }
 
+    /// Gets the value at `version` from the cold ledger db if the version is below the tiering
+    /// horizon, otherwise from `hot_db`.
+    fn get_tiered<S: Schema<Key = Version>>(
+        &self,
+        hot_db: &DB,
+        version: Version,
+    ) -> Result<Option<S::Value>> {
+        let cold_ledger_db = match self.ledger_db.cold_ledger_db() {
+            Some(cold_ledger_db) => cold_ledger_db,
+            None => return hot_db.get::<S>(&version),
+        };
+        if version < cold_ledger_db.horizon() {
+            return cold_ledger_db.db().get::<S>(&version);
+        }
+
+        match hot_db.get::<S>(&version)? {
+            Some(value) => Ok(Some(value)),
+            // The version may have been moved to the cold ledger db since the horizon was read.
+            None => cold_ledger_db.db().get::<S>(&version),
+        }
+    }
+
+    /// Gets an iterator over the values from `start_version` on, reading the versions below the
+    /// tiering horizon from the cold ledger db and the rest from `hot_db`.
+    fn get_tiered_iter<'a, S: Schema<Key = Version>>(
+        &'a self,
+        hot_db: &'a DB,
+        start_version: Version,
+    ) -> Result<impl Iterator<Item = Result<(Version, S::Value)>> + 'a> {
+        // The hot iterator is created before the horizon is read, so that the versions moved to
+        // the cold ledger db in between are still visible in its snapshot.
+        let mut hot_iter = hot_db.iter::<S>(ReadOptions::default())?;
+        let mut cold_iter = None;
+        let mut horizon = 0;
+        if let Some(cold_ledger_db) = self.ledger_db.cold_ledger_db() {
+            horizon = cold_ledger_db.horizon();
+            if start_version < horizon {
+                let mut iter = cold_ledger_db.db().iter::<S>(ReadOptions::default())?;
+                iter.seek(&start_version)?;
+                cold_iter = Some(iter.take_while(move |res| {
+                    res.as_ref().map_or(true, |(version, _)| *version < horizon)
+                }));
+            }
+        }
+        hot_iter.seek(&max(start_version, horizon))?;
+
+        Ok(cold_iter.into_iter().flatten().chain(hot_iter))
+    }
+
     /// Save executed transaction vm output given `version`
     pub fn put_write_set(
         &self,
@@ -237,21 +305,65 @@ impl TransactionStore {
         Ok(())
     }
 
-    /// Prune the transaction by account store given a list of transaction
+    /// Prune the transaction by account store and its time index given a list of consecutive
+    /// transactions
     pub fn prune_transaction_by_account(
         &self,
         transactions: &[Transaction],
         db_batch: &SchemaBatch,
     ) -> Result<()> {
+        let mut block_timestamp_usecs = None;
         for transaction in transactions {
+            if let Some(timestamp_usecs) = get_block_timestamp_usecs(transaction) {
+                block_timestamp_usecs = Some(timestamp_usecs);
+            }
             if let Some(txn) = transaction.try_as_signed_user_txn() {
                 db_batch
                     .delete::<TransactionByAccountSchema>(&(txn.sender(), txn.sequence_number()))?;
+                let timestamp_usecs = match block_timestamp_usecs {
+                    Some(timestamp_usecs) => Some(timestamp_usecs),
+                    None => {
+                        self.get_indexed_block_timestamp_usecs(txn.sender(), txn.sequence_number())?
+                    },
+                };
+                if let Some(timestamp_usecs) = timestamp_usecs {
+                    db_batch.delete::<TransactionByAccountTimeSchema>(&(
+                        txn.sender(),
+                        timestamp_usecs,
+                        txn.sequence_number(),
+                    ))?;
+                }
             }
         }
         Ok(())
     }
 
+    /// Finds the block timestamp under which the transaction sent by `address` with
+    /// `sequence_number` is in the time index, for the transactions whose block metadata
+    /// transaction isn't at hand. The account's entries are scanned in sequence number order,
+    /// which is cheap when pruning since the older ones are already gone.
+    fn get_indexed_block_timestamp_usecs(
+        &self,
+        address: AccountAddress,
+        sequence_number: u64,
+    ) -> Result<Option<u64>> {
+        let mut iter = self
+            .ledger_db
+            .transaction_db()
+            .iter::<TransactionByAccountTimeSchema>(ReadOptions::default())?;
+        iter.seek(&(address, 0, 0))?;
+        for res in iter {
+            let ((account, timestamp_usecs, seq_num), _version) = res?;
+            if account != address || seq_num > sequence_number {
+                break;
+            }
+            if seq_num == sequence_number {
+                return Ok(Some(timestamp_usecs));
+            }
+        }
+        Ok(None)
+    }
+
     /// Prune the transaction schema store between a range of version in [begin, end)
     pub fn prune_transaction_schema(
         &self,
@@ -350,3 +462,31 @@ impl TransactionStore {
         Ok(())
     }
 }
+
+/// Returns the block timestamp if `transaction` starts a block.
+pub(crate) fn get_block_timestamp_usecs(transaction: &Transaction) -> Option<u64> {
+    match transaction {
+        Transaction::BlockMetadata(block_metadata) => Some(block_metadata.timestamp_usecs()),
+        Transaction::BlockMetadataExt(block_metadata) => Some(block_metadata.timestamp_usecs()),
+        _ => None,
+    }
+}
+
+/// Returns the timestamp of the block each of the consecutive `transactions` is in. The ones
+/// before the first block metadata transaction are in the block of the previous version, whose
+/// timestamp is `previous_block_timestamp_usecs` if known.
+pub(crate) fn get_block_timestamps_usecs<'a>(
+    transactions: impl IntoIterator<Item = &'a Transaction>,
+    previous_block_timestamp_usecs: Option<u64>,
+) -> Vec<Option<u64>> {
+    let mut block_timestamp_usecs = previous_block_timestamp_usecs;
+    transactions
+        .into_iter()
+        .map(|transaction| {
+            if let Some(timestamp_usecs) = get_block_timestamp_usecs(transaction) {
+                block_timestamp_usecs = Some(timestamp_usecs);
+            }
+            block_timestamp_usecs
+        })
+        .collect()
+}
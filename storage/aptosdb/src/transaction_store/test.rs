@@ -199,6 +199,114 @@ proptest! {
 
         prop_assert_eq!(&actual_scan, &expected_scan);
     }
+
+    #[test]
+    fn test_get_account_transaction_version_iter_by_time(
+        universe in any_with::<AccountInfoUniverse>(5),
+        gens in vec(
+            (any::<Index>(), any::<SignatureCheckedTransactionGen>()),
+            1..=50,
+        ),
+        start_timestamp_usecs in 0_u64..=13_000,
+        time_range_usecs in 0_u64..=13_000,
+        ledger_version in 0_u64..50,
+    ) {
+        let tmp_dir = TempPath::new();
+        let db = AptosDB::new_for_test(&tmp_dir);
+        let store = &db.transaction_store;
+        let txns = init_store(universe, gens, store);
+        let end_timestamp_usecs = start_timestamp_usecs + time_range_usecs;
+
+        let mut expected_scan = BTreeMap::<AccountAddress, Vec<(u64, Version)>>::new();
+        for (version, txn) in txns.iter().enumerate() {
+            let version = version as Version;
+            let txn = txn.try_as_signed_user_txn().unwrap();
+            let timestamp_usecs = block_timestamp_usecs(version);
+            let txn_metadatas = expected_scan.entry(txn.sender()).or_default();
+            if version <= ledger_version
+                && timestamp_usecs >= start_timestamp_usecs
+                && timestamp_usecs < end_timestamp_usecs
+            {
+                txn_metadatas.push((txn.sequence_number(), version));
+            }
+        }
+        expected_scan.entry(AccountAddress::from_hex_literal("0x42").unwrap()).or_default();
+
+        let actual_scan = expected_scan
+            .keys()
+            .map(|address| {
+                let txn_metadatas = store
+                    .get_account_transaction_version_iter_by_time(
+                        *address,
+                        start_timestamp_usecs,
+                        end_timestamp_usecs,
+                        ledger_version,
+                    )
+                    .unwrap()
+                    .collect::<Result<Vec<_>>>()
+                    .unwrap();
+                (*address, txn_metadatas)
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        prop_assert_eq!(&actual_scan, &expected_scan);
+    }
+
+    #[test]
+    fn test_prune_transaction_by_account(
+        universe in any_with::<AccountInfoUniverse>(3),
+        gens in vec(
+            (any::<Index>(), any::<SignatureCheckedTransactionGen>()),
+            1..=30,
+        ),
+        num_pruned in 0_usize..=30,
+    ) {
+        let tmp_dir = TempPath::new();
+        let db = AptosDB::new_for_test(&tmp_dir);
+        let store = &db.transaction_store;
+        let txns = init_store(universe, gens, store);
+        let num_pruned = num_pruned.min(txns.len());
+
+        // None of the block metadata transactions are in the list, so the timestamps are looked
+        // up in the time index.
+        let batch = SchemaBatch::new();
+        store.prune_transaction_by_account(&txns[..num_pruned], &batch).unwrap();
+        store.ledger_db.transaction_db().write_schemas(batch).unwrap();
+
+        let ledger_version = txns.len() as Version - 1;
+        for (version, txn) in txns.iter().enumerate() {
+            let txn = txn.try_as_signed_user_txn().unwrap();
+            let expected = (version >= num_pruned).then_some(version as Version);
+            prop_assert_eq!(
+                store
+                    .get_account_transaction_version(
+                        txn.sender(),
+                        txn.sequence_number(),
+                        ledger_version,
+                    )
+                    .unwrap(),
+                expected
+            );
+            prop_assert_eq!(
+                store
+                    .ledger_db
+                    .transaction_db()
+                    .get::<TransactionByAccountTimeSchema>(&(
+                        txn.sender(),
+                        block_timestamp_usecs(version as Version),
+                        txn.sequence_number(),
+                    ))
+                    .unwrap(),
+                expected
+            );
+        }
+    }
+}
+
+/// The block timestamp of the transactions put by `init_store`, as if there were a few of them in
+/// each block.
+fn block_timestamp_usecs(version: Version) -> u64 {
+    version / 4 * 1000
 }
 
 fn init_store(
@@ -218,7 +326,13 @@ fn init_store(
     let batch = SchemaBatch::new();
     for (ver, txn) in txns.iter().enumerate() {
         store
-            .put_transaction(ver as Version, txn, /*skip_index=*/ false, &batch)
+            .put_transaction(
+                ver as Version,
+                txn,
+                Some(block_timestamp_usecs(ver as Version)),
+                /*skip_index=*/ false,
+                &batch,
+            )
             .unwrap();
     }
     store
@@ -6,6 +6,7 @@ use crate::{
         event::EventSchema, ledger_info::LedgerInfoSchema, state_value::StateValueSchema,
         state_value_index::StateValueIndexSchema,
         transaction_by_account::TransactionByAccountSchema,
+        transaction_by_account_time::TransactionByAccountTimeSchema,
     },
     state_kv_db::StateKvDb,
 };
@@ -325,6 +326,75 @@ impl<'a> Iterator for AccountTransactionVersionIter<'a> {
     }
 }
 
+pub struct AccountTransactionTimeVersionIter<'a> {
+    inner: SchemaIterator<'a, TransactionByAccountTimeSchema>,
+    address: AccountAddress,
+    end_timestamp_usecs: u64,
+    prev_seq_num: Option<u64>,
+    ledger_version: Version,
+}
+
+impl<'a> AccountTransactionTimeVersionIter<'a> {
+    pub(crate) fn new(
+        inner: SchemaIterator<'a, TransactionByAccountTimeSchema>,
+        address: AccountAddress,
+        end_timestamp_usecs: u64,
+        ledger_version: Version,
+    ) -> Self {
+        Self {
+            inner,
+            address,
+            end_timestamp_usecs,
+            ledger_version,
+            prev_seq_num: None,
+        }
+    }
+}
+
+impl<'a> AccountTransactionTimeVersionIter<'a> {
+    fn next_impl(&mut self) -> Result<Option<(u64, Version)>> {
+        Ok(match self.inner.next().transpose()? {
+            Some(((address, timestamp_usecs, seq_num), version)) => {
+                // No more transactions sent by this account.
+                if address != self.address {
+                    return Ok(None);
+                }
+                if timestamp_usecs >= self.end_timestamp_usecs {
+                    return Ok(None);
+                }
+
+                // Ensure seq_num_{i+1} > seq_num_{i}
+                if let Some(prev_seq_num) = self.prev_seq_num {
+                    ensure!(
+                        prev_seq_num < seq_num,
+                        "DB corruption: account transaction sequence numbers are not strictly \
+                         increasing over time: previous: {}, current: {}",
+                        prev_seq_num,
+                        seq_num,
+                    );
+                }
+
+                // No more transactions (in this view of the ledger).
+                if version > self.ledger_version {
+                    return Ok(None);
+                }
+
+                self.prev_seq_num = Some(seq_num);
+                Some((seq_num, version))
+            },
+            None => None,
+        })
+    }
+}
+
+impl<'a> Iterator for AccountTransactionTimeVersionIter<'a> {
+    type Item = Result<(u64, Version)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_impl().transpose()
+    }
+}
+
 pub struct EpochEndingLedgerInfoIter<'a> {
     inner: SchemaIterator<'a, LedgerInfoSchema>,
     next_epoch: u64,
@@ -16,12 +16,50 @@ use crate::{
     storage::BackupStorage,
     utils::{unix_timestamp_sec, GlobalRestoreOptions, RestoreRunMode, TrustedWaypointOpt},
 };
-use anyhow::Result;
+use anyhow::{ensure, Context, Result};
 use aptos_db::state_restore::StateSnapshotRestoreMode;
 use aptos_executor_types::VerifyExecutionMode;
 use aptos_logger::prelude::*;
-use aptos_types::transaction::Version;
-use std::{path::PathBuf, sync::Arc};
+use aptos_types::{transaction::Version, waypoint::Waypoint};
+use serde::{Deserialize, Serialize};
+use std::{
+    cmp::max,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// What previous runs of an incremental verification have verified. The next run only verifies
+/// the backups beyond it, and the epoch history must extend the one verified before, so that the
+/// newly verified data chains onto the previously verified data.
+#[derive(Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct VerifyWatermark {
+    /// All the transactions below this version have been verified.
+    pub next_transaction_version: Version,
+    /// Version of the latest verified state snapshot.
+    pub state_snapshot_version: Option<Version>,
+    /// Waypoint of the latest verified epoch ending LedgerInfo.
+    pub epoch_ending_waypoint: Option<Waypoint>,
+}
+
+impl VerifyWatermark {
+    /// Loads the watermark, or returns the default one if the file doesn't exist yet.
+    pub async fn load(path: &Path) -> Result<Self> {
+        if !tokio::fs::try_exists(path).await? {
+            return Ok(Self::default());
+        }
+        serde_json::from_slice(&tokio::fs::read(path).await?)
+            .with_context(|| format!("Failed to parse verify watermark {:?}.", path))
+    }
+
+    /// Replaces the watermark file atomically, so that an interrupted run leaves the previous
+    /// watermark intact.
+    pub async fn save(&self, path: &Path) -> Result<()> {
+        let tmp_path = path.with_extension("tmp");
+        tokio::fs::write(&tmp_path, serde_json::to_vec_pretty(self)?).await?;
+        tokio::fs::rename(&tmp_path, path).await?;
+        Ok(())
+    }
+}
 
 pub struct VerifyCoordinator {
     storage: Arc<dyn BackupStorage>,
@@ -34,6 +72,9 @@ pub struct VerifyCoordinator {
     skip_epoch_endings: bool,
     validate_modules: bool,
     output_transaction_analysis: Option<PathBuf>,
+    /// If set, only what's beyond the watermark persisted in this file is verified, and the
+    /// watermark is advanced on success.
+    watermark_file: Option<PathBuf>,
 }
 
 impl VerifyCoordinator {
@@ -48,6 +89,7 @@ impl VerifyCoordinator {
         skip_epoch_endings: bool,
         validate_modules: bool,
         output_transaction_analysis: Option<PathBuf>,
+        watermark_file: Option<PathBuf>,
     ) -> Result<Self> {
         Ok(Self {
             storage,
@@ -60,6 +102,7 @@ impl VerifyCoordinator {
             skip_epoch_endings,
             validate_modules,
             output_transaction_analysis,
+            watermark_file,
         })
     }
 
@@ -89,16 +132,53 @@ impl VerifyCoordinator {
             self.concurrent_downloads,
         )
         .await?;
+        let watermark = match &self.watermark_file {
+            Some(path) => {
+                let watermark = VerifyWatermark::load(path).await?;
+                info!(watermark = ?watermark, "Verifying incrementally.");
+                Some(watermark)
+            },
+            None => None,
+        };
+        let start_version = max(
+            self.start_version,
+            watermark
+                .as_ref()
+                .map_or(0, |watermark| watermark.next_transaction_version),
+        );
+
         let ver_max = Version::max_value();
-        let state_snapshot =
+        let mut state_snapshot =
             metadata_view.select_state_snapshot(self.state_snapshot_before_version)?;
         let transactions =
-            metadata_view.select_transaction_backups(self.start_version, self.end_version)?;
+            metadata_view.select_transaction_backups(start_version, self.end_version)?;
         let epoch_endings = metadata_view.select_epoch_ending_backups(ver_max)?;
 
+        let mut trusted_waypoints = self.trusted_waypoints_opt.verify()?;
+        if let Some(watermark) = &watermark {
+            if let Some(waypoint) = watermark.epoch_ending_waypoint {
+                // The epoch ending LedgerInfo verified before must still be in the epoch history.
+                let trusted_waypoint = trusted_waypoints
+                    .entry(waypoint.version())
+                    .or_insert(waypoint);
+                ensure!(
+                    *trusted_waypoint == waypoint,
+                    "Trusted waypoint {} conflicts with the verified waypoint {}.",
+                    trusted_waypoint,
+                    waypoint,
+                );
+            }
+            if state_snapshot.as_ref().map(|backup| backup.version)
+                <= watermark.state_snapshot_version
+            {
+                info!("No state snapshot newer than the watermark.");
+                state_snapshot = None;
+            }
+        }
+
         let global_opt = GlobalRestoreOptions {
             target_version: ver_max,
-            trusted_waypoints: Arc::new(self.trusted_waypoints_opt.verify()?),
+            trusted_waypoints: Arc::new(trusted_waypoints),
             run_mode: Arc::new(RestoreRunMode::Verify),
             concurrent_downloads: self.concurrent_downloads,
             replay_concurrency_level: 0, // won't replay, doesn't matter
@@ -121,6 +201,23 @@ impl VerifyCoordinator {
             ))
         };
 
+        if let (Some(waypoint), Some(epoch_history)) = (
+            watermark
+                .as_ref()
+                .and_then(|watermark| watermark.epoch_ending_waypoint),
+            epoch_history.as_ref(),
+        ) {
+            ensure!(
+                epoch_history
+                    .epoch_endings
+                    .last()
+                    .map_or(false, |li| li.version() >= waypoint.version()),
+                "Epoch history doesn't reach the verified waypoint {}.",
+                waypoint,
+            );
+        }
+
+        let state_snapshot_version = state_snapshot.as_ref().map(|backup| backup.version);
         if let Some(backup) = state_snapshot {
             info!(
                 epoch = backup.epoch,
@@ -142,20 +239,40 @@ impl VerifyCoordinator {
             .await?;
         }
 
+        let next_transaction_version = transactions.last().map(|b| b.last_version + 1);
         let txn_manifests = transactions.into_iter().map(|b| b.manifest).collect();
         TransactionRestoreBatchController::new(
             global_opt,
             self.storage,
             txn_manifests,
-            None,
+            // Incrementally, the chunks before the start version are skipped.
+            watermark.as_ref().map(|_| start_version),
             None, /* replay_from_version */
-            epoch_history,
+            epoch_history.clone(),
             VerifyExecutionMode::NoVerify,
             self.output_transaction_analysis,
         )
         .run()
         .await?;
 
+        if let (Some(path), Some(mut watermark)) = (&self.watermark_file, watermark) {
+            if let Some(version) = next_transaction_version {
+                watermark.next_transaction_version =
+                    max(watermark.next_transaction_version, version);
+            }
+            if let Some(version) = state_snapshot_version {
+                watermark.state_snapshot_version = Some(version);
+            }
+            if let Some(li) = epoch_history
+                .as_ref()
+                .and_then(|epoch_history| epoch_history.epoch_endings.last())
+            {
+                watermark.epoch_ending_waypoint = Some(Waypoint::new_epoch_boundary(li)?);
+            }
+            watermark.save(path).await?;
+            info!(watermark = ?watermark, "Verify watermark advanced.");
+        }
+
         Ok(())
     }
 }
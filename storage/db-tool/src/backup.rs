@@ -163,6 +163,14 @@ pub struct VerifyOpt {
         help = "Optionally, while verifying transactions, output analysis files to specified dir."
     )]
     output_transaction_analysis: Option<PathBuf>,
+    #[clap(
+        long,
+        value_parser,
+        help = "Optionally, verify incrementally: only verify the backups beyond the watermark \
+        persisted in this file by previous runs (created if it doesn't exist), and advance it on \
+        success. The epoch history is always verified, and must extend the one verified before."
+    )]
+    watermark_file: Option<PathBuf>,
 }
 
 impl Command {
@@ -246,6 +254,7 @@ impl Command {
                     opt.skip_epoch_endings,
                     opt.validate_modules,
                     opt.output_transaction_analysis,
+                    opt.watermark_file,
                 )?
                 .run()
                 .await?
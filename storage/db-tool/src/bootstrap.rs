@@ -5,7 +5,8 @@
 use anyhow::{ensure, format_err, Context, Result};
 use aptos_config::config::{
     RocksdbConfigs, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
-    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
+    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_LEDGER_TIERING_CONFIG,
+    NO_OP_STORAGE_PRUNER_CONFIG,
 };
 use aptos_db::AptosDB;
 use aptos_executor::db_bootstrapper::calculate_genesis;
@@ -53,8 +54,10 @@ impl Command {
             StorageDirPaths::from_path(&self.db_dir),
             false,
             NO_OP_STORAGE_PRUNER_CONFIG, /* pruner */
+            &NO_OP_LEDGER_TIERING_CONFIG,
             RocksdbConfigs::default(),
             false, /* indexer */
+            false, /* event type index */
             BUFFERED_STATE_TARGET_ITEMS,
             DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
         )
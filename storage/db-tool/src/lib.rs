@@ -8,6 +8,7 @@ mod backup_maintenance;
 mod bootstrap;
 mod replay_verify;
 pub mod restore;
+mod snapshot;
 #[cfg(test)]
 mod tests;
 mod utils;
@@ -32,8 +33,15 @@ pub enum DBTool {
 
     ReplayVerify(replay_verify::Opt),
 
+    Reshard(db_debugger::reshard::Cmd),
+
     #[clap(subcommand)]
     Restore(restore::Command),
+
+    #[clap(subcommand)]
+    Snapshot(snapshot::Command),
+
+    Truncate(db_debugger::truncate::Cmd),
 }
 
 impl DBTool {
@@ -44,7 +52,10 @@ impl DBTool {
             DBTool::Bootstrap(cmd) => cmd.run(),
             DBTool::Debug(cmd) => cmd.run(),
             DBTool::ReplayVerify(cmd) => cmd.run().await,
+            DBTool::Reshard(cmd) => cmd.run(),
             DBTool::Restore(cmd) => cmd.run().await,
+            DBTool::Snapshot(cmd) => cmd.run(),
+            DBTool::Truncate(cmd) => cmd.run(),
         }
     }
 }
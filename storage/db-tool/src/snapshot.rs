@@ -0,0 +1,362 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Exports the state at a version as a self-contained snapshot directory, and imports it into a
+//! DB. Unlike a backup, a snapshot doesn't need a backup storage: it's a set of chunk files and a
+//! manifest, meant to be distributed out-of-band (e.g., over a CDN or a torrent), and verified
+//! with nothing but the files themselves (and optionally a trusted waypoint) before restoring.
+//!
+//! The snapshot directory contains:
+//!   - `chunk_<idx>.bcs`: BCS serialized `StateValueChunkWithProof` of consecutive state values.
+//!   - `manifest.json`: the `SnapshotManifest`, listing the size and SHA3-256 checksum of every
+//!     chunk file, and proving the state root hash at the version.
+//!
+//! The export is deterministic: the same DB state, version and chunk size always produce
+//! byte-identical files.
+
+use anyhow::{ensure, format_err, Context, Result};
+use aptos_config::config::{
+    RocksdbConfigs, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
+    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_LEDGER_TIERING_CONFIG,
+    NO_OP_STORAGE_PRUNER_CONFIG,
+};
+use aptos_crypto::HashValue;
+use aptos_db::AptosDB;
+use aptos_storage_interface::{DbReader, DbWriter};
+use aptos_types::{
+    ledger_info::LedgerInfoWithSignatures, proof::TransactionInfoWithProof,
+    state_store::state_value::StateValueChunkWithProof, transaction::Version, waypoint::Waypoint,
+};
+use clap::{Parser, Subcommand};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+/// Bumped on incompatible changes of the snapshot format.
+const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+/// Exports and imports state snapshots in a portable, chunked and checksummed format.
+#[derive(Subcommand)]
+pub enum Command {
+    #[clap(about = "Export the state at a version to a snapshot directory.")]
+    Export(ExportOpt),
+    #[clap(about = "Verify the checksums and the state root proof of a snapshot directory.")]
+    Verify(VerifyOpt),
+    #[clap(about = "Verify a snapshot directory, and restore the state in it to a DB.")]
+    Import(ImportOpt),
+}
+
+#[derive(Parser)]
+pub struct ExportOpt {
+    #[clap(long, value_parser)]
+    db_dir: PathBuf,
+
+    /// The version of the state to export, which must be a state checkpoint. Defaults to the
+    /// latest state checkpoint in the DB.
+    #[clap(long)]
+    version: Option<Version>,
+
+    /// The directory the snapshot is written to. It must not contain a snapshot already.
+    #[clap(long, value_parser)]
+    output_dir: PathBuf,
+
+    /// The number of state values in each chunk file.
+    #[clap(long, default_value_t = 100_000)]
+    chunk_size: usize,
+}
+
+#[derive(Parser)]
+pub struct VerifyOpt {
+    #[clap(long, value_parser)]
+    snapshot_dir: PathBuf,
+
+    /// If given, the ledger info in the manifest must match this waypoint, which ties the
+    /// snapshot to a trusted chain. Otherwise, only the consistency of the snapshot is verified.
+    #[clap(long)]
+    trusted_waypoint: Option<Waypoint>,
+}
+
+#[derive(Parser)]
+pub struct ImportOpt {
+    #[clap(flatten)]
+    verify: VerifyOpt,
+
+    #[clap(long, value_parser)]
+    target_db_dir: PathBuf,
+}
+
+/// A file of the snapshot, identified by its name in the snapshot directory.
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SnapshotFile {
+    pub name: String,
+    pub size: u64,
+    pub sha3_256: HashValue,
+}
+
+/// A chunk of the snapshot, holding the state values with hashed keys in
+/// [`first_key`, `last_key`] (right side inclusive).
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SnapshotChunk {
+    /// index of the first state value in this chunk over all state values.
+    pub first_index: u64,
+    /// index of the last state value in this chunk over all state values.
+    pub last_index: u64,
+    /// hashed key of the first state value in this chunk.
+    pub first_key: HashValue,
+    /// hashed key of the last state value in this chunk.
+    pub last_key: HashValue,
+    /// BCS serialized `StateValueChunkWithProof`.
+    pub file: SnapshotFile,
+}
+
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SnapshotManifest {
+    pub format_version: u32,
+    /// Version at which the state snapshot is taken.
+    pub version: Version,
+    /// Epoch in which the state snapshot is taken.
+    pub epoch: u64,
+    /// Hash of the state tree root.
+    pub root_hash: HashValue,
+    /// All state values in chunks.
+    pub chunks: Vec<SnapshotChunk>,
+    /// Proves that at `version` the state root hash is `root_hash`, in the chain represented by
+    /// `ledger_info`.
+    pub txn_info_with_proof: TransactionInfoWithProof,
+    /// The latest ledger info in `epoch` known to the exporting DB (the epoch ending one if the
+    /// epoch has ended).
+    pub ledger_info: LedgerInfoWithSignatures,
+}
+
+impl Command {
+    pub fn run(self) -> Result<()> {
+        match self {
+            Command::Export(opt) => opt.run(),
+            Command::Verify(opt) => opt.run().map(|_| ()),
+            Command::Import(opt) => opt.run(),
+        }
+    }
+}
+
+impl ExportOpt {
+    pub fn run(self) -> Result<()> {
+        ensure!(self.chunk_size > 0, "The chunk size must be positive.");
+        let manifest_path = self.output_dir.join(MANIFEST_FILE_NAME);
+        ensure!(
+            !manifest_path.exists(),
+            "{:?} already contains a snapshot.",
+            self.output_dir,
+        );
+        fs::create_dir_all(&self.output_dir)?;
+
+        let db = open_db(&self.db_dir, /*readonly=*/ true)?;
+        let version = match self.version {
+            Some(version) => version,
+            None => db
+                .get_latest_state_checkpoint_version()?
+                .ok_or_else(|| format_err!("The DB has no state checkpoint."))?,
+        };
+
+        let (txn_info_with_proof, ledger_info) =
+            db.get_backup_handler().get_state_root_proof(version)?;
+        let root_hash = txn_info_with_proof
+            .transaction_info()
+            .ensure_state_checkpoint_hash()
+            .with_context(|| format_err!("Version {} is not a state checkpoint.", version))?;
+
+        let num_state_values = db.get_state_leaf_count(version)?;
+        println!(
+            "Exporting {} state values at version {} in chunks of {}.",
+            num_state_values, version, self.chunk_size
+        );
+
+        let mut chunks = vec![];
+        for first_index in (0..num_state_values).step_by(self.chunk_size) {
+            let chunk =
+                db.get_state_value_chunk_with_proof(version, first_index, self.chunk_size)?;
+            ensure!(
+                chunk.root_hash == root_hash,
+                "Root hash mismatch in chunk {}. root hash: {}, expected: {}",
+                chunks.len(),
+                chunk.root_hash,
+                root_hash,
+            );
+
+            let bytes = bcs::to_bytes(&chunk)?;
+            let name = format!("chunk_{}.bcs", chunks.len());
+            fs::write(self.output_dir.join(&name), &bytes)?;
+            chunks.push(SnapshotChunk {
+                first_index: chunk.first_index,
+                last_index: chunk.last_index,
+                first_key: chunk.first_key,
+                last_key: chunk.last_key,
+                file: SnapshotFile {
+                    name,
+                    size: bytes.len() as u64,
+                    sha3_256: HashValue::sha3_256_of(&bytes),
+                },
+            });
+        }
+
+        let manifest = SnapshotManifest {
+            format_version: SNAPSHOT_FORMAT_VERSION,
+            version,
+            epoch: ledger_info.ledger_info().epoch(),
+            root_hash,
+            chunks,
+            txn_info_with_proof,
+            ledger_info,
+        };
+        // The manifest is written last, so that a partially exported snapshot can't be imported.
+        fs::write(&manifest_path, serde_json::to_vec_pretty(&manifest)?)?;
+        println!(
+            "Exported {} chunks to {:?}.",
+            manifest.chunks.len(),
+            self.output_dir
+        );
+
+        Ok(())
+    }
+}
+
+impl VerifyOpt {
+    /// Verifies the whole snapshot, and returns its manifest.
+    pub fn run(&self) -> Result<SnapshotManifest> {
+        let manifest: SnapshotManifest =
+            serde_json::from_slice(&fs::read(self.snapshot_dir.join(MANIFEST_FILE_NAME))?)
+                .with_context(|| format_err!("Failed loading the snapshot manifest."))?;
+        ensure!(
+            manifest.format_version == SNAPSHOT_FORMAT_VERSION,
+            "Unsupported snapshot format version {}, expected {}.",
+            manifest.format_version,
+            SNAPSHOT_FORMAT_VERSION,
+        );
+
+        manifest
+            .txn_info_with_proof
+            .verify(manifest.ledger_info.ledger_info(), manifest.version)?;
+        let state_root_hash = manifest
+            .txn_info_with_proof
+            .transaction_info()
+            .ensure_state_checkpoint_hash()?;
+        ensure!(
+            state_root_hash == manifest.root_hash,
+            "Root hash mismatch with that in proof. root hash: {}, expected: {}",
+            manifest.root_hash,
+            state_root_hash,
+        );
+        ensure!(
+            manifest.ledger_info.ledger_info().epoch() == manifest.epoch,
+            "Epoch mismatch with that of the ledger info. epoch: {}, expected: {}",
+            manifest.epoch,
+            manifest.ledger_info.ledger_info().epoch(),
+        );
+        if let Some(waypoint) = &self.trusted_waypoint {
+            waypoint
+                .verify(manifest.ledger_info.ledger_info())
+                .with_context(|| format_err!("The ledger info doesn't match the waypoint."))?;
+        }
+
+        let mut next_index = 0;
+        for chunk in &manifest.chunks {
+            ensure!(
+                chunk.first_index == next_index,
+                "Chunks are not continuous. Expecting index {}, got {}.",
+                next_index,
+                chunk.first_index,
+            );
+            read_chunk(&self.snapshot_dir, chunk, manifest.root_hash)?;
+            next_index = chunk.last_index + 1;
+        }
+
+        println!(
+            "Verified the snapshot of {} state values at version {}.",
+            next_index, manifest.version
+        );
+        Ok(manifest)
+    }
+}
+
+impl ImportOpt {
+    pub fn run(self) -> Result<()> {
+        // Nothing is written before the whole snapshot is verified.
+        let manifest = self.verify.run()?;
+
+        let db = open_db(&self.target_db_dir, /*readonly=*/ false)?;
+        let mut receiver = db.get_state_snapshot_receiver(manifest.version, manifest.root_hash)?;
+        for (chunk_idx, chunk) in manifest.chunks.iter().enumerate() {
+            // The chunk files are read again, so they are verified again.
+            let chunk_with_proof =
+                read_chunk(&self.verify.snapshot_dir, chunk, manifest.root_hash)?;
+            receiver.add_chunk(chunk_with_proof.raw_values, chunk_with_proof.proof)?;
+            println!(
+                "Imported chunk {}/{}, last index {}.",
+                chunk_idx + 1,
+                manifest.chunks.len(),
+                chunk.last_index
+            );
+        }
+        receiver.finish_box()?;
+
+        println!(
+            "Imported the state at version {} to {:?}.",
+            manifest.version, self.target_db_dir
+        );
+        Ok(())
+    }
+}
+
+/// Reads a chunk file, verifying its checksum and that it matches the manifest. The range proof
+/// of the chunk is verified when the chunk is added to the state snapshot receiver.
+fn read_chunk(
+    snapshot_dir: &Path,
+    chunk: &SnapshotChunk,
+    root_hash: HashValue,
+) -> Result<StateValueChunkWithProof> {
+    let bytes = fs::read(snapshot_dir.join(&chunk.file.name))?;
+    ensure!(
+        bytes.len() as u64 == chunk.file.size,
+        "Size mismatch of {}. size: {}, expected: {}",
+        chunk.file.name,
+        bytes.len(),
+        chunk.file.size,
+    );
+    let sha3_256 = HashValue::sha3_256_of(&bytes);
+    ensure!(
+        sha3_256 == chunk.file.sha3_256,
+        "Checksum mismatch of {}. checksum: {}, expected: {}",
+        chunk.file.name,
+        sha3_256,
+        chunk.file.sha3_256,
+    );
+
+    let chunk_with_proof: StateValueChunkWithProof = bcs::from_bytes(&bytes)?;
+    ensure!(
+        chunk_with_proof.first_index == chunk.first_index
+            && chunk_with_proof.last_index == chunk.last_index
+            && chunk_with_proof.first_key == chunk.first_key
+            && chunk_with_proof.last_key == chunk.last_key
+            && chunk_with_proof.root_hash == root_hash
+            && chunk_with_proof.raw_values.len() as u64 == chunk.last_index - chunk.first_index + 1,
+        "{} doesn't match the manifest.",
+        chunk.file.name,
+    );
+    Ok(chunk_with_proof)
+}
+
+fn open_db(db_dir: &Path, readonly: bool) -> Result<AptosDB> {
+    AptosDB::open(
+        StorageDirPaths::from_path(db_dir),
+        readonly,
+        NO_OP_STORAGE_PRUNER_CONFIG, /* pruner */
+        &NO_OP_LEDGER_TIERING_CONFIG,
+        RocksdbConfigs::default(),
+        false, /* indexer */
+        false, /* event type index */
+        BUFFERED_STATE_TARGET_ITEMS,
+        DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    )
+}
@@ -64,6 +64,22 @@ fn test_various_cmd_parsing() {
         "--start-version",
         "Max",
     ]);
+    run_cmd(&[
+        "aptos-db-tool",
+        "snapshot",
+        "import",
+        "--snapshot-dir",
+        ".",
+        "--target-db-dir",
+        ".",
+    ]);
+    run_cmd(&[
+        "aptos-db-tool",
+        "reshard",
+        "--db-dir",
+        ".",
+        "--opt-out-backup-checkpoint",
+    ]);
 }
 
 fn run_cmd(args: &[&str]) {
@@ -74,13 +90,18 @@ fn run_cmd(args: &[&str]) {
 mod dbtool_tests {
     use crate::DBTool;
     use aptos_backup_cli::{
-        coordinators::backup::BackupCompactor,
+        coordinators::{backup::BackupCompactor, verify::VerifyWatermark},
         metadata,
         metadata::{cache::MetadataCacheOpt, view::MetadataView},
         storage::{local_fs::LocalFs, BackupStorage},
         utils::test_utils::start_local_backup_service,
     };
-    use aptos_config::config::{RocksdbConfigs, StorageDirPaths};
+    use aptos_config::config::{
+        RocksdbConfigs, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
+        DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_LEDGER_TIERING_CONFIG,
+        NO_OP_STORAGE_PRUNER_CONFIG,
+    };
+    use aptos_crypto::HashValue;
     use aptos_db::AptosDB;
     use aptos_executor_test_helpers::integration_test_impl::{
         test_execution_with_storage_impl, test_execution_with_storage_impl_inner,
@@ -90,6 +111,7 @@ mod dbtool_tests {
     use aptos_types::{
         state_store::{state_key::StateKeyTag::AccessPath, state_key_prefix::StateKeyPrefix},
         transaction::Version,
+        waypoint::Waypoint,
     };
     use clap::Parser;
     use std::{
@@ -97,6 +119,7 @@ mod dbtool_tests {
         fs,
         ops::Deref,
         path::{Path, PathBuf},
+        str::FromStr,
         sync::Arc,
         time::Duration,
     };
@@ -623,6 +646,241 @@ mod dbtool_tests {
         rt.shutdown_timeout(Duration::from_secs(1));
     }
 
+    #[test]
+    fn test_incremental_verify() {
+        let backup_dir = TempPath::new();
+        backup_dir.create_as_dir().unwrap();
+        let new_db_dir = TempPath::new();
+        let old_db_dir = TempPath::new();
+        let (rt, _) = db_restore_test_setup(
+            16,
+            16,
+            PathBuf::from(backup_dir.path()),
+            PathBuf::from(old_db_dir.path()),
+            PathBuf::from(new_db_dir.path()),
+            false,
+        );
+
+        let watermark_file = TempPath::new();
+        let verify = || {
+            rt.block_on(
+                DBTool::try_parse_from([
+                    "aptos-db-tool",
+                    "backup",
+                    "verify",
+                    "--local-fs-dir",
+                    backup_dir.path().to_str().unwrap(),
+                    "--watermark-file",
+                    watermark_file.path().to_str().unwrap(),
+                ])
+                .unwrap()
+                .run(),
+            )
+        };
+        let load_watermark = || -> VerifyWatermark {
+            serde_json::from_slice(&fs::read(watermark_file.path()).unwrap()).unwrap()
+        };
+
+        // The first run verifies everything, and persists the watermark.
+        verify().unwrap();
+        let watermark = load_watermark();
+        assert!(watermark.next_transaction_version > 0);
+        assert!(watermark.state_snapshot_version.is_some());
+        let waypoint = watermark.epoch_ending_waypoint.unwrap();
+
+        // Nothing is newer than the watermark, which stays the same.
+        verify().unwrap();
+        assert_eq!(load_watermark(), watermark);
+
+        // The epoch history of the backup must extend the verified one.
+        let conflicting_watermark = VerifyWatermark {
+            epoch_ending_waypoint: Some(
+                Waypoint::from_str(&format!("{}:{}", waypoint.version(), HashValue::zero()))
+                    .unwrap(),
+            ),
+            ..watermark
+        };
+        fs::write(
+            watermark_file.path(),
+            serde_json::to_vec(&conflicting_watermark).unwrap(),
+        )
+        .unwrap();
+        verify().unwrap_err();
+        assert_eq!(load_watermark(), conflicting_watermark);
+
+        rt.shutdown_timeout(Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_snapshot_export_import() {
+        let db_dir = TempPath::new();
+        db_dir.create_as_dir().unwrap();
+        let db = test_execution_with_storage_impl_inner(false, db_dir.path());
+        let version = db.get_latest_state_checkpoint_version().unwrap().unwrap();
+        let num_state_values = db.get_state_leaf_count(version).unwrap();
+        let expected_state_values = db
+            .get_state_value_chunk_with_proof(version, 0, num_state_values)
+            .unwrap()
+            .raw_values;
+
+        let rt = Runtime::new().unwrap();
+        let export = |snapshot_dir: &TempPath| {
+            rt.block_on(
+                DBTool::try_parse_from([
+                    "aptos-db-tool",
+                    "snapshot",
+                    "export",
+                    "--db-dir",
+                    db_dir.path().to_str().unwrap(),
+                    "--version",
+                    version.to_string().as_str(),
+                    "--output-dir",
+                    snapshot_dir.path().to_str().unwrap(),
+                    "--chunk-size",
+                    "10",
+                ])
+                .unwrap()
+                .run(),
+            )
+        };
+        let import = |snapshot_dir: &TempPath, target_db_dir: &TempPath| {
+            rt.block_on(
+                DBTool::try_parse_from([
+                    "aptos-db-tool",
+                    "snapshot",
+                    "import",
+                    "--snapshot-dir",
+                    snapshot_dir.path().to_str().unwrap(),
+                    "--target-db-dir",
+                    target_db_dir.path().to_str().unwrap(),
+                ])
+                .unwrap()
+                .run(),
+            )
+        };
+
+        // The export is deterministic.
+        let snapshot_dir = TempPath::new();
+        let another_snapshot_dir = TempPath::new();
+        export(&snapshot_dir).unwrap();
+        export(&another_snapshot_dir).unwrap();
+        for entry in fs::read_dir(snapshot_dir.path()).unwrap() {
+            let entry = entry.unwrap();
+            assert_eq!(
+                fs::read(entry.path()).unwrap(),
+                fs::read(another_snapshot_dir.path().join(entry.file_name())).unwrap()
+            );
+        }
+        // Exporting into an existing snapshot fails.
+        export(&snapshot_dir).unwrap_err();
+
+        let new_db_dir = TempPath::new();
+        import(&snapshot_dir, &new_db_dir).unwrap();
+        let new_db = AptosDB::open(
+            StorageDirPaths::from_path(new_db_dir.path()),
+            true, /* readonly */
+            NO_OP_STORAGE_PRUNER_CONFIG,
+            &NO_OP_LEDGER_TIERING_CONFIG,
+            RocksdbConfigs::default(),
+            false, /* indexer */
+            false, /* event type index */
+            BUFFERED_STATE_TARGET_ITEMS,
+            DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+        )
+        .unwrap();
+        assert_eq!(
+            new_db
+                .get_state_value_chunk_with_proof(version, 0, num_state_values)
+                .unwrap()
+                .raw_values,
+            expected_state_values
+        );
+
+        // A corrupted chunk is detected before anything is imported.
+        let chunk_path = another_snapshot_dir.path().join("chunk_1.bcs");
+        let mut chunk_bytes = fs::read(&chunk_path).unwrap();
+        chunk_bytes[0] ^= 1;
+        fs::write(&chunk_path, chunk_bytes).unwrap();
+        let another_new_db_dir = TempPath::new();
+        import(&another_snapshot_dir, &another_new_db_dir).unwrap_err();
+        assert!(!another_new_db_dir.path().exists());
+
+        rt.shutdown_timeout(Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_reshard() {
+        let db_dir = TempPath::new();
+        db_dir.create_as_dir().unwrap();
+        let db = test_execution_with_storage_impl_inner(false, db_dir.path());
+        let ledger_info = db.get_latest_ledger_info().unwrap();
+        let ledger_version = ledger_info.ledger_info().version();
+        let transactions = db
+            .get_transactions(0, ledger_version + 1, ledger_version, true)
+            .unwrap();
+        let version = db.get_latest_state_checkpoint_version().unwrap().unwrap();
+        let num_state_values = db.get_state_leaf_count(version).unwrap();
+        let expected_state_values = db
+            .get_state_value_chunk_with_proof(version, 0, num_state_values)
+            .unwrap()
+            .raw_values;
+        let block_events = db.get_latest_block_events(1000).unwrap();
+        assert!(!block_events.is_empty());
+        drop(db);
+
+        let backup_checkpoint_dir = TempPath::new();
+        let reshard = || {
+            DBTool::try_parse_from([
+                "aptos-db-tool",
+                "reshard",
+                "--db-dir",
+                db_dir.path().to_str().unwrap(),
+                "--batch-size",
+                "10",
+                "--backup-checkpoint-dir",
+                backup_checkpoint_dir.path().to_str().unwrap(),
+            ])
+            .unwrap()
+        };
+        let rt = Runtime::new().unwrap();
+        rt.block_on(reshard().run()).unwrap();
+        assert!(backup_checkpoint_dir.path().exists());
+        // The DB is sharded already.
+        rt.block_on(reshard().run()).unwrap_err();
+
+        let db = AptosDB::open(
+            StorageDirPaths::from_path(db_dir.path()),
+            true, /* readonly */
+            NO_OP_STORAGE_PRUNER_CONFIG,
+            &NO_OP_LEDGER_TIERING_CONFIG,
+            RocksdbConfigs {
+                enable_storage_sharding: true,
+                ..Default::default()
+            },
+            false, /* indexer */
+            false, /* event type index */
+            BUFFERED_STATE_TARGET_ITEMS,
+            DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+        )
+        .unwrap();
+        assert_eq!(db.get_latest_ledger_info().unwrap(), ledger_info);
+        assert_eq!(
+            db.get_transactions(0, ledger_version + 1, ledger_version, true)
+                .unwrap(),
+            transactions
+        );
+        assert_eq!(
+            db.get_state_value_chunk_with_proof(version, 0, num_state_values)
+                .unwrap()
+                .raw_values,
+            expected_state_values
+        );
+        // The sharded DB looks blocks up by height, in the index built by the migration.
+        assert_eq!(db.get_latest_block_events(1000).unwrap(), block_events);
+
+        rt.shutdown_timeout(Duration::from_secs(1));
+    }
+
     fn dir_size<P: AsRef<Path>>(path: P) -> u64 {
         let mut size = 0;
 
@@ -10,13 +10,15 @@ use crate::{
     metadata::{MetadataKey, MetadataValue},
     schema::{
         column_families, indexer_metadata::IndexerMetadataSchema, table_info::TableInfoSchema,
+        table_key::TableKeySchema,
     },
 };
 use anyhow::{bail, ensure, Result};
 use aptos_config::config::RocksdbConfig;
+use aptos_crypto::HashValue;
 use aptos_logger::warn;
 use aptos_rocksdb_options::gen_rocksdb_options;
-use aptos_schemadb::{SchemaBatch, DB};
+use aptos_schemadb::{ReadOptions, SchemaBatch, DB};
 use aptos_storage_interface::{state_view::DbStateView, DbReader};
 use aptos_types::{
     access_path::Path,
@@ -39,12 +41,16 @@ use move_resource_viewer::{AnnotatedMoveValue, MoveValueAnnotator};
 use std::{
     collections::{BTreeMap, HashMap},
     convert::TryInto,
-    sync::{atomic::Ordering, Arc},
+    sync::{atomic::Ordering, Arc, RwLock},
 };
 #[derive(Debug)]
 pub struct Indexer {
     db: DB,
     next_version: AtomicVersion,
+    table_keys_next_version: AtomicVersion,
+    /// Held for writing while the table key index is updated, and for reading while it is
+    /// scanned, so that a scan sees the index at a single version.
+    table_keys_lock: RwLock<()>,
 }
 
 impl Indexer {
@@ -64,10 +70,15 @@ impl Indexer {
         let next_version = db
             .get::<IndexerMetadataSchema>(&MetadataKey::LatestVersion)?
             .map_or(0, |v| v.expect_version());
+        let table_keys_next_version = db
+            .get::<IndexerMetadataSchema>(&MetadataKey::TableKeysLatestVersion)?
+            .map_or(0, |v| v.expect_version());
 
         Ok(Self {
             db,
             next_version: AtomicVersion::new(next_version),
+            table_keys_next_version: AtomicVersion::new(table_keys_next_version),
+            table_keys_lock: RwLock::new(()),
         })
     }
 
@@ -119,6 +130,16 @@ impl Indexer {
         }
 
         let mut batch = SchemaBatch::new();
+        // The table key index is only maintained once it has caught up with the rest of the
+        // indexer, see `index_table_keys`.
+        let index_table_keys = self.table_keys_next_version() == next_version;
+        if index_table_keys {
+            Self::collect_table_keys(write_sets, &mut batch)?;
+            batch.put::<IndexerMetadataSchema>(
+                &MetadataKey::TableKeysLatestVersion,
+                &MetadataValue::Version(end_version - 1),
+            )?;
+        }
         match table_info_parser.finish(&mut batch) {
             Ok(_) => {},
             Err(err) => {
@@ -136,19 +157,123 @@ impl Indexer {
             &MetadataKey::LatestVersion,
             &MetadataValue::Version(end_version - 1),
         )?;
+        let _guard = self
+            .table_keys_lock
+            .write()
+            .expect("Table key index lock is poisoned");
         self.db.write_schemas(batch)?;
         self.next_version.store(end_version, Ordering::Relaxed);
+        if index_table_keys {
+            self.table_keys_next_version
+                .store(end_version, Ordering::Relaxed);
+        }
+
+        Ok(())
+    }
+
+    /// Builds the table key index for transactions that were indexed before the table key index
+    /// existed. Expected to be called with continuous versions until `table_keys_next_version`
+    /// reaches `next_version`, after which the index is maintained by `index_with_annotator`.
+    pub fn index_table_keys(&self, first_version: Version, write_sets: &[&WriteSet]) -> Result<()> {
+        let next_version = self.table_keys_next_version();
+        ensure!(
+            first_version == next_version,
+            "Table key index expects to see continuous transaction versions. Expecting: {}, got: {}",
+            next_version,
+            first_version,
+        );
+        let end_version = first_version + write_sets.len() as Version;
+        ensure!(
+            end_version <= self.next_version(),
+            "Table key index can't get ahead of the indexer. Indexer next version: {}, got transactions up to {}",
+            self.next_version(),
+            end_version,
+        );
+
+        let mut batch = SchemaBatch::new();
+        Self::collect_table_keys(write_sets, &mut batch)?;
+        batch.put::<IndexerMetadataSchema>(
+            &MetadataKey::TableKeysLatestVersion,
+            &MetadataValue::Version(end_version - 1),
+        )?;
+        let _guard = self
+            .table_keys_lock
+            .write()
+            .expect("Table key index lock is poisoned");
+        self.db.write_schemas(batch)?;
+        self.table_keys_next_version
+            .store(end_version, Ordering::Relaxed);
 
         Ok(())
     }
 
+    fn collect_table_keys(write_sets: &[&WriteSet], batch: &mut SchemaBatch) -> Result<()> {
+        // Only the last write to each key within the batch matters.
+        let mut latest = HashMap::new();
+        for write_set in write_sets {
+            for (state_key, write_op) in write_set.iter() {
+                if let StateKeyInner::TableItem { handle, key } = state_key.inner() {
+                    latest.insert(
+                        (*handle, HashValue::sha3_256_of(key)),
+                        write_op.bytes().map(|_| key.clone()),
+                    );
+                }
+            }
+        }
+        for (index_key, key) in latest {
+            match key {
+                Some(key) => batch.put::<TableKeySchema>(&index_key, &key)?,
+                None => batch.delete::<TableKeySchema>(&index_key)?,
+            }
+        }
+        Ok(())
+    }
+
     pub fn next_version(&self) -> Version {
         self.next_version.load(Ordering::Relaxed)
     }
 
+    pub fn table_keys_next_version(&self) -> Version {
+        self.table_keys_next_version.load(Ordering::Relaxed)
+    }
+
     pub fn get_table_info(&self, handle: TableHandle) -> Result<Option<TableInfo>> {
         self.db.get::<TableInfoSchema>(&handle)
     }
+
+    /// Returns up to `limit` keys of the table at `version`, in the order of the SHA3-256 hashes
+    /// of the serialized keys, starting after the key with hash `start_after` if given. The index
+    /// only holds the keys of its latest version, so this returns `None` for any other version.
+    pub fn get_table_item_keys(
+        &self,
+        handle: TableHandle,
+        version: Version,
+        start_after: Option<HashValue>,
+        limit: usize,
+    ) -> Result<Option<Vec<Vec<u8>>>> {
+        let _guard = self
+            .table_keys_lock
+            .read()
+            .expect("Table key index lock is poisoned");
+        if self.table_keys_next_version() != version + 1 {
+            return Ok(None);
+        }
+        let mut iter = self.db.iter::<TableKeySchema>(ReadOptions::default())?;
+        iter.seek(&(handle, start_after.unwrap_or_else(HashValue::zero)))?;
+
+        let mut keys = vec![];
+        for res in iter {
+            let ((item_handle, key_hash), key) = res?;
+            if item_handle != handle || keys.len() >= limit {
+                break;
+            }
+            if Some(key_hash) == start_after {
+                continue;
+            }
+            keys.push(key);
+        }
+        Ok(Some(keys))
+    }
 }
 
 struct TableInfoParser<'a, R> {
@@ -8,17 +8,20 @@
 
 pub(crate) mod indexer_metadata;
 pub(crate) mod table_info;
+pub(crate) mod table_key;
 
 use aptos_schemadb::ColumnFamilyName;
 
 pub const DEFAULT_COLUMN_FAMILY_NAME: ColumnFamilyName = "default";
 pub const INDEXER_METADATA_CF_NAME: ColumnFamilyName = "indexer_metadata";
 pub const TABLE_INFO_CF_NAME: ColumnFamilyName = "table_info";
+pub const TABLE_KEY_CF_NAME: ColumnFamilyName = "table_key";
 
 pub fn column_families() -> Vec<ColumnFamilyName> {
     vec![
         /* empty cf */ DEFAULT_COLUMN_FAMILY_NAME,
         INDEXER_METADATA_CF_NAME,
         TABLE_INFO_CF_NAME,
+        TABLE_KEY_CF_NAME,
     ]
 }
@@ -0,0 +1,56 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema indexing the keys of every table, ordered by the
+//! SHA3-256 hash of the serialized key within each table, which is the order in which table
+//! contents are enumerated.
+//!
+//! ```text
+//! |<-------key------->|<---value--->|
+//! | handle | key_hash |  key bytes  |
+//! ```
+
+use crate::schema::TABLE_KEY_CF_NAME;
+use anyhow::{ensure, Result};
+use aptos_crypto::HashValue;
+use aptos_schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use aptos_types::{account_address::AccountAddress, state_store::table::TableHandle};
+
+type Key = (TableHandle, HashValue);
+
+define_schema!(TableKeySchema, Key, Vec<u8>, TABLE_KEY_CF_NAME);
+
+impl KeyCodec<TableKeySchema> for Key {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let mut encoded = self.0 .0.to_vec();
+        encoded.extend_from_slice(self.1.as_ref());
+        Ok(encoded)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        ensure!(
+            data.len() == AccountAddress::LENGTH + HashValue::LENGTH,
+            "Unexpected key length: {}",
+            data.len()
+        );
+        let handle = TableHandle(AccountAddress::try_from(&data[..AccountAddress::LENGTH])?);
+        let key_hash = HashValue::from_slice(&data[AccountAddress::LENGTH..])?;
+        Ok((handle, key_hash))
+    }
+}
+
+impl ValueCodec<TableKeySchema> for Vec<u8> {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(self.clone())
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Ok(data.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod test;
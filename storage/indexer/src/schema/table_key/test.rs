@@ -0,0 +1,19 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn test_encode_decode(
+        table_handle in any::<TableHandle>(),
+        key_hash in any::<HashValue>(),
+        key in any::<Vec<u8>>(),
+    ) {
+        assert_encode_decode::<TableKeySchema>(&(table_handle, key_hash), &key);
+    }
+}
+
+test_no_panic_decoding!(TableKeySchema);
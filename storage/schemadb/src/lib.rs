@@ -317,6 +317,12 @@ impl DB {
         rocksdb::checkpoint::Checkpoint::new(&self.inner)?.create_checkpoint(path)?;
         Ok(())
     }
+
+    /// Catches up with the writes of the primary instance, for a DB opened with
+    /// `open_cf_as_secondary`.
+    pub fn try_catch_up_with_primary(&self) -> Result<()> {
+        Ok(self.inner.try_catch_up_with_primary()?)
+    }
 }
 
 impl Drop for DB {
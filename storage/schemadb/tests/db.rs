@@ -322,6 +322,14 @@ fn test_open_as_secondary() {
         db_sec.get::<TestSchema1>(&TestField(0)).unwrap(),
         Some(TestField(0)),
     );
+
+    // The writes after opening are visible once caught up.
+    db.put::<TestSchema1>(&TestField(1), &TestField(1)).unwrap();
+    db_sec.try_catch_up_with_primary().unwrap();
+    assert_eq!(
+        db_sec.get::<TestSchema1>(&TestField(1)).unwrap(),
+        Some(TestField(1)),
+    );
 }
 
 #[test]
@@ -16,6 +16,7 @@ use aptos_types::{
     account_address::AccountAddress,
     state_store::{
         state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
+        table::TableHandle,
     },
     transaction::Version,
 };
@@ -48,6 +49,21 @@ pub trait TStateView {
     /// Get state storage usage info at epoch ending.
     fn get_usage(&self) -> Result<StateStorageUsage>;
 
+    /// Scans up to `limit` entries of the given table, ordered by the SHA3-256 hashes of the
+    /// serialized keys and starting after the key with hash `start_after`. Returns the keys found
+    /// together with the hash to continue the scan from, or `None` if the table has no entries
+    /// left. Since no more than `limit` entries are scanned, fewer keys may be returned even if
+    /// the table is not exhausted. Returns `None` if the view can't enumerate the table contents
+    /// consistently with its version.
+    fn get_table_item_keys(
+        &self,
+        _handle: &TableHandle,
+        _start_after: Option<HashValue>,
+        _limit: usize,
+    ) -> Result<Option<(Vec<Vec<u8>>, Option<HashValue>)>> {
+        Ok(None)
+    }
+
     fn as_in_memory_state_view(&self) -> InMemoryStateView {
         unreachable!("in-memory state view conversion not supported yet")
     }
@@ -87,6 +103,15 @@ where
     fn get_usage(&self) -> Result<StateStorageUsage> {
         self.deref().get_usage()
     }
+
+    fn get_table_item_keys(
+        &self,
+        handle: &TableHandle,
+        start_after: Option<HashValue>,
+        limit: usize,
+    ) -> Result<Option<(Vec<Vec<u8>>, Option<HashValue>)>> {
+        self.deref().get_table_item_keys(handle, start_after, limit)
+    }
 }
 
 impl<'a, S: 'a + StateView> AsAccountWithStateView<'a> for S {
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::cached_state_view::ShardedStateCache;
-use anyhow::{anyhow, format_err, Result};
+use anyhow::{anyhow, bail, format_err, Result};
 use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_types::{
     access_path::AccessPath,
@@ -35,6 +35,7 @@ use aptos_types::{
     },
     write_set::WriteSet,
 };
+use move_core_types::language_storage::TypeTag;
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, sync::Arc};
 use thiserror::Error;
@@ -101,6 +102,28 @@ pub enum Order {
     Descending,
 }
 
+/// The pruners whose prune window and batch size can be reconfigured at runtime
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PrunerType {
+    /// The ledger pruner (together with the state kv pruner, which follows the ledger config)
+    Ledger,
+    StateMerkle,
+    EpochSnapshot,
+}
+
+impl std::str::FromStr for PrunerType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "ledger" => Ok(PrunerType::Ledger),
+            "state_merkle" => Ok(PrunerType::StateMerkle),
+            "epoch_snapshot" => Ok(PrunerType::EpochSnapshot),
+            _ => bail!("Unknown pruner type: {}", s),
+        }
+    }
+}
+
 macro_rules! delegate_read {
     ($(
         $(#[$($attr:meta)*])*
@@ -180,6 +203,11 @@ pub trait DbReader: Send + Sync {
         /// [AptosDB::get_first_write_set_version]: ../aptosdb/struct.AptosDB.html#method.get_first_write_set_version
         fn get_first_write_set_version(&self) -> Result<Option<Version>>;
 
+        /// See [AptosDB::get_first_state_kv_version].
+        ///
+        /// [AptosDB::get_first_state_kv_version]: ../aptosdb/struct.AptosDB.html#method.get_first_state_kv_version
+        fn get_first_state_kv_version(&self) -> Result<Version>;
+
         /// See [AptosDB::get_transaction_outputs].
         ///
         /// [AptosDB::get_transaction_outputs]: ../aptosdb/struct.AptosDB.html#method.get_transaction_outputs
@@ -200,6 +228,20 @@ pub trait DbReader: Send + Sync {
             ledger_version: Version,
         ) -> Result<Vec<EventWithVersion>>;
 
+        /// Returns the events of type `type_tag` across all accounts, in the order they were
+        /// emitted, starting at the `start_index`-th event of the transaction at `start_version`.
+        /// Will return no more than `limit` events, and will ignore events with
+        /// `version > ledger_version`. Requires the event type index to be enabled since before
+        /// `start_version`.
+        fn get_events_by_type(
+            &self,
+            type_tag: &TypeTag,
+            start_version: Version,
+            start_index: u64,
+            limit: u64,
+            ledger_version: Version,
+        ) -> Result<Vec<EventWithVersion>>;
+
         fn get_transaction_iterator(
             &self,
             start_version: Version,
@@ -316,6 +358,21 @@ pub trait DbReader: Send + Sync {
             ledger_version: Version,
         ) -> Result<AccountTransactionsWithProof>;
 
+        /// Returns the list of transactions sent by an account with `address` in the blocks with
+        /// timestamps in `[start_timestamp_usecs, end_timestamp_usecs)`, in sequence number order.
+        /// Will return no more than `limit` transactions, and will ignore transactions with
+        /// `txn.version > ledger_version`. Optionally fetch events for each transaction when
+        /// `include_events` is `true`.
+        fn get_account_transactions_in_time_range(
+            &self,
+            address: AccountAddress,
+            start_timestamp_usecs: u64,
+            end_timestamp_usecs: u64,
+            limit: u64,
+            include_events: bool,
+            ledger_version: Version,
+        ) -> Result<AccountTransactionsWithProof>;
+
         /// Returns proof of new state for a given ledger info with signatures relative to version known
         /// to client
         fn get_state_proof_with_ledger_info(
@@ -443,6 +500,18 @@ pub trait DbReader: Send + Sync {
         /// Get table info from the internal indexer.
         fn get_table_info(&self, handle: TableHandle) -> Result<TableInfo>;
 
+        /// Get up to `limit` keys of a table at `version` from the internal indexer, ordered by
+        /// the SHA3-256 hashes of the serialized keys and starting after the key with hash
+        /// `start_after`. Returns `None` if the indexer is not enabled, or if its table key index
+        /// is not at `version`.
+        fn get_table_item_keys(
+            &self,
+            handle: TableHandle,
+            version: Version,
+            start_after: Option<HashValue>,
+            limit: usize,
+        ) -> Result<Option<Vec<Vec<u8>>>>;
+
         /// Returns whether the internal indexer DB has been enabled or not
         fn indexer_enabled(&self) -> bool;
 
@@ -558,6 +627,17 @@ pub trait DbWriter: Send + Sync {
     ) -> Result<()> {
         unimplemented!()
     }
+
+    /// Updates the prune window and the batch size of the given pruner. The new config takes
+    /// effect immediately, without restarting the node.
+    fn update_pruner_config(
+        &self,
+        pruner_type: PrunerType,
+        prune_window: Version,
+        batch_size: usize,
+    ) -> Result<()> {
+        unimplemented!()
+    }
 }
 
 #[derive(Clone)]
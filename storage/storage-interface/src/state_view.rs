@@ -4,10 +4,12 @@
 
 use crate::DbReader;
 use anyhow::Result;
+use aptos_crypto::HashValue;
 use aptos_state_view::TStateView;
 use aptos_types::{
     state_store::{
         state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
+        table::TableHandle,
     },
     transaction::Version,
 };
@@ -38,6 +40,36 @@ impl TStateView for DbStateView {
     fn get_usage(&self) -> Result<StateStorageUsage> {
         self.db.get_state_storage_usage(self.version)
     }
+
+    fn get_table_item_keys(
+        &self,
+        handle: &TableHandle,
+        start_after: Option<HashValue>,
+        limit: usize,
+    ) -> Result<Option<(Vec<Vec<u8>>, Option<HashValue>)>> {
+        let version = match self.version {
+            Some(version) => version,
+            None => return Ok(Some((vec![], None))),
+        };
+        if limit == 0 {
+            return Ok(Some((vec![], start_after)));
+        }
+
+        // The index only reflects its latest version, any other version can't be served.
+        let keys = match self
+            .db
+            .get_table_item_keys(*handle, version, start_after, limit)?
+        {
+            Some(keys) => keys,
+            None => return Ok(None),
+        };
+        let next = if keys.len() < limit {
+            None
+        } else {
+            keys.last().map(|key| HashValue::sha3_256_of(key))
+        };
+        Ok(Some((keys, next)))
+    }
 }
 
 pub trait LatestDbStateCheckpointView {
@@ -23,10 +23,13 @@ use aptos_rest_client::Client as RestClient;
 use aptos_sdk::{
     move_types::account_address::AccountAddress,
     transaction_builder::aptos_stdlib,
-    types::on_chain_config::{OnChainConsensusConfig, OnChainExecutionConfig},
+    types::on_chain_config::{
+        DagConsensusConfigV1, OnChainConsensusConfig, OnChainExecutionConfig,
+    },
 };
 use aptos_testcases::{
     compatibility_test::SimpleValidatorUpgrade,
+    consensus_chaos_test::ConsensusChaosTest,
     consensus_reliability_tests::ChangingWorkingQuorumTest,
     forge_setup_test::ForgeSetupTest,
     framework_upgrade::FrameworkUpgrade,
@@ -55,7 +58,7 @@ use aptos_testcases::{
     validator_reboot_stress_test::ValidatorRebootStressTest,
     CompositeNetworkTest,
 };
-use clap::{Parser, Subcommand, __derive_refs::once_cell::sync::Lazy};
+use clap::{__derive_refs::once_cell::sync::Lazy, Parser, Subcommand};
 use futures::stream::{FuturesUnordered, StreamExt};
 use rand::{rngs::ThreadRng, seq::SliceRandom, Rng};
 use std::{
@@ -549,6 +552,7 @@ fn get_test_suite(
         "large_db_simple_test" => large_db_simple_test(),
         "consensus_only_realistic_env_max_tps" => run_consensus_only_realistic_env_max_tps(),
         "quorum_store_reconfig_enable_test" => quorum_store_reconfig_enable_test(),
+        "dag_quorum_store_chaos_test" => dag_quorum_store_chaos_test(),
         "mainnet_like_simulation_test" => mainnet_like_simulation_test(),
         "gather_metrics" => gather_metrics(),
         _ => return Err(format_err!("Invalid --suite given: {:?}", test_name)),
@@ -1430,6 +1434,34 @@ fn validator_reboot_stress_test() -> ForgeConfig {
         }))
 }
 
+/// Runs DAG ordering with quorum store payloads, while cycling through network
+/// latency, validator restarts and partitions. Liveness and the absence of forks
+/// are checked after every fault.
+fn dag_quorum_store_chaos_test() -> ForgeConfig {
+    ForgeConfig::default()
+        .with_initial_validator_count(NonZeroUsize::new(10).unwrap())
+        .with_initial_fullnode_count(2)
+        .add_network_test(ConsensusChaosTest {
+            latency_ms: 200,
+            jitter_ms: 50,
+            num_restarted_validators: 2,
+            partition_percentage: 20,
+            fault_duration: Duration::from_secs(30),
+            recovery_timeout: Duration::from_secs(120),
+        })
+        .with_genesis_helm_config_fn(Arc::new(|helm_values| {
+            // A few epoch changes over the test, to also exercise DAG epoch transitions
+            helm_values["chain"]["epoch_duration_secs"] = 300.into();
+            helm_values["chain"]["on_chain_consensus_config"] = serde_yaml::to_value(
+                OnChainConsensusConfig::DagV2(DagConsensusConfigV1::default()),
+            )
+            .expect("must serialize");
+        }))
+        .with_emit_job(EmitJobRequest::default().mode(EmitJobMode::ConstTps { tps: 1000 }))
+        // Validators are restarted on purpose, so there is no add_no_restarts()
+        .with_success_criteria(SuccessCriteria::new(500).add_wait_for_catchup_s(240))
+}
+
 fn apply_config_for_quorum_store_single_node(config: &mut NodeConfig) {
     config
         .consensus
@@ -5,14 +5,14 @@ use aptos_crypto::{
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
     hash::{CryptoHasher as _, TestOnlyHasher},
     multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
-    secp256k1_ecdsa,
+    p256_ecdsa, secp256k1_ecdsa,
     traits::{SigningKey, Uniform},
 };
 use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
 use aptos_types::{
     access_path::{AccessPath, Path},
     account_config::{CoinStoreResource, DepositEvent, WithdrawEvent},
-    contract_event, event,
+    block_metadata_ext, contract_event, event,
     state_store::state_key::StateKey,
     transaction,
     transaction::authenticator::{AccountAuthenticator, TransactionAuthenticator},
@@ -59,6 +59,13 @@ fn trace_crypto_values(tracer: &mut Tracer, samples: &mut Samples) -> Result<()>
     tracer.trace_value(samples, &secp256k1_public_key)?;
     tracer.trace_value(samples, &secp256k1_signature)?;
 
+    let secp256r1_private_key = p256_ecdsa::PrivateKey::generate(&mut rng);
+    let secp256r1_public_key = aptos_crypto::PrivateKey::public_key(&secp256r1_private_key);
+    let secp256r1_signature = secp256r1_private_key.sign(&message).unwrap();
+    tracer.trace_value(samples, &secp256r1_private_key)?;
+    tracer.trace_value(samples, &secp256r1_public_key)?;
+    tracer.trace_value(samples, &secp256r1_signature)?;
+
     Ok(())
 }
 
@@ -74,6 +81,7 @@ pub fn get_registry() -> Result<Registry> {
     // stdlib types
     tracer.trace_type::<contract_event::ContractEvent>(&samples)?;
     tracer.trace_type::<language_storage::TypeTag>(&samples)?;
+    tracer.trace_type::<block_metadata_ext::BlockMetadataExt>(&samples)?;
     tracer.trace_type::<transaction::Transaction>(&samples)?;
     tracer.trace_type::<transaction::TransactionArgument>(&samples)?;
     tracer.trace_type::<transaction::TransactionPayload>(&samples)?;
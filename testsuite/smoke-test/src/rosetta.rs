@@ -1085,6 +1085,7 @@ async fn parse_block_transactions(
                 assert!(matches!(
                     actual_txn.transaction,
                     aptos_types::transaction::Transaction::BlockMetadata(_)
+                        | aptos_types::transaction::Transaction::BlockMetadataExt(_)
                 ));
                 assert!(transaction.operations.is_empty());
             },
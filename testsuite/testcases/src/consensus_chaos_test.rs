@@ -0,0 +1,148 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::NetworkLoadTest;
+use anyhow::Context;
+use aptos_forge::{
+    GroupNetworkDelay, NetworkContext, NetworkTest, Result, Swarm, SwarmChaos, SwarmExt,
+    SwarmNetworkDelay, SwarmNetworkPartition, Test, TestReport,
+};
+use aptos_logger::info;
+use rand::{seq::SliceRandom, thread_rng};
+use std::time::{Duration, Instant};
+use tokio::runtime::Runtime;
+
+/// Cycles through network latency, validator restarts and partial partitions
+/// while load is running. After every fault is healed, the test asserts that
+/// all nodes make progress again and that no fork happened.
+pub struct ConsensusChaosTest {
+    /// Latency injected between the two halves of the validator set
+    pub latency_ms: u64,
+    pub jitter_ms: u64,
+    /// Number of validators restarted at once
+    pub num_restarted_validators: usize,
+    /// Percentage of validators cut off from the rest of the network
+    pub partition_percentage: u64,
+    /// How long each fault is kept in place before it is healed
+    pub fault_duration: Duration,
+    /// How long the network has to recover after each fault is healed
+    pub recovery_timeout: Duration,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Fault {
+    Latency,
+    Restart,
+    Partition,
+}
+
+impl ConsensusChaosTest {
+    fn create_delay_chaos(&self, swarm: &dyn Swarm) -> SwarmChaos {
+        let all_validators = swarm.validators().map(|v| v.peer_id()).collect::<Vec<_>>();
+        let (first_half, second_half) = all_validators.split_at(all_validators.len() / 2);
+        SwarmChaos::Delay(SwarmNetworkDelay {
+            group_network_delays: vec![GroupNetworkDelay {
+                name: "consensus-chaos-delay".to_string(),
+                source_nodes: first_half.to_vec(),
+                target_nodes: second_half.to_vec(),
+                latency_ms: self.latency_ms,
+                jitter_ms: self.jitter_ms,
+                correlation_percentage: 50,
+            }],
+        })
+    }
+
+    fn create_partition_chaos(&self) -> SwarmChaos {
+        SwarmChaos::Partition(SwarmNetworkPartition {
+            partition_percentage: self.partition_percentage,
+        })
+    }
+
+    fn inject_fault(&self, swarm: &mut dyn Swarm, runtime: &Runtime, fault: Fault) -> Result<()> {
+        match fault {
+            Fault::Latency => {
+                let chaos = self.create_delay_chaos(swarm);
+                swarm.inject_chaos(chaos.clone())?;
+                std::thread::sleep(self.fault_duration);
+                swarm.remove_chaos(chaos)?;
+            },
+            Fault::Restart => {
+                let all_validators = swarm.validators().map(|v| v.peer_id()).collect::<Vec<_>>();
+                let addresses: Vec<_> = all_validators
+                    .choose_multiple(&mut thread_rng(), self.num_restarted_validators)
+                    .cloned()
+                    .collect();
+                for address in &addresses {
+                    let validator = swarm.validator_mut(*address).unwrap();
+                    runtime.block_on(async { validator.stop().await })?;
+                }
+                std::thread::sleep(self.fault_duration);
+                for address in &addresses {
+                    let validator = swarm.validator_mut(*address).unwrap();
+                    runtime.block_on(async { validator.start().await })?;
+                }
+            },
+            Fault::Partition => {
+                let chaos = self.create_partition_chaos();
+                swarm.inject_chaos(chaos.clone())?;
+                std::thread::sleep(self.fault_duration);
+                swarm.remove_chaos(chaos)?;
+            },
+        }
+        Ok(())
+    }
+}
+
+impl Test for ConsensusChaosTest {
+    fn name(&self) -> &'static str {
+        "consensus chaos test"
+    }
+}
+
+impl NetworkLoadTest for ConsensusChaosTest {
+    fn test(
+        &self,
+        swarm: &mut dyn Swarm,
+        report: &mut TestReport,
+        duration: Duration,
+    ) -> Result<()> {
+        let start = Instant::now();
+        let runtime = Runtime::new().unwrap();
+
+        for fault in [Fault::Latency, Fault::Restart, Fault::Partition]
+            .into_iter()
+            .cycle()
+        {
+            if start.elapsed() >= duration {
+                break;
+            }
+
+            info!("Injecting {:?} fault", fault);
+            self.inject_fault(swarm, &runtime, fault)?;
+
+            runtime
+                .block_on(swarm.liveness_check(Instant::now() + self.recovery_timeout))
+                .with_context(|| format!("Network did not recover from {:?} fault", fault))?;
+            swarm
+                .fork_check()
+                .with_context(|| format!("Fork detected after {:?} fault", fault))?;
+            report.report_text(format!(
+                "Network recovered from {:?} fault after {}s",
+                fault,
+                start.elapsed().as_secs()
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn finish(&self, swarm: &mut dyn Swarm) -> Result<()> {
+        swarm.remove_all_chaos()
+    }
+}
+
+impl NetworkTest for ConsensusChaosTest {
+    fn run(&self, ctx: &mut NetworkContext<'_>) -> Result<()> {
+        <dyn NetworkLoadTest>::run(self, ctx)
+    }
+}
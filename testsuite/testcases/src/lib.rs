@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod compatibility_test;
+pub mod consensus_chaos_test;
 pub mod consensus_reliability_tests;
 pub mod forge_setup_test;
 pub mod framework_upgrade;
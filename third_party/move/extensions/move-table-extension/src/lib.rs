@@ -95,6 +95,19 @@ pub trait TableResolver {
         key: &[u8],
         maybe_layout: Option<&MoveTypeLayout>,
     ) -> Result<Option<Bytes>, anyhow::Error>;
+
+    /// Scans up to `limit` entries of the table, ordered by the SHA3-256 hashes of the serialized
+    /// keys and starting after the key with hash `start_after`. Returns the keys found and the
+    /// hash to continue from, which is `None` once the table is exhausted. Returns `None` if the
+    /// environment doesn't support enumerating table contents.
+    fn resolve_table_keys(
+        &self,
+        _handle: &TableHandle,
+        _start_after: Option<&[u8; 32]>,
+        _limit: usize,
+    ) -> Result<Option<(Vec<Vec<u8>>, Option<[u8; 32]>)>, anyhow::Error> {
+        Ok(None)
+    }
 }
 
 /// The native table context extension. This needs to be attached to the NativeContextExtensions
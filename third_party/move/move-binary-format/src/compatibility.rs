@@ -8,8 +8,9 @@ use crate::{
     file_format_common::VERSION_5,
     normalized::Module,
 };
-use move_core_types::vm_status::StatusCode;
-use std::collections::BTreeSet;
+use move_core_types::{identifier::Identifier, language_storage::ModuleId, vm_status::StatusCode};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeSet, fmt};
 
 /// The result of a linking and layout compatibility check. Here is what the different combinations. NOTE that if `check_struct_layout` is false, type safety over a series of upgrades cannot be guaranteed.
 /// mean:
@@ -71,13 +72,46 @@ impl Compatibility {
 
     /// Check compatibility for `new_module` relative to old module `old_module`.
     pub fn check(&self, old_module: &Module, new_module: &Module) -> PartialVMResult<()> {
-        let mut struct_and_pub_function_linking = true;
-        let mut struct_layout = true;
-        let mut friend_linking = true;
+        let incompatibilities = Self::incompatibilities(old_module, new_module);
+        let violates = |category| {
+            incompatibilities
+                .iter()
+                .any(|incompatibility| incompatibility.categories().contains(&category))
+        };
+
+        if self.check_struct_and_pub_function_linking
+            && violates(CompatibilityCategory::StructAndPublicFunctionLinking)
+        {
+            return Err(PartialVMError::new(
+                StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE,
+            ).with_message(format!("Module Update Failure: Public function/struct signature of new module differs from existing module in {:?}::{}", old_module.address, old_module.name)));
+        }
+        if self.check_struct_layout && violates(CompatibilityCategory::StructLayout) {
+            return Err(PartialVMError::new(
+                StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE,
+            ).with_message(format!("Module Update Failure: Struct layout of new module differs from existing modul in {:?}::{}", old_module.address, old_module.name)));
+        }
+        if self.check_friend_linking && violates(CompatibilityCategory::FriendLinking) {
+            return Err(PartialVMError::new(
+                StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE,
+            ).with_message(format!("Module Update Failure: Friend signature of new module differs from existing module in {:?}::{}", old_module.address, old_module.name)));
+        }
+
+        Ok(())
+    }
+
+    /// Collects every rule violated by `new_module` relative to old module `old_module`,
+    /// regardless of which checks are enabled. `check` rejects the upgrade if any of them falls
+    /// into an enabled category.
+    pub fn incompatibilities(old_module: &Module, new_module: &Module) -> Vec<Incompatibility> {
+        let mut incompatibilities = vec![];
 
         // module's name and address are unchanged
         if old_module.address != new_module.address || old_module.name != new_module.name {
-            struct_and_pub_function_linking = false;
+            incompatibilities.push(Incompatibility::ModuleIdChanged {
+                old: old_module.module_id(),
+                new: new_module.module_id(),
+            });
         }
 
         // old module's structs are a subset of the new module's structs
@@ -88,19 +122,21 @@ impl Compatibility {
                     // Struct not present in new . Existing modules that depend on this struct will fail to link with the new version of the module.
                     // Also, struct layout cannot be guaranteed transitively, because after
                     // removing the struct, it could be re-added later with a different layout.
-                    struct_and_pub_function_linking = false;
-                    struct_layout = false;
-                    break;
+                    incompatibilities.push(Incompatibility::StructRemoved { name: name.clone() });
+                    continue;
                 },
             };
 
-            if !struct_abilities_compatibile(old_struct.abilities, new_struct.abilities)
-                || !struct_type_parameters_compatibile(
-                    &old_struct.type_parameters,
-                    &new_struct.type_parameters,
-                )
-            {
-                struct_and_pub_function_linking = false;
+            if !struct_abilities_compatibile(old_struct.abilities, new_struct.abilities) {
+                incompatibilities
+                    .push(Incompatibility::StructAbilitiesChanged { name: name.clone() });
+            }
+            if !struct_type_parameters_compatibile(
+                &old_struct.type_parameters,
+                &new_struct.type_parameters,
+            ) {
+                incompatibilities
+                    .push(Incompatibility::StructTypeParametersChanged { name: name.clone() });
             }
             if new_struct.fields != old_struct.fields {
                 // Fields changed. Code in this module will fail at runtime if it tries to
@@ -109,7 +145,7 @@ impl Compatibility {
                 // choose that changing the name (but not position or type) of a field is
                 // compatible. The VM does not care about the name of a field
                 // (it's purely informational), but clients presumably do.
-                struct_layout = false
+                incompatibilities.push(Incompatibility::StructLayoutChanged { name: name.clone() });
             }
         }
 
@@ -131,11 +167,10 @@ impl Compatibility {
             let new_func = match new_module.exposed_functions.get(name) {
                 Some(new_func) => new_func,
                 None => {
-                    if matches!(old_func.visibility, Visibility::Friend) {
-                        friend_linking = false;
-                    } else {
-                        struct_and_pub_function_linking = false;
-                    }
+                    incompatibilities.push(Incompatibility::FunctionRemoved {
+                        name: name.clone(),
+                        visibility: old_func.visibility,
+                    });
                     continue;
                 },
             };
@@ -150,6 +185,13 @@ impl Compatibility {
                 // private can become public or friend, or stay private
                 (Visibility::Private, _) => true,
             };
+            if !is_vis_compatible {
+                incompatibilities.push(Incompatibility::FunctionVisibilityChanged {
+                    name: name.clone(),
+                    old: old_func.visibility,
+                    new: new_func.visibility,
+                });
+            }
             let is_entry_compatible = if old_module.file_format_version < VERSION_5
                 && new_module.file_format_version < VERSION_5
             {
@@ -161,20 +203,23 @@ impl Compatibility {
                 // If it was not an entry function, it is allowed to become one.
                 !old_func.is_entry || new_func.is_entry
             };
-            if !is_vis_compatible
-                || !is_entry_compatible
-                || old_func.parameters != new_func.parameters
+            if !is_entry_compatible {
+                incompatibilities.push(Incompatibility::FunctionEntryChanged {
+                    name: name.clone(),
+                    visibility: old_func.visibility,
+                });
+            }
+            if old_func.parameters != new_func.parameters
                 || old_func.return_ != new_func.return_
                 || !fun_type_parameters_compatibile(
                     &old_func.type_parameters,
                     &new_func.type_parameters,
                 )
             {
-                if matches!(old_func.visibility, Visibility::Friend) {
-                    friend_linking = false;
-                } else {
-                    struct_and_pub_function_linking = false;
-                }
+                incompatibilities.push(Incompatibility::FunctionSignatureChanged {
+                    name: name.clone(),
+                    visibility: old_func.visibility,
+                });
             }
         }
 
@@ -183,29 +228,135 @@ impl Compatibility {
         // - additions to the list are allowed
         // - removals are not allowed
         //
-        let old_friend_module_ids: BTreeSet<_> = old_module.friends.iter().cloned().collect();
-        let new_friend_module_ids: BTreeSet<_> = new_module.friends.iter().cloned().collect();
-        if !old_friend_module_ids.is_subset(&new_friend_module_ids) {
-            friend_linking = false;
+        let new_friend_module_ids: BTreeSet<_> = new_module.friends.iter().collect();
+        for friend in &old_module.friends {
+            if !new_friend_module_ids.contains(friend) {
+                incompatibilities.push(Incompatibility::FriendRemoved {
+                    module: friend.clone(),
+                });
+            }
         }
 
-        if self.check_struct_and_pub_function_linking && !struct_and_pub_function_linking {
-            return Err(PartialVMError::new(
-                StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE,
-            ).with_message(format!("Module Update Failure: Public function/struct signature of new module differs from existing module in {:?}::{}", old_module.address, old_module.name)));
-        }
-        if self.check_struct_layout && !struct_layout {
-            return Err(PartialVMError::new(
-                StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE,
-            ).with_message(format!("Module Update Failure: Struct layout of new module differs from existing modul in {:?}::{}", old_module.address, old_module.name)));
-        }
-        if self.check_friend_linking && !friend_linking {
-            return Err(PartialVMError::new(
-                StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE,
-            ).with_message(format!("Module Update Failure: Friend signature of new module differs from existing module in {:?}::{}", old_module.address, old_module.name)));
+        incompatibilities
+    }
+}
+
+/// The class of compatibility guarantee broken by an [`Incompatibility`]. These map one-to-one
+/// onto the flags of [`Compatibility`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum CompatibilityCategory {
+    /// Dependent modules referencing public functions or structs may fail to link.
+    StructAndPublicFunctionLinking,
+    /// Previously published values of a struct can no longer be deserialized.
+    StructLayout,
+    /// Friend modules referencing friend functions may fail to link.
+    FriendLinking,
+}
+
+/// A single rule violated by the new version of a module.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Incompatibility {
+    /// The address or the name of the module changed.
+    ModuleIdChanged { old: ModuleId, new: ModuleId },
+    /// A struct present in the old module does not exist anymore.
+    StructRemoved { name: Identifier },
+    /// Abilities were removed from a struct.
+    StructAbilitiesChanged { name: Identifier },
+    /// Struct type parameters changed in number, constraints or phantom-ness.
+    StructTypeParametersChanged { name: Identifier },
+    /// Struct fields changed (names, types or order).
+    StructLayoutChanged { name: Identifier },
+    /// A public, friend or entry function was removed.
+    FunctionRemoved {
+        name: Identifier,
+        visibility: Visibility,
+    },
+    /// Visibility of a function was reduced.
+    FunctionVisibilityChanged {
+        name: Identifier,
+        old: Visibility,
+        new: Visibility,
+    },
+    /// An entry function is no longer an entry function (or, for legacy file formats, the
+    /// `public(script)` status changed).
+    FunctionEntryChanged {
+        name: Identifier,
+        visibility: Visibility,
+    },
+    /// Parameters, return types or type parameters of a function changed.
+    FunctionSignatureChanged {
+        name: Identifier,
+        visibility: Visibility,
+    },
+    /// A friend declaration was removed.
+    FriendRemoved { module: ModuleId },
+}
+
+impl Incompatibility {
+    /// Returns the compatibility category this violation belongs to. Violations on friend
+    /// functions only break friend linking, while all other function violations break public
+    /// linking.
+    pub fn categories(&self) -> &'static [CompatibilityCategory] {
+        use CompatibilityCategory::*;
+        use Incompatibility::*;
+
+        let function_category = |visibility: &Visibility| -> &'static [CompatibilityCategory] {
+            if matches!(visibility, Visibility::Friend) {
+                &[FriendLinking]
+            } else {
+                &[StructAndPublicFunctionLinking]
+            }
+        };
+
+        match self {
+            ModuleIdChanged { .. }
+            | StructAbilitiesChanged { .. }
+            | StructTypeParametersChanged { .. } => &[StructAndPublicFunctionLinking],
+            // Removing a struct also breaks the layout guarantee transitively, as it could be
+            // re-added later with a different layout.
+            StructRemoved { .. } => &[StructAndPublicFunctionLinking, StructLayout],
+            StructLayoutChanged { .. } => &[StructLayout],
+            FunctionRemoved { visibility, .. }
+            | FunctionVisibilityChanged {
+                old: visibility, ..
+            }
+            | FunctionEntryChanged { visibility, .. }
+            | FunctionSignatureChanged { visibility, .. } => function_category(visibility),
+            FriendRemoved { .. } => &[FriendLinking],
         }
+    }
+}
 
-        Ok(())
+impl fmt::Display for Incompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use Incompatibility::*;
+
+        match self {
+            ModuleIdChanged { old, new } => write!(f, "module id changed from {} to {}", old, new),
+            StructRemoved { name } => write!(f, "struct `{}` was removed", name),
+            StructAbilitiesChanged { name } => {
+                write!(f, "abilities of struct `{}` were removed", name)
+            },
+            StructTypeParametersChanged { name } => {
+                write!(f, "type parameters of struct `{}` changed", name)
+            },
+            StructLayoutChanged { name } => write!(f, "fields of struct `{}` changed", name),
+            FunctionRemoved { name, visibility } => {
+                write!(f, "{:?} function `{}` was removed", visibility, name)
+            },
+            FunctionVisibilityChanged { name, old, new } => write!(
+                f,
+                "visibility of function `{}` changed from {:?} to {:?}",
+                name, old, new
+            ),
+            FunctionEntryChanged { name, .. } => {
+                write!(f, "entry modifier of function `{}` changed", name)
+            },
+            FunctionSignatureChanged { name, .. } => {
+                write!(f, "signature of function `{}` changed", name)
+            },
+            FriendRemoved { module } => write!(f, "friend declaration `{}` was removed", module),
+        }
     }
 }
 
@@ -510,6 +510,37 @@ fn deep_friend_list_ok_1() {
     adapter.publish_modules(vec![module]);
 }
 
+#[test]
+fn lazy_loading_does_not_load_friends() {
+    let data_store = InMemoryStorage::new();
+    let mut adapter = Adapter::new(data_store);
+
+    let mut modules = vec![];
+
+    // create a chain of friends which is too deep to be loaded eagerly
+    let max = 1000u64;
+    friend_chain(1, max, &mut modules);
+    adapter.publish_modules(modules);
+
+    let module_id = ModuleId::new(
+        WORKING_ACCOUNT,
+        Identifier::new(format!("A{}", max - 1)).unwrap(),
+    );
+    let adapter = adapter.fresh();
+    assert!(adapter.vm.load_module(&module_id, &adapter.store).is_err());
+
+    let config = VMConfig {
+        verifier: VerifierConfig {
+            max_dependency_depth: Some(100),
+            ..Default::default()
+        },
+        lazy_friend_loading: true,
+        ..Default::default()
+    };
+    let vm = MoveVM::new_with_config(vec![], config).unwrap();
+    vm.load_module(&module_id, &adapter.store).unwrap();
+}
+
 fn leaf_module(name: &str) -> CompiledModule {
     let mut module = empty_module();
     module.identifiers[0] = Identifier::new(name).unwrap();
@@ -27,6 +27,14 @@ pub struct VMConfig {
     pub type_base_cost: u64,
     pub type_byte_cost: u64,
     pub aggregator_v2_type_tagging: bool,
+    /// When this flag is set to true, loading a module for execution skips its friend closure.
+    /// Friends are loaded only if they are used themselves. Dependencies are still loaded eagerly
+    /// as they are needed for linking.
+    pub lazy_friend_loading: bool,
+    /// When this flag is set to true, struct layouts are also cached in a cache of the loader
+    /// which survives loader flushes. Layouts are only invalidated when a module defining one of
+    /// the structs they are made of is republished.
+    pub type_layout_cache: bool,
 }
 
 impl Default for VMConfig {
@@ -42,6 +50,8 @@ impl Default for VMConfig {
             type_base_cost: 0,
             type_byte_cost: 0,
             aggregator_v2_type_tagging: true,
+            lazy_friend_loading: false,
+            type_layout_cache: false,
         }
     }
 }
@@ -0,0 +1,173 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Cache of struct layouts which survives loader flushes.
+//!
+//! The type cache of a `Loader` is dropped whenever the loader is flushed, which happens after
+//! every module upgrade. Layouts of hot resource types then have to be derived again, even though
+//! the modules they depend on did not change. Every loader keeps this cache next to its type cache,
+//! but does not drop it on flush. Each entry records the version of every module defining a struct
+//! which the layout is made of. The version of a module is bumped every time it is (re)published,
+//! so layouts depending on it are never returned again and simply age out of the LRU.
+
+use super::StructLayoutInfoCacheItem;
+use lru::LruCache;
+use move_core_types::language_storage::{ModuleId, StructTag};
+use std::collections::HashMap;
+
+/// Maximal number of struct layouts kept in the cache of a loader.
+const LAYOUT_CACHE_CAPACITY: usize = 10_000;
+
+struct LayoutCacheEntry {
+    // The modules defining the struct and the structs nested in its fields, at the version the
+    // layout was derived from.
+    dependencies: Vec<(ModuleId, u64)>,
+    item: StructLayoutInfoCacheItem,
+}
+
+pub(crate) struct LayoutCache {
+    // Bumped whenever modules are invalidated, so that layouts derived before are not inserted.
+    generation: u64,
+    versions: HashMap<ModuleId, u64>,
+    layouts: LruCache<StructTag, LayoutCacheEntry>,
+}
+
+impl LayoutCache {
+    pub(crate) fn new() -> Self {
+        Self {
+            generation: 0,
+            versions: HashMap::new(),
+            layouts: LruCache::new(LAYOUT_CACHE_CAPACITY),
+        }
+    }
+
+    fn version(versions: &HashMap<ModuleId, u64>, module_id: &ModuleId) -> u64 {
+        versions.get(module_id).copied().unwrap_or(0)
+    }
+
+    /// Looks up the layout of the struct, unless a module it depends on was republished since it
+    /// was cached. The current generation is returned as well: callers which derive the layout on
+    /// a miss must insert it with this generation, so that a concurrent republish makes the
+    /// insertion a no-op instead of caching a stale layout.
+    pub(crate) fn get(
+        &mut self,
+        struct_tag: &StructTag,
+    ) -> (u64, Option<StructLayoutInfoCacheItem>) {
+        let versions = &self.versions;
+        let item = self.layouts.get(struct_tag).and_then(|entry| {
+            entry
+                .dependencies
+                .iter()
+                .all(|(module_id, version)| Self::version(versions, module_id) == *version)
+                .then(|| entry.item.clone())
+        });
+        (self.generation, item)
+    }
+
+    /// Caches the layout of the struct, which depends on the given modules.
+    pub(crate) fn insert(
+        &mut self,
+        struct_tag: StructTag,
+        generation: u64,
+        dependencies: impl IntoIterator<Item = ModuleId>,
+        item: StructLayoutInfoCacheItem,
+    ) {
+        if self.generation != generation {
+            return;
+        }
+        let dependencies = dependencies
+            .into_iter()
+            .map(|module_id| {
+                let version = Self::version(&self.versions, &module_id);
+                (module_id, version)
+            })
+            .collect();
+        self.layouts
+            .put(struct_tag, LayoutCacheEntry { dependencies, item });
+    }
+
+    /// Invalidates all cached layouts which depend on the given modules.
+    pub(crate) fn invalidate<'a>(&mut self, module_ids: impl IntoIterator<Item = &'a ModuleId>) {
+        for module_id in module_ids {
+            *self.versions.entry(module_id.clone()).or_insert(0) += 1;
+        }
+        self.generation += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_core_types::{
+        account_address::AccountAddress, identifier::Identifier, value::MoveStructLayout,
+    };
+
+    fn item(node_count: u64) -> StructLayoutInfoCacheItem {
+        StructLayoutInfoCacheItem {
+            struct_layout: MoveStructLayout::new(vec![]),
+            node_count,
+            has_identifier_mappings: false,
+        }
+    }
+
+    fn struct_tag(module: &str, name: &str) -> StructTag {
+        StructTag {
+            address: AccountAddress::from_hex_literal("0xcafe").unwrap(),
+            module: Identifier::new(module).unwrap(),
+            name: Identifier::new(name).unwrap(),
+            type_params: vec![],
+        }
+    }
+
+    #[test]
+    fn republish_invalidates_layouts() {
+        let mut cache = LayoutCache::new();
+        let struct_tag = struct_tag("M", "S");
+
+        let (generation, cached) = cache.get(&struct_tag);
+        assert!(cached.is_none());
+        cache.insert(
+            struct_tag.clone(),
+            generation,
+            [struct_tag.module_id()],
+            item(3),
+        );
+        assert_eq!(cache.get(&struct_tag).1.unwrap().node_count, 3);
+
+        cache.invalidate([&struct_tag.module_id()]);
+        let (new_generation, cached) = cache.get(&struct_tag);
+        assert!(cached.is_none());
+        assert_ne!(new_generation, generation);
+
+        // Layouts derived before the republish are not inserted.
+        cache.insert(
+            struct_tag.clone(),
+            generation,
+            [struct_tag.module_id()],
+            item(3),
+        );
+        assert!(cache.get(&struct_tag).1.is_none());
+    }
+
+    #[test]
+    fn republishing_a_field_module_invalidates_layouts() {
+        let mut cache = LayoutCache::new();
+        let outer = struct_tag("Outer", "S");
+        let inner = struct_tag("Inner", "T");
+        let unrelated = struct_tag("Unrelated", "U");
+
+        let (generation, _) = cache.get(&outer);
+        cache.insert(
+            outer.clone(),
+            generation,
+            [outer.module_id(), inner.module_id()],
+            item(2),
+        );
+        cache.invalidate([&unrelated.module_id()]);
+        assert!(cache.get(&outer).1.is_some());
+
+        // The layout of the outer struct changes with the struct of its field.
+        cache.invalidate([&inner.module_id()]);
+        assert!(cache.get(&outer).1.is_none());
+    }
+}
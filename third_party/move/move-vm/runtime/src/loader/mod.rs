@@ -30,7 +30,7 @@ use move_core_types::{
 use move_vm_types::loaded_data::runtime_types::{
     AbilityInfo, DepthFormula, StructIdentifier, StructType, Type,
 };
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use sha3::{Digest, Sha3_256};
 use std::{
     collections::{btree_map, BTreeMap, BTreeSet, HashMap},
@@ -39,10 +39,12 @@ use std::{
 };
 
 mod function;
+mod layout_cache;
 mod modules;
 mod type_loader;
 
 pub(crate) use function::{Function, FunctionHandle, FunctionInstantiation, LoadedFunction, Scope};
+use layout_cache::LayoutCache;
 pub(crate) use modules::{Module, ModuleCache};
 use type_loader::intern_type;
 
@@ -173,6 +175,14 @@ pub(crate) struct Loader {
     // other transactions.
     module_cache_hits: RwLock<BTreeSet<ModuleId>>,
 
+    // Modules published through this loader since the last flush. Their layouts are invalidated
+    // in the layout cache again on flush, since the publishing transaction may have been
+    // abandoned by the adapter.
+    published_modules: RwLock<BTreeSet<ModuleId>>,
+
+    // Struct layouts which survive flushes, see `LayoutCache`.
+    layout_cache: Mutex<LayoutCache>,
+
     vm_config: VMConfig,
 }
 
@@ -185,6 +195,9 @@ impl Clone for Loader {
             natives: self.natives.clone(),
             invalidated: RwLock::new(*self.invalidated.read()),
             module_cache_hits: RwLock::new(self.module_cache_hits.read().clone()),
+            published_modules: RwLock::new(self.published_modules.read().clone()),
+            // The layout cache is only an optimization, the clone starts with an empty one.
+            layout_cache: Mutex::new(LayoutCache::new()),
             vm_config: self.vm_config.clone(),
         }
     }
@@ -199,6 +212,8 @@ impl Loader {
             natives,
             invalidated: RwLock::new(false),
             module_cache_hits: RwLock::new(BTreeSet::new()),
+            published_modules: RwLock::new(BTreeSet::new()),
+            layout_cache: Mutex::new(LayoutCache::new()),
             vm_config,
         }
     }
@@ -243,10 +258,18 @@ impl Loader {
             *self.scripts.write() = ScriptCache::new();
             *self.module_cache.write() = ModuleCache::new();
             *self.type_cache.write() = TypeCache::new();
+            let published_modules = std::mem::take(&mut *self.published_modules.write());
+            self.layout_cache.lock().invalidate(&published_modules);
             *invalidated = false;
         }
     }
 
+    /// Invalidates the cached layouts which depend on the published module.
+    pub(crate) fn invalidate_published_module(&self, module_id: &ModuleId) {
+        self.layout_cache.lock().invalidate([module_id]);
+        self.published_modules.write().insert(module_id.clone());
+    }
+
     /// Mark this cache as invalidated.
     pub(crate) fn mark_as_invalid(&self) {
         *self.invalidated.write() = true;
@@ -380,7 +403,7 @@ impl Loader {
     //
 
     // Loading verifies the module if it was never loaded.
-    fn load_function_without_type_args(
+    pub(crate) fn load_function_without_type_args(
         &self,
         module_id: &ModuleId,
         function_name: &IdentStr,
@@ -664,10 +687,79 @@ impl Loader {
             /* dependencies_depth */ 0,
         )?;
 
+        // with lazy loading, friends of modules which are already cached may not be loaded yet,
+        // but they are needed to check for cyclic friend relations.
+        if self.vm_config.lazy_friend_loading {
+            self.load_friend_closure(module, bundle_verified, bundle_unverified, data_store)?;
+        }
+
         // make sure there is no cyclic dependency
         self.verify_module_cyclic_relations(module, bundle_verified, bundle_unverified)
     }
 
+    // Loads all modules reachable from the module via the friend relation, including friends of
+    // modules which are already in the code cache. Only needed when modules are loaded lazily,
+    // otherwise the code cache always contains the friend closure of its modules.
+    fn load_friend_closure(
+        &self,
+        module: &CompiledModule,
+        bundle_verified: &BTreeMap<ModuleId, CompiledModule>,
+        bundle_unverified: &BTreeSet<ModuleId>,
+        data_store: &TransactionDataCache,
+    ) -> VMResult<()> {
+        let mut visited = BTreeSet::new();
+        let mut frontier: Vec<_> = module
+            .immediate_friends()
+            .into_iter()
+            .map(|module_id| (module_id, 1))
+            .collect();
+        while let Some((module_id, depth)) = frontier.pop() {
+            if bundle_unverified.contains(&module_id) || !visited.insert(module_id.clone()) {
+                continue;
+            }
+            // keep the same depth limit as eager loading of the friend closure
+            if let Some(max_dependency_depth) = self.vm_config.verifier.max_dependency_depth {
+                if depth > max_dependency_depth {
+                    return Err(
+                        PartialVMError::new(StatusCode::MAX_DEPENDENCY_DEPTH_REACHED)
+                            .finish(Location::Undefined),
+                    );
+                }
+            }
+            if let Some(verified) = bundle_verified.get(&module_id) {
+                frontier.extend(
+                    verified
+                        .immediate_friends()
+                        .into_iter()
+                        .map(|friend| (friend, depth + 1)),
+                );
+                continue;
+            }
+
+            let cached = self.module_cache.read().module_at(&module_id);
+            let loaded = match cached {
+                Some(cached) => cached,
+                None => self.load_and_verify_module_and_dependencies(
+                    &module_id,
+                    bundle_verified,
+                    data_store,
+                    &mut BTreeSet::new(),
+                    &mut BTreeSet::new(),
+                    /* allow_module_loading_failure */ true,
+                    /* dependencies_depth */ 0,
+                )?,
+            };
+            frontier.extend(
+                loaded
+                    .module()
+                    .immediate_friends()
+                    .into_iter()
+                    .map(|friend| (friend, depth + 1)),
+            );
+        }
+        Ok(())
+    }
+
     fn verify_module_cyclic_relations(
         &self,
         module: &CompiledModule,
@@ -800,6 +892,21 @@ impl Loader {
             return Ok(cached);
         }
 
+        if self.vm_config.lazy_friend_loading {
+            // Modules in storage have been checked for cyclic relations when they were
+            // published, so it is enough to load the dependencies needed for linking. Friends
+            // are loaded when (and if) they are used.
+            return self.load_and_verify_module_and_dependencies(
+                id,
+                bundle_verified,
+                data_store,
+                &mut BTreeSet::new(),
+                &mut BTreeSet::new(),
+                /* allow_module_loading_failure */ true,
+                /* dependencies_depth */ 0,
+            );
+        }
+
         // otherwise, load the transitive closure of the target module
         let module_ref = self.load_and_verify_module_and_dependencies_and_friends(
             id,
@@ -1716,7 +1823,7 @@ impl Script {
 }
 
 #[derive(Clone)]
-struct StructLayoutInfoCacheItem {
+pub(crate) struct StructLayoutInfoCacheItem {
     struct_layout: MoveStructLayout,
     node_count: u64,
     has_identifier_mappings: bool,
@@ -1907,6 +2014,33 @@ impl Loader {
             }
         }
 
+        // The struct tag is only needed to consult the layout cache. An invalidated loader may
+        // still hold outdated modules, so it must neither read from nor write to it.
+        let struct_tag = if self.vm_config.type_layout_cache && !self.is_invalidated() {
+            self.struct_name_to_type_tag(name, ty_args, &mut PseudoGasContext {
+                cost: 0,
+                max_cost: u64::MAX,
+                cost_base: self.vm_config.type_base_cost,
+                cost_per_byte: self.vm_config.type_byte_cost,
+            })
+            .ok()
+        } else {
+            None
+        };
+        let layout_cache_generation = match &struct_tag {
+            Some(struct_tag) => {
+                let (generation, item) = self.layout_cache.lock().get(struct_tag);
+                if let Some(item) = item {
+                    *count += item.node_count;
+                    let result = (item.struct_layout.clone(), item.has_identifier_mappings);
+                    self.cache_struct_layout_info(name, ty_args, item);
+                    return Ok(result);
+                }
+                Some(generation)
+            },
+            None => None,
+        };
+
         let count_before = *count;
         let struct_type = self.get_struct_type_by_identifier(name)?;
 
@@ -1943,22 +2077,62 @@ impl Loader {
         let field_node_count = *count - count_before;
         let struct_layout = MoveStructLayout::new(field_layouts);
 
-        let mut cache = self.type_cache.write();
-        let info = cache
-            .structs
-            .entry(name.clone())
-            .or_insert_with(HashMap::new)
-            .entry(ty_args.to_vec())
-            .or_insert_with(StructInfoCache::new);
-        info.struct_layout_info = Some(StructLayoutInfoCacheItem {
+        let item = StructLayoutInfoCacheItem {
             struct_layout: struct_layout.clone(),
             node_count: field_node_count,
             has_identifier_mappings,
-        });
+        };
+        if let (Some(struct_tag), Some(generation)) = (struct_tag, layout_cache_generation) {
+            let mut dependencies = BTreeSet::from([name.module.clone()]);
+            for ty in &field_tys {
+                self.collect_layout_dependencies(ty, &mut dependencies)?;
+            }
+            self.layout_cache
+                .lock()
+                .insert(struct_tag, generation, dependencies, item.clone());
+        }
+        self.cache_struct_layout_info(name, ty_args, item);
 
         Ok((struct_layout, has_identifier_mappings))
     }
 
+    /// Collects the modules defining the structs the layout of the type is made of, i.e. the
+    /// modules whose republishing may change the layout.
+    fn collect_layout_dependencies(
+        &self,
+        ty: &Type,
+        dependencies: &mut BTreeSet<ModuleId>,
+    ) -> PartialVMResult<()> {
+        let (name, ty_args) = match ty {
+            Type::Vector(ty) => return self.collect_layout_dependencies(ty, dependencies),
+            Type::Struct { name, .. } => (name, &[][..]),
+            Type::StructInstantiation { name, ty_args, .. } => (name, ty_args.as_slice()),
+            _ => return Ok(()),
+        };
+        dependencies.insert(name.module.clone());
+        let struct_type = self.get_struct_type_by_identifier(name)?;
+        for field_ty in &struct_type.fields {
+            self.collect_layout_dependencies(&self.subst(field_ty, ty_args)?, dependencies)?;
+        }
+        Ok(())
+    }
+
+    fn cache_struct_layout_info(
+        &self,
+        name: &StructIdentifier,
+        ty_args: &[Type],
+        item: StructLayoutInfoCacheItem,
+    ) {
+        self.type_cache
+            .write()
+            .structs
+            .entry(name.clone())
+            .or_insert_with(HashMap::new)
+            .entry(ty_args.to_vec())
+            .or_insert_with(StructInfoCache::new)
+            .struct_layout_info = Some(item);
+    }
+
     // TODO[agg_v2](cleanup):
     // Currently aggregator checks are hardcoded and leaking to loader.
     // It seems that this is only because there is no support for native
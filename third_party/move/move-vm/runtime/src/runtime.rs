@@ -185,6 +185,7 @@ impl VMRuntime {
                 // old module.
                 self.loader.mark_as_invalid();
             }
+            self.loader.invalidate_published_module(&module.self_id());
             data_store.publish_module(&module.self_id(), blob, is_republishing)?;
         }
         Ok(())
@@ -355,6 +355,21 @@ impl<'r, 'l> Session<'r, 'l> {
         Ok((func, instantiation))
     }
 
+    /// Returns true if the function is private or `public(friend)`, i.e. if only its own module
+    /// and friends can call it.
+    pub fn is_friend_or_private_function(
+        &self,
+        module_id: &ModuleId,
+        function_name: &IdentStr,
+    ) -> VMResult<bool> {
+        let (_, function, _, _) = self
+            .move_vm
+            .runtime
+            .loader()
+            .load_function_without_type_args(module_id, function_name, &self.data_cache)?;
+        Ok(function.is_friend_or_private())
+    }
+
     /// Load a module, a function, and all of its types into cache
     pub fn load_function(
         &self,
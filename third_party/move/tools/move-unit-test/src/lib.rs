@@ -21,7 +21,7 @@ use move_core_types::language_storage::ModuleId;
 use move_vm_runtime::native_functions::NativeFunctionTable;
 use move_vm_test_utils::gas_schedule::CostTable;
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     io::{Result, Write},
     marker::Send,
     sync::Mutex,
@@ -41,6 +41,10 @@ pub struct UnitTestingConfig {
     #[clap(name = "filter", short = 'f', long = "filter")]
     pub filter: Option<String>,
 
+    /// If set, only the tests in modules with these names are run
+    #[clap(skip)]
+    pub module_filter: Option<BTreeSet<String>>,
+
     /// List all tests
     #[clap(name = "list", short = 'l', long = "list")]
     pub list: bool,
@@ -128,6 +132,7 @@ impl UnitTestingConfig {
         Self {
             gas_limit: bound.or(Some(DEFAULT_EXECUTION_BOUND)),
             filter: None,
+            module_filter: None,
             num_threads: 8,
             report_statistics: false,
             report_storage_on_error: false,
@@ -252,6 +257,9 @@ impl UnitTestingConfig {
         if let Some(filter_str) = &self.filter {
             test_runner.filter(filter_str)
         }
+        if let Some(module_names) = &self.module_filter {
+            test_runner.filter_modules(module_names)
+        }
 
         let test_results = test_runner.run(&shared_writer).unwrap();
         if self.report_statistics {
@@ -30,7 +30,7 @@ use move_vm_test_utils::{
     InMemoryStorage,
 };
 use rayon::prelude::*;
-use std::{io::Write, marker::Send, sync::Mutex, time::Instant};
+use std::{collections::BTreeSet, io::Write, marker::Send, sync::Mutex, time::Instant};
 #[cfg(feature = "evm-backend")]
 use {
     evm::{backend::MemoryVicinity, ExitReason},
@@ -204,6 +204,14 @@ impl TestRunner {
             }
         }
     }
+
+    pub fn filter_modules(&mut self, module_names: &BTreeSet<String>) {
+        for (module_id, module_test) in self.tests.module_tests.iter_mut() {
+            if !module_names.contains(module_id.name().as_str()) {
+                module_test.tests.clear();
+            }
+        }
+    }
 }
 
 // TODO: do not expose this to backend implementations
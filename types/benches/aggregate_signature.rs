@@ -0,0 +1,109 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+#[macro_use]
+extern crate criterion;
+
+use aptos_crypto::{bls12381, test_utils::TestAptosCrypto};
+use aptos_types::{
+    account_address::AccountAddress,
+    aggregate_signature::{IncrementalAggregateSignature, PartialSignatures},
+    validator_verifier::{random_validator_verifier, ValidatorVerifier},
+};
+use criterion::{measurement::Measurement, BatchSize, BenchmarkGroup, BenchmarkId, Criterion};
+use std::mem::size_of;
+
+const NUM_VALIDATORS: usize = 200;
+
+fn bench_group(c: &mut Criterion) {
+    let mut group = c.benchmark_group("aggregate_signature");
+
+    let (signers, verifier) = random_validator_verifier(NUM_VALIDATORS, None, false);
+    let message = TestAptosCrypto("Hello Aptos!".to_owned());
+    let signatures: Vec<(AccountAddress, u16, bls12381::Signature)> = signers
+        .iter()
+        .map(|signer| {
+            (
+                signer.author(),
+                verifier.address_to_validator_index()[&signer.author()] as u16,
+                signer.sign(&message).unwrap(),
+            )
+        })
+        .collect();
+
+    // Memory kept per pending batch once every validator signed. The partial signatures size
+    // is a lower bound, as it ignores the allocation overhead of the map.
+    let partial_signatures_bytes =
+        NUM_VALIDATORS * (size_of::<AccountAddress>() + size_of::<bls12381::Signature>());
+    let mut incremental = IncrementalAggregateSignature::new(NUM_VALIDATORS as u16);
+    for (_, index, signature) in &signatures {
+        incremental
+            .add_signature(*index, signature.clone())
+            .unwrap();
+    }
+    let incremental_bytes =
+        size_of::<IncrementalAggregateSignature>() + incremental.get_signers_bitvec().num_buckets();
+    println!(
+        "Memory per batch with {} signers: partial signatures >= {} bytes, incremental {} bytes",
+        NUM_VALIDATORS, partial_signatures_bytes, incremental_bytes
+    );
+
+    partial_signatures(&mut group, &verifier, &signatures);
+    incremental_aggregation(&mut group, &signatures);
+
+    group.finish();
+}
+
+fn partial_signatures<M: Measurement>(
+    g: &mut BenchmarkGroup<M>,
+    verifier: &ValidatorVerifier,
+    signatures: &[(AccountAddress, u16, bls12381::Signature)],
+) {
+    g.bench_with_input(
+        BenchmarkId::new("partial_signatures", signatures.len()),
+        signatures,
+        |b, signatures| {
+            b.iter_batched(
+                || signatures.to_vec(),
+                |signatures| {
+                    let mut partial_signatures = PartialSignatures::empty();
+                    for (author, _, signature) in signatures {
+                        partial_signatures.add_signature(author, signature);
+                    }
+                    verifier.aggregate_signatures(&partial_signatures).unwrap()
+                },
+                BatchSize::SmallInput,
+            )
+        },
+    );
+}
+
+fn incremental_aggregation<M: Measurement>(
+    g: &mut BenchmarkGroup<M>,
+    signatures: &[(AccountAddress, u16, bls12381::Signature)],
+) {
+    g.bench_with_input(
+        BenchmarkId::new("incremental", signatures.len()),
+        signatures,
+        |b, signatures| {
+            b.iter_batched(
+                || signatures.to_vec(),
+                |signatures| {
+                    let mut incremental =
+                        IncrementalAggregateSignature::new(signatures.len() as u16);
+                    for (_, index, signature) in signatures {
+                        incremental.add_signature(index, signature).unwrap();
+                    }
+                    incremental.to_aggregate_signature()
+                },
+                BatchSize::SmallInput,
+            )
+        },
+    );
+}
+
+criterion_group!(
+    name = aggregate_signature_benches;
+    config = Criterion::default();
+    targets = bench_group);
+criterion_main!(aggregate_signature_benches);
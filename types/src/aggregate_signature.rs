@@ -1,6 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use anyhow::ensure;
 use aptos_bitvec::BitVec;
 use aptos_crypto::bls12381;
 use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
@@ -66,6 +67,61 @@ impl AggregateSignature {
     }
 }
 
+/// Aggregated signature built incrementally as the partial signatures arrive. Only the bit mask
+/// of the signers and the running aggregate are kept in memory, instead of every partial
+/// signature. It is only kept in memory and never sent through the network.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IncrementalAggregateSignature {
+    validator_bitmask: BitVec,
+    sig: Option<bls12381::Signature>,
+}
+
+impl IncrementalAggregateSignature {
+    pub fn new(num_validators: u16) -> Self {
+        Self {
+            validator_bitmask: BitVec::with_num_bits(num_validators),
+            sig: None,
+        }
+    }
+
+    pub fn contains(&self, validator_index: u16) -> bool {
+        self.validator_bitmask.is_set(validator_index)
+    }
+
+    /// Optimistically aggregates the signature of the validator at `validator_index`, the
+    /// partial signature is not verified.
+    pub fn add_signature(
+        &mut self,
+        validator_index: u16,
+        signature: bls12381::Signature,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            !self.contains(validator_index),
+            "Validator {} already signed",
+            validator_index
+        );
+        let sig = match &self.sig {
+            Some(sig) => bls12381::Signature::aggregate(vec![sig.clone(), signature])?,
+            None => signature,
+        };
+        self.validator_bitmask.set(validator_index);
+        self.sig = Some(sig);
+        Ok(())
+    }
+
+    pub fn get_signers_bitvec(&self) -> &BitVec {
+        &self.validator_bitmask
+    }
+
+    pub fn get_num_voters(&self) -> usize {
+        self.validator_bitmask.count_ones() as usize
+    }
+
+    pub fn to_aggregate_signature(&self) -> AggregateSignature {
+        AggregateSignature::new(self.validator_bitmask.clone(), self.sig.clone())
+    }
+}
+
 /// Partial signature from a set of validators. This struct is only used when aggregating the votes
 /// from different validators. It is only kept in memory and never sent through the network.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq)]
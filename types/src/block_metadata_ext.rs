@@ -0,0 +1,149 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{block_metadata::BlockMetadata, randomness::Randomness};
+use aptos_crypto::HashValue;
+use move_core_types::{
+    account_address::AccountAddress,
+    value::{MoveStruct, MoveValue},
+};
+use serde::{Deserialize, Serialize};
+
+/// The extended block metadata, which in addition to `BlockMetadata` carries the seed of the
+/// on-chain randomness for the block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlockMetadataExt {
+    V0(BlockMetadata),
+    V1(BlockMetadataWithRandomness),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlockMetadataWithRandomness {
+    pub id: HashValue,
+    pub epoch: u64,
+    pub round: u64,
+    pub proposer: AccountAddress,
+    #[serde(with = "serde_bytes")]
+    pub previous_block_votes_bitvec: Vec<u8>,
+    pub failed_proposer_indices: Vec<u32>,
+    pub timestamp_usecs: u64,
+    pub randomness: Option<Randomness>,
+}
+
+impl BlockMetadataExt {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_v1(
+        id: HashValue,
+        epoch: u64,
+        round: u64,
+        proposer: AccountAddress,
+        previous_block_votes_bitvec: Vec<u8>,
+        failed_proposer_indices: Vec<u32>,
+        timestamp_usecs: u64,
+        randomness: Option<Randomness>,
+    ) -> Self {
+        Self::V1(BlockMetadataWithRandomness {
+            id,
+            epoch,
+            round,
+            proposer,
+            previous_block_votes_bitvec,
+            failed_proposer_indices,
+            timestamp_usecs,
+            randomness,
+        })
+    }
+
+    pub fn id(&self) -> HashValue {
+        match self {
+            BlockMetadataExt::V0(metadata) => metadata.id(),
+            BlockMetadataExt::V1(metadata) => metadata.id,
+        }
+    }
+
+    pub fn epoch(&self) -> u64 {
+        match self {
+            BlockMetadataExt::V0(metadata) => metadata.epoch(),
+            BlockMetadataExt::V1(metadata) => metadata.epoch,
+        }
+    }
+
+    pub fn round(&self) -> u64 {
+        match self {
+            BlockMetadataExt::V0(metadata) => metadata.round(),
+            BlockMetadataExt::V1(metadata) => metadata.round,
+        }
+    }
+
+    pub fn proposer(&self) -> AccountAddress {
+        match self {
+            BlockMetadataExt::V0(metadata) => metadata.proposer(),
+            BlockMetadataExt::V1(metadata) => metadata.proposer,
+        }
+    }
+
+    pub fn previous_block_votes_bitvec(&self) -> &Vec<u8> {
+        match self {
+            BlockMetadataExt::V0(metadata) => metadata.previous_block_votes_bitvec(),
+            BlockMetadataExt::V1(metadata) => &metadata.previous_block_votes_bitvec,
+        }
+    }
+
+    pub fn failed_proposer_indices(&self) -> &Vec<u32> {
+        match self {
+            BlockMetadataExt::V0(metadata) => metadata.failed_proposer_indices(),
+            BlockMetadataExt::V1(metadata) => &metadata.failed_proposer_indices,
+        }
+    }
+
+    pub fn timestamp_usecs(&self) -> u64 {
+        match self {
+            BlockMetadataExt::V0(metadata) => metadata.timestamp_usecs(),
+            BlockMetadataExt::V1(metadata) => metadata.timestamp_usecs,
+        }
+    }
+
+    pub fn randomness(&self) -> Option<&Randomness> {
+        match self {
+            BlockMetadataExt::V0(_) => None,
+            BlockMetadataExt::V1(metadata) => metadata.randomness.as_ref(),
+        }
+    }
+
+    /// Arguments of `block::block_prologue_ext`, which are the arguments of `block_prologue`
+    /// followed by the randomness seed.
+    pub fn get_prologue_ext_move_args(self, signer: AccountAddress) -> Vec<MoveValue> {
+        let seed = MoveValue::Vector(
+            self.randomness()
+                .map(|randomness| vec![MoveValue::vector_u8(randomness.randomness().to_vec())])
+                .unwrap_or_default(),
+        );
+        let mut args = BlockMetadata::from(self).get_prologue_move_args(signer);
+        // `Option<vector<u8>>` is represented by a vector of at most one element.
+        args.push(MoveValue::Struct(MoveStruct::new(vec![seed])));
+        args
+    }
+}
+
+impl From<BlockMetadata> for BlockMetadataExt {
+    fn from(metadata: BlockMetadata) -> Self {
+        BlockMetadataExt::V0(metadata)
+    }
+}
+
+impl From<BlockMetadataExt> for BlockMetadata {
+    fn from(metadata: BlockMetadataExt) -> Self {
+        match metadata {
+            BlockMetadataExt::V0(metadata) => metadata,
+            BlockMetadataExt::V1(metadata) => BlockMetadata::new(
+                metadata.id,
+                metadata.epoch,
+                metadata.round,
+                metadata.proposer,
+                metadata.previous_block_votes_bitvec,
+                metadata.failed_proposer_indices,
+                metadata.timestamp_usecs,
+            ),
+        }
+    }
+}
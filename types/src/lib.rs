@@ -10,6 +10,7 @@ pub mod account_config;
 pub mod account_state;
 pub mod block_info;
 pub mod block_metadata;
+pub mod block_metadata_ext;
 pub mod chain_id;
 pub mod contract_event;
 pub mod epoch_change;
@@ -28,6 +29,7 @@ pub mod on_chain_config;
 pub mod proof;
 #[cfg(any(test, feature = "fuzzing"))]
 pub mod proptest_types;
+pub mod randomness;
 pub mod serde_helper;
 pub mod stake_pool;
 pub mod staking_contract;
@@ -20,6 +20,9 @@ pub struct MempoolStatus {
     pub code: MempoolStatusCode,
     /// optional message
     pub message: String,
+    /// optional number of seconds after which the transaction may be accepted, if Mempool is
+    /// overloaded
+    pub retry_after_secs: Option<u64>,
 }
 
 impl MempoolStatus {
@@ -27,6 +30,7 @@ impl MempoolStatus {
         Self {
             code,
             message: "".to_string(),
+            retry_after_secs: None,
         }
     }
 
@@ -35,6 +39,12 @@ impl MempoolStatus {
         self.message = message;
         self
     }
+
+    /// Adds a retry-after hint to the Mempool status.
+    pub fn with_retry_after_secs(mut self, retry_after_secs: u64) -> Self {
+        self.retry_after_secs = Some(retry_after_secs);
+        self
+    }
 }
 
 impl fmt::Display for MempoolStatus {
@@ -64,6 +74,10 @@ pub enum MempoolStatusCode {
     // transaction didn't pass vm_validation
     VmError = 5,
     UnknownStatus = 6,
+    // Replacement of a pending transaction didn't increase the gas unit price enough
+    InsufficientGasPriceBump = 7,
+    // Transaction was rejected by the admission control of Mempool
+    RejectedByFilter = 8,
 }
 
 impl TryFrom<u64> for MempoolStatusCode {
@@ -78,6 +92,8 @@ impl TryFrom<u64> for MempoolStatusCode {
             4 => Ok(MempoolStatusCode::InvalidUpdate),
             5 => Ok(MempoolStatusCode::VmError),
             6 => Ok(MempoolStatusCode::UnknownStatus),
+            7 => Ok(MempoolStatusCode::InsufficientGasPriceBump),
+            8 => Ok(MempoolStatusCode::RejectedByFilter),
             _ => Err("invalid StatusCode"),
         }
     }
@@ -49,6 +49,11 @@ pub enum FeatureFlag {
     OPERATOR_BENEFICIARY_CHANGE = 39,
     VM_BINARY_FORMAT_V7 = 40,
     RESOURCE_GROUPS_CHARGE_AS_SIZE_SUM = 41,
+    LAZY_FRIEND_LOADING = 42,
+    WEBAUTHN_SIGNATURE = 43,
+    RANDOMNESS = 44,
+    BATCHED_ENTRY_FUNCTIONS = 45,
+    TYPE_LAYOUT_CACHE = 46,
 }
 
 /// Representation of features on chain as a bitset.
@@ -142,4 +147,8 @@ impl Features {
     pub fn is_resource_group_charge_as_size_sum_enabled(&self) -> bool {
         self.is_enabled(FeatureFlag::RESOURCE_GROUPS_CHARGE_AS_SIZE_SUM)
     }
+
+    pub fn is_randomness_enabled(&self) -> bool {
+        self.is_enabled(FeatureFlag::RANDOMNESS)
+    }
 }
@@ -14,6 +14,12 @@ pub enum OnChainConsensusConfig {
     V1(ConsensusConfigV1),
     V2(ConsensusConfigV1),
     DagV1(DagConsensusConfigV1),
+    // DAG ordering with quorum store payloads
+    DagV2(DagConsensusConfigV1),
+    // Quorum store with round timeouts adapted to the recent round durations
+    V3(ConsensusConfigV1, RoundTimeoutConfig),
+    // DAG ordering with quorum store payloads and a configurable anchor election
+    DagV3(DagConsensusConfigV2),
 }
 
 /// The public interface that exposes all values with safe fallback.
@@ -21,9 +27,9 @@ impl OnChainConsensusConfig {
     /// The number of recent rounds that don't count into reputations.
     pub fn leader_reputation_exclude_round(&self) -> u64 {
         match &self {
-            OnChainConsensusConfig::V1(config) | OnChainConsensusConfig::V2(config) => {
-                config.exclude_round
-            },
+            OnChainConsensusConfig::V1(config)
+            | OnChainConsensusConfig::V2(config)
+            | OnChainConsensusConfig::V3(config, _) => config.exclude_round,
             _ => unimplemented!("method not supported"),
         }
     }
@@ -37,9 +43,9 @@ impl OnChainConsensusConfig {
     // to this max size.
     pub fn max_failed_authors_to_store(&self) -> usize {
         match &self {
-            OnChainConsensusConfig::V1(config) | OnChainConsensusConfig::V2(config) => {
-                config.max_failed_authors_to_store
-            },
+            OnChainConsensusConfig::V1(config)
+            | OnChainConsensusConfig::V2(config)
+            | OnChainConsensusConfig::V3(config, _) => config.max_failed_authors_to_store,
             _ => unimplemented!("method not supported"),
         }
     }
@@ -47,9 +53,9 @@ impl OnChainConsensusConfig {
     // Type and configuration used for proposer election.
     pub fn proposer_election_type(&self) -> &ProposerElectionType {
         match &self {
-            OnChainConsensusConfig::V1(config) | OnChainConsensusConfig::V2(config) => {
-                &config.proposer_election_type
-            },
+            OnChainConsensusConfig::V1(config)
+            | OnChainConsensusConfig::V2(config)
+            | OnChainConsensusConfig::V3(config, _) => &config.proposer_election_type,
             _ => unimplemented!("method not supported"),
         }
     }
@@ -59,16 +65,36 @@ impl OnChainConsensusConfig {
             OnChainConsensusConfig::V1(_config) => false,
             OnChainConsensusConfig::V2(_config) => true,
             OnChainConsensusConfig::DagV1(_) => false,
+            OnChainConsensusConfig::DagV2(_) => true,
+            OnChainConsensusConfig::V3(_, _) => true,
+            OnChainConsensusConfig::DagV3(_) => true,
+        }
+    }
+
+    /// The bounds of the adaptive round timeouts, if enabled.
+    pub fn round_timeout_config(&self) -> Option<&RoundTimeoutConfig> {
+        match &self {
+            OnChainConsensusConfig::V3(_, round_timeout_config) => Some(round_timeout_config),
+            _ => None,
         }
     }
 
     pub fn is_dag_enabled(&self) -> bool {
-        matches!(self, OnChainConsensusConfig::DagV1(_))
+        matches!(
+            self,
+            OnChainConsensusConfig::DagV1(_)
+                | OnChainConsensusConfig::DagV2(_)
+                | OnChainConsensusConfig::DagV3(_)
+        )
     }
 
-    pub fn unwrap_dag_config_v1(&self) -> &DagConsensusConfigV1 {
+    /// The DAG config, with the defaults of the fields which older versions don't have.
+    pub fn unwrap_dag_config(&self) -> DagConsensusConfigV2 {
         match &self {
-            OnChainConsensusConfig::DagV1(config) => config,
+            OnChainConsensusConfig::DagV1(config) | OnChainConsensusConfig::DagV2(config) => {
+                config.into()
+            },
+            OnChainConsensusConfig::DagV3(config) => config.clone(),
             _ => unreachable!("not a dag config"),
         }
     }
@@ -218,6 +244,68 @@ impl Default for DagConsensusConfigV1 {
     }
 }
 
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct DagConsensusConfigV2 {
+    pub dag_ordering_causal_history_window: usize,
+    pub anchor_election_mode: AnchorElectionMode,
+}
+
+impl Default for DagConsensusConfigV2 {
+    fn default() -> Self {
+        Self {
+            dag_ordering_causal_history_window: 10,
+            anchor_election_mode: AnchorElectionMode::LeaderReputation(
+                LeaderReputationType::ProposerAndVoterV2(ProposerAndVoterConfig {
+                    active_weight: 1000,
+                    inactive_weight: 10,
+                    failed_weight: 1,
+                    failure_threshold_percent: 10,
+                    proposer_window_num_validators_multiplier: 10,
+                    voter_window_num_validators_multiplier: 1,
+                    weight_by_voting_power: true,
+                    use_history_from_previous_epoch_max_count: 0,
+                }),
+            ),
+        }
+    }
+}
+
+/// Configs without an anchor election mode elect anchors by the default leader reputation, which
+/// is the one DAG consensus used before the mode was configurable.
+impl From<&DagConsensusConfigV1> for DagConsensusConfigV2 {
+    fn from(config: &DagConsensusConfigV1) -> Self {
+        Self {
+            dag_ordering_causal_history_window: config.dag_ordering_causal_history_window,
+            ..Self::default()
+        }
+    }
+}
+
+/// The bounds within which validators adapt their round timeouts to the recent round durations
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct RoundTimeoutConfig {
+    pub min_round_timeout_ms: u64,
+    pub max_round_timeout_ms: u64,
+}
+
+impl Default for RoundTimeoutConfig {
+    fn default() -> Self {
+        Self {
+            min_round_timeout_ms: 1000,
+            max_round_timeout_ms: 5000,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnchorElectionMode {
+    // Round robin rotation of anchors
+    RoundRobin,
+    // Anchors elected based on their recent anchor and vote participation
+    LeaderReputation(LeaderReputationType),
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -257,6 +345,82 @@ mod test {
         ));
     }
 
+    #[test]
+    fn test_round_timeout_config_serialization() {
+        let config = OnChainConsensusConfig::V3(ConsensusConfigV1::default(), RoundTimeoutConfig {
+            min_round_timeout_ms: 500,
+            max_round_timeout_ms: 3000,
+        });
+        let s = bcs::to_bytes(&config).unwrap();
+        let result = bcs::from_bytes::<OnChainConsensusConfig>(&s).unwrap();
+        assert_eq!(result, config);
+        assert!(result.quorum_store_enabled());
+        assert_eq!(
+            result.round_timeout_config().unwrap().max_round_timeout_ms,
+            3000
+        );
+
+        // Older configs keep the exponential backoff
+        assert!(OnChainConsensusConfig::default()
+            .round_timeout_config()
+            .is_none());
+    }
+
+    #[test]
+    fn test_dag_config_serialization() {
+        for config in [
+            OnChainConsensusConfig::DagV1(DagConsensusConfigV1::default()),
+            OnChainConsensusConfig::DagV2(DagConsensusConfigV1::default()),
+            OnChainConsensusConfig::DagV3(DagConsensusConfigV2::default()),
+            OnChainConsensusConfig::DagV3(DagConsensusConfigV2 {
+                anchor_election_mode: AnchorElectionMode::RoundRobin,
+                ..DagConsensusConfigV2::default()
+            }),
+        ] {
+            let s = serde_yaml::to_string(&config).unwrap();
+            assert_eq!(
+                serde_yaml::from_str::<OnChainConsensusConfig>(&s).unwrap(),
+                config
+            );
+            let s = bcs::to_bytes(&config).unwrap();
+            assert_eq!(
+                bcs::from_bytes::<OnChainConsensusConfig>(&s).unwrap(),
+                config
+            );
+            assert!(config.is_dag_enabled());
+        }
+        assert!(
+            !OnChainConsensusConfig::DagV1(DagConsensusConfigV1::default()).quorum_store_enabled()
+        );
+        assert!(
+            OnChainConsensusConfig::DagV2(DagConsensusConfigV1::default()).quorum_store_enabled()
+        );
+        assert!(
+            OnChainConsensusConfig::DagV3(DagConsensusConfigV2::default()).quorum_store_enabled()
+        );
+    }
+
+    #[test]
+    fn test_dag_config_v1_compatibility() {
+        // The encoding of the configs published before the anchor election became configurable
+        let config = OnChainConsensusConfig::DagV1(DagConsensusConfigV1 {
+            dag_ordering_causal_history_window: 20,
+        });
+        let bytes = vec![2, 20, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(bcs::to_bytes(&config).unwrap(), bytes);
+        assert_eq!(
+            bcs::from_bytes::<OnChainConsensusConfig>(&bytes).unwrap(),
+            config
+        );
+
+        let dag_config = config.unwrap_dag_config();
+        assert_eq!(dag_config.dag_ordering_causal_history_window, 20);
+        assert_eq!(
+            dag_config.anchor_election_mode,
+            DagConsensusConfigV2::default().anchor_election_mode
+        );
+    }
+
     #[test]
     fn test_config_onchain_payload() {
         let consensus_config = OnChainConsensusConfig::V1(ConsensusConfigV1 {
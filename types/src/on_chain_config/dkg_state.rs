@@ -0,0 +1,20 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::on_chain_config::OnChainConfig;
+use serde::{Deserialize, Serialize};
+
+/// Public result of the distributed key generation (DKG) of an epoch, stored in
+/// `0x1::randomness::DKGState`. `public_shares` holds the compressed BLS12-381 public key share of
+/// every validator, indexed by the validator index in the epoch's validator set.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct DKGState {
+    pub epoch: u64,
+    pub threshold: u64,
+    pub public_shares: Vec<Vec<u8>>,
+}
+
+impl OnChainConfig for DKGState {
+    const MODULE_IDENTIFIER: &'static str = "randomness";
+    const TYPE_IDENTIFIER: &'static str = "DKGState";
+}
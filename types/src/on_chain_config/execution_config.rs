@@ -14,7 +14,8 @@ pub enum OnChainExecutionConfig {
     /// To maintain backwards compatibility on replay, we must ensure that any new features resolve
     /// to previous behavior (before OnChainExecutionConfig was registered) in case of Missing.
     Missing,
-    // Reminder: Add V4 and future versions here, after Missing (order matters for enums).
+    V4(ExecutionConfigV4),
+    // Reminder: Add V5 and future versions here, after Missing (order matters for enums).
 }
 
 /// The public interface that exposes all values with safe fallback.
@@ -26,16 +27,27 @@ impl OnChainExecutionConfig {
             OnChainExecutionConfig::V1(config) => config.transaction_shuffler_type.clone(),
             OnChainExecutionConfig::V2(config) => config.transaction_shuffler_type.clone(),
             OnChainExecutionConfig::V3(config) => config.transaction_shuffler_type.clone(),
+            OnChainExecutionConfig::V4(config) => config.transaction_shuffler_type.clone(),
         }
     }
 
     /// The per-block gas limit being used.
     pub fn block_gas_limit(&self) -> Option<u64> {
+        self.block_execution_limit().block_gas_limit
+    }
+
+    /// The limits on the execution of a block being used.
+    pub fn block_execution_limit(&self) -> BlockExecutionLimit {
         match &self {
-            OnChainExecutionConfig::Missing => None,
-            OnChainExecutionConfig::V1(_config) => None,
-            OnChainExecutionConfig::V2(config) => config.block_gas_limit,
-            OnChainExecutionConfig::V3(config) => config.block_gas_limit,
+            OnChainExecutionConfig::Missing => BlockExecutionLimit::no_limit(),
+            OnChainExecutionConfig::V1(_config) => BlockExecutionLimit::no_limit(),
+            OnChainExecutionConfig::V2(config) => {
+                BlockExecutionLimit::with_block_gas_limit(config.block_gas_limit)
+            },
+            OnChainExecutionConfig::V3(config) => {
+                BlockExecutionLimit::with_block_gas_limit(config.block_gas_limit)
+            },
+            OnChainExecutionConfig::V4(config) => config.block_execution_limit,
         }
     }
 
@@ -47,6 +59,7 @@ impl OnChainExecutionConfig {
             OnChainExecutionConfig::V1(_config) => TransactionDeduperType::NoDedup,
             OnChainExecutionConfig::V2(_config) => TransactionDeduperType::NoDedup,
             OnChainExecutionConfig::V3(config) => config.transaction_deduper_type.clone(),
+            OnChainExecutionConfig::V4(config) => config.transaction_deduper_type.clone(),
         }
     }
 
@@ -103,6 +116,43 @@ pub struct ExecutionConfigV3 {
     pub transaction_deduper_type: TransactionDeduperType,
 }
 
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct ExecutionConfigV4 {
+    pub transaction_shuffler_type: TransactionShufflerType,
+    pub block_execution_limit: BlockExecutionLimit,
+    pub transaction_deduper_type: TransactionDeduperType,
+}
+
+/// Limits on the execution of a block. Once a limit is exceeded, the transaction exceeding it
+/// is the last one committed, and the remaining transactions of the block are retried in the
+/// next block.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+pub struct BlockExecutionLimit {
+    /// Limit on the accumulated execution and IO gas of the committed transactions.
+    pub block_gas_limit: Option<u64>,
+    /// Limit on the accumulated size (in bytes) of the write sets of the committed
+    /// transactions, so that blocks stay small enough for state sync.
+    pub max_block_output_bytes: Option<u64>,
+}
+
+impl BlockExecutionLimit {
+    pub const fn no_limit() -> Self {
+        Self::with_block_gas_limit(None)
+    }
+
+    pub const fn with_block_gas_limit(block_gas_limit: Option<u64>) -> Self {
+        Self {
+            block_gas_limit,
+            max_block_output_bytes: None,
+        }
+    }
+
+    /// Whether the execution of a block may stop before all of its transactions are committed.
+    pub fn is_limited(&self) -> bool {
+        self.block_gas_limit.is_some() || self.max_block_output_bytes.is_some()
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")] // cannot use tag = "type" as nested enums cannot work, and bcs doesn't support it
 pub enum TransactionShufflerType {
@@ -182,6 +232,23 @@ mod test {
             TransactionShufflerType::SenderAwareV2(32)
         ));
         assert!(result.block_gas_limit().is_none());
+
+        // V4 test with per-block gas and output limits
+        let block_execution_limit = BlockExecutionLimit {
+            block_gas_limit: Some(rand_gas_limit),
+            max_block_output_bytes: Some(4 * 1024 * 1024),
+        };
+        let config = OnChainExecutionConfig::V4(ExecutionConfigV4 {
+            transaction_shuffler_type: TransactionShufflerType::SenderAwareV2(32),
+            block_execution_limit,
+            transaction_deduper_type: TransactionDeduperType::TxnHashAndAuthenticatorV1,
+        });
+
+        let s = serde_yaml::to_string(&config).unwrap();
+        let result = serde_yaml::from_str::<OnChainExecutionConfig>(&s).unwrap();
+        assert!(result.block_gas_limit() == Some(rand_gas_limit));
+        assert_eq!(result.block_execution_limit(), block_execution_limit);
+        assert!(result.block_execution_limit().is_limited());
     }
 
     #[test]
@@ -23,6 +23,7 @@ mod aptos_features;
 mod aptos_version;
 mod chain_id;
 mod consensus_config;
+mod dkg_state;
 mod execution_config;
 mod gas_schedule;
 mod timed_features;
@@ -37,12 +38,14 @@ pub use self::{
         Version, APTOS_MAX_KNOWN_VERSION, APTOS_VERSION_2, APTOS_VERSION_3, APTOS_VERSION_4,
     },
     consensus_config::{
-        ConsensusConfigV1, DagConsensusConfigV1, LeaderReputationType, OnChainConsensusConfig,
-        ProposerAndVoterConfig, ProposerElectionType,
+        AnchorElectionMode, ConsensusConfigV1, DagConsensusConfigV1, DagConsensusConfigV2,
+        LeaderReputationType, OnChainConsensusConfig, ProposerAndVoterConfig, ProposerElectionType,
+        RoundTimeoutConfig,
     },
+    dkg_state::DKGState,
     execution_config::{
-        ExecutionConfigV1, ExecutionConfigV2, OnChainExecutionConfig, TransactionDeduperType,
-        TransactionShufflerType,
+        BlockExecutionLimit, ExecutionConfigV1, ExecutionConfigV2, OnChainExecutionConfig,
+        TransactionDeduperType, TransactionShufflerType,
     },
     gas_schedule::{GasSchedule, GasScheduleV2, StorageGasSchedule},
     timed_features::{TimedFeatureFlag, TimedFeatureOverride, TimedFeatures, TimedFeaturesBuilder},
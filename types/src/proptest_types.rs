@@ -12,6 +12,7 @@ use crate::{
     aggregate_signature::PartialSignatures,
     block_info::{BlockInfo, Round},
     block_metadata::BlockMetadata,
+    block_metadata_ext::BlockMetadataExt,
     chain_id::ChainId,
     contract_event::ContractEvent,
     epoch_state::EpochState,
@@ -19,6 +20,7 @@ use crate::{
     ledger_info::{generate_ledger_info_with_sig, LedgerInfo, LedgerInfoWithSignatures},
     on_chain_config::ValidatorSet,
     proof::TransactionInfoListWithProof,
+    randomness::{RandMetadata, Randomness},
     state_store::{state_key::StateKey, state_value::StateValue},
     transaction::{
         ChangeSet, ExecutionStatus, Module, ModuleBundle, RawTransaction, Script,
@@ -400,6 +402,15 @@ fn new_raw_transaction(
             expiration_time_secs,
             chain_id,
         ),
+        payload @ TransactionPayload::Batched(_) => RawTransaction::new(
+            sender,
+            sequence_number,
+            payload,
+            max_gas_amount,
+            gas_unit_price,
+            expiration_time_secs,
+            chain_id,
+        ),
     }
 }
 
@@ -973,6 +984,34 @@ impl Arbitrary for BlockMetadata {
     }
 }
 
+impl Arbitrary for BlockMetadataExt {
+    type Parameters = SizeRange;
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(num_validators_range: Self::Parameters) -> Self::Strategy {
+        (
+            any_with::<BlockMetadata>(num_validators_range),
+            any::<Option<Vec<u8>>>(),
+        )
+            .prop_map(|(metadata, seed)| {
+                let randomness = seed.map(|seed| {
+                    Randomness::new(RandMetadata::new(metadata.epoch(), metadata.round()), seed)
+                });
+                BlockMetadataExt::new_v1(
+                    metadata.id(),
+                    metadata.epoch(),
+                    metadata.round(),
+                    metadata.proposer(),
+                    metadata.previous_block_votes_bitvec().clone(),
+                    metadata.failed_proposer_indices().clone(),
+                    metadata.timestamp_usecs(),
+                    randomness,
+                )
+            })
+            .boxed()
+    }
+}
+
 #[derive(Debug)]
 struct ValidatorSetGen {
     validators: Vec<Index>,
@@ -0,0 +1,86 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
+use move_core_types::{
+    ident_str,
+    identifier::IdentStr,
+    move_resource::{MoveResource, MoveStructType},
+};
+use serde::{Deserialize, Serialize};
+
+/// Identifies the block a piece of randomness is generated for.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    CryptoHasher,
+    BCSCryptoHash,
+)]
+pub struct RandMetadata {
+    pub epoch: u64,
+    pub round: u64,
+}
+
+impl RandMetadata {
+    pub fn new(epoch: u64, round: u64) -> Self {
+        Self { epoch, round }
+    }
+}
+
+/// The per-block randomness produced by the validators, handed to the VM as part of the block
+/// metadata transaction.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Randomness {
+    metadata: RandMetadata,
+    #[serde(with = "serde_bytes")]
+    randomness: Vec<u8>,
+}
+
+impl Randomness {
+    pub fn new(metadata: RandMetadata, randomness: Vec<u8>) -> Self {
+        Self {
+            metadata,
+            randomness,
+        }
+    }
+
+    pub fn metadata(&self) -> &RandMetadata {
+        &self.metadata
+    }
+
+    pub fn epoch(&self) -> u64 {
+        self.metadata.epoch
+    }
+
+    pub fn round(&self) -> u64 {
+        self.metadata.round
+    }
+
+    pub fn randomness(&self) -> &[u8] {
+        &self.randomness
+    }
+}
+
+/// Rust representation of the `PerBlockRandomness` resource of the `randomness` module.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PerBlockRandomness {
+    pub epoch: u64,
+    pub round: u64,
+    pub seed: Option<Vec<u8>>,
+}
+
+impl MoveStructType for PerBlockRandomness {
+    const MODULE_NAME: &'static IdentStr = ident_str!("randomness");
+    const STRUCT_NAME: &'static IdentStr = ident_str!("PerBlockRandomness");
+}
+
+impl MoveResource for PerBlockRandomness {}
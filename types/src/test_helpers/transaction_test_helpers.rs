@@ -5,6 +5,7 @@
 use crate::{
     account_address::AccountAddress,
     chain_id::ChainId,
+    on_chain_config::BlockExecutionLimit,
     transaction::{
         authenticator::AccountAuthenticator,
         signature_verified_transaction::{
@@ -19,9 +20,10 @@ use aptos_crypto::{ed25519::*, traits::*, HashValue};
 const MAX_GAS_AMOUNT: u64 = 1_000_000;
 const TEST_GAS_PRICE: u64 = 100;
 
-// The block gas limit parameter for executor tests
-pub const BLOCK_GAS_LIMIT: Option<u64> = Some(1000);
-// pub const BLOCK_GAS_LIMIT: Option<u64> = None;
+// The block execution limit parameter for executor tests
+pub const BLOCK_EXECUTION_LIMIT: BlockExecutionLimit =
+    BlockExecutionLimit::with_block_gas_limit(Some(1000));
+// pub const BLOCK_EXECUTION_LIMIT: BlockExecutionLimit = BlockExecutionLimit::no_limit();
 
 static EMPTY_SCRIPT: &[u8] = include_bytes!("empty_script.mv");
 
@@ -249,9 +251,9 @@ pub fn get_test_txn_with_chain_id(
 
 pub fn block(
     mut user_txns: Vec<Transaction>,
-    maybe_block_gas_limit: Option<u64>,
+    block_execution_limit: BlockExecutionLimit,
 ) -> Vec<SignatureVerifiedTransaction> {
-    if maybe_block_gas_limit.is_none() {
+    if !block_execution_limit.is_limited() {
         user_txns.push(Transaction::StateCheckpoint(HashValue::random()));
     }
     into_signature_verified_block(user_txns)
@@ -4,14 +4,16 @@
 
 use crate::{
     account_address::AccountAddress,
-    transaction::{RawTransaction, RawTransactionWithData},
+    transaction::{
+        webauthn::PartialAuthenticatorAssertionResponse, RawTransaction, RawTransactionWithData,
+    },
 };
 use anyhow::{bail, ensure, Error, Result};
 use aptos_crypto::{
     ed25519::{Ed25519PublicKey, Ed25519Signature},
     hash::CryptoHash,
     multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
-    secp256k1_ecdsa,
+    p256_ecdsa, secp256k1_ecdsa,
     traits::Signature,
     CryptoMaterialError, HashValue, ValidCryptoMaterial, ValidCryptoMaterialStringExt,
 };
@@ -298,6 +300,14 @@ impl TransactionAuthenticator {
             } => Some(fee_payer_signer.clone()),
         }
     }
+
+    /// Returns true if any of the signers authenticated via a WebAuthn assertion.
+    pub fn has_webauthn_signature(&self) -> bool {
+        std::iter::once(self.sender())
+            .chain(self.secondary_signers())
+            .chain(self.fee_payer_signer())
+            .any(|signer| signer.has_webauthn_signature())
+    }
 }
 
 impl fmt::Display for TransactionAuthenticator {
@@ -452,6 +462,18 @@ impl AccountAuthenticator {
         }
     }
 
+    /// Returns true if this authenticator carries a WebAuthn assertion.
+    pub fn has_webauthn_signature(&self) -> bool {
+        match self {
+            Self::Ed25519 { .. } | Self::MultiEd25519 { .. } => false,
+            Self::SingleKey { authenticator } => authenticator.signature().is_webauthn(),
+            Self::MultiKey { authenticator } => authenticator
+                .signatures()
+                .iter()
+                .any(|(_, signature)| signature.is_webauthn()),
+        }
+    }
+
     /// Create a single-signature ed25519 authenticator
     pub fn ed25519(public_key: Ed25519PublicKey, signature: Ed25519Signature) -> Self {
         Self::Ed25519 {
@@ -884,6 +906,9 @@ pub enum AnySignature {
     Secp256k1Ecdsa {
         signature: secp256k1_ecdsa::Signature,
     },
+    WebAuthn {
+        signature: PartialAuthenticatorAssertionResponse,
+    },
 }
 
 impl AnySignature {
@@ -895,6 +920,14 @@ impl AnySignature {
         Self::Secp256k1Ecdsa { signature }
     }
 
+    pub fn webauthn(signature: PartialAuthenticatorAssertionResponse) -> Self {
+        Self::WebAuthn { signature }
+    }
+
+    pub fn is_webauthn(&self) -> bool {
+        matches!(self, Self::WebAuthn { .. })
+    }
+
     pub fn verify<T: Serialize + CryptoHash>(
         &self,
         public_key: &AnyPublicKey,
@@ -907,6 +940,7 @@ impl AnySignature {
             (Self::Secp256k1Ecdsa { signature }, AnyPublicKey::Secp256k1Ecdsa { public_key }) => {
                 signature.verify(message, public_key)
             },
+            (Self::WebAuthn { signature }, _) => signature.verify(message, public_key),
             _ => bail!("Invalid key, signature pairing"),
         }
     }
@@ -920,6 +954,9 @@ pub enum AnyPublicKey {
     Secp256k1Ecdsa {
         public_key: secp256k1_ecdsa::PublicKey,
     },
+    Secp256r1Ecdsa {
+        public_key: p256_ecdsa::PublicKey,
+    },
 }
 
 impl AnyPublicKey {
@@ -931,6 +968,10 @@ impl AnyPublicKey {
         Self::Secp256k1Ecdsa { public_key }
     }
 
+    pub fn secp256r1_ecdsa(public_key: p256_ecdsa::PublicKey) -> Self {
+        Self::Secp256r1Ecdsa { public_key }
+    }
+
     pub fn to_bytes(&self) -> Vec<u8> {
         bcs::to_bytes(self).expect("Only unhandleable errors happen here.")
     }
@@ -941,7 +982,7 @@ mod tests {
     use super::*;
     use crate::transaction::SignedTransaction;
     use aptos_crypto::{
-        ed25519::Ed25519PrivateKey, secp256k1_ecdsa, PrivateKey, SigningKey, Uniform,
+        ed25519::Ed25519PrivateKey, p256_ecdsa, secp256k1_ecdsa, PrivateKey, SigningKey, Uniform,
     };
 
     #[test]
@@ -1018,6 +1059,58 @@ mod tests {
         signed_txn.verify_signature().unwrap();
     }
 
+    #[test]
+    fn verify_webauthn_single_key_auth() {
+        let fake_sender = Ed25519PrivateKey::generate_for_testing();
+        let fake_sender_pub = fake_sender.public_key();
+
+        let sender = p256_ecdsa::PrivateKey::generate_for_testing();
+        let sender_pub = sender.public_key();
+
+        let single_sender_auth =
+            AuthenticationKey::any_key(AnyPublicKey::secp256r1_ecdsa(sender_pub.clone()));
+        let single_sender_addr = single_sender_auth.account_address();
+
+        let raw_txn = crate::test_helpers::transaction_test_helpers::get_test_signed_transaction(
+            single_sender_addr,
+            0,
+            &fake_sender,
+            fake_sender_pub,
+            None,
+            0,
+            0,
+            None,
+        )
+        .into_raw_transaction();
+
+        let assertion =
+            PartialAuthenticatorAssertionResponse::sign_for_testing(&sender, &raw_txn).unwrap();
+        assert!(
+            AccountAuthenticator::single_key(SingleKeyAuthenticator::new(
+                AnyPublicKey::secp256r1_ecdsa(sender_pub.clone()),
+                AnySignature::webauthn(assertion.clone()),
+            ))
+            .has_webauthn_signature()
+        );
+
+        // An assertion for a different transaction must not verify.
+        let mut other_txn = raw_txn.clone();
+        other_txn.sequence_number += 1;
+        let sk_auth = SingleKeyAuthenticator::new(
+            AnyPublicKey::secp256r1_ecdsa(sender_pub.clone()),
+            AnySignature::webauthn(assertion.clone()),
+        );
+        assert!(sk_auth.verify(&other_txn).is_err());
+
+        let sk_auth = SingleKeyAuthenticator::new(
+            AnyPublicKey::secp256r1_ecdsa(sender_pub),
+            AnySignature::webauthn(assertion),
+        );
+        let account_auth = AccountAuthenticator::single_key(sk_auth);
+        let signed_txn = SignedTransaction::new_single_sender(raw_txn, account_auth);
+        signed_txn.verify_signature().unwrap();
+    }
+
     #[test]
     fn verify_multi_key_auth() {
         let sender0 = Ed25519PrivateKey::generate_for_testing();
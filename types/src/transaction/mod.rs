@@ -8,6 +8,7 @@ use crate::{
     account_address::AccountAddress,
     aggregator::{TryFromMoveValue, TryIntoMoveValue},
     block_metadata::BlockMetadata,
+    block_metadata_ext::BlockMetadataExt,
     chain_id::ChainId,
     contract_event::{ContractEvent, FEE_STATEMENT_EVENT_TYPE},
     ledger_info::LedgerInfo,
@@ -26,7 +27,7 @@ use aptos_crypto::{
     hash::CryptoHash,
     multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
     secp256k1_ecdsa,
-    traits::{signing_message, SigningKey},
+    traits::{signing_message, Signature, SigningKey},
     CryptoMaterialError, HashValue,
 };
 use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
@@ -48,6 +49,7 @@ mod module;
 mod multisig;
 mod script;
 pub mod signature_verified_transaction;
+pub mod webauthn;
 
 use crate::{
     contract_event::TransactionEvent, executable::ModulePath, fee_statement::FeeStatement,
@@ -400,6 +402,17 @@ impl RawTransaction {
                 vec![],
             ),
             TransactionPayload::ModuleBundle(_) => ("module publishing".to_string(), vec![]),
+            TransactionPayload::Batched(entry_functions) => (
+                entry_functions
+                    .iter()
+                    .map(|entry_fn| format!("{}::{}", entry_fn.module(), entry_fn.function()))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                entry_functions
+                    .iter()
+                    .flat_map(|entry_fn| entry_fn.args().iter().cloned())
+                    .collect(),
+            ),
         };
         let mut f_args: String = "".to_string();
         for arg in args {
@@ -492,6 +505,11 @@ pub enum TransactionPayload {
     /// A multisig transaction that allows an owner of a multisig account to execute a pre-approved
     /// transaction as the multisig account.
     Multisig(Multisig),
+    /// A transaction that executes a sequence of entry functions atomically, in a single session.
+    /// Later calls observe the writes of earlier ones. If any call fails, the transaction is kept
+    /// with the failure status, gas is charged, and the writes of all calls are dropped. Entry
+    /// functions return no values, so calls can only pass data to each other through storage.
+    Batched(Vec<EntryFunction>),
 }
 
 impl TransactionPayload {
@@ -775,6 +793,96 @@ impl SignedTransaction {
         Ok(())
     }
 
+    /// Checks the signatures of several transactions at once. Single Ed25519 signatures are
+    /// batch-verified, while other authenticators are checked one by one. A failing batch is
+    /// split in halves until its invalid signatures are isolated, and a batch of one is verified
+    /// individually. Once `max_invalid_signatures` invalid signatures are found, the signatures
+    /// left unresolved are rejected without being checked, bounding the work an adversary can
+    /// cause with invalid signatures.
+    ///
+    /// Batch verification may accept maliciously crafted signatures that individual verification
+    /// rejects (see `Ed25519Signature::batch_verify_arbitrary_msgs`), so transactions accepted
+    /// this way must be verified individually before they are relayed to other nodes or
+    /// proposed in blocks.
+    pub fn check_signatures_batched(
+        txns: Vec<SignedTransaction>,
+        max_invalid_signatures: usize,
+    ) -> Vec<Result<SignatureCheckedTransaction>> {
+        let messages: Vec<_> = txns
+            .iter()
+            .map(|txn| match &txn.authenticator {
+                TransactionAuthenticator::Ed25519 { .. } => signing_message(&txn.raw_txn).ok(),
+                _ => None,
+            })
+            .collect();
+        let verify = |indices: &[usize]| -> bool {
+            let mut batch_messages = vec![];
+            let mut public_keys = vec![];
+            let mut signatures = vec![];
+            for index in indices {
+                if let (
+                    Some(message),
+                    TransactionAuthenticator::Ed25519 {
+                        public_key,
+                        signature,
+                    },
+                ) = (&messages[*index], &txns[*index].authenticator)
+                {
+                    batch_messages.push(message.as_slice());
+                    public_keys.push(public_key);
+                    signatures.push(signature);
+                }
+            }
+            match signatures.as_slice() {
+                [signature] => signature
+                    .verify_arbitrary_msg(batch_messages[0], public_keys[0])
+                    .is_ok(),
+                _ => Ed25519Signature::batch_verify_arbitrary_msgs(
+                    &batch_messages,
+                    &public_keys,
+                    &signatures,
+                )
+                .is_ok(),
+            }
+        };
+
+        let batch: Vec<usize> = (0..txns.len())
+            .filter(|index| messages[*index].is_some())
+            .collect();
+        let mut verdicts: Vec<Option<Result<(), &str>>> = vec![None; txns.len()];
+        let mut num_invalid_signatures = 0;
+        let mut pending = vec![batch.as_slice()];
+        while let Some(indices) = pending.pop() {
+            let verdict = if indices.is_empty() {
+                continue;
+            } else if num_invalid_signatures >= max_invalid_signatures {
+                Err("Signature not checked, batch has too many invalid signatures")
+            } else if verify(indices) {
+                Ok(())
+            } else if indices.len() == 1 {
+                num_invalid_signatures += 1;
+                Err("Invalid signature")
+            } else {
+                let (left, right) = indices.split_at(indices.len() / 2);
+                pending.push(right);
+                pending.push(left);
+                continue;
+            };
+            for index in indices {
+                verdicts[*index] = Some(verdict);
+            }
+        }
+
+        txns.into_iter()
+            .zip(verdicts)
+            .map(|(txn, verdict)| match verdict {
+                Some(Ok(())) => Ok(SignatureCheckedTransaction(txn)),
+                Some(Err(error)) => Err(format_err!(error)),
+                None => txn.check_signature(),
+            })
+            .collect()
+    }
+
     /// Checks that the signature of given transaction inplace. Returns `Ok(())` if
     /// the signature is valid.
     pub fn signature_is_valid(&self) -> bool {
@@ -1799,6 +1907,10 @@ pub enum Transaction {
     /// in the TransactionInfo
     /// The hash value inside is unique block id which can generate unique hash of state checkpoint transaction
     StateCheckpoint(HashValue),
+
+    /// Transaction to update the block metadata resource at the beginning of a block, which also
+    /// carries the seed of the on-chain randomness for the block.
+    BlockMetadataExt(BlockMetadataExt),
 }
 
 impl Transaction {
@@ -1816,6 +1928,13 @@ impl Transaction {
         }
     }
 
+    pub fn try_as_block_metadata_ext(&self) -> Option<&BlockMetadataExt> {
+        match self {
+            Transaction::BlockMetadataExt(v1) => Some(v1),
+            _ => None,
+        }
+    }
+
     pub fn format_for_client(&self, get_transaction_name: impl Fn(&[u8]) -> String) -> String {
         match self {
             Transaction::UserTransaction(user_txn) => {
@@ -1827,6 +1946,10 @@ impl Transaction {
             Transaction::BlockMetadata(_block_metadata) => String::from("block_metadata"),
             // TODO: display proper information for client
             Transaction::StateCheckpoint(_) => String::from("state_checkpoint"),
+            // TODO: display proper information for client
+            Transaction::BlockMetadataExt(_block_metadata_ext) => {
+                String::from("block_metadata_ext")
+            },
         }
     }
 }
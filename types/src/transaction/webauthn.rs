@@ -0,0 +1,245 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! WebAuthn (passkey) assertions used as transaction signatures.
+//!
+//! A WebAuthn authenticator does not sign the transaction directly. Instead, the signing message
+//! of the transaction is used as the `challenge` of the assertion, and the authenticator signs
+//! `authenticator_data || sha256(client_data_json)`, where `client_data_json` embeds the
+//! base64url-encoded challenge. See <https://www.w3.org/TR/webauthn-3/#sctn-verifying-assertion>.
+//!
+//! The authenticator data must report that the user was present. Its RP ID hash is not checked:
+//! accounts are not bound to a relying party, and the authenticator already scopes credentials
+//! to the relying party which created them, so an assertion is accepted for any RP ID as long as
+//! it is signed by the key of the account. Neither is the origin of the client data checked.
+
+use crate::transaction::authenticator::AnyPublicKey;
+use anyhow::{anyhow, bail, ensure, Result};
+#[cfg(any(test, feature = "fuzzing"))]
+use aptos_crypto::traits::SigningKey;
+use aptos_crypto::{
+    hash::CryptoHash,
+    p256_ecdsa,
+    traits::{signing_message, Signature},
+    HashValue,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// The only client data type accepted for assertions.
+const WEBAUTHN_GET: &str = "webauthn.get";
+
+/// Length of the RP ID hash at the start of the authenticator data.
+const RP_ID_HASH_LENGTH: usize = 32;
+/// Minimal length of the authenticator data: the RP ID hash, the flags and the signature counter.
+const MIN_AUTHENTICATOR_DATA_LENGTH: usize = RP_ID_HASH_LENGTH + 1 + 4;
+/// The user present (UP) bit of the authenticator data flags.
+const FLAG_USER_PRESENT: u8 = 0x01;
+
+/// Signature produced by the authenticator over the assertion.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum AssertionSignature {
+    Secp256r1Ecdsa { signature: p256_ecdsa::Signature },
+}
+
+/// The subset of the client data JSON that is relevant for verification.
+#[derive(Debug, Deserialize)]
+struct CollectedClientData {
+    #[serde(rename = "type")]
+    ty: String,
+    challenge: String,
+}
+
+/// The parts of an `AuthenticatorAssertionResponse` required to verify a transaction signature.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct PartialAuthenticatorAssertionResponse {
+    signature: AssertionSignature,
+    authenticator_data: Vec<u8>,
+    client_data_json: Vec<u8>,
+}
+
+impl PartialAuthenticatorAssertionResponse {
+    pub fn new(
+        signature: AssertionSignature,
+        authenticator_data: Vec<u8>,
+        client_data_json: Vec<u8>,
+    ) -> Self {
+        Self {
+            signature,
+            authenticator_data,
+            client_data_json,
+        }
+    }
+
+    /// Produces an assertion for the message the way a platform authenticator would.
+    #[cfg(any(test, feature = "fuzzing"))]
+    pub fn sign_for_testing<T: Serialize + CryptoHash>(
+        private_key: &p256_ecdsa::PrivateKey,
+        message: &T,
+    ) -> Result<Self> {
+        // RP ID hash, flags (user present) and signature counter.
+        let mut authenticator_data = vec![0u8; RP_ID_HASH_LENGTH];
+        authenticator_data.extend_from_slice(&[FLAG_USER_PRESENT, 0, 0, 0, 0]);
+        Self::sign_with_authenticator_data_for_testing(private_key, message, authenticator_data)
+    }
+
+    #[cfg(any(test, feature = "fuzzing"))]
+    fn sign_with_authenticator_data_for_testing<T: Serialize + CryptoHash>(
+        private_key: &p256_ecdsa::PrivateKey,
+        message: &T,
+        authenticator_data: Vec<u8>,
+    ) -> Result<Self> {
+        let challenge = base64::encode_config(Self::challenge(message)?, base64::URL_SAFE_NO_PAD);
+        let client_data_json = format!(
+            r#"{{"type":"{}","challenge":"{}","origin":"https://aptoslabs.com"}}"#,
+            WEBAUTHN_GET, challenge
+        )
+        .into_bytes();
+
+        let signature = private_key
+            .sign_arbitrary_message(&verification_data(&authenticator_data, &client_data_json));
+        Ok(Self::new(
+            AssertionSignature::Secp256r1Ecdsa { signature },
+            authenticator_data,
+            client_data_json,
+        ))
+    }
+
+    pub fn signature(&self) -> &AssertionSignature {
+        &self.signature
+    }
+
+    pub fn authenticator_data(&self) -> &[u8] {
+        &self.authenticator_data
+    }
+
+    pub fn client_data_json(&self) -> &[u8] {
+        &self.client_data_json
+    }
+
+    /// Returns the challenge a WebAuthn client must be asked to sign for the given message.
+    pub fn challenge<T: Serialize + CryptoHash>(message: &T) -> Result<Vec<u8>> {
+        Ok(HashValue::sha3_256_of(&signing_message(message)?).to_vec())
+    }
+
+    /// Returns the bytes signed by the authenticator, i.e.
+    /// `authenticator_data || sha256(client_data_json)`.
+    pub fn verification_data(&self) -> Vec<u8> {
+        verification_data(&self.authenticator_data, &self.client_data_json)
+    }
+
+    /// Verifies that the assertion is a signature of the message by the public key. The RP ID
+    /// hash and the origin are not checked, see the module documentation.
+    pub fn verify<T: Serialize + CryptoHash>(
+        &self,
+        message: &T,
+        public_key: &AnyPublicKey,
+    ) -> Result<()> {
+        let flags = authenticator_data_flags(&self.authenticator_data)?;
+        ensure!(
+            flags & FLAG_USER_PRESENT != 0,
+            "WebAuthn authenticator data does not report the user as present"
+        );
+        let client_data: CollectedClientData = serde_json::from_slice(&self.client_data_json)
+            .map_err(|e| anyhow!("Malformed WebAuthn client data: {}", e))?;
+        ensure!(
+            client_data.ty == WEBAUTHN_GET,
+            "Unexpected WebAuthn client data type: {}",
+            client_data.ty
+        );
+        let challenge = base64::decode_config(&client_data.challenge, base64::URL_SAFE_NO_PAD)
+            .map_err(|e| anyhow!("Malformed WebAuthn challenge: {}", e))?;
+        ensure!(
+            challenge == Self::challenge(message)?,
+            "WebAuthn challenge does not match the signed message"
+        );
+
+        match (&self.signature, public_key) {
+            (
+                AssertionSignature::Secp256r1Ecdsa { signature },
+                AnyPublicKey::Secp256r1Ecdsa { public_key },
+            ) => signature.verify_arbitrary_msg(&self.verification_data(), public_key),
+            _ => bail!("Invalid key, signature pairing"),
+        }
+    }
+}
+
+/// Returns the flags of the authenticator data, which is laid out as
+/// `rp_id_hash (32 bytes) || flags (1 byte) || sign_count (4 bytes) || extensions`.
+fn authenticator_data_flags(authenticator_data: &[u8]) -> Result<u8> {
+    ensure!(
+        authenticator_data.len() >= MIN_AUTHENTICATOR_DATA_LENGTH,
+        "Malformed WebAuthn authenticator data: expected at least {} bytes, got {}",
+        MIN_AUTHENTICATOR_DATA_LENGTH,
+        authenticator_data.len()
+    );
+    Ok(authenticator_data[RP_ID_HASH_LENGTH])
+}
+
+fn verification_data(authenticator_data: &[u8], client_data_json: &[u8]) -> Vec<u8> {
+    let mut data = authenticator_data.to_vec();
+    data.extend_from_slice(&Sha256::digest(client_data_json));
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        account_address::AccountAddress,
+        chain_id::ChainId,
+        transaction::{RawTransaction, Script, TransactionPayload},
+    };
+    use aptos_crypto::{PrivateKey, Uniform};
+
+    fn raw_txn() -> RawTransaction {
+        RawTransaction::new(
+            AccountAddress::ONE,
+            0,
+            TransactionPayload::Script(Script::new(vec![], vec![], vec![])),
+            0,
+            0,
+            0,
+            ChainId::test(),
+        )
+    }
+
+    fn verify_with_authenticator_data(authenticator_data: Vec<u8>) -> Result<()> {
+        let private_key = p256_ecdsa::PrivateKey::generate_for_testing();
+        let public_key = AnyPublicKey::secp256r1_ecdsa(private_key.public_key());
+        let raw_txn = raw_txn();
+        PartialAuthenticatorAssertionResponse::sign_with_authenticator_data_for_testing(
+            &private_key,
+            &raw_txn,
+            authenticator_data,
+        )?
+        .verify(&raw_txn, &public_key)
+    }
+
+    #[test]
+    fn test_authenticator_data_flags() {
+        let mut authenticator_data = vec![0u8; RP_ID_HASH_LENGTH];
+        authenticator_data.extend_from_slice(&[FLAG_USER_PRESENT, 0, 0, 0, 0]);
+        verify_with_authenticator_data(authenticator_data.clone()).unwrap();
+
+        // Any RP ID is accepted, and so are the extensions.
+        let mut other_rp_id = authenticator_data.clone();
+        other_rp_id[..RP_ID_HASH_LENGTH].copy_from_slice(&Sha256::digest(b"aptoslabs.com"));
+        other_rp_id.extend_from_slice(&[0xA0]);
+        verify_with_authenticator_data(other_rp_id).unwrap();
+
+        // The user must be present, even if they were verified.
+        let mut user_not_present = authenticator_data.clone();
+        user_not_present[RP_ID_HASH_LENGTH] = 0x04;
+        assert!(verify_with_authenticator_data(user_not_present).is_err());
+    }
+
+    #[test]
+    fn test_malformed_authenticator_data() {
+        assert!(verify_with_authenticator_data(vec![]).is_err());
+        // Missing the signature counter.
+        let mut truncated = vec![0u8; RP_ID_HASH_LENGTH];
+        truncated.push(FLAG_USER_PRESENT);
+        assert!(verify_with_authenticator_data(truncated).is_err());
+    }
+}
@@ -44,6 +44,45 @@ proptest! {
         assert!(signed_txn.check_signature().is_ok());
     }
 
+    #[test]
+    fn test_check_signatures_batched(
+        raw_txns in proptest::collection::vec(any::<RawTransaction>(), 2..8),
+        keypair in ed25519::keypair_strategy(),
+    ) {
+        let mut txns: Vec<_> = raw_txns
+            .into_iter()
+            .map(|raw_txn| raw_txn.sign(&keypair.private_key, keypair.public_key.clone()).unwrap().into_inner())
+            .collect();
+        prop_assume!(txns[0] != txns[1]);
+        let results = SignedTransaction::check_signatures_batched(txns.clone(), usize::MAX);
+        prop_assert!(results.iter().all(|result| result.is_ok()));
+
+        // Swapping in the signature of another transaction only fails that transaction
+        let other_signature = txns[1].authenticator().sender().signature_bytes();
+        txns[0] = SignedTransaction::new(
+            txns[0].clone().into_raw_transaction(),
+            keypair.public_key.clone(),
+            Ed25519Signature::try_from(other_signature.as_slice()).unwrap(),
+        );
+        let results = SignedTransaction::check_signatures_batched(txns.clone(), usize::MAX);
+        prop_assert!(results[0].is_err());
+        prop_assert!(results[1..].iter().all(|result| result.is_ok()));
+
+        // Past the bound on invalid signatures, unresolved signatures are rejected unchecked
+        let last = txns.len() - 1;
+        txns[last] = SignedTransaction::new(
+            txns[last].clone().into_raw_transaction(),
+            keypair.public_key.clone(),
+            Ed25519Signature::try_from(other_signature.as_slice()).unwrap(),
+        );
+        prop_assume!(txns[last].raw_transaction_ref() != txns[1].raw_transaction_ref());
+        let results = SignedTransaction::check_signatures_batched(txns.clone(), 2);
+        prop_assert!(results[0].is_err() && results[last].is_err());
+        prop_assert!(results[1..last].iter().all(|result| result.is_ok()));
+        let results = SignedTransaction::check_signatures_batched(txns, 1);
+        prop_assert!(results[0].is_err() && results[last].is_err());
+    }
+
     #[test]
     fn transaction_payload_bcs_roundtrip(txn_payload in any::<TransactionPayload>()) {
         assert_canonical_encode_decode(txn_payload);
@@ -521,7 +521,9 @@ pub fn random_validator_verifier(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::validator_signer::ValidatorSigner;
+    use crate::{
+        aggregate_signature::IncrementalAggregateSignature, validator_signer::ValidatorSigner,
+    };
     use aptos_crypto::test_utils::{TestAptosCrypto, TEST_SEED};
     use proptest::{collection::vec, prelude::*};
     use std::collections::BTreeMap;
@@ -593,6 +595,36 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_incremental_aggregate_signature() {
+        let (validator_signers, validator_verifier) = random_validator_verifier(7, None, false);
+        let dummy_struct = TestAptosCrypto("Hello, World".to_string());
+        let mut partial_signatures = PartialSignatures::empty();
+        let mut incremental = IncrementalAggregateSignature::new(validator_verifier.len() as u16);
+        for validator in validator_signers.iter().take(5) {
+            let signature = validator.sign(&dummy_struct).unwrap();
+            let index = validator_verifier.address_to_validator_index()[&validator.author()];
+            partial_signatures.add_signature(validator.author(), signature.clone());
+            incremental
+                .add_signature(index as u16, signature.clone())
+                .unwrap();
+            assert!(incremental.add_signature(index as u16, signature).is_err());
+        }
+
+        let aggregated = incremental.to_aggregate_signature();
+        assert_eq!(incremental.get_num_voters(), 5);
+        assert_eq!(
+            aggregated,
+            validator_verifier
+                .aggregate_signatures(&partial_signatures)
+                .unwrap()
+        );
+        assert_eq!(
+            validator_verifier.verify_multi_signatures(&dummy_struct, &aggregated),
+            Ok(())
+        );
+    }
+
     #[test]
     fn test_validator() {
         let validator_signer = ValidatorSigner::random(TEST_SEED);
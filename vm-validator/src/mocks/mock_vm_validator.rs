@@ -7,7 +7,7 @@ use anyhow::Result;
 use aptos_state_view::StateView;
 use aptos_types::{
     account_address::AccountAddress,
-    transaction::{SignedTransaction, VMValidatorResult},
+    transaction::{SignatureCheckedTransaction, SignedTransaction, VMValidatorResult},
     vm_status::StatusCode,
 };
 use aptos_vm::VMValidator;
@@ -38,22 +38,33 @@ impl VMValidator for MockVMValidator {
     ) -> VMValidatorResult {
         VMValidatorResult::new(None, 0)
     }
+
+    fn validate_checked_transaction(
+        &self,
+        _transaction: SignatureCheckedTransaction,
+        _state_view: &impl StateView,
+    ) -> VMValidatorResult {
+        VMValidatorResult::new(None, 0)
+    }
 }
 
 impl TransactionValidation for MockVMValidator {
     type ValidationInstance = MockVMValidator;
 
     fn validate_transaction(&self, txn: SignedTransaction) -> Result<VMValidatorResult> {
-        let txn = match txn.check_signature() {
-            Ok(txn) => txn,
-            Err(_) => {
-                return Ok(VMValidatorResult::new(
-                    Some(StatusCode::INVALID_SIGNATURE),
-                    0,
-                ))
-            },
-        };
+        match txn.check_signature() {
+            Ok(txn) => self.validate_checked_transaction(txn),
+            Err(_) => Ok(VMValidatorResult::new(
+                Some(StatusCode::INVALID_SIGNATURE),
+                0,
+            )),
+        }
+    }
 
+    fn validate_checked_transaction(
+        &self,
+        txn: SignatureCheckedTransaction,
+    ) -> Result<VMValidatorResult> {
         let sender = txn.sender();
         let ret = if sender == ACCOUNT_DNE_TEST_ADD {
             Some(StatusCode::SENDING_ACCOUNT_DOES_NOT_EXIST)
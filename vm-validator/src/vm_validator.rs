@@ -12,7 +12,7 @@ use aptos_storage_interface::{
 use aptos_types::{
     account_address::AccountAddress,
     account_view::AccountView,
-    transaction::{SignedTransaction, VMValidatorResult},
+    transaction::{SignatureCheckedTransaction, SignedTransaction, VMValidatorResult},
 };
 use aptos_vm::AptosVM;
 use fail::fail_point;
@@ -28,6 +28,12 @@ pub trait TransactionValidation: Send + Sync + Clone {
     /// Validate a txn from client
     fn validate_transaction(&self, _txn: SignedTransaction) -> Result<VMValidatorResult>;
 
+    /// Validate a txn whose signature was already checked
+    fn validate_checked_transaction(
+        &self,
+        _txn: SignatureCheckedTransaction,
+    ) -> Result<VMValidatorResult>;
+
     /// Restart the transaction validation instance
     fn restart(&mut self) -> Result<()>;
 
@@ -76,6 +82,20 @@ impl TransactionValidation for VMValidator {
         Ok(self.vm.validate_transaction(txn, &self.state_view))
     }
 
+    fn validate_checked_transaction(
+        &self,
+        txn: SignatureCheckedTransaction,
+    ) -> Result<VMValidatorResult> {
+        fail_point!("vm_validator::validate_transaction", |_| {
+            Err(anyhow::anyhow!(
+                "Injected error in vm_validator::validate_transaction"
+            ))
+        });
+        use aptos_vm::VMValidator;
+
+        Ok(self.vm.validate_checked_transaction(txn, &self.state_view))
+    }
+
     fn restart(&mut self) -> Result<()> {
         self.notify_commit();
 